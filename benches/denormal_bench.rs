@@ -0,0 +1,104 @@
+//! Benchmarks for denormal protection
+//!
+//! A feedback loop whose input has stopped decays through the subnormal range,
+//! where unprotected float math gets many times slower. Compares an unprotected
+//! comb tail against the flushed one, and times a graph's reverb/delay tail
+//! with flush-to-zero enabled as the render threads run it.
+//!
+//! Run with: cargo bench --bench denormal_bench
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::denormals::{enable_flush_to_zero, flush_denormal};
+
+const SAMPLE_RATE: f32 = 44100.0;
+const COMB_LEN: usize = 1117;
+const FEEDBACK: f32 = 0.5;
+
+/// A comb buffer whose contents are already in the subnormal range
+fn decayed_comb() -> Vec<f32> {
+    (0..COMB_LEN)
+        .map(|i| f32::MIN_POSITIVE * 0.5 * (1.0 + (i % 7) as f32 * 0.1))
+        .collect()
+}
+
+/// Run a silent-input comb tail for one second of samples
+fn comb_tail(buffer: &mut [f32], flush: bool) -> f32 {
+    let mut pos = 0;
+    let mut sum = 0.0;
+    for _ in 0..SAMPLE_RATE as usize {
+        let out = buffer[pos];
+        let next = out * FEEDBACK + out * 0.25;
+        buffer[pos] = if flush { flush_denormal(next) } else { next };
+        sum += out;
+        pos = (pos + 1) % buffer.len();
+    }
+    sum
+}
+
+/// Benchmark a decaying feedback loop with and without flushing
+fn bench_comb_tail(c: &mut Criterion) {
+    let mut group = c.benchmark_group("comb_tail");
+
+    // Baseline: normal-range audio in the loop
+    group.bench_function("active_audio", |b| {
+        b.iter_batched(
+            || vec![0.25f32; COMB_LEN],
+            |mut buf| black_box(comb_tail(black_box(&mut buf), false)),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    // The cliff: every sample is subnormal
+    group.bench_function("denormal_unprotected", |b| {
+        b.iter_batched(
+            decayed_comb,
+            |mut buf| black_box(comb_tail(black_box(&mut buf), false)),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    // Flushed: the tail collapses to zero and costs what active audio does
+    group.bench_function("denormal_flushed", |b| {
+        b.iter_batched(
+            decayed_comb,
+            |mut buf| black_box(comb_tail(black_box(&mut buf), true)),
+            criterion::BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+/// Benchmark the tail of a graph's reverb and delay after the input stops
+fn bench_graph_tail(c: &mut Criterion) {
+    enable_flush_to_zero();
+
+    let mut group = c.benchmark_group("graph_tail");
+    group.sample_size(20);
+
+    // One impulse every ten seconds: after the first block the chain is all tail
+    let code = "out $ impulse 0.1 # delay 0.3 0.9 # reverb 0.95 0.2 1.0";
+    let (_, statements) = parse_program(code).expect("parse failed");
+    let mut graph = compile_program(statements, SAMPLE_RATE, None).expect("compile failed");
+    graph.render(SAMPLE_RATE as usize * 4);
+
+    group.bench_function("reverb_delay_tail", |b| {
+        b.iter(|| black_box(graph.render(512)))
+    });
+
+    let (_, statements) =
+        parse_program("out $ saw 110 # delay 0.3 0.9 # reverb 0.95 0.2 1.0").expect("parse failed");
+    let mut active = compile_program(statements, SAMPLE_RATE, None).expect("compile failed");
+    active.render(SAMPLE_RATE as usize);
+
+    group.bench_function("reverb_delay_active", |b| {
+        b.iter(|| black_box(active.render(512)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_comb_tail, bench_graph_tail);
+criterion_main!(benches);
@@ -42,13 +42,14 @@ fn parse_node_files(all_metadata: &mut Vec<NodeMetadata>) {
         }
     };
 
-    for entry in entries {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        let path = entry.path();
+    // Sorted, so the generated file only changes when the docs do
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect();
+    paths.sort();
+
+    for path in paths {
         if path.extension().and_then(|s| s.to_str()) != Some("rs") {
             continue;
         }
@@ -103,9 +104,10 @@ struct NodeMetadata {
 #[derive(Debug, Clone)]
 struct ParamInfo {
     name: String,
-    param_type: String,   // "Hz", "float", "cycles", etc.
+    param_type: String,      // "Hz", "float", "cycles", etc.
     default: Option<String>, // Default value if optional
-    description: String,  // Parameter description
+    description: String,     // Parameter description
+    range: Option<String>,   // "20 to 20000", from the description
 }
 
 fn parse_node_file(content: &str, _file_name: &str) -> Option<NodeMetadata> {
@@ -135,17 +137,149 @@ fn parse_node_file(content: &str, _file_name: &str) -> Option<NodeMetadata> {
     let description = extract_node_description(content, struct_name)?;
 
     // Extract parameters from `pub fn new(...)` signature
-    let params = extract_node_parameters(content)?;
+    let mut params = extract_node_parameters(content)?;
+
+    // Parameter docs, ranges and the DSL example from `new`'s doc comment
+    let lines: Vec<&str> = content.lines().collect();
+    let new_line = lines
+        .iter()
+        .position(|line| line.trim().starts_with("pub fn new("))?;
+    let doc_lines = doc_lines_above(&lines, new_line);
+    for (param_name, text) in parse_node_params_section(&doc_lines) {
+        if let Some(param) = params.iter_mut().find(|p| p.name == param_name) {
+            param.range = extract_range(&text);
+            if param.default.is_none() {
+                param.default = extract_default(&text);
+            }
+            param.description = text;
+        }
+    }
+    let example = if doc_lines.iter().any(|line| line.starts_with("```phonon")) {
+        parse_example_section(&doc_lines)
+    } else {
+        String::new()
+    };
 
     Some(NodeMetadata {
         name,
         description,
         params,
         category: "Effects".to_string(), // Default category for nodes
-        example: String::new(),
+        example,
     })
 }
 
+/// The `///` lines directly above line `index`, without the slashes
+fn doc_lines_above(lines: &[&str], index: usize) -> Vec<String> {
+    let mut doc_lines = Vec::new();
+    for i in (0..index).rev() {
+        let line = lines[i].trim();
+        if line.starts_with("///") {
+            let text = line.strip_prefix("///").unwrap_or("").trim();
+            doc_lines.push(text.to_string());
+        } else if line.is_empty() {
+            continue;
+        } else {
+            break;
+        }
+    }
+    doc_lines.reverse();
+    doc_lines
+}
+
+/// Parse a node's # Parameters section: - `name`: Description (range)
+fn parse_node_params_section(doc_lines: &[String]) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut in_params = false;
+
+    for line in doc_lines {
+        if line.starts_with("# Parameters") {
+            in_params = true;
+            continue;
+        }
+        if line.starts_with("# ") && in_params {
+            break;
+        }
+        if !in_params {
+            continue;
+        }
+        let Some(rest) = line.strip_prefix("- `") else {
+            continue;
+        };
+        if let Some((name, text)) = rest.split_once('`') {
+            let text = text.trim_start_matches(':').trim();
+            params.push((name.to_string(), text.to_string()));
+        }
+    }
+
+    params
+}
+
+/// The first numeric range in a description, as "20-20000" or
+/// "0.0 to 1.0", written "20 to 20000"
+fn extract_range(text: &str) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let starts_number = chars[i].is_ascii_digit()
+            || (chars[i] == '-' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()));
+        let after_word = i > 0 && (chars[i - 1].is_alphanumeric() || chars[i - 1] == '.');
+        if !starts_number || after_word {
+            i += 1;
+            continue;
+        }
+        let (low, mut j) = read_number(&chars, i);
+        while chars.get(j) == Some(&' ') {
+            j += 1;
+        }
+        let rest: String = chars[j..].iter().collect();
+        let separator = if rest.starts_with("to ") {
+            3
+        } else if rest.starts_with('-') {
+            1
+        } else {
+            0
+        };
+        if separator > 0 {
+            let mut k = j + separator;
+            while chars.get(k) == Some(&' ') {
+                k += 1;
+            }
+            let starts_high = chars.get(k).is_some_and(|c| c.is_ascii_digit())
+                || (chars.get(k) == Some(&'-')
+                    && chars.get(k + 1).is_some_and(|c| c.is_ascii_digit()));
+            if starts_high {
+                let (high, _) = read_number(&chars, k);
+                return Some(format!("{} to {}", low, high));
+            }
+        }
+        i = j.max(i + 1);
+    }
+    None
+}
+
+/// The number starting at `start` and the index after it
+fn read_number(chars: &[char], start: usize) -> (String, usize) {
+    let mut end = start + 1;
+    while end < chars.len()
+        && (chars[end].is_ascii_digit()
+            || (chars[end] == '.' && chars.get(end + 1).is_some_and(|c| c.is_ascii_digit())))
+    {
+        end += 1;
+    }
+    (chars[start..end].iter().collect(), end)
+}
+
+/// The value after "default:" in a description
+fn extract_default(text: &str) -> Option<String> {
+    let after = text.split("default:").nth(1)?;
+    let value = after
+        .trim_start()
+        .split(|c: char| c == ')' || c == ',' || c.is_whitespace())
+        .next()?;
+    (!value.is_empty()).then(|| value.to_string())
+}
+
 fn extract_node_description(content: &str, struct_name: &str) -> Option<String> {
     // Find the struct definition line
     let struct_line_index = content
@@ -227,6 +361,7 @@ fn extract_node_parameters(content: &str) -> Option<Vec<ParamInfo>> {
             param_type: clean_type,
             default: None,
             description: String::new(),
+            range: None,
         });
     }
 
@@ -278,19 +413,7 @@ fn parse_method_with_docs(lines: &[&str], fn_line_idx: usize) -> Option<NodeMeta
     }
 
     // Collect doc comment lines above the function
-    let mut doc_lines = Vec::new();
-    for i in (0..fn_line_idx).rev() {
-        let line = lines[i].trim();
-        if line.starts_with("///") {
-            let text = line.strip_prefix("///").unwrap_or("").trim();
-            doc_lines.push(text.to_string());
-        } else if line.is_empty() {
-            continue;
-        } else {
-            break;
-        }
-    }
-    doc_lines.reverse();
+    let doc_lines = doc_lines_above(lines, fn_line_idx);
 
     // Need at least a description
     if doc_lines.is_empty() {
@@ -381,6 +504,7 @@ fn parse_param_line(line: &str) -> Option<ParamInfo> {
         name,
         param_type,
         default,
+        range: extract_range(&description),
         description,
     })
 }
@@ -447,6 +571,7 @@ pub struct GeneratedParamMetadata {
     pub param_type: String,
     pub default: Option<String>,
     pub description: String,
+    pub range: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -509,6 +634,14 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 "                description: \"{}\".to_string(),\n",
                 escape_string(&param.description)
             ));
+            if let Some(range) = &param.range {
+                output.push_str(&format!(
+                    "                range: Some(\"{}\".to_string()),\n",
+                    range
+                ));
+            } else {
+                output.push_str("                range: None,\n");
+            }
             output.push_str("            },\n");
         }
 
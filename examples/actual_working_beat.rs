@@ -1,8 +1,10 @@
 //! Actually working drum beat example with DSP filtering
 //! This uses only the DSP nodes that are actually implemented
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
+#[allow(deprecated)]
 fn main() {
     println!("🥁 Generating WORKING filtered drum beat...\n");
 
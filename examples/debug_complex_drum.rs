@@ -1,5 +1,6 @@
 //! Debug the complex drum pattern
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
 fn main() {
@@ -34,6 +35,7 @@ fn main() {
     test_code(code);
 }
 
+#[allow(deprecated)]
 fn test_code(code: &str) {
     println!("  Code: {}", code.trim());
 
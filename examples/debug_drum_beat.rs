@@ -1,5 +1,6 @@
 //! Debug why the drum beat isn't generating audio
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
 fn main() {
@@ -44,6 +45,7 @@ fn main() {
     test_code(code7);
 }
 
+#[allow(deprecated)]
 fn test_code(code: &str) {
     println!("  Code: {}", code.trim());
 
@@ -1,8 +1,11 @@
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
 use std::fs::File;
 use std::io::Write;
 
+#[allow(deprecated)]
 fn main() {
     println!("=== Raw Audio Debug ===\n");
 
@@ -1,7 +1,9 @@
 //! Demo beat - 4 kick + clap pattern with filter sweep
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
+#[allow(deprecated)]
 fn main() {
     println!("🥁 Generating 4*bd cp type beat with filter...\n");
 
@@ -1,6 +1,9 @@
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
 
+#[allow(deprecated)]
 fn main() {
     println!("=== CPS (Cycles Per Second) Demo ===\n");
 
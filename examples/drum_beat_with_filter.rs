@@ -2,8 +2,9 @@
 //!
 //! Demonstrates creating a "bd*4 cp" type beat and applying DSP effects
 
-use phonon::mini_notation::parse_mini_notation;
+use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 use std::collections::HashMap;
 use std::error::Error;
@@ -83,6 +84,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[allow(deprecated)]
 fn render_and_save(code: &str, filename: &str, duration: f32) -> Result<(), Box<dyn Error>> {
     println!("  Generating: {}", filename);
     println!("  Code: {}", code.trim());
@@ -146,6 +148,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(deprecated)]
     fn test_drum_generation() {
         // Test that we can generate a simple drum pattern
         let code = "out: impulse 4 >> mul 50 >> lpf 100 0.9";
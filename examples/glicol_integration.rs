@@ -1,8 +1,10 @@
 //! Example demonstrating Glicol-style DSP syntax with mini-notation patterns
 
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
-use phonon::mini_notation::parse_mini_notation;
+use phonon::mini_notation_v3::parse_mini_notation;
 
+#[allow(deprecated)]
 fn main() {
     println!("=== Glicol-Style DSP with Mini-Notation ===\n");
 
@@ -1,5 +1,6 @@
 //! Live coding with .phonon files - watches for changes and auto-plays
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 use std::env;
 use std::fs;
@@ -7,6 +8,7 @@ use std::process::Command;
 use std::thread;
 use std::time::{Duration, SystemTime};
 
+#[allow(deprecated)]
 fn main() {
     let args: Vec<String> = env::args().collect();
 
@@ -4,6 +4,7 @@
 //! Then edit live.phonon in your editor and save to hear changes
 
 use phonon::unified_graph::{Signal, SignalNode, UnifiedSignalGraph, Waveform};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::cell::RefCell;
 
@@ -125,6 +126,7 @@ out: ~bass * 0.4
     }
 }
 
+#[allow(deprecated)]
 fn load_graph(state: &Arc<Mutex<LiveState>>, file: &str, sample_rate: f32) {
     match fs::read_to_string(file) {
         Ok(content) => {
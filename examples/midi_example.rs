@@ -1,7 +1,7 @@
 //! Example of MIDI output with Phonon patterns
 
 use phonon::midi_output::MidiOutputHandler;
-use phonon::mini_notation::parse_mini_notation;
+use phonon::mini_notation_v3::parse_mini_notation;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Phonon MIDI Output Example");
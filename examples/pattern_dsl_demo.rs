@@ -1,5 +1,7 @@
+#[allow(deprecated)]
 use phonon::pattern_lang_parser::{PatternExpr, PatternParser, TransformOp};
 
+#[allow(deprecated)]
 fn main() {
     println!("=== Phonon Pattern DSL Demo ===\n");
     println!("We now support Tidal/Strudel-style pattern transformations!");
@@ -94,6 +96,7 @@ fn main() {
     println!("ADVANCED: compress, zoom, inside, outside");
 }
 
+#[allow(deprecated)]
 fn print_expr(expr: &PatternExpr, indent: usize) {
     let prefix = "  ".repeat(indent);
 
@@ -125,6 +128,7 @@ fn print_expr(expr: &PatternExpr, indent: usize) {
     }
 }
 
+#[allow(deprecated)]
 fn print_op(op: &TransformOp, indent: usize) {
     let prefix = "  ".repeat(indent);
 
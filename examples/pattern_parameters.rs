@@ -3,9 +3,12 @@
 //! This explores whether DSP function parameters can be defined by patterns
 //! similar to TidalCycles/Strudel.
 
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
 
+#[allow(deprecated)]
 fn main() {
     println!("=== Testing Pattern-Controlled DSP Parameters ===\n");
 
@@ -7,9 +7,11 @@
 //! - Expressions: `lpf (~lfo * 1000 + 500) 0.8`
 
 use phonon::dsp_parameter::DspParameter;
+#[allow(deprecated)]
 use phonon::glicol_parser_v2::parse_glicol_v2;
 use std::collections::HashMap;
 
+#[allow(deprecated)]
 fn main() {
     println!("=== Everything Is A Pattern ===\n");
     println!("Phonon now supports patterns as DSP parameters, just like TidalCycles/Strudel!\n");
@@ -2,7 +2,7 @@
 //!
 //! Run with: cargo run --example pattern_showcase
 
-use phonon::mini_notation::parse_mini_notation;
+use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
 use std::collections::HashMap;
 
@@ -1,10 +1,12 @@
 //! Simple player for .phonon files using the working SimpleDspExecutor
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 use std::env;
 use std::fs;
 use std::process::Command;
 
+#[allow(deprecated)]
 fn main() {
     let args: Vec<String> = env::args().collect();
 
@@ -1,6 +1,6 @@
 //! Quick one-liner demos of Phonon pattern capabilities
 
-use phonon::mini_notation::parse_mini_notation;
+use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
 use phonon::pattern_signal::*;
 use std::collections::HashMap;
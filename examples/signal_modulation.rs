@@ -7,7 +7,9 @@
 //! - Pattern gating
 //! - Complex signal multiplication
 
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
 
 fn save_wav(
@@ -31,6 +33,7 @@ fn save_wav(
     Ok(())
 }
 
+#[allow(deprecated)]
 fn main() {
     let sample_rate = 44100.0;
     let mut executor = SimpleDspExecutor::new(sample_rate);
@@ -1,5 +1,6 @@
 //! Test basic mixing of references
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
 fn main() {
@@ -35,6 +36,7 @@ fn main() {
     test_code(code);
 }
 
+#[allow(deprecated)]
 fn test_code(code: &str) {
     println!("  Code: {}", code.trim());
 
@@ -1,5 +1,7 @@
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
 
+#[allow(deprecated)]
 fn main() {
     let input = "~lfo: sin 0.5 >> mul 0.5 >> add 0.5\n~bass: saw 55 >> lpf 2000 0.8\no: ~bass >> reverb 0.8 0.5 >> mul 0.4";
 
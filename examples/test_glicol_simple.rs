@@ -1,5 +1,7 @@
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
 
+#[allow(deprecated)]
 fn main() {
     // Test each line separately
     let lines = [
@@ -1,5 +1,7 @@
+#[allow(deprecated)]
 use phonon::glicol_parser::*;
 
+#[allow(deprecated)]
 fn main() {
     // Test tokenization of "bass"
     let input = "~bass: saw 55";
@@ -1,5 +1,7 @@
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
+#[allow(deprecated)]
 fn main() {
     println!("Testing render functionality...\n");
 
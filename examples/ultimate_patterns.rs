@@ -4,6 +4,7 @@
 //! EVERY parameter can be a pattern, reference, or arithmetic expression.
 
 use phonon::dsp_parameter::DspParameter;
+#[allow(deprecated)]
 use phonon::glicol_parser_v2::parse_glicol_v2;
 use std::collections::HashMap;
 
@@ -135,6 +136,7 @@ fn main() {
     demonstrate_pattern_evolution();
 }
 
+#[allow(deprecated)]
 fn test_parse(code: &str, description: &str) {
     print!("  {} ... ", description);
     match parse_glicol_v2(code) {
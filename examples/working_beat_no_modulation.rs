@@ -1,7 +1,9 @@
 //! Working drum beat without reference modulation
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
+#[allow(deprecated)]
 fn main() {
     println!("🥁 Generating drum beat (no reference modulation)...\n");
 
@@ -1,7 +1,9 @@
 //! Working example that actually generates a drum beat with filtering
 
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple;
 
+#[allow(deprecated)]
 fn main() {
     println!("🥁 Generating filtered drum beat...\n");
 
@@ -0,0 +1,24 @@
+//! Mini-notation must never panic, whatever the performer types, and the
+//! patterns it builds must be safe to query
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use phonon::mini_notation_v3::parse_mini_notation;
+use phonon::pattern::{Fraction, State, TimeSpan};
+use std::collections::HashMap;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let pattern = parse_mini_notation(input);
+    for cycle in 0..2 {
+        pattern.query(&State {
+            span: TimeSpan::new(
+                Fraction::from_float(cycle as f64),
+                Fraction::from_float(cycle as f64 + 1.0),
+            ),
+            controls: HashMap::new(),
+        });
+    }
+});
@@ -0,0 +1,23 @@
+//! Whole programs: neither parser may panic, and recovery must agree with
+//! the strict parser on programs that parse in full
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use phonon::compositional_parser::{parse_program, parse_program_recovering};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let strict = parse_program(input);
+    let (statements, skipped) = parse_program_recovering(input);
+    if let Ok((rest, strict)) = strict {
+        if rest.trim().is_empty() {
+            assert_eq!(statements, strict);
+            assert!(skipped.is_empty());
+        }
+    }
+    for diagnostic in skipped {
+        assert!(diagnostic.line >= 1);
+    }
+});
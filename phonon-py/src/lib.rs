@@ -0,0 +1,232 @@
+//! Python bindings for Phonon: mini-notation patterns and offline rendering
+//!
+//! ```python
+//! import phonon_py as ph
+//!
+//! pat = ph.parse_mini_notation("bd*2 [~ sn]").fast(2).every(4, lambda p: p.rev())
+//! for whole, part, value in pat.query(0, 1):
+//!     print(whole, value)
+//!
+//! audio = ph.render('tempo: 0.5\nout $ s "bd sn" # lpf 800 0.3', cycles=4)
+//! audio.shape  # (frames, 2), float32
+//! ```
+//!
+//! Build with `maturin develop` (or `maturin build --release`) from this
+//! directory.
+
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2};
+use phonon::mini_notation_v3;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+use phonon::phonon_engine::PhononEngine;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+
+/// An event as returned to Python: `(whole, part, value)`, where `whole` is
+/// `None` for continuous events and spans are `(begin, end)` in cycles
+type Event = (Option<(f64, f64)>, (f64, f64), String);
+
+/// A pattern of strings, as written in mini-notation. Transforms return a new
+/// pattern; the original is unchanged.
+#[pyclass(name = "Pattern", module = "phonon_py")]
+#[derive(Clone)]
+struct PyPattern {
+    inner: Pattern<String>,
+}
+
+impl From<Pattern<String>> for PyPattern {
+    fn from(inner: Pattern<String>) -> Self {
+        Self { inner }
+    }
+}
+
+/// A number or a mini-notation string of numbers (`"<1 2>"`), as pattern
+/// parameters are written in the DSL
+fn number_pattern(value: &Bound<'_, PyAny>) -> PyResult<Pattern<f64>> {
+    if let Ok(number) = value.extract::<f64>() {
+        return Ok(Pattern::pure(number));
+    }
+    let text: String = value.extract()?;
+    // Values only appear as a pattern is queried, so check the words of the
+    // text up front rather than turning a typo into a silent default
+    let not_a_number = text
+        .split(|c: char| c.is_whitespace() || "[]<>(){},|~_*/@%?!:'".contains(c))
+        .find(|word| !word.is_empty() && *word != "." && word.parse::<f64>().is_err());
+    if let Some(word) = not_a_number {
+        return Err(PyValueError::new_err(format!(
+            "'{}' in \"{}\" is not a number",
+            word, text
+        )));
+    }
+    Ok(mini_notation_v3::parse_mini_notation(&text).fmap(|s| s.parse::<f64>().unwrap_or(0.0)))
+}
+
+fn span(span: &TimeSpan) -> (f64, f64) {
+    (span.begin.to_float(), span.end.to_float())
+}
+
+#[pymethods]
+impl PyPattern {
+    #[new]
+    fn new(text: &str) -> Self {
+        mini_notation_v3::parse_mini_notation(text).into()
+    }
+
+    /// Events overlapping `begin..end` (in cycles), ordered by onset
+    #[pyo3(signature = (begin = 0.0, end = 1.0))]
+    fn query(&self, begin: f64, end: f64) -> PyResult<Vec<Event>> {
+        if end <= begin {
+            return Err(PyValueError::new_err("query end must be after begin"));
+        }
+        let state = State {
+            span: TimeSpan::new(Fraction::from_float(begin), Fraction::from_float(end)),
+            controls: HashMap::new(),
+        };
+        let mut haps = self.inner.query(&state);
+        haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+        Ok(haps
+            .into_iter()
+            .map(|hap| (hap.whole.as_ref().map(span), span(&hap.part), hap.value))
+            .collect())
+    }
+
+    fn fast(&self, factor: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(self.inner.clone().fast(number_pattern(factor)?).into())
+    }
+
+    fn slow(&self, factor: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(self.inner.clone().slow(number_pattern(factor)?).into())
+    }
+
+    /// Shift earlier by `amount` cycles (Tidal's `<~`)
+    fn early(&self, amount: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(self.inner.clone().early(number_pattern(amount)?).into())
+    }
+
+    /// Shift later by `amount` cycles (Tidal's `~>`)
+    fn late(&self, amount: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(self.inner.clone().late(number_pattern(amount)?).into())
+    }
+
+    fn rev(&self) -> Self {
+        self.inner.clone().rev().into()
+    }
+
+    fn palindrome(&self) -> Self {
+        self.inner.clone().palindrome().into()
+    }
+
+    fn degrade(&self) -> Self {
+        self.inner.clone().degrade().into()
+    }
+
+    fn degrade_by(&self, probability: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(self
+            .inner
+            .clone()
+            .degrade_by(number_pattern(probability)?)
+            .into())
+    }
+
+    fn ply(&self, n: usize) -> Self {
+        self.inner.clone().ply(n).into()
+    }
+
+    fn iter(&self, n: usize) -> Self {
+        self.inner.clone().iter(n).into()
+    }
+
+    /// Apply `f` (a function from Pattern to Pattern) every `n` cycles
+    fn every(&self, n: i32, f: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if n <= 0 {
+            return Err(PyValueError::new_err("every needs a positive cycle count"));
+        }
+        // Called once here, so queries never need the GIL
+        let transformed: PyPattern = f.call1((self.clone(),))?.extract()?;
+        Ok(self
+            .inner
+            .clone()
+            .every(n, move |_| transformed.inner.clone())
+            .into())
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        let values: Vec<String> = self.query(0.0, 1.0)?.into_iter().map(|e| e.2).collect();
+        Ok(format!("Pattern(first cycle: {})", values.join(" ")))
+    }
+}
+
+/// Parse mini-notation (`"bd*2 [~ sn]"`, `"<0 2> 4"`) into a Pattern
+#[pyfunction]
+fn parse_mini_notation(text: &str) -> PyPattern {
+    PyPattern::new(text)
+}
+
+/// Play patterns at the same time
+#[pyfunction]
+fn stack(patterns: Vec<PyPattern>) -> PyPattern {
+    Pattern::stack(patterns.into_iter().map(|p| p.inner).collect()).into()
+}
+
+/// Play patterns one per cycle, in turn
+#[pyfunction]
+fn cat(patterns: Vec<PyPattern>) -> PyPattern {
+    Pattern::cat(patterns.into_iter().map(|p| p.inner).collect()).into()
+}
+
+/// Render a Phonon program offline to a `(frames, 2)` float32 array. Give
+/// the length in `cycles` (at the program's tempo) or `duration` seconds;
+/// the default is 4 seconds, as for `phonon render`.
+#[pyfunction]
+#[pyo3(signature = (code, cycles = None, duration = None, sample_rate = 44100))]
+fn render<'py>(
+    py: Python<'py>,
+    code: &str,
+    cycles: Option<f64>,
+    duration: Option<f64>,
+    sample_rate: u32,
+) -> PyResult<Bound<'py, PyArray2<f32>>> {
+    if cycles.is_some() && duration.is_some() {
+        return Err(PyValueError::new_err("set cycles or duration, not both"));
+    }
+    if let Some(length) = cycles.or(duration) {
+        if !(length > 0.0 && length.is_finite()) {
+            return Err(PyValueError::new_err(format!(
+                "render length must be positive, got {}",
+                length
+            )));
+        }
+    }
+
+    let samples = py
+        .allow_threads(|| -> Result<Vec<f32>, String> {
+            let mut engine = PhononEngine::builder()
+                .sample_rate(sample_rate)
+                .code(code)
+                .build()?;
+            // Unlike live playback, a script wants to hear about every typo
+            if !engine.skipped().is_empty() {
+                return Err(engine.skipped().join("\n"));
+            }
+            Ok(match cycles {
+                Some(cycles) => engine.render_cycles(cycles),
+                None => engine.render_seconds(duration.unwrap_or(4.0)),
+            })
+        })
+        .map_err(PyValueError::new_err)?;
+    let frames = samples.len() / 2;
+    let audio = Array2::from_shape_vec((frames, 2), samples)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(audio.into_pyarray_bound(py))
+}
+
+#[pymodule]
+fn phonon_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPattern>()?;
+    m.add_function(wrap_pyfunction!(parse_mini_notation, m)?)?;
+    m.add_function(wrap_pyfunction!(stack, m)?)?;
+    m.add_function(wrap_pyfunction!(cat, m)?)?;
+    m.add_function(wrap_pyfunction!(render, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,59 @@
+"""Smoke tests for the phonon_py bindings
+
+Run after `maturin develop` from phonon-py/:
+
+    pytest tests
+"""
+
+import numpy as np
+import pytest
+
+import phonon_py as ph
+
+
+def values(pattern, begin=0, end=1):
+    return [value for _, _, value in pattern.query(begin, end)]
+
+
+def test_query_and_transforms():
+    pat = ph.parse_mini_notation("bd*2 [~ sn]")
+    assert values(pat) == ["bd", "bd", "sn"]
+    assert values(pat.rev()) == ["sn", "bd", "bd"]
+    assert len(values(pat.fast(2))) == 6
+
+    whole, part, _ = pat.query(0, 1)[0]
+    assert whole == (0.0, 0.25)
+    assert part == (0.0, 0.25)
+
+
+def test_pattern_arguments():
+    pat = ph.parse_mini_notation("a b")
+    assert len(values(pat.fast("<1 2>"), 0, 2)) == 6
+    assert values(pat.every(2, lambda p: p.rev())) == ["b", "a"]
+    assert values(ph.cat([pat, pat.rev()]), 1, 2) == ["b", "a"]
+
+
+def test_pattern_arguments_must_be_numbers():
+    pat = ph.parse_mini_notation("a b")
+    with pytest.raises(ValueError):
+        pat.fast("<1 two>")
+    with pytest.raises(ValueError):
+        pat.every(0, lambda p: p)
+
+
+def test_render():
+    audio = ph.render("tempo: 2.0\nout $ sine 440 * 0.5", cycles=1)
+    assert audio.dtype == np.float32
+    assert audio.shape == (22050, 2)
+    assert abs(np.abs(audio).max() - 0.5) < 0.05
+
+    assert ph.render("out $ sine 440", duration=0.5).shape == (22050, 2)
+
+
+def test_render_errors():
+    with pytest.raises(ValueError):
+        ph.render("out $ nonexistent_function 440", duration=0.1)
+    with pytest.raises(ValueError):
+        ph.render("out $ sine 440", cycles=1, duration=1)
+    with pytest.raises(ValueError):
+        ph.render("out $ sine 440", duration=0)
@@ -0,0 +1,265 @@
+//! Adaptive quality: trade fidelity for headroom when the synth thread runs late
+//!
+//! A block that takes longer to render than it lasts is heard as an underrun
+//! click. [`QualityGovernor`] watches the synth thread's render time against
+//! the block budget and, once it stays above [`DEGRADE_LOAD`] of it, steps the
+//! graph down a [`Quality`] level: the reverbs run half their combs and
+//! diffusers and the waveshapers stop oversampling, then sample polyphony is
+//! capped. When the load settles below [`RESTORE_LOAD`] for a while it steps
+//! back up.
+//!
+//! The editor shares a [`QualityControl`] between the synth thread, which
+//! publishes the level, and the UI, which reports changes in the console and
+//! can lock full quality with `:quality locked`.
+
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+/// Smoothed load (render time / block budget) above which quality drops
+pub const DEGRADE_LOAD: f32 = 0.9;
+/// Smoothed load below which quality comes back
+pub const RESTORE_LOAD: f32 = 0.6;
+/// Blocks to wait after a change before stepping down again
+const DEGRADE_HOLD_BLOCKS: usize = 32;
+/// Blocks the load must stay low before stepping back up
+const RESTORE_HOLD_BLOCKS: usize = 400;
+/// Weight of the newest block in the smoothed load
+const LOAD_SMOOTHING: f32 = 0.05;
+/// Sample voices kept at [`Quality::Minimal`]
+pub const MINIMAL_VOICES: usize = 48;
+
+/// How much the graph spends on fidelity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Quality {
+    /// Everything as written
+    #[default]
+    Full,
+    /// Half the reverb combs and diffusers, no oversampling
+    Reduced,
+    /// As [`Quality::Reduced`], and at most [`MINIMAL_VOICES`] sample voices
+    Minimal,
+}
+
+impl Quality {
+    /// Parallel combs run by `reverb` (Freeverb has 8)
+    pub fn reverb_combs(self) -> usize {
+        match self {
+            Quality::Full => 8,
+            Quality::Reduced | Quality::Minimal => 4,
+        }
+    }
+
+    /// Input diffusion allpasses run by the plate reverb (Dattorro has 4)
+    pub fn reverb_diffusers(self) -> usize {
+        match self {
+            Quality::Full => 4,
+            Quality::Reduced | Quality::Minimal => 2,
+        }
+    }
+
+    /// Whether waveshapers and saturators oversample
+    pub fn oversampling(self) -> bool {
+        self == Quality::Full
+    }
+
+    /// Most sample voices allowed to sound at once, if capped
+    pub fn voice_cap(self) -> Option<usize> {
+        match self {
+            Quality::Minimal => Some(MINIMAL_VOICES),
+            _ => None,
+        }
+    }
+
+    /// What the level gives up, for the console
+    pub fn describe(self) -> &'static str {
+        match self {
+            Quality::Full => "full quality",
+            Quality::Reduced => "reverb at half density, no oversampling",
+            Quality::Minimal => "reverb at half density, no oversampling, 48 sample voices",
+        }
+    }
+
+    fn lower(self) -> Self {
+        match self {
+            Quality::Full => Quality::Reduced,
+            _ => Quality::Minimal,
+        }
+    }
+
+    fn higher(self) -> Self {
+        match self {
+            Quality::Minimal => Quality::Reduced,
+            _ => Quality::Full,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Quality::Full,
+            1 => Quality::Reduced,
+            _ => Quality::Minimal,
+        }
+    }
+}
+
+/// Picks the quality level from the synth thread's render times
+#[derive(Debug, Clone, Default)]
+pub struct QualityGovernor {
+    level: Quality,
+    /// Smoothed render time as a fraction of the budget
+    load: f32,
+    /// Blocks since the level last changed
+    since_change: usize,
+    /// Consecutive blocks below [`RESTORE_LOAD`]
+    calm: usize,
+}
+
+impl QualityGovernor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current level
+    pub fn level(&self) -> Quality {
+        self.level
+    }
+
+    /// Smoothed render time as a fraction of the budget
+    pub fn load(&self) -> f32 {
+        self.load
+    }
+
+    /// Feed one block's render time. Returns the new level when it changes.
+    /// While `locked` the level is held at [`Quality::Full`]
+    pub fn observe(
+        &mut self,
+        elapsed_us: usize,
+        budget_us: usize,
+        locked: bool,
+    ) -> Option<Quality> {
+        let block_load = elapsed_us as f32 / budget_us.max(1) as f32;
+        self.load += (block_load - self.load) * LOAD_SMOOTHING;
+        self.since_change = self.since_change.saturating_add(1);
+        self.calm = if self.load < RESTORE_LOAD {
+            self.calm + 1
+        } else {
+            0
+        };
+
+        let next = if locked {
+            Quality::Full
+        } else if self.load > DEGRADE_LOAD && self.since_change >= DEGRADE_HOLD_BLOCKS {
+            self.level.lower()
+        } else if self.calm >= RESTORE_HOLD_BLOCKS {
+            self.level.higher()
+        } else {
+            self.level
+        };
+        if next == self.level {
+            return None;
+        }
+        self.level = next;
+        self.since_change = 0;
+        self.calm = 0;
+        Some(next)
+    }
+}
+
+/// Quality state shared by the synth thread and the UI
+#[derive(Debug, Default)]
+pub struct QualityControl {
+    level: AtomicU8,
+    locked: AtomicBool,
+    /// Smoothed load when the level last changed, in percent
+    load_percent: AtomicUsize,
+}
+
+impl QualityControl {
+    /// Synth thread: record the level it has switched to
+    pub fn publish(&self, level: Quality, load: f32) {
+        self.load_percent
+            .store((load * 100.0).round() as usize, Ordering::Relaxed);
+        self.level.store(level as u8, Ordering::Release);
+    }
+
+    /// The level the synth thread is rendering at
+    pub fn level(&self) -> Quality {
+        Quality::from_u8(self.level.load(Ordering::Acquire))
+    }
+
+    /// Load, in percent of the block budget, at the last change
+    pub fn load_percent(&self) -> usize {
+        self.load_percent.load(Ordering::Relaxed)
+    }
+
+    /// `:quality locked` holds full quality; `:quality auto` adapts again
+    pub fn set_locked(&self, locked: bool) {
+        self.locked.store(locked, Ordering::Relaxed);
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET: usize = 10_000;
+
+    fn run(governor: &mut QualityGovernor, elapsed_us: usize, blocks: usize) -> Vec<Quality> {
+        (0..blocks)
+            .filter_map(|_| governor.observe(elapsed_us, BUDGET, false))
+            .collect()
+    }
+
+    #[test]
+    fn test_sustained_overload_steps_down_one_level_at_a_time() {
+        let mut governor = QualityGovernor::new();
+        assert!(run(&mut governor, 7_000, 100).is_empty());
+
+        // A single slow block isn't enough
+        assert_eq!(governor.observe(20_000, BUDGET, false), None);
+
+        let changes = run(&mut governor, 9_800, 40);
+        assert_eq!(changes, vec![Quality::Reduced]);
+        let changes = run(&mut governor, 9_800, 40);
+        assert_eq!(changes, vec![Quality::Minimal]);
+        assert!(run(&mut governor, 9_800, 100).is_empty());
+    }
+
+    #[test]
+    fn test_quality_returns_after_a_calm_stretch() {
+        let mut governor = QualityGovernor::new();
+        run(&mut governor, 9_800, 100);
+        assert_eq!(governor.level(), Quality::Minimal);
+
+        // In between the thresholds nothing moves
+        assert!(run(&mut governor, 7_500, 1_000).is_empty());
+        let changes = run(&mut governor, 3_000, 2 * RESTORE_HOLD_BLOCKS + 50);
+        assert_eq!(changes, vec![Quality::Reduced, Quality::Full]);
+    }
+
+    #[test]
+    fn test_lock_holds_full_quality() {
+        let mut governor = QualityGovernor::new();
+        run(&mut governor, 9_800, 60);
+        assert_eq!(governor.level(), Quality::Reduced);
+
+        assert_eq!(governor.observe(9_800, BUDGET, true), Some(Quality::Full));
+        for _ in 0..200 {
+            assert_eq!(governor.observe(12_000, BUDGET, true), None);
+        }
+    }
+
+    #[test]
+    fn test_control_round_trips_the_level() {
+        let control = QualityControl::default();
+        assert_eq!(control.level(), Quality::Full);
+        control.publish(Quality::Minimal, 0.934);
+        assert_eq!(control.level(), Quality::Minimal);
+        assert_eq!(control.load_percent(), 93);
+        control.set_locked(true);
+        assert!(control.is_locked());
+    }
+}
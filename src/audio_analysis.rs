@@ -274,6 +274,160 @@ pub struct AudioFeatures {
     pub centroid: f32,
 }
 
+/// Features of one window of a recorded signal (see `analyze_frames`)
+#[derive(Debug, Clone, Copy)]
+pub struct AnalysisFrame {
+    /// Window start in seconds
+    pub time: f32,
+    pub rms: f32,
+    /// Fundamental in Hz, 0.0 when unvoiced or silent
+    pub pitch: f32,
+    /// Spectral centroid in Hz, 0.0 when silent
+    pub centroid: f32,
+}
+
+/// Frames quieter than this are reported without pitch or centroid
+const SILENCE_RMS: f32 = 1e-4;
+/// Pitch search range for offline analysis (low enough for sub-bass)
+const MIN_PITCH_HZ: f32 = 30.0;
+const MAX_PITCH_HZ: f32 = 4000.0;
+
+/// Offline pitch / RMS / spectral-centroid analysis, one frame per `frame_secs`
+///
+/// Unlike the streaming detectors above this uses real FFTs, so it is meant
+/// for recordings (e.g. `tap ~bass "bass_debug"` files via `phonon analyze`).
+pub fn analyze_frames(samples: &[f32], sample_rate: f32, frame_secs: f32) -> Vec<AnalysisFrame> {
+    use rustfft::{num_complex::Complex, FftPlanner};
+
+    let hop = ((frame_secs * sample_rate) as usize).max(1);
+    // Pitch needs a few periods of the lowest note, so windows may overlap
+    let window = hop.max(4096).next_power_of_two();
+    let fft_size = window * 2; // zero-padded for linear autocorrelation
+
+    let mut planner = FftPlanner::<f32>::new();
+    let forward = planner.plan_fft_forward(fft_size);
+    let inverse = planner.plan_fft_inverse(fft_size);
+    let hann: Vec<f32> = (0..window)
+        .map(|i| 0.5 * (1.0 - (2.0 * PI * i as f32 / (window - 1) as f32).cos()))
+        .collect();
+
+    (0..samples.len())
+        .step_by(hop)
+        .map(|start| {
+            let time = start as f32 / sample_rate;
+            let frame = &samples[start..(start + hop).min(samples.len())];
+            let rms = (frame.iter().map(|x| x * x).sum::<f32>() / frame.len() as f32).sqrt();
+            if rms < SILENCE_RMS {
+                return AnalysisFrame {
+                    time,
+                    rms,
+                    pitch: 0.0,
+                    centroid: 0.0,
+                };
+            }
+
+            let analysis = &samples[start..(start + window).min(samples.len())];
+
+            // Spectral centroid of the Hann-windowed block
+            let mut spectrum: Vec<Complex<f32>> = (0..fft_size)
+                .map(|i| Complex::new(analysis.get(i).map_or(0.0, |x| x * hann[i]), 0.0))
+                .collect();
+            forward.process(&mut spectrum);
+            let bin_hz = sample_rate / fft_size as f32;
+            let (weighted, total) = spectrum[1..fft_size / 2].iter().enumerate().fold(
+                (0.0, 0.0),
+                |(weighted, total), (i, c)| {
+                    let mag = c.norm();
+                    (weighted + (i + 1) as f32 * bin_hz * mag, total + mag)
+                },
+            );
+            let centroid = if total > 0.0 { weighted / total } else { 0.0 };
+
+            // Autocorrelation via the power spectrum of the raw block
+            let mut acf: Vec<Complex<f32>> = (0..fft_size)
+                .map(|i| Complex::new(analysis.get(i).copied().unwrap_or(0.0), 0.0))
+                .collect();
+            forward.process(&mut acf);
+            for c in acf.iter_mut() {
+                *c = Complex::new(c.norm_sqr(), 0.0);
+            }
+            inverse.process(&mut acf);
+            let acf: Vec<f32> = acf.iter().map(|c| c.re).collect();
+
+            AnalysisFrame {
+                time,
+                rms,
+                pitch: autocorrelation_peak(&acf, analysis.len(), sample_rate),
+                centroid,
+            }
+        })
+        .collect()
+}
+
+/// Fundamental from an (unnormalised) autocorrelation of `n` samples
+///
+/// Takes the first peak within 90% of the strongest one, which avoids
+/// reporting an octave below the played note.
+fn autocorrelation_peak(acf: &[f32], n: usize, sample_rate: f32) -> f32 {
+    let min_lag = (sample_rate / MAX_PITCH_HZ) as usize;
+    let max_lag = ((sample_rate / MIN_PITCH_HZ) as usize).min(n / 2);
+    if acf[0] <= 0.0 || min_lag + 2 >= max_lag {
+        return 0.0;
+    }
+
+    // Unbiased, normalised so a periodic signal peaks near 1.0
+    let norm = |lag: usize| acf[lag] / acf[0] * n as f32 / (n - lag) as f32;
+    let best = (min_lag..=max_lag).map(norm).fold(0.0f32, f32::max);
+    if best < 0.5 {
+        return 0.0; // Unvoiced
+    }
+
+    for lag in min_lag + 1..max_lag {
+        let (prev, value, next) = (norm(lag - 1), norm(lag), norm(lag + 1));
+        if value >= 0.9 * best && value >= prev && value >= next {
+            // Parabolic interpolation around the peak
+            let denom = prev - 2.0 * value + next;
+            let offset = if denom.abs() > f32::EPSILON {
+                0.5 * (prev - next) / denom
+            } else {
+                0.0
+            };
+            return sample_rate / (lag as f32 + offset);
+        }
+    }
+    0.0
+}
+
+/// Read a WAV file as mono f32 samples (channels are averaged)
+pub fn read_wav_mono(path: &str) -> Result<(Vec<f32>, f32), String> {
+    let mut reader =
+        hound::WavReader::open(path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let spec = reader.spec();
+
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().map(|s| s.unwrap_or(0.0)).collect(),
+        hound::SampleFormat::Int => {
+            let max_val = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.unwrap_or(0) as f32 / max_val)
+                .collect()
+        }
+    };
+
+    let channels = spec.channels.max(1) as usize;
+    let mono = if channels > 1 {
+        samples
+            .chunks(channels)
+            .map(|chunk| chunk.iter().sum::<f32>() / channels as f32)
+            .collect()
+    } else {
+        samples
+    };
+
+    Ok((mono, spec.sample_rate as f32))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -368,4 +522,50 @@ mod tests {
         assert!(features.centroid >= 0.0, "Centroid should be non-negative");
         assert!(features.centroid <= 1.0, "Centroid should be normalized");
     }
+
+    fn sine(freq: f32, seconds: f32) -> Vec<f32> {
+        (0..(44100.0 * seconds) as usize)
+            .map(|i| (2.0 * PI * freq * i as f32 / 44100.0).sin() * 0.5)
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze_frames_pitch() {
+        for freq in [55.0, 110.0, 440.0] {
+            let frames = analyze_frames(&sine(freq, 0.5), 44100.0, 0.1);
+            let pitch = frames[1].pitch;
+            assert!(
+                (pitch - freq).abs() < freq * 0.02,
+                "expected ~{} Hz, got {}",
+                freq,
+                pitch
+            );
+        }
+    }
+
+    #[test]
+    fn test_analyze_frames_saw_reports_fundamental() {
+        let saw: Vec<f32> = (0..22050)
+            .map(|i| ((i as f32 * 55.0 / 44100.0) % 1.0) * 2.0 - 1.0)
+            .collect();
+        let pitch = analyze_frames(&saw, 44100.0, 0.1)[0].pitch;
+        assert!((pitch - 55.0).abs() < 2.0, "got {}", pitch);
+    }
+
+    #[test]
+    fn test_analyze_frames_centroid_and_rms() {
+        let low = analyze_frames(&sine(200.0, 0.2), 44100.0, 0.1);
+        let high = analyze_frames(&sine(3000.0, 0.2), 44100.0, 0.1);
+        assert!(high[0].centroid > low[0].centroid * 4.0);
+        assert!((low[0].rms - 0.5 / 2f32.sqrt()).abs() < 0.01);
+
+        let mut faded = sine(220.0, 0.5);
+        faded.extend(vec![0.0; 22050]);
+        let frames = analyze_frames(&faded, 44100.0, 0.25);
+        assert_eq!(frames.len(), 4);
+        assert!(frames[0].rms > 0.3);
+        assert_eq!(frames[3].rms, 0.0);
+        assert_eq!(frames[3].pitch, 0.0);
+        assert_eq!(frames[3].centroid, 0.0);
+    }
 }
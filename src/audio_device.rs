@@ -0,0 +1,302 @@
+//! Audio device selection and sample-format negotiation
+//!
+//! `phonon devices` lists the output and input devices of the default host.
+//! `phonon live --device <name>` / `phonon edit --device <name>` open a device
+//! by exact or partial (case-insensitive) name instead of the system default.
+//!
+//! Phonon renders `f32` internally. `build_output_stream_converted` opens the
+//! stream in whatever sample format the device prefers (any integer width,
+//! `f32` or `f64`) and converts from an `f32` fill callback.
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use cpal::{FromSample, Sample, SizedSample};
+
+/// One device as shown by `phonon devices`
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    pub name: String,
+    /// The host's default device for this direction
+    pub is_default: bool,
+    /// Default config: (channels, sample rate, sample format)
+    pub default_config: Option<(u16, u32, cpal::SampleFormat)>,
+    /// Lowest and highest supported sample rate
+    pub sample_rates: Option<(u32, u32)>,
+    /// Supported sample formats, in the order the host reports them
+    pub sample_formats: Vec<cpal::SampleFormat>,
+}
+
+impl DeviceInfo {
+    fn from_configs(
+        name: String,
+        is_default: bool,
+        default_config: Option<cpal::SupportedStreamConfig>,
+        configs: Vec<cpal::SupportedStreamConfigRange>,
+    ) -> Self {
+        let sample_rates = configs
+            .iter()
+            .map(|c| (c.min_sample_rate().0, c.max_sample_rate().0))
+            .reduce(|(lo, hi), (min, max)| (lo.min(min), hi.max(max)));
+        let mut sample_formats = Vec::new();
+        for config in &configs {
+            if !sample_formats.contains(&config.sample_format()) {
+                sample_formats.push(config.sample_format());
+            }
+        }
+        Self {
+            name,
+            is_default,
+            default_config: default_config
+                .map(|c| (c.channels(), c.sample_rate().0, c.sample_format())),
+            sample_rates,
+            sample_formats,
+        }
+    }
+
+    /// One-line summary, e.g. "2 ch, 48000 Hz, f32 (44100-192000 Hz; f32, i16)"
+    pub fn describe(&self) -> String {
+        let mut text = match self.default_config {
+            Some((channels, rate, format)) => format!("{} ch, {} Hz, {}", channels, rate, format),
+            None => "no default config".to_string(),
+        };
+        let formats: Vec<String> = self.sample_formats.iter().map(|f| f.to_string()).collect();
+        match self.sample_rates {
+            Some((min, max)) if min == max => {
+                text += &format!(" ({} Hz; {})", min, formats.join(", "))
+            }
+            Some((min, max)) => text += &format!(" ({}-{} Hz; {})", min, max, formats.join(", ")),
+            None => {}
+        }
+        text
+    }
+}
+
+/// All output devices of `host` (default device marked)
+pub fn list_output_devices(host: &cpal::Host) -> Result<Vec<DeviceInfo>, String> {
+    let default_name = host.default_output_device().and_then(|d| d.name().ok());
+    let devices = host
+        .output_devices()
+        .map_err(|e| format!("Failed to list output devices: {}", e))?;
+    Ok(devices
+        .map(|device| {
+            let name = device.name().unwrap_or_else(|_| "<unnamed>".to_string());
+            let configs = device
+                .supported_output_configs()
+                .map(|c| c.collect())
+                .unwrap_or_default();
+            DeviceInfo::from_configs(
+                name.clone(),
+                default_name.as_deref() == Some(name.as_str()),
+                device.default_output_config().ok(),
+                configs,
+            )
+        })
+        .collect())
+}
+
+/// All input devices of `host` (default device marked)
+pub fn list_input_devices(host: &cpal::Host) -> Result<Vec<DeviceInfo>, String> {
+    let default_name = host.default_input_device().and_then(|d| d.name().ok());
+    let devices = host
+        .input_devices()
+        .map_err(|e| format!("Failed to list input devices: {}", e))?;
+    Ok(devices
+        .map(|device| {
+            let name = device.name().unwrap_or_else(|_| "<unnamed>".to_string());
+            let configs = device
+                .supported_input_configs()
+                .map(|c| c.collect())
+                .unwrap_or_default();
+            DeviceInfo::from_configs(
+                name.clone(),
+                default_name.as_deref() == Some(name.as_str()),
+                device.default_input_config().ok(),
+                configs,
+            )
+        })
+        .collect())
+}
+
+/// Index of the device called `query`: an exact name wins, otherwise the one
+/// device whose name contains it (case-insensitive)
+pub fn match_device_name(names: &[String], query: &str, kind: &str) -> Result<usize, String> {
+    if let Some(idx) = names.iter().position(|n| n == query) {
+        return Ok(idx);
+    }
+    let needle = query.to_lowercase();
+    let matches: Vec<usize> = (0..names.len())
+        .filter(|&i| names[i].to_lowercase().contains(&needle))
+        .collect();
+    match matches.as_slice() {
+        [idx] => Ok(*idx),
+        [] => Err(format!(
+            "No {} device matching '{}'. Available: {}",
+            kind,
+            query,
+            if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            }
+        )),
+        several => Err(format!(
+            "'{}' matches several {} devices: {}",
+            query,
+            kind,
+            several
+                .iter()
+                .map(|&i| names[i].as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Output device by name (`phonon devices` shows the names), or the host default
+pub fn select_output_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::Device, String> {
+    let Some(query) = name else {
+        return host
+            .default_output_device()
+            .ok_or_else(|| "No audio output device found".to_string());
+    };
+    let devices: Vec<cpal::Device> = host
+        .output_devices()
+        .map_err(|e| format!("Failed to list output devices: {}", e))?
+        .collect();
+    let names: Vec<String> = devices
+        .iter()
+        .map(|d| d.name().unwrap_or_default())
+        .collect();
+    let idx = match_device_name(&names, query, "output")?;
+    Ok(devices
+        .into_iter()
+        .nth(idx)
+        .expect("matched index is in range"))
+}
+
+/// Input device by name, or the host default
+pub fn select_input_device(host: &cpal::Host, name: Option<&str>) -> Result<cpal::Device, String> {
+    let Some(query) = name else {
+        return host
+            .default_input_device()
+            .ok_or_else(|| "No audio input device found".to_string());
+    };
+    let devices: Vec<cpal::Device> = host
+        .input_devices()
+        .map_err(|e| format!("Failed to list input devices: {}", e))?
+        .collect();
+    let names: Vec<String> = devices
+        .iter()
+        .map(|d| d.name().unwrap_or_default())
+        .collect();
+    let idx = match_device_name(&names, query, "input")?;
+    Ok(devices
+        .into_iter()
+        .nth(idx)
+        .expect("matched index is in range"))
+}
+
+/// Build an output stream in `sample_format`, filled by an `f32` callback
+///
+/// `F32` devices get `fill` directly; every other format renders into a
+/// pre-allocated `f32` scratch buffer (no allocation in the audio thread once
+/// it has grown to the device buffer size) and converts per sample.
+pub fn build_output_stream_converted<F, E>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sample_format: cpal::SampleFormat,
+    fill: F,
+    err_fn: E,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    F: FnMut(&mut [f32], &cpal::OutputCallbackInfo) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    use cpal::SampleFormat;
+    match sample_format {
+        SampleFormat::F32 => device.build_output_stream(config, fill, err_fn, None),
+        SampleFormat::F64 => build_converted::<f64, _, _>(device, config, fill, err_fn),
+        SampleFormat::I8 => build_converted::<i8, _, _>(device, config, fill, err_fn),
+        SampleFormat::I16 => build_converted::<i16, _, _>(device, config, fill, err_fn),
+        SampleFormat::I32 => build_converted::<i32, _, _>(device, config, fill, err_fn),
+        SampleFormat::I64 => build_converted::<i64, _, _>(device, config, fill, err_fn),
+        SampleFormat::U8 => build_converted::<u8, _, _>(device, config, fill, err_fn),
+        SampleFormat::U16 => build_converted::<u16, _, _>(device, config, fill, err_fn),
+        SampleFormat::U32 => build_converted::<u32, _, _>(device, config, fill, err_fn),
+        SampleFormat::U64 => build_converted::<u64, _, _>(device, config, fill, err_fn),
+        _ => Err(cpal::BuildStreamError::StreamConfigNotSupported),
+    }
+}
+
+fn build_converted<T, F, E>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut fill: F,
+    err_fn: E,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample + FromSample<f32>,
+    F: FnMut(&mut [f32], &cpal::OutputCallbackInfo) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    // Initial size 4096 handles most buffer sizes; resizes are rare and amortized
+    let mut scratch: Vec<f32> = vec![0.0; 4096];
+    device.build_output_stream(
+        config,
+        move |data: &mut [T], info: &cpal::OutputCallbackInfo| {
+            if scratch.len() < data.len() {
+                scratch.resize(data.len(), 0.0);
+            }
+            let buffer = &mut scratch[..data.len()];
+            fill(buffer, info);
+            for (dst, src) in data.iter_mut().zip(buffer.iter()) {
+                *dst = T::from_sample(src.clamp(-1.0, 1.0));
+            }
+        },
+        err_fn,
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_match_device_name() {
+        let devices = names(&["default", "Scarlett 2i2 USB", "HDA Intel PCH", "USB Audio"]);
+        assert_eq!(
+            match_device_name(&devices, "HDA Intel PCH", "output"),
+            Ok(2)
+        );
+        // Partial, case-insensitive
+        assert_eq!(match_device_name(&devices, "scarlett", "output"), Ok(1));
+        // Exact name wins over partial matches
+        assert_eq!(match_device_name(&devices, "USB Audio", "output"), Ok(3));
+
+        let ambiguous = match_device_name(&devices, "usb", "output").unwrap_err();
+        assert!(ambiguous.contains("Scarlett 2i2 USB") && ambiguous.contains("USB Audio"));
+
+        let missing = match_device_name(&devices, "Focusrite", "output").unwrap_err();
+        assert!(missing.contains("No output device matching 'Focusrite'"));
+        assert!(missing.contains("HDA Intel PCH"));
+    }
+
+    #[test]
+    fn test_describe_device() {
+        let info = DeviceInfo {
+            name: "USB Audio".to_string(),
+            is_default: false,
+            default_config: Some((2, 48000, cpal::SampleFormat::I32)),
+            sample_rates: Some((44100, 96000)),
+            sample_formats: vec![cpal::SampleFormat::I32, cpal::SampleFormat::I16],
+        };
+        assert_eq!(
+            info.describe(),
+            "2 ch, 48000 Hz, i32 (44100-96000 Hz; i32, i16)"
+        );
+    }
+}
@@ -18,7 +18,8 @@ pub struct ProcessContext {
     /// Sample offset within the current cycle
     pub sample_offset: usize,
 
-    /// Number of samples to process in this block (usually 512)
+    /// Number of samples to process in this block (the engine's block size,
+    /// see [`crate::engine_config::EngineConfig`])
     pub block_size: usize,
 
     /// Tempo in cycles per second
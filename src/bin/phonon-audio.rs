@@ -38,7 +38,7 @@ use phonon::link_clock::{
 #[cfg(unix)]
 use phonon::render_swap::{render_swap_channel_default, Cmd, CommandSender};
 #[cfg(unix)]
-use phonon::unified_graph::UnifiedSignalGraph;
+use phonon::unified_graph::{GraphShape, UnifiedSignalGraph};
 #[cfg(unix)]
 use ringbuf::traits::{Consumer, Observer, Producer, Split};
 #[cfg(unix)]
@@ -724,6 +724,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Send Ready message to pattern engine
     IpcMessage::Ready.send(&mut stream)?;
 
+    // What the render thread's graph looks like, for planning the next swap's
+    // bus state transfer (the silent starting graph has nothing to carry)
+    let mut playing_shape = GraphShape::default();
+
     // IPC message loop - receive graph updates from pattern engine
     // Use receive_coalesced to automatically drain stale UpdateGraph messages.
     // Returns all messages in arrival order (non-UpdateGraph first, then latest UpdateGraph).
@@ -778,9 +782,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             //    samples after reload).
                                             //  * preload_plugins — VST3/VST2 disk load kept off the
                                             //    render thread (no-op without plugins).
+                                            //  * plan_bus_state_transfer — pairs the nodes of
+                                            //    unchanged buses with the playing graph's, so the
+                                            //    swap only copies their state.
                                             new_graph.enable_wall_clock_timing();
                                             new_graph.preload_samples();
                                             new_graph.preload_plugins();
+                                            new_graph.plan_bus_state_transfer(&playing_shape);
+                                            let shape = new_graph.shape();
 
                                             // Hand the finished, owned graph to the render thread by
                                             // MOVE through the render-owner swap channel. The render
@@ -791,7 +800,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                             // cross-thread borrow, no 50×500µs try_borrow_mut retry
                                             // loop, no "could not transfer state" give-up (R1), no
                                             // synth starvation (R2), and no voiceless window (R3).
-                                            if !send_cmd_retry(&mut cmd_tx.lock().unwrap(), Cmd::Swap(Box::new(new_graph))) {
+                                            if send_cmd_retry(
+                                                &mut cmd_tx.lock().unwrap(),
+                                                Cmd::Swap(Box::new(new_graph)),
+                                            ) {
+                                                playing_shape = shape;
+                                            } else {
                                                 eprintln!("⚠️  Swap channel full after retries — dropping this reload");
                                             }
                                         }
@@ -0,0 +1,202 @@
+//! Per-bus level metering and headroom reporting
+//!
+//! The render thread records each bus's peak, and the master's just before the
+//! master clipper, into atomics: no locks, no allocation. The editor polls them
+//! for its peak-hold clip indicators; `phonon render` reads the totals for its
+//! headroom report. Whenever the master goes over full scale, the loudest bus
+//! in that block takes the blame, so the report can name the bus that drove the
+//! master into clipping.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Level of one bus
+#[derive(Debug)]
+pub struct BusMeter {
+    pub name: String,
+    /// Peak since the UI last took it (f32 bits)
+    recent: AtomicU32,
+    /// Peak over the whole render (f32 bits)
+    peak: AtomicU32,
+    /// Blocks where this was the loudest bus while the master clipped
+    blamed_blocks: AtomicU32,
+}
+
+/// Meters for every bus of one graph, plus the master
+#[derive(Debug)]
+pub struct BusMeters {
+    buses: Vec<BusMeter>,
+    master_peak: AtomicU32,
+    clipped_blocks: AtomicU32,
+}
+
+/// Totals for the render statistics
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadroomReport {
+    /// Master peak before the master clipper
+    pub master_peak: f32,
+    /// Blocks where the master went over full scale
+    pub clipped_blocks: u32,
+    /// Bus name, peak, blocks blamed for master clipping
+    pub buses: Vec<(String, f32, u32)>,
+}
+
+/// Peaks are non-negative, so their bit patterns order like the values
+fn raise(slot: &AtomicU32, peak: f32) {
+    if peak > 0.0 {
+        slot.fetch_max(peak.to_bits(), Ordering::Relaxed);
+    }
+}
+
+fn load(slot: &AtomicU32) -> f32 {
+    f32::from_bits(slot.load(Ordering::Relaxed))
+}
+
+/// Level in dBFS
+pub fn to_db(level: f32) -> f32 {
+    20.0 * level.max(1e-10).log10()
+}
+
+impl BusMeters {
+    pub fn new(names: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            buses: names
+                .into_iter()
+                .map(|name| BusMeter {
+                    name,
+                    recent: AtomicU32::new(0),
+                    peak: AtomicU32::new(0),
+                    blamed_blocks: AtomicU32::new(0),
+                })
+                .collect(),
+            master_peak: AtomicU32::new(0),
+            clipped_blocks: AtomicU32::new(0),
+        }
+    }
+
+    pub fn bus_names(&self) -> impl Iterator<Item = &str> {
+        self.buses.iter().map(|bus| bus.name.as_str())
+    }
+
+    /// Record the peak of bus `index` in one block
+    pub fn record_bus(&self, index: usize, peak: f32) {
+        if let Some(bus) = self.buses.get(index) {
+            raise(&bus.recent, peak);
+            raise(&bus.peak, peak);
+        }
+    }
+
+    /// Record the master peak of one block; `loudest` is the bus with the
+    /// highest peak in that block
+    pub fn record_master(&self, peak: f32, loudest: Option<usize>) {
+        raise(&self.master_peak, peak);
+        if peak > 1.0 {
+            self.clipped_blocks.fetch_add(1, Ordering::Relaxed);
+            if let Some(bus) = loudest.and_then(|i| self.buses.get(i)) {
+                bus.blamed_blocks.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Each bus's peak since the last call (the editor's meters)
+    pub fn take_recent(&self) -> Vec<(&str, f32)> {
+        self.buses
+            .iter()
+            .map(|bus| {
+                let peak = f32::from_bits(bus.recent.swap(0, Ordering::Relaxed));
+                (bus.name.as_str(), peak)
+            })
+            .collect()
+    }
+
+    pub fn report(&self) -> HeadroomReport {
+        HeadroomReport {
+            master_peak: load(&self.master_peak),
+            clipped_blocks: self.clipped_blocks.load(Ordering::Relaxed),
+            buses: self
+                .buses
+                .iter()
+                .map(|bus| {
+                    (
+                        bus.name.clone(),
+                        load(&bus.peak),
+                        bus.blamed_blocks.load(Ordering::Relaxed),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl HeadroomReport {
+    /// The bus blamed for most of the master's clipping
+    pub fn culprit(&self) -> Option<&str> {
+        self.buses
+            .iter()
+            .filter(|(_, _, blamed)| *blamed > 0)
+            .max_by_key(|(_, _, blamed)| *blamed)
+            .map(|(name, _, _)| name.as_str())
+    }
+
+    /// Report lines: master first, then each bus, loudest first
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "master  peak {:6.1} dBFS  headroom {:5.1} dB{}",
+            to_db(self.master_peak),
+            -to_db(self.master_peak),
+            if self.clipped_blocks > 0 {
+                format!("  CLIPPED in {} blocks", self.clipped_blocks)
+            } else {
+                String::new()
+            }
+        )];
+        let mut buses: Vec<_> = self.buses.iter().collect();
+        buses.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (name, peak, blamed) in buses {
+            let mark = if *blamed > 0 {
+                format!("  drove {} clipped blocks", blamed)
+            } else if *peak > 1.0 {
+                "  over 0 dBFS".to_string()
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "~{:<6} peak {:6.1} dBFS  headroom {:5.1} dB{}",
+                name,
+                to_db(*peak),
+                -to_db(*peak),
+                mark
+            ));
+        }
+        if let Some(culprit) = self.culprit() {
+            lines.push(format!("~{} drove the master into clipping", culprit));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loudest_bus_is_blamed_for_master_clipping() {
+        let meters = BusMeters::new(["kick".to_string(), "bass".to_string()]);
+        meters.record_bus(0, 0.5);
+        meters.record_bus(1, 1.4);
+        meters.record_master(1.6, Some(1));
+        // A clean block doesn't count
+        meters.record_bus(0, 0.9);
+        meters.record_master(0.9, Some(0));
+
+        let report = meters.report();
+        assert_eq!(report.clipped_blocks, 1);
+        assert_eq!(report.culprit(), Some("bass"));
+        assert_eq!(report.buses[0], ("kick".to_string(), 0.9, 0));
+        assert!(report.lines().last().unwrap().contains("~bass drove"));
+
+        // The editor's view resets on each read; the totals don't
+        assert_eq!(meters.take_recent(), vec![("kick", 0.9), ("bass", 1.4)]);
+        assert_eq!(meters.take_recent(), vec![("kick", 0.0), ("bass", 0.0)]);
+        assert_eq!(meters.report().buses[1].1, 1.4);
+    }
+}
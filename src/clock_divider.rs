@@ -0,0 +1,171 @@
+//! Clock dividers and multipliers, as on a modular synth
+//!
+//! ```phonon
+//! ~clock $ gate "t*8"
+//! ~half $ ~clock # clockdiv 2         -- every other pulse
+//! ~fast $ ~clock # clockmult 3        -- three pulses per pulse
+//! gateout 4 ~half
+//! ```
+//!
+//! Both take a clock signal: gates or triggers from patterns (`gate`,
+//! `trig`), or audio such as a pulse wave. A clock is high from
+//! [`HIGH_THRESHOLD`] and low again below [`LOW_THRESHOLD`], so slow edges
+//! and noise don't double-count. `clockdiv` passes every Nth pulse through
+//! as it came; `clockmult` times the gap between pulses and fills each gap
+//! with N evenly spaced pulses at 50% duty. Both output 0 or 1.
+
+/// Level at which a clock goes high
+pub const HIGH_THRESHOLD: f32 = 0.5;
+/// Level below which a high clock goes low again
+pub const LOW_THRESHOLD: f32 = 0.2;
+
+/// Rising-edge detector with hysteresis
+#[derive(Debug, Clone, Default)]
+struct Edge {
+    high: bool,
+}
+
+impl Edge {
+    /// Whether the clock went high on this sample
+    fn rising(&mut self, input: f32) -> bool {
+        let was_high = self.high;
+        if input >= HIGH_THRESHOLD {
+            self.high = true;
+        } else if input < LOW_THRESHOLD {
+            self.high = false;
+        }
+        self.high && !was_high
+    }
+}
+
+/// `clockdiv`: the pulse count and whether the current pulse passes
+#[derive(Debug, Clone, Default)]
+pub struct ClockDividerState {
+    edge: Edge,
+    /// Pulses since the last one passed
+    count: u32,
+    passing: bool,
+}
+
+impl ClockDividerState {
+    /// Advance one sample, passing the first of every `division` pulses
+    pub fn process(&mut self, input: f32, division: u32) -> f32 {
+        if self.edge.rising(input) {
+            if self.count >= division.max(1) {
+                self.count = 0;
+            }
+            self.passing = self.count == 0;
+            self.count += 1;
+        }
+        if self.edge.high && self.passing {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// `clockmult`: time since the last pulse and the gap before it
+#[derive(Debug, Clone, Default)]
+pub struct ClockMultiplierState {
+    edge: Edge,
+    /// Samples since the last pulse (None before the first)
+    since_pulse: Option<u64>,
+    /// Samples between the last two pulses (None until there have been two)
+    period: Option<u64>,
+}
+
+impl ClockMultiplierState {
+    /// Advance one sample, `factor` pulses per input pulse. Until the gap
+    /// between pulses is known the input passes through, and once one gap's
+    /// worth of pulses is out it waits for the next input pulse
+    pub fn process(&mut self, input: f32, factor: u32) -> f32 {
+        if self.edge.rising(input) {
+            self.period = self.since_pulse;
+            self.since_pulse = Some(0);
+        }
+        let out = match (self.period, self.since_pulse) {
+            (Some(period), Some(since)) if since < period => {
+                let sub = period as f64 / factor.max(1) as f64;
+                (since as f64 % sub) < sub / 2.0
+            }
+            (Some(_), _) => false,
+            (None, _) => self.edge.high,
+        };
+        if let Some(since) = self.since_pulse.as_mut() {
+            *since += 1;
+        }
+        if out {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A clock high for `width` of every `period` samples
+    fn clock(period: usize, width: usize, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| if i % period < width { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    fn rising_edges(signal: &[f32]) -> Vec<usize> {
+        (0..signal.len())
+            .filter(|&i| signal[i] > 0.5 && (i == 0 || signal[i - 1] < 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn test_divider_passes_every_nth_pulse() {
+        let mut div = ClockDividerState::default();
+        let out: Vec<f32> = clock(100, 30, 1000)
+            .into_iter()
+            .map(|x| div.process(x, 4))
+            .collect();
+        assert_eq!(rising_edges(&out), vec![0, 400, 800]);
+        // Each passed pulse keeps its width
+        assert_eq!(out[..100].iter().filter(|&&x| x > 0.5).count(), 30);
+    }
+
+    #[test]
+    fn test_divider_ignores_chatter() {
+        // A pulse wave with ripple around the thresholds still counts once
+        let mut div = ClockDividerState::default();
+        let wobbly: Vec<f32> = (0..400)
+            .map(|i| match i % 100 {
+                // Dips below the high threshold, but not the low one
+                0..=39 if i % 7 == 3 => 0.45,
+                0..=39 => 0.9,
+                _ => 0.0,
+            })
+            .collect();
+        let out: Vec<f32> = wobbly.into_iter().map(|x| div.process(x, 2)).collect();
+        assert_eq!(rising_edges(&out), vec![0, 200]);
+    }
+
+    #[test]
+    fn test_multiplier_fills_the_gap() {
+        let mut mult = ClockMultiplierState::default();
+        let out: Vec<f32> = clock(300, 10, 900)
+            .into_iter()
+            .map(|x| mult.process(x, 3))
+            .collect();
+        // The first pulse passes through; after that the gap is known
+        assert_eq!(rising_edges(&out), vec![0, 300, 400, 500, 600, 700, 800]);
+        assert_eq!(out[300..400].iter().filter(|&&x| x > 0.5).count(), 50);
+    }
+
+    #[test]
+    fn test_multiplier_waits_when_the_clock_stops() {
+        let mut mult = ClockMultiplierState::default();
+        let mut input = clock(200, 10, 400);
+        input.resize(1000, 0.0);
+        let out: Vec<f32> = input.into_iter().map(|x| mult.process(x, 2)).collect();
+        assert_eq!(rising_edges(&out), vec![0, 200, 300]);
+    }
+}
@@ -0,0 +1,547 @@
+//! Collaborative session: several performers, one engine
+//!
+//! Remote clients send code over OSC (`phonon live --session`), troop/flok
+//! style. Each client gets its own bus namespace: `~bass` evaluated by `alice`
+//! becomes `~alice_bass`, so two performers can both have a `~bass`. A client
+//! can still reach someone else's bus by its full name (`~bob_kick`).
+//!
+//! Evaluations are incremental per client: a submission replaces only the
+//! buses, outputs and definitions it contains, like evaluating one block in the
+//! editor. Each client's `out` is summed into the shared output
+//! (`merge_programs`), and `tempo:`/`bpm:` set the session tempo for everyone.
+//!
+//! OSC protocol (all arguments are strings):
+//!
+//! ```text
+//! /session/join  <name>           join (or rejoin) and receive the console
+//! /session/eval  <name> <code>    evaluate code in <name>'s namespace
+//! /session/hush  <name>           drop everything <name> has evaluated
+//! /session/say   <name> <text>    chat line in the shared console
+//! /session/leave <name>           hush and stop receiving the console
+//! ```
+//!
+//! The server answers every joined client with `/session/console <line>`.
+//!
+//! A name belongs to the address that first used it until it leaves, so one
+//! performer can't evaluate, hush or leave as another. The server listens on
+//! localhost unless it is bound to another address (`--session-host`).
+
+use crate::compositional_compiler::merge_programs;
+use crate::compositional_parser::{parse_program, tokenize_line, Statement, TokenKind};
+use rosc::{OscMessage, OscPacket, OscType};
+use std::collections::BTreeSet;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
+
+/// Console lines kept for clients that join late
+const CONSOLE_HISTORY: usize = 200;
+
+/// What one performer has evaluated so far
+#[derive(Debug, Clone)]
+struct Client {
+    name: String,
+    /// Buses the client defined, by their un-namespaced names
+    buses: BTreeSet<String>,
+    /// Current statements, keyed by what they define (`~alice_bass`, `out`, ...)
+    program: Vec<(String, Statement)>,
+}
+
+/// Shared state of a collaborative session
+#[derive(Debug, Clone, Default)]
+pub struct CollabSession {
+    clients: Vec<Client>,
+    /// Last `tempo:` / `bpm:` from any client
+    tempo: Option<Statement>,
+    console: Vec<String>,
+}
+
+impl CollabSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluate `code` in `client`'s namespace, replacing the statements it
+    /// redefines. Nothing changes if any of it fails to parse or isn't allowed.
+    /// Returns the names of what was (re)defined.
+    pub fn submit(&mut self, client: &str, code: &str) -> Result<Vec<String>, String> {
+        validate_client_name(client)?;
+        let statements = parse_all(code)?;
+
+        let mut buses = self
+            .client(client)
+            .map(|c| c.buses.clone())
+            .unwrap_or_default();
+        for statement in &statements {
+            if let Statement::BusAssignment { name, .. } = statement {
+                buses.insert(name.clone());
+            }
+        }
+
+        let namespaced = parse_all(&namespace_buses(code, client, &buses))?;
+        let mut updates = Vec::new();
+        let mut tempo = None;
+        let mut hush = false;
+        for statement in namespaced {
+            match statement {
+                Statement::Tempo(_) | Statement::Bpm { .. } => tempo = Some(statement),
+                Statement::Hush { channel: None } => hush = true,
+                other => {
+                    let key = statement_key(&other)
+                        .ok_or_else(|| format!("{:?} isn't allowed in a shared session", other))?;
+                    updates.push((key, other));
+                }
+            }
+        }
+
+        let entry = self.client_entry(client);
+        if hush {
+            entry.program.clear();
+            entry.buses.clear();
+        }
+        entry.buses.extend(buses);
+        let mut changed = Vec::new();
+        for (key, statement) in updates {
+            changed.push(key.clone());
+            match entry.program.iter_mut().find(|(k, _)| *k == key) {
+                Some(slot) => slot.1 = statement,
+                None => entry.program.push((key, statement)),
+            }
+        }
+        if let Some(tempo) = tempo {
+            changed.push("tempo".to_string());
+            self.tempo = Some(tempo);
+        }
+        Ok(changed)
+    }
+
+    /// Replace everything `client` has evaluated with `code` (a whole file
+    /// rather than one block). On error the old code keeps playing.
+    pub fn replace(&mut self, client: &str, code: &str) -> Result<Vec<String>, String> {
+        let mut next = self.clone();
+        next.hush(client);
+        let changed = next.submit(client, code)?;
+        *self = next;
+        Ok(changed)
+    }
+
+    /// Drop everything `client` has evaluated
+    pub fn hush(&mut self, client: &str) {
+        if let Some(c) = self.clients.iter_mut().find(|c| c.name == client) {
+            c.program.clear();
+            c.buses.clear();
+        }
+    }
+
+    /// Remove `client` from the session (its sound stops on the next build)
+    pub fn remove(&mut self, client: &str) {
+        self.clients.retain(|c| c.name != client);
+    }
+
+    /// Client names in join order
+    pub fn clients(&self) -> Vec<&str> {
+        self.clients.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    /// The whole session as one program: the session tempo, then every
+    /// client's statements with their outputs summed
+    pub fn program(&self) -> Vec<Statement> {
+        let mut programs: Vec<Vec<Statement>> = self
+            .clients
+            .iter()
+            .map(|c| c.program.iter().map(|(_, s)| s.clone()).collect())
+            .collect();
+        if let Some(tempo) = &self.tempo {
+            programs.insert(0, vec![tempo.clone()]);
+        }
+        merge_programs(programs)
+    }
+
+    /// Add a line to the shared console
+    pub fn log(&mut self, line: String) {
+        self.console.push(line);
+        if self.console.len() > CONSOLE_HISTORY {
+            self.console.remove(0);
+        }
+    }
+
+    pub fn console(&self) -> &[String] {
+        &self.console
+    }
+
+    fn client(&self, name: &str) -> Option<&Client> {
+        self.clients.iter().find(|c| c.name == name)
+    }
+
+    fn client_entry(&mut self, name: &str) -> &mut Client {
+        let index = match self.clients.iter().position(|c| c.name == name) {
+            Some(index) => index,
+            None => {
+                self.clients.push(Client {
+                    name: name.to_string(),
+                    buses: BTreeSet::new(),
+                    program: Vec::new(),
+                });
+                self.clients.len() - 1
+            }
+        };
+        &mut self.clients[index]
+    }
+}
+
+/// Client names become bus prefixes, so they must be plain identifiers
+fn validate_client_name(name: &str) -> Result<(), String> {
+    let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid client name '{}' (letters, digits and _ only)",
+            name
+        ))
+    }
+}
+
+fn parse_all(code: &str) -> Result<Vec<Statement>, String> {
+    let (rest, statements) = parse_program(code).map_err(|e| format!("Parse error: {}", e))?;
+    if !rest.trim().is_empty() {
+        return Err(format!("Failed to parse entire code, remaining: {}", rest));
+    }
+    Ok(statements)
+}
+
+/// Prefix `client_` to every `~bus` in `code` that is one of `buses`
+fn namespace_buses(code: &str, client: &str, buses: &BTreeSet<String>) -> String {
+    code.split('\n')
+        .map(|line| {
+            let mut out = String::with_capacity(line.len());
+            let mut last = 0;
+            for token in tokenize_line(line) {
+                if token.kind == TokenKind::Bus && buses.contains(&line[token.start + 1..token.end])
+                {
+                    out.push_str(&line[last..token.start + 1]);
+                    out.push_str(client);
+                    out.push('_');
+                    last = token.start + 1;
+                }
+            }
+            out.push_str(&line[last..]);
+            out
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// What a statement defines, so a later evaluation can replace it
+fn statement_key(statement: &Statement) -> Option<String> {
+    match statement {
+        Statement::BusAssignment { name, .. } => Some(format!("~{}", name)),
+        Statement::TemplateAssignment { name, .. } => Some(format!("@{}", name)),
+        Statement::PatternAssignment { name, .. } => Some(format!("%{}", name)),
+        Statement::FunctionDef { name, .. } => Some(format!("fn {}", name)),
+        Statement::Output(_) => Some("out".to_string()),
+        Statement::OutputChannel { channel, .. } => Some(format!("o{}", channel)),
+        Statement::Cue(_) => Some("cue".to_string()),
+        Statement::CvOut { channel, .. } | Statement::GateOut { channel, .. } => {
+            Some(format!("cv{}", channel))
+        }
+        Statement::OutputMixMode(_) => Some("outmix".to_string()),
+        Statement::Visuals { .. } => Some("visuals".to_string()),
+        _ => None,
+    }
+}
+
+/// OSC front end of a `CollabSession`
+///
+/// Non-blocking: call `poll` from the control loop; it returns the merged
+/// program whenever an evaluation changed it.
+pub struct SessionServer {
+    socket: UdpSocket,
+    session: CollabSession,
+    /// Joined clients and where to send the console; a name only takes
+    /// messages from its address
+    peers: Vec<(String, SocketAddr)>,
+}
+
+impl SessionServer {
+    /// Listen for session messages on localhost `port` (0 picks a free port)
+    pub fn bind(port: u16) -> Result<Self, String> {
+        Self::bind_on(IpAddr::V4(Ipv4Addr::LOCALHOST), port)
+    }
+
+    /// Listen on `host` instead, e.g. `0.0.0.0` for performers on the network
+    pub fn bind_on(host: IpAddr, port: u16) -> Result<Self, String> {
+        let socket = UdpSocket::bind((host, port))
+            .map_err(|e| format!("Failed to bind session port {}: {}", port, e))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure session socket: {}", e))?;
+        Ok(Self {
+            socket,
+            session: CollabSession::new(),
+            peers: Vec::new(),
+        })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.socket.local_addr().ok()
+    }
+
+    pub fn session(&self) -> &CollabSession {
+        &self.session
+    }
+
+    /// Replace the host's own code (e.g. the watched file) as client `name`
+    pub fn set_host_code(&mut self, name: &str, code: &str) -> Result<Vec<Statement>, String> {
+        let changed = self.session.replace(name, code)?;
+        self.broadcast(format!("{}: {}", name, changed.join(" ")));
+        Ok(self.session.program())
+    }
+
+    /// Handle every pending message. Returns the merged program when an
+    /// evaluation or hush changed it.
+    pub fn poll(&mut self) -> Option<Vec<Statement>> {
+        let mut program = None;
+        let mut buf = [0u8; 65536];
+        while let Ok((size, addr)) = self.socket.recv_from(&mut buf) {
+            if let Ok((_, packet)) = rosc::decoder::decode_udp(&buf[..size]) {
+                for msg in flatten(packet) {
+                    if let Some(p) = self.handle_message(msg, addr) {
+                        program = Some(p);
+                    }
+                }
+            }
+        }
+        program
+    }
+
+    fn handle_message(&mut self, msg: OscMessage, addr: SocketAddr) -> Option<Vec<Statement>> {
+        let args: Vec<&str> = msg
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                OscType::String(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect();
+        match (msg.addr.as_str(), args.as_slice()) {
+            ("/session/join", [name]) => {
+                if !self.claim(name, addr) {
+                    return None;
+                }
+                for line in self.session.console().to_vec() {
+                    self.send(addr, &line);
+                }
+                self.broadcast(format!("* {} joined", name));
+                None
+            }
+            ("/session/eval", [name, code]) => {
+                if !self.claim(name, addr) {
+                    return None;
+                }
+                self.submit(name, code)
+            }
+            ("/session/hush", [name]) => {
+                if !self.claim(name, addr) {
+                    return None;
+                }
+                self.session.hush(name);
+                self.broadcast(format!("{}: hush", name));
+                Some(self.session.program())
+            }
+            ("/session/say", [name, text]) => {
+                if !self.claim(name, addr) {
+                    return None;
+                }
+                self.broadcast(format!("<{}> {}", name, text));
+                None
+            }
+            ("/session/leave", [name]) => {
+                if !self.claim(name, addr) {
+                    return None;
+                }
+                self.session.remove(name);
+                self.broadcast(format!("* {} left", name));
+                self.peers.retain(|(n, _)| n != name);
+                Some(self.session.program())
+            }
+            _ => {
+                self.send(addr, &format!("Unknown session message: {}", msg.addr));
+                None
+            }
+        }
+    }
+
+    fn submit(&mut self, name: &str, code: &str) -> Option<Vec<Statement>> {
+        match self.session.submit(name, code) {
+            Ok(changed) => {
+                self.broadcast(format!("{}: {}", name, changed.join(" ")));
+                Some(self.session.program())
+            }
+            Err(e) => {
+                self.broadcast(format!("{}: ❌ {}", name, e));
+                None
+            }
+        }
+    }
+
+    /// Check that `name` is `addr`'s, claiming it if nobody has it yet
+    /// (evaluating without joining first still gets the console). Otherwise
+    /// tells the sender why and returns false.
+    fn claim(&mut self, name: &str, addr: SocketAddr) -> bool {
+        let owner = self.peers.iter().find(|(n, _)| n == name).map(|(_, a)| *a);
+        // The host's own code has no address and can't be claimed either
+        let taken = match owner {
+            Some(owner) => owner != addr,
+            None => self.session.clients().contains(&name),
+        };
+        let result = if taken {
+            Err(format!("'{}' is another client's name", name))
+        } else {
+            validate_client_name(name)
+        };
+        match result {
+            Ok(()) => {
+                if owner.is_none() {
+                    self.peers.push((name.to_string(), addr));
+                }
+                true
+            }
+            Err(e) => {
+                self.send(addr, &e);
+                false
+            }
+        }
+    }
+
+    /// Log a console line and send it to every joined client
+    fn broadcast(&mut self, line: String) {
+        println!("🤝 {}", line);
+        for (_, addr) in &self.peers {
+            self.send(*addr, &line);
+        }
+        self.session.log(line);
+    }
+
+    fn send(&self, addr: SocketAddr, line: &str) {
+        let packet = OscPacket::Message(OscMessage {
+            addr: "/session/console".to_string(),
+            args: vec![OscType::String(line.to_string())],
+        });
+        if let Ok(buf) = rosc::encoder::encode(&packet) {
+            let _ = self.socket.send_to(&buf, addr);
+        }
+    }
+}
+
+/// Messages of a packet, bundles flattened in order
+fn flatten(packet: OscPacket) -> Vec<OscMessage> {
+    match packet {
+        OscPacket::Message(msg) => vec![msg],
+        OscPacket::Bundle(bundle) => bundle.content.into_iter().flat_map(flatten).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositional_compiler::compile_program;
+
+    fn keys(session: &CollabSession, client: &str) -> Vec<String> {
+        session
+            .client(client)
+            .unwrap()
+            .program
+            .iter()
+            .map(|(k, _)| k.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_namespace_buses() {
+        let buses: BTreeSet<String> = ["bass".to_string()].into_iter().collect();
+        assert_eq!(
+            namespace_buses(
+                "~bass $ saw 55 # lpf ~cut 0.5\nout $ ~bass",
+                "alice",
+                &buses
+            ),
+            "~alice_bass $ saw 55 # lpf ~cut 0.5\nout $ ~alice_bass"
+        );
+        // Strings (mini-notation) are left alone
+        assert_eq!(
+            namespace_buses("~bass $ s \"~bass bd\"", "bob", &buses),
+            "~bob_bass $ s \"~bass bd\""
+        );
+    }
+
+    #[test]
+    fn test_clients_get_separate_namespaces() {
+        let mut session = CollabSession::new();
+        session
+            .submit("alice", "~bass $ saw 55\nout $ ~bass * 0.2")
+            .unwrap();
+        session
+            .submit("bob", "~bass $ square 110\nout $ ~bass * 0.1")
+            .unwrap();
+        assert_eq!(keys(&session, "alice"), vec!["~alice_bass", "out"]);
+        assert_eq!(keys(&session, "bob"), vec!["~bob_bass", "out"]);
+
+        // Both outputs are summed into one graph
+        let program = session.program();
+        let outputs = program
+            .iter()
+            .filter(|s| matches!(s, Statement::Output(_)))
+            .count();
+        assert_eq!(outputs, 1);
+        assert!(compile_program(program, 44100.0, None).is_ok());
+    }
+
+    #[test]
+    fn test_incremental_evaluation() {
+        let mut session = CollabSession::new();
+        session
+            .submit("alice", "~bass $ saw 55\nout $ ~bass")
+            .unwrap();
+        // Re-evaluating one bus keeps the rest, and earlier buses stay namespaced
+        let changed = session
+            .submit("alice", "~lead $ sine 440\nout $ ~bass + ~lead")
+            .unwrap();
+        assert_eq!(changed, vec!["~alice_lead", "out"]);
+        assert_eq!(
+            keys(&session, "alice"),
+            vec!["~alice_bass", "out", "~alice_lead"]
+        );
+        match &session.client("alice").unwrap().program[1].1 {
+            Statement::Output(expr) => {
+                let text = format!("{:?}", expr);
+                assert!(text.contains("alice_bass") && text.contains("alice_lead"));
+            }
+            other => panic!("expected out, got {:?}", other),
+        }
+
+        // Tempo is shared; hush clears only that client
+        session
+            .submit("bob", "tempo: 0.75\nout $ sine 110")
+            .unwrap();
+        session.hush("alice");
+        assert!(keys(&session, "alice").is_empty());
+        assert_eq!(keys(&session, "bob"), vec!["out"]);
+        assert!(matches!(session.program()[0], Statement::Tempo(t) if t == 0.75));
+    }
+
+    #[test]
+    fn test_rejected_submissions_change_nothing() {
+        let mut session = CollabSession::new();
+        session.submit("alice", "out $ sine 220").unwrap();
+        assert!(session.submit("alice", "out $ $").is_err());
+        assert!(session.submit("alice", "~x $ sine 1\npanic").is_err());
+        assert!(session.submit("not a name", "out $ sine 1").is_err());
+        assert_eq!(keys(&session, "alice"), vec!["out"]);
+
+        // Replacing a whole file is all-or-nothing too
+        assert!(session.replace("alice", "~a $ sine 1\nout $ $").is_err());
+        assert_eq!(keys(&session, "alice"), vec!["out"]);
+        session.replace("alice", "~a $ sine 1\nout $ ~a").unwrap();
+        assert_eq!(keys(&session, "alice"), vec!["~alice_a", "out"]);
+    }
+}
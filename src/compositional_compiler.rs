@@ -9,21 +9,30 @@
     clippy::only_used_in_recursion,
     clippy::redundant_closure
 )]
-use crate::compositional_parser::{BinOp, BusType, Expr, Statement, Transform, UnOp};
-use crate::midi_input::{ArpPattern, Arpeggiator, MidiEventQueue, Scale, parse_root_note};
+use crate::compositional_parser::{
+    BinOp, BusType, Expr, ModRoute, Statement, Transform, TuningSpec, UnOp,
+};
+use crate::hrtf::{decode_gain, HrirSet, VIRTUAL_SPEAKERS};
+use crate::midi_input::{
+    ArpPattern, Arpeggiator, MidiEventQueue, Scale, TakeRequest, parse_root_note,
+};
 use crate::mini_notation_v3::parse_mini_notation;
 use crate::pattern::Pattern;
 use crate::pattern_tonal::note_to_midi;
+use crate::pitch::Tuning;
+use crate::render_assertions::RenderAssertion;
 use crate::scale_dsl::quantize_degree_pattern;
+use crate::spatial::SpatialLayout;
 use crate::superdirt_synths::SynthLibrary;
 use crate::unified_graph::{
-    DattorroState, NodeId, Signal, SignalExpr, SignalNode, TapeDelayState, UnifiedSignalGraph,
-    Waveform,
+    node_shares_state, ConvolutionState, DattorroState, LfoShape, NodeId, Oversampler, Signal,
+    SignalExpr, SignalNode, TapState, TapeDelayState, UnifiedSignalGraph, VarispeedMode, Waveform,
+    Waveshape,
 };
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// Parse a function call into a Transform enum variant.
 /// This is the SINGLE source of truth for transform name -> Transform mapping.
@@ -63,6 +72,20 @@ fn parse_transform_from_call(name: &str, args: &[Expr]) -> Result<Transform, Str
         "shuffle" if args.len() == 1 => Ok(Transform::Shuffle(Box::new(args[0].clone()))),
         "scramble" if args.len() == 1 => Ok(Transform::Scramble(Box::new(args[0].clone()))),
 
+        // Generative
+        "mutate" if args.len() == 1 || args.len() == 2 => Ok(Transform::Mutate {
+            amount: Box::new(args[0].clone()),
+            seed: args.get(1).cloned().map(Box::new),
+        }),
+        "markov" if args.len() <= 1 => Ok(Transform::Markov(args.first().cloned().map(Box::new))),
+
+        // Counted
+        "take" if args.len() == 1 => Ok(Transform::Take(Box::new(args[0].clone()))),
+        "after" if args.len() == 1 => Ok(Transform::After {
+            cycles: Box::new(args[0].clone()),
+            transform: None,
+        }),
+
         // Iteration
         "iter" if args.len() == 1 => Ok(Transform::Iter(Box::new(args[0].clone()))),
         "loopAt" if args.len() == 1 => Ok(Transform::LoopAt(Box::new(args[0].clone()))),
@@ -82,6 +105,7 @@ fn parse_transform_from_call(name: &str, args: &[Expr]) -> Result<Transform, Str
 
         // Timing feel
         "swing" if args.len() == 1 => Ok(Transform::Swing(Box::new(args[0].clone()))),
+        "quantize" if args.len() == 1 => Ok(Transform::Quantize(Box::new(args[0].clone()))),
         "groove" if args.len() == 1 => Ok(Transform::Groove {
             preset: Box::new(args[0].clone()),
             amount: None,
@@ -109,9 +133,11 @@ fn parse_transform_from_call(name: &str, args: &[Expr]) -> Result<Transform, Str
                 "degrade", "degradeBy",
                 "stutter", "stut",
                 "shuffle", "scramble",
+                "mutate", "markov",
+                "take", "after",
                 "iter", "loopAt", "ply",
                 "slice", "splice", "chop", "striate",
-                "swing", "groove",
+                "swing", "quantize", "groove",
                 "compress", "zoom",
             ];
             let suggestion = suggest_similar(name, &known_transforms);
@@ -222,8 +248,80 @@ pub struct CompilerContext {
     pub midi_event_queue: Option<MidiEventQueue>,
     /// Counter for generating anonymous bus names (for inline synth syntax)
     anon_bus_counter: usize,
+    /// Recorders from `tap ~bus "name"` and `assert metric(~bus)`, wrapped
+    /// around the bus when it is compiled
+    bus_recorders: HashMap<String, Vec<Arc<Mutex<TapState>>>>,
+    /// Recorders from `assert metric(out)`, wrapped around the final output
+    output_recorders: Vec<Arc<Mutex<TapState>>>,
+    /// Sample aliases from `alias kick = "808bd:3"`, applied to `s` patterns
+    sample_aliases: HashMap<String, String>,
+    /// Grooves from `groove ~drums "mpc60_54"`, applied to the bus's sample
+    /// patterns when it is compiled
+    bus_grooves: HashMap<String, Transform>,
+    /// Ducking from `duck ~pads ~kick`, applied to the target bus when it is
+    /// compiled
+    bus_ducks: HashMap<String, BusDuck>,
+    /// Auto-gain from `autogain ~bus`: target RMS and time constant in
+    /// seconds, wrapped around the bus when it is compiled
+    bus_autogains: HashMap<String, (f32, f32)>,
+    /// Routes from `mod { ... }` blocks, wired in once every bus is compiled
+    mod_routes: Vec<ModRoute>,
+    /// Mid and side chains from `ms { mid: ..., side: ... }`, applied to the
+    /// out1/out2 pair once every output is compiled
+    mid_side: Option<(Option<Expr>, Option<Expr>)>,
+    /// Frozen buses from `freeze ~pads 8c`: the cycle count and the offline
+    /// recording that plays in place of the bus
+    bus_freezes: HashMap<String, (f64, Arc<Vec<f32>>)>,
+    /// Per-bus tunings from `tuning ~lead 19edo`, applied to the note names
+    /// in the bus's patterns
+    bus_tunings: HashMap<String, Arc<Tuning>>,
+    /// Takes from `record ~keys 4c -> "riff1"`, by name, with the recorded
+    /// bus. Until the take is recorded `%riff1` plays silence
+    record_takes: HashMap<String, String>,
+    /// Speaker layout from `spatial: quad`: `out` is compiled once per
+    /// channel once every bus is compiled
+    spatial: Option<SpatialLayout>,
+    /// HRIR set from `spatial: binaural "kemar"`, loaded once the passes
+    /// are compiled. Without one, binaural uses the spherical head model
+    spatial_hrirs: Option<String>,
+    /// The `spatial:` channel being compiled, where `# azimuth` and
+    /// `# elevation` apply their gains
+    spatial_pass: Option<SpatialPass>,
+    /// Buses sent to `cvout` / `gateout`: control voltages, kept out of the
+    /// automatic bus mix
+    cv_buses: HashSet<String>,
+}
+
+/// A `duck ~target ~trigger` statement
+#[derive(Clone, Debug)]
+struct BusDuck {
+    trigger: String,
+    /// The trigger's definition, compiled in place when the target comes first
+    trigger_expr: Option<Expr>,
+    amount: f64,
+    attack: f64,
+    release: f64,
 }
 
+/// One channel of `out` being compiled for a `spatial:` layout
+#[derive(Clone, Debug)]
+struct SpatialPass {
+    layout: SpatialLayout,
+    /// Output channel, from 0
+    channel: usize,
+    /// Placements compiled for this channel so far
+    placements: usize,
+    /// The source of each placement, by its position in compile order:
+    /// compiled for the first channel and shared by the rest
+    sources: HashMap<usize, NodeId>,
+    /// Buses with placed sources, compiled for this channel (`None` while
+    /// compiling, so a feedback path reads the bus as it is)
+    buses: HashMap<String, Option<NodeId>>,
+}
+
+/// Trigger level (peak) at which a ducked bus drops by the full amount
+const DUCK_FULL_LEVEL: f64 = 0.1;
+
 /// Function definition storage
 #[derive(Clone, Debug)]
 struct FunctionDef {
@@ -335,9 +433,33 @@ impl CompilerContext {
             pattern_registry: HashMap::new(),
             midi_event_queue: None,
             anon_bus_counter: 0,
+            bus_recorders: HashMap::new(),
+            output_recorders: Vec::new(),
+            sample_aliases: HashMap::new(),
+            bus_grooves: HashMap::new(),
+            bus_ducks: HashMap::new(),
+            bus_autogains: HashMap::new(),
+            mod_routes: Vec::new(),
+            mid_side: None,
+            bus_freezes: HashMap::new(),
+            bus_tunings: HashMap::new(),
+            record_takes: HashMap::new(),
+            spatial: None,
+            spatial_hrirs: None,
+            spatial_pass: None,
+            cv_buses: HashSet::new(),
         }
     }
 
+    /// Point the sample names of an `s` pattern at their aliased banks
+    fn resolve_sample_aliases(&self, pattern: Pattern<String>) -> Pattern<String> {
+        if self.sample_aliases.is_empty() {
+            return pattern;
+        }
+        let aliases = self.sample_aliases.clone();
+        pattern.fmap(move |name| resolve_sample_alias(&aliases, &name))
+    }
+
     /// Generate a unique anonymous bus name
     fn generate_anon_bus_name(&mut self) -> String {
         let name = format!("_anon_{}", self.anon_bus_counter);
@@ -416,12 +538,21 @@ impl CompilerContext {
             name,
             // Effects that support effect bus routing
             "reverb"
+                | "freeverb"
+                | "shimmer"
+                | "revreverb"
+                | "tapestop"
+                | "stutter"
+                | "beatrepeat"
                 | "convolve"
                 | "convolution"
                 | "freeze"
                 | "distort"
                 | "distortion"
                 | "dist"
+                | "saturate"
+                | "sat"
+                | "tilt"
                 | "delay"
                 | "tapedelay"
                 | "tape"
@@ -434,6 +565,8 @@ impl CompilerContext {
                 | "comp"
                 | "transient_shaper"
                 | "tshaper"
+                | "transient"
+                | "noise_gate"
                 | "sidechain_compressor"
                 | "sidechain_comp"
                 | "sc_comp"
@@ -515,31 +648,342 @@ impl CompilerContext {
 }
 
 /// Compile a full program
+///
+/// `assert` statements are skipped and `tap` statements only checked, not
+/// recorded: see `compile_program_with_assertions`
 pub fn compile_program(
     statements: Vec<Statement>,
     sample_rate: f32,
     midi_event_queue: Option<MidiEventQueue>,
+) -> Result<UnifiedSignalGraph, String> {
+    compile_program_inner(statements, sample_rate, midi_event_queue, false, None)
+}
+
+/// Compile a full program like `compile_program`, reusing the statements
+/// `cache` kept from the previous compile that haven't changed, and nor has
+/// anything they refer to. The live editor re-evaluates through this.
+pub fn compile_program_cached(
+    statements: Vec<Statement>,
+    sample_rate: f32,
+    midi_event_queue: Option<MidiEventQueue>,
+    cache: &mut CompileCache,
+) -> Result<UnifiedSignalGraph, String> {
+    compile_program_inner(
+        statements,
+        sample_rate,
+        midi_event_queue,
+        false,
+        Some(cache),
+    )
+}
+
+/// Compile a program for an offline render (`phonon test`, `phonon render`)
+/// whose `assert` statements record their targets for `check_assertions`
+/// and whose `tap` statements record buses for `write_tap_files`.
+/// Live graphs never get these recorders: they lock on the render thread and
+/// hold up to ten minutes of audio each.
+pub fn compile_program_with_assertions(
+    statements: Vec<Statement>,
+    sample_rate: f32,
+) -> Result<UnifiedSignalGraph, String> {
+    compile_program_inner(statements, sample_rate, None, true, None)
+}
+
+fn compile_program_inner(
+    statements: Vec<Statement>,
+    sample_rate: f32,
+    midi_event_queue: Option<MidiEventQueue>,
+    record: bool,
+    cache: Option<&mut CompileCache>,
 ) -> Result<UnifiedSignalGraph, String> {
     let mut ctx = CompilerContext::new(sample_rate);
     ctx.midi_event_queue = midi_event_queue;
+    let mut global_tuning = None;
+    let mut a4 = None;
+    let mut transpose = None;
+    let mut freezes = Vec::new();
 
     // PASS 1: Pre-register all bus names with placeholder nodes
     // This allows circular dependencies (a -> b -> a)
+    // Taps and assertions are registered here too, so `tap ~bass "..."` may
+    // appear before or after the definition of ~bass
     for statement in &statements {
-        if let Statement::BusAssignment { name, .. } = statement {
-            // Create a placeholder node (Constant 0.0) for this bus
-            // This will be overwritten in Pass 2, but allows forward references
-            let placeholder_node = ctx.graph.add_node(SignalNode::Constant { value: 0.0 });
-            ctx.buses.insert(name.clone(), placeholder_node);
-            ctx.graph.add_bus(name.clone(), placeholder_node);
+        match statement {
+            Statement::BusAssignment { name, .. } => {
+                // Create a placeholder node (Constant 0.0) for this bus
+                // This will be overwritten in Pass 2, but allows forward references
+                let placeholder_node = ctx.graph.add_node(SignalNode::Constant { value: 0.0 });
+                ctx.buses.insert(name.clone(), placeholder_node);
+                ctx.graph.add_bus(name.clone(), placeholder_node);
+            }
+            Statement::Tap {
+                bus,
+                name,
+                duration,
+            } => {
+                let recorders = ctx.bus_recorders.entry(bus.clone()).or_default();
+                if record {
+                    recorders.push(named_tap_recorder(bus, name, *duration, sample_rate)?);
+                } else {
+                    // Nothing writes a live tap out: check it, keep ~bus
+                    // checked below, and leave the bus unwrapped
+                    tap_length(bus, *duration)?;
+                }
+            }
+            Statement::SampleAlias { name, target } => {
+                if target.is_empty() || target.contains(char::is_whitespace) {
+                    return Err(format!(
+                        "alias {} needs a single sample name, got \"{}\"",
+                        name, target
+                    ));
+                }
+                ctx.sample_aliases.insert(name.clone(), target.clone());
+            }
+            Statement::Groove {
+                buses,
+                template,
+                amount,
+            } => {
+                crate::groove::resolve_groove(template)?;
+                let transform = Transform::Groove {
+                    preset: Box::new(Expr::String(template.clone())),
+                    amount: amount.map(|amount| Box::new(Expr::Number(amount))),
+                };
+                for bus in buses {
+                    ctx.bus_grooves.insert(bus.clone(), transform.clone());
+                }
+            }
+            Statement::Duck {
+                target,
+                trigger,
+                amount,
+                attack,
+                release,
+            } => {
+                if target == trigger {
+                    return Err(format!("duck ~{}: a bus can't duck itself", target));
+                }
+                let amount = amount.unwrap_or(0.8);
+                let attack = attack.unwrap_or(0.005);
+                let release = release.unwrap_or(0.25);
+                if !(0.0..=1.0).contains(&amount) || attack <= 0.0 || release <= 0.0 {
+                    return Err(format!(
+                        "duck ~{}: :amount must be 0..1, :attack and :release above 0",
+                        target
+                    ));
+                }
+                let trigger_expr = statements.iter().find_map(|statement| match statement {
+                    Statement::BusAssignment {
+                        name,
+                        params,
+                        expr,
+                        bus_type: BusType::Signal,
+                    } if name == trigger && params.is_empty() => Some(expr.clone()),
+                    _ => None,
+                });
+                ctx.bus_ducks.insert(
+                    target.clone(),
+                    BusDuck {
+                        trigger: trigger.clone(),
+                        trigger_expr,
+                        amount,
+                        attack,
+                        release,
+                    },
+                );
+            }
+            Statement::AutoGain {
+                bus,
+                target_db,
+                speed,
+            } => {
+                let target_db = target_db.unwrap_or(-18.0);
+                if target_db >= 0.0 {
+                    return Err(format!(
+                        "autogain ~{}: :target must be below 0dB, got {}dB",
+                        bus, target_db
+                    ));
+                }
+                let time = match speed.as_deref().unwrap_or("slow") {
+                    "slow" => 8.0,
+                    "medium" => 3.0,
+                    "fast" => 1.0,
+                    other => other
+                        .parse::<f32>()
+                        .ok()
+                        .filter(|secs| *secs > 0.0)
+                        .ok_or_else(|| {
+                            format!(
+                                "autogain ~{}: :speed must be slow, medium, fast or seconds, got '{}'",
+                                bus, other
+                            )
+                        })?,
+                };
+                let target = 10f32.powf(target_db as f32 / 20.0);
+                ctx.bus_autogains.insert(bus.clone(), (target, time));
+            }
+            Statement::Assert {
+                metric,
+                target,
+                check,
+            } if record => {
+                let assertion =
+                    RenderAssertion::new(metric, target.as_deref(), check.clone(), sample_rate)?;
+                match target {
+                    Some(bus) => ctx
+                        .bus_recorders
+                        .entry(bus.clone())
+                        .or_default()
+                        .push(assertion.recorder()),
+                    None => ctx.output_recorders.push(assertion.recorder()),
+                }
+                ctx.graph.add_assertion(assertion);
+            }
+            Statement::Mod(routes) => ctx.mod_routes.extend(routes.iter().cloned()),
+            Statement::Freeze { bus, cycles } => {
+                if !(*cycles > 0.0 && cycles.is_finite()) {
+                    return Err(format!(
+                        "freeze ~{}: needs a length in cycles above 0, got {}",
+                        bus, cycles
+                    ));
+                }
+                if freezes.iter().any(|(frozen, _)| frozen == bus) {
+                    return Err(format!("freeze ~{}: the bus is already frozen", bus));
+                }
+                freezes.push((bus.clone(), *cycles));
+            }
+            Statement::MidSide { mid, side } => {
+                if ctx.mid_side.is_some() {
+                    return Err("ms: only one mid/side block per program".to_string());
+                }
+                ctx.mid_side = Some((mid.clone(), side.clone()));
+            }
+            Statement::Spatial { layout, hrirs } => {
+                if ctx.spatial.is_some() {
+                    return Err("spatial: only one speaker layout per program".to_string());
+                }
+                ctx.spatial = Some(*layout);
+                ctx.spatial_hrirs = hrirs.clone();
+            }
+            Statement::A4(hz) => {
+                if !(*hz > 0.0 && hz.is_finite()) {
+                    return Err(format!("a4 must be a frequency above 0, got {}", hz));
+                }
+                a4 = Some(*hz);
+            }
+            Statement::Transpose(semitones) => transpose = Some(*semitones),
+            Statement::Record { bus, cycles, name } => {
+                if *cycles < 1.0 || cycles.fract() != 0.0 {
+                    return Err(format!(
+                        "record ~{}: a take lasts a whole number of cycles, got {}",
+                        bus, cycles
+                    ));
+                }
+                if name.is_empty() {
+                    return Err(format!("record ~{}: the take needs a name", bus));
+                }
+                ctx.record_takes.insert(name.clone(), bus.clone());
+            }
+            Statement::Tuning { bus, spec } => {
+                let tuning = Arc::new(match spec {
+                    TuningSpec::Edo(steps) if *steps > 0.0 => Tuning::Edo(*steps),
+                    TuningSpec::Edo(steps) => {
+                        return Err(format!("tuning: {}edo needs at least one step", steps))
+                    }
+                    TuningSpec::Scala { scl, kbm } => Tuning::load_scala(scl, kbm.as_deref())?,
+                });
+                match bus {
+                    Some(bus) => {
+                        ctx.bus_tunings.insert(bus.clone(), tuning);
+                    }
+                    None => global_tuning = Some(tuning),
+                }
+            }
+            _ => {}
+        }
+    }
+    // Without these statements the program is back in standard tuning,
+    // at A4 = 440 Hz and untransposed
+    crate::pitch::set_tuning(global_tuning.clone());
+    crate::pitch::set_a4(a4.unwrap_or(crate::pitch::DEFAULT_A4));
+    crate::pitch::set_transpose(transpose.unwrap_or(0.0));
+
+    for (bus, cycles) in freezes {
+        if ctx.use_audio_nodes {
+            return Err(format!("freeze ~{}: not supported with audio nodes", bus));
+        }
+        let defined = statements.iter().any(|statement| {
+            matches!(statement, Statement::BusAssignment { name, .. } if *name == bus)
+        });
+        if !defined {
+            return Err(format!("~{} is not a signal bus (used by freeze)", bus));
         }
+        let buffer = render_frozen_bus(&statements, &bus, cycles, sample_rate)?;
+        ctx.bus_freezes.insert(bus, (cycles, buffer));
     }
 
     // PASS 2: Compile all statements (can now reference any bus, including forward refs)
-    for statement in statements {
-        compile_statement(&mut ctx, statement)?;
+    let statements: Vec<Statement> = statements
+        .into_iter()
+        .map(|statement| resolve_take_refs(&ctx, statement))
+        .collect();
+    // The last `out` wins, and is what a `spatial:` layout places
+    let output = statements.iter().rev().find_map(|statement| match statement {
+        Statement::Output(expr) => Some(expr.clone()),
+        _ => None,
+    });
+    let cache = cache.and_then(|cache| {
+        compile_setup_hash(&ctx, &statements, global_tuning.as_deref()).map(|setup| (cache, setup))
+    });
+    let mut ctx = compile_statements(ctx, statements, cache)?;
+
+    for bus in ctx.bus_recorders.keys() {
+        if !ctx.bus_expressions.contains_key(bus) {
+            return Err(format!("~{} is not a signal bus (used by tap/assert)", bus));
+        }
+    }
+    for bus in ctx.bus_grooves.keys() {
+        if !ctx.bus_expressions.contains_key(bus) {
+            return Err(format!("~{} is not a signal bus (used by groove)", bus));
+        }
+    }
+    for bus in ctx.bus_ducks.keys() {
+        if !ctx.bus_expressions.contains_key(bus) {
+            return Err(format!("~{} is not a signal bus (used by duck)", bus));
+        }
+    }
+    for bus in ctx.bus_autogains.keys() {
+        if !ctx.bus_expressions.contains_key(bus) {
+            return Err(format!("~{} is not a signal bus (used by autogain)", bus));
+        }
+    }
+    for bus in ctx.bus_tunings.keys() {
+        if !ctx.bus_expressions.contains_key(bus) {
+            return Err(format!("~{} is not a signal bus (used by tuning)", bus));
+        }
+    }
+    for bus in ctx.record_takes.values() {
+        if !ctx.bus_expressions.contains_key(bus) && midi_bus_channel(bus).is_none() {
+            return Err(format!("~{} is not a signal bus (used by record)", bus));
+        }
+    }
+    for bus in ctx.bus_freezes.keys() {
+        if !ctx.bus_expressions.contains_key(bus) {
+            return Err(format!("~{} is not a signal bus (used by freeze)", bus));
+        }
+    }
+    for route in std::mem::take(&mut ctx.mod_routes) {
+        apply_mod_route(&mut ctx, route)?;
+    }
+    if let Some((mid, side)) = ctx.mid_side.take() {
+        apply_mid_side(&mut ctx, mid, side)?;
+    }
+    if let Some(layout) = ctx.spatial {
+        apply_spatial(&mut ctx, layout, output)?;
     }
 
+    let output_recorders = std::mem::take(&mut ctx.output_recorders);
+    let cv_buses = std::mem::take(&mut ctx.cv_buses);
     let mut graph = ctx.into_graph();
 
     // Auto-routing: determine output when no explicit 'out $ expr' was set
@@ -580,9 +1024,14 @@ pub fn compile_program(
                     // multi-bus file with no `out`/`~master` still sounds), but bounded to
                     // a safe "you forgot your output gains" level. Add an explicit
                     // `out $ ...` to control the mix precisely.
+                    // A bus being pre-cued or sent to a CV output stays out of
+                    // the speakers
+                    let cue = graph.cue_output();
                     let plain_nodes: Vec<_> = bus_names
                         .iter()
+                        .filter(|name| !cv_buses.contains(*name))
                         .filter_map(|name| graph.get_bus(name))
+                        .filter(|&node| Some(node) != cue)
                         .collect();
                     if !plain_nodes.is_empty() {
                         let mixed = sum_nodes(&mut graph, &plain_nodes);
@@ -597,9 +1046,99 @@ pub fn compile_program(
         }
     }
 
+    // `assert metric(out)` records the final (possibly auto-routed) output
+    if !output_recorders.is_empty() {
+        let mut output = graph
+            .get_output()
+            .ok_or("assert ...(out): the program has no output")?;
+        for recorder in output_recorders {
+            output = add_tap_node(&mut graph, output, recorder);
+        }
+        graph.set_output(output);
+    }
+
     Ok(graph)
 }
 
+/// Merge several programs (e.g. one per editor pane) into one
+///
+/// Statements are concatenated in order, so a later program wins for buses,
+/// tempo and settings both define. Outputs are summed instead: when more than
+/// one program writes `out` (or the same `oN`/`dN` channel), the merged
+/// program outputs the sum of their expressions. Within a single program the
+/// last `out` still wins, as in `compile_program`.
+pub fn merge_programs(programs: Vec<Vec<Statement>>) -> Vec<Statement> {
+    let mut merged = Vec::new();
+    let mut output: Option<Expr> = None;
+    let mut channels: BTreeMap<usize, Expr> = BTreeMap::new();
+
+    let sum = |acc: Option<Expr>, expr: Expr| match acc {
+        Some(left) => Expr::BinOp {
+            op: BinOp::Add,
+            left: Box::new(left),
+            right: Box::new(expr),
+        },
+        None => expr,
+    };
+
+    for statements in programs {
+        let mut program_output = None;
+        let mut program_channels = BTreeMap::new();
+        for statement in statements {
+            match statement {
+                Statement::Output(expr) => program_output = Some(expr),
+                Statement::OutputChannel { channel, expr } => {
+                    program_channels.insert(channel, expr);
+                }
+                other => merged.push(other),
+            }
+        }
+        if let Some(expr) = program_output {
+            output = Some(sum(output.take(), expr));
+        }
+        for (channel, expr) in program_channels {
+            let acc = channels.remove(&channel);
+            channels.insert(channel, sum(acc, expr));
+        }
+    }
+
+    merged.extend(output.map(Statement::Output));
+    merged.extend(
+        channels
+            .into_iter()
+            .map(|(channel, expr)| Statement::OutputChannel { channel, expr }),
+    );
+    merged
+}
+
+/// `cvout` / `gateout` channels are the interface's own outputs, counted
+/// from 1, and 1 and 2 carry the main mix
+fn check_cv_channel(statement: &str, channel: usize) -> Result<(), String> {
+    if channel < 3 {
+        return Err(format!(
+            "{} {}: channels 1 and 2 play the main mix, use 3 or above",
+            statement, channel
+        ));
+    }
+    Ok(())
+}
+
+/// Names of the buses an expression reads
+fn collect_bus_refs(expr: &Expr, buses: &mut HashSet<String>) {
+    match expr {
+        Expr::BusRef(name) => {
+            buses.insert(name.clone());
+        }
+        Expr::BinOp { left, right, .. } | Expr::Chain(left, right) => {
+            collect_bus_refs(left, buses);
+            collect_bus_refs(right, buses);
+        }
+        Expr::Paren(inner) => collect_bus_refs(inner, buses),
+        Expr::Call { args, .. } => args.iter().for_each(|arg| collect_bus_refs(arg, buses)),
+        _ => {}
+    }
+}
+
 /// Headroom gain applied to the Priority-4 auto-sum fallback (plain `~name` buses
 /// with no explicit `out`/`~master`/`dN`). Raw generator buses sit near unity
 /// (~0.7 RMS / 1.0 peak); summing them straight to the DAC blasts/clips and, during
@@ -666,6 +1205,517 @@ fn is_pure_transform(expr: &Expr) -> bool {
     }
 }
 
+/// Statements compiled by the previous compile, reused when the program is
+/// compiled again. A statement is only compiled again when it changed, or
+/// when something it refers to did: editing one line of a long file
+/// recompiles that line and the lines that use what it defines.
+///
+/// Signal buses, `out` and `outN` are kept; everything else is cheap and
+/// compiled every time. The live editor keeps one and compiles through
+/// `compile_program_cached`.
+#[derive(Default)]
+pub struct CompileCache {
+    /// Hash of what pass 1 registered, which every entry depends on
+    setup: u64,
+    /// Compiled statements by key (see `statement_key`)
+    entries: HashMap<u64, CachedStatement>,
+    /// Statements the last compile took from the cache
+    reused: usize,
+}
+
+impl CompileCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of statements kept
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// How many statements the last compile reused instead of compiling
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+}
+
+/// A compiled signal bus, `out` or `outN` statement
+struct CachedStatement {
+    /// ID of its first node. The nodes go back at the same IDs, so the IDs
+    /// they hold still point at the same nodes.
+    first: NodeId,
+    nodes: Vec<Option<SignalNode>>,
+    /// The node the bus or output is
+    root: NodeId,
+    /// Content hash recorded for hot-swaps (buses and `out`)
+    hash: Option<u64>,
+    /// The bus's expression as `bus_expressions` holds it
+    bus_expression: Option<Expr>,
+}
+
+/// What a cacheable statement sets
+enum CachedTarget {
+    Bus(String),
+    Output,
+    Channel(usize),
+}
+
+/// What a name is defined as so far in a compile
+#[derive(Default)]
+struct Definition {
+    /// Hash of the statements that defined it, with the node it ended up as
+    hash: u64,
+    /// Names those statements refer to
+    names: BTreeSet<String>,
+}
+
+fn hash_of(value: impl std::hash::Hash) -> u64 {
+    use std::hash::Hasher;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Every string literal in a statement's debug text. That takes in every
+/// bus, template, pattern and function it names (and some strings that
+/// aren't names, which only add a dependency that never changes), without
+/// walking each kind of expression and transform.
+fn quoted_strings(text: &str) -> BTreeSet<String> {
+    let mut strings = BTreeSet::new();
+    let mut chars = text.chars();
+    while chars.any(|c| c == '"') {
+        let mut string = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => string.extend(chars.next()),
+                '"' => break,
+                c => string.push(c),
+            }
+        }
+        strings.insert(string);
+    }
+    strings
+}
+
+/// What `statement` sets, if it can be cached
+fn cached_target(statement: &Statement) -> Option<CachedTarget> {
+    match statement {
+        Statement::BusAssignment {
+            name,
+            params,
+            expr,
+            bus_type,
+        } if params.is_empty() && *bus_type == BusType::Signal && !is_pure_transform(expr) => {
+            Some(CachedTarget::Bus(name.clone()))
+        }
+        Statement::Output(_) => Some(CachedTarget::Output),
+        Statement::OutputChannel { channel, .. } => Some(CachedTarget::Channel(*channel)),
+        _ => None,
+    }
+}
+
+/// The name `statement` defines, for statements other than signal buses
+/// (which are followed through `ctx.buses`)
+fn defined_name(statement: &Statement) -> Option<&str> {
+    match statement {
+        Statement::BusAssignment { name, .. }
+        | Statement::TemplateAssignment { name, .. }
+        | Statement::PatternAssignment { name, .. }
+        | Statement::FunctionDef { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// Sizes of the compiler state a cached statement can't put back. A
+/// statement that changes any of them (an inline synth's anonymous bus, an
+/// effect bus send) is compiled every time.
+fn side_effect_shape(ctx: &CompilerContext) -> [usize; 11] {
+    [
+        ctx.buses.len(),
+        ctx.modifier_buses.len(),
+        ctx.transform_buses.len(),
+        ctx.templates.len(),
+        ctx.functions.len(),
+        ctx.effect_buses.len(),
+        ctx.effect_bus_sends.values().map(Vec::len).sum(),
+        ctx.sample_node_metadata.len(),
+        ctx.pattern_registry.len(),
+        ctx.anon_bus_counter,
+        ctx.cv_buses.len(),
+    ]
+}
+
+/// Key of a statement in the cache: its parsed (normalized) text, so
+/// whitespace and comment edits don't count as changes, and the current
+/// definition of everything it refers to, directly or through the
+/// definitions it refers to
+fn statement_key(
+    text: &str,
+    names: &BTreeSet<String>,
+    definitions: &HashMap<String, Definition>,
+    settings: u64,
+) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    (text, settings).hash(&mut hasher);
+    let mut seen = BTreeSet::new();
+    let mut pending: Vec<&String> = names.iter().collect();
+    while let Some(name) = pending.pop() {
+        if !seen.insert(name) {
+            continue;
+        }
+        if let Some(definition) = definitions.get(name) {
+            (name, definition.hash).hash(&mut hasher);
+            pending.extend(&definition.names);
+        }
+    }
+    hasher.finish()
+}
+
+/// The compiled form of a statement that just compiled into nodes from
+/// `first` on, or `None` when it can't be kept: nodes that share state
+/// with their copies can't be handed to two graphs
+fn cached_statement(
+    ctx: &CompilerContext,
+    target: &CachedTarget,
+    first: NodeId,
+) -> Option<CachedStatement> {
+    let nodes = ctx.graph.copy_nodes_from(first);
+    if nodes.iter().flatten().any(node_shares_state) {
+        return None;
+    }
+    let (root, hash, bus_expression) = match target {
+        CachedTarget::Bus(name) => (
+            *ctx.buses.get(name)?,
+            ctx.graph.bus_hash(name),
+            ctx.bus_expressions.get(name).cloned(),
+        ),
+        CachedTarget::Output => (ctx.graph.get_output()?, ctx.graph.output_hash(), None),
+        CachedTarget::Channel(channel) => (
+            ctx.graph
+                .get_output_channels()
+                .into_iter()
+                .find(|(c, _)| c == channel)?
+                .1,
+            None,
+            None,
+        ),
+    };
+    Some(CachedStatement {
+        first,
+        nodes,
+        root,
+        hash,
+        bus_expression,
+    })
+}
+
+/// Put a cached statement into the graph, as compiling it would. False
+/// when its node IDs are already taken.
+fn restore_statement(
+    ctx: &mut CompilerContext,
+    target: &CachedTarget,
+    cached: &CachedStatement,
+) -> bool {
+    if !ctx.graph.place_nodes(cached.first, &cached.nodes) {
+        return false;
+    }
+    match target {
+        CachedTarget::Bus(name) => {
+            if let Some(expr) = &cached.bus_expression {
+                ctx.bus_expressions.insert(name.clone(), expr.clone());
+            }
+            ctx.buses.insert(name.clone(), cached.root);
+            if let Some(hash) = cached.hash {
+                ctx.graph.set_bus_hash(name.clone(), hash);
+            }
+            ctx.graph.add_bus(name.clone(), cached.root);
+        }
+        CachedTarget::Output => {
+            ctx.graph.set_output(cached.root);
+            if let Some(hash) = cached.hash {
+                ctx.graph.set_output_hash(hash);
+            }
+        }
+        CachedTarget::Channel(channel) => ctx.graph.set_output_channel(*channel, cached.root),
+    }
+    true
+}
+
+/// Hash of everything pass 1 registered that pass 2 reads, or `None` when
+/// the compile can't be cached: taps and assertions record through state
+/// shared with the graph, visuals start a sender thread
+fn compile_setup_hash(
+    ctx: &CompilerContext,
+    statements: &[Statement],
+    global_tuning: Option<&Tuning>,
+) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+    if ctx.use_audio_nodes {
+        return None;
+    }
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    ctx.sample_rate.to_bits().hash(&mut hasher);
+    ctx.midi_event_queue
+        .as_ref()
+        .map(Arc::as_ptr)
+        .hash(&mut hasher);
+    for statement in statements {
+        if counts_cycles(statement) {
+            return None;
+        }
+        match statement {
+            Statement::Tap { .. } | Statement::Assert { .. } | Statement::Visuals { .. } => {
+                return None
+            }
+            // Each gets a placeholder node, in order
+            Statement::BusAssignment { name, .. } => name.hash(&mut hasher),
+            Statement::SampleAlias { .. }
+            | Statement::Groove { .. }
+            | Statement::Duck { .. }
+            | Statement::AutoGain { .. }
+            | Statement::Mod(_)
+            | Statement::MidSide { .. }
+            | Statement::Spatial { .. }
+            | Statement::Freeze { .. }
+            | Statement::A4(_)
+            | Statement::Transpose(_)
+            | Statement::Record { .. } => format!("{:?}", statement).hash(&mut hasher),
+            _ => {}
+        }
+    }
+    // What pass 1 took from elsewhere: trigger definitions, tuning files
+    // and frozen recordings
+    let mut ducks: Vec<String> = ctx
+        .bus_ducks
+        .iter()
+        .map(|duck| format!("{:?}", duck))
+        .collect();
+    ducks.sort();
+    ducks.hash(&mut hasher);
+    let mut tunings: Vec<String> = ctx
+        .bus_tunings
+        .iter()
+        .map(|tuning| format!("{:?}", tuning))
+        .collect();
+    tunings.sort();
+    tunings.hash(&mut hasher);
+    format!("{:?}", global_tuning).hash(&mut hasher);
+    let mut freezes: Vec<(&String, *const Vec<f32>)> = ctx
+        .bus_freezes
+        .iter()
+        .map(|(bus, (_, buffer))| (bus, Arc::as_ptr(buffer)))
+        .collect();
+    freezes.sort();
+    freezes.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Whether a statement uses `take` or `after`. Their patterns count from the
+/// origin of the graph they were compiled into, so a checkpoint would carry
+/// an old graph's count into the next evaluation instead of starting a new one.
+fn counts_cycles(statement: &Statement) -> bool {
+    match statement {
+        Statement::BusAssignment { expr, .. }
+        | Statement::TemplateAssignment { expr, .. }
+        | Statement::PatternAssignment { expr, .. }
+        | Statement::Output(expr)
+        | Statement::OutputChannel { expr, .. }
+        | Statement::Cue(expr)
+        | Statement::CvOut { expr, .. }
+        | Statement::GateOut { expr, .. } => expr_counts_cycles(expr),
+        Statement::FunctionDef {
+            body, return_expr, ..
+        } => body.iter().any(counts_cycles) || expr_counts_cycles(return_expr),
+        Statement::MidSide { mid, side } => mid.iter().chain(side).any(expr_counts_cycles),
+        _ => false,
+    }
+}
+
+fn expr_counts_cycles(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call { name, args } => {
+            matches!(name.as_str(), "take" | "after") || args.iter().any(expr_counts_cycles)
+        }
+        Expr::BusCall { args, .. } | Expr::List(args) => args.iter().any(expr_counts_cycles),
+        Expr::Transform { expr, transform } => {
+            expr_counts_cycles(expr) || transform_counts_cycles(transform)
+        }
+        Expr::Chain(left, right) | Expr::BinOp { left, right, .. } => {
+            expr_counts_cycles(left) || expr_counts_cycles(right)
+        }
+        Expr::UnOp { expr, .. } | Expr::Paren(expr) | Expr::Kwarg { value: expr, .. } => {
+            expr_counts_cycles(expr)
+        }
+        _ => false,
+    }
+}
+
+fn transform_counts_cycles(transform: &Transform) -> bool {
+    match transform {
+        Transform::Take(_) | Transform::After { .. } => true,
+        Transform::Every { transform, .. }
+        | Transform::EveryPrime { transform, .. }
+        | Transform::FoldEveryN { transform, .. }
+        | Transform::SomecyclesBy { transform, .. }
+        | Transform::Sometimes(transform)
+        | Transform::SometimesBy { transform, .. }
+        | Transform::Inside { transform, .. }
+        | Transform::Outside { transform, .. }
+        | Transform::Superimpose(transform)
+        | Transform::Chunk { transform, .. }
+        | Transform::Often(transform)
+        | Transform::Rarely(transform)
+        | Transform::AlmostAlways(transform)
+        | Transform::AlmostNever(transform)
+        | Transform::Always(transform)
+        | Transform::Whenmod { transform, .. }
+        | Transform::Within { transform, .. }
+        | Transform::Jux(transform)
+        | Transform::JuxBy { transform, .. }
+        | Transform::Off { transform, .. } => transform_counts_cycles(transform),
+        Transform::FoldEvery { transforms, .. } | Transform::Compose(transforms) => {
+            transforms.iter().any(transform_counts_cycles)
+        }
+        Transform::Effect(expr) => expr_counts_cycles(expr),
+        _ => false,
+    }
+}
+
+/// Pass 2: compile `statements` in order, taking the ones `cache` kept
+/// from the previous compile (built with the same pass 1 `setup`) instead
+/// of compiling them where nothing they depend on has changed
+///
+/// A cached statement goes back at the node IDs it had, so a statement
+/// before it that grew since takes those IDs first and it is compiled
+/// again. `orbit` and `morph` record their nodes in the graph outside the
+/// statement, so statements using them are always compiled.
+fn compile_statements(
+    mut ctx: CompilerContext,
+    statements: Vec<Statement>,
+    cache: Option<(&mut CompileCache, u64)>,
+) -> Result<CompilerContext, String> {
+    let Some((cache, setup)) = cache else {
+        for statement in statements {
+            compile_statement(&mut ctx, statement)?;
+        }
+        return Ok(ctx);
+    };
+    if cache.setup != setup {
+        cache.setup = setup;
+        cache.entries.clear();
+    }
+
+    // Bus names start out as pass 1's placeholders
+    let mut bus_nodes = ctx.buses.clone();
+    let mut definitions: HashMap<String, Definition> = bus_nodes
+        .iter()
+        .map(|(name, node)| {
+            let definition = Definition {
+                hash: hash_of(node.0),
+                names: BTreeSet::new(),
+            };
+            (name.clone(), definition)
+        })
+        .collect();
+    // Statements that define nothing by name (tempo, cue, ...), which
+    // everything after them depends on
+    let mut settings = 0;
+    let mut occurrences: HashMap<u64, usize> = HashMap::new();
+    let mut used = HashSet::new();
+    let mut compiled = HashMap::new();
+
+    for statement in statements {
+        let text = format!("{:?}", statement);
+        let mut names = quoted_strings(&text);
+        let target = cached_target(&statement)
+            .filter(|_| !names.contains("orbit") && !names.contains("morph"));
+        let key = target.as_ref().map(|target| {
+            if let CachedTarget::Bus(name) = target {
+                // A ducked bus compiles its trigger's definition in
+                if let Some(duck) = ctx.bus_ducks.get(name) {
+                    names.extend(quoted_strings(&format!("{:?}", duck)));
+                }
+            }
+            // Repeats of a statement each keep their own entry
+            let key = statement_key(&text, &names, &definitions, settings);
+            let occurrence = occurrences.entry(key).or_default();
+            *occurrence += 1;
+            hash_of((key, *occurrence))
+        });
+        let defines = defined_name(&statement).map(str::to_string);
+
+        let cached = key.zip(target.as_ref()).and_then(|(key, target)| {
+            let cached = cache.entries.get(&key)?;
+            restore_statement(&mut ctx, target, cached).then_some(key)
+        });
+        match cached {
+            Some(key) => {
+                used.insert(key);
+            }
+            None => {
+                let first = ctx.graph.next_node_id();
+                let shape = side_effect_shape(&ctx);
+                compile_statement(&mut ctx, statement)?;
+                if let (Some(key), Some(target)) = (key, &target) {
+                    if side_effect_shape(&ctx) == shape {
+                        if let Some(entry) = cached_statement(&ctx, target, first) {
+                            compiled.insert(key, entry);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Fold the statement into what it defined: its name, and the buses
+        // it pointed somewhere new (inline synths and effect buses add them)
+        let text_hash = hash_of(&text);
+        let mut defined: Vec<String> = ctx
+            .buses
+            .iter()
+            .filter(|(name, node)| bus_nodes.get(name.as_str()) != Some(*node))
+            .map(|(name, _)| name.clone())
+            .collect();
+        if let Some(name) = defines.filter(|name| !defined.contains(name)) {
+            defined.push(name);
+        }
+        if defined.is_empty() && target.is_none() {
+            settings = hash_of((settings, text_hash));
+        }
+        for name in defined {
+            let node = ctx.buses.get(&name).copied();
+            if let Some(node) = node {
+                bus_nodes.insert(name.clone(), node);
+            }
+            let definition = definitions.entry(name).or_default();
+            definition.hash = hash_of((definition.hash, text_hash, node.map(|node| node.0)));
+            definition.names.extend(names.iter().cloned());
+        }
+    }
+
+    cache.entries.retain(|key, _| used.contains(key));
+    cache.entries.extend(compiled);
+    cache.reused = used.len();
+    Ok(ctx)
+}
+
+/// Content hash of a bus or output definition
+///
+/// Hashes the parsed expression rather than the source text, so whitespace
+/// and comment edits don't count as changes. Used by
+/// `UnifiedSignalGraph::transfer_bus_states` to keep the node state of buses
+/// that were not touched when a file is re-evaluated.
+fn definition_hash(expr: &Expr) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    format!("{:?}", expr).hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Compile a single statement
 pub fn compile_statement(ctx: &mut CompilerContext, statement: Statement) -> Result<(), String> {
     match statement {
@@ -701,6 +1751,27 @@ pub fn compile_statement(ctx: &mut CompilerContext, statement: Statement) -> Res
                 // Normal bus assignment: ~name $ expr
                 // All bus assignments are compiled immediately as normal signal chains
                 // This allows effects to be chained and used inline: ~feel: delay ... # reverb ...
+                let (expr, latency) =
+                    take_bus_latency(expr).map_err(|err| format!("latency ~{}: {}", name, err))?;
+                let expr = match ctx.bus_grooves.get(&name) {
+                    Some(groove) => {
+                        let mut grooved = false;
+                        let expr = apply_bus_groove(expr, groove, &mut grooved);
+                        if !grooved {
+                            return Err(format!(
+                                "groove ~{}: the bus has no sample pattern (s \"...\") to shift",
+                                name
+                            ));
+                        }
+                        expr
+                    }
+                    None => expr,
+                };
+                let expr = match ctx.bus_ducks.get(&name) {
+                    Some(duck) => apply_bus_duck(expr, duck, &ctx.bus_expressions)
+                        .map_err(|err| format!("duck ~{}: {}", name, err))?,
+                    None => expr,
+                };
 
                 // Store the expression for transformer bus re-instantiation
                 ctx.bus_expressions.insert(name.clone(), expr.clone());
@@ -715,10 +1786,51 @@ pub fn compile_statement(ctx: &mut CompilerContext, statement: Statement) -> Res
                 } else {
                     // OLD: SignalNode path
                     // Set current_bus for self-reference detection (z^-1 feedback)
-                    ctx.current_bus = Some(name.clone());
-                    let node_id = compile_expr(ctx, expr)?;
-                    ctx.current_bus = None;
+                    let hash = definition_hash(&expr);
+                    let (hash, mut node_id) = match ctx.bus_freezes.get(&name) {
+                        // A new hash, so hot-swaps don't carry live state
+                        // into the recording; auto-gain is already part of it
+                        Some((cycles, buffer)) => (
+                            hash ^ cycles.to_bits(),
+                            ctx.graph.add_node(SignalNode::FrozenBus {
+                                buffer: Arc::clone(buffer),
+                                cycles: *cycles,
+                            }),
+                        ),
+                        None => {
+                            ctx.current_bus = Some(name.clone());
+                            let first = ctx.graph.next_node_id();
+                            let node_id = compile_expr(ctx, expr)?;
+                            ctx.current_bus = None;
+                            if let Some(seconds) = latency {
+                                if ctx.graph.shift_patterns(first, seconds) == 0 {
+                                    return Err(format!(
+                                        "latency ~{}: the bus has no pattern to move",
+                                        name
+                                    ));
+                                }
+                            }
+                            (hash, node_id)
+                        }
+                    };
+                    if let Some(&(target, time)) = ctx
+                        .bus_autogains
+                        .get(&name)
+                        .filter(|_| !ctx.bus_freezes.contains_key(&name))
+                    {
+                        node_id = ctx.graph.add_node(SignalNode::AutoGain {
+                            input: Signal::Node(node_id),
+                            target,
+                            time,
+                            mean_square: 0.0,
+                            gain: 1.0,
+                        });
+                    }
+                    for recorder in ctx.bus_recorders.get(&name).cloned().unwrap_or_default() {
+                        node_id = add_tap_node(&mut ctx.graph, node_id, recorder);
+                    }
                     ctx.buses.insert(name.clone(), node_id);
+                    ctx.graph.set_bus_hash(name.clone(), hash); // Lets hot-swaps keep its state
                     ctx.graph.add_bus(name, node_id); // Register bus in graph for auto-routing
                 }
             } else {
@@ -775,8 +1887,10 @@ pub fn compile_statement(ctx: &mut CompilerContext, statement: Statement) -> Res
                 ctx.audio_node_graph.set_output(node_id);
             } else {
                 // OLD: SignalNode path
+                let hash = definition_hash(&expr);
                 let node_id = compile_expr(ctx, expr)?;
                 ctx.graph.set_output(node_id);
+                ctx.graph.set_output_hash(hash);
             }
             Ok(())
         }
@@ -792,6 +1906,42 @@ pub fn compile_statement(ctx: &mut CompilerContext, statement: Statement) -> Res
             }
             Ok(())
         }
+        Statement::Cue(expr) => {
+            // Headphone cue: rendered alongside the main output, never into it
+            let node_id = compile_expr(ctx, expr)?;
+            ctx.graph.set_cue_output(node_id);
+            Ok(())
+        }
+        Statement::CvOut { channel, expr } => {
+            check_cv_channel("cvout", channel)?;
+            collect_bus_refs(&expr, &mut ctx.cv_buses);
+            let node_id = compile_expr(ctx, expr)?;
+            ctx.graph.set_cv_output(channel, node_id);
+            Ok(())
+        }
+        Statement::GateOut { channel, expr } => {
+            check_cv_channel("gateout", channel)?;
+            collect_bus_refs(&expr, &mut ctx.cv_buses);
+            let gate = match expr {
+                // Drops just before each event ends, so repeated notes retrigger
+                Expr::String(pattern_str) => {
+                    let pattern = parse_mini_notation(&pattern_str)
+                        .fmap(|s: String| s == "t" || s == "x" || s == "1");
+                    ctx.graph.add_node(SignalNode::PatternGate {
+                        pattern_str,
+                        pattern,
+                        retrigger_gap: crate::cv::GATE_GAP,
+                    })
+                }
+                expr => compile_expr(ctx, expr)?,
+            };
+            let node_id = ctx.graph.add_node(SignalNode::Multiply {
+                a: Signal::Node(gate),
+                b: Signal::Value(crate::cv::GATE_VOLTS),
+            });
+            ctx.graph.set_cv_output(channel, node_id);
+            Ok(())
+        }
         Statement::Tempo(cps) => {
             // tempo: value directly sets cycles per second
             // Example: tempo: 1.0 → 1 cycle per second
@@ -809,82 +1959,752 @@ pub fn compile_statement(ctx: &mut CompilerContext, statement: Statement) -> Res
             // Example: 120 BPM → 120 / 60 = 2.0 cps; 60 BPM → 1.0 cps.
             let cps = bpm / 60.0;
 
-            ctx.set_cps(cps);
-            Ok(())
-        }
-        Statement::BufferSize(size) => {
-            // buffer: value sets buffer size in samples (64-16384)
-            // Example: buffer: 1024 → 1024 samples per buffer
-            ctx.graph.set_buffer_size(size);
-            Ok(())
-        }
-        Statement::OutputMixMode(mode_str) => {
-            // outmix: sqrt|gain|tanh|hard|none
-            // Sets how multiple output channels are mixed together
-            use crate::unified_graph::OutputMixMode;
-            match OutputMixMode::from_str(&mode_str) {
-                Some(mode) => {
-                    ctx.graph.set_output_mix_mode(mode);
-                    Ok(())
-                }
-                None => Err(format!(
-                    "Invalid output mix mode '{}'. Valid modes: gain, sqrt, tanh, hard, none",
-                    mode_str
-                )),
-            }
-        }
-        Statement::FunctionDef {
-            name,
-            params,
-            body,
-            return_expr,
-        } => {
-            // Store function definition for later use
-            ctx.functions.insert(
-                name.clone(),
-                FunctionDef {
-                    params,
-                    body,
-                    return_expr,
-                },
-            );
-            Ok(())
-        }
-        Statement::Hush { channel } => {
-            match channel {
-                None => ctx.graph.hush_all(),
-                Some(ch) => ctx.graph.hush_channel(ch),
+            ctx.set_cps(cps);
+            Ok(())
+        }
+        Statement::BufferSize(size) => {
+            // buffer: value sets buffer size in samples (64-16384)
+            // Example: buffer: 1024 → 1024 samples per buffer
+            ctx.graph.set_buffer_size(size);
+            Ok(())
+        }
+        Statement::OutputMixMode(mode_str) => {
+            // outmix: sqrt|gain|tanh|hard|none
+            // Sets how multiple output channels are mixed together
+            use crate::unified_graph::OutputMixMode;
+            match OutputMixMode::from_str(&mode_str) {
+                Some(mode) => {
+                    ctx.graph.set_output_mix_mode(mode);
+                    Ok(())
+                }
+                None => Err(format!(
+                    "Invalid output mix mode '{}'. Valid modes: gain, sqrt, tanh, hard, none",
+                    mode_str
+                )),
+            }
+        }
+        Statement::Visuals { target, fps } => {
+            ctx.graph.enable_visuals(&target, fps.unwrap_or(30.0))
+        }
+        Statement::FunctionDef {
+            name,
+            params,
+            body,
+            return_expr,
+        } => {
+            // Store function definition for later use
+            ctx.functions.insert(
+                name.clone(),
+                FunctionDef {
+                    params,
+                    body,
+                    return_expr,
+                },
+            );
+            Ok(())
+        }
+        Statement::Hush { channel } => {
+            match channel {
+                None => ctx.graph.hush_all(),
+                Some(ch) => ctx.graph.hush_channel(ch),
+            }
+            Ok(())
+        }
+        Statement::Unhush { channel } => {
+            match channel {
+                None => ctx.graph.unhush_all(),
+                Some(ch) => ctx.graph.unhush_channel(ch),
+            }
+            Ok(())
+        }
+        Statement::Panic => {
+            // Stop all audio immediately (kills voices and silences outputs)
+            ctx.graph.panic();
+            Ok(())
+        }
+        Statement::ResetCycles => {
+            // Reset cycle position to 0 (like Tidal's resetCycles)
+            ctx.graph.reset_cycles();
+            Ok(())
+        }
+        Statement::SetCycle(cycle) => {
+            // Jump to specific cycle position
+            ctx.graph.set_cycle(cycle);
+            Ok(())
+        }
+        Statement::Nudge(amount) => {
+            // Shift timing by amount (positive = delay, negative = advance)
+            ctx.graph.nudge(amount);
+            Ok(())
+        }
+        Statement::Tap { .. }
+        | Statement::Assert { .. }
+        | Statement::SampleAlias { .. }
+        | Statement::Groove { .. }
+        | Statement::Duck { .. }
+        | Statement::AutoGain { .. }
+        | Statement::Mod(_)
+        | Statement::MidSide { .. }
+        | Statement::Spatial { .. }
+        | Statement::Freeze { .. }
+        | Statement::Tuning { .. }
+        | Statement::A4(_)
+        | Statement::Transpose(_)
+        | Statement::Record { .. } => {
+            // Registered by compile_program's first pass, so they apply no
+            // matter where the tapped bus or aliased sample is used
+            Ok(())
+        }
+    }
+}
+
+/// Recordings of frozen buses with the hash of the program each was rendered
+/// from, so re-evaluating leaves an unchanged freeze alone
+static FROZEN_BUSES: Mutex<BTreeMap<String, (u64, Arc<Vec<f32>>)>> = Mutex::new(BTreeMap::new());
+
+/// The frozen bus recordings, by bus, with the hash of their program
+pub fn frozen_buses() -> BTreeMap<String, (u64, Arc<Vec<f32>>)> {
+    FROZEN_BUSES
+        .lock()
+        .map(|frozen| frozen.clone())
+        .unwrap_or_default()
+}
+
+/// Put back recordings saved from [`frozen_buses`], so compiling the same
+/// program again doesn't re-render them
+pub fn restore_frozen_buses(buses: BTreeMap<String, (u64, Arc<Vec<f32>>)>) {
+    if let Ok(mut frozen) = FROZEN_BUSES.lock() {
+        frozen.extend(buses);
+    }
+}
+
+/// Render `cycles` cycles of `~bus` offline for `freeze`: the program
+/// without its outputs, playing `out $ ~bus` from cycle 0
+fn render_frozen_bus(
+    statements: &[Statement],
+    bus: &str,
+    cycles: f64,
+    sample_rate: f32,
+) -> Result<Arc<Vec<f32>>, String> {
+    use std::hash::{Hash, Hasher};
+
+    let mut program: Vec<Statement> = statements
+        .iter()
+        .filter(|statement| {
+            !matches!(
+                statement,
+                Statement::Output(_)
+                    | Statement::OutputChannel { .. }
+                    | Statement::Cue(_)
+                    | Statement::CvOut { .. }
+                    | Statement::GateOut { .. }
+                    | Statement::Hush { .. }
+                    | Statement::Unhush { .. }
+                    | Statement::Panic
+                    | Statement::ResetCycles
+                    | Statement::SetCycle(_)
+                    | Statement::Nudge(_)
+                    | Statement::Visuals { .. }
+                    | Statement::Tap { .. }
+                    | Statement::Assert { .. }
+                    | Statement::Record { .. }
+                    | Statement::MidSide { .. }
+                    | Statement::Spatial { .. }
+                    | Statement::Freeze { .. }
+            )
+        })
+        .cloned()
+        .collect();
+    program.push(Statement::Output(Expr::BusRef(bus.to_string())));
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    format!("{:?} {} {}", program, cycles, sample_rate).hash(&mut hasher);
+    let key = hasher.finish();
+    let cached = FROZEN_BUSES
+        .lock()
+        .ok()
+        .and_then(|frozen| frozen.get(bus).cloned());
+    if let Some((hash, buffer)) = cached {
+        if hash == key {
+            return Ok(buffer);
+        }
+    }
+
+    let mut graph = compile_program(program, sample_rate, None)
+        .map_err(|e| format!("freeze ~{}: {}", bus, e))?;
+    // The recording replaces the bus, not the master: no limiting
+    graph.set_master_limiter_ceiling(f32::INFINITY);
+    let samples = (cycles / graph.get_cps() as f64 * sample_rate as f64).round() as usize;
+    let buffer = Arc::new(graph.render(samples));
+    if let Ok(mut frozen) = FROZEN_BUSES.lock() {
+        frozen.insert(bus.to_string(), (key, Arc::clone(&buffer)));
+    }
+    Ok(buffer)
+}
+
+/// Run the out1/out2 pair through an `ms` block: encode to mid = (L+R)/2
+/// and side = (L-R)/2, apply each chain, then decode L = M+S, R = M-S
+fn apply_mid_side(
+    ctx: &mut CompilerContext,
+    mid: Option<Expr>,
+    side: Option<Expr>,
+) -> Result<(), String> {
+    let outputs: HashMap<usize, NodeId> = ctx.graph.get_output_channels().into_iter().collect();
+    let (Some(&left), Some(&right)) = (outputs.get(&1), outputs.get(&2)) else {
+        return Err("ms: needs a stereo pair, define out1 and out2".to_string());
+    };
+
+    let half = |ctx: &mut CompilerContext, expr: SignalExpr| {
+        ctx.graph.add_node(SignalNode::Multiply {
+            a: Signal::Expression(Box::new(expr)),
+            b: Signal::Value(0.5),
+        })
+    };
+    let mut m = half(
+        ctx,
+        SignalExpr::Add(Signal::Node(left), Signal::Node(right)),
+    );
+    let mut s = half(
+        ctx,
+        SignalExpr::Subtract(Signal::Node(left), Signal::Node(right)),
+    );
+    if let Some(chain) = mid {
+        m = compile_chain(ctx, Expr::ChainInput(m), chain).map_err(|e| format!("ms mid: {}", e))?;
+    }
+    if let Some(chain) = side {
+        s = compile_chain(ctx, Expr::ChainInput(s), chain)
+            .map_err(|e| format!("ms side: {}", e))?;
+    }
+
+    let left = ctx.graph.add_node(SignalNode::Add {
+        a: Signal::Node(m),
+        b: Signal::Node(s),
+    });
+    let right = ctx.graph.add_node(SignalNode::Add {
+        a: Signal::Expression(Box::new(SignalExpr::Subtract(
+            Signal::Node(m),
+            Signal::Node(s),
+        ))),
+        b: Signal::Value(0.0),
+    });
+    ctx.graph.set_output_channel(1, left);
+    ctx.graph.set_output_channel(2, right);
+    Ok(())
+}
+
+/// Compile `out` once per channel of a `spatial:` layout, as out1..outN in
+/// place of the main output
+///
+/// Each pass weights the sources placed with `# azimuth`/`# elevation` for
+/// its channel. A placed source is compiled in the first pass and shared by
+/// the rest, so only the gains are per channel. Binaural compiles the FOA
+/// channels and decodes them to out1/out2.
+fn apply_spatial(
+    ctx: &mut CompilerContext,
+    layout: SpatialLayout,
+    output: Option<Expr>,
+) -> Result<(), String> {
+    if ctx.use_audio_nodes {
+        return Err("spatial: not supported with audio nodes".to_string());
+    }
+    let output = output.ok_or("spatial: needs an `out $ ...` to place in the speakers")?;
+    if !ctx.graph.get_output_channels().is_empty() {
+        return Err(format!(
+            "spatial: the layout writes out1..out{} itself, remove the numbered outputs",
+            layout.channels()
+        ));
+    }
+
+    let encoding = layout.encoding();
+    let mut sources = HashMap::new();
+    let mut channels = Vec::with_capacity(encoding.channels());
+    for channel in 0..encoding.channels() {
+        ctx.spatial_pass = Some(SpatialPass {
+            layout: encoding,
+            channel,
+            placements: 0,
+            sources,
+            buses: HashMap::new(),
+        });
+        let node = compile_expr(ctx, output.clone());
+        sources = ctx
+            .spatial_pass
+            .take()
+            .map(|pass| pass.sources)
+            .unwrap_or_default();
+        channels.push(node?);
+    }
+
+    if layout == SpatialLayout::Binaural {
+        let hrirs = match &ctx.spatial_hrirs {
+            Some(set) => HrirSet::load(set, ctx.sample_rate)
+                .map_err(|e| format!("spatial: binaural: {}", e))?,
+            None => HrirSet::spherical_head(ctx.sample_rate),
+        };
+        channels = decode_binaural(ctx, &channels, &hrirs)?;
+    }
+    for (channel, node) in channels.into_iter().enumerate() {
+        ctx.graph.set_output_channel(channel + 1, node);
+    }
+    ctx.graph.clear_output();
+    Ok(())
+}
+
+/// Decode FOA to the virtual speakers and convolve each with the HRIR pair
+/// nearest its direction, summing the left and right ears
+fn decode_binaural(
+    ctx: &mut CompilerContext,
+    foa: &[NodeId],
+    hrirs: &HrirSet,
+) -> Result<Vec<NodeId>, String> {
+    let mut left = Vec::with_capacity(VIRTUAL_SPEAKERS.len());
+    let mut right = Vec::with_capacity(VIRTUAL_SPEAKERS.len());
+    for &(azimuth, elevation) in &VIRTUAL_SPEAKERS {
+        let hrir = hrirs
+            .nearest(azimuth, elevation)
+            .ok_or("spatial: binaural: the HRIR set is empty")?;
+        let weighted: Vec<NodeId> = foa
+            .iter()
+            .enumerate()
+            .map(|(channel, &node)| {
+                ctx.graph.add_node(SignalNode::Multiply {
+                    a: Signal::Node(node),
+                    b: Signal::Value(decode_gain(channel, azimuth, elevation)),
+                })
+            })
+            .collect();
+        let feed = sum_nodes(&mut ctx.graph, &weighted);
+        left.push(ctx.graph.add_node(SignalNode::Convolution {
+            input: Signal::Node(feed),
+            state: ConvolutionState::with_impulse_response(hrir.left.clone()),
+        }));
+        right.push(ctx.graph.add_node(SignalNode::Convolution {
+            input: Signal::Node(feed),
+            state: ConvolutionState::with_impulse_response(hrir.right.clone()),
+        }));
+    }
+    Ok(vec![
+        sum_nodes(&mut ctx.graph, &left),
+        sum_nodes(&mut ctx.graph, &right),
+    ])
+}
+
+/// Wire a `mod` route into its destination parameter
+///
+/// The destination is the first node on the bus with that parameter,
+/// searching back from the bus output through its inputs (but not into
+/// other buses), so `~voice.freq` finds the oscillator behind a filter. The
+/// parameter becomes `base + source * depth`, or `base * 2^(source * depth
+/// / 12)` for a depth in semitones.
+fn apply_mod_route(ctx: &mut CompilerContext, route: ModRoute) -> Result<(), String> {
+    let dest = format!("~{}.{}", route.bus, route.param);
+    if ctx.use_audio_nodes {
+        return Err(format!("mod {}: not supported with audio nodes", dest));
+    }
+    if !(1..=4).contains(&route.curve) {
+        return Err(format!(
+            "mod {}: :curve must be 1 to 4, got {}",
+            dest, route.curve
+        ));
+    }
+    let root = match ctx.buses.get(&route.bus) {
+        Some(&root) if ctx.bus_expressions.contains_key(&route.bus) => root,
+        _ => return Err(format!("mod {}: ~{} is not a signal bus", dest, route.bus)),
+    };
+    let other_buses: HashSet<NodeId> = ctx
+        .buses
+        .iter()
+        .filter(|(name, _)| **name != route.bus)
+        .map(|(_, &id)| id)
+        .collect();
+    let target =
+        find_mod_target(&mut ctx.graph, root, &route.param, &other_buses).ok_or_else(|| {
+            format!(
+                "mod {}: nothing on ~{} has a '{}' parameter",
+                dest, route.bus, route.param
+            )
+        })?;
+
+    let source = compile_expr(ctx, route.source)?;
+    let mut shaped = Signal::Node(source);
+    for _ in 1..route.curve {
+        shaped = Signal::Node(ctx.graph.add_node(SignalNode::Multiply {
+            a: shaped,
+            b: Signal::Node(source),
+        }));
+    }
+    let amount = ctx.graph.add_node(SignalNode::Multiply {
+        a: shaped,
+        b: Signal::Value(route.depth as f32),
+    });
+
+    let base = ctx
+        .graph
+        .get_node_mut(target)
+        .and_then(|node| modulation_param(node, &route.param))
+        .cloned()
+        .ok_or_else(|| format!("mod {}: destination disappeared", dest))?;
+    let modulated = if route.semitones {
+        // 2^(amount / 12) is mtof(69 + amount) / A4
+        let note = ctx.graph.add_node(SignalNode::Add {
+            a: Signal::Node(amount),
+            b: Signal::Value(69.0),
+        });
+        let freq = ctx.graph.add_node(SignalNode::MidiToFreq {
+            midi: Signal::Node(note),
+        });
+        let ratio = ctx.graph.add_node(SignalNode::Multiply {
+            a: Signal::Node(freq),
+            b: Signal::Value((1.0 / crate::pitch::a4()) as f32),
+        });
+        ctx.graph.add_node(SignalNode::Multiply {
+            a: base,
+            b: Signal::Node(ratio),
+        })
+    } else {
+        ctx.graph.add_node(SignalNode::Add {
+            a: base,
+            b: Signal::Node(amount),
+        })
+    };
+    if let Some(param) = ctx
+        .graph
+        .get_node_mut(target)
+        .and_then(|node| modulation_param(node, &route.param))
+    {
+        *param = Signal::Node(modulated);
+    }
+    Ok(())
+}
+
+/// Breadth-first search from `root` for a node with parameter `param`,
+/// not descending into the nodes of other buses
+fn find_mod_target(
+    graph: &mut UnifiedSignalGraph,
+    root: NodeId,
+    param: &str,
+    other_buses: &HashSet<NodeId>,
+) -> Option<NodeId> {
+    let mut queue = VecDeque::from([root]);
+    let mut seen = HashSet::new();
+    while let Some(id) = queue.pop_front() {
+        if !seen.insert(id) || (id != root && other_buses.contains(&id)) {
+            continue;
+        }
+        let Some(node) = graph.get_node_mut(id) else {
+            continue;
+        };
+        if modulation_param(node, param).is_some() {
+            return Some(id);
+        }
+        queue.extend(graph.node_inputs(id));
+    }
+    None
+}
+
+/// The input of `node` that a `mod` route can address as `param`
+fn modulation_param<'a>(node: &'a mut SignalNode, param: &str) -> Option<&'a mut Signal> {
+    let signal = match (node, param) {
+        (
+            SignalNode::Oscillator { freq, .. }
+            | SignalNode::SyncOscillator { freq, .. }
+            | SignalNode::Pulse { freq, .. },
+            "freq",
+        ) => freq,
+        (SignalNode::Pulse { width, .. }, "width") => width,
+        (
+            SignalNode::FMOscillator { carrier_freq, .. }
+            | SignalNode::PMOscillator { carrier_freq, .. },
+            "freq",
+        ) => carrier_freq,
+        (SignalNode::FMOscillator { modulator_freq, .. }, "mod_freq") => modulator_freq,
+        (
+            SignalNode::FMOscillator { mod_index, .. } | SignalNode::PMOscillator { mod_index, .. },
+            "index",
+        ) => mod_index,
+        (
+            SignalNode::LowPass { cutoff, .. }
+            | SignalNode::HighPass { cutoff, .. }
+            | SignalNode::MoogLadder { cutoff, .. }
+            | SignalNode::RLPF { cutoff, .. }
+            | SignalNode::RHPF { cutoff, .. },
+            "cutoff",
+        ) => cutoff,
+        (
+            SignalNode::BandPass { center, .. } | SignalNode::Notch { center, .. },
+            "cutoff" | "center",
+        ) => center,
+        (
+            SignalNode::SVF { frequency, .. } | SignalNode::Resonz { frequency, .. },
+            "cutoff" | "frequency",
+        ) => frequency,
+        (
+            SignalNode::LowPass { q, .. }
+            | SignalNode::HighPass { q, .. }
+            | SignalNode::BandPass { q, .. }
+            | SignalNode::Notch { q, .. }
+            | SignalNode::Resonz { q, .. },
+            "q" | "res" | "resonance",
+        ) => q,
+        (
+            SignalNode::MoogLadder { resonance, .. }
+            | SignalNode::RLPF { resonance, .. }
+            | SignalNode::RHPF { resonance, .. }
+            | SignalNode::SVF { resonance, .. },
+            "q" | "res" | "resonance",
+        ) => resonance,
+        (SignalNode::DJFilter { value, .. }, "value") => value,
+        (SignalNode::Comb { frequency, .. }, "frequency") => frequency,
+        (SignalNode::Delay { time, .. }, "time") => time,
+        (
+            SignalNode::Delay { feedback, .. }
+            | SignalNode::Phaser { feedback, .. }
+            | SignalNode::Comb { feedback, .. },
+            "feedback",
+        ) => feedback,
+        (
+            SignalNode::Delay { mix, .. }
+            | SignalNode::Distortion { mix, .. }
+            | SignalNode::Chorus { mix, .. },
+            "mix",
+        ) => mix,
+        (SignalNode::Distortion { drive, .. }, "drive") => drive,
+        (
+            SignalNode::Tremolo { rate, .. }
+            | SignalNode::Vibrato { rate, .. }
+            | SignalNode::Phaser { rate, .. }
+            | SignalNode::Chorus { rate, .. },
+            "rate",
+        ) => rate,
+        (
+            SignalNode::Tremolo { depth, .. }
+            | SignalNode::Vibrato { depth, .. }
+            | SignalNode::Phaser { depth, .. }
+            | SignalNode::Chorus { depth, .. },
+            "depth",
+        ) => depth,
+        _ => return None,
+    };
+    Some(signal)
+}
+
+/// `expr` scaled by `1 - amount * level`, where `level` follows the trigger
+/// bus's peak (reaching 1 at [`DUCK_FULL_LEVEL`])
+fn apply_bus_duck(
+    expr: Expr,
+    duck: &BusDuck,
+    bus_expressions: &HashMap<String, Expr>,
+) -> Result<Expr, String> {
+    // A trigger defined later is compiled again here rather than read from
+    // its (not yet compiled) bus
+    let trigger = if bus_expressions.contains_key(&duck.trigger) {
+        Expr::BusRef(duck.trigger.clone())
+    } else {
+        duck.trigger_expr
+            .clone()
+            .ok_or_else(|| format!("~{} is not a signal bus", duck.trigger))?
+    };
+    let follower = Expr::Chain(
+        Box::new(trigger),
+        Box::new(Expr::Call {
+            name: "peak_follower".to_string(),
+            args: vec![Expr::Number(duck.attack), Expr::Number(duck.release)],
+        }),
+    );
+    let level = Expr::Call {
+        name: "min".to_string(),
+        args: vec![
+            Expr::BinOp {
+                op: BinOp::Div,
+                left: Box::new(follower),
+                right: Box::new(Expr::Number(DUCK_FULL_LEVEL)),
+            },
+            Expr::Number(1.0),
+        ],
+    };
+    let gain = Expr::BinOp {
+        op: BinOp::Sub,
+        left: Box::new(Expr::Number(1.0)),
+        right: Box::new(Expr::BinOp {
+            op: BinOp::Mul,
+            left: Box::new(Expr::Number(duck.amount)),
+            right: Box::new(level),
+        }),
+    };
+    Ok(Expr::BinOp {
+        op: BinOp::Mul,
+        left: Box::new(Expr::Paren(Box::new(expr))),
+        right: Box::new(gain),
+    })
+}
+
+/// `expr` with `groove` appended to the transforms of each sample pattern
+/// (`s "..."`) it plays, so the groove shifts the final event times. Other
+/// buses it references keep their own timing. Sets `grooved` when a pattern
+/// was found.
+fn apply_bus_groove(expr: Expr, groove: &Transform, grooved: &mut bool) -> Expr {
+    fn is_sample_pattern(expr: &Expr) -> bool {
+        match expr {
+            Expr::Call { name, args } => {
+                name == "s" && matches!(args.first(), Some(Expr::String(_)))
             }
-            Ok(())
+            Expr::Transform { expr, .. } => is_sample_pattern(expr),
+            _ => false,
         }
-        Statement::Unhush { channel } => {
-            match channel {
-                None => ctx.graph.unhush_all(),
-                Some(ch) => ctx.graph.unhush_channel(ch),
+    }
+
+    if is_sample_pattern(&expr) {
+        *grooved = true;
+        return Expr::Transform {
+            expr: Box::new(expr),
+            transform: groove.clone(),
+        };
+    }
+    let mut recurse = |expr: Box<Expr>| Box::new(apply_bus_groove(*expr, groove, grooved));
+    match expr {
+        // Effects after `#` process the grooved signal; only the source moves
+        Expr::Chain(left, right) => Expr::Chain(recurse(left), right),
+        Expr::BinOp { op, left, right } => {
+            let left = recurse(left);
+            Expr::BinOp {
+                op,
+                left,
+                right: recurse(right),
             }
-            Ok(())
-        }
-        Statement::Panic => {
-            // Stop all audio immediately (kills voices and silences outputs)
-            ctx.graph.panic();
-            Ok(())
-        }
-        Statement::ResetCycles => {
-            // Reset cycle position to 0 (like Tidal's resetCycles)
-            ctx.graph.reset_cycles();
-            Ok(())
         }
-        Statement::SetCycle(cycle) => {
-            // Jump to specific cycle position
-            ctx.graph.set_cycle(cycle);
-            Ok(())
+        Expr::Paren(inner) => Expr::Paren(recurse(inner)),
+        Expr::List(items) => Expr::List(
+            items
+                .into_iter()
+                .map(|item| apply_bus_groove(item, groove, grooved))
+                .collect(),
+        ),
+        Expr::Call { name, args } if name == "stack" => Expr::Call {
+            name,
+            args: args
+                .into_iter()
+                .map(|arg| apply_bus_groove(arg, groove, grooved))
+                .collect(),
+        },
+        other => other,
+    }
+}
+
+/// Take a bus's `# latency` out of its chain, returning the chain without
+/// it and the shift in seconds. The shift is applied to the bus's
+/// patterns once compiled (see [`UnifiedSignalGraph::shift_patterns`])
+fn take_bus_latency(expr: Expr) -> Result<(Expr, Option<f64>), String> {
+    use crate::latency::MAX_LATENCY;
+
+    let Expr::Chain(left, right) = expr else {
+        return Ok((expr, None));
+    };
+    let (left, latency) = take_bus_latency(*left)?;
+    let args = match *right {
+        Expr::Call { name, args } if name == "latency" => args,
+        right => return Ok((Expr::Chain(Box::new(left), Box::new(right)), latency)),
+    };
+    if latency.is_some() {
+        return Err("a bus takes one latency".to_string());
+    }
+    match args.as_slice() {
+        [amount] => match extract_number(amount) {
+            Ok(seconds) if seconds.abs() <= MAX_LATENCY => Ok((left, Some(seconds))),
+            Ok(_) => Err(format!(
+                "at most {}s either way; use late or early to move a pattern further",
+                MAX_LATENCY
+            )),
+            Err(_) => Err("needs a time, as in # latency -12ms".to_string()),
+        },
+        _ => Err("needs a time, as in # latency -12ms".to_string()),
+    }
+}
+
+/// Replace the note names of a frequency pattern with their frequencies in
+/// a bus's own tuning (`tuning ~lead 19edo`); numbers pass through as Hz
+fn retune_note_names(pattern: Pattern<String>, tuning: Arc<Tuning>) -> Pattern<String> {
+    let a4 = crate::pitch::a4();
+    let transpose = crate::pitch::transpose_ratio();
+    pattern.fmap(move |value| {
+        if value.parse::<f64>().is_ok() {
+            return value;
         }
-        Statement::Nudge(amount) => {
-            // Shift timing by amount (positive = delay, negative = advance)
-            ctx.graph.nudge(amount);
-            Ok(())
+        match note_to_midi(&value) {
+            Some(midi) => (tuning.midi_to_hz(midi as f64, a4) * transpose).to_string(),
+            None => value,
         }
+    })
+}
+
+/// `name` with its bank replaced if the bank is aliased. An index or `:rr`
+/// on the name offsets into (or rotates through) the alias target's folder:
+/// with `alias kick = "808bd:3"`, `kick:1` plays `808bd:4`. Aliases don't
+/// chain, and bus triggers (`~name`) are left alone.
+fn resolve_sample_alias(aliases: &HashMap<String, String>, name: &str) -> String {
+    let (bank, suffix) = match name.split_once(':') {
+        Some((bank, suffix)) => (bank, Some(suffix)),
+        None => (name, None),
+    };
+    let Some(target) = aliases.get(bank) else {
+        return name.to_string();
+    };
+    let (target_bank, target_index) = match target.split_once(':') {
+        Some((target_bank, index)) => (target_bank, index.parse::<usize>().ok()),
+        None => (target.as_str(), None),
+    };
+    match suffix {
+        None => target.clone(),
+        Some("rr") => format!("{}:rr", target_bank),
+        Some(index) => match (index.parse::<usize>(), target_index) {
+            (Ok(index), Some(offset)) => format!("{}:{}", target_bank, offset + index),
+            _ => format!("{}:{}", target_bank, index),
+        },
+    }
+}
+
+/// Recording length of a named tap without an explicit duration
+const DEFAULT_BUS_TAP_SECONDS: f64 = 60.0;
+
+/// Recording length of `tap ~bus "name" [seconds]`
+fn tap_length(bus: &str, duration: Option<f64>) -> Result<f64, String> {
+    let duration = duration.unwrap_or(DEFAULT_BUS_TAP_SECONDS);
+    if duration <= 0.0 {
+        return Err(format!("tap ~{}: duration must be positive", bus));
     }
+    Ok(duration)
+}
+
+/// Recorder for `tap ~bus "name" [seconds]`, written to disk after a render.
+/// A missing `.wav` extension is added to the name.
+fn named_tap_recorder(
+    bus: &str,
+    name: &str,
+    duration: Option<f64>,
+    sample_rate: f32,
+) -> Result<Arc<Mutex<TapState>>, String> {
+    let duration = tap_length(bus, duration)?;
+    let filename = if name.to_lowercase().ends_with(".wav") {
+        name.to_string()
+    } else {
+        format!("{}.wav", name)
+    };
+    Ok(Arc::new(Mutex::new(TapState::new(
+        filename,
+        duration as f32,
+        sample_rate,
+    ))))
+}
+
+/// Wrap a node in a pass-through Tap feeding `recorder`
+fn add_tap_node(
+    graph: &mut UnifiedSignalGraph,
+    input: NodeId,
+    recorder: Arc<Mutex<TapState>>,
+) -> NodeId {
+    graph.add_node(SignalNode::Tap {
+        input: Signal::Node(input),
+        state: recorder,
+    })
 }
 
 /// Create a MIDI input node for real-time monitoring
@@ -913,6 +2733,215 @@ fn create_midi_input_node(
     }))
 }
 
+/// The MIDI channel a `~midi` bus listens to: `Some(None)` for `~midi` (all
+/// channels), `Some(Some(0))` for `~midi1` through `Some(Some(15))` for
+/// `~midi16`, and None for any other bus
+fn midi_bus_channel(name: &str) -> Option<Option<u8>> {
+    if name == "midi" {
+        return Some(None);
+    }
+    match name.strip_prefix("midi")?.parse::<u8>() {
+        Ok(channel @ 1..=16) => Some(Some(channel - 1)),
+        _ => None,
+    }
+}
+
+/// The takes a program's `record` statements ask for. The channel comes from
+/// the `~midi` bus the recorded bus reads, if any
+pub fn take_requests(statements: &[Statement]) -> Vec<TakeRequest> {
+    fn midi_channel_in(expr: &Expr) -> Option<Option<u8>> {
+        match expr {
+            Expr::BusRef(name) => midi_bus_channel(name),
+            Expr::Call { args, .. } | Expr::BusCall { args, .. } | Expr::List(args) => {
+                args.iter().find_map(midi_channel_in)
+            }
+            Expr::Chain(left, right) | Expr::BinOp { left, right, .. } => {
+                midi_channel_in(left).or_else(|| midi_channel_in(right))
+            }
+            Expr::UnOp { expr, .. } | Expr::Paren(expr) | Expr::Transform { expr, .. } => {
+                midi_channel_in(expr)
+            }
+            Expr::Kwarg { value, .. } => midi_channel_in(value),
+            _ => None,
+        }
+    }
+
+    statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Record { bus, cycles, name } => {
+                let channel = midi_bus_channel(bus).unwrap_or_else(|| {
+                    statements
+                        .iter()
+                        .find_map(|statement| match statement {
+                            Statement::BusAssignment {
+                                name: bus_name,
+                                expr,
+                                ..
+                            } if bus_name == bus => midi_channel_in(expr),
+                            _ => None,
+                        })
+                        .flatten()
+                });
+                Some(TakeRequest {
+                    name: name.clone(),
+                    bus: bus.clone(),
+                    channel,
+                    cycles: cycles.max(1.0) as usize,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Crossfade buses that were redefined since the `previous` program, rather
+/// than swapping them: `morphs` maps each bus to crossfade to its length in
+/// cycles. A redefined bus becomes `morph <previous> <new> <ramp>`; a bus that
+/// is still morphing and wasn't edited keeps its morph. Returns the buses that
+/// started a new morph
+pub fn morph_redefined_buses(
+    previous: &[Statement],
+    statements: &mut [Statement],
+    morphs: &BTreeMap<String, f64>,
+) -> Vec<String> {
+    let mut morphed = Vec::new();
+    for statement in statements.iter_mut() {
+        let Statement::BusAssignment {
+            name,
+            params,
+            expr,
+            bus_type: BusType::Signal,
+        } = statement
+        else {
+            continue;
+        };
+        let Some(&cycles) = morphs.get(name) else {
+            continue;
+        };
+        if !params.is_empty() || is_pure_transform(expr) {
+            continue;
+        }
+        let Some(old) = previous.iter().rev().find_map(|statement| match statement {
+            Statement::BusAssignment {
+                name: old_name,
+                params,
+                expr,
+                bus_type: BusType::Signal,
+            } if old_name == name && params.is_empty() => Some(expr),
+            _ => None,
+        }) else {
+            continue;
+        };
+        if *expr == morph_target(old) {
+            *expr = old.clone();
+            continue;
+        }
+        let ramp = Expr::Call {
+            name: "lfo".to_string(),
+            args: vec![
+                Expr::Call {
+                    name: "cycles".to_string(),
+                    args: vec![Expr::Number(cycles.max(0.01))],
+                },
+                Expr::Number(0.0),
+                Expr::Number(1.0),
+                Expr::Kwarg {
+                    name: "shape".to_string(),
+                    value: Box::new(Expr::Var("saw".to_string())),
+                },
+                Expr::Kwarg {
+                    name: "oneshot".to_string(),
+                    value: Box::new(Expr::Var("true".to_string())),
+                },
+            ],
+        };
+        let new = std::mem::replace(expr, Expr::Number(0.0));
+        *expr = Expr::Call {
+            name: "morph".to_string(),
+            args: vec![old.clone(), new, ramp],
+        };
+        morphed.push(name.clone());
+    }
+    morphed
+}
+
+/// The definition a bus ends up at: the incoming side of a `morph`, or the
+/// definition itself
+pub fn morph_target(expr: &Expr) -> Expr {
+    match expr {
+        Expr::Call { name, args } if name == "morph" && args.len() == 3 => morph_target(&args[1]),
+        _ => expr.clone(),
+    }
+}
+
+/// Replace `%name` references to recorded takes with the take's notes, so a
+/// take is used like any other pattern string. A take that a `record`
+/// statement hasn't captured yet is silent
+fn resolve_take_refs(ctx: &CompilerContext, statement: Statement) -> Statement {
+    fn resolve(ctx: &CompilerContext, expr: Expr) -> Expr {
+        let resolve_box = |expr: Box<Expr>| Box::new(resolve(ctx, *expr));
+        match expr {
+            Expr::PatternRef(name) => match crate::midi_input::get_take(&name) {
+                Some(take) => Expr::String(take),
+                None if ctx.record_takes.contains_key(&name) => Expr::String("~".to_string()),
+                None => Expr::PatternRef(name),
+            },
+            Expr::Call { name, args } => Expr::Call {
+                name,
+                args: args.into_iter().map(|arg| resolve(ctx, arg)).collect(),
+            },
+            Expr::BusCall { name, args } => Expr::BusCall {
+                name,
+                args: args.into_iter().map(|arg| resolve(ctx, arg)).collect(),
+            },
+            Expr::List(items) => {
+                Expr::List(items.into_iter().map(|item| resolve(ctx, item)).collect())
+            }
+            Expr::Chain(left, right) => Expr::Chain(resolve_box(left), resolve_box(right)),
+            Expr::BinOp { op, left, right } => Expr::BinOp {
+                op,
+                left: resolve_box(left),
+                right: resolve_box(right),
+            },
+            Expr::UnOp { op, expr } => Expr::UnOp {
+                op,
+                expr: resolve_box(expr),
+            },
+            Expr::Paren(inner) => Expr::Paren(resolve_box(inner)),
+            Expr::Transform { expr, transform } => Expr::Transform {
+                expr: resolve_box(expr),
+                transform,
+            },
+            Expr::Kwarg { name, value } => Expr::Kwarg {
+                name,
+                value: resolve_box(value),
+            },
+            _ => expr,
+        }
+    }
+
+    match statement {
+        Statement::BusAssignment {
+            name,
+            params,
+            expr,
+            bus_type,
+        } => Statement::BusAssignment {
+            name,
+            params,
+            expr: resolve(ctx, expr),
+            bus_type,
+        },
+        Statement::Output(expr) => Statement::Output(resolve(ctx, expr)),
+        Statement::OutputChannel { channel, expr } => Statement::OutputChannel {
+            channel,
+            expr: resolve(ctx, expr),
+        },
+        _ => statement,
+    }
+}
+
 /// Compile an expression to a node ID
 fn compile_expr(ctx: &mut CompilerContext, expr: Expr) -> Result<NodeId, String> {
     match expr {
@@ -924,7 +2953,11 @@ fn compile_expr(ctx: &mut CompilerContext, expr: Expr) -> Result<NodeId, String>
 
         Expr::String(pattern_str) => {
             // Parse mini-notation and create a Pattern node
-            let pattern = parse_mini_notation(&pattern_str);
+            let mut pattern = parse_mini_notation(&pattern_str);
+            let bus_tuning = ctx.current_bus.as_ref().and_then(|bus| ctx.bus_tunings.get(bus));
+            if let Some(tuning) = bus_tuning.cloned() {
+                pattern = retune_note_names(pattern, tuning);
+            }
             let node = SignalNode::Pattern {
                 pattern_str: pattern_str.clone(),
                 pattern,
@@ -947,17 +2980,8 @@ fn compile_expr(ctx: &mut CompilerContext, expr: Expr) -> Result<NodeId, String>
             }
 
             // Check for MIDI input buses (~midi or ~midi1-16)
-            if name == "midi" {
-                // ~midi = all MIDI channels
-                return create_midi_input_node(ctx, None);
-            } else if name.starts_with("midi") && name.len() > 4 {
-                // ~midi1 through ~midi16
-                if let Ok(channel) = name[4..].parse::<u8>() {
-                    if channel >= 1 && channel <= 16 {
-                        // Convert to 0-indexed channel (0-15)
-                        return create_midi_input_node(ctx, Some(channel - 1));
-                    }
-                }
+            if let Some(channel) = midi_bus_channel(&name) {
+                return create_midi_input_node(ctx, channel);
             }
 
             // Check if this is an effect bus
@@ -973,6 +2997,12 @@ fn compile_expr(ctx: &mut CompilerContext, expr: Expr) -> Result<NodeId, String>
                 return compile_expr(ctx, modifier_expr);
             }
 
+            // Under `spatial:`, a bus with placed sources is compiled again
+            // for each channel
+            if let Some(node) = compile_spatial_bus(ctx, &name)? {
+                return Ok(node);
+            }
+
             // Otherwise, look up normal bus reference
             ctx.buses
                 .get(&name)
@@ -1070,7 +3100,7 @@ fn compile_expr(ctx: &mut CompilerContext, expr: Expr) -> Result<NodeId, String>
                 return compile_phasor(ctx, vec![]);
             }
             if name == "rand" {
-                return compile_rand(ctx, vec![]);
+                return compile_numeric_generator(ctx, "rand", vec![]);
             }
 
             // Zero-arg oscillators = LFOs at 1 Hz (for modulation)
@@ -1102,36 +3132,43 @@ fn compile_expr(ctx: &mut CompilerContext, expr: Expr) -> Result<NodeId, String>
                 "supersnare", "superhat",
                 "lpf", "hpf", "bpf", "notch", "comb", "moog_ladder", "moog",
                 "parametric_eq", "eq",
-                "reverb", "convolve", "convolution", "freeze",
-                "distort", "distortion", "dist", "delay",
+                "reverb", "freeverb", "shimmer", "revreverb", "convolve", "convolution", "freeze",
+                "tapestop", "stutter", "beatrepeat",
+                "distort", "distortion", "dist", "saturate", "sat", "tilt", "fold", "clip",
+                "delay",
                 "tapedelay", "tape", "multitap", "pingpong", "plate", "lush",
                 "chorus", "flanger", "compressor", "comp",
-                "transient_shaper", "tshaper",
-                "expander", "expand", "bitcrush", "coarse", "djf",
+                "transient_shaper", "tshaper", "transient",
+                "expander", "expand", "bitcrush", "coarse", "crush", "shape", "squiz", "djf",
                 "tremolo", "trem", "vibrato", "vib", "phaser", "ph",
                 "widener", "width",
-                "xfade", "mix", "select", "allpass",
+                "xfade", "morph", "mix", "select", "allpass",
                 "svf_lp", "svf_hp", "svf_bp", "svf_notch",
                 "bq_lp", "bq_hp", "bq_bp", "bq_notch",
                 "resonz", "rlpf", "rhpf",
                 "env", "envelope", "env_trig", "adsr", "ad", "line", "curve", "segments",
-                "rms", "schmidt", "latch", "timer", "peak_follower", "amp_follower",
-                "n", "note", "gain", "pan", "speed", "cut", "attack", "release",
+                "rms", "schmidt", "latch", "timer", "clockdiv", "clockmult", "peak_follower", "amp_follower",
+                "n", "note", "gain", "pan", "orbit", "strike", "exciter", "decay", "damping", "speed", "cut", "attack", "release",
                 "ar", "begin", "end", "unit", "loop", "amp", "struct",
-                "tar", "tadsr", "gate", "trig",
-                "run", "scan", "irand", "mtof", "cosine",
-                "range", "min", "wrap", "sample_hold", "decimator",
-                "stack", "cat", "slowcat", "wedge", "sew",
+                "tar", "tadsr", "gate", "noise_gate", "trig",
+                "run", "scan", "irand", "randstep", "mtof", "voct", "cosine", "lfo",
+                "range", "min", "wrap", "sample_hold", "sample_and_hold", "sah", "decimator",
+                "stack", "cat", "slowcat", "wedge", "sew", "ur",
             ];
             if functions_needing_args.contains(&name.as_str()) {
                 return Err(format!("'{}' requires argument(s). Usage: {} <input> [params]", name, name));
             }
 
             // Otherwise, look up variable (function parameter)
-            ctx.buses
-                .get(&name)
-                .copied()
-                .ok_or_else(|| format!("Undefined variable: {}", name))
+            if let Some(&node) = ctx.buses.get(&name) {
+                return Ok(node);
+            }
+
+            // A note name (`sine c3`) is its frequency, as in mini-notation
+            if crate::pitch::is_note_literal(&name) {
+                return compile_expr(ctx, Expr::String(name));
+            }
+            Err(format!("Undefined variable: {}", name))
         }
 
         Expr::Call { name, args } => compile_function_call(ctx, &name, args),
@@ -1144,6 +3181,12 @@ fn compile_expr(ctx: &mut CompilerContext, expr: Expr) -> Result<NodeId, String>
 
         Expr::Transform { expr, transform } => compile_transform(ctx, *expr, transform),
 
+        // `~pitch |> voct` pipes a frequency into the converter
+        Expr::BinOp {
+            op: BinOp::UnionLeft,
+            left,
+            right,
+        } if matches!(&*right, Expr::Var(name) if name == "voct") => compile_voct(ctx, vec![*left]),
         Expr::BinOp { op, left, right } => compile_binop(ctx, op, *left, *right),
 
         Expr::UnOp { op, expr } => compile_unop(ctx, op, *expr),
@@ -1213,9 +3256,28 @@ fn compile_expr_with_bindings(
                 BinOp::Div | BinOp::DivLeft | BinOp::DivRight | BinOp::SignalDiv => {
                     SignalExpr::Divide(Signal::Node(left_node), Signal::Node(right_node))
                 }
-                BinOp::UnionLeft | BinOp::UnionRight => {
+                BinOp::AddBoth => {
+                    SignalExpr::Add(Signal::Node(left_node), Signal::Node(right_node))
+                }
+                BinOp::SubBoth => {
+                    SignalExpr::Subtract(Signal::Node(left_node), Signal::Node(right_node))
+                }
+                BinOp::MulBoth => {
+                    SignalExpr::Multiply(Signal::Node(left_node), Signal::Node(right_node))
+                }
+                BinOp::DivBoth => {
+                    SignalExpr::Divide(Signal::Node(left_node), Signal::Node(right_node))
+                }
+                BinOp::UnionLeft | BinOp::UnionRight | BinOp::UnionBothLeft => {
                     SignalExpr::Add(Signal::Node(left_node), Signal::Value(0.0))
                 }
+                BinOp::UnionBoth => SignalExpr::Add(Signal::Node(right_node), Signal::Value(0.0)),
+                BinOp::Spread => {
+                    return Ok(ctx.graph.add_node(SignalNode::RandSpread {
+                        min: Signal::Node(left_node),
+                        max: Signal::Node(right_node),
+                    }))
+                }
             };
 
             let node = SignalNode::Add {
@@ -2526,6 +4588,7 @@ fn compile_function_call(
         "wedge" => compile_wedge(ctx, args),
         "sew" => compile_sew(ctx, args),
         "stitch" => compile_stitch(ctx, args),
+        "ur" => compile_ur(ctx, args),
 
         // ========== Sample playback ==========
         "s" => {
@@ -2862,6 +4925,8 @@ fn compile_function_call(
                 }
             };
 
+            let pattern = ctx.resolve_sample_aliases(pattern);
+
             // Process kwargs to set sample parameters
             let mut gain = Signal::Value(1.0);
             let mut pan = Signal::Value(0.0);
@@ -3017,9 +5082,19 @@ fn compile_function_call(
 
         // ========== Effects ==========
         "reverb" => compile_reverb(ctx, args),
+        "freeverb" => compile_freeverb(ctx, args),
+        "shimmer" => compile_shimmer(ctx, args),
+        "revreverb" => compile_revreverb(ctx, args),
+        "tapestop" => compile_tapestop(ctx, args),
+        "stutter" => compile_stutter(ctx, args),
+        "beatrepeat" => compile_beatrepeat(ctx, args),
         "convolve" | "convolution" => compile_convolve(ctx, args),
         "freeze" => compile_freeze(ctx, args),
         "distort" | "distortion" | "dist" => compile_distortion(ctx, args),
+        "saturate" | "sat" => compile_saturate(ctx, args),
+        "fold" => compile_fold(ctx, args),
+        "clip" => compile_clip(ctx, args),
+        "tilt" => compile_tilt(ctx, args),
         "delay" => compile_delay(ctx, args),
         "tapedelay" | "tape" => compile_tapedelay(ctx, args),
         "multitap" => compile_multitap(ctx, args),
@@ -3030,12 +5105,36 @@ fn compile_function_call(
         "flanger" => compile_flanger(ctx, args),
         "compressor" | "comp" => compile_compressor(ctx, args),
         "transient_shaper" | "tshaper" => compile_transient_shaper(ctx, args),
+        "transient" => compile_transient(ctx, args),
         "sidechain_compressor" | "sidechain_comp" | "sc_comp" => {
             compile_sidechain_compressor(ctx, args)
         }
         "expander" | "expand" => compile_expander(ctx, args),
         "bitcrush" => compile_bitcrush(ctx, args),
         "coarse" => compile_coarse(ctx, args),
+        "crush" => compile_crush(ctx, args),
+        "shape" | "squiz" => match compile_voice_fx(ctx, name, &args)? {
+            Some(node) => Ok(node),
+            None => Err(format!(
+                "{} works per voice on a sample pattern with a number or pattern value, \
+                 e.g. s \"bd*4\" # {} \"0 2\"",
+                name, name
+            )),
+        },
+        "orbit" => compile_orbit(ctx, args),
+        "strike" => compile_voice_fx(ctx, name, &args)?.ok_or_else(|| {
+            "strike sets how hard a westcoast note is struck, 0 to 1: \
+             s \"westcoast*4\" # strike \"0.4 0.9\""
+                .to_string()
+        }),
+        "exciter" => compile_exciter(ctx, args),
+        "decay" | "damping" => compile_voice_fx(ctx, name, &args)?.ok_or_else(|| {
+            format!(
+                "{} sets how long a pluck note rings (decay) or how fast its \
+                 overtones fade (damping), 0 to 1: s \"pluck*4\" # {} 0.8",
+                name, name
+            )
+        }),
         "djf" => compile_djf(ctx, args),
         "ring" => compile_ring(ctx, args),
         "tremolo" | "trem" => compile_tremolo(ctx, args),
@@ -3043,6 +5142,7 @@ fn compile_function_call(
         "phaser" | "ph" => compile_phaser(ctx, args),
         "widener" | "width" => compile_widener(ctx, args),
         "xfade" => compile_xfade(ctx, args),
+        "morph" => compile_morph(ctx, args),
         "mix" => compile_mix(ctx, args),
         "if" => compile_if(ctx, args),
         "select" => compile_select(ctx, args),
@@ -3074,6 +5174,8 @@ fn compile_function_call(
         "schmidt" => compile_schmidt(ctx, args),
         "latch" => compile_latch(ctx, args),
         "timer" => compile_timer(ctx, args),
+        "clockdiv" => compile_clockdiv(ctx, args),
+        "clockmult" => compile_clockmult(ctx, args),
         "peak_follower" => compile_peak_follower(ctx, args),
         "amp_follower" => compile_amp_follower(ctx, args),
 
@@ -3082,6 +5184,14 @@ fn compile_function_call(
         "note" => compile_note_modifier(ctx, args),
         "scale" => compile_scale_modifier(ctx, args),
         "chord" => compile_chord_modifier(ctx, args),
+        "harmonize" => compile_harmonize_modifier(ctx, args),
+        "voicing" => compile_voicing_modifier(ctx, args),
+        "inversion" => compile_inversion_modifier(ctx, args),
+        "open" | "close" => {
+            let mut args = args;
+            args.push(Expr::String(name.to_string()));
+            compile_voicing_modifier(ctx, args)
+        }
         "gain" => compile_gain_modifier(ctx, args),
         "pan" => compile_pan_modifier(ctx, args),
         "speed" => compile_speed_modifier(ctx, args),
@@ -3103,18 +5213,26 @@ fn compile_function_call(
         // ========== Triggered Envelopes ==========
         "tar" => compile_tar(ctx, args),
         "tadsr" => compile_tadsr(ctx, args),
-        "gate" => compile_gate(ctx, args),
+        // `gate "t(3,8)"` is a pattern gate; `gate ~pad :key ~kick` gates audio
+        "gate" if matches!(args.as_slice(), [Expr::String(_)]) => compile_gate(ctx, args),
+        "gate" | "noise_gate" => compile_noise_gate(ctx, args),
         "trig" => compile_trig(ctx, args),
 
         // ========== Pattern Generators (Numeric) ==========
-        "run" => compile_run(ctx, args),
-        "scan" => compile_scan(ctx, args),
-        "irand" => compile_irand(ctx, args),
-        "rand" => compile_rand(ctx, args),
+        "run" | "scan" | "irand" | "rand" | "randstep" | "choose" | "wchoose" => {
+            compile_numeric_generator(ctx, name, args)
+        }
         "phasor" => compile_phasor(ctx, args),
+        "lfo" => compile_lfo(ctx, args),
+        "cycles" => Err(
+            "a length in cycles (like 0.25c) only works as an lfo rate; use Hz elsewhere"
+                .to_string(),
+        ),
 
         // ========== MIDI/Frequency Conversion ==========
         "mtof" => compile_mtof(ctx, args),
+        "voct" => compile_voct(ctx, args),
+        "latency" => Err("latency goes on a bus: ~name $ ... # latency -12ms".to_string()),
         // NOTE: sine/saw/tri/square are already defined as oscillators above
         // Pattern generators would need different names like "sine_wave", "saw_wave" etc.
         "cosine" => compile_cosine_wave(ctx, args),
@@ -3135,11 +5253,12 @@ fn compile_function_call(
         // NOTE: gain and pan are already defined as sample parameter modifiers above
         "min" => compile_min(ctx, args),
         "wrap" => compile_wrap(ctx, args),
-        "sample_hold" => compile_sample_hold(ctx, args),
+        "sample_hold" | "sample_and_hold" | "sah" => compile_sample_hold(ctx, args),
         "decimator" => compile_decimator(ctx, args),
 
         // ========== Plugin Hosting (VST/AU/CLAP/LV2) ==========
         "vst" | "vst2" | "vst3" | "au" | "clap" | "lv2" | "plugin" => compile_vst(ctx, args),
+        "extfx" => compile_extfx(ctx, args),
 
         // VST parameter modifier with explicit string name
         // Syntax: vst "Plugin" # param "Filter Cutoff" 0.5
@@ -3201,6 +5320,11 @@ fn compile_function_call(
                 }
             }
 
+            // User nodes registered through node_factory
+            if let Some(node) = crate::node_factory::lookup(name) {
+                return compile_user_node(ctx, node, args);
+            }
+
             // Check if this is a common parameter modifier being used with $ instead of #
             let parameter_modifiers = [
                 "speed",
@@ -3215,6 +5339,8 @@ fn compile_function_call(
                 "loop",
                 "crush",
                 "coarse",
+                "shape",
+                "squiz",
                 "cutoff",
                 "resonance",
                 "room",
@@ -3232,7 +5358,7 @@ fn compile_function_call(
                 ))
             } else {
                 let known_functions: &[&str] = &[
-                    "stack", "cat", "slowcat", "wedge", "sew",
+                    "stack", "cat", "slowcat", "wedge", "sew", "ur",
                     "s", "sine", "saw", "square", "tri", "triangle",
                     "fm", "pm", "blip", "vco", "wavetable", "granular",
                     "pluck", "waveguide", "formant", "vowel", "additive", "vocoder",
@@ -3249,29 +5375,32 @@ fn compile_function_call(
                     "supersnare", "superhat",
                     "lpf", "hpf", "bpf", "notch", "comb", "moog_ladder", "moog",
                     "parametric_eq", "eq",
-                    "reverb", "convolve", "convolution", "freeze",
-                    "distort", "distortion", "dist", "delay",
+                    "reverb", "freeverb", "shimmer", "revreverb", "convolve", "convolution", "freeze",
+                "tapestop", "stutter", "beatrepeat",
+                    "distort", "distortion", "dist", "saturate", "sat", "tilt", "fold", "clip",
+                    "delay",
                     "tapedelay", "tape", "multitap", "pingpong", "plate", "lush",
                     "chorus", "flanger", "compressor", "comp",
-                    "transient_shaper", "tshaper",
+                    "transient_shaper", "tshaper", "transient",
                     "sidechain_compressor", "sidechain_comp", "sc_comp",
-                    "expander", "expand", "bitcrush", "coarse", "djf", "ring",
+                    "expander", "expand", "bitcrush", "coarse", "crush", "shape", "squiz",
+                    "djf", "ring",
                     "tremolo", "trem", "vibrato", "vib", "phaser", "ph",
                     "widener", "width",
-                    "xfade", "mix", "if", "select", "allpass",
+                    "xfade", "morph", "mix", "if", "select", "allpass",
                     "svf_lp", "svf_hp", "svf_bp", "svf_notch",
                     "bq_lp", "bq_hp", "bq_bp", "bq_notch",
                     "resonz", "rlpf", "rhpf", "tap", "probe",
                     "env", "envelope", "env_trig", "adsr", "ad", "line", "curve", "segments",
-                    "rms", "schmidt", "latch", "timer", "peak_follower", "amp_follower",
-                    "n", "note", "gain", "pan", "speed", "cut", "attack", "release",
+                    "rms", "schmidt", "latch", "timer", "clockdiv", "clockmult", "peak_follower", "amp_follower",
+                    "n", "note", "gain", "pan", "orbit", "strike", "exciter", "decay", "damping", "speed", "cut", "attack", "release",
                     "ar", "begin", "end", "unit", "loop", "amp", "struct",
-                    "tar", "tadsr", "gate", "trig",
-                    "run", "scan", "irand", "rand", "phasor", "mtof", "cosine",
+                    "tar", "tadsr", "gate", "noise_gate", "trig",
+                    "run", "scan", "irand", "rand", "randstep", "phasor", "lfo", "mtof", "voct", "cosine",
                     "every_val", "sometimes_val", "sometimes_by_val", "whenmod_val",
                     "every_effect", "sometimes_effect", "whenmod_effect",
-                    "range", "min", "wrap", "sample_hold", "decimator",
-                    "vst", "vst2", "vst3", "au", "clap", "lv2", "plugin", "param",
+                    "range", "min", "wrap", "sample_hold", "sample_and_hold", "sah", "decimator",
+                    "vst", "vst2", "vst3", "au", "clap", "lv2", "plugin", "param", "extfx",
                 ];
                 let suggestion = suggest_similar(name, known_functions);
                 match suggestion {
@@ -3658,20 +5787,115 @@ fn compile_stitch(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId,
         }
     };
 
-    let bool_str = extract_pattern_str(&args[0])?;
-    let pat_true_str = extract_pattern_str(&args[1])?;
-    let pat_false_str = extract_pattern_str(&args[2])?;
+    let bool_str = extract_pattern_str(&args[0])?;
+    let pat_true_str = extract_pattern_str(&args[1])?;
+    let pat_false_str = extract_pattern_str(&args[2])?;
+
+    // Parse patterns
+    let bool_pattern = parse_mini_notation(&bool_str);
+    let pat_true = parse_mini_notation(&pat_true_str);
+    let pat_false = parse_mini_notation(&pat_false_str);
+
+    // Combine using Pattern::stitch
+    let combined_pattern = Pattern::stitch(bool_pattern, pat_true, pat_false);
+    let combined_str = format!(
+        "stitch \"{}\" \"{}\" \"{}\"",
+        bool_str, pat_true_str, pat_false_str
+    );
+
+    // Create a Sample node with the combined pattern
+    let node = SignalNode::Sample {
+        pattern_str: combined_str,
+        pattern: combined_pattern,
+        last_trigger_time: -1.0,
+        last_cycle: -1,
+        playback_positions: HashMap::new(),
+        gain: Signal::Value(1.0),
+        pan: Signal::Value(0.0),
+        speed: Signal::Value(1.0),
+        cut_group: Signal::Value(0.0),
+        n: Signal::Value(0.0),
+        note: Signal::Value(0.0),
+        attack: Signal::Value(0.0),
+        release: Signal::Value(0.0),
+        envelope_type: None,
+        unit_mode: Signal::Value(0.0),
+        loop_enabled: Signal::Value(0.0),
+        begin: Signal::Value(0.0),
+        end: Signal::Value(1.0),
+    };
+
+    Ok(ctx.graph.add_node(node))
+}
+
+/// Compile an `ur` arrangement: named fragments sequenced by a meta-pattern
+/// of their names, stretched over a number of cycles
+/// Usage: ur 8 "intro drop drop fill" [:intro "hh*4", :drop "bd*4", :fill "sn*8"]
+/// -> intro for 2 cycles, drop for 4, fill for 2, repeating every 8
+fn compile_ur(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    if args.len() < 3 {
+        return Err(
+            "ur requires 3 arguments: cycles \"meta pattern\" [:name pattern, ...]".to_string(),
+        );
+    }
+
+    let period = match &args[0] {
+        Expr::Number(n) if *n > 0.0 => *n,
+        _ => return Err("ur first argument must be a number of cycles above 0".to_string()),
+    };
+    let meta_str = match &args[1] {
+        Expr::String(s) => s.clone(),
+        _ => return Err("ur second argument must be a pattern of fragment names".to_string()),
+    };
+
+    // Fragments are named list items, each a string or an s call
+    let fragment_strs = match &args[2] {
+        Expr::List(exprs) => exprs
+            .iter()
+            .map(|expr| match expr {
+                Expr::Kwarg { name, value } => match value.as_ref() {
+                    Expr::String(s) => Ok((name.clone(), s.clone())),
+                    Expr::Call { name: call, args } if call == "s" && !args.is_empty() => {
+                        match &args[0] {
+                            Expr::String(s) => Ok((name.clone(), s.clone())),
+                            _ => Err("s() call in ur must have a string argument".to_string()),
+                        }
+                    }
+                    _ => Err(format!("ur fragment :{} must be a string or s call", name)),
+                },
+                _ => Err("ur fragments are named: [:intro \"bd*2\", :drop \"bd*4\"]".to_string()),
+            })
+            .collect::<Result<Vec<(String, String)>, String>>()?,
+        _ => return Err("ur third argument must be a list of fragments".to_string()),
+    };
 
-    // Parse patterns
-    let bool_pattern = parse_mini_notation(&bool_str);
-    let pat_true = parse_mini_notation(&pat_true_str);
-    let pat_false = parse_mini_notation(&pat_false_str);
+    // Every name the meta-pattern uses must be defined
+    use crate::pattern::{Fraction, State, TimeSpan};
+    let meta = parse_mini_notation(&meta_str);
+    let span = State {
+        span: TimeSpan::new(Fraction::from_float(0.0), Fraction::from_float(8.0)),
+        controls: HashMap::new(),
+    };
+    for hap in meta.query(&span) {
+        if !fragment_strs.iter().any(|(name, _)| *name == hap.value) {
+            return Err(format!("ur: no fragment named '{}'", hap.value));
+        }
+    }
 
-    // Combine using Pattern::stitch
-    let combined_pattern = Pattern::stitch(bool_pattern, pat_true, pat_false);
+    let fragments = fragment_strs
+        .iter()
+        .map(|(name, s)| (name.clone(), parse_mini_notation(s)))
+        .collect();
+    let combined_pattern = Pattern::ur(period, meta, fragments);
     let combined_str = format!(
-        "stitch \"{}\" \"{}\" \"{}\"",
-        bool_str, pat_true_str, pat_false_str
+        "ur {} \"{}\" [{}]",
+        period,
+        meta_str,
+        fragment_strs
+            .iter()
+            .map(|(name, s)| format!(":{} \"{}\"", name, s))
+            .collect::<Vec<_>>()
+            .join(", ")
     );
 
     // Create a Sample node with the combined pattern
@@ -3763,6 +5987,22 @@ fn is_note_pattern_string(s: &str) -> bool {
     false
 }
 
+/// A boolean keyword option such as `:raw true` (`1`/`0` also work);
+/// false when absent
+fn flag_keyword(func: &str, extractor: &ParamExtractor, name: &str) -> Result<bool, String> {
+    match extractor.get_optional_keyword(name) {
+        None => Ok(false),
+        Some(Expr::Var(v)) if v == "true" => Ok(true),
+        Some(Expr::Var(v)) if v == "false" => Ok(false),
+        Some(Expr::Number(n)) => Ok(n != 0.0),
+        Some(_) => Err(format!("{}: :{} must be true or false", func, name)),
+    }
+}
+
+/// Compile a free-running oscillator. Saw and square are band-limited
+/// unless `:raw true` asks for the naive, aliasing waveform. `:sync ~master`
+/// hard-syncs it to another signal and `:retrig "t ~ t t"` restarts its
+/// cycle on triggers.
 fn compile_oscillator(
     ctx: &mut CompilerContext,
     waveform: Waveform,
@@ -3848,6 +6088,45 @@ fn compile_oscillator(
         }
     };
 
+    let waveform = if flag_keyword("oscillator", &extractor, "raw")? {
+        waveform.raw()
+    } else {
+        waveform
+    };
+
+    let sync = extractor.get_optional_keyword("sync");
+    let retrig = extractor.get_optional_keyword("retrig");
+    if sync.is_some() || retrig.is_some() {
+        let freq = if semitone_offset >= 1000.0 {
+            Signal::Value(crate::pitch::midi_to_hz((semitone_offset - 1000.0) as f64) as f32)
+        } else if semitone_offset != 0.0 {
+            Signal::Node(ctx.graph.add_node(SignalNode::Multiply {
+                a: Signal::Node(freq_node),
+                b: Signal::Value(2.0_f32.powf(semitone_offset / 12.0)),
+            }))
+        } else {
+            Signal::Node(freq_node)
+        };
+        let sync = match sync {
+            Some(expr) => Some(Signal::Node(compile_expr(ctx, expr)?)),
+            None => None,
+        };
+        // A mini-notation string retriggers on its event onsets, like `trig`
+        let retrig = match retrig {
+            Some(Expr::String(s)) => Some(Signal::Node(compile_trig(ctx, vec![Expr::String(s)])?)),
+            Some(expr) => Some(Signal::Node(compile_expr(ctx, expr)?)),
+            None => None,
+        };
+
+        return Ok(ctx.graph.add_node(SignalNode::SyncOscillator {
+            freq,
+            waveform,
+            sync,
+            retrig,
+            state: Default::default(),
+        }));
+    }
+
     let node = SignalNode::Oscillator {
         freq: Signal::Node(freq_node),
         waveform,
@@ -5379,8 +7658,321 @@ fn compile_parametric_eq(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<N
     Ok(ctx.graph.add_node(node))
 }
 
-/// Compile reverb effect
+/// Reverb presets: (room_size, damping, mix, predelay seconds, modulation,
+/// diffusion, shimmer)
+const REVERB_PRESETS: &[(&str, [f64; 7])] = &[
+    ("room", [0.5, 0.5, 0.25, 0.005, 0.1, 0.6, 0.0]),
+    ("hall", [0.85, 0.4, 0.3, 0.025, 0.3, 0.75, 0.0]),
+    ("plate", [0.7, 0.2, 0.3, 0.0, 0.2, 0.9, 0.0]),
+    ("shimmer", [0.9, 0.3, 0.4, 0.03, 0.4, 0.8, 0.5]),
+];
+
+/// Compile reverb effect (Dattorro plate/hall reverb)
+/// Syntax: `<input> # reverb [room_size] [damping] [mix] [:preset hall]
+/// [:predelay 20ms] [:mod 0.3] [:diffusion 0.75] [:shimmer 0.5]`
+///
+/// The preset (hall by default) fills in whatever isn't given. `:shimmer`
+/// pitch-shifts the tail up an octave on each pass round the tank.
 fn compile_reverb(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let preset_name = match extractor.get_optional_keyword("preset") {
+        None => "hall".to_string(),
+        Some(Expr::String(name)) | Some(Expr::Var(name)) => name,
+        Some(_) => return Err("reverb: :preset must be a name (hall, plate, room or shimmer)".to_string()),
+    };
+    let [room, damping, mix, predelay, modulation, diffusion, shimmer] = REVERB_PRESETS
+        .iter()
+        .find(|(name, _)| *name == preset_name)
+        .map(|(_, values)| *values)
+        .ok_or_else(|| {
+            format!(
+                "reverb: unknown preset \"{}\" (use hall, plate, room or shimmer)",
+                preset_name
+            )
+        })?;
+
+    let room_node = compile_expr(ctx, extractor.get_optional(0, "room_size", room as f32))?;
+    let damp_node = compile_expr(ctx, extractor.get_optional(1, "damping", damping as f32))?;
+    let mix_node = compile_expr(ctx, extractor.get_optional(2, "mix", mix as f32))?;
+    let predelay_node = compile_expr(
+        ctx,
+        extractor
+            .get_optional_keyword("predelay")
+            .unwrap_or(Expr::Number(predelay)),
+    )?;
+    let mod_node = compile_expr(
+        ctx,
+        extractor.get_optional_keyword("mod").unwrap_or(Expr::Number(modulation)),
+    )?;
+    let diffusion_node = compile_expr(
+        ctx,
+        extractor
+            .get_optional_keyword("diffusion")
+            .unwrap_or(Expr::Number(diffusion)),
+    )?;
+    let shimmer = match extractor.get_optional_keyword("shimmer") {
+        None => shimmer,
+        Some(Expr::Number(amount)) => amount,
+        Some(_) => return Err("reverb: :shimmer must be a number from 0 to 1".to_string()),
+    };
+
+    // Room size 0-1 spans the decay range 0.1-10; pre-delay is in seconds
+    // so it takes ms/s units, while the node wants milliseconds
+    let scaled_room = ctx.graph.add_node(SignalNode::Multiply {
+        a: Signal::Node(room_node),
+        b: Signal::Value(9.9),
+    });
+    let decay_node = ctx.graph.add_node(SignalNode::Add {
+        a: Signal::Node(scaled_room),
+        b: Signal::Value(0.1),
+    });
+    let pre_delay_node = ctx.graph.add_node(SignalNode::Multiply {
+        a: Signal::Node(predelay_node),
+        b: Signal::Value(1000.0),
+    });
+
+    let node = SignalNode::DattorroReverb {
+        input: input_signal,
+        pre_delay: Signal::Node(pre_delay_node),
+        decay: Signal::Node(decay_node),
+        diffusion: Signal::Node(diffusion_node),
+        damping: Signal::Node(damp_node),
+        mod_depth: Signal::Node(mod_node),
+        mix: Signal::Node(mix_node),
+        state: DattorroState::new(ctx.sample_rate).with_shimmer(shimmer as f32, 12.0),
+    };
+
+    Ok(ctx.graph.add_node(node))
+}
+
+/// A compile-time constant kwarg of a composite effect
+fn constant_kwarg(
+    extractor: &ParamExtractor,
+    function: &str,
+    name: &str,
+    default: f64,
+) -> Result<f64, String> {
+    match extractor.get_optional_keyword(name) {
+        None => Ok(default),
+        Some(Expr::Number(value)) => Ok(value),
+        Some(_) => Err(format!("{}: :{} must be a number", function, name)),
+    }
+}
+
+/// Compile shimmer: a long modulated reverb whose tail is pitch-shifted on
+/// every pass round its feedback
+/// Syntax: `<input> # shimmer [mix] [:interval 12st] [:decay 0.85] [:amount 0.6]`
+///
+/// `:interval` is a pitch ratio (`12st` = 2, an octave; `7st` a fifth).
+fn compile_shimmer(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let interval = constant_kwarg(&extractor, "shimmer", "interval", 2.0)?;
+    if interval <= 0.0 {
+        return Err(format!("shimmer: :interval must be a pitch ratio above 0, got {}", interval));
+    }
+    let amount = constant_kwarg(&extractor, "shimmer", "amount", 0.6)?;
+    let mix_node = compile_expr(ctx, extractor.get_optional(0, "mix", 0.3))?;
+    let decay_node = compile_expr(ctx, extractor.get_optional(1, "decay", 0.85))?;
+    let decay = ctx.graph.add_node(SignalNode::Multiply {
+        a: Signal::Node(decay_node),
+        b: Signal::Value(10.0),
+    });
+
+    let node = SignalNode::DattorroReverb {
+        input: input_signal,
+        pre_delay: Signal::Value(30.0),
+        decay: Signal::Node(decay),
+        diffusion: Signal::Value(0.8),
+        damping: Signal::Value(0.3),
+        mod_depth: Signal::Value(0.5),
+        mix: Signal::Node(mix_node),
+        state: DattorroState::new(ctx.sample_rate)
+            .with_shimmer(amount as f32, 12.0 * interval.log2() as f32),
+    };
+    Ok(ctx.graph.add_node(node))
+}
+
+/// Compile reverse reverb: the reverb tail of each `time`-long stretch
+/// plays backwards during the next, swelling up instead of dying away
+/// Syntax: `<input> # revreverb [mix] [:time 2s] [:decay 0.8]`
+fn compile_revreverb(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    use crate::unified_graph::ReverseState;
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let time = constant_kwarg(&extractor, "revreverb", "time", 1.0)?;
+    if !(0.05..=10.0).contains(&time) {
+        return Err(format!("revreverb: :time must be 50ms to 10s, got {}s", time));
+    }
+    let mix_node = compile_expr(ctx, extractor.get_optional(0, "mix", 0.5))?;
+    let decay_node = compile_expr(ctx, extractor.get_optional(1, "decay", 0.8))?;
+    let decay = ctx.graph.add_node(SignalNode::Multiply {
+        a: Signal::Node(decay_node),
+        b: Signal::Value(10.0),
+    });
+
+    // Fully wet reverb minus the dry input leaves the tail alone
+    let verb = ctx.graph.add_node(SignalNode::DattorroReverb {
+        input: input_signal.clone(),
+        pre_delay: Signal::Value(0.0),
+        decay: Signal::Node(decay),
+        diffusion: Signal::Value(0.8),
+        damping: Signal::Value(0.4),
+        mod_depth: Signal::Value(0.3),
+        mix: Signal::Value(1.0),
+        state: DattorroState::new(ctx.sample_rate),
+    });
+    let inverted = ctx.graph.add_node(SignalNode::Multiply {
+        a: input_signal.clone(),
+        b: Signal::Value(-1.0),
+    });
+    let tail = ctx.graph.add_node(SignalNode::Add {
+        a: Signal::Node(verb),
+        b: Signal::Node(inverted),
+    });
+    let reversed = ctx.graph.add_node(SignalNode::Reverse {
+        input: Signal::Node(tail),
+        state: ReverseState::new(ctx.sample_rate, time as f32),
+    });
+    let wet = ctx.graph.add_node(SignalNode::Multiply {
+        a: Signal::Node(reversed),
+        b: Signal::Node(mix_node),
+    });
+    Ok(ctx.graph.add_node(SignalNode::Add {
+        a: input_signal,
+        b: Signal::Node(wet),
+    }))
+}
+
+/// Compile tapestop: on each true event of a boolean pattern, the input
+/// slows to a halt over the event, like a turntable losing power
+/// Syntax: `<input> # tapestop "~ ~ ~ t"`
+fn compile_tapestop(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    match params.as_slice() {
+        [Expr::String(pattern_str)] => Ok(compile_varispeed(
+            ctx,
+            input_signal,
+            pattern_str,
+            VarispeedMode::TapeStop,
+        )),
+        _ => Err("tapestop requires a pattern string, e.g. tapestop \"~ ~ ~ t\"".to_string()),
+    }
+}
+
+/// Compile stutter (the effect): on each true event of a boolean pattern,
+/// loop the first `1/n` of a cycle of the input for as long as the event
+/// Syntax: `<input> # stutter 16 "0 0 1 0"`
+///
+/// `stutter n` with one argument is the pattern transform instead.
+fn compile_stutter(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    match params.as_slice() {
+        [Expr::Number(division), Expr::String(pattern_str)] if *division >= 1.0 => {
+            Ok(compile_varispeed(
+                ctx,
+                input_signal,
+                pattern_str,
+                VarispeedMode::Stutter {
+                    division: *division,
+                },
+            ))
+        }
+        [Expr::Number(division), Expr::String(_)] => Err(format!(
+            "stutter: division must be at least 1, got {}",
+            division
+        )),
+        _ => Err(
+            "stutter requires a division and a pattern string, e.g. stutter 16 \"0 0 1 0\""
+                .to_string(),
+        ),
+    }
+}
+
+fn compile_varispeed(
+    ctx: &mut CompilerContext,
+    input: Signal,
+    pattern_str: &str,
+    mode: VarispeedMode,
+) -> NodeId {
+    let pattern = parse_mini_notation(pattern_str).fmap(|s: String| {
+        if s == "t" || s == "x" || s == "1" {
+            1.0
+        } else {
+            0.0
+        }
+    });
+    add_varispeed(ctx, input, pattern_str, pattern, mode)
+}
+
+fn add_varispeed(
+    ctx: &mut CompilerContext,
+    input: Signal,
+    pattern_str: &str,
+    pattern: Pattern<f64>,
+    mode: VarispeedMode,
+) -> NodeId {
+    use crate::unified_graph::VarispeedState;
+
+    ctx.graph.add_node(SignalNode::Varispeed {
+        input,
+        pattern_str: pattern_str.to_string(),
+        pattern,
+        mode,
+        state: VarispeedState::new(ctx.sample_rate),
+    })
+}
+
+/// Compile beatrepeat: each event of the pattern repeats the `1/n` of a
+/// cycle just before it, `n` being the event's value, until the event ends
+/// Syntax: `<input> # beatrepeat "~ ~ 16 [32 8]" [:decay 0.8] [:pitch 12st]`
+///
+/// `:decay` scales each repeat after the first; `:pitch` is a playback
+/// rate ratio for the repeats.
+fn compile_beatrepeat(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let pattern_str = match extractor.get_required(0, "pattern")? {
+        Expr::String(s) => s,
+        _ => {
+            return Err(
+                "beatrepeat requires a pattern string of slice sizes, e.g. beatrepeat \"~ 16\""
+                    .to_string(),
+            )
+        }
+    };
+    let decay = constant_kwarg(&extractor, "beatrepeat", "decay", 1.0)?;
+    let pitch = constant_kwarg(&extractor, "beatrepeat", "pitch", 1.0)?;
+    if !(0.0..=1.0).contains(&decay) {
+        return Err(format!("beatrepeat: :decay must be 0 to 1, got {}", decay));
+    }
+    if !(0.25..=4.0).contains(&pitch) {
+        return Err(format!("beatrepeat: :pitch must be a ratio from 0.25 to 4, got {}", pitch));
+    }
+
+    // Values are slice sizes: 16 repeats a sixteenth of a cycle
+    let pattern =
+        parse_mini_notation(&pattern_str).fmap(|s: String| s.parse::<f64>().unwrap_or(0.0));
+    Ok(add_varispeed(
+        ctx,
+        input_signal,
+        &pattern_str,
+        pattern,
+        VarispeedMode::BeatRepeat {
+            decay: decay as f32,
+            pitch,
+        },
+    ))
+}
+
+/// Compile Freeverb-style reverb (the Schroeder reverb `reverb` used to be)
+fn compile_freeverb(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
     // Extract input (handles both standalone and chained forms)
     let (input_signal, params) = extract_chain_input(ctx, &args)?;
 
@@ -5405,7 +7997,7 @@ fn compile_reverb(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId,
         room_size: Signal::Node(room_node),
         damping: Signal::Node(damp_node),
         mix: Signal::Node(mix_node),
-        state: ReverbState::default(),
+        state: ReverbState::new(ctx.sample_rate),
     };
 
     Ok(ctx.graph.add_node(node))
@@ -5475,6 +8067,18 @@ fn compile_distortion(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<Node
     let mix_expr = extractor.get_optional(1, "mix", 0.5);
     let mix_node = compile_expr(ctx, mix_expr)?;
 
+    let factor = oversample_factor("distortion", &extractor)?;
+    if factor > 1 {
+        return Ok(ctx.graph.add_node(SignalNode::Waveshaper {
+            input: input_signal,
+            shape: Waveshape::Tanh,
+            a: Signal::Node(drive_node),
+            b: Signal::Value(0.0),
+            mix: Signal::Node(mix_node),
+            oversampler: Oversampler::new(factor),
+        }));
+    }
+
     let node = SignalNode::Distortion {
         input: input_signal,
         drive: Signal::Node(drive_node),
@@ -5484,6 +8088,118 @@ fn compile_distortion(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<Node
     Ok(ctx.graph.add_node(node))
 }
 
+/// The `:oversample` factor of a waveshaping effect: 1 (the default), 2 or 4
+fn oversample_factor(name: &str, extractor: &ParamExtractor) -> Result<usize, String> {
+    match extractor.get_optional_keyword("oversample") {
+        None => Ok(1),
+        Some(Expr::Number(n)) if n == 1.0 || n == 2.0 || n == 4.0 => Ok(n as usize),
+        Some(_) => Err(format!("{}: :oversample must be 1, 2 or 4", name)),
+    }
+}
+
+/// Compile wave folder
+/// Syntax: `<input> # fold [min] [max] [:oversample 4]`, reflecting the
+/// signal back into min..max (default -1..1)
+/// On a sample pattern, `s "westcoast*4" # fold "0.2 0.8"` folds each voice
+/// by an amount from 0 to 1 instead (see [`crate::westcoast::fold`]).
+fn compile_fold(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    if let Some(node) = compile_voice_fx(ctx, "fold", &args)? {
+        return Ok(node);
+    }
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let min_node = compile_expr(ctx, extractor.get_optional(0, "min", -1.0))?;
+    let max_node = compile_expr(ctx, extractor.get_optional(1, "max", 1.0))?;
+    let factor = oversample_factor("fold", &extractor)?;
+
+    Ok(ctx.graph.add_node(SignalNode::Waveshaper {
+        input: input_signal,
+        shape: Waveshape::Fold,
+        a: Signal::Node(min_node),
+        b: Signal::Node(max_node),
+        mix: Signal::Value(1.0),
+        oversampler: Oversampler::new(factor),
+    }))
+}
+
+/// Compile soft clipper
+/// Syntax: `<input> # clip [threshold] [:oversample 4]`, giving
+/// `tanh(x / threshold) * threshold` (threshold defaults to 1)
+fn compile_clip(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let threshold_node = compile_expr(ctx, extractor.get_optional(0, "threshold", 1.0))?;
+    let factor = oversample_factor("clip", &extractor)?;
+
+    Ok(ctx.graph.add_node(SignalNode::Waveshaper {
+        input: input_signal,
+        shape: Waveshape::Clip,
+        a: Signal::Node(threshold_node),
+        b: Signal::Value(0.0),
+        mix: Signal::Value(1.0),
+        oversampler: Oversampler::new(factor),
+    }))
+}
+
+/// Compile saturation effect
+/// Syntax: `<input> # saturate <drive> [:mix 1.0] [:curve "tape"|"transformer"]`
+fn compile_saturate(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    use crate::unified_graph::{SaturationCurve, SaturatorState};
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let drive_node = compile_expr(ctx, extractor.get_optional(0, "drive", 2.0))?;
+    let mix_node = compile_expr(ctx, extractor.get_optional(1, "mix", 1.0))?;
+    let curve = match extractor.get_optional_keyword("curve") {
+        None => SaturationCurve::Tape,
+        Some(Expr::String(name)) => SaturationCurve::from_name(&name).ok_or_else(|| {
+            format!(
+                "saturate: unknown curve \"{}\" (use \"tape\" or \"transformer\")",
+                name
+            )
+        })?,
+        Some(_) => return Err("saturate: :curve must be \"tape\" or \"transformer\"".to_string()),
+    };
+
+    let node = SignalNode::Saturate {
+        input: input_signal,
+        drive: Signal::Node(drive_node),
+        mix: Signal::Node(mix_node),
+        curve,
+        state: SaturatorState::default(),
+    };
+
+    Ok(ctx.graph.add_node(node))
+}
+
+/// Compile tilt EQ
+/// Syntax: `<input> # tilt <dB> [:pivot 800] [:air 0]`, where a positive
+/// tilt brightens and a negative one darkens
+fn compile_tilt(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    use crate::unified_graph::TiltEqState;
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let tilt_node = compile_expr(ctx, extractor.get_optional(0, "tilt", 0.0))?;
+    let pivot_node = compile_expr(ctx, extractor.get_optional(1, "pivot", 800.0))?;
+    let air_node = compile_expr(ctx, extractor.get_optional(2, "air", 0.0))?;
+
+    let node = SignalNode::TiltEq {
+        input: input_signal,
+        tilt: Signal::Node(tilt_node),
+        pivot: Signal::Node(pivot_node),
+        air: Signal::Node(air_node),
+        state: TiltEqState::default(),
+    };
+
+    Ok(ctx.graph.add_node(node))
+}
+
 /// Compile range function - maps signal from -1..1 to min..max
 /// Usage: range min max signal
 /// Formula: output = min + (signal + 1) * 0.5 * (max - min)
@@ -5498,6 +8214,14 @@ fn compile_range(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, S
     // Compile min, max, and signal
     let min_node = compile_expr(ctx, args[0].clone())?;
     let max_node = compile_expr(ctx, args[1].clone())?;
+
+    // `range min max rand`: a fresh random value per trigger, like `min ~ max`
+    if matches!(&args[2], Expr::Var(name) if name == "rand") {
+        return Ok(ctx.graph.add_node(SignalNode::RandSpread {
+            min: Signal::Node(min_node),
+            max: Signal::Node(max_node),
+        }));
+    }
     let signal_node = compile_expr(ctx, args[2].clone())?;
 
     // Create the range scaling expression:
@@ -5621,17 +8345,30 @@ fn compile_min(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, Str
 /// Example: wrap (sine 5.0) 0.0 1.0  (wrap sine between 0 and 1)
 /// Example: wrap ~lfo -1.0 1.0  (wrap LFO into bipolar range)
 fn compile_wrap(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
-    if args.len() != 3 {
+    let extractor = ParamExtractor::new(args);
+    if extractor.positional_count() != 3 {
         return Err(format!(
             "wrap requires exactly 3 arguments (input, min, max), got {}",
-            args.len()
+            extractor.positional_count()
         ));
     }
 
     // Compile all three input signals
-    let input_node = compile_expr(ctx, args[0].clone())?;
-    let min_node = compile_expr(ctx, args[1].clone())?;
-    let max_node = compile_expr(ctx, args[2].clone())?;
+    let input_node = compile_expr(ctx, extractor.get_required(0, "input")?)?;
+    let min_node = compile_expr(ctx, extractor.get_required(1, "min")?)?;
+    let max_node = compile_expr(ctx, extractor.get_required(2, "max")?)?;
+
+    let factor = oversample_factor("wrap", &extractor)?;
+    if factor > 1 {
+        return Ok(ctx.graph.add_node(SignalNode::Waveshaper {
+            input: Signal::Node(input_node),
+            shape: Waveshape::Wrap,
+            a: Signal::Node(min_node),
+            b: Signal::Node(max_node),
+            mix: Signal::Value(1.0),
+            oversampler: Oversampler::new(factor),
+        }));
+    }
 
     // Create Wrap node
     let output = ctx.graph.add_node(SignalNode::Wrap {
@@ -5644,23 +8381,26 @@ fn compile_wrap(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, St
 }
 
 /// Compile sample-and-hold node
-/// Usage: sample_hold(input, trigger)
-/// Captures input when trigger crosses from negative/zero to positive
+/// Usage: sample_hold(input, trigger) or input # sah trigger
+/// Captures input when trigger crosses from negative/zero to positive.
+/// A mini-notation trigger fires on each event onset: `noise # sah "t*8"`
 fn compile_sample_hold(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
-    if args.len() != 2 {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    if params.len() != 1 {
         return Err(format!(
             "sample_hold requires exactly 2 arguments (input, trigger), got {}",
-            args.len()
+            params.len() + 1
         ));
     }
 
-    // Compile input and trigger signals
-    let input_node = compile_expr(ctx, args[0].clone())?;
-    let trigger_node = compile_expr(ctx, args[1].clone())?;
+    let trigger_node = match &params[0] {
+        Expr::String(_) => compile_trig(ctx, vec![params[0].clone()])?,
+        trigger => compile_expr(ctx, trigger.clone())?,
+    };
 
     // Create SampleAndHold node
     let output = ctx.graph.add_node(SignalNode::SampleAndHold {
-        input: Signal::Node(input_node),
+        input: input_signal,
         trigger: Signal::Node(trigger_node),
         held_value: std::cell::RefCell::new(0.0),
         last_trigger: std::cell::RefCell::new(0.0),
@@ -5920,58 +8660,60 @@ fn compile_multitap(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId
 }
 
 /// Compile ping-pong delay (stereo bouncing)
+/// Syntax: `<input> # pingpong <time> <feedback> [width] [channel] [mix] [:filter hz]`
+///
+/// Echoes start on the left and cross to the other side on every repeat.
+/// Each node is one side (`channel` 0 = left, 1 = right), so a stereo
+/// ping-pong is two of them, on `out1` and `out2`. A time in cycles
+/// (`3/16c`) follows the tempo. `:filter` lowpasses the cross-feedback.
 fn compile_pingpong(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
     let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
 
-    if params.len() < 2 {
-        return Err(format!(
-            "pingpong requires at least 2 parameters (time, feedback), got {}",
-            params.len()
-        ));
-    }
-
-    // Required parameters
-    let time_node = compile_expr(ctx, params[0].clone())?;
-    let feedback_node = compile_expr(ctx, params[1].clone())?;
-
-    // Optional parameters with defaults
-    let stereo_width_node = if params.len() > 2 {
-        compile_expr(ctx, params[2].clone())?
-    } else {
-        ctx.graph.add_node(SignalNode::Constant { value: 0.8 }) // Default: strong ping-pong
+    let (time, synced) = match extractor.get_required(0, "time")? {
+        Expr::Call { name, args } if name == "cycles" => {
+            let period = args.first().map(extract_number).transpose()?.unwrap_or(0.0);
+            if period <= 0.0 {
+                return Err("pingpong: the time in cycles must be above 0".to_string());
+            }
+            (Signal::Value(period as f32), true)
+        }
+        expr => (Signal::Node(compile_expr(ctx, expr)?), false),
     };
+    let feedback_node = compile_expr(ctx, extractor.get_required(1, "feedback")?)?;
+    // Default: strong ping-pong
+    let stereo_width_node = compile_expr(ctx, extractor.get_optional(2, "width", 0.8))?;
 
-    let channel = if params.len() > 3 {
-        if let Expr::Number(n) = params[3].clone() {
-            n != 0.0
-        } else {
+    let channel = match extractor.get_optional(3, "channel", 0.0) {
+        Expr::Number(n) => n != 0.0,
+        _ => {
             return Err(
                 "pingpong 'channel' parameter must be a constant (0=left, 1=right)".to_string(),
-            );
+            )
         }
-    } else {
-        false // Default: start on left
     };
 
-    let mix_node = if params.len() > 4 {
-        compile_expr(ctx, params[4].clone())?
-    } else {
-        ctx.graph.add_node(SignalNode::Constant { value: 0.7 }) // Default: 70% wet
-    };
+    // Default: 70% wet
+    let mix_node = compile_expr(ctx, extractor.get_optional(4, "mix", 0.7))?;
+    // Default: open
+    let filter_node = compile_expr(ctx, extractor.get_optional(5, "filter", 20000.0))?;
 
-    // Create delay buffers (1 second max each)
-    let buffer_size = ctx.sample_rate as usize;
+    // Create delay buffers (2 seconds max each, room for slow tempo-synced times)
+    let buffer_size = ctx.sample_rate as usize * 2;
 
     let node = SignalNode::PingPongDelay {
         input: input_signal,
-        time: Signal::Node(time_node),
+        time,
+        synced,
         feedback: Signal::Node(feedback_node),
         stereo_width: Signal::Node(stereo_width_node),
+        filter: Signal::Node(filter_node),
         channel,
         mix: Signal::Node(mix_node),
         buffer_l: vec![0.0; buffer_size],
         buffer_r: vec![0.0; buffer_size],
         write_idx: 0,
+        lpf_state: (0.0, 0.0),
     };
 
     Ok(ctx.graph.add_node(node))
@@ -6147,7 +8889,7 @@ fn compile_chorus(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId,
         rate: Signal::Node(rate_node),
         depth: Signal::Node(depth_node),
         mix: Signal::Node(mix_node),
-        state: ChorusState::default(),
+        state: ChorusState::new(ctx.sample_rate),
     };
 
     Ok(ctx.graph.add_node(node))
@@ -6177,7 +8919,7 @@ fn compile_flanger(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId,
         depth: Signal::Node(depth_node),
         rate: Signal::Node(rate_node),
         feedback: Signal::Node(feedback_node),
-        state: FlangerState::default(),
+        state: FlangerState::new(ctx.sample_rate),
     };
 
     Ok(ctx.graph.add_node(node))
@@ -6264,12 +9006,33 @@ fn compile_transient_shaper(ctx: &mut CompilerContext, args: Vec<Expr>) -> Resul
         input: input_signal,
         attack_db: Signal::Node(attack_node),
         sustain_db: Signal::Node(sustain_node),
+        linear: false,
         state: TransientShaperState::default(),
     };
 
     Ok(ctx.graph.add_node(node))
 }
 
+/// Compile the drum-bus transient shaper
+/// Syntax: `<input> # transient :attack +6dB :sustain -3dB`. Attack and
+/// sustain are gains (1 leaves that part alone), so dB literals read as
+/// written; both default to 1
+fn compile_transient(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let attack_node = compile_expr(ctx, extractor.get_optional(0, "attack", 1.0))?;
+    let sustain_node = compile_expr(ctx, extractor.get_optional(1, "sustain", 1.0))?;
+
+    Ok(ctx.graph.add_node(SignalNode::TransientShaper {
+        input: input_signal,
+        attack_db: Signal::Node(attack_node),
+        sustain_db: Signal::Node(sustain_node),
+        linear: true,
+        state: crate::unified_graph::TransientShaperState::default(),
+    }))
+}
+
 /// Compile sidechain compressor effect
 fn compile_sidechain_compressor(
     ctx: &mut CompilerContext,
@@ -6341,22 +9104,40 @@ fn compile_expander(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId
 fn compile_bitcrush(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
     // Extract input (handles both standalone and chained forms)
     let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
 
-    if params.len() != 2 {
+    if extractor.positional_count() != 2 {
         return Err(format!(
             "bitcrush requires 2 parameters (bits, sample_rate), got {}",
-            params.len()
+            extractor.positional_count()
         ));
     }
 
-    let bits_node = compile_expr(ctx, params[0].clone())?;
-    let sr_node = compile_expr(ctx, params[1].clone())?;
+    let bits_node = compile_expr(ctx, extractor.get_required(0, "bits")?)?;
+    let sr_node = compile_expr(ctx, extractor.get_required(1, "sample_rate")?)?;
+
+    // Oversampling only applies to the quantizer: the rate reduction's
+    // aliasing is the point of the effect
+    let factor = oversample_factor("bitcrush", &extractor)?;
+    let (input_signal, bits) = if factor > 1 {
+        let quantized = ctx.graph.add_node(SignalNode::Waveshaper {
+            input: input_signal,
+            shape: Waveshape::Quantize,
+            a: Signal::Node(bits_node),
+            b: Signal::Value(0.0),
+            mix: Signal::Value(1.0),
+            oversampler: Oversampler::new(factor),
+        });
+        (Signal::Node(quantized), Signal::Value(16.0))
+    } else {
+        (input_signal, Signal::Node(bits_node))
+    };
 
     use crate::unified_graph::BitCrushState;
 
     let node = SignalNode::BitCrush {
         input: input_signal,
-        bits: Signal::Node(bits_node),
+        bits,
         sample_rate: Signal::Node(sr_node),
         state: BitCrushState::default(),
     };
@@ -6367,7 +9148,12 @@ fn compile_bitcrush(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId
 /// Compile coarse effect (sample rate reduction)
 /// coarse n - reduces sample rate to 1/n (TidalCycles equivalent)
 /// Implemented as bitcrush with bits=16 (no bit reduction, just sample rate reduction)
+/// On a sample pattern, `s "bd*4" # coarse "8 16"` runs per voice instead.
 fn compile_coarse(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    if let Some(node) = compile_voice_fx(ctx, "coarse", &args)? {
+        return Ok(node);
+    }
+
     // Extract input (handles both standalone and chained forms)
     let (input_signal, params) = extract_chain_input(ctx, &args)?;
 
@@ -6394,6 +9180,169 @@ fn compile_coarse(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId,
     Ok(ctx.graph.add_node(node))
 }
 
+/// Compile crush effect (bit depth reduction, SuperDirt's `crush`)
+/// crush bits - 1 is the harshest, 16 is nearly clean. Per voice on sample
+/// patterns, otherwise a bitcrush over the whole signal.
+fn compile_crush(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    if let Some(node) = compile_voice_fx(ctx, "crush", &args)? {
+        return Ok(node);
+    }
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+
+    if params.len() != 1 {
+        return Err(format!(
+            "crush requires 1 parameter (bits), got {}",
+            params.len()
+        ));
+    }
+
+    let bits_node = compile_expr(ctx, params[0].clone())?;
+
+    use crate::unified_graph::BitCrushState;
+
+    let node = SignalNode::BitCrush {
+        input: input_signal,
+        bits: Signal::Node(bits_node),
+        sample_rate: Signal::Value(1.0), // No sample rate reduction
+        state: BitCrushState::default(),
+    };
+
+    Ok(ctx.graph.add_node(node))
+}
+
+/// Compile a per-voice SuperDirt effect (`shape`, `crush`, `coarse`, `squiz`,
+/// `fold`) or voice setting (`orbit`, `strike`, `decay`, `damping`) chained
+/// onto a sample pattern: `s "bd*4" # crush "4 8"`
+///
+/// Each event carries the value at its onset in its context, and the voice
+/// it triggers applies the effect. Returns None unless the input is a Sample
+/// node and the value a number or pattern.
+fn compile_voice_fx(
+    ctx: &mut CompilerContext,
+    name: &str,
+    args: &[Expr],
+) -> Result<Option<NodeId>, String> {
+    let [Expr::ChainInput(input_id), value] = args else {
+        return Ok(None);
+    };
+    let Some((values, _)) = try_extract_numeric_pattern(value) else {
+        return Ok(None);
+    };
+    Ok(tag_sample_events(ctx, *input_id, name, values))
+}
+
+/// Copy the Sample node `input_id` with each event's context tagged with
+/// `key`, valued from `values` at its onset. None if it isn't a Sample node
+fn tag_sample_events<T>(
+    ctx: &mut CompilerContext,
+    input_id: NodeId,
+    key: &str,
+    values: Pattern<T>,
+) -> Option<NodeId>
+where
+    T: Clone + std::fmt::Display + Send + Sync + 'static,
+{
+    let mut node = match ctx.graph.get_node(input_id) {
+        Some(node @ SignalNode::Sample { .. }) => node.clone(),
+        _ => return None,
+    };
+
+    if let SignalNode::Sample {
+        pattern,
+        last_trigger_time,
+        last_cycle,
+        playback_positions,
+        ..
+    } = &mut node
+    {
+        *pattern = with_onset_context(pattern.clone(), key, values);
+        *last_trigger_time = -1.0;
+        *last_cycle = -1;
+        playback_positions.clear();
+    }
+
+    Some(ctx.graph.add_node(node))
+}
+
+/// Compile `exciter`: `s "pluck*4" # exciter "<noise pick bow>"` picks what
+/// sets each pluck note's string going
+fn compile_exciter(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    use crate::pattern::{Fraction, State, TimeSpan};
+    use crate::pluck::Exciter;
+
+    let usage = || {
+        format!(
+            "exciter picks how a pluck note starts, one of {}: \
+             s \"pluck*4\" # exciter \"<noise pick bow>\"",
+            Exciter::NAMES.join(", ")
+        )
+    };
+    let [Expr::ChainInput(input_id), Expr::String(names)] = args.as_slice() else {
+        return Err(usage());
+    };
+
+    // A misspelt exciter is an error rather than a quiet fallback to noise
+    let exciters = parse_mini_notation(names);
+    let span = State {
+        span: TimeSpan::new(Fraction::from_float(0.0), Fraction::from_float(8.0)),
+        controls: HashMap::new(),
+    };
+    if let Some(hap) = exciters
+        .query(&span)
+        .into_iter()
+        .find(|hap| Exciter::from_name(&hap.value).is_none())
+    {
+        return Err(format!("no exciter named '{}'. {}", hap.value, usage()));
+    }
+
+    tag_sample_events(ctx, *input_id, "exciter", exciters).ok_or_else(usage)
+}
+
+/// Compile `orbit`: `s "hh*8" # orbit 1` sends the pattern's voices to orbit 1,
+/// and `orbit 1` plays them, so they can take their own effect chain:
+/// `out $ ~drums + (orbit 1 # reverb 0.6 0.8)`
+///
+/// The sent voices leave their pattern's own output, which must still be
+/// played for them to trigger. Orbit 0 is that output.
+fn compile_orbit(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    if let Some(node) = compile_voice_fx(ctx, "orbit", &args)? {
+        return Ok(node);
+    }
+    match args.as_slice() {
+        [Expr::Number(n)] if *n >= 1.0 && n.fract() == 0.0 => {
+            Ok(ctx.graph.add_orbit_node(*n as usize))
+        }
+        _ => Err("orbit takes an orbit number from 1: `orbit 1` plays it, \
+             s \"hh*8\" # orbit 1 sends to it"
+            .to_string()),
+    }
+}
+
+/// Tag every event of `pattern` with the value of `values` at its onset
+fn with_onset_context<T>(pattern: Pattern<String>, key: &str, values: Pattern<T>) -> Pattern<String>
+where
+    T: Clone + std::fmt::Display + Send + Sync + 'static,
+{
+    use crate::pattern::{Fraction, State, TimeSpan};
+
+    let key = key.to_string();
+    Pattern::new(move |state: &State| {
+        let mut haps = pattern.query(state);
+        for hap in &mut haps {
+            let onset = hap.whole.map_or(hap.part.begin, |whole| whole.begin);
+            let probe = State {
+                span: TimeSpan::new(onset, onset + Fraction::new(1, 1_000_000)),
+                controls: state.controls.clone(),
+            };
+            if let Some(value) = values.query(&probe).first() {
+                hap.context.insert(key.clone(), value.value.to_string());
+            }
+        }
+        haps
+    })
+}
+
 /// Compile djf (DJ filter) effect
 /// djf value - DJ filter sweep: 0-0.5 = lowpass, 0.5-1 = highpass
 /// Maps 0-1 parameter to filter type and cutoff frequency
@@ -6578,6 +9527,44 @@ fn compile_xfade(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, S
     Ok(ctx.graph.add_node(node))
 }
 
+/// Compile a morph between two versions of a signal
+/// Syntax: morph ~a ~b ~mix
+/// Crossfades from a (mix 0) to b (mix 1), like xfade. Both sides are
+/// versions of the bus being defined: written inline, the previous
+/// definition keeps its state across the re-evaluation instead of
+/// restarting, and the new one keeps its state once the morph is dropped.
+fn compile_morph(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let [from, to, mix] = <[Expr; 3]>::try_from(args).map_err(|args| {
+        format!(
+            "morph requires 3 parameters (from, to, mix), got {}",
+            args.len()
+        )
+    })?;
+
+    // `morph (sine 220) ...` continues `~bus $ sine 220`
+    let hash = |mut expr: &Expr| {
+        while let Expr::Paren(inner) = expr {
+            expr = &**inner;
+        }
+        definition_hash(expr)
+    };
+    let hashes = [hash(&from), hash(&to)];
+    let from_node = compile_expr(ctx, from)?;
+    let to_node = compile_expr(ctx, to)?;
+    if let Some(bus) = ctx.current_bus.clone() {
+        for (hash, node) in hashes.into_iter().zip([from_node, to_node]) {
+            ctx.graph.continue_bus(bus.clone(), hash, node);
+        }
+    }
+    let mix_node = compile_expr(ctx, mix)?;
+
+    Ok(ctx.graph.add_node(SignalNode::XFade {
+        signal_a: Signal::Node(from_node),
+        signal_b: Signal::Node(to_node),
+        position: Signal::Node(mix_node),
+    }))
+}
+
 /// Compile Mix (sum multiple signals)
 /// Syntax: mix signal1 signal2 signal3 ...
 /// Sums all input signals together
@@ -6887,9 +9874,6 @@ fn compile_rhpf(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, St
 /// Usage: signal # tap "filename.wav" duration
 /// Records signal to WAV file while passing it through unchanged
 fn compile_tap(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
-    use crate::unified_graph::TapState;
-    use std::sync::{Arc, Mutex};
-
     // Extract input (handles both standalone and chained forms)
     let (input_signal, params) = extract_chain_input(ctx, &args)?;
 
@@ -7517,6 +10501,39 @@ fn compile_timer(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, S
     Ok(ctx.graph.add_node(node))
 }
 
+/// Compile clock divider: every Nth pulse of a gate or trigger signal
+/// Syntax: `~clock # clockdiv 4`
+fn compile_clockdiv(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    use crate::clock_divider::ClockDividerState;
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+    let division_node = compile_expr(ctx, extractor.get_required(0, "division")?)?;
+
+    Ok(ctx.graph.add_node(SignalNode::ClockDivider {
+        input: input_signal,
+        division: Signal::Node(division_node),
+        state: ClockDividerState::default(),
+    }))
+}
+
+/// Compile clock multiplier: N pulses per pulse of a gate or trigger signal,
+/// spread over the time between its pulses
+/// Syntax: `~clock # clockmult 3`
+fn compile_clockmult(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    use crate::clock_divider::ClockMultiplierState;
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+    let factor_node = compile_expr(ctx, extractor.get_required(0, "factor")?)?;
+
+    Ok(ctx.graph.add_node(SignalNode::ClockMultiplier {
+        input: input_signal,
+        factor: Signal::Node(factor_node),
+        state: ClockMultiplierState::default(),
+    }))
+}
+
 fn compile_peak_follower(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
     // Extract input (handles both standalone and chained forms)
     let (input_signal, params) = extract_chain_input(ctx, &args)?;
@@ -7587,6 +10604,9 @@ fn compile_chain(ctx: &mut CompilerContext, left: Expr, right: Expr) -> Result<N
     // The chain operator passes left as input to right
     // We need to handle this based on what 'right' is
     match right {
+        Expr::Call { name, args } if is_placement(&name) => {
+            compile_placement(ctx, left, name, args)
+        }
         Expr::Call { name, mut args } => {
             // Prepend left as first argument using proper ChainInput marker
             let left_node = compile_or_extract_node(ctx, left)?;
@@ -7711,6 +10731,156 @@ fn compile_chain(ctx: &mut CompilerContext, left: Expr, right: Expr) -> Result<N
     }
 }
 
+/// Whether a chained call places its source for `spatial:`
+fn is_placement(name: &str) -> bool {
+    name == "azimuth" || name == "elevation"
+}
+
+/// Compile `source # azimuth deg # elevation deg`
+///
+/// In a `spatial:` pass this is the source weighted by the channel's gain.
+/// Anywhere else (the plain `out`, or a program without a layout) the source
+/// passes through. Adjacent azimuth/elevation calls make one placement, the
+/// last of each winning; elevation defaults to 0.
+fn compile_placement(
+    ctx: &mut CompilerContext,
+    left: Expr,
+    name: String,
+    args: Vec<Expr>,
+) -> Result<NodeId, String> {
+    let (mut azimuth, mut elevation) = (None, None);
+    let mut source = left;
+    let mut placement = Some((name, args));
+    while let Some((name, mut args)) = placement.take() {
+        if args.len() != 1 {
+            return Err(format!("{} takes one argument, the angle in degrees", name));
+        }
+        let slot = if name == "azimuth" {
+            &mut azimuth
+        } else {
+            &mut elevation
+        };
+        if slot.is_none() {
+            *slot = args.pop();
+        }
+        source = match source {
+            Expr::Chain(inner, right) => match *right {
+                Expr::Call { name, args } if is_placement(&name) => {
+                    placement = Some((name, args));
+                    *inner
+                }
+                right => Expr::Chain(inner, Box::new(right)),
+            },
+            source => source,
+        };
+    }
+
+    let Some(pass) = ctx.spatial_pass.as_mut() else {
+        return compile_or_extract_node(ctx, source);
+    };
+    let (layout, channel, index) = (pass.layout, pass.channel, pass.placements);
+    pass.placements += 1;
+    let shared = pass.sources.get(&index).copied();
+    let input = match shared {
+        Some(node) => node,
+        None => {
+            // A source with placements of its own differs per channel
+            let channel_free = !places_source(ctx, &source);
+            let node = compile_or_extract_node(ctx, source)?;
+            if let Some(pass) = ctx.spatial_pass.as_mut().filter(|_| channel_free) {
+                pass.sources.insert(index, node);
+            }
+            node
+        }
+    };
+
+    let azimuth = match azimuth {
+        Some(expr) => Signal::Node(compile_expr(ctx, expr)?),
+        None => Signal::Value(0.0),
+    };
+    let elevation = match elevation {
+        Some(expr) => Signal::Node(compile_expr(ctx, expr)?),
+        None => Signal::Value(0.0),
+    };
+    Ok(ctx.graph.add_node(SignalNode::SpatialGain {
+        input: Signal::Node(input),
+        azimuth,
+        elevation,
+        layout,
+        channel,
+    }))
+}
+
+/// A bus with placed sources, compiled for the channel of the `spatial:`
+/// pass in progress. `None` outside a pass, and for buses that don't place
+/// anything (or are frozen), which are shared by every channel
+fn compile_spatial_bus(ctx: &mut CompilerContext, name: &str) -> Result<Option<NodeId>, String> {
+    let Some(pass) = &ctx.spatial_pass else {
+        return Ok(None);
+    };
+    if let Some(&compiled) = pass.buses.get(name) {
+        return Ok(compiled);
+    }
+    let expr = match ctx.bus_expressions.get(name) {
+        Some(expr) if !ctx.bus_freezes.contains_key(name) && places_source(ctx, expr) => {
+            expr.clone()
+        }
+        _ => return Ok(None),
+    };
+
+    if let Some(pass) = ctx.spatial_pass.as_mut() {
+        pass.buses.insert(name.to_string(), None);
+    }
+    let outer_bus = ctx.current_bus.replace(name.to_string());
+    let node = compile_expr(ctx, expr);
+    ctx.current_bus = outer_bus;
+    let node = node?;
+    if let Some(pass) = ctx.spatial_pass.as_mut() {
+        pass.buses.insert(name.to_string(), Some(node));
+    }
+    Ok(Some(node))
+}
+
+/// Whether an expression places a source with `# azimuth`/`# elevation`,
+/// itself or through the buses and templates it uses
+fn places_source(ctx: &CompilerContext, expr: &Expr) -> bool {
+    fn walk(ctx: &CompilerContext, expr: &Expr, seen: &mut HashSet<String>) -> bool {
+        match expr {
+            Expr::Call { name, args } => {
+                is_placement(name) || args.iter().any(|arg| walk(ctx, arg, seen))
+            }
+            Expr::BusCall { args, .. } | Expr::List(args) => {
+                args.iter().any(|arg| walk(ctx, arg, seen))
+            }
+            Expr::Chain(left, right) | Expr::BinOp { left, right, .. } => {
+                walk(ctx, left, seen) || walk(ctx, right, seen)
+            }
+            Expr::Transform { expr: inner, .. }
+            | Expr::UnOp { expr: inner, .. }
+            | Expr::Paren(inner)
+            | Expr::Kwarg { value: inner, .. } => walk(ctx, inner, seen),
+            Expr::BusRef(name) => {
+                let definition = ctx
+                    .bus_expressions
+                    .get(name)
+                    .filter(|_| !ctx.bus_freezes.contains_key(name))
+                    .or_else(|| ctx.modifier_buses.get(name));
+                seen.insert(format!("~{}", name))
+                    && definition.is_some_and(|definition| walk(ctx, definition, seen))
+            }
+            Expr::TemplateRef(name) => {
+                seen.insert(format!("@{}", name))
+                    && ctx
+                        .templates
+                        .get(name)
+                        .is_some_and(|template| walk(ctx, template, seen))
+            }
+            _ => false,
+        }
+    }
+    walk(ctx, expr, &mut HashSet::new())
+}
+
 /// Helper to modify a Sample node's parameter
 /// Returns a new Sample node with the updated parameter
 fn modify_sample_param(
@@ -7921,6 +11091,8 @@ fn transform_contains_effect(transform: &Transform) -> bool {
         Transform::Sometimes(transform) => transform_contains_effect(transform),
         Transform::SometimesBy { transform, .. } => transform_contains_effect(transform),
         Transform::Whenmod { transform, .. } => transform_contains_effect(transform),
+        Transform::FoldEveryN { transform, .. } => transform_contains_effect(transform),
+        Transform::SomecyclesBy { transform, .. } => transform_contains_effect(transform),
         Transform::Compose(transforms) => transforms.iter().any(|t| transform_contains_effect(t)),
         _ => false,
     }
@@ -8008,6 +11180,7 @@ fn compile_effect_transform(
                     input,
                     effect: Signal::Node(effect_node),
                     n: n_val,
+                    offset: 0,
                 };
                 Ok(ctx.graph.add_node(node))
             } else if transform_contains_effect(&transform) {
@@ -8018,6 +11191,37 @@ fn compile_effect_transform(
             }
         }
 
+        Transform::EveryPrime {
+            n,
+            offset,
+            transform,
+        } => {
+            let n_val = match *n {
+                Expr::Number(num) if num >= 1.0 => num as i32,
+                _ => return Err("every' requires a positive numeric n".to_string()),
+            };
+            let offset_val = match *offset {
+                Expr::Number(num) => num as i32,
+                _ => return Err("every' requires numeric offset".to_string()),
+            };
+
+            if let Transform::Effect(effect_expr) = *transform {
+                let effect_node = compile_effect_chain(ctx, input.clone(), *effect_expr)?;
+
+                let node = SignalNode::EveryEffect {
+                    input,
+                    effect: Signal::Node(effect_node),
+                    n: n_val,
+                    offset: offset_val,
+                };
+                Ok(ctx.graph.add_node(node))
+            } else if transform_contains_effect(&transform) {
+                compile_effect_transform(ctx, input, *transform)
+            } else {
+                Err("Expected effect transform inside every'".to_string())
+            }
+        }
+
         Transform::Sometimes(transform) => {
             if let Transform::Effect(effect_expr) = *transform {
                 let effect_node = compile_effect_chain(ctx, input.clone(), *effect_expr)?;
@@ -8057,14 +11261,66 @@ fn compile_effect_transform(
             }
         }
 
+        Transform::SomecyclesBy { prob, transform } => {
+            // SometimesEffect already decides once per cycle
+            let prob_val = match *prob {
+                Expr::Number(num) => num,
+                _ => return Err("somecyclesBy requires a numeric probability".to_string()),
+            };
+
+            if let Transform::Effect(effect_expr) = *transform {
+                let effect_node = compile_effect_chain(ctx, input.clone(), *effect_expr)?;
+
+                let node = SignalNode::SometimesEffect {
+                    input,
+                    effect: Signal::Node(effect_node),
+                    prob: Signal::Value(prob_val as f32),
+                };
+                Ok(ctx.graph.add_node(node))
+            } else if transform_contains_effect(&transform) {
+                compile_effect_transform(ctx, input, *transform)
+            } else {
+                Err("Expected effect transform inside somecyclesBy".to_string())
+            }
+        }
+
+        Transform::FoldEveryN { ns, transform } => {
+            // foldEvery [3, 5] (# lpf 300) == every 3 (# lpf 300) $ every 5 (# lpf 300)
+            let effect_expr = match *transform {
+                Transform::Effect(effect_expr) => effect_expr,
+                _ => return Err("Expected effect transform inside foldEvery".to_string()),
+            };
+
+            let mut current = input;
+            for n in ns.iter().rev() {
+                let n_val = match n {
+                    Expr::Number(num) if *num >= 1.0 => *num as i32,
+                    _ => return Err("foldEvery requires positive cycle counts".to_string()),
+                };
+                let effect_node =
+                    compile_effect_chain(ctx, current.clone(), (*effect_expr).clone())?;
+                let node = SignalNode::EveryEffect {
+                    input: current,
+                    effect: Signal::Node(effect_node),
+                    n: n_val,
+                    offset: 0,
+                };
+                current = Signal::Node(ctx.graph.add_node(node));
+            }
+            match current {
+                Signal::Node(id) => Ok(id),
+                _ => Err("foldEvery requires at least one cycle count".to_string()),
+            }
+        }
+
         Transform::Whenmod {
             modulo,
             offset,
             transform,
         } => {
             let modulo_val = match *modulo {
-                Expr::Number(num) => num as i32,
-                _ => return Err("whenmod requires numeric modulo".to_string()),
+                Expr::Number(num) if num >= 1.0 => num as i32,
+                _ => return Err("whenmod requires a positive numeric modulo".to_string()),
             };
             let offset_val = match *offset {
                 Expr::Number(num) => num as i32,
@@ -8811,17 +12067,7 @@ fn apply_transform_to_pattern<T: Clone + Send + Sync + Debug + 'static>(
                 _ => return Err("groove preset must be a string (e.g., groove \"mpc\")".to_string()),
             };
 
-            let template = match preset_name.as_str() {
-                "mpc" | "mpc_swing" => crate::groove::presets::mpc_swing(0.5),
-                "hiphop" | "lazy_hiphop" => crate::groove::presets::lazy_hiphop(),
-                "reggae" | "reggae_one_drop" => crate::groove::presets::reggae_one_drop(),
-                "one_drop" => crate::groove::presets::reggae_one_drop(),
-                "jazz" | "jazz_swing" => crate::groove::presets::jazz_swing(0.5),
-                "drunken" | "drunk" => crate::groove::presets::drunken(0.5),
-                _ => return Err(format!("Unknown groove preset '{}'. Available: mpc, hiphop, reggae, jazz, drunken", preset_name)),
-            };
-
-            let template = Arc::new(template);
+            let template = Arc::new(crate::groove::resolve_groove(&preset_name)?);
 
             // Resolve amount pattern (default 1.0)
             let amount_pattern = match amount {
@@ -9027,31 +12273,25 @@ fn apply_transform_to_pattern<T: Clone + Send + Sync + Debug + 'static>(
             offset,
             transform,
         } => {
-            // every' n offset transform: apply transform when (cycle - offset) % n == 0
+            // every' n offset transform: apply transform when cycle % n == offset
             let n_val = extract_number(&n)? as i32;
             let offset_val = extract_number(&offset)? as i32;
+            if n_val <= 0 {
+                return Err(format!("every' requires n > 0, got {}", n_val));
+            }
 
-            // Clone the pattern, transform, and templates for use in the closure
             let inner_transform = (*transform).clone();
             let pattern_clone = pattern.clone();
             let templates_clone = ctx.templates.clone();
 
-            // Manually inline Pattern::every' logic
-            Ok(Pattern::new(move |state| {
-                let cycle = state.span.begin.to_float().floor() as i32;
-                if (cycle - offset_val) % n_val == 0 {
-                    // Apply the transform on matching cycles
-                    match apply_transform_to_pattern_simple(
-                        &templates_clone,
-                        pattern_clone.clone(),
-                        inner_transform.clone(),
-                    ) {
-                        Ok(transformed) => transformed.query(state),
-                        Err(_) => pattern_clone.query(state), // Fallback to original on error
-                    }
-                } else {
-                    // Use original pattern on other cycles
-                    pattern_clone.query(state)
+            Ok(pattern.every_offset(n_val, offset_val, move |p| {
+                match apply_transform_to_pattern_simple(
+                    &templates_clone,
+                    p,
+                    inner_transform.clone(),
+                ) {
+                    Ok(transformed) => transformed,
+                    Err(_) => pattern_clone.clone(), // Fallback to original on error
                 }
             }))
         }
@@ -9093,6 +12333,52 @@ fn apply_transform_to_pattern<T: Clone + Send + Sync + Debug + 'static>(
                 }
             }))
         }
+        Transform::FoldEveryN { ns, transform } => {
+            // foldEvery [n1, n2] transform: every n1 transform $ every n2 transform
+            let ns = ns
+                .iter()
+                .map(|n| {
+                    let n_val = extract_number(n)? as i32;
+                    if n_val <= 0 {
+                        return Err(format!("foldEvery requires n > 0, got {}", n_val));
+                    }
+                    Ok(n_val)
+                })
+                .collect::<Result<Vec<i32>, String>>()?;
+
+            let inner_transform = (*transform).clone();
+            let pattern_clone = pattern.clone();
+            let templates_clone = ctx.templates.clone();
+
+            Ok(pattern.fold_every(ns, move |p| {
+                match apply_transform_to_pattern_simple(
+                    &templates_clone,
+                    p,
+                    inner_transform.clone(),
+                ) {
+                    Ok(transformed) => transformed,
+                    Err(_) => pattern_clone.clone(), // Fallback to original on error
+                }
+            }))
+        }
+        Transform::SomecyclesBy { prob, transform } => {
+            // Apply transform to whole cycles with the given probability
+            let prob_val = extract_number(&prob)?;
+            let inner_transform = (*transform).clone();
+            let pattern_clone = pattern.clone();
+            let templates_clone = ctx.templates.clone();
+
+            Ok(pattern.somecycles_by(prob_val, move |p| {
+                match apply_transform_to_pattern_simple(
+                    &templates_clone,
+                    p,
+                    inner_transform.clone(),
+                ) {
+                    Ok(transformed) => transformed,
+                    Err(_) => pattern_clone.clone(), // Fallback to original on error
+                }
+            }))
+        }
         Transform::Sometimes(transform) => {
             // Apply transform 50% of the time (per cycle)
             use rand::{rngs::StdRng, Rng, SeedableRng};
@@ -9257,9 +12543,25 @@ fn apply_transform_to_pattern<T: Clone + Send + Sync + Debug + 'static>(
             // We need to handle this specially
             Err("range transform only works with numeric patterns (from oscillators), not sample patterns".to_string())
         }
-        Transform::Quantize(_steps_expr) => {
-            // Note: quantize() only works on Pattern<f64>, not Pattern<T>
-            Err("quantize transform only works with numeric patterns (from oscillators), not sample patterns".to_string())
+        Transform::Quantize(steps_expr) => {
+            // Snap event onsets to a grid of `steps` per cycle
+            match steps_expr.as_ref() {
+                Expr::String(pattern_str) => {
+                    let string_pattern = parse_mini_notation(pattern_str);
+                    let steps_pattern = string_pattern.fmap(|s| s.parse::<f64>().unwrap_or(1.0));
+                    Ok(pattern.quantize_onsets(steps_pattern))
+                }
+                _ => {
+                    let steps = extract_number(&steps_expr)?;
+                    if steps <= 0.0 {
+                        return Err(format!(
+                            "quantize needs a grid above 0 steps, got {}",
+                            steps
+                        ));
+                    }
+                    Ok(pattern.quantize_onsets(Pattern::pure(steps)))
+                }
+            }
         }
         Transform::Focus {
             cycle_begin,
@@ -9290,6 +12592,35 @@ fn apply_transform_to_pattern<T: Clone + Send + Sync + Debug + 'static>(
             // Note: walk() only works on Pattern<f64>, not Pattern<T>
             Err("walk transform only works with numeric patterns (from oscillators), not sample patterns".to_string())
         }
+        Transform::Mutate { amount, seed } => {
+            let amount = extract_number(&amount)?;
+            let seed = match seed {
+                Some(seed) => extract_number(&seed)? as u64,
+                None => 0,
+            };
+            Ok(pattern.mutate(amount, seed))
+        }
+        Transform::Markov(cycles) => {
+            let cycles = match cycles {
+                Some(cycles) => extract_number(&cycles)?.max(1.0) as usize,
+                None => 1,
+            };
+            Ok(pattern.markov(cycles, 0))
+        }
+        Transform::Take(cycles) => {
+            Ok(pattern.take(extract_number(&cycles)?, ctx.graph.count_origin()))
+        }
+        Transform::After { cycles, transform } => {
+            let cycles = extract_number(&cycles)?;
+            let origin = ctx.graph.count_origin();
+            match transform {
+                Some(transform) => {
+                    let next = apply_transform_to_pattern(ctx, pattern.clone(), *transform)?;
+                    Ok(pattern.switch_after(cycles, next, origin))
+                }
+                None => Ok(pattern.after(cycles, origin)),
+            }
+        }
         Transform::Inside {
             begin,
             end,
@@ -9478,6 +12809,9 @@ fn apply_transform_to_pattern<T: Clone + Send + Sync + Debug + 'static>(
         } => {
             let modulo_val = extract_number(&modulo)? as i32;
             let offset_val = extract_number(&offset)? as i32;
+            if modulo_val <= 0 {
+                return Err(format!("whenmod requires modulo > 0, got {}", modulo_val));
+            }
             let inner_transform = (*transform).clone();
             let pattern_clone = pattern.clone();
             let templates_clone = ctx.templates.clone();
@@ -9666,7 +13000,7 @@ fn extract_number(expr: &Expr) -> Result<f64, String> {
 }
 
 /// Check if an operator is a structure-aware pattern operator
-/// Includes both explicit structure operators (|+, +|, etc.) and bare operators (+, -, *, /)
+/// Includes explicit structure operators (|+, +|, |+|, etc.) and bare operators (+, -, *, /)
 /// which use "both structure" semantics when applied to patterns
 fn is_structure_operator(op: &BinOp) -> bool {
     matches!(
@@ -9682,6 +13016,13 @@ fn is_structure_operator(op: &BinOp) -> bool {
             | BinOp::DivRight
             | BinOp::UnionLeft
             | BinOp::UnionRight
+            // Both-structure operators (|+|, |*|, |>|, ...)
+            | BinOp::AddBoth
+            | BinOp::SubBoth
+            | BinOp::MulBoth
+            | BinOp::DivBoth
+            | BinOp::UnionBoth
+            | BinOp::UnionBothLeft
             // Bare operators (both-structure semantics on patterns)
             | BinOp::Add
             | BinOp::Sub
@@ -9716,6 +13057,11 @@ fn try_extract_numeric_pattern(expr: &Expr) -> Option<(Pattern<f64>, String)> {
             Some((Pattern::pure(*n), n.to_string()))
         }
         Expr::Paren(inner) => try_extract_numeric_pattern(inner),
+        Expr::Call { name, args } => {
+            // Numeric generators (run 8, irand 4, ...) combine structurally too
+            let pattern = numeric_generator_pattern(name, args)?.ok()?;
+            Some((pattern, format!("{} {:?}", name, args)))
+        }
         _ => None,
     }
 }
@@ -9777,6 +13123,31 @@ fn compile_binop(
                     left_pattern.union_right(right_pattern),
                     format!("{} <| {}", left_str, right_str),
                 ),
+                // Tidal both-structure operators: events split at every boundary
+                BinOp::AddBoth => (
+                    left_pattern.add_sect(right_pattern),
+                    format!("{} |+| {}", left_str, right_str),
+                ),
+                BinOp::SubBoth => (
+                    left_pattern.sub_sect(right_pattern),
+                    format!("{} |-| {}", left_str, right_str),
+                ),
+                BinOp::MulBoth => (
+                    left_pattern.mul_sect(right_pattern),
+                    format!("{} |*| {}", left_str, right_str),
+                ),
+                BinOp::DivBoth => (
+                    left_pattern.div_sect(right_pattern),
+                    format!("{} |/| {}", left_str, right_str),
+                ),
+                BinOp::UnionBoth => (
+                    left_pattern.union_sect(right_pattern),
+                    format!("{} |>| {}", left_str, right_str),
+                ),
+                BinOp::UnionBothLeft => (
+                    left_pattern.union_sect_left(right_pattern),
+                    format!("{} |<| {}", left_str, right_str),
+                ),
                 // Bare operators use "both structure" semantics
                 BinOp::Add => (
                     left_pattern.add_both(right_pattern),
@@ -9795,7 +13166,11 @@ fn compile_binop(
                     format!("{} / {}", left_str, right_str),
                 ),
                 // Signal operators should never reach here - they're not structure operators
-                BinOp::SignalAdd | BinOp::SignalSub | BinOp::SignalMul | BinOp::SignalDiv => {
+                BinOp::SignalAdd
+                | BinOp::SignalSub
+                | BinOp::SignalMul
+                | BinOp::SignalDiv
+                | BinOp::Spread => {
                     unreachable!("Signal operators should not be handled as structure operators")
                 }
             };
@@ -9832,23 +13207,23 @@ fn compile_binop(
 
     // Arithmetic operations are done via Signal::Expression
     let expr = match op {
-        BinOp::Add | BinOp::AddLeft | BinOp::AddRight => {
+        BinOp::Add | BinOp::AddLeft | BinOp::AddRight | BinOp::AddBoth => {
             SignalExpr::Add(Signal::Node(left_node), Signal::Node(right_node))
         }
-        BinOp::Sub | BinOp::SubLeft | BinOp::SubRight => {
+        BinOp::Sub | BinOp::SubLeft | BinOp::SubRight | BinOp::SubBoth => {
             SignalExpr::Subtract(Signal::Node(left_node), Signal::Node(right_node))
         }
-        BinOp::Mul | BinOp::MulLeft | BinOp::MulRight => {
+        BinOp::Mul | BinOp::MulLeft | BinOp::MulRight | BinOp::MulBoth => {
             SignalExpr::Multiply(Signal::Node(left_node), Signal::Node(right_node))
         }
-        BinOp::Div | BinOp::DivLeft | BinOp::DivRight => {
+        BinOp::Div | BinOp::DivLeft | BinOp::DivRight | BinOp::DivBoth => {
             SignalExpr::Divide(Signal::Node(left_node), Signal::Node(right_node))
         }
-        BinOp::UnionLeft => {
+        BinOp::UnionLeft | BinOp::UnionBothLeft => {
             // Union left: pass through left value (structure from left)
             SignalExpr::Add(Signal::Node(left_node), Signal::Value(0.0))
         }
-        BinOp::UnionRight => {
+        BinOp::UnionRight | BinOp::UnionBoth => {
             // Union right: pass through right value (structure from right)
             SignalExpr::Add(Signal::Node(right_node), Signal::Value(0.0))
         }
@@ -9857,6 +13232,13 @@ fn compile_binop(
         BinOp::SignalSub => SignalExpr::Subtract(Signal::Node(left_node), Signal::Node(right_node)),
         BinOp::SignalMul => SignalExpr::Multiply(Signal::Node(left_node), Signal::Node(right_node)),
         BinOp::SignalDiv => SignalExpr::Divide(Signal::Node(left_node), Signal::Node(right_node)),
+        // A random value between the two sides, drawn per trigger
+        BinOp::Spread => {
+            return Ok(ctx.graph.add_node(SignalNode::RandSpread {
+                min: Signal::Node(left_node),
+                max: Signal::Node(right_node),
+            }))
+        }
     };
 
     // We need a node that outputs this expression
@@ -9938,8 +13320,30 @@ fn compile_n_modifier(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<Node
                 }
             };
 
-            // Compile the n pattern
-            let n_value = compile_expr(ctx, args[1].clone())?;
+            // Compile the n pattern. For samples, `irand` gives every trigger its
+            // own random file (rather than one per cycle): a spread over
+            // [-0.5, count - 0.5) that the sample's n rounds to 0..count-1
+            let is_sample = matches!(
+                ctx.graph.get_node(sample_node_id),
+                Some(SignalNode::Sample { .. })
+            );
+            let n_expr = match &args[1] {
+                Expr::Paren(inner) => &**inner,
+                other => other,
+            };
+            let n_value = match n_expr {
+                Expr::Call { name, args } if is_sample && name == "irand" && args.len() == 1 => {
+                    let count = compile_expr(ctx, args[0].clone())?;
+                    ctx.graph.add_node(SignalNode::RandSpread {
+                        min: Signal::Value(-0.5),
+                        max: Signal::Expression(Box::new(SignalExpr::Subtract(
+                            Signal::Node(count),
+                            Signal::Value(0.5),
+                        ))),
+                    })
+                }
+                _ => compile_expr(ctx, args[1].clone())?,
+            };
 
             // Modify the Sample or SynthPattern node
             modify_sample_param(ctx, sample_node_id, "n", Signal::Node(n_value))
@@ -10113,6 +13517,114 @@ fn compile_chord_modifier(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<
     }
 }
 
+/// Compile auto-harmonization: n "c4 d4 ef4" # harmonize "3rds" :scale "c minor"
+///
+/// Each melody note becomes a stack of itself plus parallel harmony kept in
+/// the key (`3rds`, `4ths`, `5ths`, `6ths`, `7ths`, `octaves`, `triads`).
+/// The key defaults to C major.
+fn compile_harmonize_modifier(
+    ctx: &mut CompilerContext,
+    args: Vec<Expr>,
+) -> Result<NodeId, String> {
+    let usage = "harmonize (n \"c4 d4 ef4\" # harmonize \"3rds\" :scale \"c minor\")";
+    let params = ParamExtractor::new(args);
+    if params.positional_count() != 2 {
+        return Err(format!("{} requires a melody input and a harmony", usage));
+    }
+    let melody = chord_stack_input(ctx, &params.positional[0], usage)?;
+    let harmony_str = match &params.positional[1] {
+        Expr::String(s) => s.clone(),
+        _ => return Err(format!("{}: harmony must be a string pattern", usage)),
+    };
+    let key = match params.get_optional_keyword("scale") {
+        Some(Expr::String(spec)) => crate::scale_dsl::parse_key(&spec)
+            .ok_or_else(|| format!("harmonize: unknown key '{}'", spec))?,
+        Some(_) => return Err(format!("{}: :scale must be a string", usage)),
+        None => (0, Scale::Major),
+    };
+    let harmonized =
+        crate::scale_dsl::harmonize_pattern(melody, parse_mini_notation(&harmony_str), key);
+    let node = SignalNode::Pattern {
+        pattern_str: format!("harmonize({harmony_str})"),
+        pattern: harmonized,
+        last_value: 0.0,
+        last_trigger_time: -1.0,
+    };
+    Ok(ctx.graph.add_node(node))
+}
+
+/// The note pattern a chord modifier (voicing, harmonize) applies to: the
+/// chain input must be a note Pattern node, as `n`, `note` and `chord` produce
+fn chord_stack_input(ctx: &CompilerContext, input: &Expr, usage: &str) -> Result<Pattern<String>, String> {
+    match input {
+        Expr::ChainInput(node_id) => match ctx.graph.get_node(*node_id) {
+            Some(SignalNode::Pattern { pattern, .. }) => Ok(pattern.clone()),
+            _ => Err(format!("{} expects a chord pattern input", usage)),
+        },
+        _ => Err(format!("{} must be used with the chain operator", usage)),
+    }
+}
+
+/// Compile chord voicing: n "c e" # chord "maj7" # voicing "drop2 open"
+///
+/// `# open` and `# close` are shorthands for the matching voicing.
+fn compile_voicing_modifier(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let usage = "voicing (n \"c e\" # chord \"maj7\" # voicing \"drop2\")";
+    if args.len() != 2 {
+        return Err(format!("{} requires a chord input and a voicing name", usage));
+    }
+    let chords = chord_stack_input(ctx, &args[0], usage)?;
+    let voicing_str = match &args[1] {
+        Expr::String(s) => s.clone(),
+        _ => return Err(format!("{}: voicing must be a string pattern", usage)),
+    };
+    let voiced =
+        crate::pattern_tonal::voicing_pattern(chords, parse_mini_notation(&voicing_str));
+    let node = SignalNode::Pattern {
+        pattern_str: format!("voicing({voicing_str})"),
+        pattern: voiced,
+        last_value: 0.0,
+        last_trigger_time: -1.0,
+    };
+    Ok(ctx.graph.add_node(node))
+}
+
+/// Compile chord inversion: n "c" # chord "maj" # inversion 1
+///
+/// The count can be patterned (`inversion "0 1 2"`); negative counts invert
+/// downwards.
+fn compile_inversion_modifier(
+    ctx: &mut CompilerContext,
+    args: Vec<Expr>,
+) -> Result<NodeId, String> {
+    let usage = "inversion (n \"c\" # chord \"maj\" # inversion 1)";
+    if args.len() != 2 {
+        return Err(format!("{} requires a chord input and an inversion count", usage));
+    }
+    let chords = chord_stack_input(ctx, &args[0], usage)?;
+    let steps_str = match &args[1] {
+        Expr::String(s) => s.clone(),
+        Expr::Number(n) => n.to_string(),
+        Expr::UnOp {
+            op: UnOp::Neg,
+            expr,
+        } => match **expr {
+            Expr::Number(n) => (-n).to_string(),
+            _ => return Err(format!("{}: inversion must be a number or pattern", usage)),
+        },
+        _ => return Err(format!("{}: inversion must be a number or pattern", usage)),
+    };
+    let inverted =
+        crate::pattern_tonal::inversion_pattern(chords, parse_mini_notation(&steps_str));
+    let node = SignalNode::Pattern {
+        pattern_str: format!("inversion({steps_str})"),
+        pattern: inverted,
+        last_value: 0.0,
+        last_trigger_time: -1.0,
+    };
+    Ok(ctx.graph.add_node(node))
+}
+
 /// Compile note function/modifier
 ///
 /// Two modes:
@@ -10122,6 +13634,15 @@ fn compile_chord_modifier(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<
 /// For note NAMES (c4, d4, etc.) uses absolute pitch mode (converts to frequencies)
 /// For NUMBERS uses relative semitone offset mode
 fn compile_note_modifier(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    // A bare note name (`# note c4`) means the same as its mini-notation string
+    let args: Vec<Expr> = args
+        .into_iter()
+        .map(|arg| match arg {
+            Expr::Var(name) if crate::pitch::is_note_literal(&name) => Expr::String(name),
+            other => other,
+        })
+        .collect();
+
     match args.len() {
         // Standalone mode: note "c4 e4 g4" → frequency pattern
         1 => {
@@ -10652,6 +14173,9 @@ fn compile_ar_modifier(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<Nod
 /// Compile amp modifier: applies amplitude/gain to ANY signal
 /// Works with oscillators, samples, filters, etc.
 /// Usage: sine 440 # amp 0.3  OR  s "bd" # amp "0.5 0.8 1.0"
+///
+/// On a sample pattern amp is a per-hit level (multiplied into its gain), so
+/// velocity-layered kits pick their layer from it
 fn compile_amp(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
     // Extract input signal and parameters
     let (input_signal, params) = extract_chain_input(ctx, &args)?;
@@ -10666,6 +14190,19 @@ fn compile_amp(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, Str
     // Compile the amplitude value (can be a number or pattern)
     let amp_value = compile_expr(ctx, params[0].clone())?;
 
+    if let Signal::Node(input_id) = &input_signal {
+        if let Some(SignalNode::Sample { gain, .. }) = ctx.graph.get_node(*input_id) {
+            let level = match gain {
+                Signal::Value(v) if *v == 1.0 => Signal::Node(amp_value),
+                gain => Signal::Expression(Box::new(SignalExpr::Multiply(
+                    gain.clone(),
+                    Signal::Node(amp_value),
+                ))),
+            };
+            return modify_sample_param(ctx, *input_id, "gain", level);
+        }
+    }
+
     // Create a Multiply node to apply amplitude
     let node = SignalNode::Multiply {
         a: input_signal,
@@ -10823,11 +14360,48 @@ fn compile_gate(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, St
     let node = SignalNode::PatternGate {
         pattern_str: pattern_str.clone(),
         pattern: bool_pattern,
+        retrigger_gap: 0.0,
     };
 
     Ok(ctx.graph.add_node(node))
 }
 
+/// Compile noise gate
+/// Syntax: `<input> # gate [threshold] [attack] [release] [:hold 0.05]
+/// [:hysteresis 6] [:key ~bus]`, with the thresholds in dB. With `:key` the
+/// key's level opens the gate instead of the input's, so a rhythm bus can
+/// chop a pad
+fn compile_noise_gate(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    use crate::nodes::noise_gate::{NoiseGateState, DEFAULT_HYSTERESIS_DB};
+
+    let (input_signal, params) = extract_chain_input(ctx, &args)?;
+    let extractor = ParamExtractor::new(params);
+
+    let key = match extractor.get_optional_keyword("key") {
+        Some(expr) => Some(Signal::Node(compile_expr(ctx, expr)?)),
+        None => None,
+    };
+    let threshold_node = compile_expr(ctx, extractor.get_optional(0, "threshold", -30.0))?;
+    let attack_node = compile_expr(ctx, extractor.get_optional(1, "attack", 0.001))?;
+    let release_node = compile_expr(ctx, extractor.get_optional(2, "release", 0.05))?;
+    let hold_node = compile_expr(ctx, extractor.get_optional(3, "hold", 0.0))?;
+    let hysteresis_node = compile_expr(
+        ctx,
+        extractor.get_optional(4, "hysteresis", DEFAULT_HYSTERESIS_DB),
+    )?;
+
+    Ok(ctx.graph.add_node(SignalNode::NoiseGate {
+        input: input_signal,
+        key,
+        threshold: Signal::Node(threshold_node),
+        hysteresis: Signal::Node(hysteresis_node),
+        attack: Signal::Node(attack_node),
+        hold: Signal::Node(hold_node),
+        release: Signal::Node(release_node),
+        state: NoiseGateState::default(),
+    }))
+}
+
 /// Compile trig: pattern to trigger pulse
 /// Usage: trig "t(3,8)" -> outputs 1.0 for one sample at each event start
 fn compile_trig(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
@@ -10855,93 +14429,160 @@ fn compile_trig(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, St
     Ok(ctx.graph.add_node(node))
 }
 
-/// Compile run pattern generator: run 4 -> generates 0,1,2,3 per cycle
-fn compile_run(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
-    if args.len() != 1 {
-        return Err(format!("run requires 1 argument (n), got {}", args.len()));
-    }
-
-    // Extract n value
-    let n = extract_number(&args[0])? as usize;
-    if n == 0 {
-        return Err("run requires n > 0".to_string());
+/// Size argument of a numeric generator: `run 8` or patterned `run "<4 8>"`
+fn generator_size_pattern(name: &str, expr: &Expr) -> Result<Pattern<f64>, String> {
+    match expr {
+        Expr::String(s) => Ok(parse_mini_notation(s).fmap(|v| v.parse::<f64>().unwrap_or(0.0))),
+        Expr::Paren(inner) => generator_size_pattern(name, inner),
+        _ => {
+            let n = extract_number(expr)?;
+            if n < 1.0 {
+                return Err(format!("{} requires n > 0", name));
+            }
+            Ok(Pattern::pure(n))
+        }
     }
-
-    // Create the run pattern
-    let pattern = Pattern::<f64>::run(n);
-
-    // Wrap in PatternEvaluator node
-    let node = SignalNode::PatternEvaluator { pattern };
-    Ok(ctx.graph.add_node(node))
 }
 
-/// Compile scan pattern generator: scan 4 -> cumulative pattern (0), (0 1), (0 1 2), (0 1 2 3)
-fn compile_scan(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
-    if args.len() != 1 {
-        return Err(format!("scan requires 1 argument (n), got {}", args.len()));
-    }
-
-    // Extract n value
-    let n = extract_number(&args[0])? as usize;
-    if n == 0 {
-        return Err("scan requires n > 0".to_string());
+/// Numeric value of a choose/wchoose option: `0`, `-12` or `"7"`
+fn choice_value(name: &str, expr: &Expr) -> Result<f64, String> {
+    match expr {
+        Expr::String(s) => s
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("{} options must be numbers, got \"{}\"", name, s)),
+        _ => extract_number(expr).map_err(|_| format!("{} options must be numbers", name)),
     }
-
-    // Create the scan pattern
-    let pattern = Pattern::<f64>::scan(n);
-
-    // Wrap in PatternEvaluator node
-    let node = SignalNode::PatternEvaluator { pattern };
-    Ok(ctx.graph.add_node(node))
 }
 
-/// Compile irand pattern generator
-/// irand n -> random integers 0 to n-1 per cycle
-/// irand min max -> random integers min to max per cycle
-fn compile_irand(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
-    match args.len() {
-        1 => {
-            // irand n -> 0 to n-1
-            let n = extract_number(&args[0])? as usize;
-            if n == 0 {
-                return Err("irand requires n > 0".to_string());
+/// Build the Pattern<f64> behind a numeric generator call
+///
+/// Returns None when `name` is not a numeric generator. Shared by the
+/// generator nodes below and by the structure operators, so that
+/// `note ("0 7" |+ run 4)` combines at the pattern level.
+///
+/// - `run n`: 0 .. n-1 evenly spaced per cycle
+/// - `scan n`: run 1, run 2, .. run n over successive cycles
+/// - `irand n` / `irand min max`: random integer per cycle
+/// - `rand`: random float 0-1 per cycle
+/// - `randstep n` / `randstep n min max`: new random value every 1/n cycle
+/// - `choose [0, 3, 7]`: random pick per cycle
+/// - `wchoose [[0, 3], [7, 1]]`: weighted random pick per cycle
+fn numeric_generator_pattern(name: &str, args: &[Expr]) -> Option<Result<Pattern<f64>, String>> {
+    let result = match name {
+        "run" | "scan" => {
+            if args.len() != 1 {
+                return Some(Err(format!(
+                    "{} requires 1 argument (n), got {}",
+                    name,
+                    args.len()
+                )));
+            }
+            let make: fn(usize) -> Pattern<f64> = if name == "run" {
+                Pattern::<f64>::run
+            } else {
+                Pattern::<f64>::scan
+            };
+            match &args[0] {
+                // Constant size: plain generator (keeps scan's cycle counting intact)
+                Expr::Number(_) | Expr::UnOp { .. } => extract_number(&args[0]).and_then(|n| {
+                    if n < 1.0 {
+                        Err(format!("{} requires n > 0", name))
+                    } else {
+                        Ok(make(n as usize))
+                    }
+                }),
+                size => generator_size_pattern(name, size)
+                    .map(|count| Pattern::<f64>::sized_by(count, make)),
             }
-            let pattern = Pattern::<f64>::irand(n);
-            let node = SignalNode::PatternEvaluator { pattern };
-            Ok(ctx.graph.add_node(node))
         }
-        2 => {
+        "irand" => match args.len() {
+            // irand n -> 0 to n-1
+            1 => generator_size_pattern(name, &args[0])
+                .map(|count| Pattern::<f64>::sized_by(count, Pattern::<f64>::irand)),
             // irand min max -> min to max (inclusive)
-            let min = extract_number(&args[0])? as i64;
-            let max = extract_number(&args[1])? as i64;
-            if max < min {
-                return Err(format!(
-                    "irand max ({}) must be >= min ({})",
-                    max, min
-                ));
+            2 => irand_range_pattern(&args[0], &args[1]),
+            _ => Err(format!(
+                "irand requires 1 or 2 arguments (n or min max), got {}",
+                args.len()
+            )),
+        },
+        "rand" => {
+            if !args.is_empty() {
+                return Some(Err(format!("rand takes no arguments, got {}", args.len())));
             }
-            let range = (max - min + 1) as usize;
-            // Use irand(range) and add min offset
-            let base_pattern = Pattern::<f64>::irand(range);
-            let pattern = base_pattern.map(move |v| v + min as f64);
-            let node = SignalNode::PatternEvaluator { pattern };
-            Ok(ctx.graph.add_node(node))
+            Ok(Pattern::<f64>::rand())
+        }
+        "randstep" => {
+            let (lo, hi) = match args.len() {
+                1 => (0.0, 1.0),
+                3 => match (extract_number(&args[1]), extract_number(&args[2])) {
+                    (Ok(lo), Ok(hi)) => (lo, hi),
+                    (Err(e), _) | (_, Err(e)) => return Some(Err(e)),
+                },
+                n => {
+                    return Some(Err(format!(
+                        "randstep requires 1 or 3 arguments (steps or steps min max), got {}",
+                        n
+                    )))
+                }
+            };
+            // Step k of cycle c reads rand's value for cycle c * n + k, so
+            // the sequence repeats exactly whenever the same cycle comes round
+            generator_size_pattern(name, &args[0]).map(|count| {
+                Pattern::<f64>::sized_by(count, |n| {
+                    Pattern::<f64>::rand().fast(Pattern::pure(n as f64))
+                })
+                .map(move |v| lo + v * (hi - lo))
+            })
         }
-        _ => Err(format!(
-            "irand requires 1 or 2 arguments (n or min max), got {}",
-            args.len()
-        )),
-    }
+        "choose" => match args {
+            [Expr::List(options)] if !options.is_empty() => options
+                .iter()
+                .map(|expr| choice_value(name, expr))
+                .collect::<Result<Vec<f64>, String>>()
+                .map(Pattern::choose),
+            _ => Err("choose requires a list argument: choose [0, 3, 7]".to_string()),
+        },
+        "wchoose" => match args {
+            [Expr::List(pairs)] if !pairs.is_empty() => pairs
+                .iter()
+                .map(|pair| match pair {
+                    Expr::List(pair) if pair.len() == 2 => {
+                        Ok((choice_value(name, &pair[0])?, extract_number(&pair[1])?))
+                    }
+                    _ => Err("wchoose requires list of [value, weight] pairs".to_string()),
+                })
+                .collect::<Result<Vec<(f64, f64)>, String>>()
+                .map(Pattern::wchoose),
+            _ => Err("wchoose requires a list argument: wchoose [[0, 3], [7, 1]]".to_string()),
+        },
+        _ => return None,
+    };
+    Some(result)
 }
 
-/// Compile rand pattern generator: rand -> random floats 0.0-1.0 per cycle
-fn compile_rand(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
-    if !args.is_empty() {
-        return Err(format!("rand takes no arguments, got {}", args.len()));
+/// irand min max -> random integers min to max (inclusive) per cycle
+fn irand_range_pattern(min: &Expr, max: &Expr) -> Result<Pattern<f64>, String> {
+    let min = extract_number(min)? as i64;
+    let max = extract_number(max)? as i64;
+    if max < min {
+        return Err(format!("irand max ({}) must be >= min ({})", max, min));
     }
+    let range = (max - min + 1) as usize;
+    // Use irand(range) and add min offset
+    Ok(Pattern::<f64>::irand(range).map(move |v| v + min as f64))
+}
 
-    // Create the rand pattern
-    let pattern = Pattern::<f64>::rand();
+/// Compile a numeric pattern generator (run, scan, irand, rand, randstep, choose, wchoose)
+/// into a PatternEvaluator node usable for `n`, `note` or any control input
+fn compile_numeric_generator(
+    ctx: &mut CompilerContext,
+    name: &str,
+    args: Vec<Expr>,
+) -> Result<NodeId, String> {
+    let pattern = numeric_generator_pattern(name, &args)
+        .unwrap_or_else(|| Err(format!("{} is not a numeric generator", name)))?;
 
     // Wrap in PatternEvaluator node
     let node = SignalNode::PatternEvaluator { pattern };
@@ -10971,6 +14612,56 @@ fn compile_phasor(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId,
     Ok(ctx.graph.add_node(node))
 }
 
+/// Compile a low-frequency oscillator
+/// Syntax: `lfo <rate> [min] [max] [:shape sine] [:phase 0] [:oneshot false]`
+/// The rate is in Hz, or a period in cycles (`lfo 0.25c`, `lfo 4c`) that
+/// stays locked to the tempo. Shapes: sine, tri, saw, ramp, square, random.
+fn compile_lfo(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    let extractor = ParamExtractor::new(args);
+
+    let (rate, synced) = match extractor.get_required(0, "rate")? {
+        Expr::Call { name, args } if name == "cycles" => {
+            let period = args.first().map(extract_number).transpose()?.unwrap_or(0.0);
+            if period <= 0.0 {
+                return Err("lfo: the period in cycles must be above 0".to_string());
+            }
+            (Signal::Value(period as f32), true)
+        }
+        expr => (Signal::Node(compile_expr(ctx, expr)?), false),
+    };
+    let min_node = compile_expr(ctx, extractor.get_optional(1, "min", -1.0))?;
+    let max_node = compile_expr(ctx, extractor.get_optional(2, "max", 1.0))?;
+
+    let shape = match extractor.get_optional_keyword("shape") {
+        None => LfoShape::Sine,
+        Some(Expr::Var(name)) | Some(Expr::String(name)) => {
+            LfoShape::from_name(&name).ok_or_else(|| {
+                format!(
+                    "lfo: unknown :shape '{}' (sine, tri, saw, ramp, square, random)",
+                    name
+                )
+            })?
+        }
+        Some(other) => return Err(format!("lfo: :shape must be a name, got {:?}", other)),
+    };
+    let phase_offset = match extractor.get_optional_keyword("phase") {
+        Some(expr) => extract_number(&expr)? as f32,
+        None => 0.0,
+    };
+    let oneshot = flag_keyword("lfo", &extractor, "oneshot")?;
+
+    Ok(ctx.graph.add_node(SignalNode::Lfo {
+        rate,
+        synced,
+        shape,
+        phase_offset,
+        min: Signal::Node(min_node),
+        max: Signal::Node(max_node),
+        oneshot,
+        state: Default::default(),
+    }))
+}
+
 /// Compile mtof (MIDI to frequency) conversion
 /// mtof(midi_pattern) -> frequency pattern
 /// Formula: freq = 440 * 2^((midi - 69) / 12)
@@ -11005,6 +14696,21 @@ fn compile_mtof(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, St
     Ok(ctx.graph.add_node(node))
 }
 
+/// Frequency to 1 V/octave control voltage, 0 V at C4, for `cvout`:
+/// `voct ~pitch` or `~pitch |> voct`
+fn compile_voct(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    if args.len() != 1 {
+        return Err(format!(
+            "voct requires 1 argument (freq), got {}",
+            args.len()
+        ));
+    }
+    let freq = compile_expr(ctx, args.into_iter().next().unwrap())?;
+    Ok(ctx.graph.add_node(SignalNode::HzToVolts {
+        freq: Signal::Node(freq),
+    }))
+}
+
 fn compile_sine_wave(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
     if !args.is_empty() {
         return Err(format!("sine takes no arguments, got {}", args.len()));
@@ -11154,6 +14860,7 @@ fn compile_every_effect(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<No
         input: Signal::Node(input),
         effect: Signal::Node(effect),
         n,
+        offset: 0,
     };
 
     Ok(ctx.graph.add_node(node))
@@ -11304,6 +15011,56 @@ fn compile_vst(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, Str
     Ok(ctx.graph.add_node(node))
 }
 
+/// Compile an external effect plugin processing a signal
+///
+/// Syntax:
+/// - extfx "LSP Compressor" ~bus :param "threshold" 0.3 :param "ratio" 0.5
+/// - ~bus # extfx "Dragonfly Room Reverb" :wet 0.4
+///
+/// `:param "Name" value` sets a parameter by its full plugin name (which may
+/// contain spaces); `:name value` matches a parameter by name prefix. Values
+/// are in the parameter's own units and can be patterns or signals. The
+/// effect runs a block at a time (see `plugin_host::external_fx`).
+fn compile_extfx(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeId, String> {
+    const USAGE: &str = "extfx requires a plugin name and an input: extfx \"Plugin\" ~bus";
+    let mut args = args.into_iter().peekable();
+
+    let (input, plugin_name) = match (args.next(), args.next()) {
+        (Some(Expr::ChainInput(node_id)), Some(Expr::String(name))) => (node_id, name),
+        (Some(Expr::String(name)), Some(input)) if !matches!(input, Expr::Kwarg { .. }) => {
+            (compile_expr(ctx, input)?, name)
+        }
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let mut params = Vec::new();
+    let mut values = Vec::new();
+    while let Some(arg) = args.next() {
+        let (name, exact, value) = match arg {
+            Expr::Kwarg { name, value } if name == "param" => {
+                let Expr::String(param_name) = *value else {
+                    return Err("extfx :param needs a parameter name string: :param \"threshold\" 0.3".to_string());
+                };
+                match args.next_if(|arg| !matches!(arg, Expr::Kwarg { .. })) {
+                    Some(value) => (param_name, true, value),
+                    None => return Err(format!("extfx :param \"{}\" is missing a value", param_name)),
+                }
+            }
+            Expr::Kwarg { name, value } => (name, false, *value),
+            other => return Err(format!("extfx: unexpected argument {:?}", other)),
+        };
+        values.push(Signal::Node(compile_expr(ctx, value)?));
+        params.push(crate::plugin_host::ExternalFxParam { name, exact });
+    }
+
+    let state = crate::plugin_host::ExternalFxState::new(plugin_name, params, ctx.sample_rate);
+    Ok(ctx.graph.add_node(SignalNode::ExternalFx {
+        input: Signal::Node(input),
+        params: values,
+        state: Arc::new(Mutex::new(state)),
+    }))
+}
+
 /// Compile VST parameter modifier with explicit string name
 /// Syntax: vst "Plugin" # param "Filter Cutoff" 0.5
 /// This allows setting parameters by their exact VST name
@@ -11376,6 +15133,70 @@ fn compile_vst_param(ctx: &mut CompilerContext, args: Vec<Expr>) -> Result<NodeI
     }
 }
 
+/// Compile a node registered through node_factory
+/// Syntax: saw 110 # wavefold 3 :symmetry 0.2
+/// Positional args fill the spec's parameters in order, kwargs by name, and
+/// the rest take their defaults
+fn compile_user_node(
+    ctx: &mut CompilerContext,
+    node: crate::node_factory::RegisteredNode,
+    args: Vec<Expr>,
+) -> Result<NodeId, String> {
+    let spec = &node.spec;
+    let (mut inputs, params) = if spec.takes_input {
+        if args.is_empty() {
+            return Err(format!(
+                "{} needs an input signal, e.g. saw 110 # {}",
+                spec.name, spec.name
+            ));
+        }
+        let (input, params) = extract_chain_input(ctx, &args)?;
+        (vec![input], params)
+    } else {
+        (Vec::new(), args)
+    };
+
+    let names: Vec<&str> = spec.params.iter().map(|p| p.name.as_str()).collect();
+    let extractor = ParamExtractor::new(params);
+    if extractor.positional_count() > names.len() {
+        return Err(format!(
+            "{} takes {} parameters ({}), got {}",
+            spec.name,
+            names.len(),
+            names.join(", "),
+            extractor.positional_count()
+        ));
+    }
+    if let Some(unknown) = extractor
+        .kwargs
+        .keys()
+        .find(|k| !names.contains(&k.as_str()))
+    {
+        return Err(format!(
+            "{} has no parameter '{}' (parameters: {})",
+            spec.name,
+            unknown,
+            names.join(", ")
+        ));
+    }
+
+    for (index, param) in spec.params.iter().enumerate() {
+        let expr = extractor.get_optional(index, &param.name, param.default);
+        inputs.push(Signal::Node(compile_expr(ctx, expr)?));
+    }
+
+    let state = crate::node_factory::UserNodeState::new(
+        node.factory.clone(),
+        inputs.len(),
+        ctx.sample_rate,
+    );
+    Ok(ctx.graph.add_node(SignalNode::UserNode {
+        name: spec.name.clone(),
+        inputs,
+        state: Arc::new(Mutex::new(state)),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -11770,6 +15591,58 @@ mod tests {
         assert!(result.is_ok(), "Failed to compile vst in bus");
     }
 
+    #[test]
+    fn test_compile_extfx() {
+        let code = r#"
+            ~drums $ saw 110
+            out $ extfx "LSP Compressor" ~drums :param "threshold" 0.3 :makeup (sine 1 * 0.5 + 0.5)
+        "#;
+        let (_, statements) = parse_program(code).unwrap();
+        let graph = compile_program(statements, 44100.0, None).expect("Failed to compile extfx");
+        let (state, values) = graph
+            .nodes
+            .iter()
+            .flatten()
+            .find_map(|node| match &**node {
+                SignalNode::ExternalFx { state, params, .. } => Some((state.clone(), params.len())),
+                _ => None,
+            })
+            .expect("no extfx node");
+        let state = state.lock().unwrap();
+        assert_eq!(state.plugin_name(), "LSP Compressor");
+        assert_eq!(values, 2);
+        let params: Vec<_> = state
+            .params()
+            .iter()
+            .map(|p| (p.name.as_str(), p.exact))
+            .collect();
+        assert_eq!(params, vec![("threshold", true), ("makeup", false)]);
+
+        // Chained, and the errors
+        for (code, ok) in [
+            (r#"out $ saw 110 # extfx "Dragonfly Room Reverb" :wet 0.4"#, true),
+            (r#"out $ extfx "LSP Compressor""#, false),
+            (r#"out $ extfx "LSP Compressor" (saw 110) :param "threshold""#, false),
+            (r#"out $ extfx "LSP Compressor" (saw 110) :param 3 0.5"#, false),
+        ] {
+            let (_, statements) = parse_program(code).unwrap();
+            assert_eq!(compile_program(statements, 44100.0, None).is_ok(), ok, "{}", code);
+        }
+
+        // A plugin that isn't installed passes its input through, a block or
+        // a sample at a time
+        let compile = |code: &str| {
+            let (_, statements) = parse_program(code).unwrap();
+            compile_program(statements, 44100.0, None).unwrap()
+        };
+        let mut dry = compile("out $ saw 110");
+        let mut wet = compile(r#"out $ saw 110 # extfx "No Such Plugin""#);
+        assert_eq!(wet.render(512), dry.render(512));
+        for _ in 0..64 {
+            assert_eq!(wet.process_sample(), dry.process_sample());
+        }
+    }
+
     // ========== parse_transform_from_call Tests ==========
 
     #[test]
@@ -11777,6 +15650,21 @@ mod tests {
         assert!(matches!(parse_transform_from_call("fast", &[Expr::Number(2.0)]), Ok(Transform::Fast(_))));
     }
 
+    #[test]
+    fn test_parse_transform_counted() {
+        assert!(matches!(
+            parse_transform_from_call("take", &[Expr::Number(8.0)]),
+            Ok(Transform::Take(_))
+        ));
+        assert!(matches!(
+            parse_transform_from_call("after", &[Expr::Number(8.0)]),
+            Ok(Transform::After {
+                transform: None,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn test_parse_transform_slow() {
         assert!(matches!(parse_transform_from_call("slow", &[Expr::Number(0.5)]), Ok(Transform::Slow(_))));
@@ -12012,4 +15900,60 @@ mod tests {
         assert!(!is_pure_transform(&Expr::Number(42.0)));
         assert!(!is_pure_transform(&Expr::String("bd sn".to_string())));
     }
+
+    #[test]
+    fn test_merge_programs_sums_outputs() {
+        let (_, drums) = parse_program("~kick $ s \"bd*4\"\nout $ ~kick").unwrap();
+        let (_, synths) = parse_program("tempo: 0.6\nout $ saw 55\no2 $ sine 220").unwrap();
+        let merged = merge_programs(vec![drums, synths]);
+
+        let outputs: Vec<&Statement> = merged
+            .iter()
+            .filter(|s| matches!(s, Statement::Output(_)))
+            .collect();
+        assert_eq!(outputs.len(), 1);
+        assert!(matches!(
+            outputs[0],
+            Statement::Output(Expr::BinOp { op: BinOp::Add, .. })
+        ));
+        assert!(merged
+            .iter()
+            .any(|s| matches!(s, Statement::OutputChannel { channel: 2, .. })));
+        assert!(merged.iter().any(|s| matches!(s, Statement::Tempo(_))));
+
+        let graph = compile_program(merged, 44100.0, None).unwrap();
+        assert!(graph.has_output());
+    }
+
+    #[test]
+    fn test_resolve_sample_alias() {
+        let aliases = HashMap::from([
+            ("kick".to_string(), "808bd:3".to_string()),
+            ("hat".to_string(), "hh27".to_string()),
+        ]);
+        assert_eq!(resolve_sample_alias(&aliases, "kick"), "808bd:3");
+        assert_eq!(resolve_sample_alias(&aliases, "kick:1"), "808bd:4");
+        assert_eq!(resolve_sample_alias(&aliases, "kick:rr"), "808bd:rr");
+        assert_eq!(resolve_sample_alias(&aliases, "hat"), "hh27");
+        assert_eq!(resolve_sample_alias(&aliases, "hat:2"), "hh27:2");
+        assert_eq!(resolve_sample_alias(&aliases, "sn:2"), "sn:2");
+        assert_eq!(resolve_sample_alias(&aliases, "~kick"), "~kick");
+        assert_eq!(resolve_sample_alias(&aliases, "~"), "~");
+
+        // Aliases apply wherever they appear in the program
+        let (rest, statements) =
+            parse_program("out $ s \"kick sn\"\nalias kick = \"808bd:3\"").unwrap();
+        assert!(rest.trim().is_empty(), "left {:?}", rest);
+        assert_eq!(
+            statements[1],
+            Statement::SampleAlias {
+                name: "kick".to_string(),
+                target: "808bd:3".to_string(),
+            }
+        );
+        assert!(compile_program(statements, 44100.0, None).is_ok());
+        let (_, statements) = parse_program("alias kick = \"808bd sn\"").unwrap();
+        let err = compile_program(statements, 44100.0, None).unwrap_err();
+        assert!(err.contains("single sample name"), "{}", err);
+    }
 }
@@ -15,14 +15,16 @@
 //! - `sum(~name[N..M])` to mix indexed buses
 
 #![allow(clippy::while_let_loop)]
+use crate::error_diagnostics::{diagnose_parse_failure, DiagnosticError};
 use crate::macro_expander::expand_macros;
+use crate::spatial::SpatialLayout;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until, take_while, take_while1},
+    bytes::complete::{tag, take_until, take_while, take_while1, take_while_m_n},
     character::complete::{alpha1, alphanumeric1, char, digit1, space0},
-    combinator::{map, not, opt, peek, recognize, value},
-    multi::{many0, separated_list0},
-    sequence::{delimited, pair, preceded, terminated, tuple},
+    combinator::{map, map_res, not, opt, peek, recognize, value},
+    multi::{many0, many1, separated_list0, separated_list1},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
@@ -57,6 +59,12 @@ pub enum Statement {
     Output(Expr),
     /// Multi-channel output: out1: expr, out2: expr, etc.
     OutputChannel { channel: usize, expr: Expr },
+    /// Headphone cue output, kept out of the main mix: cue $ expr, precue ~bus
+    Cue(Expr),
+    /// Control voltage on a hardware output channel: cvout 3 (~pitch |> voct)
+    CvOut { channel: usize, expr: Expr },
+    /// Gate on a hardware output channel: gateout 4 "t ~ t t"
+    GateOut { channel: usize, expr: Expr },
     /// Tempo: cps: 2.0 or tempo: 0.5 (cycles per second)
     Tempo(f64),
     /// BPM: bpm: 120 or bpm: 120 "4/4" (beats per minute with optional time signature)
@@ -66,6 +74,8 @@ pub enum Statement {
     },
     /// Output mixing mode: outmix: sqrt, gain, tanh, hard, none
     OutputMixMode(String),
+    /// Visuals feed: visuals: osc://localhost:3333 [fps]
+    Visuals { target: String, fps: Option<f64> },
     /// Function definition: fn name param1 param2: body
     FunctionDef {
         name: String,
@@ -87,6 +97,162 @@ pub enum Statement {
     Nudge(f64),
     /// Buffer size for audio processing: buffer: 1024
     BufferSize(usize),
+    /// Named tap: tap ~bass "bass_debug" [seconds] records the bus to bass_debug.wav
+    Tap {
+        bus: String,
+        name: String,
+        duration: Option<f64>,
+    },
+    /// Sample alias: alias kick = "808bd:3" makes `s "kick"` play 808bd:3
+    SampleAlias { name: String, target: String },
+    /// Groove: groove ~drums ~hats "mpc60_54" [amount] shifts the sample
+    /// patterns of the listed buses
+    Groove {
+        buses: Vec<String>,
+        template: String,
+        amount: Option<f64>,
+    },
+    /// Ducking: duck ~pads ~kick :amount 0.8 :release 0.25 lowers ~pads
+    /// while ~kick is sounding
+    Duck {
+        target: String,
+        trigger: String,
+        amount: Option<f64>,
+        attack: Option<f64>,
+        release: Option<f64>,
+    },
+    /// Auto-gain: autogain ~bus :target -18dB :speed slow eases the bus
+    /// toward a target RMS level. `speed` is slow, medium, fast or seconds
+    AutoGain {
+        bus: String,
+        target_db: Option<f64>,
+        speed: Option<String>,
+    },
+    /// Render assertion: assert rms(~kick) in 0.1..0.4, assert max_db(out) < 0
+    /// `target` is the bus name, or None for the main output
+    Assert {
+        metric: String,
+        target: Option<String>,
+        check: AssertCheck,
+    },
+    /// Modulation matrix: mod { ~lfo -> ~filter.cutoff * 800; ~env -> ~osc.freq * 12st }
+    Mod(Vec<ModRoute>),
+    /// Tuning: tuning 19edo, tuning "scale.scl" ["keys.kbm"], or for one
+    /// bus only: tuning ~lead 31edo
+    Tuning {
+        bus: Option<String>,
+        spec: TuningSpec,
+    },
+    /// Reference pitch: a4 432 tunes every note against A4 = 432 Hz
+    A4(f64),
+    /// Global transposition: transpose -2st (semitones)
+    Transpose(f64),
+    /// MIDI take: record ~keys 4c -> "riff1" captures the notes played into
+    /// ~keys over the next 4 cycles as the pattern %riff1
+    Record {
+        bus: String,
+        cycles: f64,
+        name: String,
+    },
+    /// Bus freeze: freeze ~pads 8c renders ~pads offline for 8 cycles and
+    /// loops the recording in its place
+    Freeze { bus: String, cycles: f64 },
+    /// Mid/side block: ms { mid: <effects>, side: <effects> } runs the
+    /// out1/out2 pair through separate mid and side chains
+    MidSide {
+        mid: Option<Expr>,
+        side: Option<Expr>,
+    },
+    /// Speaker layout: spatial: quad|5.1|foa|binaural ["hrirs"] renders
+    /// `out` once per channel, with sources placed by `# azimuth` and
+    /// `# elevation`. Binaural can name an HRIR set
+    Spatial {
+        layout: SpatialLayout,
+        hrirs: Option<String>,
+    },
+}
+
+/// The tuning a `tuning` statement selects
+#[derive(Debug, Clone, PartialEq)]
+pub enum TuningSpec {
+    /// Equal divisions of the octave: `19edo`
+    Edo(f64),
+    /// A Scala scale file, with an optional keyboard mapping file
+    Scala { scl: String, kbm: Option<String> },
+}
+
+/// One route of a `mod` block: `source -> ~bus.param * depth [:curve n]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModRoute {
+    pub source: Expr,
+    /// Bus holding the node to modulate
+    pub bus: String,
+    /// Parameter name on that node (cutoff, freq, q, ...)
+    pub param: String,
+    /// Amount the source is scaled by before it's applied
+    pub depth: f64,
+    /// Depth is in semitones (`12st`): the parameter is scaled, not offset
+    pub semitones: bool,
+    /// The source is raised to this power first (1 = linear)
+    pub curve: u32,
+}
+
+/// Condition of an `assert` statement
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssertCheck {
+    /// metric == value, !=, <, <=, >, >=
+    Compare { op: CompareOp, value: f64 },
+    /// lo <= metric <= hi, written `in lo..hi`
+    Range { lo: f64, hi: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CompareOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+        }
+    }
+}
+
+impl AssertCheck {
+    /// Whether `value` satisfies the condition
+    pub fn holds(&self, value: f64) -> bool {
+        match *self {
+            AssertCheck::Compare { op, value: v } => match op {
+                CompareOp::Eq => value == v,
+                CompareOp::Ne => value != v,
+                CompareOp::Lt => value < v,
+                CompareOp::Le => value <= v,
+                CompareOp::Gt => value > v,
+                CompareOp::Ge => value >= v,
+            },
+            AssertCheck::Range { lo, hi } => value >= lo && value <= hi,
+        }
+    }
+}
+
+impl std::fmt::Display for AssertCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssertCheck::Compare { op, value } => write!(f, "{} {}", op.symbol(), value),
+            AssertCheck::Range { lo, hi } => write!(f, "in {}..{}", lo, hi),
+        }
+    }
 }
 
 /// Expression - the core of the language
@@ -182,6 +348,16 @@ pub enum Transform {
         transforms: Vec<Transform>,
         n: Box<Expr>,
     },
+    /// foldEvery [n1, n2] transform: apply transform every n cycles for each n (Tidal)
+    FoldEveryN {
+        ns: Vec<Expr>,
+        transform: Box<Transform>,
+    },
+    /// somecyclesBy prob transform: apply transform to whole cycles with probability
+    SomecyclesBy {
+        prob: Box<Expr>,
+        transform: Box<Transform>,
+    },
     /// sometimes f: apply transform f 50% of the time (per cycle)
     Sometimes(Box<Transform>),
     /// sometimesBy prob f: apply transform f with given probability
@@ -218,7 +394,8 @@ pub enum Transform {
     Scramble(Box<Expr>),
     /// swing amount: add swing feel
     Swing(Box<Expr>),
-    /// groove preset [amount]: apply groove template (mpc, hiphop, reggae, jazz, drunken)
+    /// groove preset [amount]: apply groove template (mpc, mpc60_54, hiphop, reggae, jazz,
+    /// drunken, or a groove file)
     Groove {
         preset: Box<Expr>,
         amount: Option<Box<Expr>>,
@@ -305,7 +482,7 @@ pub enum Transform {
     Binary(Box<Expr>),
     /// range min max: scale numeric values to range (numeric patterns only)
     Range { min: Box<Expr>, max: Box<Expr> },
-    /// quantize steps: quantize numeric values (numeric patterns only)
+    /// quantize steps: snap event onsets to the nearest 1/steps of a cycle
     Quantize(Box<Expr>),
     /// focus cycle_begin cycle_end: focus on specific cycles
     Focus {
@@ -322,6 +499,21 @@ pub enum Transform {
     Log(Box<Expr>),
     /// walk step_size: random walk (numeric patterns only)
     Walk(Box<Expr>),
+    /// mutate amount [seed]: vary a fraction of the events each cycle
+    Mutate {
+        amount: Box<Expr>,
+        seed: Option<Box<Expr>>,
+    },
+    /// markov [cycles]: regenerate from a Markov chain learned from the pattern
+    Markov(Option<Box<Expr>>),
+    /// take cycles: play for `cycles` cycles, then go silent
+    Take(Box<Expr>),
+    /// after cycles [transform]: wait `cycles` cycles, then play (or apply
+    /// the transform from then on)
+    After {
+        cycles: Box<Expr>,
+        transform: Option<Box<Transform>>,
+    },
     /// inside begin end transform: apply transform inside time range
     Inside {
         begin: Box<Expr>,
@@ -375,6 +567,7 @@ pub enum Transform {
         velocity_var: Box<Expr>,
     },
     /// within begin end transform: apply transform within time window
+    /// (also written Tidal-style as `within (begin, end) transform`)
     Within {
         begin: Box<Expr>,
         end: Box<Expr>,
@@ -433,12 +626,25 @@ pub enum BinOp {
     UnionLeft,  // |> (structure from left, values from right) - same as #
     UnionRight, // <| (structure from right, values from left)
 
+    // Both-structure operators (Tidal's |op|)
+    // Events are split wherever either side has a boundary
+    AddBoth,       // |+|
+    SubBoth,       // |-|
+    MulBoth,       // |*|
+    DivBoth,       // |/|
+    UnionBoth,     // |>| (values from right)
+    UnionBothLeft, // |<| (values from left)
+
     // Signal operators (audio-rate, sample-by-sample)
     // Use ~ prefix to distinguish from pattern operators
     SignalAdd, // ~+
     SignalSub, // ~-
     SignalMul, // ~*
     SignalDiv, // ~/
+
+    // Random spread: a value between the two sides, drawn per trigger when
+    // used as a parameter (`# gain (0.9 ~ 1.0)`)
+    Spread, // ~ (with spaces around it, unlike a bus reference)
 }
 
 /// Unary operators
@@ -510,53 +716,10 @@ fn preprocess_multiline(input: &str) -> String {
             continue;
         }
 
-        // Check if this line starts a new definition
-        // A definition line has the pattern: identifier followed by $, #, or : (for tempo/bpm/outmix)
-        // Examples: tempo:, out $, o1 $, d1 #, ~bus $, fn name = ..., etc.
-        let is_definition = {
-            let mut found = false;
-
-            // Check for $ separator: ~bus $ expr, out $ expr
-            if let Some(dollar_pos) = trimmed.find('$') {
-                let before = trimmed[..dollar_pos].trim();
-                if !before.is_empty()
-                    && before.chars().all(|c| c.is_alphanumeric() || c == '~' || c == '_')
-                {
-                    found = true;
-                }
-            }
-
-            // Check for # separator: ~bus # expr (modifier bus)
-            if !found {
-                if let Some(hash_pos) = trimmed.find('#') {
-                    let before = trimmed[..hash_pos].trim();
-                    if !before.is_empty()
-                        && before.chars().all(|c| c.is_alphanumeric() || c == '~' || c == '_')
-                    {
-                        found = true;
-                    }
-                }
-            }
-
-            // Check for : separator: ~bus: expr, tempo: val, cps: val
-            if !found {
-                if let Some(colon_pos) = trimmed.find(':') {
-                    let before = &trimmed[..colon_pos];
-                    if !before.is_empty()
-                        && before.chars().all(|c| c.is_alphanumeric() || c == '~' || c == '_')
-                    {
-                        found = true;
-                    }
-                }
-            }
-
-            // Function definitions
-            if !found && trimmed.starts_with("fn ") {
-                found = true;
-            }
-
-            found
-        };
+        // Lines inside an open `{ ... }` block (`mid: ...`) belong to it
+        let in_block =
+            current_statement.matches('{').count() > current_statement.matches('}').count();
+        let is_definition = !in_block && is_statement_start(trimmed);
 
         if is_definition {
             // Push accumulated statement if any
@@ -583,6 +746,92 @@ fn preprocess_multiline(input: &str) -> String {
     result.join("\n")
 }
 
+/// Heads of statements that have no `$`, `#` or `:` separator. A line
+/// starting with one is never joined onto the statement above it
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "fn ",        // fn name a b = ...
+    "freeze ~",   // freeze ~pads 8c
+    "tap ~",      // tap ~bass "bass_debug"
+    "assert ",    // assert rms(~kick) in 0.1..0.4
+    "record ~",   // record ~midi 4c -> "riff"
+    "alias ",     // alias kick = "808bd:3"
+    "tuning ",    // tuning ~lead 19edo
+    "a4 ",        // a4 432Hz
+    "transpose ", // transpose -2st
+    "duck ~",     // duck ~pads ~kick :amount 0.8
+    "autogain ~", // autogain ~lead :target -18dB
+    "groove ~",   // groove ~drums "mpc60_66"
+    "precue ",    // precue ~next
+    "cvout ",     // cvout 3 (~pitch |> voct)
+    "gateout ",   // gateout 4 "t ~ t t"
+];
+
+/// Whether a (trimmed, non-comment) line starts a new statement rather than
+/// continuing the previous one
+///
+/// A definition line has the pattern: identifier followed by $, #, or : (for tempo/bpm/outmix)
+/// Examples: tempo:, out $, o1 $, d1 #, ~bus $, fn name = ..., etc.
+fn is_statement_start(trimmed: &str) -> bool {
+    let mut found = false;
+
+    // Check for $ separator: ~bus $ expr, out $ expr
+    if let Some(dollar_pos) = trimmed.find('$') {
+        let before = trimmed[..dollar_pos].trim();
+        if !before.is_empty()
+            && before
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '~' || c == '_')
+        {
+            found = true;
+        }
+    }
+
+    // Check for # separator: ~bus # expr (modifier bus)
+    if !found {
+        if let Some(hash_pos) = trimmed.find('#') {
+            let before = trimmed[..hash_pos].trim();
+            if !before.is_empty()
+                && before
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '~' || c == '_')
+            {
+                found = true;
+            }
+        }
+    }
+
+    // Check for : separator: ~bus: expr, tempo: val, cps: val
+    if !found {
+        if let Some(colon_pos) = trimmed.find(':') {
+            let before = &trimmed[..colon_pos];
+            if !before.is_empty()
+                && before
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '~' || c == '_')
+            {
+                found = true;
+            }
+        }
+    }
+
+    // Keyword statements: fn definitions, freeze, tap, ...
+    if !found && STATEMENT_KEYWORDS.iter().any(|k| trimmed.starts_with(k)) {
+        found = true;
+    }
+
+    // Modulation matrix: `mod {` opens a block whose routes follow
+    if !found && trimmed.starts_with("mod") && trimmed[3..].trim_start().starts_with('{') {
+        found = true;
+    }
+
+    // Mid/side block: `ms {`
+    if !found && trimmed.starts_with("ms") && trimmed[2..].trim_start().starts_with('{') {
+        found = true;
+    }
+
+    found
+}
+
 /// Parse the statements of an already-preprocessed program from a borrowed slice.
 ///
 /// The returned remaining-input slice (and any error) borrows `input`, but the
@@ -664,6 +913,101 @@ pub fn parse_program(input: &str) -> IResult<&str, Vec<Statement>> {
     }
 }
 
+/// Parse a program, skipping the statements that don't parse instead of
+/// stopping at the first one
+///
+/// Each skipped statement gets a diagnostic pointing at where it went wrong;
+/// the rest are returned in order, so one typo'd line doesn't take the whole
+/// set down with it. A program that parses in full gives the same
+/// statements as `parse_program`.
+pub fn parse_program_recovering(input: &str) -> (Vec<Statement>, Vec<DiagnosticError>) {
+    if let Ok((rest, statements)) = parse_program(input) {
+        if rest.trim().is_empty() {
+            return (statements, Vec::new());
+        }
+    }
+
+    let mut statements = Vec::new();
+    let mut diagnostics = Vec::new();
+    for (start, end) in statement_spans(input) {
+        let source = &input[start..end];
+        let preprocessed = preprocess_multiline(source);
+        let stopped = match parse_statements(&preprocessed) {
+            Ok((rest, parsed)) if rest.trim().is_empty() => {
+                statements.extend(parsed);
+                continue;
+            }
+            Ok((rest, _)) => statement_error_position(rest),
+            Err(_) => statement_error_position(&preprocessed),
+        };
+        // A one-line statement ends like its preprocessed text; continuation
+        // lines are re-flowed, so find the offending token instead
+        let offset = if source.contains('\n') {
+            source.len() - map_remaining(source, stopped).len()
+        } else {
+            source.trim_end().len() - stopped.trim_end().len()
+        };
+        diagnostics.push(diagnose_parse_failure(input, &input[start + offset..]));
+    }
+    (statements, diagnostics)
+}
+
+/// Byte ranges of `input` holding one statement each, continuation lines
+/// included, grouped as `preprocess_multiline` joins them
+fn statement_spans(input: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let line = line.trim_end_matches(['\n', '\r']);
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("--") {
+            spans.extend(current.take());
+            continue;
+        }
+        let end = start + line.len();
+        match current {
+            Some((span_start, span_end)) => {
+                let text = &input[span_start..span_end];
+                let in_block = text.matches('{').count() > text.matches('}').count();
+                if in_block || !is_statement_start(trimmed) {
+                    current = Some((span_start, end));
+                } else {
+                    spans.push((span_start, span_end));
+                    current = Some((start, end));
+                }
+            }
+            None => current = Some((start, end)),
+        }
+    }
+    spans.extend(current);
+    spans
+}
+
+/// Where the statement at the start of `input` went wrong: for a statement
+/// with a well-formed head (`~bass $`, `out $`, `tempo:`), where its
+/// expression stops parsing
+fn statement_error_position(input: &str) -> &str {
+    let Some(separator) = input.find(['$', '#', ':']) else {
+        return input;
+    };
+    let head = input[..separator].trim();
+    let is_head = !head.is_empty()
+        && head
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '~' || c == '_' || c == ' ');
+    if !is_head {
+        return input;
+    }
+    let body = input[separator + 1..].trim_start();
+    match parse_expr(body) {
+        Ok((rest, _)) if !rest.trim().is_empty() => rest.trim_start(),
+        _ => body,
+    }
+}
+
 /// Parse a program with macro expansion
 ///
 /// This is the recommended entry point for parsing Phonon code.
@@ -703,10 +1047,29 @@ fn parse_statement(input: &str) -> IResult<&str, Statement> {
         parse_unhush,       // Try unhush command (before hush to avoid prefix match)
         parse_hush,         // Try hush/hushN command
         parse_panic,        // Try panic command
+        alt((
+            parse_tap,      // Try named bus tap
+            parse_alias,    // Try sample alias
+            parse_groove,   // Try bus groove
+            parse_duck,     // Try bus ducking
+            parse_autogain, // Try bus auto-gain
+            parse_mod,      // Try modulation matrix
+            parse_tuning,   // Try tuning
+            parse_a4,       // Try reference pitch
+            parse_transpose, // Try global transposition
+            parse_record,   // Try MIDI take recording
+            parse_freeze,   // Try bus freeze
+            parse_visuals,  // Try visuals feed
+            parse_ms,       // Try mid/side block
+            parse_spatial,  // Try speaker layout
+        )),
+        parse_assert, // Try render assertion
         parse_bus_assignment,
         parse_template_assignment,
         parse_pattern_assignment,
         parse_output_or_channel, // Try output (combines channel + single)
+        parse_cue,               // Headphone cue output
+        parse_control_out,       // CV / gate outputs
         parse_bpm,               // Try BPM before tempo (bpm: vs tempo:)
         parse_tempo,
         parse_buffer_size,       // Buffer size configuration
@@ -894,6 +1257,46 @@ fn parse_output_or_channel(input: &str) -> IResult<&str, Statement> {
     }
 }
 
+/// Parse headphone cue output: cue $ expr, or precue ~bus
+fn parse_cue(input: &str) -> IResult<&str, Statement> {
+    alt((
+        map(
+            preceded(
+                terminated(keyword("precue"), hspace1),
+                preceded(char('~'), parse_identifier),
+            ),
+            |bus: &str| Statement::Cue(Expr::BusRef(bus.to_string())),
+        ),
+        map(
+            preceded(
+                tuple((keyword("cue"), space0, alt((char('$'), char(':'))), space0)),
+                parse_expr,
+            ),
+            Statement::Cue,
+        ),
+    ))(input)
+}
+
+/// Parse control voltage outputs: cvout 3 expr, gateout 4 expr
+fn parse_control_out(input: &str) -> IResult<&str, Statement> {
+    let (input, gate) = alt((
+        map(keyword("cvout"), |_| false),
+        map(keyword("gateout"), |_| true),
+    ))(input)?;
+    let (input, _) = hspace1(input)?;
+    let (input, channel) = map_res(digit1, |d: &str| d.parse::<usize>())(input)?;
+    let (input, _) = space0(input)?;
+    let (input, _) = opt(alt((char('$'), char(':'))))(input)?;
+    let (input, _) = space0(input)?;
+    let (input, expr) = parse_expr(input)?;
+    let statement = if gate {
+        Statement::GateOut { channel, expr }
+    } else {
+        Statement::CvOut { channel, expr }
+    };
+    Ok((input, statement))
+}
+
 /// Parse tempo: cps: 2.0 or tempo: 0.5 (cycles per second)
 fn parse_tempo(input: &str) -> IResult<&str, Statement> {
     let (input, _) = alt((tag("cps"), tag("tempo")))(input)?;
@@ -968,6 +1371,23 @@ fn parse_outmix(input: &str) -> IResult<&str, Statement> {
     Ok((input, Statement::OutputMixMode(mode.to_string())))
 }
 
+/// Parse visuals feed: visuals: osc://host:port [fps]
+fn parse_visuals(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = tuple((keyword("visuals"), space0, char(':'), space0))(input)?;
+    let (input, target) = take_while1(|c: char| !c.is_whitespace())(input)?;
+    let (input, fps) = opt(preceded(
+        hspace1,
+        terminated(parse_number, opt(keyword("fps"))),
+    ))(input)?;
+    Ok((
+        input,
+        Statement::Visuals {
+            target: target.to_string(),
+            fps,
+        },
+    ))
+}
+
 /// Parse hush command: silence outputs (hush = all, hush1 = channel 1, etc.)
 fn parse_hush(input: &str) -> IResult<&str, Statement> {
     let (input, _) = tag("hush")(input)?;
@@ -1012,6 +1432,346 @@ fn parse_nudge(input: &str) -> IResult<&str, Statement> {
     Ok((input, Statement::Nudge(amount)))
 }
 
+/// Parse named tap: tap ~bus "name" [seconds]
+fn parse_tap(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(tag("tap"), hspace1)(input)?;
+    let (input, _) = char('~')(input)?;
+    let (input, bus) = parse_identifier(input)?;
+    let (input, _) = hspace1(input)?;
+    let (input, name) = delimited(char('"'), take_until("\""), char('"'))(input)?;
+    let (input, duration) = opt(preceded(hspace1, parse_number))(input)?;
+    Ok((
+        input,
+        Statement::Tap {
+            bus: bus.to_string(),
+            name: name.to_string(),
+            duration,
+        },
+    ))
+}
+
+/// Parse sample alias: alias kick = "808bd:3"
+fn parse_alias(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(tag("alias"), hspace1)(input)?;
+    let (input, name) = parse_identifier(input)?;
+    let (input, _) = delimited(space0, char('='), space0)(input)?;
+    let (input, target) = delimited(char('"'), take_until("\""), char('"'))(input)?;
+    Ok((
+        input,
+        Statement::SampleAlias {
+            name: name.to_string(),
+            target: target.trim().to_string(),
+        },
+    ))
+}
+
+/// Parse bus groove: groove ~drums ~hats "mpc60_54" [amount]
+fn parse_groove(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(tag("groove"), hspace1)(input)?;
+    let (input, buses) = many1(terminated(preceded(char('~'), parse_identifier), hspace1))(input)?;
+    let (input, template) = delimited(char('"'), take_until("\""), char('"'))(input)?;
+    let (input, amount) = opt(preceded(hspace1, parse_number))(input)?;
+    Ok((
+        input,
+        Statement::Groove {
+            buses: buses.into_iter().map(str::to_string).collect(),
+            template: template.to_string(),
+            amount,
+        },
+    ))
+}
+
+/// Parse bus ducking: duck ~pads ~kick [:amount 0.8] [:attack 0.005] [:release 0.25]
+fn parse_duck(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(tag("duck"), hspace1)(input)?;
+    let (input, target) = terminated(preceded(char('~'), parse_identifier), hspace1)(input)?;
+    let (input, trigger) = preceded(char('~'), parse_identifier)(input)?;
+    let (input, options) = many0(preceded(
+        hspace1,
+        separated_pair(
+            preceded(
+                char(':'),
+                alt((tag("amount"), tag("attack"), tag("release"))),
+            ),
+            hspace1,
+            parse_number,
+        ),
+    ))(input)?;
+    let option = |key: &str| {
+        options
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    };
+    Ok((
+        input,
+        Statement::Duck {
+            target: target.to_string(),
+            trigger: trigger.to_string(),
+            amount: option("amount"),
+            attack: option("attack"),
+            release: option("release"),
+        },
+    ))
+}
+
+/// Parse bus auto-gain: autogain ~bus [:target -18dB] [:speed slow|medium|fast|seconds]
+fn parse_autogain(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(tag("autogain"), hspace1)(input)?;
+    let (input, bus) = preceded(char('~'), parse_identifier)(input)?;
+    let (input, target_db) = opt(preceded(
+        tuple((hspace1, tag(":target"), hspace1)),
+        terminated(parse_number, opt(alt((tag("dB"), tag("db"))))),
+    ))(input)?;
+    let (input, speed) = opt(preceded(
+        tuple((hspace1, tag(":speed"), hspace1)),
+        take_while1(|c: char| c.is_alphanumeric() || c == '.'),
+    ))(input)?;
+    Ok((
+        input,
+        Statement::AutoGain {
+            bus: bus.to_string(),
+            target_db,
+            speed: speed.map(str::to_string),
+        },
+    ))
+}
+
+/// Parse tuning: tuning [~bus] 19edo | "scale.scl" ["keys.kbm"]
+fn parse_tuning(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(tag("tuning"), hspace1)(input)?;
+    let (input, bus) = opt(terminated(preceded(char('~'), parse_identifier), hspace1))(input)?;
+    let quoted = || delimited(char('"'), take_until("\""), char('"'));
+    let (input, spec) = alt((
+        map(terminated(parse_number, keyword("edo")), TuningSpec::Edo),
+        map(
+            pair(quoted(), opt(preceded(hspace1, quoted()))),
+            |(scl, kbm): (&str, Option<&str>)| TuningSpec::Scala {
+                scl: scl.to_string(),
+                kbm: kbm.map(str::to_string),
+            },
+        ),
+    ))(input)?;
+    Ok((
+        input,
+        Statement::Tuning {
+            bus: bus.map(str::to_string),
+            spec,
+        },
+    ))
+}
+
+/// Parse reference pitch: a4 432 [Hz]
+fn parse_a4(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(keyword("a4"), hspace1)(input)?;
+    let (input, hz) = terminated(parse_number, opt(keyword("Hz")))(input)?;
+    Ok((input, Statement::A4(hz)))
+}
+
+/// Parse global transposition: transpose -2st (or a bare semitone count)
+fn parse_transpose(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(keyword("transpose"), hspace1)(input)?;
+    let (input, semitones) = terminated(parse_number, opt(keyword("st")))(input)?;
+    Ok((input, Statement::Transpose(semitones)))
+}
+
+/// Parse MIDI take recording: record ~bus 4c -> "name"
+fn parse_record(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(keyword("record"), hspace1)(input)?;
+    let (input, bus) = preceded(char('~'), parse_identifier)(input)?;
+    let (input, _) = hspace1(input)?;
+    let (input, cycles) = terminated(parse_number, opt(char('c')))(input)?;
+    let (input, _) = delimited(space0, tag("->"), space0)(input)?;
+    let (input, name) = delimited(char('"'), take_until("\""), char('"'))(input)?;
+    Ok((
+        input,
+        Statement::Record {
+            bus: bus.to_string(),
+            cycles,
+            name: name.to_string(),
+        },
+    ))
+}
+
+/// Parse bus freeze: freeze ~bus 8c
+fn parse_freeze(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(keyword("freeze"), hspace1)(input)?;
+    let (input, bus) = preceded(char('~'), parse_identifier)(input)?;
+    let (input, _) = hspace1(input)?;
+    let (input, cycles) = terminated(parse_number, opt(char('c')))(input)?;
+    Ok((
+        input,
+        Statement::Freeze {
+            bus: bus.to_string(),
+            cycles,
+        },
+    ))
+}
+
+/// Parse modulation matrix: mod { source -> ~bus.param [* depth[st]] [:curve n]; ... }
+///
+/// Routes are separated by `;` or whitespace (multi-line blocks arrive
+/// joined onto one line)
+fn parse_mod(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(keyword("mod"), space0)(input)?;
+    let (input, _) = char('{')(input)?;
+    let (input, routes) = many0(preceded(skip_mod_separators, parse_mod_route))(input)?;
+    let (input, _) = preceded(skip_mod_separators, char('}'))(input)?;
+    Ok((input, Statement::Mod(routes)))
+}
+
+/// Skip whitespace, comments and `;` between the routes of a `mod` block
+fn skip_mod_separators(input: &str) -> IResult<&str, ()> {
+    let (mut input, _) = skip_space_and_comments(input)?;
+    while let Some(rest) = input.strip_prefix(';') {
+        input = skip_space_and_comments(rest)?.0;
+    }
+    Ok((input, ()))
+}
+
+fn parse_mod_route(input: &str) -> IResult<&str, ModRoute> {
+    let (input, source) = parse_non_greedy_arg(input)?;
+    let (input, _) = tuple((space0, tag("->"), space0))(input)?;
+    let (input, bus) = preceded(char('~'), parse_identifier)(input)?;
+    let (input, param) = preceded(char('.'), parse_identifier)(input)?;
+    let (input, depth) = opt(preceded(
+        tuple((space0, char('*'), space0)),
+        pair(parse_number, opt(keyword("st"))),
+    ))(input)?;
+    let (input, curve) = opt(preceded(tuple((hspace1, tag(":curve"), hspace1)), digit1))(input)?;
+    let (depth, semitones) = depth
+        .map(|(depth, st)| (depth, st.is_some()))
+        .unwrap_or((1.0, false));
+    Ok((
+        input,
+        ModRoute {
+            source,
+            bus: bus.to_string(),
+            param: param.to_string(),
+            depth,
+            semitones,
+            curve: curve.and_then(|n| n.parse().ok()).unwrap_or(1),
+        },
+    ))
+}
+
+/// Parse mid/side block: ms { mid: <effects>, side: <effects> }
+///
+/// Each chain runs until the next `mid:`/`side:` label or the closing
+/// brace, so the two may be separated by `,`, `;` or a line break
+fn parse_ms(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(keyword("ms"), space0)(input)?;
+    let (input, _) = char('{')(input)?;
+    let (rest, body) = terminated(take_until("}"), char('}'))(input)?;
+
+    let fail = || nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify));
+    let mut labels: Vec<(usize, &str)> = body
+        .match_indices("mid:")
+        .chain(body.match_indices("side:"))
+        .filter(|(i, _)| {
+            body[..*i]
+                .chars()
+                .next_back()
+                .map_or(true, |c| c.is_whitespace() || c == ',' || c == ';')
+        })
+        .collect();
+    labels.sort_unstable();
+    if labels.is_empty() || !body[..labels[0].0].trim().is_empty() {
+        return Err(fail());
+    }
+
+    let (mut mid, mut side) = (None, None);
+    for (n, &(start, label)) in labels.iter().enumerate() {
+        let end = labels.get(n + 1).map_or(body.len(), |&(next, _)| next);
+        let text = body[start + label.len()..end]
+            .trim()
+            .trim_end_matches([',', ';'])
+            .trim();
+        let expr = match parse_expr(text) {
+            Ok((left, expr)) if left.trim().is_empty() => expr,
+            _ => return Err(fail()),
+        };
+        let slot = if label == "mid:" { &mut mid } else { &mut side };
+        if slot.replace(expr).is_some() {
+            return Err(fail());
+        }
+    }
+    Ok((rest, Statement::MidSide { mid, side }))
+}
+
+/// Parse speaker layout: spatial: quad|4.0|5.1|foa|binaural ["hrir set"]
+fn parse_spatial(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = tuple((tag("spatial"), space0, char(':'), space0))(input)?;
+    let (rest, name) = take_while1(|c: char| c.is_alphanumeric() || c == '.')(input)?;
+    let (rest, hrirs) = opt(preceded(
+        hspace1,
+        delimited(char('"'), take_until("\""), char('"')),
+    ))(rest)?;
+    match SpatialLayout::from_name(name) {
+        Some(layout) if hrirs.is_none() || layout == SpatialLayout::Binaural => Ok((
+            rest,
+            Statement::Spatial {
+                layout,
+                hrirs: hrirs.map(str::to_string),
+            },
+        )),
+        _ => Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        ))),
+    }
+}
+
+/// Parse render assertion: assert metric(~bus|out) <op> value | in lo..hi
+fn parse_assert(input: &str) -> IResult<&str, Statement> {
+    let (input, _) = terminated(tag("assert"), hspace1)(input)?;
+    let (input, metric) = parse_identifier(input)?;
+    let (input, _) = terminated(char('('), space0)(input)?;
+    let (input, target) = alt((
+        map(preceded(char('~'), parse_identifier), |bus: &str| {
+            Some(bus.to_string())
+        }),
+        map(keyword("out"), |_| None),
+    ))(input)?;
+    let (input, _) = preceded(space0, char(')'))(input)?;
+    let (input, _) = space0(input)?;
+
+    let (input, check) = alt((
+        map(
+            preceded(
+                terminated(keyword("in"), space0),
+                separated_pair(parse_number, tag(".."), parse_number),
+            ),
+            |(lo, hi)| AssertCheck::Range { lo, hi },
+        ),
+        map(
+            separated_pair(
+                alt((
+                    value(CompareOp::Eq, tag("==")),
+                    value(CompareOp::Ne, tag("!=")),
+                    value(CompareOp::Le, tag("<=")),
+                    value(CompareOp::Ge, tag(">=")),
+                    value(CompareOp::Lt, tag("<")),
+                    value(CompareOp::Gt, tag(">")),
+                )),
+                space0,
+                parse_number,
+            ),
+            |(op, value)| AssertCheck::Compare { op, value },
+        ),
+    ))(input)?;
+
+    Ok((
+        input,
+        Statement::Assert {
+            metric: metric.to_string(),
+            target,
+            check,
+        },
+    ))
+}
+
 // ============================================================================
 // Expression parsing with proper precedence
 // ============================================================================
@@ -1364,6 +2124,7 @@ fn parse_transform_expr(input: &str) -> IResult<&str, Expr> {
 
 /// Parse additive expression: expr + expr | expr - expr
 /// Also handles Tidal pattern structure operators: |+, +|, |-, -|, |>, <|
+/// and their both-structure forms: |+|, |-|, |>|, |<|
 /// Also handles signal operators: ~+, ~-
 fn parse_additive_expr(input: &str) -> IResult<&str, Expr> {
     let (input, mut expr) = parse_multiplicative_expr(input)?;
@@ -1378,6 +2139,14 @@ fn parse_additive_expr(input: &str) -> IResult<&str, Expr> {
             Some((input, BinOp::SignalAdd))
         } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("~-")(input) {
             Some((input, BinOp::SignalSub))
+        } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|+|")(input) {
+            Some((input, BinOp::AddBoth))
+        } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|-|")(input) {
+            Some((input, BinOp::SubBoth))
+        } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|>|")(input) {
+            Some((input, BinOp::UnionBoth))
+        } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|<|")(input) {
+            Some((input, BinOp::UnionBothLeft))
         } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|+")(input) {
             Some((input, BinOp::AddLeft))
         } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("+|")(input) {
@@ -1395,6 +2164,8 @@ fn parse_additive_expr(input: &str) -> IResult<&str, Expr> {
             Some((input, BinOp::UnionLeft))
         } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("<|")(input) {
             Some((input, BinOp::UnionRight))
+        } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("~ ")(input) {
+            Some((input, BinOp::Spread))
         } else if let Ok((input, _)) = char::<_, nom::error::Error<&str>>('+')(input) {
             Some((input, BinOp::Add))
         } else if let Ok((input, _)) = char::<_, nom::error::Error<&str>>('-')(input) {
@@ -1429,6 +2200,7 @@ fn parse_additive_expr(input: &str) -> IResult<&str, Expr> {
 
 /// Parse multiplicative expression: expr * expr | expr / expr
 /// Also handles Tidal pattern structure operators: |*, *|, |/, /|
+/// and their both-structure forms: |*|, |/|
 /// Also handles signal operators: ~*, ~/
 fn parse_multiplicative_expr(input: &str) -> IResult<&str, Expr> {
     let (input, mut expr) = parse_unary_expr(input)?;
@@ -1443,6 +2215,10 @@ fn parse_multiplicative_expr(input: &str) -> IResult<&str, Expr> {
             Some((input, BinOp::SignalMul))
         } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("~/")(input) {
             Some((input, BinOp::SignalDiv))
+        } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|*|")(input) {
+            Some((input, BinOp::MulBoth))
+        } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|/|")(input) {
+            Some((input, BinOp::DivBoth))
         } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("|*")(input) {
             Some((input, BinOp::MulLeft))
         } else if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("*|")(input) {
@@ -1479,6 +2255,11 @@ fn parse_multiplicative_expr(input: &str) -> IResult<&str, Expr> {
 
 /// Parse unary expression: -expr
 fn parse_unary_expr(input: &str) -> IResult<&str, Expr> {
+    // A unit literal keeps its sign: `-6dB` is a gain of 0.5, not -2
+    if let Ok(result) = parse_unit_literal(input) {
+        return Ok(result);
+    }
+
     // Try unary minus
     if let Ok((input, _)) = char::<_, nom::error::Error<&str>>('-')(input) {
         let (input, _) = space0(input)?;
@@ -1500,6 +2281,8 @@ fn parse_primary_expr(input: &str) -> IResult<&str, Expr> {
     let (input, _) = space0(input)?;
 
     alt((
+        parse_cycles_literal,
+        parse_unit_literal,
         map(parse_number, Expr::Number),
         parse_string_literal,
         parse_signal_function_call, // Try ~add, ~sub, ~mul, ~div before bus call/ref
@@ -1507,6 +2290,7 @@ fn parse_primary_expr(input: &str) -> IResult<&str, Expr> {
         parse_bus_ref_expr,         // Then try ~name (simple bus reference)
         parse_template_ref_expr,
         parse_pattern_ref_expr,
+        parse_sharp_note_literal,
         parse_function_call, // Try function call first (requires space + args)
         parse_var,           // Then try bare variable (no args)
         parse_list_expr,
@@ -1530,8 +2314,12 @@ fn parse_list_expr(input: &str) -> IResult<&str, Expr> {
     let (input, _) = char('[')(input)?;
     let (input, _) = space0(input)?;
 
-    // Parse comma-separated expressions
-    let (input, exprs) = separated_list0(delimited(space0, char(','), space0), parse_expr)(input)?;
+    // Parse comma-separated expressions. Items can be named with `:name`,
+    // as in `ur`'s fragments: [:intro "bd*2", :drop "bd*4"]
+    let (input, exprs) = separated_list0(
+        delimited(space0, char(','), space0),
+        alt((parse_kwarg, parse_expr)),
+    )(input)?;
 
     let (input, _) = space0(input)?;
     let (input, _) = char(']')(input)?;
@@ -1682,6 +2470,23 @@ fn parse_pattern_ref_expr(input: &str) -> IResult<&str, Expr> {
     Ok((input, Expr::PatternRef(name.to_string())))
 }
 
+/// Parse a sharp note name: `a#4`, `f#-1`. It becomes the variable `as4`,
+/// like other note names (`c3`, `ef5`), which compile to their frequency
+fn parse_sharp_note_literal(input: &str) -> IResult<&str, Expr> {
+    let (input, (letter, octave)) = terminated(
+        separated_pair(
+            take_while_m_n(1, 1, |c: char| matches!(c.to_ascii_lowercase(), 'a'..='g')),
+            char('#'),
+            recognize(pair(opt(char('-')), digit1)),
+        ),
+        not(take_while1(|c: char| c.is_alphanumeric() || c == '_')),
+    )(input)?;
+    Ok((
+        input,
+        Expr::Var(format!("{}s{}", letter.to_ascii_lowercase(), octave)),
+    ))
+}
+
 /// Parse variable reference (bare identifier)
 fn parse_var(input: &str) -> IResult<&str, Expr> {
     let (input, name) = parse_identifier(input)?;
@@ -1710,9 +2515,10 @@ fn parse_kwarg(input: &str) -> IResult<&str, Expr> {
         )));
     }
 
-    // Require space before value
+    // Require space before value. Values are single arguments, so a bare
+    // word (`:shape tri :phase 0.25`) never swallows the next kwarg
     let (rest, _) = space1(rest)?;
-    let (rest, value) = parse_primary_expr(rest)?;
+    let (rest, value) = parse_non_greedy_arg(rest)?;
 
     Ok((
         rest,
@@ -1730,6 +2536,8 @@ fn parse_non_greedy_arg(input: &str) -> IResult<&str, Expr> {
     let (input, _) = space0(input)?;
 
     alt((
+        parse_cycles_literal,
+        parse_unit_literal,
         map(parse_number, Expr::Number),
         parse_string_literal,
         parse_signal_function_call, // ~add, ~sub, ~mul, ~div
@@ -1738,6 +2546,7 @@ fn parse_non_greedy_arg(input: &str) -> IResult<&str, Expr> {
         parse_pattern_ref_expr,
         parse_paren_expr,           // For complex expressions, use parens
         parse_list_expr,
+        parse_sharp_note_literal,
         parse_var,                  // Bare variable
         // NOTE: parse_bus_call_expr is NOT included - use parens for bus calls in args
         // NOTE: parse_function_call is NOT included - use parens for nested calls
@@ -1797,6 +2606,7 @@ fn parse_transform(input: &str) -> IResult<&str, Transform> {
         parse_transform_group_2,
         parse_transform_group_3,
         parse_transform_group_4,
+        parse_transform_group_5,
     ))(input)
 }
 
@@ -2091,9 +2901,28 @@ fn parse_transform_group_1b(input: &str) -> IResult<&str, Transform> {
     ))(input)
 }
 
-/// Parse conditional transforms (every', whenmod, foldEvery)
+/// Parse conditional transforms (every', whenmod, foldEvery, somecyclesBy)
 fn parse_conditional_transforms(input: &str) -> IResult<&str, Transform> {
     alt((
+        // foldEvery [n1, n2] transform (Tidal form: list of cycle counts)
+        map(
+            tuple((
+                terminated(tag("foldEvery"), space1),
+                delimited(
+                    terminated(char('['), space0),
+                    separated_list1(
+                        delimited(space0, char(','), space0),
+                        map(parse_number, Expr::Number),
+                    ),
+                    preceded(space0, char(']')),
+                ),
+                preceded(space1, parse_transform),
+            )),
+            |(_, ns, transform)| Transform::FoldEveryN {
+                ns,
+                transform: Box::new(transform),
+            },
+        ),
         // foldEvery [t1, t2, t3] n (MUST come first to avoid conflicts)
         map(
             tuple((
@@ -2134,9 +2963,51 @@ fn parse_conditional_transforms(input: &str) -> IResult<&str, Transform> {
                 transform: Box::new(transform),
             },
         ),
+        // somecyclesBy prob transform (MUST come before somecycles)
+        map(
+            tuple((
+                terminated(tag("somecyclesBy"), space1),
+                terminated(parse_primary_expr, space1),
+                parse_transform,
+            )),
+            |(_, prob, transform)| Transform::SomecyclesBy {
+                prob: Box::new(prob),
+                transform: Box::new(transform),
+            },
+        ),
+        // somecycles transform (50% of cycles)
+        map(
+            preceded(terminated(tag("somecycles"), space1), parse_transform),
+            |transform| Transform::SomecyclesBy {
+                prob: Box::new(Expr::Number(0.5)),
+                transform: Box::new(transform),
+            },
+        ),
     ))(input)
 }
 
+/// Parse within: `within begin end transform` or Tidal's `within (begin, end) transform`
+fn parse_within(input: &str) -> IResult<&str, Transform> {
+    let (input, _) = terminated(tag("within"), space1)(input)?;
+    let (input, (begin, end)) = alt((
+        delimited(
+            terminated(char('('), space0),
+            separated_pair(parse_expr, delimited(space0, char(','), space0), parse_expr),
+            preceded(space0, char(')')),
+        ),
+        separated_pair(parse_primary_expr, space1, parse_primary_expr),
+    ))(input)?;
+    let (input, transform) = preceded(space1, parse_transform)(input)?;
+    Ok((
+        input,
+        Transform::Within {
+            begin: Box::new(begin),
+            end: Box::new(end),
+            transform: Box::new(transform),
+        },
+    ))
+}
+
 /// Parse a list of transforms: [transform1, transform2, ...]
 fn parse_transform_list(input: &str) -> IResult<&str, Vec<Transform>> {
     delimited(
@@ -2401,6 +3272,46 @@ fn parse_transform_group_3(input: &str) -> IResult<&str, Transform> {
     ))(input)
 }
 
+/// Parse transform group 5 (mutate, markov, take, after)
+fn parse_transform_group_5(input: &str) -> IResult<&str, Transform> {
+    alt((
+        // mutate amount [seed]
+        map(
+            tuple((
+                terminated(tag("mutate"), space1),
+                parse_primary_expr,
+                opt(preceded(space1, parse_primary_expr)),
+            )),
+            |(_, amount, seed)| Transform::Mutate {
+                amount: Box::new(amount),
+                seed: seed.map(Box::new),
+            },
+        ),
+        // markov [cycles]
+        map(
+            pair(keyword("markov"), opt(preceded(space1, parse_primary_expr))),
+            |(_, cycles)| Transform::Markov(cycles.map(Box::new)),
+        ),
+        // take cycles
+        map(
+            preceded(terminated(tag("take"), space1), parse_primary_expr),
+            |expr| Transform::Take(Box::new(expr)),
+        ),
+        // after cycles [transform]
+        map(
+            tuple((
+                terminated(tag("after"), space1),
+                parse_primary_expr,
+                opt(preceded(space1, parse_transform)),
+            )),
+            |(_, cycles, transform)| Transform::After {
+                cycles: Box::new(cycles),
+                transform: transform.map(Box::new),
+            },
+        ),
+    ))(input)
+}
+
 /// Parse transform group 4 (fourth group of transforms)
 fn parse_transform_group_4(input: &str) -> IResult<&str, Transform> {
     alt((
@@ -2539,20 +3450,8 @@ fn parse_transform_group_4(input: &str) -> IResult<&str, Transform> {
                 velocity_var: Box::new(velocity_var),
             },
         ),
-        // within begin end transform
-        map(
-            tuple((
-                terminated(tag("within"), space1),
-                terminated(parse_primary_expr, space1),
-                terminated(parse_primary_expr, space1),
-                parse_transform,
-            )),
-            |(_, begin, end, transform)| Transform::Within {
-                begin: Box::new(begin),
-                end: Box::new(end),
-                transform: Box::new(transform),
-            },
-        ),
+        // within begin end transform / within (begin, end) transform
+        parse_within,
         // euclid pulses steps
         map(
             tuple((
@@ -2597,7 +3496,63 @@ fn parse_number(input: &str) -> IResult<&str, f64> {
         value = -value;
     }
 
-    Ok((input, value))
+    Ok((input, value))
+}
+
+/// Parse a fraction of two numbers: `3/16`
+fn parse_fraction(input: &str) -> IResult<&str, f64> {
+    map(
+        separated_pair(parse_number, char('/'), parse_number),
+        |(num, den)| num / den,
+    )(input)
+}
+
+/// Parse a length in cycles: `0.25c`, `4c`, `1/4c`. It becomes a `cycles`
+/// call, which tempo-synced functions such as `lfo` read as a period
+fn parse_cycles_literal(input: &str) -> IResult<&str, Expr> {
+    let (input, n) = terminated(
+        alt((parse_fraction, parse_number)),
+        pair(
+            char('c'),
+            not(take_while1(|c: char| c.is_alphanumeric() || c == '_')),
+        ),
+    )(input)?;
+    Ok((
+        input,
+        Expr::Call {
+            name: "cycles".to_string(),
+            args: vec![Expr::Number(n)],
+        },
+    ))
+}
+
+/// Parse a number with a unit suffix, converted to the plain value a
+/// parameter takes: `12st` and `-30ct` are pitch ratios (2^(st/12)), `-6dB`
+/// is a linear gain (0.501), `250ms` and `2s` are seconds. A leading `+`
+/// is allowed, as in `+6dB`
+fn parse_unit_literal(input: &str) -> IResult<&str, Expr> {
+    let (input, (n, unit)) = pair(
+        preceded(opt(char('+')), parse_number),
+        terminated(
+            alt((
+                tag("st"),
+                tag("ct"),
+                tag("dB"),
+                tag("db"),
+                tag("ms"),
+                tag("s"),
+            )),
+            not(take_while1(|c: char| c.is_alphanumeric() || c == '_')),
+        ),
+    )(input)?;
+    let value = match unit {
+        "st" => 2.0_f64.powf(n / 12.0),
+        "ct" => 2.0_f64.powf(n / 1200.0),
+        "dB" | "db" => 10.0_f64.powf(n / 20.0),
+        "ms" => n / 1000.0,
+        _ => n,
+    };
+    Ok((input, Expr::Number(value)))
 }
 
 /// Parse string literal: "..."
@@ -2698,6 +3653,193 @@ fn multispace1(input: &str) -> IResult<&str, &str> {
     Ok((input, consumed))
 }
 
+// ============================================================================
+// Tokens for editors
+// ============================================================================
+
+/// Lexical class of a source span, for syntax highlighting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Statement head: out, o1, tempo, bpm, fn, hush, ...
+    Keyword,
+    /// Function or transform name in a parsed statement
+    Function,
+    /// Parameter of a `fn` or parameterised bus
+    Variable,
+    /// Identifier whose role is unknown (line not parsed as a statement)
+    Identifier,
+    /// Bus reference: ~name
+    Bus,
+    /// Template or pattern reference: @name, %name
+    Reference,
+    /// Keyword argument: :name
+    Kwarg,
+    Number,
+    String,
+    /// `#` and `$`
+    ChainOperator,
+    Operator,
+    Comment,
+    Whitespace,
+    Unknown,
+}
+
+/// A classified span of one line (byte offsets into the line)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Tokens of one source line, and whether its statement parses
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineTokens {
+    pub tokens: Vec<Token>,
+    pub parses: bool,
+}
+
+/// Split one line into tokens using the parser's own lexical rules
+///
+/// Identifiers come back as `TokenKind::Identifier`; `classify_source`
+/// resolves them once it knows the line's statement parses.
+pub fn tokenize_line(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < line.len() {
+        let rest = &line[pos..];
+        let c = rest.chars().next().unwrap();
+
+        let (kind, len) = if c.is_whitespace() {
+            (TokenKind::Whitespace, c.len_utf8())
+        } else if let Ok((after, _)) = parse_comment(rest) {
+            (TokenKind::Comment, rest.len() - after.len())
+        } else if c == '"' {
+            // An unterminated string runs to the end of the line
+            match parse_string_literal(rest) {
+                Ok((after, _)) => (TokenKind::String, rest.len() - after.len()),
+                Err(_) => (TokenKind::String, rest.len()),
+            }
+        } else if c.is_ascii_digit() {
+            match parse_number(rest) {
+                Ok((after, _)) => (TokenKind::Number, rest.len() - after.len()),
+                Err(_) => (TokenKind::Unknown, 1),
+            }
+        } else if let Ok((after, _)) = parse_identifier(rest) {
+            (TokenKind::Identifier, rest.len() - after.len())
+        } else if matches!(c, '~' | '@' | '%' | ':') {
+            let kind = match c {
+                '~' => TokenKind::Bus,
+                ':' => TokenKind::Kwarg,
+                _ => TokenKind::Reference,
+            };
+            match parse_identifier(&rest[1..]) {
+                Ok((after, _)) => (kind, rest.len() - after.len()),
+                Err(_) => (TokenKind::Operator, 1),
+            }
+        } else if c == '#' || c == '$' {
+            (TokenKind::ChainOperator, 1)
+        } else if c.is_ascii_punctuation() {
+            (TokenKind::Operator, 1)
+        } else {
+            (TokenKind::Unknown, c.len_utf8())
+        };
+
+        tokens.push(Token {
+            kind,
+            start: pos,
+            end: pos + len,
+        });
+        pos += len;
+    }
+
+    tokens
+}
+
+/// Tokenize a whole program and check each statement against the parser
+///
+/// Lines are grouped into statements the same way `parse_program` joins
+/// continuation lines, and each group is parsed on its own, so one broken
+/// statement doesn't mark the rest of the buffer. In statements that parse,
+/// the head identifier becomes a `Keyword`, `fn`/bus parameters become
+/// `Variable`s and every other identifier is a `Function`. Returns one entry
+/// per `\n`-separated line.
+pub fn classify_source(source: &str) -> Vec<LineTokens> {
+    let lines: Vec<&str> = source.split('\n').collect();
+    let mut result: Vec<LineTokens> = lines
+        .iter()
+        .map(|line| LineTokens {
+            tokens: tokenize_line(line),
+            parses: true,
+        })
+        .collect();
+
+    // Group lines into statements: [start, end)
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    let mut open: Option<usize> = None;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("--") {
+            if let Some(start) = open.take() {
+                groups.push((start, i));
+            }
+        } else if is_statement_start(trimmed) || open.is_none() {
+            if let Some(start) = open.take() {
+                groups.push((start, i));
+            }
+            open = Some(i);
+        }
+    }
+    if let Some(start) = open {
+        groups.push((start, lines.len()));
+    }
+
+    for (start, end) in groups {
+        let text = lines[start..end].join("\n");
+        let params = match parse_program(&text) {
+            Ok((rest, statements)) if rest.trim().is_empty() && !statements.is_empty() => {
+                statements
+                    .iter()
+                    .flat_map(|stmt| match stmt {
+                        Statement::FunctionDef { params, .. }
+                        | Statement::BusAssignment { params, .. } => params.clone(),
+                        _ => Vec::new(),
+                    })
+                    .collect::<Vec<_>>()
+            }
+            _ => {
+                for line in &mut result[start..end] {
+                    line.parses = false;
+                }
+                continue;
+            }
+        };
+
+        let mut head = true;
+        for (i, line) in result[start..end].iter_mut().enumerate() {
+            let text = lines[start + i];
+            for token in &mut line.tokens {
+                if token.kind == TokenKind::Whitespace || token.kind == TokenKind::Comment {
+                    continue;
+                }
+                if token.kind == TokenKind::Identifier {
+                    token.kind = if head {
+                        TokenKind::Keyword
+                    } else if params.iter().any(|p| p == &text[token.start..token.end]) {
+                        TokenKind::Variable
+                    } else {
+                        TokenKind::Function
+                    };
+                }
+                head = false;
+            }
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3805,6 +4947,629 @@ o2 $ s "cp(2,4)"
         }
     }
 
+    #[test]
+    fn test_parse_targeted_transforms() {
+        let (rest, t) = parse_transform("within (0.5, 1.0) (fast 2)").unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(t, Transform::Within { .. }));
+
+        let (rest, t) = parse_transform("within 0.25 0.75 rev").unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(t, Transform::Within { .. }));
+
+        let (rest, t) = parse_transform("foldEvery [2, 4] (fast 2)").unwrap();
+        assert!(rest.is_empty());
+        match t {
+            Transform::FoldEveryN { ns, .. } => assert_eq!(ns.len(), 2),
+            other => panic!("Expected FoldEveryN, got {:?}", other),
+        }
+
+        // The transform-list form is still supported
+        let (_, t) = parse_transform("foldEvery [rev, fast 2] 4").unwrap();
+        assert!(matches!(t, Transform::FoldEvery { .. }));
+
+        let (rest, t) = parse_transform("somecyclesBy 0.3 (# lpf 300)").unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(t, Transform::SomecyclesBy { .. }));
+
+        let (_, t) = parse_transform("somecycles rev").unwrap();
+        assert!(matches!(t, Transform::SomecyclesBy { .. }));
+    }
+
+    #[test]
+    fn test_parse_named_tap() {
+        let (rest, stmt) = parse_statement(r#"tap ~bass "bass_debug""#).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Tap {
+                bus: "bass".to_string(),
+                name: "bass_debug".to_string(),
+                duration: None,
+            }
+        );
+
+        let (_, stmt) = parse_statement(r#"tap ~lead "lead.wav" 8"#).unwrap();
+        assert!(matches!(stmt, Statement::Tap { duration: Some(d), .. } if d == 8.0));
+    }
+
+    #[test]
+    fn test_parse_generative_transforms() {
+        let (rest, transform) = parse_transform("mutate 0.1 7").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            transform,
+            Transform::Mutate {
+                amount: Box::new(Expr::Number(0.1)),
+                seed: Some(Box::new(Expr::Number(7.0))),
+            }
+        );
+        assert_eq!(
+            parse_transform("markov").unwrap().1,
+            Transform::Markov(None)
+        );
+        assert_eq!(
+            parse_transform("markov 2").unwrap().1,
+            Transform::Markov(Some(Box::new(Expr::Number(2.0))))
+        );
+    }
+
+    #[test]
+    fn test_parse_counted_transforms() {
+        assert_eq!(
+            parse_transform("take 8").unwrap().1,
+            Transform::Take(Box::new(Expr::Number(8.0)))
+        );
+        assert_eq!(
+            parse_transform("after 8").unwrap().1,
+            Transform::After {
+                cycles: Box::new(Expr::Number(8.0)),
+                transform: None,
+            }
+        );
+        let (rest, transform) = parse_transform("after 4 (fast 2)").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            transform,
+            Transform::After {
+                cycles: Box::new(Expr::Number(4.0)),
+                transform: Some(Box::new(Transform::Fast(Box::new(Expr::Number(2.0))))),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_tuning() {
+        let (rest, stmt) = parse_statement("tuning 19edo").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Tuning {
+                bus: None,
+                spec: TuningSpec::Edo(19.0),
+            }
+        );
+
+        let (_, stmt) = parse_statement(r#"tuning ~lead "bp.scl" "bp.kbm""#).unwrap();
+        assert_eq!(
+            stmt,
+            Statement::Tuning {
+                bus: Some("lead".to_string()),
+                spec: TuningSpec::Scala {
+                    scl: "bp.scl".to_string(),
+                    kbm: Some("bp.kbm".to_string()),
+                },
+            }
+        );
+
+        assert!(parse_statement("tuning 19").is_err());
+    }
+
+    #[test]
+    fn test_parse_a4_and_transpose() {
+        assert_eq!(parse_statement("a4 432").unwrap().1, Statement::A4(432.0));
+        assert_eq!(parse_statement("a4 442Hz").unwrap().1, Statement::A4(442.0));
+        assert_eq!(
+            parse_statement("transpose -2st").unwrap().1,
+            Statement::Transpose(-2.0)
+        );
+        assert_eq!(
+            parse_statement("transpose 7").unwrap().1,
+            Statement::Transpose(7.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_visuals() {
+        let (rest, stmt) = parse_statement("visuals: osc://localhost:3333").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Visuals {
+                target: "osc://localhost:3333".to_string(),
+                fps: None,
+            }
+        );
+        assert_eq!(
+            parse_statement("visuals: osc://127.0.0.1:3333 60fps").unwrap().1,
+            Statement::Visuals {
+                target: "osc://127.0.0.1:3333".to_string(),
+                fps: Some(60.0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_record() {
+        let (rest, stmt) = parse_statement(r#"record ~keys 4c -> "riff1""#).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Record {
+                bus: "keys".to_string(),
+                cycles: 4.0,
+                name: "riff1".to_string(),
+            }
+        );
+        assert!(parse_statement(r#"record ~keys -> "riff1""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_sample_alias() {
+        let (rest, stmt) = parse_statement(r#"alias kick = "808bd:3""#).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::SampleAlias {
+                name: "kick".to_string(),
+                target: "808bd:3".to_string(),
+            }
+        );
+
+        let (_, statements) = parse_program("alias hat=\"hh27\"\nout $ s \"hat*4\"").unwrap();
+        assert_eq!(statements.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_duck_statement() {
+        let (rest, stmt) = parse_statement("duck ~pads ~kick :amount 0.8 :release 0.25").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Duck {
+                target: "pads".to_string(),
+                trigger: "kick".to_string(),
+                amount: Some(0.8),
+                attack: None,
+                release: Some(0.25),
+            }
+        );
+
+        let (_, stmt) = parse_statement("duck ~bass ~kick").unwrap();
+        assert!(matches!(stmt, Statement::Duck { amount: None, .. }));
+    }
+
+    #[test]
+    fn test_parse_autogain_statement() {
+        let (rest, stmt) = parse_statement("autogain ~drums :target -18dB :speed slow").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::AutoGain {
+                bus: "drums".to_string(),
+                target_db: Some(-18.0),
+                speed: Some("slow".to_string()),
+            }
+        );
+
+        let (_, stmt) = parse_statement("autogain ~pads :speed 2.5").unwrap();
+        assert!(
+            matches!(stmt, Statement::AutoGain { target_db: None, speed: Some(s), .. } if s == "2.5")
+        );
+    }
+
+    #[test]
+    fn test_parse_bare_word_kwarg_values() {
+        let (rest, expr) = parse_expr("saw 55 :raw true :retrig \"t ~ t t\"").unwrap();
+        assert!(rest.is_empty());
+        match expr {
+            Expr::Call { name, args } => {
+                assert_eq!(name, "saw");
+                assert_eq!(args.len(), 3);
+                assert!(matches!(
+                    &args[1],
+                    Expr::Kwarg { name, value } if name == "raw" && **value == Expr::Var("true".to_string())
+                ));
+            }
+            other => panic!("expected a call, got {:?}", other),
+        }
+
+        let (rest, expr) = parse_expr("lfo 0.25c :shape tri :phase 0.25").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            expr,
+            Expr::Call {
+                name: "lfo".to_string(),
+                args: vec![
+                    Expr::Call {
+                        name: "cycles".to_string(),
+                        args: vec![Expr::Number(0.25)],
+                    },
+                    Expr::Kwarg {
+                        name: "shape".to_string(),
+                        value: Box::new(Expr::Var("tri".to_string())),
+                    },
+                    Expr::Kwarg {
+                        name: "phase".to_string(),
+                        value: Box::new(Expr::Number(0.25)),
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_mod_block() {
+        let route = |source: &str, bus: &str, param: &str, depth, semitones, curve| ModRoute {
+            source: Expr::BusRef(source.to_string()),
+            bus: bus.to_string(),
+            param: param.to_string(),
+            depth,
+            semitones,
+            curve,
+        };
+        let (rest, stmts) = parse_program(
+            "mod {\n  ~lfo -> ~filter.cutoff * 800;\n  ~env -> ~osc.freq * 12st :curve 2\n}",
+        )
+        .unwrap();
+        assert!(rest.is_empty(), "{:?}", rest);
+        assert_eq!(
+            stmts,
+            vec![Statement::Mod(vec![
+                route("lfo", "filter", "cutoff", 800.0, false, 1),
+                route("env", "osc", "freq", 12.0, true, 2),
+            ])]
+        );
+
+        let (rest, stmt) = parse_statement("mod { ~a -> ~b.q; ~a -> ~b.cutoff * -200 }").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Mod(vec![
+                route("a", "b", "q", 1.0, false, 1),
+                route("a", "b", "cutoff", -200.0, false, 1),
+            ])
+        );
+
+        // A route needs a bus parameter as its destination
+        assert!(parse_statement("mod { ~lfo -> ~filter }").is_err());
+    }
+
+    #[test]
+    fn test_parse_freeze() {
+        let (rest, stmts) =
+            parse_program("~pads $ saw 110 # lpf 800 0.7\nfreeze ~pads 8c\nout $ ~pads").unwrap();
+        assert!(rest.trim().is_empty(), "{:?}", rest);
+        assert_eq!(
+            stmts[1],
+            Statement::Freeze {
+                bus: "pads".to_string(),
+                cycles: 8.0
+            }
+        );
+        assert_eq!(stmts.len(), 3);
+        // The `c` is optional
+        assert!(matches!(
+            parse_statement("freeze ~pads 4"),
+            Ok((_, Statement::Freeze { cycles, .. })) if cycles == 4.0
+        ));
+    }
+
+    #[test]
+    fn test_parse_ms_block() {
+        let (rest, stmts) = parse_program(
+            "ms {\n  mid: lpf 8000 0.7\n  side: hpf 200 0.7 # widener 1.5\n}\nout1 $ ~l",
+        )
+        .unwrap();
+        assert!(rest.trim().is_empty(), "{:?}", rest);
+        assert_eq!(stmts.len(), 2);
+        let Statement::MidSide { mid, side } = &stmts[0] else {
+            panic!("expected an ms block, got {:?}", stmts[0]);
+        };
+        assert!(matches!(mid, Some(Expr::Call { name, .. }) if name == "lpf"));
+        assert!(matches!(side, Some(Expr::Chain(..))));
+
+        // One chain is enough; commas separate them on one line
+        let (_, stmt) = parse_statement("ms { side: gain 0 }").unwrap();
+        assert!(matches!(stmt, Statement::MidSide { mid: None, side: Some(_) }));
+        let (_, stmt) = parse_statement("ms { mid: lpf 800 0.7, side: hpf 90 0.7 }").unwrap();
+        assert!(matches!(stmt, Statement::MidSide { mid: Some(_), side: Some(_) }));
+
+        assert!(parse_statement("ms { }").is_err());
+        assert!(parse_statement("ms { mid: lpf 800 0.7, mid: hpf 90 0.7 }").is_err());
+        assert!(parse_statement("ms { left: lpf 800 0.7 }").is_err());
+    }
+
+    #[test]
+    fn test_parse_ur_fragments() {
+        let (rest, expr) =
+            parse_expr("ur 8 \"intro drop\" [:intro \"hh*4\", :drop (s \"bd*4\")]").unwrap();
+        assert_eq!(rest, "");
+        let Expr::Call { name, args } = expr else {
+            panic!("Expected call, got {:?}", expr);
+        };
+        assert_eq!(name, "ur");
+        assert_eq!(args.len(), 3);
+        let Expr::List(fragments) = &args[2] else {
+            panic!("Expected list, got {:?}", args[2]);
+        };
+        assert!(matches!(&fragments[0], Expr::Kwarg { name, .. } if name == "intro"));
+        assert!(matches!(&fragments[1], Expr::Kwarg { name, .. } if name == "drop"));
+    }
+
+    #[test]
+    fn test_parse_spatial() {
+        let (rest, stmts) = parse_program(
+            "spatial: quad\n~a $ saw 55 # azimuth \"0 90 180 270\" # elevation 0\nout $ ~a",
+        )
+        .unwrap();
+        assert!(rest.trim().is_empty(), "{:?}", rest);
+        assert_eq!(
+            stmts[0],
+            Statement::Spatial {
+                layout: SpatialLayout::Quad,
+                hrirs: None
+            }
+        );
+        assert_eq!(stmts.len(), 3);
+
+        let (_, stmt) = parse_statement("spatial: 5.1").unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Spatial {
+                layout: SpatialLayout::Surround51,
+                ..
+            }
+        ));
+        let (_, stmt) = parse_statement("spatial:foa").unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Spatial {
+                layout: SpatialLayout::Foa,
+                ..
+            }
+        ));
+        assert!(parse_statement("spatial: 7.1").is_err());
+
+        // Binaural, optionally with an HRIR set
+        let (_, stmt) = parse_statement("spatial: binaural \"kemar.sofa\"").unwrap();
+        assert_eq!(
+            stmt,
+            Statement::Spatial {
+                layout: SpatialLayout::Binaural,
+                hrirs: Some("kemar.sofa".to_string())
+            }
+        );
+        // Only binaural takes one
+        assert!(parse_spatial("spatial: quad \"kemar.sofa\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_program_recovering() {
+        let code = "tempo: 0.5\n~bass $ saw 55 # lpf 800 0.7 )\n~hats $ s \"hh*8\"\nout $ ~hats";
+        let (stmts, diagnostics) = parse_program_recovering(code);
+        assert_eq!(stmts.len(), 3);
+        assert!(matches!(&stmts[1], Statement::BusAssignment { name, .. } if name == "hats"));
+        assert_eq!(diagnostics.len(), 1);
+        // At the stray parenthesis
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 30));
+        assert_eq!(
+            diagnostics[0].source_line.as_deref(),
+            Some("~bass $ saw 55 # lpf 800 0.7 )")
+        );
+
+        // Continuation lines are skipped with their statement
+        let (stmts, diagnostics) =
+            parse_program_recovering("~x $ sine 3\n  # lpf 800 )\nout $ sine 440");
+        assert_eq!(stmts.len(), 1);
+        assert_eq!(diagnostics[0].line, 2);
+
+        // A program that parses is left as it is
+        let code = "~a $ sine 440\n  # lpf 800 0.7\nout $ ~a";
+        let (stmts, diagnostics) = parse_program_recovering(code);
+        assert_eq!(stmts, parse_program(code).unwrap().1);
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_parse_groove_statement() {
+        let (rest, stmt) = parse_statement(r#"groove ~drums ~hats "mpc60_54" 0.5"#).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Groove {
+                buses: vec!["drums".to_string(), "hats".to_string()],
+                template: "mpc60_54".to_string(),
+                amount: Some(0.5),
+            }
+        );
+
+        let (rest, stmt) = parse_statement(r#"groove ~drums "feel.toml""#).unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(stmt, Statement::Groove { amount: None, .. }));
+
+        // Without a bus it's not a groove statement
+        assert!(parse_statement(r#"groove "mpc""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_cue() {
+        let (rest, stmt) = parse_statement("precue ~next").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(stmt, Statement::Cue(Expr::BusRef("next".to_string())));
+
+        let (rest, stmt) = parse_statement("cue $ ~next * 0.5").unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(stmt, Statement::Cue(Expr::BinOp { .. })));
+
+        // Not a cue: a call to something starting with "cue"
+        assert!(parse_statement("cued $ sine 440").is_err());
+    }
+
+    #[test]
+    fn test_parse_control_out() {
+        let (rest, stmt) = parse_statement("cvout 3 (~pitch |> voct)").unwrap();
+        assert!(rest.is_empty());
+        assert!(matches!(
+            stmt,
+            Statement::CvOut {
+                channel: 3,
+                expr: Expr::Paren(_)
+            }
+        ));
+
+        let (rest, stmt) = parse_statement("gateout 4 \"t ~ t t\"").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::GateOut {
+                channel: 4,
+                expr: Expr::String("t ~ t t".to_string())
+            }
+        );
+        assert!(parse_statement("cvout ~pitch").is_err());
+    }
+
+    #[test]
+    fn test_parse_assert() {
+        let (rest, stmt) = parse_statement("assert rms(~kick) in 0.1..0.4").unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(
+            stmt,
+            Statement::Assert {
+                metric: "rms".to_string(),
+                target: Some("kick".to_string()),
+                check: AssertCheck::Range { lo: 0.1, hi: 0.4 },
+            }
+        );
+
+        let (_, stmt) = parse_statement("assert onset_count(~drums) == 8").unwrap();
+        assert!(matches!(
+            stmt,
+            Statement::Assert {
+                check: AssertCheck::Compare {
+                    op: CompareOp::Eq,
+                    ..
+                },
+                ..
+            }
+        ));
+
+        let (rest, stmt) = parse_statement("assert max_db(out) <= -0.5").unwrap();
+        assert!(rest.is_empty());
+        match stmt {
+            Statement::Assert { target, check, .. } => {
+                assert_eq!(target, None);
+                assert_eq!(check.to_string(), "<= -0.5");
+                assert!(check.holds(-1.0) && !check.holds(0.0));
+            }
+            other => panic!("Expected Assert, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_pattern_both_structure_operators() {
+        // |op| operators: structure from both sides
+        let cases = [
+            ("\"0 3 5\" |+| \"<0 12>\"", BinOp::AddBoth),
+            ("\"0 3 5\" |-| \"1 2\"", BinOp::SubBoth),
+            ("\"1 2\" |*| \"2 3 4\"", BinOp::MulBoth),
+            ("\"1 2\" |/| \"2 3 4\"", BinOp::DivBoth),
+            ("a |>| b", BinOp::UnionBoth),
+            ("a |<| b", BinOp::UnionBothLeft),
+        ];
+        for (code, expected) in cases {
+            match parse_expr(code) {
+                Ok((rest, Expr::BinOp { op, .. })) => {
+                    assert_eq!(op, expected, "wrong operator for {}", code);
+                    assert!(
+                        rest.trim().is_empty(),
+                        "unparsed input for {}: {:?}",
+                        code,
+                        rest
+                    );
+                }
+                other => panic!("Expected BinOp for {}, got {:?}", code, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_unit_literals() {
+        let number = |code: &str| match parse_expr(code).unwrap() {
+            (rest, Expr::Number(n)) if rest.trim().is_empty() => n,
+            other => panic!("{}: {:?}", code, other),
+        };
+        assert!((number("12st") - 2.0).abs() < 1e-12);
+        assert!((number("-7st") - 2.0_f64.powf(-7.0 / 12.0)).abs() < 1e-12);
+        assert!((number("1200ct") - 2.0).abs() < 1e-12);
+        assert!((number("-6dB") - 0.501).abs() < 1e-3);
+        assert!((number("+6dB") - 1.995).abs() < 1e-3);
+        assert!((number("0db") - 1.0).abs() < 1e-12);
+        assert!((number("250ms") - 0.25).abs() < 1e-12);
+        assert!((number("2s") - 2.0).abs() < 1e-12);
+
+        let (_, expr) = parse_expr("1/4c").unwrap();
+        match expr {
+            Expr::Call { name, args } => {
+                assert_eq!(name, "cycles");
+                assert!(matches!(args.as_slice(), [Expr::Number(n)] if (n - 0.25).abs() < 1e-12));
+            }
+            other => panic!("{:?}", other),
+        }
+
+        // Without a suffix, `/` still divides
+        let (_, expr) = parse_expr("1/4").unwrap();
+        assert!(matches!(expr, Expr::BinOp { op: BinOp::Div, .. }));
+        // and a suffix must end the word
+        assert!(!matches!(parse_expr("2sec"), Ok((rest, _)) if rest.is_empty()));
+    }
+
+    #[test]
+    fn test_note_literals() {
+        let (rest, expr) = parse_expr("sine c3").unwrap();
+        assert!(rest.trim().is_empty());
+        assert!(matches!(expr, Expr::Call { ref args, .. } if args == &[Expr::Var("c3".to_string())]));
+
+        // Sharps use `#`, which otherwise chains
+        let (rest, expr) = parse_expr("sine a#4 # lpf 800 0.5").unwrap();
+        assert!(rest.trim().is_empty());
+        match expr {
+            Expr::Chain(left, _) => assert!(matches!(
+                *left,
+                Expr::Call { ref args, .. } if args == &[Expr::Var("as4".to_string())]
+            )),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_spread_operator() {
+        let (rest, expr) = parse_expr("0.9 ~ 1.1").unwrap();
+        assert!(rest.trim().is_empty());
+        assert!(matches!(
+            expr,
+            Expr::BinOp {
+                op: BinOp::Spread,
+                ..
+            }
+        ));
+
+        // Bus references keep the tilde attached
+        let (_, expr) = parse_expr("~a + ~b").unwrap();
+        assert!(matches!(expr, Expr::BinOp { op: BinOp::Add, .. }));
+    }
+
     #[test]
     fn test_pattern_union_right() {
         // <| operator: structure from right, values from left
@@ -3840,4 +5605,62 @@ out $ sine ~shifted
             result
         );
     }
+
+    #[test]
+    fn test_tokenize_line() {
+        let line = "~bass $ saw 55 # lpf 800 :q 0.7 -- warm";
+        let kinds: Vec<(TokenKind, &str)> = tokenize_line(line)
+            .into_iter()
+            .filter(|t| t.kind != TokenKind::Whitespace)
+            .map(|t| (t.kind, &line[t.start..t.end]))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TokenKind::Bus, "~bass"),
+                (TokenKind::ChainOperator, "$"),
+                (TokenKind::Identifier, "saw"),
+                (TokenKind::Number, "55"),
+                (TokenKind::ChainOperator, "#"),
+                (TokenKind::Identifier, "lpf"),
+                (TokenKind::Number, "800"),
+                (TokenKind::Kwarg, ":q"),
+                (TokenKind::Number, "0.7"),
+                (TokenKind::Comment, "-- warm"),
+            ]
+        );
+
+        // Unterminated strings run to the end of the line
+        let tokens = tokenize_line("s \"bd sn");
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::String);
+        assert_eq!(tokens.last().unwrap().end, 8);
+    }
+
+    #[test]
+    fn test_classify_source() {
+        let code = "tempo: 0.5\nfn thick x = saw x # lpf 800 0.5\nout $ s \"bd sn\"\n  # fast 2\n~broken $ $\n-- note";
+        let lines = classify_source(code);
+        assert_eq!(lines.len(), 6);
+        let parses: Vec<bool> = lines.iter().map(|l| l.parses).collect();
+        assert_eq!(parses, vec![true, true, true, true, false, true]);
+
+        let kind_of = |line: usize, word: &str| {
+            let text = code.split('\n').nth(line).unwrap();
+            lines[line]
+                .tokens
+                .iter()
+                .find(|t| &text[t.start..t.end] == word)
+                .unwrap()
+                .kind
+        };
+        assert_eq!(kind_of(0, "tempo"), TokenKind::Keyword);
+        assert_eq!(kind_of(1, "fn"), TokenKind::Keyword);
+        assert_eq!(kind_of(1, "thick"), TokenKind::Function);
+        assert_eq!(kind_of(1, "x"), TokenKind::Variable);
+        assert_eq!(kind_of(2, "out"), TokenKind::Keyword);
+        assert_eq!(kind_of(2, "s"), TokenKind::Function);
+        // Continuation line belongs to the statement above
+        assert_eq!(kind_of(3, "fast"), TokenKind::Function);
+        assert_eq!(kind_of(5, "-- note"), TokenKind::Comment);
+    }
 }
@@ -0,0 +1,68 @@
+//! Control voltages for modular synths: `cvout` and `gateout`
+//!
+//! ```phonon
+//! ~pitch $ "c3 e3 g3 c4"
+//! cvout 3 (~pitch |> voct)
+//! gateout 4 "t ~ t t"
+//! ```
+//!
+//! Each statement sends a signal, in volts, to a hardware output channel
+//! (numbered from 1; channels 1 and 2 carry the main mix). `voct` turns a
+//! frequency into 1 V/octave pitch with 0 V at C4, and `gateout` is
+//! [`GATE_VOLTS`] during each event, dropping for the last [`GATE_GAP`]
+//! seconds so that back-to-back events retrigger the envelope.
+//!
+//! This needs a DC-coupled interface (an ES-8 or similar): a sample at
+//! digital full scale comes out as [`DEFAULT_FULL_SCALE`] volts unless the
+//! editor's `cv_full_scale` setting says otherwise.
+
+/// The pitch `voct` puts at 0 V
+pub const ZERO_VOLT_MIDI: f64 = 60.0;
+/// Height of a gate
+pub const GATE_VOLTS: f32 = 5.0;
+/// How long a gate drops before the end of each event, in seconds
+pub const GATE_GAP: f64 = 0.002;
+/// Volts at digital full scale, for interfaces like the ES-8
+pub const DEFAULT_FULL_SCALE: f32 = 10.0;
+
+/// 1 V/octave control voltage of a frequency (0 V for anything that isn't
+/// a pitch, like a rest's 0 Hz)
+pub fn hz_to_volts(hz: f32) -> f32 {
+    if !(hz > 0.0 && hz.is_finite()) {
+        return 0.0;
+    }
+    ((crate::pitch::hz_to_midi(hz as f64) - ZERO_VOLT_MIDI) / 12.0) as f32
+}
+
+/// The sample that makes an interface with `full_scale` volts at digital
+/// full scale put out `volts`, clipped to what it can reach
+pub fn volts_to_sample(volts: f32, full_scale: f32) -> f32 {
+    if !volts.is_finite() {
+        return 0.0;
+    }
+    (volts / full_scale).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volt_per_octave() {
+        assert!(hz_to_volts(261.63).abs() < 1e-3);
+        assert!((hz_to_volts(523.25) - 1.0).abs() < 1e-3);
+        assert!((hz_to_volts(130.81) + 1.0).abs() < 1e-3);
+        // A semitone is 1/12 V
+        assert!((hz_to_volts(440.0) - 0.75).abs() < 1e-3);
+        assert_eq!(hz_to_volts(0.0), 0.0);
+        assert_eq!(hz_to_volts(f32::NAN), 0.0);
+    }
+
+    #[test]
+    fn test_volts_to_sample_calibration() {
+        assert_eq!(volts_to_sample(GATE_VOLTS, DEFAULT_FULL_SCALE), 0.5);
+        assert_eq!(volts_to_sample(1.0, 5.0), 0.2);
+        assert_eq!(volts_to_sample(-12.0, 10.0), -1.0);
+        assert_eq!(volts_to_sample(f32::INFINITY, 10.0), 0.0);
+    }
+}
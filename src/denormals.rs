@@ -0,0 +1,126 @@
+//! Denormal protection and DC blocking
+//!
+//! A decaying reverb or delay tail eventually holds values so small they are
+//! subnormal floats, which many CPUs process tens of times slower than normal
+//! ones: rendering a silent tail can cost more than the sound that caused it.
+//! Two layers keep that from happening:
+//!
+//! - render threads call [`enable_flush_to_zero`], so the FPU itself treats
+//!   subnormals as zero (x86_64 FTZ/DAZ, aarch64 FZ);
+//! - feedback writes in delays, combs and reverbs go through [`flush_denormal`],
+//!   so their state decays to a true zero on targets without those modes too.
+//!
+//! [`DcBlocker`] is the optional master DC blocker
+//! (`UnifiedSignalGraph::set_master_dc_blocker`).
+
+/// Magnitudes below this are flushed to zero (far below audibility, well above
+/// the subnormal range so products of flushed values stay normal)
+pub const DENORMAL_THRESHOLD: f32 = 1e-30;
+
+/// DC blocker corner frequency
+pub const DC_BLOCKER_HZ: f32 = 10.0;
+
+/// `x`, or zero if it is small enough to head into the subnormal range
+#[inline(always)]
+pub fn flush_denormal(x: f32) -> f32 {
+    if x.abs() < DENORMAL_THRESHOLD {
+        0.0
+    } else {
+        x
+    }
+}
+
+/// Make the FPU flush subnormal results and inputs to zero on the calling
+/// thread. Returns false where that isn't supported (explicit flushing still
+/// applies there).
+pub fn enable_flush_to_zero() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        // MXCSR bit 15 = flush-to-zero, bit 6 = denormals-are-zero
+        #[allow(deprecated)]
+        unsafe {
+            use std::arch::x86_64::{_mm_getcsr, _mm_setcsr};
+            _mm_setcsr(_mm_getcsr() | 0x8040);
+        }
+        true
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        // FPCR bit 24 = flush-to-zero
+        unsafe {
+            let fpcr: u64;
+            std::arch::asm!("mrs {}, fpcr", out(reg) fpcr);
+            std::arch::asm!("msr fpcr, {}", in(reg) fpcr | (1 << 24));
+        }
+        true
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        false
+    }
+}
+
+/// One-pole DC blocker: `y[n] = x[n] - x[n-1] + r * y[n-1]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DcBlocker {
+    coeff: f32,
+    x1: f32,
+    y1: f32,
+}
+
+impl DcBlocker {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            coeff: 1.0 - 2.0 * std::f32::consts::PI * DC_BLOCKER_HZ / sample_rate,
+            x1: 0.0,
+            y1: 0.0,
+        }
+    }
+
+    #[inline]
+    pub fn process(&mut self, x: f32) -> f32 {
+        let y = x - self.x1 + self.coeff * self.y1;
+        self.x1 = x;
+        self.y1 = flush_denormal(y);
+        self.y1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flush_denormal() {
+        assert_eq!(flush_denormal(f32::MIN_POSITIVE / 4.0), 0.0);
+        assert_eq!(flush_denormal(-1e-35), 0.0);
+        assert_eq!(flush_denormal(1e-6), 1e-6);
+        assert_eq!(flush_denormal(-0.5), -0.5);
+    }
+
+    #[test]
+    fn test_flush_to_zero_mode() {
+        if enable_flush_to_zero() {
+            let tiny = std::hint::black_box(f32::MIN_POSITIVE);
+            assert_eq!(tiny * std::hint::black_box(0.25), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_dc_blocker_removes_offset() {
+        let sr = 44100.0;
+        let mut blocker = DcBlocker::new(sr);
+        // 440 Hz sine riding on a 0.3 DC offset
+        let out: Vec<f32> = (0..44100)
+            .map(|i| {
+                let t = i as f32 / sr;
+                blocker.process(0.3 + 0.5 * (2.0 * std::f32::consts::PI * 440.0 * t).sin())
+            })
+            .collect();
+        let tail = &out[22050..];
+        let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+        let peak = tail.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!(mean.abs() < 0.001, "DC left: {}", mean);
+        assert!((peak - 0.5).abs() < 0.02, "sine level changed: {}", peak);
+    }
+}
@@ -0,0 +1,157 @@
+//! Engine introspection: `phonon describe --json`
+//!
+//! Dumps what the DSL offers right now — every documented function with its
+//! parameters (from `FUNCTION_METADATA`, plus registered user nodes), the
+//! built-in synths and the sample banks found on disk — so editor plugins and
+//! documentation sites can be generated from the engine instead of drifting
+//! away from it.
+
+use crate::modal_editor::completion::FUNCTION_METADATA;
+use crate::sample_loader::SampleBank;
+use serde::Serialize;
+
+/// Everything `describe` reports
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineDescription {
+    pub version: String,
+    /// Sorted by name
+    pub functions: Vec<FunctionDescription>,
+    /// Names of the synth functions (described under `functions`)
+    pub synths: Vec<String>,
+    pub samples: Vec<SampleBankDescription>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionDescription {
+    pub name: String,
+    pub category: String,
+    pub description: String,
+    pub example: String,
+    /// In positional order; each can also be passed as `:name value`
+    pub params: Vec<ParamDescription>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamDescription {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub param_type: String,
+    pub optional: bool,
+    pub default: Option<String>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SampleBankDescription {
+    /// Name used in `s "name:index"`
+    pub name: String,
+    /// Number of samples (`index` wraps around this)
+    pub count: usize,
+}
+
+/// Category of the built-in synths in `FUNCTION_METADATA`
+const SYNTH_CATEGORY: &str = "Synths";
+
+/// Describe the DSL as this build and machine see it
+pub fn describe() -> EngineDescription {
+    let mut functions: Vec<FunctionDescription> = FUNCTION_METADATA
+        .values()
+        .map(|metadata| FunctionDescription {
+            name: metadata.name.to_string(),
+            category: metadata.category.to_string(),
+            description: metadata.description.to_string(),
+            example: metadata.example.to_string(),
+            params: metadata
+                .params
+                .iter()
+                .map(|p| ParamDescription {
+                    name: p.name.to_string(),
+                    param_type: p.param_type.to_string(),
+                    optional: p.optional,
+                    default: p.default.map(str::to_string),
+                    description: p.description.to_string(),
+                })
+                .collect(),
+        })
+        .collect();
+    functions.extend(
+        crate::node_factory::registered()
+            .into_iter()
+            .filter(|spec| !FUNCTION_METADATA.contains_key(spec.name.as_str()))
+            .map(|spec| FunctionDescription {
+                name: spec.name,
+                category: "User Nodes".to_string(),
+                description: spec.description,
+                example: String::new(),
+                params: spec
+                    .params
+                    .into_iter()
+                    .map(|p| ParamDescription {
+                        name: p.name,
+                        param_type: "float".to_string(),
+                        optional: true,
+                        default: Some(p.default.to_string()),
+                        description: p.description,
+                    })
+                    .collect(),
+            }),
+    );
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let synths = functions
+        .iter()
+        .filter(|f| f.category == SYNTH_CATEGORY)
+        .map(|f| f.name.clone())
+        .collect();
+    let samples = SampleBank::new()
+        .banks()
+        .into_iter()
+        .map(|(name, count)| SampleBankDescription { name, count })
+        .collect();
+
+    EngineDescription {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        functions,
+        synths,
+        samples,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_lists_functions_and_synths() {
+        let description = describe();
+        let names: Vec<&str> = description
+            .functions
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+
+        let lpf = description
+            .functions
+            .iter()
+            .find(|f| f.name == "lpf")
+            .unwrap();
+        assert_eq!(lpf.category, "Filters");
+        assert!(!lpf.params.is_empty());
+        assert!(description.synths.contains(&"superkick".to_string()));
+
+        let json = serde_json::to_value(&description).unwrap();
+        assert!(json["functions"][0]["params"].is_array());
+        assert!(json["functions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|f| f["params"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|p| p["type"].is_string())));
+    }
+}
@@ -0,0 +1,165 @@
+//! Engine settings shared by `phonon render`, `phonon live` and `phonon edit`
+//!
+//! The block size is how many frames the graph renders per call, and so how
+//! often a pattern change, MIDI note or swap can take effect. Smaller blocks
+//! tighten the feel on fast machines; larger ones give a slow machine more
+//! room per call. It's set with `--block-size` (or `block_size` in the
+//! editor config) and checked against [`MIN_BLOCK_SIZE`]..=[`MAX_BLOCK_SIZE`].
+//!
+//! The live paths render stereo, so a block is `2 * block_size` interleaved
+//! samples. [`EngineConfig::latency_report`] states what the block and the
+//! playback ring add up to, printed at startup.
+//!
+//! Programs embedding Phonon set these (and more) through
+//! [`crate::phonon_engine::PhononEngine::builder`].
+
+use crate::audio_node::ProcessContext;
+use crate::pattern::Fraction;
+
+/// Frames per block when none is given
+pub const DEFAULT_BLOCK_SIZE: usize = 512;
+/// Smallest block accepted
+pub const MIN_BLOCK_SIZE: usize = 64;
+/// Largest block accepted
+pub const MAX_BLOCK_SIZE: usize = 2048;
+
+/// Sample rate, block size and channel count for one engine
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineConfig {
+    pub sample_rate: f32,
+    /// Frames rendered per block
+    pub block_size: usize,
+    /// Channels rendered: 1 (the left of the main mix) or 2
+    pub channels: usize,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 44100.0,
+            block_size: DEFAULT_BLOCK_SIZE,
+            channels: 2,
+        }
+    }
+}
+
+impl EngineConfig {
+    /// Config for `sample_rate` with `block_size` frames (the default when
+    /// `None`). A block size out of range is an error
+    pub fn new(sample_rate: f32, block_size: Option<usize>) -> Result<Self, String> {
+        let block_size = block_size.unwrap_or(DEFAULT_BLOCK_SIZE);
+        if !(MIN_BLOCK_SIZE..=MAX_BLOCK_SIZE).contains(&block_size) {
+            return Err(format!(
+                "block size must be {}-{} frames, got {}",
+                MIN_BLOCK_SIZE, MAX_BLOCK_SIZE, block_size
+            ));
+        }
+        Ok(Self {
+            sample_rate,
+            block_size,
+            channels: 2,
+        })
+    }
+
+    /// The same config rendering `channels` channels, 1 or 2
+    pub fn with_channels(self, channels: usize) -> Result<Self, String> {
+        if !(1..=2).contains(&channels) {
+            return Err(format!(
+                "channels must be 1 (mono) or 2 (stereo), got {}",
+                channels
+            ));
+        }
+        Ok(Self { channels, ..self })
+    }
+
+    /// Interleaved samples in one block of `channels` channels
+    pub fn block_samples(&self, channels: usize) -> usize {
+        self.block_size * channels
+    }
+
+    /// Length of one block in milliseconds
+    pub fn block_ms(&self) -> f32 {
+        self.block_size as f32 / self.sample_rate * 1000.0
+    }
+
+    /// Time the render thread has for one block, in microseconds
+    pub fn block_budget_us(&self) -> usize {
+        (self.block_size as f64 / self.sample_rate as f64 * 1_000_000.0) as usize
+    }
+
+    /// e.g. "512 frames (11.6 ms)"
+    pub fn describe_block(&self) -> String {
+        format!("{} frames ({:.1} ms)", self.block_size, self.block_ms())
+    }
+
+    /// Longest wait, in milliseconds, from a change to hearing it: the block
+    /// being rendered plus a full ring of `ring_samples` interleaved samples
+    pub fn latency_ms(&self, ring_samples: usize, channels: usize) -> f32 {
+        let ring_frames = ring_samples / channels.max(1);
+        self.block_ms() + ring_frames as f32 / self.sample_rate * 1000.0
+    }
+
+    /// e.g. "block 512 frames (11.6 ms) + ring 46.4 ms = up to 58.0 ms"
+    pub fn latency_report(&self, ring_samples: usize, channels: usize) -> String {
+        let total = self.latency_ms(ring_samples, channels);
+        format!(
+            "block {} + ring {:.1} ms = up to {:.1} ms",
+            self.describe_block(),
+            total - self.block_ms(),
+            total
+        )
+    }
+
+    /// Context for a node rendering one block of this engine
+    pub fn process_context(&self, cycle_position: Fraction, tempo: f64) -> ProcessContext {
+        ProcessContext::new(cycle_position, 0, self.block_size, tempo, self.sample_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_size_range() {
+        assert_eq!(
+            EngineConfig::new(44100.0, None).unwrap().block_size,
+            DEFAULT_BLOCK_SIZE
+        );
+        assert_eq!(EngineConfig::new(48000.0, Some(64)).unwrap().block_size, 64);
+        assert!(EngineConfig::new(48000.0, Some(2048)).is_ok());
+        assert!(EngineConfig::new(48000.0, Some(32)).is_err());
+        assert!(EngineConfig::new(48000.0, Some(4096)).is_err());
+    }
+
+    #[test]
+    fn test_channels() {
+        let engine = EngineConfig::new(48000.0, None).unwrap();
+        assert_eq!(engine.channels, 2);
+        assert_eq!(engine.with_channels(1).unwrap().channels, 1);
+        assert!(engine.with_channels(0).is_err());
+        assert!(engine.with_channels(3).is_err());
+    }
+
+    #[test]
+    fn test_latency_report() {
+        let engine = EngineConfig::new(48000.0, Some(480)).unwrap();
+        assert!((engine.block_ms() - 10.0).abs() < 1e-4);
+        assert_eq!(engine.block_budget_us(), 10_000);
+        assert_eq!(engine.block_samples(2), 960);
+        // 4800 stereo samples = 2400 frames = 50 ms
+        assert!((engine.latency_ms(4800, 2) - 60.0).abs() < 1e-3);
+        assert_eq!(
+            engine.latency_report(4800, 2),
+            "block 480 frames (10.0 ms) + ring 50.0 ms = up to 60.0 ms"
+        );
+    }
+
+    #[test]
+    fn test_process_context_carries_block_size() {
+        let engine = EngineConfig::new(44100.0, Some(128)).unwrap();
+        let context = engine.process_context(Fraction::from_float(1.5), 0.5);
+        assert_eq!(context.block_size, 128);
+        assert_eq!(context.sample_rate, 44100.0);
+    }
+}
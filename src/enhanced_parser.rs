@@ -3,6 +3,9 @@
 //! Enhanced parser for the complete modular synthesis DSL
 //!
 //! Supports arithmetic operations, bus references, and pattern integration
+//!
+//! Deprecated and unused by the commands; arithmetic and buses are in
+//! [`crate::compositional_parser`].
 
 use crate::signal_graph::{Node, NodeId, ProcessorType, SignalGraph, SourceType};
 use std::collections::HashMap;
@@ -593,7 +596,7 @@ impl EnhancedParser {
         match expr {
             Expression::Pattern(pattern_str) => {
                 // Parse the pattern using mini-notation
-                use crate::mini_notation::parse_mini_notation;
+                use crate::mini_notation_v3::parse_mini_notation;
 
                 // Parse the pattern string to get events
                 let pattern = parse_mini_notation(pattern_str);
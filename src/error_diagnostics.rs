@@ -268,6 +268,14 @@ pub fn check_for_common_mistakes(input: &str) -> Vec<String> {
                 i + 1
             ));
         }
+
+        // Check for Glicol-style `>>` chains (Phonon chains with #)
+        if trimmed.contains(">>") {
+            warnings.push(format!(
+                "Line {}: `>>` chains are the old Glicol syntax. Chain with '#': out $ saw 220 # lpf 1000 0.7",
+                i + 1
+            ));
+        }
     }
 
     warnings
@@ -306,4 +314,13 @@ mod tests {
         assert!(warnings.iter().any(|w| w.contains("chain operator")));
         assert!(warnings.iter().any(|w| w.contains("parentheses")));
     }
+
+    #[test]
+    fn test_check_glicol_chain() {
+        let warnings = check_for_common_mistakes("out: sin 440 >> mul 0.5");
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Glicol"));
+        assert!(warnings[0].starts_with("Line 1:"));
+    }
 }
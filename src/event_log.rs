@@ -0,0 +1,169 @@
+//! Triggered-event log for the editor's event pane
+//!
+//! The render thread reports every event it triggers (`cycle:pos name params`)
+//! through a bounded channel, so a performer can tell a silent pattern that
+//! produces no events apart from one whose events make no sound. Sending never
+//! blocks: when the pane isn't showing nothing is formatted at all, and when the
+//! UI falls behind the surplus events are dropped.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Arc;
+
+/// Events in flight between the render thread and the UI
+const CHANNEL_CAPACITY: usize = 512;
+
+/// Lines kept for display
+const HISTORY_LINES: usize = 500;
+
+/// One triggered event
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoggedEvent {
+    /// Absolute cycle position of the event onset
+    pub cycle: f64,
+    /// Sample, bus (`~bass`) or note name
+    pub name: String,
+    /// Parameter summary (`gain=0.80 pan=0.00 ...`)
+    pub params: String,
+}
+
+impl LoggedEvent {
+    /// `cycle:pos name params`, e.g. `12:0.250 bd:2 gain=0.80`
+    pub fn line(&self) -> String {
+        let cycle = self.cycle.floor();
+        format!(
+            "{}:{:.3} {} {}",
+            cycle as i64,
+            self.cycle - cycle,
+            self.name,
+            self.params
+        )
+    }
+}
+
+/// Render-thread end of the log (cloned into every graph)
+#[derive(Debug, Clone)]
+pub struct EventLogSender {
+    tx: SyncSender<LoggedEvent>,
+    enabled: Arc<AtomicBool>,
+}
+
+impl EventLogSender {
+    /// Whether anyone is looking; check before formatting an event
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Queue an event, dropping it if the channel is full
+    pub fn send(&self, event: LoggedEvent) {
+        let _ = self.tx.try_send(event);
+    }
+}
+
+/// UI end of the log: receives events and keeps the most recent lines
+#[derive(Debug)]
+pub struct EventLog {
+    rx: Receiver<LoggedEvent>,
+    sender: EventLogSender,
+    lines: VecDeque<String>,
+}
+
+impl EventLog {
+    /// A disabled log; call `set_enabled(true)` when the pane opens
+    pub fn new() -> Self {
+        let (tx, rx) = sync_channel(CHANNEL_CAPACITY);
+        Self {
+            rx,
+            sender: EventLogSender {
+                tx,
+                enabled: Arc::new(AtomicBool::new(false)),
+            },
+            lines: VecDeque::new(),
+        }
+    }
+
+    pub fn sender(&self) -> EventLogSender {
+        self.sender.clone()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sender.is_enabled()
+    }
+
+    /// Start or stop logging; stopping drops anything still queued
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.sender.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            while self.rx.try_recv().is_ok() {}
+        }
+    }
+
+    /// Move queued events into the visible history. Returns how many arrived.
+    pub fn drain(&mut self) -> usize {
+        let mut count = 0;
+        while let Ok(event) = self.rx.try_recv() {
+            self.lines.push_back(event.line());
+            count += 1;
+        }
+        while self.lines.len() > HISTORY_LINES {
+            self.lines.pop_front();
+        }
+        count
+    }
+
+    /// Logged lines, oldest first
+    pub fn lines(&self) -> &VecDeque<String> {
+        &self.lines
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(cycle: f64, name: &str) -> LoggedEvent {
+        LoggedEvent {
+            cycle,
+            name: name.to_string(),
+            params: "gain=1.00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_event_line_format() {
+        assert_eq!(event(12.25, "bd:2").line(), "12:0.250 bd:2 gain=1.00");
+        assert_eq!(event(0.0, "~bass").line(), "0:0.000 ~bass gain=1.00");
+    }
+
+    #[test]
+    fn test_log_is_bounded_and_toggleable() {
+        let mut log = EventLog::new();
+        let sender = log.sender();
+        assert!(!sender.is_enabled());
+
+        log.set_enabled(true);
+        assert!(sender.is_enabled());
+        // The channel drops what doesn't fit instead of blocking the render thread
+        for i in 0..CHANNEL_CAPACITY + 10 {
+            sender.send(event(i as f64, "bd"));
+        }
+        assert_eq!(log.drain(), CHANNEL_CAPACITY);
+        assert_eq!(log.lines().len(), HISTORY_LINES);
+        assert_eq!(log.lines().back().unwrap(), "511:0.000 bd gain=1.00");
+
+        sender.send(event(1.5, "sn"));
+        log.set_enabled(false);
+        assert_eq!(log.drain(), 0);
+    }
+}
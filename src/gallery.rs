@@ -0,0 +1,44 @@
+//! The example gallery: short, finished pieces behind `phonon examples`
+//!
+//! The pieces live in `examples/gallery` as `.phonon` files and are compiled
+//! into the binary, so `phonon examples play acid` works from any directory.
+//! Each one ends with `assert` lines on its synth buses, which hold without
+//! any samples installed; `phonon test examples/` checks them, so a change
+//! that breaks a piece fails there.
+
+/// A piece in the gallery
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    /// The file name without `.phonon`
+    pub name: &'static str,
+    pub code: &'static str,
+}
+
+impl Example {
+    /// The first `--` comment line, without the `--`
+    pub fn description(&self) -> &'static str {
+        self.code
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("--"))
+            .map(str::trim)
+            .unwrap_or("")
+    }
+}
+
+macro_rules! gallery {
+    ($($name:literal),* $(,)?) => {
+        &[$(Example {
+            name: $name,
+            code: include_str!(concat!("../examples/gallery/", $name, ".phonon")),
+        }),*]
+    };
+}
+
+/// Every piece, in the order `phonon examples list` shows them
+pub const EXAMPLES: &[Example] = gallery!["acid", "arpeggio", "drone", "dub", "house", "techno"];
+
+/// The piece called `name`
+pub fn find(name: &str) -> Option<&'static Example> {
+    let name = name.strip_suffix(".phonon").unwrap_or(name);
+    EXAMPLES.iter().find(|example| example.name == name)
+}
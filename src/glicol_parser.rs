@@ -5,6 +5,10 @@
 //! - `o: sin 440 >> mul 0.5`
 //! - `~amp: sin 1.0 >> mul 0.3 >> add 0.5`
 //! - Integration with mini-notation patterns
+//!
+//! Deprecated: Phonon's `#` replaced `>>` for chaining, and the commands
+//! read programs with [`crate::compositional_parser`]. Kept for code that
+//! still builds `DspEnvironment`s.
 
 #![allow(clippy::useless_vec)]
 use crate::glicol_dsp::{DspChain, DspEnvironment, DspNode};
@@ -2,6 +2,9 @@
 //! Enhanced Glicol parser with pattern parameter support
 //!
 //! This parser allows patterns as parameters: `lpf "1000 2000 500" 0.8`
+//!
+//! Deprecated along with [`crate::glicol_parser`]: patterns as parameters
+//! are part of the language [`crate::compositional_parser`] reads.
 
 use crate::dsp_parameter::DspParameter;
 use crate::glicol_dsp_v2::{DspChain, DspEnvironment, DspNode};
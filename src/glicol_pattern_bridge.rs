@@ -7,7 +7,7 @@
 //! and synthesis to modulate patterns.
 
 use crate::glicol_dsp::{DspChain, DspEnvironment};
-use crate::mini_notation::parse_mini_notation;
+use crate::mini_notation_v3::parse_mini_notation;
 use crate::pattern::{Fraction, Pattern, State, TimeSpan};
 use crate::signal_graph::SignalGraph;
 use std::collections::HashMap;
@@ -0,0 +1,284 @@
+//! Building graphs from Rust without the DSL
+//!
+//! ```
+//! use phonon::graph_builder::GraphBuilder;
+//! use phonon::unified_graph::Waveform;
+//!
+//! let mut builder = GraphBuilder::new(44100.0);
+//! builder.set_tempo(0.5);
+//! let drums = builder.add_sample("bd sn");
+//! let lfo = builder.add_pattern("300 2000");
+//! let bass = builder.add_waveform(Waveform::Saw, 55.0);
+//! let bass = builder.add_lowpass(bass, lfo, 0.7);
+//! let bass = builder.add_gain(bass, 0.3);
+//! let mix = builder.add_mix([drums, bass]);
+//! builder.set_output(mix);
+//! let mut graph = builder.build();
+//! let audio = graph.render(44100);
+//! ```
+//!
+//! The graph's raw nodes, [`SignalNode`](crate::unified_graph::SignalNode),
+//! carry their running state (oscillator phase, sample playback positions,
+//! filter memory) in their fields, and change whenever a node gains a
+//! parameter or an optimisation. They are hidden from the docs and may change
+//! in any release. [`GraphBuilder`] is the stable way in: it adds nodes by what
+//! they do, takes numbers, other nodes, buses or mini-notation as an [`Input`],
+//! and hands back [`Node`] handles.
+
+use crate::mini_notation_v3::parse_mini_notation;
+use crate::unified_graph::{
+    FilterState, NodeId, ReverbState, Signal, SignalNode, UnifiedSignalGraph, Waveform,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Longest time [`GraphBuilder::add_delay`] can delay by, in seconds
+pub const MAX_DELAY_TIME: f32 = 1.0;
+
+/// A node added by a [`GraphBuilder`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Node(NodeId);
+
+impl Node {
+    /// The node's ID in the built graph
+    pub fn id(self) -> NodeId {
+        self.0
+    }
+}
+
+/// A node parameter: a number, a [`Node`], a bus or a mini-notation
+/// pattern of numbers
+#[derive(Debug, Clone)]
+pub struct Input(Signal);
+
+impl Input {
+    /// The bus `name` (without the `~`), as set by
+    /// [`GraphBuilder::set_bus`]
+    pub fn bus(name: &str) -> Self {
+        Self(Signal::Bus(name.to_string()))
+    }
+}
+
+impl From<f32> for Input {
+    fn from(value: f32) -> Self {
+        Self(Signal::Value(value))
+    }
+}
+
+impl From<f64> for Input {
+    fn from(value: f64) -> Self {
+        Self(Signal::Value(value as f32))
+    }
+}
+
+impl From<Node> for Input {
+    fn from(node: Node) -> Self {
+        Self(Signal::Node(node.0))
+    }
+}
+
+/// A pattern, as `"200 400 800"`
+impl From<&str> for Input {
+    fn from(pattern: &str) -> Self {
+        Self(Signal::Pattern(pattern.to_string()))
+    }
+}
+
+/// Adds nodes to a new graph, then hands the graph over with
+/// [`build`](Self::build)
+pub struct GraphBuilder {
+    graph: UnifiedSignalGraph,
+}
+
+impl GraphBuilder {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            graph: UnifiedSignalGraph::new(sample_rate),
+        }
+    }
+
+    /// Tempo in cycles per second
+    pub fn set_tempo(&mut self, cps: f32) {
+        self.graph.set_cps(cps);
+    }
+
+    fn add(&mut self, node: SignalNode) -> Node {
+        Node(self.graph.add_node(node))
+    }
+
+    /// Sine oscillator at `freq` Hz
+    pub fn add_oscillator(&mut self, freq: impl Into<Input>) -> Node {
+        self.add_waveform(Waveform::Sine, freq)
+    }
+
+    /// Oscillator of any waveform at `freq` Hz
+    pub fn add_waveform(&mut self, waveform: Waveform, freq: impl Into<Input>) -> Node {
+        self.add(SignalNode::Oscillator {
+            freq: freq.into().0,
+            waveform,
+            semitone_offset: 0.0,
+            phase: RefCell::new(0.0),
+            pending_freq: RefCell::new(None),
+            last_sample: RefCell::new(0.0),
+        })
+    }
+
+    /// White noise
+    pub fn add_noise(&mut self) -> Node {
+        self.add(SignalNode::WhiteNoise)
+    }
+
+    /// Samples played by a mini-notation pattern of names, as `s "bd*2 sn"`
+    pub fn add_sample(&mut self, pattern: &str) -> Node {
+        self.add(SignalNode::Sample {
+            pattern_str: pattern.to_string(),
+            pattern: parse_mini_notation(pattern),
+            last_trigger_time: -1.0,
+            last_cycle: -1,
+            playback_positions: HashMap::new(),
+            gain: Signal::Value(1.0),
+            pan: Signal::Value(0.0),
+            speed: Signal::Value(1.0),
+            cut_group: Signal::Value(0.0),
+            n: Signal::Value(0.0),
+            note: Signal::Value(0.0),
+            attack: Signal::Value(0.0),
+            release: Signal::Value(0.0),
+            envelope_type: None,
+            unit_mode: Signal::Value(0.0),
+            loop_enabled: Signal::Value(0.0),
+            begin: Signal::Value(0.0),
+            end: Signal::Value(1.0),
+        })
+    }
+
+    /// A mini-notation pattern of numbers as a stepped control signal
+    pub fn add_pattern(&mut self, pattern: &str) -> Node {
+        self.add(SignalNode::Pattern {
+            pattern_str: pattern.to_string(),
+            pattern: parse_mini_notation(pattern),
+            last_value: 0.0,
+            last_trigger_time: -1.0,
+        })
+    }
+
+    /// The sum of `inputs`
+    pub fn add_mix<I>(&mut self, inputs: impl IntoIterator<Item = I>) -> Node
+    where
+        I: Into<Input>,
+    {
+        let signals = inputs.into_iter().map(|input| input.into().0).collect();
+        self.add(SignalNode::Mix { signals })
+    }
+
+    /// `input` times `gain`
+    pub fn add_gain(&mut self, input: impl Into<Input>, gain: impl Into<Input>) -> Node {
+        self.add(SignalNode::Multiply {
+            a: input.into().0,
+            b: gain.into().0,
+        })
+    }
+
+    /// Resonant low-pass filter, `cutoff` in Hz
+    pub fn add_lowpass(
+        &mut self,
+        input: impl Into<Input>,
+        cutoff: impl Into<Input>,
+        q: impl Into<Input>,
+    ) -> Node {
+        self.add(SignalNode::LowPass {
+            input: input.into().0,
+            cutoff: cutoff.into().0,
+            q: q.into().0,
+            state: FilterState::default(),
+        })
+    }
+
+    /// Resonant high-pass filter, `cutoff` in Hz
+    pub fn add_highpass(
+        &mut self,
+        input: impl Into<Input>,
+        cutoff: impl Into<Input>,
+        q: impl Into<Input>,
+    ) -> Node {
+        self.add(SignalNode::HighPass {
+            input: input.into().0,
+            cutoff: cutoff.into().0,
+            q: q.into().0,
+            state: FilterState::default(),
+        })
+    }
+
+    /// Band-pass filter around `center` Hz
+    pub fn add_bandpass(
+        &mut self,
+        input: impl Into<Input>,
+        center: impl Into<Input>,
+        q: impl Into<Input>,
+    ) -> Node {
+        self.add(SignalNode::BandPass {
+            input: input.into().0,
+            center: center.into().0,
+            q: q.into().0,
+            state: FilterState::default(),
+        })
+    }
+
+    /// Feedback delay of `time` seconds, up to [`MAX_DELAY_TIME`]; `mix` 0
+    /// is dry, 1 wet
+    pub fn add_delay(
+        &mut self,
+        input: impl Into<Input>,
+        time: impl Into<Input>,
+        feedback: impl Into<Input>,
+        mix: impl Into<Input>,
+    ) -> Node {
+        let buffer_size = (self.graph.sample_rate() * MAX_DELAY_TIME) as usize;
+        self.add(SignalNode::Delay {
+            input: input.into().0,
+            time: time.into().0,
+            feedback: feedback.into().0,
+            mix: mix.into().0,
+            buffer: vec![0.0; buffer_size],
+            write_idx: 0,
+        })
+    }
+
+    /// Freeverb reverb; `room_size`, `damping` and `mix` run 0 to 1
+    pub fn add_reverb(
+        &mut self,
+        input: impl Into<Input>,
+        room_size: impl Into<Input>,
+        damping: impl Into<Input>,
+        mix: impl Into<Input>,
+    ) -> Node {
+        let state = ReverbState::new(self.graph.sample_rate());
+        self.add(SignalNode::Reverb {
+            input: input.into().0,
+            room_size: room_size.into().0,
+            damping: damping.into().0,
+            mix: mix.into().0,
+            state,
+        })
+    }
+
+    /// Name `node` as the bus `name`, for [`Input::bus`]
+    pub fn set_bus(&mut self, name: &str, node: Node) {
+        self.graph.add_bus(name.to_string(), node.0);
+    }
+
+    /// Send `node` to the main output
+    pub fn set_output(&mut self, node: Node) {
+        self.graph.set_output(node.0);
+    }
+
+    /// Send `node` to output `channel`, counting from 1
+    pub fn set_output_channel(&mut self, channel: usize, node: Node) {
+        self.graph.set_output_channel(channel, node.0);
+    }
+
+    /// The finished graph, ready to render
+    pub fn build(self) -> UnifiedSignalGraph {
+        self.graph
+    }
+}
@@ -23,9 +23,13 @@
 //! -- Apply groove to a pattern
 //! out $ s "bd sn bd sn" $ apply_groove ~groove
 //! ```
+//!
+//! Named grooves (`groove "mpc60_54"`) are the built-in presets or groove
+//! files in `~/.phonon/grooves` (see [`resolve_groove`]).
 
 use crate::pattern::{Fraction, Pattern, State, TimeSpan};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// A groove template containing timing deviations per grid position
@@ -42,7 +46,8 @@ pub struct GrooveTemplate {
     /// Positive values = later (push), negative values = earlier (pull)
     pub deviations: Vec<f64>,
 
-    /// Optional velocity/accent deviations (not yet implemented)
+    /// Optional velocity deviations per grid position: the event's gain is
+    /// multiplied by 1.0 + deviation
     pub velocity_deviations: Option<Vec<f64>>,
 
     /// Source information (original file, BPM, etc.)
@@ -110,6 +115,48 @@ impl GrooveTemplate {
         self.deviations[grid_pos]
     }
 
+    /// Get the velocity deviation for a position within a cycle, if the
+    /// template has velocities
+    pub fn velocity_deviation_at(&self, cycle_position: f64) -> Option<f64> {
+        let velocities = self.velocity_deviations.as_ref()?;
+        let pos = cycle_position.rem_euclid(1.0);
+        let grid_pos = (pos * self.grid_size as f64).floor() as usize;
+        velocities.get(grid_pos).copied()
+    }
+
+    /// Parse a groove file. Each offset is one grid step (16 offsets make a
+    /// 16th-note groove), given as a fraction of the step:
+    ///
+    /// ```toml
+    /// offsets = [0.0, 0.12, 0.0, 0.08, ...]     # positive = late
+    /// velocities = [1.0, 0.7, 0.9, 0.6, ...]    # optional, 1.0 = unchanged
+    /// ```
+    pub fn from_toml(name: &str, text: &str) -> Result<Self, String> {
+        let file: GrooveFile = toml::from_str(text).map_err(|e| e.to_string())?;
+        if file.offsets.is_empty() {
+            return Err("offsets is empty".to_string());
+        }
+
+        let grid_size = file.offsets.len() as u32;
+        let deviations = file
+            .offsets
+            .iter()
+            .map(|offset| offset / grid_size as f64)
+            .collect();
+        let mut template = GrooveTemplate::new(name.to_string(), grid_size, deviations);
+        if let Some(velocities) = file.velocities {
+            if velocities.len() != file.offsets.len() {
+                return Err(format!(
+                    "{} velocities for {} offsets",
+                    velocities.len(),
+                    file.offsets.len()
+                ));
+            }
+            template.velocity_deviations = Some(velocities.iter().map(|v| v - 1.0).collect());
+        }
+        Ok(template)
+    }
+
     /// Get deviation with interpolation between grid positions
     pub fn deviation_at_interpolated(&self, cycle_position: f64) -> f64 {
         let pos = cycle_position.rem_euclid(1.0);
@@ -170,6 +217,46 @@ impl GrooveTemplate {
     }
 }
 
+/// Contents of a groove file (see `GrooveTemplate::from_toml`)
+#[derive(Debug, serde::Deserialize)]
+struct GrooveFile {
+    offsets: Vec<f64>,
+    velocities: Option<Vec<f64>>,
+}
+
+/// Default groove file directory: `~/.phonon/grooves`
+pub fn default_groove_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".phonon").join("grooves"))
+}
+
+/// Look up a groove by name: a built-in preset (including `mpc60_54` and the
+/// other MPC60 swing amounts), `name.toml` in `~/.phonon/grooves`, or a path
+/// to a groove file
+pub fn resolve_groove(name: &str) -> Result<GrooveTemplate, String> {
+    if let Some(template) = presets::by_name(name) {
+        return Ok(template);
+    }
+
+    let path = if name.ends_with(".toml") || name.contains('/') {
+        Some(PathBuf::from(name))
+    } else {
+        default_groove_dir()
+            .map(|dir| dir.join(format!("{}.toml", name)))
+            .filter(|path| path.exists())
+    };
+    let Some(path) = path else {
+        return Err(format!(
+            "Unknown groove preset '{}'. Available: mpc, mpc60_50 to mpc60_75, hiphop, \
+             reggae, jazz, drunken, or a groove file in ~/.phonon/grooves",
+            name
+        ));
+    };
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("groove file {}: {}", path.display(), e))?;
+    GrooveTemplate::from_toml(name, &text)
+        .map_err(|e| format!("groove file {}: {}", path.display(), e))
+}
+
 /// Analyze audio to extract onset times and timing deviations
 pub struct GrooveAnalyzer {
     sample_rate: f32,
@@ -481,6 +568,18 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
                         *whole = TimeSpan::new(whole.begin + shift, whole.end + shift);
                     }
 
+                    // Velocities scale the event's gain (read when it triggers)
+                    if let Some(velocity) = template.velocity_deviation_at(cycle_pos) {
+                        let gain = hap
+                            .context
+                            .get("groove_gain")
+                            .and_then(|gain| gain.parse::<f64>().ok())
+                            .unwrap_or(1.0)
+                            * (1.0 + velocity * groove_amount).max(0.0);
+                        hap.context
+                            .insert("groove_gain".to_string(), gain.to_string());
+                    }
+
                     hap
                 })
                 .collect()
@@ -536,6 +635,42 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
 pub mod presets {
     use super::GrooveTemplate;
 
+    /// Preset by name, as used by `groove "name"`
+    pub fn by_name(name: &str) -> Option<GrooveTemplate> {
+        let template = match name {
+            "mpc" | "mpc_swing" => mpc_swing(0.5),
+            "hiphop" | "lazy_hiphop" => lazy_hiphop(),
+            "reggae" | "reggae_one_drop" | "one_drop" => reggae_one_drop(),
+            "jazz" | "jazz_swing" => jazz_swing(0.5),
+            "drunken" | "drunk" => drunken(0.5),
+            _ => {
+                let percent: f64 = name.strip_prefix("mpc60_")?.parse().ok()?;
+                if !(50.0..=75.0).contains(&percent) {
+                    return None;
+                }
+                mpc60(percent)
+            }
+        };
+        Some(template)
+    }
+
+    /// MPC60 16th-note swing: the second 16th of each pair lands `percent`%
+    /// of the way through the pair (50 = straight, 54/58/62/66 are the
+    /// classic settings, 75 = dotted)
+    pub fn mpc60(percent: f64) -> GrooveTemplate {
+        let mut deviations = vec![0.0; 16];
+        for i in (1..16).step_by(2) {
+            // A pair of 16ths is 1/8 of a cycle
+            deviations[i] = (percent - 50.0) / 100.0 / 8.0;
+        }
+
+        let mut template = GrooveTemplate::new(format!("mpc60_{}", percent), 16, deviations);
+        template
+            .metadata
+            .insert("style".to_string(), "mpc".to_string());
+        template
+    }
+
     /// MPC-style swing (delays 2nd and 4th 16th notes)
     pub fn mpc_swing(amount: f64) -> GrooveTemplate {
         // 16th note grid
@@ -770,6 +905,38 @@ mod tests {
         assert!((pos1 - 0.30).abs() < 0.01, "Expected ~0.30, got {}", pos1);
     }
 
+    #[test]
+    fn test_preset_mpc60() {
+        let groove = presets::by_name("mpc60_54").unwrap();
+
+        assert_eq!(groove.grid_size, 16);
+        assert_eq!(groove.deviations[0], 0.0);
+        // 54% of a 1/8-cycle pair is 0.04 / 8 later than straight
+        assert!((groove.deviations[1] - 0.005).abs() < 1e-9);
+        assert!(presets::by_name("mpc60_80").is_none());
+        assert!(presets::by_name("mpc60_x").is_none());
+    }
+
+    #[test]
+    fn test_groove_file() {
+        let template = GrooveTemplate::from_toml(
+            "push",
+            "offsets = [0.0, 0.2, 0.0, -0.1]\nvelocities = [1.0, 0.5, 1.0, 1.2]\n",
+        )
+        .unwrap();
+
+        assert_eq!(template.grid_size, 4);
+        // Offsets are fractions of a grid step
+        assert!((template.deviation_at(0.25) - 0.05).abs() < 1e-9);
+        assert!((template.deviation_at(0.75) + 0.025).abs() < 1e-9);
+        assert!((template.velocity_deviation_at(0.3).unwrap() + 0.5).abs() < 1e-9);
+
+        assert!(
+            GrooveTemplate::from_toml("bad", "offsets = [0.0, 0.1]\nvelocities = [1.0]").is_err()
+        );
+        assert!(GrooveTemplate::from_toml("empty", "offsets = []").is_err());
+    }
+
     #[test]
     fn test_preset_mpc_swing() {
         let groove = presets::mpc_swing(0.5);
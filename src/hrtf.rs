@@ -0,0 +1,326 @@
+//! Binaural rendering for `spatial: binaural`
+//!
+//! The placed sources are encoded to first-order ambisonics, decoded to a
+//! cube of virtual speakers, and each speaker is convolved with the
+//! head-related impulse responses (HRIRs) for its direction: one per ear,
+//! summed into the left and right outputs for headphones.
+//!
+//! The HRIRs come from a set:
+//!
+//! - built in: a spherical head model (interaural delay and head shadow, no
+//!   pinna cues), used by `spatial: binaural`
+//! - a `.sofa` file, with phonon built with `--features sofa`
+//! - a directory of stereo WAV files named `<azimuth>_<elevation>.wav`
+//!   (`90_0.wav`, `-30_15.wav`), at the program's sample rate
+//!
+//! `spatial: binaural "kemar"` looks for `kemar.sofa` or `kemar/` in
+//! `~/.phonon/hrtf` when there's no such path.
+
+use crate::spatial::SpatialLayout;
+use std::path::{Path, PathBuf};
+
+/// Virtual speaker directions (azimuth, elevation in degrees): the corners
+/// of a cube, four above the horizon and four below
+pub const VIRTUAL_SPEAKERS: [(f32, f32); 8] = [
+    (45.0, 35.26),
+    (135.0, 35.26),
+    (225.0, 35.26),
+    (315.0, 35.26),
+    (45.0, -35.26),
+    (135.0, -35.26),
+    (225.0, -35.26),
+    (315.0, -35.26),
+];
+
+/// Head radius in metres, for the spherical head model
+const HEAD_RADIUS: f32 = 0.0875;
+
+/// Speed of sound in m/s
+const SPEED_OF_SOUND: f32 = 343.0;
+
+/// Length of the modelled HRIRs in seconds
+const MODEL_LENGTH: f32 = 0.003;
+
+/// The HRIR pair measured (or modelled) for one direction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hrir {
+    pub azimuth: f32,
+    pub elevation: f32,
+    pub left: Vec<f32>,
+    pub right: Vec<f32>,
+}
+
+/// A set of HRIRs, one pair per direction
+#[derive(Debug, Clone, PartialEq)]
+pub struct HrirSet {
+    pub responses: Vec<Hrir>,
+}
+
+impl HrirSet {
+    /// The spherical head model, at the virtual speaker directions
+    pub fn spherical_head(sample_rate: f32) -> Self {
+        let responses = VIRTUAL_SPEAKERS
+            .iter()
+            .map(|&(azimuth, elevation)| Hrir {
+                azimuth,
+                elevation,
+                left: spherical_head_response(sample_rate, azimuth, elevation, -90.0),
+                right: spherical_head_response(sample_rate, azimuth, elevation, 90.0),
+            })
+            .collect();
+        Self { responses }
+    }
+
+    /// Load the set named in `spatial: binaural "<set>"`
+    pub fn load(set: &str, sample_rate: f32) -> Result<Self, String> {
+        let path = resolve_set(set).ok_or_else(|| {
+            format!(
+                "HRIR set not found: {} (a .sofa file, a directory of \
+                 <azimuth>_<elevation>.wav files, or a name in ~/.phonon/hrtf)",
+                set
+            )
+        })?;
+        if path.is_dir() {
+            load_wav_dir(&path, sample_rate)
+        } else {
+            load_sofa(&path, sample_rate)
+        }
+    }
+
+    /// The pair for the direction nearest `azimuth` and `elevation`
+    pub fn nearest(&self, azimuth: f32, elevation: f32) -> Option<&Hrir> {
+        let target = direction(azimuth, elevation);
+        self.responses.iter().max_by(|a, b| {
+            let closeness = |hrir: &Hrir| dot(direction(hrir.azimuth, hrir.elevation), target);
+            closeness(a).total_cmp(&closeness(b))
+        })
+    }
+}
+
+/// Gain of an FOA channel (W, Y, Z, X) in the feed of the virtual speaker
+/// at `azimuth`/`elevation`: a basic decoder with max-rE weighting
+pub fn decode_gain(channel: usize, azimuth: f32, elevation: f32) -> f32 {
+    let count = VIRTUAL_SPEAKERS.len() as f32;
+    let weight = if channel == 0 { 1.0 } else { 3f32.sqrt() };
+    weight * SpatialLayout::Foa.gain(channel, azimuth, elevation) / count
+}
+
+/// Unit vector (front, right, up) for a direction in degrees
+fn direction(azimuth: f32, elevation: f32) -> [f32; 3] {
+    let (azimuth, elevation) = (azimuth.to_radians(), elevation.to_radians());
+    [
+        elevation.cos() * azimuth.cos(),
+        elevation.cos() * azimuth.sin(),
+        elevation.sin(),
+    ]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// One ear's response in the spherical head model (Brown & Duda): the
+/// Woodworth interaural delay, then a one-pole head-shadow filter that
+/// boosts highs facing the ear and cuts them behind the head
+fn spherical_head_response(sample_rate: f32, azimuth: f32, elevation: f32, ear: f32) -> Vec<f32> {
+    let angle = dot(direction(azimuth, elevation), direction(ear, 0.0))
+        .clamp(-1.0, 1.0)
+        .acos();
+    let head_delay = HEAD_RADIUS / SPEED_OF_SOUND;
+    let delay = if angle < std::f32::consts::FRAC_PI_2 {
+        -head_delay * angle.cos()
+    } else {
+        head_delay * (angle - std::f32::consts::FRAC_PI_2)
+    };
+    // Shifted so the nearest ear hears it at 0
+    let delay = ((delay + head_delay) * sample_rate).round() as usize;
+
+    let alpha = 1.05 + 0.95 * (angle.to_degrees() / 150.0 * std::f32::consts::PI).cos();
+    let k = sample_rate * HEAD_RADIUS / SPEED_OF_SOUND;
+    let (b0, b1, a1) = (
+        (1.0 + alpha * k) / (1.0 + k),
+        (1.0 - alpha * k) / (1.0 + k),
+        (1.0 - k) / (1.0 + k),
+    );
+
+    let length = ((sample_rate * MODEL_LENGTH) as usize).max(delay + 1);
+    let mut response = vec![0.0; length];
+    let (mut previous_in, mut previous_out) = (0.0, 0.0);
+    for (n, sample) in response.iter_mut().enumerate() {
+        let input = if n == delay { 1.0 } else { 0.0 };
+        *sample = b0 * input + b1 * previous_in - a1 * previous_out;
+        previous_in = input;
+        previous_out = *sample;
+    }
+    response
+}
+
+/// A path, or a name in `~/.phonon/hrtf`
+fn resolve_set(set: &str) -> Option<PathBuf> {
+    let path = PathBuf::from(set);
+    if path.exists() {
+        return Some(path);
+    }
+    let dir = dirs::home_dir()?.join(".phonon").join("hrtf");
+    [dir.join(format!("{}.sofa", set)), dir.join(set)]
+        .into_iter()
+        .find(|path| path.exists())
+}
+
+/// A directory of stereo `<azimuth>_<elevation>.wav` files
+fn load_wav_dir(dir: &Path, sample_rate: f32) -> Result<HrirSet, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("HRIR set {}: {}", dir.display(), e))?;
+    let mut responses = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let angles = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .filter(|_| path.extension().is_some_and(|ext| ext == "wav"))
+            .and_then(|stem| stem.split_once('_'))
+            .and_then(|(azimuth, elevation)| {
+                Some((azimuth.parse::<f32>().ok()?, elevation.parse::<f32>().ok()?))
+            });
+        let Some((azimuth, elevation)) = angles else {
+            continue;
+        };
+
+        let error = |e: String| format!("HRIR {}: {}", path.display(), e);
+        let mut reader = hound::WavReader::open(&path).map_err(|e| error(e.to_string()))?;
+        let spec = reader.spec();
+        if spec.channels != 2 {
+            return Err(error(format!("needs 2 channels, has {}", spec.channels)));
+        }
+        if spec.sample_rate as f32 != sample_rate {
+            return Err(error(format!(
+                "recorded at {} Hz, the program runs at {} Hz",
+                spec.sample_rate, sample_rate
+            )));
+        }
+        let samples: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>(),
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .samples::<i32>()
+                    .map(|sample| sample.map(|sample| sample as f32 / scale))
+                    .collect::<Result<_, _>>()
+            }
+        }
+        .map_err(|e| error(e.to_string()))?;
+        responses.push(Hrir {
+            azimuth,
+            elevation,
+            left: samples.iter().step_by(2).copied().collect(),
+            right: samples.iter().skip(1).step_by(2).copied().collect(),
+        });
+    }
+    if responses.is_empty() {
+        return Err(format!(
+            "HRIR set {}: no <azimuth>_<elevation>.wav files",
+            dir.display()
+        ));
+    }
+    Ok(HrirSet { responses })
+}
+
+/// The HRIRs of a SOFA file at the virtual speaker directions, resampled
+/// to the program's rate
+#[cfg(feature = "sofa")]
+fn load_sofa(path: &Path, sample_rate: f32) -> Result<HrirSet, String> {
+    use sofar::reader::{Filter, OpenOptions};
+
+    let sofa = OpenOptions::new()
+        .sample_rate(sample_rate)
+        .open(path)
+        .map_err(|e| format!("HRIR set {}: {}", path.display(), e))?;
+    let mut filter = Filter::new(sofa.filter_len());
+    let responses = VIRTUAL_SPEAKERS
+        .iter()
+        .map(|&(azimuth, elevation)| {
+            // SOFA's y axis points left
+            let [front, right, up] = direction(azimuth, elevation);
+            sofa.filter(front, -right, up, &mut filter);
+            Hrir {
+                azimuth,
+                elevation,
+                left: filter.left.to_vec(),
+                right: filter.right.to_vec(),
+            }
+        })
+        .collect();
+    Ok(HrirSet { responses })
+}
+
+#[cfg(not(feature = "sofa"))]
+fn load_sofa(path: &Path, _sample_rate: f32) -> Result<HrirSet, String> {
+    Err(format!(
+        "HRIR set {}: reading SOFA files needs phonon built with --features sofa",
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn energy(response: &[f32]) -> f32 {
+        response.iter().map(|x| x * x).sum()
+    }
+
+    fn onset(response: &[f32]) -> usize {
+        response.iter().position(|x| x.abs() > 1e-3).unwrap()
+    }
+
+    #[test]
+    fn test_spherical_head_favours_the_near_ear() {
+        let right = spherical_head_response(44100.0, 90.0, 0.0, 90.0);
+        let left = spherical_head_response(44100.0, 90.0, 0.0, -90.0);
+        assert!(energy(&right) > energy(&left) * 2.0);
+        // About 0.65 ms later at the far ear
+        let lag = onset(&left) - onset(&right);
+        assert!((25..=32).contains(&lag), "{}", lag);
+
+        // Straight ahead both ears match
+        let set = HrirSet::spherical_head(44100.0);
+        assert_eq!(set.responses.len(), VIRTUAL_SPEAKERS.len());
+        let front = spherical_head_response(44100.0, 0.0, 0.0, 90.0);
+        let other = spherical_head_response(44100.0, 0.0, 0.0, -90.0);
+        assert!(front.iter().zip(&other).all(|(a, b)| (a - b).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_nearest_direction() {
+        let set = HrirSet::spherical_head(44100.0);
+        let hrir = set.nearest(100.0, 60.0).unwrap();
+        assert_eq!((hrir.azimuth, hrir.elevation), (135.0, 35.26));
+        let hrir = set.nearest(-30.0, -10.0).unwrap();
+        assert_eq!((hrir.azimuth, hrir.elevation), (315.0, -35.26));
+    }
+
+    #[test]
+    fn test_decoder_keeps_direction() {
+        // A source to the right reaches the right-hand speakers most
+        let feed = |speaker: (f32, f32)| {
+            (0..4)
+                .map(|channel| {
+                    decode_gain(channel, speaker.0, speaker.1)
+                        * SpatialLayout::Foa.gain(channel, 90.0, 0.0)
+                })
+                .sum::<f32>()
+        };
+        assert!(feed((45.0, 35.26)) > feed((315.0, 35.26)));
+        assert!(feed((135.0, -35.26)) > feed((225.0, -35.26)));
+        // The feeds sum to the source
+        let total: f32 = VIRTUAL_SPEAKERS.iter().map(|&speaker| feed(speaker)).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_missing_set() {
+        assert!(HrirSet::load("no-such-hrir-set", 44100.0)
+            .unwrap_err()
+            .contains("not found"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Latency compensation for external gear
+//!
+//! ```phonon
+//! ~bass $ "c2 ~ eb2 g2" # latency -12ms
+//! cvout 3 (~bass |> voct)
+//! ```
+//!
+//! `# latency` at the end of a bus moves its events by a time in seconds, so
+//! a MIDI or CV device that sounds 12 ms late can be sent its notes 12 ms
+//! early. The bus's patterns are queried that much earlier (negative) or
+//! later (positive), rather than its audio being delayed, so gates, pitch
+//! and samples all move together. Being in seconds, the shift holds at any
+//! tempo: each query reads the graph's current cps.
+//!
+//! A [`Nudge`] moves a bus the same way while it plays: the editor's
+//! `:nudge ~drums +10ms` sets where it should be and the bus's patterns
+//! glide there, to line Phonon up by ear with a source it can't sync to.
+
+use crate::pattern::{Fraction, Pattern, State, TimeSpan};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// The largest shift `# latency` accepts, in seconds either way; more is a
+/// musical offset, for `late` or `early`
+pub const MAX_LATENCY: f64 = 1.0;
+
+/// How fast a nudge moves a bus toward its new offset, in seconds per
+/// second. Below 1, so its events play slightly faster or slower while it
+/// moves, but each one plays once
+pub const NUDGE_RATE: f64 = 0.05;
+
+/// `pattern` with its events moved by `seconds`, at the tempo in `cps` (an
+/// f32 in bits) when queried
+pub fn shift_by_seconds<T>(pattern: Pattern<T>, seconds: f64, cps: Arc<AtomicU32>) -> Pattern<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    shift_with(pattern, move |_| {
+        seconds * f32::from_bits(cps.load(Ordering::Relaxed)) as f64
+    })
+}
+
+/// `pattern` with its events moved by `nudge`'s offset, at the tempo in
+/// `cps` (an f32 in bits) when queried
+pub fn shift_by_nudge<T>(pattern: Pattern<T>, nudge: Arc<Nudge>, cps: Arc<AtomicU32>) -> Pattern<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    shift_with(pattern, move |cycle| {
+        let cps = f32::from_bits(cps.load(Ordering::Relaxed)) as f64;
+        nudge.offset_at(cycle, cps) * cps
+    })
+}
+
+/// How far a bus's events move
+#[derive(Debug, Clone)]
+pub enum Shift {
+    /// A fixed time in seconds (`# latency`)
+    Seconds(f64),
+    /// A live offset (`:nudge`)
+    Nudge(Arc<Nudge>),
+}
+
+impl Shift {
+    /// `pattern` with its events moved, at the tempo in `cps` (an f32 in
+    /// bits) when queried
+    pub fn apply<T>(&self, pattern: Pattern<T>, cps: Arc<AtomicU32>) -> Pattern<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        match self {
+            Shift::Seconds(seconds) => shift_by_seconds(pattern, *seconds, cps),
+            Shift::Nudge(nudge) => shift_by_nudge(pattern, Arc::clone(nudge), cps),
+        }
+    }
+}
+
+/// `pattern` with its events moved by `shift(cycle)` cycles, for a query
+/// starting at `cycle`
+fn shift_with<T, F>(pattern: Pattern<T>, shift: F) -> Pattern<T>
+where
+    T: Clone + Send + Sync + 'static,
+    F: Fn(f64) -> f64 + Send + Sync + 'static,
+{
+    Pattern::new(move |state: &State| {
+        let shift = shift(state.span.begin.to_float());
+        if shift == 0.0 {
+            return pattern.query(state);
+        }
+        let moved = |span: TimeSpan, by: f64| {
+            TimeSpan::new(
+                Fraction::from_float(span.begin.to_float() + by),
+                Fraction::from_float(span.end.to_float() + by),
+            )
+        };
+        let query = State {
+            span: moved(state.span, -shift),
+            controls: state.controls.clone(),
+        };
+        pattern
+            .query(&query)
+            .into_iter()
+            .map(|mut hap| {
+                hap.part = moved(hap.part, shift);
+                hap.whole = hap.whole.map(|whole| moved(whole, shift));
+                hap
+            })
+            .collect()
+    })
+}
+
+/// A bus's live offset in seconds, shared between the editor, which sets
+/// its target, and the bus's patterns, which read it as they play. The
+/// offset heads for the target at [`NUDGE_RATE`]
+#[derive(Debug)]
+pub struct Nudge {
+    /// Where the offset is heading (f64 bits)
+    target: AtomicU64,
+    /// The offset now (f64 bits)
+    offset: AtomicU64,
+    /// The cycle `offset` was last moved at (f64 bits, NaN before any query)
+    at: AtomicU64,
+}
+
+impl Default for Nudge {
+    fn default() -> Self {
+        Self {
+            target: AtomicU64::new(0.0f64.to_bits()),
+            offset: AtomicU64::new(0.0f64.to_bits()),
+            at: AtomicU64::new(f64::NAN.to_bits()),
+        }
+    }
+}
+
+impl Nudge {
+    /// The offset the bus is heading for
+    pub fn target(&self) -> f64 {
+        f64::from_bits(self.target.load(Ordering::Relaxed))
+    }
+
+    /// Move the target by `seconds`, within [`MAX_LATENCY`] either way.
+    /// Returns the new target
+    pub fn nudge(&self, seconds: f64) -> f64 {
+        let target = (self.target() + seconds).clamp(-MAX_LATENCY, MAX_LATENCY);
+        self.target.store(target.to_bits(), Ordering::Relaxed);
+        target
+    }
+
+    /// Head back to no offset
+    pub fn reset(&self) {
+        self.target.store(0.0f64.to_bits(), Ordering::Relaxed);
+    }
+
+    /// The offset at `cycle`, moving it toward the target by the time
+    /// passed since the last query. Queries for earlier cycles, such as
+    /// several patterns reading the same block, leave it in place; a jump
+    /// back of over a cycle (`:cue`) starts counting from there
+    fn offset_at(&self, cycle: f64, cps: f64) -> f64 {
+        let offset = f64::from_bits(self.offset.load(Ordering::Relaxed));
+        let at = f64::from_bits(self.at.load(Ordering::Relaxed));
+        if at.is_nan() || cycle < at - 1.0 {
+            self.at.store(cycle.to_bits(), Ordering::Relaxed);
+            return offset;
+        }
+        if cycle <= at {
+            return offset;
+        }
+        let step = NUDGE_RATE * (cycle - at) / cps.max(1e-6);
+        let offset = offset + (self.target() - offset).clamp(-step, step);
+        self.offset.store(offset.to_bits(), Ordering::Relaxed);
+        self.at.store(cycle.to_bits(), Ordering::Relaxed);
+        offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn onsets(pattern: &Pattern<String>, begin: f64, end: f64) -> Vec<f64> {
+        pattern
+            .query(&State {
+                span: TimeSpan::new(Fraction::from_float(begin), Fraction::from_float(end)),
+                controls: Default::default(),
+            })
+            .iter()
+            .filter_map(|hap| hap.whole.filter(|whole| whole.begin == hap.part.begin))
+            .map(|whole| whole.begin.to_float())
+            .collect()
+    }
+
+    #[test]
+    fn test_shift_follows_the_tempo() {
+        let cps = Arc::new(AtomicU32::new(2.0f32.to_bits()));
+        let pattern = Pattern::pure("a".to_string());
+        let early = shift_by_seconds(pattern, -0.125, Arc::clone(&cps));
+        // 2 cps: an eighth of a second is a quarter cycle
+        assert_eq!(onsets(&early, 0.5, 1.5), vec![0.75]);
+
+        cps.store(1.0f32.to_bits(), Ordering::Relaxed);
+        assert_eq!(onsets(&early, 0.5, 1.5), vec![0.875]);
+    }
+
+    #[test]
+    fn test_nudge_glides_without_repeating_events() {
+        let cps = Arc::new(AtomicU32::new(2.0f32.to_bits()));
+        let nudge = Arc::new(Nudge::default());
+        let pattern = Pattern::pure("a".to_string()).fast(Pattern::pure(8.0));
+        let nudged = shift_by_nudge(pattern, Arc::clone(&nudge), cps);
+
+        assert_eq!(onsets(&nudged, 0.0, 0.01), vec![0.0]);
+        assert_eq!(nudge.nudge(0.01), 0.01);
+        // Played in small blocks, every event sounds once and in order
+        let mut heard = Vec::new();
+        for block in 0..400 {
+            let begin = block as f64 / 100.0;
+            heard.extend(onsets(&nudged, begin, begin + 0.01));
+        }
+        assert_eq!(heard.len(), 32);
+        assert!(heard.windows(2).all(|pair| pair[1] > pair[0]));
+        // Two seconds in, the events are 10ms (a fiftieth of a cycle) late
+        let last = heard[31];
+        assert!((last - (3.875 + 0.02)).abs() < 1e-6, "{}", last);
+    }
+}
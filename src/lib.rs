@@ -156,6 +156,8 @@
 //! ### Main Modules
 //!
 //! - [`unified_graph`] - Central signal processing graph (start here!)
+//! - [`compositional_parser`] and [`compositional_compiler`] - The Phonon
+//!   language, as every command reads it
 //! - [`superdirt_synths`] - Synthesizer library and audio effects
 //! - [`mini_notation_v3`] - Pattern language parser and evaluator
 //! - [`voice_manager`] - Polyphonic voice allocation and sample playback
@@ -324,35 +326,56 @@ pub mod block_processor; // Core execution loop
 pub mod buffer_manager;
 pub mod buffer_pool; // Lock-free buffer pool for dataflow (Phase 5)
 pub mod dataflow_graph; // Dataflow graph coordinator (Phase 5)
+pub mod denormals; // Flush-to-zero + feedback flushing, master DC blocker
 pub mod dependency_graph;
+pub mod node_factory; // User AudioNodes registered under DSL names
 pub mod node_task; // Continuous async task wrapper for AudioNode (Phase 5)
 pub mod nodes; // Concrete AudioNode implementations // High-level graph wrapper (Phase 3)
 
+pub mod adaptive_quality; // Steps fidelity down under CPU pressure instead of underrunning
 pub mod audio;
 pub mod audio_analysis;
+pub mod audio_device; // Output/input device selection + sample-format conversion
 pub mod audio_similarity;
+pub mod bus_meters; // Per-bus peak meters and the render headroom report
+pub mod clock_divider; // `clockdiv` / `clockmult`: modular-style clock dividers and multipliers
+pub mod collab_session; // Multi-client jam sessions over OSC
 pub mod compositional_compiler;
 pub mod compositional_parser;
+pub mod cv; // `cvout` / `gateout`: control voltages and gates on DC-coupled outputs
 pub mod macro_expander;
+pub mod describe; // `phonon describe --json`: functions, synths and samples for tools
 pub mod dsp_parameter;
 pub mod engine;
+pub mod engine_config; // Block size shared by render, live and edit, with latency reporting
+#[deprecated(since = "0.1.0", note = "use compositional_parser")]
 pub mod enhanced_parser;
 pub mod envelope;
 pub mod error_diagnostics;
+pub mod event_log; // Triggered-event log for the editor pane
+pub mod gallery; // Curated .phonon pieces behind `phonon examples`
 pub mod groove;
+pub mod graph_builder; // Stable node-by-node graph building API over the hidden `SignalNode`
 pub mod glicol_dsp;
 pub mod glicol_dsp_v2;
+#[deprecated(since = "0.1.0", note = "use compositional_parser")]
 pub mod glicol_parser;
+#[deprecated(since = "0.1.0", note = "use compositional_parser")]
 pub mod glicol_parser_v2;
 pub mod glicol_pattern_bridge;
+pub mod hrtf; // HRIR sets and the FOA decoder behind `spatial: binaural`
 #[cfg(unix)]
 pub mod ipc;
+pub mod latency; // `# latency`: per-bus timing offsets for external gear
 pub mod link_clock; // Source-agnostic tempo/phase adapter (Ableton Link model)
 #[cfg(feature = "link")]
 pub mod link_backend_rusty; // rusty_link (Ableton Link) TempoSource backend — off-by-default `link` feature
 pub mod live;
+pub mod live_render; // The render thread's loop body: clock, swaps, transport and watchdog
+pub mod midi_export; // `phonon export-midi`: note patterns to a standard MIDI file
 pub mod midi_input;
 pub mod midi_output;
+#[deprecated(since = "0.1.0", note = "use mini_notation_v3")]
 pub mod mini_notation;
 pub mod mini_notation_v3;
 pub mod modal_editor;
@@ -360,8 +383,10 @@ pub mod modulation_router;
 pub mod onset_timing;
 pub mod osc_control;
 pub mod osc_live_server;
+pub mod output_buffer; // Live output buffer negotiation + latency measurement
 pub mod pattern;
 pub mod pattern_debug;
+#[deprecated(since = "0.1.0", note = "use compositional_parser")]
 pub mod pattern_lang_parser;
 pub mod pattern_metrics;
 pub mod pattern_midi;
@@ -373,17 +398,31 @@ pub mod pattern_signal;
 pub mod pattern_structure;
 pub mod pattern_test;
 pub mod pattern_tonal;
+pub mod phonon_engine; // `PhononEngine::builder()`: compile, render and stream from other programs
+pub mod pitch; // Note names and MIDI numbers to Hz, around a settable A4
 pub mod plugin_host;
+pub mod pluck; // `s "pluck"`: Karplus-Strong string voice with noise, pick or bow exciters
 pub mod reference_audio;
 pub mod render;
+pub mod render_assertions; // `assert` statements checked at render end
+pub mod render_batch; // `phonon render-batch`: many files from a manifest, in parallel
 pub mod render_swap; // Render-thread-owned graph swap primitive (SPSC command ring + graveyard)
+pub mod render_sweep; // `phonon sweep`: variants of a patch with a bus swept over values
+pub mod render_watchdog; // Panic + NaN/inf guard around each rendered block
+pub mod ring_handoff; // Synth-to-callback sample ring with a race-free stale-audio skip
+pub mod rt_log; // Lock-free log queue for the audio threads, drained by the UI
 pub mod sample_loader;
 pub mod scale_dsl;
+pub mod session; // `:save-session`: live sets suspended to disk and resumed
 pub mod shared_effect_state;
+pub mod spatial; // `spatial:` speaker layouts and azimuth/elevation gains
 pub mod signal_executor;
 pub mod signal_graph;
+#[deprecated(since = "0.1.0", note = "use compositional_parser")]
 pub mod signal_parser;
+#[deprecated(since = "0.1.0", note = "use phonon_engine::PhononEngine")]
 pub mod simple_dsp_executor;
+#[deprecated(since = "0.1.0", note = "use phonon_engine::PhononEngine")]
 pub mod simple_dsp_executor_v2;
 pub mod stress_harness;
 pub mod superdirt_synths;
@@ -392,9 +431,13 @@ pub mod synth_voice;
 pub mod synth_voice_manager;
 mod test_methods;
 pub mod thread_pool;
+pub mod tidal_convert; // `phonon convert --from tidal`: best-effort Tidal → Phonon translation
 pub mod unified_graph;
+#[deprecated(since = "0.1.0", note = "use compositional_parser and compositional_compiler")]
 pub mod unified_graph_parser;
+pub mod visuals; // `visuals:` band levels and onsets over OSC for audio-reactive visuals
 pub mod voice_manager;
+pub mod westcoast; // `s "westcoast"`: sine → wavefolder → low-pass gate voice
 
 #[cfg(target_arch = "x86_64")]
 pub mod voice_simd;
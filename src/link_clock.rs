@@ -20,6 +20,8 @@
 //!   `LiveClock::set_position` (main) or `Cmd::SetTempo` / `Cmd::SetCycle`
 //!   (phonon-audio) paths. This keeps `src/render_swap.rs` and
 //!   `src/unified_graph.rs` out of the Link work entirely (design §7).
+//!   A frontend that only steers playback by command (the editor's `:sync`)
+//!   asks a [`SyncFollower`] for the tempo and seek to send instead.
 //!
 //! ## The mapping (design §4.1)
 //!
@@ -195,6 +197,82 @@ pub fn snapshot_from_source<S: TempoSource + ?Sized>(
     }
 }
 
+/// Phase error `target - position` in cycles, taken to the nearest multiple of
+/// `span` cycles: in `[-span / 2, span / 2]`. Used when only the phase within
+/// a bar (or beat) is shared, not which bar it is. A `span` that isn't a
+/// positive number leaves the error as it is.
+#[inline]
+pub fn phase_error_within(target: f64, position: f64, span: f64) -> f64 {
+    let err = target - position;
+    if span.is_finite() && span > 0.0 {
+        err - (err / span).round() * span
+    } else {
+        err
+    }
+}
+
+/// What a [`SyncFollower`] asks of playback for one step: the `cps` to play
+/// at, and a cycle to seek to when joining.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SyncStep {
+    pub cps: f64,
+    pub seek: Option<f64>,
+}
+
+/// Follows a [`TempoSource`] for a frontend that steers playback by command
+/// (`Cmd::SetTempo` / `Cmd::Seek`) rather than owning the `LiveClock`.
+///
+/// Only the phase within the source's [`quantum`](TempoSource::quantum) is
+/// matched, so Phonon keeps its own cycle count: joining seeks at most half a
+/// quantum, and from then on the bounded [`nudged_cps`] correction keeps it
+/// in phase with no further seek (design §4.3). MIDI clock and tap tempo,
+/// which have no shared session epoch, are followed this way.
+#[derive(Clone, Debug, Default)]
+pub struct SyncFollower {
+    joined: bool,
+}
+
+impl SyncFollower {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seek into phase again at the next step, e.g. after the source restarted
+    /// or was re-tapped.
+    pub fn rejoin(&mut self) {
+        self.joined = false;
+    }
+
+    /// The correction for playback at `position` (cycles) at instant `at`, or
+    /// `None` while the source is stopped or has no tempo yet.
+    pub fn step<S: TempoSource + ?Sized>(
+        &mut self,
+        src: &S,
+        at: Instant,
+        position: f64,
+        beats_per_cycle: f64,
+    ) -> Option<SyncStep> {
+        let snap = snapshot_from_source(src, at, beats_per_cycle, 0);
+        if !snap.playing || !snap.cps.is_finite() || snap.cps <= 0.0 {
+            return None;
+        }
+        let span = beat_to_cycle(src.quantum(), beats_per_cycle);
+        let err = phase_error_within(snap.target_cycle, position, span);
+
+        if !self.joined || needs_hard_reseek(err) {
+            self.joined = true;
+            return Some(SyncStep {
+                cps: snap.cps,
+                seek: Some(position + err),
+            });
+        }
+        Some(SyncStep {
+            cps: nudged_cps(snap.cps, err),
+            seek: None,
+        })
+    }
+}
+
 /// Deterministic in-process [`TempoSource`] for tests — no network, no native
 /// dependency. Beats advance linearly from a base instant at the configured
 /// tempo.
@@ -449,6 +527,46 @@ mod tests {
         assert!(!snap.playing);
     }
 
+    // ---- command-driven follower (:sync) ------------------------------------
+
+    #[test]
+    fn test_phase_error_within_a_span() {
+        assert!((phase_error_within(2.3, 10.0, 1.0) - 0.3).abs() < EPS);
+        assert!((phase_error_within(2.8, 10.0, 1.0) + 0.2).abs() < EPS);
+        assert!((phase_error_within(0.3, 0.25, 0.25) - 0.05).abs() < EPS);
+        // No span: the whole error
+        assert!((phase_error_within(2.3, 10.0, 0.0) + 7.7).abs() < EPS);
+    }
+
+    #[test]
+    fn test_sync_follower_joins_within_the_bar_then_nudges() {
+        let base = Instant::now();
+        // Beat 2.2 of a 4-beat bar: 0.55 into the cycle
+        let src = MockTempoSource::with_origin(120.0, 4.0, base, 2.2);
+        let mut follower = SyncFollower::new();
+
+        // Joining from cycle 10 seeks to the nearest cycle at that phase
+        let join = follower.step(&src, base, 10.0, 4.0).unwrap();
+        assert!((join.cps - 0.5).abs() < EPS);
+        assert!((join.seek.unwrap() - 9.55).abs() < EPS);
+
+        // Then a little behind: faster by at most the nudge, no seek
+        let step = follower.step(&src, base, 9.54, 4.0).unwrap();
+        assert_eq!(step.seek, None);
+        assert!(step.cps > 0.5 && step.cps <= 0.5 * (1.0 + MAX_PHASE_NUDGE) + EPS);
+
+        // A quantum of one beat only lines up the beat
+        let beats = MockTempoSource::with_origin(120.0, 1.0, base, 2.2);
+        follower.rejoin();
+        let join = follower.step(&beats, base, 10.0, 4.0).unwrap();
+        assert!((join.seek.unwrap() - 10.05).abs() < EPS);
+
+        // Nothing to follow while stopped
+        let mut stopped = src.clone();
+        stopped.set_playing(false);
+        assert_eq!(follower.step(&stopped, base, 10.0, 4.0), None);
+    }
+
     // ---- convergence: the varispeed nudge actually reduces phase error ------
 
     #[test]
@@ -5,9 +5,9 @@
 //! `RefCell<UnifiedSignalGraph>` using panicking `.borrow()`/`.borrow_mut()`.
 //! That path was the last raw-borrow graph swap in the tree (rt-safety F-10 /
 //! C4) and was unreachable from every CLI command — the real `phonon live`
-//! command carries its own ring-buffer synthesis loop in `main.rs`, and the
-//! render-owner primitive (`src/render_swap.rs`) is now the single blessed
-//! swap channel. `LiveSession`/`MultiFileWatcher` were therefore retired
+//! command carries its own ring-buffer synthesis loop in `main.rs` (around
+//! `src/live_render.rs`), and the render-owner primitive
+//! (`src/render_swap.rs`) is now the single blessed swap channel. `LiveSession`/`MultiFileWatcher` were therefore retired
 //! rather than migrated, since they had no consumers.
 //!
 //! Only the `LiveRepl` stub remains — it is still referenced by the `phonon
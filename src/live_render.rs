@@ -0,0 +1,214 @@
+//! The render thread's half of live playback
+//!
+//! `phonon live`, the editor and
+//! [`PhononEngine::stream`](crate::phonon_engine::PhononEngine::stream) all
+//! play the same way: one thread owns the graph and renders it block by block
+//! into a ring, taking new programs, hush, seek and transport commands from a
+//! [`RenderSwap`] at each block boundary. [`LiveRender`] is that loop body, so
+//! the three differ only in where the blocks go:
+//!
+//! - a sample-advancing [`LiveClock`] is the only timing source. A swapped-in
+//!   graph is seeded from it, a tempo change rebases it and a seek moves it;
+//! - a stopped transport renders nothing, so the clock and every voice stay
+//!   where they were, and the edges are faded (see
+//!   [`Transport`](crate::render_swap::Transport));
+//! - each block runs under a [`RenderWatchdog`], and a graph whose render
+//!   panics is replaced with a silent one until the next program arrives.
+
+use crate::render_swap::RenderSwap;
+use crate::render_watchdog::RenderWatchdog;
+use crate::unified_graph::{LiveClock, UnifiedSignalGraph};
+
+/// The graph a render thread owns, with its clock and watchdog
+pub struct LiveRender {
+    render_swap: RenderSwap<UnifiedSignalGraph>,
+    graph: Box<UnifiedSignalGraph>,
+    /// Heap address of `graph`, so a swap (or a crash replacement) shows up
+    /// as a changed address
+    graph_addr: usize,
+    /// `None` until a real program plays; it is then seeded from that
+    /// program's own cycle position, so `setCycle` / `resetCycles` hold
+    clock: Option<LiveClock>,
+    /// Whether `graph` is only a placeholder waiting for the first swap
+    awaiting_swap: bool,
+    watchdog: RenderWatchdog,
+}
+
+impl LiveRender {
+    /// Play `graph` from its compiled cycle position
+    pub fn new(
+        render_swap: RenderSwap<UnifiedSignalGraph>,
+        graph: Box<UnifiedSignalGraph>,
+        watchdog: RenderWatchdog,
+    ) -> Self {
+        Self {
+            graph_addr: address(&graph),
+            render_swap,
+            graph,
+            clock: None,
+            awaiting_swap: false,
+            watchdog,
+        }
+    }
+
+    /// Play silence, with the clock unseeded, until the first swap replaces
+    /// `placeholder` (`phonon live` on a file that doesn't compile yet)
+    pub fn awaiting_swap(
+        render_swap: RenderSwap<UnifiedSignalGraph>,
+        placeholder: Box<UnifiedSignalGraph>,
+        watchdog: RenderWatchdog,
+    ) -> Self {
+        Self {
+            awaiting_swap: true,
+            ..Self::new(render_swap, placeholder, watchdog)
+        }
+    }
+
+    /// Block boundary: apply the commands sent since the last block and move
+    /// the clock along with them. Returns whether a different graph now plays
+    pub fn begin_block(&mut self) -> bool {
+        self.render_swap.apply_pending_commands(&mut self.graph);
+        let seek = self.render_swap.take_seek();
+        let addr = address(&self.graph);
+        let swapped = addr != self.graph_addr;
+        self.graph_addr = addr;
+        if self.awaiting_swap && !swapped {
+            return false;
+        }
+        self.awaiting_swap = false;
+
+        match self.clock.as_mut() {
+            None => {
+                self.clock = Some(LiveClock::new(
+                    self.graph.sample_rate(),
+                    self.graph.get_cps(),
+                    self.graph.get_cycle_position(),
+                ))
+            }
+            Some(clock) => {
+                // Follow the graph's tempo, rebasing so the position never jumps
+                clock.set_cps(self.graph.get_cps());
+                if let Some(cycle) = seek {
+                    // The graph has already moved; the clock follows
+                    clock.set_position(cycle);
+                }
+                if swapped {
+                    // Carry on from the live position rather than re-triggering
+                    // the events between the absorbed position and this one
+                    self.graph.set_cycle_position(clock.position());
+                }
+            }
+        }
+        if swapped {
+            self.watchdog.graph_changed();
+        }
+        swapped
+    }
+
+    /// Render the next block of interleaved stereo into `buffer`, advancing
+    /// the clock. Returns false, with the block silent and the clock where it
+    /// was, before the first program plays or while the transport is stopped
+    pub fn render_block(&mut self, buffer: &mut [f32]) -> bool {
+        let frames = buffer.len() / 2;
+        let clock = match self.clock.as_mut() {
+            Some(clock) if !self.render_swap.transport().is_held() => clock,
+            _ => {
+                buffer.fill(0.0);
+                return false;
+            }
+        };
+        let (start_cycle, increment, cps) = clock.advance_buffer(frames);
+        let graph = &mut self.graph;
+        if !self.watchdog.run_block(buffer, |buf| {
+            graph.process_buffer_at(buf, start_cycle, increment, cps)
+        }) {
+            // Nothing is absorbed from the crashed graph; the silent one keeps
+            // the stream and the clock running until the next program
+            let mut silent = UnifiedSignalGraph::new(self.graph.sample_rate());
+            silent.set_cps(cps);
+            self.render_swap.replace(&mut self.graph, Box::new(silent));
+        }
+        // Fades out the block a stop lands on, in the first after a start
+        self.render_swap.finish_block(buffer, frames);
+        true
+    }
+
+    pub fn graph(&self) -> &UnifiedSignalGraph {
+        &self.graph
+    }
+
+    pub fn graph_mut(&mut self) -> &mut UnifiedSignalGraph {
+        &mut self.graph
+    }
+
+    /// The clock, once a real program plays
+    pub fn clock(&self) -> Option<&LiveClock> {
+        self.clock.as_ref()
+    }
+
+    /// The clock, for an outside tempo source to steer (see `phonon live`'s
+    /// Link follower). Seed the graph from it after moving its position
+    pub fn clock_mut(&mut self) -> Option<&mut LiveClock> {
+        self.clock.as_mut()
+    }
+}
+
+fn address(graph: &UnifiedSignalGraph) -> usize {
+    graph as *const UnifiedSignalGraph as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositional_compiler::compile_program;
+    use crate::compositional_parser::parse_program;
+    use crate::render_swap::{render_swap_channel_default, Cmd};
+
+    fn graph(code: &str) -> Box<UnifiedSignalGraph> {
+        let (_, statements) = parse_program(code).unwrap();
+        Box::new(compile_program(statements, 44100.0, None).unwrap())
+    }
+
+    #[test]
+    fn test_placeholder_is_silent_until_the_first_swap() {
+        let (mut tx, rsw, _graveyard) = render_swap_channel_default();
+        let (reports, _) = std::sync::mpsc::channel();
+        let placeholder = Box::new(UnifiedSignalGraph::new(44100.0));
+        let mut live = LiveRender::awaiting_swap(rsw, placeholder, RenderWatchdog::new(reports));
+        let mut block = vec![1.0f32; 512];
+
+        assert!(!live.begin_block());
+        assert!(!live.render_block(&mut block));
+        assert!(block.iter().all(|&x| x == 0.0));
+        assert!(live.clock().is_none());
+
+        assert!(tx.send(Cmd::Swap(graph("out $ sine 440 * 0.5"))).is_ok());
+        assert!(live.begin_block());
+        assert!(live.render_block(&mut block));
+        assert!(block.iter().any(|&x| x.abs() > 0.1));
+    }
+
+    #[test]
+    fn test_stop_holds_the_clock() {
+        let (mut tx, rsw, _graveyard) = render_swap_channel_default();
+        let (reports, _) = std::sync::mpsc::channel();
+        let mut live = LiveRender::new(rsw, graph("out $ sine 440"), RenderWatchdog::new(reports));
+        let mut block = vec![0.0f32; 512];
+        live.begin_block();
+        live.render_block(&mut block);
+
+        assert!(tx.send(Cmd::Stop).is_ok());
+        live.begin_block();
+        assert!(live.render_block(&mut block)); // faded out
+        let held = live.clock().unwrap().position();
+        live.begin_block();
+        assert!(!live.render_block(&mut block));
+        assert!(block.iter().all(|&x| x == 0.0));
+        assert_eq!(live.clock().unwrap().position(), held);
+
+        assert!(tx.send(Cmd::Start).is_ok());
+        live.begin_block();
+        assert!(live.render_block(&mut block));
+        assert!(live.clock().unwrap().position() > held);
+    }
+}
@@ -6,6 +6,7 @@
     clippy::manual_strip
 )]
 use clap::{Parser, Subcommand};
+use phonon::engine_config::EngineConfig;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -54,7 +55,7 @@ enum Commands {
         #[arg(long, default_value = "0.01")]
         fade_out: f32,
 
-        /// Block size for processing (default: 512)
+        /// Frames per block for the realtime path (64-2048, default: 512)
         #[arg(short, long, default_value = "512")]
         block_size: usize,
 
@@ -69,6 +70,113 @@ enum Commands {
         /// Output stereo WAV (for pan/jux effects, default: false)
         #[arg(long, default_value = "false")]
         stereo: bool,
+
+        /// Output one WAV channel per numbered output (out1..outN), e.g. the
+        /// channels of a `spatial:` layout (default: false)
+        #[arg(long, default_value = "false", conflicts_with = "stereo")]
+        multichannel: bool,
+
+        /// Start at this cycle instead of 0, to preview the middle of a long piece
+        #[arg(long, default_value = "0")]
+        start_cycle: f64,
+
+        /// Master clipper: hard, soft, tanh or off (default: hard)
+        #[arg(long, default_value = "hard")]
+        clip: String,
+    },
+
+    /// Render the .phonon files listed in a manifest, in parallel
+    RenderBatch {
+        /// Manifest (.toml): files, lengths and output names
+        manifest: PathBuf,
+
+        /// Renders at once (default: the manifest's `jobs`, else all cores)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+
+    /// Render variants of a patch with a bus swept over a range of values
+    Sweep {
+        /// Input file (.phonon)
+        input: PathBuf,
+
+        /// Bus and values: ~cutoff=200..2000:8 (8 steps) or ~cutoff=200,800,3200;
+        /// repeat to sweep several buses (every combination is rendered)
+        #[arg(short, long = "param", required = true)]
+        params: Vec<String>,
+
+        /// Output directory
+        #[arg(short, long, default_value = "sweep")]
+        out: PathBuf,
+
+        /// Duration of each variant in seconds (default: 4.0)
+        #[arg(short, long, default_value = "4.0")]
+        duration: f64,
+
+        /// Number of cycles (overrides duration if specified)
+        #[arg(short, long)]
+        cycles: Option<f64>,
+
+        /// Sample rate in Hz (default: 44100)
+        #[arg(short, long, default_value = "44100")]
+        sample_rate: u32,
+
+        /// Renders at once (default: all cores)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+
+    /// Export the note, n and sample patterns of a file as a MIDI file
+    ExportMidi {
+        /// Input file (.phonon)
+        input: PathBuf,
+
+        /// Output MIDI file
+        output: PathBuf,
+
+        /// Number of cycles to export, one 4/4 bar each (default: 8)
+        #[arg(short, long, default_value = "8")]
+        cycles: f64,
+    },
+
+    /// Translate code from another live coding language into Phonon
+    Convert {
+        /// Input file
+        input: PathBuf,
+
+        /// Source language (currently only "tidal")
+        #[arg(long, default_value = "tidal")]
+        from: String,
+
+        /// Write the Phonon code here instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// List DSL functions with their parameters, synths and sample banks
+    Describe {
+        /// Print everything as JSON, for editor plugins and doc generators
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a function's parameters, their ranges and an example
+    Doc {
+        /// DSL function name, e.g. lpf
+        name: String,
+    },
+
+    /// List the example gallery, or play a piece from it
+    Examples {
+        #[command(subcommand)]
+        action: ExamplesAction,
+    },
+
+    /// List sample banks, or with --stats the memory each takes decoded
+    Samples {
+        /// Show decoded memory per folder against the cache limit
+        #[arg(long)]
+        stats: bool,
     },
 
     /// Play DSL file or code (render and auto-play)
@@ -106,6 +214,32 @@ enum Commands {
         /// OSC port to listen on (optional)
         #[arg(short, long, default_value = "9000")]
         port: u16,
+
+        /// Host a collaborative session: remote clients evaluate code over
+        /// OSC on --port, each in its own bus namespace
+        #[arg(long)]
+        session: bool,
+
+        /// Address the session listens on (0.0.0.0 for performers on the
+        /// network)
+        #[arg(long, default_value = "127.0.0.1")]
+        session_host: std::net::IpAddr,
+
+        /// Device buffer size in frames (negotiated down if unsupported)
+        #[arg(short, long)]
+        buffer_size: Option<usize>,
+
+        /// Target output latency in milliseconds (alternative to --buffer-size)
+        #[arg(long)]
+        latency: Option<f32>,
+
+        /// Frames rendered per block (64-2048, default: 512)
+        #[arg(long)]
+        block_size: Option<usize>,
+
+        /// Output device name, exact or partial (see `phonon devices`)
+        #[arg(long)]
+        device: Option<String>,
     },
 
     /// Start interactive REPL
@@ -120,15 +254,45 @@ enum Commands {
         #[arg(short, long, default_value = "4.0")]
         duration: f32,
 
-        /// Audio buffer size in samples (default: 512, range: 64-16384)
+        /// Audio buffer size in samples (range: 64-16384); requested as the
+        /// device buffer size, and sets the block when --block-size isn't given
         #[arg(short, long)]
         buffer_size: Option<usize>,
+
+        /// Target output latency in milliseconds (alternative to --buffer-size)
+        #[arg(long)]
+        latency: Option<f32>,
+
+        /// Frames rendered per block (64-2048, default: 512)
+        #[arg(long)]
+        block_size: Option<usize>,
+
+        /// Output device name, exact or partial (see `phonon devices`)
+        #[arg(long)]
+        device: Option<String>,
     },
 
-    /// Run tests on DSL files
+    /// List audio output and input devices
+    Devices {},
+
+    /// Render DSL files and check their `assert` statements
     Test {
-        /// Input file or directory
+        /// Input file or directory (searched recursively for .phonon files)
         input: PathBuf,
+
+        /// Seconds to render per file (default: 4.0)
+        #[arg(short, long, default_value = "4.0")]
+        duration: f32,
+    },
+
+    /// Analyze a WAV file (e.g. a `tap` recording): pitch, RMS and spectral centroid over time
+    Analyze {
+        /// WAV file to analyze
+        file: String,
+
+        /// Analysis window in seconds (default: 0.1)
+        #[arg(short, long, default_value = "0.1")]
+        window: f32,
     },
 
     /// Send pattern to MIDI device
@@ -169,6 +333,26 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum ExamplesAction {
+    /// List the pieces with a line about each
+    List,
+
+    /// Play a piece on the default output device
+    Play {
+        /// Piece name, as shown by `phonon examples list`
+        name: String,
+
+        /// Duration in seconds (default: 16.0)
+        #[arg(short, long, default_value = "16.0")]
+        duration: f32,
+
+        /// Render to this WAV file instead of playing
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
 #[derive(Subcommand)]
 enum PluginAction {
     /// Scan system paths for available plugins
@@ -214,12 +398,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize logging - redirect to file for Edit mode to prevent TUI corruption
     let is_edit_mode = matches!(cli.command, Commands::Edit { .. });
     if is_edit_mode {
-        // Redirect tracing to a log file to prevent TUI corruption
-        
+        // Redirect tracing to a log file to prevent TUI corruption. Events
+        // and the audio threads' messages queue lock-free and the editor's UI
+        // loop writes them out, so logging never blocks the render path
         let log_file = std::fs::File::create("/tmp/phonon_audio_errors.log")
             .unwrap_or_else(|_| std::fs::File::create("/dev/null").unwrap());
+        phonon::rt_log::set_sink(log_file);
         tracing_subscriber::fmt()
-            .with_writer(std::sync::Mutex::new(log_file))
+            .with_writer(|| phonon::rt_log::RtLogWriter)
             .with_ansi(false)
             .init();
     } else {
@@ -243,10 +429,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             gain,
             fade_in,
             fade_out,
-            block_size: _,
+            block_size,
             realtime,
             parallel,
             stereo,
+            multichannel,
+            start_cycle,
+            clip,
         } => {
             use hound::{SampleFormat, WavSpec, WavWriter};
             use std::collections::HashMap;
@@ -272,12 +461,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 input.clone()
             };
 
+            // Long reverb/delay tails render at full speed
+            phonon::denormals::enable_flush_to_zero();
+
             // Calculate duration from cycles if specified
             let final_duration = if let Some(cycle_count) = cycles {
                 cycle_count as f32
             } else {
                 duration
             };
+            let engine = EngineConfig::new(sample_rate as f32, Some(block_size))?;
 
             // Print info
             println!("🎵 Phonon Renderer");
@@ -297,11 +490,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Output:      {output}");
             println!("Duration:    {final_duration} seconds");
             println!("Sample rate: {sample_rate} Hz");
+            if realtime {
+                println!("Block size:  {}", engine.describe_block());
+            }
             println!("Master gain: {gain:.1}");
             println!();
 
             // Parse and compile using compositional parser (supports $ and # and new transform bus syntax)
-            use phonon::compositional_compiler::compile_program;
+            use phonon::compositional_compiler::compile_program_with_assertions;
             use phonon::compositional_parser::parse_program;
 
             // Parse the DSL
@@ -310,22 +506,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             // Check for parse errors (unparsed input remaining)
             if !remaining.trim().is_empty() {
-                use phonon::error_diagnostics::{
-                    check_for_common_mistakes, diagnose_parse_failure,
-                };
+                use phonon::error_diagnostics::diagnose_parse_failure;
 
                 // Provide detailed diagnostic
                 let diagnostic = diagnose_parse_failure(&dsl_code, remaining);
                 eprintln!("{}", diagnostic);
 
                 // Check for common mistakes in the entire file
-                let warnings = check_for_common_mistakes(&dsl_code);
-                if !warnings.is_empty() {
-                    eprintln!("⚠️  Additional warnings:");
-                    for warning in warnings {
-                        eprintln!("  • {}", warning);
-                    }
-                }
+                print_common_mistakes(&dsl_code);
 
                 eprintln!();
                 eprintln!("The renderer will continue with the successfully parsed portion.");
@@ -333,8 +521,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
 
             // Compile to graph using compositional compiler
-            let mut graph = compile_program(statements, sample_rate as f32, None)
+            let mut graph = compile_program_with_assertions(statements, sample_rate as f32)
                 .map_err(|e| format!("Compile error: {}", e))?;
+            let master_clip = phonon::unified_graph::MasterClip::from_str(&clip)
+                .ok_or_else(|| format!("Unknown --clip mode: {} (hard, soft, tanh, off)", clip))?;
+            graph.set_master_clip(master_clip);
+            let bus_meters = graph.enable_bus_meters();
 
             // Print auto-routing info if it happened
             if graph.has_output() && !graph.get_all_bus_names().is_empty() {
@@ -347,6 +539,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Note: Graph is already compiled by DslCompiler above
             // out_signal is handled by the graph's output system
 
+            // Cue to the start cycle (patterns are queried by cycle, so the
+            // events are the same as in a render from the start)
+            let start_sample = if start_cycle > 0.0 {
+                graph.seek_to_cycle(start_cycle);
+                println!("⏩ Starting at cycle {}", start_cycle);
+                (start_cycle / graph.get_cps() as f64 * sample_rate as f64).round() as usize
+            } else {
+                0
+            };
+
             // Recalculate duration based on actual tempo from DSL file
             let final_duration = if let Some(cycle_count) = cycles {
                 // Convert cycles to seconds using the tempo from the DSL
@@ -361,8 +563,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let mut output_buffer = Vec::with_capacity(total_samples);
             let mut left_buffer: Vec<f32> = Vec::new();
             let mut right_buffer: Vec<f32> = Vec::new();
-
-            if stereo {
+            // Interleaved in output_buffer when rendering several channels
+            let mut channels = 1;
+
+            if multichannel {
+                // MULTICHANNEL MODE: Sample-by-sample, a frame of out1..outN at a time
+                let (count, frames) = graph.render_channels(total_samples);
+                println!("🔊 Multichannel mode: {} channels", count);
+                channels = count;
+                output_buffer = frames
+                    .into_iter()
+                    .map(|sample| (sample * gain).clamp(-1.0, 1.0))
+                    .collect();
+            } else if stereo {
                 // STEREO MODE: Sample-by-sample for proper pan/jux stereo output
                 println!("🔊 Stereo mode: Using process_sample_stereo() for pan/jux separation");
 
@@ -389,8 +602,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!();
             } else if realtime {
                 // REALTIME MODE: Use process_buffer() like live mode for profiling
-                const BLOCK_SIZE: usize = 512;
-                let num_blocks = total_samples.div_ceil(BLOCK_SIZE);
+                let block_size = engine.block_size;
+                let num_blocks = total_samples.div_ceil(block_size);
 
                 // Check if graph contains effects that need sequential processing
                 let needs_sequential = graph.has_sequential_dependencies();
@@ -450,7 +663,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     // referenced sample so the clones share a warm bank (no concurrent
                     // disk-load thundering herd).
                     let warmup_samples = graph.compute_parallel_warmup_samples(total_samples);
-                    let warmup_blocks = warmup_samples.div_ceil(BLOCK_SIZE);
+                    let warmup_blocks = warmup_samples.div_ceil(block_size);
 
                     // Pre-clone graphs for parallel processing
                     let graph_clones: Vec<_> = chunks.iter().map(|_| graph.clone()).collect();
@@ -468,16 +681,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             let start_block = block_range.start;
                             let warmup_start = start_block.saturating_sub(warmup_blocks);
                             for wb in warmup_start..start_block {
-                                my_graph.seek_to_sample(wb * BLOCK_SIZE);
-                                let mut warm_buf = vec![0.0f32; BLOCK_SIZE * 2];
+                                my_graph.seek_to_sample(start_sample + wb * block_size);
+                                let mut warm_buf = vec![0.0f32; block_size * 2];
                                 my_graph.process_buffer(&mut warm_buf);
                             }
 
                             for block_idx in block_range {
-                                let block_start = block_idx * BLOCK_SIZE;
-                                let block_samples = (total_samples - block_start).min(BLOCK_SIZE);
+                                let block_start = block_idx * block_size;
+                                let block_samples = (total_samples - block_start).min(block_size);
 
-                                my_graph.seek_to_sample(block_idx * BLOCK_SIZE);
+                                my_graph.seek_to_sample(start_sample + block_idx * block_size);
                                 // CRITICAL: process_buffer expects STEREO (interleaved L/R), so 2x size
                                 let mut stereo_buffer = vec![0.0f32; block_samples * 2];
                                 let block_start_time = Instant::now();
@@ -512,7 +725,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     // SEQUENTIAL MODE: Process blocks one at a time
                     for block_idx in 0..num_blocks {
                         let remaining = total_samples - output_buffer.len();
-                        let block_samples = remaining.min(BLOCK_SIZE);
+                        let block_samples = remaining.min(block_size);
                         // CRITICAL: process_buffer expects STEREO (interleaved L/R), so 2x size
                         let mut stereo_buffer = vec![0.0f32; block_samples * 2];
 
@@ -549,12 +762,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 // Independent graph clones in parallel mode can produce discontinuities
                 // at block edges. Scan for large jumps and smooth them with a short fade.
                 {
-                    const BLOCK_SIZE_XFADE: usize = 512;
                     const FADE_SAMPLES: usize = 32;
                     const CLICK_THRESHOLD: f32 = 0.1;
 
                     let len = output_buffer.len();
-                    let mut block_start = BLOCK_SIZE_XFADE;
+                    let mut block_start = block_size;
                     while block_start < len {
                         if block_start > 0 {
                             let prev = output_buffer[block_start - 1];
@@ -571,7 +783,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                 }
                             }
                         }
-                        block_start += BLOCK_SIZE_XFADE;
+                        block_start += block_size;
                     }
                 }
 
@@ -591,7 +803,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 );
 
                 // Calculate if realtime is achievable
-                let block_duration_ms = (BLOCK_SIZE as f64 / sample_rate as f64) * 1000.0;
+                let block_duration_ms = (block_size as f64 / sample_rate as f64) * 1000.0;
                 let avg_block_time_ms =
                     total_process_time.as_secs_f64() * 1000.0 / num_blocks as f64;
                 let cpu_usage_percent = (avg_block_time_ms / block_duration_ms) * 100.0;
@@ -648,16 +860,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     right_buffer[i] *= fade;
                 }
             } else {
-                // Apply fades to mono buffer
-                for i in 0..fade_in_samples.min(output_buffer.len()) {
+                // Apply fades to mono (or interleaved multichannel) buffer
+                let frames = output_buffer.len() / channels;
+                for (i, frame) in output_buffer
+                    .chunks_mut(channels)
+                    .enumerate()
+                    .take(fade_in_samples)
+                {
                     let fade = i as f32 / fade_in_samples as f32;
-                    output_buffer[i] *= fade;
+                    frame.iter_mut().for_each(|sample| *sample *= fade);
                 }
 
-                let start = output_buffer.len().saturating_sub(fade_out_samples);
-                for i in start..output_buffer.len() {
-                    let fade = (output_buffer.len() - i) as f32 / fade_out_samples as f32;
-                    output_buffer[i] *= fade;
+                let start = frames.saturating_sub(fade_out_samples);
+                for (i, frame) in output_buffer.chunks_mut(channels).enumerate().skip(start) {
+                    let fade = (frames - i) as f32 / fade_out_samples as f32;
+                    frame.iter_mut().for_each(|sample| *sample *= fade);
                 }
             }
 
@@ -688,7 +905,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             // Write WAV file
             let spec = WavSpec {
-                channels: if stereo { 2 } else { 1 },
+                channels: if stereo { 2 } else { channels as u16 },
                 sample_rate,
                 bits_per_sample: 16,
                 sample_format: SampleFormat::Int,
@@ -710,7 +927,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         .map_err(|e| format!("Failed to write sample: {e}"))?;
                 }
             } else {
-                // Write mono samples
+                // Write mono (or interleaved multichannel) samples
                 for &sample in &output_buffer {
                     let sample_i16 = (sample * 32767.0) as i16;
                     writer
@@ -736,6 +953,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             );
             println!("DC offset:      {dc_offset:.6}");
 
+            // Per-bus headroom (metered on the block path only)
+            if realtime && !stereo && !multichannel {
+                println!();
+                println!("Headroom:");
+                for line in bus_meters.report().lines() {
+                    println!("  {line}");
+                }
+            }
+
             println!();
             println!("✅ Successfully rendered to: {output}");
 
@@ -750,7 +976,22 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!();
                 println!("🔍 Tap recordings:");
                 for file in tap_files {
-                    println!("   {}", file);
+                    println!("   {}  (inspect with: phonon analyze {})", file, file);
+                }
+            }
+
+            // Report `assert` statements (phonon test renders and checks these too)
+            if graph.has_assertions() {
+                println!();
+                println!("🧪 Assertions:");
+                for outcome in graph.check_assertions() {
+                    let mark = if outcome.passed { "✅" } else { "❌" };
+                    match outcome.value {
+                        Some(v) => println!("   {} {}  ({:.4})", mark, outcome.description, v),
+                        None => {
+                            println!("   {} {}  (no audio recorded)", mark, outcome.description)
+                        }
+                    }
                 }
             }
         }
@@ -762,8 +1003,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             gain,
         } => {
             use hound::{SampleFormat, WavSpec, WavWriter};
-            use phonon::compositional_compiler::compile_program;
-            use phonon::compositional_parser::parse_program;
+            use phonon::phonon_engine::PhononEngine;
             use std::process::Command;
 
             // Read DSL code
@@ -794,34 +1034,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("Gain:       {gain:.1}");
             println!();
 
-            // Parse using compositional_parser (supports vst, $ and # syntax)
-            let (remaining, statements) =
-                parse_program(&dsl_code).map_err(|e| format!("Failed to parse DSL: {:?}", e))?;
-
-            if !remaining.trim().is_empty() {
-                use phonon::error_diagnostics::{
-                    check_for_common_mistakes, diagnose_parse_failure,
-                };
-                let diagnostic = diagnose_parse_failure(&dsl_code, remaining);
+            // Mono, as the WAV below; a statement that doesn't parse is skipped
+            let mut engine = PhononEngine::builder()
+                .sample_rate(sample_rate)
+                .channels(1)
+                .code(dsl_code.as_str())
+                .build()
+                .map_err(|e| {
+                    print_common_mistakes(&dsl_code);
+                    format!("Failed to compile DSL: {}", e)
+                })?;
+            for diagnostic in engine.skipped() {
                 eprintln!("{}", diagnostic);
-                let warnings = check_for_common_mistakes(&dsl_code);
-                if !warnings.is_empty() {
-                    eprintln!("⚠️  Additional warnings:");
-                    for warning in warnings {
-                        eprintln!("  • {}", warning);
-                    }
-                }
+            }
+            if !engine.skipped().is_empty() {
+                print_common_mistakes(&dsl_code);
             }
 
-            // Compile to graph using compositional compiler
-            let mut graph = compile_program(statements, sample_rate as f32, None)
-                .map_err(|e| format!("Compile error: {}", e))?;
-
-            // Calculate samples
-            let num_samples = (duration * sample_rate as f32) as usize;
-
-            // Render audio
-            let buffer = graph.render(num_samples);
+            let buffer = engine.render_seconds(duration as f64);
 
             // Apply gain and calculate stats
             let mut peak: f32 = 0.0;
@@ -894,12 +1124,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             file,
             duration: _,
             pattern: _,
-            port: _,
+            port,
+            session,
+            session_host,
+            buffer_size,
+            latency,
+            block_size,
+            device,
         } => {
             // Import the phonon_poll implementation
-            use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+            use cpal::traits::{DeviceTrait, StreamTrait};
 
-            use phonon::unified_graph::{LiveClock, UnifiedSignalGraph};
+            use phonon::audio_device::{build_output_stream_converted, select_output_device};
+            use phonon::live_render::LiveRender;
+            use phonon::output_buffer::{
+                buffer_frames, describe_buffer, negotiate_output_config, requested_buffer_frames,
+                ring_capacity, LatencyMonitor,
+            };
+            use phonon::unified_graph::UnifiedSignalGraph;
 
             use std::sync::{Arc, Mutex};
             use std::sync::atomic::{AtomicUsize, Ordering};
@@ -917,20 +1159,35 @@ out sine(440) * 0.2
                 std::fs::write(&file, default_content)?;
             }
 
-            // Setup audio
+            // Setup audio (--device name, or the system default)
             let host = cpal::default_host();
-            let device = host
-                .default_output_device()
-                .ok_or("No audio output device found")?;
+            let device = select_output_device(&host, device.as_deref())?;
 
-            let config = device.default_output_config()?;
-            let sample_rate = config.sample_rate().0 as f32;
+            let supported = device.default_output_config()?;
+            let sample_rate = supported.sample_rate().0 as f32;
+            let channels = supported.channels() as usize;
+            let engine = EngineConfig::new(sample_rate, block_size)?;
+
+            // Fixed device buffer from --buffer-size / --latency, negotiated down
+            // to what the device accepts (device default when neither is given)
+            let requested = requested_buffer_frames(buffer_size, latency, sample_rate);
+            let config = negotiate_output_config(&device, &supported, requested);
 
             println!("🎵 Phonon Live");
             println!("==============");
             println!("📂 Watching: {}", file.display());
-            println!("🎧 Audio: {} @ {} Hz", device.name()?, sample_rate);
-            println!();
+            println!(
+                "🎧 Audio: {} @ {} Hz ({})",
+                device.name()?,
+                sample_rate,
+                supported.sample_format()
+            );
+            println!("🔧 Buffer: {}", describe_buffer(&config));
+            if let (Some(asked), actual) = (requested, buffer_frames(&config)) {
+                if actual != Some(asked) {
+                    println!("   (requested {} frames, not supported by the device)", asked);
+                }
+            }
 
             // Shared state for live reloading with ring-buffered synthesis
             //
@@ -961,7 +1218,7 @@ out sine(440) * 0.2
             // carries its own lib-level `Send`/`Sync` (`unified_graph.rs`, justified
             // by exactly this single-owner discipline), and it already implements
             // `RenderGraph`, so it moves through the ring with no local wrapper.
-            let (mut cmd_tx, mut render_swap, mut graveyard) =
+            let (mut cmd_tx, render_swap, mut graveyard) =
                 render_swap_channel_default::<UnifiedSignalGraph>();
 
             // Janitor: drop retired graphs off the render (synth) thread. Dropping a
@@ -977,8 +1234,15 @@ out sine(440) * 0.2
 
             // Ring buffer: background synth writes, audio callback reads
             // Size: 1 second of audio @ 48kHz = 48000 samples
-            // Provides smooth playback even if synth thread lags briefly
-            let ring_buffer_size = (sample_rate * 1.0) as usize; // 1 second buffer
+            // Provides smooth playback even if synth thread lags briefly.
+            // With a fixed device buffer the ring holds only a few device buffers,
+            // so the requested latency isn't swamped by a second of queued audio.
+            let ring_buffer_size = buffer_frames(&config).map_or(
+                (sample_rate * 1.0) as usize, // 1 second buffer
+                |frames| ring_capacity(frames, channels, engine.block_samples(2) * 2),
+            );
+            println!("🔧 Latency: {}", engine.latency_report(ring_buffer_size, 2));
+            println!();
             let ring = HeapRb::<f32>::new(ring_buffer_size);
             let (mut ring_producer, mut ring_consumer) = ring.split();
 
@@ -1002,15 +1266,45 @@ out sine(440) * 0.2
             let parse_phonon =
                 |content: &str, sample_rate: f32| -> Result<UnifiedSignalGraph, String> {
                     use phonon::compositional_compiler::compile_program;
-                    use phonon::compositional_parser::parse_program;
+                    use phonon::compositional_parser::parse_program_recovering;
 
-                    // Parse using compositional parser
-                    match parse_program(content) {
-                        Ok((_, statements)) => compile_program(statements, sample_rate, None),
-                        Err(e) => Err(format!("Parse error: {:?}", e)),
+                    // A statement that doesn't parse is skipped, so a typo
+                    // on one line doesn't silence the others
+                    let (statements, skipped) = parse_program_recovering(content);
+                    for diagnostic in &skipped {
+                        eprintln!("{}", diagnostic);
+                    }
+                    if statements.is_empty() && !skipped.is_empty() {
+                        return Err("Parse error: nothing parsed".to_string());
                     }
+                    compile_program(statements, sample_rate, None)
                 };
 
+            // Collaborative session: the watched file plays as client "host"
+            // alongside whatever remote clients evaluate
+            use phonon::collab_session::SessionServer;
+            let mut session_server = if session {
+                let server = SessionServer::bind_on(session_host, port)?;
+                println!(
+                    "🤝 Session: clients send /session/join|eval|hush|say to {}:{}",
+                    session_host, port
+                );
+                Some(server)
+            } else {
+                None
+            };
+            let build_graph = |content: &str,
+                               server: Option<&mut SessionServer>|
+             -> Result<UnifiedSignalGraph, String> {
+                match server {
+                    Some(server) => {
+                        let program = server.set_host_code("host", content)?;
+                        phonon::compositional_compiler::compile_program(program, sample_rate, None)
+                    }
+                    None => parse_phonon(content, sample_rate),
+                }
+            };
+
             // Initial load — build the graph the render (synth) thread starts out
             // owning. The render-owner primitive requires a single owned graph at
             // all times, so on parse failure we still hand it an empty, silent graph
@@ -1022,12 +1316,13 @@ out sine(440) * 0.2
             let initial_graph: Box<UnifiedSignalGraph> = {
                 let mut loaded: Option<UnifiedSignalGraph> = None;
                 if let Ok(content) = std::fs::read_to_string(&file) {
-                    match parse_phonon(&content, sample_rate) {
+                    match build_graph(&content, session_server.as_mut()) {
                         Ok(mut new_graph) => {
                             // Enable wall-clock timing from the start so timing transfers
                             // on subsequent reloads have a valid reference point
                             new_graph.enable_wall_clock_timing();
                             new_graph.preload_samples();
+                            new_graph.preload_plugins();
                             let mut state_lock = file_state.lock().unwrap();
                             state_lock.last_content = content;
                             drop(state_lock);
@@ -1061,6 +1356,24 @@ out sine(440) * 0.2
             // below so the render thread stays the sole mutator of its LiveClock.
             let mut link_follower: Option<LinkFollower> = configure_link_follower();
 
+            // The shape of the graph the render thread plays, kept here so the
+            // next graph can plan its bus state transfer off the render thread
+            let mut playing_shape = initial_graph.shape();
+
+            // The render side of playback: the owned graph, its clock and a crash
+            // / NaN guard. Until the first *real* graph arrives (the initial parse
+            // failed) it renders silence and leaves the clock unseeded, so that
+            // graph's own compiled cycle position (setCycle / resetCycles) holds.
+            // The watchdog prints its own reports to stderr, so nothing needs to
+            // read the channel here.
+            let (watchdog_tx, _) = std::sync::mpsc::channel();
+            let watchdog = phonon::render_watchdog::RenderWatchdog::new(watchdog_tx);
+            let mut live = if initial_is_real {
+                LiveRender::new(render_swap, initial_graph, watchdog)
+            } else {
+                LiveRender::awaiting_swap(render_swap, initial_graph, watchdog)
+            };
+
             // Background synthesis thread: the single owner of the live graph
             // (render-owner model). It continuously renders samples into the ring
             // buffer and applies swaps — arriving via the render-owner command ring
@@ -1068,112 +1381,52 @@ out sine(440) * 0.2
             // never a cross-thread borrow, so there is no retry loop and no
             // voiceless-published window (design §4.1; R1/R2/R3 gone).
             std::thread::spawn(move || {
-                let mut buffer = [0.0f32; 512]; // Render in chunks (stereo interleaved)
-                let frames = buffer.len() / 2; // frames of cycle-time per chunk
-
-                // Sample-advancing live clock — THE single source of timing truth
-                // (pattern-timing audit T1 / pt-F1). Advancing by samples emitted,
-                // NOT by wall-clock at render time, keeps the pattern on the sample
-                // grid even while the synth thread renders ahead into the ring (so
-                // no startup / post-underrun onset clustering). Tempo comes from the
-                // compiled graph; a reload with a new `tempo:` rebases the clock via
-                // LiveClock::set_cps without teleporting the position (pt-F2).
-                let mut clock: Option<LiveClock> = None;
-
-                // The render thread's single owned graph. Swaps move a new graph in
-                // through `render_swap` and retire the old one to the graveyard.
-                let mut cur: Box<UnifiedSignalGraph> = initial_graph;
-
-                // Until the first *real* graph is installed we render silence and
-                // leave the clock unseeded (mirrors the old `graph == None` state),
-                // so the first real graph's own compiled cycle position is honoured.
-                let mut have_real_graph = initial_is_real;
+                // Decaying tails must not fall into slow subnormal arithmetic
+                phonon::denormals::enable_flush_to_zero();
+                // Render in blocks (stereo interleaved)
+                let mut buffer = vec![0.0f32; engine.block_samples(2)];
 
                 loop {
-                    // Check if we have space in ring buffer
-                    let space = ring_producer.vacant_len();
-
-                    if space >= buffer.len() {
-                        // Buffer boundary: apply any pending swaps to the owned
-                        // graph. This runs the in-memory transfer_* + pointer swap as
-                        // one uninterrupted step and ships the retired graph to the
-                        // graveyard — no cross-thread borrow, no retry loop, no
-                        // voiceless-published window (design §4.1, R1/R2/R3 gone).
-                        let applied = render_swap.apply_pending_commands(&mut cur);
-                        if applied > 0 {
-                            // Only Cmd::Swap is ever sent on this path, so any
-                            // applied command means a new graph is now current.
-                            have_real_graph = true;
-                        }
-
-                        if !have_real_graph {
-                            // No real graph yet (initial parse failed): write silence.
-                            buffer.fill(0.0);
-                            ring_producer.push_slice(&buffer);
-                            continue;
-                        }
+                    if ring_producer.vacant_len() < buffer.len() {
+                        // Ring buffer is full, sleep briefly
+                        std::thread::sleep(StdDuration::from_micros(100));
+                        continue;
+                    }
 
-                        match clock.as_mut() {
-                            None => {
-                                // First real graph: seed the clock from its compiled
-                                // start position so setCycle / resetCycles in the
-                                // initial file are honoured.
-                                clock = Some(LiveClock::new(
-                                    sample_rate,
-                                    cur.get_cps(),
-                                    cur.get_cycle_position(),
-                                ));
-                            }
-                            Some(c) => {
-                                // Follow the graph's tempo, rebasing on change so the
-                                // position never teleports (pt-F2).
-                                c.set_cps(cur.get_cps());
-                                if applied > 0 {
-                                    // A swap installed a new graph this boundary: seed
-                                    // its node timing from the live clock so it
-                                    // continues from the current sample-advanced
-                                    // position instead of re-triggering the events
-                                    // between the transferred and the true position.
-                                    cur.set_cycle_position(c.position());
-                                }
-                            }
+                    // Buffer boundary: apply any pending swaps to the owned graph
+                    // and move the sample-advancing clock (THE single source of
+                    // timing truth, pt-F1) along with them.
+                    live.begin_block();
+
+                    // Network tempo sync (design §4.3/§5). When a Link source is
+                    // configured, fold its latest published snapshot into the
+                    // clock BEFORE advancing: a bounded varispeed nudge via
+                    // set_cps every buffer, and a single deliberate set_position
+                    // reseek ONLY at join / large phase error — never a per-buffer
+                    // teleport (T1). With no source configured `link_follower` is
+                    // None and this is skipped, so the render path is unchanged.
+                    if let (Some(follower), Some(clock)) =
+                        (link_follower.as_mut(), live.clock_mut())
+                    {
+                        if follower.fold(clock) {
+                            // A join / large-error reseek moved the clock: seed the
+                            // graph's node timing to the new position so it
+                            // continues from there instead of re-triggering the
+                            // skipped span (as a swap is seeded).
+                            let position = clock.position();
+                            live.graph_mut().set_cycle_position(position);
                         }
+                    }
 
-                        // Network tempo sync (design §4.3/§5). When a Link source is
-                        // configured, fold its latest published snapshot into the
-                        // clock BEFORE advancing: a bounded varispeed nudge via
-                        // set_cps every buffer, and a single deliberate set_position
-                        // reseek ONLY at join / large phase error — never a per-buffer
-                        // teleport (T1). With no source configured `link_follower` is
-                        // None and this is skipped, so the render path is unchanged.
-                        if let Some(follower) = link_follower.as_mut() {
-                            let c = clock.as_mut().unwrap();
-                            if follower.fold(c) {
-                                // A join / large-error reseek moved the clock: seed the
-                                // graph's node timing to the new position so it
-                                // continues from there instead of re-triggering the
-                                // skipped span (mirrors the swap-seed above).
-                                cur.set_cycle_position(c.position());
-                            }
-                        }
+                    live.render_block(&mut buffer);
 
-                        // Advance the clock by exactly this chunk and render with that
-                        // timing (single source of truth).
-                        let c = clock.as_mut().unwrap();
-                        let (start_cycle, increment, cps) = c.advance_buffer(frames);
-                        cur.process_buffer_at(&mut buffer, start_cycle, increment, cps);
-
-                        // Write to ring buffer
-                        let written = ring_producer.push_slice(&buffer);
-                        if written < buffer.len() {
-                            eprintln!(
-                                "⚠️  Ring buffer full, dropped {} samples",
-                                buffer.len() - written
-                            );
-                        }
-                    } else {
-                        // Ring buffer is full, sleep briefly
-                        std::thread::sleep(StdDuration::from_micros(100));
+                    // Write to ring buffer
+                    let written = ring_producer.push_slice(&buffer);
+                    if written < buffer.len() {
+                        phonon::rt_log::log(format_args!(
+                            "⚠️  Ring buffer full, dropped {} samples",
+                            buffer.len() - written
+                        ));
                     }
                 }
             });
@@ -1183,11 +1436,16 @@ out sine(440) * 0.2
             let err_fn = |err| eprintln!("Audio stream error: {err}");
 
             let underrun_count_cb = Arc::clone(&underrun_count);
-            let stream = device.build_output_stream(
-                &config.into(),
-                move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+            let latency_monitor = Arc::new(LatencyMonitor::new());
+            let latency_cb = Arc::clone(&latency_monitor);
+            let stream = build_output_stream_converted(
+                &device,
+                &config,
+                supported.sample_format(),
+                move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
                     // Read from ring buffer - this is MUCH faster than synthesis!
                     let available = ring_consumer.occupied_len();
+                    latency_cb.record(info, available / channels, sample_rate);
 
                     if available >= data.len() {
                         // Ring buffer has enough samples, read them
@@ -1204,7 +1462,6 @@ out sine(440) * 0.2
                     }
                 },
                 err_fn,
-                None,
             )?;
 
             stream.play()?;
@@ -1213,11 +1470,62 @@ out sine(440) * 0.2
             println!("🎹 Press Ctrl+C to stop");
             println!();
 
+            // Hand a freshly compiled graph to the render thread.
+            //
+            // Control-thread work only — off the render thread (design §4.4):
+            // enable wall-clock timing, preload samples (disk I/O) and pair the
+            // nodes of unchanged buses with the playing graph's. The
+            // live-state transfer (session timing / FX tails / voices) and the
+            // pointer swap happen ON the render thread inside
+            // apply_pending_commands (UnifiedSignalGraph::absorb_state), so there
+            // is no cross-thread borrow here (design §4.1; R1/R2/R3 gone).
+            //
+            // The graph moves through the render-owner command ring. The ring is
+            // human-paced (one save or evaluation per swap) and far larger than
+            // needed, so it effectively never fills; if it momentarily does
+            // (render thread briefly behind) we retry, and the graph is handed
+            // back on Err so it is never lost.
+            let mut swap_in = |mut new_graph: UnifiedSignalGraph| -> bool {
+                new_graph.enable_wall_clock_timing();
+                new_graph.preload_samples();
+                new_graph.preload_plugins();
+                new_graph.plan_bus_state_transfer(&playing_shape);
+                let shape = new_graph.shape();
+
+                let mut pending = Cmd::Swap(Box::new(new_graph));
+                for _ in 0..50 {
+                    match cmd_tx.send(pending) {
+                        Ok(()) => {
+                            playing_shape = shape;
+                            return true;
+                        }
+                        Err(cmd) => {
+                            pending = cmd;
+                            std::thread::sleep(StdDuration::from_micros(500));
+                        }
+                    }
+                }
+                false
+            };
+
             // Poll for changes
             let mut last_reported_underruns = 0usize;
+            let started = std::time::Instant::now();
+            let mut latency_reported = false;
             loop {
                 std::thread::sleep(StdDuration::from_millis(100));
 
+                // Report measured latency once the ring has settled
+                if !latency_reported && started.elapsed() >= StdDuration::from_secs(1) {
+                    latency_reported = true;
+                    println!(
+                        "⏱️  Latency: {:.1} ms (ring {:.1} ms + device {:.1} ms)",
+                        latency_monitor.total_ms(),
+                        latency_monitor.ring_ms(),
+                        latency_monitor.device_ms()
+                    );
+                }
+
                 // Log underrun stats every 100 underruns (off the audio callback, no jitter)
                 let current_underruns = underrun_count.load(Ordering::Relaxed);
                 if current_underruns.saturating_sub(last_reported_underruns) >= 100 {
@@ -1225,6 +1533,19 @@ out sine(440) * 0.2
                     eprintln!("⚠️  Audio underrun (synth can't keep up) — total: {current_underruns}");
                 }
 
+                // Apply evaluations from session clients
+                if let Some(program) = session_server.as_mut().and_then(|s| s.poll()) {
+                    use phonon::compositional_compiler::compile_program;
+                    match compile_program(program, sample_rate, None) {
+                        Ok(new_graph) => {
+                            if !swap_in(new_graph) {
+                                eprintln!("⚠️  Swap channel full — session update dropped");
+                            }
+                        }
+                        Err(e) => println!("❌ Session compile error: {e}"),
+                    }
+                }
+
                 // Check for file changes
                 if let Ok(metadata) = std::fs::metadata(&file) {
                     if let Ok(modified) = metadata.modified() {
@@ -1245,46 +1566,9 @@ out sine(440) * 0.2
                                 if content != last_content {
                                     println!("🔄 Reloading...");
 
-                                    match parse_phonon(&content, sample_rate) {
-                                        Ok(mut new_graph) => {
-                                            // Control-thread work only — off the render
-                                            // thread (design §4.4): enable wall-clock
-                                            // timing and preload samples (disk I/O). The
-                                            // live-state transfer (session timing / FX
-                                            // tails / voices) and the pointer swap now
-                                            // happen ON the render thread inside
-                                            // apply_pending_commands (UnifiedSignalGraph::absorb_state),
-                                            // so there is no cross-thread borrow and no
-                                            // retry loop here (design §4.1; R1/R2/R3 gone).
-                                            new_graph.enable_wall_clock_timing();
-                                            new_graph.preload_samples();
-
-                                            // Hand the finished graph to the render thread
-                                            // by move through the render-owner command
-                                            // ring. The ring is human-paced (one save per
-                                            // swap) and far larger than needed, so it
-                                            // effectively never fills; if it momentarily
-                                            // does (render thread briefly behind) we retry,
-                                            // and the graph is handed back on Err so it is
-                                            // never lost.
-                                            let mut pending = Cmd::Swap(Box::new(new_graph));
-                                            let mut sent = false;
-                                            for _ in 0..50 {
-                                                match cmd_tx.send(pending) {
-                                                    Ok(()) => {
-                                                        sent = true;
-                                                        break;
-                                                    }
-                                                    Err(cmd) => {
-                                                        pending = cmd;
-                                                        std::thread::sleep(
-                                                            StdDuration::from_micros(500),
-                                                        );
-                                                    }
-                                                }
-                                            }
-
-                                            if sent {
+                                    match build_graph(&content, session_server.as_mut()) {
+                                        Ok(new_graph) => {
+                                            if swap_in(new_graph) {
                                                 // Update file state
                                                 let mut state_lock = file_state.lock().unwrap();
                                                 state_lock.last_content = content;
@@ -1316,20 +1600,455 @@ out sine(440) * 0.2
             repl.run()?;
         }
 
-        Commands::Edit { file, duration, buffer_size } => {
+        Commands::Edit {
+            file,
+            duration,
+            buffer_size,
+            latency,
+            block_size,
+            device,
+        } => {
             use phonon::modal_editor::ModalEditor;
 
-            let mut editor = ModalEditor::new(duration, file.clone(), buffer_size)?;
+            let mut editor = ModalEditor::new(
+                duration,
+                file.clone(),
+                buffer_size,
+                latency,
+                block_size,
+                device.as_deref(),
+            )?;
             editor.run()?;
         }
 
-        Commands::Test { input } => {
+        Commands::Devices {} => {
+            use phonon::audio_device::{list_input_devices, list_output_devices, DeviceInfo};
+
+            let host = cpal::default_host();
+            println!("🎧 Audio devices ({})", host.id().name());
+            println!("==================");
+
+            let print_devices = |title: &str, devices: &[DeviceInfo]| {
+                println!();
+                println!("{}:", title);
+                if devices.is_empty() {
+                    println!("  (none)");
+                }
+                for (i, dev) in devices.iter().enumerate() {
+                    let marker = if dev.is_default { "*" } else { " " };
+                    println!("{} [{}] {}", marker, i, dev.name);
+                    println!("        {}", dev.describe());
+                }
+            };
+            print_devices("Output devices", &list_output_devices(&host)?);
+            print_devices("Input devices", &list_input_devices(&host)?);
+
+            println!();
+            println!("* = default. Select with: phonon live --device \"<name>\"");
+        }
+
+        Commands::Test { input, duration } => {
+            use phonon::compositional_compiler::compile_program_with_assertions;
+            use phonon::compositional_parser::parse_program;
+
             println!("🧪 Phonon Test Runner");
             println!("====================");
-            println!("Input: {}", input.display());
+
+            let files = collect_phonon_files(&input)?;
+            if files.is_empty() {
+                return Err(format!("No .phonon files found in {}", input.display()).into());
+            }
+
+            const SAMPLE_RATE: f32 = 44100.0;
+            let (mut passed, mut failed, mut untested) = (0, 0, 0);
+
+            for path in &files {
+                println!();
+                println!("📄 {}", path.display());
+
+                let result = std::fs::read_to_string(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|code| {
+                        let (rest, statements) =
+                            parse_program(&code).map_err(|e| format!("Parse error: {:?}", e))?;
+                        if !rest.trim().is_empty() {
+                            return Err(format!("Parse error near: {}", rest.trim()));
+                        }
+                        compile_program_with_assertions(statements, SAMPLE_RATE)
+                    });
+                let mut graph = match result {
+                    Ok(graph) => graph,
+                    Err(e) => {
+                        println!("   ❌ {}", e);
+                        failed += 1;
+                        continue;
+                    }
+                };
+
+                if !graph.has_assertions() {
+                    println!("   ⚪ no assertions");
+                    untested += 1;
+                    continue;
+                }
+
+                let mut remaining = (duration * SAMPLE_RATE) as usize;
+                while remaining > 0 {
+                    let block = remaining.min(EngineConfig::default().block_size);
+                    graph.render(block);
+                    remaining -= block;
+                }
+
+                for outcome in graph.check_assertions() {
+                    let value = match outcome.value {
+                        Some(v) => format!("{:.4}", v),
+                        None => "no audio recorded".to_string(),
+                    };
+                    if outcome.passed {
+                        println!("   ✅ {}  ({})", outcome.description, value);
+                        passed += 1;
+                    } else {
+                        println!("   ❌ {}  (got {})", outcome.description, value);
+                        failed += 1;
+                    }
+                }
+            }
+
             println!();
-            println!("⚠️  Test mode not yet implemented");
-            println!("   This will run validation tests on DSL files");
+            println!(
+                "{} passed, {} failed, {} files without assertions",
+                passed, failed, untested
+            );
+            if failed > 0 {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::RenderBatch { manifest, jobs } => {
+            use phonon::render_batch::{render_batch, summary};
+
+            println!("🎚️  Phonon Batch Render: {}", manifest.display());
+            println!("====================");
+
+            let results = render_batch(&manifest, jobs)?;
+            for line in summary(&results) {
+                println!("{line}");
+            }
+            if results.iter().any(|r| r.outcome.is_err()) {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Sweep {
+            input,
+            params,
+            out,
+            duration,
+            cycles,
+            sample_rate,
+            jobs,
+        } => {
+            use phonon::modal_editor::render_queue::RenderLength;
+            use phonon::render_batch::summary;
+            use phonon::render_sweep::{render_sweep, SweepParam};
+
+            let params = params
+                .iter()
+                .map(|spec| SweepParam::parse(spec))
+                .collect::<Result<Vec<_>, _>>()?;
+            let length = match cycles {
+                Some(cycles) => RenderLength::parse(&format!("{}c", cycles))?,
+                None => RenderLength::parse(&format!("{}s", duration))?,
+            };
+
+            println!("🎛️  Phonon Sweep: {} → {}", input.display(), out.display());
+            println!("====================");
+
+            let results = render_sweep(&input, &params, &out, length, sample_rate as f32, jobs)?;
+            for line in summary(&results) {
+                println!("{line}");
+            }
+            if results.iter().any(|r| r.outcome.is_err()) {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::ExportMidi {
+            input,
+            output,
+            cycles,
+        } => {
+            let export = phonon::midi_export::export_file(&input, cycles, &output)?;
+            println!(
+                "🎹 {} → {} ({} cycles at {:.1} BPM)",
+                input.display(),
+                output.display(),
+                cycles,
+                export.bpm()
+            );
+            for track in &export.tracks {
+                println!("   {:<12} {} notes", track.name, track.notes.len());
+            }
+            if export.tracks.is_empty() {
+                println!("   (no note, n or sample patterns found)");
+            }
+        }
+
+        Commands::Convert {
+            input,
+            from,
+            output,
+        } => {
+            if from != "tidal" {
+                return Err(format!("Can't convert from '{}' (supported: tidal)", from).into());
+            }
+            let source = std::fs::read_to_string(&input)
+                .map_err(|e| format!("Failed to read {}: {}", input.display(), e))?;
+            let conversion = phonon::tidal_convert::convert_tidal(&source);
+            match &output {
+                Some(path) => {
+                    std::fs::write(path, &conversion.code)
+                        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+                    eprintln!("✅ {} → {}", input.display(), path.display());
+                }
+                None => print!("{}", conversion.code),
+            }
+            for warning in &conversion.warnings {
+                eprintln!("⚠️  {}", warning);
+            }
+        }
+
+        Commands::Describe { json } => {
+            let description = phonon::describe::describe();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&description)?);
+            } else {
+                println!("Phonon {}", description.version);
+                let mut categories: Vec<&str> = description
+                    .functions
+                    .iter()
+                    .map(|f| f.category.as_str())
+                    .collect();
+                categories.sort();
+                categories.dedup();
+                for category in categories {
+                    println!("\n{}:", category);
+                    for function in description
+                        .functions
+                        .iter()
+                        .filter(|f| f.category == category)
+                    {
+                        let params: Vec<&str> =
+                            function.params.iter().map(|p| p.name.as_str()).collect();
+                        println!("  {} {}", function.name, params.join(" "));
+                    }
+                }
+                let banks: Vec<String> = description
+                    .samples
+                    .iter()
+                    .map(|bank| format!("{}({})", bank.name, bank.count))
+                    .collect();
+                println!("\nSample banks: {}", banks.join(" "));
+            }
+        }
+
+        Commands::Doc { name } => {
+            use phonon::modal_editor::completion::FunctionDocs;
+
+            let Some(docs) = FunctionDocs::get(&name) else {
+                return Err(format!("No docs for '{}' (see phonon describe)", name).into());
+            };
+            for line in docs.format_lines(usize::MAX) {
+                println!("{}", line.text);
+            }
+        }
+
+        Commands::Examples { action } => {
+            use phonon::gallery::{self, EXAMPLES};
+            use phonon::phonon_engine::PhononEngine;
+
+            match action {
+                ExamplesAction::List => {
+                    for example in EXAMPLES {
+                        println!("{:<10} {}", example.name, example.description());
+                    }
+                    println!();
+                    println!("Play one with: phonon examples play <name>");
+                }
+
+                ExamplesAction::Play {
+                    name,
+                    duration,
+                    output,
+                } => {
+                    let Some(example) = gallery::find(&name) else {
+                        let names: Vec<&str> = EXAMPLES.iter().map(|e| e.name).collect();
+                        return Err(
+                            format!("No example '{}' (try: {})", name, names.join(", ")).into()
+                        );
+                    };
+
+                    println!("🎵 {}", example.description());
+                    let mut engine = PhononEngine::builder()
+                        .channels(1)
+                        .code(example.code)
+                        .build()
+                        .map_err(|e| format!("Failed to compile '{}': {}", name, e))?;
+                    for diagnostic in engine.skipped() {
+                        eprintln!("{}", diagnostic);
+                    }
+
+                    if let Some(path) = output {
+                        use hound::{SampleFormat, WavSpec, WavWriter};
+
+                        let samples = engine.render_seconds(duration as f64);
+                        let spec = WavSpec {
+                            channels: 1,
+                            sample_rate: engine.config().sample_rate as u32,
+                            bits_per_sample: 32,
+                            sample_format: SampleFormat::Float,
+                        };
+                        let mut writer = WavWriter::create(&path, spec)?;
+                        for sample in samples {
+                            writer.write_sample(sample)?;
+                        }
+                        writer.finalize()?;
+                        println!("✅ Rendered {} seconds to {}", duration, path.display());
+                    } else {
+                        let _stream = engine.stream()?;
+                        println!("🔊 Playing for {} seconds (Ctrl+C to stop)", duration);
+                        std::thread::sleep(std::time::Duration::from_secs_f32(duration));
+                    }
+                }
+            }
+        }
+
+        Commands::Samples { stats } => {
+            use phonon::sample_loader::{
+                format_bytes, sample_memory_limit, set_extra_sample_dirs, set_sample_memory_limit,
+                SampleBank,
+            };
+
+            // Search the same directories, with the same cap, as the editor
+            if let Ok(config) = phonon::modal_editor::config::EditorConfig::load() {
+                set_extra_sample_dirs(config.sample_paths);
+                if let Some(mb) = config.sample_memory_mb {
+                    set_sample_memory_limit(mb * 1024 * 1024);
+                }
+            }
+            let bank = SampleBank::new();
+
+            if stats {
+                let folders = bank.decoded_memory();
+                let total: usize = folders.iter().map(|f| f.bytes).sum();
+                for folder in &folders {
+                    println!(
+                        "{:<24} {:>5} files {:>10}",
+                        folder.folder,
+                        folder.files,
+                        format_bytes(folder.bytes)
+                    );
+                }
+                println!(
+                    "\n{} folders, {} decoded (cache limit {}, sample_memory_mb)",
+                    folders.len(),
+                    format_bytes(total),
+                    format_bytes(sample_memory_limit())
+                );
+            } else {
+                for (name, count) in bank.banks() {
+                    println!("{:<24} {:>5}", name, count);
+                }
+            }
+        }
+
+        Commands::Analyze { file, window } => {
+            use phonon::audio_analysis::{analyze_frames, read_wav_mono};
+            use phonon::midi_input::MidiEvent;
+
+            if window <= 0.0 {
+                return Err("--window must be positive".into());
+            }
+
+            let (samples, sample_rate) = read_wav_mono(&file)?;
+            let frames = analyze_frames(&samples, sample_rate, window);
+
+            println!(
+                "🔍 {} ({:.2}s, {} Hz)",
+                file,
+                samples.len() as f32 / sample_rate,
+                sample_rate
+            );
+            println!();
+            println!("   time      rms     dB  envelope              pitch          centroid");
+
+            let loudest = frames.iter().map(|f| f.rms).fold(0.0f32, f32::max);
+            for frame in &frames {
+                let db = 20.0 * frame.rms.max(1e-6).log10();
+                let bar_len = if loudest > 0.0 {
+                    (frame.rms / loudest * 20.0).round() as usize
+                } else {
+                    0
+                };
+                let pitch = if frame.pitch > 0.0 {
+                    let midi = (69.0 + 12.0 * (frame.pitch / 440.0).log2())
+                        .round()
+                        .clamp(0.0, 127.0) as u8;
+                    format!(
+                        "{:7.1} Hz {:<4}",
+                        frame.pitch,
+                        MidiEvent::midi_to_note_name(midi)
+                    )
+                } else {
+                    format!("{:>15}", "-")
+                };
+                let centroid = if frame.centroid > 0.0 {
+                    format!("{:7.0} Hz", frame.centroid)
+                } else {
+                    format!("{:>10}", "-")
+                };
+                println!(
+                    "{:6.2}s  {:6.3} {:6.1}  {:<20}  {}  {}",
+                    frame.time,
+                    frame.rms,
+                    db,
+                    "█".repeat(bar_len),
+                    pitch,
+                    centroid
+                );
+            }
+
+            // Summary over the frames that actually sound
+            let mut pitches: Vec<f32> = frames
+                .iter()
+                .map(|f| f.pitch)
+                .filter(|&p| p > 0.0)
+                .collect();
+            pitches.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let sounding: Vec<_> = frames.iter().filter(|f| f.centroid > 0.0).collect();
+
+            println!();
+            println!("📊 Summary:");
+            println!(
+                "   Peak RMS:        {:.3} ({:.1} dB)",
+                loudest,
+                20.0 * loudest.max(1e-6).log10()
+            );
+            if pitches.is_empty() {
+                println!("   Median pitch:    - (no stable pitch)");
+            } else {
+                println!(
+                    "   Median pitch:    {:.1} Hz ({}/{} frames pitched)",
+                    pitches[pitches.len() / 2],
+                    pitches.len(),
+                    frames.len()
+                );
+            }
+            if sounding.is_empty() {
+                println!("   Mean centroid:   - (silent)");
+            } else {
+                let mean = sounding.iter().map(|f| f.centroid).sum::<f32>() / sounding.len() as f32;
+                println!("   Mean centroid:   {:.0} Hz", mean);
+            }
         }
 
         Commands::Midi {
@@ -1631,6 +2350,35 @@ out sine(440) * 0.2
     Ok(())
 }
 
+/// Print the warnings of `check_for_common_mistakes` for `code`, if any
+fn print_common_mistakes(code: &str) {
+    let warnings = phonon::error_diagnostics::check_for_common_mistakes(code);
+    if !warnings.is_empty() {
+        eprintln!("⚠️  Additional warnings:");
+        for warning in warnings {
+            eprintln!("  • {}", warning);
+        }
+    }
+}
+
+/// `.phonon` files under `path` (or `path` itself if it is a file), sorted
+fn collect_phonon_files(path: &std::path::Path) -> std::io::Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let entry_path = entry?.path();
+        if entry_path.is_dir() {
+            files.extend(collect_phonon_files(&entry_path)?);
+        } else if entry_path.extension().is_some_and(|ext| ext == "phonon") {
+            files.push(entry_path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
 /// Truncate string to max length with ellipsis
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
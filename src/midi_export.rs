@@ -0,0 +1,468 @@
+//! Standard MIDI file export: `phonon export-midi file.ph --cycles 8 out.mid`
+//!
+//! Queries the note-producing patterns of a compiled graph, without rendering
+//! any audio, and writes a type-1 MIDI file with one track per bus (plus `out`
+//! for patterns written straight into an output). Each cycle is one 4/4 bar,
+//! and the tempo comes from the graph's cps.
+//!
+//! What becomes notes:
+//! - sample patterns (`s "bd sn"`): General MIDI drums on channel 10, matched
+//!   by sample name; other samples take free notes from C4 up
+//! - synth note patterns (`saw "c3 e3"`, chords included)
+//! - note-name patterns (`note "c4 e4"`)
+//! - scale degrees (`n "0 2 4" # scale "minor" "c4"`)
+//!
+//! Numeric patterns that modulate parameters (`lpf "200 800"`) are left out.
+
+use crate::compositional_compiler::compile_program;
+use crate::link_clock::DEFAULT_BEATS_PER_CYCLE;
+use crate::modal_editor::render_queue::parse_job_code;
+use crate::pattern::{Fraction, Pattern, State, TimeSpan};
+use crate::pattern_tonal::{note_to_midi_chord, SCALES};
+use crate::unified_graph::{NodeId, Signal, SignalNode, UnifiedSignalGraph};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Ticks per quarter note
+pub const TICKS_PER_BEAT: u32 = 480;
+
+/// MIDI channel of drum notes (channel 10)
+const DRUM_CHANNEL: u8 = 9;
+
+const VELOCITY: u8 = 100;
+
+/// General MIDI drum notes for common sample names
+const GM_DRUMS: &[(&str, u8)] = &[
+    ("bd", 36),
+    ("kick", 36),
+    ("sn", 38),
+    ("sd", 38),
+    ("snare", 38),
+    ("rim", 37),
+    ("cp", 39),
+    ("clap", 39),
+    ("hh", 42),
+    ("ch", 42),
+    ("hc", 42),
+    ("oh", 46),
+    ("ho", 46),
+    ("lt", 45),
+    ("mt", 47),
+    ("ht", 50),
+    ("cr", 49),
+    ("crash", 49),
+    ("rd", 51),
+    ("ride", 51),
+    ("cb", 56),
+    ("tamb", 54),
+    ("perc", 60),
+];
+
+/// One exported note, in cycles
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportedNote {
+    pub start: f64,
+    pub end: f64,
+    pub note: u8,
+    pub channel: u8,
+}
+
+/// Notes of one bus
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiTrack {
+    pub name: String,
+    pub notes: Vec<ExportedNote>,
+}
+
+/// Everything exported from one graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiExport {
+    pub cps: f32,
+    pub tracks: Vec<MidiTrack>,
+}
+
+/// Where a pattern's values go
+enum Source<'a> {
+    Drums(&'a Pattern<String>),
+    Notes(&'a Pattern<String>),
+    Degrees {
+        pattern: &'a Pattern<String>,
+        intervals: &'a [i32],
+        root: u8,
+    },
+}
+
+/// Node ids a parameter signal reads from
+fn signal_nodes(signal: &Signal, ids: &mut Vec<usize>) {
+    match signal {
+        Signal::Node(id) => ids.push(id.0),
+        Signal::Expression(expr) => {
+            use crate::unified_graph::SignalExpr::*;
+            match &**expr {
+                Add(a, b)
+                | Multiply(a, b)
+                | Subtract(a, b)
+                | Divide(a, b)
+                | Modulo(a, b)
+                | Min(a, b) => {
+                    signal_nodes(a, ids);
+                    signal_nodes(b, ids);
+                }
+                Scale { input, min, max } => {
+                    signal_nodes(input, ids);
+                    signal_nodes(min, ids);
+                    signal_nodes(max, ids);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether a pattern holds note names (as opposed to plain numbers)
+fn has_note_names(pattern_str: &str) -> bool {
+    pattern_str
+        .split(|c: char| !(c.is_alphanumeric() || c == '#' || c == '\''))
+        .any(|word| {
+            word.parse::<f64>().is_err()
+                && word.starts_with(|c: char| matches!(c.to_ascii_lowercase(), 'a'..='g'))
+                && !note_to_midi_chord(word).is_empty()
+        })
+}
+
+/// Nodes reached from `start`, stopping at other buses. Parameter patterns of
+/// sample players (`# n`, `# gain`, ...) are left out: they shape the hits
+/// rather than being notes of their own.
+fn walk<'a>(
+    graph: &'a UnifiedSignalGraph,
+    deps: &HashMap<usize, Vec<usize>>,
+    starts: &[usize],
+    stop: &HashSet<usize>,
+) -> Vec<Source<'a>> {
+    let mut seen = HashSet::new();
+    let mut parameters = HashSet::new();
+    let mut stack: Vec<usize> = starts.to_vec();
+    let mut found = Vec::new();
+    while let Some(id) = stack.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        match graph.get_node(NodeId(id)) {
+            Some(SignalNode::Sample {
+                pattern,
+                gain,
+                pan,
+                speed,
+                n,
+                note,
+                ..
+            }) => {
+                let mut ids = Vec::new();
+                for signal in [gain, pan, speed, n, note] {
+                    signal_nodes(signal, &mut ids);
+                }
+                parameters.extend(ids);
+                found.push((id, Source::Drums(pattern)));
+            }
+            Some(SignalNode::SynthPattern { pattern, .. }) => {
+                found.push((id, Source::Notes(pattern)));
+            }
+            Some(SignalNode::Pattern {
+                pattern,
+                pattern_str,
+                ..
+            }) if has_note_names(pattern_str) => {
+                found.push((id, Source::Notes(pattern)));
+            }
+            Some(SignalNode::ScaleQuantize {
+                pattern,
+                scale_name,
+                root_note,
+                ..
+            }) => {
+                if let Some(intervals) = SCALES.get(scale_name.as_str()) {
+                    found.push((
+                        id,
+                        Source::Degrees {
+                            pattern,
+                            intervals,
+                            root: *root_note,
+                        },
+                    ));
+                }
+            }
+            _ => {}
+        }
+        for &dep in deps.get(&id).map(Vec::as_slice).unwrap_or_default() {
+            if !stop.contains(&dep) {
+                stack.push(dep);
+            }
+        }
+    }
+    // Node order, so exports are deterministic
+    found.sort_by_key(|(id, _)| *id);
+    found
+        .into_iter()
+        .filter(|(id, _)| !parameters.contains(id))
+        .map(|(_, source)| source)
+        .collect()
+}
+
+/// Drum note for a sample name (`bd:3` → bass drum); unknown names get the
+/// next free note from C4 up
+fn drum_note(sample: &str, others: &mut BTreeMap<String, u8>) -> u8 {
+    let name = sample.split(':').next().unwrap_or(sample);
+    if let Some(&(_, note)) = GM_DRUMS.iter().find(|(drum, _)| *drum == name) {
+        return note;
+    }
+    let next = 60 + others.len().min(67) as u8;
+    *others.entry(name.to_string()).or_insert(next)
+}
+
+/// Query `source` over `[0, cycles)` into notes
+fn notes_of(
+    source: &Source,
+    cycles: f64,
+    channel: u8,
+    others: &mut BTreeMap<String, u8>,
+) -> Vec<ExportedNote> {
+    let pattern = match source {
+        Source::Drums(p) | Source::Notes(p) => p,
+        Source::Degrees { pattern, .. } => pattern,
+    };
+    let state = State {
+        span: TimeSpan::new(Fraction::from_float(0.0), Fraction::from_float(cycles)),
+        controls: HashMap::new(),
+    };
+    let mut notes = Vec::new();
+    for hap in pattern.query(&state) {
+        // Only onsets inside the range; a fragment is part of an earlier note
+        let Some(whole) = hap.whole else { continue };
+        if whole.begin != hap.part.begin {
+            continue;
+        }
+        let (start, end) = (whole.begin.to_float(), whole.end.to_float());
+        let value = hap.value.trim();
+        if value.is_empty() || value == "~" {
+            continue;
+        }
+        let (keys, channel) = match source {
+            Source::Drums(_) => (vec![drum_note(value, others)], DRUM_CHANNEL),
+            Source::Notes(_) => (note_to_midi_chord(value), channel),
+            Source::Degrees {
+                intervals, root, ..
+            } => {
+                let Ok(degree) = value.parse::<i32>() else {
+                    continue;
+                };
+                // The same mapping as the ScaleQuantize node
+                let octave = degree / intervals.len() as i32;
+                let step = degree.rem_euclid(intervals.len() as i32);
+                let note = *root as i32 + octave * 12 + intervals[step as usize];
+                (vec![note.clamp(0, 127) as u8], channel)
+            }
+        };
+        for note in keys {
+            notes.push(ExportedNote {
+                start,
+                end: end.min(cycles),
+                note: note.min(127),
+                channel,
+            });
+        }
+    }
+    notes
+}
+
+/// Notes of every bus (and of patterns written straight into an output) over
+/// the first `cycles` cycles
+pub fn export_graph(graph: &UnifiedSignalGraph, cycles: f64) -> MidiExport {
+    let deps = graph.build_dag_dependencies();
+    let mut buses: Vec<(String, usize)> = graph
+        .get_all_bus_names()
+        .into_iter()
+        .filter(|name| !name.starts_with('_'))
+        .filter_map(|name| graph.get_bus(&name).map(|id| (name, id.0)))
+        .collect();
+    buses.sort();
+    let bus_nodes: HashSet<usize> = buses.iter().map(|(_, id)| *id).collect();
+
+    let mut sources: Vec<(String, Vec<Source>)> = buses
+        .iter()
+        .map(|(name, id)| {
+            let stop: HashSet<usize> = bus_nodes.iter().copied().filter(|b| b != id).collect();
+            (format!("~{}", name), walk(graph, &deps, &[*id], &stop))
+        })
+        .collect();
+    let outputs: Vec<usize> = graph
+        .get_output()
+        .into_iter()
+        .chain(graph.get_output_channels().into_iter().map(|(_, id)| id))
+        .map(|id| id.0)
+        .filter(|id| !bus_nodes.contains(id))
+        .collect();
+    sources.push(("out".to_string(), walk(graph, &deps, &outputs, &bus_nodes)));
+
+    let mut others = BTreeMap::new();
+    let mut tracks = Vec::new();
+    let mut channel = 0u8;
+    for (name, sources) in sources {
+        let mut notes: Vec<ExportedNote> = sources
+            .iter()
+            .flat_map(|source| notes_of(source, cycles, channel, &mut others))
+            .collect();
+        if notes.is_empty() {
+            continue;
+        }
+        notes.sort_by(|a, b| a.start.total_cmp(&b.start).then(a.note.cmp(&b.note)));
+        if notes.iter().any(|n| n.channel == channel) {
+            // The next melodic track gets the next channel, skipping drums
+            channel = (channel + 1) % 16;
+            if channel == DRUM_CHANNEL {
+                channel += 1;
+            }
+        }
+        tracks.push(MidiTrack { name, notes });
+    }
+
+    MidiExport {
+        cps: graph.get_cps(),
+        tracks,
+    }
+}
+
+/// Compile the file at `input` and write its first `cycles` cycles to `output`
+/// as a MIDI file
+pub fn export_file(input: &Path, cycles: f64, output: &Path) -> Result<MidiExport, String> {
+    if !(cycles > 0.0 && cycles.is_finite()) {
+        return Err(format!("cycles must be positive, got {}", cycles));
+    }
+    let code = std::fs::read_to_string(input)
+        .map_err(|e| format!("Failed to read {}: {}", input.display(), e))?;
+    let statements = parse_job_code(&code)?;
+    let graph = compile_program(statements, 44100.0, None)?;
+    let export = export_graph(&graph, cycles);
+    std::fs::write(output, export.to_smf())
+        .map_err(|e| format!("Failed to write {}: {}", output.display(), e))?;
+    Ok(export)
+}
+
+/// Variable-length quantity, as used for MIDI delta times
+fn push_vlq(bytes: &mut Vec<u8>, mut value: u32) {
+    let mut groups = vec![(value & 0x7f) as u8];
+    value >>= 7;
+    while value > 0 {
+        groups.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.extend(groups.iter().rev());
+}
+
+fn push_chunk(file: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    file.extend_from_slice(kind);
+    file.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    file.extend_from_slice(data);
+}
+
+/// Track name meta event at time 0
+fn track_name(data: &mut Vec<u8>, name: &str) {
+    data.extend_from_slice(&[0x00, 0xff, 0x03]);
+    push_vlq(data, name.len() as u32);
+    data.extend_from_slice(name.as_bytes());
+}
+
+impl MidiExport {
+    /// Tempo in quarter notes per minute
+    pub fn bpm(&self) -> f64 {
+        self.cps as f64 * 60.0 * DEFAULT_BEATS_PER_CYCLE
+    }
+
+    /// The export as a type-1 standard MIDI file: a tempo track, then one
+    /// track per bus
+    pub fn to_smf(&self) -> Vec<u8> {
+        let ticks_per_cycle = TICKS_PER_BEAT as f64 * DEFAULT_BEATS_PER_CYCLE;
+        let tick = |cycle: f64| (cycle * ticks_per_cycle).round().max(0.0) as u32;
+
+        let mut file = Vec::new();
+        let mut header = Vec::new();
+        header.extend_from_slice(&1u16.to_be_bytes());
+        header.extend_from_slice(&(self.tracks.len() as u16 + 1).to_be_bytes());
+        header.extend_from_slice(&(TICKS_PER_BEAT as u16).to_be_bytes());
+        push_chunk(&mut file, b"MThd", &header);
+
+        // Tempo track: 4/4, one cycle per bar
+        let mut tempo = Vec::new();
+        track_name(&mut tempo, "phonon");
+        let micros = ((60_000_000.0 / self.bpm().max(1e-3)).round() as u32).min(0xff_ffff);
+        tempo.extend_from_slice(&[0x00, 0xff, 0x51, 0x03]);
+        tempo.extend_from_slice(&micros.to_be_bytes()[1..]);
+        tempo.extend_from_slice(&[0x00, 0xff, 0x58, 0x04, 4, 2, 24, 8]);
+        tempo.extend_from_slice(&[0x00, 0xff, 0x2f, 0x00]);
+        push_chunk(&mut file, b"MTrk", &tempo);
+
+        for track in &self.tracks {
+            // (tick, is_note_on, channel, note): offs sort before ons at a tick
+            let mut events: Vec<(u32, bool, u8, u8)> = Vec::new();
+            for note in &track.notes {
+                let (on, off) = (tick(note.start), tick(note.end));
+                events.push((on, true, note.channel, note.note));
+                events.push((off.max(on + 1), false, note.channel, note.note));
+            }
+            events.sort();
+
+            let mut data = Vec::new();
+            track_name(&mut data, &track.name);
+            let mut now = 0;
+            for (at, on, channel, note) in events {
+                push_vlq(&mut data, at - now);
+                now = at;
+                let (status, velocity) = if on {
+                    (0x90 | channel, VELOCITY)
+                } else {
+                    (0x80 | channel, 0)
+                };
+                data.extend_from_slice(&[status, note, velocity]);
+            }
+            data.extend_from_slice(&[0x00, 0xff, 0x2f, 0x00]);
+            push_chunk(&mut file, b"MTrk", &data);
+        }
+        file
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vlq() {
+        let encode = |value| {
+            let mut bytes = Vec::new();
+            push_vlq(&mut bytes, value);
+            bytes
+        };
+        assert_eq!(encode(0), vec![0x00]);
+        assert_eq!(encode(0x7f), vec![0x7f]);
+        assert_eq!(encode(0x80), vec![0x81, 0x00]);
+        assert_eq!(encode(1920), vec![0x8f, 0x00]);
+        assert_eq!(encode(0x0fff_ffff), vec![0xff, 0xff, 0xff, 0x7f]);
+    }
+
+    #[test]
+    fn test_drum_notes() {
+        let mut others = BTreeMap::new();
+        assert_eq!(drum_note("bd", &mut others), 36);
+        assert_eq!(drum_note("sn:2", &mut others), 38);
+        assert_eq!(drum_note("glitch", &mut others), 60);
+        assert_eq!(drum_note("arpy", &mut others), 61);
+        assert_eq!(drum_note("glitch:1", &mut others), 60);
+    }
+
+    #[test]
+    fn test_has_note_names() {
+        assert!(has_note_names("c4 e4 g4"));
+        assert!(has_note_names("<c'maj e'min>"));
+        assert!(!has_note_names("200 800 1600"));
+        assert!(!has_note_names("0 0.5 ~ 1"));
+    }
+}
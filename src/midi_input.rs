@@ -8,10 +8,11 @@
     clippy::should_implement_trait,
     clippy::unnecessary_map_or
 )]
+use crate::link_clock::TempoSource;
 use midir::{Ignore, MidiInput, MidiInputConnection, MidiInputPort};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
 use std::time::Instant;
 
 /// Shared MIDI event queue for real-time monitoring
@@ -260,6 +261,202 @@ impl Default for MidiInputHandler {
     }
 }
 
+/// MIDI clock ticks per beat
+const CLOCK_TICKS_PER_BEAT: f64 = 24.0;
+
+/// Ticks the tempo is measured over: the last beat
+const TEMPO_WINDOW_TICKS: usize = 24;
+
+/// A longer gap between ticks means the clock stopped; the tempo is then
+/// measured afresh
+const MAX_TICK_GAP_SECS: f64 = 0.5;
+
+/// Tempo and beat position of an external MIDI clock, from when its ticks
+/// (24 a beat) arrive and its Start, Stop, Continue and Song Position
+/// messages. Once Start or a song position has placed the ticks in the bar,
+/// the quantum is a whole cycle; before that only the beat is known.
+#[derive(Debug, Clone)]
+pub struct ClockFollower {
+    beats_per_cycle: f64,
+    /// When the last ticks arrived, oldest first
+    ticks: VecDeque<Instant>,
+    /// Position of the last tick, in ticks since Start or the song position
+    tick: i64,
+    /// Whether Start or a song position placed the ticks in the bar
+    positioned: bool,
+    /// Running after Start or Continue, stopped after Stop, `None` until the
+    /// master sends one
+    running: Option<bool>,
+    /// Counts Starts, Continues and song positions: each moves the beat
+    resyncs: u64,
+}
+
+impl ClockFollower {
+    pub fn new(beats_per_cycle: f64) -> Self {
+        Self {
+            beats_per_cycle,
+            ticks: VecDeque::with_capacity(TEMPO_WINDOW_TICKS + 1),
+            tick: -1,
+            positioned: false,
+            running: None,
+            resyncs: 0,
+        }
+    }
+
+    /// Take one message that arrived at `at`; anything but clock and
+    /// transport messages is ignored
+    pub fn receive(&mut self, message: &[u8], at: Instant) {
+        match message {
+            [0xF8, ..] => {
+                let gap = self.ticks.back().map_or(0.0, |last| {
+                    at.saturating_duration_since(*last).as_secs_f64()
+                });
+                if gap > MAX_TICK_GAP_SECS {
+                    self.ticks.clear();
+                }
+                self.ticks.push_back(at);
+                if self.ticks.len() > TEMPO_WINDOW_TICKS + 1 {
+                    self.ticks.pop_front();
+                }
+                // Stopped, ticks keep the tempo but don't move the song
+                if self.running != Some(false) {
+                    self.tick += 1;
+                }
+            }
+            [0xFA, ..] => {
+                self.tick = -1;
+                self.positioned = true;
+                self.running = Some(true);
+                self.resyncs += 1;
+            }
+            [0xFB, ..] => {
+                self.running = Some(true);
+                self.resyncs += 1;
+            }
+            [0xFC, ..] => self.running = Some(false),
+            // Song position, in sixteenths (six ticks): the next tick plays there
+            [0xF2, lsb, msb, ..] => {
+                let sixteenths = ((*msb as i64) << 7) | *lsb as i64;
+                self.tick = sixteenths * 6 - 1;
+                self.positioned = true;
+                self.resyncs += 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Seconds between ticks, averaged over the last beat
+    fn tick_secs(&self) -> Option<f64> {
+        let (first, last) = (self.ticks.front()?, self.ticks.back()?);
+        let ticks = self.ticks.len() - 1;
+        let secs = last.duration_since(*first).as_secs_f64() / ticks as f64;
+        (secs > 0.0).then_some(secs)
+    }
+
+    /// Whether Start, Stop or Continue last ran or stopped the transport
+    pub fn running(&self) -> Option<bool> {
+        self.running
+    }
+
+    /// How many times the beat has been moved (Start, Continue, song
+    /// position), to know when to seek into phase again
+    pub fn resyncs(&self) -> u64 {
+        self.resyncs
+    }
+}
+
+impl TempoSource for ClockFollower {
+    fn tempo_bpm(&self) -> f64 {
+        self.tick_secs()
+            .map_or(0.0, |secs| 60.0 / (secs * CLOCK_TICKS_PER_BEAT))
+    }
+
+    /// Carries on from the last tick at the measured tempo, so a late or
+    /// missing tick doesn't hold the beat back
+    fn beat_at(&self, at: Instant) -> f64 {
+        let since_tick = match (self.ticks.back(), self.tick_secs()) {
+            (Some(last), Some(secs)) => at.saturating_duration_since(*last).as_secs_f64() / secs,
+            _ => 0.0,
+        };
+        (self.tick as f64 + since_tick) / CLOCK_TICKS_PER_BEAT
+    }
+
+    fn quantum(&self) -> f64 {
+        if self.positioned {
+            self.beats_per_cycle
+        } else {
+            1.0
+        }
+    }
+
+    fn is_playing(&self) -> bool {
+        self.running != Some(false) && self.tick_secs().is_some()
+    }
+}
+
+/// MIDI clock in for following another device's tempo: a [`ClockFollower`]
+/// fed by an input port's clock and transport messages as they arrive
+pub struct MidiClockIn {
+    /// Held to keep receiving; dropping it disconnects
+    _connection: MidiInputConnection<()>,
+    follower: Arc<Mutex<ClockFollower>>,
+    port_name: String,
+}
+
+impl MidiClockIn {
+    /// Connect to the input whose name contains `device_name` (the first
+    /// input when empty)
+    pub fn connect(
+        device_name: &str,
+        beats_per_cycle: f64,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut midi_in = MidiInput::new("Phonon MIDI Clock In")?;
+        let ports = midi_in.ports();
+        let port = ports
+            .iter()
+            .find(|p| {
+                midi_in
+                    .port_name(p)
+                    .map_or(false, |n| n.contains(device_name))
+            })
+            .ok_or_else(|| format!("MIDI device '{}' not found", device_name))?
+            .clone();
+        let port_name = midi_in.port_name(&port)?;
+
+        // Clock is what we're here for; sysex and active sensing aren't
+        midi_in.ignore(Ignore::SysexAndActiveSense);
+
+        let follower = Arc::new(Mutex::new(ClockFollower::new(beats_per_cycle)));
+        let receiving = Arc::clone(&follower);
+        let connection = midi_in.connect(
+            &port,
+            "phonon-clock-in",
+            move |_, message, _| {
+                let at = Instant::now();
+                if let Ok(mut follower) = receiving.lock() {
+                    follower.receive(message, at);
+                }
+            },
+            (),
+        )?;
+
+        Ok(Self {
+            _connection: connection,
+            follower,
+            port_name,
+        })
+    }
+
+    /// The clock as received so far
+    pub fn follower(&self) -> MutexGuard<'_, ClockFollower> {
+        self.follower.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn port_name(&self) -> &str {
+        &self.port_name
+    }
+}
+
 /// Complete MIDI note with start and end times (for legato calculation)
 #[derive(Debug, Clone)]
 struct NoteEvent {
@@ -902,6 +1099,130 @@ pub struct LiveRecordingPreview {
     pub elapsed_secs: f64,
 }
 
+// ========== Takes ==========
+
+/// Takes captured by `record` statements, as mini-notation, by name
+static TAKES: RwLock<BTreeMap<String, String>> = RwLock::new(BTreeMap::new());
+
+/// Keep a finished take so `%name` plays it
+pub fn store_take(name: &str, pattern: String) {
+    if let Ok(mut takes) = TAKES.write() {
+        takes.insert(name.to_string(), pattern);
+    }
+}
+
+/// The mini-notation of a recorded take
+pub fn get_take(name: &str) -> Option<String> {
+    TAKES.read().ok()?.get(name).cloned()
+}
+
+/// Every recorded take, by name
+pub fn takes() -> BTreeMap<String, String> {
+    TAKES.read().map(|takes| takes.clone()).unwrap_or_default()
+}
+
+/// What a `record ~keys 4c -> "riff1"` statement asks for
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TakeRequest {
+    /// Take name, played back as `%name`
+    pub name: String,
+    /// Bus being recorded
+    pub bus: String,
+    /// MIDI channel (0-15) the bus listens to, None for all channels
+    pub channel: Option<u8>,
+    /// Length of the take in cycles
+    pub cycles: usize,
+}
+
+/// A take in progress: note-ons quantized to a grid of steps per cycle
+#[derive(Debug, Clone)]
+pub struct TakeRecording {
+    pub request: TakeRequest,
+    /// Cycle the take starts on (the first cycle boundary after arming)
+    pub start_cycle: f64,
+    steps_per_cycle: usize,
+    steps: Vec<BTreeSet<u8>>,
+}
+
+impl TakeRecording {
+    /// Arm a take at `now` (in cycles); it starts on the next cycle boundary
+    pub fn new(request: TakeRequest, now: f64, steps_per_cycle: usize) -> Self {
+        let steps_per_cycle = steps_per_cycle.max(1);
+        let steps = vec![BTreeSet::new(); request.cycles * steps_per_cycle];
+        Self {
+            request,
+            start_cycle: now.ceil(),
+            steps_per_cycle,
+            steps,
+        }
+    }
+
+    /// Record a note-on at `cycle`. Notes on other channels, and notes that
+    /// land outside the take once quantized, are ignored
+    pub fn note_on(&mut self, cycle: f64, channel: u8, note: u8) {
+        if self.request.channel.is_some_and(|c| c != channel) {
+            return;
+        }
+        let step = ((cycle - self.start_cycle) * self.steps_per_cycle as f64).round();
+        if step >= 0.0 && (step as usize) < self.steps.len() {
+            self.steps[step as usize].insert(note);
+        }
+    }
+
+    /// Whether the take is over at `cycle`
+    pub fn is_done(&self, cycle: f64) -> bool {
+        cycle >= self.start_cycle + self.request.cycles as f64
+    }
+
+    /// The take as mini-notation: one group per cycle, `~` for empty steps
+    /// and `[c4,e4]` for chords. A take of several cycles alternates them
+    /// with `<...>`. The grid is thinned to the coarsest one that keeps
+    /// every note in place
+    pub fn to_mini_notation(&self) -> String {
+        let stride = (1..=self.steps_per_cycle)
+            .rev()
+            .find(|stride| {
+                self.steps_per_cycle % stride == 0
+                    && self
+                        .steps
+                        .iter()
+                        .enumerate()
+                        .all(|(i, notes)| notes.is_empty() || i % stride == 0)
+            })
+            .unwrap_or(1);
+        let step_token = |notes: &BTreeSet<u8>| match notes.len() {
+            0 => "~".to_string(),
+            1 => MidiEvent::midi_to_note_name(*notes.iter().next().unwrap()),
+            _ => format!(
+                "[{}]",
+                notes
+                    .iter()
+                    .map(|&note| MidiEvent::midi_to_note_name(note))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        };
+        let cycles: Vec<String> = self
+            .steps
+            .chunks(self.steps_per_cycle)
+            .map(|cycle| {
+                cycle
+                    .iter()
+                    .step_by(stride)
+                    .map(step_token)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        if cycles.len() == 1 {
+            cycles[0].clone()
+        } else {
+            let groups: Vec<String> = cycles.iter().map(|c| format!("[{}]", c)).collect();
+            format!("<{}>", groups.join(" "))
+        }
+    }
+}
+
 // ========== Scale Locking ==========
 
 /// Musical scale definition - intervals from root (in semitones)
@@ -1246,6 +1567,28 @@ mod tests {
         assert_eq!(MidiEvent::midi_to_note_name(48), "c3");
     }
 
+    #[test]
+    fn test_take_recording_to_mini_notation() {
+        let request = TakeRequest {
+            name: "riff1".to_string(),
+            bus: "keys".to_string(),
+            channel: Some(0),
+            cycles: 2,
+        };
+        // Armed mid-cycle, so the take starts at cycle 3
+        let mut take = TakeRecording::new(request, 2.4, 16);
+        assert_eq!(take.start_cycle, 3.0);
+        take.note_on(2.99, 0, 60); // a touch early: quantized onto the downbeat
+        take.note_on(3.5, 0, 64);
+        take.note_on(3.51, 0, 67);
+        take.note_on(3.75, 1, 72); // other channel
+        take.note_on(4.25, 0, 62);
+        take.note_on(5.2, 0, 65); // after the take
+        assert!(!take.is_done(4.9));
+        assert!(take.is_done(5.0));
+        assert_eq!(take.to_mini_notation(), "<[c4 ~ [e4,g4] ~] [~ d4 ~ ~]>");
+    }
+
     #[test]
     fn test_parse_note_on() {
         let bytes = [0x90, 60, 100]; // Note on, channel 0, C4, velocity 100
@@ -1710,6 +2053,55 @@ mod tests {
         assert!((arp.samples_per_step as i64 - 5512).abs() < 10);
     }
 
+    #[test]
+    fn test_clock_follower_tempo_and_position() {
+        use std::time::Duration;
+        // 120 BPM: a tick every 1/48 s
+        let base = Instant::now();
+        let at = |tick: usize| base + Duration::from_secs_f64(tick as f64 / 48.0);
+        let mut clock = ClockFollower::new(4.0);
+        assert!(!clock.is_playing());
+
+        // Already running: the tempo and the beat, but not the bar
+        for tick in 0..30 {
+            clock.receive(&[0xF8], at(tick));
+        }
+        assert!((clock.tempo_bpm() - 120.0).abs() < 1e-6);
+        assert!(clock.is_playing());
+        assert_eq!(clock.quantum(), 1.0);
+        assert_eq!(clock.running(), None);
+
+        // Start: the first tick after it is the downbeat
+        clock.receive(&[0xFA], at(30));
+        for tick in 30..55 {
+            clock.receive(&[0xF8], at(tick));
+        }
+        assert!((clock.beat_at(at(54)) - 1.0).abs() < 1e-9);
+        assert!((clock.beat_at(at(66)) - 1.5).abs() < 1e-9);
+        assert_eq!(clock.quantum(), 4.0);
+        assert_eq!((clock.running(), clock.resyncs()), (Some(true), 1));
+
+        // Stopped, ticks keep coming but the beat holds
+        clock.receive(&[0xFC], at(55));
+        clock.receive(&[0xF8], at(55));
+        assert!(!clock.is_playing());
+
+        // Song position 16 (four beats), then Continue from there
+        clock.receive(&[0xF2, 16, 0], at(56));
+        clock.receive(&[0xFB], at(56));
+        clock.receive(&[0xF8], at(56));
+        assert!((clock.beat_at(at(56)) - 4.0).abs() < 1e-9);
+        assert_eq!(clock.resyncs(), 3);
+
+        // After a long gap the tempo is measured afresh
+        let later =
+            |tick: usize| at(56) + Duration::from_secs(2) + Duration::from_millis(tick as u64 * 25);
+        for tick in 0..3 {
+            clock.receive(&[0xF8], later(tick));
+        }
+        assert!((clock.tempo_bpm() - 100.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_arp_pattern_from_str() {
         assert_eq!(ArpPattern::from_str("up"), Some(ArpPattern::Up));
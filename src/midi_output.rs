@@ -10,6 +10,7 @@ use crate::pattern_midi::MidiMessage;
 use crate::pattern_tonal::note_to_midi;
 use midir::{MidiOutput, MidiOutputConnection, MidiOutputPort};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -305,6 +306,116 @@ impl MidiScheduler {
     }
 }
 
+/// MIDI clock resolution: ticks per beat
+pub const CLOCK_TICKS_PER_BEAT: f64 = 24.0;
+
+/// Further ticks than this between two updates is a jump (`:cue`), which
+/// resyncs instead of sending a burst
+const MAX_TICK_BURST: i64 = 24;
+
+/// The MIDI clock and transport messages due as the cycle position moves:
+/// 24 ticks a beat, Start the first time the transport runs, then Stop and
+/// Continue as it stops and starts
+pub struct ClockTicker {
+    beats_per_cycle: f64,
+    /// Tick the position was last at, once started
+    last_tick: Option<i64>,
+    running: bool,
+}
+
+impl ClockTicker {
+    pub fn new(beats_per_cycle: f64) -> Self {
+        Self {
+            beats_per_cycle,
+            last_tick: None,
+            running: false,
+        }
+    }
+
+    /// Messages to send with playback at `cycle` and the transport `running`
+    pub fn update(&mut self, cycle: f64, running: bool) -> Vec<MidiMessage> {
+        let tick = (cycle * self.beats_per_cycle * CLOCK_TICKS_PER_BEAT).floor() as i64;
+        let mut messages = Vec::new();
+        if running != self.running {
+            self.running = running;
+            messages.push(match (running, self.last_tick) {
+                (false, _) => MidiMessage::Stop,
+                (true, None) => MidiMessage::Start,
+                (true, Some(_)) => MidiMessage::Continue,
+            });
+            if running {
+                self.last_tick = Some(tick);
+            }
+            return messages;
+        }
+        if let (true, Some(last)) = (running, self.last_tick) {
+            let due = tick - last;
+            if (1..=MAX_TICK_BURST).contains(&due) {
+                messages.extend((0..due).map(|_| MidiMessage::Clock));
+            }
+            self.last_tick = Some(tick);
+        }
+        messages
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+}
+
+/// MIDI clock out for other gear to follow, from the live cycle position the
+/// editor's render thread publishes. It leads the speakers by the audio
+/// cushion, as the position does.
+pub struct MidiClockOut {
+    running: Arc<AtomicBool>,
+    alive: Arc<AtomicBool>,
+}
+
+impl MidiClockOut {
+    /// Connect to the output whose name contains `device_name` and start
+    /// ticking from `cycle_bits` (an `f64` stored as bits), with the
+    /// transport stopped
+    pub fn connect(
+        device_name: &str,
+        cycle_bits: Arc<AtomicU64>,
+        beats_per_cycle: f64,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut handler = MidiOutputHandler::new()?;
+        handler.connect(device_name)?;
+        let running = Arc::new(AtomicBool::new(false));
+        let alive = Arc::new(AtomicBool::new(true));
+
+        let (running_clock, alive_clock) = (Arc::clone(&running), Arc::clone(&alive));
+        thread::spawn(move || {
+            let mut ticker = ClockTicker::new(beats_per_cycle);
+            while alive_clock.load(Ordering::Relaxed) {
+                let cycle = f64::from_bits(cycle_bits.load(Ordering::Relaxed));
+                for message in ticker.update(cycle, running_clock.load(Ordering::Relaxed)) {
+                    let _ = handler.send(message);
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+            // Followers stop with us
+            if ticker.is_running() {
+                let _ = handler.send(MidiMessage::Stop);
+            }
+        });
+
+        Ok(Self { running, alive })
+    }
+
+    /// Run or stop the transport: Start (the first time) or Continue, or Stop
+    pub fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::Relaxed);
+    }
+}
+
+impl Drop for MidiClockOut {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Relaxed);
+    }
+}
+
 /// Helper function to convert note strings to MIDI messages
 pub fn note_to_midi_message(note_str: &str, channel: u8, velocity: u8) -> Option<MidiMessage> {
     // Handle special pattern names for drums
@@ -320,7 +431,8 @@ pub fn note_to_midi_message(note_str: &str, channel: u8, velocity: u8) -> Option
         "cy" | "crash" => 49,         // Crash cymbal
         "rd" | "ride" => 51,          // Ride cymbal
         "~" | "_" => return None,     // Rest/silence
-        _ => note_to_midi(note_str)?, // Try to parse as note
+        // Try to parse as note, sent as the standard key nearest its tuned pitch
+        _ => crate::pitch::nearest_standard_key(note_to_midi(note_str)?),
     };
     Some(MidiMessage::NoteOn {
         channel,
@@ -359,6 +471,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_clock_ticker_follows_the_transport() {
+        let bytes = |messages: Vec<MidiMessage>| -> Vec<u8> {
+            messages.iter().flat_map(MidiMessage::to_bytes).collect()
+        };
+        // One beat a cycle: 24 ticks a cycle
+        let mut ticker = ClockTicker::new(1.0);
+        assert!(ticker.update(0.0, false).is_empty());
+        assert_eq!(bytes(ticker.update(0.0, true)), [0xFA]);
+        assert_eq!(bytes(ticker.update(1.5 / 24.0, true)), [0xF8]);
+        assert_eq!(bytes(ticker.update(3.5 / 24.0, true)), [0xF8, 0xF8]);
+
+        // Stopped, the position holds and no ticks go out
+        assert_eq!(bytes(ticker.update(3.5 / 24.0, false)), [0xFC]);
+        assert!(ticker.update(3.5 / 24.0, false).is_empty());
+        assert_eq!(bytes(ticker.update(3.5 / 24.0, true)), [0xFB]);
+        assert_eq!(bytes(ticker.update(4.5 / 24.0, true)), [0xF8]);
+
+        // A jump resyncs without a burst
+        assert!(ticker.update(32.0, true).is_empty());
+        assert_eq!(bytes(ticker.update(32.0 + 1.5 / 24.0, true)), [0xF8]);
+    }
+
     #[test]
     fn test_pattern_to_midi() {
         use crate::pattern::*;
@@ -0,0 +1,261 @@
+//! Audition: hear one line on its own (Alt+Enter)
+//!
+//! The bus or expression under the cursor (or the selection) is compiled with
+//! the buffer's other buses, tempo and functions but with itself as the only
+//! output, rendered for one cycle from the live position, and mixed over the
+//! running output at low volume. Nothing reaches the live graph, so a line can
+//! be checked before it is evaluated.
+//!
+//! The render thread owns an [`AuditionMixer`]; finished buffers travel back to
+//! the UI to be freed, so the render thread never deallocates them.
+
+use crate::compositional_compiler::compile_program;
+use crate::compositional_parser::{parse_program, BusType, Expr, Statement};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+
+/// Level of the audition relative to the live output
+pub const AUDITION_GAIN: f32 = 0.4;
+
+/// Longest audition rendered, however slow the tempo
+const MAX_AUDITION_SECONDS: f32 = 8.0;
+
+/// Fade at both ends so the audition doesn't click in or out
+const FADE_SECONDS: f32 = 0.005;
+
+/// Buffers in flight in either direction
+const CHANNEL_CAPACITY: usize = 4;
+
+/// Build the program that plays only `target`: the statements of `context` that
+/// define things (buses, tempo, functions) with `target` as the output.
+/// `target` is a bus definition (`~bass $ ...`, auditioned as `~bass`), an
+/// output line, or a bare expression.
+pub fn audition_program(context: &str, target: &str) -> Result<Vec<Statement>, String> {
+    let target = target.trim();
+    if target.is_empty() || target.starts_with("--") {
+        return Err("Nothing to audition here".to_string());
+    }
+
+    let (output, own_definition) = match single_statement(target) {
+        Some(Statement::BusAssignment {
+            name,
+            params,
+            expr,
+            bus_type: BusType::Signal,
+        }) if params.is_empty() => {
+            let definition = Statement::BusAssignment {
+                name: name.clone(),
+                params,
+                expr,
+                bus_type: BusType::Signal,
+            };
+            (Expr::BusRef(name), Some(definition))
+        }
+        Some(Statement::Output(expr)) | Some(Statement::OutputChannel { expr, .. }) => (expr, None),
+        Some(_) => {
+            return Err("Only a bus, an output or an expression can be auditioned".to_string())
+        }
+        None => match single_statement(&format!("out $ {}", target)) {
+            Some(Statement::Output(expr)) => (expr, None),
+            _ => return Err(format!("Can't audition: {}", target)),
+        },
+    };
+
+    let mut program = definitions(context);
+    if let Some(definition) = own_definition {
+        // The line as written, not the buffer's (possibly older) version of it
+        if let Statement::BusAssignment { name, .. } = &definition {
+            program.retain(
+                |s| !matches!(s, Statement::BusAssignment { name: other, .. } if other == name),
+            );
+        }
+        program.push(definition);
+    }
+    program.push(Statement::Output(output));
+    Ok(program)
+}
+
+/// The statement `source` parses to, if it is exactly one
+fn single_statement(source: &str) -> Option<Statement> {
+    match parse_program(source) {
+        Ok((rest, mut statements)) if rest.trim().is_empty() && statements.len() == 1 => {
+            statements.pop()
+        }
+        _ => None,
+    }
+}
+
+/// Definitions from `context` (whatever of it parses): everything that makes
+/// sound or acts on the session is left out
+fn definitions(context: &str) -> Vec<Statement> {
+    let statements = parse_program(context)
+        .map(|(_, statements)| statements)
+        .unwrap_or_default();
+    statements
+        .into_iter()
+        .filter(|s| {
+            matches!(
+                s,
+                Statement::BusAssignment { .. }
+                    | Statement::TemplateAssignment { .. }
+                    | Statement::PatternAssignment { .. }
+                    | Statement::FunctionDef { .. }
+                    | Statement::Tempo(_)
+                    | Statement::Bpm { .. }
+            )
+        })
+        .collect()
+}
+
+/// Render one cycle of `program` from `start_cycle` as stereo-interleaved
+/// samples, faded at both ends and scaled to [`AUDITION_GAIN`]. `default_cps`
+/// applies when the program sets no tempo, as for evaluated code.
+pub fn render_audition(
+    program: Vec<Statement>,
+    sample_rate: f32,
+    start_cycle: f64,
+    default_cps: Option<f32>,
+) -> Result<Vec<f32>, String> {
+    let sets_tempo = program
+        .iter()
+        .any(|s| matches!(s, Statement::Tempo(_) | Statement::Bpm { .. }));
+    let mut graph = compile_program(program, sample_rate, None)?;
+    if let (Some(cps), false) = (default_cps, sets_tempo) {
+        graph.set_cps(cps);
+    }
+    graph.preload_samples();
+    graph.seek_to_cycle(start_cycle);
+
+    let cycle_frames = (sample_rate / graph.get_cps().max(f32::EPSILON)) as usize;
+    let frames = cycle_frames.min((sample_rate * MAX_AUDITION_SECONDS) as usize);
+    let mut buffer = vec![0.0f32; frames * 2];
+    for block in buffer.chunks_mut(1024) {
+        graph.process_buffer(block);
+    }
+
+    let fade = ((sample_rate * FADE_SECONDS) as usize).clamp(1, frames / 2 + 1);
+    for (i, frame) in buffer.chunks_exact_mut(2).enumerate() {
+        let edge = i.min(frames - 1 - i);
+        let level = AUDITION_GAIN * (edge as f32 / fade as f32).min(1.0);
+        frame[0] *= level;
+        frame[1] *= level;
+    }
+    Ok(buffer)
+}
+
+/// UI end: sends rendered auditions and frees the finished ones
+#[derive(Debug)]
+pub struct Audition {
+    tx: SyncSender<Vec<f32>>,
+    spent: Receiver<Vec<f32>>,
+}
+
+/// Render-thread end: mixes the current audition into rendered blocks
+#[derive(Debug)]
+pub struct AuditionMixer {
+    rx: Receiver<Vec<f32>>,
+    spent: SyncSender<Vec<f32>>,
+    playing: Vec<f32>,
+    pos: usize,
+}
+
+/// A connected UI / render-thread pair
+pub fn audition_channel() -> (Audition, AuditionMixer) {
+    let (tx, rx) = sync_channel(CHANNEL_CAPACITY);
+    let (spent_tx, spent_rx) = sync_channel(CHANNEL_CAPACITY + 1);
+    (
+        Audition {
+            tx,
+            spent: spent_rx,
+        },
+        AuditionMixer {
+            rx,
+            spent: spent_tx,
+            playing: Vec::new(),
+            pos: 0,
+        },
+    )
+}
+
+impl Audition {
+    /// Start playing `buffer` (stereo interleaved), replacing any audition still
+    /// playing. Returns false if the render thread is too far behind to take it.
+    pub fn play(&self, buffer: Vec<f32>) -> bool {
+        self.collect();
+        self.tx.try_send(buffer).is_ok()
+    }
+
+    /// Free the auditions the render thread has finished with
+    pub fn collect(&self) {
+        while self.spent.try_recv().is_ok() {}
+    }
+}
+
+impl AuditionMixer {
+    /// Add the next stretch of the current audition to `buffer`
+    pub fn mix_into(&mut self, buffer: &mut [f32]) {
+        if let Ok(next) = self.rx.try_recv() {
+            let finished = std::mem::replace(&mut self.playing, next);
+            self.pos = 0;
+            // The UI collects before every send, so this only fails once the
+            // editor is gone
+            let _ = self.spent.try_send(finished);
+        }
+        if self.pos >= self.playing.len() {
+            return;
+        }
+        let remaining = &self.playing[self.pos..];
+        let n = remaining.len().min(buffer.len());
+        for (out, sample) in buffer[..n].iter_mut().zip(remaining) {
+            *out += sample;
+        }
+        self.pos += n;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTEXT: &str = "tempo: 1.0\n~bass $ saw 55\n~lead $ sine 440\nout $ ~bass + ~lead";
+
+    #[test]
+    fn test_audition_program_plays_only_the_target() {
+        let program = audition_program(CONTEXT, "~lead $ sine 440").unwrap();
+        let outputs: Vec<&Statement> = program
+            .iter()
+            .filter(|s| matches!(s, Statement::Output(_)))
+            .collect();
+        assert_eq!(
+            outputs,
+            vec![&Statement::Output(Expr::BusRef("lead".into()))]
+        );
+        assert!(program.contains(&Statement::Tempo(1.0)));
+
+        // A bare expression works too; comments and settings don't
+        assert!(audition_program(CONTEXT, "s \"bd sn\" # gain 0.5").is_ok());
+        assert!(audition_program(CONTEXT, "-- a comment").is_err());
+        assert!(audition_program(CONTEXT, "tempo: 2.0").is_err());
+    }
+
+    #[test]
+    fn test_mixer_plays_audition_once_over_the_output() {
+        let (audition, mut mixer) = audition_channel();
+        assert!(audition.play(vec![0.5; 6]));
+
+        let mut block = vec![0.1f32; 4];
+        mixer.mix_into(&mut block);
+        assert_eq!(block, vec![0.6; 4]);
+
+        let mut block = vec![0.0f32; 4];
+        mixer.mix_into(&mut block);
+        assert_eq!(block, vec![0.5, 0.5, 0.0, 0.0]);
+        mixer.mix_into(&mut block);
+        assert_eq!(block, vec![0.5, 0.5, 0.0, 0.0]);
+
+        // A new audition replaces the finished one, which goes back to the UI
+        assert!(audition.play(vec![0.25; 2]));
+        mixer.mix_into(&mut block);
+        assert_eq!(block[0], 0.75);
+        audition.collect();
+    }
+}
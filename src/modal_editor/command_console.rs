@@ -4,6 +4,9 @@
 
 #![allow(clippy::single_char_add_str)]
 use super::completion::*;
+use super::render_queue::RenderLength;
+use super::snippets::SnippetLibrary;
+use super::tempo_sync::SyncSource;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
@@ -12,6 +15,49 @@ use ratatui::{
     Frame,
 };
 
+/// Command that needs the editor to act (the console itself has no buffer
+/// or audio access)
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConsoleAction {
+    /// `:render <length> <file.wav>` - render the buffer in the background
+    Render {
+        length: RenderLength,
+        path: std::path::PathBuf,
+    },
+    /// `/snippet <query>` - insert a snippet at the cursor
+    InsertSnippet { name: String, body: String },
+    /// `/snippet-save <name>` - save the current block as a snippet
+    SaveSnippet { name: String },
+    /// `:split [file]` - open a file (or a scratch buffer) in a second pane
+    Split { path: Option<std::path::PathBuf> },
+    /// `:unsplit` - close the pane without focus
+    Unsplit,
+    /// `:cue <cycle>` - jump live playback to a cycle
+    Cue { cycle: f64 },
+    /// `:stop` / `:start` - hold live playback where it is, or carry on
+    Transport { running: bool },
+    /// `:freeze ~bus <cycles>` - bounce a bus to audio in place
+    Freeze { bus: String, cycles: f64 },
+    /// `:unfreeze ~bus` - play the bus live again
+    Unfreeze { bus: String },
+    /// `:morph ~bus <cycles>` / `:unmorph ~bus` - crossfade the bus to its
+    /// new definition when re-evaluated, or swap it instantly again
+    Morph { bus: String, cycles: Option<f64> },
+    /// `:nudge ~bus +10ms` / `:nudge ~bus reset` - move a bus's events
+    /// while it plays (seconds; `None` goes back to no offset)
+    Nudge { bus: String, by: Option<f64> },
+    /// `:sync midi [device]` / `:sync tap` / `:sync off` - follow MIDI clock
+    /// or tapped beats for tempo, or the code again (`None`)
+    Sync { source: Option<SyncSource> },
+    /// `:save-session <file.phsn>` - suspend the live set to disk
+    SaveSession { path: std::path::PathBuf },
+    /// `:load-session <file.phsn>` - resume a saved live set
+    LoadSession { path: std::path::PathBuf },
+    /// `:quality [locked|auto]` - show or pin the adaptive quality level
+    /// (`None` just shows it)
+    Quality { locked: Option<bool> },
+}
+
 /// Command console state
 pub struct CommandConsole {
     /// Whether the console is visible
@@ -22,6 +68,8 @@ pub struct CommandConsole {
     cursor_pos: usize,
     /// Command results/output
     output: Vec<String>,
+    /// Snippet library, loaded from disk on first use
+    snippets: Option<SnippetLibrary>,
 }
 
 impl CommandConsole {
@@ -32,6 +80,7 @@ impl CommandConsole {
             input: String::new(),
             cursor_pos: 0,
             output: vec!["Command console - type /help for help".to_string()],
+            snippets: None,
         }
     }
 
@@ -45,6 +94,32 @@ impl CommandConsole {
         }
     }
 
+    /// Use `library` instead of loading `~/.phonon/snippets`
+    pub fn set_snippet_library(&mut self, library: SnippetLibrary) {
+        self.snippets = Some(library);
+    }
+
+    fn snippet_library(&mut self) -> &mut SnippetLibrary {
+        self.snippets.get_or_insert_with(SnippetLibrary::load)
+    }
+
+    /// Save `body` as snippet `name` and report the result in the output pane
+    pub fn save_snippet(&mut self, name: &str, body: &str) {
+        let result = self.snippet_library().save(name, body);
+        self.output.clear();
+        match result {
+            Ok(path) => self
+                .output
+                .push(format!("Saved snippet '{}' to {}", name, path.display())),
+            Err(e) => self.output.push(e),
+        }
+    }
+
+    /// Lines currently shown in the output pane
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+
     /// Check if console is visible
     pub fn is_visible(&self) -> bool {
         self.visible
@@ -90,13 +165,15 @@ impl CommandConsole {
     }
 
     /// Execute the current command
-    pub fn execute_command(&mut self) {
-        let command = self.input.trim();
+    /// Returns the action for the editor to perform, if any
+    pub fn execute_command(&mut self) -> Option<ConsoleAction> {
+        let command = self.input.trim().to_string();
         self.output.clear();
 
         if command.is_empty() {
-            return;
+            return None;
         }
+        let mut action = None;
 
         // Parse command
         let parts: Vec<&str> = command.split_whitespace().collect();
@@ -179,6 +256,266 @@ impl CommandConsole {
                 }
             }
 
+            ":doc" | "/doc" => match parts.as_slice() {
+                [_, name] => match FunctionDocs::get(name) {
+                    Some(docs) => self.output.extend(
+                        docs.format_lines(usize::MAX)
+                            .into_iter()
+                            .map(|line| line.text),
+                    ),
+                    None => {
+                        self.output.push(format!("No docs for: {}", name));
+                        self.output
+                            .push("Type /functions to see all functions".to_string());
+                    }
+                },
+                _ => self.output.push("Usage: :doc <function>".to_string()),
+            },
+
+            ":render" | "/render" => match parts.as_slice() {
+                [_, length, path] => match RenderLength::parse(length) {
+                    Ok(length) => {
+                        action = Some(ConsoleAction::Render {
+                            length,
+                            path: std::path::PathBuf::from(path),
+                        });
+                    }
+                    Err(e) => self.output.push(e),
+                },
+                _ => {
+                    self.output
+                        .push("Usage: :render <length> <file.wav>".to_string());
+                    self.output
+                        .push("  e.g. :render 32c out.wav   (or 10s for seconds)".to_string());
+                }
+            },
+
+            ":split" | "/split" => match parts.as_slice() {
+                [_] => action = Some(ConsoleAction::Split { path: None }),
+                [_, path] => {
+                    action = Some(ConsoleAction::Split {
+                        path: Some(std::path::PathBuf::from(path)),
+                    });
+                }
+                _ => self.output.push("Usage: :split [file.ph]".to_string()),
+            },
+
+            ":unsplit" | "/unsplit" => action = Some(ConsoleAction::Unsplit),
+
+            ":cue" | "/cue" => match parts.as_slice() {
+                [_, cycle] => match cycle.parse::<f64>() {
+                    Ok(cycle) if cycle.is_finite() && cycle >= 0.0 => {
+                        action = Some(ConsoleAction::Cue { cycle });
+                    }
+                    _ => self
+                        .output
+                        .push(format!("Invalid cycle: {} (expected e.g. 32)", cycle)),
+                },
+                _ => self.output.push("Usage: :cue <cycle>".to_string()),
+            },
+
+            ":stop" | "/stop" => action = Some(ConsoleAction::Transport { running: false }),
+            ":start" | "/start" => action = Some(ConsoleAction::Transport { running: true }),
+
+            ":freeze" | "/freeze" => match parts.as_slice() {
+                [_, bus, cycles] if bus.starts_with('~') => {
+                    match cycles.trim_end_matches('c').parse::<f64>() {
+                        Ok(cycles) if cycles.is_finite() && cycles > 0.0 => {
+                            action = Some(ConsoleAction::Freeze {
+                                bus: bus[1..].to_string(),
+                                cycles,
+                            });
+                        }
+                        _ => self
+                            .output
+                            .push(format!("Invalid length: {} (expected e.g. 8c)", cycles)),
+                    }
+                }
+                _ => self.output.push("Usage: :freeze ~bus <cycles>".to_string()),
+            },
+
+            ":unfreeze" | "/unfreeze" => match parts.as_slice() {
+                [_, bus] if bus.starts_with('~') => {
+                    action = Some(ConsoleAction::Unfreeze {
+                        bus: bus[1..].to_string(),
+                    });
+                }
+                _ => self.output.push("Usage: :unfreeze ~bus".to_string()),
+            },
+
+            ":morph" | "/morph" => match parts.as_slice() {
+                [_, bus, cycles] if bus.starts_with('~') => {
+                    match cycles.trim_end_matches('c').parse::<f64>() {
+                        Ok(cycles) if cycles.is_finite() && cycles > 0.0 => {
+                            action = Some(ConsoleAction::Morph {
+                                bus: bus[1..].to_string(),
+                                cycles: Some(cycles),
+                            });
+                        }
+                        _ => self
+                            .output
+                            .push(format!("Invalid length: {} (expected e.g. 4c)", cycles)),
+                    }
+                }
+                _ => self.output.push("Usage: :morph ~bus <cycles>".to_string()),
+            },
+
+            ":unmorph" | "/unmorph" => match parts.as_slice() {
+                [_, bus] if bus.starts_with('~') => {
+                    action = Some(ConsoleAction::Morph {
+                        bus: bus[1..].to_string(),
+                        cycles: None,
+                    });
+                }
+                _ => self.output.push("Usage: :unmorph ~bus".to_string()),
+            },
+
+            ":nudge" | "/nudge" => match parts.as_slice() {
+                [_, bus, "reset"] if bus.starts_with('~') => {
+                    action = Some(ConsoleAction::Nudge {
+                        bus: bus[1..].to_string(),
+                        by: None,
+                    });
+                }
+                [_, bus, amount] if bus.starts_with('~') => match parse_nudge(amount) {
+                    Some(seconds) => {
+                        action = Some(ConsoleAction::Nudge {
+                            bus: bus[1..].to_string(),
+                            by: Some(seconds),
+                        });
+                    }
+                    None => self
+                        .output
+                        .push(format!("Invalid offset: {} (expected e.g. +10ms)", amount)),
+                },
+                _ => self
+                    .output
+                    .push("Usage: :nudge ~bus <+/-ms> (or reset)".to_string()),
+            },
+
+            ":sync" | "/sync" => match parts.as_slice() {
+                [_, "midi", device @ ..] => {
+                    action = Some(ConsoleAction::Sync {
+                        source: Some(SyncSource::Midi(device.join(" "))),
+                    });
+                }
+                [_, "tap"] => {
+                    action = Some(ConsoleAction::Sync {
+                        source: Some(SyncSource::Tap),
+                    });
+                }
+                [_, "off"] => action = Some(ConsoleAction::Sync { source: None }),
+                _ => self
+                    .output
+                    .push("Usage: :sync midi [device] | tap | off".to_string()),
+            },
+
+            ":save-session" | "/save-session" => match parts.as_slice() {
+                [_, path] => {
+                    action = Some(ConsoleAction::SaveSession {
+                        path: std::path::PathBuf::from(path),
+                    });
+                }
+                _ => self
+                    .output
+                    .push("Usage: :save-session <file.phsn>".to_string()),
+            },
+
+            ":load-session" | "/load-session" => match parts.as_slice() {
+                [_, path] => {
+                    action = Some(ConsoleAction::LoadSession {
+                        path: std::path::PathBuf::from(path),
+                    });
+                }
+                _ => self
+                    .output
+                    .push("Usage: :load-session <file.phsn>".to_string()),
+            },
+
+            ":quality" | "/quality" => match parts.as_slice() {
+                [_] => action = Some(ConsoleAction::Quality { locked: None }),
+                [_, "locked"] => action = Some(ConsoleAction::Quality { locked: Some(true) }),
+                [_, "auto"] => {
+                    action = Some(ConsoleAction::Quality {
+                        locked: Some(false),
+                    })
+                }
+                _ => self
+                    .output
+                    .push("Usage: :quality [locked|auto]".to_string()),
+            },
+
+            ":samples" | "/samples" => match parts.as_slice() {
+                [_] => self.show_sample_memory(),
+                [_, "limit", mb] => match mb.parse::<usize>() {
+                    Ok(mb) if mb > 0 => {
+                        crate::sample_loader::set_sample_memory_limit(mb * 1024 * 1024);
+                        self.show_sample_memory();
+                    }
+                    _ => self.output.push(format!(
+                        "Invalid limit: {} (expected megabytes, e.g. 256)",
+                        mb
+                    )),
+                },
+                _ => self
+                    .output
+                    .push("Usage: :samples [limit <megabytes>]".to_string()),
+            },
+
+            "/snippets" => {
+                let query = parts[1..].join(" ");
+                let lines: Vec<String> = self
+                    .snippet_library()
+                    .search(&query)
+                    .iter()
+                    .map(|s| format!("  {} - {}", s.name, s.description))
+                    .collect();
+                if lines.is_empty() {
+                    self.output.push(format!("No snippets matching: {}", query));
+                } else {
+                    self.output
+                        .push("Snippets (/snippet <name> to insert):".to_string());
+                    self.output.extend(lines);
+                }
+                let dir = self
+                    .snippet_library()
+                    .dir()
+                    .map(|d| d.display().to_string());
+                if let Some(dir) = dir {
+                    self.output.push(format!("Library: {}", dir));
+                }
+            }
+
+            "/snippet" => {
+                if parts.len() > 1 {
+                    let query = parts[1..].join(" ");
+                    match self.snippet_library().find(&query) {
+                        Some(snippet) => {
+                            action = Some(ConsoleAction::InsertSnippet {
+                                name: snippet.name.clone(),
+                                body: snippet.body.clone(),
+                            });
+                        }
+                        None => {
+                            self.output.push(format!("No snippet matching: {}", query));
+                            self.output.push("Type /snippets to browse".to_string());
+                        }
+                    }
+                } else {
+                    self.output
+                        .push("Usage: /snippet <name or query>".to_string());
+                }
+            }
+
+            "/snippet-save" => match parts.as_slice() {
+                [_, name] => {
+                    action = Some(ConsoleAction::SaveSnippet {
+                        name: name.to_string(),
+                    });
+                }
+                _ => self.output.push("Usage: /snippet-save <name>".to_string()),
+            },
+
             "/categories" => {
                 self.output.push("Function categories:".to_string());
                 self.output
@@ -201,13 +538,52 @@ impl CommandConsole {
                 self.output.push("  /functions [category]".to_string());
                 self.output.push("  /search <query>".to_string());
                 self.output.push("  /params <function>".to_string());
+                self.output.push("  :doc <function>".to_string());
                 self.output.push("  /categories".to_string());
+                self.output.push("  /snippets [query]".to_string());
+                self.output.push("  /snippet <name>".to_string());
+                self.output.push("  /snippet-save <name>".to_string());
+                self.output
+                    .push("  :render <length> <file.wav>".to_string());
+                self.output.push("  :split [file]".to_string());
+                self.output.push("  :unsplit".to_string());
+                self.output.push("  :cue <cycle>".to_string());
+                self.output.push("  :stop / :start".to_string());
+                self.output.push("  :quality [locked|auto]".to_string());
             }
         }
 
         // Clear input after execution
         self.input.clear();
         self.cursor_pos = 0;
+        action
+    }
+
+    /// Resident sample memory by folder, against the cache limit
+    fn show_sample_memory(&mut self) {
+        use crate::sample_loader::{
+            format_bytes, held_memory, resident_memory, sample_memory_limit,
+        };
+
+        let folders = resident_memory();
+        let total: usize = folders.iter().map(|f| f.bytes).sum();
+        self.output.push(format!(
+            "Resident samples: {} of {} limit",
+            format_bytes(total),
+            format_bytes(sample_memory_limit())
+        ));
+        self.output.push(format!(
+            "  {} in use by the playing program (kept past the limit until it is replaced)",
+            format_bytes(held_memory())
+        ));
+        for folder in folders {
+            self.output.push(format!(
+                "  {:<20} {:>4} files {:>10}",
+                folder.folder,
+                folder.files,
+                format_bytes(folder.bytes)
+            ));
+        }
     }
 
     /// Show general help
@@ -224,14 +600,73 @@ impl CommandConsole {
             .push("  /search <query>      - Search functions by name/description".to_string());
         self.output
             .push("  /params <function>   - Show parameters for function".to_string());
+        self.output
+            .push("  :doc lpf             - Parameters, ranges and an example".to_string());
         self.output
             .push("  /categories          - List all categories".to_string());
+        self.output
+            .push("  /snippets [query]    - Browse snippets (fuzzy search)".to_string());
+        self.output
+            .push("  /snippet <name>      - Insert snippet at cursor".to_string());
+        self.output
+            .push("  /snippet-save <name> - Save current block as a snippet".to_string());
+        self.output
+            .push("  :render 32c out.wav  - Render buffer to WAV in the background".to_string());
+        self.output
+            .push("  :split synths.ph     - Edit a second file side by side".to_string());
+        self.output
+            .push("  :unsplit             - Close the other pane".to_string());
+        self.output
+            .push("  :cue 32              - Jump playback to cycle 32".to_string());
+        self.output
+            .push("  :stop / :start       - Pause playback and resume where it was".to_string());
+        self.output
+            .push("  :freeze ~pads 8c     - Bounce a bus to audio (:unfreeze ~pads)".to_string());
+        self.output
+            .push("  :morph ~pad 4c       - Crossfade ~pad on re-eval (:unmorph ~pad)".to_string());
+        self.output
+            .push("  :nudge ~drums +10ms  - Move a playing bus later (-ms: earlier)".to_string());
+        self.output
+            .push("  :sync midi [device]  - Follow MIDI clock (tap: Alt+T taps, off)".to_string());
+        self.output
+            .push("  :save-session s.phsn - Suspend the set to disk (:load-session)".to_string());
+        self.output
+            .push("  :samples [limit MB]  - Sample memory by folder (and set the cap)".to_string());
+        self.output
+            .push("  :quality [locked]    - CPU quality level (locked: never degrade)".to_string());
         self.output.push("".to_string());
         self.output.push("Examples:".to_string());
         self.output.push("  /help lpf".to_string());
         self.output.push("  /functions Filters".to_string());
         self.output.push("  /search reverb".to_string());
         self.output.push("  /params adsr".to_string());
+        self.output.push("  /snippet acid".to_string());
+        self.output.push("".to_string());
+        self.output.push("Editing:".to_string());
+        self.output
+            .push("  Shift+arrows/Home/End - Select".to_string());
+        self.output
+            .push("  C-w / Alt+W  - Cut / copy selection (C-y pastes)".to_string());
+        self.output
+            .push("  Alt+;        - Toggle comments on selected lines".to_string());
+        self.output
+            .push("  Tab / S-Tab  - Indent / dedent selection (also Alt+] / Alt+[)".to_string());
+        self.output
+            .push("  Alt+H        - Toggle inline help for the call under the cursor".to_string());
+        self.output
+            .push("  Alt+E        - Toggle the event log (events as they trigger)".to_string());
+        self.output
+            .push("  Alt+Enter    - Audition the line or selection for one cycle".to_string());
+        self.output
+            .push("  Alt+Space    - Stop / start playback (Space in vim normal mode)".to_string());
+        self.output
+            .push("  Alt+T        - Tap tempo: tap the beat, playback follows".to_string());
+        self.output
+            .push("  Alt+G        - Edit the line's pattern string as a step grid".to_string());
+        self.output
+            .push("  Alt+Up/Down  - Scrub the number under the cursor (C- fine, S- coarse)".to_string());
+        self.output
+            .push("  Alt+Left/Right - Nudge the line's bus 5ms earlier / later".to_string());
         self.output.push("".to_string());
         self.output.push("MIDI Input:".to_string());
         self.output
@@ -256,7 +691,7 @@ impl CommandConsole {
         self.output
             .push("  Alt+P  - Open plugin browser".to_string());
         self.output
-            .push("  Alt+G  - Open GUI for all loaded plugins".to_string());
+            .push("  Alt+Shift+G - Open GUI for all loaded plugins".to_string());
         self.output.push("".to_string());
         self.output.push("Press Esc or Alt+/ to close".to_string());
     }
@@ -403,3 +838,17 @@ impl CommandConsole {
         f.render_widget(input_paragraph, chunks[1]);
     }
 }
+
+/// A `:nudge` offset in seconds, from `+10ms`, `-2.5ms` or `0.01s` (a bare
+/// number is milliseconds)
+fn parse_nudge(amount: &str) -> Option<f64> {
+    let amount = amount.strip_prefix('+').unwrap_or(amount);
+    let seconds = if let Some(ms) = amount.strip_suffix("ms") {
+        ms.parse::<f64>().ok()? / 1000.0
+    } else if let Some(s) = amount.strip_suffix('s') {
+        s.parse::<f64>().ok()?
+    } else {
+        amount.parse::<f64>().ok()? / 1000.0
+    };
+    seconds.is_finite().then_some(seconds)
+}
@@ -3,12 +3,33 @@
 //! Provides full documentation for functions including:
 //! - Short description
 //! - Long description (if available)
-//! - Parameters with types and defaults
+//! - Parameters with types, defaults and ranges
 //! - Example code
 //! - Category
+//!
+//! `phonon doc <name>` and the editor's `:doc <name>` print these.
 
 use super::function_metadata::FUNCTION_METADATA;
-use super::generated_metadata::get_all_functions;
+use super::generated_metadata::{get_all_functions, GeneratedNodeMetadata, GeneratedParamMetadata};
+
+/// DSL names documented under a node file's name in the generated metadata
+/// (the node structs are named after what they do, not what the DSL calls them)
+const NODE_NAMES: &[(&str, &str)] = &[
+    ("lpf", "lowpassfilter"),
+    ("hpf", "highpassfilter"),
+    ("bpf", "bandpassfilter"),
+    ("notch", "notchfilter"),
+    ("moog", "moogladder"),
+    ("ad", "adenvelope"),
+    ("asr", "asrenvelope"),
+    ("distort", "distortion"),
+    ("plate", "dattorroreverb"),
+    ("ring_mod", "ringmod"),
+    ("pitch_shift", "pitchshifter"),
+    ("sample_hold", "sampleandhold"),
+    ("pink_noise", "pinknoise"),
+    ("brown_noise", "brownnoise"),
+];
 
 /// Full documentation for a function
 #[derive(Debug, Clone)]
@@ -36,6 +57,8 @@ pub struct ParamDoc {
     pub default: Option<String>,
     /// Description of the parameter
     pub description: String,
+    /// Useful values, as "20 to 20000" (if documented)
+    pub range: Option<String>,
 }
 
 impl FunctionDocs {
@@ -45,17 +68,24 @@ impl FunctionDocs {
     pub fn get(function_name: &str) -> Option<Self> {
         let curated = FUNCTION_METADATA.get(function_name);
         let generated = get_all_functions();
-        let gen = generated.get(function_name);
+        let node_name = NODE_NAMES
+            .iter()
+            .find(|(name, _)| *name == function_name)
+            .map_or(function_name, |(_, node)| *node);
+        let gen = generated.get(node_name);
 
-        // Need at least one source
+        // Need at least one source; user nodes describe themselves
         if curated.is_none() && gen.is_none() {
-            return None;
+            return crate::node_factory::lookup(function_name)
+                .map(|node| Self::from_spec(&node.spec));
         }
 
-        // Get description (prefer curated)
+        // Get description (prefer curated, unless it's a stub)
         let short_description = curated
-            .map(|m| m.description.to_string())
+            .and_then(|m| written(m.description))
+            .map(str::to_string)
             .or_else(|| gen.map(|g| g.description.clone()))
+            .or_else(|| curated.map(|m| m.description.to_string()))
             .unwrap_or_default();
 
         // Get category (prefer curated)
@@ -64,20 +94,33 @@ impl FunctionDocs {
             .or_else(|| gen.map(|g| g.category.clone()))
             .unwrap_or_else(|| "Unknown".to_string());
 
-        // Get example (only from generated for now)
-        let example = gen
-            .map(|g| g.example.clone())
-            .filter(|e| !e.is_empty());
+        // Get example (prefer curated)
+        let example = curated
+            .map(|m| m.example.to_string())
+            .filter(|e| !e.is_empty())
+            .or_else(|| gen.map(|g| g.example.clone()).filter(|e| !e.is_empty()));
 
         // Get parameters (prefer curated, has more detail)
         let params = if let Some(m) = curated {
             m.params
                 .iter()
-                .map(|p| ParamDoc {
-                    name: p.name.to_string(),
-                    param_type: p.param_type.to_string(),
-                    default: p.default.map(|d| d.to_string()),
-                    description: p.description.to_string(),
+                .map(|p| {
+                    let node_param = gen.and_then(|g| node_param(g, p.name));
+                    let description = written(p.description)
+                        .map(str::to_string)
+                        .or_else(|| node_param.map(|n| n.description.clone()))
+                        .unwrap_or_else(|| p.description.to_string());
+                    // A curated description that quotes numbers has its own range
+                    let range = node_param
+                        .and_then(|n| n.range.clone())
+                        .filter(|_| !written(p.description).is_some_and(has_digits));
+                    ParamDoc {
+                        name: p.name.to_string(),
+                        param_type: p.param_type.to_string(),
+                        default: p.default.map(|d| d.to_string()),
+                        description,
+                        range,
+                    }
                 })
                 .collect()
         } else if let Some(g) = gen {
@@ -88,6 +131,7 @@ impl FunctionDocs {
                     param_type: p.param_type.clone(),
                     default: p.default.clone(),
                     description: p.description.clone(),
+                    range: p.range.clone(),
                 })
                 .collect()
         } else {
@@ -103,6 +147,27 @@ impl FunctionDocs {
         })
     }
 
+    /// Documentation of a node registered through node_factory
+    fn from_spec(spec: &crate::node_factory::NodeSpec) -> Self {
+        FunctionDocs {
+            name: spec.name.clone(),
+            short_description: spec.description.clone(),
+            category: "User Nodes".to_string(),
+            params: spec
+                .params
+                .iter()
+                .map(|p| ParamDoc {
+                    name: p.name.clone(),
+                    param_type: "float".to_string(),
+                    default: Some(p.default.to_string()),
+                    description: p.description.clone(),
+                    range: None,
+                })
+                .collect(),
+            example: None,
+        }
+    }
+
     /// Format documentation as lines for display
     ///
     /// Returns a vector of (text, is_header) pairs for styling
@@ -126,11 +191,17 @@ impl FunctionDocs {
                     .as_ref()
                     .map(|d| format!(" (default: {})", d))
                     .unwrap_or_default();
+                let range_str = param
+                    .range
+                    .as_ref()
+                    .filter(|_| !has_digits(&param.description))
+                    .map(|r| format!(" [{}]", r))
+                    .unwrap_or_default();
 
-                // Format: "  name    type    description (default: value)"
+                // Format: "  name    type    description [range] (default: value)"
                 let param_line = format!(
-                    "  {:12} {:8} {}{}",
-                    param.name, param.param_type, param.description, default_str
+                    "  {:12} {:8} {}{}{}",
+                    param.name, param.param_type, param.description, range_str, default_str
                 );
 
                 // Truncate if too long
@@ -149,13 +220,37 @@ impl FunctionDocs {
         // Example section
         if let Some(example) = &self.example {
             lines.push(DocLine::subheader("Example:".to_string()));
-            lines.push(DocLine::example(format!("  {}", example)));
+            for line in example.lines() {
+                lines.push(DocLine::example(format!("  {}", line)));
+            }
         }
 
         lines
     }
 }
 
+/// A node's parameter by its DSL name (`cutoff` is the node's `cutoff_input`)
+fn node_param<'a>(
+    node: &'a GeneratedNodeMetadata,
+    name: &str,
+) -> Option<&'a GeneratedParamMetadata> {
+    let input_name = format!("{}_input", name);
+    node.params
+        .iter()
+        .find(|p| p.name == name || p.name == input_name)
+        .filter(|p| !p.description.is_empty())
+}
+
+/// `text`, unless it's a stub's "TODO: ..." placeholder
+fn written(text: &str) -> Option<&str> {
+    (!text.is_empty() && !text.starts_with("TODO")).then_some(text)
+}
+
+/// Whether a description already spells out its numbers (and so its range)
+fn has_digits(text: &str) -> bool {
+    text.chars().any(|c| c.is_ascii_digit())
+}
+
 /// A line of documentation with styling information
 #[derive(Debug, Clone)]
 pub struct DocLine {
@@ -169,6 +264,8 @@ pub enum DocLineStyle {
     Header,
     Subheader,
     Param,
+    /// The parameter under the cursor (inline help)
+    ActiveParam,
     Example,
     Empty,
 }
@@ -195,6 +292,13 @@ impl DocLine {
         }
     }
 
+    pub fn active_param(text: String) -> Self {
+        Self {
+            text,
+            style: DocLineStyle::ActiveParam,
+        }
+    }
+
     pub fn example(text: String) -> Self {
         Self {
             text,
@@ -256,6 +360,27 @@ mod tests {
         assert!(lines[0].text.contains("lpf"));
     }
 
+    #[test]
+    fn test_docs_take_ranges_and_examples_from_the_node_file() {
+        let docs = FunctionDocs::get("lpf").unwrap();
+        let cutoff = docs.params.iter().find(|p| p.name == "cutoff").unwrap();
+        assert_eq!(cutoff.range.as_deref(), Some("20 to 20000"));
+        assert!(docs.example.unwrap().contains("lpf"));
+
+        // A stub in the curated table, documented in its node file
+        let docs = FunctionDocs::get("rhpf").unwrap();
+        assert!(!docs.short_description.starts_with("TODO"));
+        let cutoff = docs.params.iter().find(|p| p.name == "cutoff").unwrap();
+        assert_eq!(cutoff.range.as_deref(), Some("20 to 20000"));
+        assert!(cutoff.description.contains("Hz"));
+        let lines = docs.format_lines(200);
+        let example_lines = lines
+            .iter()
+            .filter(|l| l.style == DocLineStyle::Example)
+            .count();
+        assert_eq!(example_lines, 2);
+    }
+
     #[test]
     fn test_param_docs() {
         let docs = FunctionDocs::get("lpf").unwrap();
@@ -313,7 +313,72 @@ lazy_static::lazy_static! {
         // Effects
         m.insert("reverb", FunctionMetadata {
             name: "reverb",
-            description: "Reverb effect - adds space and ambience",
+            description: "Dattorro reverb with presets - adds space and ambience",
+            params: vec![
+                ParamMetadata {
+                    name: "room_size",
+                    param_type: "0-1",
+                    optional: true,
+                    default: Some("preset"),
+                    description: "Room size (0-1)",
+                },
+                ParamMetadata {
+                    name: "damping",
+                    param_type: "0-1",
+                    optional: true,
+                    default: Some("preset"),
+                    description: "High frequency damping (0-1)",
+                },
+                ParamMetadata {
+                    name: "mix",
+                    param_type: "0-1",
+                    optional: true,
+                    default: Some("preset"),
+                    description: "Wet level (0-1)",
+                },
+                ParamMetadata {
+                    name: "preset",
+                    param_type: "name",
+                    optional: true,
+                    default: Some("hall"),
+                    description: "hall, plate, room or shimmer",
+                },
+                ParamMetadata {
+                    name: "predelay",
+                    param_type: "seconds",
+                    optional: true,
+                    default: Some("preset"),
+                    description: "Pre-delay before the tail (up to 500ms)",
+                },
+                ParamMetadata {
+                    name: "mod",
+                    param_type: "0-1",
+                    optional: true,
+                    default: Some("preset"),
+                    description: "Tail modulation depth",
+                },
+                ParamMetadata {
+                    name: "diffusion",
+                    param_type: "0-1",
+                    optional: true,
+                    default: Some("preset"),
+                    description: "Echo density",
+                },
+                ParamMetadata {
+                    name: "shimmer",
+                    param_type: "0-1",
+                    optional: true,
+                    default: Some("0"),
+                    description: "Octave-up pitch shift in the feedback",
+                },
+            ],
+            example: "~wet: ~dry # reverb :preset plate :predelay 20ms",
+            category: "Effects",
+        });
+
+        m.insert("freeverb", FunctionMetadata {
+            name: "freeverb",
+            description: "Freeverb-style Schroeder reverb (the old reverb sound)",
             params: vec![
                 ParamMetadata {
                     name: "room_size",
@@ -337,7 +402,7 @@ lazy_static::lazy_static! {
                     description: "Wet/dry mix (0-1)",
                 },
             ],
-            example: "~wet: ~dry # reverb 0.8 0.5 :mix 0.4",
+            example: "~wet: ~dry # freeverb 0.8 0.5 :mix 0.4",
             category: "Effects",
         });
 
@@ -1795,6 +1860,45 @@ lazy_static::lazy_static! {
             category: "Transforms",
         });
 
+        m.insert("take", FunctionMetadata {
+            name: "take",
+            description: "Play for n cycles from evaluation, then go silent",
+            params: vec![
+                ParamMetadata {
+                    name: "cycles",
+                    param_type: "float",
+                    optional: false,
+                    default: None,
+                    description: "Cycles to play",
+                },
+            ],
+            example: "~intro: s \"hh*8\" $ take 8",
+            category: "Transforms",
+        });
+
+        m.insert("after", FunctionMetadata {
+            name: "after",
+            description: "Wait n cycles from evaluation, then play (or apply a transform)",
+            params: vec![
+                ParamMetadata {
+                    name: "cycles",
+                    param_type: "float",
+                    optional: false,
+                    default: None,
+                    description: "Cycles to wait",
+                },
+                ParamMetadata {
+                    name: "transform",
+                    param_type: "function",
+                    optional: true,
+                    default: None,
+                    description: "Transform to apply after the wait, instead of silence before it",
+                },
+            ],
+            example: "~kick: s \"bd*4\" $ after 8",
+            category: "Transforms",
+        });
+
         // Pattern Manipulation Transforms
         m.insert("mask", FunctionMetadata {
             name: "mask",
@@ -2569,6 +2673,36 @@ lazy_static::lazy_static! {
             category: "Utilities",
         });
 
+        m.insert("morph", FunctionMetadata {
+            name: "morph",
+            description: "Crossfade between two versions of a bus, the old one carrying on",
+            params: vec![
+                ParamMetadata {
+                    name: "from",
+                    param_type: "signal",
+                    optional: false,
+                    default: None,
+                    description: "Outgoing version (mix = 0)",
+                },
+                ParamMetadata {
+                    name: "to",
+                    param_type: "signal",
+                    optional: false,
+                    default: None,
+                    description: "Incoming version (mix = 1)",
+                },
+                ParamMetadata {
+                    name: "mix",
+                    param_type: "0-1",
+                    optional: false,
+                    default: None,
+                    description: "Crossfade position, e.g. a one-shot lfo ramp",
+                },
+            ],
+            example: "~pad $ morph (sine 220) (saw 110) (lfo 4c 0 1 :shape saw :oneshot true)",
+            category: "Utilities",
+        });
+
         m.insert("xline", FunctionMetadata {
             name: "xline",
             description: "Exponential line generator - smooth exponential ramp",
@@ -15,6 +15,7 @@ pub struct GeneratedParamMetadata {
     pub param_type: String,
     pub default: Option<String>,
     pub description: String,
+    pub range: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -30,912 +31,939 @@ pub struct GeneratedNodeMetadata {
 pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
     let mut map = HashMap::new();
 
-    // vibrato (Effects)
-    map.insert("vibrato".to_string(), GeneratedNodeMetadata {
-        name: "vibrato".to_string(),
-        description: "Vibrato effect with pattern-controlled rate and depth".to_string(),
+    // absolute (Effects)
+    map.insert("absolute".to_string(), GeneratedNodeMetadata {
+        name: "absolute".to_string(),
+        description: "Absolute value node: out = |input|".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~rectified: ~signal # absolute".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Input signal to rectify".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // adenvelope (Effects)
+    map.insert("adenvelope".to_string(), GeneratedNodeMetadata {
+        name: "adenvelope".to_string(),
+        description: "AD Envelope Generator Node".to_string(),
+        category: "Effects".to_string(),
+        example: "~trigger: \"x ~ x ~\"
+~envelope: ~trigger # ad_envelope 0.001 0.2
+~sound: sine 440 * ~envelope".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "rate_input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Trigger signal (rising edge triggers envelope)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "depth_input".to_string(),
+                name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "max_depth".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Attack time in seconds".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
+                name: "decay_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Decay time in seconds".to_string(),
+                range: None,
             },
         ],
     });
 
-    // rectifier (Effects)
-    map.insert("rectifier".to_string(), GeneratedNodeMetadata {
-        name: "rectifier".to_string(),
-        description: "Rectifier node: performs full-wave or half-wave rectification".to_string(),
+    // addition (Effects)
+    map.insert("addition".to_string(), GeneratedNodeMetadata {
+        name: "addition".to_string(),
+        description: "Addition node: out = a + b".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~sine: sine 440
+~saw: saw 220
+~combined: ~sine # addition ~saw".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First input signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "mode".to_string(),
-                param_type: "RectifierMode".to_string(),
+                name: "input_b".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second input signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // moogladder (Effects)
-    map.insert("moogladder".to_string(), GeneratedNodeMetadata {
-        name: "moogladder".to_string(),
-        description: "Moog Ladder filter node with pattern-controlled cutoff and resonance".to_string(),
+    // additive (Effects)
+    map.insert("additive".to_string(), GeneratedNodeMetadata {
+        name: "additive".to_string(),
+        description: "Additive synthesis node with pattern-controlled parameters".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~freq: sine 0.25 * 1000 + 110
+~additive: ~freq # additive 8 [1.0, 0.5, 0.33, 0.25, 0.2, 0.17, 0.14, 0.125]".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "frequency_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Fundamental frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "cutoff".to_string(),
+                name: "num_harmonics_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Number of harmonics to use (1-32)".to_string(),
+                range: Some("1 to 32".to_string()),
             },
             GeneratedParamMetadata {
-                name: "resonance".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "harmonic_weights".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Amplitude of each harmonic (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
-        ],
-    });
-
-    // impulse (Effects)
-    map.insert("impulse".to_string(), GeneratedNodeMetadata {
-        name: "impulse".to_string(),
-        description: "Impulse generator node with pattern-controlled frequency".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "frequency_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                name: "harmonic_detune".to_string(),
+                param_type: "Arc".to_string(),
+                default: Some("0.0".to_string()),
+                description: "Detune each harmonic in cents (default: 0.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // tremolo (Effects)
-    map.insert("tremolo".to_string(), GeneratedNodeMetadata {
-        name: "tremolo".to_string(),
-        description: "Tremolo effect with pattern-controlled rate and depth".to_string(),
+    // adsr (Effects)
+    map.insert("adsr".to_string(), GeneratedNodeMetadata {
+        name: "adsr".to_string(),
+        description: "ADSR Envelope Generator Node".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~gate: \"x ~ x ~\"
+~env: ~gate # adsr 0.01 0.1 0.7 0.2
+~synth: sine 440 * ~env".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "gate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Gate signal (rising edge = note on, falling edge = note off)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "rate_input".to_string(),
+                name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Attack time in seconds".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "depth_input".to_string(),
+                name: "decay_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Decay time in seconds".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // stereowidener (Effects)
-    map.insert("stereowidener".to_string(), GeneratedNodeMetadata {
-        name: "stereowidener".to_string(),
-        description: "Stereo widener node with pattern-controlled width".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "sustain_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sustain level (0.0 to 1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "width_input".to_string(),
+                name: "release_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Release time in seconds".to_string(),
+                range: None,
             },
         ],
     });
 
-    // crossoverlow (Effects)
-    map.insert("crossoverlow".to_string(), GeneratedNodeMetadata {
-        name: "crossoverlow".to_string(),
-        description: "Low band output of a 3-band Linkwitz-Riley crossover".to_string(),
+    // allpassfilter (Effects)
+    map.insert("allpassfilter".to_string(), GeneratedNodeMetadata {
+        name: "allpassfilter".to_string(),
+        description: "All-pass filter node with pattern-controlled frequency and Q".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~filtered: ~signal # allpass_filter 1000 0.707".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "low_freq_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Signal to process".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "high_freq_input".to_string(),
+                name: "freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Center frequency in Hz".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // brownnoise (Effects)
-    map.insert("brownnoise".to_string(), GeneratedNodeMetadata {
-        name: "brownnoise".to_string(),
-        description: "Brown noise node: generates random walk values scaled by amplitude".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "amplitude_input".to_string(),
+                name: "q_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.707".to_string()),
+                description: "Q/resonance factor (default: 0.707)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // or (Effects)
-    map.insert("or".to_string(), GeneratedNodeMetadata {
-        name: "or".to_string(),
-        description: "Logical OR node: out = (a > threshold || b > threshold) ? 1.0 : 0.0".to_string(),
+    // and (Effects)
+    map.insert("and".to_string(), GeneratedNodeMetadata {
+        name: "and".to_string(),
+        description: "Logical AND node: out = (a > threshold && b > threshold) ? 1.0 : 0.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~check_low: ~signal # gt 0.3
+~check_high: ~signal # lt 0.7
+~in_range: ~check_low # and ~check_high".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First input signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second input signal (default threshold: 0.5)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // sidechaincompressor (Effects)
-    map.insert("sidechaincompressor".to_string(), GeneratedNodeMetadata {
-        name: "sidechaincompressor".to_string(),
-        description: "Sidechain compressor node: compression controlled by external signal".to_string(),
+    // arenvelope (Effects)
+    map.insert("arenvelope".to_string(), GeneratedNodeMetadata {
+        name: "arenvelope".to_string(),
+        description: "AR Envelope Generator Node".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~gate: \"x ~ x ~\"
+~envelope: ~gate # ar_envelope 0.01 0.2
+~synth: sine 440 * ~envelope".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "main_input".to_string(),
+                name: "gate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Gate signal (rising/falling edge controls envelope)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sidechain_input".to_string(),
+                name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Attack time in seconds".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "threshold_input".to_string(),
+                name: "release_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Release time in seconds".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // asrenvelope (Effects)
+    map.insert("asrenvelope".to_string(), GeneratedNodeMetadata {
+        name: "asrenvelope".to_string(),
+        description: "ASR Envelope Generator Node".to_string(),
+        category: "Effects".to_string(),
+        example: "~gate: \"x ~ x ~\"
+~envelope: ~gate # asr_envelope 0.01 0.6 0.2
+~synth: sine 440 * ~envelope".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "ratio_input".to_string(),
+                name: "gate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Gate signal (rising/falling edge controls envelope)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Attack time in seconds".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "sustain_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Sustain level (0.0 to 1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "release_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Release time in seconds".to_string(),
+                range: None,
             },
         ],
     });
 
-    // polybleposc (Effects)
-    map.insert("polybleposc".to_string(), GeneratedNodeMetadata {
-        name: "polybleposc".to_string(),
-        description: "Anti-aliased oscillator using PolyBLEP technique".to_string(),
+    // autopan (Effects)
+    map.insert("autopan".to_string(), GeneratedNodeMetadata {
+        name: "autopan".to_string(),
+        description: "Auto-pan node with pattern-controlled rate, depth, and waveform selection".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~panned: ~signal # auto_pan 0.5 1.0 sine".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "frequency".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to pan".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "waveform".to_string(),
-                param_type: "PolyBLEPWaveform".to_string(),
+                name: "rate_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "LFO rate in Hz (0.01-20 typical)".to_string(),
+                range: Some("0.01 to 20".to_string()),
             },
-        ],
-    });
-
-    // lessthan (Effects)
-    map.insert("lessthan".to_string(), GeneratedNodeMetadata {
-        name: "lessthan".to_string(),
-        description: "Less-than comparison node: out = (a < b) ? 1.0 : 0.0".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Modulation depth 0.0-1.0 (0=center, 1=full L-R)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "waveform".to_string(),
+                param_type: "AutoPanWaveform".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "LFO shape (Sine, Triangle, Square)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // noisegate (Effects)
-    map.insert("noisegate".to_string(), GeneratedNodeMetadata {
-        name: "noisegate".to_string(),
-        description: "Noise Gate node: smooth gate with attack/release".to_string(),
+    // bandpassfilter (Effects)
+    map.insert("bandpassfilter".to_string(), GeneratedNodeMetadata {
+        name: "bandpassfilter".to_string(),
+        description: "BandPass filter node with pattern-controlled center frequency and Q".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~filtered: ~signal # bandpass_filter 1000 1.0".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "threshold_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "attack_input".to_string(),
+                name: "center_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Center frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "release_input".to_string(),
+                name: "q_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("1.0".to_string()),
+                description: "Resonance/bandwidth factor (default: 1.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // scalequantize (Effects)
-    map.insert("scalequantize".to_string(), GeneratedNodeMetadata {
-        name: "scalequantize".to_string(),
-        description: "Scale Quantize node: map frequencies to musical scales".to_string(),
+    // biquad (Effects)
+    map.insert("biquad".to_string(), GeneratedNodeMetadata {
+        name: "biquad".to_string(),
+        description: "Biquad filter node: high-quality second-order IIR filter".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~filtered: ~signal # biquad 1000 0.707 lowpass".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "scale".to_string(),
-                param_type: "Arc".to_string(),
+                name: "frequency_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Cutoff/center frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "root".to_string(),
+                name: "q_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Quality factor (0.1 wide to 20.0 sharp)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "mode".to_string(),
+                param_type: "FilterMode".to_string(),
+                default: None,
+                description: "Filter mode (Lowpass, Highpass, Bandpass, Notch)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // pulse (Effects)
-    map.insert("pulse".to_string(), GeneratedNodeMetadata {
-        name: "pulse".to_string(),
-        description: "Pulse wave oscillator with pattern-controlled frequency and width".to_string(),
+    // bitcrush (Effects)
+    map.insert("bitcrush".to_string(), GeneratedNodeMetadata {
+        name: "bitcrush".to_string(),
+        description: "Bitcrusher node: reduces bit depth and sample rate for lo-fi effects".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~crushed: ~signal # bitcrush 4 4".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "freq_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to process".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "width_input".to_string(),
+                name: "bits_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Bit depth (1-16, 16=CD quality, 4=lo-fi, 1=extreme)".to_string(),
+                range: Some("1 to 16".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Sample rate reduction factor (1-64, 1=full, 64=extreme)".to_string(),
+                range: Some("1 to 64".to_string()),
             },
         ],
     });
 
-    // subtraction (Effects)
-    map.insert("subtraction".to_string(), GeneratedNodeMetadata {
-        name: "subtraction".to_string(),
-        description: "Subtraction node: out = a - b".to_string(),
+    // blip (Effects)
+    map.insert("blip".to_string(), GeneratedNodeMetadata {
+        name: "blip".to_string(),
+        description: "Band-limited impulse train generator".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~impulses: blip 2
+~envelope: ~impulses # adsr 0.001 0.2 0 0.1
+~synth: sine 440 * ~envelope".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Impulse frequency in Hz".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // brownnoise (Effects)
+    map.insert("brownnoise".to_string(), GeneratedNodeMetadata {
+        name: "brownnoise".to_string(),
+        description: "Brown noise node: generates random walk values scaled by amplitude".to_string(),
+        category: "Effects".to_string(),
+        example: "~noise: brown_noise 0.5
+~filtered: ~noise # lpf 1000 0.8".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "amplitude_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Noise amplitude (0.0 to 1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // waveguide (Effects)
-    map.insert("waveguide".to_string(), GeneratedNodeMetadata {
-        name: "waveguide".to_string(),
-        description: "Digital waveguide node for physical modeling".to_string(),
+    // chorus (Effects)
+    map.insert("chorus".to_string(), GeneratedNodeMetadata {
+        name: "chorus".to_string(),
+        description: "Chorus node with pattern-controlled rate, depth, and mix".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 440
+~chorused: ~signal # chorus 1.5 0.01 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "excitation_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to process".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "frequency_input".to_string(),
+                name: "rate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "LFO rate in Hz (0.5-2.0 typical)".to_string(),
+                range: Some("0.5 to 2.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "decay_input".to_string(),
+                name: "depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Delay modulation depth in seconds (0.005-0.030 typical)".to_string(),
+                range: Some("0.005 to 0.030".to_string()),
             },
             GeneratedParamMetadata {
-                name: "brightness_input".to_string(),
+                name: "mix_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Wet/dry mix (0.0=dry, 1.0=wet)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // additive (Effects)
-    map.insert("additive".to_string(), GeneratedNodeMetadata {
-        name: "additive".to_string(),
-        description: "Additive synthesis node with pattern-controlled parameters".to_string(),
+    // clamp (Effects)
+    map.insert("clamp".to_string(), GeneratedNodeMetadata {
+        name: "clamp".to_string(),
+        description: "Clamp node: out = input.clamp(min, max)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~clamped: ~signal # clamp -0.5 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "frequency_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to constrain".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "num_harmonics_input".to_string(),
+                name: "min_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "harmonic_weights".to_string(),
-                param_type: "Arc".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Minimum value (lower bound)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "harmonic_detune".to_string(),
-                param_type: "Arc".to_string(),
+                name: "max_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Maximum value (upper bound)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // ringmod (Effects)
-    map.insert("ringmod".to_string(), GeneratedNodeMetadata {
-        name: "ringmod".to_string(),
-        description: "Ring modulation node: out = carrier * modulator".to_string(),
+    // clip (Effects)
+    map.insert("clip".to_string(), GeneratedNodeMetadata {
+        name: "clip".to_string(),
+        description: "Clip node: soft clipping with configurable threshold".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~clipped: ~signal # clip 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "carrier_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to clip".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "modulator_input".to_string(),
+                name: "threshold_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Clipping threshold (0.0-1.0 typical)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // invert (Effects)
-    map.insert("invert".to_string(), GeneratedNodeMetadata {
-        name: "invert".to_string(),
-        description: "Invert node: out = -input".to_string(),
+    // combfilter (Effects)
+    map.insert("combfilter".to_string(), GeneratedNodeMetadata {
+        name: "combfilter".to_string(),
+        description: "Comb filter node with pattern-controlled delay time and feedback".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: brown_noise 0.3
+~resonant: ~signal # comb_filter 0.009 0.7".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to filter".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // pinknoise (Effects)
-    map.insert("pinknoise".to_string(), GeneratedNodeMetadata {
-        name: "pinknoise".to_string(),
-        description: "Pink noise node: generates 1/f spectrum noise scaled by amplitude".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "amplitude_input".to_string(),
+                name: "delay_time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Delay time in seconds".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // rms (Effects)
-    map.insert("rms".to_string(), GeneratedNodeMetadata {
-        name: "rms".to_string(),
-        description: "RMS calculation node with pattern-controlled window size".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "feedback_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Feedback amount (-0.99 to 0.99)".to_string(),
+                range: Some("-0.99 to 0.99".to_string()),
             },
             GeneratedParamMetadata {
-                name: "window_time_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "max_delay".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Maximum delay time in seconds (buffer size)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // not (Effects)
-    map.insert("not".to_string(), GeneratedNodeMetadata {
-        name: "not".to_string(),
-        description: "Logical NOT node: out = (input > threshold) ? 0.0 : 1.0".to_string(),
+    // compressor (Effects)
+    map.insert("compressor".to_string(), GeneratedNodeMetadata {
+        name: "compressor".to_string(),
+        description: "Compressor node: smooth dynamics compression".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 220
+~compressed: ~signal # compressor -10 4 0.01 0.1".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to compress".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // envelopefollower (Effects)
-    map.insert("envelopefollower".to_string(), GeneratedNodeMetadata {
-        name: "envelopefollower".to_string(),
-        description: "Envelope follower node: tracks signal amplitude with attack/release smoothing".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "threshold_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Threshold in dB (e.g., -10.0)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "ratio_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Compression ratio (1=none, 4=typical, 20=heavy)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Attack time in seconds".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "release_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Release time in seconds".to_string(),
+                range: None,
             },
         ],
     });
 
-    // schmidt (Effects)
-    map.insert("schmidt".to_string(), GeneratedNodeMetadata {
-        name: "schmidt".to_string(),
-        description: "Schmidt trigger node: comparator with hysteresis".to_string(),
+    // constant (Effects)
+    map.insert("constant".to_string(), GeneratedNodeMetadata {
+        name: "constant".to_string(),
+        description: "Constant value node".to_string(),
         category: "Effects".to_string(),
         example: "".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "value".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // convolution (Effects)
+    map.insert("convolution".to_string(), GeneratedNodeMetadata {
+        name: "convolution".to_string(),
+        description: "Convolution node with FFT-based processing".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: saw 220
+~reverb: ~signal # convolution cathedral_ir 0.5".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "high_threshold_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to convolve".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "low_threshold_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "impulse_response".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Impulse response (up to 10 seconds at 44.1kHz)".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // absolute (Effects)
-    map.insert("absolute".to_string(), GeneratedNodeMetadata {
-        name: "absolute".to_string(),
-        description: "Absolute value node: out = |input|".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "mix".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-        ],
-    });
-
-    // vocoder (Effects)
-    map.insert("vocoder".to_string(), GeneratedNodeMetadata {
-        name: "vocoder".to_string(),
-        description: "Vocoder node with pattern-controlled carrier, modulator, bands, and bandwidth".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
-            GeneratedParamMetadata {
-                name: "carrier".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "modulator".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "num_bands_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "bandwidth_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Wet/dry mix (0.0=dry, 1.0=wet)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // clip (Effects)
-    map.insert("clip".to_string(), GeneratedNodeMetadata {
-        name: "clip".to_string(),
-        description: "Clip node: soft clipping with configurable threshold".to_string(),
+    // cos (Effects)
+    map.insert("cos".to_string(), GeneratedNodeMetadata {
+        name: "cos".to_string(),
+        description: "Cosine function node: out = cos(input)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo: sine 0.25 # cos".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "threshold_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId providing input signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // decimator (Effects)
-    map.insert("decimator".to_string(), GeneratedNodeMetadata {
-        name: "decimator".to_string(),
-        description: "Decimator node: Reduces effective sample rate".to_string(),
+    // crossoverlow (Effects)
+    map.insert("crossoverlow".to_string(), GeneratedNodeMetadata {
+        name: "crossoverlow".to_string(),
+        description: "Low band output of a 3-band Linkwitz-Riley crossover".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~low_band: ~signal # crossover_low 250 2000".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to split".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "factor_input".to_string(),
+                name: "low_freq_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("250".to_string()),
+                description: "NodeId providing low/mid crossover frequency in Hz (default: 250)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "smooth_input".to_string(),
+                name: "high_freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing mid/high crossover frequency in Hz (not used for low band)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // onepolefilter (Effects)
-    map.insert("onepolefilter".to_string(), GeneratedNodeMetadata {
-        name: "onepolefilter".to_string(),
-        description: "One-pole filter node with pattern-controlled cutoff".to_string(),
+    // curve (Effects)
+    map.insert("curve".to_string(), GeneratedNodeMetadata {
+        name: "curve".to_string(),
+        description: "Curve Generator Node".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~trigger: \"x ~ x ~\"
+~ramp: ~trigger # curve 0 1 0.5 3".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing trigger signal (> 0.5 triggers ramp restart)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "cutoff_input".to_string(),
+                name: "start_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing start value".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "mode".to_string(),
-                param_type: "OnePoleMode".to_string(),
+                name: "end_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing end value".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // peakdetector (Effects)
-    map.insert("peakdetector".to_string(), GeneratedNodeMetadata {
-        name: "peakdetector".to_string(),
-        description: "Peak detector node: tracks peak values with decay".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "duration_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing duration in seconds".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "decay_time_input".to_string(),
+                name: "curve_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing curve amount (-10 to +10, 0 = linear)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // karplusstrong (Effects)
-    map.insert("karplusstrong".to_string(), GeneratedNodeMetadata {
-        name: "karplusstrong".to_string(),
-        description: "Karplus-Strong plucked string synthesis node".to_string(),
+    // dattorroreverb (Effects)
+    map.insert("dattorroreverb".to_string(), GeneratedNodeMetadata {
+        name: "dattorroreverb".to_string(),
+        description: "Dattorro reverb node with pattern-controlled parameters".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~reverb: ~signal # dattorro_reverb 0.8 0.7 0.5 0.3".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to process".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "freq_input".to_string(),
+                name: "size".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing room size 0.0-1.0 (scales delay times)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "decay_input".to_string(),
+                name: "decay".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId providing decay time 0.0-1.0 (feedback amount)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
-        ],
-    });
-
-    // curve (Effects)
-    map.insert("curve".to_string(), GeneratedNodeMetadata {
-        name: "curve".to_string(),
-        description: "Curve Generator Node".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
+                name: "damping".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing high-frequency damping 0.0-1.0".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "start_input".to_string(),
+                name: "mix".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing wet/dry mix 0.0-1.0".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
+        ],
+    });
+
+    // decimator (Effects)
+    map.insert("decimator".to_string(), GeneratedNodeMetadata {
+        name: "decimator".to_string(),
+        description: "Decimator node: Reduces effective sample rate".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: sine 440
+~lofi: ~signal # decimator 8 0.2".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "end_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to decimate".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "duration_input".to_string(),
+                name: "factor_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("4".to_string()),
+                description: "NodeId providing decimation factor 1.0-64.0 (default: 4)".to_string(),
+                range: Some("1.0 to 64.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "curve_input".to_string(),
+                name: "smooth_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0".to_string()),
+                description: "NodeId providing smoothing amount 0.0-1.0 (default: 0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // greaterthanorequal (Effects)
-    map.insert("greaterthanorequal".to_string(), GeneratedNodeMetadata {
-        name: "greaterthanorequal".to_string(),
-        description: "Greater-than-or-equal comparison node: out = (a >= b - tolerance) ? 1.0 : 0.0".to_string(),
+    // delay (Effects)
+    map.insert("delay".to_string(), GeneratedNodeMetadata {
+        name: "delay".to_string(),
+        description: "Delay node with pattern-controlled delay time".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~delayed: ~signal # delay 0.2 1.0 44100".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to delay".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "delay_time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing delay time in seconds".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "tolerance".to_string(),
+                name: "max_delay".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Maximum delay time in seconds (determines buffer size)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
@@ -945,209 +973,201 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
         name: "diffuser".to_string(),
         description: "Multi-channel Hadamard diffuser".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: s \"bd\"
+~diffused: ~signal # diffuser 0.75".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to diffuse".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "diffusion_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Diffusion amount (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz".to_string(),
+                range: None,
             },
         ],
     });
 
-    // stereosplitter (Effects)
-    map.insert("stereosplitter".to_string(), GeneratedNodeMetadata {
-        name: "stereosplitter".to_string(),
-        description: "Stereo splitter node - identity passthrough with stereo semantics".to_string(),
+    // distortion (Effects)
+    map.insert("distortion".to_string(), GeneratedNodeMetadata {
+        name: "distortion".to_string(),
+        description: "Distortion node: soft clipping waveshaper".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~distorted: ~signal # distortion 5 0.8".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "stereo_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to distort".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "drive_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("5".to_string()),
+                description: "NodeId providing drive amount 1.0-100.0 (default: 5)".to_string(),
+                range: Some("1.0 to 100.0".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "mix_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("1.0".to_string()),
+                description: "NodeId providing wet/dry mix 0.0-1.0 (default: 1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // and (Effects)
-    map.insert("and".to_string(), GeneratedNodeMetadata {
-        name: "and".to_string(),
-        description: "Logical AND node: out = (a > threshold && b > threshold) ? 1.0 : 0.0".to_string(),
+    // division (Effects)
+    map.insert("division".to_string(), GeneratedNodeMetadata {
+        name: "division".to_string(),
+        description: "Division node: out = a / b".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal_a: sine 110
+~signal_b: sine 55 * 0.5 + 0.5
+~result: ~signal_a # division ~signal_b".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing numerator (dividend)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing denominator (divisor)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // adsr (Effects)
-    map.insert("adsr".to_string(), GeneratedNodeMetadata {
-        name: "adsr".to_string(),
-        description: "ADSR Envelope Generator Node".to_string(),
+    // djfilter (Effects)
+    map.insert("djfilter".to_string(), GeneratedNodeMetadata {
+        name: "djfilter".to_string(),
+        description: "DJ filter node with pattern-controlled position and resonance".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~filtered: ~signal # dj_filter 0.5 0.8".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "gate_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "attack_input".to_string(),
+                name: "position".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.0".to_string()),
+                description: "NodeId providing filter position -1.0 to +1.0 (default: 0.0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "decay_input".to_string(),
+                name: "resonance".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.7".to_string()),
+                description: "NodeId providing resonance amount 0.0-1.0 (default: 0.7)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
+        ],
+    });
+
+    // envelopefollower (Effects)
+    map.insert("envelopefollower".to_string(), GeneratedNodeMetadata {
+        name: "envelopefollower".to_string(),
+        description: "Envelope follower node: tracks signal amplitude with attack/release smoothing".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: sine 110
+~envelope: ~signal # envelope_follower 0.01 0.2".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "sustain_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to analyze".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "release_input".to_string(),
+                name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.005".to_string()),
+                description: "NodeId providing attack time in seconds (default: 0.005)".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // pitch (Effects)
-    map.insert("pitch".to_string(), GeneratedNodeMetadata {
-        name: "pitch".to_string(),
-        description: "Pitch detection node".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "release_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.1".to_string()),
+                description: "NodeId providing release time in seconds (default: 0.1)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // combfilter (Effects)
-    map.insert("combfilter".to_string(), GeneratedNodeMetadata {
-        name: "combfilter".to_string(),
-        description: "Comb filter node with pattern-controlled delay time and feedback".to_string(),
+    // equalto (Effects)
+    map.insert("equalto".to_string(), GeneratedNodeMetadata {
+        name: "equalto".to_string(),
+        description: "Equal To node: out = (|a - b| < tolerance) ? 1.0 : 0.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~sig_a: sine 110
+~trigger: ~sig_a # equal_to 0.5 0.001".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "delay_time_input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing first signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "feedback_input".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "max_delay".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId providing second signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
+                name: "tolerance".to_string(),
                 param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-        ],
-    });
-
-    // sampleplayback (Effects)
-    map.insert("sampleplayback".to_string(), GeneratedNodeMetadata {
-        name: "sampleplayback".to_string(),
-        description: "Sample playback node with trigger and speed control".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
-            GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "speed_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "sample_data".to_string(),
-                param_type: "Arc".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("1e-6".to_string()),
+                description: "Epsilon for floating-point comparison (default: 1e-6)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // gate (Effects)
-    map.insert("gate".to_string(), GeneratedNodeMetadata {
-        name: "gate".to_string(),
-        description: "Gate node: out = if |input| > threshold { input } else { 0.0 }".to_string(),
+    // exp (Effects)
+    map.insert("exp".to_string(), GeneratedNodeMetadata {
+        name: "exp".to_string(),
+        description: "Exponential node: out = e^input".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo: sine 0.25
+~exp: ~lfo # exp".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "threshold_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId providing input signal".to_string(),
+                range: None,
             },
         ],
     });
@@ -1157,1791 +1177,2149 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
         name: "expander".to_string(),
         description: "Expander node: upward dynamics expansion".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 110
+~expanded: ~signal # expander -10 2 0.01 0.1".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to expand".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "threshold_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("-10.0".to_string()),
+                description: "NodeId providing threshold in dB (default: -10.0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "ratio_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("2.0".to_string()),
+                description: "NodeId providing expansion ratio 1.0-10.0 (default: 2.0)".to_string(),
+                range: Some("1.0 to 10.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.005".to_string()),
+                description: "NodeId providing attack time in seconds (default: 0.005)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "release_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.1".to_string()),
+                description: "NodeId providing release time in seconds (default: 0.1)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // formant (Effects)
-    map.insert("formant".to_string(), GeneratedNodeMetadata {
-        name: "formant".to_string(),
-        description: "Formant filter node for vowel synthesis".to_string(),
+    // flanger (Effects)
+    map.insert("flanger".to_string(), GeneratedNodeMetadata {
+        name: "flanger".to_string(),
+        description: "Flanger node with pattern-controlled rate, depth, and feedback".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~flanged: ~signal # flanger 0.5 0.005 0.7 44100".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to flange".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "formant".to_string(),
+                name: "rate_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.5".to_string()),
+                description: "NodeId providing LFO rate in Hz (default: 0.5)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "intensity".to_string(),
+                name: "depth_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("0.005".to_string()),
+                description: "NodeId providing delay depth in seconds (default: 0.005)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "feedback_input".to_string(),
                 param_type: "NodeId".to_string(),
+                default: Some("0.7".to_string()),
+                description: "NodeId providing feedback 0.0-0.95 (default: 0.7)".to_string(),
+                range: Some("0.0 to 0.95".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // phaser (Effects)
-    map.insert("phaser".to_string(), GeneratedNodeMetadata {
-        name: "phaser".to_string(),
-        description: "Phaser node with pattern-controlled rate, depth, feedback, and stages".to_string(),
+    // fmcrossmod (Effects)
+    map.insert("fmcrossmod".to_string(), GeneratedNodeMetadata {
+        name: "fmcrossmod".to_string(),
+        description: "FM Cross-Modulation node: carrier modulated by any audio signal".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~kick: s \"bd*4\"
+~bass: saw 55
+~modulated: fmcrossmod ~bass ~kick 2.0".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "carrier_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of carrier signal (signal to be modulated)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "rate_input".to_string(),
+                name: "modulator_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of modulator signal (controls modulation)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "depth_input".to_string(),
+                name: "mod_depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for modulation depth (0.0 = no effect, higher = more modulation)".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // fmoscillator (Effects)
+    map.insert("fmoscillator".to_string(), GeneratedNodeMetadata {
+        name: "fmoscillator".to_string(),
+        description: "FM Oscillator with pattern-controlled parameters".to_string(),
+        category: "Effects".to_string(),
+        example: "~carrier: \"440 880 1760\"
+~modulator: \"440 440 440\"
+~fm: ~carrier # fm_oscillator ~modulator 2.0".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "feedback_input".to_string(),
+                name: "carrier_freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing carrier frequency (Hz)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "stages".to_string(),
-                param_type: "usize".to_string(),
+                name: "modulator_freq_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing modulator frequency (Hz)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
+                name: "mod_index_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing modulation index depth (0.0-10.0+)".to_string(),
+                range: Some("0.0 to 10.0".to_string()),
             },
         ],
     });
 
-    // latch (Effects)
-    map.insert("latch".to_string(), GeneratedNodeMetadata {
-        name: "latch".to_string(),
-        description: "Latch node: captures input when trigger rises above 0.5 threshold".to_string(),
+    // fold (Effects)
+    map.insert("fold".to_string(), GeneratedNodeMetadata {
+        name: "fold".to_string(),
+        description: "Fold node: out = fold(input, min, max)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 110 * 2
+~folded: ~signal # fold -1 1".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to fold".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
+                name: "min_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("-1.0".to_string()),
+                description: "NodeId providing minimum boundary (default: -1.0)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "max_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("1.0".to_string()),
+                description: "NodeId providing maximum boundary (default: 1.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // lerp (Effects)
-    map.insert("lerp".to_string(), GeneratedNodeMetadata {
-        name: "lerp".to_string(),
-        description: "Lerp node: linear interpolation between two signals".to_string(),
+    // formant (Effects)
+    map.insert("formant".to_string(), GeneratedNodeMetadata {
+        name: "formant".to_string(),
+        description: "Formant filter node for vowel synthesis".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~saw: saw 110
+~vocal: ~saw # formant 1.5 0.8".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to filter (saw/pulse waves work best)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "formant".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0".to_string()),
+                description: "NodeId providing vowel selection 0.0-4.0 (default: 0 = A)".to_string(),
+                range: Some("0.0 to 4.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "mix_input".to_string(),
+                name: "intensity".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.8".to_string()),
+                description: "NodeId providing effect intensity 0.0-1.0 (default: 0.8)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // allpassfilter (Effects)
-    map.insert("allpassfilter".to_string(), GeneratedNodeMetadata {
-        name: "allpassfilter".to_string(),
-        description: "All-pass filter node with pattern-controlled frequency and Q".to_string(),
+    // frequencyshifter (Effects)
+    map.insert("frequencyshifter".to_string(), GeneratedNodeMetadata {
+        name: "frequencyshifter".to_string(),
+        description: "Frequency shifter node with pattern-controlled shift amount".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~shifted: ~signal # frequency_shifter 50".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "freq_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to shift".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "q_input".to_string(),
+                name: "shift_hz_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0".to_string()),
+                description: "NodeId providing shift amount in Hz (default: 0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // stereomerger (Effects)
-    map.insert("stereomerger".to_string(), GeneratedNodeMetadata {
-        name: "stereomerger".to_string(),
-        description: "Merge two mono signals into stereo (currently outputs mono mix)".to_string(),
+    // gain (Effects)
+    map.insert("gain".to_string(), GeneratedNodeMetadata {
+        name: "gain".to_string(),
+        description: "Gain node: out = input * gain".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~quiet: ~signal # gain 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "left".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to amplify".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "right".to_string(),
+                name: "gain_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("1.0".to_string()),
+                description: "NodeId providing gain amount (default: 1.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // highpassfilter (Effects)
-    map.insert("highpassfilter".to_string(), GeneratedNodeMetadata {
-        name: "highpassfilter".to_string(),
-        description: "HighPass filter node with pattern-controlled cutoff and Q".to_string(),
+    // gate (Effects)
+    map.insert("gate".to_string(), GeneratedNodeMetadata {
+        name: "gate".to_string(),
+        description: "Gate node: out = if |input| > threshold { input } else { 0.0 }".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~noisy: sine 440 + white_noise 0.1
+~clean: ~noisy # gate 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to gate".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "cutoff_input".to_string(),
+                name: "threshold_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "q_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.1".to_string()),
+                description: "NodeId providing threshold in linear amplitude (default: 0.1)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // cos (Effects)
-    map.insert("cos".to_string(), GeneratedNodeMetadata {
-        name: "cos".to_string(),
-        description: "Cosine function node: out = cos(input)".to_string(),
+    // granular (Effects)
+    map.insert("granular".to_string(), GeneratedNodeMetadata {
+        name: "granular".to_string(),
+        description: "Granular synthesis node".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~granular: granular source_buffer 0.5 100 10 0 0.1 44100".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "source".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio buffer to granulate (Arc<Vec<f32>>)".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // line (Effects)
-    map.insert("line".to_string(), GeneratedNodeMetadata {
-        name: "line".to_string(),
-        description: "Line Generator Node".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "start_input".to_string(),
+                name: "position_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing playback position 0.0-1.0".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "end_input".to_string(),
+                name: "grain_size_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("100".to_string()),
+                description: "NodeId providing grain size 5-500 ms (default: 100)".to_string(),
+                range: Some("5 to 500".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "density_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("10".to_string()),
+                description: "NodeId providing density 1-100 grains/sec (default: 10)".to_string(),
+                range: Some("1 to 100".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "pitch_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("0".to_string()),
+                description: "NodeId providing pitch shift -12 to +12 semitones (default: 0)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "spray_input".to_string(),
                 param_type: "NodeId".to_string(),
+                default: Some("0.1".to_string()),
+                description: "NodeId providing random position offset 0.0-1.0 (default: 0.1)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // greaterthan (Effects)
+    map.insert("greaterthan".to_string(), GeneratedNodeMetadata {
+        name: "greaterthan".to_string(),
+        description: "Greater Than node: out = (a > b) ? 1.0 : 0.0".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: sine 0.5
+~threshold: 0.5
+~gate: ~signal > ~threshold
+out: sine 220 * ~gate".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "duration_input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First input signal (left side of comparison)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second input signal (right side of comparison)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // equalto (Effects)
-    map.insert("equalto".to_string(), GeneratedNodeMetadata {
-        name: "equalto".to_string(),
-        description: "Equal To node: out = (|a - b| < tolerance) ? 1.0 : 0.0".to_string(),
+    // greaterthanorequal (Effects)
+    map.insert("greaterthanorequal".to_string(), GeneratedNodeMetadata {
+        name: "greaterthanorequal".to_string(),
+        description: "Greater-than-or-equal comparison node: out = (a >= b - tolerance) ? 1.0 : 0.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~sig_a: sine 110
+~trigger: ~sig_a # greater_than_or_equal 0.5 0.001".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing first signal (left side)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing second signal (right side)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "tolerance".to_string(),
                 param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("1e-6".to_string()),
+                description: "Epsilon for floating-point comparison (default: 1e-6)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // granular (Effects)
-    map.insert("granular".to_string(), GeneratedNodeMetadata {
-        name: "granular".to_string(),
-        description: "Granular synthesis node".to_string(),
+    // highpassfilter (Effects)
+    map.insert("highpassfilter".to_string(), GeneratedNodeMetadata {
+        name: "highpassfilter".to_string(),
+        description: "HighPass filter node with pattern-controlled cutoff and Q".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~cutoff: sine 0.5 * 2000 + 1000
+~filtered: ~signal # hpf ~cutoff 1.0
+out: ~filtered".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "source".to_string(),
-                param_type: "Arc".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "position_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "grain_size_input".to_string(),
+                name: "cutoff_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Cutoff frequency in Hz (20-20000)".to_string(),
+                range: Some("20 to 20000".to_string()),
             },
             GeneratedParamMetadata {
-                name: "density_input".to_string(),
+                name: "q_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Resonance factor (0.1-20.0)".to_string(),
+                range: Some("0.1 to 20.0".to_string()),
             },
+        ],
+    });
+
+    // hilberttransformer (Effects)
+    map.insert("hilberttransformer".to_string(), GeneratedNodeMetadata {
+        name: "hilberttransformer".to_string(),
+        description: "Hilbert transformer node producing I/Q outputs".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: saw 440
+~i: ~signal # hilbert
+~q: ~signal # hilbert_q
+out: ~i".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "pitch_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to transform".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // impulse (Effects)
+    map.insert("impulse".to_string(), GeneratedNodeMetadata {
+        name: "impulse".to_string(),
+        description: "Impulse generator node with pattern-controlled frequency".to_string(),
+        category: "Effects".to_string(),
+        example: "~trig: sine 0.5 # impulse
+~note: ~trig # adsr 0.01 0.1 0.5 0.2
+out: sine 220 * ~note".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "spray_input".to_string(),
+                name: "frequency_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Spike frequency in Hz".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // invert (Effects)
+    map.insert("invert".to_string(), GeneratedNodeMetadata {
+        name: "invert".to_string(),
+        description: "Invert node: out = -input".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: sine 220
+~inverted: ~signal # invert
+out: (~signal + ~inverted) * 0.5".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
+                name: "input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to invert".to_string(),
+                range: None,
             },
         ],
     });
 
-    // resample (Effects)
-    map.insert("resample".to_string(), GeneratedNodeMetadata {
-        name: "resample".to_string(),
-        description: "Resample node with pattern-controlled resampling ratio".to_string(),
+    // karplusstrong (Effects)
+    map.insert("karplusstrong".to_string(), GeneratedNodeMetadata {
+        name: "karplusstrong".to_string(),
+        description: "Karplus-Strong plucked string synthesis node".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~trig: \"x ~ x ~\" # bpm_to_impulse 120
+~freq: \"220 330 440 330\"
+~decay: 0.92
+~pluck: ~trig # karplus ~freq ~decay
+out: ~pluck * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Rising edge (>= 0.5) triggers new note".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "ratio_input".to_string(),
+                name: "freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Fundamental frequency in Hz (27.5-20000)".to_string(),
+                range: Some("27.5 to 20000".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "decay_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("0.95".to_string()),
+                description: "Sustain duration (0-1, default: 0.95)".to_string(),
+                range: Some("0 to 1".to_string()),
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // quantizer (Effects)
-    map.insert("quantizer".to_string(), GeneratedNodeMetadata {
-        name: "quantizer".to_string(),
-        description: "Quantizer node: snap values to nearest step".to_string(),
+    // lag (Effects)
+    map.insert("lag".to_string(), GeneratedNodeMetadata {
+        name: "lag".to_string(),
+        description: "Lag node: exponential smoothing with time constant control".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~pitch: \"220 330 440 330\"
+~smooth: ~pitch # lag 0.1
+out: sine ~smooth * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to smooth".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "step_size_input".to_string(),
+                name: "lag_time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Time constant in seconds (0.001-10.0 recommended)".to_string(),
+                range: Some("0.001 to 10.0".to_string()),
             },
         ],
     });
 
-    // multiplication (Effects)
-    map.insert("multiplication".to_string(), GeneratedNodeMetadata {
-        name: "multiplication".to_string(),
-        description: "Multiplication node: out = a * b".to_string(),
+    // latch (Effects)
+    map.insert("latch".to_string(), GeneratedNodeMetadata {
+        name: "latch".to_string(),
+        description: "Latch node: captures input when trigger rises above 0.5 threshold".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~random: noise 1
+~clock: \"x ~ x ~\" # bpm_to_impulse 120
+~stepped: ~random # latch ~clock
+out: sine ~stepped * 440 * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal value to sample".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Trigger signal (rising edge > 0.5 latches)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // notequalto (Effects)
-    map.insert("notequalto".to_string(), GeneratedNodeMetadata {
-        name: "notequalto".to_string(),
-        description: "Not Equal To node: out = (|a - b| >= tolerance) ? 1.0 : 0.0".to_string(),
+    // lerp (Effects)
+    map.insert("lerp".to_string(), GeneratedNodeMetadata {
+        name: "lerp".to_string(),
+        description: "Lerp node: linear interpolation between two signals".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo: sine 0.25
+~mix: (~lfo + 1) * 0.5
+~sine: sine 220
+~saw: saw 220
+~blend: ~sine # lerp ~saw ~mix
+out: ~blend * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal 1 (0% when mix = 0.0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal 2 (100% when mix = 1.0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "tolerance".to_string(),
-                param_type: "f32".to_string(),
+                name: "mix_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Blend amount (0.0-1.0, extrapolates outside range)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // multitapdelay (Effects)
-    map.insert("multitapdelay".to_string(), GeneratedNodeMetadata {
-        name: "multitapdelay".to_string(),
-        description: "Multi-tap delay node: creates rhythmic delay patterns with multiple taps".to_string(),
+    // lessthan (Effects)
+    map.insert("lessthan".to_string(), GeneratedNodeMetadata {
+        name: "lessthan".to_string(),
+        description: "Less-than comparison node: out = (a < b) ? 1.0 : 0.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo: sine 0.5
+~threshold: 0.0
+~gate: ~lfo # lt ~threshold
+out: sine 220 * ~gate".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First signal (left side of comparison)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "time_input".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second signal (right side of comparison)".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // lessthanorequal (Effects)
+    map.insert("lessthanorequal".to_string(), GeneratedNodeMetadata {
+        name: "lessthanorequal".to_string(),
+        description: "Less-than-or-equal comparison node: out = (a <= b + tolerance) ? 1.0 : 0.0".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: sine 220
+~threshold: 0.5
+~gate: ~signal # lte ~threshold 1e-6
+out: sine 440 * ~gate".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "taps_input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First signal (left side of comparison)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "feedback_input".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second signal (right side of comparison)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "mix_input".to_string(),
+                name: "tolerance".to_string(),
+                param_type: "f32".to_string(),
+                default: Some("1e-6".to_string()),
+                description: "Floating-point epsilon (default: 1e-6)".to_string(),
+                range: None,
+            },
+        ],
+    });
+
+    // limiter (Effects)
+    map.insert("limiter".to_string(), GeneratedNodeMetadata {
+        name: "limiter".to_string(),
+        description: "Limiter node: hard limiting dynamics processor".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: saw 110 + saw 165
+~limited: ~signal # limiter -3 -0.1
+out: ~limited * 0.5".to_string(),
+        params: vec![
+            GeneratedParamMetadata {
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to limit".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "max_delay".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                name: "threshold_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("-3.0".to_string()),
+                description: "Threshold in dB (default: -3.0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-        ],
-    });
-
-    // notchfilter (Effects)
-    map.insert("notchfilter".to_string(), GeneratedNodeMetadata {
-        name: "notchfilter".to_string(),
-        description: "Notch filter node with pattern-controlled center frequency and Q".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
-            GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "center_freq_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "q_input".to_string(),
+                name: "ceiling_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("0.0".to_string()),
+                description: "Output ceiling in dB (default: 0.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // chorus (Effects)
-    map.insert("chorus".to_string(), GeneratedNodeMetadata {
-        name: "chorus".to_string(),
-        description: "Chorus node with pattern-controlled rate, depth, and mix".to_string(),
+    // line (Effects)
+    map.insert("line".to_string(), GeneratedNodeMetadata {
+        name: "line".to_string(),
+        description: "Line Generator Node".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~trig: \"x ~ x ~\" # bpm_to_impulse 120
+~freq_ramp: ~trig # line 220 440 0.5
+out: sine ~freq_ramp * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "start_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Starting value".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "rate_input".to_string(),
+                name: "end_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Ending value".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "depth_input".to_string(),
+                name: "duration_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Ramp duration in seconds".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "mix_input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Trigger signal (rising edge > 0.5 starts ramp)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // hilberttransformer (Effects)
-    map.insert("hilberttransformer".to_string(), GeneratedNodeMetadata {
-        name: "hilberttransformer".to_string(),
-        description: "Hilbert transformer node producing I/Q outputs".to_string(),
+    // log (Effects)
+    map.insert("log".to_string(), GeneratedNodeMetadata {
+        name: "log".to_string(),
+        description: "Natural logarithm node: out = ln(max(|input|, 1e-10))".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~freq: sine 0.5 * 2000 + 2200
+~scaled: ~freq # log
+out: sine 220 * (~scaled * 0.1) * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal values (negative inputs produce NaN)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // arenvelope (Effects)
-    map.insert("arenvelope".to_string(), GeneratedNodeMetadata {
-        name: "arenvelope".to_string(),
-        description: "AR Envelope Generator Node".to_string(),
+    // lowpassfilter (Effects)
+    map.insert("lowpassfilter".to_string(), GeneratedNodeMetadata {
+        name: "lowpassfilter".to_string(),
+        description: "Low-pass filter node with pattern-controlled cutoff and Q".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 110
+~cutoff: sine 0.5 * 4000 + 2000
+~filtered: ~signal # lpf ~cutoff 0.8
+out: ~filtered".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "gate_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "attack_input".to_string(),
+                name: "cutoff_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Cutoff frequency in Hz (20-20000)".to_string(),
+                range: Some("20 to 20000".to_string()),
             },
             GeneratedParamMetadata {
-                name: "release_input".to_string(),
+                name: "q_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Resonance factor (0.1-20.0, 0.707 for Butterworth)".to_string(),
+                range: Some("0.1 to 20.0".to_string()),
             },
         ],
     });
 
-    // xline (Effects)
-    map.insert("xline".to_string(), GeneratedNodeMetadata {
-        name: "xline".to_string(),
-        description: "Exponential line generator node".to_string(),
+    // max (Effects)
+    map.insert("max".to_string(), GeneratedNodeMetadata {
+        name: "max".to_string(),
+        description: "Max node: out = max(a, b)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~env1: impulse 2 # adsr 0.01 0.1 0.5 0.2
+~env2: impulse 3 # adsr 0.02 0.05 0.7 0.1
+~combined: ~env1 # max ~env2
+out: sine 220 * ~combined * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "start_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "end_input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "duration_input".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Second signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // limiter (Effects)
-    map.insert("limiter".to_string(), GeneratedNodeMetadata {
-        name: "limiter".to_string(),
-        description: "Limiter node: hard limiting dynamics processor".to_string(),
+    // min (Effects)
+    map.insert("min".to_string(), GeneratedNodeMetadata {
+        name: "min".to_string(),
+        description: "Minimum node: out = min(a, b)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo1: sine 0.25
+~lfo2: sine 0.33
+~combined: ~lfo1 # min ~lfo2
+~freq: ~combined * 2000 + 1000
+out: sine ~freq * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "threshold_input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "ceiling_input".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // flanger (Effects)
-    map.insert("flanger".to_string(), GeneratedNodeMetadata {
-        name: "flanger".to_string(),
-        description: "Flanger node with pattern-controlled rate, depth, and feedback".to_string(),
+    // mix (Effects)
+    map.insert("mix".to_string(), GeneratedNodeMetadata {
+        name: "mix".to_string(),
+        description: "Mix node: weighted sum of N inputs".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~sig1: sine 220
+~sig2: sine 330
+~sig3: sine 440
+~mixed: mix [~sig1, ~sig2, ~sig3] [0.5, 0.3, 0.2]
+out: ~mixed".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "rate_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "depth_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "feedback_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "inputs".to_string(),
+                param_type: "Vec".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Vector of signal NodeIds to mix".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
+                name: "weights".to_string(),
+                param_type: "Vec".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Vector of weights (must match inputs length)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // tap (Effects)
-    map.insert("tap".to_string(), GeneratedNodeMetadata {
-        name: "tap".to_string(),
-        description: "Tap tempo converter: converts beats to seconds".to_string(),
+    // mod (Effects)
+    map.insert("mod".to_string(), GeneratedNodeMetadata {
+        name: "mod".to_string(),
+        description: "Modulo node: out = a % b".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~phase: line 0 10 2 # trigger
+~wrapped: ~phase # modulo 2
+out: sine (~wrapped * 3.14159) * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "beats".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Dividend (value to be wrapped)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "bpm".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Divisor (wrapping period)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // samplepattern (Effects)
-    map.insert("samplepattern".to_string(), GeneratedNodeMetadata {
-        name: "samplepattern".to_string(),
-        description: "Sample Pattern Node".to_string(),
+    // moogladder (Effects)
+    map.insert("moogladder".to_string(), GeneratedNodeMetadata {
+        name: "moogladder".to_string(),
+        description: "Moog Ladder filter node with pattern-controlled cutoff and resonance".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~saw: saw 110
+~cutoff: sine 0.5 * 4000 + 2000
+~filtered: ~saw # moog ~cutoff 2.5
+out: ~filtered * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "pattern".to_string(),
-                param_type: "Arc".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "voice_manager".to_string(),
-                param_type: "Arc".to_string(),
+                name: "input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sample_bank".to_string(),
-                param_type: "Arc".to_string(),
+                name: "cutoff".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Cutoff frequency in Hz (20-20000)".to_string(),
+                range: Some("20 to 20000".to_string()),
             },
-        ],
-    });
-
-    // patternevaluator (Effects)
-    map.insert("patternevaluator".to_string(), GeneratedNodeMetadata {
-        name: "patternevaluator".to_string(),
-        description: "Pattern Evaluator Node".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "pattern".to_string(),
-                param_type: "Arc".to_string(),
+                name: "resonance".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Resonance amount (0.0-4.0, >3.5 = self-oscillating)".to_string(),
+                range: Some("0.0 to 4.0".to_string()),
             },
         ],
     });
 
-    // lessthanorequal (Effects)
-    map.insert("lessthanorequal".to_string(), GeneratedNodeMetadata {
-        name: "lessthanorequal".to_string(),
-        description: "Less-than-or-equal comparison node: out = (a <= b + tolerance) ? 1.0 : 0.0".to_string(),
+    // multiplication (Effects)
+    map.insert("multiplication".to_string(), GeneratedNodeMetadata {
+        name: "multiplication".to_string(),
+        description: "Multiplication node: out = a * b".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~osc: sine 220
+~env: impulse 2 # adsr 0.01 0.1 0.5 0.2
+~gated: ~osc # * ~env
+out: ~gated * 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First signal (multiplicand)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "tolerance".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Second signal (multiplier)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // compressor (Effects)
-    map.insert("compressor".to_string(), GeneratedNodeMetadata {
-        name: "compressor".to_string(),
-        description: "Compressor node: smooth dynamics compression".to_string(),
+    // multitapdelay (Effects)
+    map.insert("multitapdelay".to_string(), GeneratedNodeMetadata {
+        name: "multitapdelay".to_string(),
+        description: "Multi-tap delay node: creates rhythmic delay patterns with multiple taps".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~delay: ~signal # multitap_delay 0.125 3 0.5 0.6".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to delay".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "threshold_input".to_string(),
+                name: "time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of base delay time in seconds (0.01-0.5 typical)".to_string(),
+                range: Some("0.01 to 0.5".to_string()),
             },
             GeneratedParamMetadata {
-                name: "ratio_input".to_string(),
+                name: "taps_input".to_string(),
                 param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                default: Some("3".to_string()),
+                description: "NodeId of number of taps (2-8, default: 3)".to_string(),
+                range: Some("2 to 8".to_string()),
             },
             GeneratedParamMetadata {
-                name: "attack_input".to_string(),
+                name: "feedback_input".to_string(),
                 param_type: "NodeId".to_string(),
+                default: Some("0.5".to_string()),
+                description: "NodeId of feedback amount (0.0-0.95, default: 0.5)".to_string(),
+                range: Some("0.0 to 0.95".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "mix_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: Some("0.5".to_string()),
+                description: "NodeId of wet/dry mix (0.0-1.0, default: 0.5)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "max_delay".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Maximum delay time in seconds (determines buffer size, > 0.0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "release_input".to_string(),
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
+            },
+        ],
+    });
+
+    // noise (Effects)
+    map.insert("noise".to_string(), GeneratedNodeMetadata {
+        name: "noise".to_string(),
+        description: "White noise node: generates random values scaled by amplitude".to_string(),
+        category: "Effects".to_string(),
+        example: "~noise: noise 0.5".to_string(),
+        params: vec![
+            GeneratedParamMetadata {
+                name: "amplitude_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of the amplitude control signal (0.0-1.0 typical)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // djfilter (Effects)
-    map.insert("djfilter".to_string(), GeneratedNodeMetadata {
-        name: "djfilter".to_string(),
-        description: "DJ filter node with pattern-controlled position and resonance".to_string(),
+    // noisegate (Effects)
+    map.insert("noisegate".to_string(), GeneratedNodeMetadata {
+        name: "noisegate".to_string(),
+        description: "Noise Gate node: smooth gate with attack/release".to_string(),
         category: "Effects".to_string(),
         example: "".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "threshold_db".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "position".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "attack".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "resonance".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "release".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
         ],
     });
 
-    // oscillator (Effects)
-    map.insert("oscillator".to_string(), GeneratedNodeMetadata {
-        name: "oscillator".to_string(),
-        description: "Oscillator node with pattern-controlled frequency".to_string(),
+    // not (Effects)
+    map.insert("not".to_string(), GeneratedNodeMetadata {
+        name: "not".to_string(),
+        description: "Logical NOT node: out = (input > threshold) ? 0.0 : 1.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~gate: 0.7
+~inverted: not ~gate".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "freq_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "waveform".to_string(),
-                param_type: "Waveform".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId of the input signal (threshold: 0.5, >0.5 = true)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // sin (Effects)
-    map.insert("sin".to_string(), GeneratedNodeMetadata {
-        name: "sin".to_string(),
-        description: "Sine function node: out = sin(input)".to_string(),
+    // notequalto (Effects)
+    map.insert("notequalto".to_string(), GeneratedNodeMetadata {
+        name: "notequalto".to_string(),
+        description: "Not Equal To node: out = (|a - b| >= tolerance) ? 1.0 : 0.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~val1: 5.0
+~val2: 5.1
+~result: not_equal_to ~val1 ~val2 0.01".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of first value to compare".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "input_b".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "NodeId of second value to compare".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "tolerance".to_string(),
+                param_type: "f32".to_string(),
+                default: Some("1e-6".to_string()),
+                description: "Epsilon for floating-point comparison (default: 1e-6)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // reverb (Effects)
-    map.insert("reverb".to_string(), GeneratedNodeMetadata {
-        name: "reverb".to_string(),
-        description: "Reverb node with pattern-controlled parameters".to_string(),
+    // notchfilter (Effects)
+    map.insert("notchfilter".to_string(), GeneratedNodeMetadata {
+        name: "notchfilter".to_string(),
+        description: "Notch filter node with pattern-controlled center frequency and Q".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~filtered: ~signal # notch_filter 1000 10".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "room_size".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "damping".to_string(),
+                name: "center_freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of center frequency to reject (Hz)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "wet".to_string(),
+                name: "q_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of resonance/Q factor (1.0-100.0 typical)".to_string(),
+                range: Some("1.0 to 100.0".to_string()),
             },
         ],
     });
 
-    // biquad (Effects)
-    map.insert("biquad".to_string(), GeneratedNodeMetadata {
-        name: "biquad".to_string(),
-        description: "Biquad filter node: high-quality second-order IIR filter".to_string(),
+    // onepolefilter (Effects)
+    map.insert("onepolefilter".to_string(), GeneratedNodeMetadata {
+        name: "onepolefilter".to_string(),
+        description: "One-pole filter node with pattern-controlled cutoff".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~filtered: ~signal # one_pole_filter 1000".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "frequency_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "q_input".to_string(),
+                name: "cutoff_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of cutoff frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "mode".to_string(),
-                param_type: "FilterMode".to_string(),
+                param_type: "OnePoleMode".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "LowPass or HighPass filtering".to_string(),
+                range: None,
             },
         ],
     });
 
-    // squareroot (Effects)
-    map.insert("squareroot".to_string(), GeneratedNodeMetadata {
-        name: "squareroot".to_string(),
-        description: "Square root node: out = sqrt(|input|)".to_string(),
+    // or (Effects)
+    map.insert("or".to_string(), GeneratedNodeMetadata {
+        name: "or".to_string(),
+        description: "Logical OR node: out = (a > threshold || b > threshold) ? 1.0 : 0.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~gate1: 0.7
+~gate2: 0.2
+~combined: or ~gate1 ~gate2".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of first signal (threshold: 0.5)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "input_b".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "NodeId of second signal (threshold: 0.5)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // asrenvelope (Effects)
-    map.insert("asrenvelope".to_string(), GeneratedNodeMetadata {
-        name: "asrenvelope".to_string(),
-        description: "ASR Envelope Generator Node".to_string(),
+    // oscillator (Effects)
+    map.insert("oscillator".to_string(), GeneratedNodeMetadata {
+        name: "oscillator".to_string(),
+        description: "Oscillator node with pattern-controlled frequency".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~osc: oscillator 440 sine".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "gate_input".to_string(),
+                name: "freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "attack_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "waveform".to_string(),
+                param_type: "Waveform".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Waveform type (Sine, Saw, Square, Triangle)".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // pan (Effects)
+    map.insert("pan".to_string(), GeneratedNodeMetadata {
+        name: "pan".to_string(),
+        description: "Pan node with equal-power panning law".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: sine 440
+~panned: ~signal # pan -0.5".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "sustain_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of mono signal to pan".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "release_input".to_string(),
+                name: "pan_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of pan position (-1.0 = left, 0.0 = center, 1.0 = right)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // dattorroreverb (Effects)
-    map.insert("dattorroreverb".to_string(), GeneratedNodeMetadata {
-        name: "dattorroreverb".to_string(),
-        description: "Dattorro reverb node with pattern-controlled parameters".to_string(),
+    // parametriceq (Effects)
+    map.insert("parametriceq".to_string(), GeneratedNodeMetadata {
+        name: "parametriceq".to_string(),
+        description: "Parametric EQ node with 3-band control".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~eq: ~signal # parametric_eq 100 3 1000 0 1 8000 2".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to equalize".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "size".to_string(),
+                name: "low_freq".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for low shelf frequency (20-500 Hz)".to_string(),
+                range: Some("20 to 500".to_string()),
             },
             GeneratedParamMetadata {
-                name: "decay".to_string(),
+                name: "low_gain".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for low shelf gain in dB (-24 to +24)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "damping".to_string(),
+                name: "mid_freq".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for mid band frequency (200-8000 Hz)".to_string(),
+                range: Some("200 to 8000".to_string()),
             },
             GeneratedParamMetadata {
-                name: "mix".to_string(),
+                name: "mid_gain".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for mid band gain in dB (-24 to +24)".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // convolution (Effects)
-    map.insert("convolution".to_string(), GeneratedNodeMetadata {
-        name: "convolution".to_string(),
-        description: "Convolution node with FFT-based processing".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "mid_q".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for mid band Q factor (0.1-10.0)".to_string(),
+                range: Some("0.1 to 10.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "impulse_response".to_string(),
-                param_type: "Arc".to_string(),
+                name: "high_freq".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for high shelf frequency (2000-20000 Hz)".to_string(),
+                range: Some("2000 to 20000".to_string()),
             },
             GeneratedParamMetadata {
-                name: "mix".to_string(),
+                name: "high_gain".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId for high shelf gain in dB (-24 to +24)".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // patternevaluator (Effects)
+    map.insert("patternevaluator".to_string(), GeneratedNodeMetadata {
+        name: "patternevaluator".to_string(),
+        description: "Pattern Evaluator Node".to_string(),
+        category: "Effects".to_string(),
+        example: "~freq: run 8
+~osc: sine ~freq".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
+                name: "pattern".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Pattern to evaluate (numeric values)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // mod (Effects)
-    map.insert("mod".to_string(), GeneratedNodeMetadata {
-        name: "mod".to_string(),
-        description: "Modulo node: out = a % b".to_string(),
+    // peakdetector (Effects)
+    map.insert("peakdetector".to_string(), GeneratedNodeMetadata {
+        name: "peakdetector".to_string(),
+        description: "Peak detector node: tracks peak values with decay".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~peak: peak_detector ~signal 0.1".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to detect peaks from".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "decay_time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of decay time in seconds (0.01-1.0 typical)".to_string(),
+                range: Some("0.01 to 1.0".to_string()),
             },
         ],
     });
 
-    // rlpf (Effects)
-    map.insert("rlpf".to_string(), GeneratedNodeMetadata {
-        name: "rlpf".to_string(),
-        description: "Resonant low-pass filter node with pattern-controlled cutoff and resonance".to_string(),
+    // phaser (Effects)
+    map.insert("phaser".to_string(), GeneratedNodeMetadata {
+        name: "phaser".to_string(),
+        description: "Phaser node with pattern-controlled rate, depth, feedback, and stages".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~phased: ~signal # phaser 0.5 0.8 0.5 4".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to phase".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "freq".to_string(),
+                name: "rate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of LFO rate in Hz (0.1-10 typical)".to_string(),
+                range: Some("0.1 to 10".to_string()),
             },
             GeneratedParamMetadata {
-                name: "res".to_string(),
+                name: "depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of modulation depth (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "feedback_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "NodeId of feedback amount (0.0-0.95)".to_string(),
+                range: Some("0.0 to 0.95".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "stages".to_string(),
+                param_type: "usize".to_string(),
+                default: None,
+                description: "Number of all-pass stages (4-8 typical, more = richer)".to_string(),
+                range: Some("4 to 8".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // frequencyshifter (Effects)
-    map.insert("frequencyshifter".to_string(), GeneratedNodeMetadata {
-        name: "frequencyshifter".to_string(),
-        description: "Frequency shifter node with pattern-controlled shift amount".to_string(),
+    // pingpongdelay (Effects)
+    map.insert("pingpongdelay".to_string(), GeneratedNodeMetadata {
+        name: "pingpongdelay".to_string(),
+        description: "Ping-pong delay node: stereo bouncing delay effect".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~delayed: ~signal # pingpong_delay 0.125 0.5 0.8".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to delay".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "shift_hz_input".to_string(),
+                name: "time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of delay time in seconds (0.05-1.0 typical)".to_string(),
+                range: Some("0.05 to 1.0".to_string()),
             },
-        ],
-    });
-
-    // constant (Effects)
-    map.insert("constant".to_string(), GeneratedNodeMetadata {
-        name: "constant".to_string(),
-        description: "Constant value node".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "value".to_string(),
-                param_type: "f32".to_string(),
+                name: "feedback_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of feedback amount (0.0-0.95)".to_string(),
+                range: Some("0.0 to 0.95".to_string()),
             },
-        ],
-    });
-
-    // fmoscillator (Effects)
-    map.insert("fmoscillator".to_string(), GeneratedNodeMetadata {
-        name: "fmoscillator".to_string(),
-        description: "FM Oscillator with pattern-controlled parameters".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "carrier_freq_input".to_string(),
+                name: "stereo_width_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of stereo width for ping-pong effect (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "modulator_freq_input".to_string(),
+                name: "mix_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "mod_index_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "channel".to_string(),
+                param_type: "bool".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // distortion (Effects)
-    map.insert("distortion".to_string(), GeneratedNodeMetadata {
-        name: "distortion".to_string(),
-        description: "Distortion node: soft clipping waveshaper".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "max_delay".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // pinknoise (Effects)
+    map.insert("pinknoise".to_string(), GeneratedNodeMetadata {
+        name: "pinknoise".to_string(),
+        description: "Pink noise node: generates 1/f spectrum noise scaled by amplitude".to_string(),
+        category: "Effects".to_string(),
+        example: "~pink: pink_noise 0.5".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "drive_input".to_string(),
+                name: "amplitude_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of amplitude control signal (0.0-1.0 typical)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
+        ],
+    });
+
+    // pitch (Effects)
+    map.insert("pitch".to_string(), GeneratedNodeMetadata {
+        name: "pitch".to_string(),
+        description: "Pitch detection node".to_string(),
+        category: "Effects".to_string(),
+        example: "~voice: sample \"voice\"
+~detected_freq: pitch ~voice
+~synth: sine ~detected_freq * 2".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "mix_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to analyze for pitch".to_string(),
+                range: None,
             },
         ],
     });
 
-    // fold (Effects)
-    map.insert("fold".to_string(), GeneratedNodeMetadata {
-        name: "fold".to_string(),
-        description: "Fold node: out = fold(input, min, max)".to_string(),
+    // pitchshifter (Effects)
+    map.insert("pitchshifter".to_string(), GeneratedNodeMetadata {
+        name: "pitchshifter".to_string(),
+        description: "Pitch shifter node with pattern-controlled shift amount and window size".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~shifted: ~signal # pitch_shifter 12 0.02".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to pitch shift".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "min_input".to_string(),
+                name: "shift_semitones_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of pitch shift amount in semitones".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "max_input".to_string(),
+                name: "window_size_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of window size in seconds (0.01-0.1 typical)".to_string(),
+                range: Some("0.01 to 0.1".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // bandpassfilter (Effects)
-    map.insert("bandpassfilter".to_string(), GeneratedNodeMetadata {
-        name: "bandpassfilter".to_string(),
-        description: "BandPass filter node with pattern-controlled center frequency and Q".to_string(),
+    // pmoscillator (Effects)
+    map.insert("pmoscillator".to_string(), GeneratedNodeMetadata {
+        name: "pmoscillator".to_string(),
+        description: "PM Oscillator with pattern-controlled parameters".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~carrier: 440
+~lfo: sine 5
+~pm: pm_oscillator ~carrier ~lfo 5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "carrier_freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of carrier frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "center_input".to_string(),
+                name: "modulator_freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of modulator frequency in Hz (usually LFO)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "q_input".to_string(),
+                name: "mod_index_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of modulation index depth (0.0-20.0 typical)".to_string(),
+                range: Some("0.0 to 20.0".to_string()),
             },
         ],
     });
 
-    // greaterthan (Effects)
-    map.insert("greaterthan".to_string(), GeneratedNodeMetadata {
-        name: "greaterthan".to_string(),
-        description: "Greater Than node: out = (a > b) ? 1.0 : 0.0".to_string(),
+    // polybleposc (Effects)
+    map.insert("polybleposc".to_string(), GeneratedNodeMetadata {
+        name: "polybleposc".to_string(),
+        description: "Anti-aliased oscillator using PolyBLEP technique".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~osc: polyblep_osc 440 saw".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "frequency".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "waveform".to_string(),
+                param_type: "PolyBLEPWaveform".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Waveform type (Saw, Square, Triangle)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // random (Effects)
-    map.insert("random".to_string(), GeneratedNodeMetadata {
-        name: "random".to_string(),
-        description: "Random value node: generates random values scaled by amplitude".to_string(),
+    // power (Effects)
+    map.insert("power".to_string(), GeneratedNodeMetadata {
+        name: "power".to_string(),
+        description: "Power node: out = input ^ exponent".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~warped: power ~signal 2".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "amplitude_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of base signal".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "exponent_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "NodeId of exponent value (0.0-3.0 typical)".to_string(),
+                range: Some("0.0 to 3.0".to_string()),
             },
         ],
     });
 
-    // rhpf (Effects)
-    map.insert("rhpf".to_string(), GeneratedNodeMetadata {
-        name: "rhpf".to_string(),
-        description: "Resonant HighPass Filter node with pattern-controlled cutoff and resonance".to_string(),
+    // pulse (Effects)
+    map.insert("pulse".to_string(), GeneratedNodeMetadata {
+        name: "pulse".to_string(),
+        description: "Pulse wave oscillator with pattern-controlled frequency and width".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo: sine 2
+~pulse: pulse 220 ~lfo".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "cutoff_input".to_string(),
+                name: "freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing frequency in Hz".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "res_input".to_string(),
+                name: "width_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing pulse width (0.0-1.0, 0.5 = square)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // transient (Effects)
-    map.insert("transient".to_string(), GeneratedNodeMetadata {
-        name: "transient".to_string(),
-        description: "Transient detector node: detects rapid amplitude changes".to_string(),
+    // quantize (Effects)
+    map.insert("quantize".to_string(), GeneratedNodeMetadata {
+        name: "quantize".to_string(),
+        description: "Quantize node: Reduces bit depth".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~8bit: ~signal # quantize 8.0".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to quantize".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "threshold_input".to_string(),
+                name: "bits_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of bit depth (1.0 to 16.0, clamped to this range)".to_string(),
+                range: Some("1.0 to 16.0".to_string()),
             },
         ],
     });
 
-    // delay (Effects)
-    map.insert("delay".to_string(), GeneratedNodeMetadata {
-        name: "delay".to_string(),
-        description: "Delay node with pattern-controlled delay time".to_string(),
+    // quantizer (Effects)
+    map.insert("quantizer".to_string(), GeneratedNodeMetadata {
+        name: "quantizer".to_string(),
+        description: "Quantizer node: snap values to nearest step".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo: sine 0.25
+~quantized: ~lfo # quantizer 1.0".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to quantize".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "delay_time_input".to_string(),
+                name: "step_size_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "max_delay".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId of step size (quantization grid, prevents division by zero with min 0.0001)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // blip (Effects)
-    map.insert("blip".to_string(), GeneratedNodeMetadata {
-        name: "blip".to_string(),
-        description: "Band-limited impulse train generator".to_string(),
+    // random (Effects)
+    map.insert("random".to_string(), GeneratedNodeMetadata {
+        name: "random".to_string(),
+        description: "Random value node: generates random values scaled by amplitude".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~noise: random 0.5
+~filtered: ~noise # lpf 5000 0.8".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "freq_input".to_string(),
+                name: "amplitude_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of the amplitude control signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // slewlimiter (Effects)
-    map.insert("slewlimiter".to_string(), GeneratedNodeMetadata {
-        name: "slewlimiter".to_string(),
-        description: "Slew limiter node: limits rate of change in a signal".to_string(),
+    // rectifier (Effects)
+    map.insert("rectifier".to_string(), GeneratedNodeMetadata {
+        name: "rectifier".to_string(),
+        description: "Rectifier node: performs full-wave or half-wave rectification".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~rectified: ~signal # rectifier".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "rise_time_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId of input signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "fall_time_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "mode".to_string(),
+                param_type: "RectifierMode".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Rectification mode (FullWave uses absolute value, HalfWave clips negatives to zero)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // wrap (Effects)
-    map.insert("wrap".to_string(), GeneratedNodeMetadata {
-        name: "wrap".to_string(),
-        description: "Wrap node: out = wrap(input, min, max)".to_string(),
+    // resample (Effects)
+    map.insert("resample".to_string(), GeneratedNodeMetadata {
+        name: "resample".to_string(),
+        description: "Resample node with pattern-controlled resampling ratio".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~sample: sample 0.5
+~pitched: ~sample # resample 1.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing the signal to resample".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "min_input".to_string(),
+                name: "ratio_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing resampling ratio (0.5 = half speed, 2.0 = double speed)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "max_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // parametriceq (Effects)
-    map.insert("parametriceq".to_string(), GeneratedNodeMetadata {
-        name: "parametriceq".to_string(),
-        description: "Parametric EQ node with 3-band control".to_string(),
+    // resonz (Effects)
+    map.insert("resonz".to_string(), GeneratedNodeMetadata {
+        name: "resonz".to_string(),
+        description: "Resonz filter node with pattern-controlled center frequency and bandwidth".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~noise: random 0.5
+~filtered: ~noise # resonz 440 0.01".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "low_freq".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "low_gain".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "mid_freq".to_string(),
+                name: "freq".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing center frequency in Hz (20 to 20000)".to_string(),
+                range: Some("20 to 20000".to_string()),
             },
             GeneratedParamMetadata {
-                name: "mid_gain".to_string(),
+                name: "rq".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing bandwidth ratio (0.001 to 1.0, where smaller = narrower)".to_string(),
+                range: Some("0.001 to 1.0".to_string()),
             },
+        ],
+    });
+
+    // reverb (Effects)
+    map.insert("reverb".to_string(), GeneratedNodeMetadata {
+        name: "reverb".to_string(),
+        description: "Reverb node with pattern-controlled parameters".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: sine 440
+~reverbed: ~signal # reverb 0.7 0.5 0.3".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "mid_q".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing the signal to process".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "high_freq".to_string(),
+                name: "room_size".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing room size (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "high_gain".to_string(),
+                name: "damping".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-        ],
-    });
-
-    // exp (Effects)
-    map.insert("exp".to_string(), GeneratedNodeMetadata {
-        name: "exp".to_string(),
-        description: "Exponential node: out = e^input".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
+                description: "NodeId providing damping amount (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
+            },
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "wet".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing wet/dry mix (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // addition (Effects)
-    map.insert("addition".to_string(), GeneratedNodeMetadata {
-        name: "addition".to_string(),
-        description: "Addition node: out = a + b".to_string(),
+    // rhpf (Effects)
+    map.insert("rhpf".to_string(), GeneratedNodeMetadata {
+        name: "rhpf".to_string(),
+        description: "Resonant HighPass Filter node with pattern-controlled cutoff and resonance".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: saw 55
+~filtered: ~signal # rhpf 500 5.0".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing audio signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "cutoff_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing cutoff frequency (Hz, 20-20000)".to_string(),
+                range: Some("20 to 20000".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "res_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "NodeId providing resonance/Q factor (0.1-20.0)".to_string(),
+                range: Some("0.1 to 20.0".to_string()),
             },
         ],
     });
 
-    // sign (Effects)
-    map.insert("sign".to_string(), GeneratedNodeMetadata {
-        name: "sign".to_string(),
-        description: "Sign node: out = sign(input)".to_string(),
+    // ringmod (Effects)
+    map.insert("ringmod".to_string(), GeneratedNodeMetadata {
+        name: "ringmod".to_string(),
+        description: "Ring modulation node: out = carrier * modulator".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~carrier: sine 440
+~mod: sine 220
+~bell: ~carrier * ~mod".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "carrier_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of carrier signal (typically audio)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "modulator_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "NodeId of modulator signal (LFO or audio)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // pmoscillator (Effects)
-    map.insert("pmoscillator".to_string(), GeneratedNodeMetadata {
-        name: "pmoscillator".to_string(),
-        description: "PM Oscillator with pattern-controlled parameters".to_string(),
+    // rlpf (Effects)
+    map.insert("rlpf".to_string(), GeneratedNodeMetadata {
+        name: "rlpf".to_string(),
+        description: "Resonant low-pass filter node with pattern-controlled cutoff and resonance".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~synth: saw 55
+~filtered: ~synth # rlpf 500 0.8".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "carrier_freq_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "modulator_freq_input".to_string(),
+                name: "freq".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing cutoff frequency in Hz (20 to 20000)".to_string(),
+                range: Some("20 to 20000".to_string()),
             },
             GeneratedParamMetadata {
-                name: "mod_index_input".to_string(),
+                name: "res".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing resonance amount (0.0 to 1.0, where 1.0 = self-oscillation)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // mix (Effects)
-    map.insert("mix".to_string(), GeneratedNodeMetadata {
-        name: "mix".to_string(),
-        description: "Mix node: weighted sum of N inputs".to_string(),
+    // rms (Effects)
+    map.insert("rms".to_string(), GeneratedNodeMetadata {
+        name: "rms".to_string(),
+        description: "RMS calculation node with pattern-controlled window size".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~level: ~signal # rms 0.1".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "inputs".to_string(),
-                param_type: "Vec".to_string(),
+                name: "input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing the signal to analyze".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "weights".to_string(),
-                param_type: "Vec".to_string(),
+                name: "window_time_input".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing window time in seconds".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // quantize (Effects)
-    map.insert("quantize".to_string(), GeneratedNodeMetadata {
-        name: "quantize".to_string(),
-        description: "Quantize node: Reduces bit depth".to_string(),
+    // sampleandhold (Effects)
+    map.insert("sampleandhold".to_string(), GeneratedNodeMetadata {
+        name: "sampleandhold".to_string(),
+        description: "Sample-and-hold node: captures input when trigger crosses from negative to positive".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~noise: random 1.0
+~clock: square 4.0
+~stepped: ~noise # sample_hold ~clock".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to sample".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "bits_input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of trigger signal (zero crossing triggers sampling)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // adenvelope (Effects)
-    map.insert("adenvelope".to_string(), GeneratedNodeMetadata {
-        name: "adenvelope".to_string(),
-        description: "AD Envelope Generator Node".to_string(),
+    // samplepattern (Effects)
+    map.insert("samplepattern".to_string(), GeneratedNodeMetadata {
+        name: "samplepattern".to_string(),
+        description: "Sample Pattern Node".to_string(),
         category: "Effects".to_string(),
         example: "".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "pattern".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Pattern of sample names to play".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "attack_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "voice_manager".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Shared voice manager for sample playback".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "decay_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "sample_bank".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample bank for loading samples (wrapped in Mutex)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // power (Effects)
-    map.insert("power".to_string(), GeneratedNodeMetadata {
-        name: "power".to_string(),
-        description: "Power node: out = input ^ exponent".to_string(),
+    // sampleplayback (Effects)
+    map.insert("sampleplayback".to_string(), GeneratedNodeMetadata {
+        name: "sampleplayback".to_string(),
+        description: "Sample playback node with trigger and speed control".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~trigger: square 4.0
+~playback: sample_playback ~trigger 1.0".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing trigger signal (>0.5 = start playback, rising edge)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "exponent_input".to_string(),
+                name: "speed_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing playback speed (1.0 = normal, 2.0 = double speed)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "sample_data".to_string(),
+                param_type: "Arc".to_string(),
+                default: None,
+                description: "Arc-wrapped sample data to play back".to_string(),
+                range: None,
             },
         ],
     });
 
-    // when (Effects)
-    map.insert("when".to_string(), GeneratedNodeMetadata {
-        name: "when".to_string(),
-        description: "When node: conditional signal router".to_string(),
+    // scalequantize (Effects)
+    map.insert("scalequantize".to_string(), GeneratedNodeMetadata {
+        name: "scalequantize".to_string(),
+        description: "Scale Quantize node: map frequencies to musical scales".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~lfo: sine 0.25
+~quantized: ~lfo # scale_quantize 440 major".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "condition".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of frequency to quantize (in Hz)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "true_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "scale".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Arc-wrapped vector of semitone offsets (e.g., [0, 2, 4, 5, 7, 9, 11] for major)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "false_input".to_string(),
+                name: "root".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of root frequency in Hz".to_string(),
+                range: None,
             },
         ],
     });
 
-    // slice (Effects)
-    map.insert("slice".to_string(), GeneratedNodeMetadata {
-        name: "slice".to_string(),
-        description: "Slice node with trigger and position control".to_string(),
+    // schmidt (Effects)
+    map.insert("schmidt".to_string(), GeneratedNodeMetadata {
+        name: "schmidt".to_string(),
+        description: "Schmidt trigger node: comparator with hysteresis".to_string(),
         category: "Effects".to_string(),
         example: "".to_string(),
         params: vec![
@@ -2950,196 +3328,208 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "NodeId".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
+                name: "high_threshold_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "slice_start_input".to_string(),
+                name: "low_threshold_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
                 description: "".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // segments (Effects)
+    map.insert("segments".to_string(), GeneratedNodeMetadata {
+        name: "segments".to_string(),
+        description: "Multi-Segment Envelope Generator Node".to_string(),
+        category: "Effects".to_string(),
+        example: "~trigger: square 4.0
+~env: trigger # segments [(1.0, 0.01), (0.7, 0.1), (0.0, 0.2)]".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "slice_end_input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing trigger signal (>0.5 = trigger, rising edge)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "segments".to_string(),
+                param_type: "Vec".to_string(),
+                default: None,
+                description: "Vector of segments defining the envelope shape".to_string(),
+                range: None,
             },
         ],
     });
 
-    // svf (Effects)
-    map.insert("svf".to_string(), GeneratedNodeMetadata {
-        name: "svf".to_string(),
-        description: "State Variable Filter node with multiple filter modes".to_string(),
+    // sidechaincompressor (Effects)
+    map.insert("sidechaincompressor".to_string(), GeneratedNodeMetadata {
+        name: "sidechaincompressor".to_string(),
+        description: "Sidechain compressor node: compression controlled by external signal".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~kick: s \"bd*4\"
+~bass: saw 55
+~ducked: ~bass # sidechain_comp ~kick -10 4.0 0.01 0.1".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "main_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to compress (e.g., bass)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "cutoff".to_string(),
+                name: "sidechain_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal controlling compression (e.g., kick)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "q".to_string(),
+                name: "threshold_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "mode".to_string(),
-                param_type: "SVFMode".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Threshold in dB (e.g., -10.0)".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // division (Effects)
-    map.insert("division".to_string(), GeneratedNodeMetadata {
-        name: "division".to_string(),
-        description: "Division node: out = a / b".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "ratio_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Compression ratio (1=none, 4=typical, 20=heavy)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "attack_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Attack time in seconds".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // tan (Effects)
-    map.insert("tan".to_string(), GeneratedNodeMetadata {
-        name: "tan".to_string(),
-        description: "Tangent node: out = tan(input)".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "release_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Release time in seconds".to_string(),
+                range: None,
             },
         ],
     });
 
-    // xfade (Effects)
-    map.insert("xfade".to_string(), GeneratedNodeMetadata {
-        name: "xfade".to_string(),
-        description: "XFade node: crossfader between two signals".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
-            GeneratedParamMetadata {
-                name: "input_a".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
+    // sign (Effects)
+    map.insert("sign".to_string(), GeneratedNodeMetadata {
+        name: "sign".to_string(),
+        description: "Sign node: out = sign(input)".to_string(),
+        category: "Effects".to_string(),
+        example: "~sine: sine 440
+~square: ~sine # sign".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of input signal".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // sin (Effects)
+    map.insert("sin".to_string(), GeneratedNodeMetadata {
+        name: "sin".to_string(),
+        description: "Sine function node: out = sin(input)".to_string(),
+        category: "Effects".to_string(),
+        example: "~signal: ramp
+~shaped: ~signal # sin".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "position".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "curve".to_string(),
-                param_type: "XFadeCurve".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "NodeId of input signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // bitcrush (Effects)
-    map.insert("bitcrush".to_string(), GeneratedNodeMetadata {
-        name: "bitcrush".to_string(),
-        description: "Bitcrusher node: reduces bit depth and sample rate for lo-fi effects".to_string(),
+    // slewlimiter (Effects)
+    map.insert("slewlimiter".to_string(), GeneratedNodeMetadata {
+        name: "slewlimiter".to_string(),
+        description: "Slew limiter node: limits rate of change in a signal".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: square 2.0
+~smooth: ~signal # slew_limiter 0.1 0.1".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of signal to smooth".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "bits_input".to_string(),
+                name: "rise_time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of rise time signal (in seconds)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "sample_rate_input".to_string(),
+                name: "fall_time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of fall time signal (in seconds)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // lag (Effects)
-    map.insert("lag".to_string(), GeneratedNodeMetadata {
-        name: "lag".to_string(),
-        description: "Lag node: exponential smoothing with time constant control".to_string(),
+    // slice (Effects)
+    map.insert("slice".to_string(), GeneratedNodeMetadata {
+        name: "slice".to_string(),
+        description: "Slice node with trigger and position control".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~input: sine 440
+~trigger: square 2.0
+~sliced: ~input # slice ~trigger 0.25 0.75".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing audio to accumulate".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "lag_time_input".to_string(),
+                name: "trigger_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing trigger signal (>0.5 = start playback)".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // timer (Effects)
-    map.insert("timer".to_string(), GeneratedNodeMetadata {
-        name: "timer".to_string(),
-        description: "Timer node: measures elapsed time since last reset".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
+                name: "slice_start_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing slice start position (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "slice_end_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "NodeId providing slice end position (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
@@ -3149,205 +3539,243 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
         name: "spectralfreeze".to_string(),
         description: "Spectral freeze node with pattern-controlled freeze trigger and blur".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440
+~frozen: ~signal # spectral_freeze 1.0 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing the signal to freeze".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "freeze_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing freeze trigger (>0.5 = frozen, ≤0.5 = passthrough)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "blur_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId providing spectral blur amount (0.0 = no blur, 1.0 = max blur)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate in Hz (usually 44100.0)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // log (Effects)
-    map.insert("log".to_string(), GeneratedNodeMetadata {
-        name: "log".to_string(),
-        description: "Natural logarithm node: out = ln(max(|input|, 1e-10))".to_string(),
+    // squareroot (Effects)
+    map.insert("squareroot".to_string(), GeneratedNodeMetadata {
+        name: "squareroot".to_string(),
+        description: "Square root node: out = sqrt(|input|)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~signal: sine 440 * sine 440
+~rooted: ~signal # sqrt".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "NodeId of input signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // gain (Effects)
-    map.insert("gain".to_string(), GeneratedNodeMetadata {
-        name: "gain".to_string(),
-        description: "Gain node: out = input * gain".to_string(),
+    // stereomerger (Effects)
+    map.insert("stereomerger".to_string(), GeneratedNodeMetadata {
+        name: "stereomerger".to_string(),
+        description: "Merge two mono signals into stereo (currently outputs mono mix)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~left: sine 440
+~right: saw 220
+out: ~left # merger ~right".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "left".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Left channel input signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "gain_input".to_string(),
+                name: "right".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Right channel input signal".to_string(),
+                range: None,
             },
         ],
     });
 
-    // pitchshifter (Effects)
-    map.insert("pitchshifter".to_string(), GeneratedNodeMetadata {
-        name: "pitchshifter".to_string(),
-        description: "Pitch shifter node with pattern-controlled shift amount and window size".to_string(),
+    // stereosplitter (Effects)
+    map.insert("stereosplitter".to_string(), GeneratedNodeMetadata {
+        name: "stereosplitter".to_string(),
+        description: "Stereo splitter node - identity passthrough with stereo semantics".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~reverb: sine 440 # reverb 0.5
+~stereo: ~reverb # splitter
+out: ~stereo".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "stereo_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Interleaved stereo input signal".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // stereowidener (Effects)
+    map.insert("stereowidener".to_string(), GeneratedNodeMetadata {
+        name: "stereowidener".to_string(),
+        description: "Stereo widener node with pattern-controlled width".to_string(),
+        category: "Effects".to_string(),
+        example: "~synth: saw 110
+~width: lfo 0.5 0.5 2.0
+out: ~synth # widener ~width".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "shift_semitones_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to widen".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "window_size_input".to_string(),
+                name: "width_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Width amount (0.0=mono, 1.0=normal, 2.0=ultra-wide)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate for filter calculations".to_string(),
+                range: None,
             },
         ],
     });
 
-    // pingpongdelay (Effects)
-    map.insert("pingpongdelay".to_string(), GeneratedNodeMetadata {
-        name: "pingpongdelay".to_string(),
-        description: "Ping-pong delay node: stereo bouncing delay effect".to_string(),
+    // subtraction (Effects)
+    map.insert("subtraction".to_string(), GeneratedNodeMetadata {
+        name: "subtraction".to_string(),
+        description: "Subtraction node: out = a - b".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~sig_a: sine 440
+~sig_b: sine 330
+out: ~sig_a # sub ~sig_b".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Minuend (signal to subtract from)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "time_input".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Subtrahend (signal to subtract)".to_string(),
+                range: None,
             },
+        ],
+    });
+
+    // svf (Effects)
+    map.insert("svf".to_string(), GeneratedNodeMetadata {
+        name: "svf".to_string(),
+        description: "State Variable Filter node with multiple filter modes".to_string(),
+        category: "Effects".to_string(),
+        example: "~osc: saw 110
+~cutoff: lfo 0.5 500 2000
+out: ~osc # svf ~cutoff 2.0 lpf".to_string(),
+        params: vec![
             GeneratedParamMetadata {
-                name: "feedback_input".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to filter".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "stereo_width_input".to_string(),
+                name: "cutoff".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Cutoff frequency in Hz (20-20000)".to_string(),
+                range: Some("20 to 20000".to_string()),
             },
             GeneratedParamMetadata {
-                name: "mix_input".to_string(),
+                name: "q".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "channel".to_string(),
-                param_type: "bool".to_string(),
-                default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "max_delay".to_string(),
-                param_type: "f32".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Resonance factor (0.1-20.0, higher = sharper)".to_string(),
+                range: Some("0.1 to 20.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "sample_rate".to_string(),
-                param_type: "f32".to_string(),
+                name: "mode".to_string(),
+                param_type: "SVFMode".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Filter type (LowPass, HighPass, BandPass, Notch)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // sampleandhold (Effects)
-    map.insert("sampleandhold".to_string(), GeneratedNodeMetadata {
-        name: "sampleandhold".to_string(),
-        description: "Sample-and-hold node: captures input when trigger crosses from negative to positive".to_string(),
+    // tan (Effects)
+    map.insert("tan".to_string(), GeneratedNodeMetadata {
+        name: "tan".to_string(),
+        description: "Tangent node: out = tan(input)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~osc: sine 440
+out: ~osc # tan".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
-                param_type: "NodeId".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Signal to transform (clamped to ±1.5 to avoid asymptotes)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // wavetable (Effects)
-    map.insert("wavetable".to_string(), GeneratedNodeMetadata {
-        name: "wavetable".to_string(),
-        description: "Wavetable oscillator with linear interpolation".to_string(),
+    // tap (Effects)
+    map.insert("tap".to_string(), GeneratedNodeMetadata {
+        name: "tap".to_string(),
+        description: "Tap tempo converter: converts beats to seconds".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~beats: 1.0
+~bpm: 120
+~delay_time: tap ~beats ~bpm
+out: sine 440 # delay ~delay_time 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "freq_input".to_string(),
+                name: "beats".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Beat count (1.0=quarter note, 2.0=half note, etc.)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "wavetable".to_string(),
-                param_type: "Arc".to_string(),
+                name: "bpm".to_string(),
+                param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Tempo in beats per minute (20-300)".to_string(),
+                range: Some("20 to 300".to_string()),
             },
         ],
     });
@@ -3357,373 +3785,533 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
         name: "tapedelay".to_string(),
         description: "Tape delay node: vintage tape echo with wow, flutter, and saturation".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~synth: saw 110
+out: ~synth # tape_delay 0.375 0.6 0.5 0.5 7.0 0.3 0.4 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to delay".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "time_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Delay time in seconds (0.001-max_delay)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "feedback_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Feedback amount (0.0-0.95)".to_string(),
+                range: Some("0.0 to 0.95".to_string()),
             },
             GeneratedParamMetadata {
                 name: "wow_rate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Wow rate in Hz (0.1-2.0)".to_string(),
+                range: Some("0.1 to 2.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "wow_depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Wow depth (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "flutter_rate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Flutter rate in Hz (5.0-10.0)".to_string(),
+                range: Some("5.0 to 10.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "flutter_depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Flutter depth (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "saturation_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Tape saturation (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "mix_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Wet/dry mix (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
             GeneratedParamMetadata {
                 name: "max_delay".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Maximum delay time (buffer size)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "sample_rate".to_string(),
                 param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate (Hz)".to_string(),
+                range: None,
+            },
+        ],
+    });
+
+    // timer (Effects)
+    map.insert("timer".to_string(), GeneratedNodeMetadata {
+        name: "timer".to_string(),
+        description: "Timer node: measures elapsed time since last reset".to_string(),
+        category: "Effects".to_string(),
+        example: "~beat: lfo 1.0 0 1
+~time: timer ~beat
+out: sine 440 * (cos (time * tau))".to_string(),
+        params: vec![
+            GeneratedParamMetadata {
+                name: "trigger_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Trigger signal (rising edge resets timer)".to_string(),
+                range: None,
+            },
+        ],
+    });
+
+    // transient (Effects)
+    map.insert("transient".to_string(), GeneratedNodeMetadata {
+        name: "transient".to_string(),
+        description: "Transient detector node: detects rapid amplitude changes".to_string(),
+        category: "Effects".to_string(),
+        example: "~drums: s \"bd sn\"
+~trigger: ~drums # transient 0.1
+out: sine (tap 1 120) * ~trigger".to_string(),
+        params: vec![
+            GeneratedParamMetadata {
+                name: "input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Signal to analyze".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "threshold_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Threshold for detection (0.01-0.5 typical)".to_string(),
+                range: Some("0.01 to 0.5".to_string()),
             },
         ],
     });
 
-    // autopan (Effects)
-    map.insert("autopan".to_string(), GeneratedNodeMetadata {
-        name: "autopan".to_string(),
-        description: "Auto-pan node with pattern-controlled rate, depth, and waveform selection".to_string(),
+    // tremolo (Effects)
+    map.insert("tremolo".to_string(), GeneratedNodeMetadata {
+        name: "tremolo".to_string(),
+        description: "Tremolo effect with pattern-controlled rate and depth".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~synth: sine 440
+out: ~synth # tremolo 4.0 0.7".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to modulate".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "rate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "LFO rate in Hz (0.5-20 typical)".to_string(),
+                range: Some("0.5 to 20".to_string()),
             },
             GeneratedParamMetadata {
                 name: "depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "waveform".to_string(),
-                param_type: "AutoPanWaveform".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "Modulation depth (0.0-1.0)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // lowpassfilter (Effects)
-    map.insert("lowpassfilter".to_string(), GeneratedNodeMetadata {
-        name: "lowpassfilter".to_string(),
-        description: "Low-pass filter node with pattern-controlled cutoff and Q".to_string(),
+    // vco (Effects)
+    map.insert("vco".to_string(), GeneratedNodeMetadata {
+        name: "vco".to_string(),
+        description: "Analog-style VCO with polyBLEP anti-aliasing".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~freq: lfo 0.5 110 220
+~pw: 0.5
+out: vco ~freq pulse ~pw".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Frequency in Hz (can be modulated)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "cutoff_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "waveform".to_string(),
+                param_type: "VCOWaveform".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Waveform type (Saw, Pulse, Triangle, Sine)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "q_input".to_string(),
+                name: "pulse_width".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Pulse width (0.0-1.0, used for Pulse only)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
             },
         ],
     });
 
-    // clamp (Effects)
-    map.insert("clamp".to_string(), GeneratedNodeMetadata {
-        name: "clamp".to_string(),
-        description: "Clamp node: out = input.clamp(min, max)".to_string(),
+    // vibrato (Effects)
+    map.insert("vibrato".to_string(), GeneratedNodeMetadata {
+        name: "vibrato".to_string(),
+        description: "Vibrato effect with pattern-controlled rate and depth".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~synth: sine 440
+out: ~synth # vibrato 5.0 0.005".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Audio signal to modulate".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "min_input".to_string(),
+                name: "rate_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "LFO rate in Hz (3-10 typical)".to_string(),
+                range: Some("3 to 10".to_string()),
             },
             GeneratedParamMetadata {
-                name: "max_input".to_string(),
+                name: "depth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Modulation depth in seconds (0.002-0.01 typical)".to_string(),
+                range: Some("0.002 to 0.01".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "max_depth".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Maximum depth (buffer size)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Sample rate (Hz)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // pan (Effects)
-    map.insert("pan".to_string(), GeneratedNodeMetadata {
-        name: "pan".to_string(),
-        description: "Pan node with equal-power panning law".to_string(),
+    // vocoder (Effects)
+    map.insert("vocoder".to_string(), GeneratedNodeMetadata {
+        name: "vocoder".to_string(),
+        description: "Vocoder node with pattern-controlled carrier, modulator, bands, and bandwidth".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~carrier: saw 110
+~modulator: s \"speech.wav\"
+out: ~carrier # vocoder ~modulator 16 100".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "carrier".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to be vocoded (usually synth)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "pan_input".to_string(),
+                name: "modulator".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal providing spectral envelope (usually voice/speech)".to_string(),
+                range: None,
             },
-        ],
-    });
-
-    // fmcrossmod (Effects)
-    map.insert("fmcrossmod".to_string(), GeneratedNodeMetadata {
-        name: "fmcrossmod".to_string(),
-        description: "FM Cross-Modulation node: carrier modulated by any audio signal".to_string(),
-        category: "Effects".to_string(),
-        example: "".to_string(),
-        params: vec![
             GeneratedParamMetadata {
-                name: "carrier_input".to_string(),
+                name: "num_bands_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Number of parallel filters (16-32 typical)".to_string(),
+                range: Some("16 to 32".to_string()),
             },
             GeneratedParamMetadata {
-                name: "modulator_input".to_string(),
+                name: "bandwidth_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Filter bandwidth (0.5-2.0 typical)".to_string(),
+                range: Some("0.5 to 2.0".to_string()),
             },
             GeneratedParamMetadata {
-                name: "mod_depth_input".to_string(),
-                param_type: "NodeId".to_string(),
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Sample rate (Hz)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // max (Effects)
-    map.insert("max".to_string(), GeneratedNodeMetadata {
-        name: "max".to_string(),
-        description: "Max node: out = max(a, b)".to_string(),
+    // waveguide (Effects)
+    map.insert("waveguide".to_string(), GeneratedNodeMetadata {
+        name: "waveguide".to_string(),
+        description: "Digital waveguide node for physical modeling".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~strike: transient (s \"kick\") 0.1
+~freq: 110
+out: ~strike # waveguide ~freq 0.95 0.5".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "excitation_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Trigger/excitation signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "frequency_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Fundamental frequency in Hz".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "decay_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Decay factor (0.0=fast, 1.0=infinite sustain)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "brightness_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Tone brightness (0.0=dark, 1.0=bright)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "sample_rate".to_string(),
+                param_type: "f32".to_string(),
+                default: None,
+                description: "Sample rate (Hz)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // segments (Effects)
-    map.insert("segments".to_string(), GeneratedNodeMetadata {
-        name: "segments".to_string(),
-        description: "Multi-Segment Envelope Generator Node".to_string(),
+    // wavetable (Effects)
+    map.insert("wavetable".to_string(), GeneratedNodeMetadata {
+        name: "wavetable".to_string(),
+        description: "Wavetable oscillator with linear interpolation".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~freq: lfo 0.5 110 220
+out: wavetable ~freq sine 256".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "trigger_input".to_string(),
+                name: "freq_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Frequency in Hz (can be modulated)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "segments".to_string(),
-                param_type: "Vec".to_string(),
+                name: "wavetable".to_string(),
+                param_type: "Arc".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Pre-computed waveform data (Arc-wrapped for sharing)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // noise (Effects)
-    map.insert("noise".to_string(), GeneratedNodeMetadata {
-        name: "noise".to_string(),
-        description: "White noise node: generates random values scaled by amplitude".to_string(),
+    // when (Effects)
+    map.insert("when".to_string(), GeneratedNodeMetadata {
+        name: "when".to_string(),
+        description: "When node: conditional signal router".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~gate: lfo 1.0 0 1
+~on_signal: sine 440
+~off_signal: 0
+out: when ~gate ~on_signal ~off_signal".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "amplitude_input".to_string(),
+                name: "condition".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Condition signal (compared against 0.5 threshold)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "true_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Signal when condition > 0.5".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "false_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Signal when condition <= 0.5".to_string(),
+                range: None,
             },
         ],
     });
 
-    // min (Effects)
-    map.insert("min".to_string(), GeneratedNodeMetadata {
-        name: "min".to_string(),
-        description: "Minimum node: out = min(a, b)".to_string(),
+    // wrap (Effects)
+    map.insert("wrap".to_string(), GeneratedNodeMetadata {
+        name: "wrap".to_string(),
+        description: "Wrap node: out = wrap(input, min, max)".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~phase: lfo 1.0 -2 2
+out: wrap ~phase 0 1".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input_a".to_string(),
+                name: "input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Signal to wrap".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "input_b".to_string(),
+                name: "min_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Minimum of range".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "max_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Maximum of range".to_string(),
+                range: None,
             },
         ],
     });
 
-    // xor (Effects)
-    map.insert("xor".to_string(), GeneratedNodeMetadata {
-        name: "xor".to_string(),
-        description: "Logical XOR node: out = (a > threshold != b > threshold) ? 1.0 : 0.0".to_string(),
+    // xfade (Effects)
+    map.insert("xfade".to_string(), GeneratedNodeMetadata {
+        name: "xfade".to_string(),
+        description: "XFade node: crossfader between two signals".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~sig_a: sine 440
+~sig_b: saw 110
+~pos: lfo 0.5 0 1
+out: xfade ~sig_a ~sig_b ~pos equal_power".to_string(),
         params: vec![
             GeneratedParamMetadata {
                 name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "First signal (position=0.0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second signal (position=1.0)".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "position".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Crossfade amount (0.0-1.0, auto-clamped)".to_string(),
+                range: Some("0.0 to 1.0".to_string()),
+            },
+            GeneratedParamMetadata {
+                name: "curve".to_string(),
+                param_type: "XFadeCurve".to_string(),
+                default: None,
+                description: "Crossfade curve (Linear or EqualPower)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // resonz (Effects)
-    map.insert("resonz".to_string(), GeneratedNodeMetadata {
-        name: "resonz".to_string(),
-        description: "Resonz filter node with pattern-controlled center frequency and bandwidth".to_string(),
+    // xline (Effects)
+    map.insert("xline".to_string(), GeneratedNodeMetadata {
+        name: "xline".to_string(),
+        description: "Exponential line generator node".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~trigger: lfo 1.0 0 1
+~freq: xline 440 880 1.0 ~trigger
+out: sine ~freq".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "input".to_string(),
+                name: "start_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Starting value (must be > 0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "freq".to_string(),
+                name: "end_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Ending value (must be > 0)".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "rq".to_string(),
+                name: "duration_input".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Duration in seconds".to_string(),
+                range: None,
+            },
+            GeneratedParamMetadata {
+                name: "trigger_input".to_string(),
+                param_type: "NodeId".to_string(),
+                default: None,
+                description: "Trigger signal (rising edge restarts)".to_string(),
+                range: None,
             },
         ],
     });
 
-    // vco (Effects)
-    map.insert("vco".to_string(), GeneratedNodeMetadata {
-        name: "vco".to_string(),
-        description: "Analog-style VCO with polyBLEP anti-aliasing".to_string(),
+    // xor (Effects)
+    map.insert("xor".to_string(), GeneratedNodeMetadata {
+        name: "xor".to_string(),
+        description: "Logical XOR node: out = (a > threshold != b > threshold) ? 1.0 : 0.0".to_string(),
         category: "Effects".to_string(),
-        example: "".to_string(),
+        example: "~a: lfo 0.5 0 1
+~b: lfo 0.3 0 1
+out: sine 440 * (a # xor b)".to_string(),
         params: vec![
             GeneratedParamMetadata {
-                name: "freq_input".to_string(),
+                name: "input_a".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
-            },
-            GeneratedParamMetadata {
-                name: "waveform".to_string(),
-                param_type: "VCOWaveform".to_string(),
-                default: None,
-                description: "".to_string(),
+                description: "First signal".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
-                name: "pulse_width".to_string(),
+                name: "input_b".to_string(),
                 param_type: "NodeId".to_string(),
                 default: None,
-                description: "".to_string(),
+                description: "Second signal".to_string(),
+                range: None,
             },
         ],
     });
@@ -3740,6 +4328,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "float".to_string(),
                 default: None,
                 description: "Speed multiplier".to_string(),
+                range: None,
             },
         ],
     });
@@ -3756,6 +4345,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "float".to_string(),
                 default: None,
                 description: "Speed/pitch multiplier".to_string(),
+                range: None,
             },
         ],
     });
@@ -3772,6 +4362,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "float".to_string(),
                 default: None,
                 description: "Slowdown factor".to_string(),
+                range: None,
             },
         ],
     });
@@ -3798,12 +4389,14 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "int".to_string(),
                 default: None,
                 description: "Apply function every n cycles".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "f".to_string(),
                 param_type: "function".to_string(),
                 default: None,
                 description: "Function to apply".to_string(),
+                range: None,
             },
         ],
     });
@@ -3820,6 +4413,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "cycles".to_string(),
                 default: None,
                 description: "Rotation amount in cycles".to_string(),
+                range: None,
             },
         ],
     });
@@ -3836,6 +4430,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "cycles".to_string(),
                 default: None,
                 description: "Rotation amount in cycles".to_string(),
+                range: None,
             },
         ],
     });
@@ -3852,6 +4447,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "cycles".to_string(),
                 default: None,
                 description: "Shift amount".to_string(),
+                range: None,
             },
         ],
     });
@@ -3868,6 +4464,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "cycles".to_string(),
                 default: None,
                 description: "Shift amount".to_string(),
+                range: None,
             },
         ],
     });
@@ -3884,6 +4481,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "cycles".to_string(),
                 default: None,
                 description: "Offset in cycles".to_string(),
+                range: None,
             },
         ],
     });
@@ -3900,6 +4498,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "int".to_string(),
                 default: None,
                 description: "Number of repetitions".to_string(),
+                range: None,
             },
         ],
     });
@@ -3916,6 +4515,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "float".to_string(),
                 default: None,
                 description: "Chance to drop each event 0-1".to_string(),
+                range: Some("0 to 1".to_string()),
             },
         ],
     });
@@ -3942,6 +4542,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "function".to_string(),
                 default: None,
                 description: "Function to apply".to_string(),
+                range: None,
             },
         ],
     });
@@ -3958,6 +4559,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "function".to_string(),
                 default: None,
                 description: "Function to apply".to_string(),
+                range: None,
             },
         ],
     });
@@ -3974,6 +4576,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "function".to_string(),
                 default: None,
                 description: "Function to apply".to_string(),
+                range: None,
             },
         ],
     });
@@ -3990,6 +4593,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "function".to_string(),
                 default: None,
                 description: "Function to apply".to_string(),
+                range: None,
             },
         ],
     });
@@ -4006,6 +4610,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "function".to_string(),
                 default: None,
                 description: "Function to apply".to_string(),
+                range: None,
             },
         ],
     });
@@ -4022,6 +4627,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "function".to_string(),
                 default: None,
                 description: "Function to apply".to_string(),
+                range: None,
             },
         ],
     });
@@ -4038,6 +4644,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "pattern".to_string(),
                 default: None,
                 description: "Pattern to layer with".to_string(),
+                range: None,
             },
         ],
     });
@@ -4054,6 +4661,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "pattern".to_string(),
                 default: None,
                 description: "Pattern to append".to_string(),
+                range: None,
             },
         ],
     });
@@ -4070,6 +4678,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "int".to_string(),
                 default: None,
                 description: "Number of repetitions".to_string(),
+                range: None,
             },
         ],
     });
@@ -4086,6 +4695,7 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "int".to_string(),
                 default: None,
                 description: "Number of subdivisions".to_string(),
+                range: None,
             },
         ],
     });
@@ -4102,18 +4712,21 @@ pub fn get_all_functions() -> HashMap<String, GeneratedNodeMetadata> {
                 param_type: "int".to_string(),
                 default: None,
                 description: "Number of echoes including original".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "time".to_string(),
                 param_type: "cycles".to_string(),
                 default: None,
                 description: "Delay between echoes".to_string(),
+                range: None,
             },
             GeneratedParamMetadata {
                 name: "decay".to_string(),
                 param_type: "float".to_string(),
                 default: None,
                 description: "Volume multiplier per echo 0-1".to_string(),
+                range: Some("0 to 1".to_string()),
             },
         ],
     });
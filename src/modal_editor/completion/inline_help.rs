@@ -0,0 +1,271 @@
+//! Inline help for the function call under the cursor
+//!
+//! While the cursor is inside a call such as `plate 0.02 2.5 :decay ...`,
+//! the editor shows a help pane built from `FUNCTION_METADATA`: the one-line
+//! description plus every parameter with its range/unit and default, with the
+//! parameter currently being typed highlighted.
+
+use super::docs::DocLine;
+use super::function_metadata::{FunctionMetadata, FUNCTION_METADATA};
+
+/// The call the cursor is in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CallSite {
+    /// Function name (a `FUNCTION_METADATA` key)
+    pub function: &'static str,
+    /// Index of the parameter being typed, if it can be determined
+    pub active_param: Option<usize>,
+}
+
+/// Find the function call containing `cursor_pos` in `line`
+///
+/// The call is the last known function in the current chain segment: the
+/// text after the last `#` or `$` (or unclosed `(`) before the cursor.
+/// Arguments are counted positionally until a `:name` keyword appears.
+pub fn call_at_cursor(line: &str, cursor_pos: usize) -> Option<CallSite> {
+    if cursor_pos > line.len() || !line.is_char_boundary(cursor_pos) {
+        return None;
+    }
+    let before = &line[..cursor_pos];
+    // Comments have no calls
+    if let Some(comment) = before.find("--") {
+        if !in_string(&before[..comment]) {
+            return None;
+        }
+    }
+
+    let segment = &before[segment_start(before)..];
+    let tokens = split_args(segment);
+    let typing = !segment.is_empty() && !segment.ends_with(char::is_whitespace);
+
+    let func_idx = tokens
+        .iter()
+        .rposition(|t| FUNCTION_METADATA.contains_key(t.as_str()))?;
+    let metadata = FUNCTION_METADATA.get(tokens[func_idx].as_str())?;
+
+    let args = &tokens[func_idx + 1..];
+    if args.is_empty() && typing {
+        // Still typing the function name itself
+        return Some(CallSite {
+            function: metadata.name,
+            active_param: None,
+        });
+    }
+
+    let (complete, current) = if typing {
+        (&args[..args.len() - 1], args.last())
+    } else {
+        (args, None)
+    };
+
+    let mut positional = 0;
+    let mut pending_kwarg: Option<&str> = None;
+    let mut used_kwargs = false;
+    for arg in complete {
+        if let Some(name) = arg.strip_prefix(':') {
+            pending_kwarg = Some(name);
+            used_kwargs = true;
+        } else if pending_kwarg.take().is_none() {
+            positional += 1;
+        }
+    }
+
+    let active_param = match (current, pending_kwarg) {
+        (Some(arg), _) if arg.starts_with(':') => kwarg_index(metadata, &arg[1..]),
+        (_, Some(name)) => kwarg_index(metadata, name),
+        _ if used_kwargs => None,
+        _ => Some(positional).filter(|&i| i < metadata.params.len()),
+    };
+
+    Some(CallSite {
+        function: metadata.name,
+        active_param,
+    })
+}
+
+/// Help pane lines for `site`, each at most `max_width` characters
+pub fn inline_help_lines(site: &CallSite, max_width: usize) -> Vec<DocLine> {
+    let Some(metadata) = FUNCTION_METADATA.get(site.function) else {
+        return Vec::new();
+    };
+    let truncate = |text: String| -> String {
+        if text.chars().count() > max_width {
+            let cut: String = text.chars().take(max_width.saturating_sub(3)).collect();
+            format!("{}...", cut)
+        } else {
+            text
+        }
+    };
+
+    let mut lines = vec![DocLine::header(truncate(format!(
+        "{} - {}",
+        metadata.name, metadata.description
+    )))];
+    for (i, param) in metadata.params.iter().enumerate() {
+        let default = match (param.optional, param.default) {
+            (_, Some(default)) => format!(" [default {}]", default),
+            (true, None) => " [optional]".to_string(),
+            (false, None) => " [required]".to_string(),
+        };
+        let active = site.active_param == Some(i);
+        let text = truncate(format!(
+            "{} :{:<12} {:<8} {}{}",
+            if active { "▶" } else { " " },
+            param.name,
+            param.param_type,
+            param.description,
+            default
+        ));
+        lines.push(if active {
+            DocLine::active_param(text)
+        } else {
+            DocLine::param(text)
+        });
+    }
+    lines
+}
+
+/// Parameter index for a (possibly partial) keyword name
+fn kwarg_index(metadata: &FunctionMetadata, name: &str) -> Option<usize> {
+    metadata
+        .params
+        .iter()
+        .position(|p| p.name == name)
+        .or_else(|| {
+            if name.is_empty() {
+                None
+            } else {
+                metadata
+                    .params
+                    .iter()
+                    .position(|p| p.name.starts_with(name))
+            }
+        })
+}
+
+fn in_string(text: &str) -> bool {
+    text.matches('"').count() % 2 == 1
+}
+
+/// Byte offset where the current chain segment starts
+fn segment_start(text: &str) -> usize {
+    let mut start = 0;
+    let mut enclosing = Vec::new();
+    let mut quoted = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            '(' => {
+                enclosing.push(start);
+                start = i + 1;
+            }
+            ')' => start = enclosing.pop().unwrap_or(0),
+            '#' | '$' => start = i + 1,
+            _ => {}
+        }
+    }
+    start
+}
+
+/// Split a segment into arguments: whitespace-separated, but quoted strings
+/// and parenthesized groups stay whole
+fn split_args(segment: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut depth = 0usize;
+    for c in segment.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if c.is_whitespace() && !quoted && depth == 0 {
+            if !current.is_empty() {
+                tokens.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modal_editor::completion::DocLineStyle;
+
+    fn site(line: &str) -> Option<CallSite> {
+        call_at_cursor(line, line.len())
+    }
+
+    #[test]
+    fn test_kwarg_under_cursor() {
+        let s = site("~verb $ ~dry # plate 0.02 :decay").unwrap();
+        assert_eq!(s.function, "plate");
+        assert_eq!(s.active_param, Some(1));
+
+        // Typing the value of a kwarg keeps it active
+        let s = site("~verb $ ~dry # plate 0.02 :mix 0.").unwrap();
+        assert_eq!(s.active_param, Some(5));
+        let s = site("~verb $ ~dry # plate 0.02 :damping ").unwrap();
+        assert_eq!(s.active_param, Some(3));
+
+        // Partial kwarg names resolve by prefix
+        let s = site("x # plate 0.02 2.5 :diff").unwrap();
+        assert_eq!(s.active_param, Some(2));
+    }
+
+    #[test]
+    fn test_positional_arguments() {
+        assert_eq!(site("out $ saw 55 # lpf ").unwrap().active_param, Some(0));
+        assert_eq!(
+            site("out $ saw 55 # lpf 800").unwrap().active_param,
+            Some(0)
+        );
+        assert_eq!(
+            site("out $ saw 55 # lpf 800 ").unwrap().active_param,
+            Some(1)
+        );
+        // Strings and parenthesized expressions are single arguments
+        let s = site("out $ saw 55 # lpf \"800 1200\" ").unwrap();
+        assert_eq!(s.active_param, Some(1));
+        let s = site("out $ saw 55 # lpf (~lfo * 900 + 1200) ").unwrap();
+        assert_eq!((s.function, s.active_param), ("lpf", Some(1)));
+    }
+
+    #[test]
+    fn test_call_boundaries() {
+        // Cursor on the function name: help without an active parameter
+        let s = site("out $ saw 55 # lpf").unwrap();
+        assert_eq!((s.function, s.active_param), ("lpf", None));
+        // A new chain segment without a known function has no help
+        assert!(site("out $ saw 55 # lpf 800 # ").is_none());
+        // Inside parentheses, the inner call wins
+        let s = site("out $ saw 55 # lpf (sine ").unwrap();
+        assert_eq!(s.function, "sine");
+        // Comments have no calls
+        assert!(site("-- try lpf 800 ").is_none());
+    }
+
+    #[test]
+    fn test_help_lines_list_params_and_defaults() {
+        let s = site("x # plate 0.02 :decay").unwrap();
+        let lines = inline_help_lines(&s, 200);
+        assert!(lines[0].text.starts_with("plate - "));
+        assert_eq!(lines.len(), 1 + FUNCTION_METADATA["plate"].params.len());
+        assert_eq!(lines[2].style, DocLineStyle::ActiveParam);
+        assert!(lines[2].text.contains(":decay"));
+        assert!(lines[2].text.contains("[required]"));
+        assert!(lines[3].text.contains("[default 0.7]"));
+        assert!(inline_help_lines(&s, 20)
+            .iter()
+            .all(|l| l.text.chars().count() <= 20));
+    }
+}
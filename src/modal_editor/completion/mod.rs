@@ -10,6 +10,7 @@ mod discovery;
 mod docs;
 mod function_metadata;
 pub mod generated_metadata;
+mod inline_help;
 mod matching;
 mod parameter;
 mod state;
@@ -20,7 +21,8 @@ pub use docs::{DocLine, DocLineStyle, FunctionDocs, ParamDoc};
 pub use function_metadata::{
     functions_by_category, search_functions, FunctionMetadata, FUNCTION_METADATA,
 };
-pub use matching::{filter_completions, filter_completions_with_plugins};
+pub use inline_help::{call_at_cursor, inline_help_lines, CallSite};
+pub use matching::{docstring_search, filter_completions, filter_completions_with_plugins};
 pub use parameter::generate_kwargs_template;
 pub use state::CompletionState;
 
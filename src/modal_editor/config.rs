@@ -0,0 +1,258 @@
+//! Editor settings file: `~/.phonon/config.toml`
+//!
+//! Everything is optional; a missing file means the built-in defaults.
+//!
+//! ```toml
+//! keymap = "vim"                 # "emacs" (default) or "vim"
+//! default_tempo = 0.5            # cps when the code sets no tempo/bpm
+//! sample_paths = ["~/samples"]   # searched before ~/phonon/samples and dirt-samples
+//! sample_memory_mb = 512         # decoded samples kept in memory (least recently used go first)
+//! buffer_size = 256              # device buffer (--buffer-size wins)
+//! block_size = 256               # frames per render block, 64-2048 (--block-size wins)
+//! ring_buffer_ms = 120           # audio cushion when the device buffer isn't fixed
+//! dc_block = true                # DC blocker on the master output
+//! master_clip = "soft"           # master clipper: "hard" (default), "soft", "tanh", "off"
+//! cue_device = "Headphones"      # where `cue`/`precue` play ("3/4" = channels 3/4)
+//! cv_full_scale = 10.0           # volts at digital full scale, for `cvout`/`gateout`
+//! midi_clock_out = "IAC"         # MIDI output sent clock and start/stop/continue
+//! midi_clock_in = "TR-8"         # MIDI input whose clock playback follows (`:sync midi`)
+//!
+//! [keys]                         # see keymap.rs for action names and key specs
+//! eval_block = "C-e"
+//!
+//! [theme]
+//! function = "cyan"
+//! number = "#ffaa00"
+//! ```
+
+use super::highlighting::Theme;
+use super::keymap::{KeyList, Keymap, KeymapStyle};
+use crate::engine_config::EngineConfig;
+use crate::unified_graph::MasterClip;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Parsed `config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EditorConfig {
+    pub keymap: KeymapStyle,
+    /// Binding overrides: action name → key spec(s)
+    pub keys: BTreeMap<String, KeyList>,
+    /// Vim normal-mode binding overrides
+    pub normal_keys: BTreeMap<String, KeyList>,
+    /// Color overrides: theme key → color
+    pub theme: BTreeMap<String, String>,
+    pub default_tempo: Option<f32>,
+    pub sample_paths: Vec<PathBuf>,
+    /// Cap on decoded sample memory, in megabytes. Samples the playing
+    /// program has loaded are kept beyond it
+    pub sample_memory_mb: Option<usize>,
+    pub buffer_size: Option<usize>,
+    /// Frames per render block
+    pub block_size: Option<usize>,
+    pub ring_buffer_ms: Option<f32>,
+    /// Master DC blocker
+    pub dc_block: bool,
+    /// Master clipper mode (hard when unset)
+    pub master_clip: Option<String>,
+    /// Headphone cue output: a device name (or part of one), or "3/4" for
+    /// channels 3 and 4 of the main device
+    pub cue_device: Option<String>,
+    /// Volts a DC-coupled interface puts out at digital full scale (10 when
+    /// unset)
+    pub cv_full_scale: Option<f32>,
+    /// MIDI output (a port name or part of one) that gets 24 ticks a beat,
+    /// four beats a cycle, and follows `:stop` / `:start`
+    pub midi_clock_out: Option<String>,
+    /// MIDI input (a port name or part of one) whose clock sets the tempo
+    /// from startup, as `:sync midi` does
+    pub midi_clock_in: Option<String>,
+}
+
+/// Default config location: `~/.phonon/config.toml`
+pub fn default_config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".phonon").join("config.toml"))
+}
+
+impl EditorConfig {
+    /// Load `~/.phonon/config.toml` (defaults if it doesn't exist)
+    pub fn load() -> Result<Self, String> {
+        match default_config_path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load a config file (defaults if it doesn't exist)
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Parse and validate config text
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut config: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        if let Some(tempo) = config.default_tempo {
+            if !(tempo > 0.0 && tempo.is_finite()) {
+                return Err(format!("default_tempo must be positive, got {}", tempo));
+            }
+        }
+        if let Some(frames) = config.block_size {
+            EngineConfig::new(44100.0, Some(frames))?;
+        }
+        if let Some(volts) = config.cv_full_scale {
+            if !(volts > 0.0 && volts.is_finite()) {
+                return Err(format!("cv_full_scale must be positive, got {}", volts));
+            }
+        }
+        if let Some(ms) = config.ring_buffer_ms {
+            if !(ms > 0.0 && ms.is_finite()) {
+                return Err(format!("ring_buffer_ms must be positive, got {}", ms));
+            }
+        }
+        config.sample_paths = config.sample_paths.iter().map(|p| expand_home(p)).collect();
+        // Surface bad keys and colors now rather than on first use
+        config.keymap()?;
+        config.theme()?;
+        config.master_clip()?;
+        Ok(config)
+    }
+
+    /// Keymap preset plus the `[keys]` / `[normal_keys]` overrides
+    pub fn keymap(&self) -> Result<Keymap, String> {
+        Keymap::with_overrides(self.keymap, &self.keys, &self.normal_keys)
+    }
+
+    /// Master clipper mode
+    pub fn master_clip(&self) -> Result<MasterClip, String> {
+        match &self.master_clip {
+            None => Ok(MasterClip::default()),
+            Some(mode) => MasterClip::from_str(mode).ok_or_else(|| {
+                format!(
+                    "master_clip must be hard, soft, tanh or off, got \"{}\"",
+                    mode
+                )
+            }),
+        }
+    }
+
+    /// Default theme plus the `[theme]` overrides
+    pub fn theme(&self) -> Result<Theme, String> {
+        let mut theme = Theme::default();
+        for (key, color) in &self.theme {
+            theme.set(key, color)?;
+        }
+        Ok(theme)
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_empty_config_is_default() {
+        let config = EditorConfig::parse("").unwrap();
+        assert_eq!(config, EditorConfig::default());
+        assert_eq!(config.keymap().unwrap().style(), KeymapStyle::Emacs);
+        assert_eq!(config.theme().unwrap(), Theme::default());
+    }
+
+    #[test]
+    fn test_full_config() {
+        let config = EditorConfig::parse(
+            r##"
+keymap = "vim"
+default_tempo = 0.75
+sample_paths = ["/opt/samples", "~/breaks"]
+sample_memory_mb = 256
+buffer_size = 256
+block_size = 128
+ring_buffer_ms = 120
+dc_block = true
+master_clip = "tanh"
+cue_device = "3/4"
+cv_full_scale = 5.0
+
+[keys]
+eval_block = ["C-e", "F5"]
+
+[theme]
+number = "#ffaa00"
+background = "darkgray"
+"##,
+        )
+        .unwrap();
+        assert_eq!(config.default_tempo, Some(0.75));
+        assert_eq!(config.buffer_size, Some(256));
+        assert_eq!(config.block_size, Some(128));
+        assert_eq!(config.ring_buffer_ms, Some(120.0));
+        assert!(config.dc_block);
+        assert_eq!(config.master_clip().unwrap(), MasterClip::Tanh);
+        assert!(EditorConfig::parse("master_clip = \"fold\"").is_err());
+        assert_eq!(config.cue_device.as_deref(), Some("3/4"));
+        assert_eq!(config.cv_full_scale, Some(5.0));
+        assert!(EditorConfig::parse("cv_full_scale = -5.0").is_err());
+        assert_eq!(config.sample_paths[0], PathBuf::from("/opt/samples"));
+        assert_eq!(config.sample_memory_mb, Some(256));
+        assert!(!config.sample_paths[1].starts_with("~"));
+
+        let keymap = config.keymap().unwrap();
+        assert_eq!(keymap.style(), KeymapStyle::Vim);
+        let f5 = KeyEvent::new(KeyCode::F(5), KeyModifiers::NONE);
+        assert!(keymap.lookup(&f5).is_some());
+
+        let theme = config.theme().unwrap();
+        assert_eq!(theme.number, Color::Rgb(255, 170, 0));
+        assert_eq!(theme.background, Color::DarkGray);
+    }
+
+    #[test]
+    fn test_invalid_configs_are_rejected() {
+        for text in [
+            "keymap = \"nano\"",
+            "default_tempo = -1.0",
+            "ring_buffer_ms = 0",
+            "block_size = 4096",
+            "unknown_setting = 1",
+            "[keys]\neval_block = \"C-Nope\"",
+            "[keys]\nfly = \"C-f\"",
+            "[theme]\nnumber = \"chartreuse-ish\"",
+        ] {
+            assert!(
+                EditorConfig::parse(text).is_err(),
+                "should reject {:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_file_gives_defaults() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = EditorConfig::load_from(&tmp.path().join("config.toml")).unwrap();
+        assert_eq!(config, EditorConfig::default());
+
+        let path = tmp.path().join("broken.toml");
+        fs::write(&path, "keymap = ").unwrap();
+        let err = EditorConfig::load_from(&path).unwrap_err();
+        assert!(err.contains("broken.toml"));
+    }
+}
@@ -0,0 +1,188 @@
+//! Headphone cue output
+//!
+//! `cue $ expr` and `precue ~bus` render into the graph's cue buffer, next to
+//! (never into) the main output. The synth thread queues each block's cue on
+//! its own ring, which plays either on a second device (DJ headphones) or on
+//! channels 3/4 of the main device, chosen by `cue_device` in the editor
+//! config. With no cue destination configured the cue is rendered and dropped.
+
+use crate::audio_device::{build_output_stream_converted, select_output_device};
+use cpal::traits::{DeviceTrait, StreamTrait};
+use ringbuf::traits::{Consumer, Observer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Where the cue plays
+#[derive(Debug, Clone, PartialEq)]
+pub enum CueTarget {
+    /// Channels 3/4 of the main output device (`cue_device = "3/4"`)
+    MainChannels,
+    /// A second output device, by name or part of one
+    Device(String),
+}
+
+impl CueTarget {
+    pub fn parse(spec: &str) -> Self {
+        match spec.trim() {
+            "3/4" | "3-4" => CueTarget::MainChannels,
+            name => CueTarget::Device(name.to_string()),
+        }
+    }
+}
+
+/// Synth-thread end: queues the cue of each rendered block
+pub struct CueFeed {
+    producer: HeapProd<f32>,
+    /// Stereo-interleaved staging, sized once for the synthesis block
+    scratch: Vec<f32>,
+}
+
+/// Audio-callback end: plays the queued cue
+pub struct CueTap {
+    consumer: HeapCons<f32>,
+    clear: Arc<AtomicBool>,
+}
+
+/// A connected feed / tap pair with room for `capacity` samples (stereo
+/// interleaved), plus the flag the main callback raises when it drops queued
+/// audio, so the cue drops its backlog too and stays in step
+pub fn cue_channel(capacity: usize, block_len: usize) -> (CueFeed, CueTap, Arc<AtomicBool>) {
+    let (producer, consumer) = HeapRb::<f32>::new(capacity).split();
+    let clear = Arc::new(AtomicBool::new(false));
+    (
+        CueFeed {
+            producer,
+            scratch: vec![0.0; block_len],
+        },
+        CueTap {
+            consumer,
+            clear: Arc::clone(&clear),
+        },
+        clear,
+    )
+}
+
+impl CueFeed {
+    /// Queue `frames` frames of `cue` (the graph's mono cue buffer); a graph
+    /// without a cue output queues silence so the cue keeps time with the main
+    /// output
+    pub fn push(&mut self, cue: &[f32], frames: usize) {
+        let len = (frames * 2).min(self.scratch.len());
+        for (i, frame) in self.scratch[..len].chunks_exact_mut(2).enumerate() {
+            let sample = cue.get(i).copied().unwrap_or(0.0);
+            frame[0] = sample;
+            frame[1] = sample;
+        }
+        self.producer.push_slice(&self.scratch[..len]);
+    }
+}
+
+impl CueTap {
+    /// Drop the backlog if the main output just dropped its own
+    fn sync(&mut self) {
+        if self.clear.swap(false, Ordering::Relaxed) {
+            let queued = self.consumer.occupied_len();
+            self.consumer.skip(queued);
+        }
+    }
+
+    /// Next stereo frame (silence when the cue has fallen behind)
+    fn next_frame(&mut self) -> [f32; 2] {
+        if self.consumer.occupied_len() < 2 {
+            return [0.0, 0.0];
+        }
+        let left = self.consumer.try_pop().unwrap_or(0.0);
+        let right = self.consumer.try_pop().unwrap_or(0.0);
+        [left, right]
+    }
+
+    /// Write the cue into channels `first`/`first + 1` of an interleaved
+    /// device buffer with `channels` channels, leaving the others untouched
+    pub fn fill(&mut self, data: &mut [f32], channels: usize, first: usize) {
+        self.sync();
+        if channels < first + 2 {
+            return;
+        }
+        for frame in data.chunks_exact_mut(channels) {
+            let [left, right] = self.next_frame();
+            frame[first] = left;
+            frame[first + 1] = right;
+        }
+    }
+}
+
+/// Play the cue on its own device at the main output's sample rate
+pub fn start_cue_device(
+    host: &cpal::Host,
+    name: &str,
+    sample_rate: f32,
+    mut tap: CueTap,
+) -> Result<cpal::Stream, String> {
+    let device = select_output_device(host, Some(name))?;
+    let supported = device
+        .default_output_config()
+        .map_err(|e| format!("cue device: {}", e))?;
+    let mut config = supported.config();
+    config.sample_rate = cpal::SampleRate(sample_rate as u32);
+    let channels = config.channels as usize;
+
+    let stream = build_output_stream_converted(
+        &device,
+        &config,
+        supported.sample_format(),
+        move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+            data.fill(0.0);
+            tap.fill(data, channels, 0);
+        },
+        |err| crate::rt_log::log(format_args!("Cue stream error: {}", err)),
+    )
+    .map_err(|e| {
+        format!(
+            "cue device {} can't play at {} Hz: {}",
+            name, sample_rate as u32, e
+        )
+    })?;
+    stream
+        .play()
+        .map_err(|e| format!("cue device {}: {}", name, e))?;
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cue_target() {
+        assert_eq!(CueTarget::parse("3/4"), CueTarget::MainChannels);
+        assert_eq!(
+            CueTarget::parse("Headphones"),
+            CueTarget::Device("Headphones".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cue_reaches_channels_three_and_four() {
+        let (mut feed, mut tap, clear) = cue_channel(64, 8);
+        feed.push(&[0.5, -0.5], 2);
+        // A block without a cue output still keeps time
+        feed.push(&[], 2);
+
+        let mut data = vec![0.1f32; 4 * 5];
+        tap.fill(&mut data, 4, 2);
+        let cue: Vec<[f32; 2]> = data.chunks(4).map(|f| [f[2], f[3]]).collect();
+        assert_eq!(cue[0], [0.5, 0.5]);
+        assert_eq!(cue[1], [-0.5, -0.5]);
+        assert_eq!(cue[2], [0.0, 0.0]);
+        // Past the queued audio: silence, and channels 1/2 untouched
+        assert_eq!(cue[4], [0.0, 0.0]);
+        assert!(data.chunks(4).all(|f| f[0] == 0.1 && f[1] == 0.1));
+
+        // The main output dropping its queue drops the cue's too
+        feed.push(&[0.9, 0.9], 2);
+        clear.store(true, Ordering::Relaxed);
+        tap.fill(&mut data, 4, 2);
+        assert_eq!(data[2], 0.0);
+    }
+}
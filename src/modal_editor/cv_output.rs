@@ -0,0 +1,172 @@
+//! Control voltage outputs
+//!
+//! `cvout` and `gateout` render into the graph's CV buffers, in volts. On a
+//! device with more than two channels the synth thread scales each block to
+//! the interface (`cv_full_scale` volts at digital full scale) and queues
+//! channels 3 and up on a ring of their own, which the audio callback writes
+//! beside the main mix on 1/2. A cue on channels 3/4 is written after, so it
+//! takes those two.
+
+use crate::cv::volts_to_sample;
+use crate::unified_graph::UnifiedSignalGraph;
+use ringbuf::traits::{Consumer, Observer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Device channels before the first CV channel (the main stereo mix)
+const FIRST_CV_CHANNEL: usize = 2;
+
+/// Synth-thread end: queues channels 3 and up of each rendered block
+pub struct CvFeed {
+    producer: HeapProd<f32>,
+    /// CV channels per frame
+    lanes: usize,
+    /// Volts at digital full scale
+    full_scale: f32,
+    /// Interleaved staging, sized once for the synthesis block
+    scratch: Vec<f32>,
+}
+
+/// Audio-callback end: plays the queued CV
+pub struct CvTap {
+    consumer: HeapCons<f32>,
+    lanes: usize,
+    clear: Arc<AtomicBool>,
+}
+
+/// A connected feed / tap pair for a device with `channels` channels,
+/// queueing up to `frames` frames, plus the flag the main callback raises
+/// when it drops queued audio. `None` for a stereo device, which has no
+/// channels to spare
+pub fn cv_channel(
+    channels: usize,
+    frames: usize,
+    block_frames: usize,
+    full_scale: f32,
+) -> Option<(CvFeed, CvTap, Arc<AtomicBool>)> {
+    let lanes = channels.checked_sub(FIRST_CV_CHANNEL).filter(|&n| n > 0)?;
+    let (producer, consumer) = HeapRb::<f32>::new(frames * lanes).split();
+    let clear = Arc::new(AtomicBool::new(false));
+    Some((
+        CvFeed {
+            producer,
+            lanes,
+            full_scale,
+            scratch: vec![0.0; block_frames * lanes],
+        },
+        CvTap {
+            consumer,
+            lanes,
+            clear: Arc::clone(&clear),
+        },
+        clear,
+    ))
+}
+
+impl CvFeed {
+    /// Queue `frames` frames of the graph's CV outputs; channels without one
+    /// (and graphs without any) queue 0 V so the CV keeps time with the main
+    /// output
+    pub fn push(&mut self, graph: &UnifiedSignalGraph, frames: usize) {
+        let len = (frames * self.lanes).min(self.scratch.len());
+        self.scratch[..len].fill(0.0);
+        for channel in graph.cv_channels() {
+            let Some(lane) = channel.checked_sub(FIRST_CV_CHANNEL + 1) else {
+                continue;
+            };
+            if lane >= self.lanes {
+                continue;
+            }
+            let volts = graph.cv_buffer(channel);
+            for (frame, &v) in self.scratch[..len]
+                .chunks_exact_mut(self.lanes)
+                .zip(volts.iter())
+            {
+                frame[lane] = volts_to_sample(v, self.full_scale);
+            }
+        }
+        self.producer.push_slice(&self.scratch[..len]);
+    }
+}
+
+impl CvTap {
+    /// Write the CV into channels 3 and up of an interleaved device buffer
+    /// with `channels` channels (0 V when the CV has fallen behind)
+    pub fn fill(&mut self, data: &mut [f32], channels: usize) {
+        if self.clear.swap(false, Ordering::Relaxed) {
+            let queued = self.consumer.occupied_len();
+            self.consumer.skip(queued);
+        }
+        for frame in data.chunks_exact_mut(channels) {
+            let lanes = &mut frame[FIRST_CV_CHANNEL..FIRST_CV_CHANNEL + self.lanes];
+            if self.consumer.occupied_len() < self.lanes {
+                lanes.fill(0.0);
+            } else {
+                self.consumer.pop_slice(lanes);
+            }
+        }
+    }
+}
+
+/// The warning for CV channels the device doesn't have, if any
+pub fn missing_channels_warning(graph: &UnifiedSignalGraph, channels: usize) -> Option<String> {
+    let missing: Vec<String> = graph
+        .cv_channels()
+        .into_iter()
+        .filter(|&channel| channel > channels)
+        .map(|channel| channel.to_string())
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "CV channel {} not sent: this output has {} channels",
+        missing.join(", "),
+        channels
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositional_compiler::compile_program;
+    use crate::compositional_parser::parse_program;
+
+    fn graph(code: &str) -> UnifiedSignalGraph {
+        let (_, statements) = parse_program(code).unwrap();
+        compile_program(statements, 44100.0, None).unwrap()
+    }
+
+    #[test]
+    fn test_cv_reaches_its_channels_scaled() {
+        let mut graph = graph("out $ sine 440\ncvout 3 2\ngateout 4 \"t\"");
+        graph.render(4);
+        let (mut feed, mut tap, clear) = cv_channel(4, 16, 4, 10.0).unwrap();
+        feed.push(&graph, 4);
+
+        let mut data = vec![0.1f32; 4 * 6];
+        tap.fill(&mut data, 4);
+        let frames: Vec<&[f32]> = data.chunks(4).collect();
+        assert_eq!(frames[0], [0.1, 0.1, 0.2, 0.5]);
+        assert_eq!(frames[3], [0.1, 0.1, 0.2, 0.5]);
+        // Past the queued CV: 0 V
+        assert_eq!(frames[4], [0.1, 0.1, 0.0, 0.0]);
+
+        feed.push(&graph, 4);
+        clear.store(true, Ordering::Relaxed);
+        tap.fill(&mut data, 4);
+        assert_eq!(data[2], 0.0);
+    }
+
+    #[test]
+    fn test_stereo_device_has_no_cv_channels() {
+        assert!(cv_channel(2, 16, 4, 10.0).is_none());
+        let graph = graph("out $ sine 440\ncvout 3 1\ngateout 6 \"t\"");
+        assert_eq!(
+            missing_channels_warning(&graph, 4).as_deref(),
+            Some("CV channel 6 not sent: this output has 4 channels")
+        );
+        assert_eq!(missing_channels_warning(&graph, 8), None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::compositional_parser::{tokenize_line, LineTokens, Token, TokenKind};
 use ratatui::style::{Color, Style};
 use ratatui::text::Span;
 
@@ -25,6 +26,8 @@ pub const FUNCTIONS: &[&str] = &[
     "spreadr",
     "when",
     "whenmod",
+    "take",
+    "after",
     "off",
     "superimpose",
     // Oscillators
@@ -102,11 +105,147 @@ pub const FUNCTIONS: &[&str] = &[
     "d6",
     "d7",
     "d8",
+    "cue",
+    "precue",
+    "cvout",
+    "gateout",
     // Commands
     "hush",
     "panic",
 ];
 
+/// Editor colors: syntax highlighting plus the editor background and selection
+///
+/// Defaults to the built-in scheme; the `[theme]` table in
+/// `~/.phonon/config.toml` overrides individual colors by name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Statement heads: out, o1, tempo, fn, ...
+    pub keyword: Color,
+    pub function: Color,
+    /// `fn` and bus parameters
+    pub variable: Color,
+    pub bus: Color,
+    pub number: Color,
+    pub string: Color,
+    /// `#` and `$`
+    pub chain_operator: Color,
+    pub operator: Color,
+    pub comment: Color,
+    pub text: Color,
+    /// Lines whose statement fails to parse
+    pub unparsed: Color,
+    pub background: Color,
+    pub selection: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            keyword: Color::Blue,
+            function: Color::Blue,
+            variable: Color::White,
+            bus: Color::Magenta,
+            number: Color::Rgb(255, 165, 0), // Orange
+            string: Color::White,
+            chain_operator: Color::Rgb(255, 20, 147), // Hot Pink
+            operator: Color::Rgb(150, 150, 150),      // Light Gray
+            comment: Color::Rgb(100, 100, 100),       // Dark Gray
+            text: Color::White,
+            unparsed: Color::DarkGray,
+            background: Color::Black,
+            selection: Color::Blue,
+        }
+    }
+}
+
+impl Theme {
+    /// Names accepted by [`Theme::set`]
+    pub const KEYS: &'static [&'static str] = &[
+        "keyword",
+        "function",
+        "variable",
+        "bus",
+        "number",
+        "string",
+        "chain_operator",
+        "operator",
+        "comment",
+        "text",
+        "unparsed",
+        "background",
+        "selection",
+    ];
+
+    /// Set one color by name, e.g. `set("number", "#ffaa00")`
+    pub fn set(&mut self, key: &str, color: &str) -> Result<(), String> {
+        let color = parse_color(color)?;
+        let slot = match key {
+            "keyword" => &mut self.keyword,
+            "function" => &mut self.function,
+            "variable" => &mut self.variable,
+            "bus" => &mut self.bus,
+            "number" => &mut self.number,
+            "string" => &mut self.string,
+            "chain_operator" => &mut self.chain_operator,
+            "operator" => &mut self.operator,
+            "comment" => &mut self.comment,
+            "text" => &mut self.text,
+            "unparsed" => &mut self.unparsed,
+            "background" => &mut self.background,
+            "selection" => &mut self.selection,
+            _ => {
+                return Err(format!(
+                    "Unknown theme color '{}' (expected one of: {})",
+                    key,
+                    Self::KEYS.join(", ")
+                ))
+            }
+        };
+        *slot = color;
+        Ok(())
+    }
+}
+
+/// Parse a color: a name (`red`, `darkgray`, `lightblue`, ...), `#rrggbb`,
+/// or a 256-color palette index (`0`-`255`)
+pub fn parse_color(text: &str) -> Result<Color, String> {
+    let name = text.trim().to_lowercase().replace(['-', '_', ' '], "");
+    let color = match name.as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => {
+            if let Some(hex) = name.strip_prefix('#') {
+                let value = u32::from_str_radix(hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 6)
+                    .ok_or_else(|| format!("Invalid hex color '{}' (use #rrggbb)", text))?;
+                Color::Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8)
+            } else if let Ok(index) = name.parse::<u8>() {
+                Color::Indexed(index)
+            } else {
+                return Err(format!("Unknown color '{}'", text));
+            }
+        }
+    };
+    Ok(color)
+}
+
 /// Syntax highlight a single line of Phonon code
 ///
 /// Returns a vector of styled spans suitable for rendering in a terminal UI.
@@ -121,117 +260,63 @@ pub const FUNCTIONS: &[&str] = &[
 /// - Comments (--): Dark Gray (RGB 100, 100, 100)
 /// - Default: White
 pub fn highlight_line(line: &str) -> Vec<Span<'static>> {
-    let mut spans = Vec::new();
-    let mut current = String::new();
-    let mut in_string = false;
-    let mut in_comment = false;
-
-    // Check if line starts with -- (comment)
-    let line_trimmed = line.trim_start();
-    if line_trimmed.starts_with("--") {
-        // Entire line is a comment
-        spans.push(Span::styled(
-            line.to_string(),
-            Style::default().fg(Color::Rgb(100, 100, 100)),
-        ));
-        return spans;
-    }
-
-    for ch in line.chars() {
-        if in_comment {
-            current.push(ch);
-            continue;
-        }
-
-        // String detection
-        if ch == '"' {
-            if in_string {
-                current.push(ch);
-                // Mininotation strings → White
-                spans.push(Span::styled(
-                    current.clone(),
-                    Style::default().fg(Color::White),
-                ));
-                current.clear();
-                in_string = false;
-            } else {
-                // Flush current token
-                if !current.is_empty() {
-                    spans.push(Span::styled(current.clone(), token_style(&current)));
-                    current.clear();
-                }
-                current.push(ch);
-                in_string = true;
-            }
-            continue;
-        }
-
-        if in_string {
-            current.push(ch);
-            continue;
-        }
-
-        // Operators and delimiters
-        if "(){}[]:|#$<>=+*-/,".contains(ch) {
-            // Flush current token
-            if !current.is_empty() {
-                spans.push(Span::styled(current.clone(), token_style(&current)));
-                current.clear();
-            }
-            // # and $ → Hot Pink, others → Light Gray
-            let color = if ch == '#' || ch == '$' {
-                Color::Rgb(255, 20, 147) // Hot Pink
-            } else {
-                Color::Rgb(150, 150, 150) // Light Gray
-            };
-            spans.push(Span::styled(ch.to_string(), Style::default().fg(color)));
-            continue;
-        }
-
-        // Whitespace
-        if ch.is_whitespace() {
-            // Flush current token
-            if !current.is_empty() {
-                spans.push(Span::styled(current.clone(), token_style(&current)));
-                current.clear();
-            }
-            spans.push(Span::raw(ch.to_string()));
-            continue;
-        }
+    highlight_line_with(line, &Theme::default())
+}
 
-        current.push(ch);
+/// Syntax highlight a single line with the colors from `theme`
+///
+/// The line is tokenized on its own, without parsing, so identifiers are
+/// only recognized as functions if they are in [`FUNCTIONS`]. The editor
+/// uses [`highlight_tokens`] with the output of
+/// [`classify_source`](crate::compositional_parser::classify_source) instead.
+pub fn highlight_line_with(line: &str, theme: &Theme) -> Vec<Span<'static>> {
+    let tokens = tokenize_line(line);
+    if tokens.is_empty() {
+        return vec![Span::raw(" ")];
     }
+    tokens
+        .iter()
+        .map(|token| token_span(line, token, theme))
+        .collect()
+}
 
-    // Flush remaining
-    if !current.is_empty() {
-        let style = if in_comment {
-            Style::default().fg(Color::Rgb(100, 100, 100)) // Comments → Dark gray
-        } else if in_string {
-            Style::default().fg(Color::White) // Strings → White
-        } else {
-            token_style(&current)
-        };
-        spans.push(Span::styled(current, style));
+/// Highlight a line already classified by the parser
+///
+/// Lines whose statement doesn't parse are drawn entirely in `theme.unparsed`.
+pub fn highlight_tokens(line: &str, classified: &LineTokens, theme: &Theme) -> Vec<Span<'static>> {
+    if line.is_empty() {
+        return vec![Span::raw(" ")];
     }
-
-    if spans.is_empty() {
-        spans.push(Span::raw(" "));
+    if !classified.parses {
+        return vec![Span::styled(
+            line.to_string(),
+            Style::default().fg(theme.unparsed),
+        )];
     }
-
-    spans
+    classified
+        .tokens
+        .iter()
+        .map(|token| token_span(line, token, theme))
+        .collect()
 }
 
-/// Determine the style for a token based on its content
-fn token_style(token: &str) -> Style {
-    if FUNCTIONS.contains(&token) {
-        Style::default().fg(Color::Blue) // Functions → Blue
-    } else if token.starts_with('~') {
-        Style::default().fg(Color::Magenta) // Buses → Magenta
-    } else if token.chars().all(|c| c.is_ascii_digit() || c == '.') {
-        Style::default().fg(Color::Rgb(255, 165, 0)) // Numbers → Orange
-    } else {
-        Style::default().fg(Color::White) // Default
-    }
+fn token_span(line: &str, token: &Token, theme: &Theme) -> Span<'static> {
+    let text = line[token.start..token.end].to_string();
+    let color = match token.kind {
+        TokenKind::Whitespace => return Span::raw(text),
+        TokenKind::Keyword => theme.keyword,
+        TokenKind::Function => theme.function,
+        TokenKind::Variable => theme.variable,
+        TokenKind::Identifier if FUNCTIONS.contains(&text.as_str()) => theme.function,
+        TokenKind::Identifier | TokenKind::Unknown => theme.text,
+        TokenKind::Bus | TokenKind::Reference => theme.bus,
+        TokenKind::Number => theme.number,
+        TokenKind::String => theme.string,
+        TokenKind::ChainOperator => theme.chain_operator,
+        TokenKind::Operator | TokenKind::Kwarg => theme.operator,
+        TokenKind::Comment => theme.comment,
+    };
+    Span::styled(text, Style::default().fg(color))
 }
 
 #[cfg(test)]
@@ -500,4 +585,68 @@ mod tests {
             .iter()
             .any(|s| s.content == "unknown_thing" && s.style.fg == Some(Color::White)));
     }
+
+    #[test]
+    fn test_themed_highlighting() {
+        let mut theme = Theme::default();
+        theme.set("function", "cyan").unwrap();
+        theme.set("number", "#ffaa00").unwrap();
+        theme.set("comment", "darkgray").unwrap();
+
+        let spans = highlight_line_with("~a $ lpf 800", &theme);
+        let style_of = |text: &str| spans.iter().find(|s| s.content == text).unwrap().style.fg;
+        assert_eq!(style_of("lpf"), Some(Color::Cyan));
+        assert_eq!(style_of("800"), Some(Color::Rgb(255, 170, 0)));
+        assert_eq!(style_of("~a"), Some(Color::Magenta));
+
+        let comment = highlight_line_with("-- note", &theme);
+        assert_eq!(comment[0].style.fg, Some(Color::DarkGray));
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("Light-Blue"), Ok(Color::LightBlue));
+        assert_eq!(parse_color("#102030"), Ok(Color::Rgb(16, 32, 48)));
+        assert_eq!(parse_color("208"), Ok(Color::Indexed(208)));
+        assert!(parse_color("#12345").is_err());
+        assert!(parse_color("mauve").is_err());
+        assert!(Theme::default().set("sparkle", "red").is_err());
+    }
+
+    #[test]
+    fn test_parser_driven_highlighting() {
+        use crate::compositional_parser::classify_source;
+
+        let mut theme = Theme::default();
+        theme.set("keyword", "yellow").unwrap();
+        theme.set("variable", "green").unwrap();
+
+        let code = "fn thick x = saw x # lpf 800\nout $ thick 55\nout $ $";
+        let classified = classify_source(code);
+        let lines: Vec<Vec<Span>> = code
+            .split('\n')
+            .zip(&classified)
+            .map(|(line, tokens)| highlight_tokens(line, tokens, &theme))
+            .collect();
+
+        let color_of = |line: usize, text: &str| {
+            lines[line]
+                .iter()
+                .find(|s| s.content == text)
+                .unwrap()
+                .style
+                .fg
+        };
+        assert_eq!(color_of(0, "fn"), Some(Color::Yellow));
+        assert_eq!(color_of(0, "x"), Some(Color::Green));
+        assert_eq!(color_of(0, "lpf"), Some(Color::Blue));
+        assert_eq!(color_of(1, "out"), Some(Color::Yellow));
+        // User-defined functions are recognized without being in FUNCTIONS
+        assert_eq!(color_of(1, "thick"), Some(Color::Blue));
+
+        // A line that doesn't parse is greyed out as a whole
+        assert_eq!(lines[2].len(), 1);
+        assert_eq!(span_text(&lines[2]), "out $ $");
+        assert_eq!(lines[2][0].style.fg, Some(Color::DarkGray));
+    }
 }
@@ -0,0 +1,631 @@
+//! Key bindings: named editor actions and the keys bound to them
+//!
+//! Every editor command has a name (`eval_block`, `kill_line`, ...). The
+//! emacs-style preset is the default; `keymap = "vim"` in
+//! `~/.phonon/config.toml` adds a modal normal mode on top of it (Esc enters
+//! normal mode, `i`/`a`/`o` go back to inserting). Individual bindings are
+//! overridden by action name, replacing that action's default keys:
+//!
+//! ```toml
+//! [keys]
+//! eval_block = "C-e"
+//! hush = ["C-h", "F12"]
+//! kill_line = []          # unbind
+//!
+//! [normal_keys]           # vim normal mode only
+//! eval_all = "R"
+//! ```
+//!
+//! Key specs are `C-` (Ctrl), `M-`/`A-` (Alt) and `S-` (Shift) prefixes
+//! followed by a character or a key name (`Tab`, `Enter`, `Esc`, `Space`,
+//! `Backspace`, `Delete`, arrows, `Home`, `End`, `PageUp`, `PageDown`, `F1`-`F12`).
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// An editor command that can be bound to a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Save,
+    EvalBlock,
+    EvalAll,
+    Hush,
+    ToggleTransport,
+    TapTempo,
+    Undo,
+    Redo,
+    ToggleConsole,
+    TogglePluginBrowser,
+    OpenPluginGuis,
+    OpenStepGrid,
+    ScrubUp,
+    ScrubDown,
+    ScrubUpFine,
+    ScrubDownFine,
+    ScrubUpCoarse,
+    ScrubDownCoarse,
+    NudgeEarlier,
+    NudgeLater,
+    ToggleConfigPanel,
+    ToggleInlineHelp,
+    ToggleEventLog,
+    Audition,
+    SplitPane,
+    CycleMidiDevice,
+    ToggleMidiRecording,
+    MidiSmartPaste,
+    InsertMidiNotes,
+    InsertMidiOffsets,
+    InsertMidiVelocity,
+    InsertMidiLegato,
+    ForwardChar,
+    BackwardChar,
+    NextLine,
+    PreviousLine,
+    LineStart,
+    LineEnd,
+    PageUp,
+    PageDown,
+    DeleteChar,
+    KillLine,
+    Yank,
+    Cut,
+    Copy,
+    ToggleComment,
+    Indent,
+    Dedent,
+    // Vim modes
+    NormalMode,
+    InsertMode,
+    Append,
+    AppendLineEnd,
+    InsertLineStart,
+    OpenLineBelow,
+    OpenLineAbove,
+    ToggleSelection,
+}
+
+/// Every action with its config name
+const ACTION_NAMES: &[(Action, &str)] = &[
+    (Action::Quit, "quit"),
+    (Action::Save, "save"),
+    (Action::EvalBlock, "eval_block"),
+    (Action::EvalAll, "eval_all"),
+    (Action::Hush, "hush"),
+    (Action::ToggleTransport, "toggle_transport"),
+    (Action::TapTempo, "tap_tempo"),
+    (Action::Undo, "undo"),
+    (Action::Redo, "redo"),
+    (Action::ToggleConsole, "toggle_console"),
+    (Action::TogglePluginBrowser, "toggle_plugin_browser"),
+    (Action::OpenPluginGuis, "open_plugin_guis"),
+    (Action::OpenStepGrid, "open_step_grid"),
+    (Action::ScrubUp, "scrub_up"),
+    (Action::ScrubDown, "scrub_down"),
+    (Action::ScrubUpFine, "scrub_up_fine"),
+    (Action::ScrubDownFine, "scrub_down_fine"),
+    (Action::ScrubUpCoarse, "scrub_up_coarse"),
+    (Action::ScrubDownCoarse, "scrub_down_coarse"),
+    (Action::NudgeEarlier, "nudge_earlier"),
+    (Action::NudgeLater, "nudge_later"),
+    (Action::ToggleConfigPanel, "toggle_config_panel"),
+    (Action::ToggleInlineHelp, "toggle_inline_help"),
+    (Action::ToggleEventLog, "toggle_event_log"),
+    (Action::Audition, "audition"),
+    (Action::SplitPane, "split_pane"),
+    (Action::CycleMidiDevice, "cycle_midi_device"),
+    (Action::ToggleMidiRecording, "toggle_midi_recording"),
+    (Action::MidiSmartPaste, "midi_smart_paste"),
+    (Action::InsertMidiNotes, "insert_midi_notes"),
+    (Action::InsertMidiOffsets, "insert_midi_offsets"),
+    (Action::InsertMidiVelocity, "insert_midi_velocity"),
+    (Action::InsertMidiLegato, "insert_midi_legato"),
+    (Action::ForwardChar, "forward_char"),
+    (Action::BackwardChar, "backward_char"),
+    (Action::NextLine, "next_line"),
+    (Action::PreviousLine, "previous_line"),
+    (Action::LineStart, "line_start"),
+    (Action::LineEnd, "line_end"),
+    (Action::PageUp, "page_up"),
+    (Action::PageDown, "page_down"),
+    (Action::DeleteChar, "delete_char"),
+    (Action::KillLine, "kill_line"),
+    (Action::Yank, "yank"),
+    (Action::Cut, "cut"),
+    (Action::Copy, "copy"),
+    (Action::ToggleComment, "toggle_comment"),
+    (Action::Indent, "indent"),
+    (Action::Dedent, "dedent"),
+    (Action::NormalMode, "normal_mode"),
+    (Action::InsertMode, "insert_mode"),
+    (Action::Append, "append"),
+    (Action::AppendLineEnd, "append_line_end"),
+    (Action::InsertLineStart, "insert_line_start"),
+    (Action::OpenLineBelow, "open_line_below"),
+    (Action::OpenLineAbove, "open_line_above"),
+    (Action::ToggleSelection, "toggle_selection"),
+];
+
+/// Default (emacs-style) bindings, active in both presets
+const EMACS_BINDINGS: &[(Action, &[&str])] = &[
+    // Ctrl+Q conflicts with terminal flow control
+    (Action::Quit, &["M-q"]),
+    (Action::Save, &["C-s"]),
+    (Action::EvalBlock, &["C-x"]),
+    (Action::EvalAll, &["C-l"]),
+    (Action::Hush, &["C-h"]),
+    (Action::ToggleTransport, &["M-Space"]),
+    (Action::TapTempo, &["M-t"]),
+    (Action::Undo, &["C-u"]),
+    (Action::Redo, &["C-r"]),
+    (Action::ToggleConsole, &["M-/"]),
+    (Action::TogglePluginBrowser, &["M-p"]),
+    (Action::OpenPluginGuis, &["M-G"]),
+    (Action::OpenStepGrid, &["M-g"]),
+    (Action::ScrubUp, &["M-Up"]),
+    (Action::ScrubDown, &["M-Down"]),
+    (Action::ScrubUpFine, &["C-M-Up"]),
+    (Action::ScrubDownFine, &["C-M-Down"]),
+    (Action::ScrubUpCoarse, &["M-S-Up"]),
+    (Action::ScrubDownCoarse, &["M-S-Down"]),
+    (Action::NudgeEarlier, &["M-Left"]),
+    (Action::NudgeLater, &["M-Right"]),
+    (Action::ToggleConfigPanel, &["M-,"]),
+    (Action::ToggleInlineHelp, &["M-h"]),
+    (Action::ToggleEventLog, &["M-e"]),
+    (Action::Audition, &["M-Enter"]),
+    (Action::SplitPane, &["M-o"]),
+    (Action::CycleMidiDevice, &["M-m"]),
+    (Action::ToggleMidiRecording, &["M-r"]),
+    (Action::MidiSmartPaste, &["M-I"]),
+    (Action::InsertMidiNotes, &["M-i"]),
+    (Action::InsertMidiOffsets, &["M-n"]),
+    (Action::InsertMidiVelocity, &["M-v"]),
+    (Action::InsertMidiLegato, &["M-l"]),
+    (Action::ForwardChar, &["C-f"]),
+    (Action::BackwardChar, &["C-b"]),
+    (Action::NextLine, &["C-n"]),
+    (Action::PreviousLine, &["C-p"]),
+    (Action::LineStart, &["C-a"]),
+    (Action::LineEnd, &["C-e"]),
+    (Action::PageUp, &["PageUp"]),
+    (Action::PageDown, &["PageDown"]),
+    (Action::DeleteChar, &["C-d"]),
+    (Action::KillLine, &["C-k"]),
+    (Action::Yank, &["C-y"]),
+    (Action::Cut, &["C-w"]),
+    (Action::Copy, &["M-w"]),
+    (Action::ToggleComment, &["M-;"]),
+    (Action::Indent, &["M-]"]),
+    (Action::Dedent, &["M-["]),
+];
+
+/// Vim normal-mode bindings
+const VIM_NORMAL_BINDINGS: &[(Action, &[&str])] = &[
+    (Action::BackwardChar, &["h"]),
+    (Action::NextLine, &["j"]),
+    (Action::PreviousLine, &["k"]),
+    (Action::ForwardChar, &["l"]),
+    (Action::LineStart, &["0"]),
+    (Action::LineEnd, &["$"]),
+    (Action::PageUp, &["C-b"]),
+    (Action::PageDown, &["C-f"]),
+    (Action::DeleteChar, &["x"]),
+    (Action::KillLine, &["D"]),
+    (Action::Yank, &["p"]),
+    (Action::Undo, &["u"]),
+    (Action::InsertMode, &["i"]),
+    (Action::Append, &["a"]),
+    (Action::AppendLineEnd, &["A"]),
+    (Action::InsertLineStart, &["I"]),
+    (Action::OpenLineBelow, &["o"]),
+    (Action::OpenLineAbove, &["O"]),
+    (Action::ToggleSelection, &["v"]),
+    (Action::Copy, &["y"]),
+    (Action::Cut, &["d"]),
+    (Action::Indent, &[">"]),
+    (Action::Dedent, &["<"]),
+    (Action::ToggleConsole, &[":"]),
+    (Action::EvalBlock, &["Enter"]),
+    (Action::ToggleTransport, &["Space"]),
+];
+
+impl Action {
+    /// Name used in the config file
+    pub fn name(self) -> &'static str {
+        ACTION_NAMES
+            .iter()
+            .find(|(action, _)| *action == self)
+            .map(|(_, name)| *name)
+            .unwrap_or("unknown")
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        ACTION_NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(action, _)| *action)
+    }
+
+    /// All actions
+    pub fn all() -> impl Iterator<Item = Action> {
+        ACTION_NAMES.iter().map(|(action, _)| *action)
+    }
+
+    /// Cursor movements (in vim visual mode these extend the selection)
+    pub fn is_motion(self) -> bool {
+        matches!(
+            self,
+            Action::ForwardChar
+                | Action::BackwardChar
+                | Action::NextLine
+                | Action::PreviousLine
+                | Action::LineStart
+                | Action::LineEnd
+                | Action::PageUp
+                | Action::PageDown
+        )
+    }
+
+    /// Actions that work on the selection, so it must survive until they run
+    pub fn uses_selection(self) -> bool {
+        matches!(
+            self,
+            Action::Cut
+                | Action::Copy
+                | Action::ToggleComment
+                | Action::Indent
+                | Action::Dedent
+                | Action::DeleteChar
+                | Action::ToggleSelection
+                | Action::Audition
+        )
+    }
+}
+
+/// A key plus modifiers
+///
+/// Shift is folded into the character for character keys (`M-I` is Alt+Shift+I),
+/// and Shift+Tab is stored as `S-Tab` whichever way the terminal reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) => {
+                if modifiers.contains(KeyModifiers::SHIFT) && c.is_ascii_lowercase() {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else {
+                    modifiers.remove(KeyModifiers::SHIFT);
+                    KeyCode::Char(c)
+                }
+            }
+            KeyCode::BackTab => {
+                modifiers.insert(KeyModifiers::SHIFT);
+                KeyCode::Tab
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parse a key spec such as `C-x`, `M-/`, `S-Tab` or `F5`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec.trim();
+        loop {
+            let prefix = [
+                ("C-", KeyModifiers::CONTROL),
+                ("Ctrl-", KeyModifiers::CONTROL),
+                ("M-", KeyModifiers::ALT),
+                ("A-", KeyModifiers::ALT),
+                ("Alt-", KeyModifiers::ALT),
+                ("S-", KeyModifiers::SHIFT),
+                ("Shift-", KeyModifiers::SHIFT),
+            ]
+            .into_iter()
+            .find(|(p, _)| rest.len() > p.len() && rest.starts_with(p));
+            match prefix {
+                Some((p, modifier)) => {
+                    modifiers.insert(modifier);
+                    rest = &rest[p.len()..];
+                }
+                None => break,
+            }
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_lowercase().as_str() {
+                "tab" => KeyCode::Tab,
+                "enter" | "return" | "ret" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "space" | "spc" => KeyCode::Char(' '),
+                "backspace" | "bs" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(format!("Unknown key '{}' in '{}'", rest, spec)),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "C-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "M-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "S-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Keymap preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapStyle {
+    /// Modeless, Ctrl/Alt chords (the default)
+    #[default]
+    Emacs,
+    /// Emacs chords plus a vim-style normal mode
+    Vim,
+}
+
+/// One key or a list of keys for an action in the config file
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn specs(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(spec) => vec![spec.as_str()],
+            KeyList::Many(specs) => specs.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Key → action tables: one for normal editing, one for vim normal mode
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    style: KeymapStyle,
+    bindings: HashMap<KeyBinding, Action>,
+    normal: HashMap<KeyBinding, Action>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(KeymapStyle::Emacs)
+    }
+}
+
+impl Keymap {
+    /// The preset bindings for `style`
+    pub fn new(style: KeymapStyle) -> Self {
+        let mut keymap = Self {
+            style,
+            bindings: HashMap::new(),
+            normal: HashMap::new(),
+        };
+        load_table(&mut keymap.bindings, EMACS_BINDINGS);
+        if style == KeymapStyle::Vim {
+            load_table(&mut keymap.bindings, &[(Action::NormalMode, &["Esc"])]);
+            load_table(&mut keymap.normal, VIM_NORMAL_BINDINGS);
+        }
+        keymap
+    }
+
+    /// Preset plus config overrides (`[keys]` and `[normal_keys]`)
+    pub fn with_overrides(
+        style: KeymapStyle,
+        keys: &BTreeMap<String, KeyList>,
+        normal_keys: &BTreeMap<String, KeyList>,
+    ) -> Result<Self, String> {
+        let mut keymap = Self::new(style);
+        for (table, overrides) in [(false, keys), (true, normal_keys)] {
+            for (name, list) in overrides {
+                let action = Action::from_name(name)
+                    .ok_or_else(|| format!("Unknown action '{}' in key bindings", name))?;
+                let keys = list
+                    .specs()
+                    .into_iter()
+                    .map(KeyBinding::parse)
+                    .collect::<Result<Vec<_>, _>>()?;
+                keymap.bind(action, &keys, table);
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn style(&self) -> KeymapStyle {
+        self.style
+    }
+
+    /// Bind `action` to exactly `keys`, dropping its previous keys. A key that
+    /// was bound to another action now runs this one.
+    pub fn bind(&mut self, action: Action, keys: &[KeyBinding], normal_mode: bool) {
+        let table = if normal_mode {
+            &mut self.normal
+        } else {
+            &mut self.bindings
+        };
+        table.retain(|_, a| *a != action);
+        for key in keys {
+            table.insert(*key, action);
+        }
+    }
+
+    pub fn lookup(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&KeyBinding::from_event(key)).copied()
+    }
+
+    /// Vim normal-mode binding for `key`
+    pub fn lookup_normal(&self, key: &KeyEvent) -> Option<Action> {
+        self.normal.get(&KeyBinding::from_event(key)).copied()
+    }
+
+    /// Keys bound to `action` (normal editing table), for help text
+    pub fn keys_for(&self, action: Action) -> Vec<KeyBinding> {
+        let mut keys: Vec<KeyBinding> = self
+            .bindings
+            .iter()
+            .filter(|(_, a)| **a == action)
+            .map(|(key, _)| *key)
+            .collect();
+        keys.sort_by_key(|key| key.to_string());
+        keys
+    }
+}
+
+fn load_table(table: &mut HashMap<KeyBinding, Action>, bindings: &[(Action, &[&str])]) {
+    for (action, specs) in bindings {
+        for spec in *specs {
+            let key = KeyBinding::parse(spec).expect("built-in key specs are valid");
+            table.insert(key, *action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_parse_key_specs() {
+        let ctrl_x = KeyBinding::parse("C-x").unwrap();
+        assert_eq!(ctrl_x.code, KeyCode::Char('x'));
+        assert_eq!(ctrl_x.modifiers, KeyModifiers::CONTROL);
+        assert_eq!(KeyBinding::parse("Alt-/").unwrap().to_string(), "M-/");
+        assert_eq!(KeyBinding::parse("M--").unwrap().code, KeyCode::Char('-'));
+        assert_eq!(
+            KeyBinding::parse("C-M-Space").unwrap().to_string(),
+            "C-M-Space"
+        );
+        assert_eq!(KeyBinding::parse("F5").unwrap().code, KeyCode::F(5));
+        // Shift folds into characters; Shift+Tab matches BackTab events
+        assert_eq!(KeyBinding::parse("M-S-i"), KeyBinding::parse("M-I"));
+        assert_eq!(
+            KeyBinding::parse("S-Tab").unwrap(),
+            KeyBinding::from_event(&key(KeyCode::BackTab, KeyModifiers::SHIFT))
+        );
+        assert!(KeyBinding::parse("C-Foo").is_err());
+        assert!(KeyBinding::parse("F13").is_err());
+    }
+
+    #[test]
+    fn test_emacs_preset_matches_events() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.lookup(&key(KeyCode::Char('x'), KeyModifiers::CONTROL)),
+            Some(Action::EvalBlock)
+        );
+        assert_eq!(
+            keymap.lookup(&key(
+                KeyCode::Char('I'),
+                KeyModifiers::ALT | KeyModifiers::SHIFT
+            )),
+            Some(Action::MidiSmartPaste)
+        );
+        assert_eq!(
+            keymap.lookup(&key(KeyCode::Char('x'), KeyModifiers::NONE)),
+            None
+        );
+        assert_eq!(keymap.lookup(&key(KeyCode::Esc, KeyModifiers::NONE)), None);
+        // Config names round-trip
+        for action in Action::all() {
+            assert_eq!(Action::from_name(action.name()), Some(action));
+        }
+    }
+
+    #[test]
+    fn test_overrides_replace_defaults() {
+        let mut keys = BTreeMap::new();
+        keys.insert("eval_block".to_string(), KeyList::One("C-e".to_string()));
+        keys.insert(
+            "hush".to_string(),
+            KeyList::Many(vec!["C-h".to_string(), "F12".to_string()]),
+        );
+        keys.insert("kill_line".to_string(), KeyList::Many(Vec::new()));
+        let keymap = Keymap::with_overrides(KeymapStyle::Emacs, &keys, &BTreeMap::new()).unwrap();
+
+        let ctrl = |c| key(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert_eq!(keymap.lookup(&ctrl('e')), Some(Action::EvalBlock));
+        assert_eq!(keymap.lookup(&ctrl('x')), None);
+        assert_eq!(keymap.lookup(&ctrl('k')), None);
+        assert_eq!(
+            keymap.lookup(&key(KeyCode::F(12), KeyModifiers::NONE)),
+            Some(Action::Hush)
+        );
+        assert_eq!(keymap.keys_for(Action::Hush).len(), 2);
+
+        keys.insert(
+            "no_such_action".to_string(),
+            KeyList::One("C-z".to_string()),
+        );
+        assert!(Keymap::with_overrides(KeymapStyle::Emacs, &keys, &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_vim_preset() {
+        let keymap = Keymap::new(KeymapStyle::Vim);
+        let plain = |c| key(KeyCode::Char(c), KeyModifiers::NONE);
+        assert_eq!(keymap.lookup_normal(&plain('j')), Some(Action::NextLine));
+        assert_eq!(
+            keymap.lookup_normal(&key(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+            Some(Action::AppendLineEnd)
+        );
+        assert_eq!(
+            keymap.lookup(&key(KeyCode::Esc, KeyModifiers::NONE)),
+            Some(Action::NormalMode)
+        );
+        // Emacs chords still work in vim mode
+        assert_eq!(
+            keymap.lookup(&key(KeyCode::Char('x'), KeyModifiers::CONTROL)),
+            Some(Action::EvalBlock)
+        );
+        assert_eq!(Keymap::default().lookup_normal(&plain('j')), None);
+    }
+}
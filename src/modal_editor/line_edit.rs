@@ -0,0 +1,162 @@
+//! Line-oriented edits: block comment toggle and indentation
+//!
+//! Each edit works on the whole lines touched by a byte range (the selection,
+//! or just the cursor) and returns the new content plus the byte span of the
+//! edited lines, so the editor can keep them selected.
+
+/// One indentation level
+pub const INDENT: &str = "  ";
+/// Line comment marker in Phonon code
+pub const COMMENT: &str = "--";
+
+/// Byte span `(start, end)` of the whole lines touched by `from..to`
+///
+/// `end` is the end of the last line, excluding its newline. A range ending
+/// right after a newline doesn't include the following line.
+pub fn line_span(content: &str, from: usize, to: usize) -> (usize, usize) {
+    let start = content[..from].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let to = if to > from && content[..to].ends_with('\n') {
+        to - 1
+    } else {
+        to
+    };
+    let end = content[to..]
+        .find('\n')
+        .map(|i| to + i)
+        .unwrap_or(content.len());
+    (start, end.max(start))
+}
+
+/// Apply `edit` to every line in `span`
+fn map_lines(
+    content: &str,
+    span: (usize, usize),
+    edit: impl Fn(&str) -> String,
+) -> (String, (usize, usize)) {
+    let (start, end) = span;
+    let block = content[start..end]
+        .split('\n')
+        .map(edit)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let new_end = start + block.len();
+    (
+        format!("{}{}{}", &content[..start], block, &content[end..]),
+        (start, new_end),
+    )
+}
+
+fn indent_width(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Comment out the lines in `span`, or uncomment them if every non-blank line
+/// is already a comment. Comments go at the block's smallest indentation so
+/// the code keeps its shape; blank lines are left alone.
+pub fn toggle_comment(content: &str, span: (usize, usize)) -> (String, (usize, usize)) {
+    let lines: Vec<&str> = content[span.0..span.1]
+        .split('\n')
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+    let all_commented =
+        !lines.is_empty() && lines.iter().all(|l| l.trim_start().starts_with(COMMENT));
+
+    if all_commented {
+        map_lines(content, span, |line| {
+            let indent = indent_width(line);
+            match line[indent..].strip_prefix(COMMENT) {
+                Some(rest) => {
+                    let rest = rest.strip_prefix(' ').unwrap_or(rest);
+                    format!("{}{}", &line[..indent], rest)
+                }
+                None => line.to_string(),
+            }
+        })
+    } else {
+        let column = lines.iter().map(|l| indent_width(l)).min().unwrap_or(0);
+        map_lines(content, span, |line| {
+            if line.trim().is_empty() {
+                line.to_string()
+            } else {
+                format!("{}{} {}", &line[..column], COMMENT, &line[column..])
+            }
+        })
+    }
+}
+
+/// Indent the non-blank lines in `span` by one level
+pub fn indent(content: &str, span: (usize, usize)) -> (String, (usize, usize)) {
+    map_lines(content, span, |line| {
+        if line.trim().is_empty() {
+            line.to_string()
+        } else {
+            format!("{}{}", INDENT, line)
+        }
+    })
+}
+
+/// Remove up to one level of indentation from the lines in `span`
+pub fn dedent(content: &str, span: (usize, usize)) -> (String, (usize, usize)) {
+    map_lines(content, span, |line| {
+        if let Some(rest) = line.strip_prefix('\t') {
+            return rest.to_string();
+        }
+        let spaces = indent_width(line).min(INDENT.len());
+        line[spaces..].to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = "tempo: 0.5\n~bass $ saw 55\n  # lpf 800 0.7\nout $ ~bass";
+
+    #[test]
+    fn test_line_span() {
+        // Cursor only: its own line
+        assert_eq!(line_span(CODE, 13, 13), (11, 25));
+        // Selection across lines, partial at both ends
+        assert_eq!(line_span(CODE, 13, 30), (11, 41));
+        // A selection ending at a line start stops at the previous line
+        assert_eq!(line_span(CODE, 11, 26), (11, 25));
+        assert_eq!(line_span("", 0, 0), (0, 0));
+    }
+
+    #[test]
+    fn test_toggle_comment_round_trip() {
+        let span = line_span(CODE, 11, 30);
+        let (commented, new_span) = toggle_comment(CODE, span);
+        assert_eq!(
+            commented,
+            "tempo: 0.5\n-- ~bass $ saw 55\n--   # lpf 800 0.7\nout $ ~bass"
+        );
+        assert_eq!(
+            &commented[new_span.0..new_span.1],
+            "-- ~bass $ saw 55\n--   # lpf 800 0.7"
+        );
+
+        let (uncommented, _) = toggle_comment(&commented, new_span);
+        assert_eq!(uncommented, CODE);
+    }
+
+    #[test]
+    fn test_toggle_comment_mixed_block_comments_everything() {
+        let code = "-- old idea\nout $ sine 440\n\n";
+        let (result, _) = toggle_comment(code, line_span(code, 0, code.len()));
+        assert_eq!(result, "-- -- old idea\n-- out $ sine 440\n\n");
+    }
+
+    #[test]
+    fn test_indent_and_dedent() {
+        let span = line_span(CODE, 0, 25);
+        let (indented, new_span) = indent(CODE, span);
+        assert!(indented.starts_with("  tempo: 0.5\n  ~bass $ saw 55\n  # lpf"));
+        let (dedented, _) = dedent(&indented, new_span);
+        assert_eq!(dedented, CODE);
+
+        // Dedent never removes more than one level, or non-whitespace
+        let (once, _) = dedent("      x\ny", (0, 9));
+        assert_eq!(once, "    x\ny");
+    }
+}
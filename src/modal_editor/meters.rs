@@ -0,0 +1,136 @@
+//! Per-bus clip indicators
+//!
+//! Each frame the editor takes the live graph's bus peaks ([`BusMeters`]) and
+//! holds them long enough to read. A bus that went over full scale shows CLIP
+//! for a few seconds, so a brief overload isn't missed. The indicator is drawn
+//! at the end of the line that defines the bus.
+
+use crate::bus_meters::{to_db, BusMeters};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long a peak stays on screen before a lower one replaces it
+const PEAK_HOLD: Duration = Duration::from_millis(1500);
+
+/// How long CLIP stays up after a bus goes over full scale
+const CLIP_HOLD: Duration = Duration::from_secs(3);
+
+/// Levels below this show no indicator
+const SILENCE: f32 = 1e-4;
+
+/// Level above which the indicator warns (-6 dBFS)
+const HOT: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy)]
+struct Held {
+    peak: f32,
+    since: Instant,
+    clip_until: Option<Instant>,
+}
+
+/// How an indicator should look
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Normal,
+    Hot,
+    Clip,
+}
+
+/// Peak-hold state of every bus of the live graph
+#[derive(Debug, Default)]
+pub struct BusLevels {
+    held: HashMap<String, Held>,
+}
+
+impl BusLevels {
+    /// Fold in the peaks recorded since the last update
+    pub fn update(&mut self, meters: &BusMeters, now: Instant) {
+        for (name, peak) in meters.take_recent() {
+            let held = self.held.entry(name.to_string()).or_insert(Held {
+                peak: 0.0,
+                since: now,
+                clip_until: None,
+            });
+            if peak >= held.peak || now.duration_since(held.since) > PEAK_HOLD {
+                held.peak = peak;
+                held.since = now;
+            }
+            if peak > 1.0 {
+                held.clip_until = Some(now + CLIP_HOLD);
+            }
+        }
+    }
+
+    /// Forget every bus (a new graph has different buses)
+    pub fn clear(&mut self) {
+        self.held.clear();
+    }
+
+    /// Indicator text for `bus`, or None while it is silent or unknown
+    pub fn indicator(&self, bus: &str, now: Instant) -> Option<(String, Level)> {
+        let held = self.held.get(bus)?;
+        if held.clip_until.is_some_and(|until| now < until) {
+            return Some(("● CLIP".to_string(), Level::Clip));
+        }
+        if held.peak < SILENCE {
+            return None;
+        }
+        let level = if held.peak > HOT {
+            Level::Hot
+        } else {
+            Level::Normal
+        };
+        Some((format!("▮ {:.1} dB", to_db(held.peak)), level))
+    }
+}
+
+/// Bus defined on a line (`~name $ ...`, `~name # ...`, `~name: ...`)
+pub fn bus_defined_on(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('~')?;
+    let end = rest
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let (name, after) = rest.split_at(end);
+    let after = after.trim_start();
+    let defines = after.starts_with('$') || after.starts_with('#') || after.starts_with(':');
+    (!name.is_empty() && defines).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bus_defined_on() {
+        assert_eq!(bus_defined_on("~bass $ saw 55"), Some("bass"));
+        assert_eq!(bus_defined_on("  ~fx # lpf 800 0.5"), Some("fx"));
+        assert_eq!(bus_defined_on("~old: sine 440"), Some("old"));
+        assert_eq!(bus_defined_on("out $ ~bass"), None);
+        assert_eq!(bus_defined_on("~bass * 0.5"), None);
+    }
+
+    #[test]
+    fn test_clip_holds_after_the_peak_drops() {
+        let meters = BusMeters::new(["lead".to_string()]);
+        let mut levels = BusLevels::default();
+        let start = Instant::now();
+
+        meters.record_bus(0, 1.5);
+        levels.update(&meters, start);
+        assert_eq!(levels.indicator("lead", start).unwrap().1, Level::Clip);
+
+        // Quiet again, but CLIP stays up for a while
+        meters.record_bus(0, 0.1);
+        let later = start + Duration::from_secs(2);
+        levels.update(&meters, later);
+        assert_eq!(levels.indicator("lead", later).unwrap().1, Level::Clip);
+
+        let much_later = start + Duration::from_secs(4);
+        meters.record_bus(0, 0.1);
+        levels.update(&meters, much_later);
+        let (text, level) = levels.indicator("lead", much_later).unwrap();
+        assert_eq!(level, Level::Normal);
+        assert_eq!(text, "▮ -20.0 dB");
+        assert!(levels.indicator("other", much_later).is_none());
+    }
+}
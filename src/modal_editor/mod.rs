@@ -6,43 +6,90 @@
 //! real-time audio generation using ring buffer architecture for parallel synthesis
 
 #![allow(clippy::redundant_pattern_matching)]
+mod audition;
 mod command_console;
 pub mod completion;
+pub mod config;
+mod cue_output;
+mod cv_output;
 mod highlighting;
+pub mod keymap;
+mod line_edit;
+mod meters;
+mod pane;
 mod plugin_browser;
+pub mod render_queue;
+mod scrub;
+pub mod snippets;
+mod step_grid;
+mod tempo_sync;
 pub mod test_harness;
 
-use command_console::CommandConsole;
-use highlighting::highlight_line;
+use audition::{audition_channel, audition_program, render_audition, Audition};
+use command_console::{CommandConsole, ConsoleAction};
+use config::EditorConfig;
+use cue_output::{cue_channel, start_cue_device, CueTarget};
+use cv_output::{cv_channel, missing_channels_warning};
+use highlighting::{highlight_line_with, highlight_tokens, Theme};
+use keymap::{Action, Keymap, KeymapStyle};
+use pane::{buffer_title, PaneState};
 use plugin_browser::PluginBrowser;
-
-use crate::compositional_compiler::compile_program;
-use crate::compositional_parser::parse_program;
-use crate::midi_input::{MidiEvent, MidiInputHandler, MidiMessageType, MidiRecorder};
+use render_queue::{RenderJob, RenderQueue, RenderUpdate};
+use step_grid::StepGrid;
+use tempo_sync::{SyncSource, TapTempo, TempoSync};
+
+use crate::adaptive_quality::{Quality, QualityControl, QualityGovernor};
+use crate::audio_device::{build_output_stream_converted, select_output_device};
+use crate::bus_meters::BusMeters;
+use crate::compositional_compiler::{
+    compile_program, compile_program_cached, merge_programs, morph_redefined_buses, morph_target,
+    take_requests, CompileCache,
+};
+use crate::compositional_parser::{
+    classify_source, parse_program, parse_program_recovering, LineTokens, Statement,
+};
+use crate::engine_config::{EngineConfig, MAX_BLOCK_SIZE, MIN_BLOCK_SIZE};
+use crate::error_diagnostics::DiagnosticError;
+use crate::event_log::EventLog;
+use crate::latency::Nudge;
+use crate::link_clock::{SyncFollower, TempoSource, DEFAULT_BEATS_PER_CYCLE};
+use crate::live_render::LiveRender;
+use crate::midi_input::{
+    MidiClockIn, MidiEvent, MidiInputHandler, MidiMessageType, MidiRecorder, TakeRecording,
+    TakeRequest,
+};
+use crate::midi_output::MidiClockOut;
+use crate::output_buffer::{
+    buffer_frames, negotiate_output_config, requested_buffer_frames, ring_capacity, LatencyMonitor,
+};
 use crate::plugin_host::PluginInstanceManager;
 use crate::render_swap::{render_swap_channel_default, Cmd, CommandSender, Graveyard, RenderSwap};
-use crate::unified_graph::{LiveClock, UnifiedSignalGraph};
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use crate::render_watchdog::RenderWatchdog;
+use crate::ring_handoff::sample_ring;
+use crate::session::{Session, SessionPane};
+use crate::unified_graph::{GraphShape, MasterClip, UnifiedSignalGraph};
+use cpal::traits::{DeviceTrait, StreamTrait};
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame, Terminal,
 };
-use ringbuf::traits::{Consumer, Observer, Producer, Split};
-use ringbuf::HeapRb;
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Ref, RefCell};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -52,6 +99,13 @@ use std::time::Duration as StdDuration;
 #[cfg(all(target_os = "linux", feature = "vst3"))]
 use rack::Vst3Gui;
 
+/// Lines kept between the cursor and the top/bottom edge when scrolling
+const SCROLL_MARGIN: u16 = 2;
+/// Lines scrolled per mouse wheel notch
+const MOUSE_SCROLL_LINES: u16 = 3;
+/// Seconds a bus moves per Alt+Left/Right nudge
+const NUDGE_STEP: f64 = 0.005;
+
 /// Headless render side (test / no-audio-device mode).
 ///
 /// In the audio build the render-owner state lives on the background synth
@@ -71,6 +125,10 @@ struct LocalRender {
     graveyard: Graveyard<UnifiedSignalGraph>,
     /// The single render-owned graph. `None` until the first graph is loaded.
     cur: Option<Box<UnifiedSignalGraph>>,
+    /// Crash / NaN guard, as on the synth thread
+    watchdog: RenderWatchdog,
+    /// Auditions to mix over the output, as on the synth thread
+    audition_mixer: audition::AuditionMixer,
 }
 
 impl LocalRender {
@@ -116,9 +174,19 @@ pub struct ModalEditor {
     init_tx: std::sync::mpsc::Sender<Box<UnifiedSignalGraph>>,
     /// Whether the first graph has been handed off (selects init vs. swap path).
     first_graph_sent: bool,
+    /// Shape of the last graph handed off, which the next one plans its bus
+    /// state transfer against before the swap
+    playing_shape: GraphShape,
     /// Live cycle position published by the render owner (f64 stored as bits),
     /// for UI / MIDI reads that must not touch the render-owned graph.
     current_cycle_bits: Arc<AtomicU64>,
+    /// Crash and NaN reports from the render watchdog, shown in the console
+    watchdog_rx: std::sync::mpsc::Receiver<String>,
+    /// Adaptive quality level published by the synth thread, and the
+    /// `:quality locked` override
+    quality: Arc<QualityControl>,
+    /// Quality level last reported in the console
+    shown_quality: Quality,
     /// Headless render side — `Some` only when there is no synth thread (tests).
     render_local: Option<RefCell<LocalRender>>,
     /// VST3 plugin instances, shared with every compiled graph so plugin state
@@ -128,14 +196,42 @@ pub struct ModalEditor {
         Arc<std::sync::Mutex<HashMap<String, crate::plugin_host::RealPluginInstance>>>,
     /// Audio stream (kept alive) - None in headless mode for testing
     _stream: Option<cpal::Stream>,
+    /// Headphone cue stream on its own device, if configured
+    _cue_stream: Option<cpal::Stream>,
     /// Sample rate
     sample_rate: f32,
+    /// Channels of the output device (None in headless mode)
+    output_channels: Option<usize>,
+    /// Realtime budget for rendering one synthesis chunk (µs)
+    synth_budget_us: usize,
     /// Flash highlight for evaluated chunk (start_line, end_line, frames_remaining)
     flash_highlight: Option<(usize, usize, u8)>,
     /// Kill buffer for Emacs-style cut/yank
     kill_buffer: String,
+    /// Other end of the selection (Shift+movement); the cursor is the active end
+    selection_anchor: Option<usize>,
+    /// Key bindings (emacs or vim preset plus config overrides)
+    keymap: Keymap,
+    /// Vim normal mode (only with the vim keymap)
+    vim_normal: bool,
+    /// Editor colors
+    theme: Theme,
+    /// Parser classification of `content`, recomputed when the content changes
+    highlight_cache: RefCell<(String, Vec<LineTokens>)>,
+    /// Tempo (cps) for code without a tempo:/bpm: statement
+    default_tempo: Option<f32>,
+    /// DC blocker on the master output (`dc_block` in the config)
+    dc_block: bool,
+    /// Master clipper (`master_clip` in the config)
+    master_clip: MasterClip,
+    /// Bus levels of the live graph, and their peak-hold state
+    bus_meters: Option<Arc<BusMeters>>,
+    bus_levels: meters::BusLevels,
     /// Undo stack (content, cursor_pos)
     undo_stack: Vec<(String, usize)>,
+    /// Undo depth and literal start of the last scrub, so a run of scrubs on
+    /// one number undoes in one step
+    last_scrub: Option<(usize, usize)>,
     /// Redo stack (content, cursor_pos)
     redo_stack: Vec<(String, usize)>,
     /// Console messages for display
@@ -148,20 +244,40 @@ pub struct ModalEditor {
     bus_names: Vec<String>,
     /// Command console for help and discovery
     command_console: CommandConsole,
+    /// Background `:render` jobs (offline copies of the buffer)
+    render_queue: RenderQueue,
     /// Underrun counter (shared with audio callback)
     underrun_count: Arc<AtomicUsize>,
     /// Synthesis performance stats (shared with synthesis thread)
     synth_time_us: Arc<AtomicUsize>,
     /// Ring buffer fill level (0-100%)
     ring_fill_percent: Arc<AtomicUsize>,
-    /// Signal to clear ring buffer on next audio callback (instant transitions)
+    /// Measured output latency (updated by the audio callback)
+    latency: Arc<LatencyMonitor>,
+    /// Asks the synth thread to drop queued audio once the last command applies
     should_clear_ring: Arc<AtomicBool>,
+    /// Whether `:stop` is holding playback
+    transport_stopped: bool,
+    /// MIDI clock out (`midi_clock_out` in the config), following the transport
+    midi_clock: Option<MidiClockOut>,
+    /// External tempo playback follows (`:sync`, Alt+T)
+    tempo_sync: Option<TempoSync>,
+    /// Tempo and phase correction toward `tempo_sync`
+    sync_follower: SyncFollower,
+    /// Transport and resync count last seen from the MIDI clock followed
+    sync_transport: (Option<bool>, u64),
+    /// Tempo the last sync step set, which re-evaluations keep
+    synced_cps: Option<f32>,
     /// MIDI input handler
     midi_input: Option<MidiInputHandler>,
     /// MIDI recorder for capturing patterns
     midi_recorder: Option<MidiRecorder>,
     /// Whether MIDI recording is active
     midi_recording: bool,
+    /// Takes armed by `record` statements, capturing until their last cycle
+    take_recordings: Vec<TakeRecording>,
+    /// Takes already captured; a `record` statement records once
+    takes_captured: HashSet<TakeRequest>,
     /// Recorded MIDI pattern (ready to insert)
     midi_recorded_pattern: Option<String>,
     /// Recorded MIDI pattern as n-offsets (ready to insert)
@@ -182,6 +298,14 @@ pub struct ModalEditor {
     midi_quantize: u8,
     /// Whether to show configuration panel
     show_config_panel: bool,
+    /// Whether to show help for the call under the cursor (Alt+H)
+    show_inline_help: bool,
+    /// Whether to show the event log pane (Alt+E)
+    show_event_log: bool,
+    /// Events triggered by the render thread, for the event log pane
+    event_log: EventLog,
+    /// Sends one-cycle previews (Alt+Enter) to be mixed over the output
+    audition: Audition,
     /// Live recording preview line (displayed during recording)
     recording_preview_line: Option<String>,
     /// Currently held notes during recording (for live display)
@@ -190,8 +314,36 @@ pub struct ModalEditor {
     scroll_offset: u16,
     /// Last known viewport height (for scroll calculations)
     viewport_height: u16,
+    /// Last known editor pane, borders included (for mapping mouse clicks)
+    editor_area: Rect,
+    /// The pane without focus when split side by side (Alt+O / `:split`)
+    split: Option<PaneState>,
+    /// Whether the focused pane is the left one
+    focus_left: bool,
+    /// Last known area of the pane without focus
+    other_pane_area: Rect,
+    /// Code this buffer last sent to the engine (merged with the other pane's)
+    evaluated: Option<String>,
+    /// Tempo of the last graph sent to the engine, for `:save-session`
+    evaluated_cps: f32,
+    /// Node state of a session being loaded, put into the next compiled graph
+    resumed_node_states: Option<Vec<u8>>,
+    /// Statements compiled by the last evaluation, reused by the next
+    compile_cache: CompileCache,
+    /// Buses that crossfade to a new definition over this many cycles when
+    /// re-evaluated (`:morph ~bus <cycles>`)
+    morphs: BTreeMap<String, f64>,
+    /// The program the playing graph was compiled from, morphs included
+    playing_program: Vec<Statement>,
+    /// The cycle each bus's running morph finishes at
+    morph_ends: BTreeMap<String, f64>,
+    /// Each bus's live offset (`:nudge`), shared with the playing graph and
+    /// carried over to the next
+    nudges: BTreeMap<String, Arc<Nudge>>,
     /// Plugin browser panel
     plugin_browser: PluginBrowser,
+    /// Step grid open on a pattern string (Alt+G)
+    step_grid: Option<StepGrid>,
     /// Plugin instance manager
     plugin_manager: PluginInstanceManager,
     /// Active VST3 GUI windows (plugin_name -> GUI handle)
@@ -211,9 +363,24 @@ impl ModalEditor {
         _duration: f32, // Deprecated parameter, kept for API compatibility
         file_path: Option<PathBuf>,
         buffer_size: Option<usize>,
+        latency_ms: Option<f32>,
+        block_size: Option<usize>,
+        device_name: Option<&str>,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        // Buffer size from CLI arg, clamped to valid range (default 512)
-        let synthesis_buffer_size = buffer_size.unwrap_or(512).clamp(64, 16384);
+        // ~/.phonon/config.toml; a broken file falls back to the defaults
+        // (the error is shown in the console once the editor is up)
+        let (editor_config, config_error) = match EditorConfig::load() {
+            Ok(config) => (config, None),
+            Err(e) => (EditorConfig::default(), Some(e)),
+        };
+
+        // Buffer size from CLI arg (or config). Frames per block from
+        // --block-size (or config); without either, a buffer size (in stereo
+        // samples) still sets the block as it used to
+        let buffer_size = buffer_size.or(editor_config.buffer_size);
+        let block_size = block_size
+            .or(editor_config.block_size)
+            .or(buffer_size.map(|samples| (samples / 2).clamp(MIN_BLOCK_SIZE, MAX_BLOCK_SIZE)));
 
         // Suppress stderr output that would break the TUI
         // This includes: ALSA errors, X11 authorization messages, VST3 plugin output
@@ -238,11 +405,9 @@ impl ModalEditor {
             }
         }
 
-        // Get audio device
+        // Get audio device (--device name, or the system default)
         let host = cpal::default_host();
-        let device = host
-            .default_output_device()
-            .ok_or("No output device available")?;
+        let device = select_output_device(&host, device_name)?;
 
         let default_config = device
             .default_output_config()
@@ -250,10 +415,16 @@ impl ModalEditor {
 
         let sample_rate = default_config.sample_rate().0 as f32;
         let channels = default_config.channels() as usize;
+        let engine = EngineConfig::new(sample_rate, block_size)?;
+        // Synthesis chunks are stereo-interleaved: len / 2 frames each
+        let synthesis_buffer_size = engine.block_samples(2);
+        let synth_budget_us = engine.block_budget_us();
         let sample_format = default_config.sample_format();
 
-        // Use default buffer size (ring buffer handles buffering)
-        let config: cpal::StreamConfig = default_config.into();
+        // Device buffer: fixed when --buffer-size / --latency is given (negotiated
+        // down to what the device accepts), otherwise the device default
+        let requested = requested_buffer_frames(buffer_size, latency_ms, sample_rate);
+        let config = negotiate_output_config(&device, &default_config, requested);
 
         // Note: These messages go to log file now, not visible in TUI
         // eprintln!("🎵 Audio: {} Hz, {} channels, buffer: {} samples", sample_rate as u32, channels, synthesis_buffer_size);
@@ -272,6 +443,12 @@ impl ModalEditor {
             std::sync::mpsc::channel::<Box<UnifiedSignalGraph>>();
         // Live cycle position published by the synth thread for UI / MIDI reads.
         let current_cycle_bits = Arc::new(AtomicU64::new(0));
+        // Render watchdog reports (synth thread panics, NaN output) for the console.
+        let (watchdog_tx, watchdog_rx) = std::sync::mpsc::channel::<String>();
+        // Alt+Enter previews, mixed over the output by the synth thread
+        let (audition, mut audition_mixer) = audition_channel();
+        // Adaptive quality level (synth thread) and `:quality locked` (UI)
+        let quality = Arc::new(QualityControl::default());
 
         // Underrun counter (shared between audio callback and UI)
         let underrun_count = Arc::new(AtomicUsize::new(0));
@@ -280,16 +457,68 @@ impl ModalEditor {
         let synth_time_us = Arc::new(AtomicUsize::new(0));
         let ring_fill_percent = Arc::new(AtomicUsize::new(100));
 
-        // Flag to signal audio callback to drain ring buffer on graph swap
-        // This enables instant transitions without hearing stale audio
+        // Set by hush / panic / cue after sending their command. The synth
+        // thread, once it has applied the command, marks the audio queued so
+        // far as stale and the audio callback skips it (see `ring_handoff`)
         let should_clear_ring = Arc::new(AtomicBool::new(false));
 
         // Ring buffer: background synth writes, audio callback reads
-        // Size: ~200ms - balance between latency and cushion for variation
-        // With sample preloading, we don't need a huge buffer for initialization spikes
-        let ring_buffer_size = (sample_rate as usize / 5).max(4410); // ~200ms
-        let ring = HeapRb::<f32>::new(ring_buffer_size);
-        let (mut ring_producer, mut ring_consumer) = ring.split();
+        // Size: ~200ms (or `ring_buffer_ms` from the config) - balance between
+        // latency and cushion for variation.
+        // With sample preloading, we don't need a huge buffer for initialization spikes.
+        // A fixed device buffer shrinks it to a few device buffers (requested latency).
+        let cushion_ms = editor_config.ring_buffer_ms.unwrap_or(200.0);
+        let ring_buffer_size = buffer_frames(&config).map_or(
+            ((sample_rate * cushion_ms / 1000.0) as usize).max(4410),
+            |frames| ring_capacity(frames, channels, synthesis_buffer_size * 2),
+        );
+        let (mut ring_writer, mut ring_reader) =
+            sample_ring(ring_buffer_size, Arc::clone(&underrun_count));
+
+        // Headphone cue (`cue $ ...` / `precue ~bus`): a ring of its own, played
+        // on channels 3/4 of this device or on a second device
+        let mut cue_feed = None;
+        let mut main_cue = None;
+        let mut cue_clear = None;
+        let mut cue_stream = None;
+        let mut cue_warning = None;
+        if let Some(target) = editor_config.cue_device.as_deref().map(CueTarget::parse) {
+            let (feed, tap, clear) = cue_channel(ring_buffer_size, synthesis_buffer_size);
+            let routed = match target {
+                CueTarget::MainChannels if channels >= 4 => {
+                    main_cue = Some(tap);
+                    Ok(())
+                }
+                CueTarget::MainChannels => Err(format!(
+                    "cue_device = \"3/4\" needs a 4-channel output (this one has {})",
+                    channels
+                )),
+                CueTarget::Device(name) => {
+                    start_cue_device(&host, &name, sample_rate, tap).map(|s| cue_stream = Some(s))
+                }
+            };
+            match routed {
+                Ok(()) => {
+                    cue_feed = Some(feed);
+                    cue_clear = Some(clear);
+                }
+                Err(e) => cue_warning = Some(e),
+            }
+        }
+
+        // Control voltages (`cvout` / `gateout`) on channels 3 and up
+        let cv_full_scale = editor_config
+            .cv_full_scale
+            .unwrap_or(crate::cv::DEFAULT_FULL_SCALE);
+        let (mut cv_feed, mut main_cv, cv_clear) = match cv_channel(
+            channels,
+            ring_buffer_size / 2,
+            synthesis_buffer_size / 2,
+            cv_full_scale,
+        ) {
+            Some((feed, tap, clear)) => (Some(feed), Some(tap), Some(clear)),
+            None => (None, None, None),
+        };
 
         // Janitor thread: drops retired graphs OFF the render thread. Dropping a
         // graph frees voice buffers, sample Arcs and FX delay lines — unbounded
@@ -310,8 +539,13 @@ impl ModalEditor {
         let synth_time_us_clone = Arc::clone(&synth_time_us);
         let ring_fill_clone = Arc::clone(&ring_fill_percent);
         let cycle_bits_synth = Arc::clone(&current_cycle_bits);
-        let mut render_swap = render_swap;
+        let should_clear_synth = Arc::clone(&should_clear_ring);
+        let watchdog = RenderWatchdog::new(watchdog_tx);
+        let quality_synth = Arc::clone(&quality);
         thread::spawn(move || {
+            // Decaying tails must not fall into slow subnormal arithmetic
+            crate::denormals::enable_flush_to_zero();
+
             // Render in chunks of synthesis_buffer_size samples (stereo-interleaved,
             // so buffer.len()/2 frames of cycle-time per chunk).
             let mut buffer = vec![0.0f32; synthesis_buffer_size];
@@ -320,13 +554,13 @@ impl ModalEditor {
             // Phase 1: no graph yet. Feed silence so the ring never starves
             // (matching the pre-migration "no graph ⇒ write silence" behavior),
             // until the first graph arrives on the one-shot init channel.
-            let mut cur: Box<UnifiedSignalGraph> = loop {
+            let cur: Box<UnifiedSignalGraph> = loop {
                 match init_rx.try_recv() {
                     Ok(g) => break g,
                     Err(std::sync::mpsc::TryRecvError::Empty) => {
-                        if ring_producer.vacant_len() >= buffer.len() {
+                        if ring_writer.vacant_len() >= buffer.len() {
                             buffer.fill(0.0);
-                            ring_producer.push_slice(&buffer);
+                            ring_writer.push(&buffer);
                             synth_time_us_clone.store(0, Ordering::Relaxed);
                         } else {
                             thread::sleep(StdDuration::from_micros(100));
@@ -337,39 +571,38 @@ impl ModalEditor {
                 }
             };
 
-            // Phase 2: single-owner render loop. `cur` is a plain local — no Arc,
-            // no RefCell, no cross-thread borrow. The sample-advancing LiveClock is
-            // THE single source of timing truth (pt-F1); tempo follows the compiled
-            // graph, rebasing on change without teleporting the beat (pt-F2).
-            let mut clock: Option<LiveClock> = None;
-            // Heap address of the owned graph. A swap replaces the box, so a changed
-            // address means "the graph was swapped" (ABA-safe: the retired graph
-            // stays alive in the graveyard until the janitor drops it).
-            let mut prev_ptr = cur.as_ref() as *const UnifiedSignalGraph;
+            // Phase 2: single-owner render loop. The graph is a plain local of
+            // `LiveRender` — no Arc, no RefCell, no cross-thread borrow — whose
+            // sample-advancing LiveClock is THE single source of timing truth
+            // (pt-F1); tempo follows the compiled graph, rebasing on change
+            // without teleporting the beat (pt-F2). `phonon live` and
+            // `PhononEngine::stream` render through the same loop body.
+            let mut live = LiveRender::new(render_swap, cur, watchdog);
             let mut renders = 0u64;
             let mut last_log = std::time::Instant::now();
+            // Steps quality down instead of underrunning when blocks run late
+            let mut governor = QualityGovernor::new();
 
             loop {
                 // Log render throughput once a second (log file, not the TUI).
                 if last_log.elapsed().as_secs() >= 1 {
-                    let required_renders = 86; // ~44100 / 512 buffers per second
+                    // Chunks per second needed to keep up at the device rate
+                    let required_renders = (sample_rate / frames as f32).ceil() as u64;
                     let status = if renders >= required_renders {
                         "✅"
                     } else {
                         "❌ UNDERRUN RISK"
                     };
-                    eprintln!(
+                    crate::rt_log::log(format_args!(
                         "🔧 Synth: {} renders/s (need {}) {}",
                         renders, required_renders, status
-                    );
+                    ));
                     renders = 0;
                     last_log = std::time::Instant::now();
                 }
 
-                let space = ring_producer.vacant_len();
-                let total_size = ring_producer.capacity().get();
-                let fill_percent = ((total_size - space) * 100) / total_size;
-                ring_fill_clone.store(fill_percent, Ordering::Relaxed);
+                let space = ring_writer.vacant_len();
+                ring_fill_clone.store(ring_writer.fill_percent(), Ordering::Relaxed);
 
                 if space < buffer.len() {
                     // Ring full — sleep briefly.
@@ -379,201 +612,147 @@ impl ModalEditor {
 
                 let start = std::time::Instant::now();
 
-                // Buffer boundary: apply pending swap / hush / panic to the owned
-                // graph. `absorb_state` runs the in-thread transfer, the pointer
-                // swap is atomic, and the retired graph goes to the graveyard — all
-                // as one uninterrupted step, so a swap only ever takes effect
-                // BETWEEN buffers and the graph is never rendered voiceless
-                // (design §4.1/§4.3, R1/R2/R3).
-                render_swap.apply_pending_commands(&mut cur);
-                let cur_ptr = cur.as_ref() as *const UnifiedSignalGraph;
-                let is_new_graph = !std::ptr::eq(cur_ptr, prev_ptr);
-                prev_ptr = cur_ptr;
-
-                // Seed / rebase the live clock, then render with its sample-advanced
-                // timing (single source of truth).
-                match clock.as_mut() {
-                    None => {
-                        // First graph: seed from its compiled start position so
-                        // setCycle / resetCycles are honoured (installed raw, so its
-                        // timing is pristine).
-                        clock = Some(LiveClock::new(
-                            sample_rate,
-                            cur.get_cps(),
-                            cur.get_cycle_position(),
-                        ));
-                    }
-                    Some(c) => {
-                        // Follow the graph tempo, rebasing on change so the position
-                        // never teleports (pt-F2).
-                        c.set_cps(cur.get_cps());
-                        if is_new_graph {
-                            // Seed the swapped-in graph from the live clock so it
-                            // continues from the current position with no re-trigger
-                            // burst (no C-x burst).
-                            cur.set_cycle_position(c.position());
-                        }
-                    }
+                // Buffer boundary: apply pending swap / hush / panic / seek to the
+                // owned graph. `absorb_state` runs the in-thread transfer, the
+                // pointer swap is atomic, and the retired graph goes to the
+                // graveyard — all as one uninterrupted step, so a swap only ever
+                // takes effect BETWEEN buffers and the graph is never rendered
+                // voiceless (design §4.1/§4.3, R1/R2/R3). A swapped-in graph is
+                // seeded from the live clock, so it continues from the current
+                // position with no re-trigger burst (no C-x burst).
+                //
+                // The clear flag is read BEFORE draining: the UI sends its
+                // command before setting the flag, so a set flag means the
+                // command is already queued. Discarding after applying it, the
+                // callback skips exactly the audio rendered before the command.
+                let clear = should_clear_synth.swap(false, Ordering::Acquire);
+                let is_new_graph = live.begin_block();
+                if clear {
+                    ring_writer.discard_queued();
+                }
+                if is_new_graph {
+                    live.graph_mut().set_quality(governor.level());
                 }
 
-                let c = clock.as_mut().unwrap();
-                let (start_cycle, increment, cps) = c.advance_buffer(frames);
-                cur.process_buffer_at(&mut buffer, start_cycle, increment, cps);
+                // Stopped (`:stop`): render nothing, so the clock and every
+                // voice stay exactly where they were until `:start`. A
+                // panicking render comes back as a silent block, with the
+                // crashed graph retired and a silent one playing until the
+                // next evaluation.
+                if !live.render_block(&mut buffer) {
+                    audition_mixer.mix_into(&mut buffer);
+                    synth_time_us_clone.store(0, Ordering::Relaxed);
+                    ring_writer.push(&buffer);
+                    continue;
+                }
+                audition_mixer.mix_into(&mut buffer);
+                if let Some(feed) = cue_feed.as_mut() {
+                    feed.push(live.graph().cue_buffer(), frames);
+                }
+                if let Some(feed) = cv_feed.as_mut() {
+                    feed.push(live.graph(), frames);
+                }
                 // Publish the live cycle position for UI / MIDI reads (no graph borrow).
-                cycle_bits_synth.store(c.position().to_bits(), Ordering::Relaxed);
+                if let Some(clock) = live.clock() {
+                    cycle_bits_synth.store(clock.position().to_bits(), Ordering::Relaxed);
+                }
                 renders += 1;
 
                 let elapsed_us = start.elapsed().as_micros() as usize;
                 synth_time_us_clone.store(elapsed_us, Ordering::Relaxed);
+                if let Some(level) =
+                    governor.observe(elapsed_us, synth_budget_us, quality_synth.is_locked())
+                {
+                    live.graph_mut().set_quality(level);
+                    quality_synth.publish(level, governor.load());
+                }
 
-                // DEBUG: Track peak synthesis times (budget = 11610 µs / block).
+                // DEBUG: Track peak synthesis times (budget = one block's duration).
                 static MAX_SYNTH_US: std::sync::atomic::AtomicUsize =
                     std::sync::atomic::AtomicUsize::new(0);
                 let prev_max = MAX_SYNTH_US.fetch_max(elapsed_us, Ordering::Relaxed);
-                if elapsed_us > prev_max && elapsed_us > 11610 {
-                    let voice_count = cur.active_voice_count();
-                    eprintln!(
+                if elapsed_us > prev_max && elapsed_us > synth_budget_us {
+                    let voice_count = live.graph().active_voice_count();
+                    crate::rt_log::log(format_args!(
                         "🔥 NEW PEAK: {} us ({:.1}ms) - {}% budget | voices: {}",
                         elapsed_us,
                         elapsed_us as f64 / 1000.0,
-                        elapsed_us * 100 / 11610,
+                        elapsed_us * 100 / synth_budget_us.max(1),
                         voice_count
-                    );
+                    ));
                 }
 
-                let written = ring_producer.push_slice(&buffer);
+                let written = ring_writer.push(&buffer);
                 if written < buffer.len() {
-                    eprintln!(
+                    crate::rt_log::log(format_args!(
                         "⚠️  Ring buffer full, dropped {} samples",
                         buffer.len() - written
-                    );
+                    ));
                 }
             }
         });
 
         // Audio callback: just reads from ring buffer (FAST!)
+        // Queued for the log file: no file I/O on the audio thread
         let err_fn = |err| {
-            use std::io::Write;
-            if let Ok(mut file) = std::fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open("/tmp/phonon_audio_errors.log")
-            {
-                let _ = writeln!(
-                    file,
-                    "[{}] Audio stream error: {}",
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs(),
-                    err
-                );
-            }
+            crate::rt_log::log(format_args!(
+                "[{}] Audio stream error: {}",
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                err
+            ));
         };
 
-        // Clone underrun counter for audio callbacks
-        let underrun_count_f32 = Arc::clone(&underrun_count);
-        let underrun_count_i16 = Arc::clone(&underrun_count);
-
-        // Clone clear flag for audio callbacks
-        let should_clear_f32 = Arc::clone(&should_clear_ring);
-        let should_clear_i16 = Arc::clone(&should_clear_ring);
-
-        let stream = match sample_format {
-            cpal::SampleFormat::F32 => {
-                device.build_output_stream(
-                    &config,
-                    move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
-                        // Check if we should clear the ring buffer (graph was swapped)
-                        // This enables instant transitions without hearing stale audio
-                        if should_clear_f32.swap(false, Ordering::Relaxed) {
-                            // Drain all existing samples from the ring buffer
-                            let to_drain = ring_consumer.occupied_len();
-                            ring_consumer.skip(to_drain);
-                        }
-
-                        // Read from ring buffer - MUCH faster than synthesis!
-                        let available = ring_consumer.occupied_len();
-
-                        if available >= data.len() {
-                            // Ring buffer has enough samples, read them
-                            ring_consumer.pop_slice(data);
-                        } else {
-                            // Underrun: not enough samples in buffer
-                            let read = ring_consumer.pop_slice(data);
-                            for sample in data[read..].iter_mut() {
-                                *sample = 0.0;
-                            }
-
-                            // Increment underrun counter (atomic, thread-safe)
-                            underrun_count_f32.fetch_add(1, Ordering::Relaxed);
-                        }
-                    },
-                    err_fn,
-                    None,
-                )
-            }
-            cpal::SampleFormat::I16 => {
-                // Pre-allocate conversion buffer OUTSIDE the callback to avoid
-                // allocation in the realtime audio thread (critical for performance!)
-                // Initial size 4096 handles most buffer sizes; resizes are rare and amortized
-                let mut conversion_buffer: Vec<f32> = vec![0.0; 4096];
-
-                device.build_output_stream(
-                    &config,
-                    move |data: &mut [i16], _: &cpal::OutputCallbackInfo| {
-                        // Check if we should clear the ring buffer (graph was swapped)
-                        // This enables instant transitions without hearing stale audio
-                        if should_clear_i16.swap(false, Ordering::Relaxed) {
-                            // Drain all existing samples from the ring buffer
-                            let to_drain = ring_consumer.occupied_len();
-                            ring_consumer.skip(to_drain);
-                        }
-
-                        let available = ring_consumer.occupied_len();
+        // Latency monitor for audio callbacks
+        let latency = Arc::new(LatencyMonitor::new());
+        let latency_cb = Arc::clone(&latency);
+
+        // Rendered as f32; converted to the device's sample format if it differs
+        let stream = build_output_stream_converted(
+            &device,
+            &config,
+            sample_format,
+            move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
+                // Skip audio rendered before a hush / panic / cue took effect.
+                // This enables instant transitions without hearing stale audio
+                if ring_reader.skip_stale() {
+                    for clear in cue_clear.iter().chain(cv_clear.iter()) {
+                        clear.store(true, Ordering::Relaxed);
+                    }
+                }
 
-                        // Ensure conversion buffer is large enough (rare resize, amortized)
-                        if conversion_buffer.len() < data.len() {
-                            conversion_buffer.resize(data.len(), 0.0);
-                        }
+                // Read from ring buffer - MUCH faster than synthesis!
+                let available = ring_reader.occupied_len();
+                latency_cb.record(info, available / channels, sample_rate);
 
-                        if available >= data.len() {
-                            // Read from ring buffer and convert to i16
-                            // Use pre-allocated buffer slice - NO ALLOCATION!
-                            let temp = &mut conversion_buffer[..data.len()];
-                            ring_consumer.pop_slice(temp);
-                            for (dst, src) in data.iter_mut().zip(temp.iter()) {
-                                *dst = (*src * 32767.0) as i16;
-                            }
-                        } else {
-                            // Underrun - read what's available
-                            if available > 0 {
-                                let temp = &mut conversion_buffer[..available];
-                                ring_consumer.pop_slice(temp);
-                                for (i, dst) in data.iter_mut().enumerate() {
-                                    if i < available {
-                                        *dst = (temp[i] * 32767.0) as i16;
-                                    } else {
-                                        *dst = 0;
-                                    }
-                                }
-                            } else {
-                                // No samples at all, fill with silence
-                                for dst in data.iter_mut() {
-                                    *dst = 0;
-                                }
-                            }
+                // More than two channels: the stereo main mix goes frame by
+                // frame to channels 1/2, CV to 3 and up, then the cue to 3/4
+                if main_cv.is_some() || main_cue.is_some() {
+                    let frames = data.len() / channels;
+                    if available < frames * 2 {
+                        ring_reader.note_underrun();
+                    }
+                    for frame in data.chunks_exact_mut(channels) {
+                        frame.fill(0.0);
+                        frame[0] = ring_reader.next_sample();
+                        frame[1] = ring_reader.next_sample();
+                    }
+                    if let Some(cv) = main_cv.as_mut() {
+                        cv.fill(data, channels);
+                    }
+                    if let Some(cue) = main_cue.as_mut() {
+                        cue.fill(data, channels, 2);
+                    }
+                    return;
+                }
 
-                            // Increment underrun counter (atomic, thread-safe)
-                            underrun_count_i16.fetch_add(1, Ordering::Relaxed);
-                        }
-                    },
-                    err_fn,
-                    None,
-                )
-            }
-            _ => return Err("Unsupported sample format".into()),
-        }
+                // Short of samples, the rest is silence and counts an underrun
+                ring_reader.fill(data);
+            },
+            err_fn,
+        )
         .map_err(|e| format!("Failed to build stream: {}", e))?;
 
         stream
@@ -609,28 +788,50 @@ impl ModalEditor {
             cmd_tx,
             init_tx,
             first_graph_sent: false,
+            playing_shape: GraphShape::default(),
             current_cycle_bits,
+            watchdog_rx,
+            quality,
+            shown_quality: Quality::Full,
             render_local: None,
             #[cfg(feature = "vst3")]
             shared_real_plugins: Arc::new(std::sync::Mutex::new(HashMap::new())),
             _stream: Some(stream),
+            _cue_stream: cue_stream,
             sample_rate,
+            output_channels: Some(channels),
+            synth_budget_us,
             flash_highlight: None,
             kill_buffer: String::new(),
+            selection_anchor: None,
+            keymap: Keymap::default(),
+            vim_normal: false,
+            theme: Theme::default(),
+            highlight_cache: RefCell::new((String::new(), classify_source(""))),
+            default_tempo: None,
+            dc_block: false,
+            master_clip: MasterClip::default(),
+            bus_meters: None,
+            bus_levels: meters::BusLevels::default(),
             undo_stack: Vec::new(),
+            last_scrub: None,
             redo_stack: Vec::new(),
             console_messages: vec!["Welcome to Phonon Live Coding".to_string()],
             completion_state: completion::CompletionState::new(),
             sample_names: completion::discover_samples(),
             bus_names,
             command_console: CommandConsole::new(),
+            render_queue: RenderQueue::new(),
             underrun_count,
             synth_time_us,
             ring_fill_percent,
+            latency,
             should_clear_ring,
             midi_input: None,
             midi_recorder: None,
             midi_recording: false,
+            take_recordings: Vec::new(),
+            takes_captured: HashSet::new(),
             midi_recorded_pattern: None,
             midi_recorded_n_pattern: None,
             midi_recorded_velocity: None,
@@ -644,12 +845,35 @@ impl ModalEditor {
                 .map(|d| d.name)
                 .collect(),
             midi_quantize: 16, // Default to 16th note quantization
+            transport_stopped: false,
+            midi_clock: None,
+            tempo_sync: None,
+            sync_follower: SyncFollower::new(),
+            sync_transport: (None, 0),
+            synced_cps: None,
             show_config_panel: false,
+            show_inline_help: true,
+            show_event_log: false,
+            event_log: EventLog::new(),
+            audition,
             recording_preview_line: None,
             recording_held_notes: String::new(),
             scroll_offset: 0,
             viewport_height: 20,
+            editor_area: Rect::new(0, 0, 80, 20),
+            split: None,
+            focus_left: true,
+            other_pane_area: Rect::default(),
+            evaluated: None,
+            evaluated_cps: 0.5,
+            resumed_node_states: None,
+            compile_cache: CompileCache::new(),
+            morphs: BTreeMap::new(),
+            playing_program: Vec::new(),
+            morph_ends: BTreeMap::new(),
+            nudges: BTreeMap::new(),
             plugin_browser: PluginBrowser::new(),
+            step_grid: None,
             plugin_manager: PluginInstanceManager::new(),
             #[cfg(all(target_os = "linux", feature = "vst3"))]
             vst3_guis: HashMap::new(),
@@ -658,6 +882,33 @@ impl ModalEditor {
             #[cfg(all(target_os = "linux", feature = "vst3"))]
             last_param_poll: std::time::Instant::now(),
         };
+        editor.apply_config(&editor_config);
+        if let Some(e) = config_error {
+            editor.add_console_message(&format!("⚠️  Config: {} (using defaults)", e));
+        }
+        if let Some(e) = cue_warning {
+            editor.add_console_message(&format!("⚠️  No headphone cue: {}", e));
+        }
+        if let Some(name) = editor_config.midi_clock_out.as_deref() {
+            let cycle_bits = Arc::clone(&editor.current_cycle_bits);
+            match MidiClockOut::connect(name, cycle_bits, DEFAULT_BEATS_PER_CYCLE) {
+                Ok(clock) => {
+                    editor.midi_clock = Some(clock);
+                    editor.add_console_message(&format!("🕐 MIDI clock out: {}", name));
+                }
+                Err(e) => editor.add_console_message(&format!("⚠️  No MIDI clock out: {}", e)),
+            }
+        }
+        if let Some(name) = editor_config.midi_clock_in.clone() {
+            editor.set_sync(Some(SyncSource::Midi(name)));
+            let message = editor.status_message.clone();
+            editor.add_console_message(&message);
+        }
+        editor.add_console_message(&format!(
+            "🔧 Latency: {}",
+            engine.latency_report(ring_buffer_size, 2)
+        ));
+        editor.prefetch_samples();
 
         // Initialize plugin manager
         let _ = editor.plugin_manager.initialize(sample_rate, synthesis_buffer_size);
@@ -673,22 +924,29 @@ impl ModalEditor {
     /// This allows running editor tests in CI environments without audio hardware
     pub fn new_headless() -> Result<Self, Box<dyn std::error::Error>> {
         let sample_rate = 44100.0;
+        // Budget for the default 512-sample (256-frame) chunk
+        let synth_budget_us = (256.0 / sample_rate * 1_000_000.0) as usize;
         // Render-owner swap channel + one-shot init channel, exactly as the audio
         // build — but with no synth/janitor thread: the render side lives in
         // `render_local` and the test harness drives it (still single-owner).
         let (cmd_tx, rsw, graveyard) = render_swap_channel_default::<UnifiedSignalGraph>();
         let (init_tx, init_rx) = std::sync::mpsc::channel::<Box<UnifiedSignalGraph>>();
         let current_cycle_bits = Arc::new(AtomicU64::new(0));
+        let (watchdog_tx, watchdog_rx) = std::sync::mpsc::channel::<String>();
+        let (audition, audition_mixer) = audition_channel();
         let render_local = Some(RefCell::new(LocalRender {
             init_rx,
             rsw,
             graveyard,
             cur: None,
+            watchdog: RenderWatchdog::new(watchdog_tx),
+            audition_mixer,
         }));
 
         let underrun_count = Arc::new(AtomicUsize::new(0));
         let synth_time_us = Arc::new(AtomicUsize::new(0));
         let ring_fill_percent = Arc::new(AtomicUsize::new(100));
+        let latency = Arc::new(LatencyMonitor::new());
         let should_clear_ring = Arc::new(AtomicBool::new(false));
 
         let content = String::new();
@@ -704,28 +962,50 @@ impl ModalEditor {
             cmd_tx,
             init_tx,
             first_graph_sent: false,
+            playing_shape: GraphShape::default(),
             current_cycle_bits,
+            watchdog_rx,
+            quality: Arc::new(QualityControl::default()),
+            shown_quality: Quality::Full,
             render_local,
             #[cfg(feature = "vst3")]
             shared_real_plugins: Arc::new(std::sync::Mutex::new(HashMap::new())),
             _stream: None, // No audio stream in headless mode
+            _cue_stream: None,
             sample_rate,
+            output_channels: None,
+            synth_budget_us,
             flash_highlight: None,
             kill_buffer: String::new(),
+            selection_anchor: None,
+            keymap: Keymap::default(),
+            vim_normal: false,
+            theme: Theme::default(),
+            highlight_cache: RefCell::new((String::new(), classify_source(""))),
+            default_tempo: None,
+            dc_block: false,
+            master_clip: MasterClip::default(),
+            bus_meters: None,
+            bus_levels: meters::BusLevels::default(),
             undo_stack: Vec::new(),
+            last_scrub: None,
             redo_stack: Vec::new(),
             console_messages: Vec::new(),
             completion_state: completion::CompletionState::new(),
             sample_names: completion::discover_samples(),
             bus_names,
             command_console: CommandConsole::new(),
+            render_queue: RenderQueue::new(),
             underrun_count,
             synth_time_us,
             ring_fill_percent,
+            latency,
             should_clear_ring,
             midi_input: None,
             midi_recorder: None,
             midi_recording: false,
+            take_recordings: Vec::new(),
+            takes_captured: HashSet::new(),
             midi_recorded_pattern: None,
             midi_recorded_n_pattern: None,
             midi_recorded_velocity: None,
@@ -735,12 +1015,35 @@ impl ModalEditor {
             recording_counter: 0,
             midi_devices: Vec::new(),
             midi_quantize: 16,
+            transport_stopped: false,
+            midi_clock: None,
+            tempo_sync: None,
+            sync_follower: SyncFollower::new(),
+            sync_transport: (None, 0),
+            synced_cps: None,
             show_config_panel: false,
+            show_inline_help: true,
+            show_event_log: false,
+            event_log: EventLog::new(),
+            audition,
             recording_preview_line: None,
             recording_held_notes: String::new(),
             scroll_offset: 0,
             viewport_height: 20,
+            editor_area: Rect::new(0, 0, 80, 20),
+            split: None,
+            focus_left: true,
+            other_pane_area: Rect::default(),
+            evaluated: None,
+            evaluated_cps: 0.5,
+            resumed_node_states: None,
+            compile_cache: CompileCache::new(),
+            morphs: BTreeMap::new(),
+            playing_program: Vec::new(),
+            morph_ends: BTreeMap::new(),
+            nudges: BTreeMap::new(),
             plugin_browser: PluginBrowser::new(),
+            step_grid: None,
             plugin_manager: PluginInstanceManager::new(),
             #[cfg(all(target_os = "linux", feature = "vst3"))]
             vst3_guis: HashMap::new(),
@@ -751,23 +1054,132 @@ impl ModalEditor {
         })
     }
 
+    /// Apply the keymap, theme, default tempo, DC blocker, master clipper and
+    /// sample paths from a config.
+    /// Buffer sizes only take effect at startup (see `new`).
+    pub fn apply_config(&mut self, config: &EditorConfig) {
+        match config.keymap() {
+            Ok(keymap) => self.keymap = keymap,
+            Err(e) => self.add_console_message(&format!("⚠️  Config keys: {}", e)),
+        }
+        match config.theme() {
+            Ok(theme) => self.theme = theme,
+            Err(e) => self.add_console_message(&format!("⚠️  Config theme: {}", e)),
+        }
+        self.vim_normal = false;
+        self.default_tempo = config.default_tempo;
+        self.dc_block = config.dc_block;
+        match config.master_clip() {
+            Ok(clip) => self.master_clip = clip,
+            Err(e) => self.add_console_message(&format!("⚠️  Config: {}", e)),
+        }
+        crate::sample_loader::set_extra_sample_dirs(config.sample_paths.clone());
+        if let Some(mb) = config.sample_memory_mb {
+            crate::sample_loader::set_sample_memory_limit(mb * 1024 * 1024);
+        }
+    }
+
+    /// Start decoding the samples the buffer plays in the background, so the
+    /// first eval of a freshly opened file doesn't wait on the disk
+    fn prefetch_samples(&self) {
+        let Ok((_, statements)) = parse_program(&self.content) else {
+            return;
+        };
+        if let Ok(graph) = compile_program(statements, self.sample_rate, None) {
+            graph.prefetch_samples();
+        }
+    }
+
     /// Load and compile DSL code into the audio graph
     fn load_code(&mut self, code: &str) -> Result<(), String> {
         eprintln!("🔧 load_code() called with {} bytes", code.len());
+        let (statements, skipped) = Self::parse_code(code)?;
+        self.load_program(statements)?;
+        self.report_skipped(&skipped);
+        Ok(())
+    }
 
-        // Parse the DSL code
-        let (rest, statements) = parse_program(code).map_err(|e| {
-            eprintln!("❌ Parse error: {}", e);
-            format!("Parse error: {}", e)
-        })?;
-
-        if !rest.trim().is_empty() {
-            let err = format!("Failed to parse entire code, remaining: {}", rest);
+    /// Parse DSL code, skipping the statements that don't parse so one typo
+    /// doesn't silence the rest. Fails only when nothing parses.
+    fn parse_code(code: &str) -> Result<(Vec<Statement>, Vec<DiagnosticError>), String> {
+        let (statements, skipped) = parse_program_recovering(code);
+        if let (true, Some(first)) = (statements.is_empty(), skipped.first()) {
+            let err = format!(
+                "Parse error at line {}:{}: {}",
+                first.line, first.column, first.message
+            );
             eprintln!("❌ {}", err);
             return Err(err);
         }
 
         eprintln!("✅ Parsed {} statements", statements.len());
+        Ok((statements, skipped))
+    }
+
+    /// Point out the statements an eval skipped; the rest is already playing
+    fn report_skipped(&mut self, skipped: &[DiagnosticError]) {
+        for diagnostic in skipped {
+            self.add_console_message(&format!(
+                "⚠️  Skipped `{}` (column {}): {}",
+                diagnostic.source_line.as_deref().unwrap_or_default().trim(),
+                diagnostic.column,
+                diagnostic.message
+            ));
+        }
+        if let Some(first) = skipped.first() {
+            self.error_message = Some(format!(
+                "Skipped {} unparseable statement(s), first at line {}:{}: {}",
+                skipped.len(),
+                first.line,
+                first.column,
+                first.message
+            ));
+        }
+    }
+
+    /// Send this buffer's code to the engine. When split, the other pane's
+    /// last evaluated code is merged in so both panes play in one graph.
+    fn eval_code(&mut self, code: &str) -> Result<(), String> {
+        let other = self.split.as_ref().and_then(|pane| pane.evaluated.clone());
+        match other {
+            Some(other) => {
+                let (left, right) = if self.focus_left {
+                    (code, other.as_str())
+                } else {
+                    (other.as_str(), code)
+                };
+                let (left, mut skipped) = Self::parse_code(left)?;
+                let (right, right_skipped) = Self::parse_code(right)?;
+                skipped.extend(right_skipped);
+                self.load_program(merge_programs(vec![left, right]))?;
+                self.report_skipped(&skipped);
+            }
+            None => self.load_code(code)?,
+        }
+        self.evaluated = Some(code.to_string());
+        Ok(())
+    }
+
+    /// Compile parsed statements and hand the graph to the render owner
+    fn load_program(&mut self, mut statements: Vec<Statement>) -> Result<(), String> {
+        // Crossfade the redefined buses that have a morph, from what plays
+        // now. Morphs that have finished play just their new definition
+        let now = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+        let mut previous = self.playing_program.clone();
+        for statement in &mut previous {
+            if let Statement::BusAssignment { name, expr, .. } = statement {
+                if self.morph_ends.get(name).is_some_and(|end| *end <= now) {
+                    *expr = morph_target(expr);
+                }
+            }
+        }
+        let morphed = morph_redefined_buses(&previous, &mut statements, &self.morphs);
+        let program = statements.clone();
+
+        let sets_tempo = statements
+            .iter()
+            .any(|s| matches!(s, Statement::Tempo(_) | Statement::Bpm { .. }));
+        let takes = take_requests(&statements);
 
         // Compile into a graph
         // Note: compile_program sets CPS from tempo:/bpm: statements in the code
@@ -778,14 +1190,33 @@ impl ModalEditor {
             .as_ref()
             .map(|handler| handler.get_monitoring_queue());
 
-        let mut new_graph =
-            compile_program(statements, self.sample_rate, midi_queue).map_err(|e| {
-                eprintln!("❌ Compile error: {}", e);
-                format!("Compile error: {}", e)
-            })?;
+        let mut new_graph = compile_program_cached(
+            statements,
+            self.sample_rate,
+            midi_queue,
+            &mut self.compile_cache,
+        )
+        .map_err(|e| {
+            eprintln!("❌ Compile error: {}", e);
+            format!("Compile error: {}", e)
+        })?;
 
         eprintln!("✅ Compiled graph successfully");
+        if let (Some(cps), false) = (self.default_tempo, sets_tempo) {
+            new_graph.set_cps(cps);
+        }
+        // Following `:sync`, the tempo is the master's whatever the code says
+        if let Some(cps) = self.synced_cps {
+            new_graph.set_cps(cps);
+        }
         eprintln!("📊 New graph CPS from code: {}", new_graph.get_cps());
+        if let Some(warning) = self
+            .output_channels
+            .and_then(|channels| missing_channels_warning(&new_graph, channels))
+        {
+            self.add_console_message(&format!("⚠️  {}", warning));
+        }
+        self.evaluated_cps = new_graph.get_cps();
 
         // NOTE (U1 / investigate-u1-swapping): `code` may be a single C-x chunk that
         // defines only plain `~name` buses with no `out`/`~master`/`dN` route. Those
@@ -801,6 +1232,14 @@ impl ModalEditor {
         {
             new_graph.real_plugins = Arc::clone(&self.shared_real_plugins);
         }
+        new_graph.set_event_log(Some(self.event_log.sender()));
+        new_graph.set_master_dc_blocker(self.dc_block);
+        new_graph.set_master_clip(self.master_clip);
+        let bus_meters = new_graph.enable_bus_meters();
+        for bus in new_graph.get_all_bus_names() {
+            let nudge = self.nudges.entry(bus.clone()).or_default();
+            new_graph.nudge_bus(&bus, Arc::clone(nudge));
+        }
 
         // ALWAYS enable wall-clock timing for live mode. Done on the CONTROL thread
         // (off the render hot path); the render owner's LiveClock remains the timing
@@ -810,6 +1249,17 @@ impl ModalEditor {
         // CRITICAL: Preload all samples/plugins BEFORE the graph reaches the render
         // owner. Disk I/O must stay on the control thread (design §4.4).
         new_graph.preload_samples();
+        new_graph.preload_plugins();
+
+        // Pair the nodes of unchanged buses with the playing graph's here, so
+        // the swap only copies their state. A resumed session brings its own.
+        match self.resumed_node_states.take() {
+            Some(states) => {
+                new_graph.restore_node_states(&states);
+            }
+            None => new_graph.plan_bus_state_transfer(&self.playing_shape),
+        }
+        let shape = new_graph.shape();
 
         // Hand the finished graph to the render owner (design §4.1). The state
         // transfer (session timing, FX tails, voices) now happens ON the render
@@ -822,12 +1272,18 @@ impl ModalEditor {
                 return Err("render thread gone (init channel closed)".to_string());
             }
             self.first_graph_sent = true;
+            if let Some(clock) = self.midi_clock.as_ref() {
+                clock.set_running(!self.transport_stopped);
+            }
         } else if let Err(rejected) = self.cmd_tx.swap(Box::new(new_graph)) {
             // Command ring full (render thread behind) — extremely unlikely since
             // swaps are human-paced. Drop the compiled graph; the next eval retries.
             drop(rejected);
             return Err("render thread busy (command ring full)".to_string());
         }
+        self.playing_shape = shape;
+        self.bus_meters = Some(bus_meters);
+        self.bus_levels.clear();
 
         // In headless (test) mode there is no synth thread, so apply the handoff
         // immediately into the local render side; the real audio build applies it
@@ -840,16 +1296,72 @@ impl ModalEditor {
         // the beat/groove continues. (Only hush/panic clear the ring.)
 
         eprintln!("✅ Graph handed to render owner; smooth transition to new code...");
+        self.arm_takes(takes);
+
+        self.playing_program = program;
+        self.morph_ends.retain(|_, end| *end > now);
+        for bus in morphed {
+            let cycles = self.morphs[&bus];
+            self.morph_ends.insert(bus.clone(), now + cycles);
+            self.add_console_message(&format!("〰 Morphing ~{} over {} cycles", bus, cycles));
+        }
 
         Ok(())
     }
 
+    /// Start capturing the takes of newly evaluated `record` statements on
+    /// the next cycle boundary
+    fn arm_takes(&mut self, takes: Vec<TakeRequest>) {
+        let now = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+        let steps_per_cycle = match self.midi_quantize {
+            0 => 32,
+            steps => steps as usize,
+        };
+        for request in takes {
+            if self.takes_captured.contains(&request)
+                || self.take_recordings.iter().any(|take| take.request == request)
+            {
+                continue;
+            }
+            let take = TakeRecording::new(request, now, steps_per_cycle);
+            self.add_console_message(&format!(
+                "⏺ Recording ~{} -> \"{}\" from cycle {} for {} cycles",
+                take.request.bus, take.request.name, take.start_cycle, take.request.cycles
+            ));
+            self.take_recordings.push(take);
+        }
+    }
+
+    /// Store the takes that have reached their last cycle and re-evaluate,
+    /// so `%name` picks up what was played
+    fn finish_takes(&mut self) {
+        let now = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+        let (done, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.take_recordings)
+            .into_iter()
+            .partition(|take| take.is_done(now));
+        self.take_recordings = pending;
+        if done.is_empty() {
+            return;
+        }
+        for take in done {
+            let pattern = take.to_mini_notation();
+            self.add_console_message(&format!("⏹ %{}: \"{}\"", take.request.name, pattern));
+            crate::midi_input::store_take(&take.request.name, pattern);
+            self.takes_captured.insert(take.request);
+        }
+        if let Some(code) = self.evaluated.clone() {
+            if let Err(e) = self.eval_code(&code) {
+                self.add_console_message(&format!("❌ {}", e));
+            }
+        }
+    }
+
     /// Run the modal editor
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         // Setup terminal
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
@@ -857,7 +1369,11 @@ impl ModalEditor {
 
         // Restore terminal
         disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
         terminal.show_cursor()?;
 
         result
@@ -880,6 +1396,23 @@ impl ModalEditor {
 
             // Process any pending MIDI input events
             self.process_midi_events();
+            self.finish_takes();
+            self.follow_sync();
+
+            // Report progress of background renders
+            self.poll_render_queue();
+
+            // Pick up events triggered since the last frame
+            self.event_log.drain();
+            self.audition.collect();
+            self.poll_bus_meters();
+
+            // Report synthesis crashes / NaN output
+            self.poll_watchdog();
+            self.poll_quality();
+
+            // Write out what the audio threads logged since the last frame
+            crate::rt_log::drain();
 
             // Pump VST3 GUI events and cleanup closed windows (Linux only, with vst3 feature)
             #[cfg(all(target_os = "linux", feature = "vst3"))]
@@ -930,8 +1463,8 @@ impl ModalEditor {
             // Use poll with timeout to enable flash animation
             // 100ms = reduced refresh rate (was 50ms) for less CPU usage
             if event::poll(std::time::Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    match self.handle_key_event(key) {
+                match event::read()? {
+                    Event::Key(key) => match self.handle_key_event(key) {
                         KeyResult::Continue => continue,
                         KeyResult::Quit => break,
                         KeyResult::Play => {
@@ -940,7 +1473,9 @@ impl ModalEditor {
                         KeyResult::Save => {
                             self.save_file()?;
                         }
-                    }
+                    },
+                    Event::Mouse(mouse) => self.handle_mouse_event(mouse),
+                    _ => {}
                 }
             }
         }
@@ -960,6 +1495,11 @@ impl ModalEditor {
             return self.handle_plugin_browser_key_event(key);
         }
 
+        if self.step_grid.is_some() {
+            self.handle_step_grid_key(key);
+            return KeyResult::Continue;
+        }
+
         // If config panel is visible, handle config keys
         if self.show_config_panel {
             match key.code {
@@ -975,7 +1515,7 @@ impl ModalEditor {
                     return KeyResult::Continue;
                 }
                 // Alt+Comma: Toggle off
-                KeyCode::Char(',') if key.modifiers.contains(KeyModifiers::ALT) => {
+                _ if self.keymap.lookup(&key) == Some(Action::ToggleConfigPanel) => {
                     self.show_config_panel = false;
                     self.status_message = "Configuration closed".to_string();
                     return KeyResult::Continue;
@@ -985,167 +1525,22 @@ impl ModalEditor {
             }
         }
 
-        match key.code {
-            // Quit with Alt+Q (Ctrl+Q conflicts with terminal flow control)
-            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::ALT) => KeyResult::Quit,
-
-            // Alt+/ : Toggle command console
-            KeyCode::Char('/') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.command_console.toggle();
-                KeyResult::Continue
-            }
-
-            // Alt+P: Toggle plugin browser
-            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.plugin_browser.toggle();
-                KeyResult::Continue
-            }
-
-            // Alt+G: Open VST3 plugin GUIs
-            #[cfg(all(target_os = "linux", feature = "vst3"))]
-            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.open_plugin_guis();
-                KeyResult::Continue
-            }
-
-            // Ctrl+X: Evaluate current block (chunk)
-            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.eval_chunk();
-                KeyResult::Continue
-            }
-
-            // Ctrl+L: Reload all (evaluate entire buffer)
-            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.eval_all();
-                KeyResult::Continue
-            }
-
-            // Ctrl+U: Undo
-            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.undo();
-                KeyResult::Continue
-            }
-
-            // Ctrl+R: Redo
-            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.redo();
-                KeyResult::Continue
-            }
-
-            // Ctrl+H: Hush
-            KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.hush();
-                KeyResult::Continue
-            }
-
-            // Alt+M: Connect to MIDI device (cycles through available devices)
-            KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.cycle_midi_device();
-                KeyResult::Continue
-            }
-
-            // Alt+Comma: Toggle configuration panel
-            KeyCode::Char(',') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.show_config_panel = !self.show_config_panel;
-                if self.show_config_panel {
-                    self.status_message =
-                        "⚙️  Configuration Panel (Q: quantize, Esc: close)".to_string();
-                } else {
-                    self.status_message = "Configuration closed".to_string();
-                }
-                KeyResult::Continue
-            }
-
-            // Alt+R: Start/stop MIDI recording
-            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.toggle_midi_recording();
-                KeyResult::Continue
-            }
-
-            // Alt+Shift+I: Smart paste complete pattern (~rec1: slow N $ n "..." # gain "...")
-            KeyCode::Char('I')
-                if key.modifiers.contains(KeyModifiers::ALT)
-                    && key.modifiers.contains(KeyModifiers::SHIFT) =>
-            {
-                self.insert_midi_smart_paste();
-                KeyResult::Continue
-            }
-
-            // Alt+I: Insert recorded MIDI pattern at cursor (note names)
-            KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.insert_midi_pattern();
-                KeyResult::Continue
-            }
-
-            // Alt+N: Insert recorded MIDI pattern as n-offsets
-            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.insert_midi_n_pattern();
-                KeyResult::Continue
-            }
-
-            // Alt+V: Insert recorded MIDI velocity pattern
-            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.insert_midi_velocity_pattern();
-                KeyResult::Continue
-            }
-
-            // Alt+L: Insert recorded MIDI legato pattern
-            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::ALT) => {
-                self.insert_midi_legato_pattern();
-                KeyResult::Continue
+        if self.vim_normal {
+            if let Some(result) = self.handle_normal_mode_key(key) {
+                return result;
             }
+        }
 
-            // Ctrl+S: Save
-            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => KeyResult::Save,
+        if self.handle_selection_key(key) {
+            return KeyResult::Continue;
+        }
 
-            // Emacs-style movement keys
-            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.move_cursor_right(); // Forward
-                KeyResult::Continue
-            }
-            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.move_cursor_left(); // Backward
-                KeyResult::Continue
-            }
-            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                // If completion is visible, navigate through completions
-                if self.completion_state.is_visible() {
-                    self.cycle_completion_forward();
-                } else {
-                    self.move_cursor_down(); // Next line
-                }
-                KeyResult::Continue
-            }
-            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                // If completion is visible, navigate through completions
-                if self.completion_state.is_visible() {
-                    self.cycle_completion_backward();
-                } else {
-                    self.move_cursor_up(); // Previous line
-                }
-                KeyResult::Continue
-            }
-            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.move_cursor_line_start(); // Beginning of line
-                KeyResult::Continue
-            }
-            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.move_cursor_line_end(); // End of line
-                KeyResult::Continue
-            }
-            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.delete_char_forward(); // Delete forward
-                KeyResult::Continue
-            }
-            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.kill_line(); // Kill to end of line
-                KeyResult::Continue
-            }
-            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.yank(); // Yank (paste) from kill buffer
-                KeyResult::Continue
-            }
+        // Bound commands (keymap.rs; overridable in ~/.phonon/config.toml)
+        if let Some(action) = self.keymap.lookup(&key) {
+            return self.run_action(action);
+        }
 
+        match key.code {
             // Ctrl+Space: Accept completion with defaults, or expand kwargs at cursor
             KeyCode::Char(' ') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 if self.completion_state.is_visible() {
@@ -1179,6 +1574,15 @@ impl ModalEditor {
                 KeyResult::Continue
             }
 
+            // Unbound Ctrl/Alt chords do nothing
+            KeyCode::Char(_)
+                if key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                KeyResult::Continue
+            }
+
             // Regular character input
             KeyCode::Char(c) => {
                 // '?' toggles docs panel when completion is visible
@@ -1259,9 +1663,472 @@ impl ModalEditor {
         }
     }
 
-    /// Render the UI
-    fn ui(&mut self, f: &mut Frame) {
-        let terminal_width = f.size().width;
+    /// Mouse: the wheel scrolls, a left click places the cursor and dragging
+    /// selects. Clicking the other pane of a split focuses it; clicks outside
+    /// the editor panes are ignored.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_by(-(MOUSE_SCROLL_LINES as isize)),
+            MouseEventKind::ScrollDown => self.scroll_by(MOUSE_SCROLL_LINES as isize),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let other = self.other_pane_area;
+                if self.split.is_some()
+                    && (other.x..other.x + other.width).contains(&mouse.column)
+                    && (other.y..other.y + other.height).contains(&mouse.row)
+                {
+                    self.swap_panes();
+                }
+                if let Some(pos) = self.screen_to_pos(mouse.column, mouse.row) {
+                    self.cancel_completion();
+                    self.selection_anchor = None;
+                    self.cursor_pos = pos;
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                if let Some(pos) = self.screen_to_pos(mouse.column, mouse.row) {
+                    if self.selection_anchor.is_none() {
+                        self.selection_anchor = Some(self.cursor_pos);
+                    }
+                    self.cursor_pos = pos;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Byte offset of the character drawn at terminal cell (column, row),
+    /// clamped to the end of that line; `None` outside the editor text area
+    fn screen_to_pos(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.editor_area;
+        // Inside the border
+        if column <= area.x
+            || row <= area.y
+            || column >= area.x + area.width.saturating_sub(1)
+            || row >= area.y + area.height.saturating_sub(1)
+        {
+            return None;
+        }
+        let line_count = self.content.split('\n').count();
+        let line = (self.scroll_offset + (row - area.y - 1)) as usize;
+        let line = line.min(line_count - 1);
+        let col = (column - area.x - 1) as usize;
+        Some(self.line_col_to_pos(line, col))
+    }
+
+    /// Selection keys: Shift+movement, and Tab/Esc with a selection.
+    /// Returns true if the key was consumed. Bound commands that use the
+    /// selection (cut, copy, comment, indent) keep it; any other key drops
+    /// it, and typing, Enter, yank and deletion replace it first.
+    fn handle_selection_key(&mut self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let selection = self.selection();
+        let action = self.keymap.lookup(&key);
+
+        match key.code {
+            // Shift+arrows/Home/End: extend the selection
+            KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Home
+            | KeyCode::End
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+                if shift && !alt =>
+            {
+                self.cancel_completion();
+                if self.selection_anchor.is_none() {
+                    self.selection_anchor = Some(self.cursor_pos);
+                }
+                match key.code {
+                    KeyCode::Left => self.move_cursor_left(),
+                    KeyCode::Right => self.move_cursor_right(),
+                    KeyCode::Up => self.move_cursor_up(),
+                    KeyCode::Down => self.move_cursor_down(),
+                    KeyCode::PageUp => self.page(false),
+                    KeyCode::PageDown => self.page(true),
+                    KeyCode::Home => self.move_cursor_line_start(),
+                    _ => self.move_cursor_line_end(),
+                }
+                true
+            }
+
+            // Tab / Shift+Tab with a selection: indent / dedent
+            KeyCode::Tab | KeyCode::BackTab if selection.is_some() => {
+                if key.code == KeyCode::BackTab || shift {
+                    self.edit_selected_lines(line_edit::dedent);
+                } else {
+                    self.edit_selected_lines(line_edit::indent);
+                }
+                true
+            }
+            KeyCode::Esc if selection.is_some() => {
+                self.selection_anchor = None;
+                true
+            }
+
+            _ if action.is_some_and(Action::uses_selection) => false,
+
+            _ => {
+                let deletes = matches!(key.code, KeyCode::Backspace | KeyCode::Delete);
+                let replaces = deletes
+                    || key.code == KeyCode::Enter
+                    || action == Some(Action::Yank)
+                    || (action.is_none() && matches!(key.code, KeyCode::Char(_)) && !ctrl && !alt);
+                self.selection_anchor = None;
+                if let (Some(range), true) = (selection, replaces) {
+                    self.delete_range(range);
+                    return deletes;
+                }
+                false
+            }
+        }
+    }
+
+    /// Key hint line under the status, from the current bindings
+    fn key_help_line(&self) -> String {
+        let mut hints: Vec<String> = [
+            (Action::EvalBlock, "Eval block"),
+            (Action::EvalAll, "Reload all"),
+            (Action::Undo, "Undo"),
+            (Action::Redo, "Redo"),
+            (Action::Hush, "Hush"),
+            (Action::ToggleTransport, "Stop/Start"),
+            (Action::Save, "Save"),
+            (Action::Quit, "Quit"),
+        ]
+        .iter()
+        .filter_map(|(action, label)| {
+            let key = self.keymap.keys_for(*action).into_iter().next()?;
+            Some(format!("{}: {}", key, label))
+        })
+        .collect();
+        if self.keymap.style() == KeymapStyle::Vim {
+            let mode = if self.vim_normal {
+                "-- NORMAL --"
+            } else {
+                "-- INSERT --"
+            };
+            hints.insert(0, mode.to_string());
+        }
+        hints.join(" | ")
+    }
+
+    /// Run a bound editor command
+    fn run_action(&mut self, action: Action) -> KeyResult {
+        match action {
+            Action::Quit => return KeyResult::Quit,
+            Action::Save => return KeyResult::Save,
+            Action::EvalBlock => self.eval_chunk(),
+            Action::EvalAll => self.eval_all(),
+            Action::Hush => self.hush(),
+            Action::ToggleTransport => self.set_transport(self.transport_stopped),
+            Action::TapTempo => self.tap_tempo(),
+            Action::Undo => self.undo(),
+            Action::Redo => self.redo(),
+            Action::ToggleConsole => self.command_console.toggle(),
+            Action::TogglePluginBrowser => self.plugin_browser.toggle(),
+            Action::OpenPluginGuis => {
+                #[cfg(all(target_os = "linux", feature = "vst3"))]
+                self.open_plugin_guis();
+            }
+            Action::OpenStepGrid => self.open_step_grid(),
+            Action::ScrubUp => self.scrub_number(1.0, 1.0),
+            Action::ScrubDown => self.scrub_number(-1.0, 1.0),
+            Action::ScrubUpFine => self.scrub_number(1.0, 0.1),
+            Action::ScrubDownFine => self.scrub_number(-1.0, 0.1),
+            Action::ScrubUpCoarse => self.scrub_number(1.0, 10.0),
+            Action::ScrubDownCoarse => self.scrub_number(-1.0, 10.0),
+            Action::NudgeEarlier => self.nudge_bus_at_cursor(-NUDGE_STEP),
+            Action::NudgeLater => self.nudge_bus_at_cursor(NUDGE_STEP),
+            Action::ToggleConfigPanel => {
+                self.show_config_panel = !self.show_config_panel;
+                if self.show_config_panel {
+                    self.status_message =
+                        "⚙️  Configuration Panel (Q: quantize, Esc: close)".to_string();
+                } else {
+                    self.status_message = "Configuration closed".to_string();
+                }
+            }
+            Action::SplitPane => match self.split {
+                Some(_) => self.swap_panes(),
+                None => self.open_split(None),
+            },
+            Action::ToggleInlineHelp => {
+                self.show_inline_help = !self.show_inline_help;
+                self.status_message = if self.show_inline_help {
+                    "Inline help on (Alt+H to hide)".to_string()
+                } else {
+                    "Inline help off".to_string()
+                };
+            }
+            Action::ToggleEventLog => self.toggle_event_log(),
+            Action::Audition => self.audition(),
+            Action::CycleMidiDevice => self.cycle_midi_device(),
+            Action::ToggleMidiRecording => self.toggle_midi_recording(),
+            // ~rec1: slow N $ n "..." # gain "..."
+            Action::MidiSmartPaste => self.insert_midi_smart_paste(),
+            Action::InsertMidiNotes => self.insert_midi_pattern(),
+            Action::InsertMidiOffsets => self.insert_midi_n_pattern(),
+            Action::InsertMidiVelocity => self.insert_midi_velocity_pattern(),
+            Action::InsertMidiLegato => self.insert_midi_legato_pattern(),
+            Action::ForwardChar => self.move_cursor_right(),
+            Action::BackwardChar => self.move_cursor_left(),
+            // Next/previous line navigate the completion list while it's open
+            Action::NextLine if self.completion_state.is_visible() => {
+                self.cycle_completion_forward()
+            }
+            Action::NextLine => self.move_cursor_down(),
+            Action::PreviousLine if self.completion_state.is_visible() => {
+                self.cycle_completion_backward()
+            }
+            Action::PreviousLine => self.move_cursor_up(),
+            Action::LineStart => self.move_cursor_line_start(),
+            Action::LineEnd => self.move_cursor_line_end(),
+            Action::PageUp => self.page(false),
+            Action::PageDown => self.page(true),
+            Action::DeleteChar => match self.selection() {
+                Some(range) => self.delete_range(range),
+                None => self.delete_char_forward(),
+            },
+            Action::KillLine => self.kill_line(),
+            Action::Yank => self.yank(),
+            Action::Cut => self.cut_selection(),
+            Action::Copy => self.copy_selection(),
+            Action::ToggleComment => self.edit_selected_lines(line_edit::toggle_comment),
+            Action::Indent => self.edit_selected_lines(line_edit::indent),
+            Action::Dedent => self.edit_selected_lines(line_edit::dedent),
+            Action::NormalMode => {
+                if self.completion_state.is_visible() {
+                    self.cancel_completion();
+                } else if self.keymap.style() == KeymapStyle::Vim && !self.vim_normal {
+                    // Like vim, the cursor steps back onto the last typed char
+                    if self.cursor_pos > 0 && !self.content[..self.cursor_pos].ends_with('\n') {
+                        self.move_cursor_left();
+                    }
+                    self.vim_normal = true;
+                }
+            }
+            Action::InsertMode => self.vim_normal = false,
+            Action::Append => {
+                if !self.content[self.cursor_pos..].starts_with('\n') {
+                    self.move_cursor_right();
+                }
+                self.vim_normal = false;
+            }
+            Action::AppendLineEnd => {
+                self.move_cursor_line_end();
+                self.vim_normal = false;
+            }
+            Action::InsertLineStart => {
+                self.move_cursor_line_start();
+                self.vim_normal = false;
+            }
+            Action::OpenLineBelow => {
+                self.move_cursor_line_end();
+                self.insert_char('\n');
+                self.vim_normal = false;
+            }
+            Action::OpenLineAbove => {
+                self.move_cursor_line_start();
+                self.insert_char('\n');
+                self.move_cursor_left();
+                self.vim_normal = false;
+            }
+            Action::ToggleSelection => {
+                self.selection_anchor = match self.selection_anchor {
+                    Some(_) => None,
+                    None => Some(self.cursor_pos),
+                };
+            }
+        }
+        KeyResult::Continue
+    }
+
+    /// Vim normal mode: keys run normal-mode bindings instead of typing.
+    /// Returns `None` for keys that fall through to the regular handling
+    /// (bound chords such as C-x). With a selection (`v`), motions extend it.
+    fn handle_normal_mode_key(&mut self, key: KeyEvent) -> Option<KeyResult> {
+        let action = match key.code {
+            KeyCode::Left => Some(Action::BackwardChar),
+            KeyCode::Right => Some(Action::ForwardChar),
+            KeyCode::Up => Some(Action::PreviousLine),
+            KeyCode::Down => Some(Action::NextLine),
+            KeyCode::Home => Some(Action::LineStart),
+            KeyCode::End => Some(Action::LineEnd),
+            KeyCode::PageUp => Some(Action::PageUp),
+            KeyCode::PageDown => Some(Action::PageDown),
+            _ => self.keymap.lookup_normal(&key),
+        };
+        match action {
+            Some(action) => {
+                self.cancel_completion();
+                if !action.is_motion() && !action.uses_selection() {
+                    self.selection_anchor = None;
+                }
+                Some(self.run_action(action))
+            }
+            None if self.keymap.lookup(&key).is_some() => None,
+            None => Some(KeyResult::Continue),
+        }
+    }
+
+    /// Selected byte range (start, end), if the selection is non-empty
+    fn selection(&self) -> Option<(usize, usize)> {
+        let anchor = self.selection_anchor?;
+        if anchor == self.cursor_pos
+            || anchor > self.content.len()
+            || !self.content.is_char_boundary(anchor)
+        {
+            return None;
+        }
+        Some((anchor.min(self.cursor_pos), anchor.max(self.cursor_pos)))
+    }
+
+    /// Delete `start..end` (with undo) and put the cursor there
+    fn delete_range(&mut self, (start, end): (usize, usize)) {
+        self.push_undo();
+        self.content.drain(start..end);
+        self.cursor_pos = start;
+        self.selection_anchor = None;
+        self.error_message = None;
+    }
+
+    /// Cut the selection into the kill buffer (Ctrl+W)
+    fn cut_selection(&mut self) {
+        if let Some((start, end)) = self.selection() {
+            self.kill_buffer = self.content[start..end].to_string();
+            self.delete_range((start, end));
+            self.status_message = format!("✂ Cut {} chars (C-y to paste)", end - start);
+        } else {
+            self.status_message = "No selection (Shift+arrows to select)".to_string();
+        }
+    }
+
+    /// Copy the selection into the kill buffer (Alt+W)
+    fn copy_selection(&mut self) {
+        if let Some((start, end)) = self.selection() {
+            self.kill_buffer = self.content[start..end].to_string();
+            self.selection_anchor = None;
+            self.status_message = format!("📋 Copied {} chars (C-y to paste)", end - start);
+        } else {
+            self.status_message = "No selection (Shift+arrows to select)".to_string();
+        }
+    }
+
+    /// Open the pattern string on the cursor's line as a step grid (Alt+G)
+    fn open_step_grid(&mut self) {
+        match StepGrid::open(&self.content, self.cursor_pos) {
+            Ok(grid) => {
+                self.status_message = format!(
+                    "▦ Step grid: {} rows x {} steps (Enter: write back, Esc: cancel)",
+                    grid.row_count(),
+                    grid.step_count()
+                );
+                self.step_grid = Some(grid);
+            }
+            Err(e) => self.status_message = format!("▦ {}", e),
+        }
+    }
+
+    /// Keys while the step grid is open: move, toggle, write back or cancel
+    fn handle_step_grid_key(&mut self, key: KeyEvent) {
+        let Some(grid) = self.step_grid.as_mut() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char('h') | KeyCode::Left => grid.move_cursor(0, -1),
+            KeyCode::Char('l') | KeyCode::Right => grid.move_cursor(0, 1),
+            KeyCode::Char('k') | KeyCode::Up => grid.move_cursor(-1, 0),
+            KeyCode::Char('j') | KeyCode::Down => grid.move_cursor(1, 0),
+            KeyCode::Char(' ') => grid.toggle(),
+            KeyCode::Enter => {
+                let (start, end) = grid.span;
+                let pattern = grid.to_mini_notation();
+                self.step_grid = None;
+                if self.content[start..end] != pattern {
+                    self.push_undo();
+                    self.content.replace_range(start..end, &pattern);
+                    self.cursor_pos = start;
+                    self.selection_anchor = None;
+                }
+                self.status_message = format!("▦ \"{}\"", pattern);
+            }
+            KeyCode::Esc => {
+                self.step_grid = None;
+                self.status_message = "▦ Step grid closed".to_string();
+            }
+            _ if self.keymap.lookup(&key) == Some(Action::OpenStepGrid) => {
+                self.step_grid = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Nudge the number under the cursor (Alt+Up/Down; with Ctrl for fine
+    /// steps, Shift for coarse ones) and re-evaluate its block, like turning
+    /// a knob
+    fn scrub_number(&mut self, steps: f64, scale: f64) {
+        let Some((content, (start, end))) =
+            scrub::scrub(&self.content, self.cursor_pos, steps, scale)
+        else {
+            self.status_message = "⇅ No number under the cursor".to_string();
+            return;
+        };
+        let old_end = scrub::number_at(&self.content, self.cursor_pos).map_or(end, |(_, e)| e);
+        if self.last_scrub != Some((self.undo_stack.len(), start)) {
+            self.push_undo();
+        }
+        self.last_scrub = Some((self.undo_stack.len(), start));
+        self.cursor_pos = if self.cursor_pos >= old_end {
+            end
+        } else {
+            self.cursor_pos.min(end)
+        };
+        self.content = content;
+        self.selection_anchor = None;
+
+        let number = self.content[start..end].to_string();
+        let chunk = self.get_current_chunk();
+        match self.eval_code(&chunk) {
+            Ok(()) => {
+                self.error_message = None;
+                self.status_message = format!("⇅ {}", number);
+            }
+            Err(e) => self.status_message = format!("⇅ {} ({})", number, e),
+        }
+    }
+
+    /// Apply a line edit to the selected lines (or the cursor line). A
+    /// selection is widened to the edited lines so the edit can be repeated.
+    fn edit_selected_lines(&mut self, edit: fn(&str, (usize, usize)) -> (String, (usize, usize))) {
+        let selection = self.selection();
+        let (from, to) = selection.unwrap_or((self.cursor_pos, self.cursor_pos));
+        let span = line_edit::line_span(&self.content, from, to);
+        let (content, new_span) = edit(&self.content, span);
+        if content == self.content {
+            return;
+        }
+
+        self.push_undo();
+        self.content = content;
+        if selection.is_some() {
+            self.selection_anchor = Some(new_span.0);
+            self.cursor_pos = new_span.1;
+        } else {
+            // Keep the cursor on the same character of its line
+            let delta = (new_span.1 - new_span.0) as isize - (span.1 - span.0) as isize;
+            let pos = (self.cursor_pos as isize + delta).max(new_span.0 as isize) as usize;
+            self.cursor_pos = pos.min(new_span.1);
+        }
+        self.error_message = None;
+    }
+
+    /// Render the UI
+    fn ui(&mut self, f: &mut Frame) {
+        let terminal_width = f.size().width;
 
         // Smart layout: console on side if width allows (2/3 editor, 1/3 console, min 40 for console)
         // Otherwise console on bottom (portrait mode)
@@ -1303,14 +2170,37 @@ impl ModalEditor {
 
         let (editor_chunk, status_chunk, console_chunk) = main_chunks;
 
+        // Side by side: draw the other pane, edit in the focused half
+        let editor_chunk = if self.split.is_some() {
+            let halves = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(editor_chunk);
+            let (focused, other) = if self.focus_left {
+                (halves[0], halves[1])
+            } else {
+                (halves[1], halves[0])
+            };
+            self.other_pane_area = other;
+            self.render_other_pane(f, other);
+            focused
+        } else {
+            editor_chunk
+        };
+        let title = match self.split {
+            Some(_) => format!("● {}", buffer_title(self.file_path.as_deref())),
+            None => "Phonon Live Coding".to_string(),
+        };
+
         // Editor area with white borders
         let editor_block = Block::default()
-            .title("Phonon Live Coding")
+            .title(title)
             .borders(Borders::ALL)
             .style(Style::default().fg(Color::White));
 
         // Update viewport height and ensure cursor is visible
         self.viewport_height = editor_chunk.height;
+        self.editor_area = editor_chunk;
         self.ensure_cursor_visible();
 
         let content_with_cursor = self.content_with_cursor();
@@ -1318,7 +2208,11 @@ impl ModalEditor {
             .block(editor_block)
             .wrap(Wrap { trim: false })
             .scroll((self.scroll_offset, 0))
-            .style(Style::default().fg(Color::White).bg(Color::Black));
+            .style(
+                Style::default()
+                    .fg(self.theme.text)
+                    .bg(self.theme.background),
+            );
 
         f.render_widget(paragraph, editor_chunk);
 
@@ -1480,19 +2374,7 @@ impl ModalEditor {
                         let mut styled_lines: Vec<Line> = Vec::new();
 
                         for doc_line in doc_lines.iter().take(docs_height as usize - 2) {
-                            let style = match doc_line.style {
-                                completion::DocLineStyle::Header => Style::default()
-                                    .fg(Color::Cyan)
-                                    .add_modifier(ratatui::style::Modifier::BOLD),
-                                completion::DocLineStyle::Subheader => {
-                                    Style::default().fg(Color::Yellow)
-                                }
-                                completion::DocLineStyle::Param => Style::default().fg(Color::White),
-                                completion::DocLineStyle::Example => {
-                                    Style::default().fg(Color::Green)
-                                }
-                                completion::DocLineStyle::Empty => Style::default(),
-                            };
+                            let style = doc_line_style(doc_line.style);
                             styled_lines.push(Line::from(Span::styled(&doc_line.text, style)));
                         }
 
@@ -1511,6 +2393,47 @@ impl ModalEditor {
             }
         }
 
+        // Inline help for the call under the cursor (the completion popup has its own docs)
+        if self.show_inline_help && !self.completion_state.is_visible() {
+            if let Some(site) = self.inline_help_call() {
+                let width = editor_chunk.width.saturating_sub(2);
+                let lines = completion::inline_help_lines(&site, width.saturating_sub(2) as usize);
+                let height = ((lines.len() + 2) as u16).min(editor_chunk.height / 2);
+
+                // Bottom of the editor, or the top if the cursor is down there
+                let (cursor_line, _) = self.pos_to_line_col(self.cursor_pos);
+                let cursor_row = (cursor_line as u16).saturating_sub(self.scroll_offset) + 1;
+                let bottom_y = editor_chunk.height.saturating_sub(height + 1);
+                let y = if cursor_row >= bottom_y { 1 } else { bottom_y };
+
+                if height >= 3 {
+                    let help_area = ratatui::layout::Rect {
+                        x: editor_chunk.x + 1,
+                        y: editor_chunk.y + y,
+                        width,
+                        height,
+                    };
+                    let styled_lines: Vec<Line> = lines
+                        .iter()
+                        .take(height as usize - 2)
+                        .map(|l| Line::from(Span::styled(l.text.as_str(), doc_line_style(l.style))))
+                        .collect();
+                    let category = completion::FUNCTION_METADATA
+                        .get(site.function)
+                        .map(|m| m.category)
+                        .unwrap_or_default();
+                    let help_block = Block::default()
+                        .title(format!("{} [{}] (Alt+H to hide)", site.function, category))
+                        .borders(Borders::ALL)
+                        .style(Style::default().fg(Color::Magenta).bg(Color::Black));
+                    let help_paragraph = Paragraph::new(styled_lines)
+                        .block(help_block)
+                        .style(Style::default().bg(Color::Black));
+                    f.render_widget(help_paragraph, help_area);
+                }
+            }
+        }
+
         // Configuration panel (if visible)
         if self.show_config_panel {
             let quantize_str = match self.midi_quantize {
@@ -1616,10 +2539,11 @@ impl ModalEditor {
         let underrun_count = self.underrun_count.load(Ordering::Relaxed);
         let synth_time_us = self.synth_time_us.load(Ordering::Relaxed);
         let ring_fill = self.ring_fill_percent.load(Ordering::Relaxed);
+        let latency_ms = self.latency.total_ms();
 
-        // Calculate synthesis performance
-        // 512 samples @ 44.1kHz = 11,610 microseconds per buffer (realtime budget)
-        let budget_us = 11_610;
+        // Calculate synthesis performance against the realtime budget of one
+        // synthesis chunk at the device sample rate
+        let budget_us = self.synth_budget_us.max(1);
         let synth_percent = if synth_time_us > 0 {
             (synth_time_us * 100) / budget_us
         } else {
@@ -1657,8 +2581,14 @@ impl ModalEditor {
                 "✓"
             };
             format!(
-                "🔊 {} Synth: {}% ({}/{}µs) | Buf: {}% | Underruns: {} (total)",
-                perf_status, synth_percent, synth_time_us, budget_us, ring_fill, underrun_count
+                "🔊 {} Synth: {}% ({}/{}µs) | Buf: {}% | Latency: {:.1}ms | Underruns: {} (total)",
+                perf_status,
+                synth_percent,
+                synth_time_us,
+                budget_us,
+                ring_fill,
+                latency_ms,
+                underrun_count
             )
         } else if self.is_playing {
             format!("🔊 Playing... | Underruns: {} (total)", underrun_count)
@@ -1669,7 +2599,7 @@ impl ModalEditor {
             )
         };
 
-        let help_text = "C-x: Eval block | C-l: Reload all | C-u: Undo | C-r: Redo | C-h: Hush | C-s: Save | Alt-q: Quit";
+        let help_text = self.key_help_line();
 
         let status_chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -1690,6 +2620,19 @@ impl ModalEditor {
         f.render_widget(status_paragraph, status_chunks[0]);
         f.render_widget(help_paragraph, status_chunks[1]);
 
+        // Event log shares the console's area
+        let console_chunk = match console_chunk {
+            Some(area) if self.show_event_log => {
+                let halves = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .split(area);
+                self.render_event_log(f, halves[1]);
+                Some(halves[0])
+            }
+            other => other,
+        };
+
         // Console area
         if let Some(console_area) = console_chunk {
             let console_title = format!("Console ({})", self.console_messages.len());
@@ -1733,6 +2676,20 @@ impl ModalEditor {
             self.plugin_browser.render(f, popup_area, &self.plugin_manager);
         }
 
+        // Step grid overlay, sized to the grid
+        if let Some(grid) = &self.step_grid {
+            let area = f.size();
+            let width = (area.width * 9 / 10).min((16 + 5 * grid.step_count() as u16 / 2).max(72));
+            let height = (area.height * 8 / 10).min(grid.row_count() as u16 + 6);
+            let popup_area = ratatui::layout::Rect {
+                x: (area.width - width) / 2,
+                y: (area.height - height) / 2,
+                width,
+                height,
+            };
+            grid.render(f, popup_area);
+        }
+
         // Command console overlay (rendered on top of everything)
         if self.command_console.is_visible() {
             // Create centered popup area (80% width, 60% height)
@@ -1753,10 +2710,24 @@ impl ModalEditor {
         }
     }
 
+    /// Per-line tokens of the current content, classified by the parser
+    fn classified_lines(&self) -> Ref<'_, Vec<LineTokens>> {
+        if self.highlight_cache.borrow().0 != self.content {
+            *self.highlight_cache.borrow_mut() =
+                (self.content.clone(), classify_source(&self.content));
+        }
+        Ref::map(self.highlight_cache.borrow(), |(_, lines)| lines)
+    }
+
     /// Get content with cursor indicator and syntax highlighting
     fn content_with_cursor(&self) -> Vec<Line<'_>> {
         let mut lines = Vec::new();
         let text_lines: Vec<&str> = self.content.split('\n').collect();
+        let classified = self.classified_lines();
+        let highlight = |line_idx: usize, line_text: &str| match classified.get(line_idx) {
+            Some(tokens) => highlight_tokens(line_text, tokens, &self.theme),
+            None => highlight_line_with(line_text, &self.theme),
+        };
 
         let mut current_pos = 0;
         let mut cursor_line = 0;
@@ -1812,7 +2783,7 @@ impl ModalEditor {
                     }
                 } else if cursor_col < line_text.len() {
                     // Cursor in middle of line - highlight whole line, then add cursor
-                    let mut highlighted = highlight_line(line_text);
+                    let mut highlighted = highlight(line_idx, line_text);
 
                     // Find which character position cursor is at
                     let mut char_count = 0;
@@ -1874,7 +2845,7 @@ impl ModalEditor {
                     spans = modified_spans;
                 } else {
                     // Cursor at end of line
-                    let mut highlighted = highlight_line(line_text);
+                    let mut highlighted = highlight(line_idx, line_text);
                     if is_flashing {
                         // Add flash background to all spans
                         for span in &mut highlighted {
@@ -1898,7 +2869,7 @@ impl ModalEditor {
                         lines.push(Line::from(Span::raw(" "))); // Ensure empty lines take space
                     }
                 } else {
-                    let mut spans = highlight_line(line_text);
+                    let mut spans = highlight(line_idx, line_text);
                     if is_flashing {
                         // Add flash background to all spans
                         for span in &mut spans {
@@ -1910,6 +2881,44 @@ impl ModalEditor {
             }
         }
 
+        // Selection background
+        if let Some((sel_start, sel_end)) = self.selection() {
+            let mut line_start = 0;
+            for (line_idx, line_text) in text_lines.iter().enumerate() {
+                let line_end = line_start + line_text.len();
+                if sel_start <= line_end && sel_end > line_start {
+                    let from = sel_start.max(line_start) - line_start;
+                    let to = sel_end.min(line_end) - line_start;
+                    let from_col = line_text[..from].chars().count();
+                    let to_col = line_text[..to].chars().count();
+                    let spans = std::mem::take(&mut lines[line_idx].spans);
+                    lines[line_idx].spans =
+                        with_selection(spans, from_col, to_col, self.theme.selection);
+                }
+                line_start = line_end + 1;
+            }
+        }
+
+        // Bus level / clip indicator after each bus definition
+        let now = std::time::Instant::now();
+        for (line_idx, line_text) in text_lines.iter().enumerate() {
+            let Some((text, level)) = meters::bus_defined_on(line_text)
+                .and_then(|bus| self.bus_levels.indicator(bus, now))
+            else {
+                continue;
+            };
+            let style = match level {
+                meters::Level::Normal => Style::default().fg(self.theme.comment),
+                meters::Level::Hot => Style::default().fg(Color::Yellow),
+                meters::Level::Clip => Style::default()
+                    .fg(Color::Red)
+                    .add_modifier(ratatui::style::Modifier::BOLD),
+            };
+            lines[line_idx]
+                .spans
+                .push(Span::styled(format!("  {}", text), style));
+        }
+
         // Handle cursor at very end of empty content
         if lines.is_empty() && self.cursor_pos == 0 {
             // Show cursor block for empty file
@@ -1922,127 +2931,643 @@ impl ModalEditor {
         lines
     }
 
-    /// Insert character at cursor position
-    fn insert_char(&mut self, c: char) {
-        // Save state for undo (batch consecutive chars for efficiency)
-        if c == '\n' || self.undo_stack.is_empty() {
-            self.push_undo();
+    /// Insert character at cursor position
+    fn insert_char(&mut self, c: char) {
+        // Save state for undo (batch consecutive chars for efficiency)
+        if c == '\n' || self.undo_stack.is_empty() {
+            self.push_undo();
+        }
+        self.content.insert(self.cursor_pos, c);
+        self.cursor_pos += c.len_utf8();
+        self.error_message = None;
+    }
+
+    /// Delete character before cursor
+    fn delete_char(&mut self) {
+        if self.cursor_pos > 0 {
+            self.push_undo();
+            let char_start = self
+                .content
+                .char_indices()
+                .nth(self.cursor_pos.saturating_sub(1))
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            self.content.remove(char_start);
+            self.cursor_pos = char_start;
+        }
+        self.error_message = None;
+    }
+
+    /// Delete character forward (Ctrl+D)
+    fn delete_char_forward(&mut self) {
+        if self.cursor_pos < self.content.len() {
+            self.push_undo();
+            self.content.remove(self.cursor_pos);
+        }
+        self.error_message = None;
+    }
+
+    /// Kill to end of line (Ctrl+K) - saves to kill buffer
+    fn kill_line(&mut self) {
+        self.push_undo();
+        let lines: Vec<&str> = self.content.split('\n').collect();
+        let mut current_pos = 0;
+
+        for line in lines.iter() {
+            if current_pos + line.len() >= self.cursor_pos {
+                // Found current line
+                let line_start = current_pos;
+                let line_end = current_pos + line.len();
+
+                if self.cursor_pos < line_end {
+                    // Save killed text to kill buffer
+                    self.kill_buffer = self.content[self.cursor_pos..line_end].to_string();
+                    // Remove from cursor to end of line
+                    self.content.drain(self.cursor_pos..line_end);
+                } else {
+                    // At end of line - kill the newline if it exists
+                    if self.cursor_pos < self.content.len() {
+                        self.kill_buffer = "\n".to_string();
+                        self.content.remove(self.cursor_pos);
+                    }
+                }
+                break;
+            }
+            current_pos += line.len() + 1; // +1 for newline
+        }
+        self.error_message = None;
+    }
+
+    /// Yank (paste) from kill buffer (Ctrl+Y)
+    fn yank(&mut self) {
+        if !self.kill_buffer.is_empty() {
+            self.push_undo();
+            self.content.insert_str(self.cursor_pos, &self.kill_buffer);
+            self.cursor_pos += self.kill_buffer.len();
+        }
+        self.error_message = None;
+    }
+
+    /// Push current state to undo stack
+    fn push_undo(&mut self) {
+        // Limit undo stack size to 100 states
+        if self.undo_stack.len() >= 100 {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack
+            .push((self.content.clone(), self.cursor_pos));
+        // Clear redo stack on new edit
+        self.redo_stack.clear();
+    }
+
+    /// Undo last change (Ctrl+U)
+    fn undo(&mut self) {
+        if let Some((content, cursor_pos)) = self.undo_stack.pop() {
+            // Save current state to redo stack
+            self.redo_stack
+                .push((self.content.clone(), self.cursor_pos));
+            // Restore previous state
+            self.content = content;
+            self.cursor_pos = cursor_pos;
+            self.status_message = "↶ Undo".to_string();
+            self.add_console_message("Undo");
+        } else {
+            self.status_message = "⚠️  Nothing to undo".to_string();
+        }
+        self.error_message = None;
+    }
+
+    /// Redo last undone change (Ctrl+R)
+    fn redo(&mut self) {
+        if let Some((content, cursor_pos)) = self.redo_stack.pop() {
+            // Save current state to undo stack
+            self.undo_stack
+                .push((self.content.clone(), self.cursor_pos));
+            // Restore next state
+            self.content = content;
+            self.cursor_pos = cursor_pos;
+            self.status_message = "↷ Redo".to_string();
+            self.add_console_message("Redo");
+        } else {
+            self.status_message = "⚠️  Nothing to redo".to_string();
+        }
+        self.error_message = None;
+    }
+
+    /// Carry out a console command that needs the editor
+    fn handle_console_action(&mut self, action: ConsoleAction) {
+        match action {
+            ConsoleAction::Render { length, path } => {
+                let job = RenderJob {
+                    code: self.content.clone(),
+                    length,
+                    path: path.clone(),
+                    sample_rate: self.sample_rate,
+                };
+                match self.render_queue.submit(job) {
+                    Ok(0) => {
+                        self.add_console_message(&format!(
+                            "⏺ Rendering {} ({})",
+                            path.display(),
+                            length
+                        ));
+                    }
+                    Ok(ahead) => {
+                        self.add_console_message(&format!(
+                            "⏺ Queued {} ({}), {} ahead",
+                            path.display(),
+                            length,
+                            ahead
+                        ));
+                    }
+                    Err(e) => self.add_console_message(&format!("❌ Render: {}", e)),
+                }
+                self.command_console.hide();
+            }
+            ConsoleAction::InsertSnippet { name, body } => {
+                // Snippets always start on a line of their own
+                let at_line_start =
+                    self.cursor_pos == 0 || self.content[..self.cursor_pos].ends_with('\n');
+                if at_line_start {
+                    self.insert_text(&body);
+                } else {
+                    self.insert_text(&format!("\n{}", body));
+                }
+                self.add_console_message(&format!("✂ Inserted snippet '{}'", name));
+                self.command_console.hide();
+            }
+            ConsoleAction::SaveSnippet { name } => {
+                let chunk = self.get_current_chunk();
+                self.command_console.save_snippet(&name, &chunk);
+            }
+            ConsoleAction::Split { path } => {
+                self.open_split(path);
+                self.command_console.hide();
+            }
+            ConsoleAction::Unsplit => {
+                self.close_split();
+                self.command_console.hide();
+            }
+            ConsoleAction::Cue { cycle } => {
+                self.cue(cycle);
+                self.command_console.hide();
+            }
+            ConsoleAction::Transport { running } => {
+                self.set_transport(running);
+                self.command_console.hide();
+            }
+            ConsoleAction::Freeze { bus, cycles } => {
+                self.set_freeze(&bus, Some(cycles));
+                self.command_console.hide();
+            }
+            ConsoleAction::Unfreeze { bus } => {
+                self.set_freeze(&bus, None);
+                self.command_console.hide();
+            }
+            ConsoleAction::Morph { bus, cycles } => {
+                let message = match cycles {
+                    Some(cycles) => {
+                        self.morphs.insert(bus.clone(), cycles);
+                        format!(
+                            "〰 ~{} crossfades over {} cycles when re-evaluated",
+                            bus, cycles
+                        )
+                    }
+                    None if self.morphs.remove(&bus).is_some() => {
+                        format!("~{} swaps instantly again", bus)
+                    }
+                    None => format!("~{} has no morph", bus),
+                };
+                self.add_console_message(&message);
+                self.command_console.hide();
+            }
+            ConsoleAction::Nudge { bus, by } => {
+                self.nudge(&bus, by);
+                let message = self.status_message.clone();
+                self.add_console_message(&message);
+                self.command_console.hide();
+            }
+            ConsoleAction::Sync { source } => {
+                self.set_sync(source);
+                let message = self.status_message.clone();
+                self.add_console_message(&message);
+                self.command_console.hide();
+            }
+            ConsoleAction::SaveSession { path } => {
+                self.save_session(&path);
+                self.command_console.hide();
+            }
+            ConsoleAction::LoadSession { path } => {
+                self.load_session(&path);
+                self.command_console.hide();
+            }
+            ConsoleAction::Quality { locked } => {
+                if let Some(locked) = locked {
+                    self.quality.set_locked(locked);
+                }
+                let message = if self.quality.is_locked() {
+                    "🎚 Quality locked: full quality even under CPU pressure".to_string()
+                } else {
+                    format!(
+                        "🎚 Quality adaptive, now {} (:quality locked to pin it)",
+                        self.quality.level().describe()
+                    )
+                };
+                self.add_console_message(&message);
+                self.command_console.hide();
+            }
+        }
+    }
+
+    /// Move bus `bus`'s events by `by` seconds while it plays, or back to no
+    /// offset. The bus glides there without re-evaluating, and keeps the
+    /// offset through later evaluations
+    fn nudge(&mut self, bus: &str, by: Option<f64>) {
+        let nudge = self.nudges.entry(bus.to_string()).or_default();
+        let target = match by {
+            Some(seconds) => nudge.nudge(seconds),
+            None => {
+                nudge.reset();
+                0.0
+            }
+        };
+        let playing = self.playing_program.iter().any(
+            |statement| matches!(statement, Statement::BusAssignment { name, .. } if name == bus),
+        );
+        let ms = (target * 10000.0).round() / 10.0;
+        self.status_message = if playing {
+            format!("⇆ ~{} nudged to {:+}ms", bus, ms)
+        } else {
+            format!("⇆ ~{} will play {:+}ms off once evaluated", bus, ms)
+        };
+    }
+
+    /// Nudge the bus defined on the cursor's line (Alt+Left/Right)
+    fn nudge_bus_at_cursor(&mut self, seconds: f64) {
+        let (line, _) = self.pos_to_line_col(self.cursor_pos);
+        let bus = self
+            .content
+            .lines()
+            .nth(line)
+            .and_then(meters::bus_defined_on)
+            .map(str::to_string);
+        match bus {
+            Some(bus) => self.nudge(&bus, Some(seconds)),
+            None => self.status_message = "No bus defined on this line to nudge".to_string(),
+        }
+    }
+
+    /// Write the live set to `path`: both panes' evaluated code, the tempo,
+    /// cycle position and node state, and the process's takes, frozen buses
+    /// and samples
+    fn save_session(&mut self, path: &Path) {
+        let Some(code) = self.evaluated.clone() else {
+            self.add_console_message("⚠️  Nothing evaluated to save - evaluate first");
+            return;
+        };
+        let this = SessionPane {
+            code,
+            file: self.file_path.clone(),
+        };
+        let other = self.split.as_ref().and_then(|pane| {
+            Some(SessionPane {
+                code: pane.evaluated.clone()?,
+                file: pane.file_path.clone(),
+            })
+        });
+        let panes = match (other, self.focus_left) {
+            (Some(other), true) => vec![this, other],
+            (Some(other), false) => vec![other, this],
+            (None, _) => vec![this],
+        };
+        let node_states = self.playing_node_states();
+        let cycle = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+        let session = Session::capture(panes, self.evaluated_cps, cycle, node_states);
+        match session.save(path) {
+            Ok(()) => self.add_console_message(&format!(
+                "💾 Saved session {} at cycle {:.2}",
+                path.display(),
+                cycle
+            )),
+            Err(e) => self.add_console_message(&format!("❌ {}", e)),
+        }
+    }
+
+    /// Running node state of the playing graph, asked of the render thread.
+    /// Empty if it doesn't answer in time.
+    fn playing_node_states(&mut self) -> Vec<u8> {
+        let (reply_tx, reply_rx) = std::sync::mpsc::sync_channel(1);
+        if self.cmd_tx.send(Cmd::SaveState(reply_tx)).is_err() {
+            return Vec::new();
+        }
+        // Headless: no synth thread, so answer it here
+        if let Some(rl) = self.render_local.as_ref() {
+            rl.borrow_mut().sync();
+        }
+        reply_rx
+            .recv_timeout(std::time::Duration::from_secs(1))
+            .unwrap_or_default()
+    }
+
+    /// Resume a set saved by `:save-session`: its panes replace the open
+    /// ones and play from the saved cycle at the saved tempo, with the
+    /// saved node state
+    fn load_session(&mut self, path: &Path) {
+        let session = match Session::load(path) {
+            Ok(session) => session,
+            Err(e) => {
+                self.add_console_message(&format!("❌ {}", e));
+                return;
+            }
+        };
+        let Some((first, rest)) = session.panes.split_first() else {
+            self.add_console_message(&format!("❌ {} has no code", path.display()));
+            return;
+        };
+        session.restore(self.sample_rate);
+
+        self.push_undo();
+        self.content = first.code.clone();
+        self.cursor_pos = 0;
+        self.file_path = first.file.clone();
+        self.focus_left = true;
+        self.split = rest.first().map(|pane| PaneState {
+            content: pane.code.clone(),
+            file_path: pane.file.clone(),
+            evaluated: Some(pane.code.clone()),
+            ..PaneState::default()
+        });
+
+        let content = self.content.clone();
+        self.resumed_node_states = Some(session.node_states.clone());
+        let result = self.eval_code(&content);
+        self.resumed_node_states = None;
+        if let Err(e) = result {
+            self.error_message = Some(e.clone());
+            self.add_console_message(&format!("❌ {}", e));
+            return;
+        }
+        let _ = self.cmd_tx.send(Cmd::SetTempo(session.cps as f64));
+        self.evaluated_cps = session.cps;
+        self.cue(session.cycle);
+        self.add_console_message(&format!(
+            "💾 Resumed session {} at cycle {:.2}",
+            path.display(),
+            session.cycle
+        ));
+    }
+
+    /// Add, change or (with `None`) remove the `freeze ~bus` line, then
+    /// re-evaluate the buffer so the bus is bounced or plays live again
+    fn set_freeze(&mut self, bus: &str, cycles: Option<f64>) {
+        let prefix = format!("freeze ~{}", bus);
+        let is_freeze_line = |line: &str| {
+            let line = line.trim();
+            line.strip_prefix(&prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        };
+        let mut lines: Vec<String> = self
+            .content
+            .lines()
+            .filter(|line| !is_freeze_line(line))
+            .map(str::to_string)
+            .collect();
+        let unchanged = lines.len() == self.content.lines().count();
+        if cycles.is_none() && unchanged {
+            self.add_console_message(&format!("~{} is not frozen", bus));
+            return;
+        }
+        if let Some(cycles) = cycles {
+            lines.push(format!("{} {}c", prefix, cycles));
+        }
+
+        self.push_undo();
+        let trailing_newline = self.content.ends_with('\n');
+        self.content = lines.join("\n");
+        if trailing_newline {
+            self.content.push('\n');
+        }
+        self.cursor_pos = self.cursor_pos.min(self.content.len());
+        self.eval_all();
+        match (&self.error_message, cycles) {
+            (Some(e), _) => self.add_console_message(&format!("❌ {}", e)),
+            (None, Some(cycles)) => {
+                self.add_console_message(&format!("❄ Froze ~{} ({} cycles)", bus, cycles))
+            }
+            (None, None) => self.add_console_message(&format!("❄ ~{} plays live again", bus)),
+        }
+    }
+
+    /// Open `path` (or a scratch buffer) in a pane beside the current one and
+    /// focus it. If already split, it replaces the pane without focus.
+    fn open_split(&mut self, path: Option<PathBuf>) {
+        let pane = match PaneState::open(path.as_deref()) {
+            Ok(pane) => pane,
+            Err(e) => {
+                self.add_console_message(&format!("❌ {}", e));
+                return;
+            }
+        };
+        if self.split.is_none() {
+            self.focus_left = true;
         }
-        self.content.insert(self.cursor_pos, c);
-        self.cursor_pos += c.len_utf8();
-        self.error_message = None;
+        self.split = Some(pane);
+        self.swap_panes();
+        self.add_console_message(&format!(
+            "◫ Split: {} | Alt-o switches panes, :unsplit closes",
+            buffer_title(path.as_deref())
+        ));
     }
 
-    /// Delete character before cursor
-    fn delete_char(&mut self) {
-        if self.cursor_pos > 0 {
-            self.push_undo();
-            let char_start = self
-                .content
-                .char_indices()
-                .nth(self.cursor_pos.saturating_sub(1))
-                .map(|(i, _)| i)
-                .unwrap_or(0);
-            self.content.remove(char_start);
-            self.cursor_pos = char_start;
+    /// Close the pane without focus. Its code keeps playing until the next eval.
+    fn close_split(&mut self) {
+        match self.split.take() {
+            Some(pane) => {
+                self.focus_left = true;
+                self.add_console_message(&format!(
+                    "◫ Closed {}",
+                    buffer_title(pane.file_path.as_deref())
+                ));
+            }
+            None => self.add_console_message("◫ Not split"),
         }
-        self.error_message = None;
     }
 
-    /// Delete character forward (Ctrl+D)
-    fn delete_char_forward(&mut self) {
-        if self.cursor_pos < self.content.len() {
-            self.push_undo();
-            self.content.remove(self.cursor_pos);
+    /// Move focus to the other pane, swapping its buffer into the editor
+    fn swap_panes(&mut self) {
+        let Some(pane) = self.split.as_mut() else {
+            return;
+        };
+        std::mem::swap(&mut self.content, &mut pane.content);
+        std::mem::swap(&mut self.cursor_pos, &mut pane.cursor_pos);
+        std::mem::swap(&mut self.file_path, &mut pane.file_path);
+        std::mem::swap(&mut self.selection_anchor, &mut pane.selection_anchor);
+        std::mem::swap(&mut self.scroll_offset, &mut pane.scroll_offset);
+        std::mem::swap(&mut self.undo_stack, &mut pane.undo_stack);
+        std::mem::swap(&mut self.redo_stack, &mut pane.redo_stack);
+        std::mem::swap(&mut self.evaluated, &mut pane.evaluated);
+        std::mem::swap(&mut self.editor_area, &mut self.other_pane_area);
+        self.focus_left = !self.focus_left;
+        self.flash_highlight = None;
+        self.cancel_completion();
+        self.status_message = format!("◫ {}", buffer_title(self.file_path.as_deref()));
+    }
+
+    /// Draw the pane without focus (no cursor, dimmed border)
+    fn render_other_pane(&self, f: &mut Frame, area: Rect) {
+        if let Some(pane) = self.split.as_ref() {
+            let classified = classify_source(&pane.content);
+            let lines: Vec<Line> = pane
+                .content
+                .split('\n')
+                .zip(&classified)
+                .map(|(text, tokens)| Line::from(highlight_tokens(text, tokens, &self.theme)))
+                .collect();
+            let block = Block::default()
+                .title(buffer_title(pane.file_path.as_deref()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray));
+            let paragraph = Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false })
+                .scroll((pane.scroll_offset, 0))
+                .style(
+                    Style::default()
+                        .fg(self.theme.text)
+                        .bg(self.theme.background),
+                );
+            f.render_widget(paragraph, area);
         }
-        self.error_message = None;
     }
 
-    /// Kill to end of line (Ctrl+K) - saves to kill buffer
-    fn kill_line(&mut self) {
-        self.push_undo();
-        let lines: Vec<&str> = self.content.split('\n').collect();
-        let mut current_pos = 0;
+    /// Show or hide the event log; the render thread only reports events
+    /// while it is showing
+    fn toggle_event_log(&mut self) {
+        self.show_event_log = !self.show_event_log;
+        self.event_log.set_enabled(self.show_event_log);
+        self.status_message = if self.show_event_log {
+            self.event_log.clear();
+            "Event log on (Alt+E to hide)".to_string()
+        } else {
+            "Event log off".to_string()
+        };
+    }
 
-        for line in lines.iter() {
-            if current_pos + line.len() >= self.cursor_pos {
-                // Found current line
-                let line_start = current_pos;
-                let line_end = current_pos + line.len();
+    /// Most recent triggered events, newest at the bottom
+    fn render_event_log(&self, f: &mut Frame, area: Rect) {
+        let height = area.height.saturating_sub(2) as usize;
+        let lines = self.event_log.lines();
+        let visible: Vec<Line> = lines
+            .iter()
+            .skip(lines.len().saturating_sub(height))
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+        let title = if lines.is_empty() && self.is_playing {
+            "Events (none triggered)".to_string()
+        } else {
+            "Events".to_string()
+        };
+        let paragraph = Paragraph::new(visible)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .style(Style::default().fg(Color::Yellow)),
+            )
+            .style(Style::default().fg(Color::White));
+        f.render_widget(paragraph, area);
+    }
 
-                if self.cursor_pos < line_end {
-                    // Save killed text to kill buffer
-                    self.kill_buffer = self.content[self.cursor_pos..line_end].to_string();
-                    // Remove from cursor to end of line
-                    self.content.drain(self.cursor_pos..line_end);
-                } else {
-                    // At end of line - kill the newline if it exists
-                    if self.cursor_pos < self.content.len() {
-                        self.kill_buffer = "\n".to_string();
-                        self.content.remove(self.cursor_pos);
-                    }
-                }
-                break;
-            }
-            current_pos += line.len() + 1; // +1 for newline
-        }
-        self.error_message = None;
+    /// The function call under the cursor, for the inline help pane
+    fn inline_help_call(&self) -> Option<completion::CallSite> {
+        let line_start = self.content[..self.cursor_pos]
+            .rfind('\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let line_end = self.content[self.cursor_pos..]
+            .find('\n')
+            .map(|i| self.cursor_pos + i)
+            .unwrap_or(self.content.len());
+        completion::call_at_cursor(
+            &self.content[line_start..line_end],
+            self.cursor_pos - line_start,
+        )
     }
 
-    /// Yank (paste) from kill buffer (Ctrl+Y)
-    fn yank(&mut self) {
-        if !self.kill_buffer.is_empty() {
-            self.push_undo();
-            self.content.insert_str(self.cursor_pos, &self.kill_buffer);
-            self.cursor_pos += self.kill_buffer.len();
+    /// Take the bus peaks rendered since the last frame
+    fn poll_bus_meters(&mut self) {
+        if let Some(bus_meters) = &self.bus_meters {
+            self.bus_levels
+                .update(bus_meters, std::time::Instant::now());
         }
-        self.error_message = None;
     }
 
-    /// Push current state to undo stack
-    fn push_undo(&mut self) {
-        // Limit undo stack size to 100 states
-        if self.undo_stack.len() >= 100 {
-            self.undo_stack.remove(0);
+    /// Move render watchdog reports into the console pane
+    fn poll_watchdog(&mut self) {
+        while let Ok(report) = self.watchdog_rx.try_recv() {
+            self.add_console_message(&report);
+            self.error_message = Some(report);
         }
-        self.undo_stack
-            .push((self.content.clone(), self.cursor_pos));
-        // Clear redo stack on new edit
-        self.redo_stack.clear();
     }
 
-    /// Undo last change (Ctrl+U)
-    fn undo(&mut self) {
-        if let Some((content, cursor_pos)) = self.undo_stack.pop() {
-            // Save current state to redo stack
-            self.redo_stack
-                .push((self.content.clone(), self.cursor_pos));
-            // Restore previous state
-            self.content = content;
-            self.cursor_pos = cursor_pos;
-            self.status_message = "↶ Undo".to_string();
-            self.add_console_message("Undo");
-        } else {
-            self.status_message = "⚠️  Nothing to undo".to_string();
+    /// Report adaptive quality changes from the synth thread in the console
+    fn poll_quality(&mut self) {
+        let level = self.quality.level();
+        if level == self.shown_quality {
+            return;
         }
-        self.error_message = None;
+        let message = if level > self.shown_quality {
+            format!(
+                "⚠️  CPU at {}% of the block budget - {} (:quality locked keeps full quality)",
+                self.quality.load_percent(),
+                level.describe()
+            )
+        } else if level == Quality::Full {
+            "✅ Full quality restored".to_string()
+        } else {
+            format!("↗️  Quality back up: {}", level.describe())
+        };
+        self.add_console_message(&message);
+        self.shown_quality = level;
     }
 
-    /// Redo last undone change (Ctrl+R)
-    fn redo(&mut self) {
-        if let Some((content, cursor_pos)) = self.redo_stack.pop() {
-            // Save current state to undo stack
-            self.undo_stack
-                .push((self.content.clone(), self.cursor_pos));
-            // Restore next state
-            self.content = content;
-            self.cursor_pos = cursor_pos;
-            self.status_message = "↷ Redo".to_string();
-            self.add_console_message("Redo");
-        } else {
-            self.status_message = "⚠️  Nothing to redo".to_string();
+    /// Move background render progress into the console pane
+    fn poll_render_queue(&mut self) {
+        for update in self.render_queue.poll() {
+            match update {
+                RenderUpdate::Started { path, seconds } => {
+                    self.add_console_message(&format!(
+                        "⏺ {}: rendering {:.1}s",
+                        path.display(),
+                        seconds
+                    ));
+                }
+                RenderUpdate::Progress { path, percent } => {
+                    self.add_console_message(&format!("⏺ {}: {}%", path.display(), percent));
+                }
+                RenderUpdate::Finished {
+                    path,
+                    seconds,
+                    peak,
+                } => {
+                    let peak_db = 20.0 * peak.max(1e-6).log10();
+                    self.add_console_message(&format!(
+                        "✅ Rendered {} ({:.1}s, peak {:.1} dB)",
+                        path.display(),
+                        seconds,
+                        peak_db
+                    ));
+                    self.status_message = format!("✅ Rendered {}", path.display());
+                }
+                RenderUpdate::Failed { path, error } => {
+                    self.add_console_message(&format!(
+                        "❌ Render {} failed: {}",
+                        path.display(),
+                        error
+                    ));
+                }
+            }
         }
-        self.error_message = None;
     }
 
     /// Add message to console
@@ -2171,7 +3696,7 @@ impl ModalEditor {
         let content = self.content.clone();
 
         // Load the code into the graph
-        if let Err(e) = self.load_code(&content) {
+        if let Err(e) = self.eval_code(&content) {
             self.error_message = Some(format!("Failed to load: {e}"));
         } else {
             self.status_message = "✅ Pattern reloaded!".to_string();
@@ -2229,7 +3754,7 @@ impl ModalEditor {
 
         // Evaluate ONLY the current chunk (Tidal-style block evaluation)
         // Use C-r to reload the entire buffer if needed
-        let result = self.load_code(&chunk);
+        let result = self.eval_code(&chunk);
 
         // Now we can mutate self safely - add all console messages
         self.add_console_message(&format!("📝 Evaluating: {} chars", chunk.len()));
@@ -2269,7 +3794,7 @@ impl ModalEditor {
         // Clone content to avoid borrow checker issues
         let content = self.content.clone();
 
-        if let Err(e) = self.load_code(&content) {
+        if let Err(e) = self.eval_code(&content) {
             self.error_message = Some(format!("Reload failed: {e}"));
         } else {
             self.status_message = "✅ Session reloaded!".to_string();
@@ -2354,11 +3879,214 @@ impl ModalEditor {
                 rl.borrow_mut().sync();
             }
         }
-        // Clear ring buffer for instant silence.
-        self.should_clear_ring.store(true, Ordering::Relaxed);
+        // Clear ring buffer for instant silence. Set after the send: the synth
+        // thread drops the queued audio once it has applied the command
+        self.should_clear_ring.store(true, Ordering::Release);
         self.status_message = "🔇 Hushed - C-r to reload".to_string();
     }
 
+    /// Stop (`:stop`) or start (`:start`) live playback. Stopping holds the
+    /// cycle position, voices and effect tails where they are; starting
+    /// carries on from there. Audio already queued plays out first, so
+    /// playback resumes at the sample after the last one heard.
+    fn set_transport(&mut self, running: bool) {
+        if !self.first_graph_sent {
+            self.add_console_message("⚠️  Nothing playing - evaluate first");
+            return;
+        }
+        // Already playing, or already stopped
+        if running != self.transport_stopped {
+            return;
+        }
+        let cmd = if running { Cmd::Start } else { Cmd::Stop };
+        if self.cmd_tx.send(cmd).is_err() {
+            self.error_message = Some("render thread busy (command ring full)".to_string());
+            return;
+        }
+        if let Some(rl) = self.render_local.as_ref() {
+            rl.borrow_mut().sync();
+        }
+        if let Some(clock) = self.midi_clock.as_ref() {
+            clock.set_running(running);
+        }
+        self.transport_stopped = !running;
+
+        let cycle = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+        let keys = self.keymap.keys_for(Action::ToggleTransport);
+        let key = keys.first().map_or(":start".to_string(), |k| k.to_string());
+        self.status_message = if running {
+            format!("▶️  Playing from cycle {:.2}", cycle)
+        } else {
+            format!("⏸  Stopped at cycle {:.2} - {} to start", cycle, key)
+        };
+        let message = self.status_message.clone();
+        self.add_console_message(&message);
+    }
+
+    /// Jump live playback to `cycle` (`:cue`)
+    fn cue(&mut self, cycle: f64) {
+        if !self.first_graph_sent {
+            self.add_console_message("⚠️  Nothing playing to cue - evaluate first");
+            return;
+        }
+        // The render owner seeks its graph and moves its clock at the next
+        // buffer boundary
+        let _ = self.cmd_tx.send(Cmd::Seek(cycle));
+        if let Some(rl) = self.render_local.as_ref() {
+            rl.borrow_mut().sync();
+        }
+        // Drop the audio queued from before the jump (after the send, as in
+        // `hush`)
+        self.should_clear_ring.store(true, Ordering::Release);
+        self.status_message = format!("⏩ Cued to cycle {}", cycle);
+        self.add_console_message(&format!("⏩ Cued to cycle {}", cycle));
+    }
+
+    /// Follow MIDI clock or tapped beats for tempo (`:sync`), or stop
+    /// following (`None`): the code sets the tempo again from the next
+    /// evaluation
+    fn set_sync(&mut self, source: Option<SyncSource>) {
+        let sync = match source {
+            Some(SyncSource::Midi(device)) => {
+                match MidiClockIn::connect(&device, DEFAULT_BEATS_PER_CYCLE) {
+                    Ok(clock) => {
+                        self.status_message =
+                            format!("🕐 Following MIDI clock from {}", clock.port_name());
+                        Some(TempoSync::Midi(clock))
+                    }
+                    Err(e) => {
+                        self.status_message = format!("⚠️  Can't follow MIDI clock: {}", e);
+                        return;
+                    }
+                }
+            }
+            Some(SyncSource::Tap) => {
+                let keys = self.keymap.keys_for(Action::TapTempo);
+                let key = keys.first().map_or("a key".to_string(), |k| k.to_string());
+                self.status_message = format!("🥁 Tap the beat with {}", key);
+                Some(TempoSync::Tap(TapTempo::default()))
+            }
+            None => {
+                self.synced_cps = None;
+                self.status_message =
+                    "Sync off - the code sets the tempo from the next evaluation".to_string();
+                None
+            }
+        };
+        self.tempo_sync = sync;
+        self.sync_follower.rejoin();
+        self.sync_transport = (None, 0);
+    }
+
+    /// Tap the beat (Alt+T). From the second tap playback follows the
+    /// tapped tempo, with the beat on the last tap
+    fn tap_tempo(&mut self) {
+        if self.tempo_sync.is_none() {
+            self.tempo_sync = Some(TempoSync::Tap(TapTempo::default()));
+        }
+        let Some(TempoSync::Tap(taps)) = self.tempo_sync.as_mut() else {
+            self.status_message = "⚠️  Following MIDI clock - :sync off to tap".to_string();
+            return;
+        };
+        let count = taps.tap(std::time::Instant::now());
+        self.status_message = if count < 2 {
+            "🥁 Tap - keep tapping the beat".to_string()
+        } else {
+            format!("🥁 Tapped {:.1} BPM", taps.tempo_bpm())
+        };
+        self.sync_follower.rejoin();
+        self.follow_sync();
+    }
+
+    /// Steer playback after the tempo followed: send its tempo, nudged to
+    /// stay in phase, and seek into phase on joining. A MIDI clock's Start,
+    /// Continue and Stop also start and stop the transport.
+    fn follow_sync(&mut self) {
+        if !self.first_graph_sent {
+            return;
+        }
+        // The rendered position is heard after the output latency, so it is
+        // compared with the beat then
+        let position = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+        let heard_at = std::time::Instant::now()
+            + StdDuration::from_secs_f64(self.latency.total_ms().max(0.0) / 1000.0);
+        let (step, transport) = match self.tempo_sync.as_ref() {
+            None => return,
+            Some(TempoSync::Tap(taps)) => {
+                let step =
+                    self.sync_follower
+                        .step(taps, heard_at, position, DEFAULT_BEATS_PER_CYCLE);
+                (step, self.sync_transport)
+            }
+            Some(TempoSync::Midi(clock)) => {
+                let follower = clock.follower();
+                if follower.resyncs() != self.sync_transport.1 {
+                    self.sync_follower.rejoin();
+                }
+                let step = self.sync_follower.step(
+                    &*follower,
+                    heard_at,
+                    position,
+                    DEFAULT_BEATS_PER_CYCLE,
+                );
+                (step, (follower.running(), follower.resyncs()))
+            }
+        };
+
+        if transport.0 != self.sync_transport.0 {
+            if let Some(running) = transport.0 {
+                self.set_transport(running);
+            }
+        }
+        self.sync_transport = transport;
+        if self.transport_stopped {
+            // Held where it is: seek into phase again once it carries on
+            self.sync_follower.rejoin();
+            return;
+        }
+        let Some(step) = step else {
+            return;
+        };
+        if self.synced_cps == Some(step.cps as f32) && step.seek.is_none() {
+            return;
+        }
+        let _ = self.cmd_tx.send(Cmd::SetTempo(step.cps));
+        if let Some(cycle) = step.seek {
+            let _ = self.cmd_tx.send(Cmd::Seek(cycle));
+        }
+        if let Some(rl) = self.render_local.as_ref() {
+            rl.borrow_mut().sync();
+        }
+        self.synced_cps = Some(step.cps as f32);
+    }
+
+    /// Play the selection, or the bus or expression on the cursor line, for
+    /// one cycle over the live output (Alt+Enter)
+    fn audition(&mut self) {
+        let target = match self.selection() {
+            Some((start, end)) => self.content[start..end].to_string(),
+            None => {
+                let (line, _) = self.pos_to_line_col(self.cursor_pos);
+                self.content.lines().nth(line).unwrap_or("").to_string()
+            }
+        };
+        let start_cycle = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+        let rendered = audition_program(&self.content, &target).and_then(|program| {
+            render_audition(program, self.sample_rate, start_cycle, self.default_tempo)
+        });
+        match rendered {
+            Ok(buffer) => {
+                if !self.audition.play(buffer) {
+                    self.add_console_message("⚠️  Audition dropped - synthesis is behind");
+                    return;
+                }
+                self.error_message = None;
+                self.status_message = format!("🎧 Auditioning {}", target.trim());
+            }
+            Err(e) => self.error_message = Some(format!("Audition: {}", e)),
+        }
+    }
+
     /// Panic - stop everything
     fn panic(&mut self) {
         // Route the panic through the render-owner command channel: the render
@@ -2369,8 +4097,9 @@ impl ModalEditor {
                 rl.borrow_mut().sync();
             }
         }
-        // Clear ring buffer for instant silence.
-        self.should_clear_ring.store(true, Ordering::Relaxed);
+        // Clear ring buffer for instant silence. Set after the send: the synth
+        // thread drops the queued audio once it has applied the command
+        self.should_clear_ring.store(true, Ordering::Release);
         self.status_message = "🚨 PANIC! All stopped - C-r to restart".to_string();
     }
 
@@ -2766,6 +4495,12 @@ impl ModalEditor {
                         recorder.record_event(event.clone());
                     }
                 }
+                if let MidiMessageType::NoteOn { note, .. } = event.message_type {
+                    let now = f64::from_bits(self.current_cycle_bits.load(Ordering::Relaxed));
+                    for take in &mut self.take_recordings {
+                        take.note_on(now, event.channel, note);
+                    }
+                }
 
                 // Show note-on events in status (feedback)
                 if let MidiMessageType::NoteOn { note, velocity } = event.message_type {
@@ -2968,6 +4703,10 @@ impl ModalEditor {
             "out6",
             "out7",
             "out8",
+            "cue",
+            "precue",
+            "cvout",
+            "gateout",
             "hush",
             "panic",
         ];
@@ -3032,14 +4771,91 @@ impl ModalEditor {
         (line_count.saturating_sub(1), 0)
     }
 
+    /// Byte offset of character `col` on `line`, clamped to the line's end
+    fn line_col_to_pos(&self, line: usize, col: usize) -> usize {
+        let mut pos = 0;
+        for (idx, text) in self.content.split('\n').enumerate() {
+            if idx == line {
+                return pos
+                    + text
+                        .char_indices()
+                        .nth(col)
+                        .map_or(text.len(), |(offset, _)| offset);
+            }
+            pos += text.len() + 1;
+        }
+        self.content.len()
+    }
+
+    /// Cursor column in characters
+    fn cursor_char_col(&self) -> usize {
+        self.content[..self.cursor_pos]
+            .rsplit('\n')
+            .next()
+            .map_or(0, |text| text.chars().count())
+    }
+
+    /// Lines of text that fit in the editor pane
+    fn visible_height(&self) -> u16 {
+        self.viewport_height.saturating_sub(4) // Account for borders
+    }
+
+    /// PgUp/PgDn: move the view and the cursor by one screenful
+    fn page(&mut self, down: bool) {
+        let page = self.visible_height().max(1) as usize;
+        let (line, _) = self.pos_to_line_col(self.cursor_pos);
+        let line_count = self.content.split('\n').count();
+        let target = if down {
+            (line + page).min(line_count - 1)
+        } else {
+            line.saturating_sub(page)
+        };
+        self.cursor_pos = self.line_col_to_pos(target, self.cursor_char_col());
+
+        let offset = if down {
+            self.scroll_offset.saturating_add(page as u16)
+        } else {
+            self.scroll_offset.saturating_sub(page as u16)
+        };
+        self.scroll_offset = offset.min(self.max_scroll_offset());
+    }
+
+    /// Scroll the view by `delta` lines (mouse wheel), dragging the cursor
+    /// along only as far as needed to keep it on screen
+    fn scroll_by(&mut self, delta: isize) {
+        let offset = (self.scroll_offset as isize + delta).max(0) as u16;
+        self.scroll_offset = offset.min(self.max_scroll_offset());
+
+        // Keep the cursor inside the band `ensure_cursor_visible` accepts, so
+        // it doesn't scroll straight back on the next frame
+        let (line, _) = self.pos_to_line_col(self.cursor_pos);
+        let top = if self.scroll_offset == 0 {
+            0
+        } else {
+            (self.scroll_offset + SCROLL_MARGIN) as usize
+        };
+        let bottom =
+            (self.scroll_offset + self.visible_height()).saturating_sub(SCROLL_MARGIN + 1) as usize;
+        let target = line.clamp(top, bottom.max(top));
+        if target != line {
+            self.cursor_pos = self.line_col_to_pos(target, self.cursor_char_col());
+        }
+    }
+
+    /// Largest scroll offset that still keeps the last line in view
+    fn max_scroll_offset(&self) -> u16 {
+        let last_line = self.content.split('\n').count().saturating_sub(1) as u16;
+        last_line.saturating_sub(self.visible_height().saturating_sub(SCROLL_MARGIN + 1))
+    }
+
     /// Ensure the cursor line is visible by adjusting scroll_offset
     fn ensure_cursor_visible(&mut self) {
         let (cursor_line, _) = self.pos_to_line_col(self.cursor_pos);
         let cursor_line = cursor_line as u16;
 
-        // Leave some margin (2 lines) at top and bottom when possible
-        let margin = 2u16;
-        let visible_height = self.viewport_height.saturating_sub(4); // Account for borders
+        // Leave some margin at top and bottom when possible
+        let margin = SCROLL_MARGIN;
+        let visible_height = self.visible_height();
 
         // If cursor is above visible area, scroll up
         if cursor_line < self.scroll_offset + margin {
@@ -3389,14 +5205,16 @@ impl ModalEditor {
                 self.command_console.hide();
                 KeyResult::Continue
             }
-            KeyCode::Char('/') if key.modifiers.contains(KeyModifiers::ALT) => {
+            _ if self.keymap.lookup(&key) == Some(Action::ToggleConsole) => {
                 self.command_console.toggle();
                 KeyResult::Continue
             }
 
             // Enter : Execute command
             KeyCode::Enter => {
-                self.command_console.execute_command();
+                if let Some(action) = self.command_console.execute_command() {
+                    self.handle_console_action(action);
+                }
                 KeyResult::Continue
             }
 
@@ -3799,7 +5617,7 @@ impl ModalEditor {
             match create_real_plugin_by_name(&plugin_name) {
                 Ok(mut plugin) => {
                     // Initialize plugin
-                    if let Err(e) = plugin.initialize(48000.0, 512) {
+                    if let Err(e) = plugin.initialize(self.sample_rate, 512) {
                         self.plugin_browser.set_status(format!("Init failed: {}", e));
                         return;
                     }
@@ -3838,3 +5656,61 @@ enum KeyResult {
     Play,
     Save,
 }
+
+/// Style for a documentation line (completion docs panel and inline help)
+fn doc_line_style(style: completion::DocLineStyle) -> Style {
+    match style {
+        completion::DocLineStyle::Header => Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(ratatui::style::Modifier::BOLD),
+        completion::DocLineStyle::Subheader => Style::default().fg(Color::Yellow),
+        completion::DocLineStyle::Param => Style::default().fg(Color::White),
+        completion::DocLineStyle::ActiveParam => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(ratatui::style::Modifier::BOLD),
+        completion::DocLineStyle::Example => Style::default().fg(Color::Green),
+        completion::DocLineStyle::Empty => Style::default(),
+    }
+}
+
+/// Give the characters `from..to` (columns within the line) a selection background
+fn with_selection(spans: Vec<Span<'_>>, from: usize, to: usize, color: Color) -> Vec<Span<'_>> {
+    // The cursor cell (white background) stays visible
+    let selected = |style: Style| {
+        if style.bg == Some(Color::White) {
+            style
+        } else {
+            style.bg(color).fg(Color::White)
+        }
+    };
+    let mut result = Vec::new();
+    let mut col = 0;
+    for span in spans {
+        let chars: Vec<char> = span.content.chars().collect();
+        let len = chars.len();
+        let a = from.clamp(col, col + len) - col;
+        let b = to.clamp(col, col + len) - col;
+        col += len;
+        if a == b {
+            result.push(span);
+            continue;
+        }
+        if a > 0 {
+            result.push(Span::styled(
+                chars[..a].iter().collect::<String>(),
+                span.style,
+            ));
+        }
+        result.push(Span::styled(
+            chars[a..b].iter().collect::<String>(),
+            selected(span.style),
+        ));
+        if b < len {
+            result.push(Span::styled(
+                chars[b..].iter().collect::<String>(),
+                span.style,
+            ));
+        }
+    }
+    result
+}
@@ -0,0 +1,67 @@
+//! Side-by-side editing: the second pane opened with Alt+O or `:split <file>`
+//!
+//! The focused buffer lives in the editor's own fields; the other one is kept
+//! here and the two are swapped when focus moves. Each pane remembers the code
+//! it last sent to the engine, and evaluating either pane merges both into one
+//! graph (`compositional_compiler::merge_programs`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Buffer state of the pane without focus
+#[derive(Debug, Clone, Default)]
+pub struct PaneState {
+    pub content: String,
+    pub cursor_pos: usize,
+    pub file_path: Option<PathBuf>,
+    pub selection_anchor: Option<usize>,
+    pub scroll_offset: u16,
+    pub undo_stack: Vec<(String, usize)>,
+    pub redo_stack: Vec<(String, usize)>,
+    /// Code this pane last sent to the engine
+    pub evaluated: Option<String>,
+}
+
+impl PaneState {
+    /// Open `path` (empty if it doesn't exist yet), or a scratch buffer
+    pub fn open(path: Option<&Path>) -> Result<Self, String> {
+        let content = match path {
+            Some(path) if path.exists() => fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?,
+            _ => String::new(),
+        };
+        Ok(Self {
+            content,
+            file_path: path.map(Path::to_path_buf),
+            ..Self::default()
+        })
+    }
+}
+
+/// Pane title: the file name, or `[scratch]` for an unsaved buffer
+pub fn buffer_title(path: Option<&Path>) -> String {
+    path.and_then(|p| p.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "[scratch]".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_pane() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("drums.ph");
+        fs::write(&path, "out $ s \"bd sn\"").unwrap();
+
+        let pane = PaneState::open(Some(&path)).unwrap();
+        assert_eq!(pane.content, "out $ s \"bd sn\"");
+        assert_eq!(buffer_title(pane.file_path.as_deref()), "drums.ph");
+
+        let new_file = PaneState::open(Some(&tmp.path().join("synths.ph"))).unwrap();
+        assert!(new_file.content.is_empty());
+
+        assert_eq!(buffer_title(None), "[scratch]");
+    }
+}
@@ -0,0 +1,339 @@
+//! Background render queue for the editor
+//!
+//! `:render 32c out.wav` in the command console renders the current buffer to
+//! a WAV file while live playback continues. The live graph belongs to the
+//! synth thread and isn't `Send`, so each job compiles its own copy of the
+//! buffer on a worker thread and renders it offline (stereo, 32-bit float,
+//! at the editor's sample rate). Jobs run one at a time in submission order;
+//! progress comes back over a channel that the editor drains into its console.
+
+use crate::compositional_compiler::compile_program;
+use crate::compositional_parser::{parse_program, Statement};
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+/// Frames rendered per block
+const BLOCK_FRAMES: usize = 512;
+
+/// How much to render: `32c` (cycles, the default unit) or `10s` (seconds)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderLength {
+    Cycles(f64),
+    Seconds(f64),
+}
+
+impl RenderLength {
+    /// Parse `32c`, `32` (cycles) or `10s` / `2.5s` (seconds)
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (number, seconds) = if let Some(n) = text.strip_suffix('s') {
+            (n, true)
+        } else {
+            (text.strip_suffix('c').unwrap_or(text), false)
+        };
+        let value: f64 = number
+            .parse()
+            .map_err(|_| format!("Invalid length '{}' (use e.g. 32c or 10s)", text))?;
+        if !(value > 0.0 && value.is_finite()) {
+            return Err(format!("Render length must be positive, got '{}'", text));
+        }
+        Ok(if seconds {
+            RenderLength::Seconds(value)
+        } else {
+            RenderLength::Cycles(value)
+        })
+    }
+
+    /// Length in seconds at `cps` cycles per second
+    pub fn seconds(&self, cps: f32) -> f64 {
+        match *self {
+            RenderLength::Cycles(cycles) => cycles / cps.max(1e-6) as f64,
+            RenderLength::Seconds(seconds) => seconds,
+        }
+    }
+}
+
+impl std::fmt::Display for RenderLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderLength::Cycles(c) => write!(f, "{} cycles", c),
+            RenderLength::Seconds(s) => write!(f, "{}s", s),
+        }
+    }
+}
+
+/// One queued render
+#[derive(Debug, Clone)]
+pub struct RenderJob {
+    /// Program source (the editor buffer at submission time)
+    pub code: String,
+    pub length: RenderLength,
+    pub path: PathBuf,
+    pub sample_rate: f32,
+}
+
+/// Progress reported by the worker thread
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderUpdate {
+    Started {
+        path: PathBuf,
+        seconds: f64,
+    },
+    Progress {
+        path: PathBuf,
+        percent: u8,
+    },
+    Finished {
+        path: PathBuf,
+        seconds: f64,
+        peak: f32,
+    },
+    Failed {
+        path: PathBuf,
+        error: String,
+    },
+}
+
+/// Render `job` to its WAV file, reporting `Started` and then `Progress` at
+/// every 25%. Returns (duration in seconds, peak level)
+pub fn render_job(
+    job: &RenderJob,
+    on_update: impl FnMut(RenderUpdate),
+) -> Result<(f64, f32), String> {
+    let statements = parse_job_code(&job.code)?;
+    render_statements(
+        statements,
+        job.length,
+        &job.path,
+        job.sample_rate,
+        on_update,
+    )
+}
+
+/// Parse a whole program, failing on anything left unparsed
+pub fn parse_job_code(code: &str) -> Result<Vec<Statement>, String> {
+    let (rest, statements) = parse_program(code).map_err(|e| format!("Parse error: {:?}", e))?;
+    if !rest.trim().is_empty() {
+        let near = rest.trim().lines().next().unwrap_or_default();
+        return Err(format!("Parse error near: {}", near));
+    }
+    Ok(statements)
+}
+
+/// Render an already parsed program to `path`, as [`render_job`] does
+pub fn render_statements(
+    statements: Vec<Statement>,
+    length: RenderLength,
+    path: &Path,
+    sample_rate: f32,
+    mut on_update: impl FnMut(RenderUpdate),
+) -> Result<(f64, f32), String> {
+    let mut graph = compile_program(statements, sample_rate, None)?;
+    graph.preload_samples();
+
+    let seconds = length.seconds(graph.get_cps());
+    let total_frames = (seconds * sample_rate as f64).round() as usize;
+    on_update(RenderUpdate::Started {
+        path: path.to_path_buf(),
+        seconds,
+    });
+
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: sample_rate as u32,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(path, spec)
+        .map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+
+    // process_buffer renders stereo-interleaved, like the live ring buffer
+    let mut buffer = vec![0.0f32; BLOCK_FRAMES * 2];
+    let mut rendered = 0;
+    let mut peak = 0.0f32;
+    let mut reported_quarter = 0;
+    while rendered < total_frames {
+        let frames = BLOCK_FRAMES.min(total_frames - rendered);
+        let chunk = &mut buffer[..frames * 2];
+        graph.process_buffer(chunk);
+        for &sample in chunk.iter() {
+            peak = peak.max(sample.abs());
+            writer
+                .write_sample(sample)
+                .map_err(|e| format!("Failed to write sample: {}", e))?;
+        }
+        rendered += frames;
+
+        let quarter = rendered * 4 / total_frames;
+        if quarter > reported_quarter && quarter < 4 {
+            reported_quarter = quarter;
+            on_update(RenderUpdate::Progress {
+                path: path.to_path_buf(),
+                percent: (quarter * 25) as u8,
+            });
+        }
+    }
+
+    writer
+        .finalize()
+        .map_err(|e| format!("Failed to finalize WAV: {}", e))?;
+    Ok((seconds, peak))
+}
+
+/// Renders jobs one after another on a worker thread (started on first use)
+pub struct RenderQueue {
+    jobs: Option<Sender<RenderJob>>,
+    updates_tx: Sender<RenderUpdate>,
+    updates: Receiver<RenderUpdate>,
+    /// Submitted jobs that haven't finished or failed yet
+    pending: usize,
+}
+
+impl Default for RenderQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RenderQueue {
+    pub fn new() -> Self {
+        let (updates_tx, updates) = channel();
+        Self {
+            jobs: None,
+            updates_tx,
+            updates,
+            pending: 0,
+        }
+    }
+
+    /// Queue a job; returns how many jobs are ahead of it
+    pub fn submit(&mut self, job: RenderJob) -> Result<usize, String> {
+        let ahead = self.pending;
+        if self.jobs.is_none() {
+            self.jobs = Some(Self::spawn_worker(self.updates_tx.clone())?);
+        }
+        let jobs = self.jobs.as_ref().expect("render worker started above");
+        jobs.send(job)
+            .map_err(|_| "Render worker stopped".to_string())?;
+        self.pending += 1;
+        Ok(ahead)
+    }
+
+    /// Updates received since the last poll (never blocks)
+    pub fn poll(&mut self) -> Vec<RenderUpdate> {
+        let updates: Vec<RenderUpdate> = self.updates.try_iter().collect();
+        for update in &updates {
+            if matches!(
+                update,
+                RenderUpdate::Finished { .. } | RenderUpdate::Failed { .. }
+            ) {
+                self.pending = self.pending.saturating_sub(1);
+            }
+        }
+        updates
+    }
+
+    /// Jobs queued or in progress
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    fn spawn_worker(updates: Sender<RenderUpdate>) -> Result<Sender<RenderJob>, String> {
+        let (jobs_tx, jobs_rx) = channel::<RenderJob>();
+        thread::Builder::new()
+            .name("phonon-render".to_string())
+            .spawn(move || {
+                crate::denormals::enable_flush_to_zero();
+                for job in jobs_rx {
+                    let result = render_job(&job, |update| {
+                        let _ = updates.send(update);
+                    });
+                    let update = match result {
+                        Ok((seconds, peak)) => RenderUpdate::Finished {
+                            path: job.path,
+                            seconds,
+                            peak,
+                        },
+                        Err(error) => RenderUpdate::Failed {
+                            path: job.path,
+                            error,
+                        },
+                    };
+                    if updates.send(update).is_err() {
+                        return; // Editor gone
+                    }
+                }
+            })
+            .map_err(|e| format!("Failed to start render thread: {}", e))?;
+        Ok(jobs_tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_parse_render_length() {
+        assert_eq!(RenderLength::parse("32c"), Ok(RenderLength::Cycles(32.0)));
+        assert_eq!(RenderLength::parse("8"), Ok(RenderLength::Cycles(8.0)));
+        assert_eq!(RenderLength::parse("2.5s"), Ok(RenderLength::Seconds(2.5)));
+        assert!(RenderLength::parse("0c").is_err());
+        assert!(RenderLength::parse("abc").is_err());
+        assert_eq!(RenderLength::Cycles(4.0).seconds(2.0), 2.0);
+    }
+
+    #[test]
+    fn test_queue_renders_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sketch.wav");
+        let mut queue = RenderQueue::new();
+        let ahead = queue
+            .submit(RenderJob {
+                code: "tempo: 2.0\nout $ sine 440 * 0.5".to_string(),
+                length: RenderLength::Cycles(4.0),
+                path: path.clone(),
+                sample_rate: 44100.0,
+            })
+            .unwrap();
+        assert_eq!(ahead, 0);
+        assert_eq!(queue.pending(), 1);
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let mut updates = Vec::new();
+        while queue.pending() > 0 {
+            assert!(Instant::now() < deadline, "render did not finish");
+            updates.extend(queue.poll());
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(matches!(updates[0], RenderUpdate::Started { seconds, .. } if seconds == 2.0));
+        assert!(updates
+            .iter()
+            .any(|u| matches!(u, RenderUpdate::Progress { percent: 50, .. })));
+        match updates.last().unwrap() {
+            RenderUpdate::Finished { peak, .. } => assert!((peak - 0.5).abs() < 0.05),
+            other => panic!("expected Finished, got {:?}", other),
+        }
+
+        // 4 cycles at 2 cps = 2 seconds of stereo audio
+        let reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.duration(), 88200);
+    }
+
+    #[test]
+    fn test_failed_render_reports_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let job = RenderJob {
+            code: "out $ nonexistent_function 440".to_string(),
+            length: RenderLength::Seconds(1.0),
+            path: dir.path().join("broken.wav"),
+            sample_rate: 44100.0,
+        };
+        assert!(render_job(&job, |_| {}).is_err());
+    }
+}
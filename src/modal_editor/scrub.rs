@@ -0,0 +1,91 @@
+//! Number scrubbing: nudge the numeric literal under the cursor up or down
+//!
+//! The step follows the literal's precision, so `440` moves by 1 and `0.25`
+//! by 0.01; fine and coarse scrubbing divide or multiply the step by 10.
+
+/// Byte span of the number at `cursor` (on it or just after it), with its
+/// minus sign. Digits that end an identifier (`midi1`, `o2`) don't count.
+pub fn number_at(content: &str, cursor: usize) -> Option<(usize, usize)> {
+    let bytes = content.as_bytes();
+    let is_num = |b: u8| b.is_ascii_digit() || b == b'.';
+    let mut start = cursor;
+    while start > 0 && is_num(bytes[start - 1]) {
+        start -= 1;
+    }
+    let mut end = cursor;
+    while end < bytes.len() && is_num(bytes[end]) {
+        end += 1;
+    }
+    let literal = &content[start..end];
+    if literal.parse::<f64>().is_err() || !literal.bytes().any(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    match start.checked_sub(1).map(|i| bytes[i]) {
+        Some(b) if b.is_ascii_alphanumeric() || b == b'_' => None,
+        Some(b'-') => {
+            let before = start.checked_sub(2).map(|i| bytes[i]);
+            let is_sign = before.map_or(true, |b| {
+                b.is_ascii_whitespace() || matches!(b, b'(' | b'[' | b',' | b'"' | b'<' | b'*')
+            });
+            Some((if is_sign { start - 1 } else { start }, end))
+        }
+        _ => Some((start, end)),
+    }
+}
+
+/// Step the number at `cursor` by `steps` units of its last decimal place,
+/// times `scale` (0.1 for fine, 10 for coarse). Returns the new content and
+/// the span of the new literal.
+pub fn scrub(
+    content: &str,
+    cursor: usize,
+    steps: f64,
+    scale: f64,
+) -> Option<(String, (usize, usize))> {
+    let (start, end) = number_at(content, cursor)?;
+    let literal = &content[start..end];
+    let value: f64 = literal.parse().ok()?;
+    let decimals = literal.split_once('.').map_or(0, |(_, frac)| frac.len());
+    let decimals = if scale < 1.0 { decimals + 1 } else { decimals };
+    let step = 10f64.powi(-(decimals as i32)) * scale.max(1.0);
+    let new_value = value + steps * step;
+    let mut number = format!("{:.*}", decimals, new_value);
+    if number.parse::<f64>() == Ok(0.0) {
+        number = format!("{:.*}", decimals, 0.0);
+    }
+    Some((
+        format!("{}{}{}", &content[..start], number, &content[end..]),
+        (start, start + number.len()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_at() {
+        let code = "~bass $ saw 55 # lpf (800 * -6dB) 0.7 # gain ~midi1";
+        let at = |s: &str| number_at(code, code.find(s).unwrap() + 1).map(|(a, b)| &code[a..b]);
+        assert_eq!(at("55"), Some("55"));
+        assert_eq!(at("800"), Some("800"));
+        assert_eq!(at("6dB"), Some("-6"));
+        assert_eq!(at("0.7"), Some("0.7"));
+        assert_eq!(at("1"), None); // ~midi1
+        assert_eq!(number_at(code, code.find("saw").unwrap()), None);
+        // Just after the literal counts too
+        assert_eq!(number_at("lpf 800", 7), Some((4, 7)));
+    }
+
+    #[test]
+    fn test_scrub_steps_by_precision() {
+        let bump = |code: &str, steps, scale| scrub(code, code.len(), steps, scale).unwrap().0;
+        assert_eq!(bump("sine 440", 1.0, 1.0), "sine 441");
+        assert_eq!(bump("sine 440", -1.0, 10.0), "sine 430");
+        assert_eq!(bump("sine 440", 1.0, 0.1), "sine 440.1");
+        assert_eq!(bump("gain 0.25", 1.0, 1.0), "gain 0.26");
+        assert_eq!(bump("gain 0.5", -1.0, 10.0), "gain -0.5");
+        assert_eq!(bump("x -0.1", 1.0, 1.0), "x 0.0");
+        assert_eq!(bump("lpf 9", 1.0, 1.0), "lpf 10");
+    }
+}
@@ -0,0 +1,319 @@
+//! Snippet library: reusable patterns and effect chains for the editor
+//!
+//! Snippets are plain `.phonon` files in `~/.phonon/snippets/`. The first
+//! `--` comment line is the description, the rest is inserted at the cursor.
+//! The built-in library is written there on first use (existing files are
+//! never overwritten), so it can be edited, deleted or extended like any
+//! user snippet. Browse and insert from the command console (Alt+/):
+//!
+//! ```text
+//! /snippets [query]     list snippets, fuzzy-filtered
+//! /snippet <query>      insert the best match at the cursor
+//! /snippet-save <name>  save the current block as a snippet
+//! ```
+
+use super::completion::docstring_search;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Built-in snippets: (name, description, body)
+pub const BUILTIN_SNIPPETS: &[(&str, &str, &str)] = &[
+    (
+        "acid-bass",
+        "Resonant 303-style acid line with a slow filter sweep",
+        "~acid_lfo $ sine 0.25\n\
+         ~acid $ saw \"55 55 110 55 82.5 55 110 73.4\" # rlpf (~acid_lfo * 900 + 1200) 9 * 0.3\n",
+    ),
+    (
+        "dub-delay-chain",
+        "Offbeat stabs into a long feedback delay and reverb",
+        "~dub $ saw \"~ 220 ~ 330\" # adsr 0.01 0.1 0.5 0.2 # lpf 1800 0.6 # delay 0.375 0.6 0.5 # reverb 0.6 0.4 * 0.25\n",
+    ),
+    (
+        "breakbeat-chops",
+        "Beat-locked breakbeat: slices of a break reordered on the grid",
+        "~break $ s \"breaks125\" $ splice 8 \"0 1 2 3 4 5 6 7\"\n\
+         ~chops $ s \"breaks125\" $ splice 8 \"0 0 3 2 4 5 7 6\"\n",
+    ),
+    (
+        "four-on-the-floor",
+        "House drums: kick on every beat, offbeat hats, claps on 2 and 4",
+        "~kick $ s \"bd*4\"\n\
+         ~hats $ s \"~ hh ~ hh ~ hh ~ hh\"\n\
+         ~clap $ s \"~ cp ~ cp\"\n\
+         ~drums $ ~kick + ~hats * 0.5 + ~clap * 0.7\n",
+    ),
+    (
+        "warm-pad",
+        "Detuned saw pad with chorus and a big reverb",
+        "~pad $ saw \"110 165\" # lpf 1200 0.3 # chorus 0.5 0.3 # reverb 0.6 0.5 * 0.2\n",
+    ),
+    (
+        "sub-bass",
+        "Round sine sub following a simple root movement",
+        "~sub $ sine \"55 55 41.2 49\" * 0.4\n",
+    ),
+];
+
+/// One snippet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snippet {
+    pub name: String,
+    pub description: String,
+    pub body: String,
+}
+
+impl Snippet {
+    /// Parse a snippet file: an optional leading `-- description` line, then the body
+    pub fn parse(name: &str, text: &str) -> Self {
+        let mut lines = text.lines().peekable();
+        let description = match lines.peek() {
+            Some(line) if line.trim_start().starts_with("--") => {
+                let line = lines.next().unwrap_or_default();
+                line.trim_start().trim_start_matches('-').trim().to_string()
+            }
+            _ => String::new(),
+        };
+        let body: Vec<&str> = lines.skip_while(|l| l.trim().is_empty()).collect();
+        Self {
+            name: name.to_string(),
+            description,
+            body: format!("{}\n", body.join("\n").trim_end()),
+        }
+    }
+
+    /// File contents for this snippet
+    pub fn to_file(&self) -> String {
+        if self.description.is_empty() {
+            self.body.clone()
+        } else {
+            format!("-- {}\n{}", self.description, self.body)
+        }
+    }
+}
+
+/// Default snippet directory: `~/.phonon/snippets`
+pub fn default_snippet_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".phonon").join("snippets"))
+}
+
+/// Snippet names are used as file names
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Loaded snippets plus the directory they live in
+#[derive(Debug, Clone, Default)]
+pub struct SnippetLibrary {
+    dir: Option<PathBuf>,
+    snippets: Vec<Snippet>,
+}
+
+impl SnippetLibrary {
+    /// Library from `~/.phonon/snippets` (built-ins only if there is no home dir)
+    pub fn load() -> Self {
+        match default_snippet_dir() {
+            Some(dir) => Self::load_from(&dir),
+            None => Self::builtin(),
+        }
+    }
+
+    /// Built-in snippets, not backed by a directory
+    pub fn builtin() -> Self {
+        Self {
+            dir: None,
+            snippets: BUILTIN_SNIPPETS
+                .iter()
+                .map(|(name, description, body)| Snippet {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    body: body.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Library from `dir`, installing the built-ins if the directory is new.
+    /// Falls back to the built-ins if the directory can't be created or read.
+    pub fn load_from(dir: &Path) -> Self {
+        if !dir.exists() && Self::install_builtins(dir).is_err() {
+            return Self::builtin();
+        }
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Self::builtin();
+        };
+
+        let mut snippets: Vec<Snippet> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                matches!(
+                    path.extension().and_then(|e| e.to_str()),
+                    Some("phonon") | Some("ph")
+                )
+            })
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_str()?.to_string();
+                let text = fs::read_to_string(&path).ok()?;
+                Some(Snippet::parse(&name, &text))
+            })
+            .collect();
+        snippets.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            dir: Some(dir.to_path_buf()),
+            snippets,
+        }
+    }
+
+    /// Write the built-in snippets into `dir` (existing files are kept)
+    pub fn install_builtins(dir: &Path) -> std::io::Result<usize> {
+        fs::create_dir_all(dir)?;
+        let mut written = 0;
+        for snippet in Self::builtin().snippets {
+            let path = dir.join(format!("{}.phonon", snippet.name));
+            if !path.exists() {
+                fs::write(&path, snippet.to_file())?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+
+    pub fn snippets(&self) -> &[Snippet] {
+        &self.snippets
+    }
+
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Snippets matching `query` (fuzzy over name, description and body), best first.
+    /// An empty query returns everything in name order.
+    pub fn search(&self, query: &str) -> Vec<&Snippet> {
+        let mut matches: Vec<(i32, &Snippet)> = self
+            .snippets
+            .iter()
+            .filter_map(|s| {
+                docstring_search(query, &s.name, Some(&s.description), Some(&s.body))
+                    .map(|m| (m.score, s))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        matches.into_iter().map(|(_, s)| s).collect()
+    }
+
+    /// Exact name match, otherwise the best fuzzy match
+    pub fn find(&self, query: &str) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|s| s.name == query)
+            .or_else(|| self.search(query).into_iter().next())
+    }
+
+    /// Save `body` as snippet `name` (replacing an existing one)
+    pub fn save(&mut self, name: &str, body: &str) -> Result<PathBuf, String> {
+        if !valid_name(name) {
+            return Err(format!(
+                "Invalid snippet name '{}' (use letters, digits, - and _)",
+                name
+            ));
+        }
+        if body.trim().is_empty() {
+            return Err("Nothing to save: the current block is empty".to_string());
+        }
+        let dir = self
+            .dir
+            .clone()
+            .ok_or_else(|| "No snippet directory (home directory not found)".to_string())?;
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+        let snippet = Snippet::parse(name, body);
+        let path = dir.join(format!("{}.phonon", name));
+        fs::write(&path, snippet.to_file())
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        self.snippets.retain(|s| s.name != name);
+        self.snippets.push(snippet);
+        self.snippets.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositional_compiler::compile_program;
+    use crate::compositional_parser::parse_program;
+
+    #[test]
+    fn test_builtin_snippets_compile() {
+        for (name, _, body) in BUILTIN_SNIPPETS {
+            let code = format!("tempo: 0.5\n{}", body);
+            let (rest, statements) = parse_program(&code).expect("snippet should parse");
+            assert!(rest.trim().is_empty(), "{}: unparsed {:?}", name, rest);
+            assert!(
+                compile_program(statements, 44100.0, None).is_ok(),
+                "{} should compile",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_install_and_load_user_snippets() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("snippets");
+
+        let library = SnippetLibrary::load_from(&dir);
+        assert_eq!(library.snippets().len(), BUILTIN_SNIPPETS.len());
+        assert!(dir.join("acid-bass.phonon").exists());
+
+        // User edits and additions are picked up; deleted built-ins stay deleted
+        fs::write(
+            dir.join("my-hats.phonon"),
+            "-- Busy hats\n~hats $ s \"hh*16\"\n",
+        )
+        .unwrap();
+        fs::remove_file(dir.join("sub-bass.phonon")).unwrap();
+        let library = SnippetLibrary::load_from(&dir);
+        let hats = library.find("my-hats").unwrap();
+        assert_eq!(hats.description, "Busy hats");
+        assert_eq!(hats.body, "~hats $ s \"hh*16\"\n");
+        assert!(library.snippets().iter().all(|s| s.name != "sub-bass"));
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        let library = SnippetLibrary::builtin();
+        assert_eq!(library.search("acid")[0].name, "acid-bass");
+        assert_eq!(library.search("dbdly")[0].name, "dub-delay-chain");
+        // Description matches count too
+        assert!(library
+            .search("house")
+            .iter()
+            .any(|s| s.name == "four-on-the-floor"));
+        assert_eq!(library.search("").len(), BUILTIN_SNIPPETS.len());
+        assert!(library.search("zzzzqq").is_empty());
+    }
+
+    #[test]
+    fn test_save_snippet() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut library = SnippetLibrary::load_from(tmp.path());
+        let path = library
+            .save("wobble", "~wob $ saw 55 # lpf 400 0.8")
+            .unwrap();
+        assert!(path.ends_with("wobble.phonon"));
+        assert_eq!(
+            library.find("wobble").unwrap().body,
+            "~wob $ saw 55 # lpf 400 0.8\n"
+        );
+        assert!(library.save("../escape", "x").is_err());
+        assert!(library.save("empty", "  \n").is_err());
+    }
+}
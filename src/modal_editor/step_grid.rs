@@ -0,0 +1,258 @@
+//! Step sequencer grid for a pattern string
+//!
+//! Alt+G on a line with a pattern string opens it as a grid: one row per
+//! sound or note, one column per step. hjkl or the arrows move, Space
+//! toggles a cell, Enter writes the grid back as mini-notation and Esc
+//! leaves the code alone.
+//!
+//! Only flat patterns fit a grid: steps that are a single word, `~`, a
+//! chord such as `[bd,hh]`, or a word repeated with `*n` (which opens as
+//! n steps). Anything else (subsequences, alternations, euclids) stays
+//! in text.
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Grid state for one pattern string
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepGrid {
+    /// Byte range of the string's contents (between the quotes) in the buffer
+    pub span: (usize, usize),
+    /// Row labels, in order of first appearance
+    rows: Vec<String>,
+    /// `cells[row][step]`
+    cells: Vec<Vec<bool>>,
+    /// Cursor row and step
+    row: usize,
+    step: usize,
+}
+
+impl StepGrid {
+    /// Open the pattern string on the line around `cursor`: the string the
+    /// cursor is in, or else the first one on the line
+    pub fn open(content: &str, cursor: usize) -> Result<Self, String> {
+        let line_start = content[..cursor].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = content[cursor..]
+            .find('\n')
+            .map(|i| cursor + i)
+            .unwrap_or(content.len());
+        let line = &content[line_start..line_end];
+        let code = line.find("--").map(|i| &line[..i]).unwrap_or(line);
+
+        let quotes: Vec<usize> = code.match_indices('"').map(|(i, _)| i).collect();
+        let strings: Vec<(usize, usize)> = quotes
+            .chunks_exact(2)
+            .map(|pair| (line_start + pair[0] + 1, line_start + pair[1]))
+            .collect();
+        let span = strings
+            .iter()
+            .find(|(start, end)| (*start..=*end).contains(&cursor))
+            .or_else(|| strings.first())
+            .copied()
+            .ok_or("No pattern string on this line")?;
+
+        let mut grid = Self::parse(&content[span.0..span.1])?;
+        grid.span = span;
+        Ok(grid)
+    }
+
+    /// Lay a flat mini-notation pattern out as a grid
+    pub fn parse(pattern: &str) -> Result<Self, String> {
+        let mut steps: Vec<Vec<String>> = Vec::new();
+        for token in pattern.split_whitespace() {
+            let (word, repeat) = match token.split_once('*') {
+                Some((word, n)) => match n.parse::<usize>() {
+                    Ok(n @ 1..=64) => (word, n),
+                    _ => return Err(format!("'{}' doesn't fit the grid", token)),
+                },
+                None => (token, 1),
+            };
+            let sounds: Vec<String> = if word == "~" {
+                Vec::new()
+            } else if let Some(chord) = word.strip_prefix('[').and_then(|w| w.strip_suffix(']')) {
+                chord.split(',').map(str::to_string).collect()
+            } else {
+                vec![word.to_string()]
+            };
+            if sounds.iter().any(|s| !is_plain_word(s)) {
+                return Err(format!("'{}' doesn't fit the grid", token));
+            }
+            for _ in 0..repeat {
+                steps.push(sounds.clone());
+            }
+        }
+        if steps.is_empty() {
+            return Err("Empty pattern".to_string());
+        }
+
+        let mut rows: Vec<String> = Vec::new();
+        for sound in steps.iter().flatten() {
+            if !rows.contains(sound) {
+                rows.push(sound.clone());
+            }
+        }
+        if rows.is_empty() {
+            return Err("No sounds to lay out (the pattern is all rests)".to_string());
+        }
+        let cells = rows
+            .iter()
+            .map(|row| steps.iter().map(|step| step.contains(row)).collect())
+            .collect();
+        Ok(Self {
+            span: (0, 0),
+            rows,
+            cells,
+            row: 0,
+            step: 0,
+        })
+    }
+
+    /// Move the cursor by `rows` and `steps`, stopping at the edges
+    pub fn move_cursor(&mut self, rows: isize, steps: isize) {
+        self.row = self
+            .row
+            .saturating_add_signed(rows)
+            .min(self.rows.len() - 1);
+        self.step = self
+            .step
+            .saturating_add_signed(steps)
+            .min(self.step_count() - 1);
+    }
+
+    /// Toggle the cell under the cursor
+    pub fn toggle(&mut self) {
+        let cell = &mut self.cells[self.row][self.step];
+        *cell = !*cell;
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The grid as mini-notation: `~` for empty steps, `[a,b]` for steps
+    /// with more than one sound
+    pub fn to_mini_notation(&self) -> String {
+        (0..self.step_count())
+            .map(|step| {
+                let sounds: Vec<&str> = self
+                    .rows
+                    .iter()
+                    .zip(&self.cells)
+                    .filter(|(_, cells)| cells[step])
+                    .map(|(row, _)| row.as_str())
+                    .collect();
+                match sounds.len() {
+                    0 => "~".to_string(),
+                    1 => sounds[0].to_string(),
+                    _ => format!("[{}]", sounds.join(",")),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Draw the grid, with a gap every 4 steps
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let label_width = self.rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let mut lines: Vec<Line> = self
+            .rows
+            .iter()
+            .zip(&self.cells)
+            .enumerate()
+            .map(|(r, (label, cells))| {
+                let mut spans = vec![Span::styled(
+                    format!("{:>width$} ", label, width = label_width),
+                    Style::default().fg(Color::Cyan),
+                )];
+                for (s, on) in cells.iter().enumerate() {
+                    if s > 0 && s % 4 == 0 {
+                        spans.push(Span::raw(" "));
+                    }
+                    let mut style = if *on {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    };
+                    if (r, s) == (self.row, self.step) {
+                        style = style.bg(Color::Blue).add_modifier(Modifier::BOLD);
+                    }
+                    spans.push(Span::styled(if *on { "■ " } else { "· " }, style));
+                }
+                Line::from(spans)
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            self.to_mini_notation(),
+            Style::default().fg(Color::White),
+        )));
+        lines.push(Line::from(Span::styled(
+            "hjkl/arrows: move | Space: toggle | Enter: write back | Esc: cancel",
+            Style::default().fg(Color::DarkGray),
+        )));
+
+        let block = Block::default()
+            .title(format!("Step Grid ({} steps)", self.step_count()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+/// A sample or note name, optionally with a `:n` index
+fn is_plain_word(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ':' | '.' | '_' | '#' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_toggle() {
+        let mut grid = StepGrid::parse("bd ~ [sn,hh] hh*2").unwrap();
+        assert_eq!(grid.rows, vec!["bd", "sn", "hh"]);
+        assert_eq!(grid.step_count(), 5);
+        assert_eq!(grid.to_mini_notation(), "bd ~ [sn,hh] hh hh");
+
+        // Move the kick from the first step to the second, drop the last hat
+        grid.toggle();
+        grid.move_cursor(0, 1);
+        grid.toggle();
+        grid.move_cursor(5, 10);
+        assert_eq!((grid.row, grid.step), (2, 4));
+        grid.toggle();
+        assert_eq!(grid.to_mini_notation(), "~ bd [sn,hh] hh ~");
+    }
+
+    #[test]
+    fn test_open_finds_string_on_line() {
+        let code = "tempo: 0.5\n~drums $ s \"bd sn\" # gain \"1 0.8\"\nout $ ~drums";
+        let cursor = code.find("~drums $").unwrap();
+        let grid = StepGrid::open(code, cursor).unwrap();
+        assert_eq!(&code[grid.span.0..grid.span.1], "bd sn");
+
+        // Inside the second string, that one opens
+        let cursor = code.find("0.8").unwrap();
+        let grid = StepGrid::open(code, cursor).unwrap();
+        assert_eq!(&code[grid.span.0..grid.span.1], "1 0.8");
+
+        assert!(StepGrid::open(code, 0).is_err());
+        assert!(StepGrid::parse("bd [sn sn]").is_err());
+        assert!(StepGrid::parse("<bd sn>").is_err());
+        assert!(StepGrid::parse("bd(3,8)").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Following an external tempo (`:sync`): MIDI clock from another device, or
+//! beats tapped in with Alt+T
+//!
+//! Either one is a `TempoSource`; the editor steers playback after it with a
+//! `SyncFollower`, nudging the tempo to stay in phase.
+
+use crate::link_clock::TempoSource;
+use crate::midi_input::MidiClockIn;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// A longer gap between taps starts a new tempo
+const TAP_TIMEOUT_SECS: f64 = 2.0;
+
+/// Taps the tempo is averaged over
+const MAX_TAPS: usize = 8;
+
+/// What `:sync` follows, as typed
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncSource {
+    /// MIDI clock from the input whose name contains this (any when empty)
+    Midi(String),
+    Tap,
+}
+
+/// What playback is following
+pub enum TempoSync {
+    Midi(MidiClockIn),
+    Tap(TapTempo),
+}
+
+/// Tap tempo: each tap is a beat. The tempo is the average gap between the
+/// latest taps, and the beat falls on the last one.
+#[derive(Debug, Clone, Default)]
+pub struct TapTempo {
+    taps: VecDeque<Instant>,
+}
+
+impl TapTempo {
+    /// Tap a beat at `at`; returns how many taps the tempo now comes from
+    pub fn tap(&mut self, at: Instant) -> usize {
+        let in_time = self.taps.back().is_some_and(|last| {
+            at.saturating_duration_since(*last).as_secs_f64() <= TAP_TIMEOUT_SECS
+        });
+        if !in_time {
+            self.taps.clear();
+        }
+        self.taps.push_back(at);
+        if self.taps.len() > MAX_TAPS {
+            self.taps.pop_front();
+        }
+        self.taps.len()
+    }
+
+    /// Seconds a beat, once there are two taps
+    fn beat_secs(&self) -> Option<f64> {
+        let (first, last) = (self.taps.front()?, self.taps.back()?);
+        let secs = last.duration_since(*first).as_secs_f64() / (self.taps.len() - 1) as f64;
+        (secs > 0.0).then_some(secs)
+    }
+}
+
+impl TempoSource for TapTempo {
+    fn tempo_bpm(&self) -> f64 {
+        self.beat_secs().map_or(0.0, |secs| 60.0 / secs)
+    }
+
+    fn beat_at(&self, at: Instant) -> f64 {
+        match (self.taps.back(), self.beat_secs()) {
+            (Some(last), Some(secs)) => at.saturating_duration_since(*last).as_secs_f64() / secs,
+            _ => 0.0,
+        }
+    }
+
+    /// Taps say where the beat is, not the bar
+    fn quantum(&self) -> f64 {
+        1.0
+    }
+
+    fn is_playing(&self) -> bool {
+        self.beat_secs().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_taps_set_tempo_and_beat() {
+        let base = Instant::now();
+        let at = |ms: u64| base + Duration::from_millis(ms);
+        let mut taps = TapTempo::default();
+        assert_eq!(taps.tap(at(0)), 1);
+        assert!(!taps.is_playing());
+
+        // Uneven taps average out: 1.5 s over three beats is 120 BPM
+        taps.tap(at(480));
+        taps.tap(at(1010));
+        assert_eq!(taps.tap(at(1500)), 4);
+        assert!((taps.tempo_bpm() - 120.0).abs() < 1e-9);
+        assert!((taps.beat_at(at(1500))).abs() < 1e-9);
+        assert!((taps.beat_at(at(1750)) - 0.5).abs() < 1e-9);
+
+        // After a pause the next taps start a new tempo
+        assert_eq!(taps.tap(at(5000)), 1);
+        taps.tap(at(5400));
+        assert!((taps.tempo_bpm() - 150.0).abs() < 1e-9);
+    }
+}
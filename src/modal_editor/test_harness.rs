@@ -99,6 +99,148 @@ impl EditorTestHarness {
         self.send_key_with_modifiers(KeyCode::Char(' '), KeyModifiers::CONTROL)
     }
 
+    /// Open the command console (Alt+/), type `command` and run it
+    pub fn console_command(&mut self, command: &str) -> &mut Self {
+        if !self.editor.command_console.is_visible() {
+            self.send_key_with_modifiers(KeyCode::Char('/'), KeyModifiers::ALT);
+        }
+        self.type_text(command);
+        self.enter()
+    }
+
+    /// Wait until all background `:render` jobs are done (progress goes to the
+    /// console pane, as in the run loop). Returns false on timeout.
+    pub fn wait_for_renders(&mut self, timeout: std::time::Duration) -> bool {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            self.editor.poll_render_queue();
+            if self.editor.render_queue.pending() == 0 {
+                return true;
+            }
+            if std::time::Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+    }
+
+    /// Messages in the editor's console pane
+    pub fn console_messages(&self) -> &[String] {
+        &self.editor.console_messages
+    }
+
+    /// Text of the inline help pane for the call under the cursor, if shown
+    pub fn inline_help(&self) -> Option<Vec<String>> {
+        if !self.editor.show_inline_help || self.editor.completion_state.is_visible() {
+            return None;
+        }
+        let site = self.editor.inline_help_call()?;
+        Some(
+            completion::inline_help_lines(&site, 200)
+                .into_iter()
+                .map(|line| line.text)
+                .collect(),
+        )
+    }
+
+    /// Output pane of the command console (Alt+/)
+    pub fn command_output(&self) -> &[String] {
+        self.editor.command_console.output()
+    }
+
+    /// Use a snippet library from `dir` instead of `~/.phonon/snippets`
+    pub fn use_snippet_dir(&mut self, dir: &std::path::Path) -> &mut Self {
+        self.editor
+            .command_console
+            .set_snippet_library(snippets::SnippetLibrary::load_from(dir));
+        self
+    }
+
+    /// Apply settings as if read from `~/.phonon/config.toml`
+    pub fn apply_config(&mut self, toml: &str) -> Result<&mut Self, String> {
+        let config = config::EditorConfig::parse(toml)?;
+        self.editor.apply_config(&config);
+        Ok(self)
+    }
+
+    /// Whether the vim keymap is in normal mode
+    pub fn is_vim_normal(&self) -> bool {
+        self.editor.vim_normal
+    }
+
+    /// Key hint line shown under the status
+    pub fn key_help(&self) -> String {
+        self.editor.key_help_line()
+    }
+
+    /// Draw one frame into an off-screen terminal of the given size, as the
+    /// UI loop does before reading each event
+    pub fn draw(&mut self, width: u16, height: u16) -> &mut Self {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = Terminal::new(backend).expect("test terminal");
+        terminal
+            .draw(|f| self.editor.ui(f))
+            .expect("draw editor frame");
+        self
+    }
+
+    /// Editor pane (borders included) from the last `draw`
+    pub fn editor_area(&self) -> Rect {
+        self.editor.editor_area
+    }
+
+    /// First visible line of the editor pane
+    pub fn scroll_offset(&self) -> u16 {
+        self.editor.scroll_offset
+    }
+
+    /// Send a mouse event at terminal cell (column, row)
+    pub fn mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) -> &mut Self {
+        self.editor.handle_mouse_event(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        });
+        self
+    }
+
+    /// Whether a second pane is open beside the focused one
+    pub fn is_split(&self) -> bool {
+        self.editor.split.is_some()
+    }
+
+    /// File of the focused pane
+    pub fn file_path(&self) -> Option<&std::path::Path> {
+        self.editor.file_path.as_deref()
+    }
+
+    /// Lines in the event log pane, after picking up pending events
+    pub fn event_log(&mut self) -> Vec<String> {
+        self.editor.event_log.drain();
+        self.editor.event_log.lines().iter().cloned().collect()
+    }
+
+    /// Status line message
+    pub fn status_message(&self) -> &str {
+        &self.editor.status_message
+    }
+
+    /// Error shown in the status area, if any
+    pub fn error_message(&self) -> Option<&str> {
+        self.editor.error_message.as_deref()
+    }
+
+    /// Level or clip indicator shown after the definition of `bus`, as of the
+    /// audio rendered so far
+    pub fn bus_indicator(&mut self, bus: &str) -> Option<String> {
+        self.editor.poll_bus_meters();
+        self.editor
+            .bus_levels
+            .indicator(bus, std::time::Instant::now())
+            .map(|(text, _)| text)
+    }
+
     /// Get the current line content
     pub fn current_line(&self) -> &str {
         let lines: Vec<&str> = self.editor.content.lines().collect();
@@ -119,6 +261,24 @@ impl EditorTestHarness {
         self.editor.cursor_pos
     }
 
+    /// Move the cursor to byte offset `pos` (drops any selection)
+    pub fn set_cursor(&mut self, pos: usize) -> &mut Self {
+        self.editor.cursor_pos = pos.min(self.editor.content.len());
+        self.editor.selection_anchor = None;
+        self
+    }
+
+    /// Currently selected text, if any
+    pub fn selected_text(&self) -> Option<&str> {
+        let (start, end) = self.editor.selection()?;
+        Some(&self.editor.content[start..end])
+    }
+
+    /// Contents of the kill buffer (Ctrl+K / Ctrl+W / Alt+W)
+    pub fn kill_buffer(&self) -> &str {
+        &self.editor.kill_buffer
+    }
+
     /// Check if completion dialog is shown
     pub fn is_completion_shown(&self) -> bool {
         self.editor.completion_state.is_visible()
@@ -414,7 +574,14 @@ impl EditorTestHarness {
             .ok_or("No render side (not headless)")?;
         let mut rl = rl_cell.borrow_mut();
         rl.sync();
-        let graph = match rl.cur.as_mut() {
+        let LocalRender {
+            cur,
+            rsw,
+            watchdog,
+            audition_mixer,
+            ..
+        } = &mut *rl;
+        let graph = match cur.as_mut() {
             Some(g) => g,
             None => return Err("No graph loaded".to_string()),
         };
@@ -423,6 +590,7 @@ impl EditorTestHarness {
         // swap, so a changed heap address means "the graph was swapped".
         let cur_addr = &**graph as *const UnifiedSignalGraph as usize;
         let is_new_graph = self.prev_graph_addr != Some(cur_addr);
+        let seek = rsw.take_seek();
 
         // Seed or rebase the clock for this graph once (matches the synth loop's
         // per-swap handling: first graph seeds the clock; a swapped-in graph is
@@ -438,6 +606,9 @@ impl EditorTestHarness {
             }
             Some(c) => {
                 c.set_cps(graph.get_cps());
+                if let Some(cycle) = seek {
+                    c.set_position(cycle);
+                }
                 if is_new_graph {
                     graph.set_cycle_position(c.position());
                 }
@@ -449,14 +620,35 @@ impl EditorTestHarness {
         let mut out = Vec::with_capacity(num_chunks * frames);
         for _ in 0..num_chunks {
             buffer.iter_mut().for_each(|s| *s = 0.0);
+            // Stopped: silence, with the clock and graph held, as the synth loop
+            if rsw.transport().is_held() {
+                audition_mixer.mix_into(&mut buffer);
+                out.extend(buffer.iter().step_by(2));
+                continue;
+            }
             let c = self.live_clock.as_mut().unwrap();
             c.set_cps(graph.get_cps());
             let (start_cycle, increment, cps) = c.advance_buffer(frames);
-            graph.process_buffer_at(&mut buffer, start_cycle, increment, cps);
+            // Guarded like the synth thread: a crashed graph is swapped for silence
+            if !watchdog.run_block(&mut buffer, |buf| {
+                graph.process_buffer_at(buf, start_cycle, increment, cps)
+            }) {
+                let mut silent = UnifiedSignalGraph::new(graph.sample_rate());
+                silent.set_cps(cps);
+                rsw.replace(graph, Box::new(silent));
+            }
+            rsw.finish_block(&mut buffer, frames);
+            audition_mixer.mix_into(&mut buffer);
             for i in 0..frames {
                 out.push(buffer[i * 2]);
             }
         }
+        // Publish the live cycle position, as the synth thread does
+        if let Some(c) = self.live_clock.as_ref() {
+            self.editor
+                .current_cycle_bits
+                .store(c.position().to_bits(), Ordering::Relaxed);
+        }
         self.prev_graph_addr = Some(cur_addr);
         Ok(out)
     }
@@ -0,0 +1,306 @@
+//! User DSP nodes: register an [`AudioNode`] under a DSL name
+//!
+//! A [`NodeFactory`] describes its node (name, whether it processes an input,
+//! parameters with defaults) and builds instances of it. Once registered, the
+//! compiler treats the name like a built-in:
+//!
+//! ```ignore
+//! phonon::node_factory::register(Arc::new(WavefolderFactory))?;
+//! // ~lead $ saw 110 # wavefold 3 :symmetry 0.2
+//! ```
+//!
+//! Positional arguments fill parameters in order, `:name value` sets one by
+//! name, and missing ones take their default. Any parameter can be a pattern
+//! or another signal. Built-in functions keep their names: a registered node
+//! is only found when nothing built in matches.
+//!
+//! With the `dynamic-nodes` feature, [`load_library`] registers the nodes of a
+//! shared library exporting `phonon_nodes`.
+
+use crate::audio_node::{AudioNode, NodeId, ProcessContext};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// One parameter of a registered node
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamSpec {
+    pub name: String,
+    pub default: f32,
+    pub description: String,
+}
+
+/// What a registered node is called and how it's called
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSpec {
+    /// DSL function name
+    pub name: String,
+    pub description: String,
+    /// Whether the node processes an input signal (`saw 110 # wavefold 3`) or
+    /// generates one (`dust 20`)
+    pub takes_input: bool,
+    /// Parameters in positional order
+    pub params: Vec<ParamSpec>,
+}
+
+impl NodeSpec {
+    /// A node that generates a signal
+    pub fn source(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            takes_input: false,
+            params: Vec::new(),
+        }
+    }
+
+    /// A node that processes an input signal
+    pub fn effect(name: &str, description: &str) -> Self {
+        Self {
+            takes_input: true,
+            ..Self::source(name, description)
+        }
+    }
+
+    /// Add a parameter
+    pub fn param(mut self, name: &str, default: f32, description: &str) -> Self {
+        self.params.push(ParamSpec {
+            name: name.to_string(),
+            default,
+            description: description.to_string(),
+        });
+        self
+    }
+
+    /// Number of inputs the node is built with: the processed signal (if
+    /// any), then one per parameter
+    pub fn input_count(&self) -> usize {
+        self.takes_input as usize + self.params.len()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let is_identifier = |name: &str| {
+            name.starts_with(|c: char| c.is_ascii_alphabetic())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        if !is_identifier(&self.name) {
+            return Err(format!("'{}' is not a valid function name", self.name));
+        }
+        for (i, param) in self.params.iter().enumerate() {
+            if !is_identifier(&param.name) {
+                return Err(format!(
+                    "{}: '{}' is not a valid parameter name",
+                    self.name, param.name
+                ));
+            }
+            if self.params[..i].iter().any(|p| p.name == param.name) {
+                return Err(format!(
+                    "{}: parameter '{}' is declared twice",
+                    self.name, param.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Builds the [`AudioNode`]s of one registered DSL function
+pub trait NodeFactory: Send + Sync {
+    /// Name and parameters; read once, at registration
+    fn spec(&self) -> NodeSpec;
+
+    /// A fresh node. `inputs` are the ids to report from
+    /// [`AudioNode::input_nodes`], in spec order (the processed signal first
+    /// for effects), and `process_block` receives the input buffers in that
+    /// order.
+    fn create(&self, inputs: &[NodeId], sample_rate: f32) -> Box<dyn AudioNode>;
+}
+
+/// A registered factory and its spec
+#[derive(Clone)]
+pub struct RegisteredNode {
+    pub spec: NodeSpec,
+    pub factory: Arc<dyn NodeFactory>,
+}
+
+fn registry() -> &'static RwLock<HashMap<String, RegisteredNode>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, RegisteredNode>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Make a factory's node callable from the DSL. Fails if the spec is
+/// malformed or the name is already registered.
+pub fn register(factory: Arc<dyn NodeFactory>) -> Result<(), String> {
+    let spec = factory.spec();
+    spec.validate()?;
+    let mut nodes = registry()
+        .write()
+        .map_err(|_| "Node registry is poisoned".to_string())?;
+    if nodes.contains_key(&spec.name) {
+        return Err(format!(
+            "A node named '{}' is already registered",
+            spec.name
+        ));
+    }
+    nodes.insert(spec.name.clone(), RegisteredNode { spec, factory });
+    Ok(())
+}
+
+/// Remove a registered node; graphs already compiled keep their instances.
+/// Returns whether it was registered.
+pub fn unregister(name: &str) -> bool {
+    registry()
+        .write()
+        .map(|mut nodes| nodes.remove(name).is_some())
+        .unwrap_or(false)
+}
+
+/// The registered node called `name`
+pub fn lookup(name: &str) -> Option<RegisteredNode> {
+    registry().read().ok()?.get(name).cloned()
+}
+
+/// Specs of every registered node, by name
+pub fn registered() -> Vec<NodeSpec> {
+    let mut specs: Vec<NodeSpec> = registry()
+        .read()
+        .map(|nodes| nodes.values().map(|node| node.spec.clone()).collect())
+        .unwrap_or_default();
+    specs.sort_by(|a, b| a.name.cmp(&b.name));
+    specs
+}
+
+/// Signature of the `phonon_nodes` symbol a node library exports:
+///
+/// ```ignore
+/// #[no_mangle]
+/// pub fn phonon_nodes() -> Vec<Arc<dyn NodeFactory>> {
+///     vec![Arc::new(WavefolderFactory)]
+/// }
+/// ```
+///
+/// Trait objects cross the boundary as-is, so the library must be built with
+/// the same compiler and phonon version as the host.
+#[cfg(feature = "dynamic-nodes")]
+pub type NodeLibraryEntry = fn() -> Vec<Arc<dyn NodeFactory>>;
+
+/// Load a shared library and register the nodes it exports. Returns their
+/// names. The library stays loaded for the life of the process, since its
+/// nodes may be running in a graph.
+#[cfg(feature = "dynamic-nodes")]
+pub fn load_library(path: &std::path::Path) -> Result<Vec<String>, String> {
+    static LIBRARIES: std::sync::Mutex<Vec<libloading::Library>> =
+        std::sync::Mutex::new(Vec::new());
+
+    // SAFETY: loading runs the library's initialisers; node libraries are
+    // trusted code, the same as a plugin
+    let library = unsafe { libloading::Library::new(path) }
+        .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?;
+    let factories = {
+        // SAFETY: the symbol is declared with `NodeLibraryEntry`'s signature
+        let entry = unsafe { library.get::<NodeLibraryEntry>(b"phonon_nodes") }
+            .map_err(|e| format!("{} has no phonon_nodes function: {}", path.display(), e))?;
+        entry()
+    };
+    // Kept before registering: the factories' code lives in the library
+    if let Ok(mut libraries) = LIBRARIES.lock() {
+        libraries.push(library);
+    } else {
+        std::mem::forget(library);
+    }
+
+    let mut names = Vec::new();
+    for factory in factories {
+        let name = factory.spec().name;
+        register(factory)?;
+        names.push(name);
+    }
+    Ok(names)
+}
+
+/// A registered node inside a compiled graph. Clones are built fresh by the
+/// factory, like any other node state.
+pub struct UserNodeState {
+    factory: Arc<dyn NodeFactory>,
+    node: Box<dyn AudioNode>,
+    num_inputs: usize,
+    sample_rate: f32,
+}
+
+impl UserNodeState {
+    pub fn new(factory: Arc<dyn NodeFactory>, num_inputs: usize, sample_rate: f32) -> Self {
+        let inputs: Vec<NodeId> = (0..num_inputs).collect();
+        let node = factory.create(&inputs, sample_rate);
+        Self {
+            factory,
+            node,
+            num_inputs,
+            sample_rate,
+        }
+    }
+
+    /// Process a block; `inputs` in spec order, each as long as `output`
+    pub fn process(&mut self, inputs: &[&[f32]], output: &mut [f32], context: &ProcessContext) {
+        self.node.prepare_block(context);
+        self.node
+            .process_block(inputs, output, self.sample_rate, context);
+    }
+
+    /// Rebuild the node for a new sample rate
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        *self = Self::new(self.factory.clone(), self.num_inputs, sample_rate);
+    }
+}
+
+impl Clone for UserNodeState {
+    fn clone(&self) -> Self {
+        Self::new(self.factory.clone(), self.num_inputs, self.sample_rate)
+    }
+}
+
+impl std::fmt::Debug for UserNodeState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserNodeState")
+            .field("node", &self.node.name())
+            .field("num_inputs", &self.num_inputs)
+            .field("sample_rate", &self.sample_rate)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Silence(NodeSpec);
+
+    impl NodeFactory for Silence {
+        fn spec(&self) -> NodeSpec {
+            self.0.clone()
+        }
+
+        fn create(&self, _inputs: &[NodeId], _sample_rate: f32) -> Box<dyn AudioNode> {
+            Box::new(crate::nodes::constant::ConstantNode::new(0.0))
+        }
+    }
+
+    #[test]
+    fn test_register_validates_specs() {
+        let spec = NodeSpec::effect("unit_test_fold", "Folds").param("amount", 2.0, "Gain");
+        assert_eq!(spec.input_count(), 2);
+        register(Arc::new(Silence(spec.clone()))).unwrap();
+        assert_eq!(lookup("unit_test_fold").unwrap().spec, spec);
+        assert!(registered().iter().any(|s| s.name == "unit_test_fold"));
+
+        // Names are unique, and must be usable in the DSL
+        assert!(register(Arc::new(Silence(spec))).is_err());
+        assert!(register(Arc::new(Silence(NodeSpec::source("2fold", "")))).is_err());
+        let twice = NodeSpec::source("unit_test_twice", "")
+            .param("a", 0.0, "")
+            .param("a", 1.0, "");
+        assert!(register(Arc::new(Silence(twice))).is_err());
+
+        assert!(unregister("unit_test_fold"));
+        assert!(lookup("unit_test_fold").is_none());
+        assert!(!unregister("unit_test_fold"));
+    }
+}
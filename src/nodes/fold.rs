@@ -74,7 +74,7 @@ impl FoldNode {
     /// # Returns
     /// Folded value in [min, max]
     #[inline]
-    fn fold_value(val: f32, min: f32, max: f32) -> f32 {
+    pub(crate) fn fold_value(val: f32, min: f32, max: f32) -> f32 {
         let range = max - min;
 
         // Handle degenerate case: range is zero or nearly zero
@@ -5,15 +5,83 @@
 /// this provides smooth transitions to prevent clicks and artifacts.
 use crate::audio_node::{AudioNode, NodeId, ProcessContext};
 
-/// Noise gate state for envelope follower
-#[derive(Debug, Clone)]
-struct NoiseGateState {
+/// Hysteresis when none is given, in dB: an open gate closes only once the
+/// level drops this far below the threshold
+pub const DEFAULT_HYSTERESIS_DB: f32 = 6.0;
+/// Release of the peak detector, in seconds, so the dips around a waveform's
+/// zero crossings don't read as silence (low notes need some hold as well)
+const DETECTOR_RELEASE: f32 = 0.001;
+
+/// Gate settings for one sample
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GateSettings {
+    /// Opening threshold in dB
+    pub threshold_db: f32,
+    /// How far below the threshold the level must fall to close, in dB
+    pub hysteresis_db: f32,
+    /// Opening time in seconds
+    pub attack: f32,
+    /// Time the gate stays open after the level drops, in seconds
+    pub hold: f32,
+    /// Closing time in seconds
+    pub release: f32,
+}
+
+impl GateSettings {
+    /// Settings with [`DEFAULT_HYSTERESIS_DB`] and no hold
+    pub fn new(threshold_db: f32, attack: f32, release: f32) -> Self {
+        Self {
+            threshold_db,
+            hysteresis_db: DEFAULT_HYSTERESIS_DB,
+            attack,
+            hold: 0.0,
+            release,
+        }
+    }
+}
+
+/// Noise gate state: the detector level, whether the gate is open, and its
+/// smoothed gain
+#[derive(Debug, Clone, Default)]
+pub struct NoiseGateState {
+    level: f32,    // Peak detector level of the key
+    open: bool,    // Gate open/closed decision
+    held: f32,     // Seconds the level has been below the close threshold
     envelope: f32, // Current gate envelope (0.0 = closed, 1.0 = open)
 }
 
-impl Default for NoiseGateState {
-    fn default() -> Self {
-        Self { envelope: 0.0 }
+impl NoiseGateState {
+    /// Advance one sample with `key` as the detector input (the gated signal
+    /// itself, or a sidechain) and return the gain to apply
+    pub fn process(&mut self, key: f32, settings: &GateSettings, sample_rate: f32) -> f32 {
+        let key = key.abs();
+        self.level = if key > self.level {
+            key
+        } else {
+            self.level * (-1.0 / (DETECTOR_RELEASE * sample_rate)).exp()
+        };
+        let level_db = 20.0 * self.level.max(1e-10).log10();
+
+        if level_db > settings.threshold_db {
+            self.open = true;
+        }
+        if level_db < settings.threshold_db - settings.hysteresis_db.max(0.0) {
+            self.held += 1.0 / sample_rate;
+            if self.held >= settings.hold {
+                self.open = false;
+            }
+        } else {
+            self.held = 0.0;
+        }
+
+        let (target, time) = if self.open {
+            (1.0, settings.attack.max(0.0001)) // Min 0.1ms
+        } else {
+            (0.0, settings.release.max(0.001)) // Min 1ms
+        };
+        let coeff = (-1.0 / (time * sample_rate)).exp();
+        self.envelope = coeff * self.envelope + (1.0 - coeff) * target;
+        self.envelope
     }
 }
 
@@ -21,19 +89,13 @@ impl Default for NoiseGateState {
 ///
 /// The noise gate algorithm:
 /// ```text
-/// 1. Convert input to dB: input_db = 20 * log10(abs(sample))
-/// 2. Determine target envelope:
-///    if input_db > threshold_db:
-///        target = 1.0  // Gate open
-///    else:
-///        target = 0.0  // Gate closed
-/// 3. Smooth with attack/release envelope follower:
+/// 1. Follow the input's peak level and convert it to dB
+/// 2. Open the gate when the level rises above threshold_db; close it once
+///    it has stayed below threshold_db - hysteresis_db for the hold time,
+///    so a level hovering around the threshold doesn't chatter
+/// 3. Smooth the open/closed target with attack/release:
 ///    attack_coeff = exp(-1 / (attack_time * sample_rate))
 ///    release_coeff = exp(-1 / (release_time * sample_rate))
-///    if target > envelope:
-///        envelope = attack_coeff * envelope + (1 - attack_coeff) * target
-///    else:
-///        envelope = release_coeff * envelope + (1 - release_coeff) * target
 /// 4. Apply envelope: output = input * envelope
 /// ```
 ///
@@ -161,38 +223,8 @@ impl AudioNode for NoiseGateNode {
         // Apply noise gate with envelope follower
         for i in 0..output.len() {
             let sample = input_buf[i];
-            let threshold_db = threshold_buf[i];
-            let attack_time = attack_buf[i].max(0.0001); // Min 0.1ms
-            let release_time = release_buf[i].max(0.001); // Min 1ms
-
-            // Calculate attack/release coefficients (exponential smoothing)
-            let attack_coeff = (-1.0 / (attack_time * sample_rate)).exp();
-            let release_coeff = (-1.0 / (release_time * sample_rate)).exp();
-
-            // Convert input to dB (with safety for zero/negative)
-            let abs_sample = sample.abs().max(1e-10); // Prevent log(0)
-            let input_db = 20.0 * abs_sample.log10();
-
-            // Determine target envelope (gate open or closed)
-            let target = if input_db > threshold_db {
-                1.0 // Gate open
-            } else {
-                0.0 // Gate closed
-            };
-
-            // Smooth envelope with attack/release
-            if target > self.state.envelope {
-                // Attack: gate opening (signal above threshold)
-                self.state.envelope =
-                    attack_coeff * self.state.envelope + (1.0 - attack_coeff) * target;
-            } else {
-                // Release: gate closing (signal below threshold)
-                self.state.envelope =
-                    release_coeff * self.state.envelope + (1.0 - release_coeff) * target;
-            }
-
-            // Apply envelope to gate the signal
-            output[i] = sample * self.state.envelope;
+            let settings = GateSettings::new(threshold_buf[i], attack_buf[i], release_buf[i]);
+            output[i] = sample * self.state.process(sample, &settings, sample_rate);
         }
     }
 
@@ -281,6 +313,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hysteresis_and_hold_keep_the_gate_open() {
+        let sr = 44100.0;
+        let mut settings = GateSettings::new(-20.0, 0.001, 0.001);
+
+        // Opens at -14 dB, then hovers 3 dB under the threshold: within the
+        // hysteresis it stays open
+        let mut state = NoiseGateState::default();
+        for _ in 0..441 {
+            state.process(0.2, &settings, sr);
+        }
+        let mut gain = 0.0;
+        for _ in 0..4410 {
+            gain = state.process(0.07, &settings, sr);
+        }
+        assert!(gain > 0.99, "{}", gain);
+
+        // Well below, it closes, but not before the hold time
+        settings.hold = 0.05;
+        let below: Vec<f32> = (0..4410)
+            .map(|_| state.process(0.0, &settings, sr))
+            .collect();
+        assert!(below[1000] > 0.99, "{}", below[1000]);
+        assert!(below[4409] < 0.01, "{}", below[4409]);
+    }
+
     #[test]
     fn test_noise_gate_dependencies() {
         let gate = NoiseGateNode::new(5, 10, 15, 20);
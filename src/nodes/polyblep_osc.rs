@@ -88,7 +88,7 @@ impl PolyBLEPOscNode {
 ///
 /// # Returns
 /// Correction value to subtract from naive waveform
-fn poly_blep(phase: f32, phase_increment: f32) -> f32 {
+pub(crate) fn poly_blep(phase: f32, phase_increment: f32) -> f32 {
     // Transition at phase = 0 (discontinuity going from 1.0 to 0.0)
     if phase < phase_increment {
         let t = phase / phase_increment;
@@ -5,7 +5,7 @@
 //! This module provides OSC server and client functionality for
 //! controlling Phonon in real-time during live performances.
 
-use crate::mini_notation::parse_mini_notation;
+use crate::mini_notation_v3::parse_mini_notation;
 use crate::pattern::{Fraction, Pattern, State, TimeSpan};
 use rosc::{OscMessage, OscPacket, OscType};
 use std::collections::HashMap;
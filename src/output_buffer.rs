@@ -0,0 +1,202 @@
+//! Output buffer negotiation and latency measurement for live playback
+//!
+//! `phonon live` and `phonon edit` take `--buffer-size <frames>` or
+//! `--latency <ms>`. The requested size is asked of the device as a
+//! `cpal::BufferSize::Fixed`, clamped into the range the device reports and
+//! halved until the device accepts it; if no fixed size works the device
+//! default is used. The render ring buffer is then sized to a few device
+//! buffers so the requested latency is what the listener actually hears.
+//!
+//! `LatencyMonitor` is updated from the audio callback (atomics only) and
+//! reports the measured render-to-speaker latency: the time a freshly rendered
+//! sample waits in the ring plus the device's own output latency.
+
+use cpal::traits::DeviceTrait;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Smallest fixed buffer requested from a device
+pub const MIN_BUFFER_FRAMES: u32 = 32;
+/// Largest fixed buffer requested from a device
+pub const MAX_BUFFER_FRAMES: u32 = 16384;
+/// Ring buffer depth, in device buffers, when a buffer size is requested
+pub const RING_PERIODS: usize = 4;
+
+/// Device buffer size (frames) asked for by `--buffer-size` / `--latency`
+///
+/// An explicit buffer size wins. A latency target covers the whole ring, so
+/// each device buffer gets `1 / RING_PERIODS` of it.
+pub fn requested_buffer_frames(
+    buffer_size: Option<usize>,
+    latency_ms: Option<f32>,
+    sample_rate: f32,
+) -> Option<u32> {
+    let frames = match (buffer_size, latency_ms) {
+        (Some(frames), _) => frames as f32,
+        (None, Some(ms)) => ms / 1000.0 * sample_rate / RING_PERIODS as f32,
+        (None, None) => return None,
+    };
+    Some((frames.round() as u32).clamp(MIN_BUFFER_FRAMES, MAX_BUFFER_FRAMES))
+}
+
+/// Sizes to try, best first: the request clamped into the supported range,
+/// then successively halved down to the device minimum
+pub fn candidate_buffer_sizes(requested: u32, supported: &cpal::SupportedBufferSize) -> Vec<u32> {
+    let (min, max) = match *supported {
+        cpal::SupportedBufferSize::Range { min, max } => (min.max(1), max.max(min.max(1))),
+        cpal::SupportedBufferSize::Unknown => (MIN_BUFFER_FRAMES, MAX_BUFFER_FRAMES),
+    };
+    let mut size = requested.clamp(min, max);
+    let mut sizes = vec![size];
+    while size / 2 >= min {
+        size /= 2;
+        sizes.push(size);
+    }
+    sizes
+}
+
+/// Stream config for `device`, with the closest fixed buffer size the device
+/// accepts (probed by building and dropping a silent stream).
+/// Falls back to `BufferSize::Default` when no request is made or nothing fits.
+pub fn negotiate_output_config(
+    device: &cpal::Device,
+    supported: &cpal::SupportedStreamConfig,
+    requested: Option<u32>,
+) -> cpal::StreamConfig {
+    let mut config = supported.config();
+    let Some(requested) = requested else {
+        return config;
+    };
+
+    for frames in candidate_buffer_sizes(requested, supported.buffer_size()) {
+        config.buffer_size = cpal::BufferSize::Fixed(frames);
+        let probe = device.build_output_stream_raw(
+            &config,
+            supported.sample_format(),
+            |_: &mut cpal::Data, _: &cpal::OutputCallbackInfo| {},
+            |_| {},
+            None,
+        );
+        if probe.is_ok() {
+            return config;
+        }
+    }
+
+    config.buffer_size = cpal::BufferSize::Default;
+    config
+}
+
+/// Fixed buffer size of a negotiated config (None = device default)
+pub fn buffer_frames(config: &cpal::StreamConfig) -> Option<u32> {
+    match config.buffer_size {
+        cpal::BufferSize::Fixed(frames) => Some(frames),
+        cpal::BufferSize::Default => None,
+    }
+}
+
+/// Human-readable buffer setting, e.g. "256 frames (5.8 ms)"
+pub fn describe_buffer(config: &cpal::StreamConfig) -> String {
+    match buffer_frames(config) {
+        Some(frames) => format!(
+            "{} frames ({:.1} ms)",
+            frames,
+            frames as f32 / config.sample_rate.0 as f32 * 1000.0
+        ),
+        None => "device default".to_string(),
+    }
+}
+
+/// Ring buffer capacity (interleaved samples) for a fixed device buffer:
+/// `RING_PERIODS` device buffers, but never less than `min_samples`
+/// (the synthesis thread must fit at least one block)
+pub fn ring_capacity(buffer_frames: u32, channels: usize, min_samples: usize) -> usize {
+    (buffer_frames as usize * channels * RING_PERIODS).max(min_samples)
+}
+
+/// Output latency measured from inside the audio callback
+#[derive(Debug, Default)]
+pub struct LatencyMonitor {
+    /// Callback → playback time reported by the device (µs)
+    device_us: AtomicU64,
+    /// Rendered audio waiting in the ring at the last callback (µs)
+    ring_us: AtomicU64,
+}
+
+impl LatencyMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one callback; `queued_frames` is the ring occupancy before reading
+    pub fn record(&self, info: &cpal::OutputCallbackInfo, queued_frames: usize, sample_rate: f32) {
+        let timestamp = info.timestamp();
+        if let Some(delay) = timestamp.playback.duration_since(&timestamp.callback) {
+            self.device_us
+                .store(delay.as_micros() as u64, Ordering::Relaxed);
+        }
+        let ring_us = queued_frames as f64 / sample_rate as f64 * 1_000_000.0;
+        self.ring_us.store(ring_us as u64, Ordering::Relaxed);
+    }
+
+    /// Device output latency in milliseconds
+    pub fn device_ms(&self) -> f64 {
+        self.device_us.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    /// Time rendered audio spends in the ring, in milliseconds
+    pub fn ring_ms(&self) -> f64 {
+        self.ring_us.load(Ordering::Relaxed) as f64 / 1000.0
+    }
+
+    /// Render-to-speaker latency in milliseconds (0 before the first callback)
+    pub fn total_ms(&self) -> f64 {
+        self.ring_ms() + self.device_ms()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requested_buffer_frames() {
+        assert_eq!(requested_buffer_frames(None, None, 48000.0), None);
+        assert_eq!(requested_buffer_frames(Some(256), None, 48000.0), Some(256));
+        // Explicit size wins over latency
+        assert_eq!(
+            requested_buffer_frames(Some(256), Some(100.0), 48000.0),
+            Some(256)
+        );
+        // 20 ms at 48 kHz = 960 frames, split across the ring
+        assert_eq!(
+            requested_buffer_frames(None, Some(20.0), 48000.0),
+            Some(240)
+        );
+        assert_eq!(
+            requested_buffer_frames(Some(1), None, 48000.0),
+            Some(MIN_BUFFER_FRAMES)
+        );
+        assert_eq!(
+            requested_buffer_frames(Some(1 << 20), None, 48000.0),
+            Some(MAX_BUFFER_FRAMES)
+        );
+    }
+
+    #[test]
+    fn test_candidates_negotiate_downward() {
+        let range = cpal::SupportedBufferSize::Range { min: 64, max: 1024 };
+        assert_eq!(candidate_buffer_sizes(256, &range), vec![256, 128, 64]);
+        // Too large: clamped to the device maximum first
+        assert_eq!(candidate_buffer_sizes(4096, &range)[0], 1024);
+        // Too small: clamped up to the minimum, nothing below it
+        assert_eq!(candidate_buffer_sizes(16, &range), vec![64]);
+
+        let unknown = candidate_buffer_sizes(100, &cpal::SupportedBufferSize::Unknown);
+        assert_eq!(unknown, vec![100, 50]);
+    }
+
+    #[test]
+    fn test_ring_capacity() {
+        assert_eq!(ring_capacity(256, 2, 1024), 256 * 2 * RING_PERIODS);
+        assert_eq!(ring_capacity(32, 2, 1024), 1024);
+    }
+}
@@ -639,11 +639,12 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
     }
 
     /// whenmod_val - output different values based on cycle modulo with offset
-    /// whenmod_val(modulo, offset, on_val, off_val) outputs on_val when (cycle - offset) % modulo == 0
+    /// whenmod_val(modulo, offset, on_val, off_val) outputs on_val when cycle % modulo >= offset,
+    /// the same cycles `whenmod` transforms
     pub fn whenmod_val(modulo: i32, offset: i32, on_val: f64, off_val: f64) -> Pattern<f64> {
         Pattern::new(move |state| {
             let cycle = state.span.begin.to_float().floor() as i32;
-            let value = if (cycle - offset) % modulo == 0 {
+            let value = if cycle.rem_euclid(modulo) >= offset {
                 on_val
             } else {
                 off_val
@@ -1673,6 +1674,42 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
         })
     }
 
+    /// Arrange named fragments with a meta-pattern of their names, stretched
+    /// over `period` cycles (Tidal's `ur`)
+    /// Each fragment plays at its own speed, on the global timeline, for as
+    /// long as its name holds. Unknown names and rests are silent
+    /// Example: ur(8, "intro drop drop fill", ...) plays each part for 2 cycles
+    pub fn ur(
+        period: f64,
+        meta: Pattern<String>,
+        fragments: Vec<(String, Pattern<T>)>,
+    ) -> Pattern<T> {
+        if period <= 0.0 || fragments.is_empty() {
+            return Pattern::silence();
+        }
+
+        let meta = meta.slow(Pattern::pure(period));
+        Pattern::new(move |state| {
+            let mut all_haps = Vec::new();
+
+            for slot in meta.query(state) {
+                let Some((_, fragment)) = fragments.iter().find(|(name, _)| *name == slot.value)
+                else {
+                    continue;
+                };
+
+                // The fragment is clipped to its slot
+                let sub_state = State {
+                    span: slot.part,
+                    controls: state.controls.clone(),
+                };
+                all_haps.extend(fragment.query(&sub_state));
+            }
+
+            all_haps
+        })
+    }
+
     /// Randomly choose a pattern each cycle (deterministic based on cycle number)
     pub fn randcat(patterns: Vec<Pattern<T>>) -> Pattern<T> {
         if patterns.is_empty() {
@@ -136,7 +136,7 @@ pub fn verify_polyphony<T: std::fmt::Display + Clone + Send + Sync + 'static>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mini_notation::parse_mini_notation;
+    use crate::mini_notation_v3::parse_mini_notation;
 
     #[test]
     fn test_pattern_visualization() {
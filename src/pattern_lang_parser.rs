@@ -4,6 +4,9 @@
 //! Supports syntax like:
 //! - s "bd sn" >> fast 2 >> rev
 //! - s "bd sn" >> every 4 (slow 2)
+//!
+//! Deprecated: transforms are written `s "bd sn" $ fast 2 $ rev` and parsed
+//! by [`crate::compositional_parser`].
 
 use crate::mini_notation_v3::parse_mini_notation;
 use crate::pattern::Pattern;
@@ -691,27 +691,76 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
 
     // ============= Conditional Operations =============
 
-    /// Apply function when cycle number matches condition
-    pub fn when_mod(
+    /// Apply function on the cycles whose number passes `test`
+    ///
+    /// Queries are split at cycle boundaries, so a span reaching into the
+    /// next cycle is decided per cycle (Tidal's `when`).
+    pub fn when_cycle(
         self,
-        modulo: i32,
-        offset: i32,
+        test: impl Fn(i64) -> bool + Send + Sync + 'static,
         f: impl Fn(Pattern<T>) -> Pattern<T> + Send + Sync + 'static,
     ) -> Self
     where
         T: 'static,
     {
-        let f = Arc::new(f);
+        let transformed = f(self.clone());
         Pattern::new(move |state| {
-            let cycle = state.span.begin.to_float().floor() as i32;
-            if (cycle - offset) % modulo == 0 {
-                f(self.clone()).query(state)
-            } else {
-                self.query(state)
+            let pick = |cycle: i64| if test(cycle) { &transformed } else { &self };
+            if state.span.begin >= state.span.end {
+                let cycle = state.span.begin.to_float().floor() as i64;
+                return pick(cycle).query(state);
             }
+
+            let mut haps = Vec::new();
+            let mut begin = state.span.begin;
+            while begin < state.span.end {
+                let cycle = begin.to_float().floor() as i64;
+                let end = Fraction::new(cycle + 1, 1).min(state.span.end);
+                let cycle_state = State {
+                    span: TimeSpan::new(begin, end),
+                    controls: state.controls.clone(),
+                };
+                haps.extend(pick(cycle).query(&cycle_state));
+                begin = end;
+            }
+            haps
         })
     }
 
+    /// Apply function on the last cycles of every `modulo`-cycle phrase
+    ///
+    /// Tidal's `whenmod`: applies when `cycle mod modulo >= offset`, so
+    /// `whenmod 8 6 (fast 2)` transforms cycles 6 and 7 of every 8.
+    pub fn when_mod(
+        self,
+        modulo: i32,
+        offset: i32,
+        f: impl Fn(Pattern<T>) -> Pattern<T> + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: 'static,
+    {
+        let (modulo, offset) = (modulo as i64, offset as i64);
+        self.when_cycle(move |cycle| cycle.rem_euclid(modulo) >= offset, f)
+    }
+
+    /// Apply function every `n` cycles, starting at cycle `offset`
+    ///
+    /// Tidal's `every'`: applies when `cycle mod n == offset`, so
+    /// `every' 4 3 (rev)` transforms the last cycle of every 4.
+    pub fn every_offset(
+        self,
+        n: i32,
+        offset: i32,
+        f: impl Fn(Pattern<T>) -> Pattern<T> + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: 'static,
+    {
+        let (n, offset) = (n as i64, offset as i64);
+        self.when_cycle(move |cycle| cycle.rem_euclid(n) == offset, f)
+    }
+
     /// Swap the pattern with another every n cycles
     pub fn swap(self, n: i32, other: Pattern<T>) -> Pattern<T> {
         Pattern::new(move |state| {
@@ -833,6 +882,166 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
                 .collect()
         })
     }
+
+    // ============= Structure Combination =============
+
+    /// Combine two patterns taking structure from BOTH sides (Tidal's `appBoth`)
+    ///
+    /// Every pair of events whose wholes overlap produces one event whose
+    /// whole is the intersection of the two wholes. This is the semantics of
+    /// Tidal's `|+|`, `|*|`, `|>|` family: `"1 2" |+| "10 20 30"` yields four
+    /// events `[11 21 22 32]` split at every boundary of either side.
+    pub fn app_both<U, V>(
+        self,
+        other: Pattern<U>,
+        f: impl Fn(&T, &U) -> V + Send + Sync + 'static,
+    ) -> Pattern<V>
+    where
+        U: Clone + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        Pattern::new(move |state| {
+            let left_haps = self.query(state);
+            let right_haps = other.query(state);
+            let mut result = Vec::new();
+
+            for lhap in left_haps.iter() {
+                for rhap in right_haps.iter() {
+                    let part = match sect(&lhap.part, &rhap.part) {
+                        Some(part) => part,
+                        None => continue,
+                    };
+
+                    // Continuous (whole-less) events stay continuous
+                    let whole = match (lhap.whole, rhap.whole) {
+                        (Some(lw), Some(rw)) => match sect(&lw, &rw) {
+                            Some(whole) => Some(whole),
+                            None => continue,
+                        },
+                        _ => None,
+                    };
+
+                    let mut context = lhap.context.clone();
+                    context.extend(rhap.context.clone());
+
+                    result.push(Hap {
+                        whole,
+                        part,
+                        value: f(&lhap.value, &rhap.value),
+                        context,
+                    });
+                }
+            }
+
+            result
+        })
+    }
+}
+
+/// Intersection of two timespans, `None` when they don't overlap
+fn sect(a: &TimeSpan, b: &TimeSpan) -> Option<TimeSpan> {
+    let begin = a.begin.max(b.begin);
+    let end = a.end.min(b.end);
+    if begin < end {
+        Some(TimeSpan::new(begin, end))
+    } else {
+        None
+    }
+}
+
+// ============= Both-Structure Numeric Operators =============
+// Tidal's `|op|` operators: structure comes from both patterns, events are
+// split wherever either side has a boundary (see `app_both`).
+//
+// |+| : add          |-| : subtract
+// |*| : multiply     |/| : divide
+// |>| : values from right
+// |<| : values from left
+
+impl Pattern<f64> {
+    /// Add with both structure (`|+|`)
+    /// `"0 3 5" |+| "<0 12>"` = `[0 3 5]` then `[12 15 17]` on the next cycle
+    pub fn add_sect(self, other: Pattern<f64>) -> Pattern<f64> {
+        self.app_both(other, |a, b| a + b)
+    }
+
+    /// Subtract with both structure (`|-|`)
+    pub fn sub_sect(self, other: Pattern<f64>) -> Pattern<f64> {
+        self.app_both(other, |a, b| a - b)
+    }
+
+    /// Multiply with both structure (`|*|`)
+    pub fn mul_sect(self, other: Pattern<f64>) -> Pattern<f64> {
+        self.app_both(other, |a, b| a * b)
+    }
+
+    /// Divide with both structure (`|/|`), leaving the left value on division by zero
+    pub fn div_sect(self, other: Pattern<f64>) -> Pattern<f64> {
+        self.app_both(
+            other,
+            |a, b| if b.abs() > f64::EPSILON { a / b } else { *a },
+        )
+    }
+
+    /// Union with both structure, values from the right (`|>|`)
+    pub fn union_sect(self, other: Pattern<f64>) -> Pattern<f64> {
+        self.app_both(other, |_, b| *b)
+    }
+
+    /// Union with both structure, values from the left (`|<|`)
+    pub fn union_sect_left(self, other: Pattern<f64>) -> Pattern<f64> {
+        self.app_both(other, |a, _| *a)
+    }
+
+    // ============= Patterned Generators =============
+
+    /// Build a cycle-by-cycle generator whose size is itself a pattern
+    ///
+    /// `count` is sampled at the start of every cycle and rounded to an
+    /// integer; `make` builds the generator for that size and is queried for
+    /// that cycle only. A count of zero (or less) yields a silent cycle.
+    /// This is what lets `run "<4 8>"` alternate between `run 4` and `run 8`.
+    pub fn sized_by(
+        count: Pattern<f64>,
+        make: impl Fn(usize) -> Pattern<f64> + Send + Sync + 'static,
+    ) -> Pattern<f64> {
+        Pattern::new(move |state| {
+            let mut haps = Vec::new();
+            let start_cycle = state.span.begin.to_float().floor() as i64;
+            let end_cycle = state.span.end.to_float().ceil() as i64;
+
+            for cycle in start_cycle..end_cycle {
+                let cycle_begin = Fraction::new(cycle, 1);
+                let cycle_end = Fraction::new(cycle + 1, 1);
+                let begin = cycle_begin.max(state.span.begin);
+                let end = cycle_end.min(state.span.end);
+                if begin >= end {
+                    continue;
+                }
+
+                let count_state = State {
+                    span: TimeSpan::new(cycle_begin, cycle_begin + Fraction::new(1, 1000)),
+                    controls: state.controls.clone(),
+                };
+                let size = count
+                    .query(&count_state)
+                    .first()
+                    .map(|h| h.value.round())
+                    .unwrap_or(0.0);
+                if size < 1.0 {
+                    continue;
+                }
+
+                let cycle_state = State {
+                    span: TimeSpan::new(begin, end),
+                    controls: state.controls.clone(),
+                };
+                haps.extend(make(size as usize).query(&cycle_state));
+            }
+
+            haps
+        })
+    }
 }
 
 // ============= Mini-notation String Patterns =============
@@ -936,4 +1145,103 @@ mod tests {
         assert_eq!(haps.len(), 3);
         assert!((haps[0].value - 440.0).abs() < 0.01); // A4 = 440Hz
     }
+
+    fn numeric(s: &str) -> Pattern<f64> {
+        crate::mini_notation_v3::parse_mini_notation(s).fmap(|v| v.parse::<f64>().unwrap_or(0.0))
+    }
+
+    fn cycle_values(p: &Pattern<f64>, cycle: i64) -> Vec<(f64, f64)> {
+        let state = State {
+            span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+            controls: HashMap::new(),
+        };
+        let mut haps = p.query(&state);
+        haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+        haps.iter()
+            .map(|h| (h.whole.unwrap().begin.to_float(), h.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_sect_alternation() {
+        // Tidal: "0 3 5" |+| "<0 12>" -> [0 3 5] then [12 15 17]
+        let p = numeric("0 3 5").add_sect(numeric("<0 12>"));
+        let values: Vec<f64> = cycle_values(&p, 0).iter().map(|e| e.1).collect();
+        assert_eq!(values, vec![0.0, 3.0, 5.0]);
+        let values: Vec<f64> = cycle_values(&p, 1).iter().map(|e| e.1).collect();
+        assert_eq!(values, vec![12.0, 15.0, 17.0]);
+    }
+
+    #[test]
+    fn test_add_sect_splits_at_both_boundaries() {
+        // Tidal: "1 2" |+| "10 20 30" -> (0>⅓)|11 (⅓>½)|21 (½>⅔)|22 (⅔>1)|32
+        let events = cycle_values(&numeric("1 2").add_sect(numeric("10 20 30")), 0);
+        let onsets: Vec<f64> = events.iter().map(|e| e.0).collect();
+        let values: Vec<f64> = events.iter().map(|e| e.1).collect();
+        assert_eq!(values, vec![11.0, 21.0, 22.0, 32.0]);
+        assert!((onsets[1] - 1.0 / 3.0).abs() < 1e-5);
+        assert!((onsets[2] - 0.5).abs() < 1e-5);
+        assert!((onsets[3] - 2.0 / 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_mul_sect_respects_rests() {
+        // A rest on either side removes the event
+        let values: Vec<f64> = cycle_values(&numeric("2 ~ 4 5").mul_sect(numeric("10 10 ~ 10")), 0)
+            .iter()
+            .map(|e| e.1)
+            .collect();
+        assert_eq!(values, vec![20.0, 50.0]);
+    }
+
+    #[test]
+    fn test_sized_by_follows_count_pattern() {
+        // run "<2 3>" -> [0 1] then [0 1 2]
+        let p = Pattern::<f64>::sized_by(numeric("<2 3>"), Pattern::<f64>::run);
+        let cycle0: Vec<f64> = cycle_values(&p, 0).iter().map(|e| e.1).collect();
+        let cycle1: Vec<f64> = cycle_values(&p, 1).iter().map(|e| e.1).collect();
+        assert_eq!(cycle0, vec![0.0, 1.0]);
+        assert_eq!(cycle1, vec![0.0, 1.0, 2.0]);
+
+        // A zero count silences the cycle
+        let silent = Pattern::<f64>::sized_by(Pattern::pure(0.0), Pattern::<f64>::run);
+        assert!(cycle_values(&silent, 0).is_empty());
+    }
+
+    #[test]
+    fn test_when_mod_transforms_end_of_phrase() {
+        // whenmod 4 2 (fast 2): cycles 2 and 3 of every 4 are doubled
+        let p = numeric("1").when_mod(4, 2, |p| p.fast(Pattern::pure(2.0)));
+        let counts: Vec<usize> = (0..8).map(|c| cycle_values(&p, c).len()).collect();
+        assert_eq!(counts, vec![1, 1, 2, 2, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_every_offset_selects_single_cycle() {
+        // every' 3 1 (fast 2): only cycles 1, 4, 7 are doubled
+        let p = numeric("1").every_offset(3, 1, |p| p.fast(Pattern::pure(2.0)));
+        let counts: Vec<usize> = (0..8).map(|c| cycle_values(&p, c).len()).collect();
+        assert_eq!(counts, vec![1, 2, 1, 1, 2, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_when_cycle_splits_multi_cycle_queries() {
+        let p = numeric("1").when_mod(2, 1, |p| p.fast(Pattern::pure(2.0)));
+        let state = State {
+            span: TimeSpan::new(Fraction::new(0, 1), Fraction::new(4, 1)),
+            controls: HashMap::new(),
+        };
+        assert_eq!(p.query(&state).len(), 6);
+    }
+
+    #[test]
+    fn test_union_sect_takes_values_from_one_side() {
+        let right = cycle_values(&numeric("1 2").union_sect(numeric("7 8 9")), 0);
+        let right: Vec<f64> = right.iter().map(|e| e.1).collect();
+        assert_eq!(right, vec![7.0, 8.0, 8.0, 9.0]);
+
+        let left = cycle_values(&numeric("1 2").union_sect_left(numeric("7 8 9")), 0);
+        let left: Vec<f64> = left.iter().map(|e| e.1).collect();
+        assert_eq!(left, vec![1.0, 1.0, 2.0, 2.0]);
+    }
 }
@@ -9,6 +9,7 @@ use crate::pattern::{Fraction, Hap, Pattern, State, TimeSpan};
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 impl<T: Clone + Send + Sync + 'static> Pattern<T> {
@@ -112,27 +113,65 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
     }
 
     /// Apply a function inside a time range
+    ///
+    /// Tidal's `within (begin, end) f`: events whose onset falls in
+    /// [begin, end) of the cycle come from `f(pattern)`, all others from the
+    /// original. `within 0.5 1.0 (fast 2)` doubles up the second half only.
     pub fn within(
         self,
         begin: f64,
         end: f64,
         f: impl Fn(Pattern<T>) -> Pattern<T> + Send + Sync + 'static,
     ) -> Self {
-        let b = Fraction::from_float(begin);
-        let e = Fraction::from_float(end);
-        let f = Arc::new(f);
+        const EPSILON: f64 = 1e-9;
+        let transformed = f(self.clone());
+        let inside = move |hap: &Hap<T>| {
+            let onset = hap.whole.as_ref().unwrap_or(&hap.part).begin.to_float();
+            let cycle_pos = onset - onset.floor();
+            cycle_pos >= begin - EPSILON && cycle_pos < end - EPSILON
+        };
         Pattern::new(move |state: &State| {
-            let cycle_begin = state.span.begin.to_float().floor();
-            let cycle_pos = state.span.begin.to_float() - cycle_begin;
+            let mut haps: Vec<Hap<T>> = transformed
+                .query(state)
+                .into_iter()
+                .filter(|hap| inside(hap))
+                .collect();
+            haps.extend(self.query(state).into_iter().filter(|hap| !inside(hap)));
+            haps
+        })
+    }
 
-            if cycle_pos >= begin && cycle_pos < end {
-                f(self.clone()).query(state)
-            } else {
-                self.query(state)
-            }
+    /// Apply a function every n cycles, for each n in the list
+    ///
+    /// Tidal's `foldEvery [3, 5] f`: the same as `every 3 f $ every 5 f`, so
+    /// cycles divisible by several of the numbers get `f` applied repeatedly.
+    pub fn fold_every(
+        self,
+        ns: Vec<i32>,
+        f: impl Fn(Pattern<T>) -> Pattern<T> + Send + Sync + 'static,
+    ) -> Self {
+        let f = Arc::new(f);
+        ns.into_iter().rev().fold(self, |pattern, n| {
+            let f = f.clone();
+            pattern.every_offset(n, 0, move |p| f(p))
         })
     }
 
+    /// Apply a function to a random subset of whole cycles
+    ///
+    /// Tidal's `somecyclesBy`: unlike `degradeBy`, the choice is made once per
+    /// cycle, so a cycle is either entirely transformed or left alone.
+    pub fn somecycles_by(
+        self,
+        prob: f64,
+        f: impl Fn(Pattern<T>) -> Pattern<T> + Send + Sync + 'static,
+    ) -> Self {
+        self.when_cycle(
+            move |cycle| StdRng::seed_from_u64(cycle as u64).gen::<f64>() < prob,
+            f,
+        )
+    }
+
     /// Compress pattern to fit within a time range
     pub fn compress(self, begin: Pattern<f64>, end: Pattern<f64>) -> Self
     where
@@ -350,6 +389,60 @@ impl<T: Clone + Send + Sync + 'static> Pattern<T> {
         })
     }
 
+    /// Quantize onsets - snap each event's start to the nearest 1/steps of a
+    /// cycle, keeping its length. For tightening humanized or recorded
+    /// material against strict patterns
+    pub fn quantize_onsets(self, steps: Pattern<f64>) -> Self
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        Pattern::new(move |state: &State| {
+            // Query steps pattern at cycle start
+            let cycle_start = state.span.begin.to_float().floor();
+            let steps_state = State {
+                span: TimeSpan::new(
+                    Fraction::from_float(cycle_start),
+                    Fraction::from_float(cycle_start + 0.001),
+                ),
+                controls: state.controls.clone(),
+            };
+
+            let steps_haps = steps.query(&steps_state);
+            let steps_val = steps_haps.first().map(|h| h.value).unwrap_or(1.0);
+            if steps_val <= 0.0 {
+                return self.query(state);
+            }
+
+            // Events up to half a step outside the span can snap into it
+            let half_step = Fraction::from_float(0.5 / steps_val);
+            let wide_state = State {
+                span: TimeSpan::new(state.span.begin - half_step, state.span.end + half_step),
+                controls: state.controls.clone(),
+            };
+
+            self.query(&wide_state)
+                .into_iter()
+                .filter_map(|mut hap| {
+                    let onset = hap.whole.map(|w| w.begin).unwrap_or(hap.part.begin);
+                    let snapped = (onset.to_float() * steps_val).round() / steps_val;
+                    let shift = Fraction::from_float(snapped) - onset;
+                    if let Some(whole) = hap.whole.as_mut() {
+                        *whole = TimeSpan::new(whole.begin + shift, whole.end + shift);
+                    }
+
+                    // Clip back to the queried span
+                    let begin = (hap.part.begin + shift).max(state.span.begin);
+                    let end = (hap.part.end + shift).min(state.span.end);
+                    if begin >= end {
+                        return None;
+                    }
+                    hap.part = TimeSpan::new(begin, end);
+                    Some(hap)
+                })
+                .collect()
+        })
+    }
+
     /// Shuffle time - randomize event timing slightly
     pub fn shuffle(self, amount: Pattern<f64>) -> Self
     where
@@ -1651,6 +1744,204 @@ impl<T: Clone + Send + Sync + Debug + 'static> Pattern<T> {
     }
 }
 
+// Generative operators
+impl<T: Clone + Send + Sync + Debug + 'static> Pattern<T> {
+    /// Mutate - each cycle, a seeded `amount` fraction of the events change:
+    /// most take the value of another event in the same cycle, the rest drop
+    /// out. `mutate 0.1` varies a pattern slightly and differently every
+    /// cycle while keeping its material.
+    pub fn mutate(self, amount: f64, seed: u64) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        Pattern::new(move |state: &State| {
+            let mut result = Vec::new();
+            let start_cycle = state.span.begin.to_float().floor() as i64;
+            let end_cycle = state.span.end.to_float().ceil() as i64;
+
+            for cycle in start_cycle..end_cycle {
+                // Decide over the whole cycle so every query of it agrees
+                let mut haps = self.query(&cycle_state(state, cycle));
+                haps.retain(|hap| hap.whole.is_some());
+                haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+                let values: Vec<T> = haps.iter().map(|hap| hap.value.clone()).collect();
+                let mut rng = StdRng::seed_from_u64(cycle_seed(seed, cycle));
+
+                for mut hap in haps {
+                    let mutated = rng.gen_range(0.0..1.0) < amount;
+                    let action = rng.gen_range(0.0..1.0);
+                    let pick = rng.gen_range(0..values.len());
+                    if mutated {
+                        if action < 0.25 {
+                            continue;
+                        }
+                        hap.value = values[pick].clone();
+                    }
+                    if let Some(part) = clip_span(hap.part, state.span) {
+                        hap.part = part;
+                        result.push(hap);
+                    }
+                }
+            }
+            result
+        })
+    }
+
+    /// Markov - learn which value follows which over the first `cycles`
+    /// cycles, then regenerate endlessly: every cycle keeps the rhythm of the
+    /// first and walks the learned chain from a seeded starting value, with a
+    /// seeded random choice at each step. Cycle 0 starts where the source
+    /// does.
+    pub fn markov(self, cycles: usize, seed: u64) -> Self {
+        let cycles = cycles.max(1);
+        let learn_state = State {
+            span: TimeSpan::new(Fraction::new(0, 1), Fraction::new(cycles as i64, 1)),
+            controls: Default::default(),
+        };
+        let mut events = self.query(&learn_state);
+        events.retain(|hap| hap.whole.is_some_and(|whole| whole.begin == hap.part.begin));
+        events.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+
+        // States are distinct values, compared by their debug form
+        let mut states: Vec<(String, T)> = Vec::new();
+        let sequence: Vec<usize> = events
+            .iter()
+            .map(|hap| {
+                let key = format!("{:?}", hap.value);
+                match states.iter().position(|(k, _)| *k == key) {
+                    Some(i) => i,
+                    None => {
+                        states.push((key, hap.value.clone()));
+                        states.len() - 1
+                    }
+                }
+            })
+            .collect();
+        // Transitions wrap from the last event to the first, so no state is
+        // a dead end
+        let mut transitions: Vec<Vec<usize>> = vec![Vec::new(); states.len()];
+        for (i, &from) in sequence.iter().enumerate() {
+            transitions[from].push(sequence[(i + 1) % sequence.len()]);
+        }
+        let rhythm: Vec<TimeSpan> = events
+            .iter()
+            .filter_map(|hap| hap.whole)
+            .filter(|whole| whole.begin < Fraction::new(1, 1))
+            .collect();
+        let values: Vec<T> = states.into_iter().map(|(_, value)| value).collect();
+
+        Pattern::new(move |state: &State| {
+            let mut result = Vec::new();
+            if rhythm.is_empty() {
+                return result;
+            }
+            let start_cycle = state.span.begin.to_float().floor() as i64;
+            let end_cycle = state.span.end.to_float().ceil() as i64;
+
+            for cycle in start_cycle..end_cycle {
+                let mut rng = StdRng::seed_from_u64(cycle_seed(seed, cycle));
+                let start = rng.gen_range(0..values.len());
+                let mut current = if cycle == 0 { sequence[0] } else { start };
+                let offset = Fraction::new(cycle, 1);
+
+                for (step, whole) in rhythm.iter().enumerate() {
+                    if step > 0 {
+                        let next = &transitions[current];
+                        current = next[rng.gen_range(0..next.len())];
+                    }
+                    let whole = TimeSpan::new(whole.begin + offset, whole.end + offset);
+                    if let Some(part) = clip_span(whole, state.span) {
+                        result.push(Hap::new(Some(whole), part, values[current].clone()));
+                    }
+                }
+            }
+            result
+        })
+    }
+}
+
+/// The cycle counted operators (`take`, `after`) count from, shared by every
+/// counted pattern of a graph
+///
+/// It is 0 until the graph is installed: a render counts from its start, and
+/// a live graph from the first cycle boundary after it is swapped in or cued.
+/// Querying a pattern (to preload its samples, say) never moves it.
+#[derive(Debug, Clone, Default)]
+pub struct CountOrigin(Arc<AtomicU64>);
+
+impl CountOrigin {
+    /// Count from the first cycle boundary at or after `cycle`
+    pub fn start_at(&self, cycle: f64) {
+        self.0.store(cycle.ceil().to_bits(), Ordering::Relaxed);
+    }
+
+    /// The cycle counting starts at
+    pub fn cycle(&self) -> f64 {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+// Counted operators
+impl<T: Clone + Send + Sync + Debug + 'static> Pattern<T> {
+    /// Take - play for `cycles` cycles from `origin`, then go silent
+    pub fn take(self, cycles: f64, origin: CountOrigin) -> Self {
+        self.switch_after(cycles, Pattern::silence(), origin)
+    }
+
+    /// After - stay silent for `cycles` cycles from `origin`, then play
+    pub fn after(self, cycles: f64, origin: CountOrigin) -> Self {
+        Pattern::silence().switch_after(cycles, self, origin)
+    }
+
+    /// Play this pattern for `cycles` cycles from `origin`, then `next` from
+    /// then on
+    pub fn switch_after(self, cycles: f64, next: Pattern<T>, origin: CountOrigin) -> Self {
+        Pattern::new(move |state: &State| {
+            let origin = origin.cycle();
+            let switch = Fraction::from_float(origin + cycles.max(0.0));
+            let mut result = Vec::new();
+            if state.span.begin < switch {
+                let span = TimeSpan::new(state.span.begin, state.span.end.min(switch));
+                result.extend(self.query(&State {
+                    span,
+                    controls: state.controls.clone(),
+                }));
+            }
+            if state.span.end > switch {
+                let span = TimeSpan::new(state.span.begin.max(switch), state.span.end);
+                result.extend(next.query(&State {
+                    span,
+                    controls: state.controls.clone(),
+                }));
+            }
+            result
+        })
+    }
+}
+
+/// A state spanning one whole cycle
+fn cycle_state(state: &State, cycle: i64) -> State {
+    State {
+        span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+        controls: state.controls.clone(),
+    }
+}
+
+/// Per-cycle RNG seed, so each cycle's random choices are fixed
+fn cycle_seed(seed: u64, cycle: i64) -> u64 {
+    seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ (cycle as u64)
+}
+
+/// The part of `span` overlapping `query`, if any
+fn clip_span(span: TimeSpan, query: TimeSpan) -> Option<TimeSpan> {
+    if span.end > query.begin && span.begin < query.end {
+        Some(TimeSpan::new(
+            span.begin.max(query.begin),
+            span.end.min(query.end),
+        ))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1748,4 +2039,109 @@ mod tests {
         let haps = filtered.query(&state);
         assert_eq!(haps.len(), 2); // Only 3 and 4
     }
+
+    fn cycle_values(pattern: &Pattern<String>, cycle: i64) -> Vec<String> {
+        let state = State {
+            span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+            controls: HashMap::new(),
+        };
+        let mut haps = pattern.query(&state);
+        haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+        haps.into_iter().map(|h| h.value).collect()
+    }
+
+    #[test]
+    fn test_mutate() {
+        let source = Pattern::from_string("a b c d e f g h");
+        assert_eq!(
+            cycle_values(&source.clone().mutate(0.0, 1), 3),
+            cycle_values(&source, 3)
+        );
+
+        // Everything mutated still comes from the same cycle's material
+        let mutated = source.clone().mutate(1.0, 1);
+        let changed = (0..8).any(|c| cycle_values(&mutated, c) != cycle_values(&source, c));
+        assert!(changed);
+        for c in 0..8 {
+            for value in cycle_values(&mutated, c) {
+                assert!("abcdefgh".contains(value.as_str()));
+            }
+        }
+
+        // Querying half a cycle at a time gives the same events
+        let mutated = source.mutate(0.5, 7);
+        let mut halves = Vec::new();
+        for (b, e) in [(0, 1), (1, 2)] {
+            let state = State {
+                span: TimeSpan::new(Fraction::new(10 * 2 + b, 2), Fraction::new(10 * 2 + e, 2)),
+                controls: HashMap::new(),
+            };
+            halves.extend(mutated.query(&state).into_iter().map(|h| h.value));
+        }
+        assert_eq!(halves, cycle_values(&mutated, 10));
+    }
+
+    #[test]
+    fn test_markov() {
+        // A cycle of distinct values has one way through: the source itself,
+        // from wherever the walk starts
+        let chain = Pattern::from_string("a b c d").markov(1, 3);
+        assert_eq!(cycle_values(&chain, 0), vec!["a", "b", "c", "d"]);
+        let later = cycle_values(&chain, 5).join("");
+        assert_eq!(later.len(), 4);
+        assert!("abcdabcd".contains(&later));
+
+        // Branching chains keep the rhythm and only use learned values
+        let chain = Pattern::from_string("a a b a c a").markov(1, 3);
+        for c in 0..16 {
+            let values = cycle_values(&chain, c);
+            assert_eq!(values.len(), 6);
+            assert!(values.iter().all(|v| ["a", "b", "c"].contains(&v.as_str())));
+            // b and c are always followed by a
+            for pair in values.windows(2) {
+                if pair[0] != "a" {
+                    assert_eq!(pair[1], "a");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_take_and_after() {
+        let source = Pattern::from_string("a b");
+        let intro = source.clone().take(2.0, CountOrigin::default());
+        let main = source.clone().after(2.0, CountOrigin::default());
+        for c in 0..4 {
+            let playing = if c < 2 { vec!["a", "b"] } else { vec![] };
+            assert_eq!(cycle_values(&intro, c), playing);
+            let waiting = if c < 2 { vec![] } else { vec!["a", "b"] };
+            assert_eq!(cycle_values(&main, c), waiting);
+        }
+
+        // Started mid-cycle: that cycle finishes, then two more play. Looking
+        // ahead beforehand doesn't start the count
+        let origin = CountOrigin::default();
+        let fill = source.clone().take(2.0, origin.clone());
+        assert_eq!(cycle_values(&fill, 1), vec!["a", "b"]);
+        origin.start_at(5.5);
+        let state = State {
+            span: TimeSpan::new(Fraction::new(11, 2), Fraction::new(6, 1)),
+            controls: HashMap::new(),
+        };
+        assert_eq!(fill.query(&state).len(), 1);
+        assert_eq!(cycle_values(&fill, 7), vec!["a", "b"]);
+        assert!(cycle_values(&fill, 8).is_empty());
+
+        // A query across the switch gets each side from its own pattern
+        let switched = source.switch_after(1.0, Pattern::from_string("c"), CountOrigin::default());
+        assert_eq!(cycle_values(&switched, 0), vec!["a", "b"]);
+        let state = State {
+            span: TimeSpan::new(Fraction::new(1, 2), Fraction::new(2, 1)),
+            controls: HashMap::new(),
+        };
+        let mut haps = switched.query(&state);
+        haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+        let values: Vec<String> = haps.into_iter().map(|h| h.value).collect();
+        assert_eq!(values, vec!["b", "c"]);
+    }
 }
@@ -241,12 +241,12 @@ pub fn note_to_midi_chord(note: &str) -> Vec<MidiNote> {
 
 /// Convert frequency to MIDI note number
 pub fn freq_to_midi(freq: f64) -> MidiNote {
-    (69.0 + 12.0 * (freq / 440.0).log2()).round() as MidiNote
+    crate::pitch::hz_to_midi(freq).round() as MidiNote
 }
 
 /// Convert MIDI note number to frequency
 pub fn midi_to_freq(midi: MidiNote) -> f64 {
-    440.0 * 2.0_f64.powf((midi as f64 - 69.0) / 12.0)
+    crate::pitch::midi_to_hz(midi as f64)
 }
 
 impl Pattern<String> {
@@ -497,70 +497,23 @@ impl Pattern<Vec<f64>> {
 
             haps.into_iter()
                 .map(|mut hap| {
-                    if hap.value.len() <= 1 {
-                        return hap;
-                    }
-
-                    let mut notes = hap.value.clone();
-
-                    match voicing_type.as_str() {
-                        "drop2" => {
-                            // Move second highest note down an octave
-                            notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            if notes.len() >= 2 {
-                                let second_highest = notes.len() - 2;
-                                notes[second_highest] -= 12.0;
-                                notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            }
-                        }
-                        "drop3" => {
-                            // Move third highest note down an octave
-                            notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            if notes.len() >= 3 {
-                                let third_highest = notes.len() - 3;
-                                notes[third_highest] -= 12.0;
-                                notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            }
-                        }
-                        "spread" => {
-                            // Spread notes across octaves
-                            notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            for i in 1..notes.len() {
-                                if notes[i] - notes[i - 1] < 3.0 {
-                                    notes[i] += 12.0;
-                                }
-                            }
-                        }
-                        "close" => {
-                            // Keep notes within an octave
-                            notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            let root = notes[0];
-                            for i in 1..notes.len() {
-                                while notes[i] - root > 12.0 {
-                                    notes[i] -= 12.0;
-                                }
-                            }
-                            notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                        }
-                        "invert1" => {
-                            // First inversion
-                            if notes.len() > 1 {
-                                notes[0] += 12.0;
-                                notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            }
-                        }
-                        "invert2" => {
-                            // Second inversion
-                            if notes.len() > 2 {
-                                notes[0] += 12.0;
-                                notes[1] += 12.0;
-                                notes.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                            }
-                        }
-                        _ => {} // Unknown voicing, leave as-is
+                    if hap.value.len() > 1 {
+                        voice_chord(&mut hap.value, &voicing_type);
                     }
+                    hap
+                })
+                .collect()
+        })
+    }
 
-                    hap.value = notes;
+    /// Invert chords: each step moves the lowest note up an octave, negative
+    /// steps move the highest note down
+    pub fn inversion(self, steps: i32) -> Self {
+        Pattern::new(move |state: &State| {
+            self.query(state)
+                .into_iter()
+                .map(|mut hap| {
+                    invert_chord(&mut hap.value, steps);
                     hap
                 })
                 .collect()
@@ -568,6 +521,177 @@ impl Pattern<Vec<f64>> {
     }
 }
 
+fn sort_notes(notes: &mut [f64]) {
+    notes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+}
+
+/// Re-voice one chord in place, leaving it sorted low to high
+///
+/// Voicings: `drop2`/`drop3` (second/third highest note down an octave),
+/// `open` (every other note above the bass up an octave), `close` (all notes
+/// within an octave of the bass), `spread`, and `invert1`/`invert2`.
+/// Returns false, leaving the notes alone, for an unknown voicing.
+pub fn voice_chord(notes: &mut [f64], voicing: &str) -> bool {
+    sort_notes(notes);
+    let len = notes.len();
+    match voicing {
+        "drop2" => {
+            if len >= 2 {
+                notes[len - 2] -= 12.0;
+            }
+        }
+        "drop3" => {
+            if len >= 3 {
+                notes[len - 3] -= 12.0;
+            }
+        }
+        "open" => {
+            for note in notes.iter_mut().skip(1).step_by(2) {
+                *note += 12.0;
+            }
+        }
+        "spread" => {
+            for i in 1..len {
+                if notes[i] - notes[i - 1] < 3.0 {
+                    notes[i] += 12.0;
+                }
+            }
+        }
+        "close" => {
+            if let Some(&root) = notes.first() {
+                for note in notes.iter_mut().skip(1) {
+                    while *note - root > 12.0 {
+                        *note -= 12.0;
+                    }
+                    while *note < root {
+                        *note += 12.0;
+                    }
+                }
+            }
+        }
+        "invert1" => invert_chord(notes, 1),
+        "invert2" => invert_chord(notes, 2),
+        _ => return false,
+    }
+    sort_notes(notes);
+    true
+}
+
+/// Invert one chord in place by `steps`, leaving it sorted low to high
+pub fn invert_chord(notes: &mut [f64], steps: i32) {
+    sort_notes(notes);
+    if notes.len() < 2 {
+        return;
+    }
+    for _ in 0..steps.unsigned_abs() {
+        if steps > 0 {
+            notes[0] += 12.0;
+            notes.rotate_left(1);
+        } else {
+            let last = notes.len() - 1;
+            notes[last] -= 12.0;
+            notes.rotate_right(1);
+        }
+    }
+}
+
+const PITCH_CLASS_NAMES: [&str; 12] = [
+    "c", "cs", "d", "ds", "e", "f", "fs", "g", "gs", "a", "as", "b",
+];
+
+/// A chord tone read from a pattern value: a number (semitones or MIDI) or a
+/// note name, which is written back as a note name
+fn chord_tone(value: &str) -> Option<(f64, bool)> {
+    if let Ok(n) = value.trim().parse::<f64>() {
+        return Some((n, false));
+    }
+    note_to_midi(value.trim()).map(|midi| (midi as f64, true))
+}
+
+pub(crate) fn format_chord_tone(value: f64, as_name: bool) -> String {
+    if as_name {
+        let midi = value.round().clamp(0.0, 127.0) as i32;
+        format!(
+            "{}{}",
+            PITCH_CLASS_NAMES[midi.rem_euclid(12) as usize],
+            midi.div_euclid(12) - 1
+        )
+    } else {
+        value.to_string()
+    }
+}
+
+/// Rework every chord of a note pattern, where a chord is a stack of events
+/// sharing a time span (as `n "c'maj"` or `"[c4,e4,g4]"` produce). `settings`
+/// picks the argument for each chord at its onset, so it can be patterned
+/// too. Rests and single notes pass through untouched.
+fn map_chord_stacks<F>(chords: Pattern<String>, settings: Pattern<String>, f: F) -> Pattern<String>
+where
+    F: Fn(&mut [f64], &str) + Send + Sync + 'static,
+{
+    Pattern::new(move |state: &State| {
+        let setting_haps = settings.query(state);
+        let mut haps = chords.query(state);
+        let mut done = vec![false; haps.len()];
+
+        for i in 0..haps.len() {
+            if done[i] {
+                continue;
+            }
+            let members: Vec<usize> = (i..haps.len())
+                .filter(|&j| {
+                    !done[j] && haps[j].whole == haps[i].whole && haps[j].part == haps[i].part
+                })
+                .collect();
+            for &j in &members {
+                done[j] = true;
+            }
+            let tones: Vec<(usize, f64, bool)> = members
+                .iter()
+                .filter_map(|&j| chord_tone(&haps[j].value).map(|(v, name)| (j, v, name)))
+                .collect();
+            if tones.len() < 2 {
+                continue;
+            }
+
+            let begin = haps[i].whole.unwrap_or(haps[i].part).begin.to_float();
+            let setting = setting_haps
+                .iter()
+                .find(|s| begin >= s.part.begin.to_float() && begin < s.part.end.to_float())
+                .or_else(|| setting_haps.first());
+            let Some(setting) = setting else { continue };
+
+            let mut notes: Vec<f64> = tones.iter().map(|&(_, v, _)| v).collect();
+            f(&mut notes, setting.value.trim());
+            for (&(j, _, as_name), note) in tones.iter().zip(notes) {
+                haps[j].value = format_chord_tone(note, as_name);
+            }
+        }
+
+        haps
+    })
+}
+
+/// Re-voice the chords of a note pattern with a pattern of voicing names,
+/// e.g. `"drop2 open"`; see [`voice_chord`]
+pub fn voicing_pattern(chords: Pattern<String>, voicings: Pattern<String>) -> Pattern<String> {
+    map_chord_stacks(chords, voicings, |notes, voicing| {
+        if !voice_chord(notes, voicing) && voicing != "~" {
+            eprintln!("⚠️  Unknown voicing '{}', leaving chord as-is", voicing);
+        }
+    })
+}
+
+/// Invert the chords of a note pattern by a pattern of step counts, e.g.
+/// `"0 1 2"`; see [`invert_chord`]
+pub fn inversion_pattern(chords: Pattern<String>, steps: Pattern<String>) -> Pattern<String> {
+    map_chord_stacks(chords, steps, |notes, steps| {
+        if let Ok(steps) = steps.parse::<f64>() {
+            invert_chord(notes, steps.round() as i32);
+        }
+    })
+}
+
 /// List of available scale names
 pub fn scale_list() -> Vec<&'static str> {
     vec![
@@ -679,4 +803,48 @@ mod tests {
         let haps = chord.query(&state);
         assert_eq!(haps[0].value, vec![60.0, 64.0, 67.0, 71.0]); // C E G B
     }
+
+    #[test]
+    fn test_voice_chord() {
+        let mut notes = vec![67.0, 60.0, 64.0, 71.0];
+        assert!(voice_chord(&mut notes, "drop2"));
+        assert_eq!(notes, vec![55.0, 60.0, 64.0, 71.0]);
+
+        let mut notes = vec![60.0, 64.0, 67.0];
+        assert!(voice_chord(&mut notes, "open"));
+        assert_eq!(notes, vec![60.0, 67.0, 76.0]);
+        assert!(voice_chord(&mut notes, "close"));
+        assert_eq!(notes, vec![60.0, 64.0, 67.0]);
+
+        assert!(!voice_chord(&mut notes, "nonsense"));
+        assert_eq!(notes, vec![60.0, 64.0, 67.0]);
+    }
+
+    #[test]
+    fn test_invert_chord() {
+        let mut notes = vec![60.0, 64.0, 67.0];
+        invert_chord(&mut notes, 2);
+        assert_eq!(notes, vec![67.0, 72.0, 76.0]);
+        invert_chord(&mut notes, -2);
+        assert_eq!(notes, vec![60.0, 64.0, 67.0]);
+    }
+
+    #[test]
+    fn test_voicing_pattern_keeps_note_names() {
+        let chords = crate::mini_notation_v3::parse_mini_notation("[c4,e4,g4]");
+        let inverted = inversion_pattern(chords, Pattern::pure("1".to_string()));
+
+        let state = State {
+            span: TimeSpan::new(Fraction::new(0, 1), Fraction::new(1, 1)),
+            controls: HashMap::new(),
+        };
+
+        let mut values: Vec<String> = inverted
+            .query(&state)
+            .into_iter()
+            .map(|h| h.value)
+            .collect();
+        values.sort();
+        assert_eq!(values, vec!["c5", "e4", "g4"]);
+    }
 }
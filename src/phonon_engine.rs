@@ -0,0 +1,389 @@
+//! Phonon as a library: build an engine from a program, then render it or
+//! play it
+//!
+//! ```no_run
+//! use phonon::phonon_engine::PhononEngine;
+//!
+//! let mut engine = PhononEngine::builder()
+//!     .sample_rate(48000)
+//!     .channels(2)
+//!     .sample_paths(["/home/me/samples"])
+//!     .code("tempo: 0.5\nout $ s \"bd sn\"")
+//!     .build()?;
+//! let one_cycle = engine.render_cycles(1.0); // interleaved stereo
+//!
+//! let stream = engine.stream()?; // or play it on the default device
+//! stream.load("tempo: 0.5\nout $ s \"bd*2 hh*4\"")?;
+//! # Ok::<(), String>(())
+//! ```
+//!
+//! The builder does what the CLI does by hand: checks the settings as an
+//! [`EngineConfig`], parses and compiles the program, and points the graph's
+//! sample bank at `sample_paths` ahead of the usual locations (see
+//! [`SampleBank::with_extra_dirs`](crate::sample_loader::SampleBank::with_extra_dirs)).
+//! As in `phonon live`, a statement that doesn't parse is skipped rather
+//! than failing the whole program; [`PhononEngine::skipped`] says which.
+//!
+//! [`PhononEngine::render`] runs the graph offline, block by block, and
+//! [`PhononEngine::stream`] hands it to a render thread feeding an output
+//! device, the way `phonon live` plays. Loading a new program into either
+//! carries the cycle position, effect tails and voices across.
+
+use crate::compositional_compiler::compile_program;
+use crate::compositional_parser::parse_program_recovering;
+use crate::engine_config::EngineConfig;
+use crate::live_render::LiveRender;
+use crate::render_swap::{render_swap_channel_default, Cmd, CommandSender};
+use crate::render_watchdog::RenderWatchdog;
+use crate::unified_graph::{GraphShape, UnifiedSignalGraph};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Settings for a [`PhononEngine`], from [`PhononEngine::builder`]
+#[derive(Debug, Clone)]
+pub struct PhononEngineBuilder {
+    sample_rate: u32,
+    block_size: Option<usize>,
+    channels: usize,
+    sample_paths: Vec<PathBuf>,
+    code: String,
+    device: Option<String>,
+}
+
+impl Default for PhononEngineBuilder {
+    fn default() -> Self {
+        Self {
+            sample_rate: 44100,
+            block_size: None,
+            channels: 2,
+            sample_paths: Vec::new(),
+            code: String::new(),
+            device: None,
+        }
+    }
+}
+
+impl PhononEngineBuilder {
+    /// Sample rate in Hz (44100 by default)
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Frames rendered per block (see [`EngineConfig`])
+    pub fn block_size(mut self, frames: usize) -> Self {
+        self.block_size = Some(frames);
+        self
+    }
+
+    /// 1 for mono (the left of the main mix) or 2, the default, for stereo
+    pub fn channels(mut self, channels: usize) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Directories searched for samples after the bundled ones and before
+    /// `~/phonon/samples`
+    pub fn sample_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.sample_paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The program to play (silence if never given)
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code = code.into();
+        self
+    }
+
+    /// Output device for [`PhononEngine::stream`], by full or partial name
+    /// (the system default if unset)
+    pub fn device(mut self, name: impl Into<String>) -> Self {
+        self.device = Some(name.into());
+        self
+    }
+
+    /// Check the settings and compile the program. Fails on a bad setting,
+    /// a program none of which parses, or a compile error
+    pub fn build(self) -> Result<PhononEngine, String> {
+        let config = EngineConfig::new(self.sample_rate as f32, self.block_size)?
+            .with_channels(self.channels)?;
+        let (graph, skipped) = compile(&self.code, config.sample_rate, &self.sample_paths)?;
+        Ok(PhononEngine {
+            config,
+            graph,
+            skipped,
+            sample_paths: self.sample_paths,
+            device: self.device,
+        })
+    }
+}
+
+/// Parse and compile `code` into a graph searching `sample_paths`, with the
+/// diagnostics of the statements that were skipped
+fn compile(
+    code: &str,
+    sample_rate: f32,
+    sample_paths: &[PathBuf],
+) -> Result<(UnifiedSignalGraph, Vec<String>), String> {
+    let (statements, skipped) = parse_program_recovering(code);
+    let skipped: Vec<String> = skipped.iter().map(ToString::to_string).collect();
+    if statements.is_empty() && !skipped.is_empty() {
+        return Err(skipped.join("\n"));
+    }
+    let mut graph = compile_program(statements, sample_rate, None)?;
+    if !sample_paths.is_empty() {
+        graph.set_sample_paths(sample_paths);
+    }
+    Ok((graph, skipped))
+}
+
+/// A compiled program with its settings, rendered offline or played with
+/// [`stream`](Self::stream)
+pub struct PhononEngine {
+    config: EngineConfig,
+    graph: UnifiedSignalGraph,
+    skipped: Vec<String>,
+    sample_paths: Vec<PathBuf>,
+    device: Option<String>,
+}
+
+impl PhononEngine {
+    pub fn builder() -> PhononEngineBuilder {
+        PhononEngineBuilder::default()
+    }
+
+    pub fn config(&self) -> EngineConfig {
+        self.config
+    }
+
+    /// Diagnostics for the statements of the current program that didn't
+    /// parse and were left out
+    pub fn skipped(&self) -> &[String] {
+        &self.skipped
+    }
+
+    /// The compiled graph, for anything the engine doesn't cover
+    pub fn graph(&self) -> &UnifiedSignalGraph {
+        &self.graph
+    }
+
+    pub fn graph_mut(&mut self) -> &mut UnifiedSignalGraph {
+        &mut self.graph
+    }
+
+    /// Replace the program, carrying on from the current cycle with effect
+    /// tails, the state of unchanged buses and voices (fading) from the old
+    /// one. On an error the old program stays
+    pub fn load(&mut self, code: &str) -> Result<(), String> {
+        let (mut next, skipped) = compile(code, self.config.sample_rate, &self.sample_paths)?;
+        // The offline half of `RenderGraph::absorb_state`, which would switch
+        // the graph to wall-clock timing
+        next.transfer_fx_states(&self.graph);
+        next.transfer_bus_states(&self.graph);
+        next.set_cycle_position(self.graph.get_cycle_position());
+        next.count_origin()
+            .start_at(self.graph.get_cycle_position());
+        next.transfer_voice_manager(self.graph.take_voice_manager());
+        self.graph = next;
+        self.skipped = skipped;
+        Ok(())
+    }
+
+    /// The next `frames` frames, interleaved when stereo
+    pub fn render(&mut self, frames: usize) -> Vec<f32> {
+        let mut out = Vec::with_capacity(frames * self.config.channels);
+        let mut block = vec![0.0; self.config.block_samples(2)];
+        let mut left = frames;
+        while left > 0 {
+            let n = left.min(self.config.block_size);
+            let stereo = &mut block[..n * 2];
+            stereo.fill(0.0);
+            self.graph.process_buffer(stereo);
+            match self.config.channels {
+                1 => out.extend(stereo.iter().step_by(2)),
+                _ => out.extend_from_slice(stereo),
+            }
+            left -= n;
+        }
+        out
+    }
+
+    /// The next `seconds` of audio, as [`render`](Self::render)
+    pub fn render_seconds(&mut self, seconds: f64) -> Vec<f32> {
+        self.render((seconds * self.config.sample_rate as f64).round() as usize)
+    }
+
+    /// The next `cycles` cycles at the program's tempo, as
+    /// [`render`](Self::render)
+    pub fn render_cycles(&mut self, cycles: f64) -> Vec<f32> {
+        self.render_seconds(cycles / self.graph.get_cps() as f64)
+    }
+
+    /// Play on the output device (see [`PhononEngineBuilder::device`]) until
+    /// the returned stream is dropped. The graph moves to the device's
+    /// sample rate if it differs
+    pub fn stream(self) -> Result<EngineStream, String> {
+        use crate::audio_device::{build_output_stream_converted, select_output_device};
+        use cpal::traits::{DeviceTrait, StreamTrait};
+        use ringbuf::traits::{Consumer, Observer, Producer, Split};
+        use ringbuf::HeapRb;
+
+        let host = cpal::default_host();
+        let device = select_output_device(&host, self.device.as_deref())?;
+        let supported = device
+            .default_output_config()
+            .map_err(|e| format!("No output config for the device: {}", e))?;
+        let device_channels = supported.channels() as usize;
+        let config = EngineConfig {
+            sample_rate: supported.sample_rate().0 as f32,
+            ..self.config
+        };
+
+        let mut graph = self.graph;
+        graph.set_sample_rate(config.sample_rate);
+        graph.enable_wall_clock_timing();
+        graph.preload_samples();
+        graph.preload_plugins();
+        let shape = graph.shape();
+
+        let (cmd_tx, render_swap, mut graveyard) =
+            render_swap_channel_default::<UnifiedSignalGraph>();
+        let running = Arc::new(AtomicBool::new(true));
+
+        // Retired graphs are dropped here, off the render thread
+        let janitor_running = Arc::clone(&running);
+        std::thread::spawn(move || {
+            while janitor_running.load(Ordering::Relaxed) {
+                if graveyard.collect() == 0 {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+            }
+            graveyard.collect();
+        });
+
+        // Stereo blocks, a few device buffers' worth ahead of the callback
+        let ring = HeapRb::<f32>::new(config.block_samples(2) * 8);
+        let (mut producer, mut consumer) = ring.split();
+
+        // The render thread owns the graph and plays it as `phonon live` does:
+        // new programs arrive through `render_swap` between blocks. Watchdog
+        // reports go to the log (stderr unless a sink is set)
+        let (reports, _) = std::sync::mpsc::channel();
+        let mut live = LiveRender::new(render_swap, Box::new(graph), RenderWatchdog::new(reports));
+        let render_running = Arc::clone(&running);
+        std::thread::spawn(move || {
+            crate::denormals::enable_flush_to_zero();
+            let mut block = vec![0.0f32; config.block_samples(2)];
+            while render_running.load(Ordering::Relaxed) {
+                if producer.vacant_len() < block.len() {
+                    std::thread::sleep(Duration::from_micros(100));
+                    continue;
+                }
+                live.begin_block();
+                live.render_block(&mut block);
+                producer.push_slice(&block);
+            }
+        });
+
+        // Stereo to the device's channels: mono gets the left, extra
+        // channels stay silent
+        let channels = config.channels;
+        let mut frame = [0.0f32; 2];
+        let stream = build_output_stream_converted(
+            &device,
+            &cpal::StreamConfig {
+                channels: supported.channels(),
+                sample_rate: supported.sample_rate(),
+                buffer_size: cpal::BufferSize::Default,
+            },
+            supported.sample_format(),
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for out in data.chunks_mut(device_channels) {
+                    if consumer.pop_slice(&mut frame) < 2 {
+                        frame = [0.0; 2];
+                    }
+                    for (channel, sample) in out.iter_mut().enumerate() {
+                        *sample = match (channel, channels) {
+                            (0, _) => frame[0],
+                            (1, 2) => frame[1],
+                            _ => 0.0,
+                        };
+                    }
+                }
+            },
+            |err| crate::rt_log::log(format_args!("Audio stream error: {}", err)),
+        )
+        .map_err(|e| format!("Failed to open the output stream: {}", e))?;
+        stream
+            .play()
+            .map_err(|e| format!("Failed to start the output stream: {}", e))?;
+
+        Ok(EngineStream {
+            config,
+            sample_paths: self.sample_paths,
+            commands: Mutex::new((cmd_tx, shape)),
+            running,
+            _stream: stream,
+        })
+    }
+}
+
+/// A [`PhononEngine`] playing on an output device; dropping it stops the
+/// sound and its threads
+pub struct EngineStream {
+    config: EngineConfig,
+    sample_paths: Vec<PathBuf>,
+    /// Sends to the render thread, with the shape of the graph it plays
+    commands: Mutex<(CommandSender<UnifiedSignalGraph>, GraphShape)>,
+    running: Arc<AtomicBool>,
+    _stream: cpal::Stream,
+}
+
+impl EngineStream {
+    /// The settings playing, at the device's sample rate
+    pub fn config(&self) -> EngineConfig {
+        self.config
+    }
+
+    /// Swap in a new program at the next block, as a save does in
+    /// `phonon live`. Returns the diagnostics of any statements skipped
+    pub fn load(&self, code: &str) -> Result<Vec<String>, String> {
+        let (mut graph, skipped) = compile(code, self.config.sample_rate, &self.sample_paths)?;
+        graph.enable_wall_clock_timing();
+        graph.preload_samples();
+        graph.preload_plugins();
+
+        // The render thread takes commands every block, so a full ring
+        // clears within a few milliseconds
+        let mut commands = self.commands.lock().map_err(|e| e.to_string())?;
+        let (commands, playing_shape) = &mut *commands;
+        graph.plan_bus_state_transfer(playing_shape);
+        let shape = graph.shape();
+        let mut pending = Cmd::Swap(Box::new(graph));
+        for _ in 0..50 {
+            match commands.send(pending) {
+                Ok(()) => {
+                    *playing_shape = shape;
+                    return Ok(skipped);
+                }
+                Err(cmd) => {
+                    pending = cmd;
+                    std::thread::sleep(Duration::from_micros(500));
+                }
+            }
+        }
+        Err("the render thread isn't taking new programs".to_string())
+    }
+}
+
+impl Drop for EngineStream {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
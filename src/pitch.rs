@@ -0,0 +1,433 @@
+//! Pitch conversions: note names and MIDI numbers to frequencies
+//!
+//! Every note → Hz conversion in the crate (pattern note names, synth notes,
+//! MIDI in and out) goes through [`midi_to_hz`], so the reference pitch is set
+//! in one place with [`set_a4`], a global transposition with
+//! [`set_transpose`], and the tuning with [`set_tuning`]: equal divisions of
+//! the octave (`19edo`) or a Scala `.scl` scale with an optional `.kbm`
+//! keyboard mapping.
+
+use crate::pattern_tonal::note_to_midi;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Default reference pitch: A4 (MIDI 69) in Hz
+pub const DEFAULT_A4: f64 = 440.0;
+
+/// Current A4 reference, stored as `f64` bits
+static A4_HZ: AtomicU64 = AtomicU64::new(0x407B_8000_0000_0000); // 440.0
+
+/// The frequency of A4 (MIDI 69) that every conversion is tuned to
+pub fn a4() -> f64 {
+    f64::from_bits(A4_HZ.load(Ordering::Relaxed))
+}
+
+/// Set the frequency of A4. Non-positive or non-finite values are ignored
+pub fn set_a4(hz: f64) {
+    if hz.is_finite() && hz > 0.0 {
+        A4_HZ.store(hz.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Global transposition in semitones, stored as `f64` bits
+static TRANSPOSE: AtomicU64 = AtomicU64::new(0); // 0.0
+
+/// The global transposition in semitones
+pub fn transpose() -> f64 {
+    f64::from_bits(TRANSPOSE.load(Ordering::Relaxed))
+}
+
+/// Transpose every note by `semitones` (fractional values detune)
+pub fn set_transpose(semitones: f64) {
+    if semitones.is_finite() {
+        TRANSPOSE.store(semitones.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Frequency ratio of the transposition
+#[inline]
+pub fn transpose_ratio() -> f64 {
+    2.0_f64.powf(transpose() / 12.0)
+}
+
+/// Playback-speed factor for pitched samples: the transposition and the A4
+/// reference's offset from 440 Hz
+pub fn sample_speed_ratio() -> f64 {
+    transpose_ratio() * a4() / DEFAULT_A4
+}
+
+/// Tuning used by [`midi_to_hz`]; None is standard 12-tone equal temperament
+static TUNING: RwLock<Option<Arc<Tuning>>> = RwLock::new(None);
+
+/// Whether `TUNING` is set, so standard tuning skips the lock
+static TUNED: AtomicBool = AtomicBool::new(false);
+
+/// Set the tuning every note → Hz conversion uses (None: 12-tone equal)
+pub fn set_tuning(tuning: Option<Arc<Tuning>>) {
+    if let Ok(mut current) = TUNING.write() {
+        TUNED.store(tuning.is_some(), Ordering::Relaxed);
+        *current = tuning;
+    }
+}
+
+/// The tuning set with [`set_tuning`], if any
+pub fn tuning() -> Option<Arc<Tuning>> {
+    if !TUNED.load(Ordering::Relaxed) {
+        return None;
+    }
+    TUNING.read().ok().and_then(|tuning| tuning.clone())
+}
+
+/// Frequency of a (possibly fractional) MIDI note number, transposed
+#[inline]
+pub fn midi_to_hz(midi: f64) -> f64 {
+    if TUNED.load(Ordering::Relaxed) {
+        if let Some(tuning) = tuning() {
+            return tuning.midi_to_hz(midi, a4()) * transpose_ratio();
+        }
+    }
+    a4() * 2.0_f64.powf((midi + transpose() - 69.0) / 12.0)
+}
+
+/// MIDI note number (fractional) of a frequency, in 12-tone equal
+/// temperament around the A4 reference
+#[inline]
+pub fn hz_to_midi(hz: f64) -> f64 {
+    69.0 + 12.0 * (hz / a4()).log2()
+}
+
+/// The standard MIDI key closest in pitch to `note` under the current
+/// tuning, for sending retuned notes to 12-tone instruments
+pub fn nearest_standard_key(note: u8) -> u8 {
+    hz_to_midi(midi_to_hz(note as f64)).round().clamp(0.0, 127.0) as u8
+}
+
+/// How MIDI note numbers map to frequencies
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tuning {
+    /// `n` equal divisions of the octave (`12edo` is standard tuning), with
+    /// MIDI 69 on the A4 reference
+    Edo(f64),
+    /// A Scala scale mapped onto the keys by a keyboard mapping
+    Scale(ScalaTuning),
+}
+
+impl Tuning {
+    /// Parse an equal division: `19edo`, `31edo`
+    pub fn parse_edo(spec: &str) -> Result<Self, String> {
+        spec.strip_suffix("edo")
+            .and_then(|n| n.parse::<f64>().ok())
+            .filter(|n| *n > 0.0 && n.is_finite())
+            .map(Tuning::Edo)
+            .ok_or_else(|| format!("tuning: expected a step count like 19edo, got '{}'", spec))
+    }
+
+    /// Load a Scala scale, with a keyboard mapping if given
+    pub fn load_scala(scl: &str, kbm: Option<&str>) -> Result<Self, String> {
+        let read = |path: &str| {
+            std::fs::read_to_string(path).map_err(|e| format!("tuning: can't read {}: {}", path, e))
+        };
+        let (description, degrees) =
+            parse_scl(&read(scl)?).map_err(|e| format!("tuning: {}: {}", scl, e))?;
+        let mapping = match kbm {
+            Some(path) => parse_kbm(&read(path)?).map_err(|e| format!("tuning: {}: {}", path, e))?,
+            None => KeyboardMapping::default(),
+        };
+        Ok(Tuning::Scale(ScalaTuning {
+            description,
+            degrees,
+            mapping,
+        }))
+    }
+
+    /// Frequency of a (possibly fractional) MIDI note, with `a4` as the
+    /// reference unless a keyboard mapping sets its own
+    pub fn midi_to_hz(&self, midi: f64, a4: f64) -> f64 {
+        match self {
+            Tuning::Edo(steps) => a4 * 2.0_f64.powf((midi - 69.0) / steps),
+            Tuning::Scale(scale) => scale.midi_to_hz(midi, a4),
+        }
+    }
+}
+
+/// A Scala scale (`.scl`) and the keyboard mapping (`.kbm`) placing it on keys
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalaTuning {
+    pub description: String,
+    /// Degrees 1..=n in cents above the tonic; the last one is the period
+    pub degrees: Vec<f64>,
+    pub mapping: KeyboardMapping,
+}
+
+impl ScalaTuning {
+    /// Cents of a scale degree above degree 0, across periods
+    fn degree_cents(&self, degree: i64) -> f64 {
+        let count = self.degrees.len() as i64;
+        let period = self.degrees[self.degrees.len() - 1];
+        let step = degree.rem_euclid(count);
+        let within = if step == 0 {
+            0.0
+        } else {
+            self.degrees[step as usize - 1]
+        };
+        degree.div_euclid(count) as f64 * period + within
+    }
+
+    /// Cents of a key above the mapping's middle note. Unmapped keys (`x`)
+    /// sound the degree matching their position in the mapping
+    fn key_cents(&self, key: i64) -> f64 {
+        let mapping = &self.mapping;
+        let offset = key - mapping.middle_note as i64;
+        if mapping.size == 0 {
+            return self.degree_cents(offset);
+        }
+        let size = mapping.size as i64;
+        let index = offset.rem_euclid(size) as usize;
+        let degree = mapping
+            .keys
+            .get(index)
+            .copied()
+            .flatten()
+            .unwrap_or(index) as i64;
+        let octave_degree = match mapping.octave_degree {
+            0 => self.degrees.len(),
+            n => n,
+        } as i64;
+        self.degree_cents(offset.div_euclid(size) * octave_degree + degree)
+    }
+
+    fn midi_to_hz(&self, midi: f64, a4: f64) -> f64 {
+        let key = midi.floor();
+        let frac = midi - key;
+        let low = self.key_cents(key as i64);
+        let cents = if frac > 0.0 {
+            low + (self.key_cents(key as i64 + 1) - low) * frac
+        } else {
+            low
+        };
+        let reference = self.key_cents(self.mapping.reference_note as i64);
+        let reference_freq = self.mapping.reference_freq.unwrap_or(a4);
+        reference_freq * 2.0_f64.powf((cents - reference) / 1200.0)
+    }
+}
+
+/// A Scala keyboard mapping (`.kbm`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardMapping {
+    /// Keys in one repeat of the mapping; 0 maps consecutive keys to
+    /// consecutive degrees
+    pub size: usize,
+    /// Key that plays degree 0
+    pub middle_note: i32,
+    /// Key tuned to `reference_freq`
+    pub reference_note: i32,
+    /// None uses the A4 reference (with `reference_note` 69)
+    pub reference_freq: Option<f64>,
+    /// Degrees one repeat of the mapping moves up (0: the scale length)
+    pub octave_degree: usize,
+    /// Degree played by each key of a repeat, None when unmapped
+    pub keys: Vec<Option<usize>>,
+}
+
+impl Default for KeyboardMapping {
+    /// Degree 0 on middle C, A4 on key 69, as Scala does without a `.kbm`
+    fn default() -> Self {
+        Self {
+            size: 0,
+            middle_note: 60,
+            reference_note: 69,
+            reference_freq: None,
+            octave_degree: 0,
+            keys: Vec::new(),
+        }
+    }
+}
+
+/// Lines of a Scala file that aren't `!` comments
+fn scala_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().map(str::trim).filter(|line| !line.starts_with('!'))
+}
+
+/// Parse a `.scl` file into its description and degrees in cents
+pub fn parse_scl(text: &str) -> Result<(String, Vec<f64>), String> {
+    let mut lines = scala_lines(text);
+    let description = lines.next().ok_or("empty scale file")?.to_string();
+    let count: usize = lines
+        .next()
+        .and_then(|line| line.split_whitespace().next())
+        .and_then(|n| n.parse().ok())
+        .ok_or("missing note count")?;
+    let degrees = lines
+        .filter(|line| !line.is_empty())
+        .take(count)
+        .map(parse_scl_pitch)
+        .collect::<Result<Vec<_>, _>>()?;
+    if count == 0 || degrees.len() != count {
+        return Err(format!("expected {} pitches, found {}", count, degrees.len()));
+    }
+    Ok((description, degrees))
+}
+
+/// One `.scl` pitch: cents when it has a `.`, otherwise a ratio (`3/2`, `2`)
+fn parse_scl_pitch(line: &str) -> Result<f64, String> {
+    let word = line.split_whitespace().next().unwrap_or_default();
+    let invalid = || format!("invalid pitch '{}'", word);
+    if word.contains('.') {
+        return word.parse().map_err(|_| invalid());
+    }
+    let (num, den) = word.split_once('/').unwrap_or((word, "1"));
+    let num: f64 = num.parse().map_err(|_| invalid())?;
+    let den: f64 = den.parse().map_err(|_| invalid())?;
+    if num <= 0.0 || den <= 0.0 {
+        return Err(invalid());
+    }
+    Ok(1200.0 * (num / den).log2())
+}
+
+/// Parse a `.kbm` keyboard mapping
+pub fn parse_kbm(text: &str) -> Result<KeyboardMapping, String> {
+    let mut lines = scala_lines(text).filter(|line| !line.is_empty());
+    let mut field = |name: &str| {
+        lines
+            .next()
+            .and_then(|line| line.split_whitespace().next())
+            .map(str::to_string)
+            .ok_or_else(|| format!("missing {}", name))
+    };
+    let number = |value: String, name: &str| {
+        value
+            .parse::<f64>()
+            .map_err(|_| format!("invalid {} '{}'", name, value))
+    };
+    let size = number(field("map size")?, "map size")? as usize;
+    let _first_note = field("first note")?;
+    let _last_note = field("last note")?;
+    let middle_note = number(field("middle note")?, "middle note")? as i32;
+    let reference_note = number(field("reference note")?, "reference note")? as i32;
+    let reference_freq = number(field("reference frequency")?, "reference frequency")?;
+    let octave_degree = number(field("octave degree")?, "octave degree")? as usize;
+    let mut keys = Vec::with_capacity(size);
+    for _ in 0..size {
+        match field("mapping entry") {
+            Ok(entry) if entry == "x" => keys.push(None),
+            Ok(entry) => keys.push(Some(number(entry, "mapping entry")? as usize)),
+            // Keys past the listed entries are unmapped
+            Err(_) => keys.push(None),
+        }
+    }
+    if reference_freq <= 0.0 {
+        return Err("reference frequency must be above 0".to_string());
+    }
+    Ok(KeyboardMapping {
+        size,
+        middle_note,
+        reference_note,
+        reference_freq: Some(reference_freq),
+        octave_degree,
+        keys,
+    })
+}
+
+/// Whether `word` is a note name with an octave: `c3`, `a#4`, `ef5`, `cs-1`.
+/// Bare letters (`a`, `e`) are left to variables and sample names
+pub fn is_note_literal(word: &str) -> bool {
+    let lower = word.to_ascii_lowercase();
+    let mut chars = lower.chars().peekable();
+    if !matches!(chars.next(), Some('a'..='g')) {
+        return false;
+    }
+    if matches!(chars.peek(), Some('#' | 's' | 'f')) {
+        chars.next();
+    }
+    if chars.peek() == Some(&'-') {
+        chars.next();
+    }
+    let octave: String = chars.collect();
+    !octave.is_empty() && octave.chars().all(|c| c.is_ascii_digit()) && note_to_midi(word).is_some()
+}
+
+/// Frequency of a note name (`c4`, `a#3`) or MIDI number string
+pub fn note_to_hz(note: &str) -> Option<f64> {
+    note_to_midi(note).map(|midi| midi_to_hz(midi as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_reference() {
+        assert_eq!(f64::from_bits(0x407B_8000_0000_0000), DEFAULT_A4);
+        assert!((midi_to_hz(69.0) - 440.0).abs() < 1e-9);
+        assert!((midi_to_hz(60.0) - 261.6256).abs() < 1e-3);
+        assert!((hz_to_midi(880.0) - 81.0).abs() < 1e-9);
+        assert!((note_to_hz("a#4").unwrap() - 466.1638).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_edo() {
+        let tuning = Tuning::parse_edo("19edo").unwrap();
+        assert!((tuning.midi_to_hz(69.0, 440.0) - 440.0).abs() < 1e-9);
+        assert!((tuning.midi_to_hz(88.0, 440.0) - 880.0).abs() < 1e-9);
+        assert!(Tuning::parse_edo("0edo").is_err());
+        assert!(Tuning::parse_edo("19").is_err());
+    }
+
+    #[test]
+    fn test_scala_scale() {
+        let scl = "! just.scl\n!\nJust major\n 7\n!\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1\n";
+        let (description, degrees) = parse_scl(scl).unwrap();
+        assert_eq!(description, "Just major");
+        assert_eq!(degrees.len(), 7);
+        assert!((degrees[3] - 701.955).abs() < 1e-3);
+
+        // Without a mapping, keys step through the degrees from middle C, so
+        // key 69 is degree 9 (a 5/4 above the octave) and sits on 440
+        let tuning = Tuning::Scale(ScalaTuning {
+            description,
+            degrees,
+            mapping: KeyboardMapping::default(),
+        });
+        assert!((tuning.midi_to_hz(69.0, 440.0) - 440.0).abs() < 1e-9);
+        let c4 = tuning.midi_to_hz(60.0, 440.0);
+        assert!((c4 - 176.0).abs() < 1e-9);
+        assert!((tuning.midi_to_hz(64.0, 440.0) - c4 * 3.0 / 2.0).abs() < 1e-9);
+        assert!((tuning.midi_to_hz(67.0, 440.0) - c4 * 2.0).abs() < 1e-9);
+        assert!((tuning.midi_to_hz(53.0, 440.0) - c4 / 2.0).abs() < 1e-9);
+
+        assert!(parse_scl("Broken\n3\n9/8\n").is_err());
+        assert!(parse_scl("Broken\n1\nabc\n").is_err());
+    }
+
+    #[test]
+    fn test_keyboard_mapping() {
+        // 12 keys carry a 7-note scale: white keys only, 261.6256 Hz on C4
+        let kbm = "! white.kbm\n12\n0\n127\n60\n60\n261.6256\n7\n\
+                   0\nx\n1\nx\n2\n3\nx\n4\nx\n5\nx\n6\n";
+        let mapping = parse_kbm(kbm).unwrap();
+        assert_eq!(mapping.keys[1], None);
+        assert_eq!(mapping.keys[11], Some(6));
+        let (description, degrees) =
+            parse_scl("Just\n7\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1\n").unwrap();
+        let tuning = Tuning::Scale(ScalaTuning {
+            description,
+            degrees,
+            mapping,
+        });
+        let c4 = tuning.midi_to_hz(60.0, 440.0);
+        assert!((c4 - 261.6256).abs() < 1e-9);
+        assert!((tuning.midi_to_hz(67.0, 440.0) - c4 * 1.5).abs() < 1e-9);
+        assert!((tuning.midi_to_hz(72.0, 440.0) - c4 * 2.0).abs() < 1e-9);
+        assert!((tuning.midi_to_hz(59.0, 440.0) - c4 * 15.0 / 16.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_note_literals() {
+        for word in ["c3", "a#4", "e5", "ef2", "cs-1", "G4"] {
+            assert!(is_note_literal(word), "{}", word);
+        }
+        for word in ["a", "e", "bd", "c", "cs", "h4", "c3po", "sine"] {
+            assert!(!is_note_literal(word), "{}", word);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Plucked string voice: `s "pluck"` plays a Karplus-Strong string
+//!
+//! ```phonon
+//! out $ s "pluck*4" # note "<c3 e3 g3>" # exciter "noise|pick|bow" # decay 0.9
+//! ```
+//!
+//! Each note sets a delay line ringing at its pitch, losing a little of its
+//! top end every time round the loop. `# exciter` picks what starts it:
+//! `noise` (the default) fills the string with a burst of white noise,
+//! `pick` displaces it into a plectrum's triangle for a rounder, more pitched
+//! attack, and `bow` drives it with friction for a quarter second so the note
+//! swells in. `# decay` (0-1, default [`DEFAULT_DECAY`]) is how long it rings,
+//! up to 4 seconds, and `# damping` (0-1, default [`DEFAULT_DAMPING`]) how
+//! quickly its overtones die away.
+//!
+//! Like the West Coast voice ([`crate::westcoast`]), each note is rendered
+//! when it triggers and played as a sample voice.
+
+use crate::sample_loader::StereoSample;
+use crate::unified_graph::NoiseRng;
+
+/// The sample name that plays the voice
+pub const VOICE_NAME: &str = "pluck";
+/// Pitch of note 0 (C4); `# note` and `# speed` scale it as they would a
+/// sample's playback rate
+pub const BASE_HZ: f32 = 261.63;
+/// `# decay` when none is given
+pub const DEFAULT_DECAY: f32 = 0.5;
+/// `# damping` when none is given
+pub const DEFAULT_DAMPING: f32 = 0.5;
+/// Ring time, in seconds, at `decay 0` and `decay 1`
+const MIN_RING: f32 = 0.1;
+const MAX_RING: f32 = 4.0;
+/// Lowest pitch the string is tuned to
+const MIN_HZ: f32 = 20.0;
+/// Where along the string the pick displaces it, as a fraction of its length
+const PICK_POSITION: f32 = 0.2;
+/// How long the bow drives the string, and how long it takes to bite
+const BOW_SECONDS: f32 = 0.25;
+const BOW_ATTACK: f32 = 0.06;
+/// Peak level of a bowed note
+const BOW_PEAK: f32 = 0.7;
+/// Output level below which a rung-out string has stopped
+const SILENT: f32 = 1e-4;
+
+/// What sets the string moving
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Exciter {
+    /// A burst of white noise, the classic Karplus-Strong pluck
+    #[default]
+    Noise,
+    /// A plectrum's triangle displacement
+    Pick,
+    /// Sustained friction
+    Bow,
+}
+
+impl Exciter {
+    /// The names `# exciter` takes
+    pub const NAMES: [&'static str; 3] = ["noise", "pick", "bow"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "noise" => Some(Exciter::Noise),
+            "pick" => Some(Exciter::Pick),
+            "bow" => Some(Exciter::Bow),
+            _ => None,
+        }
+    }
+}
+
+/// One plucked (or bowed) note
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pluck {
+    /// Pitch in Hz
+    pub freq: f32,
+    pub exciter: Exciter,
+    /// How long the string rings, 0-1
+    pub decay: f32,
+    /// How quickly the overtones fade, 0-1
+    pub damping: f32,
+}
+
+impl Pluck {
+    pub fn new(freq: f32, exciter: Exciter, decay: f32, damping: f32) -> Self {
+        Self {
+            freq: freq.max(MIN_HZ),
+            exciter,
+            decay: decay.clamp(0.0, 1.0),
+            damping: damping.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Seconds the string takes to fall by 60 dB
+    pub fn ring_time(&self) -> f32 {
+        MIN_RING + self.decay * (MAX_RING - MIN_RING)
+    }
+
+    /// Render the note until the string has rung out
+    pub fn render(&self, sample_rate: f32, rng: &mut NoiseRng) -> StereoSample {
+        // The loop filter averages `a` of the previous sample in, which delays
+        // the loop by `a` samples, so the delay line is that much shorter
+        let a = self.damping * 0.5;
+        let delay = (sample_rate / self.freq - a).max(1.0);
+        let size = delay.ceil() as usize + 2;
+        let gain = 10f32.powf(-3.0 / (self.ring_time() * sample_rate));
+        let bow_len = match self.exciter {
+            Exciter::Bow => (BOW_SECONDS * sample_rate) as usize,
+            _ => 0,
+        };
+        let max_len = bow_len + (self.ring_time() * sample_rate) as usize;
+
+        let mut line: Vec<f32> = match self.exciter {
+            Exciter::Noise => (0..size).map(|_| rng.next_bipolar()).collect(),
+            Exciter::Pick => (0..size)
+                .map(|j| {
+                    let x = j as f32 / size as f32;
+                    let triangle = if x < PICK_POSITION {
+                        x / PICK_POSITION
+                    } else {
+                        (1.0 - x) / (1.0 - PICK_POSITION)
+                    };
+                    // With a touch of noise from the plectrum
+                    triangle + 0.1 * rng.next_bipolar()
+                })
+                .collect(),
+            Exciter::Bow => vec![0.0; size],
+        };
+        // The loop keeps any DC it's given for as long as the note rings
+        let mean = line.iter().sum::<f32>() / size as f32;
+        line.iter_mut().for_each(|x| *x -= mean);
+
+        let mut out = Vec::with_capacity(max_len);
+        let mut write = 0;
+        let mut prev = 0.0;
+        let mut quiet = 0;
+        let mut friction = 0.0;
+        while out.len() < max_len && (out.len() < bow_len || quiet < size) {
+            let read = (write as f32 - delay).rem_euclid(size as f32);
+            let i = read as usize % size;
+            let frac = read.fract();
+            let x = line[i] * (1.0 - frac) + line[(i + 1) % size] * frac;
+            let y = ((1.0 - a) * x + a * prev) * gain;
+            prev = x;
+
+            let bowing = out.len() < bow_len;
+            line[write] = if bowing {
+                let t = out.len() as f32 / sample_rate;
+                let bite = (t / BOW_ATTACK).min(1.0) * (1.0 - t / BOW_SECONDS).min(0.2) * 5.0;
+                // Differenced noise, so the bow adds no DC either
+                let noise = rng.next_bipolar();
+                let grain = noise - friction;
+                friction = noise;
+                y + 0.05 * bite * grain
+            } else {
+                y
+            };
+            write = (write + 1) % size;
+
+            quiet = if y.abs() < SILENT { quiet + 1 } else { 0 };
+            out.push(y);
+        }
+
+        if self.exciter == Exciter::Bow {
+            let peak = out.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+            if peak > 0.0 {
+                out.iter_mut().for_each(|s| *s *= BOW_PEAK / peak);
+            }
+        }
+        StereoSample::mono(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44100.0;
+
+    fn samples(note: Pluck) -> Vec<f32> {
+        let rendered = note.render(SR, &mut NoiseRng::from_seed(7));
+        (0..rendered.len())
+            .map(|i| rendered.get_interpolated(i as f32).0)
+            .collect()
+    }
+
+    fn upward_crossings(samples: &[f32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|w| w[0] <= 0.0 && w[1] > 0.0)
+            .count()
+    }
+
+    fn peak_index(samples: &[f32]) -> usize {
+        (0..samples.len())
+            .max_by(|&a, &b| samples[a].abs().total_cmp(&samples[b].abs()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_string_rings_at_its_pitch() {
+        // Half a second in, only the fundamental is left to count
+        for freq in [110.0, 220.0, 440.0] {
+            let audio = samples(Pluck::new(freq, Exciter::Pick, 0.9, 1.0));
+            let window = &audio[22050..44100];
+            let crossings = upward_crossings(window) as f32 * 2.0;
+            assert!(
+                (crossings - freq).abs() < freq * 0.02,
+                "{} vs {}",
+                crossings,
+                freq
+            );
+        }
+    }
+
+    #[test]
+    fn test_decay_sets_the_length() {
+        let short = samples(Pluck::new(220.0, Exciter::Noise, 0.1, 0.5));
+        let long = samples(Pluck::new(220.0, Exciter::Noise, 0.9, 0.5));
+        assert!(
+            long.len() > 2 * short.len(),
+            "{} vs {}",
+            long.len(),
+            short.len()
+        );
+        assert!(long.len() <= (MAX_RING * SR) as usize);
+        assert!(long.iter().all(|s| s.is_finite() && s.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_bow_swells_in() {
+        let picked = samples(Pluck::new(220.0, Exciter::Pick, 0.5, 0.5));
+        let bowed = samples(Pluck::new(220.0, Exciter::Bow, 0.5, 0.5));
+        assert!(peak_index(&picked) < 441);
+        assert!(peak_index(&bowed) > 2205, "{}", peak_index(&bowed));
+        assert_eq!(Exciter::from_name("bow"), Some(Exciter::Bow));
+        assert_eq!(Exciter::from_name("strum"), None);
+    }
+}
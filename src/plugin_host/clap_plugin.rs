@@ -0,0 +1,673 @@
+//! CLAP effect plugins for `extfx`
+//!
+//! A small host for the CLAP plugin ABI (<https://github.com/free-audio/clap>):
+//! find an installed effect by name, instantiate and activate it, and run it a
+//! block at a time with parameter changes sent as events. CLAP is a plain C
+//! ABI, so this needs no SDK, only `libloading`.
+//!
+//! Loading and activation happen on the control thread (see
+//! `UnifiedSignalGraph::preload_plugins`); only [`ClapEffect::process`] runs on
+//! the render thread, and it doesn't allocate.
+
+use super::external_fx::BlockEffect;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// ---------------------------------------------------------------------------
+// The parts of the CLAP 1.x ABI an effect host needs (clap/include/clap/*.h)
+// ---------------------------------------------------------------------------
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ClapVersion {
+    major: u32,
+    minor: u32,
+    revision: u32,
+}
+
+const CLAP_VERSION: ClapVersion = ClapVersion {
+    major: 1,
+    minor: 2,
+    revision: 0,
+};
+
+#[repr(C)]
+struct ClapPluginEntry {
+    clap_version: ClapVersion,
+    init: unsafe extern "C" fn(plugin_path: *const c_char) -> bool,
+    deinit: unsafe extern "C" fn(),
+    get_factory: unsafe extern "C" fn(factory_id: *const c_char) -> *const c_void,
+}
+
+#[repr(C)]
+struct ClapPluginFactory {
+    get_plugin_count: unsafe extern "C" fn(factory: *const ClapPluginFactory) -> u32,
+    get_plugin_descriptor: unsafe extern "C" fn(
+        factory: *const ClapPluginFactory,
+        index: u32,
+    ) -> *const ClapPluginDescriptor,
+    create_plugin: unsafe extern "C" fn(
+        factory: *const ClapPluginFactory,
+        host: *const ClapHost,
+        plugin_id: *const c_char,
+    ) -> *const ClapPlugin,
+}
+
+#[repr(C)]
+struct ClapPluginDescriptor {
+    clap_version: ClapVersion,
+    id: *const c_char,
+    name: *const c_char,
+    vendor: *const c_char,
+    url: *const c_char,
+    manual_url: *const c_char,
+    support_url: *const c_char,
+    version: *const c_char,
+    description: *const c_char,
+    features: *const *const c_char,
+}
+
+#[repr(C)]
+struct ClapHost {
+    clap_version: ClapVersion,
+    host_data: *mut c_void,
+    name: *const c_char,
+    vendor: *const c_char,
+    url: *const c_char,
+    version: *const c_char,
+    get_extension: unsafe extern "C" fn(host: *const ClapHost, id: *const c_char) -> *const c_void,
+    request_restart: unsafe extern "C" fn(host: *const ClapHost),
+    request_process: unsafe extern "C" fn(host: *const ClapHost),
+    request_callback: unsafe extern "C" fn(host: *const ClapHost),
+}
+
+#[repr(C)]
+struct ClapPlugin {
+    desc: *const ClapPluginDescriptor,
+    plugin_data: *mut c_void,
+    init: unsafe extern "C" fn(plugin: *const ClapPlugin) -> bool,
+    destroy: unsafe extern "C" fn(plugin: *const ClapPlugin),
+    activate: unsafe extern "C" fn(
+        plugin: *const ClapPlugin,
+        sample_rate: f64,
+        min_frames_count: u32,
+        max_frames_count: u32,
+    ) -> bool,
+    deactivate: unsafe extern "C" fn(plugin: *const ClapPlugin),
+    start_processing: unsafe extern "C" fn(plugin: *const ClapPlugin) -> bool,
+    stop_processing: unsafe extern "C" fn(plugin: *const ClapPlugin),
+    reset: unsafe extern "C" fn(plugin: *const ClapPlugin),
+    process: unsafe extern "C" fn(plugin: *const ClapPlugin, process: *const ClapProcess) -> i32,
+    get_extension:
+        unsafe extern "C" fn(plugin: *const ClapPlugin, id: *const c_char) -> *const c_void,
+    on_main_thread: unsafe extern "C" fn(plugin: *const ClapPlugin),
+}
+
+#[repr(C)]
+struct ClapAudioBuffer {
+    data32: *mut *mut f32,
+    data64: *mut *mut f64,
+    channel_count: u32,
+    latency: u32,
+    constant_mask: u64,
+}
+
+#[repr(C)]
+struct ClapProcess {
+    steady_time: i64,
+    frames_count: u32,
+    transport: *const c_void,
+    audio_inputs: *const ClapAudioBuffer,
+    audio_outputs: *mut ClapAudioBuffer,
+    audio_inputs_count: u32,
+    audio_outputs_count: u32,
+    in_events: *const ClapInputEvents,
+    out_events: *const ClapOutputEvents,
+}
+
+/// `clap_process_status` for a failed block
+const CLAP_PROCESS_ERROR: i32 = 0;
+
+#[repr(C)]
+struct ClapEventHeader {
+    size: u32,
+    time: u32,
+    space_id: u16,
+    event_type: u16,
+    flags: u32,
+}
+
+const CLAP_CORE_EVENT_SPACE_ID: u16 = 0;
+const CLAP_EVENT_PARAM_VALUE: u16 = 5;
+
+#[repr(C)]
+struct ClapEventParamValue {
+    header: ClapEventHeader,
+    param_id: u32,
+    cookie: *mut c_void,
+    note_id: i32,
+    port_index: i16,
+    channel: i16,
+    key: i16,
+    value: f64,
+}
+
+#[repr(C)]
+struct ClapInputEvents {
+    ctx: *mut c_void,
+    size: unsafe extern "C" fn(list: *const ClapInputEvents) -> u32,
+    get: unsafe extern "C" fn(list: *const ClapInputEvents, index: u32) -> *const ClapEventHeader,
+}
+
+#[repr(C)]
+struct ClapOutputEvents {
+    ctx: *mut c_void,
+    try_push:
+        unsafe extern "C" fn(list: *const ClapOutputEvents, event: *const ClapEventHeader) -> bool,
+}
+
+const CLAP_NAME_SIZE: usize = 256;
+const CLAP_PATH_SIZE: usize = 1024;
+
+#[repr(C)]
+struct ClapParamInfo {
+    id: u32,
+    flags: u32,
+    cookie: *mut c_void,
+    name: [c_char; CLAP_NAME_SIZE],
+    module: [c_char; CLAP_PATH_SIZE],
+    min_value: f64,
+    max_value: f64,
+    default_value: f64,
+}
+
+#[repr(C)]
+struct ClapPluginParams {
+    count: unsafe extern "C" fn(plugin: *const ClapPlugin) -> u32,
+    get_info: unsafe extern "C" fn(
+        plugin: *const ClapPlugin,
+        param_index: u32,
+        param_info: *mut ClapParamInfo,
+    ) -> bool,
+    get_value:
+        unsafe extern "C" fn(plugin: *const ClapPlugin, param_id: u32, out_value: *mut f64) -> bool,
+    value_to_text: unsafe extern "C" fn(
+        plugin: *const ClapPlugin,
+        param_id: u32,
+        value: f64,
+        out_buffer: *mut c_char,
+        out_buffer_capacity: u32,
+    ) -> bool,
+    text_to_value: unsafe extern "C" fn(
+        plugin: *const ClapPlugin,
+        param_id: u32,
+        param_value_text: *const c_char,
+        out_value: *mut f64,
+    ) -> bool,
+    flush: unsafe extern "C" fn(
+        plugin: *const ClapPlugin,
+        in_events: *const ClapInputEvents,
+        out_events: *const ClapOutputEvents,
+    ),
+}
+
+#[repr(C)]
+struct ClapAudioPortInfo {
+    id: u32,
+    name: [c_char; CLAP_NAME_SIZE],
+    flags: u32,
+    channel_count: u32,
+    port_type: *const c_char,
+    in_place_pair: u32,
+}
+
+#[repr(C)]
+struct ClapPluginAudioPorts {
+    count: unsafe extern "C" fn(plugin: *const ClapPlugin, is_input: bool) -> u32,
+    get: unsafe extern "C" fn(
+        plugin: *const ClapPlugin,
+        index: u32,
+        is_input: bool,
+        info: *mut ClapAudioPortInfo,
+    ) -> bool,
+}
+
+const CLAP_PLUGIN_FACTORY_ID: &CStr = c"clap.plugin-factory";
+const CLAP_EXT_PARAMS: &CStr = c"clap.params";
+const CLAP_EXT_AUDIO_PORTS: &CStr = c"clap.audio-ports";
+
+// ---------------------------------------------------------------------------
+// Host callbacks: Phonon offers no host extensions and ignores requests
+// ---------------------------------------------------------------------------
+
+unsafe extern "C" fn host_get_extension(
+    _host: *const ClapHost,
+    _id: *const c_char,
+) -> *const c_void {
+    std::ptr::null()
+}
+
+unsafe extern "C" fn host_request(_host: *const ClapHost) {}
+
+fn new_host() -> Box<ClapHost> {
+    Box::new(ClapHost {
+        clap_version: CLAP_VERSION,
+        host_data: std::ptr::null_mut(),
+        name: c"Phonon".as_ptr(),
+        vendor: c"Phonon".as_ptr(),
+        url: c"".as_ptr(),
+        version: c"0.1.0".as_ptr(),
+        get_extension: host_get_extension,
+        request_restart: host_request,
+        request_process: host_request,
+        request_callback: host_request,
+    })
+}
+
+/// `ctx` of the input event list: the parameter changes of this block
+unsafe extern "C" fn events_size(list: *const ClapInputEvents) -> u32 {
+    let events = &*((*list).ctx as *const Vec<ClapEventParamValue>);
+    events.len() as u32
+}
+
+unsafe extern "C" fn events_get(
+    list: *const ClapInputEvents,
+    index: u32,
+) -> *const ClapEventHeader {
+    let events = &*((*list).ctx as *const Vec<ClapEventParamValue>);
+    match events.get(index as usize) {
+        Some(event) => &event.header,
+        None => std::ptr::null(),
+    }
+}
+
+/// Events an effect sends back (parameter gestures and the like) are dropped
+unsafe extern "C" fn events_try_push(
+    _list: *const ClapOutputEvents,
+    _event: *const ClapEventHeader,
+) -> bool {
+    true
+}
+
+// ---------------------------------------------------------------------------
+// Finding plugins
+// ---------------------------------------------------------------------------
+
+/// An initialised `.clap` library. Kept loaded for the life of the process,
+/// since its plugins may be running in a graph.
+struct ClapLibrary {
+    path: PathBuf,
+    entry: *const ClapPluginEntry,
+    _library: libloading::Library,
+}
+
+// SAFETY: the entry points are plain functions the CLAP spec allows calling
+// from any thread; the table is only read
+unsafe impl Send for ClapLibrary {}
+
+static LIBRARIES: Mutex<Vec<ClapLibrary>> = Mutex::new(Vec::new());
+
+/// The plugin factory of the `.clap` at `path`, loading and initialising the
+/// library on first use
+fn factory(path: &Path) -> Result<*const ClapPluginFactory, String> {
+    let mut libraries = LIBRARIES.lock().unwrap_or_else(|e| e.into_inner());
+    let index = match libraries.iter().position(|lib| lib.path == path) {
+        Some(index) => index,
+        None => {
+            // SAFETY: loading runs the library's initialisers; plugins are
+            // trusted code
+            let library = unsafe { libloading::Library::new(binary_path(path)) }
+                .map_err(|e| format!("failed to load {}: {}", path.display(), e))?;
+            // SAFETY: `clap_entry` is a `clap_plugin_entry` struct
+            let entry = unsafe { library.get::<*const ClapPluginEntry>(b"clap_entry") }
+                .map(|symbol| *symbol)
+                .map_err(|e| format!("{} has no clap_entry: {}", path.display(), e))?;
+            let c_path = CString::new(path.to_string_lossy().as_bytes())
+                .map_err(|_| format!("bad plugin path {}", path.display()))?;
+            // SAFETY: `entry` points into the library, which is kept loaded
+            unsafe {
+                if (*entry).clap_version.major < 1 || !((*entry).init)(c_path.as_ptr()) {
+                    return Err(format!("{} failed to initialise", path.display()));
+                }
+            }
+            libraries.push(ClapLibrary {
+                path: path.to_path_buf(),
+                entry,
+                _library: library,
+            });
+            libraries.len() - 1
+        }
+    };
+    // SAFETY: as above
+    let factory =
+        unsafe { ((*libraries[index].entry).get_factory)(CLAP_PLUGIN_FACTORY_ID.as_ptr()) };
+    if factory.is_null() {
+        return Err(format!("{} has no plugin factory", path.display()));
+    }
+    Ok(factory as *const ClapPluginFactory)
+}
+
+/// The shared library inside a `.clap`: the file itself, or the binary of a
+/// macOS bundle
+fn binary_path(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        if let Some(stem) = path.file_stem() {
+            return path.join("Contents").join("MacOS").join(stem);
+        }
+    }
+    path.to_path_buf()
+}
+
+/// Directories CLAP plugins are installed in: `CLAP_PATH`, then the
+/// platform's standard locations
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("CLAP_PATH")
+        .map(|paths| std::env::split_paths(&paths).collect())
+        .unwrap_or_default();
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    if cfg!(target_os = "macos") {
+        dirs.extend(home.map(|h| h.join("Library/Audio/Plug-Ins/CLAP")));
+        dirs.push(PathBuf::from("/Library/Audio/Plug-Ins/CLAP"));
+    } else if cfg!(windows) {
+        dirs.extend(
+            std::env::var_os("LOCALAPPDATA").map(|d| PathBuf::from(d).join("Programs/Common/CLAP")),
+        );
+        dirs.extend(std::env::var_os("COMMONPROGRAMFILES").map(|d| PathBuf::from(d).join("CLAP")));
+    } else {
+        dirs.extend(home.map(|h| h.join(".clap")));
+        dirs.push(PathBuf::from("/usr/lib/clap"));
+        dirs.push(PathBuf::from("/usr/local/lib/clap"));
+    }
+    dirs
+}
+
+/// Every `.clap` under `dir`, sorted
+fn clap_files(dir: &Path, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("clap"))
+        {
+            found.push(path);
+        } else if path.is_dir() {
+            clap_files(&path, found);
+        }
+    }
+}
+
+/// The `.clap` and plugin id of the installed plugin called `name`: an exact
+/// (case-insensitive) name, else the first whose name starts with `name`
+fn find_plugin(name: &str) -> Result<(*const ClapPluginFactory, CString), String> {
+    let mut files = Vec::new();
+    for dir in search_dirs() {
+        clap_files(&dir, &mut files);
+    }
+
+    let wanted = name.to_lowercase();
+    let mut prefix_match = None;
+    for file in files {
+        let Ok(factory) = factory(&file) else {
+            continue;
+        };
+        // SAFETY: the factory belongs to a library that stays loaded
+        unsafe {
+            for index in 0..((*factory).get_plugin_count)(factory) {
+                let desc = ((*factory).get_plugin_descriptor)(factory, index);
+                if desc.is_null() || (*desc).name.is_null() || (*desc).id.is_null() {
+                    continue;
+                }
+                let plugin_name = CStr::from_ptr((*desc).name)
+                    .to_string_lossy()
+                    .to_lowercase();
+                let id = CStr::from_ptr((*desc).id).to_owned();
+                if plugin_name == wanted {
+                    return Ok((factory, id));
+                }
+                if prefix_match.is_none() && plugin_name.starts_with(&wanted) {
+                    prefix_match = Some((factory, id));
+                }
+            }
+        }
+    }
+    prefix_match.ok_or_else(|| format!("no CLAP plugin called \"{}\"", name))
+}
+
+// ---------------------------------------------------------------------------
+// A running effect
+// ---------------------------------------------------------------------------
+
+struct ClapParam {
+    id: u32,
+    name: String,
+    min: f64,
+    max: f64,
+}
+
+/// An activated CLAP effect with its block buffers
+pub struct ClapEffect {
+    plugin: *const ClapPlugin,
+    /// Handed to the plugin at creation, so it must outlive it
+    _host: Box<ClapHost>,
+    params: Vec<ClapParam>,
+    inputs: Vec<Vec<f32>>,
+    outputs: Vec<Vec<f32>>,
+    input_ptrs: Vec<*mut f32>,
+    output_ptrs: Vec<*mut f32>,
+    /// Parameter changes of the current block, sized for all parameters
+    events: Vec<ClapEventParamValue>,
+    processing: bool,
+    steady_time: i64,
+}
+
+// SAFETY: the plugin is used by one thread at a time (it sits behind the
+// node's Mutex): created, activated and destroyed on the control thread, and
+// processed on the render thread, as CLAP's threading rules expect
+unsafe impl Send for ClapEffect {}
+
+impl ClapEffect {
+    /// Instantiate and activate the installed effect called `name` for blocks
+    /// of up to `max_frames`
+    pub fn load(name: &str, sample_rate: f32, max_frames: usize) -> Result<Self, String> {
+        let (factory, id) = find_plugin(name)?;
+        let host = new_host();
+
+        // SAFETY: the factory's library stays loaded; the host outlives the
+        // plugin (it's stored alongside); the plugin is destroyed on failure
+        unsafe {
+            let plugin = ((*factory).create_plugin)(factory, &*host, id.as_ptr());
+            if plugin.is_null() {
+                return Err(format!("\"{}\" could not be created", name));
+            }
+            if !((*plugin).init)(plugin) {
+                ((*plugin).destroy)(plugin);
+                return Err(format!("\"{}\" failed to initialise", name));
+            }
+
+            let params = plugin_params(plugin);
+            let (in_channels, out_channels) = main_channels(plugin);
+            if out_channels == 0 {
+                ((*plugin).destroy)(plugin);
+                return Err(format!("\"{}\" has no audio output", name));
+            }
+            if !((*plugin).activate)(plugin, sample_rate as f64, 1, max_frames as u32) {
+                ((*plugin).destroy)(plugin);
+                return Err(format!("\"{}\" failed to activate", name));
+            }
+
+            let mut inputs = vec![vec![0.0; max_frames]; in_channels];
+            let mut outputs = vec![vec![0.0; max_frames]; out_channels];
+            let input_ptrs = inputs.iter_mut().map(|c| c.as_mut_ptr()).collect();
+            let output_ptrs = outputs.iter_mut().map(|c| c.as_mut_ptr()).collect();
+            let events = Vec::with_capacity(params.len());
+            Ok(Self {
+                plugin,
+                _host: host,
+                params,
+                inputs,
+                outputs,
+                input_ptrs,
+                output_ptrs,
+                events,
+                processing: false,
+                steady_time: 0,
+            })
+        }
+    }
+}
+
+/// Name and range of each parameter, from the params extension
+unsafe fn plugin_params(plugin: *const ClapPlugin) -> Vec<ClapParam> {
+    let ext =
+        ((*plugin).get_extension)(plugin, CLAP_EXT_PARAMS.as_ptr()) as *const ClapPluginParams;
+    if ext.is_null() {
+        return Vec::new();
+    }
+    let mut params = Vec::new();
+    for index in 0..((*ext).count)(plugin) {
+        let mut info: ClapParamInfo = std::mem::zeroed();
+        if ((*ext).get_info)(plugin, index, &mut info) {
+            params.push(ClapParam {
+                id: info.id,
+                name: CStr::from_ptr(info.name.as_ptr())
+                    .to_string_lossy()
+                    .into_owned(),
+                min: info.min_value,
+                max: info.max_value,
+            });
+        }
+    }
+    params
+}
+
+/// Channel counts of the main input and output ports (stereo when the
+/// plugin doesn't say)
+unsafe fn main_channels(plugin: *const ClapPlugin) -> (usize, usize) {
+    let ext = ((*plugin).get_extension)(plugin, CLAP_EXT_AUDIO_PORTS.as_ptr())
+        as *const ClapPluginAudioPorts;
+    if ext.is_null() {
+        return (2, 2);
+    }
+    let channels = |is_input: bool| {
+        if ((*ext).count)(plugin, is_input) == 0 {
+            return 0;
+        }
+        let mut info: ClapAudioPortInfo = std::mem::zeroed();
+        if ((*ext).get)(plugin, 0, is_input, &mut info) {
+            info.channel_count as usize
+        } else {
+            0
+        }
+    };
+    (channels(true), channels(false))
+}
+
+impl BlockEffect for ClapEffect {
+    fn param_names(&self) -> Vec<String> {
+        self.params.iter().map(|p| p.name.clone()).collect()
+    }
+
+    fn process(&mut self, input: &[f32], output: &mut [f32], changes: &[(usize, f64)]) {
+        let frames = input.len().min(output.len());
+        let plugin = self.plugin;
+
+        self.events.clear();
+        for &(index, value) in changes {
+            let Some(param) = self.params.get(index) else {
+                continue;
+            };
+            self.events.push(ClapEventParamValue {
+                header: ClapEventHeader {
+                    size: std::mem::size_of::<ClapEventParamValue>() as u32,
+                    time: 0,
+                    space_id: CLAP_CORE_EVENT_SPACE_ID,
+                    event_type: CLAP_EVENT_PARAM_VALUE,
+                    flags: 0,
+                },
+                param_id: param.id,
+                cookie: std::ptr::null_mut(),
+                note_id: -1,
+                port_index: -1,
+                channel: -1,
+                key: -1,
+                value: value.clamp(param.min, param.max),
+            });
+        }
+        for channel in &mut self.inputs {
+            channel[..frames].copy_from_slice(&input[..frames]);
+        }
+
+        let in_events = ClapInputEvents {
+            ctx: &self.events as *const Vec<ClapEventParamValue> as *mut c_void,
+            size: events_size,
+            get: events_get,
+        };
+        let out_events = ClapOutputEvents {
+            ctx: std::ptr::null_mut(),
+            try_push: events_try_push,
+        };
+        let audio_in = ClapAudioBuffer {
+            data32: self.input_ptrs.as_mut_ptr(),
+            data64: std::ptr::null_mut(),
+            channel_count: self.inputs.len() as u32,
+            latency: 0,
+            constant_mask: 0,
+        };
+        let mut audio_out = ClapAudioBuffer {
+            data32: self.output_ptrs.as_mut_ptr(),
+            data64: std::ptr::null_mut(),
+            channel_count: self.outputs.len() as u32,
+            latency: 0,
+            constant_mask: 0,
+        };
+        let process = ClapProcess {
+            steady_time: self.steady_time,
+            frames_count: frames as u32,
+            transport: std::ptr::null(),
+            audio_inputs: &audio_in,
+            audio_outputs: &mut audio_out,
+            audio_inputs_count: u32::from(!self.inputs.is_empty()),
+            audio_outputs_count: 1,
+            in_events: &in_events,
+            out_events: &out_events,
+        };
+
+        // SAFETY: the plugin is active; every buffer holds `frames` samples
+        // and outlives the call
+        let status = unsafe {
+            if !self.processing {
+                self.processing = ((*plugin).start_processing)(plugin);
+            }
+            ((*plugin).process)(plugin, &process)
+        };
+        self.steady_time += frames as i64;
+
+        if status == CLAP_PROCESS_ERROR {
+            output[..frames].copy_from_slice(&input[..frames]);
+            return;
+        }
+        // A mono signal out: the mean of the output channels
+        let scale = 1.0 / self.outputs.len() as f32;
+        for (i, sample) in output[..frames].iter_mut().enumerate() {
+            *sample = self.outputs.iter().map(|channel| channel[i]).sum::<f32>() * scale;
+        }
+    }
+}
+
+impl Drop for ClapEffect {
+    fn drop(&mut self) {
+        // SAFETY: the plugin was initialised and activated in `load`
+        unsafe {
+            if self.processing {
+                ((*self.plugin).stop_processing)(self.plugin);
+            }
+            ((*self.plugin).deactivate)(self.plugin);
+            ((*self.plugin).destroy)(self.plugin);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! External effect state for `extfx`
+//!
+//! `extfx "Plugin" input :param value` runs its input through an installed
+//! effect plugin. The graph evaluates a sample at a time, so the node gathers
+//! [`EXTFX_BLOCK`] samples and hands the effect a whole block, at the cost of
+//! one block of latency. Parameter values are sent once per block, and only
+//! when they change.
+//!
+//! The plugin format lives behind [`BlockEffect`]: CLAP plugins with the
+//! `clap-plugins` feature (see `clap_plugin.rs`). Without a loaded effect the
+//! node passes its input straight through.
+
+/// Samples per block handed to the effect, which is also the node's latency
+pub const EXTFX_BLOCK: usize = 64;
+
+/// An effect that processes mono blocks
+pub trait BlockEffect: Send {
+    /// Names of the effect's parameters, in index order
+    fn param_names(&self) -> Vec<String>;
+
+    /// Process one block. `changes` holds `(parameter index, value)` for the
+    /// parameters that changed since the previous block, in the parameter's
+    /// own units.
+    fn process(&mut self, input: &[f32], output: &mut [f32], changes: &[(usize, f64)]);
+}
+
+/// A parameter named in the program
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalFxParam {
+    pub name: String,
+    /// `:param "Name" v` names it exactly; `:name v` may be a prefix
+    pub exact: bool,
+}
+
+/// Index of the effect parameter `param` refers to, ignoring case: an exact
+/// name match, else (unless `param.exact`) the first name it is a prefix of
+pub fn match_param(names: &[String], param: &ExternalFxParam) -> Option<usize> {
+    let wanted = param.name.to_lowercase();
+    let lowered: Vec<String> = names.iter().map(|name| name.to_lowercase()).collect();
+    lowered.iter().position(|name| *name == wanted).or_else(|| {
+        if param.exact {
+            None
+        } else {
+            lowered.iter().position(|name| name.starts_with(&wanted))
+        }
+    })
+}
+
+/// Plugin and block buffers of an `extfx` node
+pub struct ExternalFxState {
+    plugin_name: String,
+    params: Vec<ExternalFxParam>,
+    sample_rate: f32,
+    /// Whether loading has been attempted, so a missing plugin is looked for once
+    tried: bool,
+    effect: Option<Box<dyn BlockEffect>>,
+    /// Effect parameter index of each program parameter
+    param_indices: Vec<Option<usize>>,
+    last_values: Vec<f32>,
+    changes: Vec<(usize, f64)>,
+    input: Vec<f32>,
+    output: Vec<f32>,
+    pos: usize,
+}
+
+impl ExternalFxState {
+    pub fn new(plugin_name: String, params: Vec<ExternalFxParam>, sample_rate: f32) -> Self {
+        let count = params.len();
+        Self {
+            plugin_name,
+            params,
+            sample_rate,
+            tried: false,
+            effect: None,
+            param_indices: vec![None; count],
+            last_values: vec![f32::NAN; count],
+            changes: Vec::with_capacity(count),
+            input: vec![0.0; EXTFX_BLOCK],
+            output: vec![0.0; EXTFX_BLOCK],
+            pos: 0,
+        }
+    }
+
+    pub fn plugin_name(&self) -> &str {
+        &self.plugin_name
+    }
+
+    pub fn params(&self) -> &[ExternalFxParam] {
+        &self.params
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.effect.is_some()
+    }
+
+    /// Find and start the plugin. Only the first call does anything; call it
+    /// off the render thread (`UnifiedSignalGraph::preload_plugins`).
+    pub fn load(&mut self) {
+        if self.tried {
+            return;
+        }
+        self.tried = true;
+
+        #[cfg(feature = "clap-plugins")]
+        match super::clap_plugin::ClapEffect::load(&self.plugin_name, self.sample_rate, EXTFX_BLOCK)
+        {
+            Ok(effect) => self.set_effect(Box::new(effect)),
+            Err(e) => tracing::warn!("extfx: {}; passing the input through", e),
+        }
+
+        #[cfg(not(feature = "clap-plugins"))]
+        tracing::warn!(
+            "extfx \"{}\": built without plugin support (--features clap-plugins); passing the input through",
+            self.plugin_name
+        );
+    }
+
+    /// Use `effect` from the next sample on
+    pub fn set_effect(&mut self, effect: Box<dyn BlockEffect>) {
+        let names = effect.param_names();
+        for (index, param) in self.params.iter().enumerate() {
+            self.param_indices[index] = match_param(&names, param);
+            if self.param_indices[index].is_none() {
+                tracing::warn!(
+                    "extfx \"{}\" has no parameter \"{}\"",
+                    self.plugin_name,
+                    param.name
+                );
+            }
+        }
+        self.last_values.fill(f32::NAN);
+        self.input.fill(0.0);
+        self.output.fill(0.0);
+        self.pos = 0;
+        self.effect = Some(effect);
+    }
+
+    /// Drop the effect so the next `load` starts one at the new rate
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate == self.sample_rate {
+            return;
+        }
+        *self = Self::new(
+            std::mem::take(&mut self.plugin_name),
+            std::mem::take(&mut self.params),
+            sample_rate,
+        );
+    }
+
+    /// Push one input sample and return one output sample, `EXTFX_BLOCK`
+    /// samples late. `values` holds the current value of each parameter.
+    pub fn process_sample(&mut self, input: f32, values: &[f32]) -> f32 {
+        let Some(effect) = self.effect.as_mut() else {
+            return input;
+        };
+
+        let output = self.output[self.pos];
+        self.input[self.pos] = input;
+        self.pos += 1;
+
+        if self.pos == EXTFX_BLOCK {
+            self.changes.clear();
+            for ((index, last), &value) in self
+                .param_indices
+                .iter()
+                .zip(&mut self.last_values)
+                .zip(values)
+            {
+                if let Some(index) = index {
+                    if value != *last {
+                        *last = value;
+                        self.changes.push((*index, value as f64));
+                    }
+                }
+            }
+            effect.process(&self.input, &mut self.output, &self.changes);
+            self.pos = 0;
+        }
+        output
+    }
+}
+
+impl Clone for ExternalFxState {
+    /// A clone starts its own instance of the plugin
+    fn clone(&self) -> Self {
+        Self::new(
+            self.plugin_name.clone(),
+            self.params.clone(),
+            self.sample_rate,
+        )
+    }
+}
+
+impl std::fmt::Debug for ExternalFxState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalFxState")
+            .field("plugin_name", &self.plugin_name)
+            .field("params", &self.params)
+            .field("loaded", &self.effect.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// Block length and parameter changes of each call
+    type Calls = Arc<Mutex<Vec<(usize, Vec<(usize, f64)>)>>>;
+
+    /// Doubles its input and records what it was sent
+    struct Doubler {
+        calls: Calls,
+    }
+
+    impl BlockEffect for Doubler {
+        fn param_names(&self) -> Vec<String> {
+            vec![
+                "Threshold".to_string(),
+                "Ratio".to_string(),
+                "Release Time".to_string(),
+            ]
+        }
+
+        fn process(&mut self, input: &[f32], output: &mut [f32], changes: &[(usize, f64)]) {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((input.len(), changes.to_vec()));
+            for (out, x) in output.iter_mut().zip(input) {
+                *out = x * 2.0;
+            }
+        }
+    }
+
+    fn param(name: &str, exact: bool) -> ExternalFxParam {
+        ExternalFxParam {
+            name: name.to_string(),
+            exact,
+        }
+    }
+
+    fn doubler_state(params: Vec<ExternalFxParam>) -> (ExternalFxState, Calls) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut state = ExternalFxState::new("Doubler".to_string(), params, 44100.0);
+        state.set_effect(Box::new(Doubler {
+            calls: calls.clone(),
+        }));
+        (state, calls)
+    }
+
+    #[test]
+    fn test_match_param() {
+        let names = vec![
+            "Release Time".to_string(),
+            "Ratio".to_string(),
+            "Rate".to_string(),
+        ];
+        assert_eq!(match_param(&names, &param("ratio", false)), Some(1));
+        assert_eq!(match_param(&names, &param("rel", false)), Some(0));
+        assert_eq!(match_param(&names, &param("ra", false)), Some(1));
+        assert_eq!(match_param(&names, &param("rel", true)), None);
+        assert_eq!(match_param(&names, &param("RELEASE TIME", true)), Some(0));
+        assert_eq!(match_param(&names, &param("time", false)), None);
+    }
+
+    #[test]
+    fn test_passes_through_without_an_effect() {
+        let mut state = ExternalFxState::new("Missing".to_string(), vec![], 44100.0);
+        assert_eq!(state.process_sample(0.25, &[]), 0.25);
+    }
+
+    #[test]
+    fn test_processes_whole_blocks_one_block_late() {
+        let (mut state, calls) = doubler_state(vec![]);
+        let out: Vec<f32> = (0..EXTFX_BLOCK * 3)
+            .map(|i| state.process_sample(i as f32, &[]))
+            .collect();
+
+        assert!(out[..EXTFX_BLOCK].iter().all(|&x| x == 0.0));
+        for (i, &x) in out.iter().enumerate().skip(EXTFX_BLOCK) {
+            assert_eq!(x, (i - EXTFX_BLOCK) as f32 * 2.0);
+        }
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 3);
+        assert!(calls.iter().all(|(len, _)| *len == EXTFX_BLOCK));
+    }
+
+    #[test]
+    fn test_sends_only_changed_parameters() {
+        let (mut state, calls) =
+            doubler_state(vec![param("thresh", false), param("Release Time", true)]);
+        for i in 0..EXTFX_BLOCK * 3 {
+            let threshold = if i < EXTFX_BLOCK * 2 { -20.0 } else { -10.0 };
+            state.process_sample(0.0, &[threshold, 0.5]);
+        }
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0].1, vec![(0, -20.0), (2, 0.5)]);
+        assert_eq!(calls[1].1, vec![]);
+        assert_eq!(calls[2].1, vec![(0, -10.0)]);
+    }
+}
@@ -166,13 +166,13 @@ impl MidiEventBuffer {
 
     /// Convert frequency to nearest MIDI note
     pub fn freq_to_midi(freq: f64) -> u8 {
-        let midi = 69.0 + 12.0 * (freq / 440.0).log2();
+        let midi = crate::pitch::hz_to_midi(freq);
         midi.round().clamp(0.0, 127.0) as u8
     }
 
     /// Convert MIDI note to frequency
     pub fn midi_to_freq(note: u8) -> f64 {
-        440.0 * 2.0_f64.powf((note as f64 - 69.0) / 12.0)
+        crate::pitch::midi_to_hz(note as f64)
     }
 }
 
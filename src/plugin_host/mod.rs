@@ -41,10 +41,14 @@ pub mod midi;
 pub mod manager;
 pub mod mock_plugin;
 pub mod real_plugin;
+pub mod external_fx;
 
 #[cfg(feature = "vst2")]
 pub mod vst2_plugin;
 
+#[cfg(feature = "clap-plugins")]
+pub mod clap_plugin;
+
 // Re-exports for convenience
 pub use types::*;
 pub use registry::PluginRegistry;
@@ -55,6 +59,7 @@ pub use midi::MidiEventBuffer;
 pub use manager::{PluginInstanceManager, PluginSettings, NamedPluginInstance};
 pub use mock_plugin::{MockPluginInstance, RecordedMidiEvent};
 pub use real_plugin::{RealPluginInstance, RealPluginScanner};
+pub use external_fx::{BlockEffect, ExternalFxParam, ExternalFxState, EXTFX_BLOCK};
 
 #[cfg(feature = "vst3")]
 pub use real_plugin::{
@@ -64,3 +69,6 @@ pub use real_plugin::{
 
 #[cfg(feature = "vst2")]
 pub use vst2_plugin::{Vst2PluginInstance, scan_vst2_directory, create_vst2_plugin_by_name};
+
+#[cfg(feature = "clap-plugins")]
+pub use clap_plugin::ClapEffect;
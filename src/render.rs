@@ -3,6 +3,7 @@
 //!
 //! Provides functionality to render DSL patches to WAV files
 
+use crate::phonon_engine::PhononEngine;
 use std::fs;
 use std::path::Path;
 
@@ -52,34 +53,7 @@ impl Renderer {
         dsl_code: &str,
         output_path: &Path,
     ) -> Result<RenderStats, String> {
-        // Use the working SimpleDspExecutor instead of broken SignalExecutor
-        use crate::simple_dsp_executor::render_dsp_to_audio_simple;
-
-        // Strip comments and empty lines from DSL code
-        let clean_code = dsl_code
-            .lines()
-            .filter(|line| !line.trim().starts_with('#') && !line.trim().is_empty())
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        let buffer = render_dsp_to_audio_simple(
-            &clean_code,
-            self.config.sample_rate as f32,
-            self.config.duration,
-        )?;
-
-        // Apply master gain and get samples
-        let mut all_samples = buffer.data;
-        for sample in all_samples.iter_mut() {
-            *sample *= self.config.master_gain;
-        }
-
-        // Trim to exact duration (shouldn't be needed but just in case)
-        let total_samples = (self.config.duration * self.config.sample_rate as f32) as usize;
-        all_samples.truncate(total_samples);
-
-        // Apply fade in/out
-        self.apply_fades(&mut all_samples);
+        let all_samples = self.render_to_buffer(dsl_code)?;
 
         // Calculate statistics
         let stats = RenderStats::from_samples(&all_samples);
@@ -92,24 +66,9 @@ impl Renderer {
 
     /// Render to memory (returns samples)
     pub fn render_to_buffer(&self, dsl_code: &str) -> Result<Vec<f32>, String> {
-        // Use the working SimpleDspExecutor instead of broken SignalExecutor
-        use crate::simple_dsp_executor::render_dsp_to_audio_simple;
-
-        // Strip comments and empty lines from DSL code
-        let clean_code = dsl_code
-            .lines()
-            .filter(|line| !line.trim().starts_with('#') && !line.trim().is_empty())
-            .collect::<Vec<_>>()
-            .join("\n");
-
-        let buffer = render_dsp_to_audio_simple(
-            &clean_code,
-            self.config.sample_rate as f32,
-            self.config.duration,
-        )?;
+        let mut samples = self.render_program(dsl_code)?;
 
         // Apply master gain and fades
-        let mut samples = buffer.data;
         for sample in samples.iter_mut() {
             *sample *= self.config.master_gain;
         }
@@ -118,6 +77,35 @@ impl Renderer {
         Ok(samples)
     }
 
+    /// The program's mono output for the configured duration, through the
+    /// same parser and compiler as `phonon render`. Old Glicol-style `>>`
+    /// chains still render on the legacy executor, with a warning
+    fn render_program(&self, dsl_code: &str) -> Result<Vec<f32>, String> {
+        let num_samples = (self.config.duration * self.config.sample_rate as f32) as usize;
+
+        if is_glicol_chain(dsl_code) {
+            eprintln!(
+                "warning: `>>` chains are deprecated and will stop rendering; \
+                 chain with `#`, as in `out $ saw 220 # lpf 1000 0.7`"
+            );
+            let sample_rate = self.config.sample_rate as f32;
+            let mut samples = render_glicol(dsl_code, sample_rate, self.config.duration)?;
+            samples.truncate(num_samples);
+            return Ok(samples);
+        }
+
+        let mut engine = PhononEngine::builder()
+            .sample_rate(self.config.sample_rate)
+            .block_size(self.config.block_size)
+            .channels(1)
+            .code(dsl_code)
+            .build()?;
+        for diagnostic in engine.skipped() {
+            eprintln!("{}", diagnostic);
+        }
+        Ok(engine.render(num_samples))
+    }
+
     /// Apply fade in and fade out to samples
     fn apply_fades(&self, samples: &mut [f32]) {
         let sample_rate = self.config.sample_rate as f32;
@@ -174,6 +162,28 @@ impl Renderer {
     }
 }
 
+/// Whether `code` is in the Glicol dialect (`out: sin 440 >> mul 0.5`),
+/// which the Phonon language has no `>>` in
+fn is_glicol_chain(code: &str) -> bool {
+    code.lines()
+        .map(|line| line.split("--").next().unwrap_or_default())
+        .any(|line| line.contains(">>"))
+}
+
+/// Render a Glicol-dialect program on the legacy executor
+#[allow(deprecated)]
+fn render_glicol(code: &str, sample_rate: f32, duration: f32) -> Result<Vec<f32>, String> {
+    use crate::simple_dsp_executor::render_dsp_to_audio_simple;
+
+    // `#` starts a comment in that dialect
+    let clean_code = code
+        .lines()
+        .filter(|line| !line.trim().starts_with('#') && !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(render_dsp_to_audio_simple(&clean_code, sample_rate, duration)?.data)
+}
+
 /// Statistics about rendered audio
 #[derive(Debug, Clone)]
 pub struct RenderStats {
@@ -336,6 +346,23 @@ out: ~osc >> mul 0.5
         );
     }
 
+    #[test]
+    fn test_render_phonon_syntax() {
+        let config = RenderConfig {
+            duration: 0.5,
+            ..Default::default()
+        };
+        let renderer = Renderer::new(config);
+        let samples = renderer
+            .render_to_buffer("tempo: 0.5\n-- a quiet tone\nout $ sine 440 * 0.5")
+            .expect("Failed to render");
+        assert_eq!(samples.len(), 22050);
+        let stats = RenderStats::from_samples(&samples);
+        assert!(stats.rms > 0.3 && stats.rms < 0.4, "{}", stats.rms);
+        assert!(is_glicol_chain("out: sin 440 >> mul 0.5"));
+        assert!(!is_glicol_chain("out $ sine 440 -- not >> this"));
+    }
+
     #[test]
     fn test_render_to_file() {
         let dsl = r#"
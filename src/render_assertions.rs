@@ -0,0 +1,226 @@
+//! Render assertions: audio unit tests written in the DSL
+//!
+//! ```text
+//! assert rms(~kick) in 0.1..0.4
+//! assert onset_count(~drums) == 8
+//! assert max_db(out) < 0.0
+//! ```
+//!
+//! Each assertion records its target (a bus or the main output) in memory
+//! while the program renders. The recording is measured when
+//! `UnifiedSignalGraph::check_assertions` is called at render end; `phonon
+//! test` reports the results. Only offline renders record: live sessions
+//! compile with `compile_program`, which skips `assert` statements.
+
+use crate::audio_analysis::analyze_frames;
+use crate::compositional_parser::AssertCheck;
+use crate::onset_timing::detect_percussive_onsets;
+use crate::unified_graph::TapState;
+use std::sync::{Arc, Mutex};
+
+/// Longest stretch of audio an assertion keeps (bounds memory on long renders)
+const MAX_RECORD_SECONDS: f32 = 600.0;
+
+/// Measurement an assertion compares against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AssertMetric {
+    /// RMS level (linear)
+    Rms,
+    /// RMS level in dBFS
+    RmsDb,
+    /// Peak absolute sample (linear)
+    Peak,
+    /// Peak level in dBFS
+    MaxDb,
+    /// Number of detected onsets
+    OnsetCount,
+    /// Mean spectral centroid in Hz over the sounding frames
+    Centroid,
+    /// Median pitch in Hz over the pitched frames (0 if none)
+    Pitch,
+}
+
+impl AssertMetric {
+    pub const NAMES: &'static [&'static str] = &[
+        "rms",
+        "rms_db",
+        "peak",
+        "max_db",
+        "onset_count",
+        "centroid",
+        "pitch",
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rms" => Some(AssertMetric::Rms),
+            "rms_db" => Some(AssertMetric::RmsDb),
+            "peak" => Some(AssertMetric::Peak),
+            "max_db" => Some(AssertMetric::MaxDb),
+            "onset_count" | "onsets" => Some(AssertMetric::OnsetCount),
+            "centroid" => Some(AssertMetric::Centroid),
+            "pitch" => Some(AssertMetric::Pitch),
+            _ => None,
+        }
+    }
+
+    /// Measure a recording
+    pub fn measure(&self, samples: &[f32], sample_rate: f32) -> f64 {
+        let rms =
+            || (samples.iter().map(|&x| (x * x) as f64).sum::<f64>() / samples.len() as f64).sqrt();
+        let peak = || samples.iter().fold(0.0f64, |m, &x| m.max(x.abs() as f64));
+        match self {
+            AssertMetric::Rms => rms(),
+            AssertMetric::RmsDb => 20.0 * rms().log10(),
+            AssertMetric::Peak => peak(),
+            AssertMetric::MaxDb => 20.0 * peak().log10(),
+            AssertMetric::OnsetCount => detect_percussive_onsets(samples, sample_rate).len() as f64,
+            AssertMetric::Centroid => {
+                let centroids: Vec<f32> = analyze_frames(samples, sample_rate, 0.1)
+                    .iter()
+                    .map(|f| f.centroid)
+                    .filter(|&c| c > 0.0)
+                    .collect();
+                if centroids.is_empty() {
+                    0.0
+                } else {
+                    (centroids.iter().sum::<f32>() / centroids.len() as f32) as f64
+                }
+            }
+            AssertMetric::Pitch => {
+                let mut pitches: Vec<f32> = analyze_frames(samples, sample_rate, 0.1)
+                    .iter()
+                    .map(|f| f.pitch)
+                    .filter(|&p| p > 0.0)
+                    .collect();
+                pitches.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                pitches.get(pitches.len() / 2).copied().unwrap_or(0.0) as f64
+            }
+        }
+    }
+}
+
+/// An `assert` statement together with the recording of its target
+#[derive(Debug, Clone)]
+pub struct RenderAssertion {
+    /// Source form, e.g. `rms(~kick) in 0.1..0.4`
+    pub description: String,
+    metric: AssertMetric,
+    check: AssertCheck,
+    recorder: Arc<Mutex<TapState>>,
+}
+
+/// Result of checking one assertion
+#[derive(Debug, Clone)]
+pub struct AssertionOutcome {
+    pub description: String,
+    /// Measured value, None if the target never produced audio
+    pub value: Option<f64>,
+    pub passed: bool,
+}
+
+impl RenderAssertion {
+    pub fn new(
+        metric: &str,
+        target: Option<&str>,
+        check: AssertCheck,
+        sample_rate: f32,
+    ) -> Result<Self, String> {
+        let target = target.map_or("out".to_string(), |bus| format!("~{}", bus));
+        let description = format!("{}({}) {}", metric, target, check);
+        let metric = AssertMetric::from_name(metric).ok_or_else(|| {
+            format!(
+                "assert {}: unknown metric '{}'. Available: {}",
+                description,
+                metric,
+                AssertMetric::NAMES.join(", ")
+            )
+        })?;
+
+        Ok(Self {
+            description,
+            metric,
+            check,
+            recorder: Arc::new(Mutex::new(TapState::in_memory(
+                MAX_RECORD_SECONDS,
+                sample_rate,
+            ))),
+        })
+    }
+
+    /// Recorder to attach (via a Tap node) to the asserted signal
+    pub fn recorder(&self) -> Arc<Mutex<TapState>> {
+        Arc::clone(&self.recorder)
+    }
+
+    /// Measure the recording and check the condition
+    pub fn evaluate(&self) -> AssertionOutcome {
+        let value = self.recorder.lock().ok().and_then(|tap| {
+            if tap.buffer.is_empty() {
+                None
+            } else {
+                Some(self.metric.measure(&tap.buffer, tap.sample_rate))
+            }
+        });
+        AssertionOutcome {
+            description: self.description.clone(),
+            value,
+            passed: value.is_some_and(|v| self.check.holds(v)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compositional_parser::CompareOp;
+
+    fn sine(freq: f32, amp: f32, seconds: f32) -> Vec<f32> {
+        (0..(44100.0 * seconds) as usize)
+            .map(|i| (2.0 * std::f32::consts::PI * freq * i as f32 / 44100.0).sin() * amp)
+            .collect()
+    }
+
+    #[test]
+    fn test_level_metrics() {
+        let s = sine(220.0, 0.5, 0.5);
+        let rms = AssertMetric::Rms.measure(&s, 44100.0);
+        assert!((rms - 0.5 / 2f64.sqrt()).abs() < 1e-3);
+        assert!((AssertMetric::Peak.measure(&s, 44100.0) - 0.5).abs() < 1e-3);
+        let max_db = AssertMetric::MaxDb.measure(&s, 44100.0);
+        assert!((max_db + 6.02).abs() < 0.05, "max_db {}", max_db);
+        let pitch = AssertMetric::Pitch.measure(&s, 44100.0);
+        assert!((pitch - 220.0).abs() < 3.0, "pitch {}", pitch);
+    }
+
+    #[test]
+    fn test_assertion_uses_recording() {
+        let assertion = RenderAssertion::new(
+            "rms",
+            Some("kick"),
+            AssertCheck::Range { lo: 0.1, hi: 0.4 },
+            44100.0,
+        )
+        .unwrap();
+        assert_eq!(assertion.description, "rms(~kick) in 0.1..0.4");
+
+        // Nothing recorded yet: fails without a value
+        let outcome = assertion.evaluate();
+        assert!(!outcome.passed);
+        assert!(outcome.value.is_none());
+
+        for x in sine(110.0, 0.4, 0.2) {
+            assertion.recorder().lock().unwrap().record(x);
+        }
+        assert!(assertion.evaluate().passed);
+    }
+
+    #[test]
+    fn test_unknown_metric_is_rejected() {
+        let check = AssertCheck::Compare {
+            op: CompareOp::Lt,
+            value: 0.0,
+        };
+        assert!(RenderAssertion::new("loudness", None, check, 44100.0).is_err());
+    }
+}
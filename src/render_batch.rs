@@ -0,0 +1,299 @@
+//! Batch rendering from a manifest: `phonon render-batch album.toml`
+//!
+//! A manifest lists .phonon files with their length and output name; they are
+//! rendered in parallel (each entry compiles its own graph) to stereo 32-bit
+//! float WAVs, and the summary reports each entry's length and peak. Paths are
+//! relative to the manifest's directory.
+//!
+//! ```toml
+//! sample_rate = 48000          # default 44100
+//! output_dir = "renders"       # default: next to the manifest
+//! jobs = 4                     # renders at once (default: all cores)
+//! cycles = 16                  # default length for entries that set none
+//!
+//! [[render]]
+//! file = "tracks/intro.phonon"
+//! cycles = 32
+//! output = "01-intro.wav"      # default: the file's name with .wav
+//!
+//! [[render]]
+//! file = "tracks/outro.phonon"
+//! duration = 45.0              # seconds
+//! ```
+
+use crate::bus_meters::to_db;
+use crate::modal_editor::render_queue::{render_job, RenderJob, RenderLength};
+use rayon::prelude::*;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Length when neither the entry nor the manifest sets one, as for
+/// `phonon render`
+const DEFAULT_SECONDS: f64 = 4.0;
+
+/// Parsed manifest
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchManifest {
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: u32,
+    pub output_dir: Option<PathBuf>,
+    pub jobs: Option<usize>,
+    pub cycles: Option<f64>,
+    pub duration: Option<f64>,
+    pub render: Vec<BatchEntry>,
+}
+
+/// One `[[render]]` table
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchEntry {
+    pub file: PathBuf,
+    pub cycles: Option<f64>,
+    pub duration: Option<f64>,
+    pub output: Option<PathBuf>,
+}
+
+/// What rendering one entry produced
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult {
+    pub file: PathBuf,
+    pub output: PathBuf,
+    /// Rendered length in seconds and peak level, or why it failed
+    pub outcome: Result<(f64, f32), String>,
+    pub elapsed: Duration,
+}
+
+fn default_sample_rate() -> u32 {
+    44100
+}
+
+/// `cycles` or `duration`, not both
+fn length(cycles: Option<f64>, duration: Option<f64>) -> Result<Option<RenderLength>, String> {
+    match (cycles, duration) {
+        (Some(_), Some(_)) => Err("set cycles or duration, not both".to_string()),
+        (Some(cycles), None) => RenderLength::parse(&format!("{}c", cycles)).map(Some),
+        (None, Some(seconds)) => RenderLength::parse(&format!("{}s", seconds)).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+impl BatchManifest {
+    /// Parse and validate manifest text
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let manifest: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        if manifest.render.is_empty() {
+            return Err("manifest has no [[render]] entries".to_string());
+        }
+        if manifest.sample_rate == 0 {
+            return Err("sample_rate must be positive".to_string());
+        }
+        if manifest.jobs == Some(0) {
+            return Err("jobs must be at least 1".to_string());
+        }
+        length(manifest.cycles, manifest.duration)?;
+        for entry in &manifest.render {
+            length(entry.cycles, entry.duration)
+                .map_err(|e| format!("{}: {}", entry.file.display(), e))?;
+        }
+        Ok(manifest)
+    }
+
+    /// Read and parse a manifest file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// One render job per entry, with paths resolved against `base` (the
+    /// manifest's directory). Fails if an entry is unreadable or two entries
+    /// would write the same file.
+    pub fn jobs(&self, base: &Path) -> Result<Vec<(PathBuf, RenderJob)>, String> {
+        let default_length =
+            length(self.cycles, self.duration)?.unwrap_or(RenderLength::Seconds(DEFAULT_SECONDS));
+        let output_dir = base.join(self.output_dir.as_deref().unwrap_or(Path::new("")));
+        let mut outputs = HashSet::new();
+
+        self.render
+            .iter()
+            .map(|entry| {
+                let file = base.join(&entry.file);
+                let code = std::fs::read_to_string(&file)
+                    .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+                let length = length(entry.cycles, entry.duration)?.unwrap_or(default_length);
+                let output = match &entry.output {
+                    Some(output) => output_dir.join(output),
+                    None => output_dir.join(
+                        entry
+                            .file
+                            .with_extension("wav")
+                            .file_name()
+                            .unwrap_or_default(),
+                    ),
+                };
+                if !outputs.insert(output.clone()) {
+                    return Err(format!(
+                        "{} is the output of more than one entry",
+                        output.display()
+                    ));
+                }
+                let job = RenderJob {
+                    code,
+                    length,
+                    path: output,
+                    sample_rate: self.sample_rate as f32,
+                };
+                Ok((file, job))
+            })
+            .collect()
+    }
+}
+
+/// Render every entry of the manifest at `path`, `jobs` at a time (the
+/// manifest's `jobs` if None). Results come back in manifest order; an entry
+/// that fails doesn't stop the others.
+pub fn render_batch(path: &Path, jobs: Option<usize>) -> Result<Vec<BatchResult>, String> {
+    let manifest = BatchManifest::load(path)?;
+    let base = path.parent().unwrap_or(Path::new(""));
+    let renders = manifest
+        .jobs(base)?
+        .into_iter()
+        .map(|(file, job)| (file, job.path.clone(), job))
+        .collect();
+    render_parallel(renders, jobs.or(manifest.jobs), |_, job| {
+        render_job(&job, |_| {})
+    })
+}
+
+/// Run `render(output, item)` for each (source file, output, item), `threads`
+/// at a time (all cores if None), after creating the output directories.
+/// Results come back in input order.
+pub fn render_parallel<T: Send>(
+    renders: Vec<(PathBuf, PathBuf, T)>,
+    threads: Option<usize>,
+    render: impl Fn(&Path, T) -> Result<(f64, f32), String> + Sync,
+) -> Result<Vec<BatchResult>, String> {
+    for (_, output, _) in &renders {
+        if let Some(dir) = output.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.unwrap_or_else(rayon::current_num_threads))
+        .build()
+        .map_err(|e| format!("Failed to start render threads: {}", e))?;
+
+    Ok(pool.install(|| {
+        renders
+            .into_par_iter()
+            .map(|(file, output, item)| {
+                let start = Instant::now();
+                let outcome = render(&output, item);
+                BatchResult {
+                    file,
+                    output,
+                    outcome,
+                    elapsed: start.elapsed(),
+                }
+            })
+            .collect()
+    }))
+}
+
+/// Summary lines: one per entry, then the totals
+pub fn summary(results: &[BatchResult]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut total_seconds = 0.0;
+    for result in results {
+        lines.push(match &result.outcome {
+            Ok((seconds, peak)) => {
+                total_seconds += seconds;
+                format!(
+                    "✅ {}  {:.1}s  peak {:.1} dBFS{}  ({:.1}s)",
+                    result.output.display(),
+                    seconds,
+                    to_db(*peak),
+                    if *peak > 1.0 { "  CLIPPED" } else { "" },
+                    result.elapsed.as_secs_f64()
+                )
+            }
+            Err(e) => format!("❌ {}: {}", result.file.display(), e),
+        });
+    }
+    let failed = results.iter().filter(|r| r.outcome.is_err()).count();
+    lines.push(format!(
+        "{} rendered, {} failed, {:.1}s of audio",
+        results.len() - failed,
+        failed,
+        total_seconds
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = BatchManifest::parse(
+            r#"
+sample_rate = 48000
+output_dir = "out"
+cycles = 8
+
+[[render]]
+file = "a.phonon"
+
+[[render]]
+file = "b.phonon"
+duration = 2.5
+output = "two.wav"
+"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.sample_rate, 48000);
+        assert_eq!(manifest.render.len(), 2);
+        assert_eq!(manifest.render[1].duration, Some(2.5));
+
+        assert!(BatchManifest::parse("sample_rate = 44100").is_err());
+        assert!(BatchManifest::parse("[[render]]\nfile = \"a.phonon\"\nbars = 4").is_err());
+        assert!(BatchManifest::parse(
+            "[[render]]\nfile = \"a.phonon\"\ncycles = 4\nduration = 1.0"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_jobs_resolve_lengths_and_outputs() {
+        let dir = std::env::temp_dir().join(format!("phonon_batch_jobs_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.phonon"), "out $ sine 440").unwrap();
+
+        let manifest = BatchManifest::parse(
+            "output_dir = \"wav\"\ncycles = 8\n\n[[render]]\nfile = \"a.phonon\"\n\n[[render]]\nfile = \"a.phonon\"\nduration = 2.0\noutput = \"b.wav\"",
+        )
+        .unwrap();
+        let jobs = manifest.jobs(&dir).unwrap();
+        assert_eq!(jobs[0].1.path, dir.join("wav").join("a.wav"));
+        assert_eq!(jobs[0].1.length, RenderLength::Cycles(8.0));
+        assert_eq!(jobs[1].1.length, RenderLength::Seconds(2.0));
+
+        // Two entries writing one file
+        let clash = BatchManifest::parse(
+            "[[render]]\nfile = \"a.phonon\"\n\n[[render]]\nfile = \"a.phonon\"",
+        )
+        .unwrap();
+        assert!(clash
+            .jobs(&dir)
+            .unwrap_err()
+            .contains("more than one entry"));
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
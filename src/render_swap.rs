@@ -100,6 +100,20 @@ pub trait RenderGraph {
     fn set_cycle(&mut self, cycle: f64) {
         let _ = cycle;
     }
+
+    /// `Cmd::Seek(cycle)` — cue playback to `cycle` as if it had started there
+    /// (unlike `set_cycle`, which only moves the position). Defaults to
+    /// `set_cycle`.
+    fn seek(&mut self, cycle: f64) {
+        self.set_cycle(cycle);
+    }
+
+    /// `Cmd::SaveState` — the graph's running state, encoded for a session
+    /// file. Allocates, so it only runs when a save asks for it. Empty by
+    /// default.
+    fn save_state(&self) -> Vec<u8> {
+        Vec::new()
+    }
 }
 
 /// A render-thread command.
@@ -120,6 +134,59 @@ pub enum Cmd<G> {
     SetTempo(f64),
     /// Set the absolute cycle position (see [`RenderGraph::set_cycle`]).
     SetCycle(f64),
+    /// Cue playback to a cycle (see [`RenderGraph::seek`]). The render loop
+    /// picks the new position up with [`RenderSwap::take_seek`] to move its clock.
+    Seek(f64),
+    /// Pause: the render loop holds its clock and the graph where they are
+    /// (see [`Transport`]). The graph itself is not touched.
+    Stop,
+    /// Resume from where [`Cmd::Stop`] held playback.
+    Start,
+    /// Send the graph's running state (see [`RenderGraph::save_state`]) back
+    /// on this channel, for `:save-session`.
+    SaveState(std::sync::mpsc::SyncSender<Vec<u8>>),
+}
+
+/// The render loop's transport, moved by [`Cmd::Stop`] / [`Cmd::Start`].
+///
+/// A stopped loop renders nothing: its clock, voices, envelopes and effect
+/// tails stay exactly as they were, and it plays silence. So that neither edge
+/// clicks, the block a stop lands on is rendered and faded out, and the first
+/// block after a start is faded in; playback picks up at the sample after the
+/// faded-out block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transport {
+    #[default]
+    Playing,
+    /// Stop received; the next block is rendered, then faded out
+    Stopping,
+    /// Holding: render nothing, play silence
+    Stopped,
+    /// Start received; the next block is rendered, then faded in
+    Starting,
+}
+
+impl Transport {
+    /// Whether the render loop should skip rendering and play silence
+    pub fn is_held(self) -> bool {
+        self == Transport::Stopped
+    }
+
+    fn stop(self) -> Self {
+        match self {
+            Transport::Playing | Transport::Starting => Transport::Stopping,
+            held => held,
+        }
+    }
+
+    fn start(self) -> Self {
+        match self {
+            Transport::Stopped => Transport::Starting,
+            // Never got as far as fading out
+            Transport::Stopping => Transport::Playing,
+            playing => playing,
+        }
+    }
 }
 
 impl<G> Cmd<G> {
@@ -132,6 +199,10 @@ impl<G> Cmd<G> {
             Cmd::Panic => "panic",
             Cmd::SetTempo(_) => "set_tempo",
             Cmd::SetCycle(_) => "set_cycle",
+            Cmd::Seek(_) => "seek",
+            Cmd::Stop => "stop",
+            Cmd::Start => "start",
+            Cmd::SaveState(_) => "save_state",
         }
     }
 }
@@ -191,6 +262,9 @@ pub struct RenderSwap<G> {
     /// flushed on the next `apply_pending_commands` call. Under normal operation
     /// this stays empty (the janitor drains far faster than swaps arrive).
     stash: Vec<Box<G>>,
+    /// Target of the last applied [`Cmd::Seek`], until the render loop takes it
+    seeked: Option<f64>,
+    transport: Transport,
 }
 
 impl<G: RenderGraph> RenderSwap<G> {
@@ -234,12 +308,29 @@ impl<G: RenderGraph> RenderSwap<G> {
                 Cmd::Panic => cur.panic(),
                 Cmd::SetTempo(cps) => cur.set_tempo(cps),
                 Cmd::SetCycle(c) => cur.set_cycle(c),
+                Cmd::Seek(c) => {
+                    cur.seek(c);
+                    self.seeked = Some(c);
+                }
+                Cmd::Stop => self.transport = self.transport.stop(),
+                Cmd::Start => self.transport = self.transport.start(),
+                Cmd::SaveState(reply) => {
+                    let _ = reply.try_send(cur.save_state());
+                }
             }
             applied += 1;
         }
         applied
     }
 
+    /// Install `next` in place of `cur` WITHOUT state transfer, retiring the old
+    /// graph to the graveyard like a swap does. For when `cur` can no longer be
+    /// trusted (its render panicked), so nothing is absorbed from it.
+    pub fn replace(&mut self, cur: &mut Box<G>, next: Box<G>) {
+        let retired = std::mem::replace(cur, next);
+        self.retire(retired);
+    }
+
     /// Ship a retired graph to the graveyard, or stash it if the graveyard is
     /// full. Never drops the graph on the current (render) thread.
     fn retire(&mut self, retired: Box<G>) {
@@ -259,6 +350,43 @@ impl<G: RenderGraph> RenderSwap<G> {
         }
     }
 
+    /// The cycle a [`Cmd::Seek`] applied since the last call moved to, if any.
+    /// The render loop calls this after `apply_pending_commands` to move its
+    /// own clock along with the graph.
+    pub fn take_seek(&mut self) -> Option<f64> {
+        self.seeked.take()
+    }
+
+    /// Where the transport is, as of the commands applied so far. While
+    /// [`Transport::is_held`], the render loop renders nothing and does not
+    /// advance its clock.
+    pub fn transport(&self) -> Transport {
+        self.transport
+    }
+
+    /// Fade a block just rendered at a transport edge: out on the block a
+    /// [`Cmd::Stop`] landed on (the loop then holds), in on the first block
+    /// after a [`Cmd::Start`]. Other blocks are left alone. `buffer` holds
+    /// `frames` interleaved frames.
+    pub fn finish_block(&mut self, buffer: &mut [f32], frames: usize) {
+        let fade_in = match self.transport {
+            Transport::Stopping => false,
+            Transport::Starting => true,
+            _ => return,
+        };
+        let channels = (buffer.len() / frames.max(1)).max(1);
+        for (i, frame) in buffer.chunks_mut(channels).enumerate() {
+            let ramp = i as f32 / frames as f32;
+            let gain = if fade_in { ramp } else { 1.0 - ramp };
+            frame.iter_mut().for_each(|s| *s *= gain);
+        }
+        self.transport = if fade_in {
+            Transport::Playing
+        } else {
+            Transport::Stopped
+        };
+    }
+
     /// Number of commands currently queued but not yet applied.
     pub fn pending_commands(&self) -> usize {
         self.cmd_rx.occupied_len()
@@ -337,6 +465,8 @@ pub fn render_swap_channel<G>(
             cmd_rx,
             grave_tx,
             stash: Vec::new(),
+            seeked: None,
+            transport: Transport::Playing,
         },
         Graveyard { rx: grave_rx },
     )
@@ -409,12 +539,31 @@ mod tests {
         fn set_cycle(&mut self, c: f64) {
             self.cycle = c;
         }
+        fn save_state(&self) -> Vec<u8> {
+            self.id.to_le_bytes().to_vec()
+        }
     }
 
     fn boxed(id: u64, drops: &Arc<AtomicUsize>) -> Box<MockGraph> {
         Box::new(MockGraph::new(id, drops.clone()))
     }
 
+    /// `replace` installs a graph without absorbing from the (crashed) current
+    /// one, and still retires the old graph to the janitor.
+    #[test]
+    fn test_replace_skips_absorb_and_retires() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (_tx, mut rsw, mut grave) = render_swap_channel::<MockGraph>(8, 8);
+        let mut cur = boxed(0, &drops);
+
+        rsw.replace(&mut cur, boxed(1, &drops));
+        assert_eq!(cur.id, 1);
+        assert_eq!(cur.absorbed_from, None);
+        assert_eq!(drops.load(Ordering::SeqCst), 0);
+        assert_eq!(grave.collect(), 1);
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+
     /// The primary TDD test: ownership is *moved* through the channel (not shared
     /// via a `RefCell`), and the retired graph is dropped by the janitor, never
     /// on the render thread.
@@ -485,6 +634,66 @@ mod tests {
         assert_eq!(drops.load(Ordering::SeqCst), 0);
     }
 
+    /// A seek reaches the graph and is reported once to the clock owner.
+    #[test]
+    fn test_seek_is_reported_to_render_loop() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (mut tx, mut rsw, _grave) = render_swap_channel_default::<MockGraph>();
+        let mut cur = boxed(0, &drops);
+        assert_eq!(rsw.take_seek(), None);
+
+        assert!(tx.send(Cmd::Seek(32.0)).is_ok());
+        assert_eq!(rsw.apply_pending_commands(&mut cur), 1);
+        assert_eq!(cur.cycle, 32.0);
+        assert_eq!(rsw.take_seek(), Some(32.0));
+        assert_eq!(rsw.take_seek(), None);
+    }
+
+    /// A state request is answered from the current graph.
+    #[test]
+    fn test_save_state_replies_from_current_graph() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (mut tx, mut rsw, _grave) = render_swap_channel_default::<MockGraph>();
+        let mut cur = boxed(7, &drops);
+        let (reply_tx, reply_rx) = std::sync::mpsc::sync_channel(1);
+
+        assert!(tx.send(Cmd::SaveState(reply_tx)).is_ok());
+        assert_eq!(rsw.apply_pending_commands(&mut cur), 1);
+        assert_eq!(reply_rx.try_recv().unwrap(), 7u64.to_le_bytes().to_vec());
+    }
+
+    /// Stop fades the next block out and then holds; start fades back in.
+    #[test]
+    fn test_transport_fades_at_its_edges() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (mut tx, mut rsw, _grave) = render_swap_channel_default::<MockGraph>();
+        let mut cur = boxed(0, &drops);
+        let mut block = vec![1.0f32; 8];
+        rsw.finish_block(&mut block, 4);
+        assert_eq!(block, [1.0; 8]);
+
+        assert!(tx.send(Cmd::Stop).is_ok());
+        rsw.apply_pending_commands(&mut cur);
+        assert_eq!(rsw.transport(), Transport::Stopping);
+        assert!(!rsw.transport().is_held());
+        rsw.finish_block(&mut block, 4);
+        assert_eq!(block, [1.0, 1.0, 0.75, 0.75, 0.5, 0.5, 0.25, 0.25]);
+        assert!(rsw.transport().is_held());
+
+        assert!(tx.send(Cmd::Start).is_ok());
+        rsw.apply_pending_commands(&mut cur);
+        let mut block = vec![1.0f32; 4];
+        rsw.finish_block(&mut block, 4);
+        assert_eq!(block, [0.0, 0.25, 0.5, 0.75]);
+        assert_eq!(rsw.transport(), Transport::Playing);
+
+        // A start before the stop's block was rendered just keeps playing
+        assert!(tx.send(Cmd::Stop).is_ok());
+        assert!(tx.send(Cmd::Start).is_ok());
+        rsw.apply_pending_commands(&mut cur);
+        assert_eq!(rsw.transport(), Transport::Playing);
+    }
+
     /// Commands are applied in the exact order enqueued: Hush-then-Swap means the
     /// Hush lands on the OLD graph *before* it is retired, and the swap installs
     /// the new graph afterwards.
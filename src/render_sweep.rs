@@ -0,0 +1,203 @@
+//! Parameter sweeps: `phonon sweep patch.ph --param ~cutoff=200..2000:8 --out dir/`
+//!
+//! Renders one variant of a patch per value of a bus: the bus's definition is
+//! replaced by the value, so everything reading `~cutoff` hears it. Several
+//! `--param` flags render every combination. Variants are named after the
+//! values (`patch_cutoff-200.wav`) and render in parallel like
+//! `phonon render-batch`.
+
+use crate::compositional_parser::{Expr, Statement};
+use crate::modal_editor::render_queue::{parse_job_code, render_statements, RenderLength};
+use crate::render_batch::{render_parallel, BatchResult};
+use std::path::{Path, PathBuf};
+
+/// Most variants one sweep renders
+const MAX_VARIANTS: usize = 1024;
+
+/// One swept bus and its values
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepParam {
+    pub bus: String,
+    pub values: Vec<f64>,
+}
+
+impl SweepParam {
+    /// Parse `~name=from..to:steps` (evenly spaced, both ends included) or
+    /// `~name=a,b,c`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (bus, values) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("Expected ~bus=values, got '{}'", spec))?;
+        let bus = bus.trim().trim_start_matches('~');
+        if bus.is_empty() {
+            return Err(format!("Missing bus name in '{}'", spec));
+        }
+        let number = |text: &str| {
+            text.trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(|| format!("Invalid number '{}' in '{}'", text.trim(), spec))
+        };
+
+        let values = match values.split_once("..") {
+            Some((from, rest)) => {
+                let (to, steps) = rest
+                    .split_once(':')
+                    .ok_or_else(|| format!("Expected from..to:steps in '{}'", spec))?;
+                let (from, to) = (number(from)?, number(to)?);
+                let steps: usize = steps
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("Steps must be a positive count in '{}'", spec))?;
+                if steps == 1 {
+                    vec![from]
+                } else {
+                    (0..steps)
+                        .map(|i| from + (to - from) * i as f64 / (steps - 1) as f64)
+                        .collect()
+                }
+            }
+            None => values.split(',').map(number).collect::<Result<_, _>>()?,
+        };
+        Ok(Self {
+            bus: bus.to_string(),
+            values,
+        })
+    }
+}
+
+/// Every combination of the swept values, as (bus, value) lists
+pub fn variants(params: &[SweepParam]) -> Vec<Vec<(String, f64)>> {
+    params.iter().fold(vec![Vec::new()], |done, param| {
+        done.iter()
+            .flat_map(|variant| {
+                param.values.iter().map(move |&value| {
+                    let mut next = variant.clone();
+                    next.push((param.bus.clone(), value));
+                    next
+                })
+            })
+            .collect()
+    })
+}
+
+/// A value as it appears in file names: at most 3 decimals, no trailing zeros
+fn value_label(value: f64) -> String {
+    let text = format!("{:.3}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Output file name of one variant: `stem_bus-value[_bus-value...].wav`
+pub fn variant_file_name(stem: &str, variant: &[(String, f64)]) -> String {
+    let mut name = stem.to_string();
+    for (bus, value) in variant {
+        name.push_str(&format!("_{}-{}", bus, value_label(*value)));
+    }
+    name + ".wav"
+}
+
+/// `statements` with each swept bus defined as its value. Fails if the patch
+/// doesn't define one of the buses.
+pub fn apply_variant(
+    statements: &[Statement],
+    variant: &[(String, f64)],
+) -> Result<Vec<Statement>, String> {
+    let mut statements = statements.to_vec();
+    for (bus, value) in variant {
+        let definition = statements.iter_mut().find_map(|s| match s {
+            Statement::BusAssignment { name, expr, .. } if name == bus => Some(expr),
+            _ => None,
+        });
+        match definition {
+            Some(expr) => *expr = Expr::Number(*value),
+            None => return Err(format!("~{} is not defined in the patch", bus)),
+        }
+    }
+    Ok(statements)
+}
+
+/// Render every variant of the patch at `file` into `out_dir`, `jobs` at a
+/// time (all cores if None). Results come back in sweep order.
+pub fn render_sweep(
+    file: &Path,
+    params: &[SweepParam],
+    out_dir: &Path,
+    length: RenderLength,
+    sample_rate: f32,
+    jobs: Option<usize>,
+) -> Result<Vec<BatchResult>, String> {
+    let code = std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let statements = parse_job_code(&code)?;
+    let variants = variants(params);
+    if variants.len() > MAX_VARIANTS {
+        return Err(format!(
+            "{} variants is too many (at most {})",
+            variants.len(),
+            MAX_VARIANTS
+        ));
+    }
+
+    let stem = file
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "sweep".to_string());
+    // Check every bus up front rather than failing each render
+    let renders = variants
+        .iter()
+        .map(|variant| {
+            let program = apply_variant(&statements, variant)?;
+            let output: PathBuf = out_dir.join(variant_file_name(&stem, variant));
+            Ok((file.to_path_buf(), output, program))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    render_parallel(renders, jobs, |output, program| {
+        render_statements(program, length, output, sample_rate, |_| {})
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sweep_param() {
+        let param = SweepParam::parse("~cutoff=200..2000:4").unwrap();
+        assert_eq!(param.bus, "cutoff");
+        assert_eq!(param.values, vec![200.0, 800.0, 1400.0, 2000.0]);
+
+        let param = SweepParam::parse("res=0.1, 0.5,0.9").unwrap();
+        assert_eq!(param.values, vec![0.1, 0.5, 0.9]);
+        assert_eq!(SweepParam::parse("~q=3..5:1").unwrap().values, vec![3.0]);
+
+        assert!(SweepParam::parse("~cutoff").is_err());
+        assert!(SweepParam::parse("~cutoff=200..2000").is_err());
+        assert!(SweepParam::parse("~cutoff=200..2000:0").is_err());
+        assert!(SweepParam::parse("=1,2").is_err());
+        assert!(SweepParam::parse("~cutoff=low,high").is_err());
+    }
+
+    #[test]
+    fn test_variants_cover_every_combination() {
+        let params = [
+            SweepParam::parse("~a=1,2").unwrap(),
+            SweepParam::parse("~b=0.25,0.5,0.75").unwrap(),
+        ];
+        let all = variants(&params);
+        assert_eq!(all.len(), 6);
+        assert_eq!(variant_file_name("pad", &all[5]), "pad_a-2_b-0.75.wav");
+        assert_eq!(
+            variant_file_name("pad", &[("cutoff".to_string(), 457.142857)]),
+            "pad_cutoff-457.143.wav"
+        );
+    }
+}
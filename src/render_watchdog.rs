@@ -0,0 +1,146 @@
+//! Render-thread watchdog
+//!
+//! A panic inside graph rendering (bad node math, an out-of-range index) used to
+//! kill the synthesis thread, leaving the stream silent until restart. The
+//! watchdog renders each block inside `catch_unwind`: a crashed block comes out
+//! as silence and the caller replaces the graph with a silent one, so the stream
+//! keeps running and the next evaluation brings the sound back.
+//!
+//! Every block is also scrubbed of NaN/inf on its way to the ring, whatever
+//! produced it, so a numeric blow-up can never reach the device.
+//!
+//! Crash and scrub reports go to the UI over a channel (the editor shows them
+//! in its console; `phonon live` prints them).
+
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::Sender;
+
+/// Guards the blocks rendered by one render thread
+#[derive(Debug)]
+pub struct RenderWatchdog {
+    reports: Sender<String>,
+    /// Whether the current graph has already been reported for non-finite output
+    nonfinite_reported: bool,
+    crashes: usize,
+}
+
+impl RenderWatchdog {
+    pub fn new(reports: Sender<String>) -> Self {
+        Self {
+            reports,
+            nonfinite_reported: false,
+            crashes: 0,
+        }
+    }
+
+    /// Render one block with `render`. Returns false if it panicked: the buffer
+    /// is then silent and the graph should be replaced before the next block.
+    pub fn run_block(&mut self, buffer: &mut [f32], render: impl FnOnce(&mut [f32])) -> bool {
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(|| render(&mut *buffer))) {
+            buffer.fill(0.0);
+            self.crashes += 1;
+            self.report(format!(
+                "💥 Synthesis crashed: {} — replaced with silence, re-evaluate to continue",
+                panic_message(payload.as_ref())
+            ));
+            self.nonfinite_reported = false;
+            return false;
+        }
+
+        let scrubbed = scrub_non_finite(buffer);
+        if scrubbed > 0 && !self.nonfinite_reported {
+            self.nonfinite_reported = true;
+            self.report(format!(
+                "⚠️  Output had {} NaN/inf samples (silenced) — check feedback or division",
+                scrubbed
+            ));
+        }
+        true
+    }
+
+    /// A different graph is now rendering; report its problems afresh
+    pub fn graph_changed(&mut self) {
+        self.nonfinite_reported = false;
+    }
+
+    /// Number of blocks that panicked so far
+    pub fn crashes(&self) -> usize {
+        self.crashes
+    }
+
+    fn report(&self, message: String) {
+        crate::rt_log::log(format_args!("{}", message));
+        let _ = self.reports.send(message);
+    }
+}
+
+/// Replace NaN and infinite samples with silence. Returns how many there were.
+pub fn scrub_non_finite(buffer: &mut [f32]) -> usize {
+    let mut count = 0;
+    for sample in buffer.iter_mut() {
+        if !sample.is_finite() {
+            *sample = 0.0;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// The message a panic was raised with
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_panicking_block_is_silenced_and_reported() {
+        let (tx, rx) = channel();
+        let mut watchdog = RenderWatchdog::new(tx);
+        let mut buffer = vec![0.5f32; 8];
+
+        let ok = watchdog.run_block(&mut buffer, |buf| {
+            buf[0] = 1.0;
+            panic!("index out of bounds: the len is 3 but the index is 7");
+        });
+        assert!(!ok);
+        assert!(buffer.iter().all(|&s| s == 0.0));
+        assert_eq!(watchdog.crashes(), 1);
+        let report = rx.try_recv().unwrap();
+        assert!(report.contains("index out of bounds"), "{}", report);
+
+        // The next block renders normally
+        assert!(watchdog.run_block(&mut buffer, |buf| buf.fill(0.25)));
+        assert!(buffer.iter().all(|&s| s == 0.25));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_non_finite_output_is_scrubbed_and_reported_once() {
+        let (tx, rx) = channel();
+        let mut watchdog = RenderWatchdog::new(tx);
+        let mut buffer = vec![0.0f32; 4];
+        let blow_up = |buf: &mut [f32]| buf.copy_from_slice(&[0.1, f32::NAN, f32::INFINITY, -0.1]);
+
+        assert!(watchdog.run_block(&mut buffer, blow_up));
+        assert_eq!(buffer, vec![0.1, 0.0, 0.0, -0.1]);
+        assert!(rx.try_recv().unwrap().contains("2 NaN/inf"));
+
+        watchdog.run_block(&mut buffer, blow_up);
+        assert!(rx.try_recv().is_err());
+
+        watchdog.graph_changed();
+        watchdog.run_block(&mut buffer, blow_up);
+        assert!(rx.try_recv().is_ok());
+    }
+}
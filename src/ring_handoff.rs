@@ -0,0 +1,202 @@
+//! The sample ring between the synthesis thread and the audio callback
+//!
+//! The synthesis thread renders blocks into a lock-free SPSC ring that the
+//! device callback plays from. After a hush, panic or cue the audio already
+//! queued is stale, and the callback should skip it rather than play up to
+//! a ring's worth of the old sound.
+//!
+//! The skip used to be a flag the UI set for the callback. But the UI sets it
+//! as it *sends* the command, so the callback could drop the queue before the
+//! synthesis thread applied the command, and then play more pre-hush audio
+//! rendered in between. Here the synthesis thread, which applies the command,
+//! marks the boundary: [`RingWriter::discard_queued`] publishes how many
+//! samples it has written so far, and [`RingReader`] skips up to that count.
+//! Both sides only touch atomics and the ring, so the handoff is free of data
+//! races and never blocks either thread (see tests/loom_ring_handoff.rs).
+
+use ringbuf::traits::{Consumer, Observer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Counters shared by the two ends
+#[derive(Debug, Default)]
+struct Shared {
+    /// Total samples written before the last [`RingWriter::discard_queued`]
+    stale_until: AtomicU64,
+}
+
+/// The synthesis thread's end
+pub struct RingWriter {
+    producer: HeapProd<f32>,
+    shared: Arc<Shared>,
+    /// Samples written so far
+    written: u64,
+}
+
+/// The audio callback's end
+pub struct RingReader {
+    consumer: HeapCons<f32>,
+    shared: Arc<Shared>,
+    /// Samples read (or skipped) so far
+    read: u64,
+    underruns: Arc<AtomicUsize>,
+}
+
+/// A ring of `capacity` samples. The reader counts underruns in `underruns`
+pub fn sample_ring(capacity: usize, underruns: Arc<AtomicUsize>) -> (RingWriter, RingReader) {
+    let (producer, consumer) = HeapRb::<f32>::new(capacity).split();
+    let shared = Arc::new(Shared::default());
+    (
+        RingWriter {
+            producer,
+            shared: Arc::clone(&shared),
+            written: 0,
+        },
+        RingReader {
+            consumer,
+            shared,
+            read: 0,
+            underruns,
+        },
+    )
+}
+
+impl RingWriter {
+    /// Queue as many of `samples` as fit, returning how many
+    pub fn push(&mut self, samples: &[f32]) -> usize {
+        let pushed = self.producer.push_slice(samples);
+        self.written += pushed as u64;
+        pushed
+    }
+
+    /// Free space, in samples
+    pub fn vacant_len(&self) -> usize {
+        self.producer.vacant_len()
+    }
+
+    /// How full the ring is, 0 to 100
+    pub fn fill_percent(&self) -> usize {
+        let capacity = self.producer.capacity().get();
+        (capacity - self.producer.vacant_len()) * 100 / capacity
+    }
+
+    /// Mark everything queued so far as stale: the reader skips it instead of
+    /// playing it. Call after applying the command that made it stale
+    pub fn discard_queued(&mut self) {
+        self.shared
+            .stale_until
+            .store(self.written, Ordering::Release);
+    }
+}
+
+impl RingReader {
+    /// Skip any stale samples, returning whether there were some
+    pub fn skip_stale(&mut self) -> bool {
+        let stale_until = self.shared.stale_until.load(Ordering::Acquire);
+        if stale_until <= self.read {
+            return false;
+        }
+        // The writer pushed these before publishing the mark, so they're in
+        // the ring
+        let stale = ((stale_until - self.read) as usize).min(self.consumer.occupied_len());
+        self.consumer.skip(stale);
+        self.read += stale as u64;
+        stale > 0
+    }
+
+    /// Samples ready to play
+    pub fn occupied_len(&self) -> usize {
+        self.consumer.occupied_len()
+    }
+
+    /// Play into `data`, filling with silence (and counting an underrun) when
+    /// the ring runs short
+    pub fn fill(&mut self, data: &mut [f32]) {
+        let read = self.consumer.pop_slice(data);
+        self.read += read as u64;
+        if read < data.len() {
+            data[read..].fill(0.0);
+            self.underruns.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The next sample, or silence when the ring is empty
+    pub fn next_sample(&mut self) -> f32 {
+        match self.consumer.try_pop() {
+            Some(sample) => {
+                self.read += 1;
+                sample
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Count an underrun noticed by the caller
+    pub fn note_underrun(&self) {
+        self.underruns.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(capacity: usize) -> (RingWriter, RingReader, Arc<AtomicUsize>) {
+        let underruns = Arc::new(AtomicUsize::new(0));
+        let (writer, reader) = sample_ring(capacity, Arc::clone(&underruns));
+        (writer, reader, underruns)
+    }
+
+    #[test]
+    fn test_fill_plays_in_order_and_counts_underruns() {
+        let (mut writer, mut reader, underruns) = ring(8);
+        assert_eq!(writer.push(&[1.0, 2.0, 3.0]), 3);
+        let mut data = [9.0; 2];
+        reader.fill(&mut data);
+        assert_eq!(data, [1.0, 2.0]);
+        assert_eq!(underruns.load(Ordering::Relaxed), 0);
+
+        reader.fill(&mut data);
+        assert_eq!(data, [3.0, 0.0]);
+        assert_eq!(underruns.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_discard_skips_only_audio_queued_before_it() {
+        let (mut writer, mut reader, _) = ring(16);
+        writer.push(&[1.0; 4]);
+        reader.fill(&mut [0.0; 1]);
+        writer.push(&[1.0; 2]);
+        writer.discard_queued();
+        // Rendered after the command was applied: must be heard
+        writer.push(&[5.0, 6.0]);
+
+        assert!(reader.skip_stale());
+        assert!(!reader.skip_stale());
+        let mut data = [0.0; 2];
+        reader.fill(&mut data);
+        assert_eq!(data, [5.0, 6.0]);
+    }
+
+    #[test]
+    fn test_discard_of_already_played_audio_is_a_no_op() {
+        let (mut writer, mut reader, _) = ring(8);
+        writer.push(&[1.0, 2.0]);
+        reader.fill(&mut [0.0; 2]);
+        writer.discard_queued();
+        writer.push(&[3.0]);
+        assert!(!reader.skip_stale());
+        assert_eq!(reader.next_sample(), 3.0);
+        assert_eq!(reader.next_sample(), 0.0);
+    }
+
+    #[test]
+    fn test_fill_percent() {
+        let (mut writer, _reader, _) = ring(10);
+        assert_eq!(writer.fill_percent(), 0);
+        writer.push(&[0.0; 5]);
+        assert_eq!(writer.fill_percent(), 50);
+        assert_eq!(writer.vacant_len(), 5);
+    }
+}
@@ -0,0 +1,252 @@
+//! Realtime-safe logging for the audio and synthesis threads
+//!
+//! `eprintln!` and the tracing file writer take locks and make syscalls, so
+//! a message from the render loop can hold it past its deadline. [`log`]
+//! instead formats the message into a fixed-size record on the caller's
+//! stack and pushes it onto a preallocated lock-free queue: nothing
+//! allocates, locks or blocks. When the queue is full the record is dropped
+//! and counted.
+//!
+//! Queueing starts with [`set_sink`]. From then on the UI thread calls
+//! [`drain`] to write the queued records to the sink (the editor's log
+//! file). Until then, as in offline renders and tests, messages go straight
+//! to stderr. [`RtLogWriter`] makes a tracing subscriber write through the
+//! same queue, so `tracing` events from the render path are safe too.
+
+use crossbeam_queue::ArrayQueue;
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Longest message kept, in bytes. Longer ones are truncated (tracing
+/// output is split over several records instead)
+pub const RECORD_BYTES: usize = 192;
+
+/// Records the global log holds before dropping
+const CAPACITY: usize = 1024;
+
+/// One message, newline included
+#[derive(Clone, Copy)]
+struct Record {
+    len: usize,
+    bytes: [u8; RECORD_BYTES],
+}
+
+impl Record {
+    fn new() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; RECORD_BYTES],
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
+
+impl fmt::Write for Record {
+    /// Copies what fits, keeping a byte for the newline and cutting on a
+    /// character boundary
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let room = RECORD_BYTES - 1 - self.len;
+        let mut take = s.len().min(room);
+        while !s.is_char_boundary(take) {
+            take -= 1;
+        }
+        self.bytes[self.len..self.len + take].copy_from_slice(&s.as_bytes()[..take]);
+        self.len += take;
+        Ok(())
+    }
+}
+
+/// A lock-free queue of log records
+pub struct RtLog {
+    queue: ArrayQueue<Record>,
+    dropped: AtomicUsize,
+    queueing: AtomicBool,
+    /// Where [`RtLog::drain_to_sink`] writes. Only the draining thread locks it
+    sink: Mutex<Option<Box<dyn Write + Send>>>,
+}
+
+impl RtLog {
+    /// A log holding up to `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queue: ArrayQueue::new(capacity.max(1)),
+            dropped: AtomicUsize::new(0),
+            queueing: AtomicBool::new(false),
+            sink: Mutex::new(None),
+        }
+    }
+
+    /// Queue a message from any thread. Before queueing starts it's written
+    /// to stderr directly
+    pub fn log(&self, args: fmt::Arguments) {
+        if !self.queueing.load(Ordering::Relaxed) {
+            eprintln!("{}", args);
+            return;
+        }
+        let mut record = Record::new();
+        let _ = fmt::Write::write_fmt(&mut record, args);
+        record.bytes[record.len] = b'\n';
+        record.len += 1;
+        self.push(record);
+    }
+
+    /// Queue raw bytes, split into as many records as they need
+    pub fn log_bytes(&self, bytes: &[u8]) {
+        if !self.queueing.load(Ordering::Relaxed) {
+            let _ = io::stderr().write_all(bytes);
+            return;
+        }
+        for chunk in bytes.chunks(RECORD_BYTES) {
+            let mut record = Record::new();
+            record.bytes[..chunk.len()].copy_from_slice(chunk);
+            record.len = chunk.len();
+            self.push(record);
+        }
+    }
+
+    fn push(&self, record: Record) {
+        if self.queue.push(record).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Queue messages from now on, for draining to `sink`
+    pub fn set_sink(&self, sink: Box<dyn Write + Send>) {
+        *self.sink.lock().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+        self.queueing.store(true, Ordering::Release);
+    }
+
+    /// Write every queued record to `out`, then a note of any dropped since
+    /// the last drain. Returns the number of records written
+    pub fn drain(&self, out: &mut dyn Write) -> io::Result<usize> {
+        let mut count = 0;
+        while let Some(record) = self.queue.pop() {
+            out.write_all(record.as_bytes())?;
+            count += 1;
+        }
+        let dropped = self.dropped.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            writeln!(out, "[rt_log] {} messages dropped (queue full)", dropped)?;
+        }
+        out.flush()?;
+        Ok(count)
+    }
+
+    /// [`drain`](Self::drain) to the sink set with [`set_sink`](Self::set_sink)
+    pub fn drain_to_sink(&self) -> usize {
+        if self.queue.is_empty() && self.dropped.load(Ordering::Relaxed) == 0 {
+            return 0;
+        }
+        let mut sink = self.sink.lock().unwrap_or_else(|e| e.into_inner());
+        match sink.as_mut() {
+            Some(sink) => self.drain(sink.as_mut()).unwrap_or(0),
+            None => self.drain(&mut io::stderr()).unwrap_or(0),
+        }
+    }
+
+    /// Records dropped since the last drain
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// The process-wide log
+pub fn global() -> &'static RtLog {
+    static GLOBAL: OnceLock<RtLog> = OnceLock::new();
+    GLOBAL.get_or_init(|| RtLog::new(CAPACITY))
+}
+
+/// Log a message on the global log: `rt_log::log(format_args!("..."))`
+pub fn log(args: fmt::Arguments) {
+    global().log(args);
+}
+
+/// Queue global messages from now on, for draining to `sink`
+pub fn set_sink(sink: impl Write + Send + 'static) {
+    global().set_sink(Box::new(sink));
+}
+
+/// Write queued global messages to the sink. Call from the UI thread
+pub fn drain() -> usize {
+    global().drain_to_sink()
+}
+
+/// An `io::Write` onto the global log, for
+/// `tracing_subscriber::fmt().with_writer(|| RtLogWriter)`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RtLogWriter;
+
+impl Write for RtLogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        global().log_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued() -> RtLog {
+        let log = RtLog::new(4);
+        log.set_sink(Box::new(io::sink()));
+        log
+    }
+
+    fn drained(log: &RtLog) -> String {
+        let mut out = Vec::new();
+        log.drain(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_messages_drain_in_order() {
+        let log = queued();
+        log.log(format_args!("synth: {} renders/s", 86));
+        log.log(format_args!("peak {:.1}ms", 12.34));
+        assert_eq!(drained(&log), "synth: 86 renders/s\npeak 12.3ms\n");
+        assert_eq!(drained(&log), "");
+    }
+
+    #[test]
+    fn test_full_queue_drops_and_reports() {
+        let log = queued();
+        for i in 0..6 {
+            log.log(format_args!("{}", i));
+        }
+        assert_eq!(log.dropped(), 2);
+        assert_eq!(
+            drained(&log),
+            "0\n1\n2\n3\n[rt_log] 2 messages dropped (queue full)\n"
+        );
+        assert_eq!(log.dropped(), 0);
+    }
+
+    #[test]
+    fn test_long_message_truncates_on_char_boundary() {
+        let log = queued();
+        let long = "é".repeat(RECORD_BYTES);
+        log.log(format_args!("{}", long));
+        let text = drained(&log);
+        assert!(text.len() <= RECORD_BYTES);
+        assert!(text.ends_with("é\n"));
+    }
+
+    #[test]
+    fn test_bytes_split_over_records() {
+        let log = RtLog::new(8);
+        log.set_sink(Box::new(io::sink()));
+        let line = format!("{}\n", "x".repeat(RECORD_BYTES * 2));
+        log.log_bytes(line.as_bytes());
+        assert_eq!(drained(&log), line);
+    }
+}
@@ -97,10 +97,12 @@
 //! ```
 
 #![allow(clippy::collapsible_if)]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Index;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock, PoisonError, RwLock};
+use std::time::SystemTime;
 
 /// Stereo sample data - supports both mono and stereo samples
 ///
@@ -188,6 +190,27 @@ impl StereoSample {
         (l + r) * 0.5
     }
 
+    /// Resample from `from_rate` to `to_rate` (linear interpolation), so the
+    /// sample plays at its recorded pitch on a graph running at `to_rate`
+    pub fn resampled(&self, from_rate: f32, to_rate: f32) -> Self {
+        if (from_rate - to_rate).abs() < f32::EPSILON || self.is_empty() {
+            return self.clone();
+        }
+        let step = from_rate / to_rate;
+        let frames = ((self.len() as f32) / step).round() as usize;
+        let positions = (0..frames).map(|i| i as f32 * step);
+
+        let left = positions
+            .clone()
+            .map(|pos| self.get_interpolated(pos).0)
+            .collect();
+        let right = self
+            .right
+            .as_ref()
+            .map(|_| positions.map(|pos| self.get_interpolated(pos).1).collect());
+        Self { left, right }
+    }
+
     /// Create a sliced version of this sample (preserves stereo if present)
     pub fn slice(&self, begin: usize, end: usize) -> Self {
         let begin = begin.min(self.left.len());
@@ -235,10 +258,19 @@ impl From<Vec<f32>> for StereoSample {
 }
 
 /// Sample bank that loads and caches WAV files
+///
+/// Folder contents are indexed when the bank is created, but WAV data is only
+/// decoded on first use (or ahead of time by [`SampleBank::prefetch`]),
+/// through a cache shared by every bank in the process.
 pub struct SampleBank {
     samples: HashMap<String, Arc<StereoSample>>,
     /// List of directories to search for samples, in priority order
     sample_dirs: Vec<PathBuf>,
+    /// Playback sample rate: files recorded at other rates are resampled on load
+    sample_rate: f32,
+    /// WAV files of each folder, sorted by file name, from the first
+    /// directory that has any
+    index: Arc<HashMap<String, Vec<PathBuf>>>,
 }
 
 impl Clone for SampleBank {
@@ -246,8 +278,131 @@ impl Clone for SampleBank {
         Self {
             samples: self.samples.clone(), // Arc makes this cheap - just increments ref count
             sample_dirs: self.sample_dirs.clone(),
+            sample_rate: self.sample_rate,
+            index: self.index.clone(),
+        }
+    }
+}
+
+/// Extra sample directories from the editor config (`sample_paths`), searched
+/// right after the bundled samples by every bank created afterwards
+static EXTRA_SAMPLE_DIRS: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// Set the extra sample directories used by new sample banks
+pub fn set_extra_sample_dirs(dirs: Vec<PathBuf>) {
+    if let Ok(mut extra) = EXTRA_SAMPLE_DIRS.write() {
+        *extra = dirs;
+    }
+}
+
+/// Decoded sample memory the shared cache keeps by default
+const DEFAULT_SAMPLE_MEMORY_LIMIT: usize = 512 * 1024 * 1024;
+
+static SAMPLE_MEMORY_LIMIT: AtomicUsize = AtomicUsize::new(DEFAULT_SAMPLE_MEMORY_LIMIT);
+
+/// Set how much decoded sample data the shared cache keeps (`sample_memory_mb`
+/// in the editor config). Beyond it the least recently used samples are
+/// dropped. A sample bank keeps every sample it has loaded, so samples the
+/// playing program has used stay resident (and counted) until it is replaced.
+pub fn set_sample_memory_limit(bytes: usize) {
+    SAMPLE_MEMORY_LIMIT.store(bytes, Ordering::Relaxed);
+    let mut state = shared_cache()
+        .state
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    state.evict(bytes, None);
+}
+
+/// The cap set by [`set_sample_memory_limit`]
+pub fn sample_memory_limit() -> usize {
+    SAMPLE_MEMORY_LIMIT.load(Ordering::Relaxed)
+}
+
+/// Decoded sample memory of one folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderMemory {
+    pub folder: String,
+    pub files: usize,
+    pub bytes: usize,
+}
+
+/// Decoded sample memory the shared cache holds right now, by folder, largest
+/// first
+pub fn resident_memory() -> Vec<FolderMemory> {
+    let state = shared_cache()
+        .state
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    memory_by_folder(
+        state
+            .entries
+            .iter()
+            .map(|(key, entry)| (key.path.as_path(), entry.bytes)),
+    )
+}
+
+/// The part of [`resident_memory`] that a sample bank or voice still holds,
+/// which the limit can't drop
+pub fn held_memory() -> usize {
+    let state = shared_cache()
+        .state
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    state
+        .entries
+        .values()
+        .filter(|entry| entry.is_held())
+        .map(|entry| entry.bytes)
+        .sum()
+}
+
+/// Files the shared cache holds decoded right now, sorted
+pub fn resident_files() -> Vec<PathBuf> {
+    let state = shared_cache()
+        .state
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let mut paths: Vec<PathBuf> = state.entries.keys().map(|key| key.path.clone()).collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Decode `paths` into the shared cache at `sample_rate` on a background
+/// thread. Files that are gone or unreadable are skipped.
+pub fn preload_files(paths: Vec<PathBuf>, sample_rate: f32) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for path in paths {
+            let _ = shared_cache().load(&path, sample_rate);
         }
+    })
+}
+
+/// Sum `(file, bytes)` by the file's folder name, largest first
+fn memory_by_folder<'a>(files: impl Iterator<Item = (&'a Path, usize)>) -> Vec<FolderMemory> {
+    let mut folders: HashMap<String, FolderMemory> = HashMap::new();
+    for (path, bytes) in files {
+        let folder = path
+            .parent()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let entry = folders.entry(folder.clone()).or_insert(FolderMemory {
+            folder,
+            files: 0,
+            bytes: 0,
+        });
+        entry.files += 1;
+        entry.bytes += bytes;
     }
+    let mut folders: Vec<FolderMemory> = folders.into_values().collect();
+    folders.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.folder.cmp(&b.folder)));
+    folders
+}
+
+/// `bytes` in megabytes, for display: "12.3 MB"
+pub fn format_bytes(bytes: usize) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
 }
 
 impl Default for SampleBank {
@@ -258,12 +413,28 @@ impl Default for SampleBank {
 
 impl SampleBank {
     pub fn new() -> Self {
+        Self::with_sample_rate(44100.0)
+    }
+
+    /// Sample bank for a graph running at `sample_rate`
+    pub fn with_sample_rate(sample_rate: f32) -> Self {
+        let extra = EXTRA_SAMPLE_DIRS
+            .read()
+            .map(|extra| extra.clone())
+            .unwrap_or_default();
+        Self::with_extra_dirs(&extra, sample_rate)
+    }
+
+    /// Sample bank searching `extra` right after the bundled samples, in
+    /// place of the `sample_paths` set by [`set_extra_sample_dirs`]
+    pub fn with_extra_dirs(extra: &[PathBuf], sample_rate: f32) -> Self {
         // Build list of sample directories to search, in priority order:
         // 1. ./samples/ (bundled repo samples - highest priority for testing)
-        // 2. ~/phonon/samples/ (user's custom samples)
-        // 3. ~/phonon/dirt-samples/ (SuperDirt compatibility)
-        // 4. ./dirt-samples/ (fallback)
-        // 5. ~/dirt-samples/ (another common location)
+        // 2. `extra` (`sample_paths` from ~/.phonon/config.toml)
+        // 3. ~/phonon/samples/ (user's custom samples)
+        // 4. ~/phonon/dirt-samples/ (SuperDirt compatibility)
+        // 5. ./dirt-samples/ (fallback)
+        // 6. ~/dirt-samples/ (another common location)
         let mut sample_dirs = Vec::new();
 
         // Bundled samples (highest priority for tests)
@@ -272,6 +443,8 @@ impl SampleBank {
             sample_dirs.push(bundled);
         }
 
+        sample_dirs.extend(extra.iter().filter(|dir| dir.exists()).cloned());
+
         if let Some(home) = dirs::home_dir() {
             // User's phonon samples
             let user_samples = home.join("phonon").join("samples");
@@ -298,45 +471,34 @@ impl SampleBank {
             sample_dirs.push(local_dirt);
         }
 
-        let mut bank = Self {
+        Self::with_dirs(sample_dirs, sample_rate)
+    }
+
+    /// Sample bank searching `sample_dirs`, in priority order. Only the folder
+    /// listings are read here; samples decode on first use.
+    pub fn with_dirs(sample_dirs: Vec<PathBuf>, sample_rate: f32) -> Self {
+        let index = Arc::new(index_sample_dirs(&sample_dirs));
+        Self {
             samples: HashMap::new(),
             sample_dirs,
-        };
-
-        // Pre-load common samples
-        let _ = bank.load_default_samples();
-        bank
-    }
-
-    /// Load default drum samples from first available directory
-    fn load_default_samples(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        // Sample names to pre-load (common drum sounds)
-        let sample_names = ["bd", "sn", "hh", "cp", "oh", "lt", "mt", "ht", "blip"];
-
-        for name in sample_names {
-            // Try to load from any available directory
-            for sample_dir in &self.sample_dirs {
-                let sample_subdir = sample_dir.join(name);
-                if sample_subdir.exists() && sample_subdir.is_dir() {
-                    // Find first .wav file in the directory
-                    if let Ok(entries) = std::fs::read_dir(&sample_subdir) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                            if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                                if ext.eq_ignore_ascii_case("wav") {
-                                    if self.load_sample(name, &path).is_ok() {
-                                        break; // Loaded successfully, stop searching
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    break; // Found the directory, even if loading failed
-                }
-            }
+            sample_rate,
+            index,
         }
+    }
 
-        Ok(())
+    /// Playback sample rate samples are resampled to
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
+    /// Change the playback sample rate. Cached samples are dropped and reload
+    /// (resampled) on next use, so call this off the audio thread.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if (self.sample_rate - sample_rate).abs() < f32::EPSILON {
+            return;
+        }
+        self.sample_rate = sample_rate;
+        self.samples.clear();
     }
 
     /// Load a sample from disk
@@ -349,105 +511,369 @@ impl SampleBank {
             return Ok(()); // Already loaded
         }
 
-        let mut reader = hound::WavReader::open(path)?;
-        let spec = reader.spec();
-
-        // Read raw samples as f32
-        let raw_samples: Vec<f32> = match spec.sample_format {
-            hound::SampleFormat::Float => {
-                reader.samples::<f32>().map(|s| s.unwrap_or(0.0)).collect()
-            }
-            hound::SampleFormat::Int => {
-                let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
-                reader
-                    .samples::<i32>()
-                    .map(|s| s.unwrap_or(0) as f32 / max_val)
-                    .collect()
-            }
-        };
-
-        // Create StereoSample, preserving stereo if present
-        let stereo_sample = if spec.channels == 2 {
-            // Deinterleave stereo: L R L R L R -> (L L L, R R R)
-            let num_frames = raw_samples.len() / 2;
-            let mut left = Vec::with_capacity(num_frames);
-            let mut right = Vec::with_capacity(num_frames);
-            for chunk in raw_samples.chunks(2) {
-                left.push(chunk[0]);
-                right.push(chunk.get(1).copied().unwrap_or(0.0));
-            }
-            StereoSample::stereo(left, right)
-        } else {
-            StereoSample::mono(raw_samples)
-        };
-
-        self.samples
-            .insert(name.to_string(), Arc::new(stereo_sample));
+        let sample = shared_cache().load(path, self.sample_rate)?;
+        self.samples.insert(name.to_string(), sample);
         Ok(())
     }
 
     /// Get a sample by name, searching all sample directories
     pub fn get_sample(&mut self, name: &str) -> Option<Arc<StereoSample>> {
-        // Parse sample name and index (e.g., "bd:3" -> "bd", 3)
-        let (base_name, sample_index) = if let Some(colon_pos) = name.find(':') {
-            let base = &name[..colon_pos];
-            let index_str = &name[colon_pos + 1..];
-            let index = index_str.parse::<usize>().unwrap_or(0);
-            (base, Some(index))
-        } else {
-            (name, None)
-        };
-
         // Check cache first (use full name as key)
         if let Some(sample) = self.samples.get(name) {
             return Some(sample.clone());
         }
 
-        // Search across all sample directories
-        for sample_dir_root in self.sample_dirs.clone() {
-            let sample_dir = sample_dir_root.join(base_name);
+        let path = self.sample_path(name)?;
+        self.load_sample(name, &path).ok()?;
+        self.samples.get(name).cloned()
+    }
+
+    /// The file `name` plays (e.g., "bd:3" -> 4th file of bd/). The index wraps
+    /// around the folder's file count; a missing or invalid one is 0.
+    fn sample_path(&mut self, name: &str) -> Option<PathBuf> {
+        let (folder, index) = match name.split_once(':') {
+            Some((folder, index)) => (folder, index.parse::<usize>().unwrap_or(0)),
+            None => (name, 0),
+        };
+
+        if !self.index.contains_key(folder) {
+            // Folders created after the bank was indexed
+            let files = self
+                .sample_dirs
+                .iter()
+                .map(|dir| wav_files(&dir.join(folder)))
+                .find(|files| !files.is_empty())?;
+            Arc::make_mut(&mut self.index).insert(folder.to_string(), files);
+        }
+
+        let files = self.index.get(folder)?;
+        files.get(index % files.len()).cloned()
+    }
+
+    /// Decode `names` ("bd", "sn:2", or "hh:rr" for a whole folder) on a
+    /// background thread, so their first trigger doesn't wait on the disk.
+    /// Names that aren't found are skipped.
+    pub fn prefetch<I>(&mut self, names: I) -> std::thread::JoinHandle<()>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut paths = Vec::new();
+        for name in names {
+            match name.strip_suffix(":rr") {
+                Some(folder) => {
+                    if self.sample_path(folder).is_some() {
+                        paths.extend(self.index.get(folder).into_iter().flatten().cloned());
+                    }
+                }
+                None => paths.extend(self.sample_path(&name)),
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        preload_files(paths, self.sample_rate)
+    }
+
+    /// Memory each folder takes once all its files are decoded at the bank's
+    /// rate, worked out from the WAV headers without decoding. Largest first.
+    pub fn decoded_memory(&self) -> Vec<FolderMemory> {
+        let sizes: Vec<(&Path, usize)> = self
+            .index
+            .values()
+            .flatten()
+            .filter_map(|path| {
+                let reader = hound::WavReader::open(path).ok()?;
+                let spec = reader.spec();
+                let frames =
+                    reader.duration() as f64 * self.sample_rate as f64 / spec.sample_rate as f64;
+                let bytes =
+                    frames.ceil() as usize * spec.channels as usize * std::mem::size_of::<f32>();
+                Some((path.as_path(), bytes))
+            })
+            .collect();
+        memory_by_folder(sizes.into_iter())
+    }
+
+    /// Sample banks `s` can play, with their number of WAV files. Like
+    /// `get_sample`, a name comes from the first directory that has WAVs for it.
+    pub fn banks(&self) -> Vec<(String, usize)> {
+        let mut banks: Vec<(String, usize)> = self
+            .index
+            .iter()
+            .map(|(name, files)| (name.clone(), files.len()))
+            .collect();
+        banks.sort();
+        banks
+    }
+
+    /// Velocity layers of `name`: the upper level of each `name-v1`,
+    /// `name-v2`, ... folder, softest first, or `None` if `name` isn't layered.
+    /// The levels come from `layers` in a `name.toml` next to the layer
+    /// folders; without one, the layers split 0..1 evenly.
+    pub fn velocity_layers(&self, name: &str) -> Option<Vec<f32>> {
+        let sample_dir = self
+            .sample_dirs
+            .iter()
+            .find(|dir| dir.join(format!("{}-v1", name)).is_dir())?;
+        let count = (1..)
+            .take_while(|layer| sample_dir.join(format!("{}-v{}", name, layer)).is_dir())
+            .count();
+
+        let config_path = sample_dir.join(format!("{}.toml", name));
+        if let Ok(text) = std::fs::read_to_string(&config_path) {
+            match toml::from_str::<VelocityLayerConfig>(&text) {
+                Ok(config) if config.layers.len() == count => return Some(config.layers),
+                Ok(config) => eprintln!(
+                    "Warning: {} lists {} layers but {} has {}; splitting evenly",
+                    config_path.display(),
+                    config.layers.len(),
+                    name,
+                    count
+                ),
+                Err(e) => eprintln!("Warning: {}: {}", config_path.display(), e),
+            }
+        }
+        Some(
+            (1..=count)
+                .map(|layer| layer as f32 / count as f32)
+                .collect(),
+        )
+    }
+}
+
+/// `name.toml` next to a velocity-layered kit's `name-v1`, `name-v2`, ...
+/// folders
+#[derive(Debug, serde::Deserialize)]
+struct VelocityLayerConfig {
+    /// Upper level of each layer, softest first
+    layers: Vec<f32>,
+}
+
+/// The layer (1-based) a hit at `level` plays from `velocity_layers`, and the
+/// gain to play it at: scaled within the layer so its top level plays the
+/// recording at unity
+pub fn pick_velocity_layer(layers: &[f32], level: f32) -> (usize, f32) {
+    let index = layers
+        .iter()
+        .position(|&top| level <= top + 1e-6)
+        .unwrap_or(layers.len().saturating_sub(1));
+    let top = layers.get(index).copied().unwrap_or(1.0);
+    let gain = if top > 0.0 { level / top } else { level };
+    (index + 1, gain)
+}
+
+/// WAV files in `dir`, sorted by file name (empty if it isn't a directory)
+fn wav_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|s| s.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+        })
+        .collect();
+    files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    files
+}
 
-            if !sample_dir.exists() || !sample_dir.is_dir() {
+/// WAV files of every folder in `sample_dirs`; a folder name is taken from the
+/// first directory that has WAVs for it
+fn index_sample_dirs(sample_dirs: &[PathBuf]) -> HashMap<String, Vec<PathBuf>> {
+    let mut index = HashMap::new();
+    for sample_dir in sample_dirs {
+        let Ok(entries) = std::fs::read_dir(sample_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if index.contains_key(&name) || !entry.path().is_dir() {
                 continue;
             }
+            let files = wav_files(&entry.path());
+            if !files.is_empty() {
+                index.insert(name, files);
+            }
+        }
+    }
+    index
+}
 
-            if let Ok(entries) = std::fs::read_dir(&sample_dir) {
-                let mut wav_files: Vec<_> = entries
-                    .filter_map(|entry| entry.ok())
-                    .filter(|entry| {
-                        entry
-                            .path()
-                            .extension()
-                            .and_then(|s| s.to_str())
-                            .map(|ext| ext.eq_ignore_ascii_case("wav"))
-                            .unwrap_or(false)
-                    })
-                    .collect();
-
-                if wav_files.is_empty() {
-                    continue;
-                }
+/// Read a WAV file, resampled to `sample_rate`
+fn decode_wav(path: &Path, sample_rate: f32) -> Result<StereoSample, hound::Error> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+
+    // Read raw samples as f32
+    let raw_samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().map(|s| s.unwrap_or(0.0)).collect(),
+        hound::SampleFormat::Int => {
+            let max_val = (1 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.unwrap_or(0) as f32 / max_val)
+                .collect()
+        }
+    };
+
+    // Create StereoSample, preserving stereo if present
+    let stereo_sample = if spec.channels == 2 {
+        // Deinterleave stereo: L R L R L R -> (L L L, R R R)
+        let num_frames = raw_samples.len() / 2;
+        let mut left = Vec::with_capacity(num_frames);
+        let mut right = Vec::with_capacity(num_frames);
+        for chunk in raw_samples.chunks(2) {
+            left.push(chunk[0]);
+            right.push(chunk.get(1).copied().unwrap_or(0.0));
+        }
+        StereoSample::stereo(left, right)
+    } else {
+        StereoSample::mono(raw_samples)
+    };
+
+    Ok(stereo_sample.resampled(spec.sample_rate as f32, sample_rate))
+}
 
-                // Sort by filename for consistent ordering
-                wav_files.sort_by_key(|entry| entry.file_name());
+/// Decoded samples shared by every bank, so a reload (which builds a new
+/// bank) doesn't decode its kit again
+struct SampleCache {
+    state: Mutex<CacheState>,
+    /// Signalled whenever a file finishes decoding
+    decoded: Condvar,
+}
 
-                // Determine which file to load
-                let file_index = if let Some(index) = sample_index {
-                    // Wrap index if larger than available files
-                    index % wav_files.len()
-                } else {
-                    0 // Default to first file
-                };
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Files some thread is decoding right now
+    loading: HashSet<CacheKey>,
+    resident_bytes: usize,
+    /// Use counter for least-recently-used eviction
+    clock: u64,
+}
 
-                if let Some(wav_file) = wav_files.get(file_index) {
-                    if self.load_sample(name, &wav_file.path()).is_ok() {
-                        return self.samples.get(name).cloned();
-                    }
-                }
+/// A file decoded for one playback rate. Keyed by modification time too, so
+/// a re-exported sample is picked up.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    sample_rate: u32,
+}
+
+struct CacheEntry {
+    sample: Arc<StereoSample>,
+    bytes: usize,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    /// Whether anything besides the cache has the sample: dropping it then
+    /// would free nothing, only lose track of it
+    fn is_held(&self) -> bool {
+        Arc::strong_count(&self.sample) > 1
+    }
+}
+
+fn shared_cache() -> &'static SampleCache {
+    static CACHE: OnceLock<SampleCache> = OnceLock::new();
+    CACHE.get_or_init(|| SampleCache {
+        state: Mutex::new(CacheState::default()),
+        decoded: Condvar::new(),
+    })
+}
+
+impl SampleCache {
+    /// `path` decoded at `sample_rate`. A file another thread (e.g. a
+    /// prefetch) is already decoding is waited for rather than decoded twice.
+    fn load(&self, path: &Path, sample_rate: f32) -> Result<Arc<StereoSample>, String> {
+        let key = CacheKey {
+            path: path.to_path_buf(),
+            modified: std::fs::metadata(path).and_then(|m| m.modified()).ok(),
+            sample_rate: sample_rate.to_bits(),
+        };
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(sample) = state.get(&key) {
+                return Ok(sample);
             }
+            if !state.loading.contains(&key) {
+                break;
+            }
+            state = self
+                .decoded
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
         }
+        state.loading.insert(key.clone());
+        drop(state);
+
+        let decoded = decode_wav(path, sample_rate)
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e));
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.loading.remove(&key);
+        self.decoded.notify_all();
+        let sample = Arc::new(decoded?);
+        state.insert(
+            key,
+            sample.clone(),
+            SAMPLE_MEMORY_LIMIT.load(Ordering::Relaxed),
+        );
+        Ok(sample)
+    }
+}
 
-        None
+impl CacheState {
+    /// A resident sample, marked as just used
+    fn get(&mut self, key: &CacheKey) -> Option<Arc<StereoSample>> {
+        self.clock += 1;
+        let clock = self.clock;
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = clock;
+        Some(entry.sample.clone())
+    }
+
+    /// Add a sample, then drop least recently used ones (never `key` itself)
+    /// until the total fits in `limit_bytes`
+    fn insert(&mut self, key: CacheKey, sample: Arc<StereoSample>, limit_bytes: usize) {
+        let channels = if sample.is_stereo() { 2 } else { 1 };
+        let bytes = sample.len() * channels * std::mem::size_of::<f32>();
+        self.clock += 1;
+        let entry = CacheEntry {
+            sample,
+            bytes,
+            last_used: self.clock,
+        };
+        if let Some(old) = self.entries.insert(key.clone(), entry) {
+            self.resident_bytes -= old.bytes;
+        }
+        self.resident_bytes += bytes;
+        self.evict(limit_bytes, Some(&key));
+    }
+
+    /// Drop least recently used samples, except `keep` and ones still held,
+    /// until the total fits in `limit_bytes`
+    fn evict(&mut self, limit_bytes: usize, keep: Option<&CacheKey>) {
+        while self.resident_bytes > limit_bytes {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .filter(|(k, entry)| Some(*k) != keep && !entry.is_held())
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(k, _)| k.clone())
+            else {
+                break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.resident_bytes -= evicted.bytes;
+            }
+        }
     }
 }
 
@@ -752,10 +1178,7 @@ mod tests {
         let audio = vec![0.5, -0.5, 0.25, -0.25];
         create_test_wav(&wav_path, &audio, 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
         bank.load_sample("test_mono", &wav_path).unwrap();
 
         let sample = bank.samples.get("test_mono").unwrap();
@@ -775,10 +1198,7 @@ mod tests {
         let interleaved = vec![0.1, 0.9, 0.2, 0.8, 0.3, 0.7];
         create_test_wav(&wav_path, &interleaved, 2);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
         bank.load_sample("test_stereo", &wav_path).unwrap();
 
         let sample = bank.samples.get("test_stereo").unwrap();
@@ -801,10 +1221,7 @@ mod tests {
         let samples_i16: Vec<i16> = vec![16384, -16384, 0];
         create_test_wav_i16(&wav_path, &samples_i16, 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
         bank.load_sample("test_i16", &wav_path).unwrap();
 
         let sample = bank.samples.get("test_i16").unwrap();
@@ -823,10 +1240,7 @@ mod tests {
         create_test_wav(&wav1, &[1.0, 1.0], 1);
         create_test_wav(&wav2, &[0.0, 0.0], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
 
         // Load first file
         bank.load_sample("cached", &wav1).unwrap();
@@ -842,10 +1256,7 @@ mod tests {
 
     #[test]
     fn test_load_sample_invalid_path_returns_error() {
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
         let result = bank.load_sample("nonexistent", Path::new("/no/such/file.wav"));
         assert!(result.is_err());
     }
@@ -858,10 +1269,7 @@ mod tests {
         let mut f = std::fs::File::create(&bad_wav).unwrap();
         f.write_all(b"this is not a wav file").unwrap();
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
         let result = bank.load_sample("bad", &bad_wav);
         assert!(result.is_err());
     }
@@ -882,10 +1290,7 @@ mod tests {
             create_test_wav(&path, &[*val; 10], 1);
         }
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         let s0 = bank.get_sample("bd:0").expect("bd:0 should load");
         let s1 = bank.get_sample("bd:1").expect("bd:1 should load");
@@ -907,10 +1312,7 @@ mod tests {
         create_test_wav(&sample_dir.join("hh0.wav"), &[0.1; 10], 1);
         create_test_wav(&sample_dir.join("hh1.wav"), &[0.2; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         // Index 2 should wrap to 0 (2 % 2 = 0)
         let s_wrapped = bank.get_sample("hh:2").expect("hh:2 should wrap");
@@ -927,10 +1329,7 @@ mod tests {
         create_test_wav(&sample_dir.join("a_first.wav"), &[0.42; 10], 1);
         create_test_wav(&sample_dir.join("b_second.wav"), &[0.99; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         let sample = bank.get_sample("cp").expect("cp should load");
         // Files are sorted by name, "a_first.wav" comes first
@@ -944,10 +1343,7 @@ mod tests {
         std::fs::create_dir(&sample_dir).unwrap();
         create_test_wav(&sample_dir.join("sn0.wav"), &[0.77; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         // "bd:abc" should parse index as 0 (unwrap_or(0))
         let sample = bank.get_sample("sn:abc").expect("sn:abc should fallback");
@@ -965,10 +1361,7 @@ mod tests {
         std::fs::create_dir(&sample_dir).unwrap();
         create_test_wav(&sample_dir.join("bd0.wav"), &[0.5; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         let first = bank.get_sample("bd:0").expect("should load");
         let second = bank.get_sample("bd:0").expect("should be cached");
@@ -985,10 +1378,7 @@ mod tests {
         create_test_wav(&sample_dir.join("bd0.wav"), &[0.1; 10], 1);
         create_test_wav(&sample_dir.join("bd1.wav"), &[0.9; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         let s0 = bank.get_sample("bd:0").expect("bd:0");
         let s1 = bank.get_sample("bd:1").expect("bd:1");
@@ -1005,10 +1395,7 @@ mod tests {
 
     #[test]
     fn test_get_sample_nonexistent_returns_none() {
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
         assert!(bank.get_sample("nonexistent_sample").is_none());
     }
 
@@ -1019,10 +1406,7 @@ mod tests {
         std::fs::create_dir(&sample_dir).unwrap();
         // No WAV files in this directory
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
         assert!(bank.get_sample("empty").is_none());
     }
 
@@ -1034,10 +1418,7 @@ mod tests {
         std::fs::write(sample_dir.join("notes.txt"), "not audio").unwrap();
         std::fs::write(sample_dir.join("data.mp3"), "fake mp3").unwrap();
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
         assert!(bank.get_sample("txt").is_none());
     }
 
@@ -1059,10 +1440,10 @@ mod tests {
         create_test_wav(&kick1.join("kick.wav"), &[0.1; 10], 1);
         create_test_wav(&kick2.join("kick.wav"), &[0.9; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir1.path().to_path_buf(), dir2.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(
+            vec![dir1.path().to_path_buf(), dir2.path().to_path_buf()],
+            44100.0,
+        );
 
         let sample = bank.get_sample("kick").expect("should find kick");
         // First directory should win
@@ -1070,6 +1451,171 @@ mod tests {
             "Should load from first dir, got {}", sample.left[0]);
     }
 
+    #[test]
+    fn test_banks_lists_playable_names() {
+        let dir1 = tempfile::tempdir().unwrap();
+        let dir2 = tempfile::tempdir().unwrap();
+        for (root, name, files) in [
+            (&dir1, "kick", 1),
+            (&dir1, "empty", 0),
+            (&dir2, "kick", 3),
+            (&dir2, "arpy", 2),
+        ] {
+            let sample_dir = root.path().join(name);
+            std::fs::create_dir(&sample_dir).unwrap();
+            for i in 0..files {
+                create_test_wav(&sample_dir.join(format!("{}.wav", i)), &[0.1; 10], 1);
+            }
+        }
+
+        let bank = SampleBank::with_dirs(
+            vec![dir1.path().to_path_buf(), dir2.path().to_path_buf()],
+            44100.0,
+        );
+        assert_eq!(
+            bank.banks(),
+            vec![("arpy".to_string(), 2), ("kick".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_velocity_layers() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "snare-v1", "snare-v2", "snare-v3", "kick-v1", "kick-v2", "hat",
+        ] {
+            let sample_dir = dir.path().join(name);
+            std::fs::create_dir(&sample_dir).unwrap();
+            create_test_wav(&sample_dir.join("0.wav"), &[0.1; 10], 1);
+        }
+        std::fs::write(dir.path().join("snare.toml"), "layers = [0.3, 0.7, 1.0]\n").unwrap();
+
+        let bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
+        assert_eq!(bank.velocity_layers("snare"), Some(vec![0.3, 0.7, 1.0]));
+        assert_eq!(bank.velocity_layers("kick"), Some(vec![0.5, 1.0]));
+        assert_eq!(bank.velocity_layers("hat"), None);
+
+        let layers = [0.3, 0.7, 1.0];
+        let (layer, gain) = pick_velocity_layer(&layers, 0.15);
+        assert_eq!(layer, 1);
+        assert!((gain - 0.5).abs() < 1e-6);
+        assert_eq!(pick_velocity_layer(&layers, 0.7), (2, 1.0));
+        assert_eq!(pick_velocity_layer(&layers, 1.0), (3, 1.0));
+        // Louder than the top layer: its loudest sample, boosted
+        let (layer, gain) = pick_velocity_layer(&layers, 1.5);
+        assert_eq!(layer, 3);
+        assert!((gain - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_prefetch_decodes_into_shared_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let sample_dir = dir.path().join("prefetched");
+        std::fs::create_dir(&sample_dir).unwrap();
+        for i in 0..3 {
+            create_test_wav(&sample_dir.join(format!("{}.wav", i)), &[0.1; 10], 1);
+        }
+
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
+        bank.prefetch(vec!["prefetched:rr".to_string(), "missing".to_string()])
+            .join()
+            .unwrap();
+        {
+            let state = shared_cache().state.lock().unwrap();
+            let resident = state
+                .entries
+                .keys()
+                .filter(|key| key.path.starts_with(&sample_dir))
+                .count();
+            assert_eq!(resident, 3);
+        }
+
+        // A new bank (as after a reload) gets the same decoded data
+        let mut other = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
+        let first = bank.get_sample("prefetched:1").unwrap();
+        let second = other.get_sample("prefetched:1").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_memory_by_folder() {
+        let files = [
+            (Path::new("/kits/bd/0.wav"), 100),
+            (Path::new("/kits/hh/0.wav"), 300),
+            (Path::new("/kits/bd/1.wav"), 400),
+        ];
+        assert_eq!(
+            memory_by_folder(files.into_iter()),
+            vec![
+                FolderMemory {
+                    folder: "bd".to_string(),
+                    files: 2,
+                    bytes: 500,
+                },
+                FolderMemory {
+                    folder: "hh".to_string(),
+                    files: 1,
+                    bytes: 300,
+                },
+            ]
+        );
+
+        // Header-based sizes match what decoding produces
+        let dir = tempfile::tempdir().unwrap();
+        let sample_dir = dir.path().join("pad");
+        std::fs::create_dir(&sample_dir).unwrap();
+        create_test_wav(&sample_dir.join("0.wav"), &[0.1; 20], 2);
+        let bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
+        assert_eq!(
+            bank.decoded_memory(),
+            vec![FolderMemory {
+                folder: "pad".to_string(),
+                files: 1,
+                bytes: 20 * 4,
+            }]
+        );
+        assert_eq!(format_bytes(3 * 1024 * 1024 / 2), "1.5 MB");
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let key = |name: &str| CacheKey {
+            path: PathBuf::from(name),
+            modified: None,
+            sample_rate: 44100,
+        };
+        // 10 mono frames = 40 bytes each
+        let sample = || Arc::new(StereoSample::mono(vec![0.0; 10]));
+
+        let mut state = CacheState::default();
+        state.insert(key("a"), sample(), 100);
+        state.insert(key("b"), sample(), 100);
+        assert!(state.get(&key("a")).is_some());
+        state.insert(key("c"), sample(), 100);
+
+        assert!(state.entries.contains_key(&key("a")));
+        assert!(!state.entries.contains_key(&key("b")));
+        assert!(state.entries.contains_key(&key("c")));
+        assert_eq!(state.resident_bytes, 80);
+
+        // Never evicts the sample just added, even when it alone is too big
+        state.insert(key("d"), sample(), 10);
+        assert_eq!(state.entries.len(), 1);
+        assert_eq!(state.resident_bytes, 40);
+
+        // A sample a bank still holds stays, and counts, past the limit
+        let held = sample();
+        state.insert(key("e"), held.clone(), 100);
+        state.insert(key("f"), sample(), 40);
+        assert!(state.entries.contains_key(&key("e")));
+        assert!(!state.entries.contains_key(&key("d")));
+        assert_eq!(state.resident_bytes, 80);
+        drop(held);
+        state.evict(40, None);
+        assert!(!state.entries.contains_key(&key("e")));
+        assert_eq!(state.resident_bytes, 40);
+    }
+
     // =========================================================================
     // SampleBank: wav file sorting (alphabetical)
     // =========================================================================
@@ -1085,10 +1631,7 @@ mod tests {
         create_test_wav(&sample_dir.join("a_first.wav"), &[0.1; 10], 1);
         create_test_wav(&sample_dir.join("b_second.wav"), &[0.2; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         let s0 = bank.get_sample("perc:0").expect("perc:0");
         let s1 = bank.get_sample("perc:1").expect("perc:1");
@@ -1113,10 +1656,7 @@ mod tests {
         // Create a file with .WAV extension
         create_test_wav(&sample_dir.join("upper.WAV"), &[0.2; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![dir.path().to_path_buf()],
-        };
+        let mut bank = SampleBank::with_dirs(vec![dir.path().to_path_buf()], 44100.0);
 
         // Should find 2 files (both .wav and .WAV)
         let s0 = bank.get_sample("mix:0").expect("should find first");
@@ -1125,6 +1665,38 @@ mod tests {
         assert!(s1.len() > 0);
     }
 
+    // =========================================================================
+    // SampleBank: resampling to the playback rate
+    // =========================================================================
+
+    #[test]
+    fn test_load_sample_resamples_to_bank_rate() {
+        let dir = tempfile::tempdir().unwrap();
+        let wav_path = dir.path().join("tone48k.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 48000,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(&wav_path, spec).unwrap();
+        for i in 0..4800 {
+            writer.write_sample(i as f32 / 4800.0).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        // Same duration (0.1s) at each bank's rate
+        for rate in [44100.0, 48000.0, 96000.0] {
+            let mut bank = SampleBank::with_dirs(vec![], rate);
+            bank.load_sample("tone", &wav_path).unwrap();
+            let sample = bank.samples.get("tone").unwrap();
+            assert_eq!(sample.len(), (rate * 0.1).round() as usize);
+            // Ramp value at 50ms is preserved
+            let mid = sample.left[(rate * 0.05) as usize];
+            assert!((mid - 0.5).abs() < 1e-3, "rate {}: {}", rate, mid);
+        }
+    }
+
     // =========================================================================
     // SampleBank: Clone uses Arc (cheap)
     // =========================================================================
@@ -1135,10 +1707,7 @@ mod tests {
         let wav_path = dir.path().join("test.wav");
         create_test_wav(&wav_path, &[0.5; 10], 1);
 
-        let mut bank = SampleBank {
-            samples: HashMap::new(),
-            sample_dirs: vec![],
-        };
+        let mut bank = SampleBank::with_dirs(vec![], 44100.0);
         bank.load_sample("shared", &wav_path).unwrap();
 
         let cloned = bank.clone();
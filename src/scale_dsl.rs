@@ -345,6 +345,113 @@ pub fn chord_quality_stack_pattern(qualities: Pattern<String>) -> Pattern<String
     })
 }
 
+/// Parse a key such as `"c minor"`, `"ef dorian"` or plain `"minor"` (on c)
+/// into a tonic pitch class and a [`Scale`]. A bare tonic (`"g"`) is major.
+pub fn parse_key(spec: &str) -> Option<(i32, Scale)> {
+    let mut words = spec.split_whitespace();
+    let first = words.next()?;
+    match words.next() {
+        Some(scale) => {
+            let tonic = note_name_to_semitone(first)?.rem_euclid(12);
+            Some((tonic, scale_from_name(scale)?))
+        }
+        None => match scale_from_name(first) {
+            Some(scale) => Some((0, scale)),
+            None => Some((note_name_to_semitone(first)?.rem_euclid(12), Scale::Major)),
+        },
+    }
+}
+
+/// Scale steps above the melody that a harmony name adds: `3rds` adds the
+/// scale note two steps up, `triads` a third and a fifth
+pub fn harmony_steps(name: &str) -> Option<Vec<i32>> {
+    let steps = match name.trim().to_lowercase().as_str() {
+        "3rds" | "thirds" => vec![2],
+        "4ths" | "fourths" => vec![3],
+        "5ths" | "fifths" => vec![4],
+        "6ths" | "sixths" => vec![5],
+        "7ths" | "sevenths" => vec![6],
+        "octaves" => vec![7],
+        "triads" => vec![2, 4],
+        "3rds-below" => vec![-2],
+        "6ths-below" => vec![-5],
+        _ => return None,
+    };
+    Some(steps)
+}
+
+/// Semitones from `note` to the scale note `steps` scale steps above it in
+/// the key. Notes outside the scale move with the scale note just below them.
+pub fn diatonic_interval(note: i32, steps: i32, tonic: i32, scale: Scale) -> i32 {
+    let intervals = scale.intervals();
+    let len = intervals.len() as i32;
+    if len == 0 {
+        return steps;
+    }
+    let rel = note - tonic;
+    let pc = rel.rem_euclid(12);
+    let idx = intervals
+        .iter()
+        .rposition(|&i| i as i32 <= pc)
+        .unwrap_or(0) as i32;
+    let degree = rel.div_euclid(12) * len + idx;
+    degree_to_semitone(degree + steps, scale) - degree_to_semitone(degree, scale)
+}
+
+/// Add parallel harmony to a melody: each note becomes a **stack** of itself
+/// and the notes `harmonies` names (`"3rds"`, `"triads"`, ...), all kept in
+/// `key`. The harmony is a pattern, resolved at each note's start.
+///
+/// This backs `n "c4 d4 ef4" # harmonize "3rds" :scale "c minor"`. Octave
+/// note names stay note names; numbers and bare names become semitones as in
+/// [`note_name_to_semitone`]. Unknown harmonies and unparseable notes (rests)
+/// pass through untouched.
+pub fn harmonize_pattern(
+    melody: Pattern<String>,
+    harmonies: Pattern<String>,
+    key: (i32, Scale),
+) -> Pattern<String> {
+    let (tonic, scale) = key;
+    Pattern::new(move |state: &State| {
+        let harmony_haps = harmonies.query(state);
+        melody
+            .query(state)
+            .into_iter()
+            .flat_map(|hap| {
+                let value = hap.value.trim();
+                let (note, as_name) = match value.parse::<f64>() {
+                    Ok(n) => (n.round() as i32, false),
+                    Err(_) => match note_name_to_semitone(value) {
+                        Some(s) => (s, value.chars().any(|c| c.is_ascii_digit())),
+                        None => return vec![hap.clone()],
+                    },
+                };
+
+                let begin = hap.part.begin.to_float();
+                let steps = harmony_haps
+                    .iter()
+                    .find(|h| {
+                        let hb = h.part.begin.to_float();
+                        let he = h.part.end.to_float();
+                        begin >= hb && begin < he
+                    })
+                    .or_else(|| harmony_haps.first())
+                    .and_then(|h| harmony_steps(&h.value))
+                    .unwrap_or_default();
+
+                std::iter::once(0)
+                    .chain(steps)
+                    .map(|step| {
+                        let harmony = note + diatonic_interval(note, step, tonic, scale);
+                        let out = crate::pattern_tonal::format_chord_tone(harmony as f64, as_name);
+                        Hap::new(hap.whole, hap.part, out)
+                    })
+                    .collect()
+            })
+            .collect()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -613,4 +720,42 @@ mod tests {
         let one = chord_quality_stack_pattern(parse_mini_notation("aug"));
         assert_eq!(query_stack_values(&one), vec![0.0, 4.0, 8.0]);
     }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("c minor"), Some((0, Scale::Minor)));
+        assert_eq!(parse_key("ef dorian"), Some((3, Scale::Dorian)));
+        assert_eq!(parse_key("minor"), Some((0, Scale::Minor)));
+        assert_eq!(parse_key("g"), Some((7, Scale::Major)));
+        assert_eq!(parse_key("c nonsense"), None);
+    }
+
+    #[test]
+    fn test_harmonize_thirds_in_c_minor() {
+        // c d ef g -> thirds ef f g bf, all inside c minor
+        let melody = parse_mini_notation("0 2 3 7");
+        let harmonized =
+            harmonize_pattern(melody, parse_mini_notation("3rds"), (0, Scale::Minor));
+        assert_eq!(
+            query_stack_values(&harmonized),
+            vec![0.0, 3.0, 2.0, 5.0, 3.0, 7.0, 7.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn test_harmonize_triads_keep_note_names() {
+        let melody = parse_mini_notation("a4");
+        let harmonized =
+            harmonize_pattern(melody, parse_mini_notation("triads"), (0, Scale::Major));
+        let state = State {
+            span: TimeSpan::new(Fraction::from_float(0.0), Fraction::from_float(1.0)),
+            controls: HashMap::new(),
+        };
+        let values: Vec<String> = harmonized
+            .query(&state)
+            .into_iter()
+            .map(|h| h.value)
+            .collect();
+        assert_eq!(values, vec!["a4", "c5", "e5"]);
+    }
 }
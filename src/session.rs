@@ -0,0 +1,143 @@
+//! Suspended live sets: `:save-session set.phsn` and `:load-session set.phsn`
+//!
+//! A session holds the graph's running state, serialized: the phase,
+//! envelope position and filter memory of every stateful node
+//! ([`UnifiedSignalGraph::save_node_states`]), with the tempo and cycle
+//! position, the sample files decoded so far, recorded takes and frozen bus
+//! recordings.
+//!
+//! The graph's structure is not serialized. Its nodes hold pattern closures
+//! and plugin handles, which can't be written out (the same reason
+//! `IpcMessage` sends code), so it is rebuilt from the evaluated code of each
+//! pane in one compile and the node state is put back into it. Takes and
+//! frozen buses are restored before compiling, so nothing is re-recorded or
+//! re-rendered, and the samples start decoding straight away. Sounding
+//! sample voices and effect tails start afresh.
+
+use crate::compositional_compiler::{
+    compile_program, frozen_buses, merge_programs, restore_frozen_buses,
+};
+use crate::compositional_parser::parse_program;
+use crate::unified_graph::UnifiedSignalGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Format version written to session files; older or newer ones are refused
+pub const SESSION_VERSION: u32 = 2;
+
+/// One editor pane of a saved session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionPane {
+    /// Code the pane last evaluated
+    pub code: String,
+    /// File the pane was editing, if any
+    pub file: Option<PathBuf>,
+}
+
+/// A live set, as written to a `.phsn` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub version: u32,
+    /// Panes left to right, merged in this order like a split editor
+    pub panes: Vec<SessionPane>,
+    /// Tempo in cycles per second
+    pub cps: f32,
+    /// Cycle position playback resumes from
+    pub cycle: f64,
+    /// Sample files that were decoded
+    pub samples: Vec<PathBuf>,
+    /// Recorded takes (`%name`), as mini-notation
+    pub takes: BTreeMap<String, String>,
+    /// Frozen bus recordings with the hash of the program they belong to
+    pub frozen_buses: BTreeMap<String, (u64, Vec<f32>)>,
+    /// Running state of the graph's nodes, from
+    /// [`UnifiedSignalGraph::save_node_states`]
+    pub node_states: Vec<u8>,
+}
+
+impl Session {
+    /// A session of `panes` playing at `cps` from `cycle` with `node_states`,
+    /// and the takes, frozen buses and decoded samples of this process
+    pub fn capture(panes: Vec<SessionPane>, cps: f32, cycle: f64, node_states: Vec<u8>) -> Self {
+        Self {
+            version: SESSION_VERSION,
+            panes,
+            cps,
+            cycle,
+            samples: crate::sample_loader::resident_files(),
+            takes: crate::midi_input::takes(),
+            frozen_buses: frozen_buses()
+                .into_iter()
+                .map(|(bus, (hash, buffer))| (bus, (hash, buffer.to_vec())))
+                .collect(),
+            node_states,
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let bytes =
+            bincode::serialize(self).map_err(|e| format!("Failed to serialize session: {}", e))?;
+        std::fs::write(path, bytes)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes =
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        // The version leads, so it can be checked before the rest is decoded
+        let version: u32 = bincode::deserialize(&bytes)
+            .map_err(|_| format!("{} is not a Phonon session", path.display()))?;
+        if version != SESSION_VERSION {
+            return Err(format!(
+                "{} is a version {} session, this Phonon reads version {}",
+                path.display(),
+                version,
+                SESSION_VERSION
+            ));
+        }
+        bincode::deserialize(&bytes)
+            .map_err(|_| format!("{} is not a Phonon session", path.display()))
+    }
+
+    /// Put the takes and frozen bus recordings back and start decoding the
+    /// samples at `sample_rate`. Call before compiling the panes.
+    pub fn restore(&self, sample_rate: f32) {
+        for (name, take) in &self.takes {
+            crate::midi_input::store_take(name, take.clone());
+        }
+        restore_frozen_buses(
+            self.frozen_buses
+                .iter()
+                .map(|(bus, (hash, buffer))| (bus.clone(), (*hash, Arc::new(buffer.clone()))))
+                .collect(),
+        );
+        let samples: Vec<PathBuf> = self
+            .samples
+            .iter()
+            .filter(|p| p.exists())
+            .cloned()
+            .collect();
+        crate::sample_loader::preload_files(samples, sample_rate);
+    }
+
+    /// Restore the session and compile its panes into one graph with the
+    /// saved node state, tempo and cycle position
+    pub fn resume(&self, sample_rate: f32) -> Result<UnifiedSignalGraph, String> {
+        self.restore(sample_rate);
+        let mut programs = Vec::new();
+        for pane in &self.panes {
+            let (rest, statements) = parse_program(&pane.code).map_err(|e| e.to_string())?;
+            if !rest.trim().is_empty() {
+                return Err(format!("Failed to parse session code: {}", rest));
+            }
+            programs.push(statements);
+        }
+        let mut graph = compile_program(merge_programs(programs), sample_rate, None)?;
+        graph.restore_node_states(&self.node_states);
+        graph.set_cps(self.cps);
+        graph.seek_to_cycle(self.cycle);
+        Ok(graph)
+    }
+}
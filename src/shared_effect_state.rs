@@ -66,12 +66,12 @@ pub enum SharedState {
     Formant(Arc<RwLock<FormantState>>),
 
     // === High priority: Oscillators ===
-    /// Oscillator phase (wrapped f32)
-    OscillatorPhase(Arc<RwLock<f32>>),
+    /// Oscillator phase (wrapped f64)
+    OscillatorPhase(Arc<RwLock<f64>>),
     /// FM oscillator phases (carrier, modulator)
-    FMOscillatorPhase(Arc<RwLock<(f32, f32)>>),
+    FMOscillatorPhase(Arc<RwLock<(f64, f64)>>),
     /// PM oscillator phase
-    PMOscillatorPhase(Arc<RwLock<f32>>),
+    PMOscillatorPhase(Arc<RwLock<f64>>),
     /// VCO phase
     VCOPhase(Arc<RwLock<f32>>),
     /// Blip phase
@@ -3,6 +3,9 @@
 //! Parser for the modular synthesis DSL
 //!
 //! Parses text-based signal flow definitions into signal graphs
+//!
+//! Deprecated: nothing in Phonon calls it any more. Programs are parsed by
+//! [`crate::compositional_parser`].
 
 #![allow(clippy::upper_case_acronyms)]
 use crate::signal_graph::{AnalysisType, Node, NodeId, ProcessorType, SignalGraph, SourceType};
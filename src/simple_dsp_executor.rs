@@ -4,6 +4,11 @@
 //! Simple DSP executor for testing
 //!
 //! A straightforward implementation that generates audio from DSP chains
+//!
+//! Deprecated: it renders [`crate::glicol_parser`] programs only.
+//! [`crate::render::Renderer`] still falls back to it for `>>` chains, with
+//! a warning; everything else renders through
+//! [`crate::phonon_engine::PhononEngine`].
 
 use crate::glicol_dsp::{DspChain, DspEnvironment, DspNode};
 use crate::sample_loader::SampleBank;
@@ -599,6 +604,7 @@ impl SimpleDspExecutor {
 }
 
 /// Render DSP code to audio
+#[allow(deprecated)]
 pub fn render_dsp_to_audio_simple(
     code: &str,
     sample_rate: f32,
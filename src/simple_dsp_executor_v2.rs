@@ -4,6 +4,8 @@
 //! DSP executor v2 - Supports pattern parameters
 //!
 //! This executor can generate audio from DSP chains with pattern parameters
+//!
+//! Deprecated with [`crate::glicol_parser_v2`], whose programs it runs.
 
 use crate::dsp_parameter::DspParameter;
 use crate::glicol_dsp_v2::{DspChain, DspEnvironment, DspNode};
@@ -0,0 +1,219 @@
+//! Multichannel spatialization: the speaker layouts of `spatial:` and the
+//! gains that place a source with `# azimuth` / `# elevation`
+//!
+//! A program with `spatial: <layout>` gets one output per channel of the
+//! layout (out1..outN), each compiled from its `out` with the placed sources
+//! weighted for that channel. Angles are in degrees: azimuth clockwise from
+//! the front (90 is right, 180 behind), elevation up from the horizon.
+//!
+//! - `quad` (or `4.0`): FL, FR, RL, RR at ±45° and ±135°
+//! - `5.1`: L, R, C, LFE, Ls, Rs (SMPTE order) at ±30°, 0° and ±110°. The
+//!   LFE channel gets no placed sources
+//! - `foa`: first-order ambisonics as AmbiX B-format, channels W, Y, Z, X
+//!   (ACN order) with SN3D normalization, for any ambisonic decoder
+//! - `binaural`: stereo for headphones, decoded from FOA through head-related
+//!   impulse responses (see [`crate::hrtf`])
+//!
+//! The speaker layouts pan at constant power between the two speakers either
+//! side of the source. Elevation spreads it over every speaker, evenly when
+//! it's straight overhead (or below).
+
+/// A `spatial:` speaker layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpatialLayout {
+    /// Four speakers: front left/right, rear left/right
+    Quad,
+    /// 5.1 surround in SMPTE channel order
+    Surround51,
+    /// First-order ambisonics (AmbiX)
+    Foa,
+    /// Headphone stereo through HRIRs
+    Binaural,
+}
+
+/// Quad speaker azimuths, in channel order
+const QUAD_SPEAKERS: [Option<f32>; 4] = [Some(-45.0), Some(45.0), Some(-135.0), Some(135.0)];
+
+/// 5.1 speaker azimuths, in channel order. The LFE has no position
+const SURROUND_51_SPEAKERS: [Option<f32>; 6] = [
+    Some(-30.0),
+    Some(30.0),
+    Some(0.0),
+    None,
+    Some(-110.0),
+    Some(110.0),
+];
+
+impl SpatialLayout {
+    /// The layout named in `spatial: <name>`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "quad" | "4.0" => Some(SpatialLayout::Quad),
+            "5.1" => Some(SpatialLayout::Surround51),
+            "foa" | "ambix" => Some(SpatialLayout::Foa),
+            "binaural" => Some(SpatialLayout::Binaural),
+            _ => None,
+        }
+    }
+
+    /// Number of output channels
+    pub fn channels(self) -> usize {
+        self.channel_names().len()
+    }
+
+    /// Channel names, in output order
+    pub fn channel_names(self) -> &'static [&'static str] {
+        match self {
+            SpatialLayout::Quad => &["FL", "FR", "RL", "RR"],
+            SpatialLayout::Surround51 => &["L", "R", "C", "LFE", "Ls", "Rs"],
+            SpatialLayout::Foa => &["W", "Y", "Z", "X"],
+            SpatialLayout::Binaural => &["L", "R"],
+        }
+    }
+
+    /// The layout sources are placed in: binaural is decoded from FOA
+    pub fn encoding(self) -> Self {
+        match self {
+            SpatialLayout::Binaural => SpatialLayout::Foa,
+            layout => layout,
+        }
+    }
+
+    /// Gain of `channel` (from 0) for a source at `azimuth` and `elevation`
+    /// degrees, in the layout's [`encoding`](Self::encoding)
+    pub fn gain(self, channel: usize, azimuth: f32, elevation: f32) -> f32 {
+        match self {
+            SpatialLayout::Quad => speaker_gain(&QUAD_SPEAKERS, channel, azimuth, elevation),
+            SpatialLayout::Surround51 => {
+                speaker_gain(&SURROUND_51_SPEAKERS, channel, azimuth, elevation)
+            }
+            SpatialLayout::Foa | SpatialLayout::Binaural => foa_gain(channel, azimuth, elevation),
+        }
+    }
+}
+
+/// AmbiX encoding gain. Ambisonic azimuth runs counter-clockwise, so it's
+/// the negated azimuth
+fn foa_gain(channel: usize, azimuth: f32, elevation: f32) -> f32 {
+    let azimuth = (-azimuth).to_radians();
+    let elevation = elevation.to_radians();
+    match channel {
+        0 => 1.0,
+        1 => azimuth.sin() * elevation.cos(),
+        2 => elevation.sin(),
+        3 => azimuth.cos() * elevation.cos(),
+        _ => 0.0,
+    }
+}
+
+/// Constant-power pan between the speakers either side of the source,
+/// blended towards an even spread as the source rises
+fn speaker_gain(speakers: &[Option<f32>], channel: usize, azimuth: f32, elevation: f32) -> f32 {
+    let Some(Some(position)) = speakers.get(channel) else {
+        return 0.0;
+    };
+    // Clockwise distance from the source to each speaker
+    let offset = |speaker: f32| (speaker - azimuth).rem_euclid(360.0);
+    let offsets = speakers.iter().flatten().map(|&speaker| offset(speaker));
+    let next = offsets.clone().fold(f32::INFINITY, f32::min);
+    let previous = offsets.fold(f32::NEG_INFINITY, f32::max);
+    let own = offset(*position);
+
+    // How far the source is from the previous speaker to the next, 0 to 1
+    let span = next + 360.0 - previous;
+    let t = if span > 0.0 {
+        (360.0 - previous) / span
+    } else {
+        0.0
+    };
+    let pan = if own == next {
+        (t * std::f32::consts::FRAC_PI_2).sin()
+    } else if own == previous {
+        (t * std::f32::consts::FRAC_PI_2).cos()
+    } else {
+        0.0
+    };
+
+    let count = speakers.iter().flatten().count() as f32;
+    let horizontal = elevation.clamp(-90.0, 90.0).to_radians().cos();
+    (horizontal * horizontal * pan * pan + (1.0 - horizontal * horizontal) / count).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn power(layout: SpatialLayout, azimuth: f32, elevation: f32) -> f32 {
+        (0..layout.channels())
+            .map(|channel| layout.gain(channel, azimuth, elevation).powi(2))
+            .sum()
+    }
+
+    #[test]
+    fn test_speaker_layouts_keep_constant_power() {
+        for layout in [SpatialLayout::Quad, SpatialLayout::Surround51] {
+            for azimuth in (-360..=360).step_by(15) {
+                for elevation in [0.0, 30.0, 90.0, -45.0] {
+                    let power = power(layout, azimuth as f32, elevation);
+                    assert!(
+                        (power - 1.0).abs() < 1e-4,
+                        "{:?} {} {}",
+                        layout,
+                        azimuth,
+                        power
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_speaker_pan_follows_azimuth() {
+        let quad = SpatialLayout::Quad;
+        // On a speaker, only that speaker plays
+        assert!((quad.gain(1, 45.0, 0.0) - 1.0).abs() < 1e-6);
+        assert!(quad.gain(0, 45.0, 0.0).abs() < 1e-6);
+        // Straight ahead, FL and FR share it
+        assert!((quad.gain(0, 0.0, 0.0) - quad.gain(1, 0.0, 0.0)).abs() < 1e-6);
+        assert_eq!(quad.gain(2, 0.0, 0.0), 0.0);
+        // Behind, RL and RR (270 is left, wrapping past 360 works)
+        assert!(quad.gain(2, 180.0, 0.0) > 0.7);
+        assert!((quad.gain(2, 225.0, 0.0) - quad.gain(2, -135.0, 0.0)).abs() < 1e-6);
+        assert!((quad.gain(0, 270.0, 0.0) - quad.gain(2, 270.0, 0.0)).abs() < 1e-6);
+
+        let surround = SpatialLayout::Surround51;
+        assert!((surround.gain(2, 0.0, 0.0) - 1.0).abs() < 1e-6);
+        for azimuth in [0.0, 90.0, 180.0, 300.0] {
+            assert_eq!(surround.gain(3, azimuth, 0.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_foa_encoding() {
+        let foa = SpatialLayout::Foa;
+        // Front: W and X
+        assert_eq!(foa.gain(0, 0.0, 0.0), 1.0);
+        assert!((foa.gain(3, 0.0, 0.0) - 1.0).abs() < 1e-6);
+        assert!(foa.gain(1, 0.0, 0.0).abs() < 1e-6);
+        // Left is positive Y
+        assert!((foa.gain(1, 270.0, 0.0) - 1.0).abs() < 1e-6);
+        assert!((foa.gain(1, 90.0, 0.0) + 1.0).abs() < 1e-6);
+        // Overhead is Z
+        assert!((foa.gain(2, 0.0, 90.0) - 1.0).abs() < 1e-6);
+        assert!(foa.gain(3, 0.0, 90.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_layout_names() {
+        assert_eq!(SpatialLayout::from_name("4.0"), Some(SpatialLayout::Quad));
+        assert_eq!(
+            SpatialLayout::from_name("5.1"),
+            Some(SpatialLayout::Surround51)
+        );
+        assert_eq!(SpatialLayout::from_name("foa"), Some(SpatialLayout::Foa));
+        assert_eq!(SpatialLayout::from_name("7.1"), None);
+        assert_eq!(SpatialLayout::Surround51.channels(), 6);
+        assert_eq!(SpatialLayout::Binaural.channels(), 2);
+        assert_eq!(SpatialLayout::Binaural.encoding(), SpatialLayout::Foa);
+    }
+}
@@ -204,7 +204,7 @@ impl SynthLibrary {
                 freq: detuned_freq,
                 semitone_offset: 0.0,
                 waveform: Waveform::Saw,
-                phase: RefCell::new((i as f32 * 0.13) % 1.0), // Slight phase offset
+                phase: RefCell::new((i as f64 * 0.13) % 1.0), // Slight phase offset
                 pending_freq: RefCell::new(None),
                 last_sample: RefCell::new(0.0),
             });
@@ -0,0 +1,708 @@
+//! Best-effort Tidal → Phonon translation: `phonon convert --from tidal file.tidal`
+//!
+//! Handles the constructs most Tidal sketches are made of:
+//!
+//! - `d1 $ sound "bd*2" # gain 0.8` → `~d1 $ s "bd*2" # gain 0.8`, with every
+//!   channel summed into `out`
+//! - prefix transforms (`every 4 (fast 2) $ ...`, `jux rev $ ...`,
+//!   `0.25 <~ ...`) become Phonon's postfix `$ every 4 (fast 2)`
+//! - control parameters map onto Phonon effects (`# cutoff 800 # resonance
+//!   0.3` → `# lpf 800 0.3`, `# room`/`# size` → `# reverb`, ...)
+//! - `setcps (120/60/4)` → `tempo: 0.5`
+//!
+//! Each translated piece is checked by compiling it, so anything Phonon
+//! doesn't understand is dropped with a warning rather than left to break the
+//! result.
+
+use crate::compositional_compiler::compile_program;
+use crate::compositional_parser::parse_program;
+use std::collections::HashMap;
+
+/// Default filter Q when a Tidal cutoff has no resonance
+const DEFAULT_Q: &str = "0.7";
+
+/// Result of a conversion: Phonon code plus one warning per dropped construct
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub code: String,
+    pub warnings: Vec<String>,
+}
+
+/// A converted statement, before channels are summed into `out`
+enum Line {
+    Text(String),
+    /// `~name $ code`: Err holds a translation that doesn't compile, None
+    /// is `d1 silence`
+    Channel {
+        name: String,
+        code: Option<Result<String, String>>,
+        line: usize,
+    },
+}
+
+/// Translate Tidal source into Phonon code
+pub fn convert_tidal(source: &str) -> Conversion {
+    let mut warnings = Vec::new();
+    let mut lines = Vec::new();
+
+    for (line, block) in blocks(source) {
+        let mut warn = |message: String| warnings.push((line, message));
+        if block.starts_with("--") {
+            lines.push(Line::Text(block));
+            continue;
+        }
+        let (head, rest) = split_word(&block);
+        match head {
+            "hush" | ":{" | ":}" => {}
+            "setcps" => match eval_number(rest) {
+                Some(cps) => lines.push(Line::Text(format!("tempo: {}", format_number(cps)))),
+                None => warn(format!("couldn't evaluate setcps {}", rest)),
+            },
+            _ => match channel_name(head, rest) {
+                Some((name, pattern)) => {
+                    let pattern = pattern.trim().trim_start_matches('$').trim();
+                    let code = if strip_parens(pattern) == "silence" {
+                        None
+                    } else {
+                        Some(translate_pattern(pattern, &mut warn))
+                    };
+                    lines.push(Line::Channel { name, code, line });
+                }
+                None => warn(format!("`{}` is not supported (dropped)", head)),
+            },
+        }
+    }
+
+    // Tidal files collect many versions of d1: the last one evaluated wins
+    let mut last = HashMap::new();
+    for (index, entry) in lines.iter().enumerate() {
+        if let Line::Channel { name, .. } = entry {
+            last.insert(name.clone(), index);
+        }
+    }
+
+    let mut code = Vec::new();
+    let mut outputs = Vec::new();
+    for (index, entry) in lines.into_iter().enumerate() {
+        match entry {
+            Line::Text(text) => code.push(text),
+            Line::Channel {
+                name,
+                code: Some(Ok(pattern)),
+                line,
+            } => {
+                let statement = format!("~{} $ {}", name, pattern);
+                if last[&name] == index {
+                    outputs.push(format!("~{}", name));
+                    code.push(statement);
+                } else {
+                    warnings.push((
+                        line,
+                        format!("{} is redefined later; keeping the last version", name),
+                    ));
+                    code.push(format!("-- {}", statement));
+                }
+            }
+            Line::Channel {
+                name,
+                code: Some(Err(pattern)),
+                line,
+            } => {
+                warnings.push((
+                    line,
+                    format!("couldn't translate {} (left commented out)", name),
+                ));
+                code.push(format!("-- ~{} $ {}", name, pattern));
+            }
+            Line::Channel { code: None, .. } => {}
+        }
+    }
+    if !outputs.is_empty() {
+        code.push(format!("out $ {}", outputs.join(" + ")));
+    }
+
+    warnings.sort_by_key(|(line, _)| *line);
+    Conversion {
+        code: code.join("\n") + "\n",
+        warnings: warnings
+            .into_iter()
+            .map(|(line, message)| format!("line {}: {}", line, message))
+            .collect(),
+    }
+}
+
+/// Source statements with their first line number: full-line comments, and
+/// top-level expressions joined with their indented continuation lines
+/// (including each statement of a `do` block)
+fn blocks(source: &str) -> Vec<(usize, String)> {
+    let mut blocks: Vec<(usize, String)> = Vec::new();
+    // Indentation of the statements inside the current `do` block
+    let mut do_indent: Option<usize> = None;
+    let mut in_do = false;
+
+    for (index, raw) in source.lines().enumerate() {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with("--") {
+            blocks.push((index + 1, trimmed.to_string()));
+            continue;
+        }
+        let text = strip_comment(trimmed).trim_end();
+        let indent = raw.len() - raw.trim_start().len();
+
+        if indent == 0 {
+            in_do = text == "do";
+            do_indent = None;
+            if !in_do {
+                blocks.push((index + 1, text.to_string()));
+            }
+            continue;
+        }
+        if in_do && do_indent.map_or(true, |level| indent <= level) {
+            do_indent = Some(indent);
+            blocks.push((index + 1, text.to_string()));
+            continue;
+        }
+        match blocks.last_mut() {
+            Some((_, block)) if !block.starts_with("--") => {
+                block.push(' ');
+                block.push_str(text);
+            }
+            _ => blocks.push((index + 1, text.to_string())),
+        }
+    }
+    blocks
+}
+
+/// `text` without a trailing `-- comment` (outside string literals)
+fn strip_comment(text: &str) -> &str {
+    let mut in_string = false;
+    let bytes = text.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'"' => in_string = !in_string,
+            b'-' if !in_string && bytes.get(i + 1) == Some(&b'-') => return &text[..i],
+            _ => {}
+        }
+    }
+    text
+}
+
+/// First word and the rest of a statement
+fn split_word(text: &str) -> (&str, &str) {
+    match text.find(|c: char| c.is_whitespace() || c == '$' || c == '(') {
+        Some(end) => (&text[..end], text[end..].trim_start()),
+        None => (text, ""),
+    }
+}
+
+/// The bus name and pattern of `d1 $ ...` or `p "name" $ ...`
+fn channel_name(head: &str, rest: &str) -> Option<(String, String)> {
+    if let Some(number) = head.strip_prefix('d') {
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            return Some((head.to_string(), rest.to_string()));
+        }
+    }
+    if head != "p" {
+        return None;
+    }
+    let (name, pattern) = if let Some(quoted) = rest.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        (&quoted[..end], &quoted[end + 1..])
+    } else {
+        split_word(rest)
+    };
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.is_empty() {
+        return None;
+    }
+    // `p 1` becomes ~p1, `p "drums"` becomes ~drums
+    let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("p{}", name)
+    } else {
+        name
+    };
+    Some((name, pattern.to_string()))
+}
+
+/// A translated pattern. Phonon wants transforms before parameters:
+/// `s "bd" $ fast 2 # gain 0.8`.
+struct Pattern {
+    source: String,
+    transforms: Vec<String>,
+    params: Vec<String>,
+}
+
+impl Pattern {
+    fn code(&self) -> String {
+        let mut code = self.source.clone();
+        for transform in &self.transforms {
+            code = format!("{} $ {}", code, transform);
+        }
+        for param in &self.params {
+            code = format!("{} # {}", code, param);
+        }
+        code
+    }
+}
+
+/// Translate one Tidal pattern expression. Err carries the best attempt when
+/// the result doesn't compile.
+fn translate_pattern(pattern: &str, warn: &mut impl FnMut(String)) -> Result<String, String> {
+    let translated = translate_expr(pattern, warn).code();
+    if compiles(&translated) {
+        Ok(translated)
+    } else {
+        Err(translated)
+    }
+}
+
+/// `f $ g $ base` → `base $ g $ f`
+fn translate_expr(expr: &str, warn: &mut impl FnMut(String)) -> Pattern {
+    let parts = split_top(expr, "$");
+    let (base, functions) = parts.split_last().expect("split yields a part");
+    let mut pattern = translate_base(base, warn);
+    for function in functions.iter().rev() {
+        let function = strip_parens(function);
+        // `(# speed 2) $ ...` sets parameters like a chain
+        if let Some(params) = function.strip_prefix('#') {
+            let params = split_params(params);
+            pattern.params.extend(translate_params(&params, warn));
+            continue;
+        }
+        pattern
+            .transforms
+            .extend(translate_transform(function, warn));
+    }
+    pattern
+}
+
+/// A pattern with its `#` parameters: `sound "bd" # gain 0.8`
+fn translate_base(base: &str, warn: &mut impl FnMut(String)) -> Pattern {
+    let base = strip_parens(base);
+
+    // `0.25 <~ pattern` / `0.25 ~> pattern`
+    for (operator, transform) in [("<~", "early"), ("~>", "late")] {
+        if let Some(at) = find_top(base, operator) {
+            let amount = strip_parens(base[..at].trim());
+            let mut pattern = translate_expr(base[at + operator.len()..].trim(), warn);
+            pattern
+                .transforms
+                .push(format!("{} {}", transform, wrap(amount)));
+            return pattern;
+        }
+    }
+
+    if let Some(list) = base.strip_prefix("stack").map(str::trim) {
+        if let Some(inner) = list.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let layers: Vec<String> = split_top(inner, ",")
+                .iter()
+                .map(|layer| translate_expr(layer, warn).code())
+                .collect();
+            return Pattern {
+                source: format!("stack [{}]", layers.join(", ")),
+                transforms: Vec::new(),
+                params: Vec::new(),
+            };
+        }
+    }
+
+    // Operators like `|+| n 12` or `+ speed 1` combine patterns in ways
+    // Phonon has no equivalent for
+    let base = match find_top_any(base, &['|', '+']) {
+        Some(at) => {
+            let operator: String = base[at..]
+                .chars()
+                .take_while(|c| matches!(c, '|' | '+' | '<' | '>' | '*' | '-'))
+                .collect();
+            warn(format!(
+                "`{}` operators are not supported (dropped `{}`)",
+                operator,
+                base[at..].trim()
+            ));
+            base[..at].trim()
+        }
+        None => base,
+    };
+
+    let mut chain = split_params(base);
+    // `n "0 2" # sound "arpy"`: Phonon chains parameters onto the sample
+    if !is_sound(&chain[0].0) {
+        if let Some(sound) = chain.iter().position(|(name, _)| is_sound(name)) {
+            chain.swap(0, sound);
+        }
+    }
+    let (source, params) = chain.split_first().expect("split yields a part");
+
+    let source = if is_sound(&source.0) {
+        format!("s {}", source.1)
+    } else {
+        format!("{} {}", source.0, source.1).trim().to_string()
+    };
+    Pattern {
+        source,
+        transforms: Vec::new(),
+        params: translate_params(params, warn),
+    }
+}
+
+/// `a 1 # b 2` as (name, args) pairs
+fn split_params(chain: &str) -> Vec<(String, String)> {
+    split_top(chain, "#")
+        .iter()
+        .map(|part| {
+            let (name, args) = split_word(strip_parens(part));
+            (name.to_string(), args.to_string())
+        })
+        .collect()
+}
+
+fn is_sound(name: &str) -> bool {
+    name == "sound" || name == "s"
+}
+
+/// Tidal control parameters as Phonon `#` chain steps. Controls that Tidal
+/// spreads over several parameters (cutoff + resonance, room + size) become
+/// one effect.
+fn translate_params(params: &[(String, String)], warn: &mut impl FnMut(String)) -> Vec<String> {
+    let mut controls: HashMap<&str, &str> = HashMap::new();
+    let mut steps = Vec::new();
+    let mut voice_fx = Vec::new();
+    // Effect steps are placed where their first control appeared
+    let mut effect_slots: Vec<(&str, usize)> = Vec::new();
+    fn slot(effect: &'static str, at: usize, slots: &mut Vec<(&'static str, usize)>) {
+        if !slots.iter().any(|(name, _)| *name == effect) {
+            slots.push((effect, at));
+        }
+    }
+
+    for (name, args) in params {
+        let name = name.as_str();
+        let args = args.as_str();
+        match name {
+            "cutoff" | "lpf" | "ctf" => {
+                controls.insert("cutoff", args);
+                slot("lpf", steps.len(), &mut effect_slots);
+            }
+            "resonance" | "lpq" | "res" => {
+                controls.insert("resonance", args);
+            }
+            "hcutoff" | "hpf" => {
+                controls.insert("hcutoff", args);
+                slot("hpf", steps.len(), &mut effect_slots);
+            }
+            "hresonance" | "hpq" => {
+                controls.insert("hresonance", args);
+            }
+            "room" => {
+                controls.insert("room", args);
+                slot("reverb", steps.len(), &mut effect_slots);
+            }
+            "size" | "sz" => {
+                controls.insert("size", args);
+            }
+            "delay" => {
+                controls.insert("delay", args);
+                slot("delay", steps.len(), &mut effect_slots);
+            }
+            "delaytime" | "delayt" => {
+                controls.insert("delaytime", args);
+            }
+            "delayfeedback" | "delayfb" => {
+                controls.insert("delayfeedback", args);
+            }
+            // Per-voice effects need the sample pattern as their input, so
+            // they go ahead of the merged effects
+            "crush" | "coarse" | "shape" | "squiz" => {
+                let step = format!("{} {}", name, args).trim().to_string();
+                if compiles(&format!("s \"bd\" # {}", step)) {
+                    voice_fx.push(step);
+                } else {
+                    warn(format!(
+                        "`{}` only converts with a number or pattern (dropped)",
+                        name
+                    ));
+                }
+            }
+            // Orbits pick a SuperDirt output bus. A converted pattern has no
+            // `orbit N` playing it, where `# orbit` would do nothing
+            "orbit" => {}
+            _ => {
+                let step = format!("{} {}", name, args).trim().to_string();
+                if compiles(&format!("s \"bd\" # {}", step)) {
+                    steps.push(step);
+                } else {
+                    warn(format!("`{}` is not supported (dropped)", name));
+                }
+            }
+        }
+    }
+
+    let fx_at = effect_slots
+        .iter()
+        .map(|(_, at)| *at)
+        .min()
+        .unwrap_or(steps.len());
+    let control =
+        |name: &str, default: &str| controls.get(name).copied().unwrap_or(default).to_string();
+    for (effect, at) in effect_slots.into_iter().rev() {
+        let step = match effect {
+            "lpf" => format!(
+                "lpf {} {}",
+                control("cutoff", ""),
+                control("resonance", DEFAULT_Q)
+            ),
+            "hpf" => format!(
+                "hpf {} {}",
+                control("hcutoff", ""),
+                control("hresonance", DEFAULT_Q)
+            ),
+            "reverb" => format!(
+                "reverb {} 0.5 {}",
+                control("size", "0.5"),
+                control("room", "")
+            ),
+            _ => format!(
+                "delay {} {} {}",
+                control("delaytime", "0.25"),
+                control("delayfeedback", "0.5"),
+                control("delay", "")
+            ),
+        };
+        steps.insert(at, step);
+    }
+    steps.splice(fx_at..fx_at, voice_fx);
+    steps
+}
+
+/// One prefix function (`every 4 (fast 2)`, `fast 2 . rev`) as postfix
+/// transforms in the order they apply
+fn translate_transform(function: &str, warn: &mut impl FnMut(String)) -> Vec<String> {
+    let function = strip_parens(function);
+    let composed = split_top(function, " . ");
+    if composed.len() > 1 {
+        // `f . g` applies g first
+        return composed
+            .iter()
+            .rev()
+            .flat_map(|part| translate_transform(part, warn))
+            .collect();
+    }
+
+    // Sections: `(0.25 <~)`, `(1/8 ~>)`
+    for (operator, transform) in [("<~", "early"), ("~>", "late")] {
+        if let Some(amount) = function.strip_suffix(operator) {
+            return vec![format!(
+                "{} {}",
+                transform,
+                wrap(strip_parens(amount.trim()))
+            )];
+        }
+    }
+
+    let (name, args) = split_word(function);
+    let name = match name {
+        "density" => "fast",
+        "sparsity" => "slow",
+        other => other,
+    };
+    let transform = format!("{} {}", name, args).trim().to_string();
+    if compiles(&format!("s \"bd\" $ {}", transform)) {
+        vec![transform]
+    } else {
+        warn(format!("`{}` is not supported (dropped)", name));
+        Vec::new()
+    }
+}
+
+/// Whether `out $ pattern` parses and compiles
+fn compiles(pattern: &str) -> bool {
+    match parse_program(&format!("out $ {}", pattern)) {
+        Ok((rest, statements)) if rest.trim().is_empty() => {
+            compile_program(statements, 44100.0, None).is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Byte offsets where `separator` appears outside brackets and strings
+fn top_level_matches(text: &str, separator: &str) -> Vec<usize> {
+    let mut matches = Vec::new();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ if depth == 0 && text[i..].starts_with(separator) => matches.push(i),
+            _ => {}
+        }
+    }
+    matches
+}
+
+/// Split on top-level occurrences of `separator`
+fn split_top<'a>(text: &'a str, separator: &str) -> Vec<&'a str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    for at in top_level_matches(text, separator) {
+        if at >= start {
+            parts.push(text[start..at].trim());
+            start = at + separator.len();
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+fn find_top(text: &str, pattern: &str) -> Option<usize> {
+    top_level_matches(text, pattern).first().copied()
+}
+
+fn find_top_any(text: &str, chars: &[char]) -> Option<usize> {
+    chars
+        .iter()
+        .filter_map(|c| find_top(text, &c.to_string()))
+        .min()
+}
+
+/// `text` without brackets that wrap all of it: `((fast 2))` → `fast 2`
+fn strip_parens(text: &str) -> &str {
+    let mut text = text.trim();
+    while text.starts_with('(') && text.ends_with(')') {
+        let inner = &text[1..text.len() - 1];
+        // `(a) (b)` starts and ends with brackets that don't match
+        let mut depth = 0;
+        let balanced = inner.chars().all(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth >= 0
+        });
+        if !balanced {
+            break;
+        }
+        text = inner.trim();
+    }
+    text
+}
+
+/// Parenthesise an argument unless it is a number or a string: `1/4` → `(1/4)`
+fn wrap(arg: &str) -> String {
+    if arg.parse::<f64>().is_ok() || arg.starts_with('"') && arg.ends_with('"') {
+        arg.to_string()
+    } else {
+        format!("({})", arg)
+    }
+}
+
+/// Numbers the way they'd be typed: no trailing zeros
+fn format_number(value: f64) -> String {
+    let text = format!("{:.6}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Evaluate `(135/60/4)`-style arithmetic: numbers, + - * / and brackets
+pub fn eval_number(text: &str) -> Option<f64> {
+    let tokens: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut pos = 0;
+    let value = eval_sum(&tokens, &mut pos)?;
+    (pos == tokens.len() && value.is_finite()).then_some(value)
+}
+
+fn eval_sum(tokens: &[char], pos: &mut usize) -> Option<f64> {
+    let mut value = eval_product(tokens, pos)?;
+    while let Some(&op) = tokens.get(*pos).filter(|c| matches!(c, '+' | '-')) {
+        *pos += 1;
+        let rhs = eval_product(tokens, pos)?;
+        value = if op == '+' { value + rhs } else { value - rhs };
+    }
+    Some(value)
+}
+
+fn eval_product(tokens: &[char], pos: &mut usize) -> Option<f64> {
+    let mut value = eval_atom(tokens, pos)?;
+    while let Some(&op) = tokens.get(*pos).filter(|c| matches!(c, '*' | '/')) {
+        *pos += 1;
+        let rhs = eval_atom(tokens, pos)?;
+        value = if op == '*' { value * rhs } else { value / rhs };
+    }
+    Some(value)
+}
+
+fn eval_atom(tokens: &[char], pos: &mut usize) -> Option<f64> {
+    match tokens.get(*pos)? {
+        '(' => {
+            *pos += 1;
+            let value = eval_sum(tokens, pos)?;
+            if tokens.get(*pos) != Some(&')') {
+                return None;
+            }
+            *pos += 1;
+            Some(value)
+        }
+        '-' => {
+            *pos += 1;
+            eval_atom(tokens, pos).map(|v| -v)
+        }
+        _ => {
+            let start = *pos;
+            while tokens
+                .get(*pos)
+                .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+            {
+                *pos += 1;
+            }
+            tokens[start..*pos].iter().collect::<String>().parse().ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_number() {
+        assert_eq!(eval_number("(135/60/4)"), Some(0.5625));
+        assert_eq!(eval_number("0.5"), Some(0.5));
+        assert_eq!(eval_number("1 + 2 * 3"), Some(7.0));
+        assert_eq!(eval_number("-(1/2)"), Some(-0.5));
+        assert_eq!(eval_number("bpm / 60"), None);
+        assert_eq!(eval_number("(1/0)"), None);
+    }
+
+    #[test]
+    fn test_blocks_join_continuations_and_split_do() {
+        let source = "d1 $ sound \"bd*2\" -- kick\n  # gain 0.8\n\ndo\n  d2 $ s \"hh\"\n  d3 $ s \"sn\"\n    # pan 1\n-- the end";
+        let blocks: Vec<(usize, String)> = blocks(source);
+        assert_eq!(
+            blocks,
+            vec![
+                (1, "d1 $ sound \"bd*2\" # gain 0.8".to_string()),
+                (5, "d2 $ s \"hh\"".to_string()),
+                (6, "d3 $ s \"sn\" # pan 1".to_string()),
+                (8, "-- the end".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_top_ignores_nested_and_quoted() {
+        assert_eq!(
+            split_top("every 4 (fast 2 $ rev) $ s \"bd $ sn\"", "$"),
+            vec!["every 4 (fast 2 $ rev)", "s \"bd $ sn\""]
+        );
+        assert_eq!(strip_parens("((fast 2))"), "fast 2");
+        assert_eq!(strip_parens("(fast 2) (rev)"), "(fast 2) (rev)");
+    }
+}
@@ -73,6 +73,11 @@
 //! - **Analysis**: `RMS`, `Pitch`, `Transient`
 //! - **Math**: `Add`, `Multiply`, `When`
 //!
+//! `SignalNode` is internal: its variants and fields change between
+//! releases, so it is hidden from the docs. The examples below show how nodes
+//! are wired inside the crate; code outside it should use
+//! [`GraphBuilder`](crate::graph_builder::GraphBuilder) or the DSL.
+//!
 //! # Basic Example: Simple Sample Playback
 //!
 //! ```rust
@@ -479,20 +484,31 @@
 //! - [`SampleBank`] - Sample loading from dirt-samples
 //! - [`mini_notation_v3`] - Pattern parsing and querying
 
+use crate::adaptive_quality::Quality;
+use crate::bus_meters::BusMeters;
+use crate::denormals::{flush_denormal, DcBlocker};
+use crate::event_log::{EventLogSender, LoggedEvent};
+use crate::latency::{Nudge, Shift};
 use crate::midi_input::{ArpPattern, Arpeggiator, Scale, scale_lock};
 use crate::mini_notation_v3::parse_mini_notation;
+use crate::node_factory::UserNodeState;
+use crate::nodes::noise_gate::{GateSettings, NoiseGateState};
+use crate::clock_divider::{ClockDividerState, ClockMultiplierState};
 use crate::pattern::{Fraction, Pattern, State, TimeSpan};
-use crate::plugin_host::{MockPluginInstance, PluginInstanceManager, RealPluginInstance};
+use crate::plugin_host::{
+    ExternalFxState, MockPluginInstance, PluginInstanceManager, RealPluginInstance,
+};
 #[cfg(feature = "vst3")]
 use crate::plugin_host::create_real_plugin_by_name;
 #[cfg(feature = "vst2")]
 use crate::plugin_host::{Vst2PluginInstance, create_vst2_plugin_by_name};
 use crate::sample_loader::SampleBank;
 use crate::synth_voice_manager::SynthVoiceManager;
-use crate::voice_manager::{VoiceBuffers, VoiceManager};
+use crate::voice_manager::{VoiceBuffers, VoiceFx, VoiceManager};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::f32::consts::PI;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -513,7 +529,6 @@ lazy_static::lazy_static! {
 // a thread-local `Vec<f32>` that is reused across samples (capacity retained) and
 // zip it back against the node's `params` map at apply time — zero per-sample
 // allocations and zero name clones. Thread-local so each render thread has its own.
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 thread_local! {
     static PLUGIN_PARAM_SCRATCH: RefCell<Vec<f32>> = const { RefCell::new(Vec::new()) };
 }
@@ -521,13 +536,11 @@ thread_local! {
 /// Take ownership of the thread-local param scratch buffer (leaving it empty).
 /// Ownership is taken (rather than a borrow held) so that evaluating a parameter
 /// signal that re-enters plugin evaluation cannot double-borrow the cell.
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 fn take_plugin_param_scratch() -> Vec<f32> {
     PLUGIN_PARAM_SCRATCH.with(|s| std::mem::take(&mut *s.borrow_mut()))
 }
 
 /// Return the scratch buffer to the thread-local cell, retaining its capacity.
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 fn put_plugin_param_scratch(buf: Vec<f32>) {
     PLUGIN_PARAM_SCRATCH.with(|s| {
         // Keep whichever buffer has the larger capacity (a re-entrant eval may have
@@ -542,10 +555,8 @@ fn put_plugin_param_scratch(buf: Vec<f32>) {
 /// RAII wrapper: hands out the reusable plugin-param scratch buffer and guarantees
 /// it is returned to the thread-local cell on ANY exit path (including the plugin
 /// branch's early `return`s). Deref/DerefMut expose the inner `Vec<f32>`.
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 struct PluginParamScratch(Vec<f32>);
 
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 impl PluginParamScratch {
     fn acquire() -> Self {
         let mut buf = take_plugin_param_scratch();
@@ -554,14 +565,12 @@ impl PluginParamScratch {
     }
 }
 
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 impl Drop for PluginParamScratch {
     fn drop(&mut self) {
         put_plugin_param_scratch(std::mem::take(&mut self.0));
     }
 }
 
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 impl std::ops::Deref for PluginParamScratch {
     type Target = Vec<f32>;
     fn deref(&self) -> &Vec<f32> {
@@ -569,13 +578,19 @@ impl std::ops::Deref for PluginParamScratch {
     }
 }
 
-#[cfg(any(feature = "vst3", feature = "vst2"))]
 impl std::ops::DerefMut for PluginParamScratch {
     fn deref_mut(&mut self) -> &mut Vec<f32> {
         &mut self.0
     }
 }
 
+/// Channel key of the cue output in the hushed set (no numbered output can
+/// collide with it)
+pub const CUE_CHANNEL: usize = usize::MAX;
+
+/// Channel key of the control voltage outputs in the hushed set
+pub const CV_CHANNEL: usize = usize::MAX - 1;
+
 /// Unique identifier for nodes in the graph
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub struct NodeId(pub usize);
@@ -751,6 +766,11 @@ pub enum RuntimeEnvelopeType {
 }
 
 /// Types of nodes in the unified graph
+///
+/// Hidden from the docs: variants and their state fields change from release
+/// to release. Outside the crate, build graphs with
+/// [`GraphBuilder`](crate::graph_builder::GraphBuilder)
+#[doc(hidden)]
 #[derive(Debug, Clone)]
 pub enum SignalNode {
     // === Sources ===
@@ -760,19 +780,31 @@ pub enum SignalNode {
         freq: Signal,
         waveform: Waveform,
         semitone_offset: f32, // Semitone offset for note triggering (+0.5, -2.3, etc.)
-        phase: std::cell::RefCell<f32>, // Interior mutability for parallel synthesis
+        phase: std::cell::RefCell<f64>, // f64 so hour-long renders stay in tune
         pending_freq: std::cell::RefCell<Option<f32>>, // Frequency change waiting for zero-crossing
         last_sample: std::cell::RefCell<f32>, // For zero-crossing detection
     },
 
+    /// Oscillator whose phase restarts from outside: hard sync to another
+    /// signal's rising zero crossings, and/or retriggering on rising edges
+    /// of a trigger signal
+    /// Usage: saw 220 :sync ~master, saw 55 :retrig "t ~ t t"
+    SyncOscillator {
+        freq: Signal,
+        waveform: Waveform,
+        sync: Option<Signal>,   // Master signal for hard sync
+        retrig: Option<Signal>, // Phase reset triggers (> 0.5)
+        state: SyncOscState,
+    },
+
     /// FM (Frequency Modulation) oscillator
     /// output = sin(2π * carrier * t + mod_index * sin(2π * modulator * t))
     FMOscillator {
         carrier_freq: Signal,                     // Carrier frequency in Hz
         modulator_freq: Signal,                   // Modulator frequency in Hz
         mod_index: Signal,                        // Modulation index (depth)
-        carrier_phase: std::cell::RefCell<f32>,   // Carrier phase (0.0 to 1.0)
-        modulator_phase: std::cell::RefCell<f32>, // Modulator phase (0.0 to 1.0)
+        carrier_phase: std::cell::RefCell<f64>,   // Carrier phase (0.0 to 1.0)
+        modulator_phase: std::cell::RefCell<f64>, // Modulator phase (0.0 to 1.0)
     },
 
     /// Phase Modulation (PM) oscillator
@@ -783,7 +815,7 @@ pub enum SignalNode {
         carrier_freq: Signal,                   // Carrier frequency in Hz
         modulation: Signal,                     // External modulation signal
         mod_index: Signal,                      // Modulation index (depth)
-        carrier_phase: std::cell::RefCell<f32>, // Carrier phase (0.0 to 1.0)
+        carrier_phase: std::cell::RefCell<f64>, // Carrier phase (0.0 to 1.0)
     },
 
     /// Blip oscillator (Band-Limited Impulse Train)
@@ -1101,6 +1133,23 @@ pub enum SignalNode {
         pulse_width: Signal,   // Pattern-modulatable pulse width in seconds
     },
 
+    /// Frozen bus: `cycles` cycles of a bus rendered offline by `freeze`,
+    /// looped in step with the cycle position
+    FrozenBus {
+        buffer: Arc<Vec<f32>>,
+        cycles: f64,
+    },
+
+    /// One channel's share of a source placed by `# azimuth` and
+    /// `# elevation` (degrees) in a `spatial:` layout
+    SpatialGain {
+        input: Signal,
+        azimuth: Signal,
+        elevation: Signal,
+        layout: crate::spatial::SpatialLayout,
+        channel: usize,
+    },
+
     /// Sample player triggered by pattern
     Sample {
         pattern_str: String,
@@ -1261,6 +1310,9 @@ pub enum SignalNode {
     PatternGate {
         pattern_str: String,
         pattern: Pattern<bool>,
+        /// Seconds before each event ends that the gate drops, so back-to-back
+        /// events retrigger (0 for a gate that stays high across them)
+        retrigger_gap: f64,
     },
 
     /// Pattern to trigger pulse - outputs 1.0 for one sample at event onset, 0.0 otherwise
@@ -1275,6 +1327,10 @@ pub enum SignalNode {
     /// This allows sample playback to be routed through effects
     VoiceOutput,
 
+    /// Orbit bus: the voices sent here with `# orbit N`, which leave their
+    /// own Sample node's output. Reads the voice mix like a Sample node does
+    Orbit { orbit: usize },
+
     /// Scale quantization - maps scale degrees to frequencies
     /// Pattern contains scale degrees (0, 1, 2, 3...), quantized to musical scale
     ScaleQuantize {
@@ -1295,6 +1351,20 @@ pub enum SignalNode {
         speed: Signal, // Speed multiplier (1.0 = one ramp per cycle)
     },
 
+    /// Low-frequency oscillator for modulation, computed at control rate
+    /// and scaled into min..max
+    /// Usage: lfo 0.25c :shape tri :phase 0.25 :min 200 :max 2000
+    Lfo {
+        rate: Signal, // Hz, or the period in cycles when `synced`
+        synced: bool, // Phase follows the cycle position
+        shape: LfoShape,
+        phase_offset: f32, // In periods, 0..1
+        min: Signal,
+        max: Signal,
+        oneshot: bool, // Run one period, then hold
+        state: LfoState,
+    },
+
     /// Pattern evaluator - evaluates a numeric pattern at current cycle position
     /// Used for functions like run, scan that generate numeric patterns
     PatternEvaluator { pattern: Pattern<f64> },
@@ -1332,12 +1402,14 @@ pub enum SignalNode {
     },
 
     // === Conditional Effects ===
-    /// Apply effect every N cycles, bypass otherwise
+    /// Apply effect when cycle % n == offset, bypass otherwise
     /// Enables syntax like: s "bd" $ every 4 (# lpf 300)
+    /// and s "bd" $ every' 4 3 (# lpf 300)
     EveryEffect {
         input: Signal,
         effect: Signal,
         n: i32,
+        offset: i32,
     },
 
     /// Apply effect with probability per cycle
@@ -1348,8 +1420,8 @@ pub enum SignalNode {
         prob: Signal, // Pattern-modulatable probability
     },
 
-    /// Apply effect when (cycle - offset) % modulo == 0
-    /// Enables syntax like: s "bd" $ whenmod 3 1 (# lpf 300)
+    /// Apply effect when cycle % modulo >= offset
+    /// Enables syntax like: s "bd" $ whenmod 8 6 (# lpf 300)
     WhenmodEffect {
         input: Signal,
         effect: Signal,
@@ -1544,13 +1616,16 @@ pub enum SignalNode {
     PingPongDelay {
         input: Signal,
         time: Signal,         // Delay time per side
+        synced: bool,         // `time` is in cycles rather than seconds
         feedback: Signal,     // Feedback amount
-        stereo_width: Signal, // Stereo spread (0.0-1.0)
+        stereo_width: Signal, // How much of the input skips the right side (0.0-1.0)
+        filter: Signal,       // Lowpass cutoff (Hz) on the cross-feedback
         channel: bool,        // false = left, true = right
         mix: Signal,          // Dry/wet mix
         buffer_l: Vec<f32>,   // Left channel buffer
         buffer_r: Vec<f32>,   // Right channel buffer
         write_idx: usize,
+        lpf_state: (f32, f32), // Feedback filter, left and right
     },
 
     // === Analysis ===
@@ -1592,6 +1667,21 @@ pub enum SignalNode {
         last_trigger: f32, // Previous trigger value (for edge detection)
     },
 
+    /// Clock divider: passes every Nth pulse of a clock signal (`clockdiv`)
+    ClockDivider {
+        input: Signal,
+        division: Signal,
+        state: ClockDividerState,
+    },
+
+    /// Clock multiplier: N evenly spaced pulses per pulse of a clock signal
+    /// (`clockmult`)
+    ClockMultiplier {
+        input: Signal,
+        factor: Signal,
+        state: ClockMultiplierState,
+    },
+
     /// Pitch detector
     Pitch { input: Signal, last_pitch: f32 },
 
@@ -1652,6 +1742,9 @@ pub enum SignalNode {
     /// Formula: freq = 440 * 2^((midi - 69) / 12)
     MidiToFreq { midi: Signal },
 
+    /// Frequency to 1 V/octave control voltage, 0 V at C4 (`voct`)
+    HzToVolts { freq: Signal },
+
     /// Wrap signal into [min, max] range using modulo
     /// Wraps values outside the range back into the range periodically
     Wrap {
@@ -1660,6 +1753,12 @@ pub enum SignalNode {
         max: Signal,
     },
 
+    /// Random value in [min, max]: `0.9 ~ 1.0`, `range 0.9 1.0 rand`
+    /// Drawn per evaluation time from a seed of the node and that time, so as
+    /// a sample/synth parameter each trigger gets its own value (taken at the
+    /// event onset) and renders are reproducible
+    RandSpread { min: Signal, max: Signal },
+
     /// Sample-and-hold - captures input when trigger crosses from negative to positive
     /// Classic analog-style S&H: monitors trigger for zero crossings, samples input, holds value
     /// Useful for stepped modulation, random voltage generation, rhythmic parameter automation
@@ -1787,6 +1886,24 @@ pub enum SignalNode {
         state: SpectralFreezeState,
     },
 
+    /// Chunked reverse: each chunk of input plays backwards during the next
+    /// Used by revreverb to swell a reverb tail up instead of down
+    Reverse {
+        input: Signal,
+        state: ReverseState,
+    },
+
+    /// Variable-speed buffer reader, switched on by a pattern's events
+    /// Used by tapestop, stutter and beatrepeat; passes its input through
+    /// while off (no event, or a value of 0)
+    Varispeed {
+        input: Signal,
+        pattern_str: String,
+        pattern: Pattern<f64>,
+        mode: VarispeedMode,
+        state: VarispeedState,
+    },
+
     /// Distortion / Waveshaper
     Distortion {
         input: Signal,
@@ -1834,6 +1951,37 @@ pub enum SignalNode {
         state: StereoWidenerState,
     },
 
+    /// Tilt EQ — one knob from dark to bright: opposing low and high shelves
+    /// around `pivot`, plus an "air" shelf at 12 kHz
+    TiltEq {
+        input: Signal,
+        tilt: Signal,  // dB: positive brightens, negative darkens
+        pivot: Signal, // Hz
+        air: Signal,   // dB of the 12 kHz shelf
+        state: TiltEqState,
+    },
+
+    /// Saturation — tape or transformer style soft clipping, run at 4x
+    /// oversampling so high drive doesn't alias
+    Saturate {
+        input: Signal,
+        drive: Signal, // Input gain into the curve, 1 = gentle
+        mix: Signal,   // 0 = dry, 1 = wet
+        curve: SaturationCurve,
+        state: SaturatorState,
+    },
+
+    /// Memoryless waveshaper (`fold`, `clip`, and `distortion`, `wrap` or
+    /// `bitcrush` with `:oversample`), optionally oversampled
+    Waveshaper {
+        input: Signal,
+        shape: Waveshape,
+        a: Signal,   // First curve parameter (see `Waveshape`)
+        b: Signal,   // Second curve parameter
+        mix: Signal, // 0 = dry, 1 = wet
+        oversampler: Oversampler,
+    },
+
     /// Compressor (dynamic range compression)
     Compressor {
         input: Signal,
@@ -1847,11 +1995,13 @@ pub enum SignalNode {
 
     /// Transient shaper — independently boosts/cuts the attack (transient) and
     /// sustain (body) portions of a signal. Uses two envelope followers (fast +
-    /// slow); their difference detects transients. Attack/sustain are gains in dB.
+    /// slow); their difference detects transients. Attack/sustain are gains in
+    /// dB (`transient_shaper`), or linear gains when `linear` is set (`transient`).
     TransientShaper {
         input: Signal,
         attack_db: Signal,  // Attack (transient) gain in dB (-40.0 to 40.0)
         sustain_db: Signal, // Sustain (body) gain in dB (-40.0 to 40.0)
+        linear: bool,       // attack_db/sustain_db hold linear gains (0.01 to 100.0)
         state: TransientShaperState,
     },
 
@@ -1878,6 +2028,21 @@ pub enum SignalNode {
         state: ExpanderState,
     },
 
+    /// Noise gate - silences the input while its level, or the key's, is below
+    /// the threshold. With a key (`gate ~pad :key ~kick`) the rhythm of one bus
+    /// chops another, the trance gate. Hysteresis and hold keep a level
+    /// hovering around the threshold from chattering the gate open and shut
+    NoiseGate {
+        input: Signal,
+        key: Option<Signal>, // Sidechain deciding when the gate opens (input if None)
+        threshold: Signal,   // Opening threshold in dB (-100.0 to 0.0)
+        hysteresis: Signal,  // Close this far below the threshold, in dB (0.0 to 40.0)
+        attack: Signal,      // Opening time in seconds (0.0001 to 1.0)
+        hold: Signal,        // Open time after the level drops, in seconds (0.0 to 5.0)
+        release: Signal,     // Closing time in seconds (0.001 to 5.0)
+        state: NoiseGateState,
+    },
+
     /// Adaptive Compressor - compression that adapts to signal analysis
     /// Uses sidechain RMS/peak analysis to modulate threshold and ratio
     /// Enables complex feedback networks where compression responds to signal characteristics
@@ -1954,6 +2119,22 @@ pub enum SignalNode {
         state: Arc<Mutex<FundspState>>, // Thread-safe shared mutable fundsp unit state
     },
 
+    /// User AudioNode registered through `node_factory`
+    /// Usage: ~lead $ saw 110 # wavefold 3 :symmetry 0.2
+    UserNode {
+        name: String,        // DSL name, for debugging
+        inputs: Vec<Signal>, // [input?, param1, param2, ...] in spec order
+        state: Arc<Mutex<UserNodeState>>,
+    },
+
+    /// External effect plugin, run a block at a time (see plugin_host::external_fx)
+    /// Usage: ~comp $ extfx "Compressor" ~drums :threshold -20 :param "Release Time" 80
+    ExternalFx {
+        input: Signal,
+        params: Vec<Signal>, // In the order of the state's parameter names
+        state: Arc<Mutex<ExternalFxState>>,
+    },
+
     /// Tap/Probe - Records signal to buffer for debugging
     /// Passes signal through unchanged while recording to file
     /// Useful for debugging signal flow and analyzing what's happening at different points
@@ -1962,17 +2143,74 @@ pub enum SignalNode {
         state: Arc<Mutex<TapState>>, // Shared mutable state for recording
     },
 
+    /// Auto-gain: slowly turns its input up or down toward a target RMS level
+    /// (`autogain ~bus`), holding the gain while the input is near silent
+    AutoGain {
+        input: Signal,
+        target: f32,      // Target RMS (linear)
+        time: f32,        // Time constant of gain changes in seconds
+        mean_square: f32, // Smoothed input power
+        gain: f32,        // Current gain
+    },
+
     /// Output node
     Output { input: Signal },
 }
 
 /// Oscillator waveforms
+///
+/// Saw and square are band-limited with polyBLEP; `RawSaw` and `RawSquare`
+/// are their naive, aliasing versions (`saw 55 :raw true`)
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Waveform {
     Sine,
     Saw,
     Square,
     Triangle,
+    RawSaw,
+    RawSquare,
+}
+
+impl Waveform {
+    /// The naive version of a band-limited waveform
+    pub fn raw(self) -> Self {
+        match self {
+            Self::Saw => Self::RawSaw,
+            Self::Square => Self::RawSquare,
+            other => other,
+        }
+    }
+
+    /// One sample at `phase` (0..1), where `phase_inc` is the phase step per
+    /// sample (freq / sample_rate)
+    pub fn sample(self, phase: f32, phase_inc: f32) -> f32 {
+        use crate::nodes::polyblep_osc::poly_blep;
+
+        let dt = phase_inc.abs().min(0.5);
+        match self {
+            Self::Sine => (2.0 * PI * phase).sin(),
+            Self::Saw => 2.0 * phase - 1.0 - poly_blep(phase, dt),
+            Self::Square => {
+                Self::RawSquare.sample(phase, dt) + poly_blep(phase, dt)
+                    - poly_blep((phase + 0.5).fract(), dt)
+            }
+            Self::Triangle => {
+                if phase < 0.5 {
+                    4.0 * phase - 1.0
+                } else {
+                    3.0 - 4.0 * phase
+                }
+            }
+            Self::RawSaw => 2.0 * phase - 1.0,
+            Self::RawSquare => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+        }
+    }
 }
 
 /// fundsp Unit Types
@@ -2374,6 +2612,21 @@ impl FundspState {
             );
         }
     }
+
+    /// Rebuild the unit for a new sample rate (internal state is cleared)
+    pub fn set_sample_rate(&mut self, sample_rate: f64) {
+        if self.sample_rate == sample_rate {
+            return;
+        }
+        match self.unit_type {
+            // Built from audio-rate inputs only; no constructor to rebuild from
+            FundspUnitType::Phaser | FundspUnitType::DLowpassHz => {}
+            _ => {
+                self.sample_rate = sample_rate;
+                *self = self.clone();
+            }
+        }
+    }
 }
 
 impl Clone for FundspState {
@@ -2400,6 +2653,7 @@ impl Clone for FundspState {
             FundspUnitType::Noise => Self::new_noise(self.sample_rate),
             FundspUnitType::Pink => Self::new_pink(self.sample_rate),
             FundspUnitType::Pulse => Self::new_pulse(self.sample_rate),
+            FundspUnitType::SoftSawHz => Self::new_soft_saw_hz(self.params[0], self.sample_rate),
             _ => panic!("Clone not implemented for this fundsp unit type"),
         }
     }
@@ -2417,7 +2671,7 @@ impl std::fmt::Debug for FundspState {
 }
 
 /// Filter state for biquad filters
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilterState {
     pub x1: f32,
     pub x2: f32,
@@ -2476,6 +2730,14 @@ fn widener_allpass_coeffs(sample_rate: f32) -> biquad::Coefficients<f32> {
     .expect("valid all-pass coefficients for stereo widener")
 }
 
+/// Resize a delay line by `ratio` (new rate / old rate) and clear it, so the
+/// maximum delay time stays the same in seconds
+fn rescale_delay_line(buffer: &mut Vec<f32>, ratio: f32) {
+    let len = ((buffer.len() as f32 * ratio).round() as usize).max(1);
+    buffer.clear();
+    buffer.resize(len, 0.0);
+}
+
 /// Stereo widener state — a biquad all-pass filter used to synthesise the
 /// phase-shifted "side" signal for pseudo-stereo width in mono mode.
 #[derive(Debug, Clone)]
@@ -2497,9 +2759,448 @@ impl Default for StereoWidenerState {
     }
 }
 
+/// Frequency of the tilt EQ's "air" shelf
+const TILT_AIR_HZ: f32 = 12000.0;
+
+/// Shelf coefficients for the tilt EQ, keeping the corner below Nyquist
+fn tilt_shelf_coeffs(
+    shelf: biquad::Type<f32>,
+    sample_rate: f32,
+    freq: f32,
+) -> biquad::Coefficients<f32> {
+    use biquad::{Coefficients, ToHertz};
+    let freq = freq.clamp(20.0, sample_rate * 0.45);
+    Coefficients::<f32>::from_params(shelf, sample_rate.hz(), freq.hz(), 0.707)
+        .expect("valid shelf coefficients for tilt EQ")
+}
+
+/// Running state of a [`SignalNode::SyncOscillator`]
+#[derive(Debug, Clone, Default)]
+pub struct SyncOscState {
+    pub phase: f64,
+    pub last_sync: f32,
+    pub last_retrig: f32,
+}
+
+impl SyncOscState {
+    /// Advance one sample, restarting the cycle on a sync crossing or a
+    /// retrigger, and return the phase to play
+    pub fn step(&mut self, phase_inc: f32, sync: Option<f32>, retrig: Option<f32>) -> f32 {
+        if let Some(x) = sync {
+            if self.last_sync < 0.0 && x >= 0.0 {
+                // Restart from where the master crossed between the two
+                // samples, so the synced pitch doesn't jitter
+                let since_crossing = x / (x - self.last_sync);
+                self.phase = (since_crossing * phase_inc).rem_euclid(1.0) as f64;
+            }
+            self.last_sync = x;
+        }
+        if let Some(x) = retrig {
+            if x > 0.5 && self.last_retrig <= 0.5 {
+                self.phase = 0.0;
+            }
+            self.last_retrig = x;
+        }
+
+        let phase = self.phase as f32;
+        self.phase = (self.phase + phase_inc as f64).rem_euclid(1.0);
+        phase
+    }
+}
+
+/// How many samples an [`SignalNode::Lfo`] goes between computing its
+/// shape; it ramps linearly in between
+pub const LFO_CONTROL_INTERVAL: usize = 32;
+
+/// Waveform of an [`SignalNode::Lfo`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LfoShape {
+    Sine,
+    Triangle,
+    Saw,
+    Ramp,
+    Square,
+    Random,
+}
+
+impl LfoShape {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sine" => Some(Self::Sine),
+            "tri" | "triangle" => Some(Self::Triangle),
+            "saw" => Some(Self::Saw),
+            "ramp" => Some(Self::Ramp),
+            "square" => Some(Self::Square),
+            "random" | "sh" => Some(Self::Random),
+            _ => None,
+        }
+    }
+
+    /// Value at `phase` (in periods), from 0 to 1. Sine starts mid-way and
+    /// rising like `sine`, triangle and saw start at the bottom.
+    pub fn unit(self, phase: f64) -> f32 {
+        let p = phase.rem_euclid(1.0) as f32;
+        match self {
+            Self::Sine => 0.5 + 0.5 * (2.0 * PI * p).sin(),
+            Self::Triangle => {
+                if p < 0.5 {
+                    2.0 * p
+                } else {
+                    2.0 - 2.0 * p
+                }
+            }
+            Self::Saw => p,
+            Self::Ramp => 1.0 - p,
+            Self::Square => {
+                if p < 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::Random => {
+                // One value per period, the same on every run
+                let mut x = (phase.floor() as i64 as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+                x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                ((x ^ (x >> 31)) >> 40) as f32 / (1u64 << 24) as f32
+            }
+        }
+    }
+
+    /// Whether the shape jumps rather than glides between control points
+    fn is_stepped(self) -> bool {
+        matches!(self, Self::Square | Self::Random)
+    }
+}
+
+/// Running state of a [`SignalNode::Lfo`]
+#[derive(Debug, Clone, Default)]
+pub struct LfoState {
+    pub phase: f64,               // Free-running phase, in periods
+    pub start_cycle: Option<f64>, // Where a synced one-shot started
+    value: f32,
+    step: f32,
+    countdown: usize,
+}
+
+impl LfoState {
+    /// Start a control period, given the LFO's phase now and
+    /// `LFO_CONTROL_INTERVAL` samples from now
+    fn schedule(
+        &mut self,
+        shape: LfoShape,
+        phase_offset: f32,
+        oneshot: bool,
+        min: f32,
+        max: f32,
+        now: f64,
+        next: f64,
+    ) {
+        let at = |phase: f64| {
+            // A one-shot holds where its single period ends
+            let phase = if oneshot {
+                phase.clamp(0.0, 1.0 - 1e-9)
+            } else {
+                phase
+            };
+            min + (max - min) * shape.unit(phase + phase_offset as f64)
+        };
+        self.value = at(now);
+        self.step = if shape.is_stepped() {
+            0.0
+        } else {
+            (at(next) - self.value) / LFO_CONTROL_INTERVAL as f32
+        };
+        self.countdown = LFO_CONTROL_INTERVAL;
+    }
+
+    fn next_sample(&mut self) -> f32 {
+        let value = self.value;
+        self.value += self.step;
+        self.countdown = self.countdown.saturating_sub(1);
+        value
+    }
+}
+
+/// Tilt EQ state — low, high and air shelves, rebuilt whenever the tilt,
+/// pivot, air gain or sample rate they were built for changes
+#[derive(Debug, Clone)]
+pub struct TiltEqState {
+    low: biquad::DirectForm2Transposed<f32>,
+    high: biquad::DirectForm2Transposed<f32>,
+    air: biquad::DirectForm2Transposed<f32>,
+    /// (tilt dB, pivot Hz, air dB, sample rate) of the current coefficients
+    params: (f32, f32, f32, f32),
+}
+
+impl Default for TiltEqState {
+    fn default() -> Self {
+        let flat = tilt_shelf_coeffs(biquad::Type::LowShelf(0.0), 44100.0, 800.0);
+        Self {
+            low: biquad::DirectForm2Transposed::<f32>::new(flat),
+            high: biquad::DirectForm2Transposed::<f32>::new(flat),
+            air: biquad::DirectForm2Transposed::<f32>::new(flat),
+            params: (0.0, 800.0, 0.0, 44100.0),
+        }
+    }
+}
+
+impl TiltEqState {
+    pub fn process(&mut self, x: f32, tilt: f32, pivot: f32, air: f32, sample_rate: f32) -> f32 {
+        use biquad::Biquad;
+
+        let params = (tilt, pivot, air, sample_rate);
+        if params != self.params {
+            self.low.update_coefficients(tilt_shelf_coeffs(
+                biquad::Type::LowShelf(-tilt / 2.0),
+                sample_rate,
+                pivot,
+            ));
+            self.high.update_coefficients(tilt_shelf_coeffs(
+                biquad::Type::HighShelf(tilt / 2.0),
+                sample_rate,
+                pivot,
+            ));
+            self.air.update_coefficients(tilt_shelf_coeffs(
+                biquad::Type::HighShelf(air),
+                sample_rate,
+                TILT_AIR_HZ,
+            ));
+            self.params = params;
+        }
+        self.air.run(self.high.run(self.low.run(x)))
+    }
+
+    pub fn reset(&mut self) {
+        use biquad::Biquad;
+        self.low.reset_state();
+        self.high.reset_state();
+        self.air.reset_state();
+    }
+}
+
+/// Transfer curves for the `saturate` node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaturationCurve {
+    /// Biased tanh: soft knee with even harmonics
+    Tape,
+    /// Symmetric, harder knee with mostly odd harmonics
+    Transformer,
+}
+
+impl SaturationCurve {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "tape" => Some(Self::Tape),
+            "transformer" | "iron" => Some(Self::Transformer),
+            _ => None,
+        }
+    }
+
+    /// Both curves have unit slope at zero and level off toward +/-1
+    fn shape(self, x: f32) -> f32 {
+        match self {
+            Self::Tape => {
+                const BIAS: f32 = 0.2;
+                let t = BIAS.tanh();
+                ((x + BIAS).tanh() - t) / (1.0 - t * t)
+            }
+            Self::Transformer => x / (1.0 + x.abs().powi(3)).cbrt(),
+        }
+    }
+}
+
+/// Delay of an [`Oversampler`]'s filters, up and down together, in input
+/// samples (the same at 2x and 4x)
+const OVERSAMPLE_LATENCY: usize = 48;
+
+/// Blackman-windowed sinc lowpass at the original Nyquist for `factor` times
+/// oversampling (a half-band filter at 2x), unity DC gain
+fn oversample_fir(factor: usize) -> &'static [f32] {
+    static FIR_2X: std::sync::OnceLock<Vec<f32>> = std::sync::OnceLock::new();
+    static FIR_4X: std::sync::OnceLock<Vec<f32>> = std::sync::OnceLock::new();
+    let cell = if factor == 2 { &FIR_2X } else { &FIR_4X };
+    cell.get_or_init(|| {
+        let len = OVERSAMPLE_LATENCY * factor + 1;
+        let cutoff = 0.5 / factor as f32;
+        let center = (len - 1) as f32 / 2.0;
+        let mut taps: Vec<f32> = (0..len)
+            .map(|i| {
+                let t = i as f32 - center;
+                let sinc = if t == 0.0 {
+                    2.0 * cutoff
+                } else {
+                    (2.0 * PI * cutoff * t).sin() / (PI * t)
+                };
+                let phase = 2.0 * PI * i as f32 / (len - 1) as f32;
+                sinc * (0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+            })
+            .collect();
+        let sum: f32 = taps.iter().sum();
+        taps.iter_mut().for_each(|tap| *tap /= sum);
+        taps
+    })
+}
+
+/// Runs a memoryless nonlinearity at 2x or 4x the sample rate so the
+/// harmonics it adds above Nyquist are filtered out instead of aliasing.
+/// A factor of 1 runs it directly, with no latency.
+#[derive(Debug, Clone)]
+pub struct Oversampler {
+    factor: usize,
+    /// Recent input samples, newest first
+    input: Vec<f32>,
+    /// Recent shaped samples at the oversampled rate, newest first
+    shaped: Vec<f32>,
+}
+
+impl Oversampler {
+    /// `factor` is 1, 2 or 4
+    pub fn new(factor: usize) -> Self {
+        let taps = if factor > 1 {
+            OVERSAMPLE_LATENCY * factor + 1
+        } else {
+            0
+        };
+        Self {
+            factor,
+            input: vec![0.0; OVERSAMPLE_LATENCY + 1],
+            shaped: vec![0.0; taps],
+        }
+    }
+
+    /// Shape one sample. Returns the shaped sample and the input delayed by
+    /// the same latency, for dry/wet mixing without comb filtering.
+    pub fn process(&mut self, x: f32, mut shape: impl FnMut(f32) -> f32) -> (f32, f32) {
+        if self.factor <= 1 {
+            return (shape(x), x);
+        }
+        let fir = oversample_fir(self.factor);
+        self.input.copy_within(..OVERSAMPLE_LATENCY, 1);
+        self.input[0] = x;
+
+        let mut wet = 0.0;
+        for phase in 0..self.factor {
+            // Polyphase interpolation: the zero-stuffed input through the FIR
+            let upsampled: f32 = self
+                .input
+                .iter()
+                .enumerate()
+                .filter_map(|(j, x)| fir.get(phase + self.factor * j).map(|h| h * x))
+                .sum::<f32>()
+                * self.factor as f32;
+            let last = self.shaped.len() - 1;
+            self.shaped.copy_within(..last, 1);
+            self.shaped[0] = shape(upsampled);
+            // Decimate on phase 0, which lines up with a whole input sample
+            if phase == 0 {
+                wet = fir.iter().zip(&self.shaped).map(|(h, s)| h * s).sum();
+            }
+        }
+        (wet, self.input[OVERSAMPLE_LATENCY])
+    }
+
+    /// Shape one sample at the original rate, with the same latency as
+    /// [`process`](Self::process) so switching between them doesn't shift
+    /// the signal. It aliases, but costs a fraction as much.
+    pub fn process_plain(&mut self, x: f32, mut shape: impl FnMut(f32) -> f32) -> (f32, f32) {
+        if self.factor <= 1 {
+            return (shape(x), x);
+        }
+        self.input.copy_within(..OVERSAMPLE_LATENCY, 1);
+        self.input[0] = x;
+        let delayed = self.input[OVERSAMPLE_LATENCY];
+        (shape(delayed), delayed)
+    }
+}
+
+/// Saturator state — a 4x [`Oversampler`] and a DC blocker for the tape
+/// curve's asymmetry
+#[derive(Debug, Clone)]
+pub struct SaturatorState {
+    oversampler: Oversampler,
+    dc_blocker: DcBlocker,
+}
+
+impl Default for SaturatorState {
+    fn default() -> Self {
+        Self {
+            oversampler: Oversampler::new(4),
+            dc_blocker: DcBlocker::new(44100.0),
+        }
+    }
+}
+
+impl SaturatorState {
+    /// Saturate one sample. The wet signal is divided by `sqrt(drive)` so
+    /// turning up the drive changes the tone more than the level.
+    /// `oversample` off runs the curve at the original rate (see
+    /// [`Oversampler::process_plain`]).
+    pub fn process(
+        &mut self,
+        x: f32,
+        drive: f32,
+        mix: f32,
+        curve: SaturationCurve,
+        oversample: bool,
+    ) -> f32 {
+        let shape = |x: f32| curve.shape(x * drive);
+        let (wet, dry) = if oversample {
+            self.oversampler.process(x, shape)
+        } else {
+            self.oversampler.process_plain(x, shape)
+        };
+        let wet = self.dc_blocker.process(wet / drive.sqrt());
+        dry * (1.0 - mix) + wet * mix
+    }
+}
+
+/// Memoryless curves of `distortion`, `fold`, `clip`, `wrap` and
+/// `bitcrush`'s quantizer, run by [`SignalNode::Waveshaper`] so they can be
+/// oversampled. `a` and `b` are the curve's two parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Waveshape {
+    /// `tanh(a * x)`, a = drive
+    Tanh,
+    /// Reflects the signal back into a..b
+    Fold,
+    /// `tanh(x / a) * a`, a = threshold
+    Clip,
+    /// Wraps the signal around into a..b
+    Wrap,
+    /// Rounds to steps of 2^-a, a = bits
+    Quantize,
+}
+
+impl Waveshape {
+    pub fn apply(self, x: f32, a: f32, b: f32) -> f32 {
+        match self {
+            Self::Tanh => (x * a.clamp(1.0, 100.0)).tanh(),
+            Self::Fold => crate::nodes::FoldNode::fold_value(x, a, b),
+            Self::Clip => {
+                let threshold = a.max(1e-6);
+                (x / threshold).tanh() * threshold
+            }
+            Self::Wrap => {
+                let range = b - a;
+                if range.abs() < 1e-10 {
+                    a
+                } else {
+                    (x - a).rem_euclid(range) + a
+                }
+            }
+            Self::Quantize => {
+                let levels = 2.0_f32.powf(a.clamp(1.0, 16.0));
+                (x * levels).round() / levels
+            }
+        }
+    }
+}
+
 /// SVF (State Variable Filter) state
 /// Chamberlin topology for multi-mode filtering
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SVFState {
     pub low: f32,  // Lowpass integrator state
     pub band: f32, // Bandpass integrator state
@@ -2517,7 +3218,7 @@ impl Default for SVFState {
 /// Biquad Filter state
 /// High-quality second-order IIR filter (uses `biquad` crate)
 /// Stores filter coefficients and internal state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BiquadState {
     pub x1: f32, // Previous input sample 1
     pub x2: f32, // Previous input sample 2
@@ -2547,7 +3248,7 @@ impl Default for BiquadState {
 }
 
 /// Envelope state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnvState {
     phase: RefCell<EnvPhase>,
     level: RefCell<f32>,
@@ -2555,14 +3256,14 @@ pub struct EnvState {
     release_start_level: RefCell<f32>, // Level when release phase began
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ADSRState {
     phase: RefCell<ADSRPhase>,
     level: f32,
     cycle_pos: f32, // Current position in cycle (0.0 to 1.0)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ADSRPhase {
     Attack,
     Decay,
@@ -2580,14 +3281,14 @@ impl Default for ADSRState {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ADState {
     phase: RefCell<ADPhase>,
     level: f32,
     cycle_pos: f32, // Current position in cycle (0.0 to 1.0)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ADPhase {
     Attack,
     Decay,
@@ -2603,7 +3304,7 @@ impl Default for ADState {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum EnvPhase {
     Idle,
     Attack,
@@ -2708,6 +3409,9 @@ pub struct DattorroState {
     // Modulation LFOs
     lfo_phase: f32,
 
+    // Pitch shifter in the tank feedback (off unless built with_shimmer)
+    shimmer: ShimmerState,
+
     sample_rate: f32,
 }
 
@@ -2774,9 +3478,20 @@ impl DattorroState {
             right_lpf_state: 0.0,
 
             lfo_phase: 0.0,
+            shimmer: ShimmerState::default(),
             sample_rate: sr,
         }
     }
+
+    /// Pitch-shift the tank feedback by `semitones`, blended in by `amount`
+    /// (0-1), so each pass round the tank climbs another interval. Keeps the
+    /// shifter's buffer when the settings are unchanged.
+    pub fn with_shimmer(mut self, amount: f32, semitones: f32) -> Self {
+        if (amount, semitones) != (self.shimmer.amount, self.shimmer.semitones) {
+            self.shimmer = ShimmerState::new(self.sample_rate, amount, semitones);
+        }
+        self
+    }
 }
 
 impl Default for DattorroState {
@@ -2785,6 +3500,164 @@ impl Default for DattorroState {
     }
 }
 
+/// Shimmer pitch shifter: two read heads sweep a 50ms window at the
+/// shifted speed, with Hann crossfades so each head fades out before it
+/// jumps back
+#[derive(Debug, Clone, Default)]
+pub struct ShimmerState {
+    amount: f32,
+    semitones: f32,
+    buffer: Vec<f32>,
+    write_idx: usize,
+    phase: f32,
+}
+
+impl ShimmerState {
+    pub fn new(sample_rate: f32, amount: f32, semitones: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        let window = if amount > 0.0 { (sample_rate * 0.05) as usize } else { 0 };
+        Self {
+            amount,
+            semitones,
+            buffer: vec![0.0; window],
+            write_idx: 0,
+            phase: 0.0,
+        }
+    }
+
+    /// Blend the pitch-shifted signal into `input`
+    pub fn process(&mut self, input: f32) -> f32 {
+        let len = self.buffer.len();
+        if len < 2 {
+            return input;
+        }
+        self.buffer[self.write_idx] = input;
+
+        let window = (len - 1) as f32;
+        let mut shifted = 0.0;
+        for head in [self.phase, (self.phase + 0.5) % 1.0] {
+            // Delay runs from the whole window down to zero (shifting up) or
+            // back up (shifting down) as the head's phase advances
+            let read = (self.write_idx as f32 - (1.0 - head) * window).rem_euclid(len as f32);
+            let idx = read as usize % len;
+            let frac = read - read.floor();
+            let sample = self.buffer[idx] * (1.0 - frac) + self.buffer[(idx + 1) % len] * frac;
+            let fade = (head * std::f32::consts::PI).sin();
+            shifted += sample * fade * fade;
+        }
+
+        let ratio = 2f32.powf(self.semitones / 12.0);
+        self.phase = (self.phase + (ratio - 1.0) / window).rem_euclid(1.0);
+        self.write_idx = (self.write_idx + 1) % len;
+        input + (shifted - input) * self.amount
+    }
+}
+
+/// Reverse State: two chunk-long halves, one being written while the other
+/// is read backwards, with short fades where the read jumps
+#[derive(Debug, Clone)]
+pub struct ReverseState {
+    buffer: Vec<f32>,
+    chunk: usize,
+    pos: usize,
+    /// Which half is being written
+    half: usize,
+    fade: usize,
+}
+
+impl ReverseState {
+    pub fn new(sample_rate: f32, chunk_seconds: f32) -> Self {
+        let chunk = ((sample_rate * chunk_seconds) as usize).max(2);
+        Self {
+            buffer: vec![0.0; chunk * 2],
+            chunk,
+            pos: 0,
+            half: 0,
+            fade: ((sample_rate * 0.005) as usize).clamp(1, chunk / 2),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.buffer.iter_mut().for_each(|s| *s = 0.0);
+        self.pos = 0;
+        self.half = 0;
+    }
+
+    pub fn process(&mut self, input: f32) -> f32 {
+        let chunk = self.chunk;
+        self.buffer[self.half * chunk + self.pos] = input;
+        let read = (1 - self.half) * chunk + (chunk - 1 - self.pos);
+        let edge = self.pos.min(chunk - 1 - self.pos);
+        let gain = (edge as f32 / self.fade as f32).min(1.0);
+        let output = self.buffer[read] * gain;
+
+        self.pos += 1;
+        if self.pos == chunk {
+            self.pos = 0;
+            self.half = 1 - self.half;
+        }
+        output
+    }
+}
+
+/// What a `Varispeed` node does while its pattern is on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VarispeedMode {
+    /// Slow to a halt over each event, like cutting a turntable's power
+    TapeStop,
+    /// Loop the first `1/division` of a cycle of each event
+    Stutter { division: f64 },
+    /// Repeat the `1/n` of a cycle just before each event, `n` being the
+    /// event's value; each repeat is `decay` times as loud as the last and
+    /// plays `pitch` times as fast
+    BeatRepeat { decay: f32, pitch: f64 },
+}
+
+/// Varispeed State: a recording of the last few seconds of input, read back
+/// a (fractional) number of samples behind the write head
+#[derive(Debug, Clone)]
+pub struct VarispeedState {
+    buffer: Vec<f32>,
+    write_idx: usize,
+}
+
+impl VarispeedState {
+    pub fn new(sample_rate: f32) -> Self {
+        Self {
+            buffer: vec![0.0; (sample_rate * 10.0) as usize], // 10 seconds max
+            write_idx: 0,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.buffer.iter_mut().for_each(|s| *s = 0.0);
+        self.write_idx = 0;
+    }
+
+    /// Record `input` and read back from `delay` samples ago (0 is `input`)
+    pub fn process(&mut self, input: f32, delay: f64) -> f32 {
+        let len = self.buffer.len();
+        self.buffer[self.write_idx] = input;
+        let delay = delay.clamp(0.0, (len - 2) as f64);
+        let whole = delay.floor() as usize;
+        let frac = (delay - whole as f64) as f32;
+        let a = self.buffer[(self.write_idx + len - whole) % len];
+        let b = self.buffer[(self.write_idx + len - whole - 1) % len];
+        self.write_idx = (self.write_idx + 1) % len;
+        a + (b - a) * frac
+    }
+}
+
+/// One-pole coefficient for the ping-pong feedback lowpass; 20 kHz and
+/// up leaves the feedback untouched
+fn pingpong_filter_coeff(cutoff: f32, sample_rate: f32) -> f32 {
+    if cutoff >= 20000.0 {
+        1.0
+    } else {
+        1.0 - (-std::f32::consts::TAU * cutoff.min(sample_rate * 0.5) / sample_rate).exp()
+    }
+}
+
 /// Tape Delay State
 #[derive(Debug, Clone)]
 pub struct TapeDelayState {
@@ -2889,7 +3762,7 @@ impl Default for FlangerState {
 }
 
 /// Moog Ladder Filter state
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoogLadderState {
     stage1: f32, // First filter stage
     stage2: f32, // Second filter stage
@@ -2966,7 +3839,7 @@ fn next_default_noise_seed() -> u64 {
 /// never calls `rand::thread_rng()`, so the render loop pays no TLS lookup or reseed
 /// check (improvement-plan P4 / rt F-11). Same seed → same stream (determinism);
 /// distinct seeds avalanche to decorrelated streams (independence).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct NoiseRng {
     state: u32,
 }
@@ -3012,7 +3885,7 @@ impl NoiseRng {
 
 /// Pink noise state (Voss-McCartney algorithm)
 /// Uses multiple octave bins updated at different rates
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PinkNoiseState {
     bins: [f32; 16],   // 16 octave bins for quality pink noise
     counter: u32,      // Sample counter for bin update decisions
@@ -3051,7 +3924,7 @@ impl Default for PinkNoiseState {
 
 /// Brown noise state (random walk / Brownian motion)
 /// Uses leaky integrator to prevent DC drift
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrownNoiseState {
     accumulator: f32,         // Current accumulated value
     pub(crate) rng: NoiseRng, // Per-node PRNG (seeded once; no thread_rng on the hot path)
@@ -3083,7 +3956,7 @@ impl Default for BrownNoiseState {
 
 /// Impulse generator state
 /// Generates single-sample impulses at specified frequency
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImpulseState {
     phase: f32, // Current phase position [0, 1)
 }
@@ -3103,7 +3976,7 @@ impl Default for ImpulseState {
 
 /// Wavetable oscillator state
 /// Reads through a stored waveform at variable speeds for different pitches
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WavetableState {
     table: Vec<f32>, // Wavetable data (one cycle)
     phase: f32,      // Current phase position [0, 1)
@@ -3296,7 +4169,7 @@ impl Default for GranularState {
 
 /// Karplus-Strong string synthesis state
 /// Physical modeling of plucked strings using delay line + lowpass filter
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KarplusStrongState {
     delay_line: Vec<f32>, // Circular buffer for string simulation
     write_pos: usize,     // Current write position
@@ -3382,7 +4255,7 @@ impl Default for KarplusStrongState {
 /// Digital Waveguide Physical Modeling state
 /// Uses bidirectional delay lines to simulate wave propagation in physical media
 /// More sophisticated than Karplus-Strong, can model various acoustic instruments
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WaveguideState {
     forward_delay: Vec<f32>,  // Forward-propagating wave
     backward_delay: Vec<f32>, // Backward-propagating wave
@@ -3527,7 +4400,7 @@ impl Default for WaveguideState {
 /// - /i/ (beet):   F1=270, F2=2290, F3=3010
 /// - /o/ (boat):   F1=570, F2=840,  F3=2410
 /// - /u/ (boot):   F1=300, F2=870,  F3=2240
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FormantState {
     /// State variable filter states for each formant
     state1: FilterState,
@@ -3685,7 +4558,7 @@ impl Default for FormantState {
 ///
 /// Classic additive synthesis: fundamental + harmonics weighted by amplitudes
 /// Example: additive 440 "1.0 0.5 0.25" creates 440Hz + 880Hz + 1320Hz
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdditiveState {
     phase: f32,       // Phase accumulator [0, 1)
     sample_rate: f32, // Sample rate for phase increment calculation
@@ -3953,7 +4826,7 @@ impl Default for PitchShifterState {
 
 /// Lag (exponential slew limiter) state
 /// Smooths abrupt changes with exponential approach
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LagState {
     previous_output: f32, // Previous smoothed output value
 }
@@ -3972,6 +4845,13 @@ impl Default for LagState {
     }
 }
 
+/// Window over which `AutoGain` measures its input's RMS
+const AUTOGAIN_WINDOW_SECS: f32 = 0.3;
+/// Input RMS (-60 dBFS) below which `AutoGain` holds its gain
+const AUTOGAIN_GATE: f32 = 0.001;
+/// Largest boost (and, inverted, cut) `AutoGain` applies: +/-18 dB
+const AUTOGAIN_RANGE: f32 = 8.0;
+
 /// Tap State - Records signal to buffer for debugging
 #[derive(Debug, Clone)]
 pub struct TapState {
@@ -3994,6 +4874,18 @@ impl TapState {
         }
     }
 
+    /// Recorder that is analysed in memory and never written to disk
+    /// (used by render assertions). The buffer grows as samples arrive.
+    pub fn in_memory(max_secs: f32, sample_rate: f32) -> Self {
+        Self {
+            buffer: Vec::new(),
+            filename: String::new(),
+            max_samples: (max_secs * sample_rate) as usize,
+            sample_rate,
+            enabled: true,
+        }
+    }
+
     /// Record a sample (if still recording)
     pub fn record(&mut self, sample: f32) {
         if self.enabled && self.buffer.len() < self.max_samples {
@@ -4036,7 +4928,7 @@ impl TapState {
 
 /// XLine (exponential envelope) state
 /// Generates exponential ramp from start to end over duration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct XLineState {
     elapsed_samples: usize, // Number of samples generated so far
 }
@@ -4054,7 +4946,7 @@ impl Default for XLineState {
 }
 
 /// ASR envelope phase
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ASRPhase {
     Idle,    // Envelope at 0, waiting for gate
     Attack,  // Rising from 0 to 1
@@ -4064,7 +4956,7 @@ pub enum ASRPhase {
 
 /// ASR (Attack-Sustain-Release) envelope state
 /// Gate-based envelope: attacks when gate goes high, sustains while high, releases when gate goes low
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ASRState {
     phase: RefCell<ASRPhase>,
     current_level: f32, // Current envelope output [0, 1]
@@ -4358,6 +5250,21 @@ impl ConvolutionState {
         }
     }
 
+    /// Convolve with a given impulse response (an HRIR for `spatial:
+    /// binaural`) instead of the built-in room
+    pub fn with_impulse_response(impulse_response: Vec<f32>) -> Self {
+        let impulse_response = if impulse_response.is_empty() {
+            vec![0.0]
+        } else {
+            impulse_response
+        };
+        Self {
+            input_buffer: vec![0.0; impulse_response.len()],
+            buffer_index: 0,
+            impulse_response,
+        }
+    }
+
     pub fn process(&mut self, input: f32) -> f32 {
         // Store input in circular buffer
         self.input_buffer[self.buffer_index] = input;
@@ -4656,6 +5563,54 @@ impl OutputMixMode {
     }
 }
 
+/// What the master does to peaks above the limiter ceiling
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MasterClip {
+    /// Brick-wall clamp at the ceiling (default)
+    #[default]
+    Hard,
+    /// Untouched up to half the ceiling, then a smooth knee into it
+    Soft,
+    /// `ceiling * tanh(x / ceiling)`: gentle saturation at every level
+    Tanh,
+    /// No clipping at all (NaN/Inf are still removed)
+    Off,
+}
+
+impl MasterClip {
+    /// Parse from string (config, `--clip`)
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "hard" => Some(MasterClip::Hard),
+            "soft" => Some(MasterClip::Soft),
+            "tanh" => Some(MasterClip::Tanh),
+            "off" | "none" => Some(MasterClip::Off),
+            _ => None,
+        }
+    }
+
+    /// Apply to one sample with the given ceiling
+    #[inline]
+    pub fn apply(self, x: f32, ceiling: f32) -> f32 {
+        match self {
+            MasterClip::Hard => x.clamp(-ceiling, ceiling),
+            MasterClip::Soft => {
+                let knee = ceiling * 0.5;
+                let level = x.abs();
+                if level <= knee {
+                    x
+                } else {
+                    // Slope 1 at the knee, approaching the ceiling asymptotically
+                    let range = ceiling - knee;
+                    (knee + range * ((level - knee) / range).tanh()).copysign(x)
+                }
+            }
+            MasterClip::Tanh => ceiling * (x / ceiling).tanh(),
+            MasterClip::Off => x,
+        }
+    }
+}
+
 /// Request for parallel bus synthesis
 /// Collects all parameters needed to synthesize a bus buffer independently
 #[derive(Clone)]
@@ -4745,9 +5700,9 @@ fn eval_node_isolated(
             // >= 1000: Absolute MIDI note (subtract 1000, convert to Hz)
             // < 1000: Relative semitone offset from base frequency
             let freq_val = if *semitone_offset >= 1000.0 {
-                // Absolute MIDI: convert to Hz using A4=440Hz as reference
+                // Absolute MIDI: convert to Hz against the A4 reference
                 let midi = *semitone_offset - 1000.0;
-                440.0 * 2.0_f32.powf((midi - 69.0) / 12.0)
+                crate::pitch::midi_to_hz(midi as f64) as f32
             } else if *semitone_offset != 0.0 {
                 // Relative: apply semitone offset to base frequency
                 base_freq * 2.0_f32.powf(*semitone_offset / 12.0)
@@ -4756,30 +5711,13 @@ fn eval_node_isolated(
             };
 
             // Generate sample based on waveform
-            let phase_val = *phase.borrow();
-            let sample = match waveform {
-                Waveform::Sine => (2.0 * PI * phase_val).sin(),
-                Waveform::Saw => 2.0 * phase_val - 1.0,
-                Waveform::Square => {
-                    if phase_val < 0.5 {
-                        1.0
-                    } else {
-                        -1.0
-                    }
-                }
-                Waveform::Triangle => {
-                    if phase_val < 0.5 {
-                        4.0 * phase_val - 1.0
-                    } else {
-                        -4.0 * phase_val + 3.0
-                    }
-                }
-            };
+            let phase_val = *phase.borrow() as f32;
+            let sample = waveform.sample(phase_val, freq_val / sample_rate);
 
             // Update phase for next sample
             {
                 let mut p = phase.borrow_mut();
-                *p += freq_val / sample_rate;
+                *p += freq_val as f64 / sample_rate as f64;
                 if *p >= 1.0 {
                     *p -= 1.0;
                 }
@@ -5158,6 +6096,9 @@ pub static ENV_FLAG_READS: std::sync::atomic::AtomicU64 = std::sync::atomic::Ato
 /// `O(N^2)` for big patches; the fix caches it and rebuilds only when the graph
 /// structure changes (compile / hot-reload). The stress test asserts this stays
 /// FLAT across steady render buffers — the plan is compiled once, not per buffer.
+/// Source of [`UnifiedSignalGraph::graph_id`]s
+static NEXT_GRAPH_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
 pub static DAG_PLAN_BUILDS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
 
 /// Number of fresh per-node scratch buffers heap-allocated by `process_buffer_dag`
@@ -5271,6 +6212,21 @@ struct DagPlan {
     fingerprint: u64,
 }
 
+/// The parts of a graph the next graph plans its bus state transfer against:
+/// bus, morph and output roots with their definition hashes, and each node's
+/// variant and inputs. Kept on the control thread after the graph itself has
+/// gone to the render thread (see [`UnifiedSignalGraph::plan_bus_state_transfer`]).
+#[derive(Debug, Clone, Default)]
+pub struct GraphShape {
+    graph_id: u64,
+    /// Bus name -> (definition hash, root node)
+    buses: HashMap<String, (u64, usize)>,
+    continued_buses: Vec<(String, u64, usize)>,
+    /// (definition hash, root node) of `out`
+    output: Option<(u64, usize)>,
+    nodes: Vec<Option<(std::mem::Discriminant<SignalNode>, Vec<usize>)>>,
+}
+
 /// The unified signal graph that processes everything
 pub struct UnifiedSignalGraph {
     /// All nodes in the graph (Rc for cheap cloning - eliminates deep clone overhead)
@@ -5285,6 +6241,43 @@ pub struct UnifiedSignalGraph {
     /// Multi-output: channel number -> node ID
     outputs: HashMap<usize, NodeId>,
 
+    /// Headphone cue output (`cue $ ...`, `precue ~bus`), never mixed into the
+    /// main output
+    cue_output: Option<NodeId>,
+
+    /// Cue output of the last rendered buffer (mono)
+    cue_buffer: Vec<f32>,
+
+    /// Control voltage outputs (`cvout`, `gateout`): hardware channel
+    /// (1-indexed) -> node whose output is in volts
+    cv_outputs: BTreeMap<usize, NodeId>,
+
+    /// Volts of each control voltage output over the last rendered buffer
+    cv_buffers: BTreeMap<usize, Vec<f32>>,
+
+    /// Content hash of each bus definition (set by the compiler).
+    /// Buses whose hash is unchanged across a hot-swap keep their node state.
+    bus_hashes: HashMap<String, u64>,
+
+    /// Inline definitions that continue a bus: (bus, definition hash, node).
+    /// Both sides of a `morph` are versions of the bus they're assigned to,
+    /// so either one takes over that version's state across a hot-swap.
+    continued_buses: Vec<(String, u64, NodeId)>,
+
+    /// Content hash of the `out $ ...` definition (set by the compiler)
+    output_hash: Option<u64>,
+
+    /// Identifies this graph (and its clones) to [`GraphShape`]s taken from it
+    graph_id: u64,
+
+    /// Bus state to carry across the next hot-swap: the id of the graph it
+    /// comes from and (new node, old node) pairs. Planned on the control
+    /// thread by [`Self::plan_bus_state_transfer`]; the swap only copies.
+    bus_state_plan: Option<(u64, Vec<(usize, usize)>)>,
+
+    /// `assert` statements, checked against their recordings at render end
+    assertions: Vec<crate::render_assertions::RenderAssertion>,
+
     /// Hushed (silenced) output channels
     hushed_channels: std::collections::HashSet<usize>,
 
@@ -5308,6 +6301,10 @@ pub struct UnifiedSignalGraph {
     /// Cycles per second (tempo)
     pub cps: f32,
 
+    /// The tempo again, as f32 bits, for patterns that read it as they play
+    /// (see [`Self::shift_patterns`])
+    shared_cps: Arc<std::sync::atomic::AtomicU32>,
+
     /// Buffer size for audio processing (samples per buffer)
     /// Default is 512, can be set via "buffer: 1024" in code
     pub buffer_size: usize,
@@ -5418,6 +6415,9 @@ pub struct UnifiedSignalGraph {
     /// Replaces per-sample HashMap rebuilding with direct array indexing
     voice_buffers: VoiceBuffers,
 
+    /// Orbit number -> the `orbit N` node its voices play through
+    orbit_nodes: HashMap<usize, NodeId>,
+
     /// Current sample index within the buffer (for voice_buffers lookup)
     current_sample_idx: usize,
 
@@ -5502,6 +6502,13 @@ pub struct UnifiedSignalGraph {
     /// Set to 1.0 or above to disable
     pub master_limiter_ceiling: f32,
 
+    /// How the master limiter clips
+    master_clip: MasterClip,
+
+    /// Optional DC blocker on the master output (left, right), applied before
+    /// the limiter. See [`Self::set_master_dc_blocker`].
+    master_dc_blocker: Option<[DcBlocker; 2]>,
+
     /// When set, [`process_buffer_dag`](Self::process_buffer_dag) records the raw
     /// pre-sanitisation signal metrics into [`Self::last_raw_probe`] just before the
     /// Phase 4b–4d limiter/flush. Off by default so the production render path pays
@@ -5529,6 +6536,34 @@ pub struct UnifiedSignalGraph {
     /// it once enabled. See [`Self::set_preserve_voices_on_swap`].
     preserve_voices_on_swap: bool,
 
+    /// Fidelity under CPU pressure, set by the editor's synth thread. See
+    /// [`Self::set_quality`]
+    quality: Quality,
+
+    /// Where triggered events are reported for the editor's event pane. Carried
+    /// across swaps like `preserve_voices_on_swap`. See [`Self::set_event_log`].
+    event_log: Option<EventLogSender>,
+
+    /// Next file of each round-robin folder (`s "hh:rr"`), by folder name.
+    /// Carried across swaps so live edits don't restart the rotation.
+    round_robin: HashMap<String, usize>,
+
+    /// Velocity layers of each sample folder triggered so far (`None` when not
+    /// layered), from [`SampleBank::velocity_layers`]. Rescanned after a swap.
+    velocity_layers: HashMap<String, Option<Vec<f32>>>,
+
+    /// Per-bus peak meters and the node of each metered bus. Clones share the
+    /// meters. See [`Self::enable_bus_meters`].
+    bus_meters: Option<(Arc<BusMeters>, Vec<NodeId>)>,
+
+    /// Band levels, onsets and cycle phase for `visuals:`. Clones share the
+    /// feed. See [`Self::enable_visuals`].
+    visuals: Option<crate::visuals::VisualsAnalyzer>,
+
+    /// Where this graph's `take` / `after` patterns count from. Set when the
+    /// graph is swapped in or cued; clones share it.
+    count_origin: crate::pattern_ops_extended::CountOrigin,
+
     /// Previous buffer tail (stereo interleaved) for zero-crossing crossfade.
     /// Stores the last N stereo sample pairs from the previous buffer to smooth
     /// discontinuities at buffer boundaries.
@@ -5601,6 +6636,16 @@ impl Clone for UnifiedSignalGraph {
             buses: self.buses.clone(),
             output: self.output,
             outputs: self.outputs.clone(),
+            cue_output: self.cue_output,
+            cue_buffer: Vec::new(),
+            cv_outputs: self.cv_outputs.clone(),
+            cv_buffers: BTreeMap::new(),
+            bus_hashes: self.bus_hashes.clone(),
+            continued_buses: self.continued_buses.clone(),
+            output_hash: self.output_hash,
+            graph_id: self.graph_id,
+            bus_state_plan: self.bus_state_plan.clone(),
+            assertions: self.assertions.clone(),
             hushed_channels: self.hushed_channels.clone(),
             output_mix_mode: self.output_mix_mode,
             sample_rate: self.sample_rate,
@@ -5608,6 +6653,7 @@ impl Clone for UnifiedSignalGraph {
             cycle_offset: self.cycle_offset,
             use_wall_clock: self.use_wall_clock,
             cps: self.cps,
+            shared_cps: Arc::clone(&self.shared_cps),
             cached_cycle_position: self.cached_cycle_position,
             next_node_id: self.next_node_id,
             value_cache: HashMap::new(), // Fresh cache for cloned instance
@@ -5625,10 +6671,11 @@ impl Clone for UnifiedSignalGraph {
             dag_scratch_pool: Vec::new(),  // Fresh pool for the cloned instance
             dag_current_buffers: HashMap::new(),
             sample_bank: RefCell::new(self.sample_bank.borrow().clone()), // Clone loaded samples (cheap Arc increment)
-            voice_manager: RefCell::new(VoiceManager::new()),
+            voice_manager: RefCell::new(VoiceManager::with_sample_rate(self.sample_rate)),
             voice_output_cache: HashMap::new(), // Fresh cache
             voice_output_cache_stereo: HashMap::new(), // Fresh stereo cache
             voice_buffers: VoiceBuffers::default(), // Fresh Vec-based buffers
+            orbit_nodes: self.orbit_nodes.clone(),
             current_sample_idx: 0,
             current_dag_node_id: None,
             eval_call_stack: std::collections::HashSet::new(),
@@ -5648,10 +6695,19 @@ impl Clone for UnifiedSignalGraph {
             shared_state: self.shared_state.clone(),
             bypass_sequential_effects: self.bypass_sequential_effects,
             master_limiter_ceiling: self.master_limiter_ceiling,
+            master_clip: self.master_clip,
+            master_dc_blocker: self.master_dc_blocker,
             raw_probe_enabled: self.raw_probe_enabled,
             last_raw_probe: RawSignalProbe::default(),
             node_state_sanitize: self.node_state_sanitize,
             preserve_voices_on_swap: self.preserve_voices_on_swap,
+            quality: self.quality,
+            event_log: self.event_log.clone(),
+            round_robin: self.round_robin.clone(),
+            velocity_layers: self.velocity_layers.clone(),
+            bus_meters: self.bus_meters.clone(),
+            visuals: self.visuals.clone(),
+            count_origin: self.count_origin.clone(),
             prev_buffer_tail: Vec::new(),
             // Fresh per-node white-noise PRNG map; lazily reseeded on first eval. The base
             // seed carries so an explicitly-seeded graph stays reproducible across clones.
@@ -5691,12 +6747,25 @@ impl Drop for UnifiedSignalGraph {
     }
 }
 
+/// Whether `node` keeps its state behind an `Arc`, which a clone of the node
+/// shares instead of copying
+pub fn node_shares_state(node: &SignalNode) -> bool {
+    matches!(
+        node,
+        SignalNode::FundspUnit { .. }
+            | SignalNode::UserNode { .. }
+            | SignalNode::ExternalFx { .. }
+            | SignalNode::Tap { .. }
+            | SignalNode::SignalAsPattern { .. }
+    )
+}
+
 /// Convert MIDI note number to frequency in Hz
-/// MIDI note 69 (A4) = 440 Hz
+/// MIDI note 69 (A4) = 440 Hz unless the reference is changed (see [`crate::pitch`])
 /// Each semitone is a factor of 2^(1/12)
 #[inline]
 pub fn midi_note_to_freq(note: u8) -> f32 {
-    440.0 * 2.0f32.powf((note as f32 - 69.0) / 12.0)
+    crate::pitch::midi_to_hz(note as f64) as f32
 }
 
 impl UnifiedSignalGraph {
@@ -5706,6 +6775,16 @@ impl UnifiedSignalGraph {
             buses: HashMap::new(),
             output: None,
             outputs: HashMap::new(),
+            cue_output: None,
+            cue_buffer: Vec::new(),
+            cv_outputs: BTreeMap::new(),
+            cv_buffers: BTreeMap::new(),
+            bus_hashes: HashMap::new(),
+            continued_buses: Vec::new(),
+            output_hash: None,
+            graph_id: NEXT_GRAPH_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            bus_state_plan: None,
+            assertions: Vec::new(),
             hushed_channels: std::collections::HashSet::new(),
             output_mix_mode: OutputMixMode::default(),
             sample_rate,
@@ -5714,6 +6793,7 @@ impl UnifiedSignalGraph {
             use_wall_clock: false, // Default to sample-based for offline rendering
             cps: 0.5,              // Default 0.5 cycles per second
             buffer_size: 512,      // Default buffer size
+            shared_cps: Arc::new(std::sync::atomic::AtomicU32::new(0.5f32.to_bits())),
             cached_cycle_position: 0.0,
             next_node_id: 0,
             value_cache: HashMap::new(),
@@ -5730,11 +6810,12 @@ impl UnifiedSignalGraph {
             dag_plan: None,                      // Compiled lazily on first render
             dag_scratch_pool: Vec::new(),
             dag_current_buffers: HashMap::new(),
-            sample_bank: RefCell::new(SampleBank::new()),
-            voice_manager: RefCell::new(VoiceManager::new()),
+            sample_bank: RefCell::new(SampleBank::with_sample_rate(sample_rate)),
+            voice_manager: RefCell::new(VoiceManager::with_sample_rate(sample_rate)),
             voice_output_cache: HashMap::new(),
             voice_output_cache_stereo: HashMap::new(),
             voice_buffers: VoiceBuffers::default(),
+            orbit_nodes: HashMap::new(),
             current_sample_idx: 0,
             current_dag_node_id: None,
             eval_call_stack: std::collections::HashSet::new(),
@@ -5752,12 +6833,21 @@ impl UnifiedSignalGraph {
             shared_state: None, // Disabled by default
             bypass_sequential_effects: false, // Normal mode by default
             master_limiter_ceiling: 0.95, // Default: -0.4dB headroom for safety
+            master_clip: MasterClip::Hard,
+            master_dc_blocker: None,
             raw_probe_enabled: false, // Off by default: zero overhead on the render path
             last_raw_probe: RawSignalProbe::default(),
             node_state_sanitize: true, // The F-6 fix is on by default
             // G7: default from PHONON_PRESERVE_VOICES so a live user can opt in
             // without a code change; unset ⇒ false ⇒ exact current fade behavior.
             preserve_voices_on_swap: read_env_flag("PHONON_PRESERVE_VOICES"),
+            quality: Quality::Full,
+            event_log: None,
+            round_robin: HashMap::new(),
+            velocity_layers: HashMap::new(),
+            bus_meters: None,
+            visuals: None,
+            count_origin: Default::default(),
             prev_buffer_tail: Vec::new(),
             white_noise_rng: RefCell::new(HashMap::new()),
             noise_seed_base: None,
@@ -5783,6 +6873,8 @@ impl UnifiedSignalGraph {
     /// accumulated in `cached_cycle_position`, so it is already continuous across a
     /// tempo change and only the per-sample increment changes; no rebase is needed.
     pub fn set_cps(&mut self, cps: f32) {
+        self.shared_cps
+            .store(cps.to_bits(), std::sync::atomic::Ordering::Relaxed);
         if (self.cps - cps).abs() < 1e-9 {
             self.cps = cps;
             return;
@@ -5797,8 +6889,81 @@ impl UnifiedSignalGraph {
         self.cps = cps;
     }
 
-    pub fn get_cps(&self) -> f32 {
-        self.cps
+    pub fn get_cps(&self) -> f32 {
+        self.cps
+    }
+
+    /// Move the events of every pattern node from `first` on by `seconds`
+    /// (a bus's `# latency`), at whatever the tempo is as they play.
+    /// Returns how many nodes moved
+    pub fn shift_patterns(&mut self, first: NodeId, seconds: f64) -> usize {
+        let ids: Vec<usize> = (first.0..self.nodes.len()).collect();
+        self.shift_pattern_nodes(&ids, &Shift::Seconds(seconds))
+    }
+
+    /// Let `nudge` move the events of bus `name` while it plays (the
+    /// editor's `:nudge`). The bus's own nodes are moved, up to the other
+    /// buses it reads. Returns how many nodes will move
+    pub fn nudge_bus(&mut self, name: &str, nudge: Arc<Nudge>) -> usize {
+        let Some(root) = self.buses.get(name).map(|id| id.0) else {
+            return 0;
+        };
+        let bus_roots: std::collections::HashSet<usize> =
+            self.buses.values().map(|id| id.0).collect();
+        let mut visited = std::collections::HashSet::new();
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if let Some(Some(node)) = self.nodes.get(id) {
+                stack.extend(
+                    self.get_all_node_inputs(node)
+                        .into_iter()
+                        .filter(|input| !bus_roots.contains(input)),
+                );
+            }
+        }
+
+        let ids: Vec<usize> = visited.into_iter().collect();
+        self.shift_pattern_nodes(&ids, &Shift::Nudge(nudge))
+    }
+
+    /// Move the events of every pattern node in `ids` by `shift`. Returns
+    /// how many nodes moved
+    fn shift_pattern_nodes(&mut self, ids: &[usize], shift: &Shift) -> usize {
+        let cps = &self.shared_cps;
+        let mut shifted = 0;
+        for &id in ids {
+            let Some(Some(node)) = self.nodes.get_mut(id) else {
+                continue;
+            };
+            match Rc::make_mut(node) {
+                SignalNode::Pattern { pattern, .. }
+                | SignalNode::Sample { pattern, .. }
+                | SignalNode::SynthPattern { pattern, .. }
+                | SignalNode::EnvelopePattern { pattern, .. }
+                | SignalNode::ScaleQuantize { pattern, .. } => {
+                    *pattern = shift.apply(pattern.clone(), Arc::clone(cps));
+                }
+                SignalNode::StructuredSignal {
+                    bool_pattern: pattern,
+                    ..
+                }
+                | SignalNode::TriggeredAR { pattern, .. }
+                | SignalNode::TriggeredADSR { pattern, .. }
+                | SignalNode::PatternGate { pattern, .. }
+                | SignalNode::PatternTrigger { pattern, .. } => {
+                    *pattern = shift.apply(pattern.clone(), Arc::clone(cps));
+                }
+                SignalNode::PatternEvaluator { pattern } => {
+                    *pattern = shift.apply(pattern.clone(), Arc::clone(cps));
+                }
+                _ => continue,
+            }
+            shifted += 1;
+        }
+        shifted
     }
 
     /// Fix the base seed for white-noise nodes, making their per-node PRNG streams
@@ -5841,6 +7006,26 @@ impl UnifiedSignalGraph {
         self.bypass_sequential_effects = bypass;
     }
 
+    /// Enable/disable the DC blocker on the master output, applied before the
+    /// limiter (off by default). Enabling it starts from a fresh filter state.
+    pub fn set_master_dc_blocker(&mut self, enabled: bool) {
+        self.master_dc_blocker = enabled.then(|| [DcBlocker::new(self.sample_rate); 2]);
+    }
+
+    /// Whether the master DC blocker is on
+    pub fn master_dc_blocker(&self) -> bool {
+        self.master_dc_blocker.is_some()
+    }
+
+    /// How the master limiter clips (default [`MasterClip::Hard`])
+    pub fn set_master_clip(&mut self, clip: MasterClip) {
+        self.master_clip = clip;
+    }
+
+    pub fn master_clip(&self) -> MasterClip {
+        self.master_clip
+    }
+
     /// Set master limiter ceiling (0.0 to 1.0)
     /// This is a safety limiter applied to all output to prevent clipping
     /// Default: 0.95 (-0.4dB headroom)
@@ -5939,6 +7124,28 @@ impl UnifiedSignalGraph {
                 *state = MoogLadderState::default();
             }
 
+            SignalNode::TiltEq { state, .. } => state.reset(),
+
+            SignalNode::Reverse { state, .. } => state.reset(),
+
+            SignalNode::Varispeed { state, .. } => state.reset(),
+
+            SignalNode::Saturate { state, .. } => {
+                *state = SaturatorState::default();
+            }
+
+            SignalNode::Waveshaper { oversampler, .. } => {
+                *oversampler = Oversampler::new(oversampler.factor);
+            }
+
+            SignalNode::SyncOscillator { state, .. } => {
+                *state = SyncOscState::default();
+            }
+
+            SignalNode::Lfo { state, .. } => {
+                *state = LfoState::default();
+            }
+
             SignalNode::ParametricEQ { state, .. } => {
                 state.low_band = FilterState::default();
                 state.mid_band = FilterState::default();
@@ -5970,10 +7177,11 @@ impl UnifiedSignalGraph {
                 *write_pos = 0;
             }
 
-            SignalNode::PingPongDelay { buffer_l, buffer_r, write_idx, .. } => {
+            SignalNode::PingPongDelay { buffer_l, buffer_r, write_idx, lpf_state, .. } => {
                 buffer_l.iter_mut().for_each(|s| *s = 0.0);
                 buffer_r.iter_mut().for_each(|s| *s = 0.0);
                 *write_idx = 0;
+                *lpf_state = (0.0, 0.0);
             }
 
             SignalNode::TapeDelay { state, .. } => {
@@ -5991,7 +7199,8 @@ impl UnifiedSignalGraph {
 
             SignalNode::DattorroReverb { state, .. } => {
                 let dsr = state.sample_rate;
-                *state = DattorroState::new(dsr);
+                *state = DattorroState::new(dsr)
+                    .with_shimmer(state.shimmer.amount, state.shimmer.semitones);
             }
 
             // --- Modulation delays ---
@@ -6540,11 +7749,15 @@ impl UnifiedSignalGraph {
                     SignalNode::Comb { .. } |
                     SignalNode::Convolution { .. } |
                     SignalNode::SpectralFreeze { .. } |
+                    SignalNode::Reverse { .. } |
+                    SignalNode::Varispeed { .. } |
                     SignalNode::Granular { .. } |
                     SignalNode::KarplusStrong { .. } |
                     SignalNode::Waveguide { .. } |
                     SignalNode::Vocoder { .. } |
-                    SignalNode::PitchShift { .. } => {
+                    SignalNode::PitchShift { .. } |
+                    SignalNode::SyncOscillator { .. } |
+                    SignalNode::Lfo { .. } => {
                         return true;
                     }
                     // An oscillator whose frequency is a running/modulated signal has
@@ -6625,12 +7838,9 @@ impl UnifiedSignalGraph {
         result
     }
 
-    /// Preload all samples referenced in pattern nodes
-    /// This should be called before swapping a graph into the audio thread
-    /// to avoid disk I/O during audio processing
-    pub fn preload_samples(&self) {
-        use std::collections::HashSet;
-        let mut sample_names: HashSet<String> = HashSet::new();
+    /// Sample names the graph's sample patterns play in their first 16 cycles
+    fn referenced_sample_names(&self) -> std::collections::HashSet<String> {
+        let mut sample_names = std::collections::HashSet::new();
 
         // Walk through all nodes and collect sample names from Pattern<String> patterns
         for opt_node in &self.nodes {
@@ -6661,6 +7871,25 @@ impl UnifiedSignalGraph {
             }
         }
 
+        sample_names
+    }
+
+    /// Start decoding the samples this graph plays on a background thread
+    /// (see [`SampleBank::prefetch`]), so a later `preload_samples` finds
+    /// them ready instead of reading the disk
+    pub fn prefetch_samples(&self) {
+        let sample_names = self.referenced_sample_names();
+        if !sample_names.is_empty() {
+            self.sample_bank.borrow_mut().prefetch(sample_names);
+        }
+    }
+
+    /// Preload all samples referenced in pattern nodes
+    /// This should be called before swapping a graph into the audio thread
+    /// to avoid disk I/O during audio processing
+    pub fn preload_samples(&self) {
+        let sample_names = self.referenced_sample_names();
+
         // Preload all discovered samples
         if !sample_names.is_empty() {
             let mut bank = self.sample_bank.borrow_mut();
@@ -6679,7 +7908,7 @@ impl UnifiedSignalGraph {
     /// Instantiate + initialise every external plugin referenced by the graph.
     ///
     /// This MUST run OFF the audio render thread (ideally at compile/reload) so the
-    /// render path never blocks loading a VST3/VST2 from disk and never blocks on a
+    /// render path never blocks loading a VST3/VST2/CLAP from disk and never blocks on a
     /// poisoned lock (rt-safety audit F-2). It is idempotent — already-loaded
     /// plugins are skipped — and is also invoked once via [`Self::ensure_prepared`]
     /// on the first render as a safety net so a live session with plugins never goes
@@ -6688,6 +7917,15 @@ impl UnifiedSignalGraph {
     /// Mock plugins (`mock:` / `MockSynth`) are intentionally NOT preloaded here;
     /// they are cheap, stateful, and created inline in the mock render branch.
     pub fn preload_plugins(&mut self) {
+        for node in self.nodes.iter().flatten() {
+            if let SignalNode::ExternalFx { state, .. } = &**node {
+                match state.lock() {
+                    Ok(mut fx) => fx.load(),
+                    Err(poisoned) => poisoned.into_inner().load(),
+                }
+            }
+        }
+
         #[cfg(any(feature = "vst3", feature = "vst2"))]
         {
             // Collect the distinct, non-mock plugin ids referenced by the graph.
@@ -6820,9 +8058,9 @@ impl UnifiedSignalGraph {
                 {
                     if let Some(base_freq) = self.signal_constant_value(freq) {
                         let effective_freq = if *semitone_offset >= 1000.0 {
-                            // Absolute MIDI note -> Hz (A4 = 440Hz)
+                            // Absolute MIDI note -> Hz
                             let midi = *semitone_offset - 1000.0;
-                            440.0 * 2.0_f32.powf((midi - 69.0) / 12.0)
+                            crate::pitch::midi_to_hz(midi as f64) as f32
                         } else if *semitone_offset != 0.0 {
                             base_freq * 2.0_f32.powf(*semitone_offset / 12.0)
                         } else {
@@ -6830,13 +8068,52 @@ impl UnifiedSignalGraph {
                         };
                         let new_phase =
                             (sample_index as f64 * effective_freq as f64 / sr).rem_euclid(1.0);
-                        *phase.borrow_mut() = new_phase as f32;
+                        *phase.borrow_mut() = new_phase;
                     }
                 }
             }
         }
     }
 
+    /// Jump playback to `cycle` as if it had started there (`:cue`, `render
+    /// --start-cycle`)
+    ///
+    /// Positions the clock and constant-frequency oscillators via
+    /// [`seek_to_sample`](Self::seek_to_sample), cuts the voices sounding before
+    /// the jump, and re-arms pattern triggers so events from `cycle` on fire —
+    /// also after a jump backwards. Patterns (including their `degrade`/`rand`
+    /// choices) are queried by cycle, so the events heard after a cue are the
+    /// same as in a render from the start.
+    pub fn seek_to_cycle(&mut self, cycle: f64) {
+        let cycle = cycle.max(0.0);
+        let sample_index = (cycle / self.cps as f64 * self.sample_rate as f64).round() as usize;
+        self.seek_to_sample(sample_index);
+        self.set_cycle(cycle);
+
+        self.voice_manager.borrow_mut().kill_all();
+        self.synth_voice_manager.borrow_mut().kill_all();
+
+        for node_rc in self.nodes.iter_mut().flatten() {
+            match std::rc::Rc::make_mut(node_rc) {
+                SignalNode::Sample {
+                    last_trigger_time,
+                    last_cycle,
+                    ..
+                } => {
+                    // Fresh-node sentinel: the next buffer arms it at its start
+                    *last_trigger_time = -1.0;
+                    *last_cycle = -1;
+                }
+                SignalNode::SynthPattern {
+                    last_trigger_time, ..
+                } => {
+                    *last_trigger_time = cycle - 0.001;
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// How many samples of "warmup" each parallel render chunk must process
     /// BEFORE its assigned output range so that sample voices triggered in an
     /// earlier chunk are already active (with the correct playback position)
@@ -6967,13 +8244,14 @@ impl UnifiedSignalGraph {
     /// Replaces with a fresh VoiceManager
     pub fn take_voice_manager(&mut self) -> crate::voice_manager::VoiceManager {
         use std::mem;
-        let fresh_vm = crate::voice_manager::VoiceManager::new();
+        let fresh_vm = crate::voice_manager::VoiceManager::with_sample_rate(self.sample_rate);
         mem::replace(self.voice_manager.get_mut(), fresh_vm)
     }
 
     /// Transfer a VoiceManager into this graph (from old graph)
     /// Release all voices with quick fade to prevent accumulation during rapid graph swaps
     pub fn transfer_voice_manager(&mut self, mut voice_manager: crate::voice_manager::VoiceManager) {
+        voice_manager.set_sample_rate(self.sample_rate);
         // Release synthesis voices - they reference old graph's node IDs which no longer exist
         voice_manager.release_synthesis_voices();
         // Release sample voices - they would accumulate during rapid graph swaps
@@ -7003,6 +8281,68 @@ impl UnifiedSignalGraph {
         self.preserve_voices_on_swap = enabled;
     }
 
+    /// Render at `quality`: below [`Quality::Full`] the reverb thins out,
+    /// waveshapers stop oversampling and sample polyphony may be capped
+    /// (see [`crate::adaptive_quality`])
+    pub fn set_quality(&mut self, quality: Quality) {
+        self.quality = quality;
+    }
+
+    pub fn quality(&self) -> Quality {
+        self.quality
+    }
+
+    /// Report every triggered sample, bus and synth note to `log` (the
+    /// editor's event pane). Nothing is formatted while the log is disabled.
+    pub fn set_event_log(&mut self, log: Option<EventLogSender>) {
+        self.event_log = log;
+    }
+
+    /// Meter the peak of every named bus and of the master (before the master
+    /// clipper) from now on, for clip indicators and the headroom report
+    pub fn enable_bus_meters(&mut self) -> Arc<BusMeters> {
+        let mut buses: Vec<(&String, NodeId)> = self
+            .buses
+            .iter()
+            .filter(|(name, _)| !name.starts_with('_'))
+            .map(|(name, &node)| (name, node))
+            .collect();
+        buses.sort_by(|a, b| a.0.cmp(b.0));
+        let meters = Arc::new(BusMeters::new(
+            buses.iter().map(|(name, _)| (*name).clone()),
+        ));
+        let nodes = buses.into_iter().map(|(_, node)| node).collect();
+        self.bus_meters = Some((Arc::clone(&meters), nodes));
+        meters
+    }
+
+    /// Send band levels, onsets and the cycle phase of the output to `target`
+    /// (`osc://host:port`) at `fps` frames per second, for audio-reactive
+    /// visuals
+    pub fn enable_visuals(&mut self, target: &str, fps: f64) -> Result<(), String> {
+        let analyzer = crate::visuals::VisualsAnalyzer::new(self.sample_rate);
+        crate::visuals::start(&analyzer, target, fps)?;
+        self.visuals = Some(analyzer);
+        Ok(())
+    }
+
+    /// Where this graph's `take` / `after` patterns count from (a shared
+    /// handle: moving it moves them all)
+    pub fn count_origin(&self) -> crate::pattern_ops_extended::CountOrigin {
+        self.count_origin.clone()
+    }
+
+    /// Send one triggered event to the event log, if one is listening
+    fn log_event(&self, cycle: f64, name: &str, params: impl FnOnce() -> String) {
+        if let Some(log) = self.event_log.as_ref().filter(|log| log.is_enabled()) {
+            log.send(LoggedEvent {
+                cycle,
+                name: name.to_string(),
+                params: params(),
+            });
+        }
+    }
+
     /// Transfer a VoiceManager into this graph **preserving** its live voices
     /// (the G7 flag-on path). Unlike [`transfer_voice_manager`](Self::transfer_voice_manager),
     /// which quick-releases every voice, this keeps held notes sounding across the
@@ -7038,6 +8378,15 @@ impl UnifiedSignalGraph {
         self.nodes.iter().filter(|n| n.is_some()).count()
     }
 
+    /// Whether any node keeps its state behind an `Arc`, which a clone of the
+    /// graph shares instead of copying
+    pub fn has_shared_node_state(&self) -> bool {
+        self.nodes
+            .iter()
+            .flatten()
+            .any(|node| node_shares_state(node))
+    }
+
     /// Get the voice pool size (for diagnostics)
     pub fn voice_pool_size(&self) -> usize {
         self.voice_manager.borrow().pool_size()
@@ -7065,6 +8414,9 @@ impl UnifiedSignalGraph {
         // => new_offset = old_cycle_pos - old_elapsed * new_cps
         self.cycle_offset = old_cycle_pos - old_elapsed * self.cps as f64;
 
+        // Counted patterns (`take 8`) count from the next cycle boundary
+        self.count_origin.start_at(old_cycle_pos);
+
         // DEBUG: Log timing transfer details (pt-F9: gated so hot-reloads don't
         // pay per-swap `eprintln!` jitter unless DEBUG_TIMING_TRANSFER is set).
         if self.debug_flags.timing_transfer {
@@ -7089,6 +8441,7 @@ impl UnifiedSignalGraph {
         // CRITICAL: Transfer cycle bus cache to prevent spurious resynthesis on reload
         // Without this, new graph has cache_floor=-1, causing unnecessary cache invalidation
         self.cycle_bus_cache = old_graph.cycle_bus_cache.clone();
+        self.round_robin = old_graph.round_robin.clone();
 
         // Also transfer the cached cycle position to ensure consistency
         self.cached_cycle_position = old_cycle_pos;
@@ -7465,6 +8818,14 @@ impl UnifiedSignalGraph {
             stack.push(output_id.0);
         }
 
+        // And the cue and control voltage outputs
+        if let Some(cue_id) = self.cue_output {
+            stack.push(cue_id.0);
+        }
+        for &cv_id in self.cv_outputs.values() {
+            stack.push(cv_id.0);
+        }
+
         // DFS to find all reachable nodes
         while let Some(node_id) = stack.pop() {
             if reachable.contains(&node_id) {
@@ -7710,12 +9071,18 @@ impl UnifiedSignalGraph {
             | SignalNode::BrownNoise { .. }
             | SignalNode::Pattern { .. }
             | SignalNode::Sample { .. }
+            | SignalNode::FrozenBus { .. }
             | SignalNode::PatternTrigger { .. } => {
                 // No signal inputs for sources
             }
             SignalNode::Phasor { speed } => {
                 collect!(speed);
             }
+            SignalNode::Lfo { rate, min, max, .. } => {
+                collect!(rate);
+                collect!(min);
+                collect!(max);
+            }
             SignalNode::PluginInstance {
                 audio_inputs,
                 params,
@@ -7794,6 +9161,7 @@ impl UnifiedSignalGraph {
                 time,
                 feedback,
                 stereo_width,
+                filter,
                 mix,
                 ..
             } => {
@@ -7801,6 +9169,7 @@ impl UnifiedSignalGraph {
                 collect!(time);
                 collect!(feedback);
                 collect!(stereo_width);
+                collect!(filter);
                 collect!(mix);
             }
             SignalNode::Reverb {
@@ -7929,6 +9298,26 @@ impl UnifiedSignalGraph {
                 collect!(attack);
                 collect!(release);
             }
+            SignalNode::NoiseGate {
+                input,
+                key,
+                threshold,
+                hysteresis,
+                attack,
+                hold,
+                release,
+                ..
+            } => {
+                collect!(input);
+                if let Some(key) = key {
+                    collect!(key);
+                }
+                collect!(threshold);
+                collect!(hysteresis);
+                collect!(attack);
+                collect!(hold);
+                collect!(release);
+            }
             SignalNode::MoogLadder {
                 input,
                 cutoff,
@@ -7958,6 +9347,9 @@ impl UnifiedSignalGraph {
             SignalNode::MidiToFreq { midi } => {
                 collect!(midi);
             }
+            SignalNode::HzToVolts { freq } => {
+                collect!(freq);
+            }
 
             // === Multi-input ===
             SignalNode::Mix { signals } => {
@@ -8001,6 +9393,46 @@ impl UnifiedSignalGraph {
                 collect!(width);
             }
 
+            // === Tilt EQ / saturation ===
+            SignalNode::TiltEq {
+                input,
+                tilt,
+                pivot,
+                air,
+                ..
+            } => {
+                collect!(input);
+                collect!(tilt);
+                collect!(pivot);
+                collect!(air);
+            }
+            SignalNode::Saturate {
+                input, drive, mix, ..
+            } => {
+                collect!(input);
+                collect!(drive);
+                collect!(mix);
+            }
+            SignalNode::Waveshaper {
+                input, a, b, mix, ..
+            } => {
+                collect!(input);
+                collect!(a);
+                collect!(b);
+                collect!(mix);
+            }
+            SignalNode::SyncOscillator {
+                freq, sync, retrig, ..
+            } => {
+                collect!(freq);
+                if let Some(sync) = sync {
+                    collect!(sync);
+                }
+                if let Some(retrig) = retrig {
+                    collect!(retrig);
+                }
+            }
+
             // === AmpFollower (envelope follower) ===
             SignalNode::AmpFollower {
                 input,
@@ -8085,6 +9517,10 @@ impl UnifiedSignalGraph {
                 collect!(min);
                 collect!(max);
             }
+            SignalNode::RandSpread { min, max } => {
+                collect!(min);
+                collect!(max);
+            }
 
             // === Router ===
             SignalNode::Router { input, .. } => {
@@ -8407,6 +9843,16 @@ impl UnifiedSignalGraph {
             SignalNode::Timer { trigger, .. } => {
                 collect!(trigger);
             }
+            SignalNode::ClockDivider {
+                input, division, ..
+            } => {
+                collect!(input);
+                collect!(division);
+            }
+            SignalNode::ClockMultiplier { input, factor, .. } => {
+                collect!(input);
+                collect!(factor);
+            }
             SignalNode::Pitch { input, .. } => {
                 collect!(input);
             }
@@ -8468,6 +9914,12 @@ impl UnifiedSignalGraph {
                 collect!(input);
                 collect!(trigger);
             }
+            SignalNode::Reverse { input, .. } => {
+                collect!(input);
+            }
+            SignalNode::Varispeed { input, .. } => {
+                collect!(input);
+            }
             SignalNode::Vibrato {
                 input, rate, depth, ..
             } => {
@@ -8503,6 +9955,19 @@ impl UnifiedSignalGraph {
             SignalNode::Tap { input, .. } => {
                 collect!(input);
             }
+            SignalNode::AutoGain { input, .. } => {
+                collect!(input);
+            }
+            SignalNode::SpatialGain {
+                input,
+                azimuth,
+                elevation,
+                ..
+            } => {
+                collect!(input);
+                collect!(azimuth);
+                collect!(elevation);
+            }
             SignalNode::AdaptiveCompressor {
                 main_input,
                 sidechain_input,
@@ -8604,11 +10069,17 @@ impl UnifiedSignalGraph {
             }
 
             // === FundspUnit (variable inputs) ===
-            SignalNode::FundspUnit { inputs, .. } => {
+            SignalNode::FundspUnit { inputs, .. } | SignalNode::UserNode { inputs, .. } => {
                 for sig in inputs {
                     collect!(sig);
                 }
             }
+            SignalNode::ExternalFx { input, params, .. } => {
+                collect!(input);
+                for sig in params {
+                    collect!(sig);
+                }
+            }
 
             // === Catch-all for nodes not yet covered ===
             _ => {
@@ -8638,9 +10109,15 @@ impl UnifiedSignalGraph {
             | SignalNode::Chorus { input, .. }
             | SignalNode::Flanger { input, .. }
             | SignalNode::StereoWidener { input, .. }
+            | SignalNode::TiltEq { input, .. }
+            | SignalNode::Reverse { input, .. }
+            | SignalNode::Varispeed { input, .. }
+            | SignalNode::Saturate { input, .. }
+            | SignalNode::Waveshaper { input, .. }
             | SignalNode::Compressor { input, .. }
             | SignalNode::TransientShaper { input, .. }
             | SignalNode::Expander { input, .. }
+            | SignalNode::NoiseGate { input, .. }
             | SignalNode::LowPass { input, .. }
             | SignalNode::HighPass { input, .. }
             | SignalNode::BandPass { input, .. }
@@ -8748,6 +10225,11 @@ impl UnifiedSignalGraph {
         mix(self.outputs.len() as u64);
         mix(self.outputs.values().map(|n| n.0 as u64).sum());
         mix(self.output.map(|n| n.0 as u64 + 1).unwrap_or(0));
+        mix(self.cue_output.map(|n| n.0 as u64 + 1).unwrap_or(0));
+        for (&ch, &node) in &self.cv_outputs {
+            mix(ch as u64);
+            mix(node.0 as u64);
+        }
         fp
     }
 
@@ -8763,6 +10245,9 @@ impl UnifiedSignalGraph {
         let bus_node_ids: std::collections::HashSet<usize> =
             self.buses.values().map(|id| id.0).collect();
         let output_node_id = self.output.map(|id| id.0);
+        let cue_node_id = self.cue_output.map(|id| id.0);
+        let cv_node_ids: std::collections::HashSet<usize> =
+            self.cv_outputs.values().map(|id| id.0).collect();
         let numbered_output_ids: std::collections::HashSet<usize> =
             self.outputs.values().map(|id| id.0).collect();
 
@@ -8778,6 +10263,8 @@ impl UnifiedSignalGraph {
                 .filter(|&node_id| {
                     bus_node_ids.contains(&node_id)
                         || Some(node_id) == output_node_id
+                        || Some(node_id) == cue_node_id
+                        || cv_node_ids.contains(&node_id)
                         || numbered_output_ids.contains(&node_id)
                 })
                 .collect()
@@ -8952,7 +10439,7 @@ impl UnifiedSignalGraph {
                             .collect();
 
                         // Save original oscillator state (phase + offset only)
-                        let mut original_osc_state: Vec<(usize, f32, f32)> = Vec::new();
+                        let mut original_osc_state: Vec<(usize, f32, f64)> = Vec::new();
                         for &osc_id in &oscillator_ids {
                             if let Some(Some(node_rc)) = self.nodes.get(osc_id) {
                                 if let SignalNode::Oscillator {
@@ -9331,6 +10818,42 @@ impl UnifiedSignalGraph {
             }
         }
 
+        // Cue output: its own stream for the headphones, never in the main mix.
+        // Sanitised and limited like the main output (Phase 4b/4c).
+        if let Some(cue_id) = self.cue_output.map(|id| id.0) {
+            self.cue_buffer.resize(buffer_size, 0.0);
+            match current_buffers.get(&cue_id) {
+                Some(mono_buf) if !self.hushed_channels.contains(&CUE_CHANNEL) => {
+                    let ceiling = self.master_limiter_ceiling.min(1.0);
+                    for (dst, &src) in self.cue_buffer.iter_mut().zip(mono_buf.iter()) {
+                        *dst = if src.is_finite() {
+                            crate::denormals::flush_denormal(src.clamp(-ceiling, ceiling))
+                        } else {
+                            0.0
+                        };
+                    }
+                }
+                _ => self.cue_buffer.fill(0.0),
+            }
+        }
+
+        // Control voltages: volts, for the editor to scale to its interface.
+        // Not limited (a pitch CV is well above the audio ceiling), and held at
+        // 0 V while hushed
+        let cv_hushed = self.hushed_channels.contains(&CV_CHANNEL);
+        for (&ch, &node_id) in &self.cv_outputs {
+            let volts = self.cv_buffers.entry(ch).or_default();
+            volts.resize(buffer_size, 0.0);
+            match current_buffers.get(&node_id.0) {
+                Some(mono_buf) if !cv_hushed => {
+                    for (dst, &src) in volts.iter_mut().zip(mono_buf.iter()) {
+                        *dst = if src.is_finite() { src } else { 0.0 };
+                    }
+                }
+                _ => volts.fill(0.0),
+            }
+        }
+
         // Phase 4: Apply output mixing mode (prevent clipping from voice accumulation)
         match self.output_mix_mode {
             OutputMixMode::Gain => {
@@ -9407,14 +10930,43 @@ impl UnifiedSignalGraph {
             };
         }
 
+        // Meters: each bus's peak, and the master's before the clipper; a clipping
+        // block is blamed on its loudest bus
+        if let Some((meters, nodes)) = self.bus_meters.as_ref() {
+            let mut loudest: Option<(usize, f32)> = None;
+            for (i, node) in nodes.iter().enumerate() {
+                if let Some(bus_buf) = current_buffers.get(&node.0) {
+                    let peak = bus_buf.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+                    meters.record_bus(i, peak);
+                    if loudest.map_or(true, |(_, p)| peak > p) {
+                        loudest = Some((i, peak));
+                    }
+                }
+            }
+            let master = buffer.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+            meters.record_master(master, loudest.map(|(i, _)| i));
+        }
+        if let Some(visuals) = self.visuals.as_mut() {
+            visuals.process(buffer, buffer_start_cycle);
+        }
+
+        // Phase 4a: Optional master DC blocker (removes offsets some chains build up)
+        if let Some(blockers) = self.master_dc_blocker.as_mut() {
+            for frame in buffer.chunks_exact_mut(2) {
+                frame[0] = blockers[0].process(frame[0]);
+                frame[1] = blockers[1].process(frame[1]);
+            }
+        }
+
         // Phase 4b: Apply master limiter (safety limiter to protect speakers/ears)
         // This is applied AFTER OutputMixMode to catch any peaks that slip through
-        // Default ceiling is 0.95 (-0.4dB) for safety margin
-        if self.master_limiter_ceiling < 1.0 {
+        // Default ceiling is 0.95 (-0.4dB) for safety margin, hard clipped unless
+        // `master_clip` says otherwise
+        if self.master_limiter_ceiling < 1.0 && self.master_clip != MasterClip::Off {
             let ceiling = self.master_limiter_ceiling;
+            let clip = self.master_clip;
             for sample in buffer.iter_mut() {
-                // Hard limit to ceiling (brick-wall limiter)
-                *sample = sample.clamp(-ceiling, ceiling);
+                *sample = clip.apply(*sample, ceiling);
             }
         }
 
@@ -9691,12 +11243,207 @@ impl UnifiedSignalGraph {
                 eprintln!("  bus sample[{}] = {}", i, sample);
             }
         }
-
-        // CRITICAL: Update cached_cycle_position to point to where the NEXT buffer should start.
-        // The loop above sets it to buffer_start_cycle + (buffer_size - 1) * sample_increment,
-        // but we need buffer_start_cycle + buffer_size * sample_increment to avoid 1-sample drift
-        // per buffer. Without this fix, timing drifts by N samples after N buffers.
-        self.cached_cycle_position = buffer_start_cycle + buffer_size as f64 * sample_increment;
+
+        // CRITICAL: Update cached_cycle_position to point to where the NEXT buffer should start.
+        // The loop above sets it to buffer_start_cycle + (buffer_size - 1) * sample_increment,
+        // but we need buffer_start_cycle + buffer_size * sample_increment to avoid 1-sample drift
+        // per buffer. Without this fix, timing drifts by N samples after N buffers.
+        self.cached_cycle_position = buffer_start_cycle + buffer_size as f64 * sample_increment;
+    }
+
+    /// Transfer the full node state of unchanged buses from old graph to this graph
+    ///
+    /// Plans the transfer against `old_graph` and applies it at once, for
+    /// callers that own both graphs on one thread. A hot-swap splits the two
+    /// halves: [`Self::plan_bus_state_transfer`] on the control thread and
+    /// [`Self::apply_bus_state_plan`] on the render thread. Returns the number
+    /// of nodes whose state was carried.
+    pub fn transfer_bus_states(&mut self, old_graph: &UnifiedSignalGraph) -> usize {
+        self.plan_bus_state_transfer(&old_graph.shape());
+        self.apply_bus_state_plan(old_graph)
+    }
+
+    /// What [`Self::plan_bus_state_transfer`] needs to know about this graph,
+    /// to keep on the control thread once the graph goes to the render thread
+    pub fn shape(&self) -> GraphShape {
+        GraphShape {
+            graph_id: self.graph_id,
+            buses: self
+                .bus_hashes
+                .iter()
+                .filter_map(|(name, hash)| Some((name.clone(), (*hash, self.buses.get(name)?.0))))
+                .collect(),
+            continued_buses: self
+                .continued_buses
+                .iter()
+                .map(|(name, hash, node)| (name.clone(), *hash, node.0))
+                .collect(),
+            output: self
+                .output
+                .zip(self.output_hash)
+                .map(|(id, hash)| (hash, id.0)),
+            nodes: self
+                .nodes
+                .iter()
+                .map(|node| {
+                    node.as_deref()
+                        .map(|node| (std::mem::discriminant(node), self.get_all_node_inputs(node)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Plan which nodes take over the state of the graph `old` was taken from
+    ///
+    /// A bus whose definition hash matches the old graph's was compiled from the
+    /// same text, so its subgraph has the same shape. Both subgraphs are walked in
+    /// lockstep from the bus root and every stateful node (oscillator phase,
+    /// envelope position, filter memory) is paired with its old counterpart,
+    /// so re-evaluating a file leaves untouched buses sounding seamlessly.
+    ///
+    /// The walk stops at other buses (they are matched by their own hash) and at
+    /// any node whose variant or input count differs, e.g. when a template the
+    /// bus uses was edited.
+    ///
+    /// Nodes marked with [`Self::continue_bus`] are matched the same way, in
+    /// both directions: a bus that starts morphing away from its old definition
+    /// doesn't restart it, and once the morph is dropped the new definition
+    /// carries on from the morph's incoming side.
+    ///
+    /// Runs on the control thread: the walk allocates, so the swap itself only
+    /// copies state across the planned pairs.
+    pub fn plan_bus_state_transfer(&mut self, old: &GraphShape) {
+        let old_root = |name: &String, hash: &u64| {
+            if let Some((old_hash, id)) = old.buses.get(name) {
+                if old_hash == hash {
+                    return Some(*id);
+                }
+            }
+            old.continued_buses
+                .iter()
+                .find(|(old_name, old_hash, _)| old_name == name && old_hash == hash)
+                .map(|(_, _, id)| *id)
+        };
+        let mut roots: Vec<(usize, usize)> = self
+            .bus_hashes
+            .iter()
+            .filter_map(|(name, hash)| Some((self.buses.get(name)?.0, old_root(name, hash)?)))
+            .collect();
+        roots.extend(
+            self.continued_buses
+                .iter()
+                .filter_map(|(name, hash, node)| Some((node.0, old_root(name, hash)?))),
+        );
+        if let (Some(new_out), Some(hash), Some((old_hash, old_out))) =
+            (self.output, self.output_hash, old.output)
+        {
+            if hash == old_hash {
+                roots.push((new_out.0, old_out));
+            }
+        }
+
+        let bus_roots: std::collections::HashSet<usize> =
+            self.buses.values().map(|id| id.0).collect();
+        let mut visited = std::collections::HashSet::new();
+        let mut pairs = Vec::new();
+
+        for (new_root, old_root) in roots {
+            let mut stack = vec![(new_root, old_root)];
+            while let Some((new_id, old_id)) = stack.pop() {
+                if !visited.insert(new_id) {
+                    continue;
+                }
+                let (Some(Some(new_node)), Some(Some((old_variant, old_inputs)))) =
+                    (self.nodes.get(new_id), old.nodes.get(old_id))
+                else {
+                    continue;
+                };
+                if std::mem::discriminant(&**new_node) != *old_variant {
+                    continue;
+                }
+                let new_inputs = self.get_all_node_inputs(new_node);
+                if new_inputs.len() != old_inputs.len() {
+                    continue;
+                }
+                pairs.push((new_id, old_id));
+                for (&new_input, &old_input) in new_inputs.iter().zip(old_inputs) {
+                    if new_input != new_root && bus_roots.contains(&new_input) {
+                        continue;
+                    }
+                    stack.push((new_input, old_input));
+                }
+            }
+        }
+
+        // Own the paired nodes now, so copying into them never clones
+        for &(new_id, _) in &pairs {
+            if let Some(Some(node)) = self.nodes.get_mut(new_id) {
+                Rc::make_mut(node);
+            }
+        }
+        self.bus_state_plan = Some((old.graph_id, pairs));
+    }
+
+    /// Copy the state planned by [`Self::plan_bus_state_transfer`] out of
+    /// `old_graph`, if that's the graph the plan was made against. Bounded and
+    /// allocation-free beyond the state itself, for the render thread. Returns
+    /// the number of nodes whose state was carried.
+    pub fn apply_bus_state_plan(&mut self, old_graph: &UnifiedSignalGraph) -> usize {
+        let Some((source, pairs)) = &self.bus_state_plan else {
+            return 0;
+        };
+        if *source != old_graph.graph_id {
+            return 0;
+        }
+        let mut carried = 0;
+        for &(new_id, old_id) in pairs {
+            if let (Some(Some(new_rc)), Some(Some(old_rc))) =
+                (self.nodes.get_mut(new_id), old_graph.nodes.get(old_id))
+            {
+                if let Some(new_node) = Rc::get_mut(new_rc) {
+                    if carry_node_state(new_node, old_rc) {
+                        carried += 1;
+                    }
+                }
+            }
+        }
+        carried
+    }
+
+    /// The running state of every stateful node (oscillator phase, envelope
+    /// position, filter memory), encoded for a session file
+    ///
+    /// The graph itself can't be written out, since its patterns are closures;
+    /// a session rebuilds it from code and puts this back with
+    /// [`Self::restore_node_states`]. Allocates, so the render thread only
+    /// calls it on request (`Cmd::SaveState`).
+    pub fn save_node_states(&self) -> Vec<u8> {
+        let states: Vec<SavedNodeState> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(id, node)| save_node_state(id, node.as_deref()?))
+            .collect();
+        bincode::serialize(&states).unwrap_or_default()
+    }
+
+    /// Put back node state saved by [`Self::save_node_states`] from a graph
+    /// compiled from the same code. Nodes whose variant doesn't match (the
+    /// code compiled differently) keep their fresh state. Returns the number
+    /// of nodes restored.
+    pub fn restore_node_states(&mut self, bytes: &[u8]) -> usize {
+        let Ok(states) = bincode::deserialize::<Vec<SavedNodeState>>(bytes) else {
+            return 0;
+        };
+        let mut restored = 0;
+        for saved in &states {
+            if let Some(Some(node)) = self.nodes.get_mut(saved.node) {
+                if restore_node_state(Rc::make_mut(node), saved) {
+                    restored += 1;
+                }
+            }
+        }
+        restored
     }
 
     /// Transfer FX state from old graph to this graph
@@ -9971,8 +11718,10 @@ impl UnifiedSignalGraph {
                     SignalNode::PingPongDelay {
                         input,
                         time,
+                        synced,
                         feedback,
                         stereo_width,
+                        filter,
                         channel,
                         mix,
                         ..
@@ -9988,13 +11737,16 @@ impl UnifiedSignalGraph {
                             Some(SignalNode::PingPongDelay {
                                 input: input.clone(),
                                 time: time.clone(),
+                                synced: *synced,
                                 feedback: feedback.clone(),
                                 stereo_width: stereo_width.clone(),
+                                filter: filter.clone(),
                                 channel: *channel,
                                 mix: mix.clone(),
                                 buffer_l: buffer_l.clone(),
                                 buffer_r: buffer_r.clone(),
                                 write_idx: *write_idx,
+                                lpf_state: (0.0, 0.0),
                             })
                         } else {
                             None
@@ -10009,7 +11761,7 @@ impl UnifiedSignalGraph {
                         damping,
                         mod_depth,
                         mix,
-                        ..
+                        state: new_state,
                     } => {
                         let key = self.make_fx_key(&mut fx_counters, &bus_name, "dattorroreverb");
                         if let Some(ExtractedFxState::DattorroReverb(state)) = state_map.get(&key) {
@@ -10022,7 +11774,10 @@ impl UnifiedSignalGraph {
                                 damping: damping.clone(),
                                 mod_depth: mod_depth.clone(),
                                 mix: mix.clone(),
-                                state: state.clone(),
+                                state: state.clone().with_shimmer(
+                                    new_state.shimmer.amount,
+                                    new_state.shimmer.semitones,
+                                ),
                             })
                         } else {
                             None
@@ -10261,6 +12016,146 @@ impl UnifiedSignalGraph {
         self.sample_rate
     }
 
+    /// Search `dirs` for samples right after the bundled ones, for this graph
+    /// alone (see [`SampleBank::with_extra_dirs`]). Samples already loaded
+    /// are dropped, so call this before rendering
+    pub fn set_sample_paths(&mut self, dirs: &[std::path::PathBuf]) {
+        *self.sample_bank.get_mut() = SampleBank::with_extra_dirs(dirs, self.sample_rate);
+        self.velocity_layers.clear();
+    }
+
+    /// Move the graph to a new sample rate (e.g. the rate the output device
+    /// actually opened at).
+    ///
+    /// Filter coefficients are recomputed on the next sample, delay lines are
+    /// resized so delay times stay the same in seconds, and rate-dependent
+    /// effect state (reverbs, chorus, vocoder, ...) is rebuilt. Playing voices
+    /// and buffered audio are dropped, so call this before rendering or from
+    /// the control thread between blocks, not mid-buffer.
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        if sample_rate == self.sample_rate || sample_rate <= 0.0 {
+            return;
+        }
+        let ratio = sample_rate / self.sample_rate;
+        let sr = sample_rate;
+        self.sample_rate = sr;
+        self.voice_manager.get_mut().set_sample_rate(sr);
+        self.sample_bank.get_mut().set_sample_rate(sr);
+        self.synth_voice_manager = RefCell::new(SynthVoiceManager::new(sr));
+
+        for node_rc in self.nodes.iter_mut().flatten() {
+            match Rc::make_mut(node_rc) {
+                // Chamberlin SVF coefficients are cached per cutoff
+                SignalNode::LowPass { state, .. }
+                | SignalNode::HighPass { state, .. }
+                | SignalNode::BandPass { state, .. }
+                | SignalNode::DJFilter { state, .. }
+                | SignalNode::Notch { state, .. } => {
+                    state.cached_fc = -1.0;
+                }
+
+                SignalNode::StereoWidener { state, .. } => {
+                    use biquad::Biquad;
+                    state
+                        .allpass
+                        .update_coefficients(widener_allpass_coeffs(sr));
+                    state.sample_rate = sr;
+                    state.allpass.reset_state();
+                }
+
+                SignalNode::Saturate { state, .. } => {
+                    state.dc_blocker = DcBlocker::new(sr);
+                }
+
+                // --- Delay lines: same length in seconds ---
+                SignalNode::Delay {
+                    buffer, write_idx, ..
+                }
+                | SignalNode::MultiTapDelay {
+                    buffer, write_idx, ..
+                } => {
+                    rescale_delay_line(buffer, ratio);
+                    *write_idx = 0;
+                }
+
+                SignalNode::Comb {
+                    buffer, write_pos, ..
+                } => {
+                    rescale_delay_line(buffer, ratio);
+                    *write_pos = 0;
+                }
+
+                SignalNode::PingPongDelay {
+                    buffer_l,
+                    buffer_r,
+                    write_idx,
+                    ..
+                } => {
+                    rescale_delay_line(buffer_l, ratio);
+                    rescale_delay_line(buffer_r, ratio);
+                    *write_idx = 0;
+                }
+
+                SignalNode::Limiter { state, .. } => {
+                    let lookahead = (state.delay_buffer.len() as f32 * ratio).round() as usize;
+                    *state = LimiterState::new(lookahead);
+                }
+
+                SignalNode::Vibrato {
+                    delay_buffer,
+                    buffer_pos,
+                    ..
+                } => {
+                    // Re-sized from the sample rate on next use
+                    delay_buffer.clear();
+                    *buffer_pos = 0;
+                }
+
+                // --- Effects whose state is built from the sample rate ---
+                SignalNode::TapeDelay { state, .. } => *state = TapeDelayState::new(sr),
+                SignalNode::Reverb { state, .. } => *state = ReverbState::new(sr),
+                SignalNode::DattorroReverb { state, .. } => {
+                    *state = DattorroState::new(sr)
+                        .with_shimmer(state.shimmer.amount, state.shimmer.semitones)
+                }
+                SignalNode::Chorus { state, .. } => *state = ChorusState::new(sr),
+                SignalNode::Flanger { state, .. } => *state = FlangerState::new(sr),
+                SignalNode::Formant { state, .. } | SignalNode::Vowel { state, .. } => {
+                    *state = FormantState::new(sr);
+                }
+                SignalNode::Additive { state, .. } => *state = AdditiveState::new(sr),
+                SignalNode::Convolution { state, .. } => *state = ConvolutionState::new(sr),
+                SignalNode::Vocoder { state, .. } => {
+                    *state = VocoderState::new(state.num_bands, sr);
+                }
+                SignalNode::PitchShift { state, .. } => {
+                    let grain_ms = state.grain_size as f32 / state.sample_rate * 1000.0;
+                    *state = PitchShifterState::new(grain_ms, sr);
+                }
+                SignalNode::LushReverb { state, .. } => {
+                    // Same seed the compiler uses
+                    *state = crate::nodes::lush_reverb::LushReverbState::new(sr, (sr as u64) * 42);
+                }
+                SignalNode::FundspUnit { state, .. } => {
+                    if let Ok(mut unit) = state.lock() {
+                        unit.set_sample_rate(sr as f64);
+                    }
+                }
+                SignalNode::UserNode { state, .. } => {
+                    if let Ok(mut node) = state.lock() {
+                        node.set_sample_rate(sr);
+                    }
+                }
+                SignalNode::ExternalFx { state, .. } => {
+                    if let Ok(mut fx) = state.lock() {
+                        fx.set_sample_rate(sr);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
     /// Write all tap buffers to their respective files
     /// Call this after rendering is complete to save debug recordings
     pub fn write_tap_files(&self) -> Vec<String> {
@@ -10270,6 +12165,9 @@ impl UnifiedSignalGraph {
             if let Some(node) = node_option {
                 if let SignalNode::Tap { state, .. } = &**node {
                     if let Ok(tap_state) = state.lock() {
+                        if tap_state.filename.is_empty() {
+                            continue; // In-memory recorder
+                        }
                         match tap_state.write_to_file() {
                             Ok(()) => {
                                 written_files.push(tap_state.filename.clone());
@@ -10296,7 +12194,61 @@ impl UnifiedSignalGraph {
             .and_then(|opt| opt.as_ref().map(|rc| &**rc))
     }
 
+    /// Get a mutable reference to a node by its ID, for rewiring its inputs
+    /// after compilation (`mod` routes)
+    pub fn get_node_mut(&mut self, node_id: NodeId) -> Option<&mut SignalNode> {
+        self.nodes
+            .get_mut(node_id.0)
+            .and_then(|opt| opt.as_mut().map(Rc::make_mut))
+    }
+
+    /// IDs of the nodes a node reads from
+    pub fn node_inputs(&self, node_id: NodeId) -> Vec<NodeId> {
+        self.get_node(node_id)
+            .map(|node| {
+                self.get_all_node_inputs(node)
+                    .into_iter()
+                    .map(NodeId)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The ID the next added node will get; nodes added from here on have
+    /// IDs at or above it
+    pub fn next_node_id(&self) -> NodeId {
+        NodeId(self.next_node_id)
+    }
+
+    /// Copies of the nodes from `first` on, by ID (`None` for empty IDs)
+    pub fn copy_nodes_from(&self, first: NodeId) -> Vec<Option<SignalNode>> {
+        self.nodes
+            .iter()
+            .skip(first.0)
+            .map(|node| node.as_deref().cloned())
+            .collect()
+    }
+
+    /// Add copies of `nodes` with IDs from `first` on, leaving the IDs
+    /// skipped to get there empty. Adds nothing and returns false when
+    /// `first` is below the next node ID.
+    pub fn place_nodes(&mut self, first: NodeId, nodes: &[Option<SignalNode>]) -> bool {
+        if nodes.is_empty() {
+            return true;
+        }
+        if first.0 < self.next_node_id.max(self.nodes.len()) {
+            return false;
+        }
+        self.nodes.resize(first.0, None);
+        self.nodes
+            .extend(nodes.iter().map(|node| node.clone().map(std::rc::Rc::new)));
+        self.next_node_id = self.nodes.len();
+        self.max_node_id = self.max_node_id.max(self.next_node_id - 1);
+        true
+    }
+
     /// Add a node to the graph and return its ID
+    #[doc(hidden)]
     pub fn add_node(&mut self, node: SignalNode) -> NodeId {
         let id = NodeId(self.next_node_id);
         self.next_node_id += 1;
@@ -10320,6 +12272,47 @@ impl UnifiedSignalGraph {
         self.buses.insert(name, node_id);
     }
 
+    /// Record the content hash of a bus definition (see [`Self::transfer_bus_states`])
+    pub fn set_bus_hash(&mut self, name: String, hash: u64) {
+        self.bus_hashes.insert(name, hash);
+    }
+
+    /// Mark `node` as continuing bus `name` where the previous graph defined
+    /// it with content hash `hash` (see [`Self::transfer_bus_states`])
+    pub fn continue_bus(&mut self, name: String, hash: u64, node: NodeId) {
+        self.continued_buses.push((name, hash, node));
+    }
+
+    /// Record the content hash of the main output definition
+    pub fn set_output_hash(&mut self, hash: u64) {
+        self.output_hash = Some(hash);
+    }
+
+    /// The content hash recorded for bus `name`
+    pub fn bus_hash(&self, name: &str) -> Option<u64> {
+        self.bus_hashes.get(name).copied()
+    }
+
+    /// The content hash recorded for the main output
+    pub fn output_hash(&self) -> Option<u64> {
+        self.output_hash
+    }
+
+    /// Register a render assertion (`assert rms(~kick) in 0.1..0.4`)
+    pub fn add_assertion(&mut self, assertion: crate::render_assertions::RenderAssertion) {
+        self.assertions.push(assertion);
+    }
+
+    /// Whether the program contains any `assert` statements
+    pub fn has_assertions(&self) -> bool {
+        !self.assertions.is_empty()
+    }
+
+    /// Evaluate all assertions against what has been rendered so far
+    pub fn check_assertions(&self) -> Vec<crate::render_assertions::AssertionOutcome> {
+        self.assertions.iter().map(|a| a.evaluate()).collect()
+    }
+
     /// Get a bus by name
     pub fn get_bus(&self, name: &str) -> Option<NodeId> {
         self.buses.get(name).copied()
@@ -10751,13 +12744,16 @@ impl UnifiedSignalGraph {
         let node = SignalNode::PingPongDelay {
             input,
             time,
+            synced: false,
             feedback,
             stereo_width,
+            filter: Signal::Value(20000.0), // Open
             channel: false, // Start with left channel
             mix,
             buffer_l: vec![0.0; buffer_size],
             buffer_r: vec![0.0; buffer_size],
             write_idx: 0,
+            lpf_state: (0.0, 0.0),
         };
         self.nodes.push(Some(Rc::new(node)));
         node_id
@@ -10917,6 +12913,16 @@ impl UnifiedSignalGraph {
         self.output = Some(node_id);
     }
 
+    /// Main output node, if set
+    pub fn get_output(&self) -> Option<NodeId> {
+        self.output
+    }
+
+    /// Remove the main output, leaving the numbered ones
+    pub fn clear_output(&mut self) {
+        self.output = None;
+    }
+
     /// Check if output is set
     pub fn has_output(&self) -> bool {
         self.output.is_some() || !self.outputs.is_empty()
@@ -10932,6 +12938,38 @@ impl UnifiedSignalGraph {
         self.outputs.insert(channel, node_id);
     }
 
+    /// Set the headphone cue output
+    pub fn set_cue_output(&mut self, node_id: NodeId) {
+        self.cue_output = Some(node_id);
+    }
+
+    /// Headphone cue output node, if set
+    pub fn cue_output(&self) -> Option<NodeId> {
+        self.cue_output
+    }
+
+    /// Cue output of the last rendered buffer, one mono sample per frame
+    /// (empty when the graph has no cue output)
+    pub fn cue_buffer(&self) -> &[f32] {
+        &self.cue_buffer
+    }
+
+    /// Send a node's output, in volts, to a hardware channel (1-indexed)
+    pub fn set_cv_output(&mut self, channel: usize, node_id: NodeId) {
+        self.cv_outputs.insert(channel, node_id);
+    }
+
+    /// Hardware channels with a control voltage output, lowest first
+    pub fn cv_channels(&self) -> Vec<usize> {
+        self.cv_outputs.keys().copied().collect()
+    }
+
+    /// Volts on `channel` over the last rendered buffer, one per frame (empty
+    /// when nothing is sent to it)
+    pub fn cv_buffer(&self, channel: usize) -> &[f32] {
+        self.cv_buffers.get(&channel).map_or(&[], Vec::as_slice)
+    }
+
     /// Silence all output channels
     pub fn hush_all(&mut self) {
         for &channel in self.outputs.keys() {
@@ -10941,6 +12979,12 @@ impl UnifiedSignalGraph {
         if self.output.is_some() {
             self.hushed_channels.insert(0);
         }
+        if self.cue_output.is_some() {
+            self.hushed_channels.insert(CUE_CHANNEL);
+        }
+        if !self.cv_outputs.is_empty() {
+            self.hushed_channels.insert(CV_CHANNEL);
+        }
     }
 
     /// Silence a specific output channel
@@ -10968,6 +13012,27 @@ impl UnifiedSignalGraph {
         self.hush_all();
     }
 
+    /// An `orbit N` node: plays the voices sent to orbit `orbit` with
+    /// `# orbit N`, taking them out of their own pattern's output
+    pub fn add_orbit_node(&mut self, orbit: usize) -> NodeId {
+        if let Some(&node) = self.orbit_nodes.get(&orbit) {
+            return node;
+        }
+        let node = self.add_node(SignalNode::Orbit { orbit });
+        self.orbit_nodes.insert(orbit, node);
+        node
+    }
+
+    /// Route the voice just triggered to `orbit` (0 leaves it on its own node)
+    fn send_last_voice_to_orbit(&self, orbit: usize) {
+        if orbit > 0 {
+            let orbit_node = self.orbit_nodes.get(&orbit).map(|node| node.0);
+            self.voice_manager
+                .borrow_mut()
+                .set_last_voice_orbit(orbit, orbit_node);
+        }
+    }
+
     /// Get the number of currently active voices
     pub fn active_voice_count(&self) -> usize {
         self.voice_manager.borrow().active_voice_count()
@@ -11052,6 +13117,9 @@ impl UnifiedSignalGraph {
             SignalNode::MidiToFreq { midi } => {
                 self.traverse_signal_for_samples(midi, visited, sample_nodes);
             }
+            SignalNode::HzToVolts { freq } => {
+                self.traverse_signal_for_samples(freq, visited, sample_nodes);
+            }
             SignalNode::Mix { signals } => {
                 for signal in signals {
                     self.traverse_signal_for_samples(signal, visited, sample_nodes);
@@ -11097,6 +13165,7 @@ impl UnifiedSignalGraph {
             | SignalNode::Noise { .. }
             | SignalNode::UnitDelay { .. }
             | SignalNode::VoiceOutput
+            | SignalNode::Orbit { .. }
             | SignalNode::MidiVoiceFreq
             | SignalNode::MidiVoiceGate => {
                 // Leaf nodes - no children
@@ -11209,6 +13278,9 @@ impl UnifiedSignalGraph {
                 SignalNode::MidiToFreq { midi } => {
                     self.find_signal_dependencies(midi, visited);
                 }
+                SignalNode::HzToVolts { freq } => {
+                    self.find_signal_dependencies(freq, visited);
+                }
                 SignalNode::Wrap { input, min, max } => {
                     self.find_signal_dependencies(input, visited);
                     self.find_signal_dependencies(min, visited);
@@ -11437,10 +13509,13 @@ impl UnifiedSignalGraph {
             }
             SignalNode::MidiToFreq { midi } => {
                 let midi_val = self.eval_signal_from_buffers(midi, sample_idx);
-                // freq = 440 * 2^((midi - 69) / 12)
-                let freq = 440.0 * (2.0_f32).powf((midi_val - 69.0) / 12.0);
+                let freq = crate::pitch::midi_to_hz(midi_val as f64) as f32;
                 Some(freq)
             }
+            SignalNode::HzToVolts { freq } => {
+                let hz = self.eval_signal_from_buffers(freq, sample_idx);
+                Some(crate::cv::hz_to_volts(hz))
+            }
             SignalNode::Wrap { input, min, max } => {
                 let input_val = self.eval_signal_from_buffers(input, sample_idx);
                 let min_val = self.eval_signal_from_buffers(min, sample_idx);
@@ -11993,6 +14068,41 @@ impl UnifiedSignalGraph {
         self.eval_expression(expr)
     }
 
+    /// `name` (`"snare"` or `"snare:2"`) redirected to the velocity layer a hit
+    /// at `level` plays (`"snare-v2:2"`), with the gain to play it at, or
+    /// `None` if the folder isn't layered
+    fn velocity_layer(&mut self, name: &str, level: f32) -> Option<(String, f32)> {
+        let (folder, index) = match name.split_once(':') {
+            Some((folder, index)) => (folder, Some(index)),
+            None => (name, None),
+        };
+        if !self.velocity_layers.contains_key(folder) {
+            let layers = self.sample_bank.borrow().velocity_layers(folder);
+            self.velocity_layers.insert(folder.to_string(), layers);
+        }
+        let layers = self.velocity_layers.get(folder)?.as_ref()?;
+        let (layer, gain) = crate::sample_loader::pick_velocity_layer(layers, level);
+        let layered = match index {
+            Some(index) => format!("{}-v{}:{}", folder, layer, index),
+            None => format!("{}-v{}", folder, layer),
+        };
+        Some((layered, gain))
+    }
+
+    /// Value of a `RandSpread` node at `cycle_pos`
+    fn eval_rand_spread(&mut self, seed: usize, min: &Signal, max: &Signal, cycle_pos: f64) -> f32 {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let min_val = self.eval_signal_at_time(min, cycle_pos);
+        let max_val = self.eval_signal_at_time(max, cycle_pos);
+        // Onsets are exact fractions of a cycle; rounding keeps float error
+        // out of the seed
+        let time = (cycle_pos * 1e9).round() as i64 as u64;
+        let mut rng =
+            StdRng::seed_from_u64(time ^ (seed as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        min_val + (max_val - min_val) * rng.gen::<f32>()
+    }
+
     /// Evaluate a signal at a specific cycle position
     /// This allows per-event DSP parameter evaluation
     fn eval_signal_at_time(&mut self, signal: &Signal, cycle_pos: f64) -> f32 {
@@ -12003,6 +14113,14 @@ impl UnifiedSignalGraph {
         }
         match signal {
             Signal::Node(id) => {
+                // Random spreads are drawn at `cycle_pos` itself (an event's onset
+                // when evaluating trigger parameters), never from a cached buffer
+                if let Some(Some(node)) = self.nodes.get(id.0) {
+                    if let SignalNode::RandSpread { min, max } = &*Rc::clone(node) {
+                        return self.eval_rand_spread(id.0, min, max, cycle_pos);
+                    }
+                }
+
                 // CYCLE DETECTION: Check if we're already evaluating this node.
                 // If so, we have a circular reference and must break the cycle.
                 if self.eval_call_stack.contains(&id.0) {
@@ -12369,9 +14487,9 @@ impl UnifiedSignalGraph {
                 // >= 1000: Absolute MIDI note (subtract 1000, convert to Hz)
                 // < 1000: Relative semitone offset from base frequency
                 current_freq = if *semitone_offset >= 1000.0 {
-                    // Absolute MIDI: convert to Hz using A4=440Hz as reference
+                    // Absolute MIDI: convert to Hz against the A4 reference
                     let midi = *semitone_offset - 1000.0;
-                    440.0 * 2.0_f32.powf((midi - 69.0) / 12.0)
+                    crate::pitch::midi_to_hz(midi as f64) as f32
                 } else if *semitone_offset != 0.0 {
                     // Relative: apply semitone offset to base frequency
                     current_freq * 2.0_f32.powf(*semitone_offset / 12.0)
@@ -12383,26 +14501,9 @@ impl UnifiedSignalGraph {
                 // Extract phase value to drop borrow immediately
                 let phase_val = {
                     let p = phase.borrow();
-                    *p
-                };
-                let sample = match waveform {
-                    Waveform::Sine => (2.0 * PI * phase_val).sin(),
-                    Waveform::Saw => 2.0 * phase_val - 1.0,
-                    Waveform::Square => {
-                        if phase_val < 0.5 {
-                            1.0
-                        } else {
-                            -1.0
-                        }
-                    }
-                    Waveform::Triangle => {
-                        if phase_val < 0.5 {
-                            4.0 * phase_val - 1.0
-                        } else {
-                            3.0 - 4.0 * phase_val
-                        }
-                    }
+                    *p as f32
                 };
+                let sample = waveform.sample(phase_val, current_freq / self.sample_rate);
 
                 // Update phase and detect zero-crossings
                 if let Some(Some(node)) = self.nodes.get(node_id.0) {
@@ -12435,7 +14536,7 @@ impl UnifiedSignalGraph {
                         // during frequency changes, not for pitch shifting.
                         {
                             let mut p = phase.borrow_mut();
-                            *p += current_freq / self.sample_rate;
+                            *p += current_freq as f64 / self.sample_rate as f64;
                             if *p >= 1.0 {
                                 *p -= 1.0;
                             }
@@ -12463,8 +14564,8 @@ impl UnifiedSignalGraph {
 
                 // FM synthesis: carrier modulated by modulator
                 // output = sin(2π * carrier_phase + mod_index * sin(2π * modulator_phase))
-                let carrier_p = *carrier_phase.borrow();
-                let modulator_p = *modulator_phase.borrow();
+                let carrier_p = *carrier_phase.borrow() as f32;
+                let modulator_p = *modulator_phase.borrow() as f32;
                 let modulator_value = (2.0 * PI * modulator_p).sin();
                 let modulation = index * modulator_value;
                 let sample = (2.0 * PI * carrier_p + modulation).sin();
@@ -12479,7 +14580,7 @@ impl UnifiedSignalGraph {
                     {
                         {
                             let mut cp = carrier_phase.borrow_mut();
-                            *cp += carrier_f / self.sample_rate;
+                            *cp += carrier_f as f64 / self.sample_rate as f64;
                             if *cp >= 1.0 {
                                 *cp -= 1.0;
                             }
@@ -12487,7 +14588,7 @@ impl UnifiedSignalGraph {
 
                         {
                             let mut mp = modulator_phase.borrow_mut();
-                            *mp += modulator_f / self.sample_rate;
+                            *mp += modulator_f as f64 / self.sample_rate as f64;
                             if *mp >= 1.0 {
                                 *mp -= 1.0;
                             }
@@ -12511,7 +14612,7 @@ impl UnifiedSignalGraph {
 
                 // PM synthesis: carrier phase modulated directly by external signal
                 // output = sin(2π * carrier_phase + mod_index * modulation_signal)
-                let carrier_p = *carrier_phase.borrow();
+                let carrier_p = *carrier_phase.borrow() as f32;
                 let modulation_value = index * mod_signal;
                 let sample = (2.0 * PI * carrier_p + modulation_value).sin();
 
@@ -12519,7 +14620,7 @@ impl UnifiedSignalGraph {
                 if let Some(Some(node)) = self.nodes.get(node_id.0) {
                     if let SignalNode::PMOscillator { carrier_phase, .. } = &**node {
                         let mut cp = carrier_phase.borrow_mut();
-                        *cp += carrier_f / self.sample_rate;
+                        *cp += carrier_f as f64 / self.sample_rate as f64;
                         if *cp >= 1.0 {
                             *cp -= 1.0;
                         }
@@ -13903,6 +16004,34 @@ impl UnifiedSignalGraph {
 
             SignalNode::Constant { value } => *value,
 
+            SignalNode::SpatialGain {
+                input,
+                azimuth,
+                elevation,
+                layout,
+                channel,
+            } => {
+                let gain = layout.gain(
+                    *channel,
+                    self.eval_signal(azimuth),
+                    self.eval_signal(elevation),
+                );
+                self.eval_signal(input) * gain
+            }
+
+            SignalNode::FrozenBus { buffer, cycles } => {
+                let len = buffer.len();
+                if len == 0 {
+                    0.0
+                } else {
+                    let phase = self.get_cycle_position().rem_euclid(*cycles) / cycles;
+                    let pos = phase * len as f64;
+                    let idx = (pos as usize).min(len - 1);
+                    let frac = (pos - idx as f64) as f32;
+                    buffer[idx] + (buffer[(idx + 1) % len] - buffer[idx]) * frac
+                }
+            }
+
             SignalNode::Phasor { speed } => {
                 // Cycle-synced ramp from 0 to 1
                 let speed_val = self.eval_signal(speed);
@@ -13911,6 +16040,51 @@ impl UnifiedSignalGraph {
                 ((cycle_pos * speed_val as f64) % 1.0) as f32
             }
 
+            SignalNode::Lfo {
+                rate,
+                synced,
+                shape,
+                phase_offset,
+                min,
+                max,
+                oneshot,
+                state,
+            } => {
+                let mut state = state.clone();
+                if state.countdown == 0 {
+                    let rate_val = self.eval_signal(rate) as f64;
+                    let min_val = self.eval_signal(min);
+                    let max_val = self.eval_signal(max);
+
+                    let (now, next) = if *synced {
+                        let cycle = self.get_cycle_position();
+                        let later = self.get_cycle_position_for_sample_offset(LFO_CONTROL_INTERVAL);
+                        let start = if *oneshot {
+                            *state.start_cycle.get_or_insert(cycle)
+                        } else {
+                            0.0
+                        };
+                        let period = rate_val.max(1e-6);
+                        ((cycle - start) / period, (later - start) / period)
+                    } else {
+                        // The phase keeps counting periods, which `random` uses
+                        let now = state.phase;
+                        state.phase +=
+                            rate_val * LFO_CONTROL_INTERVAL as f64 / self.sample_rate as f64;
+                        (now, state.phase)
+                    };
+                    state.schedule(*shape, *phase_offset, *oneshot, min_val, max_val, now, next);
+                }
+                let value = state.next_sample();
+
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    if let SignalNode::Lfo { state: s, .. } = Rc::make_mut(node_rc) {
+                        *s = state;
+                    }
+                }
+                value
+            }
+
             SignalNode::PatternEvaluator { pattern } => {
                 // Evaluate the pattern at the current cycle position
                 use crate::pattern::{Fraction, State, TimeSpan};
@@ -14340,11 +16514,15 @@ impl UnifiedSignalGraph {
                     }
                 }
 
-                // Fall back to stub plugin instance or silence
+                // Fall back to stub plugin instance; without one an effect
+                // passes its input through and an instrument is silent
                 let mut inst_ref = instance.borrow_mut();
                 if inst_ref.is_none() {
-                    tracing::debug!("Plugin {} not loaded, returning silence", plugin_id);
-                    return 0.0;
+                    tracing::debug!("Plugin {} not loaded, bypassing", plugin_id);
+                    return match audio_inputs.first() {
+                        Some(input) => self.eval_signal(input),
+                        None => 0.0,
+                    };
                 }
 
                 let inst = inst_ref.as_mut().unwrap();
@@ -14363,10 +16541,15 @@ impl UnifiedSignalGraph {
                 }
             }
 
-            SignalNode::EveryEffect { input, effect, n } => {
-                // Apply effect every N cycles, bypass otherwise
+            SignalNode::EveryEffect {
+                input,
+                effect,
+                n,
+                offset,
+            } => {
+                // Apply effect every N cycles (starting at offset), bypass otherwise
                 let current_cycle = self.get_cycle_position().floor() as i32;
-                if current_cycle % n == 0 {
+                if current_cycle.rem_euclid(*n) == *offset {
                     self.eval_signal_at_time(effect, self.get_cycle_position())
                 } else {
                     self.eval_signal_at_time(input, self.get_cycle_position())
@@ -14397,9 +16580,9 @@ impl UnifiedSignalGraph {
                 modulo,
                 offset,
             } => {
-                // Apply effect when (cycle - offset) % modulo == 0
+                // Apply effect on the last cycles of each phrase (Tidal whenmod)
                 let current_cycle = self.get_cycle_position().floor() as i32;
-                if (current_cycle - offset) % modulo == 0 {
+                if current_cycle.rem_euclid(*modulo) >= *offset {
                     self.eval_signal_at_time(effect, self.get_cycle_position())
                 } else {
                     self.eval_signal_at_time(input, self.get_cycle_position())
@@ -14414,8 +16597,14 @@ impl UnifiedSignalGraph {
 
             SignalNode::MidiToFreq { midi } => {
                 let midi_val = self.eval_signal(midi);
-                // freq = 440 * 2^((midi - 69) / 12)
-                440.0 * (2.0_f32).powf((midi_val - 69.0) / 12.0)
+                crate::pitch::midi_to_hz(midi_val as f64) as f32
+            }
+
+            SignalNode::HzToVolts { freq } => crate::cv::hz_to_volts(self.eval_signal(freq)),
+
+            SignalNode::RandSpread { min, max } => {
+                let cycle_pos = self.get_cycle_position();
+                self.eval_rand_spread(node_id.0, min, max, cycle_pos)
             }
 
             SignalNode::Wrap { input, min, max } => {
@@ -14594,18 +16783,206 @@ impl UnifiedSignalGraph {
                 let mix = (width - 1.0).abs();
                 let y = x * (1.0 - mix * 0.3) + phase_shifted * mix * 0.3;
 
-                // Persist the advanced filter state.
+                // Persist the advanced filter state.
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    let node = Rc::make_mut(node_rc);
+                    if let SignalNode::StereoWidener { state, .. } = node {
+                        state.allpass = allpass;
+                        state.sample_rate = state_sr;
+                    }
+                }
+
+                y
+            }
+
+            SignalNode::Reverse { input, .. } => {
+                let x = self.eval_signal(input);
+                if self.bypass_sequential_effects {
+                    return x;
+                }
+
+                let mut y = 0.0;
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    if let SignalNode::Reverse { state, .. } = Rc::make_mut(node_rc) {
+                        y = state.process(x);
+                    }
+                }
+                y
+            }
+
+            SignalNode::Varispeed {
+                input,
+                pattern,
+                mode,
+                ..
+            } => {
+                let x = self.eval_signal(input);
+                if self.bypass_sequential_effects {
+                    return x;
+                }
+
+                // The "on" event under the playhead, if any
+                let position = self.get_cycle_position();
+                let sample_width = 1.0 / self.sample_rate as f64 / self.cps as f64;
+                let query_state = State {
+                    span: TimeSpan::new(
+                        Fraction::from_float(position),
+                        Fraction::from_float(position + sample_width),
+                    ),
+                    controls: HashMap::new(),
+                };
+                let event = pattern
+                    .query(&query_state)
+                    .into_iter()
+                    .filter(|event| event.value > 0.0)
+                    .find_map(|event| {
+                        let whole = event.whole?;
+                        Some((whole.begin.to_float(), whole.end.to_float(), event.value))
+                    });
+
+                // Samples per cycle; elapsed and length of the event in samples
+                let cycle = self.sample_rate as f64 / self.cps as f64;
+                let (delay, gain) = match (mode, event) {
+                    (_, None) => (0.0, 1.0),
+                    (VarispeedMode::TapeStop, Some((begin, end, _))) => {
+                        // Speed falls linearly from 1 to 0, so the read head
+                        // falls behind by length * p^2 / 2; fade out the last
+                        // eighth, where the pitch is too low to hear
+                        let length = (end - begin) * cycle;
+                        let p = ((position - begin) / (end - begin)).clamp(0.0, 1.0);
+                        (length * p * p / 2.0, ((1.0 - p) * 8.0).min(1.0) as f32)
+                    }
+                    (VarispeedMode::Stutter { division }, Some((begin, _, _))) => {
+                        // Replay the slice that started the event; 2ms edges
+                        // keep the loop points from clicking
+                        let slice = cycle / division;
+                        let elapsed = (position - begin) * cycle;
+                        let offset = elapsed % slice;
+                        let edge = offset.min(slice - offset);
+                        let fade = self.sample_rate as f64 * 0.002;
+                        (elapsed - offset, (edge / fade).min(1.0) as f32)
+                    }
+                    (VarispeedMode::BeatRepeat { decay, pitch }, Some((begin, _, n))) => {
+                        // Read the slice before the event, `pitch` times as
+                        // fast, starting over every slice
+                        let slice = cycle / n;
+                        let elapsed = (position - begin) * cycle;
+                        let offset = elapsed % slice;
+                        let repeat = (elapsed / slice).floor() as i32;
+                        let phase = (offset * pitch) % slice;
+                        let edge = phase.min(slice - phase).min(offset).min(slice - offset);
+                        let fade = self.sample_rate as f64 * 0.002;
+                        (
+                            elapsed + slice - phase,
+                            decay.powi(repeat) * (edge / fade).min(1.0) as f32,
+                        )
+                    }
+                };
+
+                let mut y = x;
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    if let SignalNode::Varispeed { state, .. } = Rc::make_mut(node_rc) {
+                        y = state.process(x, delay) * gain;
+                    }
+                }
+                y
+            }
+
+            SignalNode::TiltEq {
+                input,
+                tilt,
+                pivot,
+                air,
+                ..
+            } => {
+                let x = self.eval_signal(input);
+                let tilt = self.eval_signal(tilt).clamp(-24.0, 24.0);
+                let pivot = self.eval_signal(pivot);
+                let air = self.eval_signal(air).clamp(-24.0, 24.0);
+                let sr = self.sample_rate;
+
+                let mut y = x;
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    if let SignalNode::TiltEq { state, .. } = Rc::make_mut(node_rc) {
+                        y = state.process(x, tilt, pivot, air, sr);
+                    }
+                }
+                y
+            }
+
+            SignalNode::Saturate {
+                input,
+                drive,
+                mix,
+                curve,
+                ..
+            } => {
+                let x = self.eval_signal(input);
+                let drive = self.eval_signal(drive).clamp(1.0, 100.0);
+                let mix = self.eval_signal(mix).clamp(0.0, 1.0);
+                let curve = *curve;
+                let oversample = self.quality.oversampling();
+
+                let mut y = x;
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    if let SignalNode::Saturate { state, .. } = Rc::make_mut(node_rc) {
+                        y = state.process(x, drive, mix, curve, oversample);
+                    }
+                }
+                y
+            }
+
+            SignalNode::Waveshaper {
+                input,
+                shape,
+                a,
+                b,
+                mix,
+                ..
+            } => {
+                let x = self.eval_signal(input);
+                let a = self.eval_signal(a);
+                let b = self.eval_signal(b);
+                let mix = self.eval_signal(mix).clamp(0.0, 1.0);
+                let shape = *shape;
+                let oversample = self.quality.oversampling();
+
+                let mut y = x;
                 if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
-                    let node = Rc::make_mut(node_rc);
-                    if let SignalNode::StereoWidener { state, .. } = node {
-                        state.allpass = allpass;
-                        state.sample_rate = state_sr;
+                    if let SignalNode::Waveshaper { oversampler, .. } = Rc::make_mut(node_rc) {
+                        let (wet, dry) = if oversample {
+                            oversampler.process(x, |x| shape.apply(x, a, b))
+                        } else {
+                            oversampler.process_plain(x, |x| shape.apply(x, a, b))
+                        };
+                        y = dry * (1.0 - mix) + wet * mix;
                     }
                 }
-
                 y
             }
 
+            SignalNode::SyncOscillator {
+                freq,
+                waveform,
+                sync,
+                retrig,
+                ..
+            } => {
+                let phase_inc = self.eval_signal(freq) / self.sample_rate;
+                let sync = sync.as_ref().map(|s| self.eval_signal(s));
+                let retrig = retrig.as_ref().map(|s| self.eval_signal(s));
+                let waveform = *waveform;
+
+                let mut sample = 0.0;
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    if let SignalNode::SyncOscillator { state, .. } = Rc::make_mut(node_rc) {
+                        let phase = state.step(phase_inc, sync, retrig);
+                        sample = waveform.sample(phase, phase_inc);
+                    }
+                }
+                sample
+            }
+
             SignalNode::LowPass {
                 input, cutoff, q, ..
             } => {
@@ -14699,10 +17076,12 @@ impl UnifiedSignalGraph {
                 let room = self.eval_signal(room_size).clamp(0.0, 1.0);
                 let damp = self.eval_signal(damping).clamp(0.0, 1.0);
                 let mix_val = self.eval_signal(mix).clamp(0.0, 1.0);
+                // Fewer combs when the CPU is short (adaptive quality)
+                let combs = self.quality.reverb_combs();
 
                 // Process comb filters (parallel)
                 let mut comb_out = 0.0;
-                for i in 0..8 {
+                for i in 0..combs {
                     let buf_len = state.comb_buffers[i].len();
                     let read_idx = state.comb_indices[i];
                     let delayed = state.comb_buffers[i][read_idx];
@@ -14712,7 +17091,7 @@ impl UnifiedSignalGraph {
 
                     // Feedback
                     let feedback = 0.84 * room;
-                    let to_write = input_val + filtered * feedback;
+                    let to_write = flush_denormal(input_val + filtered * feedback);
 
                     comb_out += delayed;
 
@@ -14722,12 +17101,12 @@ impl UnifiedSignalGraph {
                         if let SignalNode::Reverb { state: s, .. } = node {
                             s.comb_buffers[i][read_idx] = to_write;
                             s.comb_indices[i] = (read_idx + 1) % buf_len;
-                            s.comb_filter_stores[i] = filtered;
+                            s.comb_filter_stores[i] = flush_denormal(filtered);
                         }
                     }
                 }
 
-                let mut allpass_out = comb_out / 8.0;
+                let mut allpass_out = comb_out / combs as f32;
 
                 // Process allpass filters (series)
                 for i in 0..4 {
@@ -14735,7 +17114,7 @@ impl UnifiedSignalGraph {
                     let read_idx = state.allpass_indices[i];
                     let delayed = state.allpass_buffers[i][read_idx];
 
-                    let to_write = allpass_out + delayed * 0.5;
+                    let to_write = flush_denormal(allpass_out + delayed * 0.5);
                     allpass_out = delayed - allpass_out * 0.5;
 
                     if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
@@ -14776,6 +17155,7 @@ impl UnifiedSignalGraph {
                 let damping_val = self.eval_signal(damping).clamp(0.0, 1.0);
                 let mod_depth_val = self.eval_signal(mod_depth).clamp(0.0, 1.0);
                 let mix_val = self.eval_signal(mix).clamp(0.0, 1.0);
+                let diffusers = self.quality.reverb_diffusers();
 
                 // Helper function for allpass filter
                 // y[n] = -x[n] + x[n-D] + g * (x[n] - y[n-D])
@@ -14820,11 +17200,11 @@ impl UnifiedSignalGraph {
                             input_val
                         };
 
-                        // 2. INPUT DIFFUSION (4 series allpass filters)
+                        // 2. INPUT DIFFUSION (4 series allpass filters, 2 at reduced quality)
                         let input_diffusion_gain = 0.75 * diffusion_val;
                         let mut diffused = predelay_out;
 
-                        for i in 0..4 {
+                        for i in 0..diffusers {
                             diffused = allpass(
                                 &mut s.input_diffusion_buffers[i],
                                 &mut s.input_diffusion_indices[i],
@@ -14852,7 +17232,8 @@ impl UnifiedSignalGraph {
 
                         // LEFT TANK
                         // Read previous right tank output for cross-coupling
-                        let right_to_left = s.right_delay2_buffer[s.right_delay2_idx];
+                        let right_to_left =
+                            s.shimmer.process(s.right_delay2_buffer[s.right_delay2_idx]);
 
                         // Input to left tank (with cross-coupling from right)
                         let left_input = input_to_tanks + right_to_left * decay_gain;
@@ -15281,13 +17662,20 @@ impl UnifiedSignalGraph {
                 input,
                 attack_db,
                 sustain_db,
+                linear,
                 state,
             } => {
                 let input_val = self.eval_signal(input);
-                let attack_db_v = self.eval_signal(attack_db).clamp(-40.0, 40.0);
-                let sustain_db_v = self.eval_signal(sustain_db).clamp(-40.0, 40.0);
-                let attack_lin = 10.0_f32.powf(attack_db_v / 20.0);
-                let sustain_lin = 10.0_f32.powf(sustain_db_v / 20.0);
+                let attack_v = self.eval_signal(attack_db);
+                let sustain_v = self.eval_signal(sustain_db);
+                let (attack_lin, sustain_lin) = if *linear {
+                    (attack_v.clamp(0.01, 100.0), sustain_v.clamp(0.01, 100.0))
+                } else {
+                    (
+                        10.0_f32.powf(attack_v.clamp(-40.0, 40.0) / 20.0),
+                        10.0_f32.powf(sustain_v.clamp(-40.0, 40.0) / 20.0),
+                    )
+                };
                 let (fast_a, fast_r, slow_a, slow_r) =
                     transient_shaper_coeffs(self.sample_rate);
 
@@ -15493,6 +17881,44 @@ impl UnifiedSignalGraph {
                 input_val * gain_boost
             }
 
+            SignalNode::NoiseGate {
+                input,
+                key,
+                threshold,
+                hysteresis,
+                attack,
+                hold,
+                release,
+                state,
+            } => {
+                let input_val = self.eval_signal(input);
+                // The key, when given, opens and closes the gate instead of the input
+                let key_val = match key {
+                    Some(key) => self.eval_signal(key),
+                    None => input_val,
+                };
+                let settings = GateSettings {
+                    threshold_db: self.eval_signal(threshold).clamp(-100.0, 0.0),
+                    hysteresis_db: self.eval_signal(hysteresis).clamp(0.0, 40.0),
+                    attack: self.eval_signal(attack).clamp(0.0001, 1.0),
+                    hold: self.eval_signal(hold).clamp(0.0, 5.0),
+                    release: self.eval_signal(release).clamp(0.001, 5.0),
+                };
+
+                let mut gate = state.clone();
+                let gain = gate.process(key_val, &settings, self.sample_rate);
+
+                // Persist detector and envelope state
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    let node = Rc::make_mut(node_rc);
+                    if let SignalNode::NoiseGate { state: s, .. } = node {
+                        *s = gate;
+                    }
+                }
+
+                input_val * gain
+            }
+
             SignalNode::AdaptiveCompressor {
                 main_input,
                 sidechain_input,
@@ -15936,6 +18362,46 @@ impl UnifiedSignalGraph {
                 }
             }
 
+            SignalNode::UserNode { inputs, state, .. } => {
+                // Per-sample path: the node sees one-sample blocks
+                let input_values: Vec<f32> = inputs
+                    .iter()
+                    .map(|signal| self.eval_signal(signal))
+                    .collect();
+                let input_slices: Vec<&[f32]> =
+                    input_values.iter().map(std::slice::from_ref).collect();
+                let context = crate::audio_node::ProcessContext::new(
+                    Fraction::from_float(self.get_cycle_position()),
+                    0,
+                    1,
+                    self.cps as f64,
+                    self.sample_rate,
+                );
+                let mut output = [0.0f32];
+                // try_lock + silence fallback: never panic the render thread
+                if let Ok(mut node) = state.try_lock() {
+                    node.process(&input_slices, &mut output, &context);
+                }
+                output[0]
+            }
+
+            SignalNode::ExternalFx {
+                input,
+                params,
+                state,
+            } => {
+                let sample = self.eval_signal(input);
+                let mut values = PluginParamScratch::acquire();
+                for signal in params {
+                    values.push(self.eval_signal(signal));
+                }
+                // try_lock + passthrough fallback: never block the render thread
+                match state.try_lock() {
+                    Ok(mut fx) => fx.process_sample(sample, &values),
+                    Err(_) => sample,
+                }
+            }
+
             SignalNode::Tap { input, state } => {
                 // Evaluate input signal
                 let sample = self.eval_signal(input);
@@ -15949,6 +18415,37 @@ impl UnifiedSignalGraph {
                 sample
             }
 
+            SignalNode::AutoGain { input, .. } => {
+                let sample = self.eval_signal(input);
+                let mut gain_val = 1.0;
+
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    if let SignalNode::AutoGain {
+                        target,
+                        time,
+                        mean_square,
+                        gain,
+                        ..
+                    } = Rc::make_mut(node_rc)
+                    {
+                        let window_coeff =
+                            1.0 - (-1.0 / (AUTOGAIN_WINDOW_SECS * self.sample_rate)).exp();
+                        *mean_square += (sample * sample - *mean_square) * window_coeff;
+
+                        let rms = mean_square.sqrt();
+                        if rms > AUTOGAIN_GATE {
+                            let wanted =
+                                (*target / rms).clamp(1.0 / AUTOGAIN_RANGE, AUTOGAIN_RANGE);
+                            let coeff = 1.0 - (-1.0 / (*time * self.sample_rate)).exp();
+                            *gain += (wanted - *gain) * coeff;
+                        }
+                        gain_val = *gain;
+                    }
+                }
+
+                sample * gain_val
+            }
+
             SignalNode::Output { input } => self.eval_signal(input),
 
             SignalNode::Pattern {
@@ -16326,6 +18823,13 @@ impl UnifiedSignalGraph {
                             }
                         }
 
+                        // Groove files with velocities scale each hit (set by groove)
+                        if let Some(groove_gain) = event.context.get("groove_gain") {
+                            if let Ok(groove_mult) = groove_gain.parse::<f32>() {
+                                gain_val *= groove_mult;
+                            }
+                        }
+
                         // Check event context for pan override (set by transforms like jux)
                         let pan_val = if let Some(pan_str) = event.context.get("pan") {
                             pan_str.parse::<f32>().unwrap_or(0.0).clamp(-1.0, 1.0)
@@ -16358,17 +18862,48 @@ impl UnifiedSignalGraph {
 
                         // Modify sample name with n index if n > 0
                         // e.g., "bd" with n=2 becomes "bd:2"
-                        let final_sample_name = if n_index > 0 {
+                        // `hh:rr` takes the folder's files in turn, one per trigger
+                        // (offset by n); the bank wraps the index to the file count
+                        let round_robin =
+                            actual_name.strip_suffix(":rr").filter(|_| !is_bus_trigger);
+                        let final_sample_name = if let Some(folder) = round_robin {
+                            let next = self.round_robin.entry(folder.to_string()).or_insert(0);
+                            let index = *next + n_index;
+                            *next += 1;
+                            format!("{}:{}", folder, index)
+                        } else if n_index > 0 {
                             format!("{}:{}", actual_name, n_index)
                         } else {
                             actual_name.to_string()
                         };
 
+                        // Velocity-layered kits (`snare-v1`, `snare-v2`, ...) play the
+                        // layer for this hit's level, scaled within that layer
+                        let layered = if is_bus_trigger {
+                            None
+                        } else {
+                            self.velocity_layer(&final_sample_name, gain_val)
+                        };
+                        let final_sample_name = match layered {
+                            Some((layered_name, layer_gain)) => {
+                                gain_val = layer_gain;
+                                layered_name
+                            }
+                            None => final_sample_name,
+                        };
+
                         // Evaluate note modifier for pitch shifting
                         // Note is in semitones: 0 = original, 12 = octave up, -12 = octave down
                         // Supports: numbers (5), letter notes (c4, e4, g4), solfège (do, re, mi)
                         // Also supports chord notation: "c4'maj" -> vec![0, 4, 7] (C, E, G)
                         let chord_notes = self.eval_note_signal_as_chord(note, event_start_abs);
+                        // Samples triggered with a note follow the global transpose
+                        // and A4 reference; plain hits keep their recorded pitch
+                        let global_pitch = if matches!(note, Signal::Value(v) if *v == 0.0) {
+                            1.0
+                        } else {
+                            crate::pitch::sample_speed_ratio() as f32
+                        };
 
                         // CRITICAL: If note pattern returned empty (rest), skip this event entirely
                         // This handles `# note "~ c4"` where ~ should produce silence
@@ -16376,6 +18911,22 @@ impl UnifiedSignalGraph {
                             continue;
                         }
 
+                        let log_name = if is_bus_trigger {
+                            sample_name
+                        } else {
+                            final_sample_name.as_str()
+                        };
+                        self.log_event(event_start_abs, log_name, || {
+                            let mut params = format!(
+                                "gain={:.2} pan={:.2} speed={:.2}",
+                                gain_val, pan_val, speed_val
+                            );
+                            if chord_notes != [0.0] {
+                                params.push_str(&format!(" note={:?}", chord_notes));
+                            }
+                            params
+                        });
+
                         // Scale gain by 1/sqrt(n) to prevent clipping when multiple voices sum
                         // Using sqrt gives perceptually correct loudness (RMS scaling)
                         let chord_size = chord_notes.len();
@@ -16472,6 +19023,60 @@ impl UnifiedSignalGraph {
                                 .clamp(0.0, 1.0)
                         };
 
+                        // Per-voice effects (set in the event context by
+                        // `# shape`, `# crush`, `# coarse`, `# squiz`)
+                        let fx_value = |key: &str| {
+                            event
+                                .context
+                                .get(key)
+                                .and_then(|value| value.parse::<f32>().ok())
+                                .unwrap_or(0.0)
+                        };
+                        let mut voice_fx = VoiceFx {
+                            shape: fx_value("shape").clamp(0.0, 1.0),
+                            crush: fx_value("crush").clamp(0.0, 32.0),
+                            coarse: fx_value("coarse").clamp(0.0, 4096.0),
+                            squiz: fx_value("squiz").clamp(0.0, 64.0),
+                            fold: fx_value("fold").clamp(0.0, 1.0),
+                        };
+                        // The West Coast voice folds its own oscillator, before
+                        // its gate, and is struck as hard as `# strike` says
+                        let westcoast = (!is_bus_trigger
+                            && final_sample_name == crate::westcoast::VOICE_NAME)
+                            .then(|| {
+                                let strike = event
+                                    .context
+                                    .get("strike")
+                                    .and_then(|value| value.parse::<f32>().ok())
+                                    .unwrap_or(crate::westcoast::DEFAULT_STRIKE);
+                                (std::mem::take(&mut voice_fx.fold), strike)
+                            });
+                        // A pluck note is set going by `# exciter` and rings for
+                        // as long as `# decay` and `# damping` say
+                        let pluck = (!is_bus_trigger
+                            && final_sample_name == crate::pluck::VOICE_NAME)
+                            .then(|| {
+                                let setting = |key: &str, default: f32| {
+                                    event
+                                        .context
+                                        .get(key)
+                                        .and_then(|value| value.parse::<f32>().ok())
+                                        .unwrap_or(default)
+                                };
+                                let exciter = event
+                                    .context
+                                    .get("exciter")
+                                    .and_then(|name| crate::pluck::Exciter::from_name(name))
+                                    .unwrap_or_default();
+                                (
+                                    exciter,
+                                    setting("decay", crate::pluck::DEFAULT_DECAY),
+                                    setting("damping", crate::pluck::DEFAULT_DAMPING),
+                                )
+                            });
+                        // Output orbit (set by `# orbit`), 0 = this node's own bus
+                        let orbit = fx_value("orbit").max(0.0) as usize;
+
                         // DEBUG: Print cut group info
                         if self.debug_flags.cut_groups {
                             eprintln!("Triggering {} at cycle {:.3}, cut_group_val={:.1}, cut_group_opt={:?}",
@@ -16500,7 +19105,7 @@ impl UnifiedSignalGraph {
                             } else {
                                 1.0
                             };
-                            let final_speed = speed_val * pitch_shift_multiplier;
+                            let final_speed = speed_val * pitch_shift_multiplier * global_pitch;
 
                             // Handle bus triggering vs regular sample loading
                             if is_bus_trigger {
@@ -16555,6 +19160,8 @@ impl UnifiedSignalGraph {
                                         note_semitones, // Pitch offset for note parameter
                                     );
 
+                                    self.send_last_voice_to_orbit(orbit);
+
                                     // Note: unit mode and loop don't apply to synthesis voices
                                     // Synthesis continues until envelope finishes
                                 } else {
@@ -16564,9 +19171,28 @@ impl UnifiedSignalGraph {
                                     );
                                 }
                             } else {
-                                // Regular sample loading
-                                let sample_data_opt =
-                                    self.sample_bank.borrow_mut().get_sample(&final_sample_name);
+                                // Regular sample loading; a West Coast or pluck note is
+                                // rendered at its pitch and played at unit speed
+                                let sample_data_opt = if let Some((fold, strike)) = westcoast {
+                                    let freq = crate::westcoast::BASE_HZ * final_speed.abs();
+                                    let note = crate::westcoast::WestCoast::new(freq, fold, strike);
+                                    Some(std::sync::Arc::new(note.render(self.sample_rate)))
+                                } else if let Some((exciter, decay, damping)) = pluck {
+                                    let freq = crate::pluck::BASE_HZ * final_speed.abs();
+                                    let note =
+                                        crate::pluck::Pluck::new(freq, exciter, decay, damping);
+                                    let mut rng = NoiseRng::seeded_default();
+                                    Some(std::sync::Arc::new(
+                                        note.render(self.sample_rate, &mut rng),
+                                    ))
+                                } else {
+                                    self.sample_bank.borrow_mut().get_sample(&final_sample_name)
+                                };
+                                let final_speed = if westcoast.is_some() || pluck.is_some() {
+                                    1.0
+                                } else {
+                                    final_speed
+                                };
                                 // DEBUG: Log sample loading
                                 if self.debug_flags.sample_events
                                     && self.sample_count < 20
@@ -16783,6 +19409,8 @@ impl UnifiedSignalGraph {
                                     self.voice_manager
                                         .borrow_mut()
                                         .set_last_voice_loop_enabled(loop_enabled_bool);
+                                    self.voice_manager.borrow_mut().set_last_voice_fx(voice_fx);
+                                    self.send_last_voice_to_orbit(orbit);
                                 }
                             }
                         } // End chord loop
@@ -16954,8 +19582,8 @@ impl UnifiedSignalGraph {
                         // Convert Waveform to SynthWaveform (once for all chord notes)
                         let synth_waveform = match waveform {
                             Waveform::Sine => SynthWaveform::Sine,
-                            Waveform::Saw => SynthWaveform::Saw,
-                            Waveform::Square => SynthWaveform::Square,
+                            Waveform::Saw | Waveform::RawSaw => SynthWaveform::Saw,
+                            Waveform::Square | Waveform::RawSquare => SynthWaveform::Square,
                             Waveform::Triangle => SynthWaveform::Triangle,
                         };
 
@@ -16990,6 +19618,13 @@ impl UnifiedSignalGraph {
                         };
                         let scaled_gain = gain_val * chord_gain_scale;
 
+                        self.log_event(event_start_abs, note_name, || {
+                            format!(
+                                "gain={:.2} pan={:.2} voices={}",
+                                gain_val, pan_val, chord_size
+                            )
+                        });
+
                         for frequency in note_frequencies {
                             self.synth_voice_manager.borrow_mut().trigger_note(
                                 frequency,
@@ -17069,8 +19704,8 @@ impl UnifiedSignalGraph {
 
                                 let synth_waveform = match waveform {
                                     Waveform::Sine => SynthWaveform::Sine,
-                                    Waveform::Saw => SynthWaveform::Saw,
-                                    Waveform::Square => SynthWaveform::Square,
+                                    Waveform::Saw | Waveform::RawSaw => SynthWaveform::Saw,
+                                    Waveform::Square | Waveform::RawSquare => SynthWaveform::Square,
                                     Waveform::Triangle => SynthWaveform::Triangle,
                                 };
 
@@ -17316,18 +19951,7 @@ impl UnifiedSignalGraph {
                             voice.phase -= 1.0;
                         }
 
-                        let osc_out = match waveform {
-                            Waveform::Sine => (voice.phase * std::f32::consts::TAU).sin(),
-                            Waveform::Saw => 2.0 * voice.phase - 1.0,
-                            Waveform::Square => if voice.phase < 0.5 { 1.0 } else { -1.0 },
-                            Waveform::Triangle => {
-                                if voice.phase < 0.5 {
-                                    4.0 * voice.phase - 1.0
-                                } else {
-                                    3.0 - 4.0 * voice.phase
-                                }
-                            }
-                        };
+                        let osc_out = waveform.sample(voice.phase, phase_inc);
 
                         // Apply envelope and sum
                         output += osc_out * voice.envelope_level;
@@ -17358,6 +19982,18 @@ impl UnifiedSignalGraph {
                 self.voice_manager.borrow_mut().process()
             }
 
+            SignalNode::Orbit { .. } => {
+                // The voices routed here by `# orbit` carry this node's id as
+                // their source node
+                let buffer_output = self.voice_buffers.get(node_id.0, self.current_sample_idx);
+                let newly_triggered = self
+                    .voice_output_cache
+                    .get(&node_id.0)
+                    .copied()
+                    .unwrap_or(0.0);
+                buffer_output + newly_triggered
+            }
+
             SignalNode::ScaleQuantize {
                 pattern,
                 scale_name,
@@ -17719,7 +20355,7 @@ impl UnifiedSignalGraph {
                         ..
                     } = node
                     {
-                        buf[*idx] = output;
+                        buf[*idx] = flush_denormal(output);
                         *idx = (*idx + 1) % buf.len();
                     }
                 }
@@ -18901,9 +21537,15 @@ impl UnifiedSignalGraph {
                 output
             }
 
-            SignalNode::PatternGate { pattern, .. } => {
+            SignalNode::PatternGate {
+                pattern,
+                retrigger_gap,
+                ..
+            } => {
                 // Query pattern and output 1.0 if inside a true event, 0.0 otherwise
                 let sample_width = 1.0 / self.sample_rate as f64 / self.cps as f64;
+                let now = self.get_cycle_position();
+                let gap = *retrigger_gap * self.cps as f64;
 
                 let query_state = State {
                     span: TimeSpan::new(
@@ -18914,8 +21556,14 @@ impl UnifiedSignalGraph {
                 };
                 let events = pattern.query(&query_state);
 
-                // Output 1.0 if any true event is active
-                let gate_active = events.iter().any(|e| e.value);
+                // Output 1.0 if any true event is active (and not in its last
+                // `retrigger_gap` seconds)
+                let gate_active = events.iter().any(|e| {
+                    e.value
+                        && e.whole
+                            .as_ref()
+                            .map_or(true, |whole| whole.end.to_float() - now > gap)
+                });
                 if gate_active {
                     1.0
                 } else {
@@ -19019,7 +21667,7 @@ impl UnifiedSignalGraph {
 
                 // Write to delay line (input + feedback)
                 // Apply soft clipping to prevent feedback explosion
-                let to_write = (input_val + delayed * fb).tanh();
+                let to_write = flush_denormal((input_val + delayed * fb).tanh());
 
                 // Update buffer and write index
                 if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
@@ -19111,7 +21759,7 @@ impl UnifiedSignalGraph {
                 let filtered = state.lpf_state * cutoff_coef + saturated * (1.0 - cutoff_coef);
 
                 // Write to buffer
-                let to_write = input_val + filtered * fb;
+                let to_write = flush_denormal(input_val + filtered * fb);
 
                 // Update state
                 if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
@@ -19169,7 +21817,7 @@ impl UnifiedSignalGraph {
                 tap_sum /= tap_count as f32;
 
                 // Write with feedback
-                let to_write = input_val + tap_sum * fb;
+                let to_write = flush_denormal(input_val + tap_sum * fb);
 
                 if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
                     let node = Rc::make_mut(node_rc);
@@ -19191,13 +21839,16 @@ impl UnifiedSignalGraph {
             SignalNode::PingPongDelay {
                 input,
                 time,
+                synced,
                 feedback,
                 stereo_width,
+                filter,
                 channel,
                 mix,
                 buffer_l,
                 buffer_r,
                 write_idx,
+                lpf_state,
             } => {
                 let input_val = self.eval_signal(input);
 
@@ -19206,39 +21857,30 @@ impl UnifiedSignalGraph {
                     return input_val;
                 }
 
-                let delay_time = self.eval_signal(time).clamp(0.001, 1.0);
+                let buffer_len = buffer_l.len();
+                let sample_rate = self.sample_rate();
+                let mut delay_time = self.eval_signal(time);
+                if *synced {
+                    delay_time /= self.cps;
+                }
+                let delay_time = delay_time.clamp(0.001, (buffer_len - 1) as f32 / sample_rate);
                 let fb = self.eval_signal(feedback).clamp(0.0, 0.95);
                 let width = self.eval_signal(stereo_width).clamp(0.0, 1.0);
+                let cutoff = self.eval_signal(filter).max(20.0);
                 let mix_val = self.eval_signal(mix).clamp(0.0, 1.0);
 
-                let buffer_len = buffer_l.len();
-                let sample_rate = self.sample_rate();
                 let delay_samples = (delay_time * sample_rate) as usize;
-
                 let read_idx =
                     (*write_idx + buffer_len - delay_samples.min(buffer_len - 1)) % buffer_len;
+                let (delayed_l, delayed_r) = (buffer_l[read_idx], buffer_r[read_idx]);
 
-                // Read from opposite channel for ping-pong effect
-                let (delayed, opposite) = if *channel {
-                    (buffer_r[read_idx], buffer_l[read_idx])
-                } else {
-                    (buffer_l[read_idx], buffer_r[read_idx])
-                };
-
-                // Mix with opposite channel
-                let ping_ponged = delayed * (1.0 - width) + opposite * width;
-
-                // Write to both buffers
-                let to_write_l = if *channel {
-                    ping_ponged * fb
-                } else {
-                    input_val + ping_ponged * fb
-                };
-                let to_write_r = if *channel {
-                    input_val + ping_ponged * fb
-                } else {
-                    ping_ponged * fb
-                };
+                // Each side feeds back into the other through the lowpass;
+                // the input starts on the left, and `width` keeps it off the right
+                let coeff = pingpong_filter_coeff(cutoff, sample_rate);
+                let lpf_l = lpf_state.0 + (delayed_l - lpf_state.0) * coeff;
+                let lpf_r = lpf_state.1 + (delayed_r - lpf_state.1) * coeff;
+                let to_write_l = input_val + lpf_r * fb;
+                let to_write_r = input_val * (1.0 - width) + lpf_l * fb;
 
                 if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
                     let node = Rc::make_mut(node_rc);
@@ -19246,17 +21888,20 @@ impl UnifiedSignalGraph {
                         buffer_l: buf_l,
                         buffer_r: buf_r,
                         write_idx: idx,
+                        lpf_state: lpf,
                         ..
                     } = node
                     {
-                        buf_l[*idx] = to_write_l;
-                        buf_r[*idx] = to_write_r;
+                        buf_l[*idx] = flush_denormal(to_write_l);
+                        buf_r[*idx] = flush_denormal(to_write_r);
                         *idx = (*idx + 1) % buffer_len;
+                        *lpf = (flush_denormal(lpf_l), flush_denormal(lpf_r));
                     }
                 }
 
                 // Mix
-                input_val * (1.0 - mix_val) + ping_ponged * mix_val
+                let wet = if *channel { delayed_r } else { delayed_l };
+                input_val * (1.0 - mix_val) + wet * mix_val
             }
 
             SignalNode::RMS {
@@ -19417,6 +22062,50 @@ impl UnifiedSignalGraph {
                 output_val
             }
 
+            SignalNode::ClockDivider {
+                input,
+                division,
+                state,
+            } => {
+                let input_val = self.eval_signal(input);
+                let division = self.eval_signal(division).round().max(1.0) as u32;
+
+                let mut divider = state.clone();
+                let output = divider.process(input_val, division);
+
+                // Persist the pulse count
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    let node = Rc::make_mut(node_rc);
+                    if let SignalNode::ClockDivider { state: s, .. } = node {
+                        *s = divider;
+                    }
+                }
+
+                output
+            }
+
+            SignalNode::ClockMultiplier {
+                input,
+                factor,
+                state,
+            } => {
+                let input_val = self.eval_signal(input);
+                let factor = self.eval_signal(factor).round().max(1.0) as u32;
+
+                let mut multiplier = state.clone();
+                let output = multiplier.process(input_val, factor);
+
+                // Persist the measured period
+                if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
+                    let node = Rc::make_mut(node_rc);
+                    if let SignalNode::ClockMultiplier { state: s, .. } = node {
+                        *s = multiplier;
+                    }
+                }
+
+                output
+            }
+
             SignalNode::Pitch { input, last_pitch } => {
                 // Simplified pitch detection - would need more sophisticated algorithm
                 let _input_val = self.eval_signal(input);
@@ -20175,6 +22864,10 @@ impl UnifiedSignalGraph {
         cps: f32,
     ) {
         // Update CPS from GlobalClock (single source of truth for tempo)
+        if cps != self.cps {
+            self.shared_cps
+                .store(cps.to_bits(), std::sync::atomic::Ordering::Relaxed);
+        }
         self.cps = cps;
 
         // Process using the externally-provided timing
@@ -20237,6 +22930,12 @@ impl UnifiedSignalGraph {
             }
         }
 
+        // Adaptive quality: fade out the oldest sample voices over the cap.
+        // Voices triggered in this block are trimmed at the start of the next
+        if let Some(cap) = self.quality.voice_cap() {
+            self.voice_manager.borrow_mut().limit_polyphony(cap);
+        }
+
         // CRITICAL: Clear buffer cache at start of each buffer render
         // This prevents stale cached values from previous buffer
         self.buffer_cache.borrow_mut().clear();
@@ -20304,10 +23003,8 @@ impl UnifiedSignalGraph {
         self.cached_cycle_position = buffer_start_cycle;
 
         for i in 0..buffer_size {
-            // Increment cycle position (skip first sample since we already set it)
-            if i > 0 {
-                self.cached_cycle_position += sample_increment;
-            }
+            // Position from the sample index rather than accumulated, so it can't drift
+            self.cached_cycle_position = buffer_start_cycle + i as f64 * sample_increment;
 
             // CRITICAL: Clear stateful_value_cache for each sample to allow re-evaluation
             // Without this, Sample nodes return cached values from sample 0 and never trigger
@@ -20662,6 +23359,21 @@ impl UnifiedSignalGraph {
         (left, right)
     }
 
+    /// Render every numbered output (out1..outN), interleaved frame by frame
+    /// Returns (channel_count, samples); a program with only `out` is one channel
+    pub fn render_channels(&mut self, num_samples: usize) -> (usize, Vec<f32>) {
+        let channel_count = self.outputs.keys().copied().max().unwrap_or(1);
+        let mut samples = Vec::with_capacity(num_samples * channel_count);
+
+        for _ in 0..num_samples {
+            let mut frame = self.process_sample_multi();
+            frame.resize(channel_count, 0.0);
+            samples.extend(frame);
+        }
+
+        (channel_count, samples)
+    }
+
     // ============================================================================
     // BUFFER-BASED EVALUATION (NEW ARCHITECTURE)
     // ============================================================================
@@ -20747,6 +23459,7 @@ impl UnifiedSignalGraph {
                     | SignalNode::Tremolo { input, .. }
                     | SignalNode::RingMod { input, .. }
                     | SignalNode::Expander { input, .. }
+                    | SignalNode::NoiseGate { input, .. }
                     | SignalNode::Comb { input, .. }
                     | SignalNode::TapeDelay { input, .. }
                     | SignalNode::PingPongDelay { input, .. }
@@ -21391,9 +24104,9 @@ impl UnifiedSignalGraph {
                     // >= 1000: Absolute MIDI note (subtract 1000, convert to Hz)
                     // < 1000: Relative semitone offset from base frequency
                     let final_freq = if *semitone_offset >= 1000.0 {
-                        // Absolute MIDI: convert to Hz using A4=440Hz as reference
+                        // Absolute MIDI: convert to Hz against the A4 reference
                         let midi = *semitone_offset - 1000.0;
-                        440.0 * 2.0_f32.powf((midi - 69.0) / 12.0)
+                        crate::pitch::midi_to_hz(midi as f64) as f32
                     } else if *semitone_offset != 0.0 {
                         // Relative: apply semitone offset to base frequency
                         current_freq * 2.0_f32.powf(*semitone_offset / 12.0)
@@ -21420,25 +24133,10 @@ impl UnifiedSignalGraph {
 
                     current_freq = final_freq;
 
-                    // Generate sample based on waveform
-                    let sample = match waveform {
-                        Waveform::Sine => (2.0 * std::f32::consts::PI * current_phase).sin(),
-                        Waveform::Saw => 2.0 * current_phase - 1.0,
-                        Waveform::Square => {
-                            if current_phase < 0.5 {
-                                1.0
-                            } else {
-                                -1.0
-                            }
-                        }
-                        Waveform::Triangle => {
-                            if current_phase < 0.5 {
-                                4.0 * current_phase - 1.0
-                            } else {
-                                3.0 - 4.0 * current_phase
-                            }
-                        }
-                    };
+                    // Generate sample based on waveform (phase accumulates in f64,
+                    // the wrapped value is exact enough in f32)
+                    let phase_val = current_phase as f32;
+                    let sample = waveform.sample(phase_val, final_freq / self.sample_rate);
 
                     output[i] = sample;
 
@@ -21459,7 +24157,7 @@ impl UnifiedSignalGraph {
                     // CRITICAL FIX: Use final_freq (pitch-shifted) for phase increment,
                     // not requested_freq (original oscillator freq)
                     // pending_freq only affects anti-click smoothing, not pitch shifting
-                    current_phase += final_freq / self.sample_rate;
+                    current_phase += final_freq as f64 / self.sample_rate as f64;
                     if current_phase >= 1.0 {
                         current_phase -= 1.0;
                     }
@@ -21765,6 +24463,51 @@ impl UnifiedSignalGraph {
                 }
             }
 
+            SignalNode::UserNode { inputs, state, .. } => {
+                // Whole block at once, the way the node was written
+                let mut input_buffers = vec![vec![0.0; buffer_size]; inputs.len()];
+                for (signal, buffer) in inputs.iter().zip(input_buffers.iter_mut()) {
+                    self.eval_signal_buffer(signal, buffer);
+                }
+                let input_slices: Vec<&[f32]> = input_buffers.iter().map(Vec::as_slice).collect();
+                let context = crate::audio_node::ProcessContext::new(
+                    Fraction::from_float(self.cached_cycle_position),
+                    0,
+                    buffer_size,
+                    self.cps as f64,
+                    self.sample_rate,
+                );
+                match state.try_lock() {
+                    Ok(mut node) => node.process(&input_slices, output, &context),
+                    Err(_) => output.fill(0.0),
+                }
+            }
+
+            SignalNode::ExternalFx {
+                input,
+                params,
+                state,
+            } => {
+                let mut input_buffer = vec![0.0; buffer_size];
+                self.eval_signal_buffer(input, &mut input_buffer);
+                let mut param_buffers = vec![vec![0.0; buffer_size]; params.len()];
+                for (signal, buffer) in params.iter().zip(param_buffers.iter_mut()) {
+                    self.eval_signal_buffer(signal, buffer);
+                }
+                match state.try_lock() {
+                    Ok(mut fx) => {
+                        let mut values = vec![0.0; params.len()];
+                        for (i, (out, &x)) in output.iter_mut().zip(&input_buffer).enumerate() {
+                            for (value, buffer) in values.iter_mut().zip(&param_buffers) {
+                                *value = buffer[i];
+                            }
+                            *out = fx.process_sample(x, &values);
+                        }
+                    }
+                    Err(_) => output.copy_from_slice(&input_buffer),
+                }
+            }
+
             SignalNode::Distortion { input, drive, mix } => {
                 // Allocate buffers for input and parameters
                 let mut input_buffer = vec![0.0; buffer_size];
@@ -21888,6 +24631,7 @@ impl UnifiedSignalGraph {
 
                 // Process entire buffer through Freeverb algorithm
                 // Update state directly sample-by-sample to match the original eval_node behavior
+                let combs = self.quality.reverb_combs();
                 if let Some(Some(node_rc)) = self.nodes.get_mut(node_id.0) {
                     let node = Rc::make_mut(node_rc);
                     if let SignalNode::Reverb { state: s, .. } = node {
@@ -21897,9 +24641,9 @@ impl UnifiedSignalGraph {
                             let damp = damping_buffer[i].clamp(0.0, 1.0);
                             let mix_val = mix_buffer[i].clamp(0.0, 1.0);
 
-                            // Process comb filters (8 parallel)
+                            // Process comb filters (8 parallel, 4 at reduced quality)
                             let mut comb_out = 0.0;
-                            for j in 0..8 {
+                            for j in 0..combs {
                                 let buf_len = s.comb_buffers[j].len();
                                 let read_idx = s.comb_indices[j];
                                 let delayed = s.comb_buffers[j][read_idx];
@@ -21910,17 +24654,17 @@ impl UnifiedSignalGraph {
 
                                 // Feedback with room-size dependent gain
                                 let feedback = 0.84 * room;
-                                let to_write = input_val + filtered * feedback;
+                                let to_write = flush_denormal(input_val + filtered * feedback);
 
                                 comb_out += delayed;
 
                                 // Update buffer and state
                                 s.comb_buffers[j][read_idx] = to_write;
                                 s.comb_indices[j] = (read_idx + 1) % buf_len;
-                                s.comb_filter_stores[j] = filtered;
+                                s.comb_filter_stores[j] = flush_denormal(filtered);
                             }
 
-                            let mut allpass_out = comb_out / 8.0;
+                            let mut allpass_out = comb_out / combs as f32;
 
                             // Process allpass filters (4 in series)
                             for j in 0..4 {
@@ -21928,7 +24672,7 @@ impl UnifiedSignalGraph {
                                 let read_idx = s.allpass_indices[j];
                                 let delayed = s.allpass_buffers[j][read_idx];
 
-                                let to_write = allpass_out + delayed * 0.5;
+                                let to_write = flush_denormal(allpass_out + delayed * 0.5);
                                 allpass_out = delayed - allpass_out * 0.5;
 
                                 // Update buffer and state
@@ -22003,7 +24747,7 @@ impl UnifiedSignalGraph {
 
                             // Write to delay line (input + feedback)
                             // Apply soft clipping to prevent feedback explosion
-                            let to_write = (input_buffer[i] + delayed * fb).tanh();
+                            let to_write = flush_denormal((input_buffer[i] + delayed * fb).tanh());
                             delay_buffer[current_write_idx] = to_write;
 
                             // Mix dry and wet
@@ -22796,7 +25540,7 @@ impl UnifiedSignalGraph {
                     let filtered = lpf_state * cutoff_coef + saturated * (1.0 - cutoff_coef);
 
                     // Write to buffer
-                    delay_buffer[write_idx] = input_buffer[i] + filtered * fb;
+                    delay_buffer[write_idx] = flush_denormal(input_buffer[i] + filtered * fb);
 
                     // Mix dry and wet
                     output[i] = input_buffer[i] * (1.0 - mix_val) + filtered * mix_val;
@@ -23116,7 +25860,9 @@ impl UnifiedSignalGraph {
                 let mut right_lpf_state = state.right_lpf_state;
 
                 let mut lfo_phase = state.lfo_phase;
+                let mut shimmer = state.shimmer.clone();
                 let sample_rate = state.sample_rate;
+                let diffusers = self.quality.reverb_diffusers();
 
                 // Helper function for allpass filter
                 let allpass =
@@ -23163,11 +25909,11 @@ impl UnifiedSignalGraph {
                         input_val
                     };
 
-                    // 2. INPUT DIFFUSION (4 series allpass filters)
+                    // 2. INPUT DIFFUSION (4 series allpass filters, 2 at reduced quality)
                     let input_diffusion_gain = 0.75 * diffusion_val;
                     let mut diffused = predelay_out;
 
-                    for j in 0..4 {
+                    for j in 0..diffusers {
                         diffused = allpass(
                             &mut input_diffusion_buffers[j],
                             &mut input_diffusion_indices[j],
@@ -23195,7 +25941,7 @@ impl UnifiedSignalGraph {
 
                     // LEFT TANK
                     // Read previous right tank output for cross-coupling
-                    let right_to_left = right_delay2_buffer[right_delay2_idx];
+                    let right_to_left = shimmer.process(right_delay2_buffer[right_delay2_idx]);
 
                     // Input to left tank (with cross-coupling from right)
                     let left_input = input_to_tanks + right_to_left * decay_gain;
@@ -23352,6 +26098,7 @@ impl UnifiedSignalGraph {
                         s.right_lpf_state = right_lpf_state;
 
                         s.lfo_phase = lfo_phase;
+                        s.shimmer = shimmer;
                     }
                 }
             }
@@ -23394,66 +26141,64 @@ impl UnifiedSignalGraph {
             SignalNode::PingPongDelay {
                 input,
                 time,
+                synced,
                 feedback,
                 stereo_width,
+                filter,
                 channel,
                 mix,
                 buffer_l,
                 buffer_r,
                 write_idx,
+                lpf_state,
             } => {
                 let mut input_buffer = vec![0.0; buffer_size];
                 let mut time_buffer = vec![0.0; buffer_size];
                 let mut feedback_buffer = vec![0.0; buffer_size];
                 let mut stereo_width_buffer = vec![0.0; buffer_size];
+                let mut filter_buffer = vec![0.0; buffer_size];
                 let mut mix_buffer = vec![0.0; buffer_size];
 
                 self.eval_signal_buffer(input, &mut input_buffer);
                 self.eval_signal_buffer(time, &mut time_buffer);
                 self.eval_signal_buffer(feedback, &mut feedback_buffer);
                 self.eval_signal_buffer(stereo_width, &mut stereo_width_buffer);
+                self.eval_signal_buffer(filter, &mut filter_buffer);
                 self.eval_signal_buffer(mix, &mut mix_buffer);
 
                 let buf_len = buffer_l.len();
                 let mut left_buf = buffer_l.clone();
                 let mut right_buf = buffer_r.clone();
                 let mut current_write_idx = *write_idx;
-                let current_channel = *channel;
+                let (mut lpf_l, mut lpf_r) = *lpf_state;
+                let max_delay = (buf_len - 1) as f32 / self.sample_rate;
+                let time_scale = if *synced { 1.0 / self.cps } else { 1.0 };
 
                 for i in 0..buffer_size {
-                    let delay_time = time_buffer[i].clamp(0.001, 1.0);
+                    let delay_time = (time_buffer[i] * time_scale).clamp(0.001, max_delay);
                     let fb = feedback_buffer[i].clamp(0.0, 0.95);
                     let width = stereo_width_buffer[i].clamp(0.0, 1.0);
+                    let cutoff = filter_buffer[i].max(20.0);
                     let mix_val = mix_buffer[i].clamp(0.0, 1.0);
 
                     let delay_samples = (delay_time * self.sample_rate) as usize;
                     let delay_samples = delay_samples.min(buf_len - 1);
 
                     let read_idx = (current_write_idx + buf_len - delay_samples) % buf_len;
+                    let (delayed_l, delayed_r) = (left_buf[read_idx], right_buf[read_idx]);
 
-                    let (delayed, opposite) = if current_channel {
-                        (right_buf[read_idx], left_buf[read_idx])
-                    } else {
-                        (left_buf[read_idx], right_buf[read_idx])
-                    };
-
-                    let ping_ponged = delayed * (1.0 - width) + opposite * width;
+                    let wet = if *channel { delayed_r } else { delayed_l };
+                    output[i] = input_buffer[i] * (1.0 - mix_val) + wet * mix_val;
 
-                    output[i] = input_buffer[i] * (1.0 - mix_val) + ping_ponged * mix_val;
+                    // Cross-feedback through the lowpass, as in the sample path
+                    let coeff = pingpong_filter_coeff(cutoff, self.sample_rate);
+                    lpf_l = flush_denormal(lpf_l + (delayed_l - lpf_l) * coeff);
+                    lpf_r = flush_denormal(lpf_r + (delayed_r - lpf_r) * coeff);
+                    let to_write_l = input_buffer[i] + lpf_r * fb;
+                    let to_write_r = input_buffer[i] * (1.0 - width) + lpf_l * fb;
 
-                    let to_write_l = if current_channel {
-                        ping_ponged * fb
-                    } else {
-                        input_buffer[i] + ping_ponged * fb
-                    };
-                    let to_write_r = if current_channel {
-                        input_buffer[i] + ping_ponged * fb
-                    } else {
-                        ping_ponged * fb
-                    };
-
-                    left_buf[current_write_idx] = to_write_l;
-                    right_buf[current_write_idx] = to_write_r;
+                    left_buf[current_write_idx] = flush_denormal(to_write_l);
+                    right_buf[current_write_idx] = flush_denormal(to_write_r);
 
                     current_write_idx = (current_write_idx + 1) % buf_len;
                 }
@@ -23464,12 +26209,14 @@ impl UnifiedSignalGraph {
                         buffer_l: buf_l,
                         buffer_r: buf_r,
                         write_idx: idx,
+                        lpf_state: lpf,
                         ..
                     } = node
                     {
                         *buf_l = left_buf;
                         *buf_r = right_buf;
                         *idx = current_write_idx;
+                        *lpf = (lpf_l, lpf_r);
                     }
                 }
             }
@@ -23860,9 +26607,10 @@ impl UnifiedSignalGraph {
                 }
             }
 
-            SignalNode::Sample { .. } => {
+            SignalNode::Sample { .. } | SignalNode::Orbit { .. } => {
                 // In hybrid mode, Sample nodes read from voice_buffers which contain
                 // pre-rendered audio from Phase 2. Just copy the buffer directly.
+                // Orbit nodes hold the voices routed to them the same way.
                 // CRITICAL: Do NOT fall back to eval_node - that would re-trigger voices!
                 let node_idx = node_id.0;
                 if node_idx < self.voice_buffers.buffers.len() {
@@ -24338,6 +27086,128 @@ impl UnifiedSignalGraph {
     }
 }
 
+/// The running state of the stateful node variants: phase, envelope position
+/// and filter memory, not parameters or inputs. Generates the functions that
+/// carry it across a hot reload and save it in a session file.
+macro_rules! node_state {
+    ($($variant:ident { $($field:ident),+ }),+ $(,)?) => {
+        /// Copy the running state of `old` into `new` (both compiled from the same
+        /// definition). Parameters and inputs stay as compiled; only phase, envelope
+        /// and filter memory move across. Returns false for stateless nodes.
+        fn carry_node_state(new: &mut SignalNode, old: &SignalNode) -> bool {
+            match old {
+                $(SignalNode::$variant { $($field,)+ .. } => {
+                    let carried = ($($field.clone(),)+);
+                    match new {
+                        SignalNode::$variant { $($field,)+ .. } => {
+                            ($(*$field,)+) = carried;
+                            true
+                        }
+                        _ => false,
+                    }
+                })+
+                _ => false,
+            }
+        }
+
+        /// The running state of node `node_id`, or None for stateless nodes
+        fn save_node_state(node_id: usize, node: &SignalNode) -> Option<SavedNodeState> {
+            match node {
+                $(SignalNode::$variant { $($field,)+ .. } => Some(SavedNodeState {
+                    node: node_id,
+                    variant: stringify!($variant).to_string(),
+                    state: bincode::serialize(&($($field,)+)).ok()?,
+                }),)+
+                _ => None,
+            }
+        }
+
+        /// Put `saved` into `node` if it is the same variant. Returns false
+        /// otherwise.
+        fn restore_node_state(node: &mut SignalNode, saved: &SavedNodeState) -> bool {
+            match node {
+                $(SignalNode::$variant { $($field,)+ .. }
+                    if saved.variant == stringify!($variant) =>
+                {
+                    match bincode::deserialize(&saved.state) {
+                        Ok(state) => {
+                            ($(*$field,)+) = state;
+                            true
+                        }
+                        Err(_) => false,
+                    }
+                })+
+                _ => false,
+            }
+        }
+    };
+}
+
+node_state!(
+    // Oscillators
+    Oscillator {
+        phase,
+        pending_freq,
+        last_sample
+    },
+    FMOscillator {
+        carrier_phase,
+        modulator_phase
+    },
+    PMOscillator { carrier_phase },
+    Blip { phase },
+    VCO { phase },
+    Pulse { phase },
+    Wavetable { state },
+    Additive { state },
+    KarplusStrong { state },
+    Waveguide { state },
+    PinkNoise { state },
+    BrownNoise { state },
+    Impulse { state },
+    // Envelopes and ramps
+    Envelope { state },
+    ADSR { state },
+    AD { state },
+    ASR { state },
+    XLine { state },
+    Lag { state },
+    Curve { elapsed_time },
+    Segments {
+        current_segment,
+        segment_elapsed,
+        current_value
+    },
+    EnvelopePattern { state },
+    StructuredSignal { state },
+    TriggeredAR { state },
+    TriggeredADSR { state },
+    // Filters
+    LowPass { state },
+    HighPass { state },
+    BandPass { state },
+    Notch { state },
+    SVF { state },
+    Biquad { state },
+    Resonz { state },
+    RLPF { state },
+    RHPF { state },
+    MoogLadder { state },
+    Formant { state },
+    Vowel { state },
+);
+
+/// The running state of one node, as saved in a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SavedNodeState {
+    /// Index of the node in the graph
+    node: usize,
+    /// Name of the node's variant, checked before the state is put back
+    variant: String,
+    /// The variant's state fields, bincode-encoded
+    state: Vec<u8>,
+}
+
 /// Render-thread-owned swap wiring for the real audio graph
 /// (task `render-owner-transfer-boundary`, design §4.1/§4.3/§4.5).
 ///
@@ -24367,13 +27237,19 @@ impl crate::render_swap::RenderGraph for UnifiedSignalGraph {
     /// thread with `prev` still exclusively owned:
     ///
     /// 1. [`transfer_session_timing`](Self::transfer_session_timing) — carries the
-    ///    wall-clock reference so the beat never jumps (R1). It also calls
+    ///    wall-clock reference so the beat never jumps (R1), and starts the
+    ///    `take` / `after` count at the next cycle boundary. It also calls
     ///    [`transfer_render_continuity`](Self::transfer_render_continuity), which
     ///    seeds `self.prev_buffer_tail` so the Phase-4d boundary crossfade fires on
     ///    `self`'s first block exactly as today (D3 seam behavior unchanged).
     /// 2. [`transfer_fx_states`](Self::transfer_fx_states) — carries delay / reverb
     ///    tails.
-    /// 3. [`transfer_voice_manager`](Self::transfer_voice_manager)`(prev.`[`take_voice_manager`](Self::take_voice_manager)`())`
+    /// 3. [`apply_bus_state_plan`](Self::apply_bus_state_plan) — carries oscillator
+    ///    phase, envelopes and filter memory into buses whose definition is
+    ///    unchanged, so they play on without a restart. The node pairs were
+    ///    planned on the control thread
+    ///    ([`plan_bus_state_transfer`](Self::plan_bus_state_transfer)).
+    /// 4. [`transfer_voice_manager`](Self::transfer_voice_manager)`(prev.`[`take_voice_manager`](Self::take_voice_manager)`())`
     ///    — moves the live voice manager across and fades its voices (`release_*`),
     ///    byte-for-byte as today (design §4.5 non-goal: voice semantics unchanged).
     ///    Because the take + install happen here and the caller pointer-swaps
@@ -24385,12 +27261,20 @@ impl crate::render_swap::RenderGraph for UnifiedSignalGraph {
     /// called here: they run on the control thread before the swap is enqueued
     /// (design §4.4).
     fn absorb_state(&mut self, prev: &mut Self) {
-        // Immutable-borrow transfers first (timing, then FX tails)...
+        // Immutable-borrow transfers first (timing, FX tails, bus state)...
         self.transfer_session_timing(prev);
         self.transfer_fx_states(prev);
+        self.apply_bus_state_plan(prev);
         // Carry the G7 preservation policy forward so a live session keeps it
         // once enabled (the freshly-compiled `self` starts from its own default).
         self.preserve_voices_on_swap |= prev.preserve_voices_on_swap;
+        if self.event_log.is_none() {
+            self.event_log = prev.event_log.take();
+        }
+        // Continue the DC blocker's filter state so enabling it stays click-free
+        if let (Some(mine), Some(theirs)) = (&mut self.master_dc_blocker, &prev.master_dc_blocker) {
+            *mine = *theirs;
+        }
         // ...then the mutable take, which ends only after the shared borrows above.
         let voices = prev.take_voice_manager();
         if self.preserve_voices_on_swap {
@@ -24426,6 +27310,19 @@ impl crate::render_swap::RenderGraph for UnifiedSignalGraph {
     fn set_cycle(&mut self, cycle: f64) {
         self.set_cycle_position(cycle);
     }
+
+    /// `Cmd::Seek(cycle)` → cue playback ([`seek_to_cycle`](Self::seek_to_cycle)),
+    /// counting `take` / `after` from the cue point.
+    fn seek(&mut self, cycle: f64) {
+        self.seek_to_cycle(cycle);
+        self.count_origin.start_at(cycle);
+    }
+
+    /// `Cmd::SaveState` → the running node state
+    /// ([`save_node_states`](Self::save_node_states))
+    fn save_state(&self) -> Vec<u8> {
+        self.save_node_states()
+    }
 }
 
 #[cfg(test)]
@@ -24668,6 +27565,46 @@ mod render_owner_boundary_tests {
         assert_eq!(cur.active_voice_count(), 0, "panic killed all voices");
     }
 
+    /// `take` / `after` count from the boundary after the swap (or from a cue
+    /// point), not from cycle 0 — and preloading the incoming graph, which
+    /// queries its patterns from cycle 0, doesn't start the count.
+    #[test]
+    fn test_boundary_swap_starts_counted_patterns() {
+        use crate::render_swap::Cmd;
+
+        let mut cur = Box::new(synth_graph());
+        cur.enable_wall_clock_timing();
+        cur.cps = 0.5;
+        cur.cycle_offset = 500.25;
+        cur.cached_cycle_position = 500.25;
+
+        let (_, statements) =
+            crate::compositional_parser::parse_program("out $ saw \"110 220\" $ take 8")
+                .expect("parse");
+        let mut incoming = Box::new(
+            crate::compositional_compiler::compile_program(statements, 44100.0, None)
+                .expect("compile"),
+        );
+        incoming.cps = 0.5;
+        incoming.preload_samples();
+        let origin = incoming.count_origin();
+        assert_eq!(origin.cycle(), 0.0);
+
+        let (mut tx, mut rsw, _grave) = render_swap_channel::<UnifiedSignalGraph>(8, 8);
+        assert!(tx.swap(incoming).is_ok());
+        assert_eq!(rsw.apply_pending_commands(&mut cur), 1);
+        // The next boundary after ~500.25 (a little later under test load)
+        assert!(
+            (501.0..=502.0).contains(&origin.cycle()),
+            "counting from {}",
+            origin.cycle()
+        );
+
+        assert!(tx.send(Cmd::Seek(32.5)).is_ok());
+        rsw.apply_pending_commands(&mut cur);
+        assert_eq!(origin.cycle(), 33.0);
+    }
+
     // =====================================================================
     // G7 — voice preservation across graph swap (feat-voice-preservation-swap)
     //
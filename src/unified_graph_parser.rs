@@ -3,6 +3,13 @@
 //!
 //! Enables inline synth definitions, pattern embedding, and universal modulation.
 //!
+//! Deprecated: this was the first parser for the language below, and its
+//! parentheses-and-colons dialect has drifted from what the commands accept.
+//! `phonon render`, `play`, `live` and `edit` all go through
+//! [`crate::compositional_parser::parse_program`] and
+//! [`crate::compositional_compiler::compile_program`] (or
+//! [`crate::phonon_engine::PhononEngine`], which wraps both).
+//!
 //! # Phonon DSL
 //!
 //! The Phonon DSL allows you to create audio graphs that combine synthesis, patterns,
@@ -2439,7 +2446,7 @@ impl DslCompiler {
             }
             DslExpression::Synth { synth_type, params } => {
                 use crate::superdirt_synths::SynthLibrary;
-                let library = SynthLibrary::with_sample_rate(44100.0);
+                let library = SynthLibrary::with_sample_rate(self.graph.sample_rate());
 
                 // Extract frequency (first param)
                 let freq = params
@@ -2580,7 +2587,7 @@ impl DslCompiler {
                 params,
             } => {
                 use crate::superdirt_synths::SynthLibrary;
-                let library = SynthLibrary::with_sample_rate(44100.0);
+                let library = SynthLibrary::with_sample_rate(self.graph.sample_rate());
 
                 let input_node = self.compile_expression(*input);
 
@@ -3475,6 +3482,7 @@ impl DslCompiler {
                         let gate_id = self.graph.add_node(SignalNode::PatternGate {
                             pattern_str: pattern_str.clone(),
                             pattern: bool_pattern,
+                            retrigger_gap: 0.0,
                         });
                         // Now multiply by the signal
                         self.graph.add_node(SignalNode::Multiply {
@@ -3498,6 +3506,7 @@ impl DslCompiler {
                         self.graph.add_node(SignalNode::PatternGate {
                             pattern_str,
                             pattern: bool_pattern,
+                            retrigger_gap: 0.0,
                         })
                     }
                     "trig" => {
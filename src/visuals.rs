@@ -0,0 +1,269 @@
+//! Audio-reactive visuals feed over OSC
+//!
+//! `visuals: osc://localhost:3333` sends a bundle per frame (30 fps by
+//! default, up to 60) for Hydra, Processing and the like:
+//!
+//! - `/phonon/bands` 8 floats: RMS level of each band, low to high
+//! - `/phonon/rms` float: RMS level of the whole output
+//! - `/phonon/onsets` 8 ints: 1 if the band had an onset since the last frame
+//! - `/phonon/onset` int: 1 if any band had one
+//! - `/phonon/cycle` 2 floats: cycle position and its phase within the cycle
+//!
+//! The render thread only runs the band filters and stores levels into
+//! atomics, like the bus meters; a sender thread reads them at frame rate
+//! and does the OSC. The thread stops once the last graph holding the feed
+//! is dropped.
+
+use biquad::{Biquad, Coefficients, DirectForm2Transposed, ToHertz};
+use rosc::{OscBundle, OscMessage, OscPacket, OscTime, OscType};
+use std::net::{ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Band centres in Hz, sub bass to air
+pub const BAND_CENTERS: [f32; 8] = [60.0, 150.0, 400.0, 1000.0, 2500.0, 5000.0, 8000.0, 12000.0];
+const BANDS: usize = BAND_CENTERS.len();
+
+/// An onset is a band's fast level jumping this far (in power) above its
+/// slow average; it re-arms once back under `REARM_RATIO` times it
+const ONSET_RATIO: f32 = 4.0;
+const REARM_RATIO: f32 = 1.5;
+/// Quietest band power that can count as an onset
+const ONSET_FLOOR: f32 = 1e-5;
+
+/// Levels shared between the render thread and the sender thread
+#[derive(Debug, Default)]
+pub struct VisualsFeed {
+    /// RMS of each band (f32 bits)
+    bands: [AtomicU32; BANDS],
+    /// RMS of the whole output (f32 bits)
+    rms: AtomicU32,
+    /// Bit per band with an onset since the sender last looked
+    onsets: AtomicU32,
+    /// Cycle position at the start of the last block (f64 bits)
+    cycle: AtomicU64,
+}
+
+/// One frame of the feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisualsFrame {
+    pub bands: [f32; BANDS],
+    pub rms: f32,
+    pub onsets: [bool; BANDS],
+    pub cycle: f64,
+}
+
+impl VisualsFeed {
+    /// Read the current levels, clearing the onset flags
+    pub fn take_frame(&self) -> VisualsFrame {
+        let onsets = self.onsets.swap(0, Ordering::Relaxed);
+        VisualsFrame {
+            bands: std::array::from_fn(|i| f32::from_bits(self.bands[i].load(Ordering::Relaxed))),
+            rms: f32::from_bits(self.rms.load(Ordering::Relaxed)),
+            onsets: std::array::from_fn(|i| onsets & (1 << i) != 0),
+            cycle: f64::from_bits(self.cycle.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl VisualsFrame {
+    /// The frame as an OSC bundle, to be handled immediately
+    pub fn to_packet(&self) -> OscPacket {
+        let message = |addr: &str, args: Vec<OscType>| {
+            OscPacket::Message(OscMessage {
+                addr: addr.to_string(),
+                args,
+            })
+        };
+        let onsets = self.onsets.map(|onset| OscType::Int(onset as i32));
+        OscPacket::Bundle(OscBundle {
+            timetag: OscTime::from((0, 1)),
+            content: vec![
+                message("/phonon/bands", self.bands.map(OscType::Float).to_vec()),
+                message("/phonon/rms", vec![OscType::Float(self.rms)]),
+                message("/phonon/onsets", onsets.to_vec()),
+                message(
+                    "/phonon/onset",
+                    vec![OscType::Int(self.onsets.contains(&true) as i32)],
+                ),
+                message(
+                    "/phonon/cycle",
+                    vec![
+                        OscType::Float(self.cycle as f32),
+                        OscType::Float(self.cycle.rem_euclid(1.0) as f32),
+                    ],
+                ),
+            ],
+        })
+    }
+}
+
+/// Render-side analysis: a band-pass filter and two level followers per band
+#[derive(Clone)]
+pub struct VisualsAnalyzer {
+    feed: Arc<VisualsFeed>,
+    filters: Vec<DirectForm2Transposed<f32>>,
+    /// Fast (~30 ms) and slow (~300 ms) power of each band
+    fast: [f32; BANDS],
+    slow: [f32; BANDS],
+    armed: [bool; BANDS],
+    power: f32,
+    fast_coef: f32,
+    slow_coef: f32,
+}
+
+impl VisualsAnalyzer {
+    pub fn new(sample_rate: f32) -> Self {
+        let nyquist = sample_rate * 0.45;
+        let filters = BAND_CENTERS
+            .iter()
+            .map(|&center| {
+                let coeffs = Coefficients::<f32>::from_params(
+                    biquad::Type::BandPass,
+                    sample_rate.hz(),
+                    center.min(nyquist).hz(),
+                    1.4,
+                )
+                .expect("band centres are below Nyquist");
+                DirectForm2Transposed::<f32>::new(coeffs)
+            })
+            .collect();
+        let coef = |secs: f32| 1.0 - (-1.0 / (secs * sample_rate)).exp();
+        Self {
+            feed: Arc::new(VisualsFeed::default()),
+            filters,
+            fast: [0.0; BANDS],
+            slow: [0.0; BANDS],
+            armed: [true; BANDS],
+            power: 0.0,
+            fast_coef: coef(0.03),
+            slow_coef: coef(0.3),
+        }
+    }
+
+    pub fn feed(&self) -> Arc<VisualsFeed> {
+        Arc::clone(&self.feed)
+    }
+
+    /// Analyse one block of interleaved stereo output starting at `cycle`
+    pub fn process(&mut self, buffer: &[f32], cycle: f64) {
+        let mut onsets = 0u32;
+        for frame in buffer.chunks_exact(2) {
+            let mono = (frame[0] + frame[1]) * 0.5;
+            self.power += (mono * mono - self.power) * self.fast_coef;
+            for (band, filter) in self.filters.iter_mut().enumerate() {
+                let y = filter.run(mono);
+                let fast = self.fast[band] + (y * y - self.fast[band]) * self.fast_coef;
+                let slow = self.slow[band] + (y * y - self.slow[band]) * self.slow_coef;
+                if self.armed[band] && fast > ONSET_FLOOR && fast > slow * ONSET_RATIO {
+                    onsets |= 1 << band;
+                    self.armed[band] = false;
+                } else if fast < slow * REARM_RATIO {
+                    self.armed[band] = true;
+                }
+                self.fast[band] = fast;
+                self.slow[band] = slow;
+            }
+        }
+
+        for band in 0..BANDS {
+            let level = self.fast[band].max(0.0).sqrt();
+            self.feed.bands[band].store(level.to_bits(), Ordering::Relaxed);
+        }
+        if onsets != 0 {
+            self.feed.onsets.fetch_or(onsets, Ordering::Relaxed);
+        }
+        let rms = self.power.max(0.0).sqrt();
+        self.feed.rms.store(rms.to_bits(), Ordering::Relaxed);
+        self.feed.cycle.store(cycle.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Parse a `visuals:` target, `osc://host:port`
+pub fn parse_target(target: &str) -> Result<String, String> {
+    let address = target
+        .strip_prefix("osc://")
+        .ok_or_else(|| format!("visuals: expected osc://host:port, got '{}'", target))?;
+    match address.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {
+            Ok(address.to_string())
+        }
+        _ => Err(format!("visuals: expected osc://host:port, got '{}'", target)),
+    }
+}
+
+/// Start sending `analyzer`'s feed to `target` (`osc://host:port`) at `fps`
+pub fn start(analyzer: &VisualsAnalyzer, target: &str, fps: f64) -> Result<(), String> {
+    let address = parse_target(target)?;
+    if !(1.0..=60.0).contains(&fps) {
+        return Err(format!("visuals: frame rate must be 1 to 60 fps, got {}", fps));
+    }
+    let addr = address
+        .to_socket_addrs()
+        .map_err(|e| format!("visuals: can't resolve {}: {}", address, e))?
+        .next()
+        .ok_or_else(|| format!("visuals: can't resolve {}", address))?;
+    let socket = UdpSocket::bind(if addr.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" })
+        .map_err(|e| format!("visuals: {}", e))?;
+
+    let feed = analyzer.feed();
+    let period = Duration::from_secs_f64(1.0 / fps);
+    std::thread::Builder::new()
+        .name("phonon-visuals".to_string())
+        .spawn(move || {
+            // Once only this thread holds the feed, its graph is gone
+            while Arc::strong_count(&feed) > 1 {
+                if let Ok(buf) = rosc::encoder::encode(&feed.take_frame().to_packet()) {
+                    let _ = socket.send_to(&buf, addr);
+                }
+                std::thread::sleep(period);
+            }
+        })
+        .map_err(|e| format!("visuals: {}", e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bands_follow_the_signal() {
+        let sample_rate = 44100.0;
+        let mut analyzer = VisualsAnalyzer::new(sample_rate);
+        let feed = analyzer.feed();
+
+        // Silence, then a 1 kHz tone: the 1 kHz band lights up with an onset
+        analyzer.process(&[0.0; 1024], 0.0);
+        assert_eq!(feed.take_frame().onsets, [false; BANDS]);
+        let tone: Vec<f32> = (0..8820)
+            .flat_map(|i| {
+                let s = (i as f32 * 1000.0 * std::f32::consts::TAU / sample_rate).sin() * 0.5;
+                [s, s]
+            })
+            .collect();
+        analyzer.process(&tone, 1.25);
+
+        let frame = feed.take_frame();
+        let loudest = (0..BANDS).max_by(|&a, &b| frame.bands[a].total_cmp(&frame.bands[b]));
+        assert_eq!(loudest, Some(3));
+        assert!(frame.onsets[3]);
+        assert!((frame.rms - 0.5 / 2f32.sqrt()).abs() < 0.05);
+        assert_eq!(frame.cycle, 1.25);
+        // Onsets are reported once
+        assert!(!feed.take_frame().onsets[3]);
+
+        let OscPacket::Bundle(bundle) = frame.to_packet() else {
+            panic!("expected a bundle");
+        };
+        assert_eq!(bundle.content.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(parse_target("osc://localhost:3333").unwrap(), "localhost:3333");
+        assert!(parse_target("localhost:3333").is_err());
+        assert!(parse_target("osc://localhost").is_err());
+    }
+}
@@ -18,7 +18,10 @@
 //! - **Automatic voice allocation**: Finds free voices or steals oldest one
 //! - **Voice stealing**: When all voices are busy, the oldest voice is reused
 //! - **Per-voice control**: Gain, pan, and speed parameters for each voice
-//! - **Stereo output**: Equal-power panning for proper stereo imaging
+//! - **Stereo output**: Equal-power panning for proper stereo imaging, with
+//!   pan moves on a sounding voice smoothed over a few milliseconds
+//! - **Orbits**: A voice can leave its pattern's bus for an `orbit N` node,
+//!   so layers of one pattern can go through different effect chains
 //!
 //! # Examples
 //!
@@ -108,7 +111,8 @@ const DEFAULT_VOICE_CEILING: usize = 512;
 /// Memory: 4096 × 140 bytes = ~0.56 MB (negligible)
 const ABSOLUTE_MAX_VOICES: usize = 4096;
 
-/// Sample rate for envelope calculations (will be set per-voice)
+/// Default sample rate for envelope calculations
+/// (`VoiceManager::set_sample_rate` sets the rate per voice)
 const SAMPLE_RATE: f32 = 44100.0;
 
 /// Voice lifecycle state for proper management
@@ -122,6 +126,119 @@ pub enum VoiceState {
     Releasing,
 }
 
+/// Per-voice SuperDirt effects: `# shape`, `# crush`, `# coarse`, `# squiz`,
+/// plus `# fold`
+///
+/// Each value is fixed for the voice's lifetime (taken at the event onset).
+/// Zero leaves an effect off.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VoiceFx {
+    /// Waveshaper amount, 0..1
+    pub shape: f32,
+    /// Bit depth (fractional values allowed), 1 is the harshest
+    pub crush: f32,
+    /// Hold every sample for this many samples (1 = off)
+    pub coarse: f32,
+    /// Pitch ratio for replaying zero-crossing chunks (1 = off, 2 = octave up)
+    pub squiz: f32,
+    /// Wavefolding amount, 0..1 (see [`crate::westcoast::fold`])
+    pub fold: f32,
+}
+
+impl VoiceFx {
+    /// True when any effect changes the sound
+    pub fn is_active(&self) -> bool {
+        self.shape > 0.0
+            || self.crush > 0.0
+            || self.coarse > 1.0
+            || self.squiz > 1.0
+            || self.fold > 0.0
+    }
+}
+
+/// Longest squiz chunk, in sample frames, when the source has no zero crossing
+const SQUIZ_MAX_CHUNK: f32 = 2048.0;
+
+/// Running state of a voice's `VoiceFx` (no allocation, reset on trigger)
+#[derive(Clone, Copy, Debug, Default)]
+struct VoiceFxState {
+    /// Frame held by `coarse` and the samples left before the next one
+    held: (f32, f32),
+    hold_remaining: f32,
+    /// Start of the chunk being played through (set on the first frame)
+    chunk_start: Option<f32>,
+    /// Previous chunk as (start, signed length) in sample positions
+    chunk: (f32, f32),
+    /// Read offset into `chunk`
+    chunk_phase: f32,
+    last_mono: f32,
+}
+
+impl VoiceFxState {
+    /// Apply the effects to one frame read at `position`, in SuperDirt's
+    /// order: squiz re-reads the sample, then fold, shape, crush and coarse
+    fn process(
+        &mut self,
+        fx: &VoiceFx,
+        sample: &StereoSample,
+        position: f32,
+        speed: f32,
+        frame: (f32, f32),
+    ) -> (f32, f32) {
+        let (mut left, mut right) = frame;
+
+        if fx.squiz > 1.0 {
+            // Split the source at upward zero crossings and keep replaying
+            // the previous chunk `squiz` times faster
+            let chunk_start = *self.chunk_start.get_or_insert(position);
+            let mono = left + right;
+            let crossed = self.last_mono <= 0.0 && mono > 0.0;
+            self.last_mono = mono;
+            if (crossed && position != chunk_start)
+                || (position - chunk_start).abs() >= SQUIZ_MAX_CHUNK
+            {
+                self.chunk = (chunk_start, position - chunk_start);
+                self.chunk_start = Some(position);
+                self.chunk_phase = 0.0;
+            }
+            let (start, length) = self.chunk;
+            if length != 0.0 {
+                let offset = self.chunk_phase % length.abs();
+                (left, right) = sample.get_interpolated(start + offset.copysign(length));
+                self.chunk_phase += speed.abs() * fx.squiz;
+            }
+        }
+
+        if fx.fold > 0.0 {
+            use crate::westcoast::fold;
+            (left, right) = (fold(left, fx.fold), fold(right, fx.fold));
+        }
+
+        if fx.shape > 0.0 {
+            let amount = fx.shape.min(0.999);
+            let k = 2.0 * amount / (1.0 - amount);
+            let shape = |x: f32| (1.0 + k) * x / (1.0 + k * x.abs());
+            (left, right) = (shape(left), shape(right));
+        }
+
+        if fx.crush > 0.0 {
+            let step = 0.5f32.powf(fx.crush - 1.0);
+            (left, right) = ((left / step).round() * step, (right / step).round() * step);
+        }
+
+        if fx.coarse > 1.0 {
+            if self.hold_remaining <= 0.0 {
+                self.held = (left, right);
+                self.hold_remaining += fx.coarse;
+            }
+            self.hold_remaining -= 1.0;
+            (left, right) = self.held;
+        }
+
+        (left, right)
+    }
+}
+
 /// Vec-based voice buffer storage for O(1) lookup in hot loop
 ///
 /// This replaces HashMap<usize, Vec<f32>> for performance:
@@ -245,6 +362,12 @@ pub struct Voice {
     /// Pan position: -1.0 = hard left, 0.0 = center, 1.0 = hard right
     pan: f32,
 
+    /// Where `pan` glides to when moved while the voice sounds (see [`Voice::set_pan`])
+    pan_target: f32,
+
+    /// Output orbit: 0 = the bus of the node that triggered it, N = `orbit N`
+    orbit: usize,
+
     /// Playback speed: 1.0 = normal, 2.0 = double speed, 0.5 = half speed
     speed: f32,
 
@@ -275,6 +398,10 @@ pub struct Voice {
     /// Loop mode: whether sample should loop when it reaches the end
     loop_enabled: bool,
 
+    /// Per-voice effects (`# crush`, `# coarse`, `# shape`, `# squiz`)
+    fx: VoiceFx,
+    fx_state: VoiceFxState,
+
     /// Auto-release time: trigger release() when age reaches this value
     /// Used for legato to create sharp note durations
     /// None = no auto-release (envelope controls duration)
@@ -294,6 +421,9 @@ pub struct Voice {
 
     /// Last mono output value — used for zero-crossing detection during fadeout.
     last_mono_out: f32,
+
+    /// Output sample rate (envelope times are converted to samples with it)
+    sample_rate: f32,
 }
 
 /// Unit mode for sample playback speed interpretation
@@ -322,6 +452,8 @@ impl Voice {
             state: VoiceState::Free,
             gain: 1.0,
             pan: 0.0,
+            pan_target: 0.0,
+            orbit: 0,
             speed: 1.0,
             age: 0,
             cut_group: None,
@@ -332,9 +464,12 @@ impl Voice {
             release: 0.1,                 // 100ms default release
             unit_mode: UnitMode::Rate,    // Default to rate mode
             loop_enabled: false,          // Default to no looping
+            fx: VoiceFx::default(),
+            fx_state: VoiceFxState::default(),
             fadeout_remaining: 0,
             last_mono_out: 0.0,
             auto_release_at_sample: None, // No auto-release by default
+            sample_rate: SAMPLE_RATE,
         }
     }
 
@@ -390,10 +525,13 @@ impl Voice {
         self.state = VoiceState::Playing;
         self.gain = gain;
         self.pan = pan.clamp(-1.0, 1.0);
+        self.pan_target = self.pan;
+        self.orbit = 0;
         self.speed = speed; // Allow negative speed for reverse playback
         self.age = 0;
         self.fadeout_remaining = 0;
         self.last_mono_out = 0.0;
+        self.set_fx(VoiceFx::default());
         self.cut_group = cut_group;
         self.attack = attack.max(0.0001); // Minimum 0.1ms
         self.release = release.max(0.001); // Minimum 1ms
@@ -401,7 +539,7 @@ impl Voice {
         self.buffer_trigger_offset = None; // Will be set by VoiceManager if needed
 
         // Configure and trigger envelope (recreate as percussion type)
-        self.envelope = VoiceEnvelope::new_percussion(SAMPLE_RATE, self.attack, self.release);
+        self.envelope = VoiceEnvelope::new_percussion(self.sample_rate, self.attack, self.release);
         self.envelope.trigger();
     }
 
@@ -430,10 +568,13 @@ impl Voice {
         self.state = VoiceState::Playing;
         self.gain = gain;
         self.pan = pan.clamp(-1.0, 1.0);
+        self.pan_target = self.pan;
+        self.orbit = 0;
         self.speed = speed; // Allow negative speed for reverse playback
         self.age = 0;
         self.fadeout_remaining = 0;
         self.last_mono_out = 0.0;
+        self.set_fx(VoiceFx::default());
         self.cut_group = cut_group;
         self.attack = attack;
         self.release = release;
@@ -441,7 +582,7 @@ impl Voice {
         self.buffer_trigger_offset = None; // Will be set by VoiceManager if needed
 
         // Create and trigger ADSR envelope
-        self.envelope = VoiceEnvelope::new_adsr(SAMPLE_RATE, attack, decay, sustain, release);
+        self.envelope = VoiceEnvelope::new_adsr(self.sample_rate, attack, decay, sustain, release);
         self.envelope.trigger();
     }
 
@@ -468,15 +609,18 @@ impl Voice {
         self.state = VoiceState::Playing;
         self.gain = gain;
         self.pan = pan.clamp(-1.0, 1.0);
+        self.pan_target = self.pan;
+        self.orbit = 0;
         self.speed = speed; // Allow negative speed for reverse playback
         self.age = 0;
         self.fadeout_remaining = 0;
         self.last_mono_out = 0.0;
+        self.set_fx(VoiceFx::default());
         self.cut_group = cut_group;
         self.buffer_trigger_offset = None; // Will be set by VoiceManager if needed
 
         // Create and trigger segments envelope
-        self.envelope = VoiceEnvelope::new_segments(SAMPLE_RATE, levels, times);
+        self.envelope = VoiceEnvelope::new_segments(self.sample_rate, levels, times);
         self.envelope.trigger();
     }
 
@@ -505,15 +649,18 @@ impl Voice {
         self.state = VoiceState::Playing;
         self.gain = gain;
         self.pan = pan.clamp(-1.0, 1.0);
+        self.pan_target = self.pan;
+        self.orbit = 0;
         self.speed = speed; // Allow negative speed for reverse playback
         self.age = 0;
         self.fadeout_remaining = 0;
         self.last_mono_out = 0.0;
+        self.set_fx(VoiceFx::default());
         self.cut_group = cut_group;
         self.buffer_trigger_offset = None; // Will be set by VoiceManager if needed
 
         // Create and trigger curve envelope
-        self.envelope = VoiceEnvelope::new_curve(SAMPLE_RATE, start, end, duration, curve);
+        self.envelope = VoiceEnvelope::new_curve(self.sample_rate, start, end, duration, curve);
         self.envelope.trigger();
     }
 
@@ -527,6 +674,47 @@ impl Voice {
         self.loop_enabled = enabled;
     }
 
+    /// Set the per-voice effects (resets their running state)
+    pub fn set_fx(&mut self, fx: VoiceFx) {
+        self.fx = fx;
+        self.fx_state = VoiceFxState::default();
+    }
+
+    /// Move the voice in the stereo field. A sounding voice glides there over
+    /// a few milliseconds so the change doesn't zipper; a free one jumps
+    pub fn set_pan(&mut self, pan: f32) {
+        self.pan_target = pan.clamp(-1.0, 1.0);
+        if self.state == VoiceState::Free {
+            self.pan = self.pan_target;
+        }
+    }
+
+    /// Current (smoothed) pan position
+    pub fn pan(&self) -> f32 {
+        self.pan
+    }
+
+    /// Output orbit (0 = the triggering node's own bus)
+    pub fn orbit(&self) -> usize {
+        self.orbit
+    }
+
+    /// Time constant of the pan glide
+    const PAN_GLIDE_SECONDS: f32 = 0.005;
+
+    /// Advance the pan glide by one sample
+    #[inline]
+    fn glide_pan(&mut self) {
+        if self.pan == self.pan_target {
+            return;
+        }
+        let step = 1.0 - (-1.0 / (Self::PAN_GLIDE_SECONDS * self.sample_rate)).exp();
+        self.pan += (self.pan_target - self.pan) * step;
+        if (self.pan_target - self.pan).abs() < 1e-4 {
+            self.pan = self.pan_target;
+        }
+    }
+
     /// Process one sample of audio (mono)
     pub fn process(&mut self) -> f32 {
         let (left, right) = self.process_stereo();
@@ -559,6 +747,7 @@ impl Voice {
         if self.state == VoiceState::Free {
             return (0.0, 0.0);
         }
+        self.glide_pan();
 
         // Zero-crossing fadeout: voice is winding down to silence.
         // Apply linear ramp toward zero; cut immediately on zero crossing.
@@ -664,7 +853,16 @@ impl Voice {
 
             if is_in_bounds {
                 // Get interpolated stereo sample (handles both mono and stereo)
-                let (sample_left, sample_right) = sample.get_interpolated(self.position);
+                let (mut sample_left, mut sample_right) = sample.get_interpolated(self.position);
+                if self.fx.is_active() {
+                    (sample_left, sample_right) = self.fx_state.process(
+                        &self.fx,
+                        sample,
+                        self.position,
+                        self.speed,
+                        (sample_left, sample_right),
+                    );
+                }
 
                 // Apply gain and envelope
                 let gained_left = sample_left * self.gain * env_value;
@@ -812,6 +1010,9 @@ pub struct VoiceManager {
     /// F-4 telemetry: number of voices stolen because the pool was saturated at
     /// the ceiling. Counted atomically for off-thread reporting.
     steal_events: AtomicU64,
+
+    /// Output sample rate (envelope times are converted to samples with it)
+    sample_rate: f32,
 }
 
 impl Default for VoiceManager {
@@ -896,9 +1097,30 @@ impl VoiceManager {
             samples_since_adjustment: 0,
             growth_events: AtomicU64::new(0),
             steal_events: AtomicU64::new(0),
+            sample_rate: SAMPLE_RATE,
+        }
+    }
+
+    /// Create a product-path VoiceManager for a given output sample rate
+    pub fn with_sample_rate(sample_rate: f32) -> Self {
+        let mut manager = Self::new();
+        manager.set_sample_rate(sample_rate);
+        manager
+    }
+
+    /// Set the output sample rate used for envelope timing (all voices)
+    pub fn set_sample_rate(&mut self, sample_rate: f32) {
+        self.sample_rate = sample_rate;
+        for voice in &mut self.voices {
+            voice.sample_rate = sample_rate;
         }
     }
 
+    /// Output sample rate used for envelope timing
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
     /// Shrink the voice pool if too many voices are unused
     /// Only shrinks down to initial_voices, never below
     /// Returns number of voices removed
@@ -929,12 +1151,12 @@ impl VoiceManager {
             let remove_count = current_count - target_size;
             self.voices.truncate(target_size);
 
-            eprintln!(
+            crate::rt_log::log(format_args!(
                 "🔻 Voice pool shrunk: {} → {} voices ({}% usage)",
                 current_count,
                 target_size,
                 (usage_ratio * 100.0) as u32
-            );
+            ));
             remove_count
         } else {
             0
@@ -970,7 +1192,9 @@ impl VoiceManager {
         if voices_to_add > 0 {
             for _ in 0..voices_to_add {
                 // Capacity was reserved to the ceiling ⇒ this never reallocates.
-                self.voices.push(Voice::new());
+                let mut voice = Voice::new();
+                voice.sample_rate = self.sample_rate;
+                self.voices.push(voice);
             }
             // Count atomically for off-thread reporting — no `eprintln!` here.
             self.growth_events
@@ -1334,7 +1558,9 @@ impl VoiceManager {
                 self.voices[idx].synthesis_semitone_offset = semitone_offset; // Pitch offset for note parameter
                 self.voices[idx].state = VoiceState::Playing;
                 self.voices[idx].gain = gain;
-                self.voices[idx].pan = pan;
+                self.voices[idx].pan = pan.clamp(-1.0, 1.0);
+                self.voices[idx].pan_target = self.voices[idx].pan;
+                self.voices[idx].orbit = 0;
                 self.voices[idx].speed = 1.0; // Speed doesn't apply to synthesis
                 self.voices[idx].position = 0.0;
                 self.voices[idx].age = 0;
@@ -1343,7 +1569,7 @@ impl VoiceManager {
                 self.voices[idx].cut_group = cut_group;
                 self.voices[idx].source_node = self.default_source_node;
                 self.voices[idx].envelope =
-                    VoiceEnvelope::new_percussion(SAMPLE_RATE, attack, release);
+                    VoiceEnvelope::new_percussion(self.sample_rate, attack, release);
                 self.voices[idx].envelope.trigger(); // CRITICAL: Start the envelope!
                 self.voices[idx].attack = attack;
                 self.voices[idx].release = release;
@@ -1371,7 +1597,9 @@ impl VoiceManager {
             self.voices[idx].synthesis_semitone_offset = semitone_offset; // Pitch offset for note parameter
             self.voices[idx].state = VoiceState::Playing;
             self.voices[idx].gain = gain;
-            self.voices[idx].pan = pan;
+            self.voices[idx].pan = pan.clamp(-1.0, 1.0);
+            self.voices[idx].pan_target = self.voices[idx].pan;
+            self.voices[idx].orbit = 0;
             self.voices[idx].speed = 1.0;
             self.voices[idx].position = 0.0;
             self.voices[idx].age = 0;
@@ -1379,7 +1607,8 @@ impl VoiceManager {
             self.voices[idx].last_mono_out = 0.0;
             self.voices[idx].cut_group = cut_group;
             self.voices[idx].source_node = self.default_source_node;
-            self.voices[idx].envelope = VoiceEnvelope::new_percussion(SAMPLE_RATE, attack, release);
+            self.voices[idx].envelope =
+                VoiceEnvelope::new_percussion(self.sample_rate, attack, release);
             self.voices[idx].envelope.trigger(); // CRITICAL: Start the envelope!
             self.voices[idx].attack = attack;
             self.voices[idx].release = release;
@@ -1406,7 +1635,9 @@ impl VoiceManager {
         self.voices[oldest_idx].synthesis_sample_cache = 0.0;
         self.voices[oldest_idx].state = VoiceState::Playing;
         self.voices[oldest_idx].gain = gain;
-        self.voices[oldest_idx].pan = pan;
+        self.voices[oldest_idx].pan = pan.clamp(-1.0, 1.0);
+        self.voices[oldest_idx].pan_target = self.voices[oldest_idx].pan;
+        self.voices[oldest_idx].orbit = 0;
         self.voices[oldest_idx].speed = 1.0;
         self.voices[oldest_idx].position = 0.0;
         self.voices[oldest_idx].age = 0;
@@ -1415,7 +1646,7 @@ impl VoiceManager {
         self.voices[oldest_idx].cut_group = cut_group;
         self.voices[oldest_idx].source_node = self.default_source_node;
         self.voices[oldest_idx].envelope =
-            VoiceEnvelope::new_percussion(SAMPLE_RATE, attack, release);
+            VoiceEnvelope::new_percussion(self.sample_rate, attack, release);
         self.voices[oldest_idx].envelope.trigger(); // CRITICAL: Start the envelope!
         self.voices[oldest_idx].attack = attack;
         self.voices[oldest_idx].release = release;
@@ -1584,6 +1815,7 @@ impl VoiceManager {
                 if voice.state == VoiceState::Free {
                     continue; // Skip free voices
                 }
+                voice.glide_pan();
 
                 // Process envelope (scalar - complex state machine)
                 let env_value = if voice.speed < 0.0 {
@@ -1737,7 +1969,7 @@ impl VoiceManager {
                 match handle.join() {
                     Ok(output) => batch_outputs.push(output),
                     Err(e) => {
-                        eprintln!("⚠️  SIMD thread panicked: {:?}. Skipping batch to prevent audio dropout.", e);
+                        crate::rt_log::log(format_args!("⚠️  SIMD thread panicked: {:?}. Skipping batch to prevent audio dropout.", e));
                         // Push empty output to maintain buffer structure
                         batch_outputs.push(vec![HashMap::new(); buffer_size]);
                     }
@@ -1747,10 +1979,10 @@ impl VoiceManager {
 
         // Handle scope panic gracefully
         if let Err(e) = scope_result {
-            eprintln!(
+            crate::rt_log::log(format_args!(
                 "⚠️  Thread scope panicked: {:?}. Returning silent output.",
                 e
-            );
+            ));
             return vec![HashMap::new(); buffer_size];
         }
 
@@ -2030,6 +2262,7 @@ impl VoiceManager {
                         voice.age += 1;
 
                         // Equal-power panning
+                        voice.glide_pan();
                         let pan_radians = (voice.pan + 1.0) * std::f32::consts::FRAC_PI_4;
                         let left_gain = pan_radians.cos();
                         let right_gain = pan_radians.sin();
@@ -2323,6 +2556,14 @@ impl VoiceManager {
         }
     }
 
+    /// Configure per-voice effects for the last triggered voice
+    /// Must be called immediately after a trigger_sample_* method
+    pub fn set_last_voice_fx(&mut self, fx: VoiceFx) {
+        if let Some(idx) = self.last_triggered_voice_index {
+            self.voices[idx].set_fx(fx);
+        }
+    }
+
     /// Configure auto-release time for the last triggered voice (for legato)
     /// Must be called immediately after a trigger_sample_* method
     /// The voice will trigger envelope release when it reaches the specified sample count
@@ -2357,6 +2598,26 @@ impl VoiceManager {
         }
     }
 
+    /// Send the last triggered voice to `orbit`, heard through `orbit_node`
+    /// (an `orbit N` node) instead of its source node's bus. With no node for
+    /// the orbit the voice stays where it is.
+    /// Must be called immediately after a trigger_sample_* method
+    pub fn set_last_voice_orbit(&mut self, orbit: usize, orbit_node: Option<usize>) {
+        if let Some(idx) = self.last_triggered_voice_index {
+            self.voices[idx].orbit = orbit;
+            if let Some(node) = orbit_node {
+                self.voices[idx].source_node = node;
+            }
+        }
+    }
+
+    /// Move a sounding voice in the stereo field, gliding to avoid zipper noise
+    pub fn set_voice_pan(&mut self, index: usize, pan: f32) {
+        if let Some(voice) = self.voices.get_mut(index) {
+            voice.set_pan(pan);
+        }
+    }
+
     /// Set the default source node ID for all future trigger calls
     /// This is applied automatically when voices are triggered
     /// More convenient than calling set_last_voice_source_node after each trigger
@@ -2546,6 +2807,31 @@ impl VoiceManager {
         }
     }
 
+    /// Fade out the oldest sample voices until at most `max_voices` are still
+    /// sounding. Used by adaptive quality to shed load; unlike
+    /// [`cap_active_voices`](Self::cap_active_voices) the stolen voices wind
+    /// down through the zero-crossing fadeout rather than cutting. Synthesis
+    /// voices are left alone.
+    pub fn limit_polyphony(&mut self, max_voices: usize) {
+        let is_sounding_sample = |v: &Voice| {
+            v.state != VoiceState::Free && v.fadeout_remaining == 0 && v.synthesis_node_id.is_none()
+        };
+        let mut sounding = self.voices.iter().filter(|v| is_sounding_sample(v)).count();
+        while sounding > max_voices {
+            let oldest = self
+                .voices
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| is_sounding_sample(v))
+                .max_by_key(|(_, v)| v.age)
+                .map(|(idx, _)| idx);
+            let Some(idx) = oldest else { break };
+            self.voices[idx].begin_fadeout();
+            self.record_steal();
+            sounding -= 1;
+        }
+    }
+
     /// Get peak voice count since startup
     pub fn peak_voice_count(&self) -> usize {
         self.peak_voice_count
@@ -3067,6 +3353,38 @@ mod tests {
         assert!(r.abs() > l.abs(), "Hard right should have more right: l={}, r={}", l, r);
     }
 
+    #[test]
+    fn test_voice_pan_glides_while_sounding() {
+        let mut voice = Voice::new();
+        let sample = make_const_sample(10000, 1.0);
+        voice.trigger(sample, 1.0, -1.0);
+        voice.process_stereo();
+
+        voice.set_pan(1.0);
+        voice.process_stereo();
+        // One sample in, the voice has barely moved: no jump across the field
+        assert!(voice.pan() < -0.9, "pan jumped to {}", voice.pan());
+
+        // ~50ms later it has arrived
+        for _ in 0..2205 {
+            voice.process_stereo();
+        }
+        assert!((voice.pan() - 1.0).abs() < 1e-3, "pan = {}", voice.pan());
+        let (l, r) = voice.process_stereo();
+        assert!(r.abs() > 10.0 * l.abs(), "l={}, r={}", l, r);
+    }
+
+    #[test]
+    fn test_voice_trigger_snaps_pan() {
+        let mut voice = Voice::new();
+        voice.set_pan(0.5);
+        assert_eq!(voice.pan(), 0.5, "a free voice takes its pan at once");
+
+        voice.trigger(make_const_sample(100, 1.0), 1.0, -0.5);
+        assert_eq!(voice.pan(), -0.5);
+        assert_eq!(voice.orbit(), 0);
+    }
+
     #[test]
     fn test_voice_gain_affects_output() {
         let sample = make_const_sample(1000, 1.0);
@@ -3831,6 +4149,59 @@ mod tests {
         assert!(output.contains_key(&99));
     }
 
+    #[test]
+    fn test_vm_orbit_routes_voice_to_orbit_node() {
+        let mut vm = make_small_vm(4);
+        let sample = make_const_sample(10000, 0.5);
+        vm.set_default_source_node(3);
+        vm.trigger_sample(sample.clone(), 1.0);
+        vm.trigger_sample(sample.clone(), 1.0);
+        vm.set_last_voice_orbit(1, Some(7));
+
+        let vb = vm.process_buffer_vec(64, 10);
+        assert!(vb.has_data(3));
+        assert!(vb.has_data(7));
+        // Both voices are equal, so each bus carries one
+        assert!((vb.get(3, 32) - vb.get(7, 32)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_vm_unrouted_orbit_stays_on_source_node() {
+        let mut vm = make_small_vm(4);
+        let sample = make_mono_sample(1000);
+
+        vm.set_default_source_node(5);
+        vm.trigger_sample(sample, 1.0);
+        vm.set_last_voice_orbit(2, None);
+
+        let output = vm.process_per_node_stereo();
+        assert_eq!(output.len(), 1);
+        assert!(output.contains_key(&5));
+    }
+
+    #[test]
+    fn test_vm_limit_polyphony_fades_oldest_sample_voices() {
+        let mut vm = make_small_vm(8);
+        for _ in 0..4 {
+            vm.trigger_sample(make_const_sample(10000, 0.5), 1.0);
+            for _ in 0..10 {
+                vm.process_stereo();
+            }
+        }
+        assert_eq!(vm.active_voice_count(), 4);
+
+        vm.limit_polyphony(2);
+        assert_eq!(vm.steal_event_count(), 2);
+        // Already fading voices don't count, so a second call steals nothing
+        vm.limit_polyphony(2);
+        assert_eq!(vm.steal_event_count(), 2);
+
+        for _ in 0..100 {
+            vm.process_stereo();
+        }
+        assert_eq!(vm.active_voice_count(), 2);
+    }
+
     #[test]
     fn test_vm_set_last_voice_unit_mode() {
         let mut vm = make_small_vm(4);
@@ -4476,4 +4847,83 @@ mod tests {
             "no steals when under the cap"
         );
     }
+
+    // =========================================================================
+    // Per-voice effects
+    // =========================================================================
+
+    #[test]
+    fn test_voice_fx_shape_crush_coarse() {
+        let ramp = make_mono_sample(8);
+        let frame = |fx: VoiceFx, state: &mut VoiceFxState, x: f32| {
+            state.process(&fx, &ramp, 0.0, 1.0, (x, x)).0
+        };
+
+        // shape 0.5: k = 2, so 0.5 -> 0.75 and full scale stays at 1
+        let shape = VoiceFx {
+            shape: 0.5,
+            ..Default::default()
+        };
+        assert!((frame(shape, &mut VoiceFxState::default(), 0.5) - 0.75).abs() < 1e-6);
+        assert!((frame(shape, &mut VoiceFxState::default(), 1.0) - 1.0).abs() < 1e-6);
+
+        // crush 4 rounds to steps of 1/8, crush 1 to whole numbers
+        let crush = VoiceFx {
+            crush: 4.0,
+            ..Default::default()
+        };
+        assert_eq!(frame(crush, &mut VoiceFxState::default(), 0.3), 0.25);
+        let crush = VoiceFx {
+            crush: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(frame(crush, &mut VoiceFxState::default(), 0.7), 1.0);
+
+        // coarse 4 holds every 4th frame
+        let coarse = VoiceFx {
+            coarse: 4.0,
+            ..Default::default()
+        };
+        let mut state = VoiceFxState::default();
+        let held: Vec<f32> = (0..8)
+            .map(|i| frame(coarse, &mut state, i as f32))
+            .collect();
+        assert_eq!(held, vec![0.0, 0.0, 0.0, 0.0, 4.0, 4.0, 4.0, 4.0]);
+
+        // fold 1 drives 8x into -1..1: 0.5 -> 4 folds back to 0
+        let fold = VoiceFx {
+            fold: 1.0,
+            ..Default::default()
+        };
+        assert!(frame(fold, &mut VoiceFxState::default(), 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_voice_fx_squiz_raises_pitch() {
+        // 100-frame sine period
+        let sine: Vec<f32> = (0..4000)
+            .map(|i| (i as f32 * std::f32::consts::TAU / 100.0).sin() * 0.5)
+            .collect();
+        let sample = Arc::new(StereoSample::mono(sine));
+        let upward_crossings = |squiz: f32| {
+            let mut voice = Voice::new();
+            voice.trigger_with_envelope(sample.clone(), 1.0, 0.0, 1.0, None, 0.0001, 1.0);
+            voice.set_fx(VoiceFx {
+                squiz,
+                ..Default::default()
+            });
+            let out: Vec<f32> = (0..2000).map(|_| voice.process_stereo().0).collect();
+            out.windows(2).filter(|w| w[0] <= 0.0 && w[1] > 0.0).count()
+        };
+
+        let plain = upward_crossings(0.0);
+        let squizzed = upward_crossings(2.0);
+        assert!((19..=21).contains(&plain), "plain: {}", plain);
+        assert!(
+            (37..=41).contains(&squizzed),
+            "squiz 2 should double the pitch: {} vs {}",
+            squizzed,
+            plain
+        );
+    }
 }
@@ -0,0 +1,187 @@
+//! West Coast voice: `s "westcoast"` plays a sine through a wavefolder and a
+//! low-pass gate, the Buchla-style plucked "bongo"
+//!
+//! ```phonon
+//! out $ s "westcoast*4" # note "c3 g3 bf3 f4" # fold "0.2 0.8" # strike 0.7
+//! ```
+//!
+//! `# fold` (0-1) drives the sine into the folder ([`FoldNode`]) up to 8 times
+//! past its threshold, folding it back on itself for bright, hollow
+//! overtones. `# strike` (0-1, default [`DEFAULT_STRIKE`]) is how hard the
+//! gate is pinged: a harder strike opens it further, so the note is louder
+//! and brighter and rings longer. The gate responds like a vactrol, closing
+//! quickly at first and then slowly, and it lowers the cutoff as it closes,
+//! so the note darkens as it dies away.
+//!
+//! Each note is rendered once when it triggers and played as a sample
+//! voice, so gain, pan, cut groups and orbits work as for any sample.
+
+use crate::nodes::FoldNode;
+use crate::sample_loader::StereoSample;
+
+/// The sample name that plays the voice
+pub const VOICE_NAME: &str = "westcoast";
+/// Pitch of note 0 (C4); `# note` and `# speed` scale it as they would a
+/// sample's playback rate
+pub const BASE_HZ: f32 = 261.63;
+/// `# strike` when none is given
+pub const DEFAULT_STRIKE: f32 = 0.7;
+/// Gain into the folder at `fold 1`
+const MAX_DRIVE: f32 = 8.0;
+/// Longest note, in seconds, however hard it's struck
+const MAX_SECONDS: f32 = 4.0;
+/// Gate level treated as closed
+const CLOSED: f32 = 1e-3;
+/// Vactrol rise time, in seconds
+const VACTROL_ATTACK: f32 = 0.002;
+/// Vactrol decay time constants, in seconds: fast while the gate is wide
+/// open, slow as it nears closing
+const VACTROL_FAST_DECAY: f32 = 0.04;
+const VACTROL_SLOW_DECAY: f32 = 0.2;
+/// Gate cutoff when closed, in Hz; it rises 9 octaves when fully open,
+/// most of them in the first part of the gate's travel
+const GATE_CUTOFF_CLOSED: f32 = 40.0;
+
+/// Fold `x` by `amount` (0-1): 0 leaves it alone, 1 drives it 8x into the
+/// -1..1 folder
+#[inline]
+pub fn fold(x: f32, amount: f32) -> f32 {
+    let drive = 1.0 + amount.clamp(0.0, 1.0) * (MAX_DRIVE - 1.0);
+    FoldNode::fold_value(x * drive, -1.0, 1.0)
+}
+
+/// One West Coast note
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WestCoast {
+    /// Pitch in Hz
+    pub freq: f32,
+    /// Wavefolding, 0-1
+    pub fold: f32,
+    /// How hard the low-pass gate is struck, 0-1
+    pub strike: f32,
+}
+
+impl WestCoast {
+    pub fn new(freq: f32, fold: f32, strike: f32) -> Self {
+        Self {
+            freq: freq.max(0.0),
+            fold: fold.clamp(0.0, 1.0),
+            strike: strike.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Render the note until the gate has closed
+    pub fn render(&self, sample_rate: f32) -> StereoSample {
+        let max_len = (MAX_SECONDS * sample_rate) as usize;
+        let increment = self.freq / sample_rate;
+        let mut gate = LowPassGate::new(self.strike, sample_rate);
+        let mut phase = 0.0f32;
+        let mut out = Vec::with_capacity(max_len / 4);
+        while out.len() < max_len && !gate.is_closed() {
+            let sine = (phase * std::f32::consts::TAU).sin();
+            phase = (phase + increment).fract();
+            out.push(gate.process(fold(sine, self.fold)));
+        }
+        StereoSample::mono(out)
+    }
+}
+
+/// A vactrol low-pass gate: one control level opens a two-pole lowpass and
+/// a VCA together
+#[derive(Debug, Clone)]
+struct LowPassGate {
+    strike: f32,
+    level: f32,
+    attacking: bool,
+    attack_coef: f32,
+    lp: (f32, f32),
+    sample_rate: f32,
+}
+
+impl LowPassGate {
+    fn new(strike: f32, sample_rate: f32) -> Self {
+        Self {
+            strike,
+            level: 0.0,
+            attacking: strike > CLOSED,
+            attack_coef: 1.0 - (-1.0 / (VACTROL_ATTACK * sample_rate)).exp(),
+            lp: (0.0, 0.0),
+            sample_rate,
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        !self.attacking && self.level < CLOSED
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        if self.attacking {
+            self.level += (self.strike - self.level) * self.attack_coef;
+            self.attacking = self.level < self.strike * 0.99;
+        } else {
+            // The photoresistor lets go quickly and then lingers
+            let open = self.level / self.strike;
+            let tau = VACTROL_SLOW_DECAY + (VACTROL_FAST_DECAY - VACTROL_SLOW_DECAY) * open;
+            self.level *= (-1.0 / (tau * self.sample_rate)).exp();
+        }
+
+        let cutoff =
+            (GATE_CUTOFF_CLOSED * 2f32.powf(self.level.sqrt() * 9.0)).min(self.sample_rate * 0.45);
+        let g = 1.0 - (-std::f32::consts::TAU * cutoff / self.sample_rate).exp();
+        self.lp.0 += g * (x - self.lp.0);
+        self.lp.1 += g * (self.lp.0 - self.lp.1);
+        self.lp.1 * self.level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SR: f32 = 44100.0;
+
+    fn samples(note: WestCoast) -> Vec<f32> {
+        let rendered = note.render(SR);
+        (0..rendered.len())
+            .map(|i| rendered.get_interpolated(i as f32).0)
+            .collect()
+    }
+
+    fn peak(samples: &[f32]) -> f32 {
+        samples.iter().fold(0.0f32, |m, s| m.max(s.abs()))
+    }
+
+    fn upward_crossings(samples: &[f32]) -> usize {
+        samples
+            .windows(2)
+            .filter(|w| w[0] <= 0.0 && w[1] > 0.0)
+            .count()
+    }
+
+    #[test]
+    fn test_fold_amount() {
+        assert_eq!(fold(0.5, 0.0), 0.5);
+        // 0.5 * 8 = 4 folds back to 0
+        assert!(fold(0.5, 1.0).abs() < 1e-6);
+        assert!((-1.0..=1.0).contains(&fold(0.9, 0.6)));
+    }
+
+    #[test]
+    fn test_strike_sets_level_and_length() {
+        let soft = samples(WestCoast::new(220.0, 0.0, 0.3));
+        let hard = samples(WestCoast::new(220.0, 0.0, 0.9));
+        assert!(peak(&hard) > peak(&soft) * 1.5);
+        assert!(hard.len() > soft.len());
+        assert!(hard.len() < (MAX_SECONDS * SR) as usize, "the gate closes");
+        assert!(samples(WestCoast::new(220.0, 0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn test_fold_adds_overtones() {
+        let plain = samples(WestCoast::new(110.0, 0.0, 1.0));
+        let folded = samples(WestCoast::new(110.0, 0.8, 1.0));
+        // The first 50 ms, while the gate is wide open
+        let window = (0.05 * SR) as usize;
+        assert!(upward_crossings(&folded[..window]) > 2 * upward_crossings(&plain[..window]));
+    }
+}
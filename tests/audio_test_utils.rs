@@ -4,6 +4,9 @@
 // This module provides reusable functions for analyzing audio signals in tests,
 // particularly for verifying frequency-related DSP parameters.
 
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::unified_graph::UnifiedSignalGraph;
 use rustfft::{num_complex::Complex, FftPlanner};
 
 /// Find the dominant frequency in an audio buffer using FFT
@@ -284,6 +287,74 @@ pub fn band_energy(buffer: &[f32], sample_rate: f32, center_hz: f32, half_width_
         .sum()
 }
 
+/// Amplitude of a single frequency, by a DFT at just that frequency
+///
+/// Exact when the buffer holds a whole number of cycles of `freq` (e.g. a
+/// whole number of Hz over exactly one second), so harmonics and aliases can
+/// be measured one by one without FFT bin leakage.
+///
+/// # Arguments
+/// * `buffer` - Audio samples to analyze
+/// * `sample_rate` - Sample rate in Hz
+/// * `freq` - Frequency to measure in Hz
+///
+/// # Returns
+/// The peak amplitude of the `freq` component (1.0 for a full-scale sine)
+pub fn magnitude_at(buffer: &[f32], sample_rate: f32, freq: f32) -> f32 {
+    let (re, im) = buffer
+        .iter()
+        .enumerate()
+        .fold((0.0f64, 0.0f64), |(re, im), (i, x)| {
+            let phase = 2.0 * std::f64::consts::PI * freq as f64 * i as f64 / sample_rate as f64;
+            (re + *x as f64 * phase.cos(), im - *x as f64 * phase.sin())
+        });
+    (2.0 * (re * re + im * im).sqrt() / buffer.len() as f64) as f32
+}
+
+/// Parse and compile a program
+///
+/// Panics if the program doesn't parse, or parses only in part, so a typo
+/// in a test program can't pass as the compile error a test expects.
+///
+/// # Arguments
+/// * `code` - Program source
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Returns
+/// The compiled graph, or the compile error
+pub fn compile_code_at(code: &str, sample_rate: f32) -> Result<UnifiedSignalGraph, String> {
+    let (rest, statements) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    compile_program(statements, sample_rate, None)
+}
+
+/// [`compile_code_at`] at 44.1 kHz
+pub fn compile_code(code: &str) -> Result<UnifiedSignalGraph, String> {
+    compile_code_at(code, 44100.0)
+}
+
+/// Compile a program at 44.1 kHz and render it
+///
+/// # Arguments
+/// * `code` - Program source, which must compile
+/// * `samples` - Number of samples to render
+///
+/// # Returns
+/// The rendered samples, through the default master limiter
+pub fn render_code(code: &str, samples: usize) -> Vec<f32> {
+    compile_code(code)
+        .expect("Failed to compile")
+        .render(samples)
+}
+
+/// [`render_code`] with the master limiter out of the way, for tests that
+/// measure levels near or above full scale
+pub fn render_unlimited(code: &str, samples: usize) -> Vec<f32> {
+    let mut graph = compile_code(code).expect("Failed to compile");
+    graph.set_master_limiter_ceiling(1.0);
+    graph.render(samples)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
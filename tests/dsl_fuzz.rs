@@ -38,6 +38,7 @@ use proptest::prelude::*;
 use proptest::test_runner::{Config, RngAlgorithm, TestRng, TestRunner};
 
 use phonon::compositional_parser::parse_program;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::parse_dsl;
 
 // ---------------------------------------------------------------------------
@@ -49,6 +50,7 @@ use phonon::unified_graph_parser::parse_dsl;
 /// failure — acceptable under the contract, never a silent drop).
 type Summary = Result<(usize, String), ()>;
 
+#[allow(deprecated)]
 fn dsl_summary(src: &str) -> Summary {
     match parse_dsl(src) {
         Ok((rem, stmts)) => Ok((stmts.len(), rem.trim().to_string())),
@@ -313,6 +315,7 @@ fn assert_no_panic(label: &str, parse: impl Fn(&str)) {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_fuzz_parse_dsl_never_panics() {
     assert_no_panic("parse_dsl", |s| {
         let _ = parse_dsl(s);
@@ -423,6 +426,7 @@ fn test_dsl_fuzz_parse_program_no_silent_drop() {
 // ---------------------------------------------------------------------------
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_fuzz_no_silent_statement_drop() {
     // Two statements: a struct-chained source, then a trailing `out` that MUST
     // survive. Pre-fix this parsed to a single statement with a `$ sine ...` tail.
@@ -458,6 +462,7 @@ fn test_dsl_fuzz_no_silent_statement_drop() {
 // ---------------------------------------------------------------------------
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_fuzz_known_bug_modifier_bus_struct_chaining_drops() {
     // A struct-chained MODIFIER bus followed by a trailing `out` statement. The
     // trailing statement must survive once the modifier-bus path reaches parity
@@ -486,6 +491,7 @@ fn test_dsl_fuzz_known_bug_modifier_bus_struct_chaining_drops() {
 // ---------------------------------------------------------------------------
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_fuzz_malformed_corpus_no_panic() {
     let corpus: Vec<String> = vec![
         // empty / whitespace-only
@@ -593,6 +599,7 @@ fn assert_terminates(label: &str, f: impl FnOnce() + Send + 'static) {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_fuzz_terminates_on_pathological_input() {
     let cases: Vec<String> = vec![
         // absurdly long repeat-count digit run (parsed, never expanded at parse time)
@@ -1,8 +1,10 @@
 //! DSP audio verification tests
 
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
 
 #[test]
+#[allow(deprecated)]
 fn test_sine_wave_generation() {
     // Verify sine wave DSP code parses
     let code = "out: sin 440";
@@ -14,6 +16,7 @@ fn test_sine_wave_generation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_amplitude_modulation() {
     // Test amplitude modulation chain
     let code = "out: sin 440 >> mul 0.5";
@@ -22,6 +25,7 @@ fn test_amplitude_modulation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_low_pass_filter() {
     // Test low pass filter
     let code = "o: saw 110 >> lpf 1000 0.8";
@@ -33,6 +37,7 @@ fn test_low_pass_filter() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_high_pass_filter() {
     // Test high pass filter
     let code = "out: noise >> hpf 2000 0.9";
@@ -41,6 +46,7 @@ fn test_high_pass_filter() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_additive_synthesis() {
     // Test adding multiple oscillators
     let code = r#"
@@ -56,6 +62,7 @@ fn test_additive_synthesis() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_lfo_modulation() {
     // Test LFO modulating filter cutoff
     let code = r#"
@@ -67,6 +74,7 @@ fn test_lfo_modulation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_envelope() {
     // Test envelope generation
     let code = "out: sin 440 >> env 0.01 0.1 0.7 0.2";
@@ -75,6 +83,7 @@ fn test_envelope() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_delay_effect() {
     // Test delay line
     let code = r#"
@@ -87,6 +96,7 @@ fn test_delay_effect() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_reverb_effect() {
     // Test reverb
     let code = "out: impulse 1 >> reverb 0.9 0.5";
@@ -95,6 +105,7 @@ fn test_reverb_effect() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_complex_patch() {
     // Test a complex synthesizer patch
     let code = r#"~lfo1: sin 0.2 >> mul 0.5 >> add 0.5
@@ -113,6 +124,7 @@ out: ~filtered * 0.7 + ~verb * 0.3"#;
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_fm_synthesis() {
     // Test frequency modulation
     let code = r#"~mod: sin 220 >> mul 100
@@ -126,6 +138,7 @@ out: ~carrier >> mul 0.5"#;
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_ring_modulation() {
     // Test ring modulation
     let code = r#"
@@ -138,6 +151,7 @@ fn test_ring_modulation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_noise_generators() {
     // Test different noise types
     let code = r#"
@@ -154,6 +168,7 @@ fn test_noise_generators() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_distortion() {
     // Test distortion/saturation
     let code = "out: sin 220 >> mul 5 >> clip -0.7 0.7";
@@ -162,6 +177,7 @@ fn test_distortion() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_chorus_effect() {
     // Test chorus effect
     let code = r#"
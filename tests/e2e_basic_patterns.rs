@@ -8,6 +8,7 @@
 
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -49,6 +50,7 @@ fn query_single_cycle<T: Clone + Send + Sync + 'static>(
     pattern.query(&state)
 }
 
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_secs: f32) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Parse DSL");
     let compiler = DslCompiler::new(44100.0);
@@ -3,6 +3,7 @@
 //! These tests actually generate audio and verify the output is mathematically correct
 
 use phonon::signal_executor::AudioBuffer;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::render_dsp_to_audio_simple as render_dsp_to_audio;
 use std::f32::consts::PI;
 
@@ -37,6 +38,7 @@ fn has_frequency(buffer: &AudioBuffer, freq: f32, threshold: f32) -> bool {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_sine_wave_generation() {
     let code = "out: sin 440";
     let buffer = render_dsp_to_audio(code, 44100.0, 0.1).unwrap();
@@ -59,6 +61,7 @@ fn test_sine_wave_generation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scalar_multiplication() {
     let code = "out: sin 440 * 0.5";
     let buffer = render_dsp_to_audio(code, 44100.0, 0.1).unwrap();
@@ -73,6 +76,7 @@ fn test_scalar_multiplication() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_addition_mixing() {
     let code = r#"
         ~sine1: sin 440
@@ -93,6 +97,7 @@ fn test_addition_mixing() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_subtraction() {
     // Subtracting identical signals should give silence
     let code = r#"
@@ -110,6 +115,7 @@ fn test_subtraction() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_low_pass_filter() {
     let code = "out: noise >> lpf 1000 0.8";
     let buffer = render_dsp_to_audio(code, 44100.0, 0.5).unwrap();
@@ -128,6 +134,7 @@ fn test_low_pass_filter() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_amplitude_modulation() {
     let code = "out: sin 440 >> mul 0.5";
     let buffer = render_dsp_to_audio(code, 44100.0, 0.1).unwrap();
@@ -138,6 +145,7 @@ fn test_amplitude_modulation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_multiple_operators() {
     // Test that multiple operations work correctly
     let code = "out: sin 440 * 0.5 + sin 880 * 0.3";
@@ -152,6 +160,7 @@ fn test_multiple_operators() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_save_wav_file() {
     // Generate a test signal and save it
     let code = r#"
@@ -176,6 +185,7 @@ fn test_save_wav_file() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_envelope() {
     let code = "out: sin 440 >> env 0.01 0.1 0.7 0.2";
     let buffer = render_dsp_to_audio(code, 44100.0, 0.5).unwrap();
@@ -202,6 +212,7 @@ fn test_envelope() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_noise_types() {
     // Test different noise generators
     let white_buffer = render_dsp_to_audio("out: noise", 44100.0, 0.1).unwrap();
@@ -222,6 +233,7 @@ fn test_noise_types() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_complex_arithmetic() {
     // Test complex expression evaluation
     let code = r#"
@@ -0,0 +1,332 @@
+//! Race-detection harness for the synth → audio callback sample ring.
+//!
+//! The modal editor renders into an SPSC ring on the synth thread and plays it
+//! from the device callback (`src/ring_handoff.rs`). Hush, panic and `:cue`
+//! drop the audio already queued so the change is heard at once.
+//!
+//! ## The defect being modeled
+//!
+//! The UI used to send the command and set a `should_clear_ring` flag that the
+//! **audio callback** consumed, skipping everything queued. Nothing ordered that
+//! skip after the synth thread applied the command: the callback could clear
+//! first, the synth thread then rendered one more block with the old graph, and
+//! that pre-hush block was played. The fix moves the flag to the synth thread,
+//! which reads it *before* draining commands and, after applying them, publishes
+//! a sample-count mark (`Release`) that the callback skips up to (`Acquire`).
+//!
+//! ## Two execution modes
+//!
+//! * **Normal `cargo test`** (and Miri) runs `hand_model`: the real
+//!   `RingWriter` / `RingReader` driven through replayed interleavings, plus a
+//!   threaded run of the whole handoff.
+//!
+//! * **`RUSTFLAGS="--cfg loom" cargo test --test loom_ring_handoff`** runs
+//!   `loom_models`: the ring's slots in `loom::cell::UnsafeCell`s (so loom sees
+//!   every sample access) with the old and new clear protocols. loom finds the
+//!   stale-block interleaving in the old one and no race or stale sample in the
+//!   new one. See `docs/RACE_DETECTION.md`.
+
+// `cfg(loom)` is a custom cfg set via RUSTFLAGS, not a built-in; silence the
+// `unexpected_cfgs` lint for this test crate rather than editing crate-wide lints.
+#![allow(unexpected_cfgs)]
+
+/// Sample values: rendered before / after the command took effect
+const BEFORE: f32 = 1.0;
+const AFTER: f32 = 2.0;
+
+// ============================================================================
+// Hand-model — runs under normal `cargo test` (and Miri).
+// ============================================================================
+#[cfg(not(loom))]
+mod hand_model {
+    use super::{AFTER, BEFORE};
+    use phonon::ring_handoff::{sample_ring, RingReader, RingWriter};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    fn ring(capacity: usize) -> (RingWriter, RingReader) {
+        sample_ring(capacity, Arc::new(AtomicUsize::new(0)))
+    }
+
+    /// The interleaving the old protocol lost: the callback runs between the
+    /// UI's send and the synth thread applying it. Nothing is skipped early,
+    /// and once the synth thread marks the boundary only the old audio goes
+    #[test]
+    fn callback_before_apply_skips_nothing_then_all_stale() {
+        let (mut writer, mut reader) = ring(64);
+        writer.push(&[BEFORE; 8]);
+
+        // UI has sent the command; the synth thread hasn't applied it and
+        // renders another block with the old graph
+        assert!(!reader.skip_stale());
+        writer.push(&[BEFORE; 8]);
+
+        // Synth thread applies the command, marks, renders the new sound
+        writer.discard_queued();
+        writer.push(&[AFTER; 8]);
+
+        assert!(reader.skip_stale());
+        let mut data = [0.0; 8];
+        reader.fill(&mut data);
+        assert_eq!(data, [AFTER; 8]);
+    }
+
+    /// Samples the callback already played still count towards the mark, so
+    /// the skip stops at the boundary rather than eating new audio
+    #[test]
+    fn partially_played_queue_skips_to_the_boundary() {
+        let (mut writer, mut reader) = ring(16);
+        writer.push(&[BEFORE; 6]);
+        let mut data = [0.0; 4];
+        reader.fill(&mut data);
+        writer.discard_queued();
+        writer.push(&[AFTER; 4]);
+
+        assert!(reader.skip_stale());
+        reader.fill(&mut data);
+        assert_eq!(data, [AFTER; 4]);
+    }
+
+    /// The whole handoff on real threads: a UI thread sends a command and sets
+    /// the flag, the synth thread applies it at a block boundary, the callback
+    /// plays. After the callback first skips, it never plays old audio
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn threaded_handoff_never_plays_stale_audio_after_skip() {
+        for _ in 0..200 {
+            let (mut writer, mut reader) = ring(256);
+            let command = Arc::new(AtomicBool::new(false));
+            let should_clear = Arc::new(AtomicBool::new(false));
+            let done = Arc::new(AtomicBool::new(false));
+
+            let ui = {
+                let command = Arc::clone(&command);
+                let should_clear = Arc::clone(&should_clear);
+                thread::spawn(move || {
+                    thread::yield_now();
+                    command.store(true, Ordering::Release);
+                    should_clear.store(true, Ordering::Release);
+                })
+            };
+
+            let synth = {
+                let command = Arc::clone(&command);
+                let should_clear = Arc::clone(&should_clear);
+                let done = Arc::clone(&done);
+                thread::spawn(move || {
+                    let mut applied = false;
+                    // Blocks left to render once the clear is handled
+                    let mut remaining = None;
+                    while remaining != Some(0) {
+                        let clear = should_clear.swap(false, Ordering::Acquire);
+                        applied |= command.load(Ordering::Acquire);
+                        if clear {
+                            writer.discard_queued();
+                            remaining = Some(16);
+                        }
+                        remaining = remaining.map(|blocks: usize| blocks - 1);
+                        let value = if applied { AFTER } else { BEFORE };
+                        writer.push(&[value; 8]);
+                        thread::yield_now();
+                    }
+                    done.store(true, Ordering::Release);
+                })
+            };
+
+            let mut skipped = false;
+            let mut data = [0.0; 4];
+            loop {
+                let finished = done.load(Ordering::Acquire);
+                skipped |= reader.skip_stale();
+                let available = reader.occupied_len().min(data.len());
+                reader.fill(&mut data[..available]);
+                if skipped {
+                    assert!(data[..available].iter().all(|&s| s == AFTER));
+                }
+                if finished && reader.occupied_len() == 0 {
+                    break;
+                }
+            }
+            ui.join().unwrap();
+            synth.join().unwrap();
+        }
+    }
+}
+
+// ============================================================================
+// loom exhaustive models — run only under `RUSTFLAGS="--cfg loom"`.
+// ============================================================================
+#[cfg(loom)]
+mod loom_models {
+    use super::{AFTER, BEFORE};
+    use loom::cell::UnsafeCell;
+    use loom::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use loom::sync::Arc;
+    use loom::thread;
+
+    const CAPACITY: usize = 4;
+
+    /// The SPSC ring, reduced to its protocol: the writer fills a slot then
+    /// publishes `tail` (`Release`), the reader loads `tail` (`Acquire`) before
+    /// reading the slot. Slots are loom cells, so a missing happens-before on
+    /// any sample is reported as a race
+    struct Ring {
+        slots: [UnsafeCell<f32>; CAPACITY],
+        head: AtomicUsize,
+        tail: AtomicUsize,
+        /// `RingWriter::discard_queued`'s mark
+        stale_until: AtomicUsize,
+    }
+
+    unsafe impl Sync for Ring {}
+    unsafe impl Send for Ring {}
+
+    impl Ring {
+        fn new() -> Self {
+            Self {
+                slots: [
+                    UnsafeCell::new(0.0),
+                    UnsafeCell::new(0.0),
+                    UnsafeCell::new(0.0),
+                    UnsafeCell::new(0.0),
+                ],
+                head: AtomicUsize::new(0),
+                tail: AtomicUsize::new(0),
+                stale_until: AtomicUsize::new(0),
+            }
+        }
+
+        /// Writer side
+        fn push(&self, value: f32) {
+            let tail = self.tail.load(Ordering::Relaxed);
+            if tail - self.head.load(Ordering::Acquire) < CAPACITY {
+                self.slots[tail % CAPACITY].with_mut(|p| unsafe { *p = value });
+                self.tail.store(tail + 1, Ordering::Release);
+            }
+        }
+
+        /// Reader side
+        fn pop(&self) -> Option<f32> {
+            let head = self.head.load(Ordering::Relaxed);
+            if head == self.tail.load(Ordering::Acquire) {
+                return None;
+            }
+            let value = self.slots[head % CAPACITY].with(|p| unsafe { *p });
+            self.head.store(head + 1, Ordering::Release);
+            Some(value)
+        }
+
+        /// Reader side: skip `count` queued samples
+        fn skip(&self, count: usize) {
+            let head = self.head.load(Ordering::Relaxed);
+            let occupied = self.tail.load(Ordering::Acquire) - head;
+            self.head
+                .store(head + count.min(occupied), Ordering::Release);
+        }
+    }
+
+    /// The UI thread: send the command, then set the clear flag
+    fn spawn_ui(
+        command: &Arc<AtomicBool>,
+        should_clear: &Arc<AtomicBool>,
+    ) -> thread::JoinHandle<()> {
+        let command = Arc::clone(command);
+        let should_clear = Arc::clone(should_clear);
+        thread::spawn(move || {
+            command.store(true, Ordering::Release);
+            should_clear.store(true, Ordering::Release);
+        })
+    }
+
+    /// BASELINE: the callback consumes the flag and skips everything queued.
+    /// loom finds the schedule where the synth thread renders a block with
+    /// the old graph after the clear, and the callback plays it
+    #[test]
+    #[should_panic]
+    fn baseline_callback_clear_plays_stale_block() {
+        loom::model(|| {
+            let ring = Arc::new(Ring::new());
+            let command = Arc::new(AtomicBool::new(false));
+            let should_clear = Arc::new(AtomicBool::new(false));
+            let ui = spawn_ui(&command, &should_clear);
+
+            let synth = {
+                let ring = Arc::clone(&ring);
+                let command = Arc::clone(&command);
+                thread::spawn(move || {
+                    for _ in 0..2 {
+                        let applied = command.load(Ordering::Acquire);
+                        ring.push(if applied { AFTER } else { BEFORE });
+                    }
+                })
+            };
+
+            let mut cleared = false;
+            for _ in 0..2 {
+                if should_clear.swap(false, Ordering::Acquire) {
+                    ring.skip(CAPACITY);
+                    cleared = true;
+                }
+                if let Some(sample) = ring.pop() {
+                    assert!(!cleared || sample == AFTER, "stale audio after clear");
+                }
+            }
+
+            ui.join().unwrap();
+            synth.join().unwrap();
+        });
+    }
+
+    /// TARGET: the synth thread reads the flag before applying the command and
+    /// marks the boundary after. Every sample access is ordered, and once the
+    /// callback has seen the mark it never plays audio from before the command
+    #[test]
+    fn synth_side_mark_is_race_free_and_never_stale() {
+        loom::model(|| {
+            let ring = Arc::new(Ring::new());
+            let command = Arc::new(AtomicBool::new(false));
+            let should_clear = Arc::new(AtomicBool::new(false));
+            let ui = spawn_ui(&command, &should_clear);
+
+            let synth = {
+                let ring = Arc::clone(&ring);
+                let command = Arc::clone(&command);
+                let should_clear = Arc::clone(&should_clear);
+                thread::spawn(move || {
+                    let mut applied = false;
+                    for _ in 0..2 {
+                        let clear = should_clear.swap(false, Ordering::Acquire);
+                        applied |= command.load(Ordering::Acquire);
+                        if clear {
+                            let written = ring.tail.load(Ordering::Relaxed);
+                            ring.stale_until.store(written, Ordering::Release);
+                        }
+                        ring.push(if applied { AFTER } else { BEFORE });
+                    }
+                })
+            };
+
+            let mut read = 0;
+            let mut marked = false;
+            for _ in 0..2 {
+                let stale_until = ring.stale_until.load(Ordering::Acquire);
+                if stale_until > read {
+                    let occupied =
+                        ring.tail.load(Ordering::Acquire) - ring.head.load(Ordering::Relaxed);
+                    let stale = (stale_until - read).min(occupied);
+                    ring.skip(stale);
+                    read += stale;
+                    marked = true;
+                }
+                if let Some(sample) = ring.pop() {
+                    read += 1;
+                    assert!(!marked || sample == AFTER, "stale audio after mark");
+                }
+            }
+
+            ui.join().unwrap();
+            synth.join().unwrap();
+        });
+    }
+}
@@ -2,6 +2,7 @@
 //! Tests the actual signal processing, not just parsing
 
 use phonon::glicol_dsp::{DspChain, DspNode};
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
 
 /// Test that we can create and execute basic arithmetic operations
@@ -43,6 +44,7 @@ fn test_mix_node_structure() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_addition_creates_mix() {
     // Test that + operator creates a Mix node
     let code = r#"
@@ -63,6 +65,7 @@ fn test_addition_creates_mix() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scalar_multiplication_parsing() {
     // Test that * with a number creates a Mul node
     let code = r#"
@@ -79,6 +82,7 @@ fn test_scalar_multiplication_parsing() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_subtraction_creates_inverted_mix() {
     // Test that - operator creates a Mix with inverted second source
     let code = r#"
@@ -105,6 +109,7 @@ fn test_subtraction_creates_inverted_mix() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_complex_arithmetic_expression() {
     // Test a more complex expression
     let code = r#"
@@ -124,6 +129,7 @@ fn test_complex_arithmetic_expression() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_operator_precedence() {
     // Test that * has higher precedence than +
     let code = r#"
@@ -16,11 +16,13 @@
 //! from the dirt-samples search path.
 
 use phonon::unified_graph::UnifiedSignalGraph;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 const SR: f32 = 44100.0;
 const BUF: usize = 512;
 
+#[allow(deprecated)]
 fn build(code: &str) -> UnifiedSignalGraph {
     let (_, statements) = parse_dsl(code).expect("parse DSL");
     DslCompiler::new(SR).compile(statements)
@@ -0,0 +1,47 @@
+/// Tests for the `a4` reference pitch and global `transpose` statements
+///
+/// Both settings are process-wide, so everything runs in one test
+mod audio_test_utils;
+use audio_test_utils::{compile_code, render_unlimited};
+
+fn assert_same(code: &str, reference: &str) {
+    let (a, b) = (
+        render_unlimited(code, 4410),
+        render_unlimited(reference, 4410),
+    );
+    for (i, (x, y)) in a.iter().zip(&b).enumerate() {
+        assert!((x - y).abs() < 1e-3, "{} vs {} at {}: {} vs {}", code, reference, i, x, y);
+    }
+}
+
+#[test]
+fn test_a4_and_transpose() {
+    assert_same("a4 432\nout $ sine \"a4\" * 0.5", "out $ sine 432 * 0.5");
+    assert_same("a4 432\nout $ sine a5 * 0.5", "out $ sine 864 * 0.5");
+
+    // Both still apply below a call line
+    assert_same("out $ sine \"a4\" * 0.5\na4 432", "out $ sine 432 * 0.5");
+    assert_same(
+        "out $ sine \"a4\" * 0.5\ntranspose 12",
+        "out $ sine 880 * 0.5",
+    );
+
+    // -2 semitones from A4 is G4
+    let g4 = 440.0 * 2f64.powf(-2.0 / 12.0);
+    assert_same(
+        "transpose -2st\nout $ sine \"a4\" * 0.5",
+        &format!("out $ sine {} * 0.5", g4),
+    );
+    assert_same(
+        "a4 432\ntranspose 12\nout $ sine \"a4\" * 0.5",
+        "out $ sine 864 * 0.5",
+    );
+
+    // Hz values are not notes and stay put
+    assert_same("transpose 7st\nout $ sine 440 * 0.5", "out $ sine 440 * 0.5");
+
+    // Without the statements, back to 440 and untransposed
+    assert_same("out $ sine \"a4\" * 0.5", "out $ sine 440 * 0.5");
+
+    assert!(compile_code("a4 0\nout $ sine 440").is_err());
+}
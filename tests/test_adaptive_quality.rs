@@ -0,0 +1,47 @@
+/// Tests for adaptive quality: the graph's reduced levels and the editor's
+/// `:quality` command
+use phonon::adaptive_quality::Quality;
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code};
+
+#[test]
+fn test_reduced_quality_keeps_the_sound() {
+    let code = "tempo: 1.0\nout $ saw 110 # saturate 4.0 # reverb 0.8 0.5 0.5";
+    let full = compile_code(code).expect("Failed to compile").render(22050);
+
+    let mut graph = compile_code(code).expect("Failed to compile");
+    graph.set_quality(Quality::Reduced);
+    assert_eq!(graph.quality(), Quality::Reduced);
+    let reduced = graph.render(22050);
+
+    assert!(reduced.iter().all(|s| s.is_finite()));
+    // Thinner, not quieter or louder
+    let ratio = calculate_rms(&reduced) / calculate_rms(&full);
+    assert!((0.7..1.3).contains(&ratio), "rms ratio {}", ratio);
+    assert_ne!(full, reduced);
+}
+
+#[test]
+fn test_quality_console_command() {
+    let mut harness = EditorTestHarness::with_content("out $ sine 440").unwrap();
+
+    harness.console_command(":quality locked");
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|m| m.contains("Quality locked")));
+
+    harness.console_command(":quality auto");
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|m| m.contains("Quality adaptive, now full quality")));
+
+    harness.console_command(":quality sometimes");
+    assert!(harness
+        .command_output()
+        .iter()
+        .any(|l| l.contains("Usage: :quality")));
+}
@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
 use std::collections::HashMap;
 
@@ -206,6 +208,7 @@ fn test_polyrhythm_with_alternation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_alternation_audio_generation() {
     println!("\n=== Testing Alternation Audio Generation ===");
 
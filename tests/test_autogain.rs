@@ -0,0 +1,45 @@
+/// Tests for `autogain ~bus` level normalization
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code};
+
+#[test]
+fn test_autogain_reaches_target_rms() {
+    // -18 dBFS RMS
+    let target = 10f32.powf(-18.0 / 20.0);
+    for level in ["0.05", "0.9"] {
+        let code = format!(
+            "autogain ~lead :target -18dB :speed fast\n~lead $ sine 220 * {}\nout $ ~lead",
+            level
+        );
+        let buffer = compile_code(&code)
+            .expect("Failed to compile")
+            .render(44100 * 8);
+        let rms = calculate_rms(&buffer[44100 * 7..]);
+        assert!(
+            (rms / target - 1.0).abs() < 0.1,
+            "level {}: RMS {} should settle near {}",
+            level,
+            rms,
+            target
+        );
+    }
+}
+
+#[test]
+fn test_autogain_after_the_bus_definition() {
+    let before = "autogain ~lead :speed fast\n~lead $ sine 220 * 0.9\nout $ ~lead";
+    let after = "~lead $ sine 220 * 0.9\nautogain ~lead :speed fast\nout $ ~lead";
+    let render = |code| compile_code(code).expect("Failed to compile").render(44100);
+    assert_eq!(render(before), render(after));
+}
+
+#[test]
+fn test_autogain_errors() {
+    for code in [
+        "autogain ~lead :speed glacial\n~lead $ sine 220\nout $ ~lead",
+        "autogain ~lead :target 3dB\n~lead $ sine 220\nout $ ~lead",
+        "autogain ~missing\nout $ sine 220",
+    ] {
+        assert!(compile_code(code).is_err(), "{}", code);
+    }
+}
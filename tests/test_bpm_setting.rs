@@ -1,7 +1,9 @@
 /// Test BPM setting and conversion
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_bpm_120_equals_cps_2() {
     // bpm 120 should equal cps 2.0 (120 / 60 = 2)
     let input_bpm = r#"
@@ -56,6 +58,7 @@ fn test_bpm_120_equals_cps_2() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_various_bpm_values() {
     // Test common BPM values
     let test_cases = vec![
@@ -95,6 +98,7 @@ fn test_various_bpm_values() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_bpm_without_colon() {
     // bpm should work without colon (unlike cps/tempo which require colon)
     let input = r#"
@@ -115,6 +119,7 @@ fn test_bpm_without_colon() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_tempo_alias_still_works() {
     // Make sure tempo: still works as alias for cps:
     let input = r#"
@@ -0,0 +1,70 @@
+/// Tests for `freeze ~bus 8c`: the bus is rendered offline and looped in
+/// its place, in step with the cycle
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+mod audio_test_utils;
+use audio_test_utils::{compile_code, render_code};
+
+const PADS: &str =
+    "tempo: 2.0\n~saw $ saw 110 # lpf 800 0.7\n~pads $ ~saw * 0.2 + sine 3 * 0.3\nout $ ~pads\n";
+
+#[test]
+fn test_frozen_bus_sounds_like_the_live_bus() {
+    let live = render_code(PADS, 44100);
+    let frozen = render_code(&format!("{}freeze ~pads 2c", PADS), 44100);
+    for i in (0..44100).step_by(7) {
+        assert!(
+            (live[i] - frozen[i]).abs() < 1e-3,
+            "sample {}: {} vs {}",
+            i,
+            live[i],
+            frozen[i]
+        );
+    }
+}
+
+#[test]
+fn test_frozen_bus_loops() {
+    // Half a second per cycle: a 3 Hz sine doesn't repeat by itself, the
+    // one-cycle recording does
+    let frozen = render_code(&format!("{}freeze ~pads 1c", PADS), 66150);
+    for i in (100..44000).step_by(11) {
+        assert!(
+            (frozen[i] - frozen[i + 22050]).abs() < 1e-3,
+            "sample {}: {} vs {}",
+            i,
+            frozen[i],
+            frozen[i + 22050]
+        );
+    }
+    let live = render_code(PADS, 44100);
+    assert!((live[11025] - live[33075]).abs() > 0.05);
+}
+
+#[test]
+fn test_freeze_errors() {
+    for code in [
+        "out $ sine 440\nfreeze ~pads 4c",
+        "~pads $ sine 440\nout $ ~pads\nfreeze ~pads 0c",
+        "~pads $ sine 440\nout $ ~pads\nfreeze ~pads 4c\nfreeze ~pads 2c",
+    ] {
+        assert!(compile_code(code).is_err(), "{}", code);
+    }
+}
+
+#[test]
+fn test_freeze_and_unfreeze_from_the_console() {
+    let mut harness = EditorTestHarness::with_content(PADS).unwrap();
+    harness.console_command(":freeze ~pads 4c");
+    assert!(harness.content().ends_with("freeze ~pads 4c\n"));
+    assert!(harness.has_graph());
+
+    // Freezing again changes the length rather than adding a line
+    harness.console_command(":freeze ~pads 2c");
+    assert_eq!(harness.content().matches("freeze ~pads").count(), 1);
+    assert!(harness.content().contains("freeze ~pads 2c"));
+
+    harness.console_command(":unfreeze ~pads");
+    assert_eq!(harness.content(), PADS);
+    assert!(harness.has_graph());
+}
@@ -0,0 +1,130 @@
+/// Tests for `morph ~a ~b ~mix` and `:morph ~bus <cycles>`: a re-evaluated
+/// bus crossfades from its previous definition, which carries on where it
+/// was, to the new one
+use phonon::compositional_compiler::{morph_redefined_buses, morph_target};
+use phonon::compositional_parser::{parse_program, Expr, Statement};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+use std::collections::BTreeMap;
+
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code, render_code};
+
+fn parse(code: &str) -> Vec<Statement> {
+    let (rest, statements) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    statements
+}
+
+fn bus_expr<'a>(statements: &'a [Statement], bus: &str) -> &'a Expr {
+    statements
+        .iter()
+        .find_map(|statement| match statement {
+            Statement::BusAssignment { name, expr, .. } if name == bus => Some(expr),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_morph_mixes_like_xfade() {
+    let sources = "~a $ sine 220 * 0.5\n~b $ saw 110 * 0.5\n";
+    let morphed = render_code(&format!("{}out $ morph ~a ~b 0.25", sources), 8192);
+    let mixed = render_code(&format!("{}out $ ~a * 0.75 + ~b * 0.25", sources), 8192);
+    for (i, (m, x)) in morphed.iter().zip(&mixed).enumerate() {
+        assert!((m - x).abs() < 1e-4, "sample {}: {} vs {}", i, m, x);
+    }
+
+    let err = compile_code("out $ morph ~a ~b").err().unwrap();
+    assert!(err.contains("morph requires 3 parameters"), "{}", err);
+}
+
+#[test]
+fn test_only_redefined_morphing_buses_are_rewritten() {
+    let previous = parse("~pad $ sine 220\n~lead $ saw 440\n~bass $ saw 55\nout $ ~pad + ~lead");
+    let mut statements =
+        parse("~pad $ sine 330\n~lead $ saw 220\n~bass $ saw 55\nout $ ~pad + ~lead");
+    let morphs = BTreeMap::from([("pad".to_string(), 4.0), ("bass".to_string(), 2.0)]);
+
+    // ~lead has no morph and ~bass didn't change
+    let morphed = morph_redefined_buses(&previous, &mut statements, &morphs);
+    assert_eq!(morphed, vec!["pad".to_string()]);
+    assert_eq!(
+        bus_expr(&statements, "lead"),
+        bus_expr(&parse("~lead $ saw 220"), "lead")
+    );
+    assert_eq!(bus_expr(&statements, "bass"), bus_expr(&previous, "bass"));
+    match bus_expr(&statements, "pad") {
+        Expr::Call { name, args } => {
+            assert_eq!(name, "morph");
+            assert_eq!(&args[0], bus_expr(&previous, "pad"));
+        }
+        other => panic!("~pad should morph, got {:?}", other),
+    }
+    assert_eq!(
+        morph_target(bus_expr(&statements, "pad")),
+        *bus_expr(&parse("~pad $ sine 330"), "pad")
+    );
+
+    // Evaluating the same code again keeps the running morph
+    let playing = statements.clone();
+    let mut again = parse("~pad $ sine 330\n~lead $ saw 220\n~bass $ saw 55\nout $ ~pad + ~lead");
+    assert!(morph_redefined_buses(&playing, &mut again, &morphs).is_empty());
+    assert_eq!(again, playing);
+}
+
+#[test]
+fn test_morph_from_the_console_crossfades_a_re_evaluated_bus() {
+    let old = "tempo: 2.0\n~pad $ sine 220 * 0.3\nout $ ~pad";
+    let new = "tempo: 2.0\n~pad $ saw 110 * 0.3\nout $ ~pad";
+
+    let mut reference = EditorTestHarness::with_content(old).unwrap();
+    reference.ctrl_x();
+    let continuous = reference.render_live_chunks(41).unwrap();
+
+    let mut harness = EditorTestHarness::with_content(old).unwrap();
+    harness.console_command(":morph ~pad 4c");
+    harness.ctrl_x();
+    let before = harness.render_live_chunks(40).unwrap();
+    assert_eq!(before, continuous[..before.len()]);
+
+    harness.set_content(new);
+    harness.ctrl_x();
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|m| m.contains("Morphing ~pad over 4 cycles")));
+
+    // The old sine carries on where it was, barely faded yet
+    let first = harness.render_live_chunks(1).unwrap();
+    for (i, (m, c)) in first.iter().zip(&continuous[before.len()..]).enumerate() {
+        assert!((m - c).abs() < 0.01, "sample {}: {} vs {}", i, m, c);
+    }
+
+    // Two seconds (four cycles) later only the saw is left
+    harness.render_live_chunks(345).unwrap();
+    let settled = harness.render_live_chunks(20).unwrap();
+    let mut saw = EditorTestHarness::with_content(new).unwrap();
+    saw.ctrl_x();
+    let saw_rms = calculate_rms(&saw.render_live_chunks(20).unwrap());
+    assert!(
+        (calculate_rms(&settled) - saw_rms).abs() < 0.02,
+        "{} vs {}",
+        calculate_rms(&settled),
+        saw_rms
+    );
+
+    // Without the morph, the next change swaps instantly again
+    let messages = harness.console_messages().len();
+    harness.console_command(":unmorph ~pad");
+    assert!(harness.console_messages().len() > messages);
+    harness.set_content(old);
+    harness.ctrl_x();
+    assert_eq!(
+        harness
+            .console_messages()
+            .iter()
+            .filter(|m| m.contains("Morphing"))
+            .count(),
+        1
+    );
+}
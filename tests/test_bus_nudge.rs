@@ -0,0 +1,78 @@
+/// Tests for nudging a playing bus (`:nudge ~bus +10ms`, Alt+Left/Right):
+/// its events glide to the new offset without a re-evaluation
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+/// Half a second per cycle, a step up at the start of each
+const CLICKS: &str = "tempo: 2.0\n~clicks $ \"1 0\"\nout $ ~clicks";
+
+/// Samples in 340 chunks of live audio, just under two seconds
+const RENDERED: usize = 340 * 256;
+
+fn render(harness: &mut EditorTestHarness) -> Vec<f32> {
+    let audio = harness.render_live_chunks(340).unwrap();
+    assert_eq!(audio.len(), RENDERED);
+    audio
+}
+
+/// Where the audio steps up, counting a step at the start
+fn rising_edges(audio: &[f32]) -> Vec<usize> {
+    (0..audio.len())
+        .filter(|&i| i.checked_sub(1).map_or(0.0, |prev| audio[prev]) < 0.5 && audio[i] >= 0.5)
+        .collect()
+}
+
+#[test]
+fn test_nudge_moves_a_playing_bus() {
+    let mut reference = EditorTestHarness::with_content(CLICKS).unwrap();
+    reference.ctrl_x();
+    let steady = rising_edges(&render(&mut reference));
+    assert_eq!(steady.len(), 4, "{:?}", steady);
+
+    let mut harness = EditorTestHarness::with_content(CLICKS).unwrap();
+    harness.ctrl_x();
+    harness.console_command(":nudge ~clicks +10ms");
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|m| m.contains("~clicks nudged to +10ms")));
+
+    // The first step plays on time, the rest 10ms (441 samples) later once
+    // the nudge has glided there. Each plays once
+    let nudged = rising_edges(&render(&mut harness));
+    assert_eq!(nudged.len(), 4, "{:?}", nudged);
+    assert_eq!(nudged[0], steady[0]);
+    for (n, s) in nudged.iter().zip(&steady).skip(1) {
+        assert!((n - s).abs_diff(441) <= 2, "{} vs {}", n, s);
+    }
+}
+
+#[test]
+fn test_nudge_holds_across_evaluations_and_keys() {
+    let mut harness = EditorTestHarness::with_content(CLICKS).unwrap();
+    harness.ctrl_x();
+    harness.console_command(":nudge ~clicks -10ms");
+    let before = rising_edges(&render(&mut harness));
+
+    // Re-evaluating keeps the offset: the steps stay half a second apart
+    harness.ctrl_x();
+    let after = rising_edges(&render(&mut harness));
+    assert_eq!(after[0] + RENDERED - before[3], 22050);
+
+    // Alt+Right on the bus's line moves it 5ms later
+    harness.set_cursor(CLICKS.find("~clicks").unwrap());
+    harness.send_key_with_modifiers(KeyCode::Right, KeyModifiers::ALT);
+    assert!(
+        harness.status_message().contains("~clicks nudged to -5ms"),
+        "{}",
+        harness.status_message()
+    );
+    harness.console_command(":nudge ~clicks reset");
+    assert!(harness.status_message().contains("nudged to +0ms"));
+
+    harness.console_command(":nudge ~clicks soon");
+    assert!(harness
+        .command_output()
+        .iter()
+        .any(|line| line.contains("Invalid offset: soon")));
+}
@@ -0,0 +1,159 @@
+/// Tests for per-bus state preservation across hot-swaps
+///
+/// When a file is re-evaluated, buses whose definitions are unchanged keep
+/// their exact node state (oscillator phase, envelope position), so only the
+/// edited buses restart.
+use phonon::render_swap::RenderGraph;
+use phonon::unified_graph::{Signal, SignalNode, UnifiedSignalGraph};
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+/// Phase of the oscillator at the root of a bus
+fn bus_phase(graph: &UnifiedSignalGraph, bus: &str) -> f64 {
+    let id = graph.get_bus(bus).expect("bus should exist");
+    match graph.nodes[id.0].as_deref() {
+        Some(SignalNode::Oscillator { phase, .. }) => *phase.borrow(),
+        other => panic!("~{} should be an oscillator, got {:?}", bus, other),
+    }
+}
+
+const BEFORE: &str = r#"
+tempo: 1.0
+~lead $ sine 220
+~bass $ saw 55
+out $ ~lead * 0.2 + ~bass * 0.1
+"#;
+
+#[test]
+fn test_unchanged_bus_keeps_oscillator_phase() {
+    let mut old = compile_code(BEFORE).expect("Failed to compile");
+    old.render(1000);
+    let old_phase = bus_phase(&old, "lead");
+    assert!(old_phase > 0.0, "oscillator should have advanced");
+
+    // Only ~bass is edited
+    let mut new = compile_code(
+        r#"
+tempo: 1.0
+~lead $ sine 220
+~bass $ saw 82.5
+out $ ~lead * 0.2 + ~bass * 0.1
+"#,
+    )
+    .expect("Failed to compile");
+    assert!(new.transfer_bus_states(&old) > 0);
+
+    assert_eq!(bus_phase(&new, "lead"), old_phase);
+    assert_eq!(bus_phase(&new, "bass"), 0.0, "edited bus should restart");
+}
+
+#[test]
+fn test_whitespace_only_edit_counts_as_unchanged() {
+    let mut old = compile_code(BEFORE).expect("Failed to compile");
+    old.render(777);
+
+    let mut new = compile_code(
+        r#"
+tempo: 1.0
+~lead $ sine   220
+~bass $ saw 55
+out $ ~lead * 0.2 + ~bass * 0.1
+"#,
+    )
+    .expect("Failed to compile");
+    new.transfer_bus_states(&old);
+
+    assert_eq!(bus_phase(&new, "lead"), bus_phase(&old, "lead"));
+    assert_eq!(bus_phase(&new, "bass"), bus_phase(&old, "bass"));
+}
+
+#[test]
+fn test_renamed_bus_does_not_inherit_state() {
+    let mut old = compile_code(BEFORE).expect("Failed to compile");
+    old.render(1000);
+
+    let mut new = compile_code(
+        r#"
+tempo: 1.0
+~melody $ sine 220
+~bass $ saw 55
+out $ ~melody * 0.2 + ~bass * 0.1
+"#,
+    )
+    .expect("Failed to compile");
+    new.transfer_bus_states(&old);
+
+    assert_eq!(bus_phase(&new, "melody"), 0.0);
+    assert_eq!(bus_phase(&new, "bass"), bus_phase(&old, "bass"));
+}
+
+#[test]
+fn test_absorb_state_carries_bus_state() {
+    // The control thread keeps the shape of the graph it handed over and
+    // plans the next swap against it; the render thread only copies
+    let mut old = compile_code(BEFORE).expect("Failed to compile");
+    let playing = old.shape();
+    old.render(1000);
+    let old_phase = bus_phase(&old, "lead");
+
+    // Planned against another graph, the swap carries nothing
+    let mut unplanned = compile_code(BEFORE).expect("Failed to compile");
+    unplanned.plan_bus_state_transfer(&compile_code(BEFORE).expect("Failed to compile").shape());
+    unplanned.absorb_state(&mut old);
+    assert_eq!(bus_phase(&unplanned, "lead"), 0.0);
+
+    let mut new = compile_code(BEFORE).expect("Failed to compile");
+    new.plan_bus_state_transfer(&playing);
+    new.absorb_state(&mut old);
+
+    assert_eq!(bus_phase(&new, "lead"), old_phase);
+    let buffer = new.render(512);
+    assert!(
+        buffer.iter().any(|s| s.abs() > 0.01),
+        "swapped graph should sound"
+    );
+}
+
+#[test]
+fn test_morph_sides_continue_their_versions_of_the_bus() {
+    /// Phases of the oscillators a morphing bus fades between
+    fn morph_phases(graph: &UnifiedSignalGraph, bus: &str) -> (f64, f64) {
+        let id = graph.get_bus(bus).expect("bus should exist");
+        let phase = |signal: &Signal| match signal {
+            Signal::Node(id) => match graph.nodes[id.0].as_deref() {
+                Some(SignalNode::Oscillator { phase, .. }) => *phase.borrow(),
+                other => panic!("expected an oscillator, got {:?}", other),
+            },
+            other => panic!("expected a node, got {:?}", other),
+        };
+        match graph.nodes[id.0].as_deref() {
+            Some(SignalNode::XFade {
+                signal_a, signal_b, ..
+            }) => (phase(signal_a), phase(signal_b)),
+            other => panic!("~{} should morph, got {:?}", bus, other),
+        }
+    }
+
+    let mut old = compile_code(BEFORE).expect("Failed to compile");
+    old.render(1000);
+
+    // The outgoing side of the morph is the old ~lead
+    let mut morphing = compile_code(&BEFORE.replace(
+        "~lead $ sine 220",
+        "~lead $ morph (sine 220) (sine 330) 0.5",
+    ))
+    .expect("Failed to compile");
+    morphing.transfer_bus_states(&old);
+    assert_eq!(
+        morph_phases(&morphing, "lead"),
+        (bus_phase(&old, "lead"), 0.0)
+    );
+    morphing.render(500);
+
+    // And the new ~lead carries on from the incoming side
+    let mut new = compile_code(&BEFORE.replace("sine 220", "sine 330")).expect("Failed to compile");
+    new.transfer_bus_states(&morphing);
+    assert_eq!(bus_phase(&new, "lead"), morph_phases(&morphing, "lead").1);
+    assert!(bus_phase(&new, "lead") > 0.0);
+}
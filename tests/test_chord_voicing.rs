@@ -0,0 +1,93 @@
+//! Chord voicing in the compositional DSL: `# voicing "drop2"`,
+//! `# inversion 1` and the `# open` / `# close` shorthands, applied to the
+//! chord stacks that `n "c'maj"` and `# chord` produce, and `# harmonize`,
+//! which builds such stacks from a melody.
+//!
+//! Level 1 (pattern-query): the compiled node yields the re-voiced stack.
+
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+use phonon::unified_graph::SignalNode;
+use std::collections::HashMap;
+
+/// Stack values over one cycle, sorted by (start-time, value)
+fn query_stack(pattern: &Pattern<String>) -> Vec<f64> {
+    let state = State {
+        span: TimeSpan::new(Fraction::from_float(0.0), Fraction::from_float(1.0)),
+        controls: HashMap::new(),
+    };
+    let mut values: Vec<(f64, f64)> = pattern
+        .query(&state)
+        .iter()
+        .map(|h| (h.part.begin.to_float(), h.value.trim().parse::<f64>().unwrap()))
+        .collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    values.into_iter().map(|(_, v)| v).collect()
+}
+
+fn compiled_bus_stack(code: &str, bus: &str) -> Vec<f64> {
+    let (_, statements) = parse_program(code).expect("parse");
+    let graph = compile_program(statements, 44100.0, None).expect("compile");
+    let node_id = graph
+        .get_bus(bus)
+        .unwrap_or_else(|| panic!("bus ~{bus} not found"));
+    match graph.get_node(node_id).expect("node missing") {
+        SignalNode::Pattern { pattern, .. } => query_stack(pattern),
+        other => panic!("bus ~{bus} is not a Pattern node: {other:?}"),
+    }
+}
+
+#[test]
+fn test_voicing_drop2() {
+    // C maj7 [0,4,7,11]: the G drops an octave
+    let code = "~ch $ n \"c'maj7\" # voicing \"drop2\"\nout $ ~ch";
+    assert_eq!(compiled_bus_stack(code, "ch"), vec![-5.0, 0.0, 4.0, 11.0]);
+}
+
+#[test]
+fn test_inversions() {
+    let code = "~ch $ n \"c\" # chord \"maj\" # inversion 1\nout $ ~ch";
+    assert_eq!(compiled_bus_stack(code, "ch"), vec![4.0, 7.0, 12.0]);
+    let code = "~ch $ n \"c\" # chord \"maj\" # inversion 2\nout $ ~ch";
+    assert_eq!(compiled_bus_stack(code, "ch"), vec![7.0, 12.0, 16.0]);
+    let code = "~ch $ n \"c\" # chord \"maj\" # inversion -1\nout $ ~ch";
+    assert_eq!(compiled_bus_stack(code, "ch"), vec![-5.0, 0.0, 4.0]);
+}
+
+#[test]
+fn test_patterned_inversion() {
+    // One chord per half cycle, each with its own inversion
+    let code = "~ch $ n \"c c\" # chord \"maj\" # inversion \"0 1\"\nout $ ~ch";
+    assert_eq!(
+        compiled_bus_stack(code, "ch"),
+        vec![0.0, 4.0, 7.0, 4.0, 7.0, 12.0]
+    );
+}
+
+#[test]
+fn test_open_and_close() {
+    let code = "~ch $ n \"c'maj\" # open\nout $ ~ch";
+    assert_eq!(compiled_bus_stack(code, "ch"), vec![0.0, 7.0, 16.0]);
+    let code = "~ch $ n \"c'maj\" # open # close\nout $ ~ch";
+    assert_eq!(compiled_bus_stack(code, "ch"), vec![0.0, 4.0, 7.0]);
+}
+
+#[test]
+fn test_voicing_needs_chord_input() {
+    let (_, statements) = parse_program("out $ sine 440 # voicing \"drop2\"").unwrap();
+    assert!(compile_program(statements, 44100.0, None).is_err());
+}
+
+#[test]
+fn test_harmonize_in_key() {
+    // d ef f in c minor: thirds are f g af
+    let code = "~h $ n \"2 3 5\" # harmonize \"3rds\" :scale \"c minor\"\nout $ ~h";
+    assert_eq!(
+        compiled_bus_stack(code, "h"),
+        vec![2.0, 5.0, 3.0, 7.0, 5.0, 8.0]
+    );
+    // Harmonized stacks can be voiced like any chord
+    let code = "~h $ n \"0\" # harmonize \"triads\" # inversion 1\nout $ ~h";
+    assert_eq!(compiled_bus_stack(code, "h"), vec![4.0, 7.0, 12.0]);
+}
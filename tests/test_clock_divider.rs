@@ -0,0 +1,55 @@
+/// Tests for the clock divider and multiplier (`clockdiv`, `clockmult`)
+///
+/// Rendered through `gateout`, whose buffer holds the gate in volts.
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::unified_graph::UnifiedSignalGraph;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+fn render_gate(code: &str, samples: usize) -> Vec<f32> {
+    let (rest, stmts) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    let mut graph: UnifiedSignalGraph =
+        compile_program(stmts, SAMPLE_RATE, None).expect("Failed to compile");
+    graph.render(samples);
+    graph.cv_buffer(3).to_vec()
+}
+
+fn rising_edges(gate: &[f32]) -> Vec<usize> {
+    (0..gate.len())
+        .filter(|&i| gate[i] > 2.5 && (i == 0 || gate[i - 1] < 2.5))
+        .collect()
+}
+
+#[test]
+fn test_clockdiv_halves_pattern_gates() {
+    let gate = render_gate(
+        "tempo: 1.0\nout $ sine 440 * 0.1\ngateout 3 (gate \"t ~ t ~ t ~ t ~\" # clockdiv 2)",
+        44100,
+    );
+    // The first and third of the four gates
+    let edges = rising_edges(&gate);
+    assert_eq!(edges.len(), 2, "{:?}", edges);
+    assert_eq!(edges[0], 0);
+    assert!(edges[1].abs_diff(22050) <= 1, "{:?}", edges);
+}
+
+#[test]
+fn test_clockmult_subdivides_an_audio_clock() {
+    // A 2 Hz square wave as the clock: once its period is known, four
+    // pulses per period, an eighth of a second apart
+    let gate = render_gate(
+        "out $ sine 440 * 0.1\ngateout 3 (square 2 # clockmult 4)",
+        88200,
+    );
+    let edges: Vec<usize> = rising_edges(&gate)
+        .into_iter()
+        .filter(|&i| i >= 44100)
+        .collect();
+    assert!(edges.len() >= 7, "{:?}", edges);
+    for pair in edges.windows(2) {
+        let gap = (pair[1] - pair[0]) as f32;
+        assert!((gap - 5512.5).abs() < 50.0, "{:?}", edges);
+    }
+}
@@ -0,0 +1,162 @@
+//! Integration tests for collaborative sessions
+//!
+//! Two clients join one SessionServer over UDP, evaluate code in their own
+//! namespaces and read the shared console back.
+
+use phonon::collab_session::SessionServer;
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::Statement;
+use rosc::{OscMessage, OscPacket, OscType};
+use std::net::UdpSocket;
+use std::thread;
+use std::time::Duration;
+
+/// A performer's socket (also where its console lines arrive)
+fn client() -> UdpSocket {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_millis(500)))
+        .unwrap();
+    socket
+}
+
+fn send(socket: &UdpSocket, port: u16, addr: &str, args: &[&str]) {
+    let packet = OscPacket::Message(OscMessage {
+        addr: addr.to_string(),
+        args: args
+            .iter()
+            .map(|a| OscType::String(a.to_string()))
+            .collect(),
+    });
+    let buf = rosc::encoder::encode(&packet).unwrap();
+    socket.send_to(&buf, ("127.0.0.1", port)).unwrap();
+}
+
+/// Poll the server until a message changes the program
+fn poll_program(server: &mut SessionServer) -> Option<Vec<Statement>> {
+    for _ in 0..50 {
+        if let Some(program) = server.poll() {
+            return Some(program);
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    None
+}
+
+fn console_lines(socket: &UdpSocket) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut buf = [0u8; 4096];
+    while let Ok((size, _)) = socket.recv_from(&mut buf) {
+        if let Ok((_, OscPacket::Message(msg))) = rosc::decoder::decode_udp(&buf[..size]) {
+            assert_eq!(msg.addr, "/session/console");
+            if let Some(OscType::String(line)) = msg.args.first() {
+                lines.push(line.clone());
+            }
+        }
+    }
+    lines
+}
+
+#[test]
+fn test_two_clients_share_one_engine() {
+    let mut server = SessionServer::bind(0).unwrap();
+    let port = server.local_addr().unwrap().port();
+    let alice = client();
+    let bob = client();
+
+    send(&alice, port, "/session/join", &["alice"]);
+    send(&bob, port, "/session/join", &["bob"]);
+    send(
+        &alice,
+        port,
+        "/session/eval",
+        &["alice", "~bass $ saw 55\nout $ ~bass * 0.2"],
+    );
+    assert!(poll_program(&mut server).is_some());
+
+    // Bob uses the same bus name and reaches alice's bass by its full name
+    send(
+        &bob,
+        port,
+        "/session/eval",
+        &[
+            "bob",
+            "~bass $ square 110\nout $ ~bass * 0.1 + ~alice_bass * 0.1",
+        ],
+    );
+    let program = poll_program(&mut server).expect("bob's evaluation should rebuild");
+    assert_eq!(server.session().clients(), vec!["alice", "bob"]);
+    let buses: Vec<&str> = program
+        .iter()
+        .filter_map(|s| match s {
+            Statement::BusAssignment { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(buses, vec!["alice_bass", "bob_bass"]);
+    assert!(compile_program(program, 44100.0, None).is_ok());
+
+    // A broken evaluation is reported to everyone and changes nothing
+    send(&bob, port, "/session/eval", &["bob", "out $ $"]);
+    send(&bob, port, "/session/say", &["bob", "oops"]);
+    assert!(poll_program(&mut server).is_none());
+
+    let lines = console_lines(&alice);
+    assert!(lines.contains(&"* bob joined".to_string()), "{:?}", lines);
+    assert!(lines.iter().any(|l| l.starts_with("bob: ~bob_bass out")));
+    assert!(lines.iter().any(|l| l.starts_with("bob: ❌")));
+    assert!(lines.contains(&"<bob> oops".to_string()));
+}
+
+#[test]
+fn test_late_joiner_gets_console_history() {
+    let mut server = SessionServer::bind(0).unwrap();
+    let port = server.local_addr().unwrap().port();
+    let alice = client();
+
+    send(&alice, port, "/session/eval", &["alice", "out $ sine 220"]);
+    assert!(poll_program(&mut server).is_some());
+
+    let bob = client();
+    send(&bob, port, "/session/join", &["bob"]);
+    poll_program(&mut server);
+    let lines = console_lines(&bob);
+    assert_eq!(lines.first().map(String::as_str), Some("alice: out"));
+
+    // Leaving removes the client's sound
+    send(&alice, port, "/session/leave", &["alice"]);
+    let program = poll_program(&mut server).unwrap();
+    assert!(program.is_empty());
+}
+
+#[test]
+fn test_names_belong_to_their_sender() {
+    let mut server = SessionServer::bind(0).unwrap();
+    assert!(server.local_addr().unwrap().ip().is_loopback());
+    let port = server.local_addr().unwrap().port();
+    server.set_host_code("host", "out $ sine 110").unwrap();
+    let alice = client();
+    let mallory = client();
+
+    send(&alice, port, "/session/join", &["alice"]);
+    send(&alice, port, "/session/eval", &["alice", "out $ sine 220"]);
+    assert!(poll_program(&mut server).is_some());
+
+    // Nobody else can speak, evaluate, hush or leave as alice, or as the host
+    send(&mallory, port, "/session/eval", &["alice", "out $ saw 55"]);
+    send(&mallory, port, "/session/say", &["alice", "not me"]);
+    send(&mallory, port, "/session/hush", &["alice"]);
+    send(&mallory, port, "/session/leave", &["alice"]);
+    send(&mallory, port, "/session/hush", &["host"]);
+    assert!(poll_program(&mut server).is_none());
+    assert_eq!(server.session().clients(), vec!["host", "alice"]);
+    let refused = console_lines(&mallory);
+    assert_eq!(refused.len(), 5, "{:?}", refused);
+    assert!(refused.iter().all(|l| l.contains("another client's name")));
+
+    // Once alice leaves, the name is free again
+    send(&alice, port, "/session/leave", &["alice"]);
+    assert!(poll_program(&mut server).is_some());
+    send(&mallory, port, "/session/eval", &["alice", "out $ saw 55"]);
+    assert!(poll_program(&mut server).is_some());
+}
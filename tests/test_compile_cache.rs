@@ -0,0 +1,105 @@
+/// Tests for the compile cache: re-evaluating an edited program reuses the
+/// statements of the last compile that didn't change, and must sound
+/// exactly like compiling it from scratch
+use phonon::compositional_compiler::{compile_program, compile_program_cached, CompileCache};
+use phonon::compositional_parser::parse_program;
+
+fn render(code: &str, sample_rate: f32, cache: Option<&mut CompileCache>) -> Vec<f32> {
+    let (rest, statements) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    let mut graph = match cache {
+        Some(cache) => compile_program_cached(statements, sample_rate, None, cache),
+        None => compile_program(statements, sample_rate, None),
+    }
+    .expect("Failed to compile");
+    graph.render(8192)
+}
+
+/// Render each edit in turn through one cache, and each from scratch
+fn assert_edits_match_fresh(edits: &[&str]) {
+    let mut cache = CompileCache::new();
+    for code in edits {
+        let cached = render(code, 44100.0, Some(&mut cache));
+        assert_eq!(cached, render(code, 44100.0, None), "{}", code);
+    }
+}
+
+#[test]
+fn test_cached_compiles_match_fresh_ones() {
+    let base = "tempo: 1.0\n~lfo $ sine 2 * 200 + 600\n~bass $ saw 55 # lpf ~lfo 0.8\n~hats $ square 8 * 0.1\n~lead $ square \"220 330\" * 0.2";
+    assert_edits_match_fresh(&[
+        &format!("{}\nout $ ~bass + ~lead", base),
+        // Same program again
+        &format!("{}\nout $ ~bass + ~lead", base),
+        // The last statement, twice
+        &format!("{}\nout $ ~bass * 0.5 + ~lead", base),
+        &format!("{}\nout $ ~bass * 0.4 + ~lead", base),
+        // A statement in the middle
+        &format!(
+            "{}\nout $ ~bass * 0.4 + ~lead",
+            base.replace("saw 55", "saw 110")
+        ),
+        // A statement that several others use
+        &format!(
+            "{}\nout $ ~bass * 0.4 + ~lead",
+            base.replace("sine 2", "sine 3")
+                .replace("saw 55", "saw 110")
+        ),
+        // Appended and removed statements
+        &format!("{}\n~sub $ sine 55\nout $ ~bass + ~sub", base),
+        "tempo: 1.0\n~lfo $ sine 2 * 200 + 600\nout $ saw 55 # lpf ~lfo 0.8",
+    ]);
+}
+
+#[test]
+fn test_cache_follows_forward_references_and_settings() {
+    assert_edits_match_fresh(&[
+        // `out` comes first, so the edited bus is compiled after it
+        "out $ ~a + ~b\n~a $ sine 220 * 0.3\n~b $ sine 330 * 0.3",
+        "out $ ~a + ~b\n~a $ sine 220 * 0.3\n~b $ sine 440 * 0.3",
+        // A new bus changes what pass 1 registers
+        "out $ ~a + ~c\n~a $ sine 220 * 0.3\n~b $ sine 440 * 0.3\n~c $ saw 110 * 0.2",
+        // So do settings placed after the buses they affect
+        "out $ ~a + ~c\n~a $ sine 220 * 0.3\n~b $ sine 440 * 0.3\n~c $ saw 110 * 0.2\nautogain ~c",
+        "out $ ~a + ~c\n~a $ sine 220 * 0.3\n~b $ sine 440 * 0.3\n~c $ saw 110 * 0.2\nduck ~a ~b",
+        // The trigger of a duck is a dependency of the ducked bus
+        "out $ ~a + ~c\n~a $ sine 220 * 0.3\n~b $ sine 550 * 0.3\n~c $ saw 110 * 0.2\nduck ~a ~b",
+    ]);
+
+    // Entries belong to one sample rate
+    let mut cache = CompileCache::new();
+    let code = "out $ sine 440 * 0.3";
+    render(code, 44100.0, Some(&mut cache));
+    assert_eq!(
+        render(code, 48000.0, Some(&mut cache)),
+        render(code, 48000.0, None)
+    );
+}
+
+#[test]
+fn test_only_changed_statements_and_their_users_recompile() {
+    let code = |freq: u32| {
+        format!(
+            "tempo: 1.0\n~lfo $ sine 2 * 200 + 600\n~bass $ saw {} # lpf ~lfo 0.8\n~hats $ square 8 * 0.1\nout $ ~bass + ~hats",
+            freq
+        )
+    };
+    let mut cache = CompileCache::new();
+    render(&code(55), 44100.0, Some(&mut cache));
+    assert_eq!(cache.reused(), 0);
+
+    render(&code(55), 44100.0, Some(&mut cache));
+    assert_eq!(cache.reused(), 4);
+
+    // ~bass changed, and `out` uses it; ~lfo and ~hats are reused
+    render(&code(110), 44100.0, Some(&mut cache));
+    assert_eq!(cache.reused(), 2);
+}
+
+#[test]
+fn test_counted_patterns_count_from_each_evaluation() {
+    // `after` counts from the cycle its pattern is first played at, so a
+    // cached statement must not carry an earlier evaluation's count over
+    let code = "tempo: 2.0\n~a $ saw \"110 220\" $ after 4 (fast 2)\nout $ ~a * 0.3";
+    assert_edits_match_fresh(&[code, &format!("{}\nsetCycle 4", code)]);
+}
@@ -252,8 +252,8 @@ fn test_sample_through_filter_structure() {
 #[test]
 fn test_reverb_chain() {
     let graph = compile_to_graph("out: sine 440 # reverb 0.5 0.8");
-    let has_reverb = graph.nodes.iter().any(|o| o.as_ref().map_or(false, |rc| matches!(&**rc, SignalNode::Reverb { .. })));
-    assert!(has_reverb, "Graph should contain a Reverb node");
+    let has_reverb = graph.nodes.iter().any(|o| o.as_ref().map_or(false, |rc| matches!(&**rc, SignalNode::DattorroReverb { .. })));
+    assert!(has_reverb, "Graph should contain a DattorroReverb node");
 }
 
 #[test]
@@ -609,8 +609,8 @@ fn test_full_effect_chain() {
     let graph = compile_to_graph("~drums $ s \"bd sn hh*2 cp\" $ fast 2\nout $ ~drums # lpf 2000 0.8 # reverb 0.3 0.5");
     let has_sample = graph.nodes.iter().any(|o| o.as_ref().map_or(false, |rc| matches!(&**rc, SignalNode::Sample { .. })));
     let has_lpf = graph.nodes.iter().any(|o| o.as_ref().map_or(false, |rc| matches!(&**rc, SignalNode::LowPass { .. })));
-    let has_reverb = graph.nodes.iter().any(|o| o.as_ref().map_or(false, |rc| matches!(&**rc, SignalNode::Reverb { .. })));
-    assert!(has_sample && has_lpf && has_reverb, "Should have Sample, LowPass, and Reverb nodes");
+    let has_reverb = graph.nodes.iter().any(|o| o.as_ref().map_or(false, |rc| matches!(&**rc, SignalNode::DattorroReverb { .. })));
+    assert!(has_sample && has_lpf && has_reverb, "Should have Sample, LowPass, and DattorroReverb nodes");
 }
 
 #[test]
@@ -1,10 +1,12 @@
 /// Test pattern parameters on CONTINUOUS synths (not drum hits)
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod audio_test_utils;
 use audio_test_utils::find_dominant_frequency;
 
 #[test]
+#[allow(deprecated)]
 fn test_supersaw_freq_pattern_actually_cycles() {
     // Frequency patterns need FFT to verify - RMS doesn't tell us the frequency!
     // Supersaw with PATTERN freq that alternates 110 220
@@ -65,6 +67,7 @@ fn test_supersaw_freq_pattern_actually_cycles() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_oscillator_freq_pattern_cycles() {
     // Test basic oscillator with pattern freq - verify with FFT
     // CURRENT STATUS: Detects wrong frequencies (4704 Hz instead of 110 Hz)
@@ -116,6 +119,7 @@ fn test_oscillator_freq_pattern_cycles() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_architectural_limitation_drum_synths_continuous() {
     // This test DOCUMENTS the architectural limitation:
     // Drum synths (kick, snare, hat) are continuous but play only once
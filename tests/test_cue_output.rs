@@ -0,0 +1,50 @@
+/// Tests for the headphone cue output (`cue $ ...`, `precue ~bus`)
+///
+/// The cue renders alongside the main output and never leaks into it.
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code};
+
+#[test]
+fn test_cue_renders_beside_main_output() {
+    let mut with_cue =
+        compile_code("~main $ saw 110 * 0.2\n~next $ sine 440\nout $ ~main\ncue $ ~next")
+            .expect("Failed to compile");
+    let mut without = compile_code("~main $ saw 110 * 0.2\n~next $ sine 440\nout $ ~main")
+        .expect("Failed to compile");
+
+    let main = with_cue.render(4410);
+    let cue = with_cue.cue_buffer().to_vec();
+    assert_eq!(
+        main,
+        without.render(4410),
+        "cue must not change the main mix"
+    );
+    assert!(without.cue_buffer().is_empty());
+
+    assert_eq!(cue.len(), 4410);
+    assert!(
+        (calculate_rms(&cue) - 0.707).abs() < 0.05,
+        "cue rms {}",
+        calculate_rms(&cue)
+    );
+}
+
+#[test]
+fn test_precue_keeps_bus_out_of_auto_routed_mix() {
+    // No `out`: the buses are auto-summed to the main output, except the pre-cued one
+    let mut graph = compile_code("~drums $ sine 110\n~next $ sine 440\nprecue ~next")
+        .expect("Failed to compile");
+    let main = graph.render(4410);
+    let mut drums_only = compile_code("~drums $ sine 110").expect("Failed to compile");
+    assert_eq!(main, drums_only.render(4410));
+    assert!(calculate_rms(graph.cue_buffer()) > 0.5);
+}
+
+#[test]
+fn test_hush_silences_cue() {
+    let mut graph = compile_code("out $ sine 220\ncue $ sine 440").expect("Failed to compile");
+    graph.render(512);
+    graph.hush_all();
+    graph.render(512);
+    assert!(graph.cue_buffer().iter().all(|&s| s == 0.0));
+}
@@ -1,7 +1,9 @@
 /// Debug test for cut groups
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_cut_group_debug_simple() {
     // Two hi-hats with cut group 1
     let input = r#"
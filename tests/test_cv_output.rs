@@ -0,0 +1,58 @@
+/// Tests for the control voltage outputs (`cvout`, `gateout`)
+///
+/// The graph renders each CV output in volts, beside (never into) the main mix.
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+#[test]
+fn test_voct_pitch_cv() {
+    // tempo 1: each note is a quarter second
+    let mut graph = compile_code(
+        "tempo: 1.0\n~pitch $ \"c4 c5 c3 a4\"\nout $ sine 440 * 0.1\ncvout 3 (~pitch |> voct)",
+    )
+    .expect("Failed to compile");
+    graph.render(44100);
+    let cv = graph.cv_buffer(3);
+    assert_eq!(cv.len(), 44100);
+    for (i, volts) in [0.0, 1.0, -1.0, 0.75].into_iter().enumerate() {
+        let sample = cv[i * 11025 + 5000];
+        assert!((sample - volts).abs() < 0.01, "note {}: {} V", i, sample);
+    }
+}
+
+#[test]
+fn test_gate_drops_between_back_to_back_events() {
+    let mut graph = compile_code("tempo: 1.0\nout $ sine 440 * 0.1\ngateout 4 \"t ~ t t\"")
+        .expect("Failed to compile");
+    graph.render(44100);
+    let gate = graph.cv_buffer(4);
+    let at = |seconds: f32| gate[(seconds * SAMPLE_RATE) as usize];
+    assert_eq!(at(0.1), 5.0);
+    assert_eq!(at(0.3), 0.0);
+    assert_eq!(at(0.6), 5.0);
+    assert_eq!(at(0.9), 5.0);
+    // The last 2 ms of the third event are low, so the fourth retriggers
+    assert_eq!(at(0.749), 0.0);
+}
+
+#[test]
+fn test_cv_stays_out_of_the_main_mix() {
+    // No `out`: the CV bus would otherwise be auto-summed to the speakers
+    let mut graph = compile_code("~drone $ sine 110\n~pitch $ \"c5\"\ncvout 3 (voct ~pitch)")
+        .expect("Failed to compile");
+    let mut drone_only = compile_code("~drone $ sine 110").expect("Failed to compile");
+    assert_eq!(graph.render(4410), drone_only.render(4410));
+    assert!((graph.cv_buffer(3)[100] - 1.0).abs() < 0.01);
+
+    graph.hush_all();
+    graph.render(512);
+    assert!(graph.cv_buffer(3).iter().all(|&v| v == 0.0));
+}
+
+#[test]
+fn test_cv_rejects_main_mix_channels() {
+    let err = compile_code("out $ sine 440\ncvout 1 0.5").err().unwrap();
+    assert!(err.contains("channels 1 and 2"), "{}", err);
+}
@@ -1,8 +1,10 @@
 use phonon::pattern::{Fraction, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
 #[test]
+#[allow(deprecated)]
 fn debug_degrade_dsl_compilation() {
     // Test what pattern actually ends up in the graph when compiled from DSL
     let input_degraded = r#"
@@ -1,9 +1,11 @@
 mod pattern_verification_utils;
 
 use pattern_verification_utils::detect_audio_events;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn investigate_degrade_behavior() {
     // Compare raw audio characteristics
     let input_normal = r#"
@@ -73,6 +75,7 @@ fn investigate_degrade_behavior() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn investigate_stutter_behavior() {
     let input_normal = r#"
         cps: 1.0
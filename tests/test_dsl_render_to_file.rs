@@ -1,8 +1,10 @@
 use hound::{SampleFormat, WavSpec, WavWriter};
 /// Test DSL rendering to file to verify DslCompiler works end-to-end
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_render_to_wav() {
     let dsl_code = r#"
 tempo: 0.5
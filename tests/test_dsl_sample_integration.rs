@@ -1,8 +1,10 @@
 //! Test if sample playback works through DSL syntax
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_sample_playback_simple() {
     // Does out s "bd" produce audio through DslCompiler?
     // NOTE: No leading whitespace - parser is sensitive to formatting
@@ -47,6 +49,7 @@ fn test_dsl_sample_playback_simple() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_vs_direct_api() {
     // Compare DSL path vs direct API path
     use phonon::mini_notation_v3::parse_mini_notation;
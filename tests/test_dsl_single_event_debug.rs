@@ -2,9 +2,11 @@
 ///
 /// This test uses the DslCompiler to reproduce the bug where
 /// single-event patterns at slow tempo produce silence
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_compiler_single_event_slow() {
     // This should FAIL - produces silence
     let dsl_code = r#"
@@ -43,6 +45,7 @@ out $ s "bd" * 0.8
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_compiler_single_event_fast() {
     // This should PASS - produces audio
     let dsl_code = r#"
@@ -67,6 +70,7 @@ out $ s "bd" * 0.8
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_compiler_two_events_slow() {
     // This should PASS - 2 events work at slow tempo
     let dsl_code = r#"
@@ -1,8 +1,10 @@
 //! Debug DSL syntax parsing
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::parse_dsl;
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_syntax_variations() {
     println!("\n=== Testing DSL Syntax Variations ===\n");
 
@@ -0,0 +1,42 @@
+/// Tests for `duck ~target ~trigger` sidechain ducking
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code};
+
+/// RMS of the pad just after the kick (10-100ms) and late in the cycle (1.5-2s)
+fn pad_levels(code: &str) -> (f32, f32) {
+    let buffer = compile_code(code).expect("Failed to compile").render(88200);
+    (
+        calculate_rms(&buffer[441..4410]),
+        calculate_rms(&buffer[66150..]),
+    )
+}
+
+#[test]
+fn test_duck_lowers_target_while_trigger_sounds() {
+    let (dry_hit, dry_tail) =
+        pad_levels("cps: 0.5\n~kick $ s \"bd\"\n~pads $ sine 220\nout $ ~pads");
+
+    // The trigger may be defined before or after the ducked bus, and the
+    // duck line may follow both
+    for code in [
+        "cps: 0.5\nduck ~pads ~kick :amount 0.8 :release 0.25\n~kick $ s \"bd\"\n~pads $ sine 220\nout $ ~pads",
+        "cps: 0.5\nduck ~pads ~kick :amount 0.8 :release 0.25\n~pads $ sine 220\n~kick $ s \"bd\"\nout $ ~pads",
+        "cps: 0.5\n~kick $ s \"bd\"\n~pads $ sine 220\nduck ~pads ~kick :amount 0.8 :release 0.25\nout $ ~pads",
+    ] {
+        let (hit, tail) = pad_levels(code);
+        assert!(hit < dry_hit * 0.3, "Pad should duck under the kick: {} vs {}", hit, dry_hit);
+        assert!(tail > dry_tail * 0.95, "Pad should recover: {} vs {}", tail, dry_tail);
+    }
+}
+
+#[test]
+fn test_duck_errors() {
+    for code in [
+        "duck ~pads ~pads\n~pads $ sine 220\nout $ ~pads",
+        "duck ~pads ~kick\n~pads $ sine 220\nout $ ~pads",
+        "duck ~pads ~kick\n~kick $ s \"bd\"\nout $ ~kick",
+        "duck ~pads ~kick :amount 2\n~kick $ s \"bd\"\n~pads $ sine 220\nout $ ~pads",
+    ] {
+        assert!(compile_code(code).is_err(), "{}", code);
+    }
+}
@@ -14,6 +14,7 @@
 
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -61,6 +62,7 @@ fn get_events_for_cycle<T: Clone + Send + Sync + 'static>(
 }
 
 /// Render DSL code to audio samples
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_secs: f32) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Parse DSL failed");
     let compiler = DslCompiler::new(44100.0);
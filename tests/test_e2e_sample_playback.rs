@@ -18,6 +18,7 @@
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, State, TimeSpan};
 use phonon::sample_loader::SampleBank;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -82,6 +83,7 @@ fn render_pattern(pattern_str: &str, cps: f32, duration_samples: usize) -> Vec<f
     render_dsl(&code, duration_samples)
 }
 
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_samples: usize) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Failed to parse DSL");
     let compiler = DslCompiler::new(44100.0);
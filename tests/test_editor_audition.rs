@@ -0,0 +1,51 @@
+/// Tests for auditioning a line with Alt+Enter
+///
+/// The bus under the cursor plays for one cycle over the live output, without
+/// the live graph changing.
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+mod audio_test_utils;
+use audio_test_utils::calculate_rms;
+
+fn alt_enter(harness: &mut EditorTestHarness) {
+    harness.send_key_with_modifiers(KeyCode::Enter, KeyModifiers::ALT);
+}
+
+#[test]
+fn test_audition_plays_muted_bus_for_one_cycle() {
+    let code = "tempo: 2.0\n~lead $ sine 440\nout $ ~lead * 0";
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.ctrl_x();
+    assert!(calculate_rms(&harness.render_live_chunks(20).unwrap()) < 1e-4);
+
+    // Cursor on the ~lead line
+    harness.set_cursor(code.find("~lead").unwrap());
+    alt_enter(&mut harness);
+    assert!(
+        harness.error_message().is_none(),
+        "{:?}",
+        harness.error_message()
+    );
+    assert!(harness.status_message().contains("Auditioning"));
+    assert_eq!(harness.content(), code, "audition must not edit the buffer");
+
+    // Half a second at 2 cps is one cycle (~86 chunks of 256 frames)
+    let heard = harness.render_live_chunks(80).unwrap();
+    assert!(calculate_rms(&heard) > 0.1, "audition should be audible");
+
+    // Then silence again: the live graph was never touched
+    let after = harness.render_live_chunks(20).unwrap();
+    assert!(calculate_rms(&after) < 1e-4);
+}
+
+#[test]
+fn test_audition_reports_lines_it_cannot_play() {
+    let code = "-- just a comment\nout $ sine 440";
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.set_cursor(0);
+    alt_enter(&mut harness);
+    assert!(harness
+        .error_message()
+        .is_some_and(|e| e.contains("Audition")));
+}
@@ -0,0 +1,51 @@
+//! Tests for `:render` in the editor command console
+//!
+//! The buffer is rendered to WAV on a background thread while the live graph
+//! keeps playing; progress is reported in the console pane.
+
+use phonon::modal_editor::test_harness::EditorTestHarness;
+use std::time::Duration;
+
+#[test]
+fn test_render_command_writes_wav_while_playing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("idea.wav");
+    let code = "tempo: 1.0\nout $ sine 220 * 0.3";
+
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.ctrl_x();
+    assert!(harness.has_graph());
+
+    harness.console_command(&format!(":render 2c {}", path.display()));
+
+    // Live playback keeps going while the render runs
+    let live = harness.render_live_chunks(4).unwrap();
+    assert!(live.iter().any(|s| s.abs() > 0.1), "live audio stopped");
+
+    assert!(harness.wait_for_renders(Duration::from_secs(30)));
+    let messages = harness.console_messages().join("\n");
+    assert!(messages.contains("Rendered"), "console: {}", messages);
+
+    // 2 cycles at 1 cps = 2 seconds of stereo audio
+    let reader = hound::WavReader::open(&path).unwrap();
+    assert_eq!(reader.spec().channels, 2);
+    assert_eq!(reader.duration(), 88200);
+}
+
+#[test]
+fn test_render_command_reports_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("never.wav");
+
+    let mut harness = EditorTestHarness::with_content("out $ sine 220").unwrap();
+    harness.console_command(&format!(":render zero {}", path.display()));
+    assert!(harness.wait_for_renders(Duration::from_secs(1)));
+    assert!(!path.exists());
+
+    // Broken code fails in the background and says so in the console
+    harness.set_content("out $ not_a_function 3");
+    harness.console_command(&format!(":render 1s {}", path.display()));
+    assert!(harness.wait_for_renders(Duration::from_secs(30)));
+    let messages = harness.console_messages().join("\n");
+    assert!(messages.contains("failed"), "console: {}", messages);
+}
@@ -0,0 +1,51 @@
+//! Tests for the event log pane (Alt+E)
+//!
+//! Events triggered on the render side show up as `cycle:pos name params`.
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+fn alt_e(harness: &mut EditorTestHarness) {
+    harness.send_key_with_modifiers(KeyCode::Char('e'), KeyModifiers::ALT);
+}
+
+#[test]
+fn test_event_log_shows_triggered_events() {
+    let code = "tempo: 1.0\nout $ s \"bd sn\" # gain 0.5";
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.ctrl_x();
+    alt_e(&mut harness);
+
+    // Just over one cycle at 1 cps
+    harness.render_live_chunks(180).unwrap();
+    let lines = harness.event_log();
+    assert!(
+        lines.iter().any(|l| l.starts_with("0:0.000 bd gain=0.50")),
+        "{:?}",
+        lines
+    );
+    assert!(
+        lines.iter().any(|l| l.starts_with("0:0.500 sn")),
+        "{:?}",
+        lines
+    );
+}
+
+#[test]
+fn test_event_log_is_quiet_while_hidden() {
+    let code = "tempo: 1.0\nout $ s \"bd*4\"";
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.ctrl_x();
+    harness.render_live_chunks(100).unwrap();
+    assert!(harness.event_log().is_empty());
+
+    alt_e(&mut harness);
+    harness.render_live_chunks(100).unwrap();
+    assert!(!harness.event_log().is_empty());
+
+    // Hiding stops reporting
+    alt_e(&mut harness);
+    let before = harness.event_log().len();
+    harness.render_live_chunks(100).unwrap();
+    assert_eq!(harness.event_log().len(), before);
+}
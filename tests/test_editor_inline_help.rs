@@ -0,0 +1,35 @@
+//! Tests for the inline kwarg help pane in the editor
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+#[test]
+fn test_inline_help_follows_cursor_into_kwargs() {
+    let mut harness = EditorTestHarness::with_content("~verb $ ~dry # plate 0.02 ").unwrap();
+
+    harness.type_text(":decay");
+    let help = harness.inline_help().expect("help for plate");
+    assert!(help[0].starts_with("plate - "), "header: {}", help[0]);
+    let active: Vec<&String> = help.iter().filter(|l| l.starts_with('▶')).collect();
+    assert_eq!(active.len(), 1);
+    assert!(active[0].contains(":decay"));
+    // Every kwarg is listed, with defaults for the optional ones
+    assert!(help
+        .iter()
+        .any(|l| l.contains(":mix") && l.contains("[default 0.5]")));
+
+    // Leaving the call hides the pane
+    harness.type_text(" 2.5").enter();
+    assert!(harness.inline_help().is_none());
+}
+
+#[test]
+fn test_inline_help_toggle() {
+    let mut harness = EditorTestHarness::with_content("out $ saw 55 # lpf 800 ").unwrap();
+    assert!(harness.inline_help().unwrap()[2].starts_with('▶'));
+
+    harness.send_key_with_modifiers(KeyCode::Char('h'), KeyModifiers::ALT);
+    assert!(harness.inline_help().is_none());
+    harness.send_key_with_modifiers(KeyCode::Char('h'), KeyModifiers::ALT);
+    assert!(harness.inline_help().is_some());
+}
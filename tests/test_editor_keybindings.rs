@@ -0,0 +1,83 @@
+//! Tests for config-driven key bindings, the vim keymap and the default tempo
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+#[test]
+fn test_rebound_eval_key() {
+    let mut harness = EditorTestHarness::with_content("out $ sine 440 * 0.2").unwrap();
+    harness
+        .apply_config("[keys]\neval_block = \"F5\"\n")
+        .unwrap();
+    assert!(harness.key_help().starts_with("F5: Eval block"));
+
+    // The old chord is unbound and must not type an 'x'
+    harness.ctrl_x();
+    assert!(!harness.has_graph());
+    assert_eq!(harness.content(), "out $ sine 440 * 0.2");
+
+    harness.send_key(KeyCode::F(5));
+    assert!(harness.has_graph());
+}
+
+#[test]
+fn test_bad_config_is_rejected() {
+    let mut harness = EditorTestHarness::new().unwrap();
+    let err = harness
+        .apply_config("[keys]\nteleport = \"C-t\"\n")
+        .err()
+        .unwrap();
+    assert!(err.contains("teleport"));
+}
+
+#[test]
+fn test_vim_normal_and_visual_mode() {
+    let mut harness = EditorTestHarness::new().unwrap();
+    harness.apply_config("keymap = \"vim\"").unwrap();
+    assert!(harness.key_help().starts_with("-- INSERT --"));
+
+    harness.type_text("abc");
+    harness.send_key(KeyCode::Esc);
+    assert!(harness.is_vim_normal());
+    assert!(harness.key_help().starts_with("-- NORMAL --"));
+
+    // Esc steps back onto the 'c'; h moves, x deletes, typing doesn't insert
+    harness.type_text("hx");
+    assert_eq!(harness.content(), "ac");
+    harness.type_text("q");
+    assert_eq!(harness.content(), "ac");
+
+    // I inserts at line start; Esc returns to normal mode
+    harness.type_text("I");
+    assert!(!harness.is_vim_normal());
+    harness.type_text("z");
+    harness.send_key(KeyCode::Esc);
+    assert_eq!(harness.content(), "zac");
+
+    // v starts a selection that motions extend; d cuts it, p pastes
+    harness.type_text("vll");
+    assert_eq!(harness.selected_text(), Some("za"));
+    harness.type_text("d");
+    assert_eq!(harness.content(), "c");
+    assert_eq!(harness.kill_buffer(), "za");
+    harness.type_text("p");
+    assert_eq!(harness.content(), "zac");
+
+    // Emacs chords still work in normal mode
+    harness.send_key_with_modifiers(KeyCode::Char('u'), KeyModifiers::CONTROL);
+    assert_eq!(harness.content(), "c");
+}
+
+#[test]
+fn test_default_tempo_applies_without_tempo_statement() {
+    let mut harness = EditorTestHarness::with_content("out $ sine 440 * 0.2").unwrap();
+    harness.apply_config("default_tempo = 1.25").unwrap();
+    harness.ctrl_x();
+    assert_eq!(harness.get_cps(), Some(1.25));
+
+    // An explicit tempo in the code wins
+    let mut harness = EditorTestHarness::with_content("tempo: 2.0\nout $ sine 440 * 0.2").unwrap();
+    harness.apply_config("default_tempo = 1.25").unwrap();
+    harness.ctrl_x();
+    assert_eq!(harness.get_cps(), Some(2.0));
+}
@@ -0,0 +1,29 @@
+//! Tests for evaluating code with a statement that doesn't parse: the rest
+//! plays and the skipped line is pointed out
+
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+#[test]
+fn test_eval_skips_unparseable_statement() {
+    let mut harness =
+        EditorTestHarness::with_content("~a $ sine 440 * 0.2\n~b $ saw 55 )\nout $ ~a").unwrap();
+    harness.ctrl_x();
+    assert!(harness.has_graph());
+
+    let error = harness
+        .error_message()
+        .expect("the skipped line is reported");
+    assert!(error.contains("line 2:13"), "{}", error);
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|message| message.contains("Skipped `~b $ saw 55 )`")));
+}
+
+#[test]
+fn test_eval_with_nothing_parseable_keeps_playing() {
+    let mut harness = EditorTestHarness::with_content("~b $ saw 55 )").unwrap();
+    harness.ctrl_x();
+    assert!(!harness.has_graph());
+    assert!(harness.error_message().is_some());
+}
@@ -0,0 +1,83 @@
+//! Tests for viewport scrolling, PgUp/PgDn and mouse input in the editor
+
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+fn numbered_lines(count: usize) -> String {
+    (0..count)
+        .map(|i| format!("-- line {}", i))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_viewport_follows_cursor() {
+    let mut harness = EditorTestHarness::with_content(&numbered_lines(100)).unwrap();
+    harness.set_cursor(0).draw(100, 40);
+    assert_eq!(harness.scroll_offset(), 0);
+
+    // Cursor at the end of the file scrolls the view down to it
+    let len = harness.content().len();
+    harness.set_cursor(len).draw(100, 40);
+    assert!(harness.scroll_offset() > 50);
+    assert_eq!(harness.current_line(), "-- line 99");
+}
+
+#[test]
+fn test_page_down_and_up() {
+    let mut harness = EditorTestHarness::with_content(&numbered_lines(100)).unwrap();
+    harness.set_cursor(0).draw(100, 40);
+
+    harness.send_key(KeyCode::PageDown).draw(100, 40);
+    let first_page = harness.scroll_offset();
+    assert!(first_page > 0);
+    assert_ne!(harness.current_line(), "-- line 0");
+
+    harness.send_key(KeyCode::PageUp).draw(100, 40);
+    assert_eq!(harness.scroll_offset(), 0);
+    assert_eq!(harness.current_line(), "-- line 0");
+
+    // Shift+PgDn selects a screenful
+    harness.send_key_with_modifiers(KeyCode::PageDown, KeyModifiers::SHIFT);
+    assert!(harness.selected_text().unwrap().starts_with("-- line 0\n"));
+}
+
+#[test]
+fn test_mouse_wheel_scrolls_and_keeps_cursor_on_screen() {
+    let mut harness = EditorTestHarness::with_content(&numbered_lines(100)).unwrap();
+    harness.set_cursor(0).draw(100, 40);
+
+    for _ in 0..5 {
+        harness.mouse(MouseEventKind::ScrollDown, 10, 10);
+    }
+    harness.draw(100, 40);
+    assert_eq!(harness.scroll_offset(), 15);
+    // The cursor was dragged along rather than snapping the view back
+    assert_ne!(harness.current_line(), "-- line 0");
+
+    for _ in 0..10 {
+        harness.mouse(MouseEventKind::ScrollUp, 10, 10);
+    }
+    assert_eq!(harness.scroll_offset(), 0);
+}
+
+#[test]
+fn test_click_places_cursor_and_drag_selects() {
+    let mut harness = EditorTestHarness::with_content("out $ sine 440\n~lfo $ sine 0.5").unwrap();
+    harness.draw(100, 40);
+    let area = harness.editor_area();
+
+    // Second line, eighth character (inside the border)
+    let (x, y) = (area.x + 1, area.y + 1);
+    harness.mouse(MouseEventKind::Down(MouseButton::Left), x + 7, y + 1);
+    assert_eq!(harness.cursor_pos(), "out $ sine 440\n".len() + 7);
+
+    harness.mouse(MouseEventKind::Drag(MouseButton::Left), x + 12, y + 1);
+    assert_eq!(harness.selected_text(), Some("sine "));
+
+    // Past the end of a line clamps to the line end; clicks on the border are ignored
+    harness.mouse(MouseEventKind::Down(MouseButton::Left), x + 60, y);
+    assert_eq!(harness.cursor_pos(), "out $ sine 440".len());
+    harness.mouse(MouseEventKind::Down(MouseButton::Left), area.x, y + 1);
+    assert_eq!(harness.cursor_pos(), "out $ sine 440".len());
+}
@@ -0,0 +1,31 @@
+//! Tests for scrubbing numbers with Alt+Up/Down
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+#[test]
+fn test_scrub_number_and_reevaluate() {
+    let code = "out $ sine 440 * 0.2";
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.set_cursor(code.find(" *").unwrap());
+
+    harness.send_key_with_modifiers(KeyCode::Up, KeyModifiers::ALT);
+    assert_eq!(harness.content(), "out $ sine 441 * 0.2");
+    assert!(harness.has_graph());
+
+    // Fine steps add a decimal place; coarse steps are ten of the current one
+    harness.send_key_with_modifiers(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::CONTROL);
+    assert_eq!(harness.content(), "out $ sine 440.9 * 0.2");
+    harness.send_key_with_modifiers(KeyCode::Up, KeyModifiers::ALT | KeyModifiers::SHIFT);
+    assert_eq!(harness.content(), "out $ sine 441.9 * 0.2");
+    assert_eq!(harness.selected_text(), None);
+
+    // A run of scrubs undoes in one step
+    harness.send_key_with_modifiers(KeyCode::Char('u'), KeyModifiers::CONTROL);
+    assert_eq!(harness.content(), code);
+
+    harness.set_cursor(0);
+    harness.send_key_with_modifiers(KeyCode::Up, KeyModifiers::ALT);
+    assert_eq!(harness.content(), code);
+    assert!(harness.status_message().contains("No number"));
+}
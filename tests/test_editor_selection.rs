@@ -0,0 +1,102 @@
+//! Tests for selection, cut/copy/paste, block comments and indentation in the editor
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+const CODE: &str = "tempo: 0.5\n~bass $ saw 55 # lpf 800 0.7\nout $ ~bass * 0.3";
+
+fn shift(harness: &mut EditorTestHarness, code: KeyCode, times: usize) {
+    for _ in 0..times {
+        harness.send_key_with_modifiers(code, KeyModifiers::SHIFT);
+    }
+}
+
+#[test]
+fn test_shift_selection_cut_and_paste() {
+    let mut harness = EditorTestHarness::with_content(CODE).unwrap();
+    harness.set_cursor(11); // start of "~bass"
+    shift(&mut harness, KeyCode::End, 1);
+    assert_eq!(
+        harness.selected_text(),
+        Some("~bass $ saw 55 # lpf 800 0.7")
+    );
+
+    // Ctrl+W cuts into the kill buffer, Ctrl+Y pastes it back elsewhere
+    harness.send_key_with_modifiers(KeyCode::Char('w'), KeyModifiers::CONTROL);
+    assert_eq!(harness.kill_buffer(), "~bass $ saw 55 # lpf 800 0.7");
+    assert_eq!(harness.content(), "tempo: 0.5\n\nout $ ~bass * 0.3");
+    assert_eq!(harness.selected_text(), None);
+
+    harness.set_cursor(harness.content().len());
+    harness.enter();
+    harness.send_key_with_modifiers(KeyCode::Char('y'), KeyModifiers::CONTROL);
+    assert!(harness
+        .content()
+        .ends_with("* 0.3\n~bass $ saw 55 # lpf 800 0.7"));
+}
+
+#[test]
+fn test_copy_and_typing_replaces_selection() {
+    let mut harness = EditorTestHarness::with_content(CODE).unwrap();
+    harness.set_cursor(32); // "800"
+    shift(&mut harness, KeyCode::Right, 3);
+    assert_eq!(harness.selected_text(), Some("800"));
+
+    harness.send_key_with_modifiers(KeyCode::Char('w'), KeyModifiers::ALT);
+    assert_eq!(harness.kill_buffer(), "800");
+    assert_eq!(harness.content(), CODE);
+
+    // Select backwards and type over it
+    shift(&mut harness, KeyCode::Left, 3);
+    assert_eq!(harness.selected_text(), Some("800"));
+    harness.type_text("1200");
+    assert!(harness.content().contains("# lpf 1200 0.7"));
+
+    // Plain movement drops the selection
+    shift(&mut harness, KeyCode::Left, 2);
+    harness.send_key(KeyCode::Right);
+    assert_eq!(harness.selected_text(), None);
+}
+
+#[test]
+fn test_block_comment_toggle() {
+    let mut harness = EditorTestHarness::with_content(CODE).unwrap();
+    harness.set_cursor(11);
+    shift(&mut harness, KeyCode::Down, 1);
+    shift(&mut harness, KeyCode::End, 1);
+    harness.send_key_with_modifiers(KeyCode::Char(';'), KeyModifiers::ALT);
+    assert_eq!(
+        harness.content(),
+        "tempo: 0.5\n-- ~bass $ saw 55 # lpf 800 0.7\n-- out $ ~bass * 0.3"
+    );
+
+    // The edited lines stay selected, so the same key uncomments them
+    harness.send_key_with_modifiers(KeyCode::Char(';'), KeyModifiers::ALT);
+    assert_eq!(harness.content(), CODE);
+
+    // Without a selection it toggles the cursor line
+    harness.set_cursor(3);
+    harness.send_key_with_modifiers(KeyCode::Char(';'), KeyModifiers::ALT);
+    assert!(harness.content().starts_with("-- tempo: 0.5\n~bass"));
+    assert_eq!(harness.cursor_pos(), 6);
+}
+
+#[test]
+fn test_indent_and_dedent_selection() {
+    let mut harness = EditorTestHarness::with_content(CODE).unwrap();
+    harness.set_cursor(11);
+    shift(&mut harness, KeyCode::Down, 1);
+    shift(&mut harness, KeyCode::End, 1);
+
+    harness.tab();
+    assert_eq!(
+        harness.content(),
+        "tempo: 0.5\n  ~bass $ saw 55 # lpf 800 0.7\n  out $ ~bass * 0.3"
+    );
+    harness.send_key_with_modifiers(KeyCode::Char(']'), KeyModifiers::ALT);
+    assert!(harness.content().contains("\n    out $"));
+
+    harness.send_key_with_modifiers(KeyCode::BackTab, KeyModifiers::SHIFT);
+    harness.send_key_with_modifiers(KeyCode::Char('['), KeyModifiers::ALT);
+    assert_eq!(harness.content(), CODE);
+}
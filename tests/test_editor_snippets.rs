@@ -0,0 +1,53 @@
+//! Tests for the snippet library in the editor command console
+
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+#[test]
+fn test_snippet_inserts_at_cursor() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut harness = EditorTestHarness::with_content("tempo: 0.5\n").unwrap();
+    harness.use_snippet_dir(dir.path());
+
+    // Fuzzy query picks the acid line from the built-in library
+    harness.console_command("/snippet acid");
+    assert!(
+        harness
+            .content()
+            .starts_with("tempo: 0.5\n~acid_lfo $ sine 0.25\n"),
+        "content: {}",
+        harness.content()
+    );
+    assert_eq!(harness.cursor_pos(), harness.content().len());
+    let messages = harness.console_messages().join("\n");
+    assert!(messages.contains("acid-bass"), "console: {}", messages);
+
+    // Browsing lists matches without touching the buffer
+    let before = harness.content().to_string();
+    harness.console_command("/snippets dub");
+    assert_eq!(harness.content(), before);
+    assert!(harness.command_output()[1].contains("dub-delay-chain"));
+}
+
+#[test]
+fn test_snippet_save_and_reuse() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut harness = EditorTestHarness::with_content("~wob $ saw 55 # lpf 400 0.8").unwrap();
+    harness.use_snippet_dir(dir.path());
+
+    harness.console_command("/snippet-save wobble");
+    assert!(dir.path().join("wobble.phonon").exists());
+    let output = harness.command_output().join("\n");
+    assert!(
+        output.contains("Saved snippet 'wobble'"),
+        "output: {}",
+        output
+    );
+
+    // Inserting mid-line starts the snippet on a new line
+    harness.set_content("out $ sine 110");
+    harness.console_command("/snippet wobble");
+    assert_eq!(
+        harness.content(),
+        "out $ sine 110\n~wob $ saw 55 # lpf 400 0.8\n"
+    );
+}
@@ -0,0 +1,55 @@
+//! Tests for side-by-side panes whose code merges into one graph
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+fn alt_o(harness: &mut EditorTestHarness) {
+    harness.send_key_with_modifiers(KeyCode::Char('o'), KeyModifiers::ALT);
+}
+
+#[test]
+fn test_split_panes_merge_into_one_graph() {
+    let tmp = tempfile::tempdir().unwrap();
+    let synths = tmp.path().join("synths.ph");
+    std::fs::write(&synths, "out $ ~lead * 0.2").unwrap();
+
+    let drums = "tempo: 1.5\n~lead $ saw 110";
+    let mut harness = EditorTestHarness::with_content(drums).unwrap();
+    harness.ctrl_x();
+    assert_eq!(harness.get_cps(), Some(1.5));
+
+    harness.console_command(&format!(":split {}", synths.display()));
+    assert!(harness.is_split());
+    assert_eq!(harness.file_path(), Some(synths.as_path()));
+    assert_eq!(harness.content(), "out $ ~lead * 0.2");
+
+    // Evaluating this pane keeps the other pane's tempo and buses
+    harness.ctrl_x();
+    assert!(harness.has_graph());
+    assert_eq!(harness.get_cps(), Some(1.5));
+
+    // Alt-o moves focus back; the split stays
+    alt_o(&mut harness);
+    assert_eq!(harness.content(), drums);
+    assert!(harness.is_split());
+    harness.draw(120, 40);
+
+    harness.console_command(":unsplit");
+    assert!(!harness.is_split());
+    assert_eq!(harness.content(), drums);
+}
+
+#[test]
+fn test_alt_o_opens_scratch_pane() {
+    let mut harness = EditorTestHarness::with_content("out $ sine 220").unwrap();
+    alt_o(&mut harness);
+    assert!(harness.is_split());
+    assert_eq!(harness.content(), "");
+    assert_eq!(harness.file_path(), None);
+
+    harness.type_text("scratch");
+    alt_o(&mut harness);
+    assert_eq!(harness.content(), "out $ sine 220");
+    alt_o(&mut harness);
+    assert_eq!(harness.content(), "scratch");
+}
@@ -0,0 +1,32 @@
+//! Tests for the step grid (Alt+G) on pattern strings
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+#[test]
+fn test_step_grid_writes_back_mini_notation() {
+    let mut harness = EditorTestHarness::with_content("~drums $ s \"bd ~ sn ~\"").unwrap();
+    harness.send_key_with_modifiers(KeyCode::Char('g'), KeyModifiers::ALT);
+    assert!(harness.status_message().contains("2 rows x 4 steps"));
+
+    // Kick on step 2, snare off step 3; the keys don't reach the buffer
+    harness.type_text("l ");
+    harness.type_text("jl ");
+    harness.draw(100, 30);
+    assert_eq!(harness.content(), "~drums $ s \"bd ~ sn ~\"");
+
+    harness.enter();
+    assert_eq!(harness.content(), "~drums $ s \"bd bd ~ ~\"");
+
+    // Esc leaves the code alone
+    harness.send_key_with_modifiers(KeyCode::Char('g'), KeyModifiers::ALT);
+    harness.type_text(" ");
+    harness.send_key(KeyCode::Esc);
+    assert_eq!(harness.content(), "~drums $ s \"bd bd ~ ~\"");
+
+    // Patterns with structure stay in text
+    let mut harness = EditorTestHarness::with_content("~drums $ s \"bd [sn sn]\"").unwrap();
+    harness.send_key_with_modifiers(KeyCode::Char('g'), KeyModifiers::ALT);
+    harness.type_text("x");
+    assert_eq!(harness.content(), "~drums $ s \"bd [sn sn]\"x");
+}
@@ -1,8 +1,10 @@
 /// Test per-event envelope modifiers with sample patterns
 /// Tests the syntax: s "bd sn" # segments "0 1 0" "0.1 0.2"
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_segments_modifier() {
     let dsl = r#"
         tempo: 0.5
@@ -19,6 +21,7 @@ fn test_segments_modifier() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_adsr_modifier() {
     let dsl = r#"
         tempo: 0.5
@@ -33,6 +36,7 @@ fn test_adsr_modifier() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_curve_modifier() {
     let dsl = r#"
         tempo: 0.5
@@ -47,6 +51,7 @@ fn test_curve_modifier() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_mixed_envelopes() {
     let dsl = r#"
         tempo: 0.5
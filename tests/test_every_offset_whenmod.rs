@@ -0,0 +1,114 @@
+/// Tests for every' and whenmod, the phrase-level conditional transforms
+///
+/// Tidal semantics:
+/// - `every' n o f` applies f when `cycle mod n == o`
+/// - `whenmod a b f` applies f when `cycle mod a >= b`
+///
+/// so `every' 4 3 (rev)` and `whenmod 8 6 (fast 2)` both land at the end
+/// of a phrase, which is where fills go.
+use phonon::mini_notation_v3::parse_mini_notation;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+/// Values of one cycle, sorted by onset
+fn cycle_values(p: &Pattern<String>, cycle: i64) -> Vec<String> {
+    let state = State {
+        span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+        controls: std::collections::HashMap::new(),
+    };
+    let mut haps = p.query(&state);
+    haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+    haps.into_iter().map(|h| h.value).collect()
+}
+
+// ============================================================================
+// Pattern-Level Tests
+// ============================================================================
+
+#[test]
+fn test_every_offset_reverses_last_cycle_of_four() {
+    let p = parse_mini_notation("a b c").every_offset(4, 3, |p| p.rev());
+    for cycle in 0..8 {
+        let values = cycle_values(&p, cycle);
+        if cycle % 4 == 3 {
+            assert_eq!(values, vec!["c", "b", "a"], "cycle {}", cycle);
+        } else {
+            assert_eq!(values, vec!["a", "b", "c"], "cycle {}", cycle);
+        }
+    }
+}
+
+#[test]
+fn test_every_offset_zero_matches_every() {
+    let every = parse_mini_notation("a b").every(3, |p| p.rev());
+    let every_offset = parse_mini_notation("a b").every_offset(3, 0, |p| p.rev());
+    for cycle in 0..6 {
+        assert_eq!(
+            cycle_values(&every, cycle),
+            cycle_values(&every_offset, cycle)
+        );
+    }
+}
+
+#[test]
+fn test_whenmod_applies_to_last_two_cycles_of_eight() {
+    let p = parse_mini_notation("bd sn").when_mod(8, 6, |p| p.fast(Pattern::pure(2.0)));
+    let counts: Vec<usize> = (0..16).map(|c| cycle_values(&p, c).len()).collect();
+    assert_eq!(counts, vec![2, 2, 2, 2, 2, 2, 4, 4, 2, 2, 2, 2, 2, 2, 4, 4]);
+}
+
+#[test]
+fn test_whenmod_negative_cycles_follow_phrase() {
+    // Cycle -1 is the last cycle of the phrase before cycle 0
+    let p = parse_mini_notation("bd").when_mod(4, 3, |p| p.fast(Pattern::pure(2.0)));
+    assert_eq!(cycle_values(&p, -1).len(), 2);
+    assert_eq!(cycle_values(&p, -2).len(), 1);
+}
+
+// ============================================================================
+// DSL Compilation Tests
+// ============================================================================
+
+#[test]
+fn test_every_offset_compiles() {
+    let code = r#"out $ s "bd sn hh cp" $ every' 4 3 (rev)"#;
+    if let Err(e) = compile_code(code) {
+        panic!("Should compile: {}", e);
+    }
+}
+
+#[test]
+fn test_whenmod_fill_compiles() {
+    let code = r#"out $ s "bd*2 sn" $ whenmod 8 6 (fast 2)"#;
+    if let Err(e) = compile_code(code) {
+        panic!("Should compile: {}", e);
+    }
+}
+
+#[test]
+fn test_every_offset_with_effect_compiles() {
+    let code = r#"out $ s "bd sn" $ every' 4 3 (# lpf 300 0.8)"#;
+    if let Err(e) = compile_code(code) {
+        panic!("Should compile: {}", e);
+    }
+}
+
+#[test]
+fn test_conditional_transforms_reject_zero_period() {
+    assert!(compile_code(r#"out $ s "bd sn" $ every' 0 0 (rev)"#).is_err());
+    assert!(compile_code(r#"out $ s "bd sn" $ whenmod 0 0 (fast 2)"#).is_err());
+}
+
+#[test]
+fn test_whenmod_renders_audio() {
+    let code = r#"
+tempo: 4.0
+out $ s "bd sn" $ whenmod 4 2 (fast 2)
+"#;
+    let mut graph = compile_code(code).expect("Should compile");
+    let buffer = graph.render(44100);
+    let peak = buffer.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+    assert!(peak > 0.01, "Expected audible output, got peak {}", peak);
+}
@@ -1,6 +1,7 @@
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::Pattern;
 use phonon::unified_graph::{Signal, SignalNode, UnifiedSignalGraph};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -471,6 +472,7 @@ fn test_different_cut_groups_independent() {
 
 /// Test synthesis -> filter -> distortion -> reverb with pattern modulation
 #[test]
+#[allow(deprecated)]
 fn test_complex_fx_chain_with_patterns() {
     let input = r#"
         cps: 2.0
@@ -1,9 +1,12 @@
 //! Test filter audio generation
 
+#[allow(deprecated)]
 use phonon::glicol_parser_v2::parse_glicol_v2;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor_v2::SimpleDspExecutorV2;
 
 #[test]
+#[allow(deprecated)]
 fn test_simple_lowpass() {
     println!("\n=== Testing Simple Lowpass Filter ===");
 
@@ -32,6 +35,7 @@ fn test_simple_lowpass() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_filter_without_input() {
     println!("\n=== Testing Filter Without Input ===");
 
@@ -52,6 +56,7 @@ fn test_filter_without_input() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_filter_with_reference() {
     println!("\n=== Testing Filter with Reference ===");
 
@@ -0,0 +1,88 @@
+/// Tests for gain staging: per-bus meters, the headroom report and the master
+/// clipper modes
+use phonon::modal_editor::test_harness::EditorTestHarness;
+use phonon::unified_graph::{MasterClip, UnifiedSignalGraph};
+
+mod audio_test_utils;
+use audio_test_utils::{compile_code, find_peak};
+
+fn render_blocks(graph: &mut UnifiedSignalGraph, blocks: usize) -> Vec<f32> {
+    let mut out = Vec::new();
+    for _ in 0..blocks {
+        let mut buffer = vec![0.0f32; 1024];
+        graph.process_buffer(&mut buffer);
+        out.extend_from_slice(&buffer);
+    }
+    out
+}
+
+const HOT_MIX: &str = "tempo: 1.0\n~pad $ sine 220 * 0.2\n~bass $ saw 55 * 2.5\nout $ ~pad + ~bass";
+
+#[test]
+fn test_headroom_report_names_the_clipping_bus() {
+    let mut graph = compile_code(HOT_MIX).expect("Failed to compile");
+    let meters = graph.enable_bus_meters();
+    render_blocks(&mut graph, 20);
+
+    let report = meters.report();
+    assert!(
+        report.master_peak > 1.0,
+        "master peak {}",
+        report.master_peak
+    );
+    assert!(report.clipped_blocks > 0);
+    assert_eq!(report.culprit(), Some("bass"));
+
+    let pad = report
+        .buses
+        .iter()
+        .find(|(name, _, _)| name == "pad")
+        .unwrap();
+    assert!((pad.1 - 0.2).abs() < 0.02, "pad peak {}", pad.1);
+    assert!(report
+        .lines()
+        .iter()
+        .any(|l| l.contains("~bass drove the master into clipping")));
+}
+
+#[test]
+fn test_master_clip_modes() {
+    let ceiling = 0.95;
+    for clip in [MasterClip::Hard, MasterClip::Soft, MasterClip::Tanh] {
+        let mut graph = compile_code(HOT_MIX).expect("Failed to compile");
+        graph.set_master_clip(clip);
+        let out = render_blocks(&mut graph, 10);
+        assert!(
+            find_peak(&out) <= ceiling + 1e-6,
+            "{:?} peaked at {}",
+            clip,
+            find_peak(&out)
+        );
+    }
+
+    // Soft leaves quiet material alone
+    let mut graph = compile_code("tempo: 1.0\nout $ sine 440 * 0.3").expect("Failed to compile");
+    graph.set_master_clip(MasterClip::Soft);
+    let out = render_blocks(&mut graph, 10);
+    assert!((find_peak(&out) - 0.3).abs() < 0.01);
+
+    // Off lets overs through
+    let mut graph = compile_code(HOT_MIX).expect("Failed to compile");
+    graph.set_master_clip(MasterClip::Off);
+    assert!(find_peak(&render_blocks(&mut graph, 10)) > 1.0);
+
+    assert_eq!(MasterClip::from_str("TANH"), Some(MasterClip::Tanh));
+    assert_eq!(MasterClip::from_str("fold"), None);
+}
+
+#[test]
+fn test_editor_shows_clip_indicator_on_hot_bus() {
+    let mut harness = EditorTestHarness::with_content(HOT_MIX).unwrap();
+    harness.ctrl_x();
+    harness.render_live_chunks(20).unwrap();
+
+    assert_eq!(harness.bus_indicator("bass").as_deref(), Some("● CLIP"));
+    let pad = harness.bus_indicator("pad").unwrap();
+    assert!(pad.ends_with("dB"), "pad shows {}", pad);
+    assert!(harness.bus_indicator("missing").is_none());
+}
@@ -0,0 +1,51 @@
+/// Tests for the example gallery (`phonon examples`)
+use phonon::compositional_compiler::compile_program_with_assertions;
+use phonon::compositional_parser::parse_program;
+use phonon::gallery::{self, EXAMPLES};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+#[test]
+fn test_every_file_in_the_gallery_is_listed() {
+    let mut files: Vec<String> = std::fs::read_dir("examples/gallery")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "phonon"))
+        .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    let listed: Vec<&str> = EXAMPLES.iter().map(|example| example.name).collect();
+    assert_eq!(files, listed);
+}
+
+#[test]
+fn test_examples_describe_themselves() {
+    for example in EXAMPLES {
+        assert!(!example.description().is_empty(), "{}", example.name);
+    }
+    assert_eq!(gallery::find("acid.phonon").unwrap().name, "acid");
+    assert!(gallery::find("nosuchpiece").is_none());
+}
+
+#[test]
+fn test_examples_compile_and_pass_their_assertions() {
+    for example in EXAMPLES {
+        let (rest, statements) = parse_program(example.code).unwrap();
+        assert!(rest.trim().is_empty(), "{}: left {:?}", example.name, rest);
+        let mut graph = compile_program_with_assertions(statements, SAMPLE_RATE)
+            .unwrap_or_else(|e| panic!("{}: {}", example.name, e));
+        assert!(graph.has_assertions(), "{}", example.name);
+
+        // Four seconds, as `phonon test` renders by default
+        for _ in 0..(4.0 * SAMPLE_RATE) as usize / 512 {
+            graph.render(512);
+        }
+        for outcome in graph.check_assertions() {
+            assert!(
+                outcome.passed,
+                "{}: {} (got {:?})",
+                example.name, outcome.description, outcome.value
+            );
+        }
+    }
+}
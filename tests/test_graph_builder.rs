@@ -0,0 +1,74 @@
+/// Tests for the stable graph-building API (`GraphBuilder`)
+use phonon::graph_builder::{GraphBuilder, Input};
+use phonon::unified_graph::Waveform;
+
+mod audio_test_utils;
+use audio_test_utils::calculate_rms;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+fn zero_crossings(samples: &[f32]) -> usize {
+    samples
+        .windows(2)
+        .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+        .count()
+}
+
+#[test]
+fn test_oscillator_plays_its_frequency() {
+    let mut builder = GraphBuilder::new(SAMPLE_RATE);
+    let osc = builder.add_oscillator(440.0);
+    builder.set_output(osc);
+    let audio = builder.build().render(44100);
+    assert!(zero_crossings(&audio).abs_diff(440) <= 1);
+    assert!((calculate_rms(&audio) - 0.707).abs() < 0.01);
+}
+
+#[test]
+fn test_inputs_take_nodes_patterns_and_buses() {
+    let mut builder = GraphBuilder::new(SAMPLE_RATE);
+    builder.set_tempo(1.0);
+    // A half-cycle of 220 Hz, then one of 440 Hz
+    let freq = builder.add_pattern("220 440");
+    let osc = builder.add_waveform(Waveform::Sine, freq);
+    builder.set_bus("lead", osc);
+    let out = builder.add_gain(Input::bus("lead"), "0.5");
+    builder.set_output(out);
+    let audio = builder.build().render(44100);
+    assert!(zero_crossings(&audio[..22050]).abs_diff(110) <= 2);
+    assert!(zero_crossings(&audio[22050..]).abs_diff(220) <= 2);
+    assert!((calculate_rms(&audio) - 0.354).abs() < 0.01);
+}
+
+#[test]
+fn test_lowpass_takes_the_top_off_a_mix() {
+    let render = |cutoff: f32| {
+        let mut builder = GraphBuilder::new(SAMPLE_RATE);
+        let low = builder.add_oscillator(110.0);
+        let high = builder.add_oscillator(5000.0);
+        let mix = builder.add_mix([low, high]);
+        let filtered = builder.add_lowpass(mix, cutoff, 0.7);
+        builder.set_output(filtered);
+        builder.build().render(22050)
+    };
+    let open = calculate_rms(&render(20000.0)[4410..]);
+    let closed = calculate_rms(&render(400.0)[4410..]);
+    // Both sines are through the open filter; only the low one the closed
+    assert!((open - 1.0).abs() < 0.05, "{}", open);
+    assert!((closed - 0.707).abs() < 0.05, "{}", closed);
+}
+
+#[test]
+fn test_delay_repeats_later() {
+    let mut builder = GraphBuilder::new(SAMPLE_RATE);
+    builder.set_tempo(1.0);
+    // A 100 ms burst of noise at the start of the cycle
+    let gate = builder.add_pattern("1 0 0 0 0 0 0 0 0 0");
+    let noise = builder.add_noise();
+    let burst = builder.add_gain(noise, gate);
+    let wet = builder.add_delay(burst, 0.25, 0.0, 1.0);
+    builder.set_output(wet);
+    let audio = builder.build().render(22050);
+    assert!(calculate_rms(&audio[..11025]) < 1e-4);
+    assert!(calculate_rms(&audio[11025..15435]) > 0.1);
+}
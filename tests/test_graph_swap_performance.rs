@@ -2,6 +2,7 @@
 /// This simulates the live coding scenario where patterns are edited repeatedly
 
 use phonon::unified_graph::UnifiedSignalGraph;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::time::Instant;
 
@@ -36,6 +37,7 @@ macro_rules! assert_wallclock_budget {
     }};
 }
 
+#[allow(deprecated)]
 fn compile_code(code: &str, sample_rate: f32) -> UnifiedSignalGraph {
     let (_, statements) = parse_dsl(code).expect("Failed to parse DSL");
     let compiler = DslCompiler::new(sample_rate);
@@ -402,3 +402,86 @@ fn test_groove_preset_aliases() {
 
     println!("✅ groove aliases all work");
 }
+
+#[test]
+fn test_groove_mpc60_presets() {
+    for name in ["mpc60_50", "mpc60_54", "mpc60_66", "mpc60_75"] {
+        let code = format!("cps: 0.5\nout $ s \"hh*16\" $ groove \"{}\"", name);
+        let (_, statements) = parse_program(&code).expect("Parse failed");
+        let graph = compile_program(statements, 44100.0, None);
+        assert!(
+            graph.is_ok(),
+            "'{}' should compile: {:?}",
+            name,
+            graph.err()
+        );
+    }
+
+    let (_, statements) = parse_program("out $ s \"hh*16\" $ groove \"mpc60_90\"").unwrap();
+    let err = compile_program(statements, 44100.0, None).err().unwrap();
+    assert!(err.contains("Unknown groove preset"), "{}", err);
+}
+
+#[test]
+fn test_groove_statement_applies_to_buses() {
+    let straight = render_dsl(
+        "cps: 0.5\n~drums $ s \"hh*16\" # lpf 8000 0.7\nout $ ~drums",
+        1,
+    );
+    let grooved = render_dsl(
+        "cps: 0.5\ngroove ~drums \"mpc60_66\"\n~drums $ s \"hh*16\" # lpf 8000 0.7\nout $ ~drums",
+        1,
+    );
+    assert_eq!(straight.len(), grooved.len());
+    assert!(
+        calculate_rms(&grooved) > 0.01,
+        "Grooved bus should render audio"
+    );
+    assert!(
+        straight
+            .iter()
+            .zip(&grooved)
+            .any(|(a, b)| (a - b).abs() > 1e-4),
+        "groove statement should move the bus's hits"
+    );
+
+    // Below the bus definition it is still a statement of its own
+    let grooved_after = render_dsl(
+        "cps: 0.5\n~drums $ s \"hh*16\" # lpf 8000 0.7\ngroove ~drums \"mpc60_66\"\nout $ ~drums",
+        1,
+    );
+    assert_eq!(grooved, grooved_after);
+
+    // Only buses with a sample pattern can be grooved
+    for code in [
+        "groove ~bass \"mpc\"\n~bass $ saw 55\nout $ ~bass",
+        "groove ~missing \"mpc\"\nout $ s \"bd\"",
+        "groove ~drums \"nope\"\n~drums $ s \"bd\"\nout $ ~drums",
+    ] {
+        let (_, statements) = parse_program(code).expect("Parse failed");
+        assert!(
+            compile_program(statements, 44100.0, None).is_err(),
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn test_groove_file_velocities() {
+    let dir = tempfile::tempdir().unwrap();
+    let soft = dir.path().join("soft.toml");
+    std::fs::write(&soft, "offsets = [0.0, 0.0]\nvelocities = [0.5, 0.5]\n").unwrap();
+
+    let loud = render_dsl("cps: 0.5\nout $ s \"bd*2\"", 1);
+    let quiet = render_dsl(
+        &format!("cps: 0.5\nout $ s \"bd*2\" $ groove \"{}\"", soft.display()),
+        1,
+    );
+    let ratio = calculate_rms(&quiet) / calculate_rms(&loud);
+    assert!(
+        (ratio - 0.5).abs() < 0.02,
+        "Velocity 0.5 should halve the level: {}",
+        ratio
+    );
+}
@@ -1,7 +1,9 @@
 /// Test groove operations: swing, shuffle
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_swing_transform() {
     // swing should add swing/shuffle feel to events
     let input = r#"
@@ -24,6 +26,7 @@ fn test_swing_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_shuffle_transform() {
     // shuffle should shuffle pattern by n
     let input = r#"
@@ -118,6 +121,7 @@ fn test_shuffle_at_pattern_level() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_swing_with_chained_transforms() {
     // swing should work with other transforms
     let input = r#"
@@ -139,6 +143,7 @@ fn test_swing_with_chained_transforms() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_shuffle_with_chained_transforms() {
     // shuffle should work with other transforms
     let input = r#"
@@ -17,6 +17,7 @@
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
 use phonon::pattern_metrics::PatternMetrics;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -32,6 +33,7 @@ use pattern_verification_utils::{
 // ============================================================================
 
 /// Render DSL code to audio samples
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_secs: f32) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Parse DSL failed");
     let compiler = DslCompiler::new(44100.0);
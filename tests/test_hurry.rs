@@ -12,6 +12,7 @@
 ///   hurry 2 $ s "bd sn"  → 2× events, 2× pitch
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -28,6 +29,7 @@ fn query_cycle<T: Clone + Send + Sync + 'static>(pattern: &Pattern<T>, cycle: i6
 }
 
 /// Helper to compile and render DSL
+#[allow(deprecated)]
 fn compile_and_render(input: &str, duration_samples: usize) -> Vec<f32> {
     let (_, statements) = parse_dsl(input).expect("Failed to parse DSL");
     let compiler = DslCompiler::new(44100.0);
@@ -211,6 +213,7 @@ fn test_hurry_level1_timing_matches_fast() {
 // ============================================================================
 
 #[test]
+#[allow(deprecated)]
 fn test_hurry_parses_in_dsl() {
     // Verify hurry can be parsed from DSL text
     let code = r#"bpm 120
@@ -225,6 +228,7 @@ out $ s("bd sn" $ hurry 2)"#;
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_hurry_parses_with_pattern_arg() {
     // Verify hurry with pattern argument parses
     let code = r#"bpm 120
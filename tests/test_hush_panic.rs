@@ -1,9 +1,11 @@
 /// Tests for hush and panic commands in live coding
 ///
 /// Tests both the parser and the actual functionality of hush/panic commands.
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler, DslStatement};
 
 #[test]
+#[allow(deprecated)]
 fn test_parse_hush_all() {
     let input = "hush";
     let result = parse_dsl(input);
@@ -34,6 +36,7 @@ fn test_parse_hush_all() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parse_hush_channel_1() {
     let input = "hush1";
     let result = parse_dsl(input);
@@ -51,6 +54,7 @@ fn test_parse_hush_channel_1() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parse_hush_channel_2() {
     let input = "hush2";
     let result = parse_dsl(input);
@@ -68,6 +72,7 @@ fn test_parse_hush_channel_2() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parse_panic() {
     let input = "panic";
     let result = parse_dsl(input);
@@ -85,6 +90,7 @@ fn test_parse_panic() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_hush_silences_single_output() {
     // First, create an output with audio
     let input = r#"
@@ -137,6 +143,7 @@ fn test_hush_silences_single_output() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_hush_channel_silences_specific_channel() {
     // Create two output channels
     let input = r#"
@@ -179,6 +186,7 @@ fn test_hush_channel_silences_specific_channel() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_hush_all_silences_all_channels() {
     // Create multiple output channels
     let input = r#"
@@ -203,6 +211,7 @@ fn test_hush_all_silences_all_channels() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_panic_silences_and_kills_voices() {
     // Create output with sample playback (which uses voices)
     let input = r#"
@@ -225,6 +234,7 @@ fn test_panic_silences_and_kills_voices() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_panic_with_synth_pattern() {
     // Create output with synth pattern (which uses voices)
     let input = r#"
@@ -247,6 +257,7 @@ fn test_panic_with_synth_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_multiple_hush_commands() {
     // Test that we can hush multiple channels individually
     let input = r#"
@@ -274,6 +285,7 @@ fn test_multiple_hush_commands() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_hush_then_unhush_not_supported() {
     // In Tidal Cycles, there's no "unhush" command
     // Once hushed, you need to re-evaluate the pattern to unhush
@@ -299,6 +311,7 @@ fn test_hush_then_unhush_not_supported() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parse_hush_with_whitespace() {
     // Test that parser handles whitespace correctly
     let input = r#"
@@ -315,6 +328,7 @@ fn test_parse_hush_with_whitespace() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parse_panic_with_whitespace() {
     let input = r#"
         tempo: 0.5
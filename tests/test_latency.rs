@@ -0,0 +1,62 @@
+/// Tests for per-bus latency compensation (`# latency`)
+///
+/// Rendered through `gateout`, whose buffer holds the gate in volts.
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+fn rising_edges(gate: &[f32]) -> Vec<usize> {
+    (1..gate.len())
+        .filter(|&i| gate[i] > 2.5 && gate[i - 1] < 2.5)
+        .collect()
+}
+
+#[test]
+fn test_latency_is_the_same_time_at_any_tempo() {
+    for tempo in ["0.5", "2.0"] {
+        let code = format!(
+            "tempo: {}\n~clock $ gate \"t ~\" # latency 100ms\ngateout 3 ~clock",
+            tempo
+        );
+        let mut graph = compile_code(&code).unwrap();
+        graph.render(22050);
+        let edges = rising_edges(graph.cv_buffer(3));
+        assert!(edges[0].abs_diff(4410) <= 1, "tempo {}: {:?}", tempo, edges);
+    }
+}
+
+#[test]
+fn test_negative_latency_sends_events_early() {
+    let mut graph =
+        compile_code("tempo: 1.0\n~clock $ gate \"t ~\" # latency -12ms\ngateout 3 ~clock")
+            .unwrap();
+    // Up to the second cycle's gate, 12 ms (529 samples) before the second
+    graph.render(66150);
+    let edges = rising_edges(graph.cv_buffer(3));
+    assert_eq!(edges.len(), 1, "{:?}", edges);
+    assert!(edges[0].abs_diff(44100 - 529) <= 1, "{:?}", edges);
+}
+
+#[test]
+fn test_latency_follows_tempo_changes() {
+    let mut graph =
+        compile_code("tempo: 1.0\n~clock $ gate \"t ~\" # latency 100ms\ngateout 3 ~clock")
+            .unwrap();
+    graph.set_cps(0.5);
+    graph.render(22050);
+    let edges = rising_edges(graph.cv_buffer(3));
+    assert!(edges[0].abs_diff(4410) <= 1, "{:?}", edges);
+}
+
+#[test]
+fn test_latency_needs_a_bus_with_patterns() {
+    let err = compile_code("out $ sine 440 # latency 10ms").err().unwrap();
+    assert!(err.contains("goes on a bus"), "{}", err);
+    let err = compile_code("~drone $ sine 110 # latency 10ms")
+        .err()
+        .unwrap();
+    assert!(err.contains("no pattern"), "{}", err);
+    let err = compile_code("~clock $ gate \"t\" # latency 2s")
+        .err()
+        .unwrap();
+    assert!(err.contains("late or early"), "{}", err);
+}
@@ -0,0 +1,91 @@
+/// Tests for the `lfo` modulation source: shapes, ranges, tempo sync,
+/// phase offset and one-shot mode
+mod audio_test_utils;
+use audio_test_utils::{compile_code, render_unlimited};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// Checks a rising 0..0.8 ramp that restarts `per_second` times a second,
+/// `offset` periods in, away from the resets
+fn assert_ramp(out: &[f32], per_second: f64, offset: f64) {
+    for (i, sample) in out.iter().enumerate() {
+        let phase = (i as f64 * per_second / SAMPLE_RATE as f64 + offset).fract();
+        if phase < 0.01 || phase > 0.99 {
+            continue;
+        }
+        let expected = 0.8 * phase as f32;
+        assert!(
+            (sample - expected).abs() < 2e-3,
+            "sample {}: {} vs {}",
+            i,
+            sample,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_lfo_shapes_and_range() {
+    // Hz rate, scaled into min..max
+    assert_ramp(
+        &render_unlimited("out $ lfo 2 0 0.8 :shape saw", 44100),
+        2.0,
+        0.0,
+    );
+
+    // The default sine spans -1..1 like `sine`
+    let lfo = render_unlimited("out $ lfo 0.5 :min -0.9 :max 0.9", 44100);
+    let sine = render_unlimited("out $ sine 0.5 * 0.9", 44100);
+    for (i, (a, b)) in lfo.iter().zip(&sine).enumerate() {
+        assert!((a - b).abs() < 1e-3, "sample {}: {} vs {}", i, a, b);
+    }
+
+    // Random holds one value per period: 4 periods, 3 jumps
+    let random = render_unlimited("out $ lfo 4 0 1 :shape random", 44100);
+    let jumps = random.windows(2).filter(|w| w[0] != w[1]).count();
+    assert_eq!(jumps, 3);
+}
+
+#[test]
+fn test_lfo_tempo_sync_and_phase() {
+    // At 2 cycles per second, a 1-cycle period ramps twice a second
+    assert_ramp(
+        &render_unlimited("tempo: 2.0\nout $ lfo 1c 0 0.8 :shape saw", 44100),
+        2.0,
+        0.0,
+    );
+    // Four ramps per cycle, starting a quarter of the way in
+    assert_ramp(
+        &render_unlimited(
+            "tempo: 1.0\nout $ lfo 0.25c :min 0 :max 0.8 :shape saw :phase 0.25",
+            44100,
+        ),
+        4.0,
+        0.25,
+    );
+}
+
+#[test]
+fn test_lfo_oneshot_holds_at_the_end() {
+    let out = render_unlimited("out $ lfo 1 0 0.8 :shape saw :oneshot true", 66150);
+    assert_ramp(&out[..44000], 1.0, 0.0);
+    assert!(out[44200..].iter().all(|s| (s - 0.8).abs() < 1e-3));
+}
+
+#[test]
+fn test_lfo_usage_and_errors() {
+    let out = render_unlimited(
+        "tempo: 1.0\nout $ saw 110 # lpf (lfo 0.25c :min 200 :max 2000 :shape tri) 0.7",
+        4410,
+    );
+    assert!(out.iter().any(|s| s.abs() > 0.01), "silent");
+
+    for (code, message) in [
+        ("out $ sine 440 * lfo 0c", "above 0"),
+        ("out $ sine 440 * lfo 1 :shape blob", "unknown :shape"),
+        ("out $ sine 440 # delay 0.25c 0.5 0.5", "lfo rate"),
+    ] {
+        let err = compile_code(code).err().expect(code);
+        assert!(err.contains(message), "{}: {}", code, err);
+    }
+}
@@ -0,0 +1,64 @@
+/// Tests that long renders stay in tune and in time
+///
+/// Oscillator phases (plain, FM and PM) and the cycle position accumulate once
+/// per sample; in f32 a minute of accumulation at a few kHz drifts by a
+/// hundredth of a cycle or more. All are f64, so after a minute the phases and
+/// position must still match the exact values.
+use phonon::unified_graph::{SignalNode, UnifiedSignalGraph};
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// Every oscillator phase in the graph (FM: carrier then modulator)
+fn oscillator_phases(graph: &UnifiedSignalGraph) -> Vec<f64> {
+    graph
+        .nodes
+        .iter()
+        .filter_map(|n| n.as_deref())
+        .flat_map(|node| match node {
+            SignalNode::Oscillator { phase, .. } => vec![*phase.borrow()],
+            SignalNode::FMOscillator {
+                carrier_phase,
+                modulator_phase,
+                ..
+            } => vec![*carrier_phase.borrow(), *modulator_phase.borrow()],
+            SignalNode::PMOscillator { carrier_phase, .. } => vec![*carrier_phase.borrow()],
+            _ => vec![],
+        })
+        .collect()
+}
+
+#[test]
+fn test_one_minute_high_frequency_render_stays_in_phase() {
+    let mut graph = compile_code("tempo: 0.5\nout $ sine 10000 + fm 1000 3000 2 + pm 12345 0 1")
+        .expect("Failed to compile");
+
+    // One minute, a second at a time
+    let seconds = 60;
+    for _ in 0..seconds {
+        graph.render(SAMPLE_RATE as usize);
+    }
+
+    // Whole-Hz frequencies for exactly 60 s are whole numbers of periods:
+    // every phase returns to 0 (f32 accumulation is off by 0.01-0.05 here)
+    let phases = oscillator_phases(&graph);
+    assert_eq!(phases.len(), 4);
+    for phase in phases {
+        let phase_error = phase.min(1.0 - phase);
+        assert!(
+            phase_error < 1e-6,
+            "oscillator drifted {} cycles after a minute",
+            phase_error
+        );
+    }
+
+    // And the pattern clock is exactly 30 cycles in
+    let cycle_error = (graph.get_cycle_position() - 0.5 * seconds as f64).abs();
+    assert!(
+        cycle_error < 1e-6,
+        "cycle position drifted {} cycles after a minute",
+        cycle_error
+    );
+}
@@ -0,0 +1,50 @@
+/// Tests for the `ms { mid: ..., side: ... }` block on the out1/out2 pair
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+
+fn render_stereo(code: &str, samples: usize) -> (Vec<f32>, Vec<f32>) {
+    let (rest, statements) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    let mut graph = compile_program(statements, 44100.0, None).expect("Failed to compile");
+    graph.render_stereo(samples)
+}
+
+const PAIR: &str = "out1 $ sine 220 * 0.3\nout2 $ sine 330 * 0.2\n";
+
+#[test]
+fn test_ms_without_chains_is_transparent() {
+    let (dry_l, dry_r) = render_stereo(PAIR, 4410);
+    let (wet_l, wet_r) = render_stereo(&format!("{}ms {{ mid: gain 1 }}", PAIR), 4410);
+    for i in 0..4410 {
+        assert!((dry_l[i] - wet_l[i]).abs() < 1e-5, "left sample {}", i);
+        assert!((dry_r[i] - wet_r[i]).abs() < 1e-5, "right sample {}", i);
+    }
+}
+
+#[test]
+fn test_ms_muting_side_collapses_to_mono() {
+    let (dry_l, dry_r) = render_stereo(PAIR, 4410);
+    let (left, right) = render_stereo(&format!("{}ms {{\n  side: gain 0\n}}", PAIR), 4410);
+    for i in 0..4410 {
+        let mono = (dry_l[i] + dry_r[i]) * 0.5;
+        assert!((left[i] - mono).abs() < 1e-5, "sample {}", i);
+        assert!((right[i] - mono).abs() < 1e-5, "sample {}", i);
+    }
+}
+
+#[test]
+fn test_ms_mid_chain_keeps_the_difference() {
+    let (dry_l, dry_r) = render_stereo(PAIR, 4410);
+    let (left, right) = render_stereo(&format!("{}ms {{ mid: gain 0 }}", PAIR), 4410);
+    for i in 0..4410 {
+        let side = (dry_l[i] - dry_r[i]) * 0.5;
+        assert!((left[i] - side).abs() < 1e-5, "sample {}", i);
+        assert!((right[i] + side).abs() < 1e-5, "sample {}", i);
+    }
+}
+
+#[test]
+fn test_ms_needs_a_stereo_pair() {
+    let (_, statements) = parse_program("out $ sine 220\nms { side: gain 0 }").unwrap();
+    assert!(compile_program(statements, 44100.0, None).is_err());
+}
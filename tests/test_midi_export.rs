@@ -0,0 +1,67 @@
+/// Tests for `phonon export-midi`: note, n and sample patterns to a MIDI file
+use phonon::midi_export::{export_file, export_graph};
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+const SKETCH: &str = r#"tempo: 0.5
+~drums $ s "bd sn hh*2" # note "c4 e4"
+~lead $ sine "c4 e4 g4"
+out $ ~drums + ~lead * 0.2"#;
+
+#[test]
+fn test_one_track_per_bus() {
+    let export = export_graph(&compile_code(SKETCH).expect("Failed to compile"), 2.0);
+    let names: Vec<&str> = export.tracks.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["~drums", "~lead"]);
+
+    // Drum hits on channel 10, General MIDI notes; `# note` shapes the hits
+    // rather than becoming a melody
+    let drums = &export.tracks[0].notes;
+    assert_eq!(drums.len(), 8);
+    assert!(drums.iter().all(|n| n.channel == 9));
+    let first: Vec<(f64, u8)> = drums.iter().take(4).map(|n| (n.start, n.note)).collect();
+    assert_eq!(first[0], (0.0, 36));
+    assert_eq!(first[1].1, 38);
+    assert_eq!(first[2].1, 42);
+
+    let lead = &export.tracks[1].notes;
+    let keys: Vec<u8> = lead.iter().map(|n| n.note).collect();
+    assert_eq!(keys, vec![60, 64, 67, 60, 64, 67]);
+    assert!(lead.iter().all(|n| n.channel == 0));
+    assert!((lead[1].start - 1.0 / 3.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_standard_midi_file_layout() {
+    let export = export_graph(&compile_code(SKETCH).expect("Failed to compile"), 1.0);
+    assert_eq!(export.bpm(), 120.0);
+    let smf = export.to_smf();
+
+    assert_eq!(&smf[0..4], b"MThd");
+    // Type 1, tempo track + 2 bus tracks, 480 ticks per beat
+    assert_eq!(&smf[8..14], &[0, 1, 0, 3, 0x01, 0xe0]);
+    assert_eq!(smf.windows(4).filter(|w| w == b"MTrk").count(), 3);
+    // 120 BPM = 500000 µs per quarter note
+    let tempo = smf
+        .windows(3)
+        .position(|w| w == [0xff, 0x51, 0x03])
+        .unwrap();
+    assert_eq!(&smf[tempo + 3..tempo + 6], &[0x07, 0xa1, 0x20]);
+    assert!(smf.ends_with(&[0x00, 0xff, 0x2f, 0x00]));
+}
+
+#[test]
+fn test_export_file() {
+    let dir = std::env::temp_dir().join(format!("phonon_midi_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("sketch.phonon");
+    std::fs::write(&input, SKETCH).unwrap();
+
+    let output = dir.join("sketch.mid");
+    let export = export_file(&input, 4.0, &output).unwrap();
+    assert_eq!(std::fs::read(&output).unwrap(), export.to_smf());
+    assert!(export_file(&input, 0.0, &output).is_err());
+
+    std::fs::remove_dir_all(&dir).ok();
+}
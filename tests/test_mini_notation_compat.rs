@@ -0,0 +1,149 @@
+#![allow(deprecated)] // checks the `mini_notation` shim against v3
+/// Compatibility of the first mini-notation parser's users with v3
+///
+/// `mini_notation` now forwards to `mini_notation_v3`. The patterns that read
+/// the same under both parsers are checked first; after them, each pattern the
+/// first parser read differently, with what it gave in a comment and what v3
+/// gives asserted. The table in `mini_notation`'s module doc lists the same
+/// patterns.
+use phonon::mini_notation::{parse_extended_notation, parse_mini_notation, MiniNotationParser};
+use phonon::mini_notation_v3;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+use std::collections::HashMap;
+
+/// The events starting in `cycle`, as `"value@onset"`
+fn onsets(pattern: &Pattern<String>, cycle: i64) -> Vec<String> {
+    let state = State {
+        span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+        controls: HashMap::new(),
+    };
+    let mut haps: Vec<_> = pattern
+        .query(&state)
+        .into_iter()
+        .filter(|hap| {
+            hap.whole
+                .as_ref()
+                .map_or(true, |w| w.begin == hap.part.begin)
+        })
+        .map(|hap| (hap.part.begin.to_float(), hap.value))
+        .collect();
+    haps.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    haps.into_iter()
+        .map(|(onset, value)| format!("{}@{:.3}", value, onset))
+        .collect()
+}
+
+/// The first cycle of `notation` under v3
+fn v3(notation: &str) -> Vec<String> {
+    onsets(&mini_notation_v3::parse_mini_notation(notation), 0)
+}
+
+#[test]
+fn test_shim_is_v3() {
+    let patterns = ["bd sn", "bd:3 ~ sn", "<bd sn>", "bd(3,8,2)", "[bd, sn cp]"];
+    for notation in patterns {
+        let v3_pattern = mini_notation_v3::parse_mini_notation(notation);
+        for cycle in 0..2 {
+            let expected = onsets(&v3_pattern, cycle);
+            let shim = onsets(&parse_mini_notation(notation), cycle);
+            let parser = onsets(&MiniNotationParser::new(notation).parse(), cycle);
+            assert_eq!(shim, expected, "{}", notation);
+            assert_eq!(parser, expected, "{}", notation);
+        }
+    }
+}
+
+#[test]
+fn test_extended_notation_layers_with_plus() {
+    let layered = onsets(&parse_extended_notation("bd sn + hh*4"), 0);
+    assert_eq!(
+        layered,
+        ["bd@0.000", "hh@0.000", "hh@0.250", "hh@0.500", "sn@0.500", "hh@0.750"]
+    );
+}
+
+#[test]
+fn test_patterns_both_parsers_agree_on() {
+    let cases: &[(&str, &[&str])] = &[
+        (
+            "bd sn hh cp",
+            &["bd@0.000", "sn@0.250", "hh@0.500", "cp@0.750"],
+        ),
+        ("bd ~ sn ~", &["bd@0.000", "sn@0.500"]),
+        ("bd*2 sn", &["bd@0.000", "bd@0.250", "sn@0.500"]),
+        ("[bd sn] hh", &["bd@0.000", "sn@0.250", "hh@0.500"]),
+        ("bd(3,8)", &["bd@0.000", "bd@0.375", "bd@0.750"]),
+        ("[bd, sn cp]", &["bd@0.000", "sn@0.000", "cp@0.500"]),
+        ("bd _ sn", &["bd@0.000", "sn@0.500"]),
+        ("bd {sn cp}", &["bd@0.000", "sn@0.333", "cp@0.667"]),
+        ("1 2 3", &["1@0.000", "2@0.333", "3@0.667"]),
+    ];
+    for (notation, expected) in cases {
+        assert_eq!(v3(notation), *expected, "{}", notation);
+    }
+
+    let alternation = mini_notation_v3::parse_mini_notation("<bd sn hh>");
+    assert_eq!(onsets(&alternation, 1), ["sn@1.000"]);
+    // Both read parentheses around a comma list as a stack, not euclid
+    assert_eq!(v3("(bd,sn cp)"), ["bd@0.000", "sn@0.000", "cp@0.500"]);
+}
+
+#[test]
+fn test_sample_index_is_kept() {
+    // The first parser gave "bd", "sn"
+    assert_eq!(v3("bd:3 sn:1"), ["bd:3@0.000", "sn:1@0.500"]);
+}
+
+#[test]
+fn test_negative_numbers_keep_their_sign() {
+    // The first parser gave "0.5", "1"
+    assert_eq!(v3("0.5 -1"), ["0.5@0.000", "-1@0.500"]);
+}
+
+#[test]
+fn test_dot_splits_the_cycle_into_feet() {
+    // The first parser ignored the dot: three steps of a third
+    assert_eq!(v3("bd . sn sn"), ["bd@0.000", "sn@0.500", "sn@0.750"]);
+}
+
+#[test]
+fn test_slow_applies_to_groups() {
+    // The first parser read "2" as a step: bd, sn and 2 in a cycle
+    let pattern = mini_notation_v3::parse_mini_notation("[bd sn]/2");
+    assert_eq!(onsets(&pattern, 0), ["bd@0.000"]);
+    assert_eq!(onsets(&pattern, 1), ["sn@1.000"]);
+}
+
+#[test]
+fn test_patterned_fast() {
+    // The first parser gave "bd 2", then "bd 3"
+    let pattern = mini_notation_v3::parse_mini_notation("bd*<2 3>");
+    assert_eq!(onsets(&pattern, 0), ["bd@0.000", "bd@0.500"]);
+    assert_eq!(onsets(&pattern, 1), ["bd@1.000", "bd@1.333", "bd@1.667"]);
+}
+
+#[test]
+fn test_groups_inside_alternation() {
+    // The first parser gave one sn in the second cycle
+    let pattern = mini_notation_v3::parse_mini_notation("<bd [sn sn]>");
+    assert_eq!(onsets(&pattern, 1), ["sn@1.000", "sn@1.500"]);
+}
+
+#[test]
+fn test_pipe_stacks() {
+    // The first parser ignored the pipe: bd, sn and hh in thirds
+    assert_eq!(v3("bd sn|hh"), ["bd@0.000", "hh@0.000", "sn@0.500"]);
+}
+
+#[test]
+fn test_bang_is_ignored() {
+    // The first parser repeated the step: "bd bd"
+    assert_eq!(v3("bd!"), ["bd@0.000"]);
+}
+
+#[test]
+fn test_top_level_comma_ends_the_pattern() {
+    // The first parser ignored the comma: bd, sn and hh in thirds
+    assert_eq!(v3("bd sn, hh"), ["bd@0.000", "sn@0.500"]);
+    assert_eq!(v3("[bd sn, hh]"), ["bd@0.000", "hh@0.000", "sn@0.500"]);
+}
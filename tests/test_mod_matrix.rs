@@ -0,0 +1,93 @@
+/// Tests for `mod { ... }` blocks: routing modulation sources onto the
+/// parameters of nodes on other buses
+mod audio_test_utils;
+use audio_test_utils::{compile_code, render_unlimited};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// Rising zero crossings in one second, i.e. the frequency in Hz
+fn frequency(out: &[f32]) -> usize {
+    out.windows(2).filter(|w| w[0] <= 0.0 && w[1] > 0.0).count()
+}
+
+#[test]
+fn test_mod_routes_offset_and_scale() {
+    for (route, hz) in [
+        // 100 + 0.5 * 200
+        ("~m -> ~osc.freq * 200", 200),
+        // 100 * 2^(0.5 * 12 / 12)
+        ("~m -> ~osc.freq * 12st", 200),
+        // 100 + 0.5^2 * 400
+        ("~m -> ~osc.freq * 400 :curve 2", 200),
+        // Routes to the same parameter stack: (100 + 50) * 2
+        ("~m -> ~osc.freq * 100; ~m -> ~osc.freq * 24st", 300),
+    ] {
+        let code = format!(
+            "~m $ 0.5\n~osc $ sine 100\nmod {{ {} }}\nout $ ~osc * 0.5",
+            route
+        );
+        let found = frequency(&render_unlimited(&code, SAMPLE_RATE as usize));
+        assert!(
+            (found as i64 - hz).abs() <= 1,
+            "{}: {}Hz, expected {}Hz",
+            route,
+            found,
+            hz
+        );
+    }
+}
+
+#[test]
+fn test_mod_multiline_block() {
+    let code = "
+~lfo $ sine 2
+~one $ 1
+~voice $ sine 100 # lpf 20000 0.7
+mod {
+    ~one -> ~voice.freq * 50
+    -- a comment between routes
+    ~lfo -> ~voice.cutoff * 800
+}
+out $ ~voice * 0.5
+";
+    // The freq route reaches the oscillator behind the filter
+    let found = frequency(&render_unlimited(code, SAMPLE_RATE as usize));
+    assert!((found as i64 - 150).abs() <= 1, "{}Hz", found);
+}
+
+#[test]
+fn test_mod_matches_a_fixed_parameter() {
+    let modulated = render_unlimited(
+        "~one $ 1\n~f $ saw 55 # lpf 20000 0.7\nmod { ~one -> ~f.cutoff * -19800 }\nout $ ~f * 0.5",
+        SAMPLE_RATE as usize,
+    );
+    let fixed = render_unlimited(
+        "~f $ saw 55 # lpf 200 0.7\nout $ ~f * 0.5",
+        SAMPLE_RATE as usize,
+    );
+    let diff = modulated
+        .iter()
+        .zip(&fixed)
+        .fold(0.0f32, |m, (a, b)| m.max((a - b).abs()));
+    assert!(diff < 1e-4, "max difference {}", diff);
+}
+
+#[test]
+fn test_mod_errors() {
+    let err = |code: &str| compile_code(code).err().expect("should not compile");
+    let base = "~lfo $ sine 1\n~f $ saw 55 # lpf 1000 0.7\nout $ ~f\n";
+
+    let e = err(&format!("{}mod {{ ~lfo -> ~nope.cutoff * 100 }}", base));
+    assert!(e.contains("~nope is not a signal bus"), "{}", e);
+    let e = err(&format!("{}mod {{ ~lfo -> ~f.width * 100 }}", base));
+    assert!(e.contains("'width'"), "{}", e);
+    let e = err(&format!(
+        "{}mod {{ ~lfo -> ~f.cutoff * 100 :curve 5 }}",
+        base
+    ));
+    assert!(e.contains(":curve"), "{}", e);
+
+    // The search stops at other buses: ~lfo's own freq isn't ~f's
+    let e = err("~lfo $ sine 1\n~f $ ~lfo * 0.5\nmod { ~lfo -> ~f.freq * 2 }\nout $ ~f");
+    assert!(e.contains("'freq'"), "{}", e);
+}
@@ -4,6 +4,7 @@
 /// Tests both the compositional compiler (primary path) and DslCompiler (secondary).
 use phonon::compositional_compiler::compile_program;
 use phonon::compositional_parser::parse_program;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler, DslStatement};
 
 // ============================================================================
@@ -24,6 +25,7 @@ fn render_compositional(code: &str, num_samples: usize) -> Vec<f32> {
     full_audio
 }
 
+#[allow(deprecated)]
 fn render_dsl_compiler(code: &str, num_samples: usize) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).unwrap();
     let compiler = DslCompiler::new(44100.0);
@@ -308,6 +310,7 @@ fn test_panic_silences_multi_channel() {
 // ============================================================================
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_parse_hush() {
     let (_, stmts) = parse_dsl("hush").unwrap();
     assert_eq!(stmts.len(), 1);
@@ -318,6 +321,7 @@ fn test_dsl_parse_hush() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_parse_hush_channel() {
     let (_, stmts) = parse_dsl("hush3").unwrap();
     assert_eq!(stmts.len(), 1);
@@ -328,6 +332,7 @@ fn test_dsl_parse_hush_channel() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_parse_unhush() {
     let (_, stmts) = parse_dsl("unhush").unwrap();
     assert_eq!(stmts.len(), 1);
@@ -338,6 +343,7 @@ fn test_dsl_parse_unhush() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_parse_unhush_channel() {
     let (_, stmts) = parse_dsl("unhush2").unwrap();
     assert_eq!(stmts.len(), 1);
@@ -348,6 +354,7 @@ fn test_dsl_parse_unhush_channel() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dsl_parse_panic() {
     let (_, stmts) = parse_dsl("panic").unwrap();
     assert_eq!(stmts.len(), 1);
@@ -0,0 +1,138 @@
+/// Tests for named bus taps and post-run analysis
+///
+/// `tap ~bass "bass_debug"` records everything ~bass produces to
+/// bass_debug.wav; `phonon analyze bass_debug.wav` then reports pitch,
+/// RMS and spectral centroid over time using `audio_analysis::analyze_frames`.
+use phonon::audio_analysis::{analyze_frames, read_wav_mono};
+use phonon::compositional_compiler::{compile_program, compile_program_with_assertions};
+use phonon::compositional_parser::{parse_program, Statement};
+use phonon::unified_graph::UnifiedSignalGraph;
+
+fn compile_code(code: &str) -> Result<UnifiedSignalGraph, String> {
+    let (rest, stmts) = parse_program(code).map_err(|e| format!("Parse error: {}", e))?;
+    if !rest.trim().is_empty() {
+        return Err(format!("Parser did not consume all input: {:?}", rest));
+    }
+    compile_program_with_assertions(stmts, 44100.0)
+}
+
+/// Tap name inside the system temp dir (without extension)
+fn temp_tap(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("phonon_{}_{}", name, std::process::id()))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_named_tap_records_bus() {
+    let name = temp_tap("bass_debug");
+    let code = format!(
+        r#"
+tempo: 1.0
+~bass $ sine 55 * 0.5
+out $ ~bass + sine 880 * 0.1
+tap ~bass "{}"
+"#,
+        name
+    );
+    let mut graph = compile_code(&code).expect("Should compile");
+    graph.render(44100);
+
+    let files = graph.write_tap_files();
+    let expected = format!("{}.wav", name);
+    assert_eq!(files, vec![expected.clone()]);
+
+    // The tap holds only ~bass, not the rest of the mix
+    let (samples, sample_rate) = read_wav_mono(&expected).expect("tap file should exist");
+    assert_eq!(samples.len(), 44100);
+    let frames = analyze_frames(&samples, sample_rate, 0.25);
+    for frame in &frames[..3] {
+        assert!((frame.pitch - 55.0).abs() < 2.0, "pitch {}", frame.pitch);
+        assert!(
+            (frame.rms - 0.5 / 2f32.sqrt()).abs() < 0.02,
+            "rms {}",
+            frame.rms
+        );
+    }
+
+    std::fs::remove_file(&expected).ok();
+}
+
+#[test]
+fn test_tap_before_definition_and_duration() {
+    let name = format!("{}.wav", temp_tap("lead"));
+    let code = format!(
+        r#"
+tap ~lead "{}" 0.5
+~lead $ saw 220
+out $ ~lead * 0.2
+"#,
+        name
+    );
+    let mut graph = compile_code(&code).expect("Should compile");
+    graph.render(44100);
+
+    // Extension isn't doubled, and recording stops after 0.5s
+    let files = graph.write_tap_files();
+    assert_eq!(files, vec![name.clone()]);
+    let (samples, _) = read_wav_mono(&name).expect("tap file should exist");
+    assert_eq!(samples.len(), 22050);
+
+    std::fs::remove_file(&name).ok();
+}
+
+#[test]
+fn test_tap_does_not_change_output() {
+    let plain = "tempo: 1.0\n~bass $ saw 55\nout $ ~bass * 0.3";
+    let tapped = format!("{}\ntap ~bass \"{}\" 0.1", plain, temp_tap("unused"));
+    let a = compile_code(plain).unwrap().render(4410);
+    let b = compile_code(&tapped).unwrap().render(4410);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_tap_requires_signal_bus() {
+    assert!(compile_code("out $ sine 440\ntap ~missing \"x\"").is_err());
+    assert!(compile_code("~a $ sine 440\nout $ ~a\ntap ~a \"x\" 0").is_err());
+}
+
+#[test]
+fn test_live_compile_checks_but_does_not_record_taps() {
+    let live = |code: &str| {
+        let (_, stmts) = parse_program(code).unwrap();
+        compile_program(stmts, 44100.0, None)
+    };
+    assert!(live("out $ sine 440\ntap ~missing \"x\"").is_err());
+    assert!(live("~a $ sine 440\nout $ ~a\ntap ~a \"x\" 0").is_err());
+
+    let code = format!(
+        "~bass $ saw 55\nout $ ~bass * 0.3\ntap ~bass \"{}\"",
+        temp_tap("live")
+    );
+    let mut graph = live(&code).expect("Should compile");
+    graph.render(4410);
+    assert!(graph.write_tap_files().is_empty());
+}
+
+#[test]
+fn test_tap_after_a_call_line_is_its_own_statement() {
+    let (rest, statements) = parse_program("~kick $ s \"bd*4\"\ntap ~kick \"k\" 4").unwrap();
+    assert!(rest.trim().is_empty(), "{:?}", rest);
+    assert_eq!(statements.len(), 2, "{:?}", statements);
+    assert_eq!(
+        statements[1],
+        Statement::Tap {
+            bus: "kick".to_string(),
+            name: "k".to_string(),
+            duration: Some(4.0),
+        }
+    );
+
+    let (_, statements) = parse_program("out $ sine 440\ntap ~missing \"x\"").unwrap();
+    assert!(
+        matches!(statements[1], Statement::Tap { .. }),
+        "{:?}",
+        statements
+    );
+}
@@ -2,6 +2,7 @@
 //!
 //! Tests various levels of nesting AND verifies that effects actually transform the audio
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 /// Helper to calculate RMS of audio buffer
@@ -29,6 +30,7 @@ fn calc_spectral_centroid(buffer: &[f32]) -> f32 {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_single_level_nesting() {
     // Compare: raw sine vs reverb(sine)
     // Reverb should change the envelope and add reflections
@@ -81,6 +83,7 @@ fn test_single_level_nesting() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_double_level_nesting() {
     // Compare: saw wave vs lpf(saw wave) with VERY low cutoff
     // LPF should significantly reduce overall amplitude
@@ -127,6 +130,7 @@ fn test_double_level_nesting() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_triple_level_nesting() {
     // Three levels: reverb(lpf(sine(pattern), ...), ...)
     let code = r#"
@@ -144,6 +148,7 @@ fn test_triple_level_nesting() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_nesting_with_arithmetic() {
     // Compare: static filter vs LFO-modulated filter
     // LFO modulation should create time-varying spectral content
@@ -203,6 +208,7 @@ fn test_nesting_with_arithmetic() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_multiple_nested_calls_in_expression() {
     // Compare: single filtered source vs sum of two differently filtered sources
     // The combined version should have different spectral characteristics
@@ -249,6 +255,7 @@ fn test_multiple_nested_calls_in_expression() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_nesting_with_pattern_strings() {
     // Compare: constant frequency vs pattern-changing frequency
     // Pattern should create varying pitch
@@ -310,6 +317,7 @@ fn test_nesting_with_pattern_strings() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_nesting_with_sample_patterns() {
     // Nesting with sample patterns
     let code = r#"
@@ -327,6 +335,7 @@ fn test_nesting_with_sample_patterns() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_nesting_with_bus_refs() {
     // Nesting with bus references
     let code = r#"
@@ -354,6 +363,7 @@ fn test_nesting_with_bus_refs() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_deeply_nested_with_mixed_types() {
     // Deep nesting with various argument types
     let code = r#"
@@ -381,6 +391,7 @@ fn test_deeply_nested_with_mixed_types() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_nesting_with_chaining() {
     // Nesting combined with # operator chaining
     let code = r#"
@@ -398,6 +409,7 @@ fn test_nesting_with_chaining() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_quadruple_level_nesting() {
     // Four levels of nesting - stress test
     let code = r#"
@@ -415,6 +427,7 @@ fn test_quadruple_level_nesting() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parallel_nested_calls() {
     // Multiple nested calls at same level (in addition)
     let code = r#"
@@ -441,6 +454,7 @@ fn test_parallel_nested_calls() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_asymmetric_nesting() {
     // Different nesting depths in same expression
     let code = r#"
@@ -467,6 +481,7 @@ fn test_asymmetric_nesting() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_nesting_with_all_numeric_args() {
     // Ensure numeric args are parsed correctly in nested context
     let code = r#"
@@ -484,6 +499,7 @@ fn test_nesting_with_all_numeric_args() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_nesting_stops_at_operators() {
     // Ensure parser correctly stops at operators
     let code = r#"
@@ -4,9 +4,11 @@
 /// - ~bass $ saw 55 # lpf 1000 0.8    ($ assigns source, # chains effects)
 /// - ~drums $ s "bd sn" $ fast 2      ($ for pattern source and transforms)
 /// - out $ ~bass + ~drums             ($ for output assignment)
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_new_bus_syntax_dollar() {
     // Test: ~bass $ saw 55
     let input = r#"
@@ -31,6 +33,7 @@ fn test_new_bus_syntax_dollar() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_new_bus_syntax_hash_chain() {
     // Test: ~bass $ saw 55 # lpf 1000 0.8
     let input = r#"
@@ -54,6 +57,7 @@ fn test_new_bus_syntax_hash_chain() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_new_bus_syntax_pattern_transform() {
     // Test: ~drums $ s "bd sn" $ fast 2
     let input = r#"
@@ -78,6 +82,7 @@ fn test_new_bus_syntax_pattern_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_new_bus_syntax_multiple_buses() {
     // Test multiple buses with new syntax
     let input = r#"
@@ -102,6 +107,7 @@ fn test_new_bus_syntax_multiple_buses() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_new_output_syntax_dollar() {
     // Test: out $ expression
     let input = r#"
@@ -124,6 +130,7 @@ fn test_new_output_syntax_dollar() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_new_output_syntax_hash() {
     // Test: out # expression (for signal routing feel)
     let input = r#"
@@ -147,6 +154,7 @@ fn test_new_output_syntax_hash() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_backward_compatibility_colon() {
     // Test that old colon syntax still works
     let input = r#"
@@ -170,6 +178,7 @@ fn test_backward_compatibility_colon() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parentheses_in_new_syntax() {
     // Test that parentheses work correctly with new syntax
     let input = r#"
@@ -194,6 +203,7 @@ fn test_parentheses_in_new_syntax() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_complex_pattern_with_new_syntax() {
     // Test complex pattern operations with new syntax
     let input = r#"
@@ -214,6 +224,7 @@ fn test_complex_pattern_with_new_syntax() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_modifier_bus_with_hash_lfo() {
     // Test: # creates modifier/parameter bus (LFO modulating filter cutoff)
     // ~lfo # sine 0.5 creates a modulation bus
@@ -240,6 +251,7 @@ fn test_modifier_bus_with_hash_lfo() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_modifier_bus_with_hash_pattern() {
     // Test: # creates pattern-based modifier bus
     // ~cutoff # "500 1000 2000" creates a stepped parameter pattern
@@ -265,6 +277,7 @@ fn test_modifier_bus_with_hash_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_multiple_modifier_buses() {
     // Test: multiple # modifier buses controlling different parameters
     let input = r#"
@@ -290,6 +303,7 @@ fn test_multiple_modifier_buses() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dollar_vs_hash_semantic_distinction() {
     // Test: $ for signal sources, # for modifier buses
     // Both should work but have semantic meaning
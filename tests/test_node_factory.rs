@@ -0,0 +1,99 @@
+/// Tests for user DSP nodes registered through `node_factory`
+use phonon::audio_node::{AudioNode, NodeId, ProcessContext};
+use phonon::node_factory::{register, NodeFactory, NodeSpec};
+use std::sync::Arc;
+
+mod audio_test_utils;
+use audio_test_utils::{compile_code, find_peak, render_code};
+
+/// Multiplies its input by `amount`
+struct ScaleNode {
+    inputs: Vec<NodeId>,
+}
+
+impl AudioNode for ScaleNode {
+    fn process_block(
+        &mut self,
+        inputs: &[&[f32]],
+        output: &mut [f32],
+        _sample_rate: f32,
+        _context: &ProcessContext,
+    ) {
+        for (i, out) in output.iter_mut().enumerate() {
+            *out = inputs[0][i] * inputs[1][i];
+        }
+    }
+
+    fn input_nodes(&self) -> Vec<NodeId> {
+        self.inputs.clone()
+    }
+}
+
+struct ScaleFactory;
+
+impl NodeFactory for ScaleFactory {
+    fn spec(&self) -> NodeSpec {
+        NodeSpec::effect("test_scale", "Multiply by a gain").param("amount", 2.0, "Gain")
+    }
+
+    fn create(&self, inputs: &[NodeId], _sample_rate: f32) -> Box<dyn AudioNode> {
+        Box::new(ScaleNode {
+            inputs: inputs.to_vec(),
+        })
+    }
+}
+
+/// Outputs its `level`
+struct LevelFactory;
+
+impl NodeFactory for LevelFactory {
+    fn spec(&self) -> NodeSpec {
+        NodeSpec::source("test_level", "A constant").param("level", 1.0, "Output value")
+    }
+
+    fn create(&self, inputs: &[NodeId], _sample_rate: f32) -> Box<dyn AudioNode> {
+        struct Level(Vec<NodeId>);
+        impl AudioNode for Level {
+            fn process_block(
+                &mut self,
+                inputs: &[&[f32]],
+                output: &mut [f32],
+                _sample_rate: f32,
+                _context: &ProcessContext,
+            ) {
+                output.copy_from_slice(inputs[0]);
+            }
+
+            fn input_nodes(&self) -> Vec<NodeId> {
+                self.0.clone()
+            }
+        }
+        Box::new(Level(inputs.to_vec()))
+    }
+}
+
+/// Peak of the first 0.1s of a program
+fn peak(code: &str) -> f32 {
+    find_peak(&render_code(code, 4410))
+}
+
+#[test]
+fn test_registered_nodes_compile_like_builtins() {
+    register(Arc::new(ScaleFactory)).unwrap();
+    register(Arc::new(LevelFactory)).unwrap();
+
+    // Chained effect, parameter default, positional and keyword parameters
+    let tone = "tempo: 1.0\nout $ (sine 100 * 0.25)";
+    assert!((peak(&format!("{} # test_scale", tone)) - 0.5).abs() < 0.01);
+    assert!((peak(&format!("{} # test_scale 3", tone)) - 0.75).abs() < 0.01);
+    assert!((peak(&format!("{} # test_scale :amount 0.4", tone)) - 0.1).abs() < 0.01);
+
+    // Parameters take signals, not just numbers
+    let code = "out $ test_level (test_level 0.2 + 0.1)";
+    assert!((peak(code) - 0.3).abs() < 1e-4);
+
+    let err = compile_code("out $ sine 100 # test_scale :drive 2").unwrap_err();
+    assert!(err.contains("has no parameter 'drive'"), "{}", err);
+    let err = compile_code("out $ test_level 1 2").unwrap_err();
+    assert!(err.contains("takes 1 parameters"), "{}", err);
+}
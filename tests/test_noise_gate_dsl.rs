@@ -0,0 +1,53 @@
+/// Tests for the audio `gate`: threshold gating, the `:key` sidechain, and
+/// that `gate "pattern"` still makes a pattern gate
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, render_code};
+
+#[test]
+fn test_gate_silences_quiet_signals() {
+    let quiet = render_code("out $ sine 440 * 0.005 # gate -30", 22050);
+    assert!(
+        calculate_rms(&quiet[4410..]) < 1e-4,
+        "{}",
+        calculate_rms(&quiet[4410..])
+    );
+
+    let loud = render_code("out $ sine 440 * 0.5 # gate :threshold -30", 22050);
+    assert!(
+        calculate_rms(&loud[4410..]) > 0.3,
+        "{}",
+        calculate_rms(&loud[4410..])
+    );
+}
+
+#[test]
+fn test_key_opens_the_gate() {
+    // The key sounds for the first half of each cycle only
+    let code = "tempo: 1.0
+~key $ sine 1000 * gate \"1 0\"
+~pad $ sine 220 * 0.5
+out $ gate ~pad :key ~key :threshold -30 :hold 0.01 :release 0.005";
+    let audio = render_code(code, 44100);
+    assert!(audio.iter().all(|s| s.is_finite()));
+
+    let open = calculate_rms(&audio[2205..19845]);
+    let shut = calculate_rms(&audio[26460..44100]);
+    assert!(open > 0.3, "{}", open);
+    assert!(shut < 0.01, "{}", shut);
+
+    // The chained form is the same gate
+    let chained = render_code(
+        "tempo: 1.0
+~key $ sine 1000 * gate \"1 0\"
+out $ sine 220 * 0.5 # gate :key ~key :threshold -30 :hold 0.01 :release 0.005",
+        44100,
+    );
+    assert_eq!(audio, chained);
+}
+
+#[test]
+fn test_pattern_gate_unchanged() {
+    let audio = render_code("tempo: 1.0\nout $ gate \"1 0\"", 44100);
+    assert!(calculate_rms(&audio[..22000]) > 0.5);
+    assert!(calculate_rms(&audio[26460..]) < 0.01);
+}
@@ -0,0 +1,35 @@
+/// Tests for bare note names in expressions: `sine c3`, `# note a#4`
+mod audio_test_utils;
+use audio_test_utils::render_unlimited;
+
+fn assert_same(a: &[f32], b: &[f32]) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!((x - y).abs() < 1e-3, "sample {}: {} vs {}", i, x, y);
+    }
+}
+
+#[test]
+fn test_bare_note_is_its_frequency() {
+    assert_same(
+        &render_unlimited("out $ sine c3 * 0.5", 4410),
+        &render_unlimited("out $ sine 130.8128 * 0.5", 4410),
+    );
+    assert_same(
+        &render_unlimited("out $ sine a#4 * 0.5", 4410),
+        &render_unlimited("out $ sine 466.1638 * 0.5", 4410),
+    );
+}
+
+#[test]
+fn test_bare_note_matches_mini_notation() {
+    for (bare, quoted) in [
+        ("out $ saw 110 # note e5", "out $ saw 110 # note \"e5\""),
+        ("out $ sine ef4 * 0.5", "out $ sine \"ef4\" * 0.5"),
+    ] {
+        assert_same(
+            &render_unlimited(bare, 4410),
+            &render_unlimited(quoted, 4410),
+        );
+    }
+}
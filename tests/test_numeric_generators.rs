@@ -0,0 +1,126 @@
+/// Tests for numeric generators: run, scan, irand, rand, choose, wchoose
+///
+/// Generators produce per-cycle numeric patterns. Their size arguments may
+/// themselves be patterns (`run "<4 8>"`), and the generators combine with
+/// other numeric patterns through the structure operators (`"0 7" |+ run 4`).
+use phonon::mini_notation_v3::parse_mini_notation;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code};
+
+fn numeric(s: &str) -> Pattern<f64> {
+    parse_mini_notation(s).fmap(|v| v.parse::<f64>().unwrap_or(0.0))
+}
+
+fn cycle_values(p: &Pattern<f64>, cycle: i64) -> Vec<f64> {
+    let state = State {
+        span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+        controls: std::collections::HashMap::new(),
+    };
+    let mut haps = p.query(&state);
+    haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+    haps.iter().map(|h| h.value).collect()
+}
+
+// ============================================================================
+// Pattern-Level Tests
+// ============================================================================
+
+#[test]
+fn test_run_with_patterned_size() {
+    let p = Pattern::<f64>::sized_by(numeric("<4 8>"), Pattern::<f64>::run);
+    assert_eq!(cycle_values(&p, 0), vec![0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(
+        cycle_values(&p, 1),
+        vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]
+    );
+}
+
+#[test]
+fn test_scan_with_patterned_size() {
+    // scan grows one step per cycle; its length follows the size pattern
+    let p = Pattern::<f64>::sized_by(numeric("3"), Pattern::<f64>::scan);
+    for cycle in 0..6 {
+        let values = cycle_values(&p, cycle);
+        assert!(
+            !values.is_empty() && values.len() <= 3,
+            "cycle {}: {:?}",
+            cycle,
+            values
+        );
+    }
+}
+
+#[test]
+fn test_run_combines_with_structure_operator() {
+    // "0 7" |+ run 4: left structure, run values added at each onset
+    let p = numeric("0 7").add_left(Pattern::<f64>::run(4));
+    assert_eq!(cycle_values(&p, 0), vec![0.0, 9.0]);
+}
+
+#[test]
+fn test_irand_stays_in_range() {
+    let p = Pattern::<f64>::irand(8);
+    for cycle in 0..32 {
+        for v in cycle_values(&p, cycle) {
+            assert!((0.0..8.0).contains(&v), "irand 8 gave {}", v);
+            assert_eq!(v, v.floor(), "irand should yield integers, got {}", v);
+        }
+    }
+}
+
+// ============================================================================
+// DSL Compilation Tests
+// ============================================================================
+
+#[test]
+fn test_generators_compile_as_standalone_patterns() {
+    for generator in [
+        "run 4",
+        "run \"<4 8>\"",
+        "scan 8",
+        "irand 8",
+        "irand 200 800",
+        "rand",
+        "choose [100, 200, 300]",
+        "wchoose [[100, 1], [200, 3]]",
+    ] {
+        let code = format!("~v $ {}\nout $ sine (~v + 220) * 0.2", generator);
+        if let Err(e) = compile_code(&code) {
+            panic!("{} should compile: {}", generator, e);
+        }
+    }
+}
+
+#[test]
+fn test_run_as_sample_index() {
+    let code = r#"out $ s "drum*8" # n (run 8)"#;
+    if let Err(e) = compile_code(code) {
+        panic!("Should compile: {}", e);
+    }
+}
+
+#[test]
+fn test_generator_in_structure_operator_compiles() {
+    let code = r#"out $ s "superpiano*2" # note ("0 7" |+ run 4)"#;
+    if let Err(e) = compile_code(code) {
+        panic!("Should compile: {}", e);
+    }
+}
+
+#[test]
+fn test_run_rejects_zero_size() {
+    assert!(compile_code("out $ sine (run 0 * 110) * 0.2").is_err());
+}
+
+#[test]
+fn test_patterned_run_renders_audio() {
+    let code = r#"
+tempo: 1.0
+out $ sine (run "<4 8>" |* 110 |+ 220) * 0.3
+"#;
+    let mut graph = compile_code(code).expect("Should compile");
+    let buffer = graph.render(44100);
+    assert!(calculate_rms(&buffer) > 0.05, "Expected audible output");
+}
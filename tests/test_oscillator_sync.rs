@@ -0,0 +1,66 @@
+/// Tests for oscillator hard sync (`:sync ~master`) and phase reset on
+/// pattern triggers (`:retrig "t ~ t t"`)
+mod audio_test_utils;
+use audio_test_utils::{compile_code, render_code};
+
+/// Largest difference between the signal and itself `period` samples later
+fn periodicity_error(signal: &[f32], period: usize) -> f32 {
+    signal
+        .iter()
+        .zip(&signal[period..])
+        .fold(0.0f32, |m, (a, b)| m.max((a - b).abs()))
+}
+
+#[test]
+fn test_hard_sync_locks_to_master() {
+    // A 250Hz saw synced to a 100Hz master repeats every 441 samples
+    let synced = render_code(
+        "~master $ sine 100\nout $ saw 250 :sync ~master * 0.5",
+        8820,
+    );
+    let free = render_code("out $ saw 250 * 0.5", 8820);
+    let peak = free.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+
+    assert!(
+        periodicity_error(&synced[441..], 441) < peak * 0.01,
+        "synced saw is not periodic at the master's rate"
+    );
+    assert!(periodicity_error(&free[441..], 441) > peak * 0.5);
+
+    // Sync resets add the familiar sync harmonics but keep the level
+    let synced_peak = synced.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+    assert!((synced_peak / peak - 1.0).abs() < 0.1, "{}", synced_peak);
+}
+
+#[test]
+fn test_retrig_restarts_the_cycle_on_pattern_triggers() {
+    // A slow naive ramp starts from its bottom on each of the 4 triggers
+    let code = "tempo: 1.0\nout $ saw 1.3 :raw true :retrig \"t ~ t t\" * 0.5";
+    let out = render_code(code, 44100);
+    let bottom = out[0];
+    assert!(bottom < 0.0);
+    let near = |at: usize| {
+        out[at - 2..at + 3]
+            .iter()
+            .fold(f32::MAX, |m, s| m.min((s - bottom).abs()))
+    };
+    assert!(near(22050) < 1e-3, "no reset at the 3rd step");
+    assert!(near(33075) < 1e-3, "no reset at the 4th step");
+    // The rest leaves the ramp running
+    assert!(near(11025) > 0.1 * bottom.abs(), "reset on a rest");
+}
+
+#[test]
+fn test_sync_options_compile() {
+    for code in [
+        "~kick $ trig \"t(3,8)\"\nout $ saw 55 :retrig ~kick",
+        "~m $ saw 80\nout $ square 190 :sync ~m :retrig \"t*2\"",
+        "~m $ sine 80\nout $ saw 110 12 :sync ~m",
+        "~m $ sine 80\nout $ sine (~m * 100 + 300) :sync ~m",
+    ] {
+        let out = compile_code(code)
+            .unwrap_or_else(|e| panic!("{}: {}", code, e))
+            .render(4410);
+        assert!(out.iter().any(|s| s.abs() > 0.01), "{} is silent", code);
+    }
+}
@@ -3,6 +3,7 @@
 //! This test verifies that o1 + o2 + o3 = (o1 alone) + (o2 alone) + (o3 alone)
 //! at the sample level, with FFT analysis to confirm frequency content.
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 fn calculate_rms(buffer: &[f32]) -> f32 {
@@ -36,6 +37,7 @@ fn buffers_match(a: &[f32], b: &[f32], tolerance: f32) -> (bool, f32, usize) {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_output_channels_sum_correctly_simple() {
     // Simple test with sine waves - perfect for verification
     println!("\n=== Testing simple sine wave isolation ===");
@@ -126,6 +128,7 @@ fn test_output_channels_sum_correctly_simple() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_output_channels_sum_correctly_with_samples() {
     // Test with actual samples - the real use case
     println!("\n=== Testing sample playback isolation ===");
@@ -231,6 +234,7 @@ fn test_output_channels_sum_correctly_with_samples() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_user_reported_bug() {
     // Regression for the user report that `808bd` seemed to switch to a different
     // sample (a "tom") when rendered alongside other channels. We verify channel
@@ -4,6 +4,7 @@
 //! Verifies that they prevent clipping and behave as expected
 
 use phonon::unified_graph::OutputMixMode;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 fn calculate_rms(buffer: &[f32]) -> f32 {
@@ -15,6 +16,7 @@ fn find_peak(buffer: &[f32]) -> f32 {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_gain_mode() {
     // Gain mode: divide by number of channels
     // With 2 channels at 0.5 each, result should be ~0.5 (sum=1.0, /2 = 0.5)
@@ -51,6 +53,7 @@ fn test_outmix_gain_mode() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_sqrt_mode() {
     // Sqrt mode (default): divide by sqrt(num_channels)
     // This preserves perceived loudness better than gain mode
@@ -86,6 +89,7 @@ fn test_outmix_sqrt_mode() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_tanh_mode() {
     // Tanh mode: soft saturation
     // Even with very loud signals, output is clamped smoothly
@@ -125,6 +129,7 @@ fn test_outmix_tanh_mode() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_hard_mode() {
     // Hard mode: brick-wall limiting at ±1.0
     let input = r#"
@@ -163,6 +168,7 @@ fn test_outmix_hard_mode() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_none_mode() {
     // None mode: no compensation, direct sum
     // This can clip, but users might want it for creative purposes
@@ -189,6 +195,7 @@ fn test_outmix_none_mode() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_default_is_none() {
     // Default mode should be None (direct sum - like a hardware mixer)
     let input = r#"
@@ -211,6 +218,7 @@ fn test_outmix_default_is_none() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_three_channels_gain() {
     // With 3 channels, gain mode divides by 3
     let input = r#"
@@ -243,6 +251,7 @@ fn test_outmix_three_channels_gain() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_three_channels_sqrt() {
     // With 3 channels, sqrt mode divides by sqrt(3) ≈ 1.732
     let input = r#"
@@ -279,6 +288,7 @@ fn test_outmix_three_channels_sqrt() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_comparison_gain_vs_sqrt() {
     // Compare gain vs sqrt modes - sqrt should be louder
     let code_base = r#"
@@ -318,6 +328,7 @@ fn test_outmix_comparison_gain_vs_sqrt() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_invalid_mode() {
     // Test that invalid mode names are rejected
     // The parser will accept any identifier, but the compiler should reject invalid modes
@@ -338,6 +349,7 @@ fn test_outmix_invalid_mode() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_outmix_single_channel_no_effect() {
     // With only one channel, mixing mode shouldn't matter much
     // (except for tanh/hard which always apply)
@@ -369,6 +381,7 @@ fn test_outmix_single_channel_no_effect() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_channel_independence_with_none_mode() {
     // CRITICAL: With outmix: none (default), channels should be INDEPENDENT
     // Adding a third channel should NOT change the levels of existing channels
@@ -428,6 +441,7 @@ fn test_channel_independence_with_none_mode() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_gain_mode_breaks_independence() {
     // Document that gain/sqrt modes intentionally break channel independence
     // This is a TRADE-OFF: prevent clipping vs. maintain independence
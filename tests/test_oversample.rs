@@ -0,0 +1,82 @@
+/// Tests for the oversampled waveshaping effects (`:oversample 2|4` on
+/// `# distortion`, `# fold`, `# clip`, `wrap` and `# bitcrush`)
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code, find_peak, magnitude_at};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// One second of output after the filters have settled
+fn render_settled(code: &str) -> Vec<f32> {
+    let skip = 4410;
+    let out = compile_code(code)
+        .expect("Failed to compile")
+        .render(skip + SAMPLE_RATE as usize);
+    out[skip..].to_vec()
+}
+
+#[test]
+fn test_oversampling_suppresses_aliases() {
+    // The 7th harmonic of 5kHz (35kHz) folds back to 9.1kHz at 44.1kHz
+    for fx in ["distortion 20 1", "fold", "clip 0.1"] {
+        let gain = if fx == "fold" { "* 4 " } else { "" };
+        let alias = |oversample: &str| {
+            let out = render_settled(&format!("out $ sine 5000 {}# {}{}", gain, fx, oversample));
+            magnitude_at(&out, SAMPLE_RATE, 9100.0) / magnitude_at(&out, SAMPLE_RATE, 5000.0)
+        };
+        let plain = alias("");
+        let oversampled = alias(" :oversample 4");
+        assert!(plain > 0.01, "{}: plain alias {}", fx, plain);
+        assert!(
+            oversampled < plain * 0.05,
+            "{}: {} oversampled vs {} plain",
+            fx,
+            oversampled,
+            plain
+        );
+    }
+}
+
+#[test]
+fn test_fold_and_clip_levels() {
+    // Folding keeps the signal inside its range
+    let folded = render_settled("out $ sine 440 * 3 # fold");
+    assert!(
+        find_peak(&folded) < 1.05,
+        "fold peak {}",
+        find_peak(&folded)
+    );
+    let narrow = render_settled("out $ sine 440 # fold -0.25 0.25 :oversample 2");
+    assert!(find_peak(&narrow) < 0.3, "fold peak {}", find_peak(&narrow));
+
+    // Clipping leaves quiet signals alone and limits loud ones
+    let quiet = render_settled("out $ sine 440 * 0.05 # clip");
+    let dry = render_settled("out $ sine 440 * 0.05");
+    let ratio = calculate_rms(&quiet) / calculate_rms(&dry);
+    assert!((ratio - 1.0).abs() < 0.01, "clip ratio {}", ratio);
+    let loud = render_settled("out $ sine 440 * 10 # clip 0.5 :oversample 4");
+    assert!(find_peak(&loud) < 0.55, "clip peak {}", find_peak(&loud));
+}
+
+#[test]
+fn test_oversample_option_everywhere() {
+    for code in [
+        "out $ saw 110 # distortion 5 0.5 :oversample 2",
+        "out $ saw 110 # fold -0.5 0.5 :oversample 4",
+        "out $ saw 110 # clip 0.3 :oversample 2",
+        "out $ wrap (sine 110 * 2) -1 1 :oversample 4",
+        "out $ saw 110 # bitcrush 4 2 :oversample 4",
+    ] {
+        let out = compile_code(code)
+            .unwrap_or_else(|e| panic!("{}: {}", code, e))
+            .render(4410);
+        assert!(calculate_rms(&out) > 0.01, "{} is silent", code);
+    }
+
+    for code in [
+        "out $ saw 110 # distortion 5 :oversample 3",
+        "out $ saw 110 # fold :oversample 8",
+    ] {
+        let err = compile_code(code).err().expect(code);
+        assert!(err.contains(":oversample"), "{}", err);
+    }
+}
@@ -3,6 +3,7 @@
 //! Tests that expressions like (~lfo * 1000 + 500) work correctly
 
 use phonon::dsp_parameter::{BinaryOp, DspParameter, ParameterExpression, UnaryOp};
+#[allow(deprecated)]
 use phonon::glicol_parser_v2::parse_glicol_v2;
 use std::collections::HashMap;
 
@@ -200,6 +201,7 @@ fn test_unary_negation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parser_accepts_expressions() {
     println!("\n=== Testing Parser Acceptance of Expressions ===");
 
@@ -6,6 +6,7 @@
 //! remainder, every subsequent statement was also dropped, yielding a
 //! struct-with-no-source => total silence.
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 fn find_peak(buffer: &[f32]) -> f32 {
@@ -15,6 +16,7 @@ fn find_peak(buffer: &[f32]) -> f32 {
 /// Core case: `out $ struct "pat" $ sine "66"` must parse fully (no tail) and
 /// render audio.
 #[test]
+#[allow(deprecated)]
 fn test_struct_source_chaining_parses_and_renders() {
     let code = r#"out $ struct "t(3,8,1)" $ sine "66""#;
 
@@ -41,6 +43,7 @@ fn test_struct_source_chaining_parses_and_renders() {
 /// The silent-drop cascade: a struct-chain statement must NOT swallow the
 /// statements that follow it.
 #[test]
+#[allow(deprecated)]
 fn test_struct_chaining_does_not_drop_following_statements() {
     let code = r#"
         ~a $ struct "t(3,8,1)" $ sine "66"
@@ -73,6 +76,7 @@ fn test_struct_chaining_does_not_drop_following_statements() {
 
 /// Existing `$` transform chaining must keep working (no regression).
 #[test]
+#[allow(deprecated)]
 fn test_transform_chaining_still_works() {
     let code = r#"out $ s "bd sn" $ fast 2 $ rev"#;
 
@@ -7,9 +7,13 @@
 //! 4. Analyze audio chunks to confirm pattern cycling
 
 use phonon::dsp_parameter::DspParameter;
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
+#[allow(deprecated)]
 use phonon::glicol_parser_v2::parse_glicol_v2;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor_v2::SimpleDspExecutorV2;
 use std::f32::consts::PI;
 
@@ -75,6 +79,7 @@ fn get_spectral_centroid(samples: &[f32], sample_rate: f32) -> f32 {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_oscillator_frequency_pattern_modulation() {
     println!("\n=== E2E Test: Oscillator Frequency Pattern Modulation ===");
 
@@ -144,6 +149,7 @@ fn test_oscillator_frequency_pattern_modulation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_filter_cutoff_pattern_spectral_analysis() {
     println!("\n=== E2E Test: Filter Cutoff Pattern with Spectral Analysis ===");
 
@@ -244,6 +250,7 @@ fn test_pattern_with_fast_operator() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_complex_modulation_chain() {
     println!("\n=== E2E Test: Complex Modulation Chain ===");
 
@@ -306,6 +313,7 @@ fn test_complex_modulation_chain() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_arithmetic_expression_in_audio() {
     println!("\n=== E2E Test: Arithmetic Expression in Audio Generation ===");
 
@@ -340,6 +348,7 @@ fn test_arithmetic_expression_in_audio() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pattern_repetition_across_cycles() {
     println!("\n=== E2E Test: Pattern Repetition Across Cycles ===");
 
@@ -414,6 +423,7 @@ fn test_pattern_repetition_across_cycles() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_multiple_patterns_synchronized() {
     println!("\n=== E2E Test: Multiple Synchronized Patterns ===");
 
@@ -483,6 +493,7 @@ fn test_pattern_string_in_expression() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_audio_quality_metrics() {
     println!("\n=== E2E Test: Audio Quality Metrics ===");
 
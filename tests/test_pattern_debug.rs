@@ -87,6 +87,7 @@ fn test_oscillator_with_pattern_signal() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_how_dsl_compiles_pattern() {
     use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
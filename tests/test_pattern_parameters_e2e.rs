@@ -5,6 +5,7 @@
 //! and that we can observe the modulation in the generated audio.
 
 use phonon::dsp_parameter::DspParameter;
+#[allow(deprecated)]
 use phonon::glicol_parser_v2::parse_glicol_v2;
 use std::collections::HashMap;
 
@@ -333,6 +334,7 @@ fn test_pattern_cycle_repetition() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_parser_accepts_pattern_strings() {
     println!("\n=== Testing Parser Acceptance of Pattern Strings ===");
 
@@ -1,7 +1,9 @@
 /// FINAL VERIFICATION: Pattern parameters actually work
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_pattern_freq_cycles_verified() {
     // SuperSaw with pattern freq over full cycle
     let input = r#"out $ supersaw "110 220" 0.5 5 * 0.2"#;
@@ -1,4 +1,5 @@
 /// Verify if pattern parameters actually work or just fall back to defaults
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod audio_test_utils;
@@ -35,6 +36,7 @@ fn measure_fundamental_spread(buffer: &[f32], sample_rate: f32, base_freq: f32)
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_supersaw_detune_fundamental_frequency_distribution() {
     // Test detune using proper FFT analysis of fundamental frequency distribution
     // For a 220 Hz supersaw with 5 voices:
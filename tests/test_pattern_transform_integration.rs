@@ -3,12 +3,14 @@
 //! Tests verify that pattern transformations (`$` operator) work correctly
 //! when used in the unified DSL syntax.
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod audio_test_utils;
 use audio_test_utils::calculate_rms;
 
 #[test]
+#[allow(deprecated)]
 fn test_fast_transform_produces_audio() {
     // Test: $ fast 2 should double the pattern speed
     let input = r#"tempo: 0.5
@@ -35,6 +37,7 @@ out $ s "bd" $ fast 2"#;
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_slow_transform_syntax() {
     // Test: $ slow 2 should half the pattern speed
     let input = r#"
@@ -70,6 +73,7 @@ fn test_slow_transform_syntax() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_rev_transform_debug() {
     // Test: $ rev should reverse the pattern
     // CURRENT STATUS: Renders successfully but produces silence - needs investigation
@@ -101,6 +105,7 @@ fn test_rev_transform_debug() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_every_transform_produces_audio() {
     // Test: $ every 4 (fast 2) should apply fast 2 every 4th cycle
     let input = r#"
@@ -129,6 +134,7 @@ fn test_every_transform_produces_audio() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_chained_transforms() {
     // Test: Multiple transforms can be chained
     // NOTE: This is a known limitation - chained transforms (a $ f $ g) don't work yet
@@ -166,6 +172,7 @@ fn test_chained_transforms() {
 
 #[test]
 #[ignore = "UNIMPLEMENTED: pattern transforms on bus references"]
+#[allow(deprecated)]
 fn test_bus_reference_with_transform() {
     // Test: Bus references work with transforms
     // NOTE: This is a known limitation - pattern transforms on bus references (~drums $ fast 2) don't work yet
@@ -204,6 +211,7 @@ fn test_bus_reference_with_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_fast_actually_doubles_speed() {
     // Test with FFT/onset detection: Verify fast 2 actually doubles event count
     let input_normal = r#"
@@ -5,6 +5,7 @@
 
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -12,6 +13,7 @@ mod pattern_verification_utils;
 use pattern_verification_utils::detect_audio_events;
 
 /// Helper to compile and render DSL
+#[allow(deprecated)]
 fn compile_and_render(input: &str, duration_samples: usize) -> Vec<f32> {
     let (_, statements) = parse_dsl(input).expect("Failed to parse DSL");
     let compiler = DslCompiler::new(44100.0);
@@ -90,10 +90,12 @@ fn test_every_transform() {
 // Test that pattern transforms work through the DSL parser and compiler
 // ============================================================================
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 /// Test that fast transform works in DSL with frequency patterns
 #[test]
+#[allow(deprecated)]
 fn test_dsl_fast_transform() {
     let input = r#"
         tempo: 0.5
@@ -119,6 +121,7 @@ fn test_dsl_fast_transform() {
 
 /// Test that slow transform works in DSL
 #[test]
+#[allow(deprecated)]
 fn test_dsl_slow_transform() {
     let input = r#"
         tempo: 0.5
@@ -143,6 +146,7 @@ fn test_dsl_slow_transform() {
 
 /// Test that rev transform works in DSL
 #[test]
+#[allow(deprecated)]
 fn test_dsl_rev_transform() {
     let input = r#"
         tempo: 0.5
@@ -167,6 +171,7 @@ fn test_dsl_rev_transform() {
 
 /// Test chained transforms in DSL
 #[test]
+#[allow(deprecated)]
 fn test_dsl_chained_transforms() {
     let input = r#"
         tempo: 0.5
@@ -191,6 +196,7 @@ fn test_dsl_chained_transforms() {
 
 /// Test every transform in DSL
 #[test]
+#[allow(deprecated)]
 fn test_dsl_every_transform() {
     let input = r#"
         tempo: 1.0
@@ -216,6 +222,7 @@ fn test_dsl_every_transform() {
 
 /// Test fast transform with sample playback
 #[test]
+#[allow(deprecated)]
 fn test_dsl_fast_with_samples() {
     let input = r#"
         tempo: 0.5
@@ -240,6 +247,7 @@ fn test_dsl_fast_with_samples() {
 
 /// Test rev transform with sample playback
 #[test]
+#[allow(deprecated)]
 fn test_dsl_rev_with_samples() {
     let input = r#"
         tempo: 0.5
@@ -264,6 +272,7 @@ fn test_dsl_rev_with_samples() {
 
 /// Test pattern transform with filter modulation
 #[test]
+#[allow(deprecated)]
 fn test_dsl_transform_filter_modulation() {
     let input = r#"
         tempo: 0.5
@@ -288,6 +297,7 @@ fn test_dsl_transform_filter_modulation() {
 
 /// Test precedence: $ transform should bind tighter than *
 #[test]
+#[allow(deprecated)]
 fn test_dsl_transform_precedence() {
     // This should parse as: (sine "110 220" $ fast 2) * 0.5
     let input = r#"
@@ -29,7 +29,7 @@
 /// - **Pattern query tests**: ✅ PASS
 /// - **Offset handling**: ✅ PASS (verified offset shifts pattern)
 /// - **Different modulos**: ✅ PASS
-/// - **Status**: Fully functional, generates values when (cycle - offset) % modulo == 0
+/// - **Status**: Fully functional, generates values when cycle % modulo >= offset
 ///
 /// ## ❌ BROKEN FUNCTIONS (3/7 - 43%)
 ///
@@ -440,13 +440,13 @@ fn test_sometimes_by_val_low_probability() {
 // ============================================================================
 // whenmod_val - Output different values based on cycle modulo with offset
 // whenmod_val(modulo, offset, on_val, off_val)
-// outputs on_val when (cycle - offset) % modulo == 0
+// outputs on_val when cycle % modulo >= offset (the cycles whenmod transforms)
 // ============================================================================
 
 #[test]
 fn test_whenmod_val_level1_pattern_query() {
-    // Test whenmod_val(3, 0, 1000, 500) - every 3rd cycle starting at 0
-    let pattern = Pattern::<f64>::whenmod_val(3, 0, 1000.0, 500.0);
+    // Test whenmod_val(3, 2, 1000, 500) - the last cycle of every 3
+    let pattern = Pattern::<f64>::whenmod_val(3, 2, 1000.0, 500.0);
 
     let mut values = Vec::new();
     for cycle in 0..9 {
@@ -462,16 +462,16 @@ fn test_whenmod_val_level1_pattern_query() {
         values.push(haps[0].value);
     }
 
-    // Pattern: 1000 on cycles 0,3,6; 500 on all others
+    // Pattern: 1000 on cycles 2,5,8; 500 on all others
     assert_eq!(
         values,
-        vec![1000.0, 500.0, 500.0, 1000.0, 500.0, 500.0, 1000.0, 500.0, 500.0]
+        vec![500.0, 500.0, 1000.0, 500.0, 500.0, 1000.0, 500.0, 500.0, 1000.0]
     );
 }
 
 #[test]
 fn test_whenmod_val_with_offset() {
-    // Test whenmod_val(3, 1, 1000, 500) - every 3rd cycle starting at offset 1
+    // Test whenmod_val(3, 1, 1000, 500) - all but the first cycle of every 3
     let pattern = Pattern::<f64>::whenmod_val(3, 1, 1000.0, 500.0);
 
     let mut values = Vec::new();
@@ -488,16 +488,16 @@ fn test_whenmod_val_with_offset() {
         values.push(haps[0].value);
     }
 
-    // (cycle - 1) % 3 == 0 when cycle = 1,4,7
+    // cycle % 3 >= 1 when cycle = 1,2,4,5,7,8
     assert_eq!(
         values,
-        vec![500.0, 1000.0, 500.0, 500.0, 1000.0, 500.0, 500.0, 1000.0, 500.0]
+        vec![500.0, 1000.0, 1000.0, 500.0, 1000.0, 1000.0, 500.0, 1000.0, 1000.0]
     );
 }
 
 #[test]
 fn test_whenmod_val_different_modulos() {
-    // Test whenmod_val(4, 2, 1000, 500) - every 4th cycle with offset 2
+    // Test whenmod_val(4, 2, 1000, 500) - the last two cycles of every 4
     let pattern = Pattern::<f64>::whenmod_val(4, 2, 1000.0, 500.0);
 
     let mut values = Vec::new();
@@ -513,9 +513,9 @@ fn test_whenmod_val_different_modulos() {
         values.push(pattern.query(&state)[0].value);
     }
 
-    // (cycle - 2) % 4 == 0 when cycle = 2,6,10
+    // cycle % 4 >= 2 when cycle = 2,3,6,7,10,11
     let expected = vec![
-        500.0, 500.0, 1000.0, 500.0, 500.0, 500.0, 1000.0, 500.0, 500.0, 500.0, 1000.0, 500.0,
+        500.0, 500.0, 1000.0, 1000.0, 500.0, 500.0, 1000.0, 1000.0, 500.0, 500.0, 1000.0, 1000.0,
     ];
     assert_eq!(values, expected);
 }
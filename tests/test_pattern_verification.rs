@@ -2,9 +2,11 @@ mod pattern_verification_utils;
 
 use pattern_verification_utils::{compare_events, detect_audio_events, get_expected_events};
 use phonon::mini_notation_v3::parse_mini_notation;
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_basic_pattern_verification() {
     // Test that a simple pattern produces events at expected times
     let input = r#"
@@ -62,6 +64,7 @@ fn test_basic_pattern_verification() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_fast_transform_verification() {
     // Test that fast(2) doubles the event rate
     let input_normal = r#"
@@ -104,6 +107,7 @@ fn test_fast_transform_verification() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_slow_transform_verification() {
     // Test that slow(2) halves the event rate
     let input_normal = r#"
@@ -145,6 +149,7 @@ fn test_slow_transform_verification() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_degrade_transform_verification() {
     // Test that degrade drops approximately 50% of events
     let input_normal = r#"
@@ -187,6 +192,7 @@ fn test_degrade_transform_verification() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_stutter_transform_verification() {
     // Test that stutter(3) triples the event count
     let input_normal = r#"
@@ -277,6 +283,7 @@ fn test_rev_transform_verification() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_combined_transforms_verification() {
     // Test that combining transforms works correctly: fast 2 then rev
     let input = r#"
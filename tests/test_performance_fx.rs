@@ -0,0 +1,114 @@
+/// Tests for the `tapestop`, `stutter` and `beatrepeat` performance effects
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code, render_code};
+
+fn zero_crossings(buffer: &[f32]) -> usize {
+    buffer
+        .windows(2)
+        .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+        .count()
+}
+
+#[test]
+fn test_tapestop_winds_down() {
+    // One cycle per second; the stop takes the second half
+    let dry = render_code("tempo: 1.0\nout $ sine 440 * 0.3", 44100);
+    let wet = render_code("tempo: 1.0\nout $ sine 440 * 0.3 # tapestop \"~ t\"", 44100);
+
+    // Untouched while the pattern is off
+    for i in 0..22000 {
+        assert!((dry[i] - wet[i]).abs() < 1e-4, "sample {}: {} vs {}", i, dry[i], wet[i]);
+    }
+
+    // The pitch drops: fewer crossings in each successive stretch
+    let early = zero_crossings(&wet[22050..27562]);
+    let late = zero_crossings(&wet[33075..38587]);
+    assert!(early < zero_crossings(&dry[22050..27562]));
+    assert!(late * 3 < early * 2, "early {} late {}", early, late);
+
+    // And it ends in silence
+    assert!(calculate_rms(&wet[44000..44100]) < 0.01);
+}
+
+#[test]
+fn test_stutter_repeats_a_slice() {
+    // A 3 Hz sine doesn't repeat every quarter cycle by itself
+    let wet = render_code("tempo: 1.0\nout $ sine 3 * 0.3 # stutter 4 \"1\"", 44100);
+    let slice = 11025;
+    for i in (slice + 500..2 * slice - 500).step_by(50) {
+        assert!(
+            (wet[i] - wet[i + slice]).abs() < 1e-3,
+            "sample {}: {} vs {}",
+            i,
+            wet[i],
+            wet[i + slice]
+        );
+        assert!((wet[i] - wet[i - slice]).abs() < 1e-3);
+    }
+
+    // Off steps pass the input through
+    let dry = render_code("tempo: 1.0\nout $ sine 3 * 0.3", 44100);
+    let off = render_code(
+        "tempo: 1.0\nout $ sine 3 * 0.3 # stutter 16 \"0 0 1 0\"",
+        44100,
+    );
+    for i in 0..22000 {
+        assert!((dry[i] - off[i]).abs() < 1e-4);
+    }
+    // The third step loops its first sixteenth of a cycle
+    assert!((off[25000] - dry[25000]).abs() > 1e-2);
+    assert!((off[25000] - off[25000 - 2756]).abs() < 1e-3);
+}
+
+#[test]
+fn test_beatrepeat_repeats_the_slice_before() {
+    let dry = render_code("tempo: 1.0\nout $ sine 3 * 0.3", 44100);
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+
+    // An eighth of a cycle (5512.5 samples) before the second half, twice
+    let wet = render_code(
+        "tempo: 1.0\nout $ sine 3 * 0.3 # beatrepeat \"~ 8\" :decay 0.5",
+        44100,
+    );
+    for k in (500..5000).step_by(50) {
+        assert!(
+            close(wet[22050 + k], dry[16538 + k]),
+            "sample {}",
+            22050 + k
+        );
+        assert!(
+            close(wet[27563 + k], 0.5 * dry[16538 + k]),
+            "sample {}",
+            27563 + k
+        );
+    }
+    for i in 0..22000 {
+        assert!(close(wet[i], dry[i]));
+    }
+
+    // An octave up plays the slice twice per repeat
+    let wet = render_code(
+        "tempo: 1.0\nout $ sine 3 * 0.3 # beatrepeat \"~ 8\" :pitch 12st",
+        44100,
+    );
+    for k in (500..2200).step_by(50) {
+        assert!(
+            close(wet[22050 + k], dry[16538 + 2 * k]),
+            "sample {}",
+            22050 + k
+        );
+    }
+}
+
+#[test]
+fn test_performance_fx_errors() {
+    for code in [
+        "out $ sine 440 # tapestop 3",
+        "out $ sine 440 # stutter 0 \"1\"",
+        "out $ sine 440 # beatrepeat 8",
+        "out $ sine 440 # beatrepeat \"8\" :decay 2",
+        "out $ sine 440 # beatrepeat \"8\" :pitch 10",
+    ] {
+        assert!(compile_code(code).is_err(), "{}", code);
+    }
+}
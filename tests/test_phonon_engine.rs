@@ -0,0 +1,116 @@
+/// Tests for the embedding API (`PhononEngine::builder`)
+use phonon::phonon_engine::PhononEngine;
+use phonon::unified_graph::SignalNode;
+
+mod audio_test_utils;
+use audio_test_utils::find_peak;
+
+const SINE: &str = "tempo: 0.5\nout $ sine 440 * 0.2";
+
+#[test]
+fn test_render_is_interleaved_stereo_by_default() {
+    let mut engine = PhononEngine::builder()
+        .sample_rate(48000)
+        .code(SINE)
+        .build()
+        .unwrap();
+    assert_eq!(engine.config().sample_rate, 48000.0);
+    assert_eq!(engine.config().channels, 2);
+    // Not a whole number of blocks
+    let audio = engine.render(1000);
+    assert_eq!(audio.len(), 2000);
+    assert!(find_peak(&audio) > 0.1);
+}
+
+#[test]
+fn test_mono_is_the_left_channel() {
+    let build = |channels| {
+        PhononEngine::builder()
+            .channels(channels)
+            .block_size(128)
+            .code(SINE)
+            .build()
+            .unwrap()
+    };
+    let stereo = build(2).render(4410);
+    let mono = build(1).render(4410);
+    assert_eq!(mono.len(), 4410);
+    let left: Vec<f32> = stereo.iter().step_by(2).copied().collect();
+    assert_eq!(mono, left);
+}
+
+#[test]
+fn test_render_cycles_follows_the_tempo() {
+    let mut engine = PhononEngine::builder()
+        .channels(1)
+        .code(SINE)
+        .build()
+        .unwrap();
+    // 0.5 cps: a cycle is two seconds
+    assert_eq!(engine.render_cycles(1.0).len(), 88200);
+    assert_eq!(engine.render_seconds(0.5).len(), 22050);
+}
+
+#[test]
+fn test_bad_settings_are_errors() {
+    let err = PhononEngine::builder().channels(6).build().err().unwrap();
+    assert!(err.contains("channels"), "{}", err);
+    let err = PhononEngine::builder()
+        .block_size(16)
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.contains("block size"), "{}", err);
+}
+
+#[test]
+fn test_statements_that_dont_parse_are_skipped() {
+    let engine = PhononEngine::builder()
+        .code("tempo: 0.5\nout $ sine 440 * 0.2\n~bad $ $ $\n")
+        .build()
+        .unwrap();
+    assert_eq!(engine.skipped().len(), 1);
+    assert!(PhononEngine::builder().code("$ $ $").build().is_err());
+}
+
+#[test]
+fn test_load_replaces_the_program_and_keeps_the_cycle() {
+    let mut engine = PhononEngine::builder()
+        .channels(1)
+        .code(SINE)
+        .build()
+        .unwrap();
+    engine.render_cycles(1.0);
+    let position = engine.graph().get_cycle_position();
+
+    engine.load("tempo: 0.5\nout $ sine 220 * 0.0").unwrap();
+    assert!((engine.graph().get_cycle_position() - position).abs() < 1e-9);
+    assert!(find_peak(&engine.render(4410)) < 1e-3);
+
+    // A program that fails leaves the current one playing
+    assert!(engine.load("out $ nosuchfunction 1").is_err());
+    assert!(engine.graph().get_cycle_position() > position);
+}
+
+#[test]
+fn test_load_keeps_unchanged_buses_playing() {
+    let code = "tempo: 0.5\n~lead $ sine 220\nout $ ~lead * 0.2";
+    let mut engine = PhononEngine::builder()
+        .channels(1)
+        .code(code)
+        .build()
+        .unwrap();
+    let lead_phase = |engine: &PhononEngine| {
+        let id = engine.graph().get_bus("lead").unwrap();
+        match engine.graph().nodes[id.0].as_deref() {
+            Some(SignalNode::Oscillator { phase, .. }) => *phase.borrow(),
+            other => panic!("~lead should be an oscillator, got {:?}", other),
+        }
+    };
+    engine.render(1000);
+    let phase = lead_phase(&engine);
+    assert!(phase > 0.0);
+
+    engine.load(&format!("{}\n~pad $ saw 55", code)).unwrap();
+    assert_eq!(lead_phase(&engine), phase);
+}
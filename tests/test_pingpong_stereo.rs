@@ -0,0 +1,74 @@
+/// Tests for the stereo ping-pong delay: echoes alternate sides, times in
+/// cycles follow the tempo and `:filter` darkens each repeat
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+
+fn render_stereo(code: &str, samples: usize) -> (Vec<f32>, Vec<f32>) {
+    let (rest, statements) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    let mut graph = compile_program(statements, 44100.0, None).expect("Failed to compile");
+    graph.render_stereo(samples)
+}
+
+/// Sample indices where the signal jumps above `threshold`
+fn onsets(buffer: &[f32], threshold: f32) -> Vec<usize> {
+    (1..buffer.len())
+        .filter(|&i| buffer[i].abs() > threshold && buffer[i - 1].abs() <= threshold)
+        .collect()
+}
+
+fn stereo_pingpong(tempo: f64, extra: &str) -> (Vec<f32>, Vec<f32>) {
+    let code = format!(
+        "tempo: {}\n~click $ impulse 0.5 * 0.5\nout1 $ ~click # pingpong 1/8c 0.5 :width 1 :channel 0 :mix 1 {}\nout2 $ ~click # pingpong 1/8c 0.5 :width 1 :channel 1 :mix 1 {}",
+        tempo, extra, extra
+    );
+    render_stereo(&code, 44100)
+}
+
+fn near(a: usize, b: usize) -> bool {
+    a.abs_diff(b) <= 2
+}
+
+#[test]
+fn test_pingpong_alternates_sides() {
+    // An eighth of a cycle at one cycle per second is 5512 samples: left,
+    // right, left, each repeat half as loud as the last
+    let (left, right) = stereo_pingpong(1.0, "");
+    let left_echoes = onsets(&left, 0.05);
+    let right_echoes = onsets(&right, 0.05);
+    assert!(
+        near(left_echoes[0], 5512),
+        "left echoes at {:?}",
+        left_echoes
+    );
+    assert!(
+        near(left_echoes[1], 16537),
+        "left echoes at {:?}",
+        left_echoes
+    );
+    assert!(
+        near(right_echoes[0], 11025),
+        "right echoes at {:?}",
+        right_echoes
+    );
+
+    let peak = |b: &[f32], at: usize| b[at - 5..at + 5].iter().fold(0.0f32, |m, x| m.max(x.abs()));
+    assert!((peak(&left, 5512) - 0.5).abs() < 0.02);
+    assert!((peak(&right, 11025) - 0.25).abs() < 0.02);
+    assert!((peak(&left, 16537) - 0.125).abs() < 0.02);
+}
+
+#[test]
+fn test_pingpong_time_follows_tempo() {
+    let (left, _) = stereo_pingpong(2.0, "");
+    let echoes = onsets(&left, 0.05);
+    assert!(near(echoes[0], 2756), "left echoes at {:?}", echoes);
+}
+
+#[test]
+fn test_pingpong_filter_darkens_repeats() {
+    let (_, open) = stereo_pingpong(1.0, "");
+    let (_, dark) = stereo_pingpong(1.0, ":filter 500");
+    let peak = |b: &[f32]| b[10000..12000].iter().fold(0.0f32, |m, x| m.max(x.abs()));
+    assert!(peak(&dark) < peak(&open) * 0.5);
+}
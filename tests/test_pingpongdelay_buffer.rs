@@ -103,7 +103,6 @@ fn test_pingpong_zero_width() {
 }
 
 #[test]
-#[ignore = "BUG: full width with 100% wet and mono output creates edge case"]
 fn test_pingpong_full_width() {
     // Test full stereo width: strong ping-pong effect
     let mut graph = create_graph();
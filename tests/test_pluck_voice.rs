@@ -0,0 +1,60 @@
+/// Tests for the Karplus-Strong voice: `s "pluck" # exciter ... # decay ...`
+mod audio_test_utils;
+use audio_test_utils::{compile_code, find_peak, render_code};
+
+fn upward_crossings(samples: &[f32]) -> usize {
+    samples
+        .windows(2)
+        .filter(|w| w[0] <= 0.0 && w[1] > 0.0)
+        .count()
+}
+
+#[test]
+fn test_pluck_retriggers_at_each_notes_pitch() {
+    // One note per cycle, c3 then c4: the picked string's fundamental
+    // doubles, counted in the second half of each note
+    let audio = render_code(
+        "tempo: 1.0\nout $ s \"pluck\" # note \"<c3 c4>\" # exciter \"pick\" # damping 1",
+        88200,
+    );
+    assert!(audio.iter().all(|s| s.is_finite()));
+    let low = upward_crossings(&audio[22050..44100]);
+    let high = upward_crossings(&audio[66150..88200]);
+    assert!((2 * low).abs_diff(high) <= 4, "{} vs {}", low, high);
+}
+
+#[test]
+fn test_exciter_and_decay_per_event() {
+    // Bowed notes swell in; picked ones start at full strength
+    let onset = |code: &str| {
+        let audio = render_code(code, 22050);
+        find_peak(&audio[..441]) / find_peak(&audio)
+    };
+    let picked = onset("tempo: 1.0\nout $ s \"pluck\" # exciter \"pick\"");
+    let bowed = onset("tempo: 1.0\nout $ s \"pluck\" # exciter \"bow\"");
+    assert!(picked > 0.8, "{}", picked);
+    assert!(bowed < 0.5, "{}", bowed);
+
+    // A short decay has died away by the second half of the cycle
+    let tail = |decay: &str| {
+        let audio = render_code(
+            &format!("tempo: 1.0\nout $ s \"pluck\" # decay {}", decay),
+            44100,
+        );
+        find_peak(&audio[22050..])
+    };
+    assert!(tail("0.05") < 0.01, "{}", tail("0.05"));
+    assert!(tail("0.9") > 0.05, "{}", tail("0.9"));
+}
+
+#[test]
+fn test_exciter_names_are_checked() {
+    assert!(compile_code("out $ s \"pluck*4\" # exciter \"noise|pick|bow\"").is_ok());
+    let err = compile_code("out $ s \"pluck*4\" # exciter \"noise strum\"")
+        .err()
+        .unwrap();
+    assert!(err.contains("strum"), "{}", err);
+
+    let err = compile_code("out $ saw 110 # decay 0.5").err().unwrap();
+    assert!(err.contains("pluck"), "{}", err);
+}
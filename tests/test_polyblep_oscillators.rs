@@ -0,0 +1,81 @@
+/// Spectral tests for the band-limited (polyBLEP) `saw` and `square`
+/// oscillators and their `:raw true` naive versions
+mod audio_test_utils;
+use audio_test_utils::{compile_code, magnitude_at};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// (fundamental, total alias) amplitudes of a 1kHz oscillator. Its
+/// harmonics sit on whole kHz, while the ones above Nyquist fold back to
+/// x.1kHz (44kHz -> 100Hz, 43kHz -> 1.1kHz, ...)
+fn spectrum(code: &str) -> (f32, f32) {
+    let out = compile_code(code)
+        .expect("Failed to compile")
+        .render(SAMPLE_RATE as usize);
+    let alias = [100.0, 1100.0, 2100.0, 3100.0, 5100.0]
+        .iter()
+        .map(|freq| magnitude_at(&out, SAMPLE_RATE, *freq))
+        .sum();
+    (magnitude_at(&out, SAMPLE_RATE, 1000.0), alias)
+}
+
+#[test]
+fn test_saw_and_square_are_band_limited() {
+    for osc in ["saw", "square"] {
+        let (fundamental, alias) = spectrum(&format!("out $ {} 1000 * 0.5", osc));
+        let (raw_fundamental, raw_alias) = spectrum(&format!("out $ {} 1000 :raw true * 0.5", osc));
+
+        // Same tone...
+        assert!(
+            (fundamental / raw_fundamental - 1.0).abs() < 0.05,
+            "{}: fundamental {} vs raw {}",
+            osc,
+            fundamental,
+            raw_fundamental
+        );
+        // ...with the folded-back harmonics suppressed
+        assert!(raw_alias > 0.01, "{}: raw alias {}", osc, raw_alias);
+        assert!(
+            alias < raw_alias * 0.1,
+            "{}: alias {} vs raw {}",
+            osc,
+            alias,
+            raw_alias
+        );
+    }
+}
+
+#[test]
+fn test_raw_option() {
+    // Sine and triangle have no steps to correct, so :raw changes nothing
+    for osc in ["sine", "tri"] {
+        let smooth = compile_code(&format!("out $ {} 220 * 0.5", osc))
+            .unwrap()
+            .render(2048);
+        let raw = compile_code(&format!("out $ {} 220 :raw true * 0.5", osc))
+            .unwrap()
+            .render(2048);
+        assert_eq!(smooth, raw, "{}", osc);
+    }
+
+    // The naive saw is a plain ramp right up to its reset, where the
+    // band-limited one is rounded off
+    let steps = |code: &str| {
+        let out = compile_code(code).unwrap().render(120);
+        out.windows(2).map(|w| w[1] - w[0]).collect::<Vec<f32>>()
+    };
+    let raw = steps("out $ saw 441 :raw 1 * 0.5");
+    let step = raw[0];
+    assert!(
+        raw[..98].iter().all(|s| (s - step).abs() < 1e-4),
+        "{:?}",
+        raw
+    );
+    assert!(raw[99] < -90.0 * step, "no reset: {:?}", &raw[95..105]);
+    let smooth = steps("out $ saw 441 * 0.5");
+    assert!(smooth[99] > raw[99] * 0.75, "{:?}", &smooth[95..105]);
+    assert!(compile_code("out $ saw 441 :raw false").is_ok());
+
+    let err = compile_code("out $ saw 441 :raw \"yes\"").err().unwrap();
+    assert!(err.contains(":raw"), "{}", err);
+}
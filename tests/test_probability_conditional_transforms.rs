@@ -5,7 +5,7 @@
 // - almostAlways: apply with 90% probability
 // - almostNever: apply with 10% probability
 // - always: always apply (100% probability)
-// - whenmod: apply when (cycle - offset) % modulo == 0
+// - whenmod: apply when cycle % modulo >= offset (the last cycles of each phrase)
 
 use phonon::compositional_compiler::compile_program;
 use phonon::compositional_parser::parse_program;
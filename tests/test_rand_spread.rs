@@ -0,0 +1,74 @@
+/// Tests for per-trigger random parameters: `# gain (0.5 ~ 1.0)`,
+/// `# speed (range 0.98 1.02 rand)`
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::sample_loader::set_extra_sample_dirs;
+
+/// A sample bank with one short constant "click" sample
+fn click_bank() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let bank = dir.path().join("spreadclick");
+    std::fs::create_dir(&bank).unwrap();
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 44100,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(bank.join("0.wav"), spec).unwrap();
+    for _ in 0..200 {
+        writer.write_sample(0.5f32).unwrap();
+    }
+    writer.finalize().unwrap();
+    dir
+}
+
+/// Peak of each of the 4 events in one cycle
+fn event_peaks(code: &str) -> Vec<f32> {
+    let (rest, stmts) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    let mut graph = compile_program(stmts, 44100.0, None).expect("Failed to compile");
+    let audio = graph.render(44100);
+    audio
+        .chunks(11025)
+        .map(|quarter| quarter.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+        .collect()
+}
+
+#[test]
+fn test_spread_draws_per_trigger() {
+    let dir = click_bank();
+    set_extra_sample_dirs(vec![dir.path().to_path_buf()]);
+
+    let fixed = event_peaks("tempo: 1.0\nout $ s \"spreadclick*4\" # gain 0.75");
+    assert!(fixed
+        .iter()
+        .all(|p| (p - fixed[0]).abs() < 1e-6 && *p > 0.0));
+    // Peak per unit of gain
+    let unit = fixed[0] / 0.75;
+
+    for code in [
+        "tempo: 1.0\nout $ s \"spreadclick*4\" # gain (0.5 ~ 1.0)",
+        "tempo: 1.0\nout $ s \"spreadclick*4\" # gain (range 0.5 1.0 rand)",
+    ] {
+        let peaks = event_peaks(code);
+        assert_eq!(peaks.len(), 4);
+        for peak in &peaks {
+            let gain = peak / unit;
+            assert!(
+                (0.5 - 1e-4..=1.0 + 1e-4).contains(&gain),
+                "{}: {}",
+                code,
+                gain
+            );
+        }
+        // Each hit gets its own value...
+        assert!(
+            peaks.windows(2).any(|w| (w[0] - w[1]).abs() > 1e-4),
+            "{:?}",
+            peaks
+        );
+        // ...and the same ones on every render
+        assert_eq!(peaks, event_peaks(code));
+    }
+}
@@ -0,0 +1,114 @@
+/// Tests for the pattern-synced stepped modulators: `randstep` and
+/// sample-and-hold (`sah`) with mini-notation triggers
+mod audio_test_utils;
+use audio_test_utils::compile_code_at;
+
+/// 48kHz at one cycle per second gives whole-sample step boundaries
+const SAMPLE_RATE: f32 = 48000.0;
+
+fn render(code: &str, samples: usize) -> Vec<f32> {
+    let mut graph = compile_code_at(code, SAMPLE_RATE).expect("Failed to compile");
+    graph.set_master_limiter_ceiling(1.0);
+    graph.render(samples)
+}
+
+/// Splits `out` into `steps` per cycle, checks each step holds a single
+/// value away from its edges and returns those values
+fn step_values(out: &[f32], steps: usize) -> Vec<f32> {
+    let len = SAMPLE_RATE as usize / steps;
+    out.chunks(len)
+        .map(|step| {
+            let inner = &step[4..len - 4];
+            for sample in inner {
+                assert!(
+                    (sample - inner[0]).abs() < 1e-6,
+                    "value moved within a step: {} vs {}",
+                    sample,
+                    inner[0]
+                );
+            }
+            inner[0]
+        })
+        .collect()
+}
+
+fn distinct(values: &[f32]) -> usize {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted.dedup();
+    sorted.len()
+}
+
+#[test]
+fn test_randstep_steps_per_cycle() {
+    let out = render("tempo: 1.0\nout $ randstep \"8\"", 2 * SAMPLE_RATE as usize);
+    let values = step_values(&out, 8);
+    assert_eq!(values.len(), 16);
+    assert!(
+        values.iter().all(|v| (0.0..1.0).contains(v)),
+        "{:?}",
+        values
+    );
+    assert!(distinct(&values[..8]) >= 6, "{:?}", values);
+    // A new cycle brings new values
+    assert_ne!(values[..8], values[8..]);
+
+    // Patterned step counts
+    let out = render(
+        "tempo: 1.0\nout $ randstep \"<4 8>\"",
+        2 * SAMPLE_RATE as usize,
+    );
+    let (first, second) = out.split_at(SAMPLE_RATE as usize);
+    assert!(distinct(&step_values(first, 4)) >= 3);
+    assert!(distinct(&step_values(second, 8)) >= 6);
+}
+
+#[test]
+fn test_randstep_is_deterministic() {
+    let code = "tempo: 1.0\nout $ randstep 8";
+    let a = render(code, SAMPLE_RATE as usize);
+    let b = render(code, SAMPLE_RATE as usize);
+    assert_eq!(step_values(&a, 8), step_values(&b, 8));
+}
+
+#[test]
+fn test_randstep_range() {
+    let out = render(
+        "tempo: 1.0\nout $ (randstep 4 200 400) * 0.001",
+        2 * SAMPLE_RATE as usize,
+    );
+    let values = step_values(&out, 4);
+    assert!(
+        values.iter().all(|v| (0.2..=0.4).contains(v)),
+        "{:?}",
+        values
+    );
+
+    // As a control signal
+    assert!(compile_code_at("out $ saw 55 # lpf (randstep 8 300 3000) 0.7", SAMPLE_RATE).is_ok());
+    assert!(compile_code_at("out $ randstep 8 1", SAMPLE_RATE).is_err());
+}
+
+#[test]
+fn test_sah_with_pattern_trigger() {
+    // A rising saw held on each quarter: one value per step, rising
+    for code in [
+        "tempo: 1.0\nout $ saw 1 # sah \"t*4\"",
+        "tempo: 1.0\nout $ sample_hold (saw 1) \"t*4\"",
+    ] {
+        let values = step_values(&render(code, SAMPLE_RATE as usize), 4);
+        assert!(
+            values.windows(2).skip(1).all(|w| w[1] > w[0]),
+            "{}: {:?}",
+            code,
+            values
+        );
+    }
+
+    assert!(compile_code_at("out $ white_noise # sah \"t(3,8)\"", SAMPLE_RATE).is_ok());
+    assert!(compile_code_at(
+        "out $ white_noise # sample_and_hold (square 4)",
+        SAMPLE_RATE
+    )
+    .is_ok());
+}
@@ -0,0 +1,42 @@
+/// Tests for `record` statements: named MIDI takes played back as `%name`
+///
+/// Takes are kept process-wide, so everything runs in one test
+use phonon::compositional_compiler::take_requests;
+use phonon::compositional_parser::parse_program;
+use phonon::midi_input::store_take;
+
+mod audio_test_utils;
+use audio_test_utils::{compile_code, render_unlimited};
+
+#[test]
+fn test_record_takes() {
+    // Not recorded and no record statement: still an error
+    assert!(compile_code("out $ sine %riff1 * 0.5").is_err());
+
+    // Armed but not captured yet: the take is silent
+    let pending = render_unlimited(
+        "record ~midi 2c -> \"riff1\"\nout $ sine %riff1 * 0.5",
+        44100,
+    );
+    assert_eq!(pending, render_unlimited("out $ sine \"~\" * 0.5", 44100));
+
+    // Once captured the take plays, and transforms like any pattern
+    store_take("riff1", "<[c4 ~ [e4,g4] ~] [~ d4 ~ ~]>".to_string());
+    let code = "tempo: 1.0\nout $ sine (%riff1 $ fast 2) * 0.5";
+    let reference = "tempo: 1.0\nout $ sine (\"<[c4 ~ [e4,g4] ~] [~ d4 ~ ~]>\" $ fast 2) * 0.5";
+    assert_eq!(
+        render_unlimited(code, 44100),
+        render_unlimited(reference, 44100)
+    );
+
+    let (_, statements) =
+        parse_program("~keys $ saw ~midi2 # lpf 800 0.5\nrecord ~keys 4c -> \"riff2\"").unwrap();
+    let requests = take_requests(&statements);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].channel, Some(1));
+    assert_eq!(requests[0].cycles, 4);
+
+    assert!(compile_code("record ~nobus 2c -> \"x\"\nout $ sine 440").is_err());
+    assert!(compile_code("record ~midi 1.5c -> \"x\"\nout $ sine 440").is_err());
+    assert!(compile_code("record ~midi 1c -> \"x\"\nout $ sine 440").is_ok());
+}
@@ -0,0 +1,130 @@
+/// Tests for `assert` statements: audio unit tests inside the DSL
+///
+/// Assertions record their target while the program renders and are checked
+/// with `UnifiedSignalGraph::check_assertions` (what `phonon test` reports).
+/// Only `compile_program_with_assertions` records them; live graphs skip them.
+use phonon::compositional_compiler::{compile_program, compile_program_with_assertions};
+use phonon::compositional_parser::{parse_program, Statement};
+use phonon::unified_graph::UnifiedSignalGraph;
+
+fn compile_code(code: &str) -> Result<UnifiedSignalGraph, String> {
+    let (rest, stmts) = parse_program(code).map_err(|e| format!("Parse error: {}", e))?;
+    if !rest.trim().is_empty() {
+        return Err(format!("Parser did not consume all input: {:?}", rest));
+    }
+    compile_program_with_assertions(stmts, 44100.0)
+}
+
+/// (description, passed) for each assertion after rendering `seconds`
+fn run_assertions(code: &str, seconds: f32) -> Vec<(String, bool)> {
+    let mut graph = compile_code(code).expect("Should compile");
+    graph.render((44100.0 * seconds) as usize);
+    graph
+        .check_assertions()
+        .into_iter()
+        .map(|o| (o.description, o.passed))
+        .collect()
+}
+
+#[test]
+fn test_passing_assertions() {
+    let results = run_assertions(
+        r#"
+tempo: 1.0
+~tone $ sine 220 * 0.5
+out $ ~tone * 0.5
+assert rms(~tone) in 0.3..0.4
+assert pitch(~tone) in 215..225
+assert max_db(out) < -5.0
+assert peak(out) <= 0.26
+"#,
+        1.0,
+    );
+    assert_eq!(results.len(), 4);
+    for (description, passed) in results {
+        assert!(passed, "{} should pass", description);
+    }
+}
+
+#[test]
+fn test_failing_assertion_reports_value() {
+    let mut graph = compile_code(
+        r#"
+~tone $ sine 220 * 0.5
+out $ ~tone
+assert rms(~tone) > 0.9
+"#,
+    )
+    .unwrap();
+    graph.render(22050);
+
+    let outcomes = graph.check_assertions();
+    assert_eq!(outcomes[0].description, "rms(~tone) > 0.9");
+    assert!(!outcomes[0].passed);
+    let value = outcomes[0].value.expect("tone was rendered");
+    assert!((value - 0.354).abs() < 0.01, "rms {}", value);
+}
+
+#[test]
+fn test_assert_before_bus_definition() {
+    let results = run_assertions(
+        r#"
+assert rms(~bass) > 0.1
+~bass $ saw 55 * 0.5
+out $ ~bass
+"#,
+        0.5,
+    );
+    assert_eq!(results, vec![("rms(~bass) > 0.1".to_string(), true)]);
+}
+
+#[test]
+fn test_onset_count() {
+    let results = run_assertions(
+        r#"
+tempo: 1.0
+~drums $ s "bd*4"
+out $ ~drums
+assert onset_count(~drums) in 3..5
+"#,
+        1.0,
+    );
+    assert!(results[0].1, "expected ~4 onsets");
+}
+
+#[test]
+fn test_assertions_do_not_change_output() {
+    let plain = "~tone $ saw 110\nout $ ~tone * 0.3";
+    let asserted = format!("{}\nassert rms(~tone) > 0\nassert max_db(out) < 0", plain);
+    let a = compile_code(plain).unwrap().render(4410);
+    let b = compile_code(&asserted).unwrap().render(4410);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_invalid_assertions_are_compile_errors() {
+    // Unknown metric
+    assert!(compile_code("out $ sine 440\nassert loudness(out) < 0").is_err());
+    // Unknown bus
+    assert!(compile_code("out $ sine 440\nassert rms(~missing) > 0").is_err());
+}
+
+#[test]
+fn test_assert_after_a_call_line_is_its_own_statement() {
+    let (rest, statements) =
+        parse_program("~kick $ s \"bd*4\"\nassert rms(~kick) in 0.1..0.4").unwrap();
+    assert!(rest.trim().is_empty(), "left {:?}", rest);
+    assert_eq!(statements.len(), 2);
+    assert!(matches!(
+        &statements[1],
+        Statement::Assert { target: Some(bus), .. } if bus == "kick"
+    ));
+}
+
+#[test]
+fn test_live_compiles_skip_assertions() {
+    let (_, statements) =
+        parse_program("~tone $ sine 220\nout $ ~tone\nassert rms(~tone) > 0.1").unwrap();
+    let graph = compile_program(statements, 44100.0, None).unwrap();
+    assert!(!graph.has_assertions());
+}
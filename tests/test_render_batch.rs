@@ -0,0 +1,89 @@
+/// Tests for `phonon render-batch`: rendering every entry of a manifest
+use phonon::render_batch::{render_batch, summary};
+use std::fs;
+use std::path::PathBuf;
+
+fn batch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("phonon_{}_{}", name, std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn wav_seconds(path: &PathBuf) -> f32 {
+    let reader = hound::WavReader::open(path).unwrap();
+    let spec = reader.spec();
+    reader.duration() as f32 / spec.sample_rate as f32
+}
+
+#[test]
+fn test_batch_renders_every_entry() {
+    let dir = batch_dir("batch_album");
+    fs::write(dir.join("one.phonon"), "tempo: 2.0\nout $ sine 220 * 0.5").unwrap();
+    fs::write(dir.join("two.phonon"), "tempo: 1.0\nout $ saw 110 * 0.3").unwrap();
+    fs::write(
+        dir.join("album.toml"),
+        r#"
+sample_rate = 22050
+output_dir = "wav"
+jobs = 2
+
+[[render]]
+file = "one.phonon"
+cycles = 4
+
+[[render]]
+file = "two.phonon"
+duration = 1.5
+output = "02-two.wav"
+"#,
+    )
+    .unwrap();
+
+    let results = render_batch(&dir.join("album.toml"), None).unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.outcome.is_ok()));
+
+    // 4 cycles at 2 cps, and 1.5 seconds
+    assert!((wav_seconds(&dir.join("wav/one.wav")) - 2.0).abs() < 0.01);
+    assert!((wav_seconds(&dir.join("wav/02-two.wav")) - 1.5).abs() < 0.01);
+
+    let (_, peak) = results[0].outcome.clone().unwrap();
+    assert!((peak - 0.5).abs() < 0.05, "peak {}", peak);
+    assert_eq!(
+        summary(&results).last().unwrap(),
+        "2 rendered, 0 failed, 3.5s of audio"
+    );
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn test_failed_entry_does_not_stop_the_batch() {
+    let dir = batch_dir("batch_broken");
+    fs::write(dir.join("good.phonon"), "out $ sine 440 * 0.2").unwrap();
+    fs::write(dir.join("bad.phonon"), "out $ nosuchfunction 440").unwrap();
+    fs::write(
+        dir.join("batch.toml"),
+        "duration = 0.5\n\n[[render]]\nfile = \"bad.phonon\"\n\n[[render]]\nfile = \"good.phonon\"",
+    )
+    .unwrap();
+
+    let results = render_batch(&dir.join("batch.toml"), Some(1)).unwrap();
+    assert!(results[0].outcome.is_err());
+    assert!(results[1].outcome.is_ok());
+    assert!(dir.join("good.wav").exists());
+
+    let lines = summary(&results);
+    assert!(lines[0].starts_with("❌"));
+    assert_eq!(lines.last().unwrap(), "1 rendered, 1 failed, 0.5s of audio");
+
+    // A missing file is caught before anything renders
+    fs::write(
+        dir.join("missing.toml"),
+        "[[render]]\nfile = \"gone.phonon\"",
+    )
+    .unwrap();
+    assert!(render_batch(&dir.join("missing.toml"), None).is_err());
+
+    fs::remove_dir_all(&dir).ok();
+}
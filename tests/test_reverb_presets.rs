@@ -0,0 +1,65 @@
+/// Tests for `reverb` presets, pre-delay and shimmer
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code, magnitude_at, render_code};
+
+#[test]
+fn test_presets_ring_out() {
+    // One hit, then the tail: a hall rings longer than a room
+    let hit = "tempo: 0.25\nout $ s \"bd ~ ~ ~\" # reverb :preset";
+    let room = render_code(&format!("{} room", hit), 44100);
+    let hall = render_code(&format!("{} hall", hit), 44100);
+    let dry = render_code("tempo: 0.25\nout $ s \"bd ~ ~ ~\"", 44100);
+    let tail = 30000..44100;
+    assert!(calculate_rms(&hall[tail.clone()]) > calculate_rms(&room[tail.clone()]));
+    assert!(calculate_rms(&room[tail.clone()]) > calculate_rms(&dry[tail]));
+
+    // Every preset compiles, and positional args still override
+    for preset in ["hall", "plate", "room", "shimmer"] {
+        render_code(
+            &format!("out $ sine 440 # reverb 0.5 0.5 :preset {}", preset),
+            512,
+        );
+    }
+    render_code("out $ sine 440 # reverb", 512);
+}
+
+#[test]
+fn test_predelay_holds_back_the_tail() {
+    // Until the 400ms pre-delay is up, only the dry kick comes out
+    let kick = "tempo: 0.25\nout $ s \"bd ~ ~ ~\"";
+    let dry = render_code(kick, 17000);
+    let soon = render_code(&format!("{} # reverb 0.8 0.3 1.0", kick), 17000);
+    let late = render_code(
+        &format!("{} # reverb 0.8 0.3 1.0 :predelay 400ms", kick),
+        17000,
+    );
+    let diff = |a: &[f32]| a.iter().zip(&dry).map(|(x, y)| (x - y).abs()).fold(0.0, f32::max);
+    assert!(diff(&soon) > 1e-3);
+    assert!(diff(&late) < 1e-6, "wet signal before the pre-delay: {}", diff(&late));
+}
+
+#[test]
+fn test_shimmer_adds_octave() {
+    let code = "out $ sine 440 * 0.3 # reverb :preset shimmer";
+    let shimmer = render_code(code, 88200);
+    let plain = render_code(&format!("{} :shimmer 0", code), 88200);
+    let octave_ratio = |b: &[f32]| {
+        magnitude_at(&b[44100..], 44100.0, 880.0) / magnitude_at(&b[44100..], 44100.0, 440.0)
+    };
+    assert!(
+        octave_ratio(&shimmer) > octave_ratio(&plain) * 2.0,
+        "shimmer {} vs plain {}",
+        octave_ratio(&shimmer),
+        octave_ratio(&plain)
+    );
+}
+
+#[test]
+fn test_reverb_errors() {
+    for code in [
+        "out $ sine 440 # reverb :preset cathedral",
+        "out $ sine 440 # reverb :shimmer (sine 1)",
+    ] {
+        assert!(compile_code(code).is_err(), "{}", code);
+    }
+}
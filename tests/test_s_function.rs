@@ -1,7 +1,9 @@
 /// Test the s() function for sample pattern triggering
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_s_function_parses() {
     let input = r#"out $ s "bd sn hh cp""#;
     let result = parse_dsl(input);
@@ -11,6 +13,7 @@ fn test_s_function_parses() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_s_function_compiles() {
     let input = r#"out $ s "bd ~ sn ~""#;
     let (_, statements) = parse_dsl(input).unwrap();
@@ -35,6 +38,7 @@ fn test_s_function_compiles() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_s_function_with_gain_param() {
     let input = r#"out $ s "bd*4" # gain 0.5"#;
     let (_, statements) = parse_dsl(input).unwrap();
@@ -51,6 +55,7 @@ fn test_s_function_with_gain_param() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_s_function_with_pattern_gain() {
     let input = r#"out $ s "bd*4" # gain "0.5 1.0 0.7 0.3""#;
     let (_, statements) = parse_dsl(input).unwrap();
@@ -68,6 +73,7 @@ fn test_s_function_with_pattern_gain() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_tidal_workflow_basic() {
     // Test basic Tidal Cycles workflow
     let input = r#"
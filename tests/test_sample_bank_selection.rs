@@ -87,6 +87,7 @@ fn test_mini_notation_with_sample_index() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_sample_playback_with_index_e2e() {
     // Integration test: render audio with indexed samples using DSL
     use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_sample_without_transform() {
     let input = r#"
         cps: 2.0
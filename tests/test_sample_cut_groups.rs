@@ -9,9 +9,11 @@
 /// - Cut group N > 0 = voices in group N stop each other
 ///
 /// This test verifies that voices in the same cut group stop each other.
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_cut_group_stops_previous_voice() {
     // Dense hi-hat pattern so consecutive voices overlap in time; all in cut
     // group 1. Canonical syntax: `# cut N`. The cut applies a 10ms fade-out to
@@ -78,6 +80,7 @@ fn test_cut_group_stops_previous_voice() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_no_cut_group_allows_overlap() {
     // Dense hi-hat pattern with no cut group: all voices should overlap.
     let input = r#"
@@ -110,6 +113,7 @@ fn test_no_cut_group_allows_overlap() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_different_cut_groups_dont_interact() {
     // Alternating cut groups 1 and 2: voices in group 1 cut each other and
     // voices in group 2 cut each other, but a group-1 voice and a group-2 voice
@@ -144,6 +148,7 @@ fn test_different_cut_groups_dont_interact() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_cut_group_pattern() {
     // Every event in the same cut group (1): each new hit fades out the previous
     // same-group voice, so overlap stays at <= 2 (fading + new).
@@ -177,6 +182,7 @@ fn test_cut_group_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_cut_group_default_is_zero() {
     // Without cut group parameter, should default to 0 (no cutting)
     // This allows multiple voices to overlap
@@ -235,6 +241,7 @@ fn test_cut_group_default_is_zero() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_hi_hat_open_close_simulation() {
     // Realistic hi-hat scenario: closed hits stop open hits
     // hh:0 = open, hh:1 = closed (both in cut group 1)
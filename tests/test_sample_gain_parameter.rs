@@ -5,9 +5,11 @@
 /// - s("bd*4", gain="1 0.8 0.6 0.4") - descending volume
 ///
 /// This test uses FFT and RMS analysis to verify gain is applied correctly.
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_gain_parameter_affects_amplitude() {
     // Pattern with two events at different gains.
     // Canonical syntax: per-event gain via `# gain "..."`.
@@ -52,6 +54,7 @@ fn test_gain_parameter_affects_amplitude() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_gain_pattern_with_multiple_events() {
     // Four kick drums with descending gain
     let input = r#"
@@ -109,6 +112,7 @@ fn test_gain_pattern_with_multiple_events() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_gain_zero_produces_silence() {
     // Sample with gain=0 should produce no audio (use same sample for both events)
     let input = r#"
@@ -138,6 +142,7 @@ fn test_gain_zero_produces_silence() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_gain_default_is_one() {
     // Without gain parameter, should default to 1.0
     let input_with_gain = r#"
@@ -6,9 +6,11 @@
 ///
 /// Pan values: -1.0 = hard left, 0.0 = center, 1.0 = hard right
 /// This test uses stereo RMS analysis to verify panning is applied correctly.
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_pan_parameter_affects_stereo_position() {
     // Pattern with two events: bd hard left, sn hard right.
     // Canonical syntax: per-event pan via `# pan "..."`.
@@ -54,6 +56,7 @@ fn test_pan_parameter_affects_stereo_position() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pan_center_produces_equal_stereo() {
     // Sample with center pan (0.0) should produce equal left/right channels
     let input = r#"
@@ -75,6 +78,7 @@ fn test_pan_center_produces_equal_stereo() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pan_pattern_with_multiple_events() {
     // Four hi-hats panning from left to right
     let input = r#"
@@ -112,6 +116,7 @@ fn test_pan_pattern_with_multiple_events() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pan_default_is_center() {
     // Without pan parameter, should default to 0.0 (center)
     let input_with_pan = r#"
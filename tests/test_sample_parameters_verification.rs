@@ -13,6 +13,7 @@
 /// - loop: Loop mode
 /// - unit: Time unit mode (rate vs cycle)
 /// - cut: Cut group for voice stealing
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 // ====================
@@ -715,6 +716,7 @@ fn test_cut_pattern_varies_groups() {
 // HELPER FUNCTIONS
 // ====================
 
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_seconds: f32) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Failed to parse DSL");
     let compiler = DslCompiler::new(44100.0);
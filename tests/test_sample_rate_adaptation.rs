@@ -0,0 +1,137 @@
+/// Tests that pitch and timing don't depend on the sample rate
+///
+/// Graphs compiled at (or moved to) 48 kHz and 96 kHz must produce the same
+/// frequencies, pattern timing and delay times as at 44.1 kHz.
+use phonon::audio_analysis::analyze_frames;
+
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code_at};
+
+/// Median detected pitch between `from` and `to` seconds
+fn pitch_between(audio: &[f32], sample_rate: f32, from: f32, to: f32) -> f32 {
+    let mut pitches: Vec<f32> = analyze_frames(audio, sample_rate, 0.05)
+        .into_iter()
+        .filter(|f| f.time >= from && f.time < to && f.pitch > 0.0)
+        .map(|f| f.pitch)
+        .collect();
+    assert!(
+        !pitches.is_empty(),
+        "no pitched frames in {}..{}s",
+        from,
+        to
+    );
+    pitches.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    pitches[pitches.len() / 2]
+}
+
+/// Time of the first sample louder than `threshold`, in seconds
+fn first_sound(audio: &[f32], sample_rate: f32, threshold: f32) -> f32 {
+    let idx = audio
+        .iter()
+        .position(|s| s.abs() > threshold)
+        .expect("output should not be silent");
+    idx as f32 / sample_rate
+}
+
+#[test]
+fn test_oscillator_pitch_at_48k_and_96k() {
+    for sample_rate in [44100.0, 48000.0, 96000.0] {
+        let mut graph =
+            compile_code_at("out $ sine 440 * 0.5", sample_rate).expect("Should compile");
+        let audio = graph.render(sample_rate as usize);
+        let pitch = pitch_between(&audio, sample_rate, 0.0, 0.9);
+        assert!(
+            (pitch - 440.0).abs() < 5.0,
+            "{} Hz: expected 440 Hz, got {:.1}",
+            sample_rate,
+            pitch
+        );
+    }
+}
+
+#[test]
+fn test_pattern_timing_at_48k_and_96k() {
+    // One cycle per second: 220 Hz for the first half, 440 Hz for the second
+    let code = r#"
+tempo: 1.0
+~freq $ "220 440"
+out $ sine ~freq * 0.5
+"#;
+    for sample_rate in [48000.0, 96000.0] {
+        let mut graph = compile_code_at(code, sample_rate).expect("Should compile");
+        let audio = graph.render(sample_rate as usize);
+        let first = pitch_between(&audio, sample_rate, 0.05, 0.4);
+        let second = pitch_between(&audio, sample_rate, 0.55, 0.9);
+        assert!(
+            (first - 220.0).abs() < 5.0,
+            "{} Hz: first half {:.1}",
+            sample_rate,
+            first
+        );
+        assert!(
+            (second - 440.0).abs() < 5.0,
+            "{} Hz: second half {:.1}",
+            sample_rate,
+            second
+        );
+    }
+}
+
+#[test]
+fn test_delay_time_at_96k() {
+    // Fully wet, no feedback: silence until the delay time has passed
+    let code = "out $ sine 440 # delay 0.25 0.0 1.0";
+    for sample_rate in [48000.0, 96000.0] {
+        let mut graph = compile_code_at(code, sample_rate).expect("Should compile");
+        let audio = graph.render((sample_rate * 0.5) as usize);
+        let onset = first_sound(&audio, sample_rate, 0.01);
+        assert!(
+            (onset - 0.25).abs() < 0.002,
+            "{} Hz: delayed signal at {:.4}s",
+            sample_rate,
+            onset
+        );
+    }
+}
+
+#[test]
+fn test_set_sample_rate_keeps_pitch_and_delay() {
+    let mut graph =
+        compile_code_at("out $ sine 440 # delay 0.25 0.0 1.0", 44100.0).expect("Should compile");
+    graph.set_sample_rate(96000.0);
+    assert_eq!(graph.sample_rate(), 96000.0);
+
+    let audio = graph.render(96000);
+    let onset = first_sound(&audio, 96000.0, 0.01);
+    assert!(
+        (onset - 0.25).abs() < 0.002,
+        "delayed signal at {:.4}s",
+        onset
+    );
+    let pitch = pitch_between(&audio, 96000.0, 0.3, 0.9);
+    assert!(
+        (pitch - 440.0).abs() < 5.0,
+        "expected 440 Hz, got {:.1}",
+        pitch
+    );
+}
+
+#[test]
+fn test_set_sample_rate_recomputes_filter() {
+    // A 1 kHz lowpass must attenuate 5 kHz by the same amount at any rate
+    let code = "out $ sine 5000 # lpf 1000 0.7";
+    let mut reference = compile_code_at(code, 44100.0).expect("Should compile");
+    let expected = calculate_rms(&reference.render(44100)[4410..]);
+
+    let mut moved = compile_code_at(code, 44100.0).expect("Should compile");
+    moved.render(4410);
+    moved.set_sample_rate(96000.0);
+    let actual = calculate_rms(&moved.render(96000)[9600..]);
+
+    assert!(
+        (actual - expected).abs() < expected * 0.2 + 1e-3,
+        "lowpass response changed: {:.4} at 44.1k vs {:.4} at 96k",
+        expected,
+        actual
+    );
+}
@@ -0,0 +1,72 @@
+/// Tests for per-trigger sample selection: round-robin folders (`hh:rr`) and
+/// `# n (irand 8)`
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::sample_loader::set_extra_sample_dirs;
+
+/// Levels of the files in the test folder, in file order
+const LEVELS: [f32; 3] = [0.1, 0.2, 0.3];
+
+/// A folder of short constant samples, one per level
+fn level_bank() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let bank = dir.path().join("rrlevels");
+    std::fs::create_dir(&bank).unwrap();
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 44100,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    for (i, level) in LEVELS.iter().enumerate() {
+        let mut writer = hound::WavWriter::create(bank.join(format!("{}.wav", i)), spec).unwrap();
+        for _ in 0..200 {
+            writer.write_sample(*level).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+    dir
+}
+
+/// Peak of each of the 6 events in one cycle
+fn event_peaks(code: &str) -> Vec<f32> {
+    let (rest, stmts) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    let mut graph = compile_program(stmts, 44100.0, None).expect("Failed to compile");
+    graph
+        .render(44100)
+        .chunks(44100 / 6)
+        .map(|slot| slot.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+        .collect()
+}
+
+/// Which file each of the 6 events in one cycle played
+fn played_files(code: &str) -> Vec<usize> {
+    // Peaks are proportional to the file's level
+    let unit = event_peaks("tempo: 1.0\nout $ s \"rrlevels*6\"")[0] / LEVELS[0];
+    event_peaks(code)
+        .iter()
+        .map(|peak| {
+            let level = peak / unit;
+            LEVELS
+                .iter()
+                .position(|l| (l - level).abs() < 0.02)
+                .unwrap_or_else(|| panic!("{}: unexpected level {}", code, level))
+        })
+        .collect()
+}
+
+#[test]
+fn test_round_robin_and_random_selection() {
+    let dir = level_bank();
+    set_extra_sample_dirs(vec![dir.path().to_path_buf()]);
+
+    let files = played_files("tempo: 1.0\nout $ s \"rrlevels:rr*6\"");
+    assert_eq!(files, vec![0, 1, 2, 0, 1, 2]);
+
+    // A random file per hit, the same ones on every render
+    let code = "tempo: 1.0\nout $ s \"rrlevels*6\" # n (irand 3)";
+    let files = played_files(code);
+    assert!(files.iter().any(|&f| f != files[0]), "{:?}", files);
+    assert_eq!(files, played_files(code));
+}
@@ -7,9 +7,11 @@
 ///
 /// Speed values: 1.0 = normal, 2.0 = double speed, 0.5 = half speed
 /// This test uses duration and RMS analysis to verify speed is applied correctly.
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_speed_parameter_affects_playback_rate() {
     // Two kick drums: normal speed and double speed
     // Double speed should finish in half the time.
@@ -56,6 +58,7 @@ fn test_speed_parameter_affects_playback_rate() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_speed_half_plays_longer() {
     // Compare normal speed vs half speed
     // Half speed should have audio lasting longer
@@ -100,6 +103,7 @@ fn test_speed_half_plays_longer() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_speed_pattern_with_multiple_values() {
     // Four samples with different speeds
     let input = r#"
@@ -137,6 +141,7 @@ fn test_speed_pattern_with_multiple_values() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_speed_default_is_one() {
     // Without speed parameter, should default to 1.0 (normal speed)
     let input_with_speed = r#"
@@ -175,6 +180,7 @@ fn test_speed_default_is_one() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_speed_extreme_values() {
     // Test very fast and very slow speeds
     let input_fast = r#"
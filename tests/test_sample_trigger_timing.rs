@@ -4,6 +4,7 @@
 //! according to pattern specifications, and that pattern parameters
 //! (gain, pan, speed) are applied correctly.
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod pattern_verification_utils;
@@ -13,6 +14,7 @@ mod audio_test_utils;
 use audio_test_utils::calculate_rms;
 
 /// Helper to compile and render DSL
+#[allow(deprecated)]
 fn compile_and_render(input: &str, duration_samples: usize) -> Vec<f32> {
     let (_, statements) = parse_dsl(input).expect("Failed to parse DSL");
     let compiler = DslCompiler::new(44100.0);
@@ -344,6 +346,7 @@ out $ s "bd*2 [sn cp] hh(3,8)""#;
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_multi_output_sample_timing() {
     // Test: Multiple outputs should each trigger samples
     let input = r#"bpm 120
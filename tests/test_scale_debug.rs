@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_parsing_debug() {
     let input = r#"
         out $ scale("0", "major", "60")
@@ -30,6 +32,7 @@ fn test_scale_parsing_debug() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_multiline_parsing_debug() {
     // Test 1: Single line (should work)
     let input1 = r#"out $ scale("0", "major", "60")"#;
@@ -69,6 +72,7 @@ fn test_multiline_parsing_debug() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_as_freq_source() {
     let input = r#"
         cps: 1.0
@@ -2,6 +2,7 @@
 //!
 //! Verifies that scale() quantizes scale degrees to musical frequencies.
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use rustfft::{num_complex::Complex, FftPlanner};
 
@@ -32,6 +33,7 @@ fn find_dominant_frequency(buffer: &[f32], sample_rate: f32) -> f32 {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_major_c4() {
     // Test C major scale starting at C4 (MIDI 60)
     // Scale degrees: 0, 1, 2, 3, 4 = C, D, E, F, G
@@ -74,6 +76,7 @@ fn test_scale_major_c4() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_minor_a4() {
     // Test A minor scale starting at A4 (MIDI 69)
     // Scale degrees: 0, 1, 2 = A, B, C
@@ -111,6 +114,7 @@ fn test_scale_minor_a4() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_pentatonic() {
     // Test pentatonic scale
     // Pentatonic scale: [0, 2, 4, 7, 9] semitones
@@ -149,6 +153,7 @@ fn test_scale_pentatonic() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_octave_wrapping() {
     // Test that scale degrees wrap to higher octaves
     // Degrees: 0, 7 (0 = C4, 7 = C5 in 7-note scale)
@@ -187,6 +192,7 @@ fn test_scale_octave_wrapping() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_produces_audio() {
     // Basic sanity check that scale() produces audio
     let input = r#"
@@ -209,6 +215,7 @@ fn test_scale_produces_audio() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_with_fast_pattern() {
     // Test scale with fast subdivision (arpeggio effect)
     let input = r#"
@@ -1,8 +1,10 @@
 //! Simple test for scale quantization without FFT
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_parse() {
     // Just test that scale() parses correctly
     let input = r#"
@@ -14,6 +16,7 @@ fn test_scale_parse() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_compile() {
     // Test that scale() compiles without errors
     let input = r#"
@@ -31,6 +34,7 @@ fn test_scale_compile() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_with_sine() {
     // Test scale() feeding into sine()
     let input = r#"
@@ -52,6 +56,7 @@ fn test_scale_with_sine() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_direct_output() {
     // Test scale() output directly (should output frequencies)
     let input = "cps: 1.0\nout $ scale \"0\" \"major\" \"60\"";
@@ -81,6 +86,7 @@ fn test_scale_direct_output() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_scale_changes() {
     // Test that scale values change with the pattern
     // Using <> alternation to get one value per cycle
@@ -0,0 +1,116 @@
+/// Tests for seeking to a cycle (`render --start-cycle`, editor `:cue`) and
+/// for stopping and starting live playback (`:stop` / `:start`)
+///
+/// A graph cued to cycle N must sound like the same graph rendered from the
+/// start and listened to from cycle N on. A stopped one must pick up where it
+/// stopped.
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+#[test]
+fn test_seek_matches_render_from_start() {
+    let code = "tempo: 1.0\nout $ sine 440 * 0.5";
+    let mut from_start = compile_code(code).expect("Failed to compile");
+    let full = from_start.render(SAMPLE_RATE as usize * 4);
+
+    let mut cued = compile_code(code).expect("Failed to compile");
+    cued.seek_to_cycle(3.0);
+    assert!((cued.get_cycle_position() - 3.0).abs() < 1e-9);
+    let tail = cued.render(SAMPLE_RATE as usize);
+
+    let expected = &full[SAMPLE_RATE as usize * 3..];
+    let max_diff = expected
+        .iter()
+        .zip(&tail)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0f32, f32::max);
+    assert!(max_diff < 1e-3, "cued render differs by {}", max_diff);
+}
+
+#[test]
+fn test_seek_lands_on_pattern_position() {
+    let code = "tempo: 1.0\nout $ sine 440 * \"1 0\"";
+    let quarter = SAMPLE_RATE as usize / 4;
+
+    // Second half of cycle 16 is silent, first half isn't
+    let mut graph = compile_code(code).expect("Failed to compile");
+    graph.seek_to_cycle(16.5);
+    assert!(calculate_rms(&graph.render(quarter)) < 0.01);
+
+    // Backwards works too
+    graph.seek_to_cycle(16.0);
+    assert!(calculate_rms(&graph.render(quarter)) > 0.3);
+}
+
+#[test]
+fn test_editor_cue_jumps_live_playback() {
+    let code = "tempo: 1.0\nout $ sine 440 * \"1 0\"";
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.ctrl_x();
+    harness.render_live_chunks(20).unwrap();
+
+    harness.console_command(":cue 32");
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|m| m.contains("Cued to cycle 32")));
+
+    // 256 frames per chunk: a few chunks stay inside the first half of cycle 32
+    let out = harness.render_live_chunks(40).unwrap();
+    assert!(
+        calculate_rms(&out) > 0.3,
+        "cued playback should start at cycle 32"
+    );
+    let position = harness.get_cycle_position().unwrap();
+    assert!((32.0..33.0).contains(&position), "at cycle {}", position);
+
+    harness.console_command(":cue soon");
+    assert!(harness
+        .command_output()
+        .iter()
+        .any(|l| l.contains("Invalid cycle")));
+}
+
+#[test]
+fn test_editor_stop_holds_playback_and_start_resumes_it() {
+    // A pitch glide, so any skipped or repeated audio shows
+    let code = "tempo: 1.0\nout $ sine \"220 330 440 550\" * 0.5";
+    let mut reference = EditorTestHarness::with_content(code).unwrap();
+    reference.ctrl_x();
+    let continuous = reference.render_live_chunks(60).unwrap();
+
+    let mut harness = EditorTestHarness::with_content(code).unwrap();
+    harness.ctrl_x();
+    let before = harness.render_live_chunks(20).unwrap();
+    assert_eq!(before, continuous[..before.len()]);
+
+    harness.console_command(":stop");
+    assert!(harness.status_message().contains("Stopped"));
+    // The block the stop lands on fades out, then silence
+    let chunk = 256;
+    let stopped = harness.render_live_chunks(20).unwrap();
+    assert!(calculate_rms(&stopped[..chunk]) > 0.05);
+    assert!(stopped[chunk..].iter().all(|&s| s == 0.0));
+    let held = harness.get_cycle_position().unwrap();
+    harness.render_live_chunks(20).unwrap();
+    assert_eq!(harness.get_cycle_position().unwrap(), held);
+
+    // Alt+Space starts again: a fade-in, then exactly the audio that would
+    // have followed the faded-out block
+    harness.send_key_with_modifiers(KeyCode::Char(' '), KeyModifiers::ALT);
+    assert!(harness.status_message().contains("Playing"));
+    let resumed = harness.render_live_chunks(30).unwrap();
+    let next = 21 * chunk;
+    assert!(calculate_rms(&resumed[..chunk]) > 0.05);
+    let max_diff = resumed[chunk..]
+        .iter()
+        .zip(&continuous[next + chunk..])
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0f32, f32::max);
+    assert!(max_diff < 1e-4, "resumed audio differs by {}", max_diff);
+}
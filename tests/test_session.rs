@@ -0,0 +1,137 @@
+/// Tests for suspending a live set to disk and resuming it
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::modal_editor::test_harness::EditorTestHarness;
+use phonon::session::{Session, SessionPane};
+
+const SET: &str = "tempo: 1.5\n~lead $ saw \"c3 e3 g3\" # lpf 1200 0.7\nout $ ~lead * 0.3";
+
+fn pane(code: &str) -> SessionPane {
+    SessionPane {
+        code: code.to_string(),
+        file: None,
+    }
+}
+
+#[test]
+fn test_resumed_session_plays_from_the_saved_cycle() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("set.phsn");
+    Session::capture(vec![pane(SET)], 1.5, 6.0, Vec::new())
+        .save(&path)
+        .unwrap();
+
+    let session = Session::load(&path).unwrap();
+    assert_eq!(session.panes, vec![pane(SET)]);
+    assert_eq!(session.cps, 1.5);
+    let mut resumed = session.resume(44100.0).unwrap();
+    assert_eq!(resumed.get_cps(), 1.5);
+
+    let (_, statements) = parse_program(SET).unwrap();
+    let mut graph = compile_program(statements, 44100.0, None).unwrap();
+    graph.seek_to_cycle(6.0);
+
+    let expected = graph.render(4410);
+    let actual = resumed.render(4410);
+    for i in 0..4410 {
+        assert!(
+            (expected[i] - actual[i]).abs() < 1e-4,
+            "sample {}: {} vs {}",
+            i,
+            expected[i],
+            actual[i]
+        );
+    }
+}
+
+#[test]
+fn test_resumed_session_carries_node_state() {
+    let compile = || {
+        let (_, statements) = parse_program(SET).unwrap();
+        compile_program(statements, 44100.0, None).unwrap()
+    };
+    let mut playing = compile();
+    playing.render(30000);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("set.phsn");
+    let cycle = playing.get_cycle_position();
+    Session::capture(vec![pane(SET)], 1.5, cycle, playing.save_node_states())
+        .save(&path)
+        .unwrap();
+    let mut resumed = Session::load(&path).unwrap().resume(44100.0).unwrap();
+
+    // Oscillator phase and filter memory come back, so the resumed graph
+    // carries on where the playing one is, unlike a fresh one at that cycle
+    let mut fresh = compile();
+    fresh.seek_to_cycle(cycle);
+    let expected = playing.render(441);
+    let error = |actual: Vec<f32>| -> f32 {
+        actual
+            .iter()
+            .zip(&expected)
+            .map(|(a, b)| (a - b).abs())
+            .sum()
+    };
+    let resumed_error = error(resumed.render(441));
+    let fresh_error = error(fresh.render(441));
+    assert!(
+        resumed_error < fresh_error * 0.1,
+        "resumed {} vs fresh {}",
+        resumed_error,
+        fresh_error
+    );
+}
+
+#[test]
+fn test_node_states_skip_nodes_that_compiled_differently() {
+    let compile = |code: &str| {
+        let (_, statements) = parse_program(code).unwrap();
+        compile_program(statements, 44100.0, None).unwrap()
+    };
+    let mut playing = compile(SET);
+    playing.render(4410);
+    let states = playing.save_node_states();
+
+    assert!(compile(SET).restore_node_states(&states) > 0);
+    assert_eq!(compile("out $ 0.5").restore_node_states(&states), 0);
+    assert_eq!(compile(SET).restore_node_states(b"not a state"), 0);
+}
+
+#[test]
+fn test_session_merges_panes_in_order() {
+    let session = Session::capture(
+        vec![pane("tempo: 2\n~a $ sine 220"), pane("out $ ~a * 0.2")],
+        2.0,
+        0.0,
+        Vec::new(),
+    );
+    let mut graph = session.resume(44100.0).unwrap();
+    assert!(graph.render(4410).iter().any(|x| x.abs() > 0.1));
+}
+
+#[test]
+fn test_load_session_rejects_other_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("set.phsn");
+    std::fs::write(&path, "out $ sine 440").unwrap();
+    assert!(Session::load(&path).is_err());
+    assert!(Session::load(&tmp.path().join("missing.phsn")).is_err());
+}
+
+#[test]
+fn test_save_and_load_session_from_the_console() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = tmp.path().join("set.phsn");
+
+    let mut harness = EditorTestHarness::with_content(SET).unwrap();
+    harness.ctrl_x();
+    harness.console_command(&format!(":save-session {}", path.display()));
+    assert!(path.exists());
+
+    let mut resumed = EditorTestHarness::with_content("out $ sine 110").unwrap();
+    resumed.console_command(&format!(":load-session {}", path.display()));
+    assert_eq!(resumed.content(), SET);
+    assert!(resumed.has_graph());
+    assert_eq!(resumed.get_cps(), Some(1.5));
+}
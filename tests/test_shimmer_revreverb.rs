@@ -0,0 +1,49 @@
+/// Tests for the `shimmer` and `revreverb` composite effects
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code, magnitude_at, render_code};
+
+#[test]
+fn test_shimmer_climbs_by_interval() {
+    let code = "out $ sine 440 * 0.3 # shimmer 0.5";
+    let ratio = |b: &[f32], freq| {
+        magnitude_at(&b[44100..], 44100.0, freq) / magnitude_at(&b[44100..], 44100.0, 440.0)
+    };
+
+    let octave = render_code(code, 88200);
+    let plain = render_code(&format!("{} :amount 0", code), 88200);
+    assert!(ratio(&octave, 880.0) > ratio(&plain, 880.0) * 2.0);
+
+    // A fifth up lands on 660
+    let fifth = render_code(&format!("{} :interval 7st", code), 88200);
+    assert!(ratio(&fifth, 659.26) > ratio(&plain, 659.26) * 2.0);
+}
+
+#[test]
+fn test_revreverb_swells_into_the_next_chunk() {
+    let kick = "tempo: 0.25\nout $ s \"bd ~ ~ ~\"";
+    let dry = render_code(kick, 44100);
+    let wet = render_code(&format!("{} # revreverb 1.0 :time 500ms", kick), 44100);
+
+    // First half second: nothing reversed yet
+    let first = wet[..22050].iter().zip(&dry).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max);
+    assert!(first < 1e-6, "wet before the first chunk ended: {}", first);
+
+    // Second half second: the tail plays backwards, so it grows
+    let tail: Vec<f32> = wet[22050..].iter().zip(&dry[22050..]).map(|(a, b)| a - b).collect();
+    let (start, end) = (
+        calculate_rms(&tail[..5000]),
+        calculate_rms(&tail[15000..21000]),
+    );
+    assert!(end > start * 2.0 && end > 1e-3, "no swell: {} -> {}", start, end);
+}
+
+#[test]
+fn test_composite_effect_errors() {
+    for code in [
+        "out $ sine 440 # shimmer :interval (sine 1)",
+        "out $ sine 440 # shimmer :interval 0",
+        "out $ sine 440 # revreverb :time 20s",
+    ] {
+        assert!(compile_code(code).is_err(), "{}", code);
+    }
+}
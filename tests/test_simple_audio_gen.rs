@@ -1,9 +1,12 @@
 //! Simple test for audio generation
 
+#[allow(deprecated)]
 use phonon::glicol_parser_v2::parse_glicol_v2;
+#[allow(deprecated)]
 use phonon::simple_dsp_executor_v2::SimpleDspExecutorV2;
 
 #[test]
+#[allow(deprecated)]
 fn test_simple_sine_generation() {
     println!("\n=== Testing Simple Sine Wave Generation ===");
 
@@ -43,6 +46,7 @@ fn test_simple_sine_generation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_saw_wave_generation() {
     println!("\n=== Testing Saw Wave Generation ===");
 
@@ -62,6 +66,7 @@ fn test_saw_wave_generation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_reference_chain() {
     println!("\n=== Testing Reference Chain ===");
 
@@ -85,6 +90,7 @@ fn test_reference_chain() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pattern_frequency() {
     println!("\n=== Testing Pattern Frequency ===");
 
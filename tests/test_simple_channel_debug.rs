@@ -1,8 +1,10 @@
 //! Simple debug test to see what's happening with channels
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_single_sample_trigger() {
     let code = r#"
         tempo: 0.5
@@ -24,6 +26,7 @@ fn test_single_sample_trigger() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_three_sample_triggers() {
     let code = r#"
         tempo: 0.5
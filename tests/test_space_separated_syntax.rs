@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_sample_pattern() {
     // Test: s "bd sn" (space-separated)
     let input = r#"out $ s "bd sn" * 0.5"#;
@@ -12,6 +14,7 @@ fn test_space_sep_sample_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_sample_pattern() {
     // Test: s "bd sn" (traditional parens)
     let input = r#"out $ s "bd sn" * 0.5"#;
@@ -23,6 +26,7 @@ fn test_traditional_sample_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_oscillator() {
     // Test: sine 440 (space-separated)
     let input = "out $ sine 440 * 0.2";
@@ -31,6 +35,7 @@ fn test_space_sep_oscillator() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_oscillator() {
     // Test: sine 440 (traditional parens)
     let input = "out $ sine 440 * 0.2";
@@ -39,6 +44,7 @@ fn test_traditional_oscillator() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_filter() {
     // Test: lpf input cutoff q (space-separated)
     let input = r#"out $ s "bd" # lpf 1000 0.8"#;
@@ -47,6 +53,7 @@ fn test_space_sep_filter() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_filter() {
     // Test: lpf(input, cutoff, q) (traditional parens)
     let input = r#"out $ s "bd" # lpf 1000 0.8"#;
@@ -55,6 +62,7 @@ fn test_traditional_filter() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_synth() {
     // Test: supersaw freq detune voices (space-separated)
     let input = "out $ supersaw 110 0.5 5 * 0.3";
@@ -63,6 +71,7 @@ fn test_space_sep_synth() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_synth() {
     // Test: supersaw(freq, detune, voices) (traditional parens)
     let input = "out $ supersaw(110, 0.5, 5) * 0.3";
@@ -71,6 +80,7 @@ fn test_traditional_synth() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_effect() {
     // Test: reverb input room_size damping mix (space-separated)
     let input = r#"out $ reverb (s "bd") 0.7 0.5 0.3"#;
@@ -79,6 +89,7 @@ fn test_space_sep_effect() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_effect() {
     // Test: reverb(input, room_size, damping, mix) (traditional parens)
     let input = r#"out $ reverb(s "bd", 0.7, 0.5, 0.3)"#;
@@ -87,6 +98,7 @@ fn test_traditional_effect() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_scale() {
     // Test: scale "0 1 2" "major" "c4" (space-separated)
     let input = r#"out $ scale "0 1 2" "major" "c4""#;
@@ -95,6 +107,7 @@ fn test_space_sep_scale() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_scale() {
     // Test: scale("0 1 2", "major", "c4") (traditional parens)
     let input = r#"out $ scale("0 1 2", "major", "c4")"#;
@@ -103,6 +116,7 @@ fn test_traditional_scale() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_synth_pattern() {
     // Test: synth "c4 e4" "saw" (space-separated)
     let input = r#"
@@ -114,6 +128,7 @@ fn test_space_sep_synth_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_synth_pattern() {
     // Test: synth("c4 e4", "saw") (traditional parens)
     let input = r#"
@@ -125,6 +140,7 @@ fn test_traditional_synth_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_mixed_syntax() {
     // Test mixing both syntaxes in same file
     let input = r#"tempo: 0.5
@@ -140,6 +156,7 @@ out $ ~drums * 0.5 + ~bass * 0.3"#;
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_with_transforms() {
     // Test: s "bd sn" $ fast 2 (space-separated with transform)
     let input = r#"
@@ -154,6 +171,7 @@ fn test_space_sep_with_transforms() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_space_sep_delay() {
     // Test: delay input time feedback mix (space-separated)
     let input = r#"out $ delay (s "bd") 0.25 0.5 0.3"#;
@@ -162,6 +180,7 @@ fn test_space_sep_delay() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_traditional_delay() {
     // Test: delay(input, time, feedback, mix) (traditional parens)
     let input = r#"out $ delay(s "bd", 0.25, 0.5, 0.3)"#;
@@ -170,6 +189,7 @@ fn test_traditional_delay() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_render_space_sep_samples() {
     // Integration test: render audio with space-separated syntax
     let input = r#"
@@ -192,6 +212,7 @@ fn test_render_space_sep_samples() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_render_traditional_samples() {
     // Integration test: render audio with traditional syntax
     let input = r#"
@@ -214,6 +235,7 @@ fn test_render_traditional_samples() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_both_syntaxes_produce_same_audio() {
     // Verify both syntaxes produce identical audio
     let space_sep = r#"
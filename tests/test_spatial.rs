@@ -0,0 +1,178 @@
+//! Tests for `spatial:` layouts: `out` rendered once per speaker (or
+//! ambisonic channel), with sources placed by `# azimuth` and `# elevation`
+
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// Render a second of `code`, returning each channel separately
+fn render_channels(code: &str) -> Vec<Vec<f32>> {
+    let mut graph = compile_code(code).expect("Failed to compile");
+    let (count, samples) = graph.render_channels(SAMPLE_RATE as usize);
+    (0..count)
+        .map(|channel| {
+            samples
+                .iter()
+                .skip(channel)
+                .step_by(count)
+                .copied()
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_quad_places_source_between_speakers() {
+    let channels = render_channels("spatial: quad\nout $ (sine 440 * 0.5) # azimuth 45");
+    assert_eq!(channels.len(), 4);
+    // On the front right speaker
+    assert!(
+        calculate_rms(&channels[1]) > 0.3,
+        "FR: {}",
+        calculate_rms(&channels[1])
+    );
+    for channel in [0, 2, 3] {
+        assert!(
+            calculate_rms(&channels[channel]) < 1e-3,
+            "{}: {}",
+            channel,
+            calculate_rms(&channels[channel])
+        );
+    }
+
+    // Straight ahead, shared evenly by the front pair
+    let channels = render_channels("spatial: 4.0\nout $ noise # azimuth 0");
+    // One noise source, not one per speaker
+    assert!(channels[0]
+        .iter()
+        .zip(&channels[1])
+        .all(|(fl, fr)| (fl - fr).abs() < 1e-6));
+    assert!(calculate_rms(&channels[0]) > 0.1);
+    assert!(calculate_rms(&channels[2]) < 1e-3);
+}
+
+#[test]
+fn test_azimuth_pattern_moves_source() {
+    let channels = render_channels(
+        "tempo: 1.0\nspatial: quad\nout $ (sine 440 * 0.5) # azimuth \"0 90 180 270\" # elevation 0",
+    );
+    let quarter = SAMPLE_RATE as usize / 4;
+    let level = |channel: usize, step: usize| {
+        // Skip the edges of each step
+        calculate_rms(&channels[channel][step * quarter + 500..(step + 1) * quarter - 500])
+    };
+    // 0: front, 90: right, 180: behind, 270: left
+    assert!(level(0, 0) > 0.2 && level(1, 0) > 0.2 && level(2, 0) < 1e-3);
+    assert!(level(1, 1) > 0.2 && level(3, 1) > 0.2 && level(0, 1) < 1e-3);
+    assert!(level(2, 2) > 0.2 && level(3, 2) > 0.2 && level(1, 2) < 1e-3);
+    assert!(level(0, 3) > 0.2 && level(2, 3) > 0.2 && level(3, 3) < 1e-3);
+}
+
+#[test]
+fn test_buses_place_their_sources() {
+    let channels = render_channels(
+        "spatial: 5.1\n~lead $ sine 440 # azimuth 0\n~pad $ saw 110 # lpf 800 0.7 # azimuth 110\nout $ ~lead * 0.3 + ~pad * 0.3",
+    );
+    assert_eq!(channels.len(), 6);
+    // C has the lead, Rs the pad, and the LFE nothing
+    assert!(calculate_rms(&channels[2]) > 0.1);
+    assert!(calculate_rms(&channels[5]) > 0.05);
+    for channel in [0, 1, 3, 4] {
+        assert!(
+            calculate_rms(&channels[channel]) < 1e-3,
+            "{}: {}",
+            channel,
+            calculate_rms(&channels[channel])
+        );
+    }
+}
+
+#[test]
+fn test_foa_writes_b_format() {
+    // A source to the right and 30 degrees up
+    let channels =
+        render_channels("spatial: foa\nout $ (sine 440 * 0.5) # azimuth 90 # elevation 30");
+    assert_eq!(channels.len(), 4);
+    let (w, y, z, x) = (&channels[0], &channels[1], &channels[2], &channels[3]);
+    let elevation = 30f32.to_radians();
+    for i in (0..w.len()).step_by(97) {
+        assert!((y[i] + w[i] * elevation.cos()).abs() < 1e-4, "Y at {}", i);
+        assert!((z[i] - w[i] * elevation.sin()).abs() < 1e-4, "Z at {}", i);
+        assert!(x[i].abs() < 1e-4, "X at {}", i);
+    }
+    assert!(calculate_rms(w) > 0.3);
+}
+
+#[test]
+fn test_placement_without_layout_passes_through() {
+    let placed = render_channels("out $ sine 440 * 0.5 # azimuth 90 # elevation 20");
+    let plain = render_channels("out $ sine 440 * 0.5");
+    assert_eq!(placed, plain);
+}
+
+#[test]
+fn test_spatial_needs_a_single_out() {
+    let compile = |code: &str| compile_code(code).err();
+    assert!(compile("spatial: quad\n~a $ sine 440 # azimuth 90")
+        .unwrap()
+        .contains("needs an `out"));
+    assert!(
+        compile("spatial: quad\nout $ sine 440 # azimuth 90\nout1 $ sine 220")
+            .unwrap()
+            .contains("numbered outputs")
+    );
+    assert!(compile("spatial: quad\nspatial: foa\nout $ sine 440")
+        .unwrap()
+        .contains("only one"));
+    assert!(compile("spatial: quad\nout $ sine 440 # azimuth 90 45").is_some());
+}
+
+#[test]
+fn test_binaural_puts_source_in_one_ear() {
+    // The built-in spherical head model
+    let right = render_channels("spatial: binaural\nout $ (sine 440 * 0.5) # azimuth 90");
+    assert_eq!(right.len(), 2);
+    assert!(
+        calculate_rms(&right[1]) > calculate_rms(&right[0]) * 1.5,
+        "L {} R {}",
+        calculate_rms(&right[0]),
+        calculate_rms(&right[1])
+    );
+    let left = render_channels("spatial: binaural\nout $ (sine 440 * 0.5) # azimuth 270");
+    assert!(calculate_rms(&left[0]) > calculate_rms(&left[1]) * 1.5);
+
+    // Straight ahead, both ears alike
+    let front = render_channels("spatial: binaural\nout $ (sine 440 * 0.5) # azimuth 0");
+    assert!((calculate_rms(&front[0]) - calculate_rms(&front[1])).abs() < 1e-3);
+}
+
+#[test]
+fn test_binaural_loads_wav_hrir_set() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = hound::WavSpec {
+        channels: 2,
+        sample_rate: SAMPLE_RATE as u32,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    // An impulse in the right ear from the right, the left from the left
+    for (name, ears) in [("90_0.wav", (0.0f32, 1.0)), ("270_0.wav", (1.0, 0.0))] {
+        let mut writer = hound::WavWriter::create(dir.path().join(name), spec).unwrap();
+        writer.write_sample(ears.0).unwrap();
+        writer.write_sample(ears.1).unwrap();
+        writer.finalize().unwrap();
+    }
+
+    let code = format!(
+        "spatial: binaural \"{}\"\nout $ (sine 440 * 0.5) # azimuth 90",
+        dir.path().display()
+    );
+    let channels = render_channels(&code);
+    assert!(calculate_rms(&channels[1]) > calculate_rms(&channels[0]) * 2.0);
+
+    let error = compile_code("spatial: binaural \"no-such-set\"\nout $ sine 440")
+        .err()
+        .unwrap();
+    assert!(error.contains("HRIR set not found"), "{}", error);
+}
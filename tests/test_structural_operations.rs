@@ -1,7 +1,9 @@
 /// Test structural pattern operations: overlay, append
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_overlay_transform() {
     // overlay should layer two patterns on top of each other
     // "bd sn" overlayed with "hh*4" should play both simultaneously
@@ -27,6 +29,7 @@ fn test_overlay_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_append_transform() {
     // append should concatenate two patterns sequentially
     // "bd sn" appended with "hh cp" should play bd/sn first, then hh/cp
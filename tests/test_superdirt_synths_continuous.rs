@@ -1,11 +1,13 @@
 //! Test SuperDirt synths - are they continuous or gated?
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod audio_test_utils;
 use audio_test_utils::calculate_rms;
 
 #[test]
+#[allow(deprecated)]
 fn test_superkick_is_continuous_or_gated() {
     let input = r#"
         tempo 1.0
@@ -71,6 +73,7 @@ fn test_superkick_is_continuous_or_gated() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_supersaw_is_continuous_or_gated() {
     let input = r#"
         tempo 1.0
@@ -122,6 +125,7 @@ fn test_supersaw_is_continuous_or_gated() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_superkick_with_samples_comparison() {
     // Compare superkick vs sample-based kick
     let input_superkick = r#"
@@ -1,9 +1,11 @@
 use phonon::superdirt_synths::SynthLibrary;
 use phonon::unified_graph::{Signal, UnifiedSignalGraph};
 /// Isolated test for SuperSaw with pattern frequency
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_supersaw_constant_freq_baseline() {
     // Phonon DSL is space-separated (parentheses/commas are not supported).
     let input = "out $ supersaw 110 0.5 5 * 0.2";
@@ -20,6 +22,7 @@ fn test_supersaw_constant_freq_baseline() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_supersaw_pattern_freq_from_dsl() {
     // Phonon DSL is space-separated; the pattern is a quoted first argument.
     let input = r#"out $ supersaw "110 220" 0.5 5 * 0.2"#;
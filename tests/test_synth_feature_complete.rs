@@ -6,9 +6,11 @@
 //! 3. Trigger them with proper timing
 //! 4. Apply envelopes for percussive sounds
 
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
 use std::collections::HashMap;
 
@@ -28,6 +30,7 @@ fn test_feature_channel_references_work() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_feature_synth_triggering_works() {
     // ✓ Synths can be triggered from patterns
     let mut executor = SimpleDspExecutor::new(44100.0);
@@ -135,6 +138,7 @@ fn test_feature_voice_polyphony() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_feature_complete_integration() {
     // ✓ Complete integration: patterns trigger synths with envelopes
     println!("\n=== FEATURE COMPLETE TEST ===");
@@ -5,12 +5,14 @@
 //! 2. Does lpf() actually filter? (should reduce high frequencies)
 //! 3. Do we need pattern-triggered gates for synths?
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod audio_test_utils;
 use audio_test_utils::calculate_rms;
 
 #[test]
+#[allow(deprecated)]
 fn test_saw_synth_is_continuous_not_gated() {
     // Test the example we gave the user
     let input = r#"
@@ -78,6 +80,7 @@ fn test_saw_synth_is_continuous_not_gated() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_lpf_actually_filters() {
     // Test 1: Raw saw wave (no filter)
     let input_raw = r#"
@@ -167,6 +170,7 @@ fn test_lpf_actually_filters() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_what_user_actually_wants_pattern_triggered_synth() {
     // This is what the user WANTS to work:
     // - Pattern triggers the synth (like "bd sd bd sd" triggers samples)
@@ -1,11 +1,13 @@
 //! Test if synths can be spawned polyphonically
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod audio_test_utils;
 use audio_test_utils::{calculate_rms, find_dominant_frequency};
 
 #[test]
+#[allow(deprecated)]
 fn test_saw_synth_produces_audio() {
     let input = "tempo: 1.0\nout $ saw 110 * 0.3";
 
@@ -62,6 +64,7 @@ fn test_synth_with_note_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_samples_vs_synths_comparison() {
     println!("\n=== Samples vs Synths ===\n");
 
@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::glicol_parser::parse_glicol;
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::simple_dsp_executor::SimpleDspExecutor;
 use std::collections::HashMap;
 
@@ -51,6 +53,7 @@ fn test_channel_reference_parsing() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_synth_triggering_basic() {
     println!("\n=== Testing Basic Synth Triggering ===");
 
@@ -81,6 +84,7 @@ fn test_synth_triggering_basic() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_alternating_synths() {
     println!("\n=== Testing Alternating Synth Patterns ===");
 
@@ -138,6 +142,7 @@ fn test_alternating_synths() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_synth_with_frequency_parameter() {
     println!("\n=== Testing Synth with Frequency Parameter ===");
 
@@ -169,6 +174,7 @@ fn test_synth_with_frequency_parameter() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_euclidean_with_synths() {
     println!("\n=== Testing Euclidean Patterns with Synths ===");
 
@@ -196,6 +202,7 @@ fn test_euclidean_with_synths() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_polyrhythm_with_synths() {
     println!("\n=== Testing Polyrhythm with Synths ===");
 
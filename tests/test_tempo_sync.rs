@@ -0,0 +1,74 @@
+/// Tests for following an external tempo: tapping the beat (Alt+T) and
+/// `:sync midi|tap|off`
+use crossterm::event::{KeyCode, KeyModifiers};
+use phonon::modal_editor::test_harness::EditorTestHarness;
+use std::thread::sleep;
+use std::time::Duration;
+
+const CLICKS: &str = "tempo: 2.0\n~clicks $ \"1 0\"\nout $ ~clicks";
+
+fn tap(harness: &mut EditorTestHarness) {
+    harness.send_key_with_modifiers(KeyCode::Char('t'), KeyModifiers::ALT);
+}
+
+#[test]
+fn test_tapped_tempo_sets_cps_and_outlasts_evaluation() {
+    let mut harness = EditorTestHarness::with_content(CLICKS).unwrap();
+    harness.ctrl_x();
+    harness.render_live_chunks(4).unwrap();
+
+    tap(&mut harness);
+    assert!(harness.status_message().contains("keep tapping"));
+    assert_eq!(harness.get_cps(), Some(2.0));
+
+    // A beat every 400ms is 150 BPM: 0.625 cps at four beats a cycle
+    for _ in 0..3 {
+        sleep(Duration::from_millis(400));
+        tap(&mut harness);
+    }
+    assert!(
+        harness.status_message().contains("Tapped"),
+        "{}",
+        harness.status_message()
+    );
+    let cps = harness.get_cps().unwrap();
+    assert!((cps - 0.625).abs() < 0.02, "{}", cps);
+
+    // Re-evaluating keeps the tapped tempo over the code's
+    harness.ctrl_x();
+    harness.render_live_chunks(4).unwrap();
+    assert_eq!(harness.get_cps(), Some(cps));
+
+    // Off: the code's tempo is back from the next evaluation
+    harness.console_command(":sync off");
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|m| m.contains("Sync off")));
+    harness.ctrl_x();
+    assert_eq!(harness.get_cps(), Some(2.0));
+}
+
+#[test]
+fn test_sync_command_sources() {
+    let mut harness = EditorTestHarness::with_content(CLICKS).unwrap();
+    harness.ctrl_x();
+
+    harness.console_command(":sync tap");
+    assert!(harness.status_message().contains("Tap the beat with M-t"));
+
+    // No such port (or no MIDI here at all): the taps are still followed
+    harness.console_command(":sync midi no-such-clock");
+    assert!(harness
+        .console_messages()
+        .iter()
+        .any(|m| m.contains("Can't follow MIDI clock")));
+    tap(&mut harness);
+    assert!(harness.status_message().contains("keep tapping"));
+
+    harness.console_command(":sync");
+    assert!(harness
+        .command_output()
+        .iter()
+        .any(|line| line.contains("Usage: :sync midi [device] | tap | off")));
+}
@@ -0,0 +1,161 @@
+/// Tests for Tidal's both-structure operators: |+|, |-|, |*|, |/|, |>|, |<|
+///
+/// In Tidal semantics the `|op|` form takes structure from BOTH sides:
+/// each pair of overlapping events yields one event whose whole is the
+/// intersection of the two wholes. Reference values below were taken from
+/// Tidal 1.9 (`queryArc ("1 2" |+| "10 20 30") (Arc 0 1)`).
+use phonon::mini_notation_v3::parse_mini_notation;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+fn numeric(s: &str) -> Pattern<f64> {
+    parse_mini_notation(s).fmap(|v| v.parse::<f64>().unwrap_or(0.0))
+}
+
+/// Query one cycle, returning (whole begin, whole end, value) sorted by onset
+fn events(p: &Pattern<f64>, cycle: i64) -> Vec<(f64, f64, f64)> {
+    let state = State {
+        span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+        controls: std::collections::HashMap::new(),
+    };
+    let mut haps = p.query(&state);
+    haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+    haps.iter()
+        .map(|h| {
+            let whole = h.whole.expect("discrete events should have a whole");
+            (whole.begin.to_float(), whole.end.to_float(), h.value)
+        })
+        .collect()
+}
+
+// ============================================================================
+// Pattern-Level Tests (Tidal reference behavior)
+// ============================================================================
+
+#[test]
+fn test_add_sect_matches_tidal_reference() {
+    // Tidal: (0>⅓)|11  (⅓>½)|21  (½>⅔)|22  (⅔>1)|32
+    let result = events(&numeric("1 2").add_sect(numeric("10 20 30")), 0);
+    let expected = [
+        (0.0, 1.0 / 3.0, 11.0),
+        (1.0 / 3.0, 0.5, 21.0),
+        (0.5, 2.0 / 3.0, 22.0),
+        (2.0 / 3.0, 1.0, 32.0),
+    ];
+    assert_eq!(result.len(), expected.len(), "events: {:?}", result);
+    for ((begin, end, value), (exp_begin, exp_end, exp_value)) in result.iter().zip(expected) {
+        assert!(
+            (begin - exp_begin).abs() < 1e-5,
+            "begin {} != {}",
+            begin,
+            exp_begin
+        );
+        assert!((end - exp_end).abs() < 1e-5, "end {} != {}", end, exp_end);
+        assert_eq!(*value, exp_value);
+    }
+}
+
+#[test]
+fn test_add_sect_with_alternation() {
+    // note ("0 3 5" |+| "<0 12>") transposes every other cycle
+    let p = numeric("0 3 5").add_sect(numeric("<0 12>"));
+    let cycle0: Vec<f64> = events(&p, 0).iter().map(|e| e.2).collect();
+    let cycle1: Vec<f64> = events(&p, 1).iter().map(|e| e.2).collect();
+    assert_eq!(cycle0, vec![0.0, 3.0, 5.0]);
+    assert_eq!(cycle1, vec![12.0, 15.0, 17.0]);
+}
+
+#[test]
+fn test_both_structure_vs_left_and_right() {
+    // |+ keeps left structure, +| keeps right, |+| splits at both
+    let left = numeric("1 2");
+    let right = numeric("10 20 30");
+    assert_eq!(events(&left.clone().add_left(right.clone()), 0).len(), 2);
+    assert_eq!(events(&left.clone().add_right(right.clone()), 0).len(), 3);
+    assert_eq!(events(&left.add_sect(right), 0).len(), 4);
+}
+
+#[test]
+fn test_sub_mul_div_sect_values() {
+    let sub: Vec<f64> = events(&numeric("10 20").sub_sect(numeric("1 2 3")), 0)
+        .iter()
+        .map(|e| e.2)
+        .collect();
+    assert_eq!(sub, vec![9.0, 8.0, 18.0, 17.0]);
+
+    let mul: Vec<f64> = events(&numeric("1 2").mul_sect(numeric("2 3 4")), 0)
+        .iter()
+        .map(|e| e.2)
+        .collect();
+    assert_eq!(mul, vec![2.0, 3.0, 6.0, 8.0]);
+
+    let div: Vec<f64> = events(&numeric("12 24").div_sect(numeric("2 3 4")), 0)
+        .iter()
+        .map(|e| e.2)
+        .collect();
+    assert_eq!(div, vec![6.0, 4.0, 8.0, 6.0]);
+}
+
+#[test]
+fn test_union_sect_both_directions() {
+    // |>| keeps right values, |<| keeps left values, both split at every boundary
+    let right: Vec<f64> = events(&numeric("1 2").union_sect(numeric("7 8 9")), 0)
+        .iter()
+        .map(|e| e.2)
+        .collect();
+    assert_eq!(right, vec![7.0, 8.0, 8.0, 9.0]);
+
+    let left: Vec<f64> = events(&numeric("1 2").union_sect_left(numeric("7 8 9")), 0)
+        .iter()
+        .map(|e| e.2)
+        .collect();
+    assert_eq!(left, vec![1.0, 1.0, 2.0, 2.0]);
+}
+
+#[test]
+fn test_rest_on_either_side_silences_event() {
+    let result: Vec<f64> = events(&numeric("1 ~ 3 4").add_sect(numeric("10 10 ~ 10")), 0)
+        .iter()
+        .map(|e| e.2)
+        .collect();
+    assert_eq!(result, vec![11.0, 14.0]);
+}
+
+// ============================================================================
+// DSL Compilation Tests
+// ============================================================================
+
+#[test]
+fn test_note_with_add_both_compiles() {
+    let code = r#"out $ s "bd*3" # note ("0 3 5" |+| "<0 12>")"#;
+    if let Err(e) = compile_code(code) {
+        panic!("Should compile: {}", e);
+    }
+}
+
+#[test]
+fn test_all_both_operators_compile() {
+    for op in ["|+|", "|-|", "|*|", "|/|", "|>|", "|<|"] {
+        let code = format!(
+            "~freq $ \"220 330\" {} \"1 2 3\"\nout $ sine ~freq * 0.2",
+            op
+        );
+        if let Err(e) = compile_code(&code) {
+            panic!("{} should compile: {}", op, e);
+        }
+    }
+}
+
+#[test]
+fn test_mul_both_renders_audio() {
+    let code = r#"
+tempo: 1.0
+out $ sine ("110 220" |*| "<1 2>") * 0.3
+"#;
+    let mut graph = compile_code(code).expect("Should compile");
+    let buffer = graph.render(44100);
+    let rms = (buffer.iter().map(|s| s * s).sum::<f32>() / buffer.len() as f32).sqrt();
+    assert!(rms > 0.05, "Expected audible output, got RMS {}", rms);
+}
@@ -0,0 +1,72 @@
+/// Tests for `phonon convert --from tidal`: best-effort Tidal → Phonon translation
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::tidal_convert::convert_tidal;
+
+const SKETCH: &str = r#"-- a Tidal sketch
+setcps (120/60/4)
+
+d1 $ every 4 (fast 2) $ sound "bd*2 [~ sn]" # cutoff 800 # resonance 0.3
+
+d2 $ 0.25 <~ n "0 2 4" # s "arpy" # room 0.3 # squiz 2
+
+d1 $ sound "bd sn"
+  # gain 0.9
+
+hush
+"#;
+
+#[test]
+fn test_converts_channels_into_buses() {
+    let conversion = convert_tidal(SKETCH);
+    let lines: Vec<&str> = conversion.code.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "-- a Tidal sketch",
+            "tempo: 0.5",
+            "-- ~d1 $ s \"bd*2 [~ sn]\" $ every 4 (fast 2) # lpf 800 0.3",
+            "~d2 $ s \"arpy\" $ early 0.25 # n \"0 2 4\" # squiz 2 # reverb 0.5 0.5 0.3",
+            "~d1 $ s \"bd sn\" # gain 0.9",
+            "out $ ~d2 + ~d1",
+        ]
+    );
+    assert_eq!(
+        conversion.warnings,
+        vec!["line 4: d1 is redefined later; keeping the last version"]
+    );
+
+    // The result is a working Phonon program
+    let (rest, statements) = parse_program(&conversion.code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    assert!(compile_program(statements, 44100.0, None).is_ok());
+}
+
+#[test]
+fn test_transforms_become_postfix() {
+    let conversion = convert_tidal("d1 $ jux rev $ (fast 2 . degrade) $ s \"hh*8\"");
+    assert_eq!(
+        conversion.code,
+        "~d1 $ s \"hh*8\" $ degrade $ fast 2 $ jux rev\nout $ ~d1\n"
+    );
+    assert!(conversion.warnings.is_empty());
+
+    // Unknown functions are dropped with a warning; the rest still converts
+    let conversion = convert_tidal("p \"drums\" $ someNewThing 3 $ density 2 $ s \"bd\"");
+    assert_eq!(
+        conversion.code,
+        "~drums $ s \"bd\" $ fast 2\nout $ ~drums\n"
+    );
+    assert_eq!(
+        conversion.warnings,
+        vec!["line 1: `someNewThing` is not supported (dropped)"]
+    );
+
+    // `let` bindings and silenced channels produce no buses
+    let conversion = convert_tidal("let pat = \"bd sn\"\nd1 silence");
+    assert_eq!(conversion.code, "\n");
+    assert_eq!(
+        conversion.warnings,
+        vec!["line 1: `let` is not supported (dropped)"]
+    );
+}
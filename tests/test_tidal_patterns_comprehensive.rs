@@ -1,7 +1,9 @@
 /// Comprehensive tests for Tidal Cycles patterns via s() function
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_basic_sample_sequence() {
     let input = r#"out $ s "bd sn hh cp""#;
     let (_, statements) = parse_dsl(input).unwrap();
@@ -17,6 +19,7 @@ fn test_basic_sample_sequence() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_subdivision_pattern() {
     let input = r#"out $ s "bd*4""#; // 4 kicks per cycle
     let (_, statements) = parse_dsl(input).unwrap();
@@ -32,6 +35,7 @@ fn test_subdivision_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_rest_pattern() {
     let input = r#"out $ s "bd ~ sn ~""#; // Kick, rest, snare, rest
     let (_, statements) = parse_dsl(input).unwrap();
@@ -47,6 +51,7 @@ fn test_rest_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_euclidean_rhythm() {
     let input = r#"out $ s "bd(3,8)""#; // 3 kicks distributed over 8 steps
     let (_, statements) = parse_dsl(input).unwrap();
@@ -62,6 +67,7 @@ fn test_euclidean_rhythm() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_alternation_pattern() {
     let input = r#"out $ s "<bd sn hh>""#; // Alternates each cycle
     let (_, statements) = parse_dsl(input).unwrap();
@@ -77,6 +83,7 @@ fn test_alternation_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_sample_selection() {
     let input = r#"out $ s "bd:0 bd:1 bd:2""#; // Different kick samples
     let (_, statements) = parse_dsl(input).unwrap();
@@ -92,6 +99,7 @@ fn test_sample_selection() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pattern_with_gain_modulation() {
     let input = r#"out $ s "bd*4" # gain "1.0 0.8 0.6 0.4""#; // Decreasing gain
     let (_, statements) = parse_dsl(input).unwrap();
@@ -107,6 +115,7 @@ fn test_pattern_with_gain_modulation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pattern_with_speed_modulation() {
     let input = r#"out $ s "bd*4" # speed "1.0 1.2 0.8 1.5""#; // Speed changes
     let (_, statements) = parse_dsl(input).unwrap();
@@ -122,6 +131,7 @@ fn test_pattern_with_speed_modulation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_layered_pattern() {
     let input = r#"out $ s "[bd, hh*8]""#; // Kick and hi-hats together
     let (_, statements) = parse_dsl(input).unwrap();
@@ -137,6 +147,7 @@ fn test_layered_pattern() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_classic_house_beat() {
     // Classic four-on-the-floor with hi-hats
     let input = r#"out $ s "[bd*4, hh*8, ~ sn ~ sn]""#;
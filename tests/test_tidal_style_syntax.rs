@@ -7,12 +7,14 @@
 //!
 //! NOT positional args like s("bd", 0.5)
 
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 mod audio_test_utils;
 use audio_test_utils::calculate_rms;
 
 /// Helper to compile and render DSL
+#[allow(deprecated)]
 fn compile_and_render(input: &str, duration_samples: usize) -> Vec<f32> {
     let (_, statements) = parse_dsl(input).expect("Failed to parse DSL");
     let compiler = DslCompiler::new(44100.0);
@@ -0,0 +1,74 @@
+/// Tests for the tilt EQ (`# tilt`) and oversampled saturation (`# saturate`)
+mod audio_test_utils;
+use audio_test_utils::{calculate_rms, compile_code, magnitude_at, render_code};
+
+const SAMPLE_RATE: f32 = 44100.0;
+
+/// RMS after the filters have settled
+fn settled_rms(code: &str) -> f32 {
+    calculate_rms(&render_code(code, 22050)[4410..])
+}
+
+#[test]
+fn test_tilt_balances_lows_against_highs() {
+    let ratio = |freq: u32, fx: &str| {
+        settled_rms(&format!("out $ sine {} # {}", freq, fx))
+            / settled_rms(&format!("out $ sine {}", freq))
+    };
+
+    // +6dB of tilt: lows down 3dB, highs up 3dB
+    let low = ratio(100, "tilt 6");
+    let high = ratio(8000, "tilt 6");
+    assert!((low - 0.708).abs() < 0.07, "100Hz: {}", low);
+    assert!((high - 1.413).abs() < 0.14, "8kHz: {}", high);
+
+    // Negative tilt darkens; the pivot itself is left alone
+    assert!(ratio(8000, "tilt -6") < 0.8);
+    let pivot = ratio(800, "tilt 6 :pivot 800");
+    assert!((pivot - 1.0).abs() < 0.1, "pivot: {}", pivot);
+
+    // The air shelf lifts only the top
+    assert!(ratio(18000, "tilt 0 :air 6") > 1.6);
+    assert!((ratio(200, "tilt 0 :air 6") - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn test_saturate_adds_harmonics_without_aliasing() {
+    let skip = 8820;
+    for curve in ["tape", "transformer"] {
+        let out = render_code(
+            &format!("out $ sine 3000 # saturate 4 :curve \"{}\"", curve),
+            skip + SAMPLE_RATE as usize,
+        );
+        let out = &out[skip..];
+        let fundamental = magnitude_at(out, SAMPLE_RATE, 3000.0);
+        let third = magnitude_at(out, SAMPLE_RATE, 9000.0);
+        // The 11th harmonic (33kHz) would fold back to 11.1kHz without
+        // oversampling
+        let alias = magnitude_at(out, SAMPLE_RATE, 11100.0);
+
+        assert!(fundamental > 0.1, "{}: fundamental {}", curve, fundamental);
+        assert!(
+            third > fundamental * 0.01,
+            "{}: 3rd harmonic {}",
+            curve,
+            third
+        );
+        assert!(alias < fundamental * 0.001, "{}: alias {}", curve, alias);
+    }
+}
+
+#[test]
+fn test_saturate_mix_and_errors() {
+    // Fully dry is the input, delayed by the oversampling filters
+    let dry = settled_rms("out $ sine 440");
+    let mixed = settled_rms("out $ sine 440 # saturate 10 :mix 0");
+    assert!((mixed / dry - 1.0).abs() < 0.01, "{} vs {}", mixed, dry);
+
+    for code in [
+        "out $ sine 440 # saturate 2 :curve \"fuzz\"",
+        "out $ sine 440 # saturate 2 :curve 3",
+    ] {
+        assert!(compile_code(code).is_err(), "{}", code);
+    }
+}
@@ -1,7 +1,9 @@
 /// Test time-shifting operations: late, early, dup
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_late_transform() {
     // late should shift pattern forward in time
     let input = r#"
@@ -24,6 +26,7 @@ fn test_late_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_early_transform() {
     // early should shift pattern backward in time
     let input = r#"
@@ -46,6 +49,7 @@ fn test_early_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_dup_transform() {
     // dup should repeat pattern n times within one cycle
     let input_normal = r#"
@@ -87,6 +91,7 @@ fn test_dup_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_late_with_chained_transforms() {
     // late should work with other transforms
     let input = r#"
@@ -108,6 +113,7 @@ fn test_late_with_chained_transforms() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_early_with_chained_transforms() {
     // early should work with other transforms
     let input = r#"
@@ -1,7 +1,9 @@
 /// Test time signature support in BPM
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_bpm_with_time_signature_4_4() {
     // bpm 120 [4/4] should work
     let input = r#"
@@ -22,6 +24,7 @@ fn test_bpm_with_time_signature_4_4() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_bpm_with_time_signature_3_4() {
     // bpm 120 [3/4] (waltz time)
     let input = r#"
@@ -42,6 +45,7 @@ fn test_bpm_with_time_signature_3_4() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_bpm_with_time_signature_6_8() {
     // bpm 120 [6/8] (compound time)
     let input = r#"
@@ -62,6 +66,7 @@ fn test_bpm_with_time_signature_6_8() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_bpm_without_time_signature_defaults_4_4() {
     // bpm 120 (without brackets) should default to 4/4
     let input_without = r#"
@@ -2,7 +2,7 @@
 ///
 /// These are advanced probability and cycle-based conditional transforms:
 /// - sometimes_by(prob, f): Apply transform with custom probability
-/// - when_mod(n, offset, f): Apply on cycles where (cycle - offset) % n == 0
+/// - when_mod(n, offset, f): Apply on cycles where cycle % n >= offset (Tidal's whenmod)
 ///
 /// Note: rarely, often, sometimes, always were verified in TIER 1
 ///
@@ -138,7 +138,7 @@ fn test_sometimes_by_deterministic_per_cycle() {
 fn test_when_mod_level1_every_n_cycles() {
     let pattern = parse_mini_notation("bd sn");
 
-    // when_mod(3, 0, f) should apply on cycles 0, 3, 6, 9, ...
+    // when_mod(3, 2, f) should apply on the last cycle of every 3: 2, 5, 8, 11, ...
     for cycle in 0..12 {
         let state = State {
             span: TimeSpan::new(
@@ -151,10 +151,10 @@ fn test_when_mod_level1_every_n_cycles() {
         let base = pattern.query(&state);
         let when_mod = pattern
             .clone()
-            .when_mod(3, 0, |p| p.fast(Pattern::pure(2.0)))
+            .when_mod(3, 2, |p| p.fast(Pattern::pure(2.0)))
             .query(&state);
 
-        if cycle % 3 == 0 {
+        if cycle % 3 == 2 {
             // Should apply fast(2)
             assert_eq!(
                 when_mod.len(),
@@ -173,15 +173,15 @@ fn test_when_mod_level1_every_n_cycles() {
         }
     }
 
-    println!("✅ when_mod(3, 0): Applies on cycles 0, 3, 6, 9...");
+    println!("✅ when_mod(3, 2): Applies on cycles 2, 5, 8, 11...");
 }
 
 #[test]
 fn test_when_mod_with_offset() {
     let pattern = parse_mini_notation("bd sn hh cp");
 
-    // when_mod(4, 1, f) should apply on cycles where (cycle - 1) % 4 == 0
-    // i.e., cycles 1, 5, 9, 13...
+    // when_mod(4, 1, f) should apply on cycles where cycle % 4 >= 1
+    // i.e., all but cycles 0, 4, 8, 12...
     for cycle in 0..16 {
         let state = State {
             span: TimeSpan::new(
@@ -197,10 +197,11 @@ fn test_when_mod_with_offset() {
             .when_mod(4, 1, |p| p.fast(Pattern::pure(2.0)))
             .query(&state);
 
-        if (cycle - 1) % 4 == 0 {
+        if cycle % 4 >= 1 {
             // Should apply
-            assert!(
-                when_mod.len() >= base.len(),
+            assert_eq!(
+                when_mod.len(),
+                base.len() * 2,
                 "Cycle {}: should apply transform",
                 cycle
             );
@@ -215,19 +216,21 @@ fn test_when_mod_with_offset() {
         }
     }
 
-    println!("✅ when_mod(4, 1): Applies on cycles 1, 5, 9, 13... (with offset)");
+    println!("✅ when_mod(4, 1): Applies on all but cycles 0, 4, 8, 12... (with offset)");
 }
 
 #[test]
 fn test_when_mod_with_every() {
     let pattern = parse_mini_notation("bd sn");
 
-    // when_mod(2, 0, f) should apply on even cycles
-    // This should be similar to every(2, f)
+    // when_mod(2, 1, f) should apply on odd cycles, the last of every 2
+    // This should be the same as every'(2, 1, f)
     let when_mod_pattern = pattern
         .clone()
-        .when_mod(2, 0, |p| p.fast(Pattern::pure(2.0)));
-    let every_pattern = pattern.clone().every(2, |p| p.fast(Pattern::pure(2.0)));
+        .when_mod(2, 1, |p| p.fast(Pattern::pure(2.0)));
+    let every_pattern = pattern
+        .clone()
+        .every_offset(2, 1, |p| p.fast(Pattern::pure(2.0)));
 
     for cycle in 0..8 {
         let state = State {
@@ -244,12 +247,12 @@ fn test_when_mod_with_every() {
         assert_eq!(
             when_mod_haps.len(),
             every_haps.len(),
-            "when_mod(2, 0) should behave like every(2) on cycle {}",
+            "when_mod(2, 1) should behave like every'(2, 1) on cycle {}",
             cycle
         );
     }
 
-    println!("✅ when_mod(2, 0) equivalent to every(2)");
+    println!("✅ when_mod(2, 1) equivalent to every'(2, 1)");
 }
 
 // ============= Multi-cycle Tests =============
@@ -325,7 +328,7 @@ fn test_when_mod_different_modulos() {
             let base = pattern.query(&state);
             let when_mod = pattern
                 .clone()
-                .when_mod(modulo, 0, |p| p.fast(Pattern::pure(2.0)))
+                .when_mod(modulo, modulo - 1, |p| p.fast(Pattern::pure(2.0)))
                 .query(&state);
 
             if when_mod.len() > base.len() {
@@ -333,12 +336,15 @@ fn test_when_mod_different_modulos() {
             }
         }
 
-        // Should apply on cycles 0, modulo, modulo*2, ...
-        let expected: Vec<i32> = (0..20).filter(|c| c % modulo == 0).collect();
+        // Should apply on the last cycle of each phrase: modulo-1, modulo*2-1, ...
+        let expected: Vec<i32> = (0..20).filter(|c| c % modulo == modulo - 1).collect();
         assert_eq!(
-            applied_cycles, expected,
-            "when_mod({}) should apply on cycles divisible by {}",
-            modulo, modulo
+            applied_cycles,
+            expected,
+            "when_mod({}, {}) should apply on the last cycle of every {}",
+            modulo,
+            modulo - 1,
+            modulo
         );
 
         println!(
@@ -375,7 +381,7 @@ fn test_sometimes_by_nested() {
 fn test_when_mod_composition() {
     let pattern = parse_mini_notation("bd sn");
 
-    // when_mod(2, 0) $ when_mod(3, 0) should apply on cycles 0, 6, 12... (LCM)
+    // when_mod(2, 1) $ when_mod(3, 2) should apply on cycles 5, 11... (odd, and last of 3)
     for cycle in 0..12 {
         let state = State {
             span: TimeSpan::new(
@@ -388,10 +394,10 @@ fn test_when_mod_composition() {
         let base = pattern.query(&state);
         let composed = pattern
             .clone()
-            .when_mod(2, 0, |p| p.when_mod(3, 0, |p2| p2.fast(Pattern::pure(2.0))))
+            .when_mod(2, 1, |p| p.when_mod(3, 2, |p2| p2.fast(Pattern::pure(2.0))))
             .query(&state);
 
-        if cycle % 2 == 0 && cycle % 3 == 0 {
+        if cycle % 2 == 1 && cycle % 3 == 2 {
             // Both conditions met
             assert_eq!(
                 composed.len(),
@@ -482,11 +488,11 @@ fn test_when_mod_negative_cycle() {
         .when_mod(4, -1, |p| p.fast(Pattern::pure(2.0)))
         .query(&state);
 
-    // Cycle 0: (0 - (-1)) % 4 = 1 % 4 = 1 (not 0, so don't apply)
+    // Cycle 0: 0 % 4 = 0 >= -1, so it applies (as on every cycle)
     let base = pattern.query(&state);
     assert_eq!(
         when_mod.len(),
-        base.len(),
+        base.len() * 2,
         "Negative offset should work correctly"
     );
 
@@ -0,0 +1,91 @@
+/// Tests for `quantize` transform - snaps event onsets to a grid
+/// Tightens humanized or recorded timing against strict patterns
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::mini_notation_v3::parse_mini_notation;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+use std::collections::HashMap;
+
+fn cycle_state(begin: f64, end: f64) -> State {
+    State {
+        span: TimeSpan::new(Fraction::from_float(begin), Fraction::from_float(end)),
+        controls: HashMap::new(),
+    }
+}
+
+fn onsets(pattern: &Pattern<String>, begin: f64, end: f64) -> Vec<f64> {
+    let mut onsets: Vec<f64> = pattern
+        .query(&cycle_state(begin, end))
+        .iter()
+        .filter(|hap| hap.whole.map(|w| w.begin) == Some(hap.part.begin))
+        .map(|hap| hap.part.begin.to_float())
+        .collect();
+    onsets.sort_by(f64::total_cmp);
+    onsets
+}
+
+// ============================================================================
+// LEVEL 1: Pattern Query Verification
+// ============================================================================
+
+#[test]
+fn test_quantize_level1_snaps_to_grid() {
+    // Swing pushes the off-beats off the 16th grid; quantize pulls them back
+    let humanized = parse_mini_notation("bd sn hh cp").swing(Pattern::pure(0.02));
+    let quantized = humanized.quantize_onsets(Pattern::pure(16.0));
+
+    let onsets = onsets(&quantized, 0.0, 1.0);
+    assert_eq!(onsets.len(), 4);
+    for (onset, expected) in onsets.iter().zip([0.0, 0.25, 0.5, 0.75]) {
+        assert!(
+            (onset - expected).abs() < 1e-6,
+            "onset {} should snap to {}",
+            onset,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_quantize_level1_keeps_event_length() {
+    let pattern = parse_mini_notation("bd sn").late(Pattern::pure(0.1));
+    let quantized = pattern.quantize_onsets(Pattern::pure(4.0));
+
+    for hap in quantized.query(&cycle_state(0.0, 2.0)) {
+        let whole = hap.whole.unwrap();
+        assert!((whole.duration().to_float() - 0.5).abs() < 1e-6);
+        // 0.1 and 0.6 snap to 0 and 0.5
+        let grid = whole.begin.to_float() * 4.0;
+        assert!((grid - grid.round()).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_quantize_level1_snaps_across_cycle_boundary() {
+    // An onset at 0.95 snaps to 1.0, so it belongs to the next cycle
+    let pattern = parse_mini_notation("~ ~ ~ bd").late(Pattern::pure(0.2));
+    let quantized = pattern.quantize_onsets(Pattern::pure(4.0));
+
+    assert!(onsets(&quantized, 0.0, 1.0).iter().all(|&o| o < 1.0));
+    let next = onsets(&quantized, 1.0, 2.0);
+    assert!(next.iter().any(|&o| (o - 1.0).abs() < 1e-6), "{:?}", next);
+}
+
+// ============================================================================
+// LEVEL 2: DSL
+// ============================================================================
+
+#[test]
+fn test_quantize_dsl_compiles() {
+    let code = r#"
+tempo: 0.5
+~drums $ s "bd sn hh cp" $ swing 0.03 $ quantize 16
+out $ ~drums
+"#;
+    let (rest, statements) = parse_program(code).expect("Parse failed");
+    assert_eq!(rest.trim(), "");
+    compile_program(statements, 44100.0, None).expect("Compile failed");
+
+    let (_, statements) = parse_program("out $ s \"bd sn\" $ quantize 0").unwrap();
+    assert!(compile_program(statements, 44100.0, None).is_err());
+}
@@ -13,6 +13,7 @@
 
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -478,6 +479,7 @@ fn test_stutter_repeats_events() {
 // HELPER: Render DSL code
 // ============================================================================
 
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_secs: f32) -> Vec<f32> {
     let result = parse_dsl(code);
     if let Err(ref e) = result {
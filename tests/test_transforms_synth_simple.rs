@@ -3,6 +3,7 @@
 
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -36,6 +37,7 @@ fn count_events_over_cycles<T: Clone + Send + Sync + 'static>(
 // ============================================================================
 
 #[test]
+#[allow(deprecated)]
 fn test_fast_with_synthesis() {
     println!("\n=== FAST TRANSFORM TEST (Synthesis) ===");
 
@@ -94,6 +96,7 @@ fn test_fast_with_synthesis() {
     println!("✅ Fast transform verified with synthesis");
 }
 #[test]
+#[allow(deprecated)]
 fn test_plain_sine() {
     use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
@@ -9,6 +9,7 @@
 
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -40,6 +41,7 @@ fn count_events_over_cycles<T: Clone + Send + Sync + 'static>(
     total
 }
 
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_secs: f32) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Parse DSL");
     let compiler = DslCompiler::new(44100.0);
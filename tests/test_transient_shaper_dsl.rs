@@ -189,3 +189,36 @@ fn test_transient_shaper_boost_vs_neutral_level_difference() {
         rms_neutral
     );
 }
+
+#[test]
+fn test_transient_keyword_form_matches_transient_shaper() {
+    // `transient` takes gains, so dB literals read as written
+    let keyword = render_dsl(
+        "tempo: 0.5\nout $ s \"bd*4\" # transient :attack +6dB :sustain -3dB",
+        1.0,
+    );
+    let positional = render_dsl("tempo: 0.5\nout $ s \"bd*4\" # transient_shaper 6 -3", 1.0);
+    assert!(calculate_rms(&keyword) > 0.01);
+    for (a, b) in keyword.iter().zip(&positional) {
+        assert!((a - b).abs() < 1e-4, "{} vs {}", a, b);
+    }
+
+    // With no arguments it leaves the drums alone
+    let neutral = render_dsl("tempo: 0.5\nout $ s \"bd*4\" # transient", 1.0);
+    let dry = render_dsl("tempo: 0.5\nout $ s \"bd*4\"", 1.0);
+    for (a, b) in neutral.iter().zip(&dry) {
+        assert!((a - b).abs() < 1e-4, "{} vs {}", a, b);
+    }
+}
+
+#[test]
+fn test_transient_attack_boost_adds_punch() {
+    let peak = |code: &str| {
+        render_dsl(code, 0.5)
+            .iter()
+            .fold(0.0f32, |m, s| m.max(s.abs()))
+    };
+    let dry = peak("tempo: 0.5\nout $ s \"bd\"");
+    let punchy = peak("tempo: 0.5\nout $ s \"bd\" # transient :attack +6dB");
+    assert!(punchy > dry * 1.2, "{} vs {}", punchy, dry);
+}
@@ -0,0 +1,63 @@
+/// Tests for `tuning` statements: equal divisions, Scala scales and per-bus
+/// overrides
+///
+/// The global tuning is process-wide, so everything runs in one test
+mod audio_test_utils;
+use audio_test_utils::{compile_code, render_unlimited};
+
+fn assert_same(code: &str, reference: &str) {
+    let (a, b) = (
+        render_unlimited(code, 4410),
+        render_unlimited(reference, 4410),
+    );
+    for (i, (x, y)) in a.iter().zip(&b).enumerate() {
+        assert!((x - y).abs() < 1e-3, "{} vs {} at {}: {} vs {}", code, reference, i, x, y);
+    }
+}
+
+#[test]
+fn test_tunings() {
+    // 19 equal steps: A4 stays put, 12 steps up is 2^(12/19)
+    let a5_19edo = 440.0 * 2f64.powf(12.0 / 19.0);
+    assert_same("tuning 19edo\nout $ sine \"a4\" * 0.5", "out $ sine 440 * 0.5");
+    assert_same(
+        "tuning 19edo\nout $ sine a5 * 0.5",
+        &format!("out $ sine {} * 0.5", a5_19edo),
+    );
+
+    // Only the named bus is retuned
+    assert_same(
+        "tuning ~lead 19edo\n~lead $ sine \"a5\"\nout $ ~lead * 0.5",
+        &format!("out $ sine {} * 0.5", a5_19edo),
+    );
+    assert_same(
+        "tuning ~lead 19edo\n~lead $ sine 1\n~other $ sine \"a5\"\nout $ ~other * 0.5",
+        "out $ sine 880 * 0.5",
+    );
+
+    // After a call line it is still a statement of its own
+    assert_same(
+        "~lead $ sine \"a5\"\ntuning ~lead 19edo\nout $ ~lead * 0.5",
+        &format!("out $ sine {} * 0.5", a5_19edo),
+    );
+
+    // A just major scale from a Scala file, degree 0 on middle C
+    let dir = tempfile::tempdir().unwrap();
+    let scl = dir.path().join("just.scl");
+    std::fs::write(
+        &scl,
+        "! just.scl\nJust major\n 7\n!\n9/8\n5/4\n4/3\n3/2\n5/3\n15/8\n2/1\n",
+    )
+    .unwrap();
+    // Key 69 is degree 9 (a 5/4 above the octave) on 440, so C4 is 176
+    assert_same(
+        &format!("tuning \"{}\"\nout $ sine \"e4\" * 0.5", scl.display()),
+        "out $ sine 264 * 0.5",
+    );
+
+    // Without a tuning statement, back to standard tuning
+    assert_same("out $ sine \"a5\" * 0.5", "out $ sine 880 * 0.5");
+
+    assert!(compile_code("tuning \"/nonexistent/scale.scl\"").is_err());
+    assert!(compile_code("tuning ~nobus 19edo\nout $ sine 440").is_err());
+}
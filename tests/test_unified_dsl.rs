@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_parse_and_compile_dsl() {
     println!("\n=== Testing Unified DSL Parser and Compilation ===");
 
@@ -30,6 +32,7 @@ fn test_parse_and_compile_dsl() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_pattern_in_dsl() {
     println!("\n=== Testing Pattern Integration in DSL ===");
 
@@ -72,6 +75,7 @@ fn test_pattern_in_dsl() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_complex_modulation_dsl() {
     println!("\n=== Testing Complex Modulation in DSL ===");
 
@@ -110,6 +114,7 @@ fn test_complex_modulation_dsl() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_filter_chain_dsl() {
     println!("\n=== Testing Filter Chain in DSL ===");
 
@@ -126,6 +131,7 @@ fn test_filter_chain_dsl() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_arithmetic_in_dsl() {
     println!("\n=== Testing Arithmetic Expressions in DSL ===");
 
@@ -149,6 +155,7 @@ fn test_arithmetic_in_dsl() {
 
 /// Example of the full vision - patterns embedded in synthesis
 #[test]
+#[allow(deprecated)]
 fn test_pattern_driven_fm_synthesis() {
     println!("\n=== Testing Pattern-Driven FM Synthesis ===");
 
@@ -181,6 +188,7 @@ fn test_pattern_driven_fm_synthesis() {
 
 /// The ultimate test - sidechain compression using patterns
 #[test]
+#[allow(deprecated)]
 fn test_sidechain_with_patterns() {
     println!("\n=== Testing Sidechain Compression with Patterns ===");
 
@@ -0,0 +1,33 @@
+/// Tests for unit-suffixed numbers in the DSL: `7st`, `-6dB`, `250ms`, `1/4c`
+mod audio_test_utils;
+use audio_test_utils::render_unlimited;
+
+fn assert_same(a: &[f32], b: &[f32]) {
+    assert_eq!(a.len(), b.len());
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        assert!((x - y).abs() < 1e-4, "sample {}: {} vs {}", i, x, y);
+    }
+}
+
+#[test]
+fn test_units_match_hand_converted_values() {
+    let pairs = [
+        ("out $ sine 440 * -6dB", "out $ sine 440 * 0.501187"),
+        ("out $ sine (220 * 7st) * 0.5", "out $ sine 329.6276 * 0.5"),
+        ("out $ sine (440 * -100ct) * 0.5", "out $ sine 415.3047 * 0.5"),
+        ("out $ sine 440 * 500ms", "out $ sine 440 * 0.5"),
+    ];
+    for (with_units, by_hand) in pairs {
+        assert_same(
+            &render_unlimited(with_units, 4410),
+            &render_unlimited(by_hand, 4410),
+        );
+    }
+}
+
+#[test]
+fn test_fractional_cycles_literal() {
+    let fraction = render_unlimited("tempo: 1.0\nout $ lfo 1/4c :min 0 :max 0.5", 44100);
+    let decimal = render_unlimited("tempo: 1.0\nout $ lfo 0.25c :min 0 :max 0.5", 44100);
+    assert_same(&fraction, &decimal);
+}
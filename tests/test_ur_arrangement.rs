@@ -0,0 +1,102 @@
+//! Tests for `ur`: named fragments sequenced by a meta-pattern of their
+//! names, stretched over a number of cycles
+//!
+//! `ur 8 "intro drop drop fill" [:intro ..., :drop ..., :fill ...]` plays
+//! each name in the meta-pattern for 2 cycles, repeating every 8.
+
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::mini_notation_v3::parse_mini_notation;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+use std::collections::HashMap;
+
+fn cycle_values(pattern: &Pattern<String>, cycle: i64) -> Vec<String> {
+    let state = State {
+        span: TimeSpan::new(
+            Fraction::from_float(cycle as f64),
+            Fraction::from_float((cycle + 1) as f64),
+        ),
+        controls: HashMap::new(),
+    };
+    let mut haps = pattern.query(&state);
+    haps.sort_by(|a, b| a.part.begin.to_float().total_cmp(&b.part.begin.to_float()));
+    haps.into_iter().map(|hap| hap.value).collect()
+}
+
+fn arrangement() -> Pattern<String> {
+    Pattern::ur(
+        8.0,
+        parse_mini_notation("intro drop drop fill"),
+        vec![
+            ("intro".to_string(), parse_mini_notation("hh*2")),
+            ("drop".to_string(), parse_mini_notation("bd sn bd sn")),
+            ("fill".to_string(), parse_mini_notation("sn*8")),
+        ],
+    )
+}
+
+#[test]
+fn test_ur_sequences_fragments_over_period() {
+    let pattern = arrangement();
+    // Each name holds for 2 of the 8 cycles, fragments at their own speed
+    assert_eq!(cycle_values(&pattern, 0), vec!["hh", "hh"]);
+    assert_eq!(cycle_values(&pattern, 1), vec!["hh", "hh"]);
+    for cycle in 2..6 {
+        assert_eq!(cycle_values(&pattern, cycle), vec!["bd", "sn", "bd", "sn"]);
+    }
+    assert_eq!(cycle_values(&pattern, 6).len(), 8);
+    assert_eq!(cycle_values(&pattern, 7).len(), 8);
+    // And repeats
+    assert_eq!(cycle_values(&pattern, 8), vec!["hh", "hh"]);
+}
+
+#[test]
+fn test_ur_rests_and_unknown_names_are_silent() {
+    let pattern = Pattern::ur(
+        4.0,
+        parse_mini_notation("a ~ b a"),
+        vec![("a".to_string(), parse_mini_notation("bd"))],
+    );
+    assert_eq!(cycle_values(&pattern, 0), vec!["bd"]);
+    assert!(cycle_values(&pattern, 1).is_empty());
+    assert!(cycle_values(&pattern, 2).is_empty());
+    assert_eq!(cycle_values(&pattern, 3), vec!["bd"]);
+}
+
+#[test]
+fn test_ur_fragment_is_clipped_to_its_slot() {
+    // A 2-cycle meta-pattern with a half-cycle slot each
+    let pattern = Pattern::ur(
+        1.0,
+        parse_mini_notation("a b"),
+        vec![
+            ("a".to_string(), parse_mini_notation("bd*4")),
+            ("b".to_string(), parse_mini_notation("hh*4")),
+        ],
+    );
+    assert_eq!(cycle_values(&pattern, 0), vec!["bd", "bd", "hh", "hh"]);
+}
+
+#[test]
+fn test_ur_compiles_in_program() {
+    let code = r#"
+tempo: 0.5
+~song $ ur 8 "intro drop drop fill" [:intro "hh*4", :drop (s "bd*4"), :fill "sn*8"]
+out $ ~song * 0.8
+"#;
+    let (rest, statements) = parse_program(code).expect("Failed to parse");
+    assert_eq!(rest.trim(), "");
+    compile_program(statements, 44100.0, None).expect("Failed to compile");
+}
+
+#[test]
+fn test_ur_rejects_undefined_fragment() {
+    let code = r#"out $ ur 4 "intro verse" [:intro "hh*4"]"#;
+    let (_, statements) = parse_program(code).unwrap();
+    let error = compile_program(statements, 44100.0, None).err().unwrap();
+    assert!(error.contains("no fragment named 'verse'"), "{}", error);
+
+    let code = r#"out $ ur 4 "intro" ["hh*4"]"#;
+    let (_, statements) = parse_program(code).unwrap();
+    assert!(compile_program(statements, 44100.0, None).is_err());
+}
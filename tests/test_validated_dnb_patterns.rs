@@ -23,6 +23,7 @@ use phonon::audio_similarity::{
 };
 use phonon::mini_notation_v3::parse_mini_notation;
 use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 use std::collections::HashMap;
 
@@ -32,6 +33,7 @@ const SAMPLE_RATE: f32 = 44100.0;
 // Test Helpers
 // ============================================================================
 
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_secs: f32) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Parse DSL failed");
     let compiler = DslCompiler::new(SAMPLE_RATE);
@@ -18,6 +18,7 @@
 use phonon::audio_similarity::{
     detect_onsets, AudioSimilarityScorer, SimilarityConfig, SpectralFeatures,
 };
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 const SAMPLE_RATE: f32 = 44100.0;
@@ -27,6 +28,7 @@ const SAMPLE_RATE: f32 = 44100.0;
 // ============================================================================
 
 /// Render DSL code to audio samples
+#[allow(deprecated)]
 fn render_dsl(code: &str, duration_secs: f32) -> Vec<f32> {
     let (_, statements) = parse_dsl(code).expect("Parse DSL failed");
     let compiler = DslCompiler::new(SAMPLE_RATE);
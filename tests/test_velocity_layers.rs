@@ -0,0 +1,75 @@
+/// Tests for velocity-layered sample folders (`snare-v1`, `snare-v2`, ...)
+/// selected by `# amp`
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use phonon::sample_loader::set_extra_sample_dirs;
+
+/// Two kits with a soft (0.1) and a hard (0.2) layer: `velsplit` splits the
+/// levels evenly, `veltoml` sets the soft layer's top to 0.25
+fn layered_kits() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 44100,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    for kit in ["velsplit", "veltoml"] {
+        for (layer, level) in [(1, 0.1f32), (2, 0.2)] {
+            let folder = dir.path().join(format!("{}-v{}", kit, layer));
+            std::fs::create_dir(&folder).unwrap();
+            let mut writer = hound::WavWriter::create(folder.join("0.wav"), spec).unwrap();
+            for _ in 0..200 {
+                writer.write_sample(level).unwrap();
+            }
+            writer.finalize().unwrap();
+        }
+    }
+    std::fs::write(dir.path().join("veltoml.toml"), "layers = [0.25, 1.0]\n").unwrap();
+    dir
+}
+
+/// Peak of each of the 4 events in one cycle
+fn event_peaks(code: &str) -> Vec<f32> {
+    let (rest, stmts) = parse_program(code).expect("Failed to parse");
+    assert!(rest.trim().is_empty(), "Unparsed input: {:?}", rest);
+    let mut graph = compile_program(stmts, 44100.0, None).expect("Failed to compile");
+    graph
+        .render(44100)
+        .chunks(11025)
+        .map(|quarter| quarter.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+        .collect()
+}
+
+/// Peaks in units of the files' levels
+fn levels(code: &str) -> Vec<f32> {
+    let unit = event_peaks("tempo: 1.0\nout $ s \"velsplit-v2*4\"")[0] / 0.2;
+    event_peaks(code).iter().map(|peak| peak / unit).collect()
+}
+
+fn assert_levels(code: &str, expected: [f32; 4]) {
+    let actual = levels(code);
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 0.005, "{}: {:?}", code, actual);
+    }
+}
+
+#[test]
+fn test_amp_selects_and_scales_layers() {
+    let dir = layered_kits();
+    set_extra_sample_dirs(vec![dir.path().to_path_buf()]);
+
+    // Even split: 0..0.5 plays v1, 0.5..1 plays v2, each scaled so the
+    // layer's top level plays the file at unity
+    assert_levels(
+        "tempo: 1.0\nout $ s \"velsplit*4\" # amp \"0.25 1.0 0.5 0.75\"",
+        [0.05, 0.2, 0.1, 0.15],
+    );
+    // `veltoml.toml` moves the split to 0.25
+    assert_levels(
+        "tempo: 1.0\nout $ s \"veltoml*4\" # amp \"0.25 1.0 0.5 0.75\"",
+        [0.1, 0.2, 0.1, 0.15],
+    );
+    // No amp: full level, the hardest layer
+    assert_levels("tempo: 1.0\nout $ s \"velsplit*4\"", [0.2; 4]);
+}
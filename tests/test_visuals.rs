@@ -0,0 +1,59 @@
+/// Tests for the `visuals:` OSC feed
+use phonon::compositional_compiler::compile_program;
+use phonon::compositional_parser::parse_program;
+use rosc::{OscPacket, OscType};
+use std::net::UdpSocket;
+use std::time::Duration;
+
+#[test]
+fn test_visuals_sends_bundles() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket
+        .set_read_timeout(Some(Duration::from_secs(2)))
+        .unwrap();
+    let port = socket.local_addr().unwrap().port();
+
+    let code = format!(
+        "visuals: osc://127.0.0.1:{} 60fps\nout $ sine 1000 * 0.5",
+        port
+    );
+    let (_, statements) = parse_program(&code).unwrap();
+    let mut graph = compile_program(statements, 44100.0, None).unwrap();
+    graph.render(44100);
+
+    // Frames keep coming while the graph lives; once the audio has been
+    // analysed, the 1 kHz band is the loudest
+    let mut buf = [0u8; 1024];
+    let mut levels = Vec::new();
+    for _ in 0..300 {
+        let (size, _) = socket.recv_from(&mut buf).expect("no visuals frame");
+        let (_, packet) = rosc::decoder::decode_udp(&buf[..size]).unwrap();
+        let OscPacket::Bundle(bundle) = packet else {
+            panic!("expected a bundle");
+        };
+        assert_eq!(bundle.content.len(), 5);
+        let bands = bundle.content.into_iter().find_map(|packet| match packet {
+            OscPacket::Message(msg) if msg.addr == "/phonon/bands" => Some(msg.args),
+            _ => None,
+        });
+        levels = bands
+            .expect("no /phonon/bands")
+            .iter()
+            .map(|arg| match arg {
+                OscType::Float(level) => *level,
+                other => panic!("unexpected {:?}", other),
+            })
+            .collect();
+        if levels.iter().any(|level| *level > 0.01) {
+            break;
+        }
+    }
+    assert_eq!(levels.len(), 8);
+    let loudest = (0..8).max_by(|&a, &b| levels[a].total_cmp(&levels[b]));
+    assert_eq!(loudest, Some(3));
+
+    for bad in ["visuals: localhost:3333", "visuals: osc://localhost:3333 120"] {
+        let (_, statements) = parse_program(bad).unwrap();
+        assert!(compile_program(statements, 44100.0, None).is_err(), "{}", bad);
+    }
+}
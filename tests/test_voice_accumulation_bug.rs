@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_voice_count_does_not_accumulate() {
     // Create a simple alternating pattern
     let input = r#"
@@ -84,6 +86,7 @@ fn test_voice_count_does_not_accumulate() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_rms_does_not_grow_exponentially() {
     // Create a simple alternating pattern
     let input = r#"
@@ -139,6 +142,7 @@ fn test_rms_does_not_grow_exponentially() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_simple_pattern_voice_stability() {
     // Even simpler: just "bd" repeated
     let input = r#"
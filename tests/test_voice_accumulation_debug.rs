@@ -1,6 +1,8 @@
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_debug_event_triggering_alternation() {
     // Simple alternating pattern
     let input = r#"
@@ -86,6 +88,7 @@ fn test_debug_event_triggering_alternation() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_compare_alternating_vs_constant() {
     println!("\n=== COMPARING ALTERNATING VS CONSTANT PATTERNS ===\n");
 
@@ -0,0 +1,88 @@
+/// Tests for the per-voice SuperDirt effects (`# shape`, `# crush`,
+/// `# coarse`, `# squiz`) on sample patterns
+use phonon::sample_loader::set_extra_sample_dirs;
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+/// A kit whose only sample holds a constant 0.2
+fn constant_kit() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let folder = dir.path().join("fxconst");
+    std::fs::create_dir(&folder).unwrap();
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 44100,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(folder.join("0.wav"), spec).unwrap();
+    for _ in 0..2000 {
+        writer.write_sample(0.2f32).unwrap();
+    }
+    writer.finalize().unwrap();
+    dir
+}
+
+/// Peak of each of the 4 events in one cycle, in units of the file's level
+fn levels(code: &str) -> Vec<f32> {
+    let peaks = |code: &str| -> Vec<f32> {
+        compile_code(code)
+            .expect("Failed to compile")
+            .render(44100)
+            .chunks(11025)
+            .map(|quarter| quarter.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+            .collect()
+    };
+    let unit = peaks("tempo: 1.0\nout $ s \"fxconst*4\"")[0] / 0.2;
+    peaks(code).iter().map(|peak| peak / unit).collect()
+}
+
+fn assert_levels(code: &str, expected: [f32; 4]) {
+    let actual = levels(code);
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 0.005, "{}: {:?}", code, actual);
+    }
+}
+
+#[test]
+fn test_voice_fx_per_event() {
+    let dir = constant_kit();
+    set_extra_sample_dirs(vec![dir.path().to_path_buf()]);
+
+    // crush 4 rounds to steps of 1/8: 0.2 -> 0.25, per event
+    assert_levels(
+        "tempo: 1.0\nout $ s \"fxconst*4\" # crush \"0 4 0 4\"",
+        [0.2, 0.25, 0.2, 0.25],
+    );
+    // shape 0.5: 3 * 0.2 / (1 + 2 * 0.2)
+    assert_levels(
+        "tempo: 1.0\nout $ s \"fxconst*4\" # shape \"<0.5>\"",
+        [0.428_571; 4],
+    );
+    // Combined effects run shape first, then crush, whatever the chain order
+    for code in [
+        "tempo: 1.0\nout $ s \"fxconst*4\" # shape 0.5 # crush 4",
+        "tempo: 1.0\nout $ s \"fxconst*4\" # crush 4 # shape 0.5",
+    ] {
+        assert_levels(code, [0.375; 4]);
+    }
+    // Later sample parameters keep the effects
+    assert_levels(
+        "tempo: 1.0\nout $ s \"fxconst*4\" # crush 4 # gain 0.5",
+        [0.125; 4],
+    );
+}
+
+#[test]
+fn test_voice_fx_outside_sample_patterns() {
+    // crush and coarse fall back to processing the whole signal
+    assert!(compile_code("out $ saw 110 # crush 4").is_ok());
+    assert!(compile_code("out $ saw 110 # coarse 4").is_ok());
+    assert!(compile_code("out $ s \"bd*4\" # crush (sine 1 * 4 + 8)").is_ok());
+
+    // shape and squiz only work per voice
+    let err = compile_code("out $ saw 110 # shape 0.5").err().unwrap();
+    assert!(err.contains("per voice"), "{}", err);
+    assert!(compile_code("out $ s \"bd*4\" # squiz \"1 2\"").is_ok());
+}
@@ -0,0 +1,78 @@
+/// Tests for orbits: `# orbit N` sends a sample pattern's voices to `orbit N`
+/// instead of the pattern's own output
+use phonon::sample_loader::set_extra_sample_dirs;
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+/// A kit whose only sample holds a constant 0.2
+fn constant_kit() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let folder = dir.path().join("orbconst");
+    std::fs::create_dir(&folder).unwrap();
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 44100,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut writer = hound::WavWriter::create(folder.join("0.wav"), spec).unwrap();
+    for _ in 0..2000 {
+        writer.write_sample(0.2f32).unwrap();
+    }
+    writer.finalize().unwrap();
+    dir
+}
+
+/// Peak of each of the 4 events in one cycle, relative to a plain event
+fn levels(code: &str) -> Vec<f32> {
+    let peaks = |code: &str| -> Vec<f32> {
+        compile_code(code)
+            .expect("Failed to compile")
+            .render(44100)
+            .chunks(11025)
+            .map(|quarter| quarter.iter().fold(0.0f32, |m, s| m.max(s.abs())))
+            .collect()
+    };
+    let unit = peaks("tempo: 1.0\nout $ s \"orbconst*4\"")[0];
+    peaks(code).iter().map(|peak| peak / unit).collect()
+}
+
+fn assert_levels(code: &str, expected: [f32; 4]) {
+    let actual = levels(code);
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 0.05, "{}: {:?}", code, actual);
+    }
+}
+
+#[test]
+fn test_orbit_moves_voices_out_of_their_pattern() {
+    let dir = constant_kit();
+    set_extra_sample_dirs(vec![dir.path().to_path_buf()]);
+
+    // No `orbit 1` to play it: the voices stay where they are
+    assert_levels(
+        "tempo: 1.0\nout $ s \"orbconst*4\" # orbit \"0 1 0 1\"",
+        [1.0; 4],
+    );
+    // With one, orbit 1's events leave the pattern...
+    assert_levels(
+        "tempo: 1.0\n~d $ s \"orbconst*4\" # orbit \"0 1 0 1\"\n~o $ orbit 1\nout $ ~d",
+        [1.0, 0.0, 1.0, 0.0],
+    );
+    // ...and are heard through it, with its own processing
+    assert_levels(
+        "tempo: 1.0\n~d $ s \"orbconst*4\" # orbit \"0 1 0 1\"\nout $ ~d + orbit 1 * 0.5",
+        [1.0, 0.5, 1.0, 0.5],
+    );
+}
+
+#[test]
+fn test_orbit_arguments() {
+    assert!(compile_code("out $ s \"bd*4\" # orbit 2").is_ok());
+    assert!(compile_code("out $ orbit 1 # lpf 1000 0.5").is_ok());
+
+    let err = compile_code("out $ orbit 0").err().unwrap();
+    assert!(err.contains("orbit number"), "{}", err);
+    assert!(compile_code("out $ saw 110 # orbit 1").is_err());
+}
@@ -0,0 +1,60 @@
+/// Tests for the West Coast voice: `s "westcoast" # fold ... # strike ...`
+mod audio_test_utils;
+use audio_test_utils::{compile_code, find_peak, render_code};
+
+fn upward_crossings(samples: &[f32]) -> usize {
+    samples
+        .windows(2)
+        .filter(|w| w[0] <= 0.0 && w[1] > 0.0)
+        .count()
+}
+
+#[test]
+fn test_westcoast_plays_notes() {
+    let audio = render_code(
+        "tempo: 1.0\nout $ s \"westcoast*4\" # fold \"0.2 0.8\" # strike 0.7",
+        44100,
+    );
+    assert!(audio.iter().all(|s| s.is_finite()));
+    // Every quarter starts with a strike
+    for quarter in audio.chunks(11025) {
+        assert!(
+            find_peak(&quarter[..2000]) > 0.1,
+            "{}",
+            find_peak(&quarter[..2000])
+        );
+    }
+
+    // Unstruck, the gate stays shut
+    let silent = render_code("tempo: 1.0\nout $ s \"westcoast\" # strike 0", 22050);
+    assert!(find_peak(&silent) < 1e-6);
+}
+
+#[test]
+fn test_westcoast_follows_note_and_strike() {
+    // First 40 ms of a plain sine note, an octave apart
+    let crossings = |code: &str| upward_crossings(&render_code(code, 1764));
+    let low = crossings("tempo: 1.0\nout $ s \"westcoast\" # note 0 # strike 1");
+    let high = crossings("tempo: 1.0\nout $ s \"westcoast\" # note 12 # strike 1");
+    assert!((2 * low).abs_diff(high) <= 2, "{} vs {}", low, high);
+
+    let soft = find_peak(&render_code(
+        "tempo: 1.0\nout $ s \"westcoast\" # strike 0.3",
+        4410,
+    ));
+    let hard = find_peak(&render_code(
+        "tempo: 1.0\nout $ s \"westcoast\" # strike 0.9",
+        4410,
+    ));
+    assert!(hard > soft * 1.5, "{} vs {}", soft, hard);
+}
+
+#[test]
+fn test_fold_and_strike_arguments() {
+    // Bounds keep `fold` a wavefolder over the whole signal
+    assert!(compile_code("out $ s \"bd*4\" # fold -1 1").is_ok());
+    assert!(compile_code("out $ saw 110 # fold -0.5 0.5").is_ok());
+
+    let err = compile_code("out $ saw 110 # strike 0.5").err().unwrap();
+    assert!(err.contains("westcoast"), "{}", err);
+}
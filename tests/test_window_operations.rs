@@ -1,7 +1,9 @@
 /// Test time window operations: zoom, focus, within
+#[allow(deprecated)]
 use phonon::unified_graph_parser::{parse_dsl, DslCompiler};
 
 #[test]
+#[allow(deprecated)]
 fn test_zoom_transform() {
     // zoom should focus on a portion of the pattern cycle
     // zoom 0.0 0.5 focuses on first half of pattern
@@ -25,6 +27,7 @@ fn test_zoom_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_focus_transform() {
     // focus should zoom to a specific section
     // focus 0.25 0.75 focuses on middle half
@@ -47,6 +50,7 @@ fn test_focus_transform() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_within_transform() {
     // within should apply a transform to a time window
     // within 0.25 0.75 (fast 2) applies fast(2) to middle half
@@ -181,6 +185,7 @@ fn test_within_at_pattern_level() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_zoom_with_chained_transforms() {
     // zoom should work with other transforms
     let input = r#"
@@ -0,0 +1,139 @@
+/// Tests for targeted transforms: within, foldEvery and somecyclesBy
+///
+/// - `within (b, e) f`: events with onsets in [b, e) of each cycle come from f
+/// - `foldEvery [n1, n2] f`: `every n1 f $ every n2 f`
+/// - `somecyclesBy p f`: whole cycles transformed with probability p
+use phonon::mini_notation_v3::parse_mini_notation;
+use phonon::pattern::{Fraction, Pattern, State, TimeSpan};
+
+mod audio_test_utils;
+use audio_test_utils::compile_code;
+
+/// (onset, value) pairs of one cycle, sorted by onset
+fn cycle_events(p: &Pattern<String>, cycle: i64) -> Vec<(f64, String)> {
+    let state = State {
+        span: TimeSpan::new(Fraction::new(cycle, 1), Fraction::new(cycle + 1, 1)),
+        controls: std::collections::HashMap::new(),
+    };
+    let mut haps = p.query(&state);
+    haps.sort_by(|a, b| a.part.begin.cmp(&b.part.begin));
+    haps.into_iter()
+        .map(|h| (h.whole.unwrap().begin.to_float(), h.value))
+        .collect()
+}
+
+fn cycle_values(p: &Pattern<String>, cycle: i64) -> Vec<String> {
+    cycle_events(p, cycle).into_iter().map(|e| e.1).collect()
+}
+
+// ============================================================================
+// within
+// ============================================================================
+
+#[test]
+fn test_within_second_half_only() {
+    // within (0.5, 1) (fast 2) $ "a b c d" -> a b [a b c d]
+    let p = parse_mini_notation("a b c d").within(0.5, 1.0, |p| p.fast(Pattern::pure(2.0)));
+    assert_eq!(cycle_values(&p, 0), vec!["a", "b", "a", "b", "c", "d"]);
+    let onsets: Vec<f64> = cycle_events(&p, 0).iter().map(|e| e.0).collect();
+    for (onset, expected) in onsets.iter().zip([0.0, 0.25, 0.5, 0.625, 0.75, 0.875]) {
+        assert!(
+            (onset - expected).abs() < 1e-5,
+            "onset {} != {}",
+            onset,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_within_first_half_rev() {
+    // within (0, 0.5) rev $ "a b c d" -> d c c d
+    let p = parse_mini_notation("a b c d").within(0.0, 0.5, |p| p.rev());
+    assert_eq!(cycle_values(&p, 0), vec!["d", "c", "c", "d"]);
+}
+
+#[test]
+fn test_within_repeats_every_cycle() {
+    let p = parse_mini_notation("a b c d").within(0.5, 1.0, |p| p.fast(Pattern::pure(2.0)));
+    for cycle in 1..4 {
+        assert_eq!(cycle_values(&p, cycle), vec!["a", "b", "a", "b", "c", "d"]);
+    }
+}
+
+// ============================================================================
+// foldEvery
+// ============================================================================
+
+#[test]
+fn test_fold_every_applies_on_each_divisor() {
+    let p = parse_mini_notation("a b").fold_every(vec![2, 3], |p| p.rev());
+    let reversed = vec!["b", "a"];
+    let plain = vec!["a", "b"];
+    // cycle 0: divisible by 2 and 3 -> reversed twice
+    assert_eq!(cycle_values(&p, 0), plain);
+    assert_eq!(cycle_values(&p, 1), plain);
+    assert_eq!(cycle_values(&p, 2), reversed);
+    assert_eq!(cycle_values(&p, 3), reversed);
+    assert_eq!(cycle_values(&p, 4), reversed);
+    assert_eq!(cycle_values(&p, 5), plain);
+}
+
+// ============================================================================
+// somecyclesBy
+// ============================================================================
+
+#[test]
+fn test_somecycles_by_transforms_whole_cycles() {
+    let p = parse_mini_notation("a b c d").somecycles_by(0.5, |p| p.rev());
+    let mut transformed = 0;
+    for cycle in 0..64 {
+        let values = cycle_values(&p, cycle);
+        if values == vec!["d", "c", "b", "a"] {
+            transformed += 1;
+        } else {
+            assert_eq!(values, vec!["a", "b", "c", "d"], "cycle {} mixed", cycle);
+        }
+    }
+    assert!(
+        (16..=48).contains(&transformed),
+        "expected roughly half the cycles, got {}",
+        transformed
+    );
+}
+
+#[test]
+fn test_somecycles_by_extremes() {
+    let never = parse_mini_notation("a b").somecycles_by(0.0, |p| p.rev());
+    let always = parse_mini_notation("a b").somecycles_by(1.0, |p| p.rev());
+    for cycle in 0..8 {
+        assert_eq!(cycle_values(&never, cycle), vec!["a", "b"]);
+        assert_eq!(cycle_values(&always, cycle), vec!["b", "a"]);
+    }
+}
+
+// ============================================================================
+// DSL Compilation Tests
+// ============================================================================
+
+#[test]
+fn test_targeted_transforms_compile() {
+    for code in [
+        r#"out $ s "bd sn hh cp" $ within (0.5, 1.0) (fast 2)"#,
+        r#"out $ s "bd sn hh cp" $ within 0.5 1.0 (fast 2)"#,
+        r#"out $ s "bd sn" $ foldEvery [2, 4] (fast 2)"#,
+        r#"out $ s "bd sn" $ somecyclesBy 0.3 (rev)"#,
+        r#"out $ s "bd sn" $ somecycles (fast 2)"#,
+        r#"out $ s "bd sn" $ somecyclesBy 0.3 (# lpf 300 0.8)"#,
+        r#"out $ s "bd sn" $ foldEvery [2, 3] (# lpf 300 0.8)"#,
+    ] {
+        if let Err(e) = compile_code(code) {
+            panic!("{} should compile: {}", code, e);
+        }
+    }
+}
+
+#[test]
+fn test_fold_every_rejects_zero() {
+    assert!(compile_code(r#"out $ s "bd sn" $ foldEvery [0, 2] (fast 2)"#).is_err());
+}